@@ -46,6 +46,24 @@ pub fn force_confirmation_after_data_plane(
     check_plane_violation(&data_intent)
 }
 
+/// [`force_confirmation_after_data_plane`] for tools whose gravity is not
+/// derived from their name — installed skills exposed as agent tools, whose
+/// level comes from the capabilities they declare.
+pub fn force_confirmation_at_level(
+    tool_name: &str,
+    level: ActionLevel,
+    ingested_data_plane: bool,
+) -> Option<String> {
+    if ingested_data_plane && level >= ActionLevel::Modify {
+        Some(format!(
+            "Data-plane content attempted control-plane action '{}' (level {:?})",
+            tool_name, level
+        ))
+    } else {
+        None
+    }
+}
+
 /// Wrap a classified intent into a ProposedAction with computed level.
 pub fn build_proposal(intent: &UserIntent) -> ProposedAction {
     let level = action_level(&intent.action);
@@ -188,4 +206,15 @@ mod tests {
         // read (it's not Modify+), so no violation reason is returned.
         assert!(force_confirmation_after_data_plane("search", true).is_none());
     }
+
+    #[test]
+    fn force_confirmation_at_level_covers_skill_tools() {
+        // Skill tool names are unknown to action_level (→ Observe); the
+        // explicit level must still arm the gate.
+        let name = "skill_find_replace_find_replace";
+        assert!(force_confirmation_after_data_plane(name, true).is_none());
+        assert!(force_confirmation_at_level(name, ActionLevel::Modify, true).is_some());
+        assert!(force_confirmation_at_level(name, ActionLevel::Modify, false).is_none());
+        assert!(force_confirmation_at_level(name, ActionLevel::Observe, true).is_none());
+    }
 }
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -14,10 +15,15 @@ const TIME_THRESHOLD_SECS: u64 = 300; // 5 minutes
 /// Policy (from spec):
 /// - High activity: commit after 50 edits OR 5 minutes since last commit
 /// - Low activity: commit on context switch (document close) or session end
+///
+/// With a journal, the documents waiting for a commit are also kept on disk,
+/// so a crash doesn't lose their commit: [`recover`](Self::recover) makes it
+/// on the next run.
 pub struct AutoCommitEngine {
     db: Arc<dyn GraphDB>,
     edit_counts: HashMap<String, u32>,
     last_commit_times: HashMap<String, Instant>,
+    journal: Option<PathBuf>,
 }
 
 impl AutoCommitEngine {
@@ -26,18 +32,81 @@ impl AutoCommitEngine {
             db,
             edit_counts: HashMap::new(),
             last_commit_times: HashMap::new(),
+            journal: None,
         }
     }
 
+    /// Keep the list of documents waiting for a commit in `path`.
+    pub fn with_journal(mut self, path: PathBuf) -> Self {
+        self.journal = Some(path);
+        self
+    }
+
     /// Record an edit for a document. Called on each save.
     pub fn record_edit(&mut self, doc_id: &str) {
-        *self.edit_counts.entry(doc_id.to_string()).or_insert(0) += 1;
+        let count = self.edit_counts.entry(doc_id.to_string()).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            self.write_journal();
+        }
+    }
+
+    /// Commit the documents a previous run journaled but never committed
+    /// (it crashed or was killed). Call once the database is unlocked.
+    pub async fn recover(&mut self) {
+        let Some(path) = &self.journal else { return };
+        let pending: Vec<String> = match std::fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+            Err(_) => return,
+        };
+        for doc_id in pending {
+            let msg = "Auto-commit after unclean shutdown";
+            match self.db.commit_document(&doc_id, msg).await {
+                Ok(commit) => {
+                    tracing::info!(
+                        "Recovered commit for {}: {}",
+                        doc_id,
+                        commit.id_string().unwrap_or_default()
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!("Recovered commit failed for {}: {e}", doc_id);
+                }
+            }
+        }
+        self.write_journal();
+    }
+
+    /// Write the documents with uncommitted edits to the journal, if any.
+    fn write_journal(&self) {
+        let Some(path) = &self.journal else { return };
+        let mut pending: Vec<&String> = self
+            .edit_counts
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(doc_id, _)| doc_id)
+            .collect();
+        pending.sort();
+        let result = serde_json::to_vec(&pending)
+            .map_err(std::io::Error::from)
+            .and_then(|json| {
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                let tmp = path.with_extension("tmp");
+                std::fs::write(&tmp, json)?;
+                std::fs::rename(&tmp, path)
+            });
+        if let Err(e) = result {
+            tracing::warn!("Could not write the auto-commit journal: {e}");
+        }
     }
 
     /// Check all tracked documents and commit any that exceed thresholds.
     pub async fn check_and_commit(&mut self) {
         let now = Instant::now();
         let doc_ids: Vec<String> = self.edit_counts.keys().cloned().collect();
+        let mut committed = false;
 
         for doc_id in doc_ids {
             let count = *self.edit_counts.get(&doc_id).unwrap_or(&0);
@@ -60,6 +129,7 @@ impl AutoCommitEngine {
                         );
                         self.edit_counts.insert(doc_id.clone(), 0);
                         self.last_commit_times.insert(doc_id, now);
+                        committed = true;
                     }
                     Err(e) => {
                         tracing::error!("Auto-commit failed for {}: {e}", doc_id);
@@ -67,6 +137,9 @@ impl AutoCommitEngine {
                 }
             }
         }
+        if committed {
+            self.write_journal();
+        }
     }
 
     /// Force-commit a specific document (e.g., on close or context switch).
@@ -96,6 +169,35 @@ impl AutoCommitEngine {
         // Remove stale entries — document is closed, no need to track it
         self.edit_counts.remove(doc_id);
         self.last_commit_times.remove(doc_id);
+        self.write_journal();
+    }
+
+    /// Commit every document with uncommitted edits (session end).
+    pub async fn commit_all(&mut self) {
+        let pending: Vec<(String, u32)> = self
+            .edit_counts
+            .drain()
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        self.last_commit_times.clear();
+
+        for (doc_id, count) in pending {
+            let msg = format!("Auto-commit at session end: {} edits", count);
+            match self.db.commit_document(&doc_id, &msg).await {
+                Ok(commit) => {
+                    tracing::info!(
+                        "Committed at session end {}: {} ({})",
+                        doc_id,
+                        msg,
+                        commit.id_string().unwrap_or_default()
+                    );
+                }
+                Err(e) => {
+                    tracing::error!("Session-end commit failed for {}: {e}", doc_id);
+                }
+            }
+        }
+        self.write_journal();
     }
 }
 
@@ -155,6 +257,42 @@ mod tests {
         assert!(commits[0].message.contains("on close"));
     }
 
+    #[tokio::test]
+    async fn commit_all_flushes_pending_edits_once() {
+        let (db, doc_id) = setup().await;
+        let mut engine = AutoCommitEngine::new(db.clone());
+
+        engine.record_edit(&doc_id);
+        engine.commit_all().await;
+        engine.commit_all().await;
+
+        let commits = db.list_document_commits(&doc_id).await.unwrap();
+        assert_eq!(commits.len(), 1);
+        assert!(commits[0].message.contains("session end"));
+    }
+
+    #[tokio::test]
+    async fn journaled_commits_are_recovered() {
+        let (db, doc_id) = setup().await;
+        let dir = std::env::temp_dir().join("sovereign_autocommit_recover");
+        let _ = std::fs::remove_dir_all(&dir);
+        let journal = dir.join("pending_commits.json");
+
+        // A run that saved an edit and then crashed.
+        let mut crashed = AutoCommitEngine::new(db.clone()).with_journal(journal.clone());
+        crashed.record_edit(&doc_id);
+        drop(crashed);
+
+        let mut next = AutoCommitEngine::new(db.clone()).with_journal(journal.clone());
+        next.recover().await;
+        next.recover().await;
+
+        let commits = db.list_document_commits(&doc_id).await.unwrap();
+        assert_eq!(commits.len(), 1);
+        assert!(commits[0].message.contains("unclean shutdown"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[tokio::test]
     async fn no_commit_on_close_without_edits() {
         let (db, doc_id) = setup().await;
@@ -96,8 +96,9 @@ pub async fn run_cycle(
     formatter: &dyn PromptFormatter,
     source: SuggestionSource,
 ) -> anyhow::Result<Vec<SuggestedLink>> {
-    // 1. Fetch all active documents
-    let docs = db.list_documents(None).await?;
+    // 1. Fetch all active documents whose content the model may read
+    let mut docs = db.list_documents(None).await?;
+    docs.retain(|d| d.ai_may_read());
     if docs.len() < 2 {
         return Ok(vec![]);
     }
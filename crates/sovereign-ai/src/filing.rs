@@ -0,0 +1,365 @@
+//! Filing suggestions for incoming external documents.
+//!
+//! Web clips, imports, shares and converted messages land wherever their
+//! source put them. When one arrives, the idle loop compares it with the
+//! contents of every thread and, if another thread is a clear match,
+//! suggests moving it there — with the tags its closest neighbours carry.
+//! The suggestion is a card like any other (see
+//! `Orchestrator::idle_suggest`); nothing is filed until the user accepts.
+//!
+//! Similarity is TF-IDF cosine over title and body words, computed on
+//! demand: no model call, and nothing stored.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sovereign_core::content::{hashtags, ContentFields};
+use sovereign_db::schema::{Document, Thread};
+
+/// How recently a document must have arrived to be offered for filing.
+pub const ARRIVAL_WINDOW_HOURS: i64 = 24;
+
+/// Below this cosine, a thread is not a clear enough match to suggest.
+const MIN_THREAD_SCORE: f32 = 0.12;
+
+/// How many of the closest documents in the chosen thread lend tags.
+const TAG_NEIGHBOURS: usize = 3;
+
+const MAX_TAGS: usize = 3;
+
+/// Words too common to say anything about a topic.
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "are", "but", "not", "you", "all", "any", "can", "had", "her", "was",
+    "one", "our", "out", "has", "have", "him", "his", "how", "its", "may", "new", "now", "see",
+    "two", "who", "did", "get", "let", "say", "she", "too", "use", "this", "that", "with", "from",
+    "they", "will", "would", "there", "their", "what", "about", "which", "when", "your", "been",
+    "were", "than", "them", "then", "into", "more", "some", "such", "also", "just", "only", "over",
+    "very", "here", "http", "https", "www", "com",
+];
+
+/// What accepting a filing suggestion does; travels as the suggestion's
+/// JSON `target`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilingTarget {
+    pub doc_id: String,
+    pub thread_id: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilingSuggestion {
+    pub target: FilingTarget,
+    pub doc_title: String,
+    pub thread_name: String,
+    pub score: f32,
+}
+
+impl FilingSuggestion {
+    /// Card text for the suggestion.
+    pub fn text(&self) -> String {
+        let mut text = format!(
+            "\"{}\" looks like it belongs in \"{}\"",
+            self.doc_title, self.thread_name
+        );
+        if !self.target.tags.is_empty() {
+            let tags: Vec<String> = self.target.tags.iter().map(|t| format!("#{t}")).collect();
+            text.push_str(&format!(", tagged {}", tags.join(" ")));
+        }
+        text.push_str(". File it there?");
+        text
+    }
+}
+
+/// The newest external document that arrived within
+/// [`ARRIVAL_WINDOW_HOURS`] and isn't in `offered`.
+pub fn newest_arrival<'a>(
+    docs: &'a [Document],
+    offered: &std::collections::HashSet<String>,
+    now: DateTime<Utc>,
+) -> Option<&'a Document> {
+    let since = now - Duration::hours(ARRIVAL_WINDOW_HOURS);
+    docs.iter()
+        .filter(|d| !d.is_owned && d.created_at >= since)
+        .filter(|d| d.id_string().is_some_and(|id| !offered.contains(&id)))
+        .max_by_key(|d| d.created_at)
+}
+
+/// Where `doc` would best be filed among `threads`, judged by the other
+/// documents in `docs`. `None` when no thread but its own is a clear match.
+pub fn suggest_filing(
+    doc: &Document,
+    docs: &[Document],
+    threads: &[Thread],
+) -> Option<FilingSuggestion> {
+    let doc_id = doc.id_string()?;
+    let others: Vec<&Document> = docs
+        .iter()
+        .filter(|d| d.id_string().as_deref() != Some(doc_id.as_str()))
+        .collect();
+    let idf = inverse_frequencies(others.iter().copied().chain([doc]));
+    let vector = |d: &Document| weigh(&term_counts(d), &idf);
+    let query = vector(doc);
+    if query.is_empty() {
+        return None;
+    }
+
+    let mut best: Option<(&Thread, String, f32)> = None;
+    for thread in threads {
+        let Some(thread_id) = thread.id_string() else {
+            continue;
+        };
+        let mut centroid: HashMap<String, f32> = HashMap::new();
+        for other in others.iter().filter(|d| d.thread_id == thread_id) {
+            for (term, w) in vector(other) {
+                *centroid.entry(term).or_default() += w;
+            }
+        }
+        let score = cosine(&query, &centroid);
+        if best.as_ref().is_none_or(|(_, _, s)| score > *s) {
+            best = Some((thread, thread_id, score));
+        }
+    }
+    let (thread, thread_id, score) = best?;
+    if score < MIN_THREAD_SCORE || thread_id == doc.thread_id {
+        return None;
+    }
+
+    // Tags the closest documents in that thread carry, weighted by how
+    // close each one is.
+    let present = hashtags(&body_of(doc), usize::MAX);
+    let mut neighbours: Vec<(f32, &Document)> = others
+        .iter()
+        .filter(|d| d.thread_id == thread_id)
+        .map(|d| (cosine(&query, &vector(d)), *d))
+        .filter(|(s, _)| *s > 0.0)
+        .collect();
+    neighbours.sort_by(|a, b| b.0.total_cmp(&a.0));
+    let mut tag_weights: Vec<(String, f32)> = Vec::new();
+    for (s, neighbour) in neighbours.into_iter().take(TAG_NEIGHBOURS) {
+        for tag in hashtags(&body_of(neighbour), usize::MAX) {
+            if present.contains(&tag) {
+                continue;
+            }
+            match tag_weights.iter_mut().find(|(t, _)| *t == tag) {
+                Some((_, w)) => *w += s,
+                None => tag_weights.push((tag, s)),
+            }
+        }
+    }
+    tag_weights.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    Some(FilingSuggestion {
+        target: FilingTarget {
+            doc_id,
+            thread_id,
+            tags: tag_weights
+                .into_iter()
+                .take(MAX_TAGS)
+                .map(|(t, _)| t)
+                .collect(),
+        },
+        doc_title: doc.title.clone(),
+        thread_name: thread.name.clone(),
+        score,
+    })
+}
+
+/// `body` with `tags` appended on a closing line, skipping ones it has.
+pub fn with_tags(body: &str, tags: &[String]) -> String {
+    let present = hashtags(body, usize::MAX);
+    let missing: Vec<String> = tags
+        .iter()
+        .filter(|t| !present.contains(t))
+        .map(|t| format!("#{t}"))
+        .collect();
+    if missing.is_empty() {
+        return body.to_string();
+    }
+    let trimmed = body.trim_end();
+    let sep = if trimmed.is_empty() { "" } else { "\n\n" };
+    format!("{trimmed}{sep}{}", missing.join(" "))
+}
+
+fn body_of(doc: &Document) -> String {
+    ContentFields::parse(&doc.content).body
+}
+
+/// Word counts of the title (counted twice: it is the densest summary)
+/// and the body.
+fn term_counts(doc: &Document) -> HashMap<String, f32> {
+    let mut counts = HashMap::new();
+    let title = doc.title.to_lowercase();
+    let body = body_of(doc).to_lowercase();
+    for (text, weight) in [(title.as_str(), 2.0), (body.as_str(), 1.0)] {
+        for word in text.split(|c: char| !c.is_alphanumeric()) {
+            if word.chars().count() < 3
+                || STOPWORDS.contains(&word)
+                || word.chars().all(|c| c.is_numeric())
+            {
+                continue;
+            }
+            *counts.entry(word.to_string()).or_default() += weight;
+        }
+    }
+    counts
+}
+
+fn inverse_frequencies<'a>(docs: impl Iterator<Item = &'a Document>) -> HashMap<String, f32> {
+    let mut df: HashMap<String, f32> = HashMap::new();
+    let mut n = 0.0;
+    for doc in docs {
+        n += 1.0;
+        for term in term_counts(doc).into_keys() {
+            *df.entry(term).or_default() += 1.0;
+        }
+    }
+    df.into_iter()
+        .map(|(term, df)| (term, ((1.0 + n) / (1.0 + df)).ln() + 1.0))
+        .collect()
+}
+
+fn weigh(counts: &HashMap<String, f32>, idf: &HashMap<String, f32>) -> HashMap<String, f32> {
+    counts
+        .iter()
+        .map(|(term, tf)| (term.clone(), tf * idf.get(term).copied().unwrap_or(1.0)))
+        .collect()
+}
+
+fn cosine(a: &HashMap<String, f32>, b: &HashMap<String, f32>) -> f32 {
+    let dot: f32 = a.iter().filter_map(|(t, x)| b.get(t).map(|y| x * y)).sum();
+    let norm = |v: &HashMap<String, f32>| v.values().map(|x| x * x).sum::<f32>().sqrt();
+    let (na, nb) = (norm(a), norm(b));
+    if na == 0.0 || nb == 0.0 {
+        0.0
+    } else {
+        dot / (na * nb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_db::schema::Thing;
+    use std::collections::HashSet;
+
+    fn thread(id: &str, name: &str) -> Thread {
+        let mut t = Thread::new(name.into(), String::new());
+        t.id = Some(Thing::from(("thread", id)));
+        t
+    }
+
+    fn doc(id: &str, thread: &str, title: &str, body: &str, owned: bool) -> Document {
+        let mut d = Document::new(title.into(), format!("thread:{thread}"), owned);
+        d.id = Some(Thing::from(("document", id)));
+        d.content = ContentFields {
+            body: body.into(),
+            ..Default::default()
+        }
+        .serialize();
+        d
+    }
+
+    fn workspace() -> (Vec<Document>, Vec<Thread>) {
+        let threads = vec![
+            thread("garden", "Garden"),
+            thread("rust", "Rust work"),
+            thread("inbox", "Web clips"),
+        ];
+        let docs = vec![
+            doc(
+                "g1",
+                "garden",
+                "Tomato seedlings",
+                "Seedlings need warmth. #vegetables",
+                true,
+            ),
+            doc(
+                "g2",
+                "garden",
+                "Compost notes",
+                "Compost the tomato vines in autumn. #soil #vegetables",
+                true,
+            ),
+            doc(
+                "r1",
+                "rust",
+                "Borrow checker",
+                "Lifetimes and the borrow checker. #rust",
+                true,
+            ),
+            doc(
+                "r2",
+                "rust",
+                "Async runtimes",
+                "Tokio tasks and futures. #rust #async",
+                true,
+            ),
+            doc(
+                "clip",
+                "inbox",
+                "Growing tomatoes from seed",
+                "Start tomato seedlings indoors, keep them warm, then transplant. Compost helps.",
+                false,
+            ),
+        ];
+        (docs, threads)
+    }
+
+    #[test]
+    fn an_arrival_is_filed_with_its_neighbours_tags() {
+        let (docs, threads) = workspace();
+        let clip = &docs[4];
+        let s = suggest_filing(clip, &docs, &threads).unwrap();
+        assert_eq!(s.target.thread_id, "thread:garden");
+        assert_eq!(s.target.tags, ["vegetables", "soil"]);
+        assert_eq!(s.thread_name, "Garden");
+        assert!(s.text().contains("\"Garden\", tagged #vegetables #soil"));
+    }
+
+    #[test]
+    fn nothing_is_suggested_without_a_clear_match() {
+        let (mut docs, threads) = workspace();
+        docs[4] = doc(
+            "clip",
+            "inbox",
+            "Quarterly tax forms",
+            "Deadline for filing forms",
+            false,
+        );
+        assert!(suggest_filing(&docs[4], &docs, &threads).is_none());
+
+        // Already where it belongs.
+        let (docs, threads) = workspace();
+        let mut clip = docs[4].clone();
+        clip.thread_id = "thread:garden".into();
+        assert!(suggest_filing(&clip, &docs, &threads).is_none());
+    }
+
+    #[test]
+    fn only_recent_unoffered_external_documents_arrive() {
+        let (mut docs, _) = workspace();
+        let now = docs[4].created_at + Duration::hours(1);
+        let mut offered = HashSet::new();
+        assert_eq!(
+            newest_arrival(&docs, &offered, now).and_then(|d| d.id_string()),
+            Some("document:clip".into())
+        );
+        offered.insert("document:clip".to_string());
+        assert!(newest_arrival(&docs, &offered, now).is_none());
+        offered.clear();
+        docs[4].created_at = now - Duration::hours(ARRIVAL_WINDOW_HOURS + 1);
+        assert!(newest_arrival(&docs, &offered, now).is_none());
+    }
+
+    #[test]
+    fn tags_are_appended_once() {
+        assert_eq!(
+            with_tags("Notes #soil\n", &["soil".into(), "vegetables".into()]),
+            "Notes #soil\n\n#vegetables"
+        );
+        assert_eq!(with_tags("", &["a".into()]), "#a");
+        assert_eq!(with_tags("#a", &["a".into()]), "#a");
+    }
+}
@@ -10,7 +10,9 @@ use crate::llm::format::{self, PromptFormatter};
 use crate::llm::prompt::{build_reasoning_system_prompt, build_router_system_prompt, format_single_turn};
 use crate::llm::AsyncLlmBackend;
 
-use super::parser::{override_model_intent, override_panel_intent, parse_intent_response};
+use super::parser::{
+    override_model_intent, override_panel_intent, override_reminder_intent, parse_intent_response,
+};
 
 /// Idle timeout before unloading the reasoning model to free VRAM.
 const REASONING_IDLE_SECS: u64 = 300; // 5 minutes
@@ -63,6 +65,12 @@ impl IntentClassifier {
         Ok(())
     }
 
+    /// Whether the reasoning model is resident (it loads on first
+    /// escalation and unloads after `REASONING_IDLE_SECS`).
+    pub fn reasoning_loaded(&self) -> bool {
+        self.reasoning.is_some()
+    }
+
     /// Hot-swap the router model to a different .gguf file.
     pub(crate) async fn swap_router(&self, model_path: &str, n_gpu_layers: i32) -> Result<()> {
         tracing::info!("Swapping router model to: {model_path}");
@@ -113,6 +121,7 @@ impl IntentClassifier {
         // model names as search targets.
         override_model_intent(user_text, &mut intent);
         override_panel_intent(user_text, &mut intent);
+        override_reminder_intent(user_text, &mut intent);
 
         if intent.confidence < self.confidence_threshold {
             tracing::info!(
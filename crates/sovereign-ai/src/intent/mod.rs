@@ -1,4 +1,5 @@
 pub mod classifier;
 pub mod parser;
+pub mod reminder;
 
 pub use classifier::IntentClassifier;
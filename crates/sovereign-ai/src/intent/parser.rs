@@ -51,12 +51,15 @@ pub fn override_panel_intent(user_text: &str, intent: &mut UserIntent) {
     if matches!(
         intent.action.as_str(),
         "open_pii_dashboard" | "open_models" | "open_inbox" | "browse" | "open_settings"
+            | "lock_session"
     ) {
         return;
     }
 
     let lower = user_text.to_lowercase();
-    let new_action = if lower.contains("pii dashboard")
+    let new_action = if is_lock_request(&lower) {
+        Some("lock_session")
+    } else if lower.contains("pii dashboard")
         || lower.contains("pii vault")
         || lower.contains("pii panel")
         || lower.contains("open pii")
@@ -105,6 +108,48 @@ pub fn override_panel_intent(user_text: &str, intent: &mut UserIntent) {
     }
 }
 
+/// Post-classification override: "remind me about Budget Overview Friday
+/// 9am" is a reminder whatever the router made of it (usually "open" or
+/// "search" on the document title). The orchestrator re-reads the time
+/// from the user text, so only the action needs correcting.
+pub fn override_reminder_intent(user_text: &str, intent: &mut UserIntent) {
+    let lower = user_text.trim().to_lowercase();
+    let action = if lower.starts_with("remind me") || lower.starts_with("set a reminder") {
+        "set_reminder"
+    } else if lower.contains("my reminders")
+        || lower.contains("list reminders")
+        || lower.contains("show reminders")
+    {
+        "list_reminders"
+    } else {
+        return;
+    };
+    if intent.action != action {
+        tracing::info!(
+            "Overriding action '{}' → '{}' (user text matches reminder pattern)",
+            intent.action,
+            action
+        );
+        intent.action = action.to_string();
+        intent.confidence = 0.9;
+    }
+}
+
+/// "lock", "lock the screen", "lock sovereign"… but not "unlock",
+/// "block" or "lock the door". Matched on the lowercased text.
+fn is_lock_request(lower: &str) -> bool {
+    let lower = lower.trim().trim_end_matches(['.', '!']);
+    lower == "lock"
+        || lower == "lock it"
+        || lower.starts_with("lock the screen")
+        || lower.starts_with("lock the session")
+        || lower.starts_with("lock screen")
+        || lower.starts_with("lock session")
+        || lower.starts_with("lock sovereign")
+        || lower.contains("lock my screen")
+        || lower.contains("lock the app")
+}
+
 /// Parse the LLM's JSON response into a UserIntent.
 /// Falls back to keyword extraction if JSON is malformed.
 pub fn parse_intent_response(response: &str) -> Result<UserIntent> {
@@ -193,6 +238,8 @@ fn extract_intent_heuristic(response: &str) -> UserIntent {
     // Must come before the generic "open"/"show"/"inbox" catch-alls below
     // so phrases like "open inbox" route to the panel toggle, not to a
     // document open or message-view.
+    } else if is_lock_request(&lower) {
+        "lock_session"
     } else if lower.contains("pii dashboard")
         || lower.contains("pii vault")
         || lower.contains("pii panel")
@@ -259,6 +306,10 @@ fn extract_intent_heuristic(response: &str) -> UserIntent {
         "create_milestone"
     } else if lower.contains("list milestone") || lower.contains("show milestone") {
         "list_milestones"
+    } else if lower.contains("due this week") || lower.contains("what's due") || lower.contains("what is due")
+        || lower.contains("my tasks") || lower.contains("open tasks") || lower.contains("to-do")
+    {
+        "tasks_due"
     // P2P / Guardian / Encryption intents
     } else if lower.contains("sync") && (lower.contains("device") || lower.contains("peer")) {
         "sync_device"
@@ -506,6 +557,14 @@ mod tests {
         assert_eq!(intent.action, "list_milestones");
     }
 
+    #[test]
+    fn heuristic_tasks_due() {
+        let intent = parse_intent_response("what's due this week?").unwrap();
+        assert_eq!(intent.action, "tasks_due");
+        let intent = parse_intent_response("show my tasks").unwrap();
+        assert_eq!(intent.action, "tasks_due");
+    }
+
     #[test]
     fn heuristic_swap_model() {
         let intent = parse_intent_response("swap model to Qwen2.5-7B").unwrap();
@@ -675,6 +734,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn heuristic_lock_session() {
+        for phrase in ["lock", "Lock the screen", "lock screen now", "lock my screen please"] {
+            let intent = parse_intent_response(phrase).unwrap();
+            assert_eq!(intent.action, "lock_session", "phrase: {phrase}");
+        }
+        for phrase in ["unlock the door", "lock the door", "block this sender", "lockfile cleanup"] {
+            let intent = parse_intent_response(phrase).unwrap();
+            assert_ne!(intent.action, "lock_session", "phrase: {phrase}");
+        }
+    }
+
     // --- override_panel_intent: corrects LLM misclassification ---
 
     fn make_intent(action: &str) -> UserIntent {
@@ -741,6 +812,22 @@ mod tests {
         assert_eq!(intent.action, "search", "unrelated text must not trigger override");
     }
 
+    #[test]
+    fn override_reminder_corrects_open_to_set_reminder() {
+        let mut intent = make_intent("open");
+        override_reminder_intent("Remind me about Budget Overview Friday 9am", &mut intent);
+        assert_eq!(intent.action, "set_reminder");
+        assert!(intent.confidence >= 0.9);
+
+        let mut intent = make_intent("search");
+        override_reminder_intent("what are my reminders?", &mut intent);
+        assert_eq!(intent.action, "list_reminders");
+
+        let mut intent = make_intent("search");
+        override_reminder_intent("find the reminder letter", &mut intent);
+        assert_eq!(intent.action, "search");
+    }
+
     // --- Post-classification override tests ---
 
     #[test]
@@ -0,0 +1,372 @@
+//! "Remind me about Budget Overview Friday 9am": split a reminder request
+//! into what to be reminded about and when.
+//!
+//! Only the end of the sentence is read as the time, so "remind me about
+//! the Monday report tomorrow" is about "the Monday report". Understood:
+//! `today`, `tonight`, `tomorrow`, weekday names (`friday`, `on fri`,
+//! `next tuesday` — all the coming one), ISO dates, clock times (`9am`,
+//! `9:30 pm`, `17:00`, `at 5`), parts of the day (`noon`, `morning`,
+//! `evening`) and offsets (`in 20 minutes`, `in 2 hours`, `in 3 days`).
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+/// A reminder request taken apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReminderRequest {
+    /// What the reminder is about: a document title or a task's text.
+    pub subject: String,
+    /// When to fire, in local time.
+    pub due: NaiveDateTime,
+}
+
+/// Hour used when only a day is given.
+pub const DEFAULT_HOUR: u32 = 9;
+
+/// Phrases that open a reminder request; dropped from the subject.
+const LEADS: &[&str] = &[
+    "remind me about ",
+    "remind me of ",
+    "remind me to ",
+    "remind me ",
+    "set a reminder about ",
+    "set a reminder for ",
+    "set a reminder to ",
+    "reminder about ",
+    "reminder for ",
+    "reminder: ",
+];
+
+#[derive(Debug, Clone, Copy)]
+enum Day {
+    Today,
+    Tomorrow,
+    Date(NaiveDate),
+    Weekday(Weekday),
+}
+
+#[derive(Debug, Default)]
+struct When {
+    day: Option<Day>,
+    time: Option<NaiveTime>,
+    offset: Option<Duration>,
+}
+
+/// Parse a reminder request relative to `now` (local time). `None` when
+/// no time is given or nothing is left to be reminded about.
+pub fn parse_reminder(text: &str, now: NaiveDateTime) -> Option<ReminderRequest> {
+    let text = text.trim().trim_end_matches(['.', '!', '?']);
+    let lead = LEADS
+        .iter()
+        .find(|l| {
+            text.get(..l.len())
+                .is_some_and(|p| p.eq_ignore_ascii_case(l))
+        })
+        .map_or(0, |l| l.len());
+    let words: Vec<&str> = text[lead..].split_whitespace().collect();
+    let lower: Vec<String> = words
+        .iter()
+        .map(|w| w.trim_end_matches(',').to_lowercase())
+        .collect();
+
+    let mut when = When::default();
+    let mut end = lower.len();
+    while end > 0 {
+        let taken = take_from_tail(&lower[..end], &mut when);
+        if taken == 0 {
+            break;
+        }
+        end -= taken;
+    }
+    if end == lower.len() {
+        return None;
+    }
+
+    let subject = words[..end].join(" ");
+    let subject = subject.trim_end_matches(',').trim();
+    if subject.is_empty() {
+        return None;
+    }
+    Some(ReminderRequest {
+        subject: subject.to_string(),
+        due: resolve(&when, now)?,
+    })
+}
+
+/// Read one time expression off the end of `words` into `when`. Returns
+/// how many words it used; 0 when the tail isn't a (new) time expression.
+fn take_from_tail(words: &[String], when: &mut When) -> usize {
+    let n = words.len();
+    let last = words[n - 1].as_str();
+    let before = |k: usize| (n > k).then(|| words[n - 1 - k].as_str());
+
+    // "in 2 hours", "in an hour"
+    if when.offset.is_none() && before(2) == Some("in") {
+        if let Some(offset) = offset(words[n - 2].as_str(), last) {
+            when.offset = Some(offset);
+            return 3;
+        }
+    }
+
+    if when.time.is_none() {
+        // "9 am", "at 9:30 pm"
+        if matches!(last, "am" | "pm" | "a.m." | "p.m.") {
+            if let Some(t) = before(1).and_then(|w| clock(&format!("{w}{last}"), false)) {
+                when.time = Some(t);
+                return 2 + usize::from(before(2) == Some("at"));
+            }
+        }
+        // "9am", "17:00", "at 5"
+        if let Some(t) = clock(last, before(1) == Some("at")) {
+            when.time = Some(t);
+            return 1 + usize::from(before(1) == Some("at"));
+        }
+        if let Some(t) = part_of_day(last) {
+            when.time = Some(t);
+            let connectors = match (before(2), before(1)) {
+                (Some("in"), Some("the")) => 2,
+                (_, Some("this" | "at")) => 1,
+                _ => 0,
+            };
+            return 1 + connectors;
+        }
+    }
+
+    if when.day.is_none() {
+        let day = match last {
+            "today" => Some(Day::Today),
+            "tomorrow" => Some(Day::Tomorrow),
+            "tonight" => {
+                when.time = when.time.or(NaiveTime::from_hms_opt(20, 0, 0));
+                Some(Day::Today)
+            }
+            _ => weekday(last).map(Day::Weekday).or_else(|| {
+                NaiveDate::parse_from_str(last, "%Y-%m-%d")
+                    .ok()
+                    .map(Day::Date)
+            }),
+        };
+        if let Some(day) = day {
+            when.day = Some(day);
+            return 1 + usize::from(matches!(before(1), Some("on" | "this" | "next")));
+        }
+    }
+
+    0
+}
+
+/// Turn the parsed parts into a time.
+fn resolve(when: &When, now: NaiveDateTime) -> Option<NaiveDateTime> {
+    let today = now.date();
+    let default_time = NaiveTime::from_hms_opt(DEFAULT_HOUR, 0, 0).unwrap_or_default();
+
+    if let Some(offset) = when.offset {
+        let at = now.checked_add_signed(offset)?;
+        return Some(match when.time {
+            Some(t) => at.date().and_time(t),
+            None => at,
+        });
+    }
+
+    let time = when.time.unwrap_or(default_time);
+    let due = match when.day {
+        Some(Day::Today) => match when.time {
+            Some(t) => today.and_time(t),
+            // "today" after the default hour: an hour from now.
+            None => today.and_time(default_time).max(now + Duration::hours(1)),
+        },
+        Some(Day::Tomorrow) => today.succ_opt()?.and_time(time),
+        Some(Day::Date(date)) => date.and_time(time),
+        Some(Day::Weekday(wd)) => {
+            let ahead =
+                (7 + wd.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+            let at = (today + Duration::days(i64::from(ahead))).and_time(time);
+            if at <= now {
+                at + Duration::days(7)
+            } else {
+                at
+            }
+        }
+        None => {
+            let at = today.and_time(time);
+            if at <= now {
+                at + Duration::days(1)
+            } else {
+                at
+            }
+        }
+    };
+    Some(due)
+}
+
+/// "9am", "9:30pm", "17:00", "9h30". A bare hour ("5") only counts when
+/// `bare` (it followed "at"); 1–7 without am/pm mean the afternoon.
+fn clock(word: &str, bare: bool) -> Option<NaiveTime> {
+    let (digits, pm) = if let Some(d) = word.strip_suffix("am").or(word.strip_suffix("a.m.")) {
+        (d, Some(false))
+    } else if let Some(d) = word.strip_suffix("pm").or(word.strip_suffix("p.m.")) {
+        (d, Some(true))
+    } else {
+        (word, None)
+    };
+    let (hour, minute) = match digits.split_once(':').or(digits.split_once('h')) {
+        Some((h, "")) => (h.parse::<u32>().ok()?, 0),
+        Some((h, m)) => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        None if pm.is_some() || bare => (digits.parse::<u32>().ok()?, 0),
+        None => return None,
+    };
+    let hour = match pm {
+        Some(pm) if (1..=12).contains(&hour) => hour % 12 + if pm { 12 } else { 0 },
+        Some(_) => return None,
+        None if bare && digits == word && (1..=7).contains(&hour) && minute == 0 => hour + 12,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+fn part_of_day(word: &str) -> Option<NaiveTime> {
+    let hour = match word {
+        "morning" => 9,
+        "noon" | "midday" | "lunchtime" => 12,
+        "afternoon" => 14,
+        "evening" => 18,
+        _ => return None,
+    };
+    NaiveTime::from_hms_opt(hour, 0, 0)
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    Some(match word {
+        "monday" | "mon" => Weekday::Mon,
+        "tuesday" | "tue" | "tues" => Weekday::Tue,
+        "wednesday" | "wed" => Weekday::Wed,
+        "thursday" | "thu" | "thur" | "thurs" => Weekday::Thu,
+        "friday" | "fri" => Weekday::Fri,
+        "saturday" | "sat" => Weekday::Sat,
+        "sunday" | "sun" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+/// "2 hours", "an hour", "30 min".
+fn offset(count: &str, unit: &str) -> Option<Duration> {
+    let count: i64 = match count {
+        "a" | "an" | "one" => 1,
+        n => n.parse().ok()?,
+    };
+    match unit {
+        "min" | "mins" | "minute" | "minutes" => Duration::try_minutes(count),
+        "hour" | "hours" | "hr" | "hrs" => Duration::try_hours(count),
+        "day" | "days" => Duration::try_days(count),
+        "week" | "weeks" => Duration::try_weeks(count),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wednesday 14 October 2026, 15:00.
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 14)
+            .unwrap()
+            .and_hms_opt(15, 0, 0)
+            .unwrap()
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn parse(text: &str) -> (String, NaiveDateTime) {
+        let req = parse_reminder(text, now()).expect(text);
+        (req.subject, req.due)
+    }
+
+    #[test]
+    fn weekday_and_clock_time() {
+        assert_eq!(
+            parse("Remind me about Budget Overview Friday 9am"),
+            ("Budget Overview".into(), at(16, 9, 0))
+        );
+        assert_eq!(
+            parse("remind me about Budget Overview on Friday at 9:30 am."),
+            ("Budget Overview".into(), at(16, 9, 30))
+        );
+    }
+
+    #[test]
+    fn tomorrow_today_and_tonight() {
+        assert_eq!(
+            parse("remind me to send the invoice tomorrow at 3pm"),
+            ("send the invoice".into(), at(15, 15, 0))
+        );
+        assert_eq!(
+            parse("remind me about Taxes tonight"),
+            ("Taxes".into(), at(14, 20, 0))
+        );
+        // The default hour has passed: an hour from now.
+        assert_eq!(
+            parse("remind me about Taxes today"),
+            ("Taxes".into(), at(14, 16, 0))
+        );
+        assert_eq!(
+            parse("remind me about Taxes tomorrow morning"),
+            ("Taxes".into(), at(15, 9, 0))
+        );
+    }
+
+    #[test]
+    fn offsets_count_from_now() {
+        assert_eq!(
+            parse("remind me about Roadmap in 2 hours"),
+            ("Roadmap".into(), at(14, 17, 0))
+        );
+        assert_eq!(
+            parse("remind me about Roadmap in an hour"),
+            ("Roadmap".into(), at(14, 16, 0))
+        );
+        assert_eq!(
+            parse("remind me about Roadmap in 3 days at 10am"),
+            ("Roadmap".into(), at(17, 10, 0))
+        );
+    }
+
+    #[test]
+    fn past_times_roll_forward() {
+        // 10am today has passed: tomorrow.
+        assert_eq!(
+            parse("remind me about Roadmap at 10am"),
+            ("Roadmap".into(), at(15, 10, 0))
+        );
+        // Wednesday 9am has passed: next week.
+        assert_eq!(
+            parse("remind me about the Monday report on wednesday"),
+            ("the Monday report".into(), at(21, 9, 0))
+        );
+    }
+
+    #[test]
+    fn bare_hours_and_dates() {
+        assert_eq!(
+            parse("reminder: call Bob next tue at 5"),
+            ("call Bob".into(), at(20, 17, 0))
+        );
+        assert_eq!(
+            parse("set a reminder for Taxes 2026-10-20 at 9:30"),
+            ("Taxes".into(), at(20, 9, 30))
+        );
+    }
+
+    #[test]
+    fn needs_a_time_and_a_subject() {
+        assert!(parse_reminder("remind me about Budget Overview", now()).is_none());
+        assert!(parse_reminder("remind me tomorrow at 9am", now()).is_none());
+        // A trailing number alone isn't a time.
+        assert!(parse_reminder("remind me about Report 5", now()).is_none());
+        assert!(parse_reminder("remind me about Report in 99999999999 weeks", now()).is_none());
+    }
+}
@@ -4,6 +4,7 @@ pub mod consolidation;
 #[cfg(feature = "encrypted-log")]
 pub mod encrypted_log;
 pub mod events;
+pub mod filing;
 pub mod injection;
 pub mod intent;
 #[cfg(feature = "jiminy")]
@@ -20,6 +21,7 @@ pub mod orchestrator;
 pub mod pii;
 pub mod reliability;
 pub mod session_log;
+pub mod skill_tools;
 pub mod tools;
 pub mod trust;
 pub mod voice;
@@ -3,6 +3,7 @@ use std::sync::{Arc, Mutex};
 use anyhow::Result;
 use async_trait::async_trait;
 use sovereign_core::interfaces::ModelBackend;
+use tracing::instrument;
 
 use super::backend::{LlamaCppBackend, SamplingConfig};
 
@@ -32,6 +33,7 @@ impl AsyncLlmBackend {
 
     /// Hot-swap the loaded model. Works through `&self` via `Arc<Mutex<>>`.
     /// Drops the old model (freeing VRAM) before loading the new one.
+    #[instrument(name = "llm.load", level = "debug", skip(self))]
     pub async fn swap(&self, model_path: &str, n_gpu_layers: i32) -> Result<()> {
         let path = model_path.to_string();
         let n_ctx = self.n_ctx;
@@ -50,6 +52,7 @@ impl AsyncLlmBackend {
 
 #[async_trait]
 impl ModelBackend for AsyncLlmBackend {
+    #[instrument(name = "llm.load", level = "debug", skip(self))]
     async fn load(&mut self, model_path: &str, n_gpu_layers: i32) -> Result<()> {
         let path = model_path.to_string();
         let n_ctx = self.n_ctx;
@@ -64,6 +67,7 @@ impl ModelBackend for AsyncLlmBackend {
         .await?
     }
 
+    #[instrument(name = "llm.generate", level = "debug", skip(self, prompt))]
     async fn generate(&self, prompt: &str, max_tokens: u32) -> Result<String> {
         let inner = self.inner.clone();
         let sampling = self.sampling.clone();
@@ -38,7 +38,9 @@ pub struct WorkspaceContext {
 /// Gather workspace context from the database (fast read-only queries).
 pub async fn gather_workspace_context(db: &dyn GraphDB) -> WorkspaceContext {
     let threads = db.list_threads().await.unwrap_or_default();
-    let docs = db.list_documents(None).await.unwrap_or_default();
+    let mut docs = db.list_documents(None).await.unwrap_or_default();
+    // Sealed documents aren't the assistant's to know about.
+    docs.retain(|d| d.ai_may_see());
     let contacts = db.list_contacts().await.unwrap_or_default();
     let conversations = db.list_conversations(None).await.unwrap_or_default();
 
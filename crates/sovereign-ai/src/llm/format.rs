@@ -21,7 +21,9 @@ pub enum PromptFormat {
 }
 
 impl PromptFormat {
-    /// Parse from config string. Returns `ChatML` for unknown values.
+    /// Parse from config string. Returns `ChatML` for unknown values;
+    /// config validation accepts exactly
+    /// [`PROMPT_FORMATS`](sovereign_core::config::PROMPT_FORMATS).
     pub fn from_str(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "qwen3" | "chatml-qwen3" => Self::ChatMLQwen3,
@@ -641,6 +643,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn every_validated_format_parses() {
+        for name in sovereign_core::config::PROMPT_FORMATS {
+            let parsed = PromptFormat::from_str(name);
+            assert_eq!(parsed == PromptFormat::ChatML, *name == "chatml", "{name}");
+        }
+    }
+
     #[test]
     fn format_from_str_qwen3() {
         assert_eq!(PromptFormat::from_str("qwen3"), PromptFormat::ChatMLQwen3);
@@ -48,6 +48,9 @@ Actions:\n\
 - adopt: mark an external document as owned\n\
 - create_milestone: create a milestone on a thread timeline\n\
 - list_milestones: list milestones for a thread\n\
+- tasks_due: list open tasks due this week or overdue\n\
+- set_reminder: remind the user about a document or task at a given time\n\
+- list_reminders: list upcoming reminders\n\
 - merge_threads: merge two threads\n\
 - split_thread: split documents out of a thread into a new one\n\
 - list_contacts: list all contacts\n\
@@ -78,7 +81,11 @@ User: switch to Ministral\n\
 User: use the llama model\n\
 {{\"action\": \"swap_model\", \"target\": \"llama\", \"confidence\": 0.92, \"entities\": []}}\n\n\
 User: what models are available?\n\
-{{\"action\": \"list_models\", \"target\": null, \"confidence\": 0.95, \"entities\": []}}"
+{{\"action\": \"list_models\", \"target\": null, \"confidence\": 0.95, \"entities\": []}}\n\n\
+User: what's due this week?\n\
+{{\"action\": \"tasks_due\", \"target\": null, \"confidence\": 0.95, \"entities\": []}}\n\n\
+User: remind me about Budget Overview Friday 9am\n\
+{{\"action\": \"set_reminder\", \"target\": \"Budget Overview\", \"confidence\": 0.95, \"entities\": [[\"doc\", \"Budget Overview\"], [\"time\", \"Friday 9am\"]]}}"
     )
 }
 
@@ -90,7 +97,7 @@ Analyze the user's request carefully and output JSON with a reasoning field.\n\
 Format: {{\"action\": \"...\", \"target\": \"...\", \"confidence\": 0.0-1.0, \"entities\": [], \"reasoning\": \"...\"}}\n\n\
 Actions: search, open, create_document, create_thread, rename_thread, delete_thread, \
 move_document, history, restore, summarize, adopt, create_milestone, list_milestones, \
-merge_threads, split_thread, list_contacts, view_messages, list_models, swap_model, chat, unknown\n\n\
+tasks_due, set_reminder, list_reminders, merge_threads, split_thread, list_contacts, view_messages, list_models, swap_model, chat, unknown\n\n\
 Examples:\n\
 User: I need to reorganize my API docs into the dev project\n\
 {{\"action\": \"move_document\", \"target\": \"API docs\", \"confidence\": 0.85, \
@@ -209,7 +216,8 @@ mod tests {
         let actions = [
             "search", "open", "create_document", "create_thread", "rename_thread",
             "delete_thread", "move_document", "history", "restore", "summarize",
-            "adopt", "create_milestone", "list_milestones", "merge_threads",
+            "adopt", "create_milestone", "list_milestones", "tasks_due", "set_reminder",
+            "list_reminders", "merge_threads",
             "split_thread", "list_contacts", "view_messages", "list_models",
             "swap_model", "chat", "unknown",
         ];
@@ -226,6 +226,18 @@ pub fn set_unlock_key(key: [u8; 32], tofu_path: PathBuf) {
     }
 }
 
+/// Forget the unlock key when the session locks. Unlisted models are then
+/// treated as before login until `set_unlock_key` is called again.
+pub fn clear_unlock_key() {
+    if let Some(v) = VERIFIER.write().unwrap().as_mut() {
+        if let Some(key) = v.key.as_mut() {
+            key.fill(0);
+        }
+        v.key = None;
+        v.tofu_path = None;
+    }
+}
+
 /// Verify a model file before loading it. `Err` means REFUSE the load.
 /// Returns the optional pinned prompt format on success (for listed models).
 pub fn verify_path(path: &str) -> anyhow::Result<Option<String>> {
@@ -1,21 +1,27 @@
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use anyhow::Result;
 use sovereign_core::config::AiConfig;
+use sovereign_core::events::Publisher;
 use sovereign_core::interfaces::{
     CommitSummary, FeedbackEvent, MilestoneSummary, ModelBackend, OrchestratorEvent,
 };
 use sovereign_core::profile::{AdaptiveParams, SuggestionFeedback, UserProfile};
 use sovereign_core::security::{self, ActionDecision, BubbleVisualState, ProposedAction};
-use sovereign_db::schema::{Milestone, Thread};
+use sovereign_db::schema::{Milestone, Reminder, Thread};
+use sovereign_db::tasks::{self, TaskFilter, TaskStatus};
 use sovereign_db::GraphDB;
 
 use crate::action_gate;
+use crate::filing;
 use crate::injection;
 use crate::intent::IntentClassifier;
 use crate::session_log::SessionLog;
+use crate::skill_tools::SkillTools;
+use crate::tools::{ToolCall, WriteToolResult};
 use crate::trust::TrustTracker;
 
 /// Central AI orchestrator. Owns the intent classifier and DB handle.
@@ -24,7 +30,7 @@ use crate::trust::TrustTracker;
 pub struct Orchestrator {
     classifier: tokio::sync::Mutex<IntentClassifier>,
     db: Arc<dyn GraphDB>,
-    event_tx: std::sync::mpsc::Sender<OrchestratorEvent>,
+    event_tx: Publisher<OrchestratorEvent>,
     /// Mutex<Option<…>> instead of Option<Mutex<…>> so the inner SessionLog
     /// can be replaced post-login (e.g. when set_session_log_key opens an
     /// encrypted log over the existing plaintext one).
@@ -54,6 +60,17 @@ pub struct Orchestrator {
     /// Mutex so the setter can be `&self` (orchestrator lives behind Arc).
     #[cfg(feature = "vision")]
     vision: Mutex<Option<crate::jiminy_vision::SharedVision>>,
+    /// Installed skills exposed to the chat agent loop as tools. `None`
+    /// until the app calls `set_skill_tools` once the registry is built.
+    skill_tools: Mutex<Option<Arc<SkillTools>>>,
+    /// End of the last key-usage anomaly already reported, so a burst is
+    /// alerted once even though each check re-reads a window before it.
+    key_alerted_until: Mutex<Option<chrono::DateTime<chrono::Utc>>>,
+    /// A broken audit chain is reported once per process, not every check.
+    key_audit_tamper_reported: std::sync::atomic::AtomicBool,
+    /// External documents already considered for a filing suggestion this
+    /// session, so each arrival is suggested (or passed over) once.
+    filing_offered: Mutex<HashSet<String>>,
 }
 
 impl Orchestrator {
@@ -61,7 +78,7 @@ impl Orchestrator {
     pub async fn new(
         config: AiConfig,
         db: Arc<dyn GraphDB>,
-        event_tx: std::sync::mpsc::Sender<OrchestratorEvent>,
+        event_tx: Publisher<OrchestratorEvent>,
     ) -> Result<Self> {
         let model_dir = config.model_dir.clone();
         let n_gpu_layers = config.n_gpu_layers;
@@ -121,6 +138,10 @@ impl Orchestrator {
             p2p_command_tx: Mutex::new(None),
             #[cfg(feature = "vision")]
             vision: Mutex::new(None),
+            skill_tools: Mutex::new(None),
+            key_alerted_until: Mutex::new(None),
+            key_audit_tamper_reported: std::sync::atomic::AtomicBool::new(false),
+            filing_offered: Mutex::new(HashSet::new()),
         })
     }
 
@@ -166,6 +187,16 @@ impl Orchestrator {
         self.trust.lock().map(|t| t.all_entries()).unwrap_or_default()
     }
 
+    /// Apply `autonomy.auto_approve_after` from the app config.
+    pub fn set_auto_approve_threshold(&self, threshold: u32) {
+        if let Ok(mut trust) = self.trust.lock() {
+            trust.set_threshold(threshold);
+            if let Err(e) = trust.save(&self.profile_dir) {
+                tracing::warn!("Failed to save trust after threshold change: {e}");
+            }
+        }
+    }
+
     /// Reset trust for a specific action.
     pub fn reset_trust_action(&self, action: &str) {
         if let Ok(mut trust) = self.trust.lock() {
@@ -199,6 +230,25 @@ impl Orchestrator {
         }
     }
 
+    /// Forget the session's keys when it is locked. The encrypted session
+    /// log is closed rather than reopened in plaintext, so nothing is
+    /// logged until the next unlock installs a key again.
+    pub fn clear_session_keys(&self) {
+        if let Ok(mut guard) = self.pii_account_key.lock() {
+            *guard = None;
+        }
+        #[cfg(feature = "encrypted-log")]
+        {
+            if let Ok(mut guard) = self.session_log_key.lock() {
+                if guard.take().is_some() {
+                    if let Ok(mut log) = self.session_log.lock() {
+                        *log = None;
+                    }
+                }
+            }
+        }
+    }
+
     /// Re-opens the session log in encrypted mode. Each subsequent entry will be
     /// encrypted with XChaCha20-Poly1305 and hash-chained to the previous entry
     /// for tamper detection.
@@ -236,6 +286,26 @@ impl Orchestrator {
         }
     }
 
+    /// Expose installed skills to the chat agent loop as tools.
+    pub fn set_skill_tools(&self, tools: SkillTools) {
+        if let Ok(mut guard) = self.skill_tools.lock() {
+            *guard = Some(Arc::new(tools));
+        }
+    }
+
+    /// Run a write tool — a built-in one or a document-modifying skill.
+    /// The caller has already gated it.
+    async fn execute_write_tool(
+        &self,
+        call: &ToolCall,
+        skills: Option<&SkillTools>,
+    ) -> WriteToolResult {
+        match skills {
+            Some(s) if s.find(&call.name).is_some() => s.execute(call, self.db.as_ref()).await,
+            _ => crate::tools::execute_write_tool(call, self.db.as_ref()).await,
+        }
+    }
+
     /// Snapshot the P2P command channel for one-shot dispatch from
     /// intent handlers. Returns None if the post-login P2P startup
     /// hasn't run yet.
@@ -382,6 +452,28 @@ impl Orchestrator {
         self.handle_query(message).await
     }
 
+    /// The last `max_turns` chat turns from the session log, oldest first —
+    /// the same history the agent loop feeds the model. Contents are in
+    /// canonical form (`[pii:<id>]` tokens); callers resolve for display.
+    pub fn chat_history(&self, max_turns: usize) -> Vec<crate::session_log::SessionEntry> {
+        // Actions and search inputs are interleaved with chat turns, so read
+        // well past the cap before filtering.
+        let mut entries: Vec<_> = self
+            .load_session_entries(max_turns.saturating_mul(4))
+            .into_iter()
+            .filter(|e| match e.entry_type.as_str() {
+                "user_input" => e.mode.as_deref() == Some("chat"),
+                "chat_response" => true,
+                _ => false,
+            })
+            .filter(|e| e.content.is_some())
+            .collect();
+        if entries.len() > max_turns {
+            entries.drain(..entries.len() - max_turns);
+        }
+        entries
+    }
+
     /// Emit an `InjectionDetected` event for the highest-severity match in
     /// `matches` (if any), using the same mechanism as the tool-output scan.
     /// `source` labels where the untrusted text came from. No-op on empty.
@@ -468,6 +560,16 @@ impl Orchestrator {
             sp
         };
 
+        // Installed skills, callable as tools alongside the built-in ones.
+        let skill_tools = self.skill_tools.lock().ok().and_then(|g| g.clone());
+        let skill_tool_names = skill_tools.as_ref().map(|t| t.names()).unwrap_or_default();
+        let system_prompt = match &skill_tools {
+            Some(tools) if !tools.tools().is_empty() => {
+                format!("{system_prompt}\n{}", tools.describe())
+            }
+            _ => system_prompt,
+        };
+
         // 6. Append current user message to turns
         turns.push(crate::llm::context::ChatTurn {
             role: crate::llm::context::ChatRole::User,
@@ -526,13 +628,26 @@ impl Orchestrator {
 
             // Check for tool calls
             if crate::tools::has_tool_call(&response, Some(&*formatter)) {
-                let calls = crate::tools::parse_tool_calls(&response, Some(&*formatter));
+                let calls = crate::tools::parse_tool_calls_with(
+                    &response,
+                    Some(&*formatter),
+                    &skill_tool_names,
+                );
                 if let Some(call) = calls.first() {
                     tracing::info!("Tool call: {} (iteration {})", call.name, iterations);
 
-                    let tool_output = if crate::tools::is_write_tool(&call.name) {
-                        // Write tool — gate through action gravity system
-                        let level = security::action_level(&call.name);
+                    let skill_tool = skill_tools.as_ref().and_then(|t| t.find(&call.name));
+                    let tool_output = if crate::tools::is_write_tool(&call.name)
+                        || skill_tool.is_some_and(|t| t.confirm)
+                    {
+                        // Write tool — gate through action gravity system.
+                        // Skills that modify documents, reach the network or
+                        // read every document are Modify-level actions.
+                        let level = if skill_tool.is_some() {
+                            security::ActionLevel::Modify
+                        } else {
+                            security::action_level(&call.name)
+                        };
                         let trusted = {
                             if let Ok(trust) = self.trust.lock() {
                                 trust.should_auto_approve(
@@ -549,8 +664,9 @@ impl Orchestrator {
                         // turn, treat this write as a data-plane-originated
                         // action and force confirmation — never auto-approve.
                         // The helper supplies the human-readable reason.
-                        let plane_violation = action_gate::force_confirmation_after_data_plane(
+                        let plane_violation = action_gate::force_confirmation_at_level(
                             &call.name,
+                            level,
                             loop_ingested_data_plane,
                         );
                         if let Some(ref reason) = plane_violation {
@@ -570,7 +686,7 @@ impl Orchestrator {
                             && (!action_gate::requires_confirmation(level) || trusted)
                         {
                             // Auto-execute (Observe/Annotate or trusted)
-                            let result = crate::tools::execute_write_tool(call, self.db.as_ref()).await;
+                            let result = self.execute_write_tool(call, skill_tools.as_deref()).await;
                             if let Some(event) = result.event {
                                 let _ = self.event_tx.send(event);
                             }
@@ -636,7 +752,7 @@ impl Orchestrator {
                                     let _ = self.event_tx.send(OrchestratorEvent::BubbleState(
                                         BubbleVisualState::Executing,
                                     ));
-                                    let result = crate::tools::execute_write_tool(call, self.db.as_ref()).await;
+                                    let result = self.execute_write_tool(call, skill_tools.as_deref()).await;
                                     if let Some(event) = result.event {
                                         let _ = self.event_tx.send(event);
                                     }
@@ -673,7 +789,20 @@ impl Orchestrator {
                         }
                     } else {
                         // Read-only tool — execute immediately
-                        let result = crate::tools::execute_tool(call, self.db.as_ref()).await;
+                        let result = match (&skill_tools, skill_tool) {
+                            (Some(tools), Some(_)) => {
+                                let r = tools.execute(call, self.db.as_ref()).await;
+                                if let Some(event) = r.event {
+                                    let _ = self.event_tx.send(event);
+                                }
+                                crate::tools::ToolResult {
+                                    tool_name: r.tool_name,
+                                    success: r.success,
+                                    output: r.output,
+                                }
+                            }
+                            _ => crate::tools::execute_tool(call, self.db.as_ref()).await,
+                        };
                         // GATING-001 (v0.0.7): arm the data-plane gate after ANY
                         // read tool, regardless of the "(owned)"/"(external)"
                         // label. "Owned" is not "trusted": owned document and
@@ -1004,6 +1133,34 @@ impl Orchestrator {
                     }
                 }
             }
+            "file_document" => {
+                if let Some(target) = target {
+                    let target: filing::FilingTarget = serde_json::from_str(target)?;
+                    let doc = self.db.get_document(&target.doc_id).await?;
+                    let mut fields = sovereign_core::content::ContentFields::parse(&doc.content);
+                    let body = filing::with_tags(&fields.body, &target.tags);
+                    if body != fields.body {
+                        fields.body = body;
+                        self.db
+                            .update_document(&target.doc_id, None, Some(&fields.serialize()))
+                            .await?;
+                    }
+                    if doc.thread_id != target.thread_id {
+                        self.db
+                            .move_document_to_thread(&target.doc_id, &target.thread_id)
+                            .await?;
+                        tracing::info!("Filed {} in {}", target.doc_id, target.thread_id);
+                        self.log_action(
+                            "file_document",
+                            &format!("{} -> {}", doc.title, target.thread_id),
+                        );
+                        let _ = self.event_tx.send(OrchestratorEvent::DocumentMoved {
+                            doc_id: target.doc_id,
+                            new_thread_id: target.thread_id,
+                        });
+                    }
+                }
+            }
             "history" => {
                 if let Some(target) = target {
                     let docs = self.db.search_documents_by_title(target).await?;
@@ -1046,7 +1203,14 @@ impl Orchestrator {
                         // `[pii:<id>]` tokens, so token resolution can't mask the
                         // raw SSN/IBAN/card numbers it may contain — sending it to
                         // the model would leak pre-tokenization plaintext.
-                        if doc.pii_scanned_at.is_none() {
+                        if !doc.ai_may_read() {
+                            let _ = self.event_tx.send(OrchestratorEvent::ChatResponse {
+                                text: format!(
+                                    "\"{}\" is marked sensitive, so I won't send its contents to the model.",
+                                    doc.title
+                                ),
+                            });
+                        } else if doc.pii_scanned_at.is_none() {
                             let _ = self.event_tx.send(OrchestratorEvent::ChatResponse {
                                 text: format!(
                                     "I can't summarize \"{}\" yet — it hasn't been scanned for personal information, \
@@ -1351,6 +1515,56 @@ impl Orchestrator {
                     });
                 }
             }
+            "tasks_due" => {
+                let today = chrono::Local::now().date_naive();
+                let by = today + chrono::Days::new(7);
+                let due = tasks::list_tasks(self.db.as_ref(), &TaskFilter::open_due_by(by)).await?;
+                let lines: Vec<String> = due
+                    .iter()
+                    .map(|t| {
+                        let when = match t.due {
+                            Some(d) if d < today => format!("overdue since {d}"),
+                            Some(d) => format!("due {d}"),
+                            None => String::new(),
+                        };
+                        format!("- {} ({}, {})", t.text, t.document_title, when)
+                    })
+                    .collect();
+                self.log_action("tasks_due", &format!("{} tasks", lines.len()));
+                let text = if lines.is_empty() {
+                    "Nothing is due this week.".to_string()
+                } else {
+                    format!("Due this week:\n{}", lines.join("\n"))
+                };
+                let _ = self.event_tx.send(OrchestratorEvent::ChatResponse { text });
+            }
+            "set_reminder" => {
+                let text = self.set_reminder(query, target).await?;
+                let _ = self.event_tx.send(OrchestratorEvent::ChatResponse { text });
+            }
+            "list_reminders" => {
+                let pending: Vec<Reminder> = self
+                    .db
+                    .list_reminders(None)
+                    .await?
+                    .into_iter()
+                    .filter(|r| r.fired_at.is_none())
+                    .collect();
+                let lines: Vec<String> = pending
+                    .iter()
+                    .map(|r| {
+                        let due = r.due_at.with_timezone(&chrono::Local);
+                        format!("- {} ({})", r.text, due.format("%a %-d %b %H:%M"))
+                    })
+                    .collect();
+                self.log_action("list_reminders", &format!("{} reminders", lines.len()));
+                let text = if lines.is_empty() {
+                    "You have no upcoming reminders.".to_string()
+                } else {
+                    format!("Upcoming reminders:\n{}", lines.join("\n"))
+                };
+                let _ = self.event_tx.send(OrchestratorEvent::ChatResponse { text });
+            }
             // Communications actions
             "list_contacts" => {
                 let contacts = self.db.list_contacts().await?;
@@ -1529,6 +1743,15 @@ impl Orchestrator {
                     success: true,
                 });
             }
+            "lock_session" => {
+                tracing::info!("Session lock requested");
+                self.log_action(action, "");
+                let _ = self.event_tx.send(OrchestratorEvent::LockRequested);
+                let _ = self.event_tx.send(OrchestratorEvent::ActionExecuted {
+                    action: action.to_string(),
+                    success: true,
+                });
+            }
             "chat" | "unknown" => {
                 // Delegate to the agent loop which handles context, tools, and history
                 self.run_chat_agent_loop(query).await?;
@@ -1556,6 +1779,76 @@ impl Orchestrator {
         Ok(())
     }
 
+    /// Set a reminder from chat ("remind me about Budget Overview Friday
+    /// 9am"). The time is read from the user's text, falling back to the
+    /// router's target. The subject picks a document with that exact
+    /// title, else an open task mentioning it, else the best title match.
+    /// Returns the chat reply.
+    async fn set_reminder(&self, query: &str, target: Option<&str>) -> Result<String> {
+        use crate::intent::reminder::parse_reminder;
+        use chrono::TimeZone;
+
+        let now = chrono::Local::now().naive_local();
+        let Some(request) = parse_reminder(query, now)
+            .or_else(|| target.and_then(|t| parse_reminder(t, now)))
+        else {
+            return Ok("When should I remind you? Try \"remind me about Budget Overview \
+                       Friday 9am\"."
+                .into());
+        };
+        let Some(due) = chrono::Local.from_local_datetime(&request.due).earliest() else {
+            return Ok(format!("{} doesn't exist on this clock.", request.due));
+        };
+        let due_utc = due.with_timezone(&chrono::Utc);
+
+        let subject = request.subject.to_lowercase();
+        let titled = self.db.search_documents_by_title(&request.subject).await?;
+        let open_tasks = tasks::list_tasks(
+            self.db.as_ref(),
+            &TaskFilter {
+                status: Some(TaskStatus::Open),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let exact = titled.iter().find(|d| d.title.to_lowercase() == subject);
+        let reminder = if let Some(doc) = exact {
+            Reminder::new(doc.id_string().unwrap_or_default(), doc.title.clone(), due_utc)
+        } else if let Some(task) = open_tasks
+            .iter()
+            .find(|t| t.text.to_lowercase().contains(&subject))
+        {
+            let mut r = Reminder::new(task.document_id.clone(), task.text.clone(), due_utc);
+            r.task_index = Some(task.index);
+            r
+        } else if let Some(doc) = titled.first() {
+            Reminder::new(doc.id_string().unwrap_or_default(), doc.title.clone(), due_utc)
+        } else {
+            return Ok(format!(
+                "I couldn't find a document or task called \"{}\".",
+                request.subject
+            ));
+        };
+
+        let created = self.db.create_reminder(reminder).await?;
+        let reminder_id = created.id_string().unwrap_or_default();
+        self.log_action(
+            "set_reminder",
+            &format!("{} at {}", created.document_id, due_utc.to_rfc3339()),
+        );
+        let _ = self.event_tx.send(OrchestratorEvent::ReminderSet {
+            reminder_id,
+            doc_id: created.document_id.clone(),
+            text: created.text.clone(),
+            due_at: due_utc.to_rfc3339(),
+        });
+        Ok(format!(
+            "I'll remind you about {} on {}.",
+            created.text,
+            due.format("%A %-d %B at %H:%M")
+        ))
+    }
+
     // Injection scanning is handled inline in the chat agent loop (handle_chat)
     // where tool outputs are scanned via injection::scan_for_injection() before
     // being fed back to the LLM. See the tool execution block above.
@@ -1579,6 +1872,55 @@ impl Orchestrator {
         Ok(result)
     }
 
+    /// Scan the key audit log for bulk decryption in quiet hours and raise
+    /// an alert in the bubble for each new burst. Looks back `lookback`
+    /// plus the policy window so bursts straddling two checks are caught.
+    /// Returns the number of alerts raised.
+    pub fn check_key_usage(&self, lookback: Duration) -> usize {
+        use sovereign_crypto::key_audit::{self, AnomalyPolicy};
+
+        let policy = AnomalyPolicy::default();
+        let since = chrono::Utc::now()
+            - chrono::Duration::from_std(lookback).unwrap_or_default()
+            - policy.window;
+        let entries = match key_audit::recent_entries(since) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Key audit log failed verification: {e}");
+                if self
+                    .key_audit_tamper_reported
+                    .swap(true, std::sync::atomic::Ordering::Relaxed)
+                {
+                    return 0;
+                }
+                let _ = self.event_tx.send(OrchestratorEvent::KeyUsageAlert {
+                    text: "The key usage log failed its integrity check — it may have been \
+                           tampered with."
+                        .into(),
+                });
+                return 1;
+            }
+        };
+
+        let mut alerted = self.key_alerted_until.lock().unwrap();
+        let mut raised = 0;
+        for anomaly in key_audit::detect_anomalies(&entries, &policy, &chrono::Local) {
+            if alerted.is_some_and(|until| anomaly.ended_at <= until) {
+                continue;
+            }
+            *alerted = Some(anomaly.ended_at);
+            tracing::warn!("Key usage anomaly: {} document keys", anomaly.document_keys);
+            let _ = self.event_tx.send(OrchestratorEvent::BubbleState(
+                BubbleVisualState::Suggesting,
+            ));
+            let _ = self.event_tx.send(OrchestratorEvent::KeyUsageAlert {
+                text: anomaly.describe(&chrono::Local),
+            });
+            raised += 1;
+        }
+        raised
+    }
+
     /// Run one memory consolidation cycle: find related document pairs and
     /// create AI-suggested links. Only runs when the system is idle.
     ///
@@ -1648,6 +1990,12 @@ impl Orchestrator {
         self.classifier.try_lock().is_ok()
     }
 
+    /// Whether the reasoning model is resident, or `None` while the
+    /// classifier is busy and can't be asked.
+    pub fn reasoning_loaded(&self) -> Option<bool> {
+        self.classifier.try_lock().ok().map(|c| c.reasoning_loaded())
+    }
+
     /// Generate a proactive suggestion based on current context.
     /// Called after N seconds of idle time. Never auto-executes.
     /// Uses adaptive thresholds from the user profile to decide whether to show.
@@ -1655,10 +2003,15 @@ impl Orchestrator {
         // Drain any pending feedback events first
         self.poll_feedback().await;
 
-        let docs = self.db.list_documents(None).await?;
+        let mut docs = self.db.list_documents(None).await?;
+        // Suggestions are drawn from content; sensitive documents lend none.
+        docs.retain(|d| d.ai_may_read());
         let threads = self.db.list_threads().await?;
 
-        if let Some((text, action)) = generate_suggestion(&docs, &threads) {
+        let suggestion = self
+            .filing_suggestion(&docs, &threads)
+            .or_else(|| generate_suggestion(&docs, &threads));
+        if let Some((text, action, target)) = suggestion {
             // Adaptive gating: check profile feedback for this action
             let should_show = {
                 if let Ok(profile) = self.profile.lock() {
@@ -1696,6 +2049,7 @@ impl Orchestrator {
                 let _ = self.event_tx.send(OrchestratorEvent::Suggestion {
                     text,
                     action,
+                    target,
                 });
             }
         }
@@ -1703,6 +2057,46 @@ impl Orchestrator {
         Ok(())
     }
 
+    /// Where the newest external arrival not considered yet should be
+    /// filed, as a suggestion. Each arrival is considered once, matched or
+    /// not.
+    fn filing_suggestion(
+        &self,
+        docs: &[sovereign_db::schema::Document],
+        threads: &[Thread],
+    ) -> Option<(String, String, Option<String>)> {
+        let mut offered = self.filing_offered.lock().ok()?;
+        let doc = filing::newest_arrival(docs, &offered, chrono::Utc::now())?;
+        offered.insert(doc.id_string()?);
+        let suggestion = filing::suggest_filing(doc, docs, threads)?;
+        let target = serde_json::to_string(&suggestion.target).ok()?;
+        Some((suggestion.text(), "file_document".into(), Some(target)))
+    }
+
+    /// Carry out a suggestion the user accepted. Accepting the card is the
+    /// confirmation, so this skips the action gate — which is why only the
+    /// actions `generate_suggestion` can propose are allowed through.
+    /// Progress reaches the UI as the action's usual events, followed by
+    /// `ActionExecuted`.
+    pub async fn run_suggestion(&self, action: &str, target: Option<&str>) -> Result<()> {
+        if !SUGGESTION_ACTIONS.contains(&action) {
+            anyhow::bail!("'{action}' is not a suggestion action");
+        }
+        self.log_action("suggestion_accepted", &format!("{action} {}", target.unwrap_or("")));
+        let _ = self
+            .event_tx
+            .send(OrchestratorEvent::BubbleState(BubbleVisualState::Executing));
+        let result = self.execute_action(action, target, action).await;
+        let _ = self.event_tx.send(OrchestratorEvent::ActionExecuted {
+            action: action.to_string(),
+            success: result.is_ok(),
+        });
+        let _ = self
+            .event_tx
+            .send(OrchestratorEvent::BubbleState(BubbleVisualState::Idle));
+        result
+    }
+
     /// Drain pending feedback events from the UI and update the profile.
     /// Saves the profile once after processing all events (batch).
     async fn poll_feedback(&self) {
@@ -1788,6 +2182,11 @@ fn format_tool_proposal(name: &str, args: &serde_json::Value) -> String {
             args["document_title"].as_str().unwrap_or("?"),
             args["thread_name"].as_str().unwrap_or("?"),
         ),
+        _ if name.starts_with(crate::skill_tools::SKILL_TOOL_PREFIX) => format!(
+            "Run skill tool {} on '{}'",
+            name,
+            args["document"].as_str().unwrap_or("?"),
+        ),
         _ => format!("{}: {}", name, args),
     }
 }
@@ -1813,6 +2212,11 @@ fn format_confirmation_message(name: &str, args: &serde_json::Value) -> String {
             args["document_title"].as_str().unwrap_or("?"),
             args["thread_name"].as_str().unwrap_or("?"),
         ),
+        _ if name.starts_with(crate::skill_tools::SKILL_TOOL_PREFIX) => format!(
+            "I'll run {} on '{}', which may change the document. OK?",
+            name,
+            args["document"].as_str().unwrap_or("?"),
+        ),
         _ => format!("I'd like to perform '{}'. Ready?", name),
     }
 }
@@ -1841,30 +2245,43 @@ fn parse_move_target(target: &str) -> (String, String) {
     }
 }
 
+/// Actions `generate_suggestion` and `filing_suggestion` can propose, and
+/// so the only ones `run_suggestion` will execute.
+const SUGGESTION_ACTIONS: &[&str] =
+    &["create_thread", "adopt", "create_milestone", "file_document"];
+
 /// Analyze documents and threads to produce a contextual suggestion.
-/// Returns (text, action) or None if no suggestion is appropriate.
+/// Returns (text, action, target) or None if no suggestion is appropriate.
+/// The target is what [`Orchestrator::run_suggestion`] acts on if accepted.
 pub(crate) fn generate_suggestion(
     docs: &[sovereign_db::schema::Document],
     threads: &[Thread],
-) -> Option<(String, String)> {
+) -> Option<(String, String, Option<String>)> {
     // Suggest creating a thread if there are docs but no threads
     if !docs.is_empty() && threads.is_empty() {
         return Some((
-            "You have documents but no threads. Create a thread to organize them?".into(),
+            "You have documents but no threads. Create a \"General\" thread to organize them?"
+                .into(),
             "create_thread".into(),
+            Some("General".into()),
         ));
     }
 
-    // Suggest adopting external content if there are many external docs
-    let external_count = docs.iter().filter(|d| !d.is_owned).count();
+    // Suggest adopting external content if there are many external docs.
+    // Accepting adopts the most recently modified one.
+    let external: Vec<_> = docs.iter().filter(|d| !d.is_owned).collect();
     let total = docs.len();
-    if total >= 3 && external_count as f64 / total as f64 > 0.7 {
+    if total >= 3 && external.len() as f64 / total as f64 > 0.7 {
+        let newest = external.iter().max_by_key(|d| d.modified_at)?;
         return Some((
             format!(
-                "{} of {} documents are external. Adopt some to make them yours?",
-                external_count, total
+                "{} of {} documents are external. Adopt \"{}\" to make it yours?",
+                external.len(),
+                total,
+                newest.title
             ),
             "adopt".into(),
+            Some(newest.title.clone()),
         ));
     }
 
@@ -1879,6 +2296,7 @@ pub(crate) fn generate_suggestion(
                     thread.name, thread_docs
                 ),
                 "create_milestone".into(),
+                Some(format!("{} documents on {}", thread_docs, thread.name)),
             ));
         }
     }
@@ -2112,9 +2530,10 @@ mod tests {
         ];
         let result = generate_suggestion(&docs, &[]);
         assert!(result.is_some());
-        let (text, action) = result.unwrap();
+        let (text, action, target) = result.unwrap();
         assert_eq!(action, "create_thread");
         assert!(text.contains("no threads"));
+        assert_eq!(target.as_deref(), Some("General"));
     }
 
     #[test]
@@ -2128,9 +2547,24 @@ mod tests {
         ];
         let result = generate_suggestion(&docs, &[thread]);
         assert!(result.is_some());
-        let (text, action) = result.unwrap();
+        let (text, action, target) = result.unwrap();
         assert_eq!(action, "adopt");
         assert!(text.contains("external"));
+        assert!(matches!(target.as_deref(), Some("A" | "B" | "C")));
+    }
+
+    #[test]
+    fn suggestion_milestone_target_names_the_thread() {
+        use sovereign_db::schema::{Document, Thread};
+        let thread = Thread::new("Research on AI".into(), "".into());
+        let docs: Vec<_> = (0..5)
+            .map(|i| Document::new(format!("D{i}"), String::new(), true))
+            .collect();
+        let (_, action, target) = generate_suggestion(&docs, &[thread]).unwrap();
+        assert_eq!(action, "create_milestone");
+        assert!(SUGGESTION_ACTIONS.contains(&action.as_str()));
+        // execute_action splits on the first " on ".
+        assert_eq!(target.as_deref(), Some("5 documents on Research on AI"));
     }
 
     #[test]
@@ -0,0 +1,457 @@
+//! Installed skills exposed as chat agent tools.
+//!
+//! Every action of every registered skill becomes a tool named
+//! `skill_<skill>_<action>`. The model passes the target document by title
+//! plus the action's manifest-declared parameters; execution goes through
+//! `SkillRegistry::execute_skill`, so signature checks, user revocations and
+//! the capability audit log apply exactly as for the skills panel.
+//!
+//! Skills that can modify documents are write tools: the agent loop routes
+//! them through the action gate like `create_document`.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use sovereign_core::content::ContentFields;
+use sovereign_core::interfaces::OrchestratorEvent;
+use sovereign_db::GraphDB;
+use sovereign_skills::{
+    Capability, SkillContext, SkillDbAccess, SkillDocument, SkillLlmAccess, SkillOutput,
+    SkillRegistry, SkillVerification,
+};
+
+use crate::tools::{ToolCall, WriteToolResult};
+
+/// Prefix shared by every skill tool name.
+pub const SKILL_TOOL_PREFIX: &str = "skill_";
+
+/// Max chars of structured skill output fed back to the model.
+const MAX_OUTPUT_CHARS: usize = 1000;
+
+/// One skill action, described as a tool.
+#[derive(Debug, Clone)]
+pub struct SkillTool {
+    pub name: String,
+    pub skill: String,
+    pub action: String,
+    pub description: String,
+    /// Parameter block in the same shape as `ToolDef::parameters`.
+    pub parameters: String,
+    /// Declared parameter names; empty means the action takes a raw
+    /// `params` string instead.
+    declared: Vec<String>,
+    /// Whether running it needs the user's confirmation: the skill may
+    /// change documents, reach the network, or read every document.
+    pub confirm: bool,
+}
+
+/// Bridges the skill registry into the agent loop's tool set.
+pub struct SkillTools {
+    registry: Arc<SkillRegistry>,
+    db: Arc<dyn SkillDbAccess>,
+    llm: Option<Arc<dyn SkillLlmAccess>>,
+    tools: Vec<SkillTool>,
+}
+
+fn tool_name(skill: &str, action: &str) -> String {
+    let sanitize = |s: &str| -> String {
+        s.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+            .collect()
+    };
+    format!("{SKILL_TOOL_PREFIX}{}_{}", sanitize(skill), sanitize(action))
+}
+
+impl SkillTools {
+    /// Snapshot the registry's skills as tools. Tampered skills are left out.
+    pub fn new(
+        registry: Arc<SkillRegistry>,
+        db: Arc<dyn SkillDbAccess>,
+        llm: Option<Arc<dyn SkillLlmAccess>>,
+    ) -> Self {
+        let mut tools = Vec::new();
+        let mut seen = HashSet::new();
+        for skill in registry.all_skills() {
+            let skill_name = skill.name();
+            if matches!(
+                registry.verification(skill_name),
+                Some(SkillVerification::Tampered)
+            ) {
+                continue;
+            }
+            let caps = skill.required_capabilities();
+            let confirm = caps.iter().any(|c| {
+                matches!(
+                    c,
+                    Capability::WriteDocument
+                        | Capability::WriteAllDocuments
+                        | Capability::WriteFilesystem
+                        | Capability::Network
+                        | Capability::ReadAllDocuments
+                )
+            });
+            let manifest = registry.manifest_for(skill_name);
+
+            for (action, label) in skill.actions() {
+                let name = tool_name(skill_name, &action);
+                if !seen.insert(name.clone()) {
+                    continue;
+                }
+                let spec = manifest.and_then(|m| m.action(&action));
+                let description = match spec {
+                    Some(s) if !s.description.is_empty() => s.description.clone(),
+                    _ => format!("Run the '{label}' action of the {skill_name} skill."),
+                };
+                let declared: Vec<String> = spec
+                    .map(|s| s.parameters.keys().cloned().collect())
+                    .unwrap_or_default();
+                let mut params = serde_json::Map::new();
+                params.insert("document".into(), "document title".into());
+                match spec {
+                    Some(s) if !s.parameters.is_empty() => {
+                        for (k, v) in &s.parameters {
+                            params.insert(k.clone(), v.clone().into());
+                        }
+                    }
+                    _ => {
+                        params.insert("params".into(), "action input (optional)".into());
+                    }
+                }
+                tools.push(SkillTool {
+                    name,
+                    skill: skill_name.to_string(),
+                    action,
+                    description: if confirm {
+                        format!("{description} Requires user confirmation.")
+                    } else {
+                        description
+                    },
+                    parameters: serde_json::Value::Object(params).to_string(),
+                    declared,
+                    confirm,
+                });
+            }
+        }
+        Self {
+            registry,
+            db,
+            llm,
+            tools,
+        }
+    }
+
+    pub fn tools(&self) -> &[SkillTool] {
+        &self.tools
+    }
+
+    pub fn find(&self, name: &str) -> Option<&SkillTool> {
+        self.tools.iter().find(|t| t.name == name)
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.tools.iter().map(|t| t.name.clone()).collect()
+    }
+
+    /// Tool list for the system prompt, in the same shape as
+    /// `format_tool_descriptions`.
+    pub fn describe(&self) -> String {
+        if self.tools.is_empty() {
+            return String::new();
+        }
+        let mut out = String::from("Installed skills, callable as tools:\n");
+        for t in &self.tools {
+            out.push_str(&format!(
+                "- {}: {} Parameters: {}\n",
+                t.name, t.description, t.parameters
+            ));
+        }
+        out
+    }
+
+    /// Execute a skill tool call on the document named in its arguments.
+    /// Content updates are saved back; files are only announced (they need
+    /// the save dialog, so the user runs the skill from the skills panel).
+    pub async fn execute(&self, call: &ToolCall, graph: &dyn GraphDB) -> WriteToolResult {
+        let fail = |output: String| WriteToolResult {
+            tool_name: call.name.clone(),
+            success: false,
+            output,
+            event: None,
+        };
+        let Some(tool) = self.find(&call.name) else {
+            return fail(format!("Unknown skill tool: {}", call.name));
+        };
+
+        let title = call
+            .arguments
+            .get("document")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        if title.is_empty() {
+            return fail("A document title is required.".into());
+        }
+        let docs = graph.search_documents_by_title(title).await.unwrap_or_default();
+        let Some(doc) = docs.into_iter().find(|d| d.ai_may_see()) else {
+            return fail(format!("Document '{title}' not found."));
+        };
+        if !doc.ai_may_read() {
+            return fail(format!("'{}' is marked sensitive.", doc.title));
+        }
+        let doc_id = doc.id_string().unwrap_or_default();
+        let doc_title = doc.title.clone();
+        let skill_doc = SkillDocument {
+            id: doc_id.clone(),
+            title: doc.title,
+            content: ContentFields::parse(&doc.content),
+        };
+        let params = tool_params(tool, &call.arguments);
+
+        // Auto-grant exactly what the skill declares, as the skills panel
+        // does; the registry then applies signature and revocation checks.
+        let registry = self.registry.clone();
+        let ctx = SkillContext {
+            granted: registry
+                .find_skill(&tool.skill)
+                .map(|s| s.required_capabilities().into_iter().collect())
+                .unwrap_or_default(),
+            db: Some(self.db.clone()),
+            llm: self.llm.clone(),
+        };
+        let (skill, action) = (tool.skill.clone(), tool.action.clone());
+        let result = tokio::task::spawn_blocking(move || {
+            registry.execute_skill(&skill, &action, &skill_doc, &params, &ctx)
+        })
+        .await;
+        let output = match result {
+            Ok(Ok(output)) => output,
+            Ok(Err(e)) => return fail(format!("Skill '{}' failed: {e}", tool.skill)),
+            Err(e) => return fail(format!("Skill '{}' panicked: {e}", tool.skill)),
+        };
+
+        let skill_event = |kind: &str, data: String| {
+            Some(OrchestratorEvent::SkillResult {
+                skill: tool.skill.clone(),
+                action: tool.action.clone(),
+                kind: kind.into(),
+                data,
+            })
+        };
+        match output {
+            SkillOutput::ContentUpdate(fields) => {
+                match graph
+                    .update_document(&doc_id, None, Some(&fields.serialize()))
+                    .await
+                {
+                    Ok(_) => WriteToolResult {
+                        tool_name: call.name.clone(),
+                        success: true,
+                        output: format!("Updated '{doc_title}'."),
+                        event: skill_event("content_update", format!("Updated '{doc_title}'")),
+                    },
+                    Err(e) => fail(format!("Failed to save '{doc_title}': {e}")),
+                }
+            }
+            SkillOutput::StructuredData { kind, json } => {
+                // Skill output can echo document text: redact structured PII
+                // as get_document does before it reaches the model.
+                let redacted =
+                    crate::pii::resolve::redact_raw_regex(&json, crate::pii::Locale::Swiss);
+                let truncated: String = redacted.chars().take(MAX_OUTPUT_CHARS).collect();
+                WriteToolResult {
+                    tool_name: call.name.clone(),
+                    success: true,
+                    output: format!("{kind}: {truncated}"),
+                    event: None,
+                }
+            }
+            SkillOutput::File { name, data, .. } => WriteToolResult {
+                tool_name: call.name.clone(),
+                success: true,
+                output: format!(
+                    "Produced file '{name}' ({} bytes). Run the skill from the skills panel to save it.",
+                    data.len()
+                ),
+                event: skill_event("file", format!("Produced '{name}'")),
+            },
+            SkillOutput::None => WriteToolResult {
+                tool_name: call.name.clone(),
+                success: true,
+                output: "Done.".into(),
+                event: None,
+            },
+        }
+    }
+}
+
+/// The skill's `params` string: a JSON object of the declared parameters,
+/// or the raw `params` argument for actions that declare none.
+fn tool_params(tool: &SkillTool, args: &serde_json::Value) -> String {
+    if tool.declared.is_empty() {
+        return match args.get("params") {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(serde_json::Value::Null) | None => String::new(),
+            Some(other) => other.to_string(),
+        };
+    }
+    let obj: serde_json::Map<String, serde_json::Value> = tool
+        .declared
+        .iter()
+        .filter_map(|k| args.get(k).map(|v| (k.clone(), v.clone())))
+        .collect();
+    serde_json::Value::Object(obj).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_db::mock::MockGraphDB;
+    use sovereign_db::schema::Document;
+    use sovereign_skills::skills::find_replace::FindReplaceSkill;
+    use sovereign_skills::skills::search::SearchSkill;
+    use sovereign_skills::skills::word_count::WordCountSkill;
+
+    struct NoDb;
+
+    impl SkillDbAccess for NoDb {
+        fn search_documents(&self, _: &str) -> anyhow::Result<Vec<(String, String, String)>> {
+            Ok(vec![])
+        }
+        fn get_document(&self, _: &str) -> anyhow::Result<(String, String, String)> {
+            anyhow::bail!("no db")
+        }
+        fn list_documents(&self, _: Option<&str>) -> anyhow::Result<Vec<(String, String)>> {
+            Ok(vec![])
+        }
+        fn create_document(&self, _: &str, _: &str, _: &str) -> anyhow::Result<String> {
+            anyhow::bail!("no db")
+        }
+        fn list_relationships(&self, _: &str) -> anyhow::Result<Vec<(String, String)>> {
+            Ok(vec![])
+        }
+        fn list_backlinks(&self, _: &str) -> anyhow::Result<Vec<(String, String)>> {
+            Ok(vec![])
+        }
+        fn list_all_documents_with_link_counts(
+            &self,
+        ) -> anyhow::Result<Vec<(String, String, u32, u32)>> {
+            Ok(vec![])
+        }
+        fn find_or_create_thread(&self, _: &str, _: &str) -> anyhow::Result<String> {
+            anyhow::bail!("no db")
+        }
+    }
+
+    fn bridge() -> SkillTools {
+        let mut registry = SkillRegistry::new();
+        registry.register(Box::new(WordCountSkill));
+        registry.register(Box::new(FindReplaceSkill));
+        SkillTools::new(Arc::new(registry), Arc::new(NoDb), None)
+    }
+
+    fn call(name: &str, args: serde_json::Value) -> ToolCall {
+        ToolCall {
+            name: name.into(),
+            arguments: args,
+        }
+    }
+
+    #[test]
+    fn every_action_becomes_a_tool() {
+        let tools = bridge();
+        let wc = tools.find("skill_word_count_count").unwrap();
+        assert!(!wc.confirm);
+        assert!(wc.parameters.contains("document"));
+        let fr = tools.find("skill_find_replace_find_replace").unwrap();
+        assert!(fr.confirm);
+        assert!(tools.describe().contains("skill_word_count_count"));
+    }
+
+    #[test]
+    fn skills_reading_every_document_need_confirmation() {
+        let mut registry = SkillRegistry::new();
+        registry.register(Box::new(SearchSkill));
+        let tools = SkillTools::new(Arc::new(registry), Arc::new(NoDb), None);
+        let search = tools.find("skill_search_search").unwrap();
+        assert!(search.confirm);
+        assert!(search.description.ends_with("Requires user confirmation."));
+    }
+
+    #[test]
+    fn undeclared_actions_take_raw_params() {
+        let tools = bridge();
+        let fr = tools.find("skill_find_replace_find_replace").unwrap();
+        let raw = r#"{"find":"a","replace":"b"}"#;
+        assert_eq!(tool_params(fr, &serde_json::json!({"params": raw})), raw);
+        assert_eq!(tool_params(fr, &serde_json::json!({})), "");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn runs_skill_on_named_document() {
+        let db = MockGraphDB::new();
+        let mut doc = Document::new("Research Notes".into(), "t:1".into(), true);
+        doc.content = ContentFields {
+            body: "one two three".into(),
+            ..Default::default()
+        }
+        .serialize();
+        db.create_document(doc).await.unwrap();
+
+        let tools = bridge();
+        let result = tools
+            .execute(
+                &call(
+                    "skill_word_count_count",
+                    serde_json::json!({"document": "Research Notes"}),
+                ),
+                &db,
+            )
+            .await;
+        assert!(result.success, "{}", result.output);
+        assert!(result.output.contains("\"words\":3"), "{}", result.output);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn content_updates_are_saved() {
+        let db = MockGraphDB::new();
+        let mut doc = Document::new("Draft".into(), "t:1".into(), true);
+        doc.content = ContentFields {
+            body: "hello world".into(),
+            ..Default::default()
+        }
+        .serialize();
+        let created = db.create_document(doc).await.unwrap();
+
+        let tools = bridge();
+        let result = tools
+            .execute(
+                &call(
+                    "skill_find_replace_find_replace",
+                    serde_json::json!({
+                        "document": "Draft",
+                        "params": r#"{"find":"world","replace":"rust"}"#
+                    }),
+                ),
+                &db,
+            )
+            .await;
+        assert!(result.success, "{}", result.output);
+        let saved = db
+            .get_document(&created.id_string().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(ContentFields::parse(&saved.content).body, "hello rust");
+    }
+
+    #[tokio::test]
+    async fn missing_document_fails() {
+        let db = MockGraphDB::new();
+        let result = bridge()
+            .execute(
+                &call("skill_word_count_count", serde_json::json!({"document": "Nope"})),
+                &db,
+            )
+            .await;
+        assert!(!result.success);
+        assert!(result.output.contains("not found"));
+    }
+}
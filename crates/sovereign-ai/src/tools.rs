@@ -146,6 +146,17 @@ fn strip_code_fences(output: &str) -> String {
 /// Parse tool calls from model output.
 /// Uses the formatter's open/close tags, with fallback to bare JSON.
 pub fn parse_tool_calls(output: &str, formatter: Option<&dyn PromptFormatter>) -> Vec<ToolCall> {
+    parse_tool_calls_with(output, formatter, &[])
+}
+
+/// Like [`parse_tool_calls`], also accepting the dynamic tool names in
+/// `extra_tools` (installed skills, see `skill_tools`).
+pub fn parse_tool_calls_with(
+    output: &str,
+    formatter: Option<&dyn PromptFormatter>,
+    extra_tools: &[String],
+) -> Vec<ToolCall> {
+    let known = |name: &str| all_tools().any(|t| t.name == name) || extra_tools.iter().any(|t| t == name);
     let open_tag = formatter.map_or("<tool_call>", |f| f.tool_call_open_tag());
     let close_tag = formatter.map_or("</tool_call>", |f| f.tool_call_close_tag());
 
@@ -162,7 +173,7 @@ pub fn parse_tool_calls(output: &str, formatter: Option<&dyn PromptFormatter>) -
                 // primary (tagged) path too — the bare-JSON fallback below
                 // already does. An unknown/hallucinated name would otherwise be
                 // accepted here and re-enter the prompt as junk on dispatch.
-                if all_tools().any(|t| t.name == call.name) {
+                if known(&call.name) {
                     calls.push(call);
                 }
             }
@@ -176,7 +187,7 @@ pub fn parse_tool_calls(output: &str, formatter: Option<&dyn PromptFormatter>) -
     if calls.is_empty() {
         let stripped = strip_code_fences(output);
         if let Ok(call) = serde_json::from_str::<ToolCall>(&stripped) {
-            if all_tools().any(|t| t.name == call.name) {
+            if known(&call.name) {
                 calls.push(call);
             }
         }
@@ -391,7 +402,7 @@ async fn execute_move_document(call: &ToolCall, db: &dyn GraphDB) -> WriteToolRe
     }
 
     let docs = db.search_documents_by_title(doc_title).await.unwrap_or_default();
-    let doc = docs.first();
+    let doc = docs.iter().find(|d| d.ai_may_see());
     let thread = db.find_thread_by_name(thread_name).await.unwrap_or(None);
 
     if let (Some(doc), Some(thread)) = (doc, thread.as_ref()) {
@@ -479,6 +490,7 @@ async fn execute_search_documents(call: &ToolCall, db: &dyn GraphDB) -> String {
     let docs = db.search_documents_by_title(query).await.unwrap_or_default();
     let matches: Vec<String> = docs
         .iter()
+        .filter(|d| d.ai_may_see())
         .take(8)
         .map(|d| {
             let ownership = if d.is_owned { "owned" } else { "external" };
@@ -501,7 +513,10 @@ async fn execute_list_threads(db: &dyn GraphDB) -> String {
         .iter()
         .map(|t| {
             let tid = t.id_string().unwrap_or_default();
-            let count = docs.iter().filter(|d| d.thread_id == tid).count();
+            let count = docs
+                .iter()
+                .filter(|d| d.thread_id == tid && d.ai_may_see())
+                .count();
             format!("- {} ({} documents)", t.name, count)
         })
         .collect();
@@ -521,8 +536,14 @@ async fn execute_get_document(call: &ToolCall, db: &dyn GraphDB) -> String {
         .unwrap_or("");
 
     let docs = db.search_documents_by_title(title).await.unwrap_or_default();
-    if let Some(doc) = docs.first() {
+    if let Some(doc) = docs.iter().find(|d| d.ai_may_see()) {
         let ownership = if doc.is_owned { "owned" } else { "external" };
+        if !doc.ai_may_read() {
+            return format!(
+                "Title: {} ({})\nContent withheld: the document is marked sensitive.",
+                doc.title, ownership
+            );
+        }
         // PII-002: replace any `[pii:<id>]` tokens with type-only labels
         // (`[Email]`, …) before the content reaches the model — no decryption.
         // PII-001: a doc that was never PII-scanned (pii_scanned_at==None:
@@ -563,7 +584,8 @@ async fn execute_list_documents(call: &ToolCall, db: &dyn GraphDB) -> String {
         db.list_documents(None).await.unwrap_or_default()
     };
 
-    let lines: Vec<String> = docs
+    let visible: Vec<_> = docs.iter().filter(|d| d.ai_may_see()).collect();
+    let lines: Vec<String> = visible
         .iter()
         .take(15)
         .map(|d| {
@@ -575,7 +597,7 @@ async fn execute_list_documents(call: &ToolCall, db: &dyn GraphDB) -> String {
     if lines.is_empty() {
         "No documents found.".into()
     } else {
-        format!("{} documents:\n{}", docs.len(), lines.join("\n"))
+        format!("{} documents:\n{}", visible.len(), lines.join("\n"))
     }
 }
 
@@ -667,6 +689,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_tool_calls_with_extra_skill_tools() {
+        let output = r#"<tool_call>{"name": "skill_word_count_count", "arguments": {"document": "Notes"}}</tool_call>"#;
+        assert!(parse_tool_calls(output, None).is_empty());
+        let extra = vec!["skill_word_count_count".to_string()];
+        let calls = parse_tool_calls_with(output, None, &extra);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].arguments["document"], "Notes");
+    }
+
     #[test]
     fn parse_tool_calls_empty() {
         let output = "Hello! I can help with that.";
@@ -788,7 +820,7 @@ mod tests {
     // --- Async tests using MockGraphDB ---
 
     use sovereign_db::mock::MockGraphDB;
-    use sovereign_db::schema::{Document, Thread};
+    use sovereign_db::schema::{Document, Sensitivity, Thread};
 
     fn mock_db() -> MockGraphDB {
         MockGraphDB::new()
@@ -858,6 +890,32 @@ mod tests {
         assert!(result.output.contains("not found"));
     }
 
+    #[tokio::test]
+    async fn sensitive_content_and_sealed_documents_stay_out_of_tools() {
+        let db = mock_db();
+        let mut private = Document::new("Private Notes".into(), "t:1".into(), true);
+        private.content = r#"{"body":"do not share","images":[]}"#.into();
+        private.sensitivity = Sensitivity::Sensitive;
+        db.create_document(private).await.unwrap();
+        let mut sealed = Document::new("Sealed Notes".into(), "t:1".into(), true);
+        sealed.sensitivity = Sensitivity::Sealed;
+        db.create_document(sealed).await.unwrap();
+
+        let call = tool_call("get_document", serde_json::json!({"title": "private"}));
+        let result = execute_tool(&call, &db).await;
+        assert!(result.output.contains("Private Notes"));
+        assert!(!result.output.contains("do not share"));
+
+        for call in [
+            tool_call("search_documents", serde_json::json!({"query": "notes"})),
+            tool_call("list_documents", serde_json::json!({})),
+            tool_call("get_document", serde_json::json!({"title": "sealed"})),
+        ] {
+            let result = execute_tool(&call, &db).await;
+            assert!(!result.output.contains("Sealed"), "{}", result.output);
+        }
+    }
+
     #[tokio::test]
     async fn execute_list_documents_all() {
         let db = mock_db();
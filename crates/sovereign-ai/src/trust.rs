@@ -75,6 +75,12 @@ impl TrustTracker {
         }
     }
 
+    /// Change how many consecutive approvals earn auto-approval. Earned
+    /// counts are kept, so lowering it can grant auto-approval at once.
+    pub fn set_threshold(&mut self, threshold: u32) {
+        self.auto_approve_threshold = threshold;
+    }
+
     /// Check whether an action can be auto-approved based on trust history
     /// accumulated in the given workflow.
     /// Only Level 3 (Modify) actions can be auto-approved.
@@ -216,6 +222,18 @@ mod tests {
         assert!(!tracker.should_auto_approve(WORKFLOW_QUERY, "create_thread", ActionLevel::Modify));
     }
 
+    #[test]
+    fn raising_the_threshold_withdraws_auto_approval() {
+        let mut tracker = TrustTracker::with_threshold(2);
+        for _ in 0..2 {
+            tracker.record_approval(WORKFLOW_QUERY, "create_thread");
+        }
+        tracker.set_threshold(3);
+        assert!(!tracker.should_auto_approve(WORKFLOW_QUERY, "create_thread", ActionLevel::Modify));
+        tracker.record_approval(WORKFLOW_QUERY, "create_thread");
+        assert!(tracker.should_auto_approve(WORKFLOW_QUERY, "create_thread", ActionLevel::Modify));
+    }
+
     #[test]
     fn auto_approve_after_threshold() {
         let mut tracker = TrustTracker::with_threshold(3);
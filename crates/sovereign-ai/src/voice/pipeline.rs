@@ -1,13 +1,13 @@
-use std::sync::mpsc;
-
 use anyhow::Result;
-use sovereign_core::config::VoiceConfig;
+use sovereign_core::config::{AppConfig, VoiceConfig};
+use sovereign_core::events::Publisher;
+use tokio::sync::watch;
 
 use crate::events::VoiceEvent;
 
 /// Runs the voice pipeline on a dedicated std::thread.
-/// Communicates with the UI via VoiceEvent channel
-/// and with the orchestrator via the query callback.
+/// Publishes its state to the UI as `VoiceEvent`s on the event bus
+/// and talks to the orchestrator via the query callback.
 pub struct VoicePipeline;
 
 #[cfg(feature = "voice-stt")]
@@ -16,27 +16,17 @@ impl VoicePipeline {
     /// Returns the thread handle for cleanup.
     pub fn spawn(
         config: VoiceConfig,
-        voice_tx: mpsc::Sender<VoiceEvent>,
+        voice_tx: Publisher<VoiceEvent>,
         query_callback: Box<dyn Fn(String) + Send + 'static>,
     ) -> Result<std::thread::JoinHandle<()>> {
-        use std::path::Path;
-
         // Validate config files exist before spawning thread
-        #[cfg(feature = "wake-word")]
-        if !Path::new(&config.wake_word_model).exists() {
-            anyhow::bail!(
-                "Wake word model not found: {}",
-                config.wake_word_model
-            );
-        }
-        if !Path::new(&config.whisper_model).exists() {
-            anyhow::bail!("Whisper model not found: {}", config.whisper_model);
-        }
+        check_models(&config)?;
 
         let handle = std::thread::Builder::new()
             .name("voice-pipeline".into())
             .spawn(move || {
-                if let Err(e) = run_pipeline(config, voice_tx.clone(), query_callback) {
+                let never = &mut || false;
+                if let Err(e) = run_pipeline(config, voice_tx.clone(), &*query_callback, never) {
                     tracing::error!("Voice pipeline error: {e}");
                     let _ = voice_tx.send(VoiceEvent::ListeningStopped);
                 }
@@ -44,6 +34,78 @@ impl VoicePipeline {
 
         Ok(handle)
     }
+
+    /// Like [`spawn`](Self::spawn), but following the `voice` section of
+    /// `configs`: the pipeline runs while it is enabled and restarts with
+    /// the new settings whenever they change, so voice can be switched on,
+    /// off or to another source without relaunching. The thread ends when
+    /// the sender of `configs` is dropped.
+    pub fn spawn_reloading(
+        mut configs: watch::Receiver<AppConfig>,
+        voice_tx: Publisher<VoiceEvent>,
+        query_callback: Box<dyn Fn(String) + Send + 'static>,
+    ) -> Result<std::thread::JoinHandle<()>> {
+        let handle = std::thread::Builder::new()
+            .name("voice-pipeline".into())
+            .spawn(move || loop {
+                if configs.has_changed().is_err() {
+                    return;
+                }
+                let config = configs.borrow_and_update().voice.clone();
+                if config.enabled {
+                    tracing::info!("Voice pipeline starting");
+                    let mut changed = || voice_changed(&mut configs, &config);
+                    let ran = check_models(&config).and_then(|()| {
+                        let tx = voice_tx.clone();
+                        run_pipeline(config.clone(), tx, &*query_callback, &mut changed)
+                    });
+                    match ran {
+                        Ok(()) => continue,
+                        Err(e) => {
+                            tracing::warn!("Voice pipeline unavailable: {e}");
+                            let _ = voice_tx.send(VoiceEvent::ListeningStopped);
+                        }
+                    }
+                } else {
+                    tracing::info!("Voice pipeline disabled in config");
+                }
+                // Idle until the voice settings change.
+                while !voice_changed(&mut configs, &config) {
+                    std::thread::sleep(std::time::Duration::from_millis(500));
+                }
+            })?;
+
+        Ok(handle)
+    }
+}
+
+/// Whether `configs` now holds voice settings other than `current` (or
+/// its sender is gone). Changes to other sections are marked seen.
+#[cfg(feature = "voice-stt")]
+fn voice_changed(configs: &mut watch::Receiver<AppConfig>, current: &VoiceConfig) -> bool {
+    match configs.has_changed() {
+        Ok(true) => configs.borrow_and_update().voice != *current,
+        Ok(false) => false,
+        Err(_) => true,
+    }
+}
+
+/// The model files `config` names must exist before the pipeline starts.
+#[cfg(feature = "voice-stt")]
+fn check_models(config: &VoiceConfig) -> Result<()> {
+    use std::path::Path;
+
+    #[cfg(feature = "wake-word")]
+    if !Path::new(&config.wake_word_model).exists() {
+        anyhow::bail!(
+            "Wake word model not found: {}",
+            config.wake_word_model
+        );
+    }
+    if !Path::new(&config.whisper_model).exists() {
+        anyhow::bail!("Whisper model not found: {}", config.whisper_model);
+    }
+    Ok(())
 }
 
 #[cfg(not(feature = "voice-stt"))]
@@ -51,7 +113,19 @@ impl VoicePipeline {
     /// Stub: voice-stt feature is not enabled at compile time.
     pub fn spawn(
         _config: VoiceConfig,
-        _voice_tx: mpsc::Sender<VoiceEvent>,
+        _voice_tx: Publisher<VoiceEvent>,
+        _query_callback: Box<dyn Fn(String) + Send + 'static>,
+    ) -> Result<std::thread::JoinHandle<()>> {
+        anyhow::bail!(
+            "Voice pipeline unavailable: built without 'voice-stt' feature. \
+             Enable it with --features voice-stt to use speech-to-text."
+        )
+    }
+
+    /// Stub: voice-stt feature is not enabled at compile time.
+    pub fn spawn_reloading(
+        _configs: watch::Receiver<AppConfig>,
+        _voice_tx: Publisher<VoiceEvent>,
         _query_callback: Box<dyn Fn(String) + Send + 'static>,
     ) -> Result<std::thread::JoinHandle<()>> {
         anyhow::bail!(
@@ -64,8 +138,9 @@ impl VoicePipeline {
 #[cfg(feature = "voice-stt")]
 fn run_pipeline(
     config: VoiceConfig,
-    voice_tx: mpsc::Sender<VoiceEvent>,
-    query_callback: Box<dyn Fn(String) + Send + 'static>,
+    voice_tx: Publisher<VoiceEvent>,
+    query_callback: &dyn Fn(String),
+    stop: &mut dyn FnMut() -> bool,
 ) -> Result<()> {
     use ringbuf::traits::*;
 
@@ -134,6 +209,11 @@ fn run_pipeline(
     tracing::info!("Voice pipeline running (frame_size={frame_size})");
 
     loop {
+        if stop() {
+            tracing::info!("Voice settings changed; stopping the pipeline");
+            return Ok(());
+        }
+
         // Read a frame of audio from the ring buffer
         let read = audio_cons.pop_slice(&mut frame_buf);
         if read < frame_size {
@@ -1,6 +1,6 @@
-use std::sync::mpsc;
 use std::sync::Arc;
 
+use sovereign_core::events::EventBus;
 use sovereign_core::interfaces::OrchestratorEvent;
 use sovereign_db::surreal::{StorageMode, SurrealGraphDB};
 use sovereign_db::GraphDB;
@@ -42,11 +42,12 @@ async fn setup_db_with_docs() -> Arc<SurrealGraphDB> {
 #[tokio::test]
 async fn orchestrator_fails_without_model_files() {
     let db = setup_db_with_docs().await;
-    let (tx, _rx) = mpsc::channel::<OrchestratorEvent>();
+    let bus = EventBus::new();
 
     // Default AiConfig has empty model paths — should fail to load
     let config = sovereign_core::config::AiConfig::default();
-    let result = sovereign_ai::Orchestrator::new(config, db, tx).await;
+    let result =
+        sovereign_ai::Orchestrator::new(config, db, bus.publisher::<OrchestratorEvent>()).await;
 
     assert!(result.is_err(), "Orchestrator should fail when model files are missing");
 }
@@ -54,11 +55,11 @@ async fn orchestrator_fails_without_model_files() {
 #[tokio::test]
 async fn voice_pipeline_fails_without_model_files() {
     let config = sovereign_core::config::VoiceConfig::default();
-    let (tx, _rx) = mpsc::channel();
+    let bus = EventBus::new();
 
     let result = sovereign_ai::voice::VoicePipeline::spawn(
         config,
-        tx,
+        bus.publisher(),
         Box::new(|_| {}),
     );
 
@@ -0,0 +1,301 @@
+//! Per-document activity timeline: "what touched this document and when".
+//!
+//! Merges what the profile already records about a document into one
+//! newest-first list for the document panel's Activity view:
+//! - its creation and every commit in its version history,
+//! - commits signed by another paired device, shown as sync arrivals,
+//! - skill audit entries targeting it (reads, writes, refused attempts),
+//! - messages carrying it as an attachment, and recent messages in
+//!   conversations linked to its thread.
+//!
+//! Nothing new is recorded for the timeline; it is rebuilt on each request.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sovereign_db::schema::{Commit, Message, MessageDirection};
+use sovereign_db::{DbResult, GraphDB};
+use sovereign_skills::SkillAuditEntry;
+
+/// Most entries one timeline returns.
+pub const MAX_ENTRIES: usize = 200;
+
+/// Messages taken from each conversation linked to the document's thread.
+const MESSAGES_PER_CONVERSATION: u32 = 20;
+
+/// Longest message excerpt shown in an entry, in characters.
+const EXCERPT_CHARS: usize = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    Created,
+    Commit,
+    Sync,
+    Audit,
+    Message,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActivityEntry {
+    pub at: DateTime<Utc>,
+    pub kind: ActivityKind,
+    pub summary: String,
+    /// Device, skill or correspondent behind the entry, when known.
+    pub actor: Option<String>,
+    /// The commit or message the entry stands for, to open it from the timeline.
+    pub ref_id: Option<String>,
+}
+
+/// What the timeline needs beyond the database.
+pub struct ActivityContext<'a> {
+    /// This device's signer id; commits signed by any other device are
+    /// shown as synced in.
+    pub local_device: Option<&'a str>,
+    /// Paired devices' names by signer id.
+    pub device_names: &'a HashMap<String, String>,
+    /// Recent skill audit entries, in any order.
+    pub skill_audit: &'a [SkillAuditEntry],
+    /// The signer id of a commit, if it carries a device signature.
+    pub signer: fn(&Commit) -> Option<String>,
+}
+
+/// The activity of `doc_id`, newest first, at most [`MAX_ENTRIES`].
+pub async fn document_activity(
+    db: &dyn GraphDB,
+    doc_id: &str,
+    ctx: &ActivityContext<'_>,
+) -> DbResult<Vec<ActivityEntry>> {
+    let doc = db.get_document(doc_id).await?;
+    let mut entries = vec![ActivityEntry {
+        at: doc.created_at,
+        kind: ActivityKind::Created,
+        summary: format!("Created “{}”", doc.title),
+        actor: None,
+        ref_id: None,
+    }];
+
+    for commit in db.list_document_commits(doc_id).await? {
+        entries.push(commit_entry(commit, ctx));
+    }
+
+    for audit in ctx
+        .skill_audit
+        .iter()
+        .filter(|e| e.target.as_deref() == Some(doc_id))
+    {
+        let summary = if audit.allowed {
+            audit.operation.replace('_', " ")
+        } else {
+            format!("{} (refused)", audit.operation.replace('_', " "))
+        };
+        entries.push(ActivityEntry {
+            at: audit.timestamp,
+            kind: ActivityKind::Audit,
+            summary,
+            actor: Some(audit.skill.clone()),
+            ref_id: None,
+        });
+    }
+
+    let mut seen = HashSet::new();
+    for message in related_messages(db, doc_id, &doc.thread_id).await? {
+        let Some(id) = message.id_string() else {
+            continue;
+        };
+        if seen.insert(id.clone()) {
+            entries.push(message_entry(message, id));
+        }
+    }
+
+    entries.sort_by(|a, b| b.at.cmp(&a.at));
+    entries.truncate(MAX_ENTRIES);
+    Ok(entries)
+}
+
+fn commit_entry(commit: Commit, ctx: &ActivityContext<'_>) -> ActivityEntry {
+    let signer = (ctx.signer)(&commit);
+    let synced = matches!(
+        (&signer, ctx.local_device),
+        (Some(s), Some(local)) if s != local
+    );
+    let actor = signer.map(|s| ctx.device_names.get(&s).cloned().unwrap_or(s));
+    let (kind, summary) = if synced {
+        (ActivityKind::Sync, format!("Synced in: {}", commit.message))
+    } else {
+        (ActivityKind::Commit, commit.message)
+    };
+    ActivityEntry {
+        at: commit.timestamp,
+        kind,
+        summary,
+        actor,
+        ref_id: commit.id.as_ref().map(sovereign_db::schema::thing_to_raw),
+    }
+}
+
+/// Messages that carry the document, then recent ones from conversations
+/// linked to its thread. Deleted messages are left out.
+async fn related_messages(
+    db: &dyn GraphDB,
+    doc_id: &str,
+    thread_id: &str,
+) -> DbResult<Vec<Message>> {
+    let mut related: Vec<Message> = db
+        .list_all_messages()
+        .await?
+        .into_iter()
+        .filter(|m| m.attachment_doc_ids.iter().any(|d| d == doc_id))
+        .collect();
+    for conv in db.list_conversations(None).await? {
+        if conv.deleted_at.is_some() || conv.linked_thread_id.as_deref() != Some(thread_id) {
+            continue;
+        }
+        let Some(conv_id) = conv.id_string() else {
+            continue;
+        };
+        related.extend(
+            db.list_messages(&conv_id, None, MESSAGES_PER_CONVERSATION)
+                .await?,
+        );
+    }
+    related.retain(|m| m.deleted_at.is_none());
+    Ok(related)
+}
+
+fn message_entry(message: Message, id: String) -> ActivityEntry {
+    let text = message
+        .subject
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| message.body.lines().next().unwrap_or(""));
+    let mut excerpt: String = text.trim().chars().take(EXCERPT_CHARS).collect();
+    if text.trim().chars().count() > EXCERPT_CHARS {
+        excerpt.push('…');
+    }
+    let (verb, at) = match message.direction {
+        MessageDirection::Inbound => ("Received", message.received_at.unwrap_or(message.sent_at)),
+        MessageDirection::Outbound => ("Sent", message.sent_at),
+    };
+    let attached = if message.attachment_doc_ids.is_empty() {
+        ""
+    } else {
+        " (attached)"
+    };
+    ActivityEntry {
+        at,
+        kind: ActivityKind::Message,
+        summary: format!("{verb}{attached}: {excerpt}"),
+        actor: Some(message.from_contact_id),
+        ref_id: Some(id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_db::mock::MockGraphDB;
+    use sovereign_db::schema::{ChannelType, Conversation, Document};
+    use sovereign_skills::Capability;
+
+    fn no_signer(_: &Commit) -> Option<String> {
+        None
+    }
+
+    fn signed_by_laptop(_: &Commit) -> Option<String> {
+        Some("peer-laptop".into())
+    }
+
+    fn audit(target: &str, allowed: bool) -> SkillAuditEntry {
+        SkillAuditEntry {
+            timestamp: Utc::now(),
+            skill: "word-count".into(),
+            capability: Capability::ReadDocument,
+            operation: "get_document".into(),
+            target: Some(target.into()),
+            allowed,
+        }
+    }
+
+    #[tokio::test]
+    async fn merges_commits_audit_and_messages_newest_first() {
+        let db = MockGraphDB::new();
+        let doc = Document::new("Budget".into(), "thread:t".into(), true);
+        let doc_id = db.create_document(doc).await.unwrap().id_string().unwrap();
+        db.commit_document(&doc_id, "First draft").await.unwrap();
+
+        let conv = Conversation::new("Finance".into(), ChannelType::Email, vec![]);
+        let conv_id = db
+            .create_conversation(conv)
+            .await
+            .unwrap()
+            .id_string()
+            .unwrap();
+        let mut mail = Message::new(
+            conv_id,
+            ChannelType::Email,
+            MessageDirection::Inbound,
+            "contact:ann".into(),
+            vec![],
+            "Here are the numbers\nmore".into(),
+        );
+        mail.attachment_doc_ids = vec![doc_id.clone()];
+        db.create_message(mail).await.unwrap();
+
+        let names = HashMap::new();
+        let skill_audit = [audit(&doc_id, false), audit("document:other", true)];
+        let ctx = ActivityContext {
+            local_device: None,
+            device_names: &names,
+            skill_audit: &skill_audit,
+            signer: no_signer,
+        };
+        let entries = document_activity(&db, &doc_id, &ctx).await.unwrap();
+
+        let kinds: Vec<_> = entries.iter().map(|e| e.kind).collect();
+        assert_eq!(kinds.len(), 4);
+        for kind in [
+            ActivityKind::Created,
+            ActivityKind::Commit,
+            ActivityKind::Audit,
+            ActivityKind::Message,
+        ] {
+            assert!(kinds.contains(&kind), "missing {kind:?}");
+        }
+        assert!(entries.windows(2).all(|w| w[0].at >= w[1].at));
+        let refused = entries
+            .iter()
+            .find(|e| e.kind == ActivityKind::Audit)
+            .unwrap();
+        assert_eq!(refused.summary, "get document (refused)");
+        let message = entries
+            .iter()
+            .find(|e| e.kind == ActivityKind::Message)
+            .unwrap();
+        assert_eq!(message.summary, "Received (attached): Here are the numbers");
+    }
+
+    #[tokio::test]
+    async fn commits_from_another_device_show_as_synced() {
+        let db = MockGraphDB::new();
+        let doc = Document::new("Plan".into(), "thread:t".into(), true);
+        let doc_id = db.create_document(doc).await.unwrap().id_string().unwrap();
+        db.commit_document(&doc_id, "Edit on laptop").await.unwrap();
+
+        let names = HashMap::from([("peer-laptop".to_string(), "Laptop".to_string())]);
+        let ctx = ActivityContext {
+            local_device: Some("peer-desktop"),
+            device_names: &names,
+            skill_audit: &[],
+            signer: signed_by_laptop,
+        };
+        let entries = document_activity(&db, &doc_id, &ctx).await.unwrap();
+        let sync = entries
+            .iter()
+            .find(|e| e.kind == ActivityKind::Sync)
+            .unwrap();
+        assert_eq!(sync.summary, "Synced in: Edit on laptop");
+        assert_eq!(sync.actor.as_deref(), Some("Laptop"));
+    }
+}
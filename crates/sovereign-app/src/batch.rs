@@ -0,0 +1,608 @@
+//! `sovereign batch ops.yaml`: apply a list of operations as one unit.
+//!
+//! The file is a YAML list with one operation per entry:
+//!
+//! ```yaml
+//! - create_thread: { name: Research, description: Papers and notes }
+//! - create_doc: { ref: plan, title: Q3 plan, thread: Research, body: "First draft" }
+//! - relate: { from: plan, to: Budget, type: Supports, strength: 0.7 }
+//! - tag: { doc: plan, tags: [draft, q3] }
+//! - move: { doc: Old notes, thread: Research }
+//! ```
+//!
+//! A document is named by the `ref` (or title) of one created earlier in
+//! the file, or as elsewhere on the CLI by id, id prefix or title; here an
+//! ambiguous name is an error, never a prompt. A thread is named by name or
+//! id, and `create_thread` reuses a thread that already has its name, so a
+//! setup file can be run again.
+//!
+//! Every name is resolved before anything is written, so a mistake fails
+//! the whole file up front; `--dry-run` stops there and prints the plan.
+//! The database has no multi-statement transactions, so if a write fails
+//! part-way the writes already made are undone in reverse order.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use sovereign_core::config::AppConfig;
+use sovereign_core::content::{hashtags, ContentFields};
+use sovereign_db::schema::{Document, RelationType, Thread};
+use sovereign_db::GraphDB;
+
+use crate::doc_ref;
+use crate::setup::create_db;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+enum Op {
+    CreateThread {
+        name: String,
+        #[serde(default)]
+        description: String,
+    },
+    CreateDoc {
+        #[serde(rename = "ref")]
+        reference: Option<String>,
+        title: String,
+        thread: String,
+        #[serde(default)]
+        body: String,
+    },
+    Relate {
+        from: String,
+        to: String,
+        #[serde(rename = "type", default = "default_relation")]
+        relation: String,
+        #[serde(default = "default_strength")]
+        strength: f32,
+    },
+    Tag {
+        doc: String,
+        tags: Vec<String>,
+    },
+    Move {
+        doc: String,
+        thread: String,
+    },
+}
+
+fn default_relation() -> String {
+    "References".into()
+}
+
+/// Same default as `add-relationship`.
+fn default_strength() -> f32 {
+    0.5
+}
+
+/// A document or thread that exists already, or one created by an earlier
+/// step (its index in the plan).
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    Existing(String),
+    New(usize),
+}
+
+#[derive(Debug, PartialEq)]
+enum Step {
+    CreateThread {
+        name: String,
+        description: String,
+    },
+    CreateDoc {
+        title: String,
+        thread: Target,
+        body: String,
+    },
+    Relate {
+        from: Target,
+        to: Target,
+        relation: RelationType,
+        strength: f32,
+    },
+    Tag {
+        doc: Target,
+        tags: Vec<String>,
+    },
+    Move {
+        doc: Target,
+        thread: Target,
+    },
+}
+
+/// One operation checked against the workspace, described for the user.
+/// `step` is `None` when there is nothing to write (a thread that exists).
+#[derive(Debug)]
+struct Planned {
+    summary: String,
+    step: Option<Step>,
+}
+
+pub async fn run(config: &AppConfig, file: PathBuf, dry_run: bool) -> Result<()> {
+    let text =
+        std::fs::read_to_string(&file).with_context(|| format!("reading {}", file.display()))?;
+    let ops: Vec<Op> =
+        serde_yml::from_str(&text).with_context(|| format!("parsing {}", file.display()))?;
+
+    let db = create_db(config).await?;
+    let docs = db.list_documents(None).await?;
+    let threads = db.list_threads().await?;
+    let planned = plan(ops, &docs, &threads)?;
+
+    for (i, p) in planned.iter().enumerate() {
+        println!("{:>3}. {}", i + 1, p.summary);
+    }
+    if dry_run {
+        println!("Dry run: nothing was written.");
+        return Ok(());
+    }
+    let writes = apply(&db, &planned).await?;
+    println!("Applied {} operations ({writes} writes).", planned.len());
+    Ok(())
+}
+
+/// Resolve every name in `ops` against the workspace and each other.
+fn plan(ops: Vec<Op>, docs: &[Document], threads: &[Thread]) -> Result<Vec<Planned>> {
+    let mut planned: Vec<Planned> = Vec::with_capacity(ops.len());
+    // Names made by this file, lowercased, to the step that makes them.
+    let mut new_threads: HashMap<String, Target> = HashMap::new();
+    let mut new_docs: HashMap<String, usize> = HashMap::new();
+
+    for (i, op) in ops.into_iter().enumerate() {
+        let at = |e: anyhow::Error| e.context(format!("operation {}", i + 1));
+        let (summary, step) = match op {
+            Op::CreateThread { name, description } => {
+                if name.trim().is_empty() {
+                    return Err(at(anyhow::anyhow!("a thread needs a name")));
+                }
+                let key = name.to_lowercase();
+                if new_threads.contains_key(&key) {
+                    return Err(at(anyhow::anyhow!("thread '{name}' is created twice")));
+                }
+                match threads.iter().find(|t| t.name.to_lowercase() == key) {
+                    Some(existing) => {
+                        let id = existing.id_string().unwrap_or_default();
+                        new_threads.insert(key, Target::Existing(id));
+                        (format!("use existing thread '{name}'"), None)
+                    }
+                    None => {
+                        new_threads.insert(key, Target::New(i));
+                        (
+                            format!("create thread '{name}'"),
+                            Some(Step::CreateThread { name, description }),
+                        )
+                    }
+                }
+            }
+            Op::CreateDoc {
+                reference,
+                title,
+                thread,
+                body,
+            } => {
+                let (thread, thread_name) =
+                    resolve_thread(&thread, threads, &new_threads).map_err(at)?;
+                for name in reference.iter().chain([&title]) {
+                    new_docs.entry(name.to_lowercase()).or_insert(i);
+                }
+                (
+                    format!("create document '{title}' in {thread_name}"),
+                    Some(Step::CreateDoc {
+                        title,
+                        thread,
+                        body,
+                    }),
+                )
+            }
+            Op::Relate {
+                from,
+                to,
+                relation,
+                strength,
+            } => {
+                if !(0.0..=1.0).contains(&strength) {
+                    return Err(at(anyhow::anyhow!(
+                        "strength must be between 0.0 and 1.0, got {strength}"
+                    )));
+                }
+                let relation: RelationType = relation
+                    .parse()
+                    .map_err(|e: String| at(anyhow::anyhow!(e)))?;
+                let (from, from_title) =
+                    resolve_doc(&from, docs, &new_docs, &planned).map_err(at)?;
+                let (to, to_title) = resolve_doc(&to, docs, &new_docs, &planned).map_err(at)?;
+                (
+                    format!("link '{from_title}' to '{to_title}' ({relation}, {strength:.2})"),
+                    Some(Step::Relate {
+                        from,
+                        to,
+                        relation,
+                        strength,
+                    }),
+                )
+            }
+            Op::Tag { doc, tags } => {
+                let (doc, title) = resolve_doc(&doc, docs, &new_docs, &planned).map_err(at)?;
+                let tags = tags
+                    .iter()
+                    .map(|t| normalize_tag(t))
+                    .collect::<Result<Vec<_>>>()
+                    .map_err(at)?;
+                let shown: Vec<String> = tags.iter().map(|t| format!("#{t}")).collect();
+                (
+                    format!("tag '{title}' {}", shown.join(" ")),
+                    Some(Step::Tag { doc, tags }),
+                )
+            }
+            Op::Move { doc, thread } => {
+                let (doc, title) = resolve_doc(&doc, docs, &new_docs, &planned).map_err(at)?;
+                let (thread, thread_name) =
+                    resolve_thread(&thread, threads, &new_threads).map_err(at)?;
+                (
+                    format!("move '{title}' to {thread_name}"),
+                    Some(Step::Move { doc, thread }),
+                )
+            }
+        };
+        planned.push(Planned { summary, step });
+    }
+    Ok(planned)
+}
+
+/// A thread by name (made by this file or existing) or by id, with how
+/// to show it.
+fn resolve_thread(
+    name: &str,
+    threads: &[Thread],
+    new_threads: &HashMap<String, Target>,
+) -> Result<(Target, String)> {
+    if let Some(target) = new_threads.get(&name.to_lowercase()) {
+        return Ok((target.clone(), format!("'{name}'")));
+    }
+    threads
+        .iter()
+        .find(|t| t.name.eq_ignore_ascii_case(name) || t.id_string().as_deref() == Some(name))
+        .map(|t| {
+            (
+                Target::Existing(t.id_string().unwrap_or_default()),
+                format!("'{}'", t.name),
+            )
+        })
+        .with_context(|| format!("no thread '{name}' (add a create_thread before it)"))
+}
+
+/// A document by a name this file gave it, or as the CLI resolves it.
+fn resolve_doc(
+    reference: &str,
+    docs: &[Document],
+    new_docs: &HashMap<String, usize>,
+    planned: &[Planned],
+) -> Result<(Target, String)> {
+    if let Some(&step) = new_docs.get(&reference.to_lowercase()) {
+        let title = match &planned[step].step {
+            Some(Step::CreateDoc { title, .. }) => title.clone(),
+            _ => reference.to_string(),
+        };
+        return Ok((Target::New(step), title));
+    }
+    match doc_ref::candidates(docs, reference.trim()).as_slice() {
+        [] => bail!("no document matches '{reference}'"),
+        [only] => Ok((
+            Target::Existing(only.id_string().unwrap_or_default()),
+            only.title.clone(),
+        )),
+        matches => bail!(
+            "'{reference}' matches {} documents; name one by id:\n{}",
+            matches.len(),
+            doc_ref::listing(matches)
+        ),
+    }
+}
+
+/// `#Draft` or `Draft` → `draft`, by the same rule the tag index uses.
+pub(crate) fn normalize_tag(tag: &str) -> Result<String> {
+    let bare = tag.trim().trim_start_matches('#');
+    match hashtags(&format!("#{bare}"), 1).as_slice() {
+        [name] if name.len() == bare.len() => Ok(name.clone()),
+        _ => bail!("'{tag}' is not a tag (letters, digits, '-' and '_' only)"),
+    }
+}
+
+/// Append `tags` the body doesn't have yet, on its closing line of tags
+/// if it ends with one.
+pub(crate) fn add_tags(body: &str, tags: &[String]) -> String {
+    let present = hashtags(body, usize::MAX);
+    let missing: Vec<String> = tags
+        .iter()
+        .filter(|t| !present.contains(t))
+        .map(|t| format!("#{t}"))
+        .collect();
+    if missing.is_empty() {
+        return body.to_string();
+    }
+    let trimmed = body.trim_end();
+    let last_line = trimmed.lines().last().unwrap_or_default();
+    let ends_with_tags = !last_line.is_empty()
+        && last_line
+            .split_whitespace()
+            .all(|w| w.starts_with('#') && hashtags(w, 1).len() == 1);
+    let sep = match (trimmed.is_empty(), ends_with_tags) {
+        (true, _) => "",
+        (false, true) => " ",
+        (false, false) => "\n\n",
+    };
+    format!("{trimmed}{sep}{}", missing.join(" "))
+}
+
+/// A write already made, and how to take it back.
+enum Undo {
+    DeleteThread(String),
+    DeleteDoc(String),
+    DeleteRelationship(String),
+    RestoreContent { doc: String, content: String },
+    MoveBack { doc: String, thread: String },
+}
+
+/// Carry out the plan, undoing it all if a write fails. Returns how many
+/// writes were made.
+async fn apply(db: &dyn GraphDB, planned: &[Planned]) -> Result<usize> {
+    let mut made: HashMap<usize, String> = HashMap::new();
+    let mut undo: Vec<Undo> = Vec::new();
+    for (i, p) in planned.iter().enumerate() {
+        let Some(step) = &p.step else { continue };
+        if let Err(e) = apply_step(db, i, step, &mut made, &mut undo).await {
+            let undone = undo.len();
+            let leftovers = roll_back(db, undo).await;
+            let mut msg = format!("operation {} ({}) failed: {e:#}", i + 1, p.summary);
+            if leftovers.is_empty() {
+                msg.push_str(&format!("; {undone} earlier writes were undone"));
+            } else {
+                msg.push_str(&format!(
+                    "; undoing earlier writes also failed, fix these by hand:\n  {}",
+                    leftovers.join("\n  ")
+                ));
+            }
+            bail!(msg);
+        }
+    }
+    Ok(undo.len())
+}
+
+async fn apply_step(
+    db: &dyn GraphDB,
+    index: usize,
+    step: &Step,
+    made: &mut HashMap<usize, String>,
+    undo: &mut Vec<Undo>,
+) -> Result<()> {
+    let id_of = |target: &Target, made: &HashMap<usize, String>| -> Result<String> {
+        match target {
+            Target::Existing(id) => Ok(id.clone()),
+            Target::New(step) => made
+                .get(step)
+                .cloned()
+                .context("an earlier operation didn't produce an id"),
+        }
+    };
+    match step {
+        Step::CreateThread { name, description } => {
+            let created = db
+                .create_thread(Thread::new(name.clone(), description.clone()))
+                .await?;
+            let id = created.id_string().context("thread missing ID")?;
+            undo.push(Undo::DeleteThread(id.clone()));
+            made.insert(index, id);
+        }
+        Step::CreateDoc {
+            title,
+            thread,
+            body,
+        } => {
+            let mut doc = Document::new(title.clone(), id_of(thread, made)?, true);
+            doc.content = ContentFields {
+                body: body.clone(),
+                ..Default::default()
+            }
+            .serialize();
+            let created = db.create_document(doc).await?;
+            let id = created.id_string().context("document missing ID")?;
+            undo.push(Undo::DeleteDoc(id.clone()));
+            made.insert(index, id);
+        }
+        Step::Relate {
+            from,
+            to,
+            relation,
+            strength,
+        } => {
+            let rel = db
+                .create_relationship(
+                    &id_of(from, made)?,
+                    &id_of(to, made)?,
+                    relation.clone(),
+                    *strength,
+                )
+                .await?;
+            if let Some(id) = rel.id_string() {
+                undo.push(Undo::DeleteRelationship(id));
+            }
+        }
+        Step::Tag { doc, tags } => {
+            let id = id_of(doc, made)?;
+            let current = db.get_document(&id).await?;
+            let mut fields = ContentFields::parse(&current.content);
+            let tagged = add_tags(&fields.body, tags);
+            if tagged != fields.body {
+                fields.body = tagged;
+                db.update_document(&id, None, Some(&fields.serialize()))
+                    .await?;
+                undo.push(Undo::RestoreContent {
+                    doc: id,
+                    content: current.content,
+                });
+            }
+        }
+        Step::Move { doc, thread } => {
+            let id = id_of(doc, made)?;
+            let current = db.get_document(&id).await?;
+            let thread = id_of(thread, made)?;
+            if current.thread_id != thread {
+                db.move_document_to_thread(&id, &thread).await?;
+                undo.push(Undo::MoveBack {
+                    doc: id,
+                    thread: current.thread_id,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Undo writes newest first. Returns the ones that couldn't be undone.
+async fn roll_back(db: &dyn GraphDB, undo: Vec<Undo>) -> Vec<String> {
+    let mut failed = Vec::new();
+    for u in undo.into_iter().rev() {
+        let (what, result) = match u {
+            Undo::DeleteThread(id) => (format!("delete thread {id}"), db.delete_thread(&id).await),
+            Undo::DeleteDoc(id) => (
+                format!("delete document {id}"),
+                db.delete_document(&id).await,
+            ),
+            Undo::DeleteRelationship(id) => (
+                format!("delete relationship {id}"),
+                db.delete_relationship(&id).await,
+            ),
+            Undo::RestoreContent { doc, content } => (
+                format!("restore the content of {doc}"),
+                db.update_document(&doc, None, Some(&content))
+                    .await
+                    .map(|_| ()),
+            ),
+            Undo::MoveBack { doc, thread } => (
+                format!("move {doc} back to {thread}"),
+                db.move_document_to_thread(&doc, &thread).await.map(|_| ()),
+            ),
+        };
+        if let Err(e) = result {
+            failed.push(format!("{what}: {e}"));
+        }
+    }
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_db::mock::MockGraphDB;
+
+    const SETUP: &str = r##"
+- create_thread: { name: Research, description: Papers }
+- create_doc: { ref: plan, title: Q3 plan, thread: research, body: "First draft" }
+- create_doc: { title: Budget, thread: Research }
+- relate: { from: plan, to: budget, type: Supports, strength: 0.7 }
+- tag: { doc: Q3 plan, tags: ["#Draft", q3] }
+"##;
+
+    #[test]
+    fn plans_resolve_names_made_earlier_in_the_file() {
+        let ops: Vec<Op> = serde_yml::from_str(SETUP).unwrap();
+        let planned = plan(ops, &[], &[]).unwrap();
+        assert_eq!(planned.len(), 5);
+        assert_eq!(
+            planned[3].summary,
+            "link 'Q3 plan' to 'Budget' (supports, 0.70)"
+        );
+        assert_eq!(
+            planned[4].step,
+            Some(Step::Tag {
+                doc: Target::New(1),
+                tags: vec!["draft".into(), "q3".into()],
+            })
+        );
+    }
+
+    #[test]
+    fn bad_names_fail_before_anything_runs() {
+        let ops: Vec<Op> =
+            serde_yml::from_str("- create_doc: { title: A, thread: Nowhere }").unwrap();
+        let err = plan(ops, &[], &[]).unwrap_err();
+        assert!(format!("{err:#}").contains("operation 1: no thread 'Nowhere'"));
+
+        let ops: Vec<Op> = serde_yml::from_str(
+            "- create_thread: { name: T }\n\
+             - create_doc: { title: A, thread: T }\n\
+             - tag: { doc: A, tags: [\"not a tag\"] }\n",
+        )
+        .unwrap();
+        let err = plan(ops, &[], &[]).unwrap_err();
+        assert!(format!("{err:#}").contains("operation 3: 'not a tag' is not a tag"));
+        assert!(serde_yml::from_str::<Vec<Op>>("- explode: {}").is_err());
+    }
+
+    #[test]
+    fn tags_go_on_a_closing_tag_line() {
+        let tags = vec!["q3".to_string(), "draft".to_string()];
+        assert_eq!(add_tags("Notes\n\n#draft\n", &tags), "Notes\n\n#draft #q3");
+        assert_eq!(add_tags("Notes", &tags), "Notes\n\n#q3 #draft");
+        assert_eq!(add_tags("", &tags), "#q3 #draft");
+        assert_eq!(add_tags("#q3 #draft", &tags), "#q3 #draft");
+    }
+
+    #[tokio::test]
+    async fn applies_the_plan_and_reruns_reuse_the_thread() {
+        let db = MockGraphDB::new();
+        let ops: Vec<Op> = serde_yml::from_str(SETUP).unwrap();
+        let planned = plan(ops, &[], &[]).unwrap();
+        assert_eq!(apply(&db, &planned).await.unwrap(), 5);
+
+        let docs = db.list_documents(None).await.unwrap();
+        assert_eq!(docs.len(), 2);
+        let plan_doc = docs.iter().find(|d| d.title == "Q3 plan").unwrap();
+        assert_eq!(
+            ContentFields::parse(&plan_doc.content).body,
+            "First draft\n\n#draft #q3"
+        );
+        assert_eq!(db.list_all_relationships().await.unwrap().len(), 1);
+
+        let threads = db.list_threads().await.unwrap();
+        let ops: Vec<Op> = serde_yml::from_str(SETUP).unwrap();
+        let again = plan(ops, &docs, &threads).unwrap();
+        assert_eq!(again[0].summary, "use existing thread 'Research'");
+        assert!(again[0].step.is_none());
+    }
+
+    #[tokio::test]
+    async fn a_failed_write_undoes_the_earlier_ones() {
+        let db = MockGraphDB::new();
+        let planned = vec![
+            Planned {
+                summary: "create thread".into(),
+                step: Some(Step::CreateThread {
+                    name: "T".into(),
+                    description: String::new(),
+                }),
+            },
+            Planned {
+                summary: "create document".into(),
+                step: Some(Step::CreateDoc {
+                    title: "A".into(),
+                    thread: Target::New(0),
+                    body: String::new(),
+                }),
+            },
+            Planned {
+                summary: "move".into(),
+                step: Some(Step::Move {
+                    doc: Target::Existing("document:missing".into()),
+                    thread: Target::New(0),
+                }),
+            },
+        ];
+        let err = apply(&db, &planned).await.unwrap_err();
+        assert!(err.to_string().contains("2 earlier writes were undone"));
+        assert!(db.list_documents(None).await.unwrap().is_empty());
+        assert!(db.list_threads().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,428 @@
+//! Bulk edit: one change applied to many documents as a single unit.
+//!
+//! The canvas selection (shift-click, or every card of a thread) sends a
+//! [`BulkEdit`] with the ids it holds: tags to add or remove, a thread to
+//! move to, a document type, favorite and reading-queue flags, or archive
+//! (a soft delete, restorable from the trash).
+//!
+//! As in `sovereign batch`, everything is checked before anything is
+//! written, and a write failing part-way undoes the earlier ones. Each
+//! write that changed something leaves a [`Revert`]; the list comes back
+//! with the report so the frontend can undo the whole edit as one entry.
+
+use std::collections::HashSet;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sovereign_core::content::ContentFields;
+use sovereign_db::schema::DocType;
+use sovereign_db::GraphDB;
+
+use crate::batch::{add_tags, normalize_tag};
+
+/// The change to make. Unset fields are left alone.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct BulkEdit {
+    #[serde(default)]
+    pub add_tags: Vec<String>,
+    #[serde(default)]
+    pub remove_tags: Vec<String>,
+    #[serde(default)]
+    pub thread_id: Option<String>,
+    #[serde(default)]
+    pub doc_type: Option<DocType>,
+    #[serde(default)]
+    pub favorite: Option<bool>,
+    #[serde(default)]
+    pub read_later: Option<bool>,
+    #[serde(default)]
+    pub archive: bool,
+}
+
+impl BulkEdit {
+    fn is_empty(&self) -> bool {
+        self.add_tags.is_empty()
+            && self.remove_tags.is_empty()
+            && self.thread_id.is_none()
+            && self.doc_type.is_none()
+            && self.favorite.is_none()
+            && self.read_later.is_none()
+            && !self.archive
+    }
+}
+
+/// How to take back one write of a bulk edit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Revert {
+    Content {
+        doc: String,
+        content: String,
+    },
+    Thread {
+        doc: String,
+        thread: String,
+    },
+    DocType {
+        doc: String,
+        doc_type: DocType,
+    },
+    Favorite {
+        doc: String,
+        favorite: bool,
+    },
+    ReadLater {
+        doc: String,
+        at: Option<DateTime<Utc>>,
+    },
+    Unarchive {
+        doc: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkEditReport {
+    /// Documents at least one write changed.
+    pub changed: usize,
+    /// The writes made, oldest first; hand to [`revert`] to undo the edit.
+    pub revert: Vec<Revert>,
+}
+
+/// Apply `edit` to every document in `doc_ids`, all or nothing.
+pub async fn apply(
+    db: &dyn GraphDB,
+    doc_ids: &[String],
+    edit: &BulkEdit,
+) -> Result<BulkEditReport> {
+    if edit.is_empty() {
+        bail!("nothing to change");
+    }
+    let add = normalize_all(&edit.add_tags)?;
+    let remove = normalize_all(&edit.remove_tags)?;
+    if let Some(tag) = add.iter().find(|t| remove.contains(t)) {
+        bail!("#{tag} is both added and removed");
+    }
+    if let Some(thread) = &edit.thread_id {
+        db.get_thread(thread)
+            .await
+            .with_context(|| format!("no thread {thread}"))?;
+    }
+
+    let mut seen = HashSet::new();
+    let mut docs = Vec::new();
+    for id in doc_ids.iter().filter(|id| seen.insert(id.as_str())) {
+        let doc = db
+            .get_document(id)
+            .await
+            .with_context(|| format!("no document {id}"))?;
+        if doc.deleted_at.is_some() {
+            bail!("'{}' is in the trash", doc.title);
+        }
+        docs.push((id.clone(), doc));
+    }
+    if docs.is_empty() {
+        bail!("no documents selected");
+    }
+
+    let mut made: Vec<Revert> = Vec::new();
+    let mut changed = 0;
+    for (id, doc) in docs {
+        let before = made.len();
+        if let Err(e) = apply_one(db, &id, doc, edit, &add, &remove, &mut made).await {
+            let undone = made.len();
+            let leftovers = roll_back(db, made).await;
+            let mut msg = format!("editing {id} failed: {e:#}");
+            if leftovers.is_empty() {
+                msg.push_str(&format!("; {undone} earlier writes were undone"));
+            } else {
+                msg.push_str(&format!(
+                    "; undoing earlier writes also failed: {}",
+                    leftovers.join("; ")
+                ));
+            }
+            bail!(msg);
+        }
+        if made.len() > before {
+            changed += 1;
+        }
+    }
+    Ok(BulkEditReport {
+        changed,
+        revert: made,
+    })
+}
+
+/// Undo a bulk edit from its [`BulkEditReport::revert`] list. Fails with
+/// the writes that couldn't be taken back, if any.
+pub async fn revert(db: &dyn GraphDB, steps: Vec<Revert>) -> Result<()> {
+    let leftovers = roll_back(db, steps).await;
+    if !leftovers.is_empty() {
+        bail!("could not undo: {}", leftovers.join("; "));
+    }
+    Ok(())
+}
+
+fn normalize_all(tags: &[String]) -> Result<Vec<String>> {
+    tags.iter()
+        .filter(|t| !t.trim().is_empty())
+        .map(|t| normalize_tag(t))
+        .collect()
+}
+
+async fn apply_one(
+    db: &dyn GraphDB,
+    id: &str,
+    doc: sovereign_db::schema::Document,
+    edit: &BulkEdit,
+    add: &[String],
+    remove: &[String],
+    made: &mut Vec<Revert>,
+) -> Result<()> {
+    if !add.is_empty() || !remove.is_empty() {
+        let mut fields = ContentFields::parse(&doc.content);
+        let body = add_tags(&remove_tags(&fields.body, remove), add);
+        if body != fields.body {
+            fields.body = body;
+            db.update_document(id, None, Some(&fields.serialize()))
+                .await?;
+            made.push(Revert::Content {
+                doc: id.to_string(),
+                content: doc.content.clone(),
+            });
+        }
+    }
+    if let Some(thread) = edit.thread_id.as_deref().filter(|t| *t != doc.thread_id) {
+        db.move_document_to_thread(id, thread).await?;
+        made.push(Revert::Thread {
+            doc: id.to_string(),
+            thread: doc.thread_id.clone(),
+        });
+    }
+    if let Some(doc_type) = edit.doc_type.filter(|t| *t != doc.doc_type) {
+        db.update_document_type(id, doc_type).await?;
+        made.push(Revert::DocType {
+            doc: id.to_string(),
+            doc_type: doc.doc_type,
+        });
+    }
+    if let Some(favorite) = edit.favorite.filter(|f| *f != doc.favorite) {
+        db.set_favorite(id, favorite, Utc::now()).await?;
+        made.push(Revert::Favorite {
+            doc: id.to_string(),
+            favorite: doc.favorite,
+        });
+    }
+    if let Some(queued) = edit.read_later.filter(|q| *q != doc.read_later.is_some()) {
+        db.set_read_later(id, queued.then(Utc::now)).await?;
+        made.push(Revert::ReadLater {
+            doc: id.to_string(),
+            at: doc.read_later,
+        });
+    }
+    if edit.archive {
+        db.soft_delete_document(id).await?;
+        made.push(Revert::Unarchive {
+            doc: id.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Undo writes newest first. Returns the ones that couldn't be undone.
+async fn roll_back(db: &dyn GraphDB, steps: Vec<Revert>) -> Vec<String> {
+    let mut failed = Vec::new();
+    for step in steps.into_iter().rev() {
+        let (what, result) = match step {
+            Revert::Content { doc, content } => (
+                format!("restore the content of {doc}"),
+                db.update_document(&doc, None, Some(&content))
+                    .await
+                    .map(|_| ()),
+            ),
+            Revert::Thread { doc, thread } => (
+                format!("move {doc} back to {thread}"),
+                db.move_document_to_thread(&doc, &thread).await.map(|_| ()),
+            ),
+            Revert::DocType { doc, doc_type } => (
+                format!("set {doc} back to {}", doc_type.as_str()),
+                db.update_document_type(&doc, doc_type).await,
+            ),
+            Revert::Favorite { doc, favorite } => (
+                format!("reset the favorite pin of {doc}"),
+                db.set_favorite(&doc, favorite, Utc::now()).await,
+            ),
+            Revert::ReadLater { doc, at } => (
+                format!("reset the reading queue flag of {doc}"),
+                db.set_read_later(&doc, at).await,
+            ),
+            Revert::Unarchive { doc } => (
+                format!("restore {doc} from the trash"),
+                db.restore_soft_deleted_document(&doc).await.map(|_| ()),
+            ),
+        };
+        if let Err(e) = result {
+            failed.push(format!("{what}: {e}"));
+        }
+    }
+    failed
+}
+
+/// Drop the `#tag` markers naming any of `tags` (lowercase, as
+/// [`normalize_tag`] gives them), and the lines left holding nothing else.
+pub(crate) fn remove_tags(body: &str, tags: &[String]) -> String {
+    let mut lines = Vec::new();
+    let mut changed = false;
+    for line in body.split('\n') {
+        let mut kept: Vec<&str> = Vec::new();
+        let mut dropped = false;
+        for word in line.split(' ') {
+            if let Some(rest) = word.strip_prefix('#') {
+                let end = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+                    .unwrap_or(rest.len());
+                if end > 0 && tags.contains(&rest[..end].to_lowercase()) {
+                    // Keep trailing punctuation: `#draft,` leaves `,`.
+                    if end < rest.len() {
+                        kept.push(&rest[end..]);
+                    }
+                    dropped = true;
+                    continue;
+                }
+            }
+            kept.push(word);
+        }
+        if !dropped {
+            lines.push(line.to_string());
+            continue;
+        }
+        changed = true;
+        let line = kept.join(" ");
+        if !line.trim().is_empty() {
+            lines.push(line);
+        }
+    }
+    if !changed {
+        return body.to_string();
+    }
+    lines.join("\n").trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_db::mock::MockGraphDB;
+    use sovereign_db::schema::{Document, Thread};
+
+    async fn doc(db: &MockGraphDB, title: &str, thread: &str, body: &str) -> String {
+        let mut doc = Document::new(title.into(), thread.into(), true);
+        doc.content = ContentFields {
+            body: body.into(),
+            ..Default::default()
+        }
+        .serialize();
+        db.create_document(doc).await.unwrap().id_string().unwrap()
+    }
+
+    fn body(doc: &sovereign_db::schema::Document) -> String {
+        ContentFields::parse(&doc.content).body
+    }
+
+    #[test]
+    fn removing_tags_keeps_the_rest_of_the_line() {
+        let text = "Plan #draft #q3\n\nbody\n\n#Draft #keep";
+        assert_eq!(
+            remove_tags(text, &["draft".into()]),
+            "Plan #q3\n\nbody\n\n#keep"
+        );
+        assert_eq!(remove_tags("notes\n\n#draft", &["draft".into()]), "notes");
+        assert_eq!(
+            remove_tags("# Heading #drafty", &["draft".into()]),
+            "# Heading #drafty"
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_to_every_document_and_reverts_as_one() {
+        let db = MockGraphDB::new();
+        let from = db
+            .create_thread(Thread::new("Inbox".into(), String::new()))
+            .await
+            .unwrap()
+            .id_string()
+            .unwrap();
+        let to = db
+            .create_thread(Thread::new("Research".into(), String::new()))
+            .await
+            .unwrap()
+            .id_string()
+            .unwrap();
+        let a = doc(&db, "A", &from, "alpha #old").await;
+        let b = doc(&db, "B", &from, "beta").await;
+
+        let edit = BulkEdit {
+            add_tags: vec!["#Paper".into()],
+            remove_tags: vec!["old".into()],
+            thread_id: Some(to.clone()),
+            favorite: Some(true),
+            ..Default::default()
+        };
+        let report = apply(&db, &[a.clone(), b.clone(), a.clone()], &edit)
+            .await
+            .unwrap();
+        assert_eq!(report.changed, 2);
+
+        let a_doc = db.get_document(&a).await.unwrap();
+        assert_eq!(body(&a_doc), "alpha\n\n#paper");
+        assert_eq!(a_doc.thread_id, to);
+        assert!(a_doc.favorite);
+        assert_eq!(body(&db.get_document(&b).await.unwrap()), "beta\n\n#paper");
+
+        revert(&db, report.revert).await.unwrap();
+        let a_doc = db.get_document(&a).await.unwrap();
+        assert_eq!(body(&a_doc), "alpha #old");
+        assert_eq!(a_doc.thread_id, from);
+        assert!(!a_doc.favorite);
+        assert_eq!(db.get_document(&b).await.unwrap().thread_id, from);
+    }
+
+    #[tokio::test]
+    async fn bad_input_fails_before_anything_is_written() {
+        let db = MockGraphDB::new();
+        let a = doc(&db, "A", "thread:t", "alpha").await;
+        let tag = BulkEdit {
+            add_tags: vec!["q3".into()],
+            ..Default::default()
+        };
+
+        assert!(apply(&db, &[a.clone(), "document:gone".into()], &tag)
+            .await
+            .is_err());
+        assert!(apply(&db, &[a.clone()], &BulkEdit::default())
+            .await
+            .is_err());
+        let both = BulkEdit {
+            add_tags: vec!["x".into()],
+            remove_tags: vec!["#X".into()],
+            ..Default::default()
+        };
+        assert!(apply(&db, &[a.clone()], &both).await.is_err());
+        assert_eq!(body(&db.get_document(&a).await.unwrap()), "alpha");
+    }
+
+    #[tokio::test]
+    async fn archive_moves_to_the_trash_and_back() {
+        let db = MockGraphDB::new();
+        let a = doc(&db, "A", "thread:t", "alpha").await;
+        let edit = BulkEdit {
+            archive: true,
+            ..Default::default()
+        };
+        let report = apply(&db, &[a.clone()], &edit).await.unwrap();
+        assert!(db.get_document(&a).await.unwrap().deleted_at.is_some());
+        assert!(apply(&db, &[a.clone()], &edit).await.is_err());
+
+        revert(&db, report.revert).await.unwrap();
+        assert!(db.get_document(&a).await.unwrap().deleted_at.is_none());
+    }
+}
@@ -0,0 +1,148 @@
+//! Canary phrases typed into the app while the session is unlocked.
+//!
+//! The phrases and their actions are chosen at onboarding and stored in
+//! `crypto/canary.store`, encrypted under the primary persona's KEK (a
+//! duress login can't read them, so it never arms them). Chat input is
+//! scanned before it reaches the orchestrator.
+
+use std::path::Path;
+
+use sovereign_crypto::canary::{CanaryAction, CanaryRule, CanarySet, CanaryStore};
+use sovereign_db::schema::{Document, Thread};
+use sovereign_db::GraphDB;
+use tauri::Emitter;
+
+use crate::err::ToStringErr;
+use crate::tauri_state::AppState;
+
+const STORE_FILE: &str = "canary.store";
+
+/// Encrypt and save the rules, and arm them for the current session.
+pub fn save_rules(
+    state: &AppState,
+    rules: &[CanaryRule],
+    kek: &[u8; sovereign_crypto::aead::KEY_SIZE],
+) -> Result<(), String> {
+    if rules.is_empty() {
+        return Ok(());
+    }
+    CanaryStore::encrypt_rules(rules, kek)
+        .and_then(|store| store.save(&store_path(&state.profile_dir)))
+        .str_err()?;
+    arm(state, rules);
+    Ok(())
+}
+
+/// Arm the stored rules at unlock. A missing store, or one the current
+/// persona's KEK can't open, leaves nothing armed.
+pub fn load(state: &AppState, kek: &[u8; sovereign_crypto::aead::KEY_SIZE]) {
+    let path = store_path(&state.profile_dir);
+    if !path.exists() {
+        return;
+    }
+    match CanaryStore::load(&path).and_then(|store| store.decrypt_rules(kek)) {
+        Ok(rules) => arm(state, &rules),
+        Err(_) => disarm(state),
+    }
+}
+
+pub fn disarm(state: &AppState) {
+    if let Ok(mut guard) = state.canary.lock() {
+        *guard = None;
+    }
+}
+
+fn arm(state: &AppState, rules: &[CanaryRule]) {
+    let set = CanarySet::new(rules);
+    if let Ok(mut guard) = state.canary.lock() {
+        *guard = (!set.is_empty()).then_some(set);
+    }
+}
+
+fn store_path(profile_dir: &Path) -> std::path::PathBuf {
+    profile_dir.join("crypto").join(STORE_FILE)
+}
+
+/// What a completed phrase did, for the caller to pass on.
+pub struct Tripped {
+    /// Event announcing the new session state (`session-locked`, ...).
+    pub event: Option<&'static str>,
+    /// The session is gone; the text must not be processed any further.
+    pub halt: bool,
+}
+
+/// Scan typed text and carry out the action of any phrase it completes.
+/// Returns `true` when the text must not be processed any further (the
+/// session is gone).
+pub async fn intercept(state: &AppState, app: &tauri::AppHandle, text: &str) -> bool {
+    let Some(tripped) = check(state, text).await else {
+        return false;
+    };
+    if let Some(event) = tripped.event {
+        let _ = app.emit(event, ());
+    }
+    tripped.halt
+}
+
+/// `intercept` without a window to tell: the daemon reports the event to
+/// its own subscribers.
+pub async fn check(state: &AppState, text: &str) -> Option<Tripped> {
+    let hit = match state.canary.lock() {
+        Ok(mut guard) => guard.as_mut().and_then(|set| set.feed_str(text)),
+        Err(_) => None,
+    };
+    let action = hit?;
+    let tripped = match action {
+        CanaryAction::Lockdown => {
+            state.lock().await;
+            Tripped { event: Some("session-locked"), halt: true }
+        }
+        CanaryAction::SilentAlert => {
+            if let Err(e) = record_alert(state.db.as_ref()).await {
+                tracing::warn!("canary alert document failed: {e}");
+            }
+            Tripped { event: None, halt: false }
+        }
+        CanaryAction::Decoy => {
+            if let Err(e) = state.enter_decoy().await {
+                tracing::warn!("decoy mode failed, locking instead: {e}");
+                state.lock().await;
+                return Some(Tripped { event: Some("session-locked"), halt: true });
+            }
+            Tripped { event: Some("decoy-mode"), halt: false }
+        }
+        CanaryAction::Wipe => {
+            // A failed wipe still locked the session.
+            let event = if crate::remote_wipe::execute(state, "canary phrase").await {
+                "device-wiped"
+            } else {
+                "session-locked"
+            };
+            Tripped { event: Some(event), halt: true }
+        }
+    };
+    Some(tripped)
+}
+
+/// A note in the first thread. It syncs to the paired
+/// devices like any other document, which is how the owner hears of it.
+async fn record_alert(db: &dyn GraphDB) -> anyhow::Result<()> {
+    let thread_id = match db.list_threads().await?.into_iter().find_map(|t| t.id_string()) {
+        Some(id) => id,
+        None => db
+            .create_thread(Thread::new("Notes".into(), String::new()))
+            .await?
+            .id_string()
+            .ok_or_else(|| anyhow::anyhow!("thread missing ID"))?,
+    };
+    let now = chrono::Utc::now();
+    let title = format!("Canary alert {}", now.format("%Y-%m-%d %H:%M"));
+    let mut doc = Document::new(title, thread_id, true);
+    doc.content = serde_json::json!({
+        "body": format!("A canary phrase was typed on this device at {}.", now.to_rfc3339()),
+        "images": [],
+    })
+    .to_string();
+    db.create_document(doc).await?;
+    Ok(())
+}
@@ -0,0 +1,135 @@
+//! `sovereign ask` and `sovereign chat`: the orchestrator from a terminal.
+//!
+//! Both go through the daemon (or a one-off backend when none is running),
+//! so a message gets the same canary check and action gating as in the
+//! app. Replies print as they arrive; an action waiting for confirmation
+//! is put to the user as a y/N prompt, and anything but yes rejects it.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+use sovereign_core::config::AppConfig;
+
+use crate::daemon_client::{Client, Incoming};
+
+/// How long to keep printing events once the chat request has returned:
+/// the reply can overtake the turn's last events through the fan-out.
+const TRAILING_EVENTS: Duration = Duration::from_millis(300);
+
+/// Ask one question and print the answer.
+pub async fn ask(config: &AppConfig, socket: Option<PathBuf>, question: String) -> Result<()> {
+    let mut client = Client::connect_or_start(config, socket).await?;
+    client.call("subscribe", Value::Null).await?;
+    turn(&mut client, &question).await
+}
+
+/// Chat until Ctrl-D or `/quit`.
+pub async fn chat(config: &AppConfig, socket: Option<PathBuf>) -> Result<()> {
+    let mut client = Client::connect_or_start(config, socket).await?;
+    client.call("subscribe", Value::Null).await?;
+    eprintln!("Chatting with Sovereign. Ctrl-D or /quit ends the session.");
+
+    while let Some(line) = read_line("> ").await? {
+        let message = line.trim();
+        if message.is_empty() {
+            continue;
+        }
+        if message == "/quit" {
+            break;
+        }
+        if let Err(e) = turn(&mut client, message).await {
+            eprintln!("error: {e:#}");
+            if client.is_closed() {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Send one message and handle what comes back until the orchestrator is
+/// done with it.
+async fn turn(client: &mut Client, message: &str) -> Result<()> {
+    let id = client.send("chat", json!({ "message": message })).await?;
+    let mut outcome: Option<Result<Value, String>> = None;
+    loop {
+        let incoming = if outcome.is_none() {
+            client.next().await?
+        } else {
+            match tokio::time::timeout(TRAILING_EVENTS, client.next()).await {
+                Ok(incoming) => incoming?,
+                Err(_) => break,
+            }
+        };
+        match incoming {
+            None if outcome.is_some() => break,
+            None => bail!("the daemon closed the connection"),
+            Some(Incoming::Reply { id: got, result }) if got == id => outcome = Some(result),
+            Some(Incoming::Reply { result: Err(e), .. }) => eprintln!("error: {e}"),
+            Some(Incoming::Reply { .. }) => {}
+            Some(Incoming::Event { event, payload }) => match event.as_str() {
+                "session-locked" => bail!("the session locked; unlock it again to continue"),
+                "action-proposed" => confirm(client, &payload).await?,
+                _ => print_event(&event, &payload),
+            },
+        }
+    }
+    match outcome {
+        Some(Err(e)) => bail!(e),
+        _ => Ok(()),
+    }
+}
+
+/// Put a proposed action to the user.
+async fn confirm(client: &mut Client, proposal: &Value) -> Result<()> {
+    println!(
+        "Sovereign wants to: {} ({})",
+        proposal["description"].as_str().unwrap_or_default(),
+        proposal["level"].as_str().unwrap_or_default()
+    );
+    let answer = read_line("Allow? [y/N] ").await?.unwrap_or_default();
+    if matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes") {
+        client.send("actions.approve", Value::Null).await?;
+    } else {
+        client
+            .send(
+                "actions.reject",
+                json!({ "reason": "Declined at the terminal" }),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+fn print_event(event: &str, payload: &Value) {
+    let field = |key: &'static str| payload[key].as_str().unwrap_or_default();
+    match event {
+        "chat-response" => println!("{}", field("text")),
+        "action-executed" if payload["success"] == false => {
+            eprintln!("{} failed", field("action"));
+        }
+        "action-rejected" => eprintln!("{} not done: {}", field("action"), field("reason")),
+        "document-created" => eprintln!("Created \"{}\" ({})", field("title"), field("doc_id")),
+        "injection-detected" => eprintln!(
+            "warning: content from {} looks like a prompt injection",
+            field("source")
+        ),
+        _ => return,
+    }
+    let _ = std::io::stdout().flush();
+}
+
+/// One line from stdin after `prompt`; `None` at end of input.
+async fn read_line(prompt: &'static str) -> Result<Option<String>> {
+    tokio::task::spawn_blocking(move || -> Result<Option<String>> {
+        print!("{prompt}");
+        std::io::stdout().flush()?;
+        let mut line = String::new();
+        let read = std::io::stdin().read_line(&mut line)?;
+        Ok((read > 0).then_some(line))
+    })
+    .await?
+}
@@ -9,6 +9,20 @@ pub struct Cli {
     #[arg(long)]
     pub config: Option<PathBuf>,
 
+    /// Log how long each database, model and sync operation takes
+    #[arg(long, global = true)]
+    pub trace_spans: bool,
+
+    /// Write span timings to FILE: a Chrome trace (chrome://tracing,
+    /// Perfetto), or folded stacks for flamegraphs if it ends in .folded
+    #[arg(long, global = true, value_name = "FILE")]
+    pub trace_profile: Option<PathBuf>,
+
+    /// Run as this user profile, creating it if it doesn't exist yet
+    /// (default: the profile opened last)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -30,6 +44,7 @@ pub enum Commands {
 
     /// Get a document by ID
     GetDoc {
+        /// Document id, a unique prefix of it, or the title
         #[arg(long)]
         id: String,
     },
@@ -42,6 +57,7 @@ pub enum Commands {
 
     /// Update a document
     UpdateDoc {
+        /// Document id, a unique prefix of it, or the title
         #[arg(long)]
         id: String,
         #[arg(long)]
@@ -52,6 +68,7 @@ pub enum Commands {
 
     /// Delete a document
     DeleteDoc {
+        /// Document id, a unique prefix of it, or the title
         #[arg(long)]
         id: String,
     },
@@ -67,7 +84,7 @@ pub enum Commands {
     /// List all threads
     ListThreads,
 
-    /// Add a relationship between two documents
+    /// Add a relationship between two documents (ids, id prefixes or titles)
     AddRelationship {
         #[arg(long)]
         from: String,
@@ -81,12 +98,14 @@ pub enum Commands {
 
     /// List relationships for a document
     ListRelationships {
+        /// Document id, a unique prefix of it, or the title
         #[arg(long)]
         doc_id: String,
     },
 
     /// Commit a document snapshot
     Commit {
+        /// Document id, a unique prefix of it, or the title
         #[arg(long)]
         doc_id: String,
         #[arg(long)]
@@ -95,41 +114,265 @@ pub enum Commands {
 
     /// List commits for a document
     ListCommits {
+        /// Document id, a unique prefix of it, or the title
+        #[arg(long)]
+        doc_id: String,
+    },
+
+    /// List, restore or purge deleted documents and threads
+    Trash {
+        #[command(subcommand)]
+        action: TrashAction,
+    },
+
+    /// Manage a document's version history
+    Commits {
+        #[command(subcommand)]
+        action: CommitsAction,
+    },
+
+    /// List skills or run one against a document
+    Skill {
+        #[command(subcommand)]
+        action: SkillAction,
+    },
+
+    /// Export a document with one of the export skills
+    Export {
+        /// Document id, a unique prefix of it, or the title
+        #[arg(long, required_unless_present = "all")]
+        doc_id: Option<String>,
+        /// html, docx, epub, pdf or txt
+        #[arg(long, default_value = "html")]
+        format: String,
+        /// Export the document's whole thread (epub only)
+        #[arg(long)]
+        thread: bool,
+        /// Export every thread and document as a workspace archive (.zip)
+        #[arg(long, conflicts_with_all = ["doc_id", "thread"])]
+        all: bool,
+        /// Encrypt the output as an age file (passphrase unless --recipient)
         #[arg(long)]
+        encrypt: bool,
+        /// age recipient (age1…) to encrypt to; repeatable
+        #[arg(long, requires = "encrypt")]
+        recipient: Vec<String>,
+        /// Output file or directory (default: current directory)
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Import a workspace archive made by `export --all`, decrypting it first
+    /// if it is an age file, or a folder of notes with --from
+    Import {
+        #[arg(long, required_unless_present = "from")]
+        input: Option<PathBuf>,
+        /// age identity file for bundles encrypted to a recipient
+        #[arg(long, requires = "input")]
+        identity: Option<PathBuf>,
+        /// Folder of notes to import instead of an archive
+        #[arg(long, conflicts_with = "input")]
+        from: Option<PathBuf>,
+        /// Thread for the folder's documents, created if no thread has that
+        /// name (default: the folder's name)
+        #[arg(long, requires = "from")]
+        thread: Option<String>,
+        /// Folder format: obsidian, plain or json
+        #[arg(long, default_value = "obsidian")]
+        format: String,
+    },
+
+    /// Export a thread as a portable bundle
+    Thread {
+        #[command(subcommand)]
+        action: ThreadAction,
+    },
+
+    /// Import a thread bundle made by `thread export` as a new thread
+    ImportThread {
+        /// Bundle file (.svt, or .svt.age if encrypted)
+        input: PathBuf,
+        /// age identity file for bundles encrypted to a recipient
+        #[arg(long)]
+        identity: Option<PathBuf>,
+    },
+
+    /// Package one document as a passphrase-sealed share (.svshare) that
+    /// opens in another instance or with `age`
+    Share {
+        /// Document ID
         doc_id: String,
+        /// Attachment to include, by file name (repeatable; none by default)
+        #[arg(long = "attach")]
+        attach: Vec<String>,
+        /// Days until the share can no longer be opened (0 = never)
+        #[arg(long, default_value_t = 7)]
+        expires_in: u32,
+        /// Output file or directory
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Also print the QR payload, when the share is small enough
+        #[arg(long)]
+        qr: bool,
+    },
+
+    /// Open a share made by `share` into the "Shared with me" thread
+    OpenShare {
+        /// Share file (.svshare)
+        #[arg(required_unless_present = "payload")]
+        input: Option<PathBuf>,
+        /// QR payload text (`sovereign-share:…`) instead of a file
+        #[arg(long, conflicts_with = "input")]
+        payload: Option<String>,
+    },
+
+    /// Take, list or roll back to snapshots of the whole workspace
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+
+    /// Apply a YAML list of operations (threads, documents, links, tags,
+    /// moves) all together, undoing them if one fails
+    Batch {
+        /// Operations file
+        file: PathBuf,
+        /// Check the file and print the plan without writing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Encrypt all existing plaintext documents (idempotent)
     #[cfg(feature = "encryption")]
     EncryptData,
 
-    /// Pair with another device on the local network
-    #[cfg(feature = "p2p")]
+    /// Pair a new device: prints the offer and code to enter on it, then waits
+    #[cfg(all(unix, feature = "p2p"))]
     PairDevice {
+        /// Daemon socket (default: ~/.sovereign/daemon.sock)
         #[arg(long)]
-        peer_id: String,
+        socket: Option<PathBuf>,
     },
 
     /// List paired devices
     #[cfg(feature = "p2p")]
     ListDevices,
 
-    /// Enroll a guardian for key recovery
-    #[cfg(feature = "p2p")]
+    /// Enroll a paired device as a guardian and deliver the key shards
+    #[cfg(all(unix, feature = "p2p"))]
     EnrollGuardian {
         #[arg(long)]
         name: String,
         #[arg(long)]
         peer_id: String,
+        /// Guardians needed to recover (default: asked, or a majority)
+        #[arg(long)]
+        threshold: Option<u8>,
+        /// Daemon socket (default: ~/.sovereign/daemon.sock)
+        #[arg(long)]
+        socket: Option<PathBuf>,
     },
 
     /// List enrolled guardians
     #[cfg(feature = "encryption")]
     ListGuardians,
 
-    /// Initiate key recovery from guardians
+    /// Restore this profile's last backup once enough guardians release their shards
+    #[cfg(all(unix, feature = "p2p"))]
+    InitiateRecovery {
+        /// Guardian multiaddr ending in /p2p/<peer>, repeatable
+        /// (default: the guardians' saved addresses)
+        #[arg(long = "guardian")]
+        guardians: Vec<String>,
+        /// Fragment host multiaddr, repeatable (default: every paired device's)
+        #[arg(long = "host")]
+        hosts: Vec<String>,
+        /// Daemon socket (default: ~/.sovereign/daemon.sock)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+    },
+
+    /// Approve or deny recovery requests for shards this device holds as a guardian
+    #[cfg(all(unix, feature = "p2p"))]
+    ApproveRecovery {
+        /// Daemon socket (default: ~/.sovereign/daemon.sock)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+    },
+
+    /// Run the backend without a window, serving JSON-RPC on a local socket
+    #[cfg(all(unix, feature = "encryption"))]
+    Daemon {
+        /// Socket path (default: ~/.sovereign/daemon.sock)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+        /// Read the passphrase from the first line of stdin instead of prompting
+        #[arg(long)]
+        passphrase_stdin: bool,
+    },
+
+    /// Ask the assistant one question (through the daemon if it is running)
+    #[cfg(all(unix, feature = "encryption"))]
+    Ask {
+        /// The question, quoted
+        question: String,
+        /// Daemon socket (default: ~/.sovereign/daemon.sock)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+    },
+
+    /// Chat with the assistant in the terminal (through the daemon if it is running)
+    #[cfg(all(unix, feature = "encryption"))]
+    Chat {
+        /// Daemon socket (default: ~/.sovereign/daemon.sock)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+    },
+
+    /// Stream live events from the daemon to stdout as NDJSON
+    #[cfg(all(unix, feature = "encryption"))]
+    Watch {
+        /// Categories to keep, comma-separated: db, sync, comms, ai, session
+        /// (default: all)
+        #[arg(long)]
+        events: Option<String>,
+        /// Daemon socket (default: ~/.sovereign/daemon.sock)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+    },
+
+    /// Read, change or check settings
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Print a completion script for bash, zsh, fish, elvish or powershell
+    Completions {
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+
+    /// Check the install (database, keys, models, skills, P2P, comms) and suggest fixes
+    Doctor {
+        /// Also ask for the passphrase and check the keys decrypt
+        #[arg(long)]
+        unlock: bool,
+    },
+
+    /// Check that every document decrypts and that version history, the
+    /// session log and media blobs are untampered
     #[cfg(feature = "encryption")]
-    InitiateRecovery,
+    Verify,
+
+    /// Purge old trash, compact history, delete unused media, backfill the
+    /// search index and rotate the key usage log, in one pass
+    #[cfg(all(unix, feature = "encryption"))]
+    Maintain {
+        /// Daemon socket (default: ~/.sovereign/daemon.sock)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+    },
 
     /// List all contacts
     ListContacts,
@@ -140,3 +383,112 @@ pub enum Commands {
         channel: Option<String>,
     },
 }
+
+#[derive(Subcommand)]
+pub enum TrashAction {
+    /// List deleted documents and threads, most recent first
+    List,
+
+    /// Restore a deleted document or thread
+    Restore {
+        /// Id, a unique prefix of it, or the exact title or thread name
+        id: String,
+    },
+
+    /// Permanently remove items deleted longer ago than --older-than
+    Purge {
+        /// Age such as 30d, 12h or 2w; 0 empties the trash
+        #[arg(long, default_value = "30d")]
+        older_than: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ThreadAction {
+    /// Write a thread's documents, history, links and media to one file
+    Export {
+        /// Thread id, a unique prefix of it, or the thread name
+        id: String,
+        /// Output file or directory (default: <thread name>.svt here)
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Encrypt the bundle as an age file (passphrase unless --recipient)
+        #[arg(long)]
+        encrypt: bool,
+        /// age recipient (age1…) to encrypt to; repeatable
+        #[arg(long, requires = "encrypt")]
+        recipient: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SnapshotAction {
+    /// Snapshot the database, keys and media, then drop the snapshots
+    /// `snapshots.keep` and `snapshots.max_age_days` no longer keep
+    Create {
+        /// Why it was taken, shown by `snapshot list`
+        #[arg(long)]
+        note: Option<String>,
+    },
+
+    /// List snapshots, newest first
+    List,
+
+    /// Replace the whole workspace with a snapshot, once its name is typed
+    /// to confirm; the current state is snapshotted first
+    Rollback {
+        /// Snapshot name, as shown by `snapshot list`
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print one setting, e.g. `ui.theme`
+    Get { key: String },
+
+    /// Change one setting and save it (the user config file, or --config)
+    Set {
+        key: String,
+        /// New value; strings need no quotes, lists and tables use TOML syntax
+        value: String,
+    },
+
+    /// Print every setting in effect as `key = value`
+    List,
+
+    /// Check the config file for unknown settings and invalid values
+    Validate,
+}
+
+#[derive(Subcommand)]
+pub enum SkillAction {
+    /// List every skill with its trust status and actions
+    List,
+
+    /// Run a skill action on a document and print its output as JSON
+    Run {
+        /// Skill name, as shown by `skill list`
+        name: String,
+        /// Action id
+        action: String,
+        /// Document id, a unique prefix of it, or the title
+        #[arg(long)]
+        doc: String,
+        /// Action parameters as a JSON object (default: {})
+        #[arg(long)]
+        params: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CommitsAction {
+    /// Delete all but a document's most recent commits
+    Compact {
+        /// Document id, a unique prefix of it, or the title
+        doc: String,
+        /// Commits to keep (the head commit is always kept)
+        #[arg(long, default_value_t = 20)]
+        keep: usize,
+    },
+}
@@ -1,8 +1,19 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
 use anyhow::Result;
 use sovereign_core::config::AppConfig;
+use sovereign_core::content::ContentFields;
+use sovereign_skills::{CoreSkill, SkillContext, SkillDocument, SkillOutput};
 use sovereign_db::schema::{thing_to_raw, Document, RelationType, Thread};
+use sovereign_db::layered::LayeredGraphDB;
 use sovereign_db::GraphDB;
 
+use crate::doc_ref;
+use crate::export_bundle::{self, SealTo};
+use crate::share_bundle;
+use crate::source_import::{self, FolderFormat};
+use crate::thread_bundle;
 use crate::setup::create_db;
 
 pub async fn create_doc(
@@ -21,6 +32,7 @@ pub async fn create_doc(
 
 pub async fn get_doc(config: &AppConfig, id: String) -> Result<()> {
     let db = create_db(config).await?;
+    let id = doc_ref::resolve(&db, &id).await?;
     let doc = db.get_document(&id).await?;
     println!("{}", serde_json::to_string_pretty(&doc)?);
     Ok(())
@@ -44,6 +56,7 @@ pub async fn update_doc(
     content: Option<String>,
 ) -> Result<()> {
     let db = create_db(config).await?;
+    let id = doc_ref::resolve(&db, &id).await?;
     let updated = db
         .update_document(&id, title.as_deref(), content.as_deref())
         .await?;
@@ -53,6 +66,7 @@ pub async fn update_doc(
 
 pub async fn delete_doc(config: &AppConfig, id: String) -> Result<()> {
     let db = create_db(config).await?;
+    let id = doc_ref::resolve(&db, &id).await?;
     db.delete_document(&id).await?;
     println!("Deleted {id}");
     Ok(())
@@ -96,6 +110,8 @@ pub async fn add_relationship(
     let rel_type: RelationType = relation_type
         .parse()
         .map_err(|e: String| anyhow::anyhow!(e))?;
+    let from = doc_ref::resolve(&db, &from).await?;
+    let to = doc_ref::resolve(&db, &to).await?;
     let rel = db.create_relationship(&from, &to, rel_type, strength).await?;
     let id = rel.id.map(|t| thing_to_raw(&t)).unwrap_or_default();
     println!("{id}");
@@ -104,6 +120,7 @@ pub async fn add_relationship(
 
 pub async fn list_relationships(config: &AppConfig, doc_id: String) -> Result<()> {
     let db = create_db(config).await?;
+    let doc_id = doc_ref::resolve(&db, &doc_id).await?;
     let outgoing = db.list_outgoing_relationships(&doc_id).await?;
     let incoming = db.list_incoming_relationships(&doc_id).await?;
     for r in &outgoing {
@@ -120,6 +137,7 @@ pub async fn list_relationships(config: &AppConfig, doc_id: String) -> Result<()
 
 pub async fn commit_doc(config: &AppConfig, doc_id: String, message: String) -> Result<()> {
     let db = create_db(config).await?;
+    let doc_id = doc_ref::resolve(&db, &doc_id).await?;
     let commit = db.commit_document(&doc_id, &message).await?;
     let id = commit.id.map(|t| thing_to_raw(&t)).unwrap_or_default();
     println!("{id} ({})", commit.snapshot.title);
@@ -128,6 +146,7 @@ pub async fn commit_doc(config: &AppConfig, doc_id: String, message: String) ->
 
 pub async fn list_commits(config: &AppConfig, doc_id: String) -> Result<()> {
     let db = create_db(config).await?;
+    let doc_id = doc_ref::resolve(&db, &doc_id).await?;
     let commits = db.list_document_commits(&doc_id).await?;
     for c in &commits {
         let id = c.id.as_ref().map(|t| thing_to_raw(t)).unwrap_or_default();
@@ -137,6 +156,813 @@ pub async fn list_commits(config: &AppConfig, doc_id: String) -> Result<()> {
     Ok(())
 }
 
+pub async fn trash_list(config: &AppConfig) -> Result<()> {
+    let db = create_db(config).await?;
+    let docs = db.list_deleted_documents().await?;
+    let threads = db.list_deleted_threads().await?;
+    for t in &threads {
+        let id = t.id_string().unwrap_or_default();
+        println!("{id}\t{}\tthread\t{}", t.deleted_at.as_deref().unwrap_or_default(), t.name);
+    }
+    for d in &docs {
+        let id = d.id_string().unwrap_or_default();
+        println!("{id}\t{}\tdocument\t{}", d.deleted_at.as_deref().unwrap_or_default(), d.title);
+    }
+    println!("({} documents, {} threads in the trash)", docs.len(), threads.len());
+    Ok(())
+}
+
+pub async fn trash_restore(config: &AppConfig, id: String) -> Result<()> {
+    let db = create_db(config).await?;
+    let docs = db.list_deleted_documents().await?;
+    let threads = db.list_deleted_threads().await?;
+    let id = find_in_trash(&docs, &threads, &id)?;
+    if id.starts_with("thread:") {
+        let thread = db.restore_soft_deleted_thread(&id).await?;
+        println!("Restored thread {id} ({})", thread.name);
+    } else {
+        let doc = db.restore_soft_deleted_document(&id).await?;
+        println!("Restored document {id} ({})", doc.title);
+    }
+    Ok(())
+}
+
+/// The id of the trashed item `reference` names: its full id, a unique
+/// prefix of its key, or its exact title or thread name.
+fn find_in_trash(docs: &[Document], threads: &[Thread], reference: &str) -> Result<String> {
+    let reference = reference.trim();
+    let items: Vec<(String, &str)> = docs
+        .iter()
+        .map(|d| (d.id_string().unwrap_or_default(), d.title.as_str()))
+        .chain(threads.iter().map(|t| (t.id_string().unwrap_or_default(), t.name.as_str())))
+        .collect();
+    if let Some((id, _)) = items.iter().find(|(id, _)| id == reference) {
+        return Ok(id.clone());
+    }
+    let key_prefix: Vec<_> = items
+        .iter()
+        .filter(|(id, _)| id.split_once(':').is_some_and(|(_, key)| !reference.is_empty() && key.starts_with(reference)))
+        .collect();
+    let matches = if key_prefix.is_empty() {
+        items.iter().filter(|(_, name)| name.eq_ignore_ascii_case(reference)).collect()
+    } else {
+        key_prefix
+    };
+    match matches.as_slice() {
+        [] => anyhow::bail!("Nothing in the trash matches '{reference}'"),
+        [(id, _)] => Ok(id.clone()),
+        _ => {
+            let listing: Vec<String> = matches.iter().map(|(id, name)| format!("  {id}\t{name}")).collect();
+            anyhow::bail!("'{reference}' matches {} items; use one of these ids:\n{}", matches.len(), listing.join("\n"))
+        }
+    }
+}
+
+pub async fn trash_purge(config: &AppConfig, older_than: String) -> Result<()> {
+    let max_age = parse_age(&older_than)?;
+    let db = create_db(config).await?;
+    let purged = db.purge_deleted(max_age).await?;
+    println!("Purged {purged} items deleted more than {older_than} ago");
+    Ok(())
+}
+
+/// `30d`, `12h`, `2w`, `90m` or `0` (everything).
+fn parse_age(age: &str) -> Result<std::time::Duration> {
+    let age = age.trim();
+    if age == "0" {
+        return Ok(std::time::Duration::ZERO);
+    }
+    let split = age.len() - age.chars().last().map_or(0, char::len_utf8);
+    let (count, unit) = age.split_at(split);
+    let count: u64 = count
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid age '{age}' (expected e.g. 30d, 12h, 2w)"))?;
+    let secs = match unit {
+        "m" => 60,
+        "h" => 3600,
+        "d" => 24 * 3600,
+        "w" => 7 * 24 * 3600,
+        _ => anyhow::bail!("Invalid age '{age}': the unit must be m, h, d or w"),
+    };
+    Ok(std::time::Duration::from_secs(count * secs))
+}
+
+pub async fn compact_commits(config: &AppConfig, doc_id: String, keep: usize) -> Result<()> {
+    let db = create_db(config).await?;
+    let doc_id = doc_ref::resolve(&db, &doc_id).await?;
+    let removed = db.prune_document_commits(&doc_id, keep).await?;
+    let left = db.list_document_commits(&doc_id).await?.len();
+    println!("Removed {removed} commits from {doc_id}; {left} kept");
+    Ok(())
+}
+
+/// Export skill for each `sovereign export --format` value.
+fn export_skill(format: &str) -> Result<Box<dyn CoreSkill>> {
+    use sovereign_skills::skills::*;
+    Ok(match format {
+        "html" => Box::new(html_export::HtmlExportSkill),
+        "docx" => Box::new(docx_export::DocxExportSkill),
+        "epub" => Box::new(epub_export::EpubExportSkill),
+        "pdf" => Box::new(pdf_export::PdfExportSkill),
+        "txt" | "text" => Box::new(plaintext_export::PlaintextExportSkill),
+        other => anyhow::bail!("Unknown export format '{other}' (expected html, docx, epub, pdf or txt)"),
+    })
+}
+
+pub async fn export_doc(
+    config: &AppConfig,
+    doc_id: String,
+    format: String,
+    thread: bool,
+    output: Option<PathBuf>,
+    seal: Option<SealTo>,
+) -> Result<()> {
+    let skill = export_skill(&format)?;
+    let action = if thread { "export_thread" } else { "export" };
+    if !skill.actions().iter().any(|(id, _)| id == action) {
+        anyhow::bail!("'{format}' export has no '{action}' action");
+    }
+
+    let db = Arc::new(create_db(config).await?);
+    let doc_id = doc_ref::resolve(db.as_ref(), &doc_id).await?;
+    let doc = db.get_document(&doc_id).await?;
+    let skill_doc = SkillDocument {
+        id: doc_id,
+        title: doc.title,
+        content: ContentFields::parse(&doc.content),
+    };
+    // Same policy as the UI: grant exactly what the skill declares.
+    let ctx = SkillContext {
+        granted: skill.required_capabilities().into_iter().collect(),
+        db: Some(sovereign_skills::wrap_db(db)),
+        llm: None,
+    };
+    let SkillOutput::File { name, data, .. } = skill.execute(action, &skill_doc, "{}", &ctx)? else {
+        anyhow::bail!("Export skill '{}' did not produce a file", skill.name());
+    };
+    write_export(name, data, output, seal)
+}
+
+/// Every thread and document in one zip: the raw records in
+/// `workspace.json` for `import`, plus each document rendered as `format`.
+pub async fn export_workspace(
+    config: &AppConfig,
+    format: String,
+    output: Option<PathBuf>,
+    seal: Option<SealTo>,
+) -> Result<()> {
+    let skill = export_skill(&format)?;
+    let db = open_workspace_db(config).await?;
+    let (workspace, rendered) = workspace_records(db, skill.as_ref()).await?;
+    println!(
+        "{} threads, {} documents",
+        workspace.threads.len(),
+        workspace.documents.len()
+    );
+    let data = export_bundle::write_archive(&workspace, &rendered)?;
+    let name = format!("sovereign-workspace-{}.zip", chrono::Utc::now().format("%Y%m%d-%H%M%S"));
+    write_export(name, data, output, seal)
+}
+
+/// The workspace as the app sees it. With an account on this device that
+/// means unlocking it and reading through the decrypting database, the way
+/// login does; a plain database is only used when there is no account.
+/// Layered like the app's, so skills can be handed the same database.
+async fn open_workspace_db(config: &AppConfig) -> Result<Arc<LayeredGraphDB>> {
+    #[cfg(feature = "encryption")]
+    {
+        use sovereign_crypto::auth::PersonaKind;
+
+        let crypto_dir = crate::setup::crypto_dir();
+        if crypto_dir.join("auth.store").exists() {
+            let auth = crate::verify::unlock(config, &crypto_dir).await?;
+            let (raw, persona): (Arc<dyn GraphDB>, _) = match auth.persona {
+                PersonaKind::Primary => (
+                    Arc::new(create_db(config).await?),
+                    sovereign_core::auth::PersonaKind::Primary,
+                ),
+                PersonaKind::Duress => (
+                    Arc::new(crate::duress::open_duress_db(config).await?),
+                    sovereign_core::auth::PersonaKind::Duress,
+                ),
+            };
+            let db = crate::setup::build_encrypted_db(
+                raw,
+                Arc::new(auth.device_key),
+                Arc::new(auth.kek),
+                persona,
+            )?;
+            return Ok(Arc::new(LayeredGraphDB::new(db)));
+        }
+    }
+    Ok(Arc::new(LayeredGraphDB::new(Arc::new(create_db(config).await?))))
+}
+
+/// Collect the workspace records and each document rendered by `skill`.
+/// Records still carrying a nonce were read without decryption; exporting
+/// them would write ciphertext that no import can open again.
+async fn workspace_records<D: GraphDB + 'static>(
+    db: Arc<D>,
+    skill: &dyn CoreSkill,
+) -> Result<(export_bundle::WorkspaceExport, Vec<(String, Vec<u8>)>)> {
+    let ctx = SkillContext {
+        granted: skill.required_capabilities().into_iter().collect(),
+        db: Some(sovereign_skills::wrap_db(db.clone())),
+        llm: None,
+    };
+
+    let mut threads = Vec::new();
+    for t in db.list_threads().await? {
+        let Some(id) = t.id_string() else { continue };
+        if t.name_nonce.is_some() {
+            anyhow::bail!("thread {id} is still encrypted; unlock the workspace to export it");
+        }
+        threads.push(export_bundle::ExportedThread {
+            id,
+            name: t.name,
+            description: t.description,
+        });
+    }
+    let mut documents = Vec::new();
+    let mut rendered = Vec::new();
+    for doc in db.list_documents(None).await? {
+        let Some(id) = doc.id_string() else { continue };
+        if doc.encryption_nonce.is_some() || doc.title_nonce.is_some() {
+            anyhow::bail!("document {id} is still encrypted; unlock the workspace to export it");
+        }
+        let skill_doc = SkillDocument {
+            id: id.clone(),
+            title: doc.title.clone(),
+            content: ContentFields::parse(&doc.content),
+        };
+        match skill.execute("export", &skill_doc, "{}", &ctx) {
+            // Titles aren't unique; prefix the record key to keep entries apart.
+            Ok(SkillOutput::File { name, data, .. }) => {
+                rendered.push((format!("{}-{name}", id.replace(':', "_")), data));
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("warning: could not render {id}: {e}"),
+        }
+        documents.push(export_bundle::ExportedDocument {
+            id,
+            thread_id: doc.thread_id,
+            title: doc.title,
+            content: doc.content,
+            is_owned: doc.is_owned,
+            doc_type: doc.doc_type,
+            sensitivity: doc.sensitivity,
+            created_at: doc.created_at.to_rfc3339(),
+            modified_at: doc.modified_at.to_rfc3339(),
+        });
+    }
+
+    let workspace = export_bundle::WorkspaceExport {
+        version: export_bundle::FORMAT_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        threads,
+        documents,
+    };
+    Ok((workspace, rendered))
+}
+
+fn write_export(name: String, data: Vec<u8>, output: Option<PathBuf>, seal: Option<SealTo>) -> Result<()> {
+    let (name, data) = match seal {
+        Some(to) => (format!("{name}.age"), seal_bundle(&data, &to)?),
+        None => (name, data),
+    };
+    let path = match output {
+        Some(p) if p.is_dir() => p.join(&name),
+        Some(p) => p,
+        None => PathBuf::from(&name),
+    };
+    std::fs::write(&path, &data)?;
+    println!("{} ({} bytes)", path.display(), data.len());
+    Ok(())
+}
+
+/// Resolve `--encrypt` / `--recipient` into a seal target, prompting for a
+/// passphrase when no recipient was given.
+pub fn bundle_seal(encrypt: bool, recipients: Vec<String>) -> Result<Option<SealTo>> {
+    if !encrypt {
+        return Ok(None);
+    }
+    #[cfg(feature = "encryption")]
+    {
+        if !recipients.is_empty() {
+            return Ok(Some(SealTo::Recipients(recipients)));
+        }
+        let pass = rpassword::prompt_password("Export passphrase: ")?;
+        if pass.is_empty() {
+            anyhow::bail!("Passphrase cannot be empty");
+        }
+        if rpassword::prompt_password("Confirm passphrase: ")? != pass {
+            anyhow::bail!("Passphrases do not match");
+        }
+        Ok(Some(SealTo::Passphrase(pass)))
+    }
+    #[cfg(not(feature = "encryption"))]
+    {
+        let _ = recipients;
+        anyhow::bail!("--encrypt needs a build with the `encryption` feature")
+    }
+}
+
+#[cfg(feature = "encryption")]
+fn seal_bundle(data: &[u8], to: &SealTo) -> Result<Vec<u8>> {
+    export_bundle::seal(data, to)
+}
+
+#[cfg(not(feature = "encryption"))]
+fn seal_bundle(_data: &[u8], _to: &SealTo) -> Result<Vec<u8>> {
+    anyhow::bail!("--encrypt needs a build with the `encryption` feature")
+}
+
+/// Recreate the threads and documents of a workspace archive. Records get
+/// fresh IDs, so importing into a populated workspace never overwrites.
+pub async fn import_workspace(
+    config: &AppConfig,
+    input: PathBuf,
+    identity: Option<PathBuf>,
+) -> Result<()> {
+    let mut bytes = std::fs::read(&input)?;
+    if export_bundle::is_age(&bytes) {
+        bytes = unseal_bundle(&bytes, identity)?;
+    }
+    let workspace = export_bundle::read_archive(&bytes)?;
+
+    let db = open_workspace_db(config).await?;
+    let threads = import_workspace_into(db.as_ref(), &workspace).await?;
+    println!(
+        "Imported {} threads, {} documents",
+        threads,
+        workspace.documents.len()
+    );
+    Ok(())
+}
+
+/// Create the archive's threads and documents in `db`, returning how many
+/// threads were created. Going through the app's database means imported
+/// documents are encrypted at rest like any other.
+async fn import_workspace_into(
+    db: &dyn GraphDB,
+    workspace: &export_bundle::WorkspaceExport,
+) -> Result<usize> {
+    let mut thread_ids = std::collections::HashMap::new();
+    for t in &workspace.threads {
+        let created = db
+            .create_thread(Thread::new(t.name.clone(), t.description.clone()))
+            .await?;
+        let new_id = created
+            .id_string()
+            .ok_or_else(|| anyhow::anyhow!("thread missing ID"))?;
+        thread_ids.insert(t.id.clone(), new_id);
+    }
+    for d in &workspace.documents {
+        let Some(thread_id) = thread_ids.get(&d.thread_id) else {
+            eprintln!("warning: skipping '{}', its thread is not in the archive", d.title);
+            continue;
+        };
+        let mut doc = Document::new(d.title.clone(), thread_id.clone(), d.is_owned);
+        doc.doc_type = d.doc_type;
+        doc.content = d.content.clone();
+        d.restore_sensitivity(&mut doc);
+        if let Ok(t) = chrono::DateTime::parse_from_rfc3339(&d.created_at) {
+            doc.created_at = t.into();
+        }
+        if let Ok(t) = chrono::DateTime::parse_from_rfc3339(&d.modified_at) {
+            doc.modified_at = t.into();
+        }
+        db.create_document(doc).await?;
+    }
+    Ok(thread_ids.len())
+}
+
+/// One thread as a portable bundle: its documents with their history, the
+/// links between them, and the media they list.
+pub async fn export_thread(
+    config: &AppConfig,
+    id: String,
+    output: Option<PathBuf>,
+    seal: Option<SealTo>,
+) -> Result<()> {
+    use thread_bundle::{BundledCommit, BundledDocument, BundledRelationship};
+
+    let db = create_db(config).await?;
+    let thread = resolve_thread(&db, &id).await?;
+    let thread_id = thread.id_string().ok_or_else(|| anyhow::anyhow!("thread missing ID"))?;
+    let docs = db.list_documents(Some(&thread_id)).await?;
+    let in_thread: std::collections::HashSet<String> = docs.iter().filter_map(|d| d.id_string()).collect();
+
+    let mut documents = Vec::new();
+    let mut relationships = Vec::new();
+    let mut hashes = std::collections::BTreeSet::new();
+    for doc in docs {
+        let Some(id) = doc.id_string() else { continue };
+        let mut history = db.list_document_commits(&id).await?;
+        history.sort_by_key(|c| c.timestamp);
+        let commits: Vec<BundledCommit> = history
+            .into_iter()
+            .map(|c| BundledCommit {
+                message: c.message,
+                timestamp: c.timestamp.to_rfc3339(),
+                title: c.snapshot.title,
+                content: c.snapshot.content,
+            })
+            .collect();
+        hashes.extend(thread_bundle::blob_hashes(&doc.content));
+        for c in &commits {
+            hashes.extend(thread_bundle::blob_hashes(&c.content));
+        }
+        // Links leaving the thread would dangle on the other instance.
+        for rel in db.list_outgoing_relationships(&id).await? {
+            let Some(to) = rel.out.as_ref().map(thing_to_raw) else { continue };
+            if in_thread.contains(&to) {
+                relationships.push(BundledRelationship {
+                    from: id.clone(),
+                    to,
+                    relation_type: rel.relation_type,
+                    strength: rel.strength,
+                });
+            }
+        }
+        documents.push(BundledDocument {
+            document: export_bundle::ExportedDocument {
+                id,
+                thread_id: doc.thread_id,
+                title: doc.title,
+                content: doc.content,
+                is_owned: doc.is_owned,
+                doc_type: doc.doc_type,
+                sensitivity: doc.sensitivity,
+                created_at: doc.created_at.to_rfc3339(),
+                modified_at: doc.modified_at.to_rfc3339(),
+            },
+            commits,
+        });
+    }
+
+    let dir = crate::media::media_dir();
+    let mut blobs = Vec::new();
+    for hash in hashes {
+        match std::fs::read(dir.join(&hash)) {
+            Ok(data) => blobs.push((hash, data)),
+            Err(e) => eprintln!("warning: media {hash} is not on this device ({e}); bundling without it"),
+        }
+    }
+
+    let bundle = thread_bundle::ThreadBundle {
+        version: thread_bundle::FORMAT_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        thread: export_bundle::ExportedThread {
+            id: thread_id,
+            name: thread.name.clone(),
+            description: thread.description,
+        },
+        documents,
+        relationships,
+    };
+    println!(
+        "Thread '{}': {} documents, {} commits, {} links, {} media files",
+        bundle.thread.name,
+        bundle.documents.len(),
+        bundle.documents.iter().map(|d| d.commits.len()).sum::<usize>(),
+        bundle.relationships.len(),
+        blobs.len()
+    );
+    let data = thread_bundle::write_bundle(&bundle, &blobs)?;
+    let name = format!("{}.svt", sovereign_skills::markdown_util::sanitize_filename(&thread.name));
+    write_export(name, data, output, seal)
+}
+
+/// The thread `reference` names: its full id, a unique prefix of its key,
+/// or its name.
+async fn resolve_thread(db: &dyn GraphDB, reference: &str) -> Result<Thread> {
+    let reference = reference.trim();
+    if reference.starts_with("thread:") {
+        if let Ok(thread) = db.get_thread(reference).await {
+            return Ok(thread);
+        }
+    }
+    let threads = db.list_threads().await?;
+    let key_prefix: Vec<&Thread> = threads
+        .iter()
+        .filter(|t| {
+            t.id_string()
+                .and_then(|id| id.strip_prefix("thread:").map(str::to_string))
+                .is_some_and(|k| !reference.is_empty() && k.starts_with(reference))
+        })
+        .collect();
+    let matches = if key_prefix.is_empty() {
+        threads.iter().filter(|t| t.name.eq_ignore_ascii_case(reference)).collect()
+    } else {
+        key_prefix
+    };
+    match matches.as_slice() {
+        [] => anyhow::bail!("No thread matches '{reference}'"),
+        [only] => Ok((*only).clone()),
+        _ => {
+            let listing: Vec<String> = matches
+                .iter()
+                .map(|t| format!("  {}\t{}", t.id_string().unwrap_or_default(), t.name))
+                .collect();
+            anyhow::bail!("'{reference}' matches {} threads; use one of these ids:\n{}", matches.len(), listing.join("\n"))
+        }
+    }
+}
+
+/// Recreate a thread bundle as a new thread. Media goes into the blob
+/// store; each document's history is replayed oldest first and the
+/// document then set to its exported state.
+pub async fn import_thread(config: &AppConfig, input: PathBuf, identity: Option<PathBuf>) -> Result<()> {
+    let mut bytes = std::fs::read(&input)?;
+    if export_bundle::is_age(&bytes) {
+        bytes = unseal_bundle(&bytes, identity)?;
+    }
+    let (bundle, blobs) = thread_bundle::read_bundle(&bytes)?;
+    for (_, data) in &blobs {
+        crate::media::store_blob(data).map_err(anyhow::Error::msg)?;
+    }
+    let dir = crate::media::media_dir();
+
+    let db = create_db(config).await?;
+    let thread = db
+        .create_thread(Thread::new(bundle.thread.name.clone(), bundle.thread.description.clone()))
+        .await?;
+    let thread_id = thread.id_string().ok_or_else(|| anyhow::anyhow!("thread missing ID"))?;
+
+    let mut doc_ids = std::collections::HashMap::new();
+    let mut commits = 0;
+    for d in &bundle.documents {
+        let exported = &d.document;
+        let (first_title, first_content) = d
+            .commits
+            .first()
+            .map_or((&exported.title, &exported.content), |c| (&c.title, &c.content));
+        let mut doc = Document::new(first_title.clone(), thread_id.clone(), exported.is_owned);
+        doc.doc_type = exported.doc_type;
+        doc.content = thread_bundle::relocate_blobs(first_content, &dir);
+        exported.restore_sensitivity(&mut doc);
+        if let Ok(t) = chrono::DateTime::parse_from_rfc3339(&exported.created_at) {
+            doc.created_at = t.into();
+        }
+        let created = db.create_document(doc).await?;
+        let id = created.id_string().ok_or_else(|| anyhow::anyhow!("document missing ID"))?;
+
+        for (i, c) in d.commits.iter().enumerate() {
+            if i > 0 {
+                let content = thread_bundle::relocate_blobs(&c.content, &dir);
+                db.update_document(&id, Some(&c.title), Some(&content)).await?;
+            }
+            db.commit_document(&id, &c.message).await?;
+            commits += 1;
+        }
+        let last = d.commits.last();
+        if last.is_some_and(|c| c.title != exported.title || c.content != exported.content) {
+            let content = thread_bundle::relocate_blobs(&exported.content, &dir);
+            db.update_document(&id, Some(&exported.title), Some(&content)).await?;
+        }
+        doc_ids.insert(exported.id.clone(), id);
+    }
+
+    let mut links = 0;
+    for rel in &bundle.relationships {
+        let (Some(from), Some(to)) = (doc_ids.get(&rel.from), doc_ids.get(&rel.to)) else {
+            continue;
+        };
+        db.create_relationship(from, to, rel.relation_type.clone(), rel.strength).await?;
+        links += 1;
+    }
+    println!(
+        "Imported thread '{}' as {thread_id}: {} documents, {commits} commits, {links} links, {} media files",
+        bundle.thread.name,
+        doc_ids.len(),
+        blobs.len()
+    );
+    Ok(())
+}
+
+/// Seal one document (and the named attachments) into a `.svshare` file.
+pub async fn share_doc(
+    config: &AppConfig,
+    doc_id: String,
+    attach: Vec<String>,
+    expires_in: u32,
+    output: Option<PathBuf>,
+    qr: bool,
+) -> Result<()> {
+    let db = create_db(config).await?;
+    let doc_id = doc_ref::resolve(&db, &doc_id).await?;
+    let listed = ContentFields::parse(&db.get_document(&doc_id).await?.content).files;
+    for name in &attach {
+        if !listed.iter().any(|f| &f.name == name) {
+            anyhow::bail!("The document has no attachment named '{name}'");
+        }
+    }
+    let passphrase = share_passphrase(true)?;
+    let expires_in = (expires_in > 0).then(|| chrono::Duration::days(expires_in.into()));
+    let share =
+        share_bundle::create(&db, &doc_id, &attach, expires_in, &passphrase, chrono::Utc::now()).await?;
+    if share.missing_media > 0 {
+        eprintln!("warning: {} media files are not on this device; sharing without them", share.missing_media);
+    }
+    write_export(share.file_name, share.sealed, output, None)?;
+    match &share.expires_at {
+        Some(at) => println!("Expires {at}"),
+        None => println!("Never expires"),
+    }
+    if qr {
+        match &share.qr_payload {
+            Some(payload) => println!("{payload}"),
+            None => eprintln!("The share is too large for a QR code; send the file instead"),
+        }
+    }
+    Ok(())
+}
+
+/// Import a share from a file or QR payload.
+pub async fn open_share(config: &AppConfig, input: Option<PathBuf>, payload: Option<String>) -> Result<()> {
+    let sealed = match (input, payload) {
+        (_, Some(payload)) => share_bundle::from_payload(&payload)?,
+        (Some(input), None) => std::fs::read(&input)?,
+        (None, None) => anyhow::bail!("Give a share file or --payload"),
+    };
+    let passphrase = share_passphrase(false)?;
+    let db = create_db(config).await?;
+    let opened = share_bundle::open(
+        &db,
+        &sealed,
+        &passphrase,
+        &sovereign_core::sovereign_dir(),
+        chrono::Utc::now(),
+    )
+    .await?;
+    println!(
+        "Opened '{}' as {} in '{}'",
+        opened.title,
+        opened.document_id,
+        share_bundle::INBOX_THREAD
+    );
+    Ok(())
+}
+
+#[cfg(feature = "encryption")]
+fn share_passphrase(confirm: bool) -> Result<String> {
+    let pass = rpassword::prompt_password("Share passphrase: ")?;
+    if pass.is_empty() {
+        anyhow::bail!("Passphrase cannot be empty");
+    }
+    if confirm && rpassword::prompt_password("Confirm passphrase: ")? != pass {
+        anyhow::bail!("Passphrases do not match");
+    }
+    Ok(pass)
+}
+
+#[cfg(not(feature = "encryption"))]
+fn share_passphrase(_confirm: bool) -> Result<String> {
+    anyhow::bail!("sharing needs a build with the `encryption` feature")
+}
+
+/// Documents per batch insert during a folder import.
+const IMPORT_BATCH: usize = 500;
+
+/// Wiki links are links the author wrote, so they start out stronger than
+/// most suggested ones.
+const WIKI_LINK_STRENGTH: f32 = 0.8;
+
+/// What a folder import did.
+#[derive(Debug, Default)]
+struct FolderImportSummary {
+    thread: String,
+    thread_created: bool,
+    documents: usize,
+    links: usize,
+    unresolved_links: usize,
+    skipped: Vec<String>,
+}
+
+pub async fn import_folder(
+    config: &AppConfig,
+    from: PathBuf,
+    thread: Option<String>,
+    format: String,
+) -> Result<()> {
+    let format: FolderFormat = format.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+    let db = create_db(config).await?;
+    let summary = import_folder_into(&db, &from, thread, format).await?;
+
+    for skipped in &summary.skipped {
+        eprintln!("warning: skipped {skipped}");
+    }
+    println!(
+        "Imported {} documents into {} thread '{}', with {} links",
+        summary.documents,
+        if summary.thread_created { "new" } else { "existing" },
+        summary.thread,
+        summary.links
+    );
+    if summary.unresolved_links > 0 {
+        println!(
+            "{} links named notes that aren't in the folder",
+            summary.unresolved_links
+        );
+    }
+    if !summary.skipped.is_empty() {
+        println!("{} files skipped", summary.skipped.len());
+    }
+    Ok(())
+}
+
+/// Read `root`, write its documents into the thread (created if no thread
+/// has that name) in batches, then link them.
+async fn import_folder_into(
+    db: &dyn GraphDB,
+    root: &Path,
+    thread: Option<String>,
+    format: FolderFormat,
+) -> Result<FolderImportSummary> {
+    let (docs, skipped) = source_import::read_folder(format, root)?;
+    let thread = thread.unwrap_or_else(|| {
+        source_import::source_name(source_import::SourceKind::Markdown, root)
+    });
+
+    let existing = db
+        .list_threads()
+        .await?
+        .into_iter()
+        .find(|t| t.name.eq_ignore_ascii_case(&thread));
+    let (thread_id, thread_created) = match existing {
+        Some(t) => (t.id_string(), false),
+        None => {
+            let created = db
+                .create_thread(Thread::new(
+                    thread.clone(),
+                    format!("Imported from {}", root.display()),
+                ))
+                .await?;
+            (created.id_string(), true)
+        }
+    };
+    let thread_id = thread_id.ok_or_else(|| anyhow::anyhow!("thread missing ID"))?;
+
+    let (pairs, unresolved_links) = source_import::resolve_links(&docs);
+    let records: Vec<Document> = docs
+        .iter()
+        .map(|d| {
+            let mut doc = Document::new(d.doc.title.clone(), thread_id.clone(), true);
+            doc.content = ContentFields {
+                body: d.doc.body.clone(),
+                ..Default::default()
+            }
+            .serialize();
+            if let Some(at) = d.doc.modified_at {
+                doc.modified_at = at;
+            }
+            // A copied file is "created" when copied; never after its last edit.
+            doc.created_at = d.created_at.map_or(doc.modified_at, |c| c.min(doc.modified_at));
+            doc
+        })
+        .collect();
+
+    let mut ids = Vec::with_capacity(records.len());
+    for batch in records.chunks(IMPORT_BATCH) {
+        let created = db.create_documents(batch.to_vec()).await?;
+        ids.extend(created.iter().map(|d| d.id_string().unwrap_or_default()));
+    }
+    for &(from, to) in &pairs {
+        db.create_relationship(
+            &ids[from],
+            &ids[to],
+            RelationType::References,
+            WIKI_LINK_STRENGTH,
+        )
+        .await?;
+    }
+
+    Ok(FolderImportSummary {
+        thread,
+        thread_created,
+        documents: ids.len(),
+        links: pairs.len(),
+        unresolved_links,
+        skipped,
+    })
+}
+
+#[cfg(feature = "encryption")]
+fn unseal_bundle(data: &[u8], identity: Option<PathBuf>) -> Result<Vec<u8>> {
+    use export_bundle::Unseal;
+    let with = match identity {
+        Some(path) => Unseal::IdentityFile(path),
+        None => Unseal::Passphrase(rpassword::prompt_password("Export passphrase: ")?),
+    };
+    export_bundle::unseal(data, &with)
+}
+
+#[cfg(not(feature = "encryption"))]
+fn unseal_bundle(_data: &[u8], _identity: Option<PathBuf>) -> Result<Vec<u8>> {
+    anyhow::bail!("this archive is encrypted; it needs a build with the `encryption` feature")
+}
+
 #[cfg(feature = "encryption")]
 pub async fn encrypt_data(
     config: &AppConfig,
@@ -175,12 +1001,15 @@ pub async fn encrypt_data(
 
     // Update each document with encrypted content and nonce. The nonce MUST
     // be persisted with the ciphertext: without it the row reads back as raw
-    // ciphertext and a re-run would encrypt it a second time.
+    // ciphertext and a re-run would encrypt it a second time. The content
+    // index is left empty here; the next unlock builds it
+    // (`EncryptedGraphDB::reindex_documents`).
     for result in &results {
         db.set_document_content_encryption(
             &result.doc_id,
             &result.encrypted_content,
             &result.nonce_b64,
+            &[],
         )
         .await?;
         tracing::info!("Encrypted {}", result.doc_id);
@@ -262,6 +1091,77 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn import_folder_batches_documents_and_links_them() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("A.md"), "Links to [[B]] and [[Nowhere]].").unwrap();
+        std::fs::write(dir.path().join("B.md"), "Back to [[A]].").unwrap();
+        let db = sovereign_db::mock::MockGraphDB::new();
+
+        let summary = import_folder_into(
+            &db,
+            dir.path(),
+            Some("Research".into()),
+            FolderFormat::Obsidian,
+        )
+        .await
+        .unwrap();
+        assert!(summary.thread_created);
+        assert_eq!(summary.documents, 2);
+        assert_eq!(summary.links, 2);
+        assert_eq!(summary.unresolved_links, 1);
+
+        let threads = db.list_threads().await.unwrap();
+        assert_eq!(threads.len(), 1);
+        let docs = db.list_documents(None).await.unwrap();
+        let thread_id = threads[0].id_string().unwrap();
+        assert!(docs.iter().all(|d| d.thread_id == thread_id));
+        assert!(docs.iter().all(|d| d.created_at <= d.modified_at));
+        assert_eq!(db.list_all_relationships().await.unwrap().len(), 2);
+
+        // A second import reuses the thread.
+        let again = import_folder_into(
+            &db,
+            dir.path(),
+            Some("research".into()),
+            FolderFormat::Obsidian,
+        )
+        .await
+        .unwrap();
+        assert!(!again.thread_created);
+    }
+
+    #[test]
+    fn parse_age_accepts_units_and_zero() {
+        assert_eq!(parse_age("30d").unwrap().as_secs(), 30 * 24 * 3600);
+        assert_eq!(parse_age("2w").unwrap().as_secs(), 14 * 24 * 3600);
+        assert_eq!(parse_age("12h").unwrap().as_secs(), 12 * 3600);
+        assert!(parse_age("0").unwrap().is_zero());
+        assert!(parse_age("30").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("3y").is_err());
+    }
+
+    #[tokio::test]
+    async fn trash_items_are_found_by_id_prefix_or_name() {
+        let db = sovereign_db::mock::MockGraphDB::new();
+        let doc = db.create_document(Document::new("Old draft".into(), "thread:t".into(), true)).await.unwrap();
+        let thread = db.create_thread(Thread::new("Archive".into(), String::new())).await.unwrap();
+        let doc_id = doc.id_string().unwrap();
+        db.soft_delete_document(&doc_id).await.unwrap();
+        db.soft_delete_thread(&thread.id_string().unwrap()).await.unwrap();
+
+        let docs = db.list_deleted_documents().await.unwrap();
+        let threads = db.list_deleted_threads().await.unwrap();
+        assert_eq!(find_in_trash(&docs, &threads, &doc_id).unwrap(), doc_id);
+        assert_eq!(find_in_trash(&docs, &threads, "old draft").unwrap(), doc_id);
+        assert_eq!(find_in_trash(&docs, &threads, "Archive").unwrap(), thread.id_string().unwrap());
+        assert!(find_in_trash(&docs, &threads, "missing").is_err());
+
+        assert_eq!(db.purge_deleted(std::time::Duration::ZERO).await.unwrap(), 2);
+        assert!(db.list_deleted_documents().await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn list_docs_empty_db() {
         let config = test_config();
@@ -347,4 +1247,67 @@ mod tests {
         assert!(list_conversations(&config, Some("email".into())).await.is_ok());
         assert!(list_conversations(&config, Some("unknown".into())).await.is_ok());
     }
+
+    #[cfg(feature = "encryption")]
+    fn encrypted_over(
+        inner: Arc<dyn GraphDB>,
+        dir: &Path,
+    ) -> sovereign_db::encrypted::EncryptedGraphDB {
+        use sovereign_crypto::key_db::KeyDatabase;
+        use tokio::sync::RwLock;
+
+        let mk = sovereign_crypto::master_key::MasterKey::from_passphrase(b"test", b"salt").unwrap();
+        let device_key = sovereign_crypto::device_key::DeviceKey::derive(&mk, "test-device").unwrap();
+        let kdb = |name: &str| Arc::new(RwLock::new(KeyDatabase::new(dir.join(name))));
+        sovereign_db::encrypted::EncryptedGraphDB::new(
+            inner,
+            kdb("keys.db"),
+            kdb("keys.messages.db"),
+            kdb("keys.threads.db"),
+            kdb("keys.conversations.db"),
+            kdb("keys.contacts.db"),
+            kdb("keys.share_records.db"),
+            Arc::new(sovereign_crypto::kek::Kek::generate()),
+            Arc::new(sovereign_crypto::index_key::IndexKey::generate()),
+            Arc::new(device_key),
+        )
+    }
+
+    #[cfg(feature = "encryption")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn workspace_round_trips_encrypted_documents_as_plaintext() {
+        use sovereign_skills::skills::plaintext_export::PlaintextExportSkill;
+
+        let dir = tempfile::tempdir().unwrap();
+        let raw: Arc<dyn GraphDB> = Arc::new(sovereign_db::mock::MockGraphDB::new());
+        let src = Arc::new(encrypted_over(raw.clone(), &dir.path().join("src")));
+        let thread = src.create_thread(Thread::new("Plans".into(), String::new())).await.unwrap();
+        let mut doc = Document::new("Budget".into(), thread.id_string().unwrap(), true);
+        doc.content = r#"{"body":"Rent is due on the 1st","images":[]}"#.into();
+        doc.sensitivity = sovereign_db::schema::Sensitivity::Sealed;
+        src.create_document(doc).await.unwrap();
+
+        // Read raw, the rows are ciphertext and the export refuses them.
+        let err = workspace_records(Arc::new(LayeredGraphDB::new(raw)), &PlaintextExportSkill)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("still encrypted"), "{err}");
+
+        let (workspace, _) = workspace_records(src, &PlaintextExportSkill).await.unwrap();
+        assert_eq!(workspace.threads[0].name, "Plans");
+        assert_eq!(workspace.documents[0].title, "Budget");
+        assert!(workspace.documents[0].content.contains("Rent is due on the 1st"));
+
+        let dst = encrypted_over(
+            Arc::new(sovereign_db::mock::MockGraphDB::new()),
+            &dir.path().join("dst"),
+        );
+        assert_eq!(import_workspace_into(&dst, &workspace).await.unwrap(), 1);
+        let docs = dst.list_documents(None).await.unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].title, "Budget");
+        assert_eq!(docs[0].content, workspace.documents[0].content);
+        assert_eq!(docs[0].sensitivity, sovereign_db::schema::Sensitivity::Sealed);
+        assert_eq!(dst.list_threads().await.unwrap()[0].name, "Plans");
+    }
 }
@@ -0,0 +1,114 @@
+//! Post-login comms startup.
+//!
+//! `install_session` calls [`start_comms`] after the P2P step when the
+//! `comms` feature is on and `comms.enabled` is set. It registers a channel
+//! for every account configured in `~/.sovereign/comms.toml` whose
+//! credentials are available, spawns the [`CommsSync`] poll loop, and stores
+//! its [`CommsHandle`] on `AppState` so the compose command can send. Sync
+//! events go out on the app's event bus.
+//!
+//! Account secrets are not kept in `comms.toml`: the email password comes
+//! from `SOVEREIGN_EMAIL_PASSWORD` and the WhatsApp token from
+//! `SOVEREIGN_WHATSAPP_TOKEN`. An account without its secret is skipped.
+//!
+//! Lock and decoy mode stop the loop and clear the handle, so each login
+//! starts it afresh with that session's database and keys; a second call
+//! while it runs is a no-op.
+
+use std::sync::Arc;
+
+use sovereign_comms::{CommsConfig, CommsSync};
+
+use crate::tauri_state::AppState;
+
+pub(crate) fn load_comms_config() -> Result<Option<CommsConfig>, String> {
+    let path = sovereign_core::sovereign_dir().join("comms.toml");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = std::fs::read_to_string(&path).map_err(|e| format!("read comms.toml: {e}"))?;
+    toml::from_str(&data)
+        .map(Some)
+        .map_err(|e| format!("parse comms.toml: {e}"))
+}
+
+pub async fn start_comms(state: &AppState) -> Result<(), String> {
+    if state.comms.read().await.is_some() {
+        return Ok(());
+    }
+    let app_config = state.current_config();
+    if !app_config.comms.enabled {
+        tracing::info!("Comms disabled in config; skipping sync start");
+        return Ok(());
+    }
+    let Some(cfg) = load_comms_config()? else {
+        return Ok(());
+    };
+    if !cfg.enabled {
+        return Ok(());
+    }
+
+    let db: Arc<dyn sovereign_db::GraphDB> = state.db.clone();
+    let mut sync = CommsSync::new(state.bus.publisher(), app_config.comms.poll_interval_secs);
+
+    #[cfg(feature = "encryption")]
+    let account_key = state.account_key().await;
+
+    #[cfg(feature = "comms-email")]
+    if let Some(email) = cfg.email.clone() {
+        match std::env::var("SOVEREIGN_EMAIL_PASSWORD") {
+            Ok(password) => {
+                #[allow(unused_mut)]
+                let mut channel = sovereign_comms::channels::email::EmailChannel::new(email, db.clone(), password)
+                    .with_pii_share_hook(Arc::new(crate::pii_share_hook::PiiShareHook::new(db.clone())));
+                #[cfg(feature = "encryption")]
+                if let Some(ref key) = account_key {
+                    channel = channel
+                        .with_pii_hook(Arc::new(crate::pii_message_hook::PiiMessageHook::new(db.clone(), key.clone())))
+                        .with_pii_contact_hook(Arc::new(crate::pii_contact_hook::PiiContactHook::new(db.clone(), key.clone())));
+                }
+                sync.add_channel(Box::new(channel));
+            }
+            Err(_) => tracing::warn!("Email account configured but SOVEREIGN_EMAIL_PASSWORD is unset; skipping"),
+        }
+    }
+
+    #[cfg(feature = "comms-signal")]
+    if let Some(signal) = cfg.signal.clone() {
+        #[allow(unused_mut)]
+        let mut channel = sovereign_comms::channels::signal::SignalChannel::new(signal, db.clone());
+        #[cfg(feature = "encryption")]
+        if let Some(ref key) = account_key {
+            channel = channel
+                .with_pii_hook(Arc::new(crate::pii_message_hook::PiiMessageHook::new(db.clone(), key.clone())))
+                .with_pii_contact_hook(Arc::new(crate::pii_contact_hook::PiiContactHook::new(db.clone(), key.clone())));
+        }
+        sync.add_channel(Box::new(channel));
+    }
+
+    #[cfg(feature = "comms-whatsapp")]
+    if let Some(whatsapp) = cfg.whatsapp.clone() {
+        match std::env::var("SOVEREIGN_WHATSAPP_TOKEN") {
+            Ok(token) => {
+                #[allow(unused_mut)]
+                let mut channel = sovereign_comms::channels::whatsapp::WhatsAppChannel::new(whatsapp, db.clone(), token);
+                #[cfg(feature = "encryption")]
+                if let Some(ref key) = account_key {
+                    channel = channel
+                        .with_pii_hook(Arc::new(crate::pii_message_hook::PiiMessageHook::new(db.clone(), key.clone())))
+                        .with_pii_contact_hook(Arc::new(crate::pii_contact_hook::PiiContactHook::new(db.clone(), key.clone())));
+                }
+                sync.add_channel(Box::new(channel));
+            }
+            Err(_) => tracing::warn!("WhatsApp account configured but SOVEREIGN_WHATSAPP_TOKEN is unset; skipping"),
+        }
+    }
+
+    // Without any channel feature compiled in, `cfg` and `db` go unused.
+    let _ = (&cfg, &db);
+
+    *state.comms.write().await = Some(sync.handle());
+    tokio::spawn(sync.run(state.shutdown.listener("comms")));
+    tracing::info!("Comms sync started");
+    Ok(())
+}
@@ -0,0 +1,220 @@
+//! `sovereign config get|set|list|validate`.
+//!
+//! Settings are named by their dotted TOML path (`ui.theme`,
+//! `skills.max_concurrent_jobs`). `set` starts from the config in effect,
+//! as Settings does, checks the value against the setting's type and
+//! `AppConfig::validate`, and saves to the user config file (or the file
+//! given with `--config`). A running daemon is then asked to reload it;
+//! like the app, it applies what it can live and reports the sections
+//! that need a restart.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use sovereign_core::config::AppConfig;
+use toml::Value;
+
+pub fn get(config: &AppConfig, key: &str) -> Result<()> {
+    let tree = Value::try_from(config)?;
+    let value = lookup(&tree, key).with_context(|| unknown(key))?;
+    match value {
+        Value::String(s) => println!("{s}"),
+        Value::Table(_) => print!("{}", toml::to_string_pretty(value)?),
+        other => println!("{other}"),
+    }
+    Ok(())
+}
+
+pub fn list(config: &AppConfig) -> Result<()> {
+    let mut lines = Vec::new();
+    flatten("", &Value::try_from(config)?, &mut lines);
+    for line in lines {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// Check `file` (when it exists) parses, has no unknown settings, and
+/// passes `AppConfig::validate`, printing each problem with its line and
+/// column; otherwise check the config in effect.
+pub fn validate(config: &AppConfig, file: &Path) -> Result<()> {
+    let problems: Vec<String> = if file.exists() {
+        let text =
+            std::fs::read_to_string(file).with_context(|| format!("reading {}", file.display()))?;
+        println!("Checking {}", file.display());
+        match AppConfig::parse_checked(&text) {
+            Ok(_) => Vec::new(),
+            Err(issues) => issues
+                .into_iter()
+                .map(|mut issue| {
+                    issue.file = Some(file.to_path_buf());
+                    issue.to_string()
+                })
+                .collect(),
+        }
+    } else {
+        println!(
+            "{} does not exist; checking the config in effect",
+            file.display()
+        );
+        config.validate().err().unwrap_or_default()
+    };
+    if problems.is_empty() {
+        println!("OK");
+        return Ok(());
+    }
+    for p in &problems {
+        println!("  {p}");
+    }
+    bail!("{} problem(s) found", problems.len())
+}
+
+pub async fn set(config: &AppConfig, file: &Path, key: &str, raw: &str) -> Result<()> {
+    let next = with_setting(config, key, raw)?;
+    if let Err(errors) = next.validate() {
+        bail!("Invalid value for {key}: {}", errors.join("; "));
+    }
+    next.save(file)?;
+    println!("Set {key} in {}", file.display());
+
+    #[cfg(all(unix, feature = "encryption"))]
+    notify_daemon(file).await?;
+    Ok(())
+}
+
+/// Ask a running daemon to reload `file`. No daemon, nothing to do.
+#[cfg(all(unix, feature = "encryption"))]
+async fn notify_daemon(file: &Path) -> Result<()> {
+    let Some(mut client) = crate::daemon_client::Client::connect(None).await? else {
+        return Ok(());
+    };
+    let report = client
+        .call("config.reload", serde_json::json!({ "path": file }))
+        .await
+        .context("the daemon could not reload the config")?;
+    let restart: Vec<&str> = report["restart_required"]
+        .as_array()
+        .map(|a| a.iter().filter_map(|s| s.as_str()).collect())
+        .unwrap_or_default();
+    if restart.is_empty() {
+        println!("The running daemon applied the change.");
+    } else {
+        println!(
+            "The running daemon needs a restart for: {}",
+            restart.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn unknown(key: &str) -> String {
+    format!("Unknown setting '{key}' (see `sovereign config list`)")
+}
+
+fn lookup<'a>(tree: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.').try_fold(tree, |node, part| node.get(part))
+}
+
+/// `config` with `key` set from the command-line text `raw`, type-checked
+/// against the setting's current value.
+fn with_setting(config: &AppConfig, key: &str, raw: &str) -> Result<AppConfig> {
+    let mut tree = Value::try_from(config)?;
+    let (parent_key, leaf) = key.rsplit_once('.').unwrap_or(("", key));
+    let current = lookup(&tree, key).cloned();
+    let value = match &current {
+        // Strings are taken verbatim, so `ui.theme dark` needs no quotes.
+        Some(Value::String(_)) => Value::String(raw.to_string()),
+        Some(existing) => {
+            let parsed = parse_value(raw).with_context(|| {
+                format!("{key} expects a value of type {}", existing.type_str())
+            })?;
+            match (existing, parsed) {
+                (Value::Float(_), Value::Integer(i)) => Value::Float(i as f64),
+                (existing, parsed) if existing.same_type(&parsed) => parsed,
+                (existing, _) => bail!("{key} expects a value of type {}", existing.type_str()),
+            }
+        }
+        // Unset optional settings and map entries (ui.shortcuts.<name>).
+        None => parse_value(raw).unwrap_or_else(|_| Value::String(raw.to_string())),
+    };
+
+    let parent = if parent_key.is_empty() {
+        Some(&mut tree)
+    } else {
+        parent_key
+            .split('.')
+            .try_fold(&mut tree, |node, part| node.get_mut(part))
+    };
+    match parent {
+        Some(Value::Table(table)) => {
+            table.insert(leaf.to_string(), value.clone());
+        }
+        _ => bail!(unknown(key)),
+    }
+    let next: AppConfig = tree
+        .try_into()
+        .with_context(|| format!("invalid value for {key}"))?;
+    // A key serde ignored, or coerced, doesn't round-trip.
+    if lookup(&Value::try_from(&next)?, key) != Some(&value) {
+        bail!(unknown(key));
+    }
+    Ok(next)
+}
+
+/// A TOML value as written on the right of `key = …`.
+fn parse_value(raw: &str) -> Result<Value> {
+    let mut table: toml::Table = toml::from_str(&format!("v = {raw}"))?;
+    table.remove("v").context("no value")
+}
+
+fn flatten(prefix: &str, value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::Table(table) => {
+            for (k, v) in table {
+                let key = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{prefix}.{k}")
+                };
+                flatten(&key, v, out);
+            }
+        }
+        other => out.push(format!("{prefix} = {other}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_are_type_checked() {
+        let config = AppConfig::default();
+        let next = with_setting(&config, "ui.theme", "light").unwrap();
+        assert_eq!(next.ui.theme, "light");
+        let next = with_setting(&config, "skills.max_concurrent_jobs", "4").unwrap();
+        assert_eq!(next.skills.max_concurrent_jobs, 4);
+        let next = with_setting(&config, "ui.scale", "1").unwrap();
+        assert_eq!(next.ui.scale, 1.0);
+        let next = with_setting(&config, "ui.shortcuts.search", "Ctrl+K").unwrap();
+        assert_eq!(next.ui.shortcuts["search"], "Ctrl+K");
+
+        let err = with_setting(&config, "skills.max_concurrent_jobs", "many").unwrap_err();
+        assert!(format!("{err:#}").contains("expects a value of type integer"));
+        assert!(with_setting(&config, "p2p.enabled", "1").is_err());
+        assert!(with_setting(&config, "ui.no_such_thing", "x").is_err());
+        assert!(with_setting(&config, "nowhere.at_all", "x").is_err());
+    }
+
+    #[test]
+    fn list_flattens_to_dotted_keys() {
+        let mut lines = Vec::new();
+        flatten(
+            "",
+            &Value::try_from(AppConfig::default()).unwrap(),
+            &mut lines,
+        );
+        assert!(lines.iter().any(|l| l.starts_with("ui.theme = ")));
+        assert!(lines.iter().all(|l| !l.starts_with('.')));
+    }
+}
@@ -0,0 +1,175 @@
+//! Hot-apply of configuration changes.
+//!
+//! Changes arrive on `AppState::config_reload` from three places:
+//! `update_config` (Settings), the daemon's `config.reload`, and
+//! [`watch_files`], which notices when a config file is edited and
+//! reloads the layered config (see `AppConfig::load_layered`). A file
+//! with problems is not applied: the watcher reports each one with its
+//! line and column as a `config-invalid` event and the running config
+//! stays as it was.
+//!
+//! The task spawned here picks each change up and applies the parts that
+//! can change while the app runs — UI theme, scale, editor font and
+//! shortcuts, skill job concurrency, auto-approval threshold, the comms
+//! poll interval, local metrics — then tells the frontend with a
+//! `config-reloaded` event.
+//! The voice pipeline follows the `voice` section itself (see
+//! `VoicePipeline::spawn_reloading`). Everything else waits for a restart
+//! (see `AppConfig::restart_required`). The daemon applies changes through
+//! [`apply`] too.
+
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+use sovereign_core::config::{AppConfig, ConfigIssue};
+use tauri::{Emitter, Manager};
+
+use crate::tauri_state::AppState;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigReloadedPayload {
+    pub theme: String,
+    /// Scale, editor font and shortcuts are applied by each window's frontend.
+    pub scale: f64,
+    pub editor_font_size: u32,
+    pub editor_font_family: String,
+    pub shortcuts: std::collections::BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigInvalidPayload {
+    /// One line per problem: `file:line:column: key problem`.
+    pub issues: Vec<String>,
+}
+
+/// How often [`watch_files`] looks at the config files.
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Spawn the listener and the file watcher. Call once, after `AppState`
+/// is managed. `explicit` is the `--config` file, if any.
+pub fn spawn(app_handle: tauri::AppHandle, explicit: Option<PathBuf>) {
+    let watch_handle = app_handle.clone();
+    let invalid_handle = app_handle.clone();
+    watch_files(
+        explicit,
+        move |next| {
+            publish(&watch_handle.state::<AppState>(), next);
+        },
+        move |issues| {
+            let issues = issues.iter().map(ToString::to_string).collect();
+            let _ = invalid_handle.emit("config-invalid", ConfigInvalidPayload { issues });
+        },
+    );
+
+    let mut rx = app_handle.state::<AppState>().config_reload.subscribe();
+    tauri::async_runtime::spawn(async move {
+        while rx.changed().await.is_ok() {
+            let config = rx.borrow_and_update().clone();
+            let state = app_handle.state::<AppState>();
+            apply(&state, &config);
+            let _ = app_handle.emit(
+                "config-reloaded",
+                ConfigReloadedPayload {
+                    theme: config.ui.theme.clone(),
+                    scale: config.ui.scale,
+                    editor_font_size: config.ui.editor_font_size,
+                    editor_font_family: config.ui.editor_font_family.clone(),
+                    shortcuts: config.ui.shortcuts.clone(),
+                },
+            );
+        }
+    });
+}
+
+/// Hand `next` to the listeners unless it is the config already in
+/// effect. Returns whether it was new.
+pub(crate) fn publish(state: &AppState, next: AppConfig) -> bool {
+    state.config_reload.send_if_modified(|current| {
+        if *current == next {
+            return false;
+        }
+        *current = next;
+        true
+    })
+}
+
+/// Poll the config files (`AppConfig::layer_paths`) on a thread of their
+/// own. When one changes, reload the layered config and pass it to
+/// `changed`, or its problems to `invalid` if any file has some.
+pub(crate) fn watch_files(
+    explicit: Option<PathBuf>,
+    changed: impl Fn(AppConfig) + Send + 'static,
+    invalid: impl Fn(Vec<ConfigIssue>) + Send + 'static,
+) {
+    let paths = AppConfig::layer_paths(explicit.as_deref());
+    let stamps = move || -> Vec<Option<SystemTime>> {
+        paths
+            .iter()
+            .map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+            .collect()
+    };
+    let spawned = std::thread::Builder::new()
+        .name("config-watch".into())
+        .spawn(move || {
+            let mut seen = stamps();
+            loop {
+                std::thread::sleep(WATCH_INTERVAL);
+                let now = stamps();
+                if now == seen {
+                    continue;
+                }
+                seen = now;
+                let (next, issues) = AppConfig::load_layered(explicit.as_deref());
+                if issues.is_empty() {
+                    changed(next);
+                } else {
+                    for issue in &issues {
+                        tracing::warn!("Config change not applied: {issue}");
+                    }
+                    invalid(issues);
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        tracing::warn!("Config file watcher unavailable: {e}");
+    }
+}
+
+pub(crate) fn apply(state: &AppState, config: &AppConfig) {
+    sovereign_core::metrics::set_enabled(config.metrics.enabled);
+    sovereign_core::metrics::set_retention(config.metrics.retention_days);
+    state
+        .skill_registry
+        .jobs()
+        .set_max_concurrent(config.skills.max_concurrent_jobs);
+    if let Some(o) = &state.orchestrator {
+        o.set_auto_approve_threshold(config.autonomy.auto_approve_after);
+    }
+    // Comms starts after unlock with the config in effect by then, so only
+    // a loop already running needs telling.
+    #[cfg(feature = "comms")]
+    if let Ok(comms) = state.comms.try_read() {
+        if let Some(handle) = comms.as_ref() {
+            if let Err(e) = handle.set_poll_interval(config.comms.poll_interval_secs) {
+                tracing::warn!("Comms poll interval not updated: {e}");
+            }
+        }
+    }
+    let changed = match state.theme.lock() {
+        Ok(mut theme) if *theme != config.ui.theme => {
+            *theme = config.ui.theme.clone();
+            true
+        }
+        _ => false,
+    };
+    // Same write-through as `toggle_theme`, so the choice survives a restart.
+    if changed {
+        if let Ok(mut profile) = sovereign_core::profile::UserProfile::load(&state.profile_dir) {
+            profile.theme = config.ui.theme.clone();
+            if let Err(e) = profile.save(&state.profile_dir) {
+                tracing::warn!("Failed to persist theme to profile: {e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,1123 @@
+//! `sovereign daemon`: the backend without a window.
+//!
+//! Brings up the same DB, orchestrator, comms and P2P stack as the app,
+//! unlocks it once from the terminal, and serves it to scripts and other
+//! frontends as newline-delimited JSON-RPC 2.0 on a Unix socket
+//! (`~/.sovereign/daemon.sock` unless `--socket` says otherwise).
+//!
+//! The socket is owner-only. Each connection must open with
+//! `auth {"token": …}`, the secret written to `~/.sovereign/daemon.token`
+//! (0600, fresh every start); anything else closes the connection.
+//!
+//! | method             | params                              | result          |
+//! |--------------------|-------------------------------------|-----------------|
+//! | `status`           | –                                   | `{version, unlocked, orchestrator}` |
+//! | `documents.list`   | `thread_id?`                        | summaries       |
+//! | `documents.get`    | `id`                                | full document   |
+//! | `documents.create` | `title, thread_id, body?`           | `{id}`          |
+//! | `documents.update` | `id, title?, body?`                 | `null`          |
+//! | `documents.delete` | `id`                                | `null`          |
+//! | `threads.list`     | –                                   | summaries       |
+//! | `chat`             | `message`                           | `null`          |
+//! | `actions.approve`  | –                                   | `null`          |
+//! | `actions.reject`   | `reason?`                           | `null`          |
+//! | `config.reload`    | `path?`                             | `{restart_required}` |
+//! | `pairing.offer`    | –                                   | `{qr_payload_b64, pin, expires_at}` |
+//! | `pairing.cancel`   | –                                   | `null`          |
+//! | `devices.list`     | –                                   | paired devices  |
+//! | `devices.rename`   | `peer_id, name`                     | `null`          |
+//! | `backup.run`       | `threshold?, shares?`               | backup summary  |
+//! | `recovery.pending` | –                                   | release requests |
+//! | `recovery.release` | `for_user, epoch, approve`          | `bool`          |
+//! | `recovery.restore` | `guardians?, hosts?`                | `{restored, approvals, threshold}` |
+//! | `maintenance.run`  | –                                   | maintenance report |
+//! | `subscribe`        | –                                   | `true`          |
+//!
+//! `subscribe` turns on event notifications for the connection, named and
+//! shaped like the app's frontend events:
+//! `{"jsonrpc":"2.0","method":"event","params":{"event":"chat-response","payload":{"text":…}}}`.
+//! Chat replies and action proposals only arrive this way, so a client
+//! subscribes before it chats and answers proposals with `actions.*`.
+//! Document changes made over the socket are announced too
+//! (`document-created`, `document-updated`, `document-deleted`).
+//!
+//! The `pairing.*`, `devices.*`, `backup.*` and `recovery.*` methods need
+//! the p2p feature and a running node; `pairing.offer` is answered by a
+//! `device-paired` or `pairing-failed` event once the new device finishes.
+//!
+//! Edits to the config files are picked up as they are saved and applied
+//! like `config.reload` does, announced with `config-reloaded`; an edit
+//! that doesn't validate is left unapplied and announced with
+//! `config-invalid` and its problems (`{"issues": ["file:line:column: …"]}`).
+//!
+//! While unlocked the daemon runs a maintenance pass (see `maintain.rs`)
+//! a day, announcing each with a `maintenance-finished` event carrying the
+//! report.
+//!
+//! [`in_process`] serves a single connection the same way without a
+//! socket, for CLI commands run while no daemon is up.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sovereign_core::config::AppConfig;
+use sovereign_core::content::ContentFields;
+use sovereign_core::interfaces::OrchestratorEvent;
+use sovereign_core::security::ActionDecision;
+use sovereign_db::schema::Document;
+use sovereign_db::GraphDB;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{broadcast, mpsc};
+
+use crate::err::ToStringErr;
+use crate::tauri_commands::documents::{doc_summary, thread_summary, to_full_document};
+#[cfg(feature = "p2p")]
+use crate::tauri_commands::{backup, pairing};
+use crate::tauri_events as ev;
+use crate::tauri_state::AppState;
+
+pub const SOCKET_FILE: &str = "daemon.sock";
+pub const TOKEN_FILE: &str = "daemon.token";
+
+/// Events buffered per subscriber before a slow one starts missing some.
+const EVENT_BUFFER: usize = 256;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// The backend refused or failed; the message is its error.
+const APP_ERROR: i64 = -32000;
+const UNAUTHORIZED: i64 = -32001;
+
+/// Run the daemon until Ctrl-C or SIGTERM. `config_file` is the
+/// `--config` file, watched for edits along with the other config layers.
+pub fn run(
+    config: &AppConfig,
+    config_file: Option<PathBuf>,
+    rt: &tokio::runtime::Runtime,
+    socket: Option<PathBuf>,
+    passphrase_stdin: bool,
+) -> Result<()> {
+    let profile_dir = sovereign_core::sovereign_dir();
+    let socket = socket.unwrap_or_else(|| profile_dir.join(SOCKET_FILE));
+    let token_path = profile_dir.join(TOKEN_FILE);
+    let passphrase = prompt_unlock(&profile_dir, passphrase_stdin)?;
+
+    rt.block_on(async {
+        let backend = start(config, profile_dir, passphrase).await?;
+        spawn_config_watch(config_file, backend.state.clone(), backend.events.clone());
+
+        let listener = bind(&socket)?;
+        let token = sovereign_crypto::random_hex_32();
+        sovereign_crypto::fs_private::write_private(&token_path, token.as_bytes())
+            .with_context(|| format!("writing {}", token_path.display()))?;
+        println!("Sovereign daemon listening on {}", socket.display());
+
+        let served = serve(
+            listener,
+            backend.state.clone(),
+            Arc::new(token),
+            backend.events,
+        )
+        .await;
+
+        let _ = std::fs::remove_file(&socket);
+        let _ = std::fs::remove_file(&token_path);
+        backend.state.shutdown.run(crate::SHUTDOWN_GRACE).await;
+        backend.state.lock().await;
+        tracing::info!("Daemon stopped");
+        served
+    })
+}
+
+/// A one-off backend for a single client when no daemon is running: it
+/// unlocks from the terminal and serves one connection in this process.
+/// Returns the client's end of the connection and its token.
+pub async fn in_process(config: &AppConfig) -> Result<(UnixStream, String)> {
+    let profile_dir = sovereign_core::sovereign_dir();
+    let passphrase = prompt_unlock(&profile_dir, false)?;
+    let backend = start(config, profile_dir, passphrase).await?;
+    let token = sovereign_crypto::random_hex_32();
+    let (client, server) = UnixStream::pair()?;
+    tokio::spawn(connection(
+        server,
+        backend.state,
+        Arc::new(token.clone()),
+        backend.events,
+    ));
+    Ok((client, token))
+}
+
+/// An unlocked backend and the broadcast its events go out on.
+struct Running {
+    state: Arc<AppState>,
+    events: broadcast::Sender<Notification>,
+}
+
+/// Read the passphrase, before the backend's startup logging begins.
+fn prompt_unlock(profile_dir: &Path, from_stdin: bool) -> Result<zeroize::Zeroizing<String>> {
+    let auth_path = profile_dir.join("crypto").join("auth.store");
+    if !auth_path.exists() {
+        bail!("No account on this device yet; complete onboarding in the app first");
+    }
+    let passphrase = zeroize::Zeroizing::new(read_passphrase(from_stdin)?);
+    if sovereign_crypto::auth::AuthStore::load(&auth_path)?.requires_second_factor() {
+        eprintln!("Touch your security key when it blinks.");
+    }
+    Ok(passphrase)
+}
+
+async fn start(
+    config: &AppConfig,
+    profile_dir: PathBuf,
+    passphrase: zeroize::Zeroizing<String>,
+) -> Result<Running> {
+    let backend = crate::init_backend(config, profile_dir).await?;
+    let state = Arc::new(backend.app_state("dark".into()));
+    // Unlocking starts comms sync, so subscribe first.
+    #[cfg(feature = "comms")]
+    let comms_rx = state.bus.subscribe();
+    // The persona is not reported: a duress unlock must look the same.
+    crate::tauri_commands::auth::unlock(&state, passphrase.as_bytes(), None)
+        .await
+        .map_err(anyhow::Error::msg)?;
+    drop(passphrase);
+
+    let (events, _) = broadcast::channel(EVENT_BUFFER);
+    spawn_event_fanout(backend.orch_rx, events.clone(), state.clone());
+    #[cfg(feature = "comms")]
+    spawn_comms_fanout(comms_rx, events.clone());
+    crate::spawn_housekeeping(backend.db.clone(), backend.autocommit.clone(), &backend.shutdown);
+    spawn_maintenance(state.clone(), events.clone());
+    spawn_reminders(state.clone());
+    spawn_relevance(state.clone());
+    Ok(Running { state, events })
+}
+
+fn read_passphrase(from_stdin: bool) -> Result<String> {
+    if from_stdin {
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line)?;
+        Ok(line.trim_end_matches(['\r', '\n']).to_string())
+    } else {
+        Ok(rpassword::prompt_password("Sovereign passphrase: ")?)
+    }
+}
+
+/// Bind the socket, clearing one left behind by a daemon that died. A
+/// socket something still answers on belongs to a running daemon.
+fn bind(path: &Path) -> Result<UnixListener> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if path.exists() {
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            bail!("A daemon is already listening on {}", path.display());
+        }
+        std::fs::remove_file(path)?;
+    }
+    let listener =
+        UnixListener::bind(path).with_context(|| format!("binding {}", path.display()))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+async fn serve(
+    listener: UnixListener,
+    state: Arc<AppState>,
+    token: Arc<String>,
+    events: broadcast::Sender<Notification>,
+) -> Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut terminate = signal(SignalKind::terminate())?;
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    tokio::spawn(connection(stream, state.clone(), token.clone(), events.clone()));
+                }
+                Err(e) => tracing::warn!("daemon accept failed: {e}"),
+            },
+            _ = tokio::signal::ctrl_c() => break,
+            _ = terminate.recv() => break,
+        }
+    }
+    Ok(())
+}
+
+/// One client. Requests run concurrently, so a `chat` waiting on an action
+/// proposal doesn't block the `actions.approve` that answers it; replies
+/// and notifications share one writer.
+async fn connection(
+    stream: UnixStream,
+    state: Arc<AppState>,
+    token: Arc<String>,
+    events: broadcast::Sender<Notification>,
+) {
+    let (read, mut write) = stream.into_split();
+    let (out_tx, mut out_rx) = mpsc::channel::<String>(64);
+    let writer = tokio::spawn(async move {
+        while let Some(mut line) = out_rx.recv().await {
+            line.push('\n');
+            if write.write_all(line.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut lines = BufReader::new(read).lines();
+    let mut authed = false;
+    let mut subscription = None;
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request = match parse_request(&line) {
+            Ok(request) => request,
+            Err(reply) => {
+                let _ = out_tx.send(reply).await;
+                continue;
+            }
+        };
+
+        if !authed {
+            authed = request.method == "auth"
+                && params::<AuthParams>(request.params)
+                    .is_ok_and(|p| token_matches(&p.token, &token));
+            let reply = if authed {
+                Ok(json!(true))
+            } else {
+                Err(RpcError::new(
+                    UNAUTHORIZED,
+                    "authenticate first with `auth`",
+                ))
+            };
+            let _ = out_tx.send(response(&request.id, reply)).await;
+            if !authed {
+                break;
+            }
+            continue;
+        }
+
+        if request.method == "subscribe" {
+            if subscription.is_none() {
+                subscription = Some(tokio::spawn(forward_events(
+                    events.subscribe(),
+                    out_tx.clone(),
+                )));
+            }
+            let _ = out_tx.send(response(&request.id, Ok(json!(true)))).await;
+            continue;
+        }
+
+        let state = state.clone();
+        let events = events.clone();
+        let out = out_tx.clone();
+        tokio::spawn(async move {
+            let result = dispatch(&state, &events, &request.method, request.params).await;
+            let _ = out.send(response(&request.id, result)).await;
+        });
+    }
+
+    if let Some(subscription) = subscription {
+        subscription.abort();
+    }
+    drop(out_tx);
+    let _ = writer.await;
+}
+
+async fn forward_events(mut rx: broadcast::Receiver<Notification>, out: mpsc::Sender<String>) {
+    loop {
+        match rx.recv().await {
+            Ok(notification) => {
+                if out.send(notification.to_line()).await.is_err() {
+                    break;
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                tracing::warn!("daemon subscriber fell behind; {missed} events dropped");
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Methods
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct AuthParams {
+    token: String,
+}
+
+#[derive(Deserialize)]
+struct IdParams {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct ListDocsParams {
+    thread_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CreateDocParams {
+    title: String,
+    thread_id: String,
+    body: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct UpdateDocParams {
+    id: String,
+    title: Option<String>,
+    body: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ReloadConfigParams {
+    path: Option<PathBuf>,
+}
+
+#[cfg(feature = "p2p")]
+#[derive(Deserialize)]
+struct RenameDeviceParams {
+    peer_id: String,
+    name: String,
+}
+
+#[cfg(feature = "p2p")]
+#[derive(Deserialize)]
+struct BackupParams {
+    threshold: Option<u8>,
+    shares: Option<u8>,
+}
+
+#[cfg(feature = "p2p")]
+#[derive(Deserialize)]
+struct ReleaseParams {
+    for_user: String,
+    epoch: u32,
+    approve: bool,
+}
+
+#[cfg(feature = "p2p")]
+#[derive(Deserialize)]
+struct RestoreParams {
+    #[serde(default)]
+    guardians: Vec<String>,
+    #[serde(default)]
+    hosts: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ChatParams {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RejectParams {
+    reason: Option<String>,
+}
+
+async fn dispatch(
+    state: &AppState,
+    events: &broadcast::Sender<Notification>,
+    method: &str,
+    params_value: Value,
+) -> Result<Value, RpcError> {
+    let unlocked = state.require_session_unlocked().await;
+    if method == "status" {
+        return Ok(json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "unlocked": unlocked.is_ok(),
+            "orchestrator": state.orchestrator.is_some(),
+        }));
+    }
+    unlocked?;
+
+    match method {
+        "auth" => Ok(json!(true)),
+        "documents.list" => {
+            let p: ListDocsParams = params(params_value)?;
+            let docs = state
+                .db
+                .list_documents(p.thread_id.as_deref())
+                .await
+                .str_err()?;
+            to_value(docs.into_iter().map(doc_summary).collect::<Vec<_>>())
+        }
+        "documents.get" => {
+            let p: IdParams = params(params_value)?;
+            let doc = crate::sealed::readable(state, &p.id).await?;
+            state.relevance.record(&p.id);
+            to_value(to_full_document(doc))
+        }
+        "documents.create" => {
+            let p: CreateDocParams = params(params_value)?;
+            let doc = Document::new(p.title, p.thread_id, true);
+            let created = state.db.create_document(doc).await.str_err()?;
+            let id = created
+                .id
+                .as_ref()
+                .map(sovereign_db::schema::thing_to_raw)
+                .unwrap_or_default();
+            if let Some(body) = p.body {
+                update_document(state, &id, None, Some(body)).await?;
+            }
+            let _ = events.send(Notification {
+                event: "document-created",
+                payload: json!(ev::DocumentCreatedPayload {
+                    doc_id: id.clone(),
+                    title: created.title,
+                    thread_id: created.thread_id,
+                }),
+            });
+            Ok(json!({ "id": id }))
+        }
+        "documents.update" => {
+            let p: UpdateDocParams = params(params_value)?;
+            update_document(state, &p.id, p.title, p.body).await?;
+            state.relevance.record(&p.id);
+            let _ = events.send(Notification {
+                event: "document-updated",
+                payload: json!(ev::DocumentChangedPayload { doc_id: p.id }),
+            });
+            Ok(Value::Null)
+        }
+        "documents.delete" => {
+            let p: IdParams = params(params_value)?;
+            state.db.soft_delete_document(&p.id).await.str_err()?;
+            let _ = events.send(Notification {
+                event: "document-deleted",
+                payload: json!(ev::DocumentChangedPayload { doc_id: p.id }),
+            });
+            Ok(Value::Null)
+        }
+        "threads.list" => {
+            let threads = state.db.list_threads().await.str_err()?;
+            to_value(threads.into_iter().map(thread_summary).collect::<Vec<_>>())
+        }
+        "chat" => {
+            let p: ChatParams = params(params_value)?;
+            if let Some(tripped) = crate::canary::check(state, &p.message).await {
+                if let Some(event) = tripped.event {
+                    publish(events, event);
+                }
+                if tripped.halt {
+                    return Ok(Value::Null);
+                }
+            }
+            let orch = state
+                .orchestrator
+                .as_ref()
+                .ok_or_else(|| "AI orchestrator not available".to_string())?;
+            orch.handle_chat(&p.message).await.str_err()?;
+            Ok(Value::Null)
+        }
+        "actions.approve" => {
+            state
+                .decision_tx
+                .send(ActionDecision::Approve)
+                .await
+                .str_err()?;
+            Ok(Value::Null)
+        }
+        "maintenance.run" => to_value(crate::maintain::run(state).await),
+        "config.reload" => {
+            let p: ReloadConfigParams = params(params_value)?;
+            let path = p.path.unwrap_or_else(AppConfig::user_config_path);
+            // load_or_default skips a bad file; surface it instead.
+            let (next, issues) = AppConfig::load_layered(Some(&path));
+            if !issues.is_empty() {
+                let issues: Vec<String> = issues.iter().map(ToString::to_string).collect();
+                return Err(issues.join("; "));
+            }
+            crate::config_reload::apply(state, &next);
+            let restart_required = state.config.restart_required(&next);
+            state.config_reload.send_replace(next);
+            publish(events, "config-reloaded");
+            Ok(json!({ "restart_required": restart_required }))
+        }
+        "actions.reject" => {
+            let p: RejectParams = params(params_value)?;
+            state
+                .decision_tx
+                .send(ActionDecision::Reject(p.reason.unwrap_or_default()))
+                .await
+                .str_err()?;
+            Ok(Value::Null)
+        }
+        #[cfg(feature = "p2p")]
+        "pairing.offer" => to_value(pairing::arm_pairing_offer(state).await?),
+        #[cfg(feature = "p2p")]
+        "pairing.cancel" => {
+            if let Some(cmd_tx) = state.p2p_command_tx().await {
+                let _ = cmd_tx
+                    .send(sovereign_p2p::P2pCommand::ClearPairingOffer)
+                    .await;
+            }
+            Ok(Value::Null)
+        }
+        #[cfg(feature = "p2p")]
+        "devices.list" => to_value(pairing::paired_devices(state).await),
+        #[cfg(feature = "p2p")]
+        "devices.rename" => {
+            let p: RenameDeviceParams = params(params_value)?;
+            let name = p.name.trim().to_string();
+            if name.is_empty() {
+                return Err(RpcError::new(INVALID_PARAMS, "device name cannot be empty"));
+            }
+            pairing::edit_paired_device(state, &p.peer_id, |m| m.rename_device(&p.peer_id, name))
+                .await?;
+            Ok(Value::Null)
+        }
+        #[cfg(feature = "p2p")]
+        "backup.run" => {
+            let p: BackupParams = params(params_value)?;
+            let requested = backup::requested_policy(p.threshold, p.shares)?;
+            to_value(backup::distribute_backup(state, requested).await?)
+        }
+        #[cfg(feature = "p2p")]
+        "recovery.pending" => match state.backup_host.read().await.as_ref() {
+            Some(host) => to_value(backup::pending_releases(host)),
+            None => Ok(json!([])),
+        },
+        #[cfg(feature = "p2p")]
+        "recovery.release" => {
+            let p: ReleaseParams = params(params_value)?;
+            let host = state
+                .backup_host
+                .read()
+                .await
+                .clone()
+                .ok_or_else(|| "backup hosting is not enabled on this device".to_string())?;
+            let found = if p.approve {
+                host.approve_shard_release(&p.for_user, p.epoch)
+            } else {
+                host.deny_shard_release(&p.for_user, p.epoch)
+            }
+            .str_err()?;
+            Ok(json!(found))
+        }
+        #[cfg(feature = "p2p")]
+        "recovery.restore" => {
+            let p: RestoreParams = params(params_value)?;
+            to_value(backup::restore_from_guardians(state, p.guardians, p.hosts).await?)
+        }
+        other => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("unknown method `{other}`"),
+        )),
+    }
+}
+
+/// Change a document's title and/or body, keeping its images, videos and
+/// attachments. The body goes through PII ingest like an editor save.
+async fn update_document(
+    state: &AppState,
+    id: &str,
+    title: Option<String>,
+    body: Option<String>,
+) -> Result<(), String> {
+    let content = match body {
+        Some(body) => {
+            let body = crate::pii_ingest::maybe_ingest_document_body(state, id, &body).await?;
+            let current = state.db.get_document(id).await.str_err()?;
+            let mut fields = ContentFields::parse(&current.content);
+            fields.body = body;
+            Some(fields.serialize())
+        }
+        None => None,
+    };
+    state
+        .db
+        .update_document(id, title.as_deref(), content.as_deref())
+        .await
+        .str_err()?;
+    state.autocommit.lock().await.record_edit(id);
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Events
+// ---------------------------------------------------------------------------
+
+/// One backend event, serialized once for every subscriber.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub event: &'static str,
+    pub payload: Value,
+}
+
+impl Notification {
+    fn to_line(&self) -> String {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "event",
+            "params": { "event": self.event, "payload": self.payload },
+        })
+        .to_string()
+    }
+}
+
+fn publish(events: &broadcast::Sender<Notification>, event: &'static str) {
+    let _ = events.send(Notification {
+        event,
+        payload: Value::Null,
+    });
+}
+
+/// Apply config file edits as they are saved, like the app does (see
+/// `config_reload::watch_files`), announcing `config-reloaded`, or
+/// `config-invalid` with the problems when an edit doesn't validate.
+fn spawn_config_watch(
+    config_file: Option<PathBuf>,
+    state: Arc<AppState>,
+    events: broadcast::Sender<Notification>,
+) {
+    let invalid_events = events.clone();
+    crate::config_reload::watch_files(
+        config_file,
+        move |next| {
+            if crate::config_reload::publish(&state, next.clone()) {
+                crate::config_reload::apply(&state, &next);
+                publish(&events, "config-reloaded");
+            }
+        },
+        move |issues| {
+            let issues: Vec<String> = issues.iter().map(ToString::to_string).collect();
+            let _ = invalid_events.send(Notification {
+                event: "config-invalid",
+                payload: json!({ "issues": issues }),
+            });
+        },
+    );
+}
+
+/// Run a maintenance pass every [`crate::maintain::INTERVAL`], starting an
+/// hour after startup so it stays out of the way of the unlock. Skipped
+/// while the session is locked.
+fn spawn_maintenance(state: Arc<AppState>, events: broadcast::Sender<Notification>) {
+    tokio::spawn(async move {
+        let first = tokio::time::Instant::now() + std::time::Duration::from_secs(3600);
+        let mut interval = tokio::time::interval_at(first, crate::maintain::INTERVAL);
+        loop {
+            interval.tick().await;
+            if state.require_session_unlocked().await.is_err() {
+                continue;
+            }
+            let report = crate::maintain::run(&state).await;
+            for e in &report.errors {
+                tracing::warn!("Maintenance: {e}");
+            }
+            tracing::info!("Maintenance pass done:\n{}", report.render());
+            let _ = events.send(Notification {
+                event: "maintenance-finished",
+                payload: json!(report),
+            });
+        }
+    });
+}
+
+/// Deliver due reminders every [`crate::reminders::CHECK_INTERVAL`]; they
+/// reach subscribers as `reminder-due` through the event fanout.
+fn spawn_reminders(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(crate::reminders::CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            crate::reminders::tick(&state).await;
+        }
+    });
+}
+
+/// Reinforce and decay relationship strengths every
+/// [`crate::relevance::INTERVAL`].
+fn spawn_relevance(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(crate::relevance::INTERVAL);
+        loop {
+            interval.tick().await;
+            crate::relevance::tick(&state).await;
+        }
+    });
+}
+
+/// Drain the orchestrator channel into the subscriber broadcast. Lock and
+/// wipe requests are carried out here, as the app's event forwarder does.
+fn spawn_event_fanout(
+    orch_rx: std::sync::mpsc::Receiver<OrchestratorEvent>,
+    events: broadcast::Sender<Notification>,
+    state: Arc<AppState>,
+) {
+    let rt = tokio::runtime::Handle::current();
+    std::thread::Builder::new()
+        .name("daemon-events".into())
+        .spawn(move || {
+            while let Ok(event) = orch_rx.recv() {
+                match event {
+                    OrchestratorEvent::LockRequested => {
+                        let state = state.clone();
+                        let events = events.clone();
+                        rt.spawn(async move {
+                            state.lock().await;
+                            publish(&events, "session-locked");
+                        });
+                    }
+                    #[cfg(feature = "p2p")]
+                    OrchestratorEvent::RemoteWipeOrdered {
+                        order_id,
+                        issuer_peer_id,
+                    } => {
+                        let state = state.clone();
+                        let events = events.clone();
+                        rt.spawn(async move {
+                            let wiped = crate::remote_wipe::execute_order(
+                                &state,
+                                &order_id,
+                                &issuer_peer_id,
+                            )
+                            .await;
+                            if wiped {
+                                publish(&events, "device-wiped");
+                            }
+                        });
+                    }
+                    event => {
+                        if let Some(notification) = notification(event) {
+                            let _ = events.send(notification);
+                        }
+                    }
+                }
+            }
+            tracing::info!("Daemon event fan-out stopped (channel closed)");
+        })
+        .expect("Failed to spawn daemon-events thread");
+}
+
+/// Comms sync events for subscribers, as the app's comms forwarder emits
+/// them.
+#[cfg(feature = "comms")]
+fn spawn_comms_fanout(
+    comms_rx: std::sync::mpsc::Receiver<sovereign_comms::CommsEvent>,
+    events: broadcast::Sender<Notification>,
+) {
+    use sovereign_comms::CommsEvent;
+    std::thread::Builder::new()
+        .name("daemon-comms-events".into())
+        .spawn(move || {
+            while let Ok(event) = comms_rx.recv() {
+                let (event, payload) = match event {
+                    CommsEvent::NewMessages {
+                        channel,
+                        count,
+                        conversation_id,
+                    } => (
+                        "new-messages",
+                        json!(ev::NewMessagesPayload {
+                            channel: channel.to_string(),
+                            count,
+                            conversation_id,
+                        }),
+                    ),
+                    CommsEvent::ContactDiscovered { contact_id, name } => (
+                        "contact-created",
+                        json!(ev::ContactCreatedPayload { contact_id, name }),
+                    ),
+                    CommsEvent::SyncComplete { .. } | CommsEvent::SyncError { .. } => continue,
+                };
+                let _ = events.send(Notification { event, payload });
+            }
+        })
+        .expect("Failed to spawn daemon-comms-events thread");
+}
+
+/// The events a headless client can act on, with the app's names and
+/// payloads. UI-only events (panels, browser, canvas) are dropped.
+fn notification(event: OrchestratorEvent) -> Option<Notification> {
+    let (event, payload) = match event {
+        OrchestratorEvent::ChatResponse { text } => {
+            ("chat-response", json!(ev::ChatResponsePayload { text }))
+        }
+        OrchestratorEvent::BubbleState(state) => (
+            "bubble-state",
+            json!(ev::BubbleStatePayload {
+                state: format!("{state:?}"),
+            }),
+        ),
+        OrchestratorEvent::ActionProposed { proposal } => (
+            "action-proposed",
+            json!(ev::ActionProposedPayload {
+                action: proposal.action,
+                level: format!("{:?}", proposal.level),
+                description: proposal.description,
+                doc_id: proposal.doc_id,
+                thread_id: proposal.thread_id,
+            }),
+        ),
+        OrchestratorEvent::ActionExecuted { action, success } => (
+            "action-executed",
+            json!(ev::ActionExecutedPayload { action, success }),
+        ),
+        OrchestratorEvent::ActionRejected { action, reason } => (
+            "action-rejected",
+            json!(ev::ActionRejectedPayload { action, reason }),
+        ),
+        OrchestratorEvent::SearchResults { query, doc_ids } => (
+            "search-results",
+            json!(ev::SearchResultsPayload { query, doc_ids }),
+        ),
+        OrchestratorEvent::Suggestion {
+            text,
+            action,
+            target,
+        } => (
+            "suggestion",
+            json!(ev::SuggestionPayload {
+                text,
+                action,
+                target,
+            }),
+        ),
+        OrchestratorEvent::SkillResult {
+            skill,
+            action,
+            kind,
+            data,
+        } => (
+            "skill-result",
+            json!(ev::SkillResultPayload {
+                skill,
+                action,
+                kind,
+                data,
+            }),
+        ),
+        OrchestratorEvent::DocumentCreated {
+            doc_id,
+            title,
+            thread_id,
+        } => (
+            "document-created",
+            json!(ev::DocumentCreatedPayload {
+                doc_id,
+                title,
+                thread_id,
+            }),
+        ),
+        OrchestratorEvent::ThreadCreated { thread_id, name } => (
+            "thread-created",
+            json!({ "thread_id": thread_id, "name": name }),
+        ),
+        OrchestratorEvent::ThreadRenamed { thread_id, name } => (
+            "thread-renamed",
+            json!(ev::ThreadRenamedPayload { thread_id, name }),
+        ),
+        OrchestratorEvent::ThreadDeleted { thread_id } => (
+            "thread-deleted",
+            json!(ev::ThreadDeletedPayload { thread_id }),
+        ),
+        OrchestratorEvent::DocumentMoved {
+            doc_id,
+            new_thread_id,
+        } => (
+            "document-moved",
+            json!(ev::DocumentMovedPayload {
+                doc_id,
+                new_thread_id,
+            }),
+        ),
+        OrchestratorEvent::SyncStatus { peer_id, status } => (
+            "sync-status",
+            json!(ev::SyncStatusPayload { peer_id, status }),
+        ),
+        OrchestratorEvent::SyncConflict {
+            doc_id,
+            description,
+        } => (
+            "sync-conflict",
+            json!(ev::SyncConflictPayload {
+                doc_id,
+                description,
+            }),
+        ),
+        OrchestratorEvent::InjectionDetected {
+            source,
+            indicators,
+            severity,
+            ..
+        } => (
+            "injection-detected",
+            json!(ev::InjectionDetectedPayload {
+                source,
+                indicators,
+                severity,
+            }),
+        ),
+        OrchestratorEvent::DevicePaired {
+            device_id,
+            device_name,
+        } => (
+            "device-paired",
+            json!(ev::DevicePairedPayload {
+                device_id,
+                device_name,
+            }),
+        ),
+        OrchestratorEvent::PairingFailed { reason, offer_dead } => (
+            "pairing-failed",
+            json!(ev::PairingFailedPayload { reason, offer_dead }),
+        ),
+        OrchestratorEvent::KeyUsageAlert { text } => {
+            ("key-usage-alert", json!(ev::KeyUsageAlertPayload { text }))
+        }
+        OrchestratorEvent::ReminderDue {
+            reminder_id,
+            doc_id,
+            text,
+        } => (
+            "reminder-due",
+            json!(ev::ReminderDuePayload {
+                reminder_id,
+                doc_id,
+                text,
+            }),
+        ),
+        _ => return None,
+    };
+    Some(Notification { event, payload })
+}
+
+// ---------------------------------------------------------------------------
+// JSON-RPC framing
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<String> for RpcError {
+    fn from(message: String) -> Self {
+        Self::new(APP_ERROR, message)
+    }
+}
+
+/// Parse one request line; a malformed one yields the error reply instead.
+fn parse_request(line: &str) -> Result<Request, String> {
+    let value: Value = serde_json::from_str(line)
+        .map_err(|e| response(&Value::Null, Err(RpcError::new(PARSE_ERROR, e.to_string()))))?;
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    serde_json::from_value(value)
+        .map_err(|e| response(&id, Err(RpcError::new(INVALID_REQUEST, e.to_string()))))
+}
+
+/// Method params; a method without any accepts them omitted.
+fn params<T: DeserializeOwned>(value: Value) -> Result<T, RpcError> {
+    let value = if value.is_null() { json!({}) } else { value };
+    serde_json::from_value(value).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+fn to_value(value: impl serde::Serialize) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|e| RpcError::new(APP_ERROR, e.to_string()))
+}
+
+fn response(id: &Value, result: Result<Value, RpcError>) -> String {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": e.code, "message": e.message },
+        }),
+    }
+    .to_string()
+}
+
+/// Compare without stopping at the first differing byte.
+fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_lines_get_error_replies() {
+        let reply: Value = serde_json::from_str(&parse_request("{nope").unwrap_err()).unwrap();
+        assert_eq!(reply["error"]["code"], PARSE_ERROR);
+        assert_eq!(reply["id"], Value::Null);
+
+        let reply: Value =
+            serde_json::from_str(&parse_request(r#"{"id":7,"params":{}}"#).unwrap_err()).unwrap();
+        assert_eq!(reply["error"]["code"], INVALID_REQUEST);
+        assert_eq!(reply["id"], 7);
+
+        let request = parse_request(r#"{"jsonrpc":"2.0","id":"a","method":"status"}"#).unwrap();
+        assert_eq!(request.method, "status");
+        assert_eq!(request.id, "a");
+    }
+
+    #[test]
+    fn params_may_be_omitted_but_not_wrong() {
+        let p: ListDocsParams = params(Value::Null).unwrap();
+        assert!(p.thread_id.is_none());
+        let err = params::<IdParams>(json!({ "id": 3 })).err().unwrap();
+        assert_eq!(err.code, INVALID_PARAMS);
+    }
+
+    #[test]
+    fn token_must_match_exactly() {
+        assert!(token_matches("abc123", "abc123"));
+        assert!(!token_matches("abc124", "abc123"));
+        assert!(!token_matches("abc12", "abc123"));
+        assert!(!token_matches("", "abc123"));
+    }
+
+    #[test]
+    fn events_keep_the_app_names_and_ui_only_ones_are_dropped() {
+        let n = notification(OrchestratorEvent::ChatResponse { text: "hi".into() }).unwrap();
+        let line: Value = serde_json::from_str(&n.to_line()).unwrap();
+        assert_eq!(line["method"], "event");
+        assert_eq!(line["params"]["event"], "chat-response");
+        assert_eq!(line["params"]["payload"]["text"], "hi");
+
+        assert!(notification(OrchestratorEvent::OpenPanel {
+            name: "inbox".into()
+        })
+        .is_none());
+    }
+}
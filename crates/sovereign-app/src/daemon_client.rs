@@ -0,0 +1,183 @@
+//! Client side of the daemon socket, for CLI commands that act on the
+//! running backend. See `daemon.rs` for the protocol.
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Value};
+use sovereign_core::config::AppConfig;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::UnixStream;
+
+use crate::daemon::{SOCKET_FILE, TOKEN_FILE};
+
+/// A line from the daemon: the reply to one of our requests, or an event.
+#[derive(Debug, PartialEq)]
+pub enum Incoming {
+    Reply {
+        id: u64,
+        result: Result<Value, String>,
+    },
+    Event {
+        event: String,
+        payload: Value,
+    },
+}
+
+pub struct Client {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+    write: OwnedWriteHalf,
+    next_id: u64,
+    closed: bool,
+}
+
+impl Client {
+    /// Connect to the daemon on `socket` (default `~/.sovereign/daemon.sock`).
+    /// If none answers, unlock a one-off backend in this process instead.
+    pub async fn connect_or_start(config: &AppConfig, socket: Option<PathBuf>) -> Result<Self> {
+        if let Some(client) = Self::connect(socket).await? {
+            return Ok(client);
+        }
+        eprintln!("No daemon running; starting a one-off session.");
+        let (stream, token) = crate::daemon::in_process(config).await?;
+        Self::open(stream, &token).await
+    }
+
+    /// Connect to a running daemon; `None` if nothing answers on `socket`.
+    pub async fn connect(socket: Option<PathBuf>) -> Result<Option<Self>> {
+        let profile_dir = sovereign_core::sovereign_dir();
+        let socket = socket.unwrap_or_else(|| profile_dir.join(SOCKET_FILE));
+        let Ok(stream) = UnixStream::connect(&socket).await else {
+            return Ok(None);
+        };
+        let token = std::fs::read_to_string(profile_dir.join(TOKEN_FILE))
+            .context("the daemon is running but its token file is unreadable")?;
+        Self::open(stream, token.trim()).await.map(Some)
+    }
+
+    async fn open(stream: UnixStream, token: &str) -> Result<Self> {
+        let (read, write) = stream.into_split();
+        let mut client = Self {
+            lines: BufReader::new(read).lines(),
+            write,
+            next_id: 0,
+            closed: false,
+        };
+        client
+            .call("auth", json!({ "token": token }))
+            .await
+            .context("the daemon refused its token")?;
+        Ok(client)
+    }
+
+    /// Send a request without waiting; the reply comes through [`Self::next`].
+    pub async fn send(&mut self, method: &str, params: Value) -> Result<u64> {
+        self.next_id += 1;
+        let mut line = json!({
+            "jsonrpc": "2.0",
+            "id": self.next_id,
+            "method": method,
+            "params": params,
+        })
+        .to_string();
+        line.push('\n');
+        if let Err(e) = self.write.write_all(line.as_bytes()).await {
+            self.closed = true;
+            bail!("the daemon connection closed: {e}");
+        }
+        Ok(self.next_id)
+    }
+
+    /// The next reply or event; `None` once the daemon hangs up.
+    pub async fn next(&mut self) -> Result<Option<Incoming>> {
+        loop {
+            let Some(line) = self.lines.next_line().await? else {
+                self.closed = true;
+                return Ok(None);
+            };
+            if let Some(incoming) = parse_incoming(&line) {
+                return Ok(Some(incoming));
+            }
+        }
+    }
+
+    /// Send a request and wait for its reply. Events arriving meanwhile
+    /// are dropped.
+    pub async fn call(&mut self, method: &str, params: Value) -> Result<Value> {
+        let id = self.send(method, params).await?;
+        loop {
+            match self.next().await? {
+                Some(Incoming::Reply { id: got, result }) if got == id => {
+                    return result.map_err(|e| anyhow!(e));
+                }
+                Some(_) => {}
+                None => bail!("the daemon closed the connection"),
+            }
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
+
+/// Decode one line. Anything but an event or the reply to a numbered
+/// request is ignored.
+fn parse_incoming(line: &str) -> Option<Incoming> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    if value["method"] == "event" {
+        return Some(Incoming::Event {
+            event: value["params"]["event"].as_str()?.to_string(),
+            payload: value["params"]["payload"].clone(),
+        });
+    }
+    let id = value["id"].as_u64()?;
+    let result = match value.get("error") {
+        Some(error) => Err(error["message"]
+            .as_str()
+            .unwrap_or("daemon error")
+            .to_string()),
+        None => Ok(value["result"].clone()),
+    };
+    Some(Incoming::Reply { id, result })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_replies_errors_and_events() {
+        assert_eq!(
+            parse_incoming(r#"{"jsonrpc":"2.0","id":3,"result":{"id":"document:a"}}"#),
+            Some(Incoming::Reply {
+                id: 3,
+                result: Ok(json!({ "id": "document:a" })),
+            })
+        );
+        assert_eq!(
+            parse_incoming(
+                r#"{"jsonrpc":"2.0","id":4,"error":{"code":-32000,"message":"locked"}}"#
+            ),
+            Some(Incoming::Reply {
+                id: 4,
+                result: Err("locked".into()),
+            })
+        );
+        assert_eq!(
+            parse_incoming(
+                r#"{"jsonrpc":"2.0","method":"event","params":{"event":"chat-response","payload":{"text":"hi"}}}"#
+            ),
+            Some(Incoming::Event {
+                event: "chat-response".into(),
+                payload: json!({ "text": "hi" }),
+            })
+        );
+        assert_eq!(parse_incoming("not json"), None);
+        assert_eq!(
+            parse_incoming(r#"{"jsonrpc":"2.0","id":null,"error":{}}"#),
+            None
+        );
+    }
+}
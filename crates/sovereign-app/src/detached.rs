@@ -0,0 +1,188 @@
+//! Detached panel windows — a document panel or the inbox popped out of
+//! the main window into its own OS window, e.g. to keep the canvas on one
+//! monitor and a document on another.
+//!
+//! Each window loads the same frontend bundle as `main`. On start the page
+//! asks `detached_panel_info` what it shows and renders only that panel.
+//! These windows are app webviews, so IPC-005 lets them call data commands
+//! like `main`; they never navigate away from the app's own pages.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+/// Every detached window's label starts with this.
+pub const LABEL_PREFIX: &str = "detached-";
+
+/// What a detached window shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PanelKind {
+    Document,
+    Inbox,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DetachedPanel {
+    pub kind: PanelKind,
+    /// Document ID; empty for the inbox.
+    pub id: String,
+}
+
+impl DetachedPanel {
+    /// Window label. One window per document, one for the inbox.
+    pub fn label(&self) -> String {
+        match self.kind {
+            PanelKind::Document => {
+                let id: String = self
+                    .id
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                    .collect();
+                format!("{LABEL_PREFIX}document-{id}")
+            }
+            PanelKind::Inbox => format!("{LABEL_PREFIX}inbox"),
+        }
+    }
+}
+
+/// Payload of `detached-panel-closed`, sent to `main` when a detached
+/// window goes away. `dock` is set when the user asked to put the panel
+/// back rather than just closing it.
+#[derive(Debug, Clone, Serialize)]
+pub struct DetachedPanelClosed {
+    pub kind: PanelKind,
+    pub id: String,
+    pub dock: bool,
+}
+
+#[derive(Debug)]
+struct Entry {
+    panel: DetachedPanel,
+    dock: bool,
+}
+
+/// Open detached windows by label.
+#[derive(Debug, Default)]
+pub struct DetachedPanels {
+    windows: Mutex<HashMap<String, Entry>>,
+}
+
+impl DetachedPanels {
+    pub fn insert(&self, label: String, panel: DetachedPanel) {
+        if let Ok(mut w) = self.windows.lock() {
+            w.insert(label, Entry { panel, dock: false });
+        }
+    }
+
+    pub fn get(&self, label: &str) -> Option<DetachedPanel> {
+        self.windows.lock().ok()?.get(label).map(|e| e.panel.clone())
+    }
+
+    /// Mark the window so its close re-docks the panel in `main`.
+    pub fn set_dock(&self, label: &str) {
+        if let Ok(mut w) = self.windows.lock() {
+            if let Some(e) = w.get_mut(label) {
+                e.dock = true;
+            }
+        }
+    }
+
+    /// Forget a closed window.
+    pub fn remove(&self, label: &str) -> Option<DetachedPanelClosed> {
+        let entry = self.windows.lock().ok()?.remove(label)?;
+        Some(DetachedPanelClosed {
+            kind: entry.panel.kind,
+            id: entry.panel.id,
+            dock: entry.dock,
+        })
+    }
+}
+
+/// True for labels of windows this module creates.
+pub fn is_detached_label(label: &str) -> bool {
+    label.starts_with(LABEL_PREFIX)
+}
+
+/// Open (or focus) the window for `panel`.
+///
+/// Must be called from `spawn_blocking` on Windows to avoid deadlock.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub fn open_window(app: &tauri::AppHandle, panel: DetachedPanel, title: &str) -> Result<(), String> {
+    use tauri::{Emitter, Manager, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+
+    let label = panel.label();
+    if let Some(window) = app.get_webview_window(&label) {
+        return window.set_focus().map_err(|e| e.to_string());
+    }
+
+    let state = app.state::<crate::tauri_state::AppState>();
+    state.detached_panels.insert(label.clone(), panel);
+
+    let window = WebviewWindowBuilder::new(app, &label, WebviewUrl::App("index.html".into()))
+        .title(title)
+        .inner_size(720.0, 600.0)
+        .min_inner_size(360.0, 280.0)
+        // App pages only: a link clicked in a rendered document must not
+        // turn a trusted window into one showing external content.
+        .on_navigation(|url| {
+            matches!(url.scheme(), "tauri" | "asset")
+                || matches!(url.host_str(), Some("localhost" | "tauri.localhost"))
+        })
+        .build()
+        .map_err(|e| {
+            state.detached_panels.remove(&label);
+            e.to_string()
+        })?;
+
+    let handle = app.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            let state = handle.state::<crate::tauri_state::AppState>();
+            if let Some(closed) = state.detached_panels.remove(&label) {
+                let _ = handle.emit_to("main", "detached-panel-closed", closed);
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(id: &str) -> DetachedPanel {
+        DetachedPanel {
+            kind: PanelKind::Document,
+            id: id.into(),
+        }
+    }
+
+    #[test]
+    fn labels_are_prefixed_and_sanitized() {
+        let label = doc("document:ab-9").label();
+        assert_eq!(label, "detached-document-document_ab_9");
+        assert!(is_detached_label(&label));
+        assert!(!is_detached_label("main"));
+        assert!(!is_detached_label("browser"));
+        let inbox = DetachedPanel {
+            kind: PanelKind::Inbox,
+            id: String::new(),
+        };
+        assert_eq!(inbox.label(), "detached-inbox");
+    }
+
+    #[test]
+    fn remove_reports_dock_request() {
+        let panels = DetachedPanels::default();
+        panels.insert("a".into(), doc("document:a"));
+        panels.insert("b".into(), doc("document:b"));
+        panels.set_dock("b");
+        assert_eq!(panels.get("a"), Some(doc("document:a")));
+        assert!(!panels.remove("a").unwrap().dock);
+        assert!(panels.remove("b").unwrap().dock);
+        assert!(panels.remove("b").is_none());
+        assert!(panels.get("a").is_none());
+    }
+}
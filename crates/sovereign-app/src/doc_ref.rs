@@ -0,0 +1,152 @@
+//! Document references on the command line.
+//!
+//! CLI commands take a document as its full record id (`document:9f3k…`),
+//! a unique prefix of the id's key (`9f3k`), or its title. Titles are
+//! matched exactly first, then as a substring, ignoring case. When a
+//! reference fits several documents an interactive terminal is asked to
+//! pick one; anywhere else the command fails with the candidates listed.
+
+use std::io::{IsTerminal, Write};
+
+use anyhow::{bail, Result};
+use sovereign_db::schema::Document;
+use sovereign_db::GraphDB;
+
+/// Candidates listed (or offered) for an ambiguous reference.
+const MAX_CHOICES: usize = 20;
+
+/// Resolve `reference` to a document's record id.
+pub async fn resolve(db: &dyn GraphDB, reference: &str) -> Result<String> {
+    let reference = reference.trim();
+    if reference.starts_with("document:") && db.get_document(reference).await.is_ok() {
+        return Ok(reference.to_string());
+    }
+    let docs = db.list_documents(None).await?;
+    let matches = candidates(&docs, reference);
+    match matches.as_slice() {
+        [] => bail!("No document matches '{reference}'"),
+        [only] => Ok(only.id_string().unwrap_or_default()),
+        _ if std::io::stdin().is_terminal() => choose(reference, &matches),
+        _ => bail!(
+            "'{reference}' matches {} documents; use one of these ids:\n{}",
+            matches.len(),
+            listing(&matches)
+        ),
+    }
+}
+
+/// The documents `reference` could mean, most specific kind of match
+/// first: id prefix, then exact title, then title substring.
+pub fn candidates<'a>(docs: &'a [Document], reference: &str) -> Vec<&'a Document> {
+    let key = reference.strip_prefix("document:").unwrap_or(reference);
+    let with_key_prefix: Vec<_> = docs
+        .iter()
+        .filter(|d| {
+            d.id_string()
+                .and_then(|id| id.strip_prefix("document:").map(str::to_string))
+                .is_some_and(|k| !key.is_empty() && k.starts_with(key))
+        })
+        .collect();
+    if !with_key_prefix.is_empty() {
+        return with_key_prefix;
+    }
+
+    let needle = reference.to_lowercase();
+    let exact: Vec<_> = docs
+        .iter()
+        .filter(|d| d.title.to_lowercase() == needle)
+        .collect();
+    if !exact.is_empty() || needle.is_empty() {
+        return exact;
+    }
+    docs.iter()
+        .filter(|d| d.title.to_lowercase().contains(&needle))
+        .collect()
+}
+
+pub fn listing(matches: &[&Document]) -> String {
+    let mut out: Vec<String> = matches
+        .iter()
+        .take(MAX_CHOICES)
+        .enumerate()
+        .map(|(i, d)| {
+            format!(
+                "  [{}] {}\t{}",
+                i + 1,
+                d.id_string().unwrap_or_default(),
+                d.title
+            )
+        })
+        .collect();
+    if matches.len() > MAX_CHOICES {
+        out.push(format!(
+            "  … and {} more; narrow it down",
+            matches.len() - MAX_CHOICES
+        ));
+    }
+    out.join("\n")
+}
+
+/// Ask which of `matches` was meant.
+fn choose(reference: &str, matches: &[&Document]) -> Result<String> {
+    eprintln!("'{reference}' matches several documents:");
+    eprintln!("{}", listing(matches));
+    let shown = matches.len().min(MAX_CHOICES);
+    loop {
+        eprint!("Which one? [1-{shown}, Enter to cancel] ");
+        std::io::stderr().flush()?;
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line)?;
+        let line = line.trim();
+        if line.is_empty() {
+            bail!("Cancelled");
+        }
+        match line.parse::<usize>() {
+            Ok(n) if (1..=shown).contains(&n) => {
+                return Ok(matches[n - 1].id_string().unwrap_or_default());
+            }
+            _ => eprintln!("Enter a number from 1 to {shown}."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_db::mock::MockGraphDB;
+
+    async fn seeded() -> (MockGraphDB, Vec<String>) {
+        let db = MockGraphDB::new();
+        let mut ids = Vec::new();
+        for title in ["Roadmap", "Roadmap review", "Budget"] {
+            let doc = db
+                .create_document(Document::new(title.into(), "thread:t".into(), true))
+                .await
+                .unwrap();
+            ids.push(doc.id_string().unwrap());
+        }
+        (db, ids)
+    }
+
+    #[tokio::test]
+    async fn resolves_ids_prefixes_and_titles() {
+        let (db, ids) = seeded().await;
+        assert_eq!(resolve(&db, &ids[2]).await.unwrap(), ids[2]);
+        let key = ids[1].strip_prefix("document:").unwrap();
+        assert_eq!(resolve(&db, key).await.unwrap(), ids[1]);
+        // An exact title wins over titles that merely contain it.
+        assert_eq!(resolve(&db, "roadmap").await.unwrap(), ids[0]);
+        assert_eq!(resolve(&db, "budg").await.unwrap(), ids[2]);
+        assert!(resolve(&db, "minutes").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn ambiguous_titles_list_the_candidates() {
+        let (db, _) = seeded().await;
+        let docs = db.list_documents(None).await.unwrap();
+        let matches = candidates(&docs, "road");
+        assert_eq!(matches.len(), 2);
+        assert!(listing(&matches).contains("Roadmap review"));
+        assert!(candidates(&docs, "").is_empty());
+    }
+}
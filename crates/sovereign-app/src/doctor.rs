@@ -0,0 +1,675 @@
+//! `sovereign doctor`: check an installation and say how to fix what's off.
+//!
+//! Each area (database, crypto files, models, skills, P2P, comms) adds
+//! findings to a [`Report`]; nothing is repaired. The checks run against the
+//! files on disk, so a running app or daemon can make some of them (the
+//! database lock, the P2P port) report as busy rather than broken.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use sovereign_core::config::AppConfig;
+use sovereign_db::GraphDB;
+
+use crate::setup::create_db;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Skip,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug)]
+pub struct Finding {
+    pub area: &'static str,
+    pub status: Status,
+    pub detail: String,
+    /// What to do about a warning or failure.
+    pub fix: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub findings: Vec<Finding>,
+}
+
+impl Report {
+    fn push(&mut self, area: &'static str, status: Status, detail: String, fix: Option<String>) {
+        self.findings.push(Finding {
+            area,
+            status,
+            detail,
+            fix,
+        });
+    }
+
+    pub(crate) fn ok(&mut self, area: &'static str, detail: impl Into<String>) {
+        self.push(area, Status::Ok, detail.into(), None);
+    }
+
+    pub(crate) fn skip(&mut self, area: &'static str, detail: impl Into<String>) {
+        self.push(area, Status::Skip, detail.into(), None);
+    }
+
+    pub(crate) fn warn(
+        &mut self,
+        area: &'static str,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) {
+        self.push(area, Status::Warn, detail.into(), Some(fix.into()));
+    }
+
+    pub(crate) fn fail(
+        &mut self,
+        area: &'static str,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) {
+        self.push(area, Status::Fail, detail.into(), Some(fix.into()));
+    }
+
+    fn count(&self, status: Status) -> usize {
+        self.findings.iter().filter(|f| f.status == status).count()
+    }
+
+    pub fn failures(&self) -> usize {
+        self.count(Status::Fail)
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for f in &self.findings {
+            let tag = match f.status {
+                Status::Ok => "ok",
+                Status::Skip => "skip",
+                Status::Warn => "warn",
+                Status::Fail => "FAIL",
+            };
+            out.push_str(&format!("  {tag:<5} {:<9} {}\n", f.area, f.detail));
+            if let Some(fix) = &f.fix {
+                out.push_str(&format!("        {:<9} fix: {fix}\n", ""));
+            }
+        }
+        let (warnings, failures) = (self.count(Status::Warn), self.failures());
+        out.push_str(&match (warnings, failures) {
+            (0, 0) => "\nEverything looks fine.\n".to_string(),
+            _ => format!(
+                "\n{warnings} warning{}, {failures} failure{}\n",
+                plural(warnings),
+                plural(failures)
+            ),
+        });
+        out
+    }
+}
+
+fn plural(n: usize) -> &'static str {
+    if n == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+/// Run every check. `unlock` also asks for the passphrase to prove the
+/// auth store and key database open.
+pub async fn run(config: &AppConfig, unlock: bool) -> Report {
+    let mut report = Report::default();
+    let profile_dir = sovereign_core::sovereign_dir();
+
+    let db = check_database(config, &mut report).await;
+
+    #[cfg(feature = "encryption")]
+    check_crypto(config, &profile_dir.join("crypto"), unlock, &mut report).await;
+    #[cfg(not(feature = "encryption"))]
+    {
+        let _ = unlock;
+        report.skip("Crypto", "encryption is not compiled into this build");
+    }
+
+    check_models(config, &mut report);
+    check_skills(
+        &profile_dir.join("skills"),
+        &profile_dir.join("trusted_skill_keys.json"),
+        &mut report,
+    );
+
+    #[cfg(feature = "p2p")]
+    check_p2p_port(&config.p2p, &mut report);
+    #[cfg(not(feature = "p2p"))]
+    report.skip("P2P", "P2P is not compiled into this build");
+
+    #[cfg(feature = "comms")]
+    check_comms(config, db, &mut report).await;
+    #[cfg(not(feature = "comms"))]
+    {
+        let _ = db;
+        report.skip("Comms", "comms is not compiled into this build");
+    }
+
+    report
+}
+
+async fn check_database(config: &AppConfig, report: &mut Report) -> Option<Arc<dyn GraphDB>> {
+    let db = match create_db(config).await {
+        Ok(db) => db,
+        Err(e) => {
+            let msg = format!("{e:#}");
+            let fix = if msg.contains("lock") {
+                "the database is in use: close the app or stop `sovereign daemon`, then re-run"
+            } else {
+                "check database.path in config.toml and that its directory is writable"
+            };
+            report.fail("Database", format!("cannot open: {msg}"), fix);
+            return None;
+        }
+    };
+    // Opening applies the (idempotent) schema indexes; there is no separate
+    // schema version to compare.
+    let (threads, docs) = match (db.list_threads().await, db.list_documents(None).await) {
+        (Ok(t), Ok(d)) => (t, d),
+        (Err(e), _) | (_, Err(e)) => {
+            report.fail(
+                "Database",
+                format!("opened, but reading failed: {e}"),
+                "restore from an export (`sovereign import`) or a backup",
+            );
+            return None;
+        }
+    };
+    report.ok(
+        "Database",
+        format!(
+            "{} mode, schema indexes current; {} threads, {} documents",
+            config.database.mode,
+            threads.len(),
+            docs.len()
+        ),
+    );
+
+    let thread_ids: std::collections::HashSet<String> = threads
+        .iter()
+        .filter_map(|t| t.id.as_ref().map(sovereign_db::schema::thing_to_raw))
+        .collect();
+    let orphans = docs
+        .iter()
+        .filter(|d| !thread_ids.contains(&d.thread_id))
+        .count();
+    if orphans > 0 {
+        report.warn(
+            "Database",
+            format!("{orphans} documents belong to a thread that no longer exists"),
+            "move them to a thread in the app, or with `sovereign update-doc`",
+        );
+    }
+    Some(Arc::new(db))
+}
+
+#[cfg(feature = "encryption")]
+async fn check_crypto(config: &AppConfig, crypto_dir: &Path, unlock: bool, report: &mut Report) {
+    use sovereign_crypto::auth::AuthStore;
+
+    let auth_path = crypto_dir.join("auth.store");
+    if !auth_path.exists() {
+        report.warn(
+            "Crypto",
+            "no auth.store: onboarding hasn't been completed on this device",
+            "open the app and finish onboarding (or pair with an existing device)",
+        );
+        return;
+    }
+    let store = match AuthStore::load(&auth_path) {
+        Ok(store) => store,
+        Err(e) => {
+            report.fail(
+                "Crypto",
+                format!("auth.store unreadable: {e}"),
+                "restore crypto/ from a backup, or recover with your recovery phrase",
+            );
+            return;
+        }
+    };
+    report.ok(
+        "Crypto",
+        format!(
+            "auth.store loads{}",
+            if store.requires_second_factor() {
+                " (security key enrolled)"
+            } else {
+                ""
+            }
+        ),
+    );
+
+    for file in ["salt", "device_id"] {
+        if !crypto_dir.join(file).exists() {
+            report.fail(
+                "Crypto",
+                format!("crypto/{file} is missing"),
+                "restore crypto/ from a backup; without it existing keys can't be derived",
+            );
+        }
+    }
+    if !crypto_dir.join("keys.db").exists() {
+        report.warn(
+            "Crypto",
+            "crypto/keys.db is missing: documents written encrypted can't be read",
+            "restore crypto/ from a backup, or re-sync from a paired device",
+        );
+    }
+
+    let attempts = crate::login_throttle::LoginAttempts::load(crypto_dir);
+    if let Some(remaining) = attempts.is_locked(
+        config.crypto.max_login_attempts,
+        config.crypto.lockout_seconds,
+    ) {
+        report.warn(
+            "Crypto",
+            format!("login is locked out for another {remaining}s after failed attempts"),
+            "wait for the lockout to pass before logging in",
+        );
+        if unlock {
+            report.skip("Crypto", "passphrase check skipped during the lockout");
+        }
+        return;
+    }
+
+    if unlock {
+        check_unlock(config, crypto_dir, &store, attempts, report).await;
+    } else {
+        report.skip(
+            "Crypto",
+            "decryption not tested (pass --unlock to try your passphrase)",
+        );
+    }
+}
+
+/// Authenticate and open the key database. Counts against the login lockout
+/// like any other attempt, and doesn't say which persona opened.
+#[cfg(feature = "encryption")]
+async fn check_unlock(
+    config: &AppConfig,
+    crypto_dir: &Path,
+    store: &sovereign_crypto::auth::AuthStore,
+    mut attempts: crate::login_throttle::LoginAttempts,
+    report: &mut Report,
+) {
+    let passphrase = match rpassword::prompt_password("Sovereign passphrase: ") {
+        Ok(p) => zeroize::Zeroizing::new(p),
+        Err(e) => {
+            report.skip("Crypto", format!("passphrase check skipped: {e}"));
+            return;
+        }
+    };
+    let result = match &store.second_factor {
+        Some(factor) => {
+            eprintln!("Touch your security key when it blinks.");
+            match crate::tauri_commands::auth::read_security_key(factor).await {
+                Ok(secret) => store.authenticate_with_second_factor(passphrase.as_bytes(), &secret),
+                Err(e) => {
+                    report.fail("Crypto", e, "plug in the enrolled security key and retry");
+                    return;
+                }
+            }
+        }
+        None => store.authenticate(passphrase.as_bytes()),
+    };
+    let auth = match result {
+        Ok(auth) => {
+            attempts.reset();
+            auth
+        }
+        Err(_) => {
+            attempts.record_failure();
+            let _ = attempts.save(crypto_dir);
+            report.fail(
+                "Crypto",
+                "the passphrase was not accepted",
+                format!(
+                    "retry; {} attempts lock login for {}s",
+                    config.crypto.max_login_attempts, config.crypto.lockout_seconds
+                ),
+            );
+            return;
+        }
+    };
+    let _ = attempts.save(crypto_dir);
+    match crate::setup::complete_auth(auth.persona, &auth.device_key, &auth.kek) {
+        Ok(_) => report.ok("Crypto", "passphrase accepted and the key database opens"),
+        Err(e) => report.fail(
+            "Crypto",
+            format!("passphrase accepted but the key database won't open: {e}"),
+            "restore crypto/keys*.db from a backup, or re-sync from a paired device",
+        ),
+    }
+}
+
+fn check_models(config: &AppConfig, report: &mut Report) {
+    let dir = Path::new(&config.ai.model_dir);
+    if !dir.is_dir() {
+        report.fail(
+            "Models",
+            format!("model directory {} doesn't exist", dir.display()),
+            "create it and add a GGUF model, or set ai.model_dir in config.toml",
+        );
+        return;
+    }
+
+    let mut needed_mb = 0u64;
+    for (role, name) in [
+        ("router", &config.ai.router_model),
+        ("reasoning", &config.ai.reasoning_model),
+    ] {
+        let path = dir.join(name);
+        let Ok(meta) = std::fs::metadata(&path) else {
+            report.fail(
+                "Models",
+                format!("{role} model {name} not found in {}", dir.display()),
+                format!("put {name} in that directory, or pick another model in Settings > Models"),
+            );
+            continue;
+        };
+        needed_mb += meta.len() / (1024 * 1024);
+        match sovereign_ai::model_integrity::verify_path(&path.to_string_lossy()) {
+            Ok(_) => report.ok(
+                "Models",
+                format!("{role} model {name} ({} MB)", meta.len() / (1024 * 1024)),
+            ),
+            Err(e) => report.fail(
+                "Models",
+                format!("{role} model {name} failed its integrity check: {e}"),
+                "delete it and download it again from a trusted source",
+            ),
+        }
+    }
+
+    if config.ai.n_gpu_layers == 0 {
+        report.ok("Models", "CPU inference (ai.n_gpu_layers = 0)");
+        return;
+    }
+    match nvidia_vram_mb() {
+        Some((free, total)) if free < needed_mb => report.warn(
+            "Models",
+            format!("{free} of {total} MB VRAM free; the models need about {needed_mb} MB"),
+            "close other GPU programs or lower ai.n_gpu_layers to offload fewer layers",
+        ),
+        Some((free, total)) => {
+            report.ok("Models", format!("{free} of {total} MB VRAM free"));
+        }
+        None => report.skip(
+            "Models",
+            "GPU memory not checked (no nvidia-smi); inference falls back to CPU if offload fails",
+        ),
+    }
+}
+
+/// Free and total memory of the first NVIDIA GPU, in MB.
+fn nvidia_vram_mb() -> Option<(u64, u64)> {
+    let output = std::process::Command::new("nvidia-smi")
+        .args([
+            "--query-gpu=memory.free,memory.total",
+            "--format=csv,noheader,nounits",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    let (free, total) = text.lines().next()?.split_once(',')?;
+    Some((free.trim().parse().ok()?, total.trim().parse().ok()?))
+}
+
+fn check_skills(skills_dir: &Path, trusted_keys: &Path, report: &mut Report) {
+    let Ok(entries) = std::fs::read_dir(skills_dir) else {
+        report.ok("Skills", "no community skills installed");
+        return;
+    };
+    let mut broken = 0;
+    for entry in entries.flatten() {
+        let manifest = entry.path().join("skill.json");
+        if !manifest.exists() {
+            continue;
+        }
+        if let Err(e) = sovereign_skills::SkillManifest::load(&manifest) {
+            broken += 1;
+            report.fail(
+                "Skills",
+                format!("{} is invalid: {e}", manifest.display()),
+                "fix the manifest, or remove the skill's directory",
+            );
+        }
+    }
+
+    let mut registry = sovereign_skills::SkillRegistry::new();
+    match sovereign_skills::TrustedKeys::load(trusted_keys) {
+        Ok(keys) => registry.set_trusted_keys(keys),
+        Err(e) => report.warn(
+            "Skills",
+            format!("trusted_skill_keys.json unreadable: {e}"),
+            "fix or delete the file; until then no skill counts as signed",
+        ),
+    }
+    if registry.scan_directory(skills_dir).is_err() {
+        return;
+    }
+    let mut unsigned = 0;
+    for manifest in registry.manifests() {
+        match registry.verification(&manifest.name) {
+            Some(sovereign_skills::SkillVerification::Tampered) => report.fail(
+                "Skills",
+                format!(
+                    "{} doesn't match its signature and won't run",
+                    manifest.name
+                ),
+                "reinstall it from its publisher, or remove it",
+            ),
+            Some(sovereign_skills::SkillVerification::Verified { .. }) => {}
+            _ => unsigned += 1,
+        }
+    }
+    let loaded = registry.manifests().len();
+    if loaded > 0 || broken == 0 {
+        report.ok(
+            "Skills",
+            format!(
+                "{loaded} community skills ({unsigned} unsigned, limited to low-risk capabilities)"
+            ),
+        );
+    }
+}
+
+/// The node listens on QUIC (UDP). A fixed port that can't be bound is
+/// taken, most often by the running app itself.
+#[cfg(feature = "p2p")]
+fn check_p2p_port(p2p: &sovereign_core::config::P2pConfig, report: &mut Report) {
+    if !p2p.enabled {
+        report.skip("P2P", "sync disabled (p2p.enabled = false)");
+        return;
+    }
+    if p2p.listen_port == 0 {
+        report.ok("P2P", "listens on a random UDP port each start");
+        return;
+    }
+    match std::net::UdpSocket::bind(("0.0.0.0", p2p.listen_port)) {
+        Ok(_) => report.ok("P2P", format!("UDP port {} is free to listen on", p2p.listen_port)),
+        Err(e) => report.warn(
+            "P2P",
+            format!("UDP port {} can't be bound: {e}", p2p.listen_port),
+            "if the app or daemon is running this is expected; otherwise pick another p2p.listen_port",
+        ),
+    }
+}
+
+#[cfg(feature = "comms")]
+async fn check_comms(config: &AppConfig, db: Option<Arc<dyn GraphDB>>, report: &mut Report) {
+    if !config.comms.enabled {
+        report.skip("Comms", "messaging disabled (comms.enabled = false)");
+        return;
+    }
+    let cfg = match crate::comms_startup::load_comms_config() {
+        Ok(Some(cfg)) => cfg,
+        Ok(None) => {
+            report.warn(
+                "Comms",
+                "comms.enabled is set but there is no comms.toml",
+                "add your accounts to ~/.sovereign/comms.toml, or turn comms off",
+            );
+            return;
+        }
+        Err(e) => {
+            report.fail("Comms", e, "fix the syntax in ~/.sovereign/comms.toml");
+            return;
+        }
+    };
+
+    if let Some(email) = cfg.email {
+        match std::env::var("SOVEREIGN_EMAIL_PASSWORD") {
+            Err(_) => report.fail(
+                "Comms",
+                format!(
+                    "email {}: SOVEREIGN_EMAIL_PASSWORD is unset",
+                    email.username
+                ),
+                "export the account's (app) password before starting Sovereign",
+            ),
+            #[cfg(feature = "comms-email")]
+            Ok(password) => check_email_login(email, db.clone(), password, report).await,
+            #[cfg(not(feature = "comms-email"))]
+            Ok(_) => report.skip(
+                "Comms",
+                format!(
+                    "email {}: login not tested (email support not compiled in)",
+                    email.username
+                ),
+            ),
+        }
+    }
+    if let Some(signal) = cfg.signal {
+        if Path::new(&signal.store_path).is_dir() {
+            report.ok(
+                "Comms",
+                format!("Signal {}: device store present", signal.phone_number),
+            );
+        } else {
+            report.fail(
+                "Comms",
+                format!(
+                    "Signal {}: no device store at {}",
+                    signal.phone_number, signal.store_path
+                ),
+                "link Sovereign as a device from the Signal app on your phone",
+            );
+        }
+    }
+    if let Some(whatsapp) = cfg.whatsapp {
+        if std::env::var("SOVEREIGN_WHATSAPP_TOKEN").is_ok_and(|t| !t.is_empty()) {
+            report.ok(
+                "Comms",
+                format!(
+                    "WhatsApp {}: token set (not verified against the API)",
+                    whatsapp.phone_number_id
+                ),
+            );
+        } else {
+            report.fail(
+                "Comms",
+                format!(
+                    "WhatsApp {}: SOVEREIGN_WHATSAPP_TOKEN is unset",
+                    whatsapp.phone_number_id
+                ),
+                "export the Cloud API access token before starting Sovereign",
+            );
+        }
+    }
+    let _ = db;
+}
+
+/// Log in to IMAP and straight back out.
+#[cfg(feature = "comms-email")]
+async fn check_email_login(
+    email: sovereign_comms::EmailAccountConfig,
+    db: Option<Arc<dyn GraphDB>>,
+    password: String,
+    report: &mut Report,
+) {
+    use sovereign_comms::{CommsError, CommunicationChannel};
+
+    let Some(db) = db else {
+        report.skip("Comms", "email login not tested without a database");
+        return;
+    };
+    let user = email.username.clone();
+    let host = format!("{}:{}", email.imap_host, email.imap_port);
+    let mut channel = sovereign_comms::channels::email::EmailChannel::new(email, db, password);
+    let timeout = std::time::Duration::from_secs(20);
+    match tokio::time::timeout(timeout, channel.connect()).await {
+        Ok(Ok(())) => report.ok("Comms", format!("email {user}: IMAP login works")),
+        Ok(Err(CommsError::AuthFailed(e))) => report.fail(
+            "Comms",
+            format!("email {user}: login refused: {e}"),
+            "check SOVEREIGN_EMAIL_PASSWORD (many providers need an app password)",
+        ),
+        Ok(Err(e)) => report.fail(
+            "Comms",
+            format!("email {user}: can't reach {host}: {e}"),
+            "check imap_host and imap_port in comms.toml and your network",
+        ),
+        Err(_) => report.fail(
+            "Comms",
+            format!(
+                "email {user}: {host} didn't answer within {}s",
+                timeout.as_secs()
+            ),
+            "check imap_host and imap_port in comms.toml and your network",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_counts_and_renders_fixes() {
+        let mut report = Report::default();
+        report.ok("Database", "fine");
+        report.warn("P2P", "port busy", "stop the app");
+        report.fail("Models", "missing", "download it");
+        assert_eq!(report.failures(), 1);
+        let text = report.render();
+        assert!(text.contains("FAIL  Models"));
+        assert!(text.contains("fix: stop the app"));
+        assert!(text.ends_with("1 warning, 1 failure\n"));
+
+        let mut clean = Report::default();
+        clean.ok("Database", "fine");
+        assert!(clean.render().ends_with("Everything looks fine.\n"));
+    }
+
+    #[test]
+    fn broken_skill_manifests_fail() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = dir.path().join("half-written");
+        std::fs::create_dir(&skill).unwrap();
+        std::fs::write(skill.join("skill.json"), "{ not json").unwrap();
+
+        let mut report = Report::default();
+        check_skills(dir.path(), &dir.path().join("keys.json"), &mut report);
+        assert_eq!(report.failures(), 1);
+        assert!(report.findings[0].detail.contains("half-written"));
+    }
+
+    #[test]
+    fn missing_model_dir_fails() {
+        let mut config = AppConfig::default();
+        config.ai.model_dir = "/nonexistent/sovereign-models".into();
+        let mut report = Report::default();
+        check_models(&config, &mut report);
+        assert_eq!(report.failures(), 1);
+        assert!(report.findings[0].fix.is_some());
+    }
+}
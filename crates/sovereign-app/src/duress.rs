@@ -6,6 +6,19 @@ use sovereign_db::schema::{
 use sovereign_db::surreal::SurrealGraphDB;
 use sovereign_db::GraphDB;
 
+/// Open the duress persona's database, seeding it on first use.
+pub async fn open_duress_db(config: &sovereign_core::config::AppConfig) -> Result<SurrealGraphDB> {
+    let mut duress_config = config.clone();
+    duress_config.database.path =
+        crate::setup::persona_db_path(config, sovereign_core::auth::PersonaKind::Duress);
+    let db = crate::setup::create_db(&duress_config).await?;
+    // Idempotent — no-op once populated.
+    if let Err(e) = seed_duress_db(&db).await {
+        tracing::warn!("duress decoy seed failed (continuing): {e}");
+    }
+    Ok(db)
+}
+
 /// Seed the duress persona database with plausible but innocuous data.
 /// Called when the duress password is used and the duress DB is empty.
 #[allow(dead_code)]
@@ -0,0 +1,241 @@
+//! Crash recovery for the editor — a swap file per document.
+//!
+//! While a panel has unsaved edits the frontend journals its title and body
+//! here; `save_document` removes the entry once the save lands. Whatever is
+//! left after a crash is offered back on the next launch. The journal also
+//! holds the auto-commit engine's pending list, so edits that were saved
+//! but not yet committed get their commit after the next unlock.
+//!
+//! Entries are sealed under the account key when a session has one, so the
+//! directory is unreadable without the passphrase.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::tauri_state::AppState;
+
+const JOURNAL_DIR: &str = "edit_journal";
+const ENTRY_EXT: &str = "swp";
+const PENDING_COMMITS_FILE: &str = "pending_commits.json";
+
+/// Where the auto-commit engine keeps the documents it still has to commit.
+pub fn pending_commits_path(profile_dir: &Path) -> PathBuf {
+    profile_dir.join(JOURNAL_DIR).join(PENDING_COMMITS_FILE)
+}
+
+/// A panel's unsaved state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub doc_id: String,
+    pub title: String,
+    pub body: String,
+    pub written_at: DateTime<Utc>,
+}
+
+/// An entry as it sits on disk.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Stored {
+    Plain(JournalEntry),
+    #[cfg(feature = "encryption")]
+    Sealed(sovereign_crypto::vault::EncryptedBlob),
+}
+
+pub struct EditJournal {
+    dir: PathBuf,
+    #[cfg(feature = "encryption")]
+    key: Option<std::sync::Arc<sovereign_crypto::account_key::AccountKey>>,
+}
+
+impl EditJournal {
+    /// A journal in `profile_dir` that writes entries in the clear.
+    pub fn open(profile_dir: &Path) -> Self {
+        Self {
+            dir: profile_dir.join(JOURNAL_DIR),
+            #[cfg(feature = "encryption")]
+            key: None,
+        }
+    }
+
+    /// The session's journal, sealed under its account key if it has one.
+    pub async fn for_session(state: &AppState) -> Self {
+        #[allow(unused_mut)]
+        let mut journal = Self::open(&state.profile_dir);
+        #[cfg(feature = "encryption")]
+        {
+            journal.key = state.account_key().await;
+        }
+        journal
+    }
+
+    fn entry_path(&self, doc_id: &str) -> PathBuf {
+        let name: String = doc_id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        self.dir.join(format!("{name}.{ENTRY_EXT}"))
+    }
+
+    /// Record `entry`, replacing the document's previous one.
+    pub fn write(&self, entry: &JournalEntry) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_vec(&self.seal(entry)?)?;
+        write_atomic(&self.entry_path(&entry.doc_id), &json)?;
+        Ok(())
+    }
+
+    /// Forget the document's entry: its edits were saved or discarded.
+    pub fn remove(&self, doc_id: &str) {
+        match std::fs::remove_file(self.entry_path(doc_id)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => tracing::warn!("Could not clear edit journal for {doc_id}: {e}"),
+        }
+    }
+
+    /// Every entry this journal can read, oldest first. Entries it can't
+    /// (corrupt, or sealed under another key) are skipped and left alone.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        let Ok(dir) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut entries: Vec<JournalEntry> = dir
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == ENTRY_EXT))
+            .filter_map(|p| match self.read(&p) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    tracing::warn!("Skipping unreadable edit journal {}: {e}", p.display());
+                    None
+                }
+            })
+            .collect();
+        entries.sort_by_key(|e| e.written_at);
+        entries
+    }
+
+    fn read(&self, path: &Path) -> Result<JournalEntry> {
+        let stored: Stored = serde_json::from_slice(&std::fs::read(path)?)?;
+        self.unseal(stored)
+    }
+
+    #[cfg(feature = "encryption")]
+    fn seal(&self, entry: &JournalEntry) -> Result<Stored> {
+        Ok(match &self.key {
+            Some(key) => {
+                let json = zeroize::Zeroizing::new(serde_json::to_vec(entry)?);
+                Stored::Sealed(sovereign_crypto::vault::EncryptedBlob::encrypt(&json, key)?)
+            }
+            None => Stored::Plain(entry.clone()),
+        })
+    }
+
+    #[cfg(not(feature = "encryption"))]
+    fn seal(&self, entry: &JournalEntry) -> Result<Stored> {
+        Ok(Stored::Plain(entry.clone()))
+    }
+
+    fn unseal(&self, stored: Stored) -> Result<JournalEntry> {
+        match stored {
+            Stored::Plain(entry) => Ok(entry),
+            #[cfg(feature = "encryption")]
+            Stored::Sealed(blob) => {
+                let key = self
+                    .key
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("sealed, and the session is locked"))?;
+                let json = zeroize::Zeroizing::new(blob.decrypt(key)?);
+                Ok(serde_json::from_slice(&json)?)
+            }
+        }
+    }
+}
+
+/// Owner-only write through a temp sibling, so a crash mid-write leaves
+/// the previous entry rather than half of the new one.
+fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let tmp = path.with_extension("tmp");
+    let mut opts = std::fs::OpenOptions::new();
+    opts.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        opts.mode(0o600);
+    }
+    opts.open(&tmp)?.write_all(bytes)?;
+    std::fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(doc_id: &str, body: &str, secs: i64) -> JournalEntry {
+        JournalEntry {
+            doc_id: doc_id.into(),
+            title: "Draft".into(),
+            body: body.into(),
+            written_at: DateTime::from_timestamp(secs, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn entries_survive_until_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = EditJournal::open(dir.path());
+        assert!(journal.entries().is_empty());
+
+        journal.write(&entry("document:b", "second", 20)).unwrap();
+        journal.write(&entry("document:a", "first", 10)).unwrap();
+        journal
+            .write(&entry("document:b", "second, edited", 30))
+            .unwrap();
+        let bodies: Vec<String> = journal.entries().into_iter().map(|e| e.body).collect();
+        assert_eq!(bodies, ["first", "second, edited"]);
+
+        journal.remove("document:a");
+        journal.remove("document:missing");
+        assert_eq!(
+            journal.entries(),
+            vec![entry("document:b", "second, edited", 30)]
+        );
+    }
+
+    #[test]
+    fn corrupt_entries_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = EditJournal::open(dir.path());
+        journal.write(&entry("document:a", "kept", 10)).unwrap();
+        std::fs::write(journal.entry_path("document:bad"), b"{half").unwrap();
+        std::fs::write(pending_commits_path(dir.path()), b"[]").unwrap();
+        assert_eq!(journal.entries(), vec![entry("document:a", "kept", 10)]);
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn sealed_entries_need_the_key() {
+        use sovereign_crypto::account_key::AccountKey;
+        use std::sync::Arc;
+
+        let dir = tempfile::tempdir().unwrap();
+        let sealed = EditJournal {
+            key: Some(Arc::new(AccountKey::from_bytes([7; 32]))),
+            ..EditJournal::open(dir.path())
+        };
+        sealed
+            .write(&entry("document:a", "secret plans", 10))
+            .unwrap();
+
+        let raw = std::fs::read_to_string(sealed.entry_path("document:a")).unwrap();
+        assert!(!raw.contains("secret plans"));
+        assert_eq!(sealed.entries()[0].body, "secret plans");
+        // Locked: the entry is kept but can't be read.
+        assert!(EditJournal::open(dir.path()).entries().is_empty());
+    }
+}
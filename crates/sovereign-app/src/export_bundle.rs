@@ -0,0 +1,251 @@
+//! Workspace export archives and encrypted export bundles.
+//!
+//! `sovereign export --all` writes a zip holding `workspace.json` (every
+//! thread and document) plus each document rendered with the chosen export
+//! skill. With `--encrypt` the output is a standard age file — sealed to
+//! X25519 recipients (`age1…`) or to a passphrase — so an off-machine backup
+//! never sits in plaintext and still opens with the stock `age` tool.
+//! `sovereign import` reads the archive back, decrypting it first if needed.
+
+use std::io::{Cursor, Read, Write};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sovereign_db::schema::{DocType, Document, Sensitivity};
+
+/// Name of the manifest entry inside the archive.
+pub const MANIFEST_NAME: &str = "workspace.json";
+
+/// Bumped when the manifest layout changes incompatibly.
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceExport {
+    pub version: u32,
+    pub exported_at: String,
+    pub threads: Vec<ExportedThread>,
+    pub documents: Vec<ExportedDocument>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedThread {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedDocument {
+    pub id: String,
+    pub thread_id: String,
+    pub title: String,
+    /// Raw content JSON, as stored.
+    pub content: String,
+    #[serde(default)]
+    pub is_owned: bool,
+    /// Absent in archives from before document types; those are notes.
+    #[serde(default)]
+    pub doc_type: DocType,
+    /// Absent in archives from before sensitivity levels; those are normal.
+    #[serde(default)]
+    pub sensitivity: Sensitivity,
+    pub created_at: String,
+    pub modified_at: String,
+}
+
+impl ExportedDocument {
+    /// Give a document recreated from this record its handling level, so
+    /// a sealed document doesn't come back readable by the assistant.
+    pub fn restore_sensitivity(&self, doc: &mut Document) {
+        if self.sensitivity != Sensitivity::Normal {
+            doc.sensitivity = self.sensitivity;
+            doc.sensitivity_at = Some(chrono::Utc::now());
+        }
+    }
+}
+
+/// Zip the manifest together with the rendered files (`name`, `bytes`).
+pub fn write_archive(workspace: &WorkspaceExport, rendered: &[(String, Vec<u8>)]) -> Result<Vec<u8>> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = zip::write::FileOptions::default();
+    zip.start_file(MANIFEST_NAME, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(workspace)?)?;
+    for (name, data) in rendered {
+        zip.start_file(format!("documents/{name}"), options)?;
+        zip.write_all(data)?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+/// Read the manifest back out of an archive made by [`write_archive`].
+pub fn read_archive(bytes: &[u8]) -> Result<WorkspaceExport> {
+    let mut zip = zip::ZipArchive::new(Cursor::new(bytes)).context("not a workspace archive")?;
+    let mut manifest = String::new();
+    zip.by_name(MANIFEST_NAME)
+        .with_context(|| format!("archive has no {MANIFEST_NAME}"))?
+        .read_to_string(&mut manifest)?;
+    let workspace: WorkspaceExport = serde_json::from_str(&manifest)?;
+    if workspace.version > FORMAT_VERSION {
+        anyhow::bail!(
+            "archive format v{} is newer than this build supports (v{FORMAT_VERSION})",
+            workspace.version
+        );
+    }
+    Ok(workspace)
+}
+
+/// Who can open an encrypted bundle.
+pub enum SealTo {
+    /// age X25519 recipients (`age1…`).
+    Recipients(Vec<String>),
+    Passphrase(String),
+}
+
+/// How to open an encrypted bundle.
+pub enum Unseal {
+    /// An age identity file (`AGE-SECRET-KEY-1…` lines).
+    IdentityFile(std::path::PathBuf),
+    Passphrase(String),
+}
+
+/// Binary age header. ASCII-armored bundles are not produced or read.
+pub fn is_age(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"age-encryption.org/v1\n")
+}
+
+/// Encrypt `plain` into an age file.
+#[cfg(feature = "encryption")]
+pub fn seal(plain: &[u8], to: &SealTo) -> Result<Vec<u8>> {
+    use age::secrecy::SecretString;
+    use std::str::FromStr;
+
+    let encryptor = match to {
+        SealTo::Recipients(keys) => {
+            if keys.is_empty() {
+                anyhow::bail!("no recipients given");
+            }
+            let recipients = keys
+                .iter()
+                .map(|k| {
+                    age::x25519::Recipient::from_str(k)
+                        .map_err(|e| anyhow::anyhow!("invalid recipient '{k}': {e}"))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            age::Encryptor::with_recipients(recipients.iter().map(|r| r as &dyn age::Recipient))?
+        }
+        SealTo::Passphrase(pass) => {
+            if pass.is_empty() {
+                anyhow::bail!("passphrase cannot be empty");
+            }
+            age::Encryptor::with_user_passphrase(SecretString::from(pass.clone()))
+        }
+    };
+    let mut out = Vec::with_capacity(plain.len() + 256);
+    let mut writer = encryptor.wrap_output(&mut out)?;
+    writer.write_all(plain)?;
+    writer.finish()?;
+    Ok(out)
+}
+
+/// Decrypt an age file made by [`seal`] (or by the `age` tool).
+#[cfg(feature = "encryption")]
+pub fn unseal(sealed: &[u8], with: &Unseal) -> Result<Vec<u8>> {
+    use age::secrecy::SecretString;
+
+    let decryptor = age::Decryptor::new(sealed).context("not an age file")?;
+    let mut reader = match with {
+        Unseal::IdentityFile(path) => {
+            if decryptor.is_scrypt() {
+                anyhow::bail!("bundle is passphrase-encrypted; omit --identity");
+            }
+            let identities = age::IdentityFile::from_file(path.to_string_lossy().into_owned())
+                .with_context(|| format!("reading identity file {}", path.display()))?
+                .into_identities()?;
+            decryptor.decrypt(identities.iter().map(|i| i.as_ref() as &dyn age::Identity))?
+        }
+        Unseal::Passphrase(pass) => {
+            if !decryptor.is_scrypt() {
+                anyhow::bail!("bundle is encrypted to recipients; pass --identity");
+            }
+            let identity = age::scrypt::Identity::new(SecretString::from(pass.clone()));
+            decryptor.decrypt(std::iter::once(&identity as &dyn age::Identity))?
+        }
+    };
+    let mut plain = Vec::new();
+    reader.read_to_end(&mut plain)?;
+    Ok(plain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> WorkspaceExport {
+        WorkspaceExport {
+            version: FORMAT_VERSION,
+            exported_at: "2026-01-01T00:00:00Z".into(),
+            threads: vec![ExportedThread {
+                id: "thread:a".into(),
+                name: "Research".into(),
+                description: String::new(),
+            }],
+            documents: vec![ExportedDocument {
+                id: "document:1".into(),
+                thread_id: "thread:a".into(),
+                title: "Notes".into(),
+                content: r#"{"body":"hello","images":[]}"#.into(),
+                is_owned: true,
+                doc_type: DocType::Spec,
+                sensitivity: Sensitivity::Sealed,
+                created_at: "2026-01-01T00:00:00Z".into(),
+                modified_at: "2026-01-01T00:00:00Z".into(),
+            }],
+        }
+    }
+
+    #[test]
+    fn archive_roundtrip() {
+        let ws = sample();
+        let bytes = write_archive(&ws, &[("Notes.html".into(), b"<p>hello</p>".to_vec())]).unwrap();
+        assert_eq!(read_archive(&bytes).unwrap(), ws);
+        assert!(read_archive(b"not a zip").is_err());
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn recipient_bundle_roundtrip() {
+        use age::secrecy::ExposeSecret;
+
+        let identity = age::x25519::Identity::generate();
+        let dir = tempfile::tempdir().unwrap();
+        let id_path = dir.path().join("key.txt");
+        std::fs::write(&id_path, identity.to_string().expose_secret()).unwrap();
+
+        let to = SealTo::Recipients(vec![identity.to_public().to_string()]);
+        let sealed = seal(b"workspace bytes", &to).unwrap();
+        assert!(is_age(&sealed));
+        assert!(!sealed.windows(9).any(|w| w == b"workspace"));
+
+        let plain = unseal(&sealed, &Unseal::IdentityFile(id_path)).unwrap();
+        assert_eq!(plain, b"workspace bytes");
+        assert!(unseal(&sealed, &Unseal::Passphrase("pw".into())).is_err());
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn passphrase_bundle_roundtrip() {
+        let sealed = seal(b"workspace bytes", &SealTo::Passphrase("correct horse".into())).unwrap();
+        assert!(is_age(&sealed));
+        let plain = unseal(&sealed, &Unseal::Passphrase("correct horse".into())).unwrap();
+        assert_eq!(plain, b"workspace bytes");
+        assert!(unseal(&sealed, &Unseal::Passphrase("wrong".into())).is_err());
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn rejects_bad_recipient() {
+        assert!(seal(b"x", &SealTo::Recipients(vec!["age1nope".into()])).is_err());
+        assert!(seal(b"x", &SealTo::Recipients(vec![])).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! Runtime registry of the optional subsystems.
+//!
+//! Cargo features decide what a binary carries; config switches decide what
+//! of that runs. [`Feature`] puts both behind one name so Settings and the
+//! frontend can ask "is voice available?" instead of each repeating the
+//! `cfg!` and config checks. Whether a subsystem is actually up is read off
+//! `AppState` by `tauri_commands::auth::list_features`.
+
+use sovereign_core::config::AppConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    Encryption,
+    Sync,
+    Comms,
+    Voice,
+    Vision,
+    Browser,
+    WebBrowse,
+    WasmPlugins,
+    Metrics,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 9] = [
+        Feature::Encryption,
+        Feature::Sync,
+        Feature::Comms,
+        Feature::Voice,
+        Feature::Vision,
+        Feature::Browser,
+        Feature::WebBrowse,
+        Feature::WasmPlugins,
+        Feature::Metrics,
+    ];
+
+    /// Stable name used over IPC.
+    pub fn id(self) -> &'static str {
+        match self {
+            Feature::Encryption => "encryption",
+            Feature::Sync => "p2p",
+            Feature::Comms => "comms",
+            Feature::Voice => "voice",
+            Feature::Vision => "vision",
+            Feature::Browser => "browser",
+            Feature::WebBrowse => "web_browse",
+            Feature::WasmPlugins => "wasm_plugins",
+            Feature::Metrics => "metrics",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.id() == id)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Feature::Encryption => "Encryption at rest",
+            Feature::Sync => "Device sync",
+            Feature::Comms => "Email and messaging",
+            Feature::Voice => "Voice",
+            Feature::Vision => "Vision",
+            Feature::Browser => "Embedded browser",
+            Feature::WebBrowse => "Web research skills",
+            Feature::WasmPlugins => "WASM plugins",
+            Feature::Metrics => "Local metrics",
+        }
+    }
+
+    /// Whether this binary was built with the subsystem.
+    pub fn compiled(self) -> bool {
+        match self {
+            Feature::Encryption => cfg!(feature = "encryption"),
+            Feature::Sync => cfg!(feature = "p2p"),
+            Feature::Comms => cfg!(feature = "comms"),
+            Feature::Voice => cfg!(feature = "voice-stt"),
+            Feature::Vision => cfg!(feature = "vision"),
+            Feature::Browser => cfg!(not(any(target_os = "android", target_os = "ios"))),
+            Feature::WebBrowse => cfg!(feature = "web-browse"),
+            Feature::WasmPlugins => cfg!(feature = "wasm-plugins"),
+            Feature::Metrics => true,
+        }
+    }
+
+    /// The config switch, for subsystems that have one. Encryption has a
+    /// switch but is set up during onboarding, not toggled afterwards.
+    fn switch(self, config: &AppConfig) -> Option<bool> {
+        match self {
+            Feature::Encryption => Some(config.crypto.enabled),
+            Feature::Sync => Some(config.p2p.enabled),
+            Feature::Comms => Some(config.comms.enabled),
+            Feature::Voice => Some(config.voice.enabled),
+            Feature::Vision => Some(config.vision.enabled),
+            Feature::Metrics => Some(config.metrics.enabled),
+            Feature::Browser | Feature::WebBrowse | Feature::WasmPlugins => None,
+        }
+    }
+
+    /// Whether Settings may turn it on and off.
+    pub fn toggleable(self) -> bool {
+        self.compiled()
+            && matches!(
+                self,
+                Feature::Sync
+                    | Feature::Comms
+                    | Feature::Voice
+                    | Feature::Vision
+                    | Feature::Metrics
+            )
+    }
+
+    /// Compiled in and switched on (subsystems without a switch are on
+    /// whenever they are compiled).
+    pub fn enabled(self, config: &AppConfig) -> bool {
+        self.compiled() && self.switch(config).unwrap_or(true)
+    }
+
+    /// Flip the subsystem's switch in `config`.
+    pub fn set_enabled(self, config: &mut AppConfig, on: bool) -> Result<(), String> {
+        if !self.compiled() {
+            return Err(format!("{} is not included in this build", self.label()));
+        }
+        let switch = match self {
+            Feature::Sync => &mut config.p2p.enabled,
+            Feature::Comms => &mut config.comms.enabled,
+            Feature::Voice => &mut config.voice.enabled,
+            Feature::Vision => &mut config.vision.enabled,
+            Feature::Metrics => &mut config.metrics.enabled,
+            _ => return Err(format!("{} can't be switched from Settings", self.label())),
+        };
+        *switch = on;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_round_trip() {
+        for feature in Feature::ALL {
+            assert_eq!(Feature::from_id(feature.id()), Some(feature));
+        }
+        assert_eq!(Feature::from_id("telemetry"), None);
+    }
+
+    #[test]
+    fn toggles_follow_the_config() {
+        let mut config = AppConfig::default();
+        assert!(!Feature::Metrics.enabled(&config));
+        Feature::Metrics.set_enabled(&mut config, true).unwrap();
+        assert!(Feature::Metrics.enabled(&config));
+        assert!(config.metrics.enabled);
+
+        assert!(Feature::Encryption.set_enabled(&mut config, true).is_err());
+        assert!(!Feature::Encryption.toggleable());
+    }
+
+    #[test]
+    fn missing_subsystems_are_never_enabled() {
+        let mut config = AppConfig::default();
+        config.p2p.enabled = true;
+        config.comms.enabled = true;
+        config.voice.enabled = true;
+        for feature in Feature::ALL {
+            if !feature.compiled() {
+                assert!(!feature.enabled(&config), "{}", feature.id());
+                assert!(!feature.toggleable(), "{}", feature.id());
+                assert!(feature.set_enabled(&mut config, true).is_err());
+            }
+        }
+    }
+}
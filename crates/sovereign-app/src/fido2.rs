@@ -0,0 +1,178 @@
+//! FIDO2 security keys (YubiKey, SoloKey, Nitrokey, …) as a second unlock
+//! factor, driven through libfido2's command-line tools: `fido2-token`,
+//! `fido2-cred` and `fido2-assert` (packaged as `fido2-tools` on Debian /
+//! Ubuntu, `libfido2` on Fedora, Arch and Homebrew). Shelling out keeps the
+//! HID transport, PIN handling and per-platform device access in the
+//! reference implementation instead of in this process.
+//!
+//! Only the `hmac-secret` output matters here: it is the key material that
+//! seals the auth store (see `sovereign_crypto::auth::SecondFactor`). The
+//! attestation and assertion signatures are not checked — there is no
+//! remote relying party to convince, and a token that returned the wrong
+//! output would simply fail to unseal the store.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use base64::Engine;
+use sovereign_crypto::auth::{HmacSecretAuthenticator, TokenSecret, HMAC_SECRET_LEN};
+use sovereign_crypto::{CryptoError, CryptoResult};
+
+/// A connected FIDO2 token as listed by `fido2-token -L`.
+#[derive(Debug, Clone)]
+pub struct SecurityKeyDevice {
+    /// Device path, e.g. `/dev/hidraw3` or `ioreg://4294971473`.
+    pub path: String,
+    /// Vendor / product description.
+    pub name: String,
+}
+
+/// List connected tokens. Errors when the libfido2 tools are missing.
+pub fn list_devices() -> CryptoResult<Vec<SecurityKeyDevice>> {
+    let out = run("fido2-token", &["-L"], "")?;
+    Ok(out.lines().filter_map(parse_device_line).collect())
+}
+
+/// `/dev/hidraw3: vendor=0x1050, product=0x0407 (Yubico YubiKey OTP+FIDO+CCID)`
+fn parse_device_line(line: &str) -> Option<SecurityKeyDevice> {
+    let (path, rest) = line.trim().split_once(": ")?;
+    let name = rest
+        .find('(')
+        .and_then(|i| rest[i + 1..].strip_suffix(')'))
+        .unwrap_or(rest);
+    Some(SecurityKeyDevice {
+        path: path.to_string(),
+        name: name.to_string(),
+    })
+}
+
+/// One token, addressed by its device path.
+pub struct Fido2Token {
+    path: String,
+}
+
+impl Fido2Token {
+    /// The token at `path`, or the first connected one.
+    pub fn open(path: Option<&str>) -> CryptoResult<Self> {
+        let path = match path {
+            Some(p) => p.to_string(),
+            None => list_devices()?
+                .into_iter()
+                .next()
+                .map(|d| d.path)
+                .ok_or_else(|| CryptoError::SecondFactor("No security key connected".into()))?,
+        };
+        Ok(Self { path })
+    }
+}
+
+impl HmacSecretAuthenticator for Fido2Token {
+    fn make_credential(&self, rp_id: &str, user_id: &[u8]) -> CryptoResult<Vec<u8>> {
+        // Input: client data hash, rp id, user name, user id.
+        let input = [
+            b64(&random_client_data_hash()),
+            rp_id.to_string(),
+            "sovereign".to_string(),
+            b64(user_id),
+        ]
+        .join("\n");
+        let out = run("fido2-cred", &["-M", "-h", "-i", "-", &self.path], &input)?;
+        // Output: client data hash, rp id, format, authdata, credential id, …
+        let cred_id = out
+            .lines()
+            .nth(4)
+            .ok_or_else(|| CryptoError::SecondFactor("fido2-cred: no credential id".into()))?;
+        unb64(cred_id)
+    }
+
+    fn hmac_secret(
+        &self,
+        rp_id: &str,
+        credential_id: &[u8],
+        salt: &[u8; HMAC_SECRET_LEN],
+    ) -> CryptoResult<TokenSecret> {
+        // Input: client data hash, rp id, credential id, hmac salt.
+        let input = [
+            b64(&random_client_data_hash()),
+            rp_id.to_string(),
+            b64(credential_id),
+            b64(salt),
+        ]
+        .join("\n");
+        let out = run("fido2-assert", &["-G", "-h", "-i", "-", &self.path], &input)?;
+        // The hmac-secret output is the last line.
+        let secret = out
+            .lines()
+            .rev()
+            .find(|l| !l.trim().is_empty())
+            .ok_or_else(|| CryptoError::SecondFactor("fido2-assert: no output".into()))?;
+        let bytes: [u8; HMAC_SECRET_LEN] = unb64(secret)?
+            .try_into()
+            .map_err(|_| CryptoError::SecondFactor("token returned no hmac-secret".into()))?;
+        Ok(TokenSecret::from_bytes(bytes))
+    }
+}
+
+fn random_client_data_hash() -> [u8; 32] {
+    rand::random()
+}
+
+fn b64(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+fn unb64(s: &str) -> CryptoResult<Vec<u8>> {
+    base64::engine::general_purpose::STANDARD
+        .decode(s.trim())
+        .map_err(|e| CryptoError::Base64(e.to_string()))
+}
+
+/// Run a libfido2 tool with `input` on stdin and return its stdout. Blocks
+/// until the user touches the token (or the tool times out).
+fn run(tool: &str, args: &[&str], input: &str) -> CryptoResult<String> {
+    let mut child = Command::new(tool)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            CryptoError::SecondFactor(format!(
+                "{tool} unavailable ({e}) — install libfido2's tools to use a security key"
+            ))
+        })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .and_then(|_| stdin.write_all(b"\n"))
+            .map_err(|e| CryptoError::SecondFactor(format!("{tool}: {e}")))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| CryptoError::SecondFactor(format!("{tool}: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(CryptoError::SecondFactor(format!("{tool}: {}", stderr.trim())));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fido2_token_listing() {
+        let d = parse_device_line(
+            "/dev/hidraw3: vendor=0x1050, product=0x0407 (Yubico YubiKey OTP+FIDO+CCID)",
+        )
+        .unwrap();
+        assert_eq!(d.path, "/dev/hidraw3");
+        assert_eq!(d.name, "Yubico YubiKey OTP+FIDO+CCID");
+
+        let d = parse_device_line("ioreg://4294971473: vendor=0x20a0, product=0x42b1").unwrap();
+        assert_eq!(d.path, "ioreg://4294971473");
+        assert_eq!(d.name, "vendor=0x20a0, product=0x42b1");
+        assert!(parse_device_line("").is_none());
+    }
+}
@@ -0,0 +1,203 @@
+//! KEK rotation for the unlocked persona, plus the resume path at login.
+//!
+//! Everything the KEK protects is re-wrapped before the new KEK is committed
+//! to `auth.store`:
+//!   1. the six per-entity key DBs (`EncryptedGraphDB::rotate_kek`),
+//!   2. the blind-index key (`index.key` / `index.duress.key`),
+//!   3. primary persona only: `canary.store`, `keystroke.store` and the
+//!      security-key recovery key,
+//!   4. the key audit log, whose MAC key comes from the KEK (re-chained).
+//!
+//! The new KEK is journaled first (`sovereign_crypto::rotation`), so a crash
+//! anywhere before the commit is finished at the next unlock. Each step
+//! accepts data already under the new KEK, which makes re-running it safe.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use sovereign_core::auth::PersonaKind;
+use sovereign_crypto::account_key::AccountKey;
+use sovereign_crypto::auth::{AuthStore, SealedRecoveryKey, TokenSecret};
+use sovereign_crypto::canary::CanaryStore;
+use sovereign_crypto::device_key::DeviceKey;
+use sovereign_crypto::index_key::IndexKey;
+use sovereign_crypto::kek::Kek;
+use sovereign_crypto::key_audit;
+use sovereign_crypto::keystroke::{EncryptedKeystrokeProfile, KeystrokeReference};
+use sovereign_crypto::rotation::{
+    PendingKekRotation, RotationAuditLog, RotationKind, RotationRecord,
+};
+use sovereign_db::encrypted::EncryptedGraphDB;
+
+use crate::err::ToStringErr;
+use crate::tauri_state::AppState;
+
+/// The rotation log before it was per persona and sealed; removed at login.
+pub const LEGACY_AUDIT_LOG_FILE: &str = "key_rotation.log";
+
+/// Persona-suffixed, like the key DBs: a duress rotation must not leave a
+/// trace the primary persona's files would reveal.
+fn journal_path(crypto_dir: &Path, persona: PersonaKind) -> PathBuf {
+    crypto_dir.join(match persona {
+        PersonaKind::Primary => "kek.rotating",
+        PersonaKind::Duress => "kek.duress.rotating",
+    })
+}
+
+/// The key audit log (`sovereign_crypto::key_audit`). Persona-suffixed
+/// like the key DBs: the duress KEK can't verify the primary's chain.
+pub fn key_audit_path(crypto_dir: &Path, persona: PersonaKind) -> PathBuf {
+    crypto_dir.join(match persona {
+        PersonaKind::Primary => "key_audit.jsonl",
+        PersonaKind::Duress => "key_audit.duress.jsonl",
+    })
+}
+
+/// The record of every rotation this persona made, sealed under its
+/// AccountKey. Persona-suffixed like the key audit log.
+pub fn audit_log(
+    crypto_dir: &Path,
+    persona: PersonaKind,
+    account_key: &AccountKey,
+) -> RotationAuditLog {
+    let path = crypto_dir.join(match persona {
+        PersonaKind::Primary => "key_rotation.log.enc",
+        PersonaKind::Duress => "key_rotation.duress.log.enc",
+    });
+    RotationAuditLog::new(path, account_key.derive_rotation_log_key())
+}
+
+/// The unlocked session's rotation log, or `None` while locked.
+pub async fn session_audit_log(state: &AppState) -> Option<RotationAuditLog> {
+    let persona = (*state.persona.read().await)?;
+    let account_key = state.account_key().await?;
+    Some(audit_log(&state.profile_dir.join("crypto"), persona, &account_key))
+}
+
+/// The session's keys and the credentials needed to commit a new KEK.
+pub struct KekRotation<'a> {
+    pub crypto_dir: &'a Path,
+    pub persona: PersonaKind,
+    pub account_key: &'a AccountKey,
+    pub device_key: &'a DeviceKey,
+    pub old_kek: &'a Kek,
+    pub password: &'a [u8],
+    pub second_factor: Option<&'a TokenSecret>,
+}
+
+impl KekRotation<'_> {
+    /// Rotate to a fresh KEK. Returns the number of document keys re-wrapped.
+    pub async fn run(&self, db: &EncryptedGraphDB) -> Result<u64, String> {
+        let new_kek = Kek::generate();
+        PendingKekRotation::new(&new_kek, self.device_key)
+            .and_then(|pending| pending.save(&journal_path(self.crypto_dir, self.persona)))
+            .str_err()?;
+        self.finish(db, Arc::new(new_kek), false).await
+    }
+
+    /// Finish a rotation a previous session journaled but never committed.
+    /// Returns the new KEK, which the session must use from now on, or
+    /// `None` when nothing was pending.
+    pub async fn resume(&self, db: &EncryptedGraphDB) -> Result<Option<Arc<Kek>>, String> {
+        let path = journal_path(self.crypto_dir, self.persona);
+        let Some(pending) = PendingKekRotation::load(&path).str_err()? else {
+            return Ok(None);
+        };
+        let new_kek = Arc::new(pending.new_kek(self.device_key).str_err()?);
+        if new_kek.as_bytes() == self.old_kek.as_bytes() {
+            // Committed, but the journal outlived the commit.
+            PendingKekRotation::clear(&path).str_err()?;
+            return Ok(None);
+        }
+        tracing::info!("Resuming KEK rotation started {}", pending.started_at);
+        self.finish(db, new_kek.clone(), true).await?;
+        Ok(Some(new_kek))
+    }
+
+    async fn finish(&self, db: &EncryptedGraphDB, new_kek: Arc<Kek>, resumed: bool) -> Result<u64, String> {
+        let keys = db.rotate_kek(new_kek.clone()).await.str_err()?;
+        self.rewrap_index_key(&new_kek)?;
+        if self.persona == PersonaKind::Primary {
+            self.rewrap_sealed_stores(&new_kek)?;
+        }
+        // A log that no longer verifies under the old key stays as it is;
+        // that must not hold up the rotation.
+        if let Err(e) = key_audit::rekey(
+            &key_audit_path(self.crypto_dir, self.persona),
+            &key_audit::audit_key(self.old_kek),
+            &key_audit::audit_key(&new_kek),
+        ) {
+            tracing::warn!("key audit log not re-chained: {e}");
+        }
+
+        // Commit point: from here on the password unlocks the new KEK.
+        let auth_path = self.crypto_dir.join("auth.store");
+        let mut store = AuthStore::load(&auth_path).str_err()?;
+        store
+            .replace_kek(self.password, self.second_factor, &new_kek)
+            .str_err()?;
+        store.save(&auth_path).str_err()?;
+
+        PendingKekRotation::clear(&journal_path(self.crypto_dir, self.persona)).str_err()?;
+        let mut record = RotationRecord::now(RotationKind::Kek, keys);
+        record.resumed = resumed;
+        let log = audit_log(self.crypto_dir, self.persona, self.account_key);
+        if let Err(e) = log.record(&record) {
+            tracing::warn!("key rotation audit log write failed: {e}");
+        }
+        Ok(keys)
+    }
+
+    fn rewrap_index_key(&self, new_kek: &Kek) -> Result<(), String> {
+        let path = self
+            .crypto_dir
+            .join(crate::setup::persona_index_filename(self.persona));
+        if !path.exists() {
+            return Ok(());
+        }
+        let index_key = IndexKey::load(&path, self.device_key, self.old_kek)
+            .or_else(|_| IndexKey::load(&path, self.device_key, new_kek))
+            .str_err()?;
+        index_key.save(&path, self.device_key, new_kek).str_err()
+    }
+
+    /// The primary persona's KEK-sealed side files. Each one already under
+    /// the new KEK (an earlier, interrupted pass) is left as is.
+    fn rewrap_sealed_stores(&self, new_kek: &Kek) -> Result<(), String> {
+        let old = self.old_kek.as_bytes();
+        let new = new_kek.as_bytes();
+
+        let canary_path = self.crypto_dir.join("canary.store");
+        if canary_path.exists() {
+            let canary = CanaryStore::load(&canary_path).str_err()?;
+            if let Ok(phrase) = canary.decrypt(old) {
+                CanaryStore::encrypt(&phrase, new)
+                    .and_then(|c| c.save(&canary_path))
+                    .str_err()?;
+            }
+        }
+
+        let keystroke_path = self.crypto_dir.join("keystroke.store");
+        if keystroke_path.exists() {
+            let json = std::fs::read_to_string(&keystroke_path).str_err()?;
+            let stored: EncryptedKeystrokeProfile = serde_json::from_str(&json).str_err()?;
+            if let Ok(reference) = KeystrokeReference::decrypt(&stored, old) {
+                let json = serde_json::to_string(&reference.encrypt(new).str_err()?).str_err()?;
+                std::fs::write(&keystroke_path, json).str_err()?;
+            }
+        }
+
+        let recovery_path = self
+            .crypto_dir
+            .join(crate::tauri_commands::auth::SECOND_FACTOR_RECOVERY_FILE);
+        if recovery_path.exists() {
+            let sealed = SealedRecoveryKey::load(&recovery_path).str_err()?;
+            if let Ok(key) = sealed.open(self.old_kek) {
+                key.seal(new_kek)
+                    .and_then(|s| s.save(&recovery_path))
+                    .str_err()?;
+            }
+        }
+        Ok(())
+    }
+}
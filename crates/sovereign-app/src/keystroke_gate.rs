@@ -0,0 +1,141 @@
+//! Keystroke-dynamics check at login.
+//!
+//! Onboarding seals a typing reference for the primary passphrase under the
+//! primary KEK (`keystroke.store`). At login the passphrase's key timings are
+//! compared against it. The reference is too noisy to refuse a correct
+//! passphrase on, so a mismatch still unlocks — but the session only lasts
+//! `keystroke_reauth_minutes` before it locks and asks for the passphrase
+//! again. A passphrase typed by someone else buys a short window, not a
+//! session.
+//!
+//! Only the primary persona has a reference; duress logins are never judged,
+//! and a login with no timings (pasted, or a client that doesn't capture
+//! them) is left unverified rather than flagged.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use sovereign_crypto::keystroke::{EncryptedKeystrokeProfile, KeystrokeReference, TypingProfile};
+use tauri::{Emitter, Manager};
+
+use crate::tauri_state::AppState;
+
+/// Sealed typing reference, next to `auth.store` in the crypto dir.
+pub const STORE_FILE: &str = "keystroke.store";
+
+/// How a login's typing compared with the enrolled reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Match,
+    Anomaly,
+    /// No reference enrolled, checks disabled, or no timings sent.
+    Unverified,
+}
+
+/// Open the sealed reference. A missing or unreadable store means nothing
+/// was enrolled (or the KEK is the duress persona's).
+pub fn load_reference(crypto_dir: &Path, kek: &[u8; 32]) -> Option<KeystrokeReference> {
+    let json = std::fs::read_to_string(crypto_dir.join(STORE_FILE)).ok()?;
+    let stored: EncryptedKeystrokeProfile = serde_json::from_str(&json).ok()?;
+    match KeystrokeReference::decrypt(&stored, kek) {
+        Ok(reference) => Some(reference),
+        Err(e) => {
+            tracing::warn!("keystroke reference unreadable: {e}");
+            None
+        }
+    }
+}
+
+/// Compare one login's timings with the reference.
+pub fn judge(reference: Option<&KeystrokeReference>, typing: &TypingProfile) -> Verdict {
+    match reference {
+        Some(_) if typing.samples.is_empty() => Verdict::Unverified,
+        Some(reference) if reference.matches(typing) => Verdict::Match,
+        Some(_) => Verdict::Anomaly,
+        None => Verdict::Unverified,
+    }
+}
+
+/// Lock the session `minutes` from now unless it has already ended. `session`
+/// is the account key this login installed: after a lock and a fresh login
+/// (or decoy mode) the key differs and the timer does nothing.
+pub fn schedule_reauth(
+    app: tauri::AppHandle,
+    minutes: u32,
+    session: Arc<sovereign_crypto::account_key::AccountKey>,
+) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(u64::from(minutes) * 60)).await;
+        let state = app.state::<AppState>();
+        let current = state.account_key().await;
+        if current.is_some_and(|key| Arc::ptr_eq(&key, &session)) {
+            tracing::info!("Re-authentication due after a keystroke anomaly; locking");
+            state.lock().await;
+            let _ = app.emit("session-locked", ());
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_crypto::keystroke::KeystrokeSample;
+
+    fn typing(press_gap: u64, hold: u64) -> TypingProfile {
+        let samples = "secret"
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let press_ms = 1_000 + i as u64 * press_gap;
+                KeystrokeSample {
+                    key: c.to_string(),
+                    press_ms,
+                    release_ms: press_ms + hold,
+                }
+            })
+            .collect();
+        TypingProfile { samples }
+    }
+
+    fn reference() -> KeystrokeReference {
+        let enrolled = [
+            typing(150, 80),
+            typing(160, 85),
+            typing(140, 75),
+            typing(155, 82),
+        ];
+        KeystrokeReference::from_enrollments(&enrolled).expect("enough samples")
+    }
+
+    #[test]
+    fn enrolled_rhythm_matches_and_a_different_one_does_not() {
+        let reference = reference();
+        assert_eq!(judge(Some(&reference), &typing(152, 80)), Verdict::Match);
+        assert_eq!(judge(Some(&reference), &typing(900, 400)), Verdict::Anomaly);
+    }
+
+    #[test]
+    fn missing_reference_or_timings_is_unverified() {
+        let reference = reference();
+        let empty = TypingProfile {
+            samples: Vec::new(),
+        };
+        assert_eq!(judge(Some(&reference), &empty), Verdict::Unverified);
+        assert_eq!(judge(None, &typing(900, 400)), Verdict::Unverified);
+    }
+
+    #[test]
+    fn reference_round_trips_through_the_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let kek = [7u8; 32];
+        let sealed = reference().encrypt(&kek).unwrap();
+        std::fs::write(
+            dir.path().join(STORE_FILE),
+            serde_json::to_string(&sealed).unwrap(),
+        )
+        .unwrap();
+        assert!(load_reference(dir.path(), &kek).is_some());
+        assert!(load_reference(dir.path(), &[8u8; 32]).is_none());
+        assert!(load_reference(&dir.path().join("missing"), &kek).is_none());
+    }
+}
@@ -1,26 +1,71 @@
 #[cfg(feature = "encryption")]
 mod account_key_migration;
+mod activity;
+mod batch;
+mod bulk_edit;
+// `ask` / `chat`: the orchestrator from a terminal, through the daemon.
+#[cfg(all(unix, feature = "encryption"))]
+mod chat_cli;
 mod cli;
 mod commands;
+mod config_cli;
+mod config_reload;
+// `sovereign daemon`: headless backend behind a local JSON-RPC socket. It
+// unlocks a session like the login screen, so it needs the encryption build.
+#[cfg(all(unix, feature = "encryption"))]
+mod daemon;
+#[cfg(all(unix, feature = "encryption"))]
+mod daemon_client;
+mod detached;
+mod doc_ref;
+mod doctor;
 mod llm_bridge;
+mod media;
+mod meeting_notes;
+mod reminders;
+mod relevance;
+mod sealed;
+// Pairing, guardians and recovery from the terminal, through the daemon.
+#[cfg(all(unix, feature = "p2p"))]
+mod p2p_cli;
 // CRYPTO-001 (v0.0.7): compiled in the encryption build, where install_session
 // seeds the duress persona's decoy database. It was previously gated behind a
 // non-existent `duress` feature, so it never compiled and the persona was
 // half-wired (seed_duress_db / persona_db_path were dead code).
 #[cfg(feature = "encryption")]
 mod duress;
+#[cfg(feature = "encryption")]
+mod fido2;
+#[cfg(feature = "encryption")]
+mod key_rotation;
 mod err;
+mod edit_journal;
+mod export_bundle;
+mod features;
+mod share_bundle;
+mod thread_bundle;
 // Server-side login lockout (CRYPTO-002). Only the encryption build's
 // `validate_password` enforces it; gating avoids dead-code warnings elsewhere.
 #[cfg(feature = "encryption")]
 mod login_throttle;
+// Keystroke-dynamics check at login; the reference is sealed under the KEK.
+#[cfg(feature = "encryption")]
+mod keystroke_gate;
+mod secure_clipboard;
 mod seed;
 mod setup;
+mod skill_cli;
+mod snapshot;
+mod source_import;
 
 mod tauri_commands;
 mod tauri_events;
 #[cfg(feature = "p2p")]
 mod sync_startup;
+#[cfg(feature = "encryption")]
+mod remote_wipe;
+#[cfg(feature = "encryption")]
+mod canary;
 mod pii_ingest;
 #[cfg(all(feature = "comms", feature = "encryption"))]
 mod pii_contact_hook;
@@ -28,9 +73,20 @@ mod pii_contact_hook;
 mod pii_message_hook;
 #[cfg(feature = "comms")]
 mod pii_share_hook;
+#[cfg(feature = "comms")]
+mod comms_startup;
 #[cfg(all(feature = "comms", feature = "encryption"))]
 mod pii_sweep;
 mod tauri_state;
+// `maintain` and the daemon's daily pass: trash, history, blobs, index, key log.
+#[cfg(all(unix, feature = "encryption"))]
+mod maintain;
+// `verify`: decrypt and integrity-check everything stored on this device.
+#[cfg(feature = "encryption")]
+mod verify;
+// `watch`: daemon events as NDJSON on stdout.
+#[cfg(all(unix, feature = "encryption"))]
+mod watch;
 
 // `web` hosts the SSRF URL validator (always needed by the embedded browser
 // on desktop) plus the reqwest/readability fetch path (gated inside the module
@@ -52,6 +108,7 @@ use std::sync::Arc;
 use anyhow::Result;
 use clap::Parser;
 use sovereign_core::config::AppConfig;
+use sovereign_core::events::EventBus;
 use sovereign_core::interfaces::{FeedbackEvent, OrchestratorEvent};
 use sovereign_core::security::ActionDecision;
 use sovereign_core::lifecycle;
@@ -60,7 +117,10 @@ use sovereign_db::GraphDB;
 #[cfg(feature = "comms")]
 use sovereign_comms::CommsSync;
 
-use cli::{Cli, Commands};
+use cli::{
+    Cli, Commands, CommitsAction, ConfigAction, SkillAction, SnapshotAction, ThreadAction,
+    TrashAction,
+};
 use setup::create_db;
 
 /// Mobile entry point. Called by Android's JNI loader via the
@@ -79,7 +139,7 @@ pub fn run() {
             return;
         }
     };
-    if let Err(e) = run_tauri(&config, &rt) {
+    if let Err(e) = run_tauri(&config, None, &rt) {
         tracing::error!("Tauri app exited with error: {e}");
     }
 }
@@ -88,16 +148,39 @@ pub fn run() {
 /// `Commands::Run` (the default) it brings up the Tauri webview, on
 /// any subcommand it dispatches to the corresponding handler.
 pub fn run_cli() -> Result<()> {
-    lifecycle::init_tracing();
-
     let cli = Cli::parse();
+    lifecycle::init_tracing_with(&lifecycle::TraceOptions {
+        trace_spans: cli.trace_spans,
+        profile: cli.trace_profile.clone(),
+    })?;
+    // Complete the profile however we leave, errors included.
+    struct FinishProfile;
+    impl Drop for FinishProfile {
+        fn drop(&mut self) {
+            lifecycle::finish_profile();
+        }
+    }
+    let _finish_profile = FinishProfile;
+
+    // Pick the user profile before anything resolves a path under it.
+    let profiles = sovereign_core::profiles::Profiles::on_this_machine();
+    let profile = cli.profile.clone().unwrap_or_else(|| profiles.last_used());
+    sovereign_core::profiles::activate(&profile)?;
+    let command = cli.command.unwrap_or(Commands::Run);
+    if matches!(command, Commands::Run) {
+        if let Err(e) = profiles.set_last_used(&profile) {
+            tracing::warn!("Could not remember profile '{profile}': {e}");
+        }
+    }
+
     let config = AppConfig::load_or_default(cli.config.as_deref());
+    let config_file = cli.config.clone().unwrap_or_else(AppConfig::user_config_path);
 
     let rt = tokio::runtime::Runtime::new()?;
 
-    match cli.command.unwrap_or(Commands::Run) {
+    match command {
         Commands::Run => {
-            run_tauri(&config, &rt)?;
+            run_tauri(&config, cli.config.clone(), &rt)?;
         }
 
         Commands::CreateDoc { title, thread_id, is_owned } => {
@@ -133,18 +216,66 @@ pub fn run_cli() -> Result<()> {
         Commands::ListCommits { doc_id } => {
             rt.block_on(commands::list_commits(&config, doc_id))?;
         }
+        Commands::Trash { action } => match action {
+            TrashAction::List => rt.block_on(commands::trash_list(&config))?,
+            TrashAction::Restore { id } => rt.block_on(commands::trash_restore(&config, id))?,
+            TrashAction::Purge { older_than } => {
+                rt.block_on(commands::trash_purge(&config, older_than))?;
+            }
+        },
+        Commands::Commits { action } => match action {
+            CommitsAction::Compact { doc, keep } => {
+                rt.block_on(commands::compact_commits(&config, doc, keep))?;
+            }
+        },
+        Commands::Skill { action } => match action {
+            SkillAction::List => skill_cli::list()?,
+            SkillAction::Run { name, action, doc, params } => {
+                rt.block_on(skill_cli::run(&config, name, action, doc, params))?;
+            }
+        },
+        Commands::Export { doc_id, format, thread, all, encrypt, recipient, output } => {
+            let seal = commands::bundle_seal(encrypt, recipient)?;
+            match doc_id {
+                Some(doc_id) if !all => {
+                    rt.block_on(commands::export_doc(&config, doc_id, format, thread, output, seal))?;
+                }
+                _ => rt.block_on(commands::export_workspace(&config, format, output, seal))?,
+            }
+        }
+
+        Commands::Thread { action } => match action {
+            ThreadAction::Export { id, out, encrypt, recipient } => {
+                let seal = commands::bundle_seal(encrypt, recipient)?;
+                rt.block_on(commands::export_thread(&config, id, out, seal))?;
+            }
+        },
+        Commands::ImportThread { input, identity } => {
+            rt.block_on(commands::import_thread(&config, input, identity))?;
+        }
+        Commands::Share { doc_id, attach, expires_in, output, qr } => {
+            rt.block_on(commands::share_doc(&config, doc_id, attach, expires_in, output, qr))?;
+        }
+        Commands::OpenShare { input, payload } => {
+            rt.block_on(commands::open_share(&config, input, payload))?;
+        }
+        Commands::Import { input, identity, from, thread, format } => {
+            if let Some(from) = from {
+                rt.block_on(commands::import_folder(&config, from, thread, format))?;
+            } else if let Some(input) = input {
+                rt.block_on(commands::import_workspace(&config, input, identity))?;
+            }
+        }
 
         #[cfg(feature = "encryption")]
         Commands::EncryptData => {
-            let (_, key_db, kek) = setup::init_crypto()?;
+            let (_, key_db, kek) = setup::init_crypto(&config)?;
             rt.block_on(commands::encrypt_data(&config, key_db, kek))?;
         }
 
-        #[cfg(feature = "p2p")]
-        Commands::PairDevice { peer_id } => {
-            println!("Pairing with peer {peer_id}...");
-            println!("(P2P pairing requires a running `sovereign run` instance)");
-            println!("Use the orchestrator command: 'pair device {peer_id}'");
+        #[cfg(all(unix, feature = "p2p"))]
+        Commands::PairDevice { socket } => {
+            rt.block_on(p2p_cli::pair_device(&config, socket))?;
         }
         #[cfg(feature = "p2p")]
         Commands::ListDevices => {
@@ -166,11 +297,9 @@ pub fn run_cli() -> Result<()> {
                 println!("No paired devices.");
             }
         }
-        #[cfg(feature = "p2p")]
-        Commands::EnrollGuardian { name, peer_id } => {
-            println!("Enrolling guardian '{name}' (peer: {peer_id})...");
-            println!("(Guardian enrollment requires a running `sovereign run` instance)");
-            println!("Use the orchestrator command: 'enroll guardian {name}'");
+        #[cfg(all(unix, feature = "p2p"))]
+        Commands::EnrollGuardian { name, peer_id, threshold, socket } => {
+            rt.block_on(p2p_cli::enroll_guardian(&config, socket, name, peer_id, threshold))?;
         }
 
         #[cfg(feature = "encryption")]
@@ -183,11 +312,70 @@ pub fn run_cli() -> Result<()> {
                 println!("No guardians enrolled.");
             }
         }
+        #[cfg(all(unix, feature = "p2p"))]
+        Commands::InitiateRecovery { guardians, hosts, socket } => {
+            rt.block_on(p2p_cli::initiate_recovery(&config, socket, guardians, hosts))?;
+        }
+        #[cfg(all(unix, feature = "p2p"))]
+        Commands::ApproveRecovery { socket } => {
+            rt.block_on(p2p_cli::approve_recovery(&config, socket))?;
+        }
+
+        #[cfg(all(unix, feature = "encryption"))]
+        Commands::Daemon { socket, passphrase_stdin } => {
+            daemon::run(&config, cli.config.clone(), &rt, socket, passphrase_stdin)?;
+        }
+        #[cfg(all(unix, feature = "encryption"))]
+        Commands::Ask { question, socket } => {
+            rt.block_on(chat_cli::ask(&config, socket, question))?;
+        }
+        #[cfg(all(unix, feature = "encryption"))]
+        Commands::Chat { socket } => {
+            rt.block_on(chat_cli::chat(&config, socket))?;
+        }
+        #[cfg(all(unix, feature = "encryption"))]
+        Commands::Watch { events, socket } => {
+            rt.block_on(watch::run(&config, socket, events))?;
+        }
+        Commands::Batch { file, dry_run } => {
+            rt.block_on(batch::run(&config, file, dry_run))?;
+        }
+        Commands::Snapshot { action } => match action {
+            SnapshotAction::Create { note } => rt.block_on(snapshot::create(&config, note))?,
+            SnapshotAction::List => snapshot::list()?,
+            SnapshotAction::Rollback { name } => rt.block_on(snapshot::rollback(&config, name))?,
+        },
+        Commands::Config { action } => match action {
+            ConfigAction::Get { key } => config_cli::get(&config, &key)?,
+            ConfigAction::Set { key, value } => {
+                rt.block_on(config_cli::set(&config, &config_file, &key, &value))?;
+            }
+            ConfigAction::List => config_cli::list(&config)?,
+            ConfigAction::Validate => config_cli::validate(&config, &config_file)?,
+        },
+        Commands::Completions { shell } => {
+            use clap::CommandFactory;
+            clap_complete::generate(shell, &mut Cli::command(), "sovereign", &mut std::io::stdout());
+        }
+        Commands::Doctor { unlock } => {
+            let report = rt.block_on(doctor::run(&config, unlock));
+            print!("{}", report.render());
+            if report.failures() > 0 {
+                anyhow::bail!("doctor found {} problem(s)", report.failures());
+            }
+        }
         #[cfg(feature = "encryption")]
-        Commands::InitiateRecovery => {
-            println!("Key recovery requires at least 3 of 5 guardian shards.");
-            println!("(Recovery flow requires a running `sovereign run` instance with P2P)");
-            println!("Use the orchestrator command: 'initiate recovery'");
+        Commands::Verify => {
+            let report = rt.block_on(verify::run(&config))?;
+            print!("{}", report.render());
+            if report.failures() > 0 {
+                anyhow::bail!("verify found {} problem(s)", report.failures());
+            }
+        }
+
+        #[cfg(all(unix, feature = "encryption"))]
+        Commands::Maintain { socket } => {
+            rt.block_on(maintain::command(&config, socket))?;
         }
 
         Commands::ListContacts => {
@@ -208,7 +396,13 @@ pub fn run_cli() -> Result<()> {
 /// `SOVEREIGN_DATA_DIR` before any sovereign code resolves a path.
 /// On desktop the env var is left unset and `sovereign_dir()` falls back
 /// to `~/.sovereign`.
-fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
+/// `config_file` is the `--config` file, watched for edits along with the
+/// other config layers.
+fn run_tauri(
+    config: &AppConfig,
+    config_file: Option<std::path::PathBuf>,
+    rt: &tokio::runtime::Runtime,
+) -> Result<()> {
     let config_for_setup = config.clone();
     let rt_handle = rt.handle().clone();
 
@@ -228,12 +422,20 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
         builder = builder.plugin(tauri_plugin_haptics::init());
     }
 
+    // Pasted images, by hash: `media://localhost/<sha256>`.
+    builder = builder.register_uri_scheme_protocol("media", |_ctx, request| media::serve(&request));
+
     builder
         .invoke_handler(tauri::generate_handler![
             // AI: status, chat, search, action gate, models, trust
             tauri_commands::ai::greet,
             tauri_commands::ai::get_status,
+            tauri_commands::ai::get_system_health,
+            tauri_commands::ai::get_metrics,
+            tauri_commands::ai::get_workspace_stats,
+            tauri_commands::ai::clear_metrics,
             tauri_commands::ai::chat_message,
+            tauri_commands::ai::get_chat_history,
             tauri_commands::ai::search_documents,
             tauri_commands::ai::search_query,
             tauri_commands::ai::approve_action,
@@ -252,20 +454,62 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
             tauri_commands::documents::toggle_theme,
             tauri_commands::documents::get_theme,
             tauri_commands::documents::get_document,
+            tauri_commands::documents::list_backlinks,
+            tauri_commands::documents::set_read_later,
+            tauri_commands::documents::set_favorite,
+            tauri_commands::documents::set_document_sensitivity,
+            tauri_commands::documents::unseal_document,
+            tauri_commands::documents::bulk_edit_documents,
+            tauri_commands::documents::revert_bulk_edit,
+            tauri_commands::documents::list_reminders,
+            tauri_commands::documents::create_reminder,
+            tauri_commands::documents::delete_reminder,
+            tauri_commands::documents::list_reading_queue,
+            tauri_commands::documents::share_document,
+            tauri_commands::documents::open_share,
+            tauri_commands::meetings::meetings_starting,
+            tauri_commands::meetings::save_meeting_notes,
             tauri_commands::documents::save_document,
+            tauri_commands::documents::set_document_type,
             tauri_commands::documents::create_document,
             tauri_commands::documents::close_document,
+            tauri_commands::documents::journal_edit,
+            tauri_commands::documents::list_recoverable_edits,
+            tauri_commands::documents::discard_journaled_edit,
             tauri_commands::documents::delete_document,
             tauri_commands::documents::list_commits,
             tauri_commands::documents::restore_commit,
+            tauri_commands::documents::get_commit_snapshot,
+            tauri_commands::documents::document_activity,
+            tauri_commands::documents::store_pasted_image,
+            tauri_commands::documents::attach_file,
+            tauri_commands::documents::remove_attachment,
+            tauri_commands::documents::read_attachment,
+            tauri_commands::documents::pdf_preview,
             tauri_commands::documents::list_skills_for_doc,
             tauri_commands::documents::execute_skill,
             tauri_commands::documents::list_all_skills,
             tauri_commands::documents::import_file,
+            tauri_commands::documents::preview_source_import,
+            tauri_commands::documents::import_source,
+            // Skill permissions
+            tauri_commands::skills::get_skill_permissions,
+            tauri_commands::skills::set_skill_capability,
+            tauri_commands::skills::list_skill_jobs,
+            tauri_commands::skills::cancel_skill_job,
+            tauri_commands::skills::clear_finished_skill_jobs,
             // Canvas
             tauri_commands::canvas::canvas_load,
             tauri_commands::canvas::update_document_position,
             tauri_commands::canvas::canvas_load_messages,
+            tauri_commands::canvas::get_window_session,
+            tauri_commands::canvas::save_window_session,
+            tauri_commands::canvas::profiling_enabled,
+            tauri_commands::canvas::report_frame_times,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            tauri_commands::windows::detach_panel,
+            tauri_commands::windows::detached_panel_info,
+            tauri_commands::windows::close_detached_panel,
             // Threads
             tauri_commands::threads::create_thread,
             tauri_commands::threads::update_thread,
@@ -274,18 +518,37 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
             // Contacts & messaging
             tauri_commands::contacts::list_contacts,
             tauri_commands::contacts::get_contact_detail,
+            tauri_commands::contacts::create_contact,
+            tauri_commands::contacts::update_contact,
             tauri_commands::contacts::list_conversations,
             tauri_commands::contacts::list_messages,
             tauri_commands::contacts::mark_message_read,
+            tauri_commands::contacts::translate_message,
+            tauri_commands::contacts::read_message_later,
+            tauri_commands::contacts::list_compose_channels,
+            tauri_commands::contacts::compose_message,
             tauri_commands::contacts::create_relationship,
             // Auth, onboarding, profile, config
             tauri_commands::auth::check_auth_state,
             tauri_commands::auth::validate_password,
+            tauri_commands::auth::restore_from_recovery_phrase,
             tauri_commands::auth::validate_password_policy,
             tauri_commands::auth::complete_onboarding,
+            tauri_commands::auth::list_security_keys,
+            tauri_commands::auth::enroll_security_key,
+            tauri_commands::auth::recover_security_key,
+            tauri_commands::auth::rotate_kek,
+            tauri_commands::auth::rotate_document_keys,
+            tauri_commands::auth::get_key_rotation_log,
+            tauri_commands::auth::lock_session,
             tauri_commands::auth::get_profile,
             tauri_commands::auth::save_profile,
+            tauri_commands::auth::list_profiles,
+            tauri_commands::auth::switch_profile,
             tauri_commands::auth::get_config,
+            tauri_commands::auth::update_config,
+            tauri_commands::auth::list_features,
+            tauri_commands::auth::set_feature_enabled,
             // Browser, web, comms
             tauri_commands::browser::get_comms_config,
             tauri_commands::browser::save_comms_config,
@@ -331,6 +594,8 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
             tauri_commands::pii::__browser_form_extracted,
             tauri_commands::pii::autofill_pii_record,
             tauri_commands::pii::generate_password,
+            tauri_commands::pii::secure_copy,
+            tauri_commands::pii::clear_secure_clipboard,
             tauri_commands::pii::list_cookies_for_entity,
             tauri_commands::pii::delete_cookie,
             tauri_commands::pii::clear_entity_cookies,
@@ -348,21 +613,37 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
             #[cfg(feature = "encryption")]
             tauri_commands::pairing::list_paired_devices,
             #[cfg(feature = "encryption")]
-            tauri_commands::pairing::forget_paired_device,
+            tauri_commands::pairing::revoke_paired_device,
+            tauri_commands::pairing::set_device_sync_policy,
+            tauri_commands::pairing::set_document_sync_exclusions,
+            tauri_commands::pairing::rename_paired_device,
+            tauri_commands::pairing::set_device_sync_paused,
+            tauri_commands::pairing::set_device_trust,
+            tauri_commands::pairing::set_device_addresses,
+            tauri_commands::pairing::share_device_keys,
+            tauri_commands::pairing::get_sync_history,
             #[cfg(feature = "encryption")]
             tauri_commands::pairing::get_local_peer_id,
+            tauri_commands::pairing::get_local_addresses,
             #[cfg(feature = "encryption")]
             tauri_commands::pairing::trigger_sync_now,
+            tauri_commands::pairing::preview_sync,
+            tauri_commands::pairing::fetch_viewer_snapshot,
+            tauri_commands::pairing::remote_wipe_device,
+            tauri_commands::pairing::approve_remote_wipe,
             #[cfg(feature = "encryption")]
             tauri_commands::pairing::cancel_pairing,
             #[cfg(feature = "encryption")]
             tauri_commands::pairing::get_p2p_settings,
             #[cfg(feature = "encryption")]
             tauri_commands::pairing::resolve_sync_conflict_keep_mine,
+            tauri_commands::pairing::resolve_sync_merge,
             // Backup (P4)
             #[cfg(feature = "encryption")]
             tauri_commands::backup::backup_now,
             #[cfg(feature = "encryption")]
+            tauri_commands::backup::refresh_shards,
+            #[cfg(feature = "encryption")]
             tauri_commands::backup::backup_status,
             #[cfg(feature = "encryption")]
             tauri_commands::backup::approve_shard_release,
@@ -373,6 +654,8 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
             tauri_commands::mobile::receive_shared_content,
             tauri_commands::mobile::set_connectivity_state,
             tauri_commands::mobile::get_connectivity_state,
+            tauri_commands::mobile::proximity_link_up,
+            tauri_commands::mobile::proximity_link_down,
             // Voice: push-to-talk control surface
             tauri_commands::voice::start_listening,
             tauri_commands::voice::stop_listening,
@@ -424,14 +707,25 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
                 format!("Backend init failed: {e:#}").into()
             })?;
 
-            // Voice pipeline (gated at compile time + runtime). Unlike the
-            // upstream desktop path this KEEPS the receiver so the voice-event
-            // forwarder (below) can drain it and surface listening/speaking/
-            // idle state to the Svelte Taskbar mic button. Returns Some(vrx)
-            // only when the pipeline actually spawned.
+            // Voice events (pipeline, push-to-talk, gesture listening) reach
+            // the Svelte Taskbar mic button through the voice-event
+            // forwarder. Subscribe before anything can publish one.
+            let voice_rx = backend.bus.subscribe::<sovereign_ai::VoiceEvent>();
+
+            // Register state with Tauri. The device_key is loaded post-login
+            // by install_session() in tauri_commands::auth.rs; not at startup.
+            // The theme is read from the persisted UserProfile so it survives
+            // restarts (toggle_theme writes back through to the profile).
+            let theme_initial = sovereign_core::profile::UserProfile::load(&backend.profile_dir)
+                .map(|p| p.theme)
+                .unwrap_or_else(|_| "dark".to_string());
+            app.manage(backend.app_state(theme_initial));
+
+            // Voice pipeline (gated at compile time). It follows the voice
+            // settings, so it is spawned even while voice is disabled and
+            // starts once enabled.
             #[cfg(feature = "voice-stt")]
-            let voice_rx = if backend.config.voice.enabled {
-                let (vtx, vrx) = mpsc::channel();
+            {
                 let voice_query_cb: Box<dyn Fn(String) + Send + 'static> =
                     if let Some(ref orch) = backend.orchestrator {
                         setup::orch_callback(orch, "Voice query error", |o, t| {
@@ -443,68 +737,18 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
                         })
                     };
 
-                match sovereign_ai::voice::VoicePipeline::spawn(
-                    backend.config.voice.clone(),
-                    vtx,
+                match sovereign_ai::voice::VoicePipeline::spawn_reloading(
+                    app.state::<tauri_state::AppState>().config_reload.subscribe(),
+                    backend.bus.publisher(),
                     voice_query_cb,
                 ) {
-                    Ok(_handle) => {
-                        tracing::info!("Voice pipeline started");
-                        std::sync::Mutex::new(Some(vrx))
-                    }
-                    Err(e) => {
-                        tracing::warn!("Voice pipeline unavailable: {e}");
-                        std::sync::Mutex::new(None)
-                    }
+                    Ok(_handle) => tracing::info!("Voice pipeline thread started"),
+                    Err(e) => tracing::warn!("Voice pipeline unavailable: {e}"),
                 }
-            } else {
-                tracing::info!("Voice pipeline disabled in config");
-                std::sync::Mutex::new(None)
-            };
+            }
             #[cfg(not(feature = "voice-stt"))]
             tracing::info!("Voice pipeline omitted (voice-stt feature disabled)");
 
-            // Register state with Tauri. The device_key is loaded post-login
-            // by install_session() in tauri_commands::auth.rs; not at startup.
-            // The theme is read from the persisted UserProfile so it survives
-            // restarts (toggle_theme writes back through to the profile).
-            let theme_initial = sovereign_core::profile::UserProfile::load(&backend.profile_dir)
-                .map(|p| p.theme)
-                .unwrap_or_else(|_| "dark".to_string());
-            app.manage(tauri_state::AppState {
-                db: backend.db.clone(),
-                orchestrator: backend.orchestrator.clone(),
-                config: backend.config.clone(),
-                skill_registry: backend.skill_registry,
-                skill_db: backend.skill_db,
-                skill_llm: backend.skill_llm,
-                decision_tx: backend.decision_tx,
-                feedback_tx: backend.feedback_tx,
-                orch_tx: backend.orch_tx,
-                theme: std::sync::Mutex::new(theme_initial),
-                autocommit: backend.autocommit.clone(),
-                model_assignments: std::sync::Mutex::new(backend.model_assignments),
-                profile_dir: backend.profile_dir,
-                #[cfg(feature = "encryption")]
-                account_key: tokio::sync::RwLock::new(None),
-                #[cfg(feature = "encryption")]
-                p2p_identity_key: tokio::sync::RwLock::new(None),
-                #[cfg(feature = "encryption")]
-                pending_pairing: tokio::sync::RwLock::new(None),
-                #[cfg(feature = "p2p")]
-                p2p_command_tx: tokio::sync::RwLock::new(None),
-                #[cfg(feature = "p2p")]
-                pairing_manager: std::sync::Arc::new(tokio::sync::RwLock::new(None)),
-                #[cfg(feature = "p2p")]
-                p2p_listen_addrs: std::sync::Arc::new(std::sync::RwLock::new(Vec::new())),
-                #[cfg(feature = "p2p")]
-                backup_host: tokio::sync::RwLock::new(None),
-                #[cfg(feature = "p2p")]
-                connectivity: std::sync::Arc::new(std::sync::atomic::AtomicU8::new(0)),
-                #[cfg(feature = "voice-stt")]
-                stt_engine: backend.stt_engine,
-            });
-
             // Auto-open DevTools (desktop debug only)
             #[cfg(all(debug_assertions, not(any(target_os = "ios", target_os = "android"))))]
             {
@@ -513,10 +757,8 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
                 }
             }
 
-            // Event forwarder. On `jiminy` builds the orchestrator stream is
-            // fanned out to BOTH the Tauri forwarder AND the JiminyBridge
-            // (robot head/antenna/emotions + ChatResponse -> sidecar /speak),
-            // so `orch_rx` is rebound to the UI receiver below.
+            // Event forwarder, subscribed to orchestrator events since
+            // backend init.
             let orch_rx = backend.orch_rx;
 
             // SIDECAR-002: provision the shared sidecar auth token before any
@@ -526,30 +768,17 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
             #[cfg(all(feature = "encryption", any(feature = "jiminy", feature = "vision")))]
             crate::setup::ensure_jiminy_token(&sovereign_core::sovereign_dir());
 
-            // Jiminy embodiment (BODY): fan-out every orchestrator event to BOTH
-            // the Tauri event forwarder AND the JiminyBridge. Rebind orch_rx to
-            // the UI receiver so spawn_event_forwarder consumes the fanned-out
-            // stream.
+            // Jiminy embodiment (BODY): the JiminyBridge subscribes to the
+            // orchestrator events alongside the Tauri forwarder (robot
+            // head/antenna/emotions + ChatResponse -> sidecar /speak).
             #[cfg(feature = "jiminy")]
-            let orch_rx = {
-                let (ui_tx, ui_rx) = mpsc::channel::<OrchestratorEvent>();
-                let (jiminy_tx, jiminy_rx) = mpsc::channel::<OrchestratorEvent>();
-                std::thread::Builder::new()
-                    .name("jiminy-fanout".into())
-                    .spawn(move || {
-                        while let Ok(event) = orch_rx.recv() {
-                            let _ = ui_tx.send(event.clone());
-                            let _ = jiminy_tx.send(event);
-                        }
-                    })
-                    .expect("Failed to spawn jiminy-fanout thread");
+            {
                 let jiminy_url = std::env::var("JIMINY_URL")
                     .unwrap_or_else(|_| "http://127.0.0.1:9100".into());
-                let _jiminy_handle =
-                    sovereign_ai::jiminy::JiminyBridge::new(&jiminy_url).spawn(jiminy_rx);
+                let _jiminy_handle = sovereign_ai::jiminy::JiminyBridge::new(&jiminy_url)
+                    .spawn(backend.bus.subscribe());
                 tracing::info!("Jiminy bridge started (sidecar at {jiminy_url})");
-                ui_rx
-            };
+            }
 
             // Jiminy camera poller (keep-compiling only — no Tauri consumer yet).
             #[cfg(feature = "jiminy")]
@@ -585,20 +814,14 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
                 let (listen_tx, mut listen_rx) = tokio::sync::mpsc::channel::<()>(2);
                 if let Some(orch) = backend.orchestrator.clone() {
                     let listen_url = format!("{}/listen", bridge_url.trim_end_matches('/'));
-                    let app_handle = app.handle().clone();
+                    // Surface listening / heard text to the Svelte mic button +
+                    // chat window, as the voice pipeline does.
+                    let voice = backend.bus.publisher::<sovereign_ai::VoiceEvent>();
                     let query_cb = setup::orch_callback(&orch, "Gesture-listen error", |o, t| {
                         Box::pin(o.handle_query(t))
                     });
                     tauri::async_runtime::spawn(async move {
-                        use tauri::Emitter;
-                        // Surface listening / heard text to the Svelte mic button +
-                        // chat window (mirrors the voice-pipeline forwarder).
-                        let voice_evt = |kind: &str, text: Option<String>| {
-                            let _ = app_handle.emit(
-                                "voice-event",
-                                tauri_events::VoiceEventPayload { kind: kind.into(), text },
-                            );
-                        };
+                        use sovereign_ai::VoiceEvent;
                         let client = reqwest::Client::builder()
                             .timeout(std::time::Duration::from_secs(30))
                             .default_headers(sovereign_ai::sidecar::auth_headers())
@@ -606,7 +829,7 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
                             .unwrap_or_default();
                         while listen_rx.recv().await.is_some() {
                             tracing::info!("Gesture-listen: recording a turn…");
-                            voice_evt("listening", None);
+                            voice.send(VoiceEvent::ListeningStarted);
                             match client.post(&listen_url).send().await {
                                 Ok(resp) => match resp.json::<serde_json::Value>().await {
                                     Ok(v) => {
@@ -618,21 +841,21 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
                                             .to_string();
                                         if text.is_empty() {
                                             tracing::info!("Gesture-listen: nothing heard");
-                                            voice_evt("idle", None);
+                                            voice.send(VoiceEvent::ListeningStopped);
                                         } else {
                                             tracing::info!("Gesture-listen heard: {text}");
-                                            voice_evt("transcription", Some(text.clone()));
+                                            voice.send(VoiceEvent::TranscriptionReady(text.clone()));
                                             query_cb(text);
                                         }
                                     }
                                     Err(e) => {
                                         tracing::warn!("Gesture-listen parse failed: {e}");
-                                        voice_evt("idle", None);
+                                        voice.send(VoiceEvent::ListeningStopped);
                                     }
                                 },
                                 Err(e) => {
                                     tracing::warn!("Gesture-listen /listen failed: {e}");
-                                    voice_evt("idle", None);
+                                    voice.send(VoiceEvent::ListeningStopped);
                                 }
                             }
                         }
@@ -659,38 +882,69 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
             }
 
             tauri_events::spawn_event_forwarder(app.handle().clone(), orch_rx);
+            #[cfg(feature = "comms")]
+            tauri_events::spawn_comms_forwarder(app.handle().clone(), backend.bus.subscribe());
+            // Local metrics: the history from earlier runs, and recording
+            // from now on if the user opted in. `config_reload` follows changes.
+            sovereign_core::metrics::open(
+                &sovereign_core::metrics::default_path(),
+                config.metrics.retention_days,
+            );
+            sovereign_core::metrics::set_enabled(config.metrics.enabled);
+            config_reload::spawn(app.handle().clone(), config_file.clone());
+
+            // Voice-event forwarder: emits "voice-event" to the Svelte
+            // frontend (listening / speaking / idle).
+            tauri_events::spawn_voice_forwarder(app.handle().clone(), voice_rx);
+
+            spawn_housekeeping(
+                backend.db.clone(),
+                backend.autocommit.clone(),
+                &backend.shutdown,
+            );
+
+            // Key usage audit: look for bulk decryption in quiet hours.
+            #[cfg(feature = "encryption")]
+            if let Some(orch) = backend.orchestrator.clone() {
+                tauri::async_runtime::spawn(async move {
+                    let period = std::time::Duration::from_secs(600);
+                    let mut interval = tokio::time::interval(period);
+                    loop {
+                        interval.tick().await;
+                        orch.check_key_usage(period);
+                    }
+                });
+            }
 
-            // Voice-event forwarder: drains the voice pipeline rx and emits
-            // "voice-event" to the Svelte frontend (listening / speaking / idle).
-            #[cfg(feature = "voice-stt")]
-            if let Some(vrx) = voice_rx.lock().unwrap().take() {
-                tauri_events::spawn_voice_forwarder(app.handle().clone(), vrx);
+            // Reminders: deliver the ones that came due.
+            {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    use tauri::Manager as _;
+                    let mut interval = tokio::time::interval(reminders::CHECK_INTERVAL);
+                    loop {
+                        interval.tick().await;
+                        if let Some(state) = app_handle.try_state::<tauri_state::AppState>() {
+                            reminders::tick(&state).await;
+                        }
+                    }
+                });
             }
 
-            // Periodic auto-commit (30s)
-            let autocommit = backend.autocommit;
-            tauri::async_runtime::spawn(async move {
-                let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
-                loop {
-                    interval.tick().await;
-                    autocommit.lock().await.check_and_commit().await;
-                }
-            });
-
-            // Hourly purge of soft-deleted items
-            let purge_db = backend.db.clone();
-            tauri::async_runtime::spawn(async move {
-                let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
-                loop {
-                    interval.tick().await;
-                    let max_age = std::time::Duration::from_secs(30 * 24 * 3600);
-                    match (*purge_db).purge_deleted(max_age).await {
-                        Ok(n) if n > 0 => tracing::info!("Purged {n} soft-deleted items"),
-                        Err(e) => tracing::warn!("Purge failed: {e}"),
-                        _ => {}
+            // Relationship strengths: reinforce and decay.
+            {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    use tauri::Manager as _;
+                    let mut interval = tokio::time::interval(relevance::INTERVAL);
+                    loop {
+                        interval.tick().await;
+                        if let Some(state) = app_handle.try_state::<tauri_state::AppState>() {
+                            relevance::tick(&state).await;
+                        }
                     }
-                }
-            });
+                });
+            }
 
             // Memory consolidation idle-watcher
             if let Some(orch) = backend.orchestrator.clone() {
@@ -782,20 +1036,58 @@ fn run_tauri(config: &AppConfig, rt: &tokio::runtime::Runtime) -> Result<()> {
         })
         .build(tauri::generate_context!())
         .expect("error building Sovereign GE (Tauri)")
-        .run(move |_app, event| {
+        .run(move |app, event| {
             // Goodnight: when the user quits, ask the bridge to play Jiminy's
             // sleep animation before the process winds down.
             #[cfg(feature = "jiminy")]
             if matches!(event, tauri::RunEvent::ExitRequested { .. }) {
                 sleep_jiminy(&jiminy_sleep_url);
             }
-            #[cfg(not(feature = "jiminy"))]
-            let _ = event;
+            // The event loop exits the process without returning, so
+            // background work has to be wound down and the profile finished
+            // here.
+            if matches!(event, tauri::RunEvent::Exit) {
+                use tauri::Manager as _;
+                if let Some(state) = app.try_state::<tauri_state::AppState>() {
+                    tauri::async_runtime::block_on(state.shutdown.run(SHUTDOWN_GRACE));
+                }
+                lifecycle::finish_profile();
+                if let Some(profile) = RELAUNCH_AS_PROFILE.get() {
+                    spawn_as_profile(profile);
+                }
+            }
         });
 
     Ok(())
 }
 
+/// Set by `switch_profile`: the profile to start a fresh process as once
+/// this one has shut down (the new one needs the database lock).
+static RELAUNCH_AS_PROFILE: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+/// Quit, and come back as user profile `name`.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub(crate) fn relaunch_as_profile(app: &tauri::AppHandle, name: String) -> Result<(), String> {
+    RELAUNCH_AS_PROFILE
+        .set(name)
+        .map_err(|_| "already switching profiles".to_string())?;
+    app.exit(0);
+    Ok(())
+}
+
+#[cfg(any(target_os = "android", target_os = "ios"))]
+pub(crate) fn relaunch_as_profile(_app: &tauri::AppHandle, _name: String) -> Result<(), String> {
+    Err("profiles are not available on mobile".into())
+}
+
+fn spawn_as_profile(profile: &str) {
+    let spawned = std::env::current_exe()
+        .and_then(|exe| std::process::Command::new(exe).args(["--profile", profile]).spawn());
+    if let Err(e) = spawned {
+        tracing::error!("Could not restart as profile '{profile}': {e}");
+    }
+}
+
 /// Best-effort goodnight: tell the jiminy-bridge to play Jiminy's sleep
 /// animation as the app exits. Uses a raw blocking TCP request because the async
 /// runtime is being torn down at this point; the bridge keeps running and plays
@@ -836,7 +1128,7 @@ fn sleep_jiminy(bridge_url: &str) {
 }
 
 /// Bundle of values produced by backend init that the Tauri setup() callback
-/// needs to register state and spawn background tasks.
+/// (or `sovereign daemon`) needs to register state and spawn background tasks.
 struct BackendInit {
     config: AppConfig,
     profile_dir: std::path::PathBuf,
@@ -847,9 +1139,11 @@ struct BackendInit {
     skill_llm: Option<Arc<dyn sovereign_skills::SkillLlmAccess>>,
     decision_tx: tokio::sync::mpsc::Sender<ActionDecision>,
     feedback_tx: tokio::sync::mpsc::Sender<FeedbackEvent>,
-    orch_tx: mpsc::Sender<OrchestratorEvent>,
+    bus: Arc<EventBus>,
+    /// Subscribed before the orchestrator can publish anything.
     orch_rx: mpsc::Receiver<OrchestratorEvent>,
     autocommit: Arc<tokio::sync::Mutex<sovereign_ai::AutoCommitEngine>>,
+    shutdown: lifecycle::Shutdown,
     model_assignments: tauri_state::ModelAssignments,
     #[cfg(feature = "voice-stt")]
     stt_engine: Option<Arc<tokio::sync::Mutex<sovereign_ai::voice::stt::SttEngine>>>,
@@ -857,8 +1151,121 @@ struct BackendInit {
     vision: sovereign_ai::jiminy_vision::SharedVision,
 }
 
+impl BackendInit {
+    /// The shared state over this backend, still locked. `run_tauri` hands
+    /// it to Tauri; `sovereign daemon` holds it itself.
+    fn app_state(&self, theme: String) -> tauri_state::AppState {
+        tauri_state::AppState {
+            db: self.db.clone(),
+            orchestrator: self.orchestrator.clone(),
+            config: self.config.clone(),
+            config_reload: tokio::sync::watch::channel(self.config.clone()).0,
+            skill_registry: self.skill_registry.clone(),
+            skill_db: self.skill_db.clone(),
+            skill_llm: self.skill_llm.clone(),
+            decision_tx: self.decision_tx.clone(),
+            feedback_tx: self.feedback_tx.clone(),
+            bus: self.bus.clone(),
+            theme: std::sync::Mutex::new(theme),
+            autocommit: self.autocommit.clone(),
+            shutdown: self.shutdown.clone(),
+            model_assignments: std::sync::Mutex::new(self.model_assignments.clone()),
+            profile_dir: self.profile_dir.clone(),
+            detached_panels: Default::default(),
+            relevance: Default::default(),
+            unsealed: Default::default(),
+            #[cfg(feature = "encryption")]
+            account_key: tokio::sync::RwLock::new(None),
+            #[cfg(feature = "encryption")]
+            p2p_identity_key: tokio::sync::RwLock::new(None),
+            #[cfg(feature = "encryption")]
+            pending_pairing: tokio::sync::RwLock::new(None),
+            #[cfg(feature = "encryption")]
+            pending_security_key: tokio::sync::Mutex::new(None),
+            #[cfg(feature = "encryption")]
+            second_factor_recovery: tokio::sync::RwLock::new(None),
+            #[cfg(feature = "encryption")]
+            persona: tokio::sync::RwLock::new(None),
+            #[cfg(feature = "encryption")]
+            canary: std::sync::Mutex::new(None),
+            #[cfg(feature = "encryption")]
+            encrypted_db: tokio::sync::RwLock::new(None),
+            #[cfg(feature = "p2p")]
+            p2p_command_tx: tokio::sync::RwLock::new(None),
+            #[cfg(feature = "p2p")]
+            pairing_manager: std::sync::Arc::new(tokio::sync::RwLock::new(None)),
+            #[cfg(feature = "p2p")]
+            p2p_listen_addrs: std::sync::Arc::new(std::sync::RwLock::new(Vec::new())),
+            #[cfg(feature = "p2p")]
+            backup_host: tokio::sync::RwLock::new(None),
+            #[cfg(feature = "p2p")]
+            connectivity: std::sync::Arc::new(std::sync::atomic::AtomicU8::new(0)),
+            #[cfg(feature = "p2p")]
+            proximity_link: std::sync::RwLock::new(None),
+            #[cfg(feature = "comms")]
+            comms: tokio::sync::RwLock::new(None),
+            #[cfg(feature = "voice-stt")]
+            stt_engine: self.stt_engine.clone(),
+        }
+    }
+}
+
+/// How long quitting waits for background work, and then again for the
+/// final saves, before giving up on it.
+pub(crate) const SHUTDOWN_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Background upkeep every frontend needs: the 30s auto-commit tick (and a
+/// last commit of pending edits at shutdown) and the hourly purge of
+/// soft-deleted items.
+fn spawn_housekeeping(
+    db: Arc<sovereign_db::layered::LayeredGraphDB>,
+    autocommit: Arc<tokio::sync::Mutex<sovereign_ai::AutoCommitEngine>>,
+    shutdown: &lifecycle::Shutdown,
+) {
+    let mut stopping = shutdown.listener("auto-commit");
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+        loop {
+            tokio::select! {
+                _ = stopping.triggered() => break,
+                _ = interval.tick() => autocommit.lock().await.check_and_commit().await,
+            }
+        }
+        autocommit.lock().await.commit_all().await;
+    });
+
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            let max_age = std::time::Duration::from_secs(30 * 24 * 3600);
+            match (*db).purge_deleted(max_age).await {
+                Ok(n) if n > 0 => tracing::info!("Purged {n} soft-deleted items"),
+                Err(e) => tracing::warn!("Purge failed: {e}"),
+                _ => {}
+            }
+        }
+    });
+
+    // Local metrics reach disk once a minute, and at shutdown.
+    shutdown.on_shutdown("metrics", || async {
+        if let Err(e) = sovereign_core::metrics::flush() {
+            tracing::warn!("Could not save metrics: {e}");
+        }
+    });
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            if let Err(e) = sovereign_core::metrics::flush() {
+                tracing::warn!("Could not save metrics: {e}");
+            }
+        }
+    });
+}
+
 /// Backend init: crypto, DB, seeding, skills, orchestrator, channels.
-/// Called once from the Tauri setup() callback.
+/// Called once from the Tauri setup() callback, or by `sovereign daemon`.
 async fn init_backend(
     config: &AppConfig,
     profile_dir: std::path::PathBuf,
@@ -896,37 +1303,13 @@ async fn init_backend(
         Arc::new(sovereign_db::layered::LayeredGraphDB::new(raw_db_arc));
 
     // Skill registry
-    let mut registry = sovereign_skills::SkillRegistry::new();
+    let registry = setup::skill_registry(&profile_dir);
     let skill_db: Arc<dyn sovereign_skills::SkillDbAccess> =
         sovereign_skills::wrap_db(db_arc.clone());
-    registry.register(Box::new(sovereign_skills::skills::text_editor::TextEditorSkill));
-    registry.register(Box::new(sovereign_skills::skills::image::ImageSkill));
-    registry.register(Box::new(sovereign_skills::skills::pdf_export::PdfExportSkill));
-    registry.register(Box::new(sovereign_skills::skills::word_count::WordCountSkill));
-    registry.register(Box::new(sovereign_skills::skills::find_replace::FindReplaceSkill));
-    registry.register(Box::new(sovereign_skills::skills::search::SearchSkill));
-    registry.register(Box::new(sovereign_skills::skills::file_import::FileImportSkill));
-    registry.register(Box::new(sovereign_skills::skills::duplicate_document::DuplicateDocumentSkill));
-    registry.register(Box::new(sovereign_skills::skills::markdown_editor::MarkdownEditorSkill));
-    registry.register(Box::new(sovereign_skills::skills::video::VideoSkill));
-    registry.register(Box::new(sovereign_skills::skills::outline_extractor::OutlineExtractorSkill));
-    registry.register(Box::new(sovereign_skills::skills::link_checker::LinkCheckerSkill));
-    registry.register(Box::new(sovereign_skills::skills::pii_detector::PiiDetectorSkill));
-    registry.register(Box::new(sovereign_skills::skills::readability_score::ReadabilityScoreSkill));
-    registry.register(Box::new(sovereign_skills::skills::html_export::HtmlExportSkill));
-    registry.register(Box::new(sovereign_skills::skills::plaintext_export::PlaintextExportSkill));
-    registry.register(Box::new(sovereign_skills::skills::table_of_contents::TableOfContentsSkill));
-    registry.register(Box::new(sovereign_skills::skills::json_yaml_formatter::JsonYamlFormatterSkill));
-    registry.register(Box::new(sovereign_skills::skills::csv_to_md::CsvToMdSkill));
-    registry.register(Box::new(sovereign_skills::skills::redactor::RedactorSkill));
-    registry.register(Box::new(sovereign_skills::skills::backlink_map::BacklinkMapSkill));
-    registry.register(Box::new(sovereign_skills::skills::orphan_finder::OrphanFinderSkill));
-    registry.register(Box::new(sovereign_skills::skills::daily_journal::DailyJournalSkill));
-    registry.register(Box::new(sovereign_skills::skills::thread_summary::ThreadSummarySkill));
-    tracing::info!("Registered {} core skills", registry.all_skills().len());
 
     // Channels
-    let (orch_tx, orch_rx) = mpsc::channel::<OrchestratorEvent>();
+    let bus = EventBus::new();
+    let orch_rx = bus.subscribe::<OrchestratorEvent>();
     let (decision_tx, decision_rx) = tokio::sync::mpsc::channel::<ActionDecision>(32);
     let (feedback_tx, feedback_rx) = tokio::sync::mpsc::channel::<FeedbackEvent>(32);
 
@@ -940,13 +1323,14 @@ async fn init_backend(
     let orchestrator = match sovereign_ai::Orchestrator::new(
         config.ai.clone(),
         db_dyn,
-        orch_tx.clone(),
+        bus.publisher(),
     )
     .await
     {
         Ok(mut o) => {
             o.set_decision_rx(decision_rx);
             o.set_feedback_rx(feedback_rx);
+            o.set_auto_approve_threshold(config.autonomy.auto_approve_after);
             #[cfg(feature = "vision")]
             o.set_vision(vision.clone());
 
@@ -966,8 +1350,13 @@ async fn init_backend(
     };
 
     let autocommit = Arc::new(tokio::sync::Mutex::new(
-        sovereign_ai::AutoCommitEngine::new(db_arc.clone()),
+        sovereign_ai::AutoCommitEngine::new(db_arc.clone())
+            .with_journal(edit_journal::pending_commits_path(&profile_dir)),
     ));
+    // Commits a crashed run never made. With encryption the database opens
+    // at login, so install_session does this instead.
+    #[cfg(not(feature = "encryption"))]
+    autocommit.lock().await.recover().await;
 
     let model_assignments = tauri_state::ModelAssignments {
         router: config.ai.router_model.clone(),
@@ -977,6 +1366,19 @@ async fn init_backend(
     let skill_llm: Option<Arc<dyn sovereign_skills::SkillLlmAccess>> =
         orchestrator.as_ref().map(|o| llm_bridge::wrap_orchestrator(o.clone()));
 
+    // Installed skills become chat agent tools ("run word count on …").
+    registry
+        .jobs()
+        .set_max_concurrent(config.skills.max_concurrent_jobs);
+    let skill_registry = Arc::new(registry);
+    if let Some(o) = &orchestrator {
+        o.set_skill_tools(sovereign_ai::skill_tools::SkillTools::new(
+            skill_registry.clone(),
+            skill_db.clone(),
+            skill_llm.clone(),
+        ));
+    }
+
     // Mobile STT engine: shared Whisper instance for voice_transcribe_buffer
     // command. On desktop the cpal pipeline owns the SttEngine; here we
     // initialise one independently so Web Audio API audio can be transcribed.
@@ -1004,14 +1406,15 @@ async fn init_backend(
         profile_dir,
         db: db_arc,
         orchestrator,
-        skill_registry: Arc::new(registry),
+        skill_registry,
         skill_db,
         skill_llm,
         decision_tx,
         feedback_tx,
-        orch_tx,
+        bus,
         orch_rx,
         autocommit,
+        shutdown: lifecycle::Shutdown::new(),
         model_assignments,
         #[cfg(feature = "voice-stt")]
         stt_engine,
@@ -1049,12 +1452,22 @@ mod ipc_classification_guard {
         "get_theme",
         "check_auth_state",
         "validate_password",
+        "restore_from_recovery_phrase",
         "validate_password_policy",
         "complete_onboarding",
+        "list_security_keys",
+        "enroll_security_key",
+        "recover_security_key",
+        "lock_session",
         "get_profile",
         "save_profile",
+        "list_profiles",
+        "switch_profile",
         "get_config",
+        "update_config",
         "generate_password",
+        "secure_copy",
+        "clear_secure_clipboard",
         "consume_pair_qr_preview",
         "complete_onboarding_paired",
         "voice_transcribe_buffer",
@@ -1062,6 +1475,9 @@ mod ipc_classification_guard {
         "get_connectivity_state",
         "start_listening",
         "stop_listening",
+        // Detached windows: what the caller shows / close the caller.
+        "detached_panel_info",
+        "close_detached_panel",
         // Sidecar token for the vision UI — reads an env var, no session needed.
         "get_jiminy_token",
     ];
@@ -1072,8 +1488,20 @@ mod ipc_classification_guard {
     /// (reveal_pii_record, create_vault_entry, autofill_pii_record,
     /// commit_signup_capture, resolve_pii_tokens, generate_pair_qr).
     const GATED_COMMANDS: &[&str] = &[
+        // runtime feature registry
+        "list_features",
+        "set_feature_enabled",
+        // key rotation
+        "rotate_kek",
+        "rotate_document_keys",
+        "get_key_rotation_log",
         // ai
+        "get_system_health",
+        "get_metrics",
+        "get_workspace_stats",
+        "clear_metrics",
         "chat_message",
+        "get_chat_history",
         "search_documents",
         "search_query",
         "approve_action",
@@ -1087,20 +1515,57 @@ mod ipc_classification_guard {
         "list_documents",
         "list_threads",
         "get_document",
+        "list_backlinks",
+        "set_read_later",
+        "set_favorite",
+        "set_document_sensitivity",
+        "unseal_document",
+        "bulk_edit_documents",
+        "revert_bulk_edit",
+        "list_reminders",
+        "create_reminder",
+        "delete_reminder",
+        "list_reading_queue",
+        "share_document",
+        "open_share",
+        "meetings_starting",
+        "save_meeting_notes",
         "save_document",
+        "set_document_type",
         "create_document",
         "close_document",
+        "journal_edit",
+        "list_recoverable_edits",
+        "discard_journaled_edit",
         "delete_document",
         "list_commits",
         "restore_commit",
+        "get_commit_snapshot",
+        "document_activity",
+        "store_pasted_image",
+        "attach_file",
+        "remove_attachment",
+        "read_attachment",
+        "pdf_preview",
         "list_skills_for_doc",
         "execute_skill",
         "list_all_skills",
         "import_file",
+        "preview_source_import",
+        "import_source",
+        // skill permissions
+        "get_skill_permissions",
+        "set_skill_capability",
+        "list_skill_jobs",
+        "cancel_skill_job",
+        "clear_finished_skill_jobs",
         // canvas
         "canvas_load",
         "update_document_position",
         "canvas_load_messages",
+        "get_window_session",
+        "save_window_session",
+        "detach_panel",
         // threads
         "create_thread",
         "update_thread",
@@ -1109,9 +1574,15 @@ mod ipc_classification_guard {
         // contacts
         "list_contacts",
         "get_contact_detail",
+        "create_contact",
+        "update_contact",
         "list_conversations",
         "list_messages",
         "mark_message_read",
+        "translate_message",
+        "read_message_later",
+        "list_compose_channels",
+        "compose_message",
         "create_relationship",
         // browser / web / comms
         "get_comms_config",
@@ -1135,6 +1606,8 @@ mod ipc_classification_guard {
         "trigger_consolidation",
         // mobile / share (IPC-001: now require_unlocked + main-webview)
         "receive_shared_content",
+        "proximity_link_up",
+        "proximity_link_down",
         // pii (account_key-gated ones included)
         "resolve_pii_tokens",
         "list_pii_entities",
@@ -1156,14 +1629,29 @@ mod ipc_classification_guard {
         // pairing (generate_pair_qr is account_key-gated)
         "generate_pair_qr",
         "list_paired_devices",
-        "forget_paired_device",
+        "revoke_paired_device",
+        "set_device_sync_policy",
+        "set_document_sync_exclusions",
+        "rename_paired_device",
+        "set_device_sync_paused",
+        "set_device_trust",
+        "set_device_addresses",
+        "share_device_keys",
+        "get_sync_history",
         "get_local_peer_id",
+        "get_local_addresses",
         "trigger_sync_now",
+        "preview_sync",
+        "fetch_viewer_snapshot",
+        "remote_wipe_device",
+        "approve_remote_wipe",
         "cancel_pairing",
         "get_p2p_settings",
         "resolve_sync_conflict_keep_mine",
+        "resolve_sync_merge",
         // backup (P4)
         "backup_now",
+        "refresh_shards",
         "backup_status",
         "approve_shard_release",
         "deny_shard_release",
@@ -1177,7 +1665,12 @@ mod ipc_classification_guard {
         // ai
         "greet",
         "get_status",
+        "get_system_health",
+        "get_metrics",
+        "get_workspace_stats",
+        "clear_metrics",
         "chat_message",
+        "get_chat_history",
         "search_documents",
         "search_query",
         "approve_action",
@@ -1196,20 +1689,59 @@ mod ipc_classification_guard {
         "toggle_theme",
         "get_theme",
         "get_document",
+        "list_backlinks",
+        "set_read_later",
+        "set_favorite",
+        "set_document_sensitivity",
+        "unseal_document",
+        "bulk_edit_documents",
+        "revert_bulk_edit",
+        "list_reminders",
+        "create_reminder",
+        "delete_reminder",
+        "list_reading_queue",
+        "share_document",
+        "open_share",
+        "meetings_starting",
+        "save_meeting_notes",
         "save_document",
+        "set_document_type",
         "create_document",
         "close_document",
+        "journal_edit",
+        "list_recoverable_edits",
+        "discard_journaled_edit",
         "delete_document",
         "list_commits",
         "restore_commit",
+        "get_commit_snapshot",
+        "document_activity",
+        "store_pasted_image",
+        "attach_file",
+        "remove_attachment",
+        "read_attachment",
+        "pdf_preview",
         "list_skills_for_doc",
         "execute_skill",
         "list_all_skills",
         "import_file",
+        "preview_source_import",
+        "import_source",
+        // skill permissions
+        "get_skill_permissions",
+        "set_skill_capability",
+        "list_skill_jobs",
+        "cancel_skill_job",
+        "clear_finished_skill_jobs",
         // canvas
         "canvas_load",
         "update_document_position",
         "canvas_load_messages",
+        "get_window_session",
+        "save_window_session",
+        "detach_panel",
+        "detached_panel_info",
+        "close_detached_panel",
         // threads
         "create_thread",
         "update_thread",
@@ -1218,18 +1750,37 @@ mod ipc_classification_guard {
         // contacts
         "list_contacts",
         "get_contact_detail",
+        "create_contact",
+        "update_contact",
         "list_conversations",
         "list_messages",
         "mark_message_read",
+        "translate_message",
+        "read_message_later",
+        "list_compose_channels",
+        "compose_message",
         "create_relationship",
         // auth
         "check_auth_state",
         "validate_password",
+        "restore_from_recovery_phrase",
         "validate_password_policy",
         "complete_onboarding",
+        "list_security_keys",
+        "enroll_security_key",
+        "recover_security_key",
+        "rotate_kek",
+        "rotate_document_keys",
+        "get_key_rotation_log",
+        "lock_session",
         "get_profile",
         "save_profile",
+        "list_profiles",
+        "switch_profile",
         "get_config",
+        "update_config",
+        "list_features",
+        "set_feature_enabled",
         // browser / web / comms
         "get_comms_config",
         "save_comms_config",
@@ -1265,6 +1816,8 @@ mod ipc_classification_guard {
         "__browser_form_extracted",
         "autofill_pii_record",
         "generate_password",
+        "secure_copy",
+        "clear_secure_clipboard",
         "list_cookies_for_entity",
         "delete_cookie",
         "clear_entity_cookies",
@@ -1274,20 +1827,37 @@ mod ipc_classification_guard {
         "consume_pair_qr_preview",
         "complete_onboarding_paired",
         "list_paired_devices",
-        "forget_paired_device",
+        "revoke_paired_device",
+        "set_device_sync_policy",
+        "set_document_sync_exclusions",
+        "rename_paired_device",
+        "set_device_sync_paused",
+        "set_device_trust",
+        "set_device_addresses",
+        "share_device_keys",
+        "get_sync_history",
         "get_local_peer_id",
+        "get_local_addresses",
         "trigger_sync_now",
+        "preview_sync",
+        "fetch_viewer_snapshot",
+        "remote_wipe_device",
+        "approve_remote_wipe",
         "cancel_pairing",
         "get_p2p_settings",
         "resolve_sync_conflict_keep_mine",
+        "resolve_sync_merge",
         // backup (P4)
         "backup_now",
+        "refresh_shards",
         "backup_status",
         "approve_shard_release",
         "deny_shard_release",
         // mobile
         "voice_transcribe_buffer",
         "receive_shared_content",
+        "proximity_link_up",
+        "proximity_link_down",
         "set_connectivity_state",
         "get_connectivity_state",
         // voice
@@ -0,0 +1,275 @@
+//! `sovereign maintain` and the daemon's daily maintenance pass.
+//!
+//! One pass, safe to repeat at any time:
+//!   - trash older than [`TRASH_RETENTION`] is purged;
+//!   - each document's history is compacted to its latest [`COMMITS_KEPT`]
+//!     commits (the head always stays);
+//!   - media blobs nothing refers to any more — no live or trashed
+//!     document, no kept commit, no synced document's path — are deleted,
+//!     along with abandoned partial transfers. Only files older than
+//!     [`ORPHAN_GRACE`] go, so a blob that arrived ahead of its document
+//!     survives;
+//!   - encrypted documents missing from the search index are indexed;
+//!   - the key usage log is archived once it passes [`KEY_LOG_MAX_BYTES`].
+//!
+//! A failing step is reported and the others still run. The daemon runs a
+//! pass every [`INTERVAL`] while unlocked; `sovereign maintain` asks it for
+//! one now.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sovereign_core::config::AppConfig;
+use sovereign_db::GraphDB;
+
+use crate::daemon_client::Client;
+use crate::media;
+use crate::tauri_state::AppState;
+
+/// How often the daemon runs a pass.
+pub const INTERVAL: Duration = Duration::from_secs(24 * 3600);
+
+/// Trash kept before it is purged (as the hourly housekeeping does).
+pub const TRASH_RETENTION: Duration = Duration::from_secs(30 * 24 * 3600);
+
+/// Commits kept per document. Generous: compaction here only stops history
+/// growing without bound; `commits compact` trims harder on request.
+pub const COMMITS_KEPT: usize = 200;
+
+/// Unreferenced blobs and partial transfers younger than this are kept.
+pub const ORPHAN_GRACE: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// Size past which the key usage log is archived and restarted.
+pub const KEY_LOG_MAX_BYTES: u64 = 16 * 1024 * 1024;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MaintenanceReport {
+    pub trash_purged: u64,
+    pub commits_pruned: u64,
+    pub blobs_removed: u64,
+    pub bytes_freed: u64,
+    pub documents_indexed: u64,
+    /// File name of the archived key usage log, when it was rotated.
+    pub key_log_archive: Option<String>,
+    pub errors: Vec<String>,
+}
+
+impl MaintenanceReport {
+    pub fn render(&self) -> String {
+        let mut lines = vec![
+            format!("Trash: {} items purged", self.trash_purged),
+            format!("History: {} old commits removed", self.commits_pruned),
+            format!(
+                "Media: {} unused files removed ({:.1} MiB)",
+                self.blobs_removed,
+                self.bytes_freed as f64 / (1024.0 * 1024.0)
+            ),
+            format!("Search index: {} documents indexed", self.documents_indexed),
+            match &self.key_log_archive {
+                Some(archive) => format!("Key usage log: archived as {archive}"),
+                None => "Key usage log: no rotation needed".to_string(),
+            },
+        ];
+        lines.extend(self.errors.iter().map(|e| format!("error: {e}")));
+        lines.join("\n")
+    }
+}
+
+/// Run every step once against the unlocked session.
+pub async fn run(state: &AppState) -> MaintenanceReport {
+    let mut report = MaintenanceReport::default();
+    let db: &dyn GraphDB = &*state.db;
+
+    // Purge and compact first, so what they drop no longer holds blobs.
+    match db.purge_deleted(TRASH_RETENTION).await {
+        Ok(n) => report.trash_purged = n,
+        Err(e) => report.errors.push(format!("trash purge: {e}")),
+    }
+    match compact_histories(db).await {
+        Ok(n) => report.commits_pruned = n,
+        Err(e) => report.errors.push(format!("commit compaction: {e}")),
+    }
+    match referenced_blobs(db).await {
+        Ok(referenced) => {
+            match remove_orphans(
+                &media::media_dir(),
+                &referenced,
+                ORPHAN_GRACE,
+                SystemTime::now(),
+            ) {
+                Ok((removed, bytes)) => {
+                    report.blobs_removed = removed;
+                    report.bytes_freed = bytes;
+                }
+                Err(e) => report.errors.push(format!("blob cleanup: {e}")),
+            }
+        }
+        // Without the full set of references nothing is safe to delete.
+        Err(e) => report
+            .errors
+            .push(format!("blob cleanup skipped, references unreadable: {e}")),
+    }
+
+    let encrypted = state.encrypted_db.read().await.clone();
+    if let Some(encrypted) = encrypted {
+        match encrypted.reindex_documents().await {
+            Ok(n) => report.documents_indexed = n,
+            Err(e) => report.errors.push(format!("search index: {e}")),
+        }
+    }
+    match sovereign_crypto::key_audit::rotate_installed(KEY_LOG_MAX_BYTES) {
+        Ok(archive) => {
+            report.key_log_archive =
+                archive.and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()));
+        }
+        Err(e) => report.errors.push(format!("key usage log: {e}")),
+    }
+    report
+}
+
+/// `sovereign maintain`: run a pass in the daemon (or a one-off session)
+/// and print what it did.
+pub async fn command(config: &AppConfig, socket: Option<PathBuf>) -> anyhow::Result<()> {
+    let mut client = Client::connect_or_start(config, socket).await?;
+    let report: MaintenanceReport =
+        serde_json::from_value(client.call("maintenance.run", Value::Null).await?)?;
+    println!("{}", report.render());
+    if !report.errors.is_empty() {
+        anyhow::bail!("maintenance finished with {} error(s)", report.errors.len());
+    }
+    Ok(())
+}
+
+async fn compact_histories(db: &dyn GraphDB) -> sovereign_db::DbResult<u64> {
+    let mut pruned = 0;
+    for doc in db.list_documents(None).await? {
+        let Some(id) = doc.id_string() else { continue };
+        pruned += db.prune_document_commits(&id, COMMITS_KEPT).await?;
+    }
+    Ok(pruned)
+}
+
+/// Hashes of every blob a live or trashed document, or one of their
+/// commits, still lists.
+async fn referenced_blobs(db: &dyn GraphDB) -> sovereign_db::DbResult<HashSet<String>> {
+    let mut docs = db.list_documents(None).await?;
+    docs.extend(db.list_deleted_documents().await?);
+    let mut paths = HashSet::new();
+    for doc in docs {
+        paths.extend(media::content_paths(&doc.content));
+        let Some(id) = doc.id_string() else { continue };
+        for commit in db.list_document_commits(&id).await? {
+            paths.extend(media::content_paths(&commit.snapshot.content));
+        }
+    }
+
+    let mut hashes: HashSet<String> = paths
+        .iter()
+        .filter_map(|p| media::blob_hash(p).map(str::to_string))
+        .collect();
+    // Synced documents name media by the sender's path; the store knows
+    // which blob each such path maps to.
+    #[cfg(feature = "p2p")]
+    {
+        let store = sovereign_p2p::blob_store::BlobStore::open(media::media_dir());
+        hashes.extend(paths.iter().filter_map(|p| store.aliased_hash(p)));
+    }
+    Ok(hashes)
+}
+
+/// Delete the blobs in `dir` that aren't `referenced`, and leftover
+/// partial writes (`.part`, `.paste`), when last modified more than
+/// `grace` before `now`. Returns the files removed and their total size.
+fn remove_orphans(
+    dir: &Path,
+    referenced: &HashSet<String>,
+    grace: Duration,
+    now: SystemTime,
+) -> std::io::Result<(u64, u64)> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((0, 0)),
+        Err(e) => return Err(e),
+    };
+    let (mut removed, mut bytes) = (0, 0);
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else { continue };
+        let partial = name.ends_with(".part") || name.ends_with(".paste");
+        let hash = name
+            .strip_suffix(".part")
+            .or_else(|| name.strip_suffix(".paste"))
+            .unwrap_or(name);
+        // index.json and anything else not named by a hash stays.
+        if !media::valid_hash(hash) || (!partial && referenced.contains(hash)) {
+            continue;
+        }
+        let meta = entry.metadata()?;
+        let age = meta
+            .modified()
+            .ok()
+            .and_then(|m| now.duration_since(m).ok())
+            .unwrap_or_default();
+        if !meta.is_file() || age < grace {
+            continue;
+        }
+        std::fs::remove_file(entry.path())?;
+        removed += 1;
+        bytes += meta.len();
+    }
+    Ok((removed, bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removes_only_old_unreferenced_blobs_and_leftovers() {
+        let dir = tempfile::tempdir().unwrap();
+        let kept = "a".repeat(64);
+        let orphan = "b".repeat(64);
+        for name in [
+            kept.clone(),
+            orphan.clone(),
+            format!("{kept}.part"),
+            "index.json".to_string(),
+        ] {
+            std::fs::write(dir.path().join(name), b"1234").unwrap();
+        }
+        let referenced = HashSet::from([kept.clone()]);
+
+        // Everything is younger than the grace period: nothing goes.
+        let now = SystemTime::now();
+        let none = remove_orphans(dir.path(), &referenced, ORPHAN_GRACE, now).unwrap();
+        assert_eq!(none, (0, 0));
+
+        let later = now + ORPHAN_GRACE + Duration::from_secs(60);
+        let done = remove_orphans(dir.path(), &referenced, ORPHAN_GRACE, later).unwrap();
+        assert_eq!(done, (2, 8));
+        assert!(dir.path().join(&kept).exists());
+        assert!(dir.path().join("index.json").exists());
+        assert!(!dir.path().join(&orphan).exists());
+        assert!(!dir.path().join(format!("{kept}.part")).exists());
+
+        // A second pass finds nothing more.
+        assert_eq!(
+            remove_orphans(dir.path(), &referenced, ORPHAN_GRACE, later).unwrap(),
+            (0, 0)
+        );
+        assert_eq!(
+            remove_orphans(
+                &dir.path().join("missing"),
+                &referenced,
+                ORPHAN_GRACE,
+                later
+            )
+            .unwrap(),
+            (0, 0)
+        );
+    }
+}
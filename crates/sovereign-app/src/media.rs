@@ -0,0 +1,246 @@
+//! Images pasted into the document editor, and files attached to documents.
+//!
+//! A pasted image is written to the P2P blob store's directory under its
+//! SHA-256, the same layout the store uses for blobs received from peers.
+//! The document lists that path in its `images` (so sync ships the bytes
+//! like any other media) and the body refers to it as `media:<hash>`,
+//! which doesn't depend on where this device keeps the file. Attached
+//! files are stored the same way and listed in the document's `files`.
+//!
+//! The webview loads the bytes through the `media` URI scheme, which only
+//! serves hash-named files from that directory, and only images, audio and
+//! video. Range requests are honoured so players can seek.
+
+use std::path::PathBuf;
+
+use sha2::{Digest, Sha256};
+
+/// Largest image accepted from the clipboard (20 MiB).
+pub const MAX_PASTED_IMAGE_BYTES: usize = 20 * 1024 * 1024;
+
+/// Largest file accepted as a document attachment (100 MiB).
+pub const MAX_ATTACHMENT_BYTES: usize = 100 * 1024 * 1024;
+
+/// Where pasted media lives: the sync blob store directory.
+pub fn media_dir() -> PathBuf {
+    sovereign_core::sovereign_dir().join("crypto").join("sync_blobs")
+}
+
+/// Image type from the file's leading bytes. Only formats the webview can
+/// show are accepted.
+pub fn sniff_image(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(b"\xff\xd8\xff") {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+/// Type of anything the `media` scheme may serve: images, audio, video.
+pub fn sniff_media(bytes: &[u8]) -> Option<&'static str> {
+    if let Some(mime) = sniff_image(bytes) {
+        return Some(mime);
+    }
+    if bytes.starts_with(b"ID3") || (bytes.len() >= 2 && bytes[0] == 0xff && bytes[1] & 0xe0 == 0xe0) {
+        Some("audio/mpeg")
+    } else if bytes.starts_with(b"OggS") {
+        Some("audio/ogg")
+    } else if bytes.starts_with(b"fLaC") {
+        Some("audio/flac")
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WAVE" {
+        Some("audio/wav")
+    } else if bytes.len() >= 12 && &bytes[4..8] == b"ftyp" {
+        match &bytes[8..12] {
+            b"M4A " | b"M4B " => Some("audio/mp4"),
+            b"qt  " => Some("video/quicktime"),
+            _ => Some("video/mp4"),
+        }
+    } else if bytes.starts_with(b"\x1a\x45\xdf\xa3") {
+        Some("video/webm")
+    } else {
+        None
+    }
+}
+
+/// Type of an attachment, for the viewer and for saving. Anything not
+/// recognised is plain bytes.
+pub fn sniff_attachment(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(b"%PDF-") {
+        "application/pdf"
+    } else {
+        sniff_media(bytes).unwrap_or("application/octet-stream")
+    }
+}
+
+/// Every media path a document's content lists: images, videos and their
+/// thumbnails, and attachments.
+pub fn content_paths(content: &str) -> Vec<String> {
+    let fields = sovereign_core::content::ContentFields::parse(content);
+    let mut paths: Vec<String> = fields
+        .images
+        .into_iter()
+        .map(|i| i.path)
+        .chain(
+            fields
+                .videos
+                .into_iter()
+                .flat_map(|v| std::iter::once(v.path).chain(v.thumbnail_path)),
+        )
+        .chain(fields.files.into_iter().map(|f| f.path))
+        .filter(|p| !p.is_empty())
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// The blob a stored path names, when its file name is a SHA-256. Paths
+/// differ between devices; the hash doesn't.
+pub fn blob_hash(path: &str) -> Option<&str> {
+    let name = std::path::Path::new(path).file_name()?.to_str()?;
+    valid_hash(name).then_some(name)
+}
+
+pub fn valid_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Store an image's bytes; returns its hash and local path. Storing the
+/// same image twice is a no-op.
+pub fn store_image(bytes: &[u8]) -> Result<(String, PathBuf), String> {
+    if bytes.len() > MAX_PASTED_IMAGE_BYTES {
+        return Err(format!(
+            "image is too large ({} MiB max)",
+            MAX_PASTED_IMAGE_BYTES / (1024 * 1024)
+        ));
+    }
+    if sniff_image(bytes).is_none() {
+        return Err("clipboard data is not a PNG, JPEG, GIF or WebP image".into());
+    }
+    store_blob(bytes)
+}
+
+/// Store an attachment's bytes; returns its hash and local path.
+pub fn store_attachment(bytes: &[u8]) -> Result<(String, PathBuf), String> {
+    if bytes.len() > MAX_ATTACHMENT_BYTES {
+        return Err(format!(
+            "file is too large ({} MiB max)",
+            MAX_ATTACHMENT_BYTES / (1024 * 1024)
+        ));
+    }
+    store_blob(bytes)
+}
+
+/// Store blob bytes under their hash, whatever they are.
+pub fn store_blob(bytes: &[u8]) -> Result<(String, PathBuf), String> {
+    let hash = format!("{:x}", Sha256::digest(bytes));
+    let dir = media_dir();
+    let path = dir.join(&hash);
+    if !path.is_file() {
+        std::fs::create_dir_all(&dir).map_err(|e| format!("media dir: {e}"))?;
+        let tmp = dir.join(format!("{hash}.paste"));
+        std::fs::write(&tmp, bytes).map_err(|e| format!("media write: {e}"))?;
+        std::fs::rename(&tmp, &path).map_err(|e| format!("media write: {e}"))?;
+    }
+    Ok((hash, path))
+}
+
+/// Parse a single `Range: bytes=…` header into an inclusive byte span of a
+/// `len`-byte body. Multi-range and unsatisfiable requests yield None.
+fn parse_range(header: &str, len: u64) -> Option<(u64, u64)> {
+    let spec = header.trim().strip_prefix("bytes=")?;
+    if spec.contains(',') || len == 0 {
+        return None;
+    }
+    let (start, end) = spec.split_once('-')?;
+    let (start, end) = if start.is_empty() {
+        let suffix: u64 = end.parse().ok()?;
+        (len.saturating_sub(suffix), len - 1)
+    } else {
+        let start: u64 = start.parse().ok()?;
+        let end = if end.is_empty() { len - 1 } else { end.parse::<u64>().ok()?.min(len - 1) };
+        (start, end)
+    };
+    (start <= end && start < len).then_some((start, end))
+}
+
+/// Handler for the `media` URI scheme: `media://localhost/<sha256>`.
+pub fn serve(request: &tauri::http::Request<Vec<u8>>) -> tauri::http::Response<Vec<u8>> {
+    use tauri::http::{header, Response, StatusCode};
+    let hash = request.uri().path().trim_start_matches('/');
+    let found = valid_hash(hash)
+        .then(|| std::fs::read(media_dir().join(hash)).ok())
+        .flatten()
+        .and_then(|bytes| sniff_media(&bytes).map(|mime| (mime, bytes)));
+    let Some((mime, bytes)) = found else {
+        return Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Vec::new())
+            .unwrap_or_default();
+    };
+    let builder = Response::builder()
+        .header(header::CONTENT_TYPE, mime)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::CACHE_CONTROL, "private, max-age=31536000, immutable");
+    let len = bytes.len() as u64;
+    let range = request
+        .headers()
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| parse_range(v, len));
+    match range {
+        Some(Some((start, end))) => builder
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(header::CONTENT_RANGE, format!("bytes {start}-{end}/{len}"))
+            .body(bytes[start as usize..=end as usize].to_vec()),
+        Some(None) => Response::builder()
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{len}"))
+            .body(Vec::new()),
+        None => builder.body(bytes),
+    }
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_known_image_formats_are_accepted() {
+        assert_eq!(sniff_image(b"\x89PNG\r\n\x1a\n...."), Some("image/png"));
+        assert_eq!(sniff_image(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(sniff_image(b"<svg onload=alert(1)>"), None);
+        assert!(store_image(b"not an image").is_err());
+        assert!(!valid_hash("../../etc/passwd"));
+        assert!(valid_hash(&"a".repeat(64)));
+    }
+
+    #[test]
+    fn audio_and_video_are_served_but_pdfs_are_not() {
+        assert_eq!(sniff_media(b"ID3\x04\0\0"), Some("audio/mpeg"));
+        assert_eq!(sniff_media(b"OggS\0\x02"), Some("audio/ogg"));
+        assert_eq!(sniff_media(b"\0\0\0\x20ftypM4A \0\0"), Some("audio/mp4"));
+        assert_eq!(sniff_media(b"\0\0\0\x20ftypisom\0\0"), Some("video/mp4"));
+        assert_eq!(sniff_media(b"%PDF-1.7"), None);
+        assert_eq!(sniff_attachment(b"%PDF-1.7"), "application/pdf");
+        assert_eq!(sniff_attachment(b"plain text"), "application/octet-stream");
+    }
+
+    #[test]
+    fn range_headers_resolve_to_inclusive_spans() {
+        assert_eq!(parse_range("bytes=0-99", 1000), Some((0, 99)));
+        assert_eq!(parse_range("bytes=900-", 1000), Some((900, 999)));
+        assert_eq!(parse_range("bytes=-100", 1000), Some((900, 999)));
+        assert_eq!(parse_range("bytes=500-5000", 1000), Some((500, 999)));
+        assert_eq!(parse_range("bytes=1000-", 1000), None);
+        assert_eq!(parse_range("bytes=0-1,5-9", 1000), None);
+        assert_eq!(parse_range("items=0-1", 1000), None);
+    }
+}
@@ -0,0 +1,324 @@
+//! Meeting notes: turn a recorded calendar meeting into a document.
+//!
+//! The meeting prompt in the frontend asks `meetings_starting` for events
+//! from the CalDAV calendar (see `sovereign_comms::calendar`), records the
+//! meeting through `voice_transcribe_buffer`, and hands the transcript to
+//! [`save`]. The notes become a `transcript` document listing the attendees
+//! — linked to their contacts when an address matches — and the action
+//! items found in the transcript as `- [ ]` checklist lines, which the tasks
+//! projection picks up like any other task.
+//!
+//! The notes go to the thread earlier notes of the same meeting went to,
+//! else to a thread named in the event summary, else to [`MEETINGS_THREAD`].
+
+use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use sovereign_core::content::ContentFields;
+use sovereign_db::schema::{DocType, Document, Thread};
+use sovereign_db::GraphDB;
+
+pub const MEETINGS_THREAD: &str = "Meetings";
+
+/// Phrases that mark a sentence as a commitment.
+const ACTION_CUES: &[&str] = &[
+    "action item",
+    "to do",
+    "todo",
+    "follow up",
+    "follow-up",
+    "i'll ",
+    "i will ",
+    "we'll ",
+    "we will ",
+    "we need to ",
+    "needs to ",
+    "let's ",
+    "can you ",
+    "could you ",
+];
+
+/// Leading words dropped from an action item.
+const ACTION_PREFIXES: &[&str] = &[
+    "action item:",
+    "action item",
+    "todo:",
+    "to do:",
+    "okay,",
+    "ok,",
+    "so,",
+    "and ",
+    "so ",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeetingAttendee {
+    pub email: String,
+    pub name: Option<String>,
+}
+
+/// A calendar event as the meeting prompt sees it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Meeting {
+    pub uid: String,
+    pub summary: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub location: Option<String>,
+    pub attendees: Vec<MeetingAttendee>,
+}
+
+#[cfg(feature = "comms")]
+impl From<sovereign_comms::calendar::CalendarEvent> for Meeting {
+    fn from(event: sovereign_comms::calendar::CalendarEvent) -> Self {
+        Self {
+            uid: event.uid,
+            summary: event.summary,
+            start: event.start,
+            end: event.end,
+            location: event.location,
+            attendees: event
+                .attendees
+                .into_iter()
+                .map(|a| MeetingAttendee {
+                    email: a.email,
+                    name: a.name,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SavedNotes {
+    pub document_id: String,
+    pub thread_id: String,
+    pub action_items: usize,
+    pub linked_contacts: usize,
+}
+
+/// Sentences of the transcript that read as commitments, tidied into task
+/// text. Whisper output has little structure, so this is a cue-phrase
+/// heuristic: it favours catching an item over missing one.
+pub fn action_items(transcript: &str) -> Vec<String> {
+    let mut items: Vec<String> = Vec::new();
+    for sentence in transcript.split(['.', '!', '?', '\n']) {
+        let sentence = sentence.trim();
+        let lower = format!("{} ", sentence.to_lowercase());
+        if !ACTION_CUES.iter().any(|cue| lower.contains(cue)) {
+            continue;
+        }
+        let mut text = sentence;
+        while let Some(prefix) = ACTION_PREFIXES.iter().find(|p| {
+            text.get(..p.len())
+                .is_some_and(|head| head.eq_ignore_ascii_case(p))
+        }) {
+            text = text[prefix.len()..].trim_start();
+        }
+        let text = text.trim_end_matches([',', ';', ':']).trim();
+        if text.split_whitespace().count() < 3 {
+            continue;
+        }
+        let mut chars = text.chars();
+        let item = match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+            None => continue,
+        };
+        if !items.iter().any(|i| i.eq_ignore_ascii_case(&item)) {
+            items.push(item);
+        }
+    }
+    items
+}
+
+/// Document title: the summary and the local start date.
+pub fn notes_title(meeting: &Meeting) -> String {
+    format!(
+        "{} — {}",
+        meeting.summary,
+        meeting.start.with_timezone(&Local).format("%Y-%m-%d")
+    )
+}
+
+/// Markdown body of the notes. `contacts` pairs each attendee with the id
+/// of the contact its address matched, if any; matched attendees link to
+/// the contact id (`contact:…`), which the document panel opens.
+pub fn notes_body(
+    meeting: &Meeting,
+    contacts: &[(MeetingAttendee, Option<String>)],
+    transcript: &str,
+    items: &[String],
+) -> String {
+    let start = meeting.start.with_timezone(&Local);
+    let end = meeting.end.with_timezone(&Local);
+    let mut body = format!(
+        "**When:** {}–{}",
+        start.format("%Y-%m-%d %H:%M"),
+        end.format("%H:%M")
+    );
+    if let Some(location) = meeting.location.as_deref().filter(|l| !l.is_empty()) {
+        body.push_str(&format!(" · **Where:** {location}"));
+    }
+    body.push('\n');
+    if !contacts.is_empty() {
+        let names: Vec<String> = contacts
+            .iter()
+            .map(|(attendee, contact_id)| {
+                let shown = attendee.name.as_deref().unwrap_or(&attendee.email);
+                match contact_id {
+                    Some(id) => format!("[{shown}]({id})"),
+                    None => shown.to_string(),
+                }
+            })
+            .collect();
+        body.push_str(&format!("**Attendees:** {}\n", names.join(", ")));
+    }
+
+    body.push_str("\n## Action items\n\n");
+    if items.is_empty() {
+        body.push_str("_None found in the transcript._\n");
+    }
+    for item in items {
+        body.push_str(&format!("- [ ] {item}\n"));
+    }
+    body.push_str("\n## Transcript\n\n");
+    body.push_str(transcript.trim());
+    body.push('\n');
+    body
+}
+
+/// The thread named in `summary`, preferring the longest name, so
+/// "Website redesign sync" lands in "Website redesign" over "Website".
+fn thread_named_in<'a>(threads: &'a [Thread], summary: &str) -> Option<&'a Thread> {
+    let summary = summary.to_lowercase();
+    threads
+        .iter()
+        .filter(|t| t.name.len() >= 3 && summary.contains(&t.name.to_lowercase()))
+        .max_by_key(|t| t.name.len())
+}
+
+async fn notes_thread(db: &dyn GraphDB, meeting: &Meeting) -> Result<String> {
+    let prefix = format!("{} — ", meeting.summary);
+    let earlier = db
+        .search_documents_by_title(&meeting.summary)
+        .await?
+        .into_iter()
+        .filter(|d| d.doc_type == DocType::Transcript && d.title.starts_with(&prefix))
+        .max_by_key(|d| d.created_at);
+    if let Some(doc) = earlier {
+        return Ok(doc.thread_id);
+    }
+
+    let threads = db.list_threads().await?;
+    let thread = match thread_named_in(&threads, &meeting.summary)
+        .or_else(|| threads.iter().find(|t| t.name == MEETINGS_THREAD))
+    {
+        Some(t) => t.clone(),
+        None => {
+            db.create_thread(Thread::new(
+                MEETINGS_THREAD.into(),
+                "Notes from recorded meetings".into(),
+            ))
+            .await?
+        }
+    };
+    thread
+        .id_string()
+        .ok_or_else(|| anyhow::anyhow!("thread missing ID"))
+}
+
+/// Save the notes for `meeting` and return where they went.
+pub async fn save(db: &dyn GraphDB, meeting: &Meeting, transcript: &str) -> Result<SavedNotes> {
+    let mut contacts = Vec::with_capacity(meeting.attendees.len());
+    for attendee in &meeting.attendees {
+        let contact = db.find_contact_by_address(&attendee.email).await?;
+        let id = contact.and_then(|c| c.id_string());
+        contacts.push((attendee.clone(), id));
+    }
+    let items = action_items(transcript);
+    let fields = ContentFields {
+        body: notes_body(meeting, &contacts, transcript, &items),
+        ..Default::default()
+    };
+
+    let thread_id = notes_thread(db, meeting).await?;
+    let mut doc = Document::new(notes_title(meeting), thread_id.clone(), true);
+    doc.doc_type = DocType::Transcript;
+    doc.content = fields.serialize();
+    let created = db.create_document(doc).await?;
+    let document_id = created
+        .id_string()
+        .ok_or_else(|| anyhow::anyhow!("document missing ID"))?;
+    Ok(SavedNotes {
+        document_id,
+        thread_id,
+        action_items: items.len(),
+        linked_contacts: contacts.iter().filter(|(_, id)| id.is_some()).count(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meeting() -> Meeting {
+        Meeting {
+            uid: "m1".into(),
+            summary: "Website redesign sync".into(),
+            start: "2026-03-17T09:00:00Z".parse().unwrap(),
+            end: "2026-03-17T09:30:00Z".parse().unwrap(),
+            location: Some("Room 4".into()),
+            attendees: vec![],
+        }
+    }
+
+    #[test]
+    fn action_items_come_from_commitments() {
+        let transcript = "Thanks for joining. Action item: send the mockups to Ana by Friday. \
+            The numbers look fine! I'll update the budget sheet.\n\
+            We need to book the venue. Okay, so we need to book the venue. Sounds good?";
+        assert_eq!(
+            action_items(transcript),
+            [
+                "Send the mockups to Ana by Friday",
+                "I'll update the budget sheet",
+                "We need to book the venue",
+            ]
+        );
+        assert!(action_items("Nothing to report today. Great work.").is_empty());
+    }
+
+    #[test]
+    fn body_links_known_attendees_and_lists_tasks() {
+        let ana = MeetingAttendee {
+            email: "ana@example.com".into(),
+            name: Some("Ana Ruiz".into()),
+        };
+        let ben = MeetingAttendee {
+            email: "ben@example.com".into(),
+            name: None,
+        };
+        let body = notes_body(
+            &meeting(),
+            &[(ana, Some("contact:abc".into())), (ben, None)],
+            " We will ship it. ",
+            &["We will ship it".into()],
+        );
+        assert!(body.contains("**Where:** Room 4"));
+        assert!(body.contains("**Attendees:** [Ana Ruiz](contact:abc), ben@example.com\n"));
+        assert!(body.contains("## Action items\n\n- [ ] We will ship it\n"));
+        assert!(body.ends_with("## Transcript\n\nWe will ship it.\n"));
+    }
+
+    #[test]
+    fn the_longest_thread_name_in_the_summary_wins() {
+        let threads = vec![
+            Thread::new("Website".into(), String::new()),
+            Thread::new("Website redesign".into(), String::new()),
+            Thread::new("Budget".into(), String::new()),
+        ];
+        let found = thread_named_in(&threads, "website Redesign sync").unwrap();
+        assert_eq!(found.name, "Website redesign");
+        assert!(thread_named_in(&threads, "Standup").is_none());
+    }
+}
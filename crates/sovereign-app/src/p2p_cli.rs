@@ -0,0 +1,249 @@
+//! `sovereign pair-device`, `enroll-guardian`, `initiate-recovery` and
+//! `approve-recovery`: the Devices and Backup panels from a terminal.
+//!
+//! Like `chat`, they go through the daemon (or a one-off backend when none
+//! is running), so pairing is answered by the same P2P node and shards go
+//! out through the same backup flow as in the app:
+//!
+//!   - `pair-device` arms a pairing offer and prints the offer and code to
+//!     enter on the new device, then waits for it to finish;
+//!   - `enroll-guardian` checks the device is paired, names it, and runs a
+//!     backup that hands every paired device a key shard;
+//!   - `initiate-recovery` asks the guardians for their shards and, once
+//!     enough have released them, restores the last backup into this
+//!     profile. Re-run it to poll;
+//!   - `approve-recovery` is the guardian's side: approve or deny pending
+//!     shard-release requests.
+
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use sovereign_core::config::AppConfig;
+use sovereign_crypto::guardian::shamir::GuardianPolicy;
+
+use crate::daemon_client::{Client, Incoming};
+
+pub async fn pair_device(config: &AppConfig, socket: Option<PathBuf>) -> Result<()> {
+    let mut client = Client::connect_or_start(config, socket).await?;
+    client.call("subscribe", Value::Null).await?;
+    let offer = client.call("pairing.offer", Value::Null).await?;
+    let expires_at = offer["expires_at"].as_i64().unwrap_or_default();
+    println!("On the new device, choose \"Pair with an existing device\" and enter:");
+    println!(
+        "  offer: {}",
+        offer["qr_payload_b64"].as_str().unwrap_or_default()
+    );
+    println!("  code:  {}", offer["pin"].as_str().unwrap_or_default());
+    println!(
+        "Waiting for it until {} (Ctrl-C cancels)…",
+        chrono::DateTime::from_timestamp_millis(expires_at)
+            .map(|t| t
+                .with_timezone(&chrono::Local)
+                .format("%H:%M:%S")
+                .to_string())
+            .unwrap_or_default()
+    );
+
+    let remaining = expires_at - chrono::Utc::now().timestamp_millis();
+    let expiry = tokio::time::sleep(Duration::from_millis(remaining.max(0) as u64));
+    tokio::pin!(expiry);
+    loop {
+        let incoming = tokio::select! {
+            incoming = client.next() => incoming?,
+            _ = tokio::signal::ctrl_c() => {
+                client.call("pairing.cancel", Value::Null).await?;
+                bail!("Pairing cancelled");
+            }
+            _ = &mut expiry => {
+                client.call("pairing.cancel", Value::Null).await?;
+                bail!("The pairing offer expired; run this again for a new one");
+            }
+        };
+        let Some(incoming) = incoming else {
+            bail!("the daemon closed the connection");
+        };
+        let Incoming::Event { event, payload } = incoming else {
+            continue;
+        };
+        match event.as_str() {
+            "device-paired" => {
+                println!(
+                    "Paired with {} ({})",
+                    payload["device_name"].as_str().unwrap_or_default(),
+                    payload["device_id"].as_str().unwrap_or_default()
+                );
+                return Ok(());
+            }
+            "pairing-failed" => {
+                let reason = payload["reason"].as_str().unwrap_or_default();
+                if payload["offer_dead"] == true {
+                    bail!("Pairing failed: {reason}; run this again for a new offer");
+                }
+                eprintln!("A pairing attempt failed ({reason}); still waiting…");
+            }
+            "session-locked" => bail!("the session locked; unlock it again to pair"),
+            _ => {}
+        }
+    }
+}
+
+pub async fn enroll_guardian(
+    config: &AppConfig,
+    socket: Option<PathBuf>,
+    name: String,
+    peer_id: String,
+    threshold: Option<u8>,
+) -> Result<()> {
+    let mut client = Client::connect_or_start(config, socket).await?;
+    let devices = client.call("devices.list", Value::Null).await?;
+    let devices = devices.as_array().cloned().unwrap_or_default();
+    let Some(device) = devices.iter().find(|d| d["peer_id"] == peer_id.as_str()) else {
+        bail!(
+            "{peer_id} is not paired with this device; pair it first with `sovereign pair-device`"
+        );
+    };
+    if device["device_name"] != name.as_str() {
+        client
+            .call(
+                "devices.rename",
+                json!({ "peer_id": peer_id, "name": name }),
+            )
+            .await?;
+    }
+
+    // Every paired device holds a shard; the threshold is the user's call.
+    let shares = devices.len().min(u8::MAX as usize) as u8;
+    if shares < 2 {
+        bail!("Guardians need at least 2 paired devices; pair another device first");
+    }
+    let default = GuardianPolicy::majority(shares as usize).threshold;
+    let threshold = match threshold {
+        Some(t) => t,
+        None if std::io::stdin().is_terminal() => loop {
+            let answer = prompt(&format!(
+                "How many of the {shares} guardians must approve a recovery? [{default}] "
+            ))?;
+            if answer.is_empty() {
+                break default;
+            }
+            match answer.parse::<u8>() {
+                Ok(t) if (2..=shares).contains(&t) => break t,
+                _ => eprintln!("Enter a number from 2 to {shares}."),
+            }
+        },
+        None => default,
+    };
+
+    let backup = client
+        .call(
+            "backup.run",
+            json!({ "threshold": threshold, "shares": shares }),
+        )
+        .await
+        .context("the backup carrying the guardian shards failed")?;
+    println!(
+        "{name} is a guardian. Backup {} (epoch {}): {} fragments across {} devices, \
+         key shards to {} guardians, any {} of whom can recover it.",
+        backup["snapshot_id"].as_str().unwrap_or_default(),
+        backup["epoch"],
+        backup["fragment_count"],
+        backup["hosts"],
+        backup["guardians"],
+        backup["threshold"]
+    );
+    println!("Deliveries complete in the background as the devices come online.");
+    Ok(())
+}
+
+pub async fn initiate_recovery(
+    config: &AppConfig,
+    socket: Option<PathBuf>,
+    guardians: Vec<String>,
+    hosts: Vec<String>,
+) -> Result<()> {
+    let mut client = Client::connect_or_start(config, socket).await?;
+    if std::io::stdin().is_terminal() {
+        let answer = prompt(
+            "Restore this profile's last backup from its guardians? \
+             Documents still here are kept. [y/N] ",
+        )?;
+        if !matches!(answer.to_ascii_lowercase().as_str(), "y" | "yes") {
+            bail!("Cancelled");
+        }
+    }
+    eprintln!("Asking the guardians for their shards…");
+    let progress = client
+        .call(
+            "recovery.restore",
+            json!({ "guardians": guardians, "hosts": hosts }),
+        )
+        .await?;
+    match progress["restored"].as_u64() {
+        Some(written) => println!("Restored {written} items from the backup."),
+        None => println!(
+            "{} of {} guardians have released their shards. Each guardian approves \
+             the request (`sovereign approve-recovery` there) and its release delay \
+             has to pass; run this again later.",
+            progress["approvals"], progress["threshold"]
+        ),
+    }
+    Ok(())
+}
+
+pub async fn approve_recovery(config: &AppConfig, socket: Option<PathBuf>) -> Result<()> {
+    let mut client = Client::connect_or_start(config, socket).await?;
+    let pending = client.call("recovery.pending", Value::Null).await?;
+    let pending = pending.as_array().cloned().unwrap_or_default();
+    if pending.is_empty() {
+        println!("No recovery requests are waiting.");
+        return Ok(());
+    }
+    if !std::io::stdin().is_terminal() {
+        bail!(
+            "{} recovery request(s) are waiting; run this in a terminal to answer them",
+            pending.len()
+        );
+    }
+    println!("Only approve a request you know the owner made: it hands them your key shard.");
+    for request in pending {
+        let for_user = request["for_user"].as_str().unwrap_or_default();
+        let short: String = for_user.chars().take(12).collect();
+        println!(
+            "Recovery for {short}… (epoch {}), requested {}",
+            request["epoch"],
+            request["requested_at"].as_str().unwrap_or("just now")
+        );
+        let approve = loop {
+            match prompt("[a]pprove, [d]eny or [s]kip? ")?
+                .to_ascii_lowercase()
+                .as_str()
+            {
+                "a" | "approve" => break Some(true),
+                "d" | "deny" => break Some(false),
+                "s" | "skip" | "" => break None,
+                _ => {}
+            }
+        };
+        let Some(approve) = approve else { continue };
+        client
+            .call(
+                "recovery.release",
+                json!({ "for_user": for_user, "epoch": request["epoch"], "approve": approve }),
+            )
+            .await?;
+        println!("{}", if approve { "Approved." } else { "Denied." });
+    }
+    Ok(())
+}
+
+/// One trimmed line from stdin after `question`.
+fn prompt(question: &str) -> Result<String> {
+    eprint!("{question}");
+    std::io::stderr().flush()?;
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
@@ -0,0 +1,273 @@
+//! Relationship strength upkeep: edges follow how the documents are used.
+//!
+//! Opening or saving a document is noted in [`Touches`]. Every
+//! [`INTERVAL`] the job looks at each `related_to` edge:
+//!   - both documents touched within [`TOGETHER_WINDOW`] of each other
+//!     since the edge was last reinforced: its strength moves a
+//!     [`REINFORCE_STEP`] of the way to 1.0;
+//!   - nothing for [`STALE_AFTER`]: its strength halves every
+//!     `relevance.half_life_days`, down to [`RelatedTo::FADED_STRENGTH`],
+//!     where traversal stops following it.
+//!
+//! Edges for links written in a body stay as they are while the link is
+//! there. Strengths are per device: the change isn't synced. The job is
+//! off with `relevance.enabled = false` and idle while the session is
+//! locked.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sovereign_core::config::RelevanceConfig;
+use sovereign_db::schema::{thing_to_raw, RelatedTo};
+use sovereign_db::{DbResult, GraphDB};
+
+use crate::tauri_state::AppState;
+
+pub const INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Documents touched this close together count as used together.
+pub const TOGETHER_WINDOW: chrono::Duration = chrono::Duration::minutes(30);
+
+/// Share of the distance to full strength one reinforcement covers.
+pub const REINFORCE_STEP: f32 = 0.2;
+
+/// Idle time before an edge starts to fade.
+pub const STALE_AFTER: chrono::Duration = chrono::Duration::days(30);
+
+/// Smaller decay steps wait for a later pass, to spare the writes.
+const MIN_DECAY_STEP: f32 = 0.01;
+
+/// When each document was last opened or saved this session.
+#[derive(Default)]
+pub struct Touches(Mutex<HashMap<String, DateTime<Utc>>>);
+
+impl Touches {
+    pub fn record(&self, doc_id: &str) {
+        self.record_at(doc_id, Utc::now());
+    }
+
+    fn record_at(&self, doc_id: &str, at: DateTime<Utc>) {
+        if let Ok(mut touches) = self.0.lock() {
+            touches.insert(doc_id.to_string(), at);
+        }
+    }
+
+    /// The touches a pass at `now` may still pair up; older ones are
+    /// forgotten. Two touches close together just after one pass are only
+    /// seen by the next, an [`INTERVAL`] later, so they are kept that long
+    /// on top of the window.
+    fn recent(&self, now: DateTime<Utc>) -> HashMap<String, DateTime<Utc>> {
+        let Ok(mut touches) = self.0.lock() else {
+            return HashMap::new();
+        };
+        let keep = TOGETHER_WINDOW + chrono::Duration::seconds(INTERVAL.as_secs() as i64);
+        touches.retain(|_, at| now - *at <= keep);
+        touches.clone()
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct RelevanceReport {
+    pub reinforced: usize,
+    pub decayed: usize,
+}
+
+/// One pass, if enabled and the session is unlocked.
+pub async fn tick(state: &AppState) {
+    let config = state.current_config().relevance;
+    if !config.enabled || state.require_session_unlocked().await.is_err() {
+        return;
+    }
+    let now = Utc::now();
+    let touches = state.relevance.recent(now);
+    match update(state.db.as_ref(), &touches, &config, now).await {
+        Ok(report) if report != RelevanceReport::default() => tracing::debug!(
+            "Relevance: {} edges reinforced, {} decayed",
+            report.reinforced,
+            report.decayed
+        ),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Relevance pass failed: {e}"),
+    }
+}
+
+/// Reinforce the edges whose documents appear together in `touches`,
+/// and decay the stale ones, as of `now`.
+pub async fn update(
+    db: &dyn GraphDB,
+    touches: &HashMap<String, DateTime<Utc>>,
+    config: &RelevanceConfig,
+    now: DateTime<Utc>,
+) -> DbResult<RelevanceReport> {
+    let mut report = RelevanceReport::default();
+    for rel in db.list_all_relationships().await? {
+        let Some(id) = rel.id_string() else { continue };
+        if let Some(strength) = reinforced(&rel, touches) {
+            db.set_relationship_strength(&id, strength, true, now)
+                .await?;
+            report.reinforced += 1;
+        } else if let Some(strength) = decayed(&rel, config.half_life_days, now) {
+            db.set_relationship_strength(&id, strength, false, now)
+                .await?;
+            report.decayed += 1;
+        }
+    }
+    Ok(report)
+}
+
+/// The new strength, when both ends were touched together since the
+/// edge was last reinforced.
+fn reinforced(rel: &RelatedTo, touches: &HashMap<String, DateTime<Utc>>) -> Option<f32> {
+    let end = |t: &Option<_>| t.as_ref().map(thing_to_raw).and_then(|id| touches.get(&id));
+    let (a, b) = (end(&rel.in_)?, end(&rel.out)?);
+    let together = (*a - *b).abs() <= TOGETHER_WINDOW;
+    let fresh = rel.reinforced_at.is_none_or(|r| (*a).max(*b) > r);
+    (together && fresh && rel.strength < 1.0)
+        .then(|| (rel.strength + REINFORCE_STEP * (1.0 - rel.strength)).min(1.0))
+}
+
+/// The new strength, when the edge has been idle past [`STALE_AFTER`]
+/// and has lost at least [`MIN_DECAY_STEP`] since it last changed.
+fn decayed(rel: &RelatedTo, half_life_days: u32, now: DateTime<Utc>) -> Option<f32> {
+    if rel.from_body || rel.strength <= RelatedTo::FADED_STRENGTH {
+        return None;
+    }
+    let stale_since = rel.reinforced_at.unwrap_or(rel.created_at) + STALE_AFTER;
+    let from = rel.adjusted_at.map_or(stale_since, |a| a.max(stale_since));
+    if now <= from {
+        return None;
+    }
+    let days = (now - from).num_seconds() as f64 / 86_400.0;
+    let factor = 0.5f64.powf(days / f64::from(half_life_days.max(1))) as f32;
+    let strength = (rel.strength * factor).max(RelatedTo::FADED_STRENGTH);
+    let faded = strength == RelatedTo::FADED_STRENGTH;
+    (faded || rel.strength - strength >= MIN_DECAY_STEP).then_some(strength)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_db::mock::MockGraphDB;
+    use sovereign_db::schema::{Document, RelationType};
+
+    async fn edge(db: &MockGraphDB, strength: f32) -> (String, String, String) {
+        let a = Document::new("A".into(), "thread:t".into(), true);
+        let b = Document::new("B".into(), "thread:t".into(), true);
+        let a = db.create_document(a).await.unwrap().id_string().unwrap();
+        let b = db.create_document(b).await.unwrap().id_string().unwrap();
+        let rel = db
+            .create_relationship(&a, &b, RelationType::References, strength)
+            .await
+            .unwrap();
+        (a, b, rel.id_string().unwrap())
+    }
+
+    async fn strength(db: &MockGraphDB, id: &str) -> f32 {
+        let rel = db.get_relationship(id).await.unwrap();
+        rel.strength
+    }
+
+    #[tokio::test]
+    async fn documents_used_together_reinforce_their_edge_once() {
+        let db = MockGraphDB::new();
+        let (a, b, rel) = edge(&db, 0.5).await;
+        let config = RelevanceConfig::default();
+        let now = Utc::now();
+        let touches = Touches::default();
+        touches.record_at(&a, now - chrono::Duration::minutes(10));
+        touches.record_at(&b, now);
+
+        let report = update(&db, &touches.recent(now), &config, now)
+            .await
+            .unwrap();
+        assert_eq!(report.reinforced, 1);
+        assert!((strength(&db, &rel).await - 0.6).abs() < 1e-6);
+
+        // The same touches don't count twice.
+        let later = now + chrono::Duration::minutes(5);
+        let report = update(&db, &touches.recent(later), &config, later)
+            .await
+            .unwrap();
+        assert_eq!(report, RelevanceReport::default());
+    }
+
+    #[tokio::test]
+    async fn touches_together_are_seen_by_the_next_pass() {
+        let db = MockGraphDB::new();
+        let (a, b, rel) = edge(&db, 0.5).await;
+        let now = Utc::now();
+        let touches = Touches::default();
+        // Used together right after the previous pass, well over a
+        // window before this one.
+        touches.record_at(&a, now - chrono::Duration::minutes(55));
+        touches.record_at(&b, now - chrono::Duration::minutes(50));
+
+        let report = update(&db, &touches.recent(now), &RelevanceConfig::default(), now)
+            .await
+            .unwrap();
+        assert_eq!(report.reinforced, 1);
+        assert!((strength(&db, &rel).await - 0.6).abs() < 1e-6);
+
+        // By the pass after, they are forgotten.
+        assert!(touches.recent(now + chrono::Duration::hours(1)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn touches_far_apart_are_not_together() {
+        let db = MockGraphDB::new();
+        let (a, b, rel) = edge(&db, 0.5).await;
+        let now = Utc::now();
+        let touches = HashMap::from([(a, now - chrono::Duration::hours(2)), (b, now)]);
+        update(&db, &touches, &RelevanceConfig::default(), now)
+            .await
+            .unwrap();
+        assert_eq!(strength(&db, &rel).await, 0.5);
+    }
+
+    #[tokio::test]
+    async fn idle_edges_halve_per_half_life_down_to_faded() {
+        let db = MockGraphDB::new();
+        let (_, _, rel) = edge(&db, 0.8).await;
+        let config = RelevanceConfig::default();
+        let none = HashMap::new();
+
+        // Still fresh: nothing changes.
+        let soon = Utc::now() + chrono::Duration::days(10);
+        update(&db, &none, &config, soon).await.unwrap();
+        assert_eq!(strength(&db, &rel).await, 0.8);
+
+        let one_half_life = Utc::now() + STALE_AFTER + chrono::Duration::days(90);
+        update(&db, &none, &config, one_half_life).await.unwrap();
+        assert!((strength(&db, &rel).await - 0.4).abs() < 0.01);
+
+        // Decay counts from the last adjustment, not again from the start.
+        let two = one_half_life + chrono::Duration::days(90);
+        update(&db, &none, &config, two).await.unwrap();
+        assert!((strength(&db, &rel).await - 0.2).abs() < 0.01);
+
+        let years = two + chrono::Duration::days(3650);
+        update(&db, &none, &config, years).await.unwrap();
+        assert_eq!(strength(&db, &rel).await, RelatedTo::FADED_STRENGTH);
+    }
+
+    #[tokio::test]
+    async fn body_links_do_not_fade() {
+        let db = MockGraphDB::new();
+        let a = Document::new("A".into(), "thread:t".into(), true);
+        let b = Document::new("B".into(), "thread:t".into(), true);
+        let a = db.create_document(a).await.unwrap().id_string().unwrap();
+        let b = db.create_document(b).await.unwrap().id_string().unwrap();
+        let rel = db.create_body_link(&a, &b).await.unwrap();
+        let years = Utc::now() + chrono::Duration::days(3650);
+        let report = update(&db, &HashMap::new(), &RelevanceConfig::default(), years)
+            .await
+            .unwrap();
+        assert_eq!(report.decayed, 0);
+        assert_eq!(
+            strength(&db, &rel.id_string().unwrap()).await,
+            RelatedTo::BODY_LINK_STRENGTH
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Reminder scheduler: delivers document reminders as they come due.
+//!
+//! Reminders are set from the document panel or from chat ("remind me
+//! about Budget Overview Friday 9am", see `sovereign_ai::intent::reminder`).
+//! Every [`CHECK_INTERVAL`] the due ones are marked fired and published as
+//! `OrchestratorEvent::ReminderDue`, which the frontend turns into a
+//! notification. With `voice.announce_reminders` set they are also spoken
+//! through Piper. Nothing fires while the session is locked; reminders
+//! that came due meanwhile are delivered on the first check after unlock.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sovereign_core::config::VoiceConfig;
+use sovereign_core::events::Publisher;
+use sovereign_core::interfaces::OrchestratorEvent;
+use sovereign_db::schema::Reminder;
+use sovereign_db::GraphDB;
+
+use crate::tauri_state::AppState;
+
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// One scheduler pass: deliver what is due now, if the session is
+/// unlocked. Returns how many reminders fired.
+pub async fn tick(state: &AppState) -> usize {
+    if state.require_session_unlocked().await.is_err() {
+        return 0;
+    }
+    let fired = fire_due(state.db.as_ref(), &state.bus.publisher(), Utc::now()).await;
+    let voice = state.current_config().voice;
+    if voice.announce_reminders && !fired.is_empty() {
+        announce(&voice, &fired);
+    }
+    fired.len()
+}
+
+/// Deliver the reminders due at `now`. Each is marked fired before it is
+/// published, so a failing database can't announce it twice. Reminders on
+/// documents that were deleted since are retired without a notification.
+pub async fn fire_due(
+    db: &dyn GraphDB,
+    events: &Publisher<OrchestratorEvent>,
+    now: DateTime<Utc>,
+) -> Vec<Reminder> {
+    let due = match db.list_due_reminders(now).await {
+        Ok(due) => due,
+        Err(e) => {
+            tracing::warn!("Could not list due reminders: {e}");
+            return Vec::new();
+        }
+    };
+
+    let mut fired = Vec::new();
+    for reminder in due {
+        let Some(id) = reminder.id_string() else {
+            continue;
+        };
+        if let Err(e) = db.mark_reminder_fired(&id, now).await {
+            tracing::warn!("Could not mark reminder {id} fired: {e}");
+            continue;
+        }
+        let live = db
+            .get_document(&reminder.document_id)
+            .await
+            .is_ok_and(|d| d.deleted_at.is_none());
+        if !live {
+            tracing::debug!("Reminder {id} retired: its document is gone");
+            continue;
+        }
+        events.send(OrchestratorEvent::ReminderDue {
+            reminder_id: id,
+            doc_id: reminder.document_id.clone(),
+            text: reminder.text.clone(),
+        });
+        fired.push(reminder);
+    }
+    fired
+}
+
+/// Speak the fired reminders, one after the other, off the async runtime.
+fn announce(voice: &VoiceConfig, fired: &[Reminder]) {
+    if voice.piper_model.is_empty() {
+        tracing::debug!("Reminder announcement skipped: no Piper voice configured");
+        return;
+    }
+    let tts = sovereign_ai::voice::tts::TtsEngine::new(
+        &voice.piper_binary,
+        &voice.piper_model,
+        &voice.piper_config,
+    );
+    let lines: Vec<String> = fired
+        .iter()
+        .map(|r| format!("Reminder: {}", r.text))
+        .collect();
+    tokio::task::spawn_blocking(move || {
+        for line in lines {
+            if let Err(e) = tts.speak(&line) {
+                tracing::warn!("Could not announce reminder: {e}");
+                break;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_core::events::EventBus;
+    use sovereign_db::mock::MockGraphDB;
+    use sovereign_db::schema::Document;
+
+    #[tokio::test]
+    async fn due_reminders_fire_once() {
+        let db = MockGraphDB::new();
+        let doc = Document::new("Budget Overview".into(), "thread:t".into(), true);
+        let doc_id = db.create_document(doc).await.unwrap().id_string().unwrap();
+        let now = Utc::now();
+        let due = Reminder::new(doc_id.clone(), "Budget Overview".into(), now);
+        let later = Reminder::new(
+            doc_id.clone(),
+            "Send it".into(),
+            now + chrono::Duration::days(1),
+        );
+        db.create_reminder(due).await.unwrap();
+        db.create_reminder(later).await.unwrap();
+
+        let bus = EventBus::new();
+        let rx = bus.subscribe::<OrchestratorEvent>();
+        let fired = fire_due(&db, &bus.publisher(), now).await;
+        assert_eq!(fired.len(), 1);
+        let event = rx.try_recv().unwrap();
+        let OrchestratorEvent::ReminderDue {
+            doc_id: due_doc,
+            text,
+            ..
+        } = event
+        else {
+            panic!("expected a ReminderDue event");
+        };
+        assert_eq!(due_doc, doc_id);
+        assert_eq!(text, "Budget Overview");
+
+        assert!(fire_due(&db, &bus.publisher(), now).await.is_empty());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn reminders_on_deleted_documents_are_retired_quietly() {
+        let db = MockGraphDB::new();
+        let doc = Document::new("Old".into(), "thread:t".into(), true);
+        let doc_id = db.create_document(doc).await.unwrap().id_string().unwrap();
+        let now = Utc::now();
+        db.create_reminder(Reminder::new(doc_id.clone(), "Old".into(), now))
+            .await
+            .unwrap();
+        db.delete_document(&doc_id).await.unwrap();
+
+        let bus = EventBus::new();
+        let rx = bus.subscribe::<OrchestratorEvent>();
+        assert!(fire_due(&db, &bus.publisher(), now).await.is_empty());
+        assert!(rx.try_recv().is_err());
+        assert!(db.list_due_reminders(now).await.unwrap().is_empty());
+    }
+}
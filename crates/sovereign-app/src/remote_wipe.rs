@@ -0,0 +1,155 @@
+//! Wiping this device: a verified remote wipe order or a wipe canary
+//! phrase.
+//!
+//! The P2P node only reports an order once it carries a valid signature
+//! from a paired device and a co-signature from one of this device's
+//! guardians (`sovereign_p2p::protocol::wipe`). Here the wipe is carried
+//! out: the session keys are dropped, every file under the crypto dir
+//! (auth store, key databases, index keys, pairing state) is overwritten
+//! and deleted, and once the crypto dir is gone a marker is left so the app
+//! shows the device as wiped instead of offering a login. The issuer of a
+//! remote order is told whether that worked. The database files stay on
+//! disk, but without their keys they are ciphertext nobody can open.
+
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::tauri_state::AppState;
+
+/// Left in the profile dir after a wipe.
+const MARKER_FILE: &str = "remote_wipe.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WipeMarker {
+    wiped_at: String,
+    reason: String,
+}
+
+/// Whether this profile was wiped.
+pub fn is_wiped(profile_dir: &Path) -> bool {
+    profile_dir.join(MARKER_FILE).exists()
+}
+
+/// Wipe the keys. `reason` is kept in the marker. Returns whether the
+/// crypto dir is gone; without that no marker is written.
+pub async fn execute(state: &AppState, reason: &str) -> bool {
+    tracing::warn!("Wiping this device: {reason}");
+    state.lock().await;
+
+    let crypto_dir = state.profile_dir.join("crypto");
+    if let Err(e) = destroy_dir(&crypto_dir, overwrite) {
+        tracing::error!("remote wipe: destroying {} failed: {e}", crypto_dir.display());
+        return false;
+    }
+    let _ = std::fs::remove_file(state.profile_dir.join("onboarding_done"));
+
+    let marker = WipeMarker {
+        wiped_at: chrono::Utc::now().to_rfc3339(),
+        reason: reason.to_string(),
+    };
+    let written = serde_json::to_vec_pretty(&marker)
+        .map_err(std::io::Error::other)
+        .and_then(|json| std::fs::write(state.profile_dir.join(MARKER_FILE), json));
+    if let Err(e) = written {
+        tracing::error!("remote wipe: writing marker failed: {e}");
+    }
+    true
+}
+
+/// Carry out a verified remote wipe order and answer its issuer. The node
+/// is kept running through the wipe so it can send the answer, then shut
+/// down as `lock` would have.
+#[cfg(feature = "p2p")]
+pub async fn execute_order(state: &AppState, order_id: &str, issuer_peer_id: &str) -> bool {
+    let node = state.p2p_command_tx.write().await.take();
+    let reason = format!("remote wipe {order_id} ordered by {issuer_peer_id}");
+    let wiped = execute(state, &reason).await;
+    if let Some(tx) = node {
+        let order_id = order_id.to_string();
+        let _ = tx.send(sovereign_p2p::P2pCommand::ReportWipe { order_id, wiped }).await;
+        let _ = tx.send(sovereign_p2p::P2pCommand::Shutdown).await;
+    }
+    wiped
+}
+
+/// Overwrite every file under `dir` with zeros, then delete the tree.
+/// Overwriting is best effort (SSDs and copy-on-write filesystems may keep
+/// old blocks), so a file that can't be overwritten is logged and the rest
+/// carry on; the tree is removed either way, and only that can fail.
+fn destroy_dir(dir: &Path, overwrite: fn(&Path) -> std::io::Result<()>) -> std::io::Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    overwrite_all(dir, overwrite);
+    std::fs::remove_dir_all(dir)
+}
+
+fn overwrite_all(dir: &Path, overwrite: fn(&Path) -> std::io::Result<()>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::warn!("remote wipe: listing {} failed: {e}", dir.display());
+            return;
+        }
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        // Links aren't followed: their targets are outside the crypto dir.
+        match entry.file_type() {
+            Ok(t) if t.is_dir() => overwrite_all(&path, overwrite),
+            Ok(t) if t.is_file() => {
+                if let Err(e) = overwrite(&path) {
+                    tracing::warn!("remote wipe: overwriting {} failed: {e}", path.display());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn overwrite(path: &Path) -> std::io::Result<()> {
+    let len = std::fs::metadata(path)?.len() as usize;
+    let mut f = std::fs::OpenOptions::new().write(true).open(path)?;
+    f.write_all(&vec![0u8; len])?;
+    f.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn destroy_dir_removes_nested_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let crypto = tmp.path().join("crypto");
+        std::fs::create_dir_all(crypto.join("backup_host")).unwrap();
+        std::fs::write(crypto.join("auth.store"), b"secret").unwrap();
+        std::fs::write(crypto.join("backup_host").join("state.json"), b"{}").unwrap();
+
+        destroy_dir(&crypto, overwrite).unwrap();
+        assert!(!crypto.exists());
+        // A missing dir is not an error (wipe re-run).
+        destroy_dir(&crypto, overwrite).unwrap();
+    }
+
+    #[test]
+    fn a_file_that_cannot_be_opened_does_not_stop_the_wipe() {
+        let tmp = tempfile::tempdir().unwrap();
+        let crypto = tmp.path().join("crypto");
+        std::fs::create_dir_all(&crypto).unwrap();
+        std::fs::write(crypto.join("a.key"), b"secret").unwrap();
+        std::fs::write(crypto.join("locked.key"), b"secret").unwrap();
+        std::fs::write(crypto.join("z.key"), b"secret").unwrap();
+
+        fn locked_out(path: &Path) -> std::io::Result<()> {
+            if path.ends_with("locked.key") {
+                return Err(std::io::ErrorKind::PermissionDenied.into());
+            }
+            overwrite(path)
+        }
+        destroy_dir(&crypto, locked_out).unwrap();
+        assert!(!crypto.exists());
+    }
+}
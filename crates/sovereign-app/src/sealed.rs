@@ -0,0 +1,121 @@
+//! Sealed documents: opened only after the password is entered again.
+//!
+//! `unseal_document` checks the password the way login does, counting
+//! failures against the same lockout, then opens the document for
+//! [`GRACE`]. Until then, commands that hand out a sealed document's
+//! content refuse it. Locking the session closes every open one.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use sovereign_db::schema::{Document, Sensitivity};
+
+use crate::err::ToStringErr;
+use crate::tauri_state::AppState;
+
+/// How long an unsealed document stays open.
+pub const GRACE: Duration = Duration::from_secs(5 * 60);
+
+/// When each document was last unsealed this session.
+#[derive(Default)]
+pub struct Unsealed(Mutex<HashMap<String, Instant>>);
+
+impl Unsealed {
+    pub fn open(&self, doc_id: &str) {
+        if let Ok(mut open) = self.0.lock() {
+            open.insert(doc_id.to_string(), Instant::now());
+        }
+    }
+
+    pub fn is_open(&self, doc_id: &str) -> bool {
+        let Ok(mut open) = self.0.lock() else {
+            return false;
+        };
+        open.retain(|_, at| at.elapsed() < GRACE);
+        open.contains_key(doc_id)
+    }
+
+    pub fn close_all(&self) {
+        if let Ok(mut open) = self.0.lock() {
+            open.clear();
+        }
+    }
+}
+
+/// Refuse `doc` if it is sealed and wasn't unsealed within [`GRACE`].
+pub fn check(state: &AppState, doc: &Document) -> Result<(), String> {
+    let id = doc.id_string().unwrap_or_default();
+    if doc.sensitivity == Sensitivity::Sealed && !state.unsealed.is_open(&id) {
+        return Err(format!(
+            "'{}' is sealed: enter your password to open it",
+            doc.title
+        ));
+    }
+    Ok(())
+}
+
+/// The document `id`, if it may be read now.
+pub async fn readable(state: &AppState, id: &str) -> Result<Document, String> {
+    let doc = state.db.get_document(id).await.str_err()?;
+    check(state, &doc)?;
+    Ok(doc)
+}
+
+/// Check `password` (and the security key, if one is enrolled) against
+/// the persona that is unlocked, under the login lockout.
+#[cfg(feature = "encryption")]
+pub async fn reauthenticate(state: &AppState, password: &str) -> Result<(), String> {
+    let crypto_dir = state.profile_dir.join("crypto");
+    let max = state.config.crypto.max_login_attempts;
+    let lockout_secs = state.config.crypto.lockout_seconds;
+    let mut attempts = crate::login_throttle::LoginAttempts::load(&crypto_dir);
+    if let Some(remaining) = attempts.is_locked(max, lockout_secs) {
+        return Err(format!(
+            "Too many failed attempts — locked for {remaining} seconds"
+        ));
+    }
+    tokio::time::sleep(Duration::from_millis(250)).await;
+
+    let store =
+        sovereign_crypto::auth::AuthStore::load(&crypto_dir.join("auth.store")).str_err()?;
+    let token_secret = match &store.second_factor {
+        Some(factor) => Some(crate::tauri_commands::auth::read_security_key(factor).await?),
+        None => None,
+    };
+    let auth = match &token_secret {
+        Some(secret) => store.authenticate_with_second_factor(password.as_bytes(), secret),
+        None => store.authenticate(password.as_bytes()),
+    };
+    // The other persona's password is as wrong here as any other.
+    let current = state.account_key().await;
+    let ok = auth.is_ok_and(|a| current.is_some_and(|k| k.as_bytes() == a.account_key.as_bytes()));
+    if !ok {
+        attempts.record_failure();
+        if let Err(e) = attempts.save(&crypto_dir) {
+            tracing::warn!("login_throttle: failed to persist failure: {e}");
+        }
+        return Err("Invalid password".into());
+    }
+    attempts.reset();
+    if let Err(e) = attempts.save(&crypto_dir) {
+        tracing::warn!("login_throttle: failed to persist reset: {e}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsealed_documents_close_on_request() {
+        let unsealed = Unsealed::default();
+        assert!(!unsealed.is_open("document:a"));
+        unsealed.open("document:a");
+        assert!(unsealed.is_open("document:a"));
+        assert!(!unsealed.is_open("document:b"));
+        unsealed.close_all();
+        assert!(!unsealed.is_open("document:a"));
+    }
+}
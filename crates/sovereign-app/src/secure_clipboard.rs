@@ -0,0 +1,116 @@
+//! Copying secrets (recovery phrases, revealed PII, pairing offers) to the
+//! system clipboard.
+//!
+//! The webview's Clipboard API leaves the text there indefinitely and can't
+//! ask clipboard managers to skip it. Here the copy carries the platform's
+//! "don't record this" hints — Windows excludes it from clipboard history,
+//! cloud sync and monitoring apps, macOS marks it `ConcealedType`, and Linux
+//! sets `x-kde-passwordManagerHint` — and is cleared after a timeout unless
+//! the user has copied something else since. Locking the session clears it
+//! too. Mobile has no native clipboard here; the frontend falls back to the
+//! Clipboard API with the same timeout.
+
+use std::time::Duration;
+
+/// How long a secret stays on the clipboard unless the caller says otherwise.
+pub const DEFAULT_CLEAR_AFTER: Duration = Duration::from_secs(30);
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod native {
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use zeroize::Zeroizing;
+
+    struct Held {
+        // Kept alive: on X11/Wayland the owning process serves the contents.
+        clipboard: arboard::Clipboard,
+        copied: Zeroizing<String>,
+        generation: u64,
+    }
+
+    static HELD: Mutex<Option<Held>> = Mutex::new(None);
+
+    fn set_hidden(clipboard: &mut arboard::Clipboard, text: &str) -> Result<(), arboard::Error> {
+        let set = clipboard.set();
+        #[cfg(target_os = "windows")]
+        let set = {
+            use arboard::SetExtWindows;
+            set.exclude_from_monitoring()
+        };
+        #[cfg(target_os = "macos")]
+        let set = {
+            use arboard::SetExtApple;
+            set.exclude_from_history()
+        };
+        #[cfg(target_os = "linux")]
+        let set = {
+            use arboard::SetExtLinux;
+            set.exclude_from_history()
+        };
+        set.text(text)
+    }
+
+    pub fn copy(text: &str, clear_after: Duration) -> Result<(), String> {
+        let mut guard = HELD.lock().map_err(|_| "clipboard lock poisoned".to_string())?;
+        let generation = guard.as_ref().map_or(0, |h| h.generation + 1);
+        let mut clipboard = match guard.take() {
+            Some(held) => held.clipboard,
+            None => arboard::Clipboard::new().map_err(|e| e.to_string())?,
+        };
+        set_hidden(&mut clipboard, text).map_err(|e| e.to_string())?;
+        *guard = Some(Held {
+            clipboard,
+            copied: Zeroizing::new(text.to_string()),
+            generation,
+        });
+        drop(guard);
+
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(clear_after).await;
+            clear_if(|held| held.generation == generation);
+        });
+        Ok(())
+    }
+
+    pub fn clear() {
+        clear_if(|_| true);
+    }
+
+    /// Clear only while the clipboard still holds our secret — never wipe
+    /// something the user copied afterwards.
+    fn clear_if(pred: impl FnOnce(&Held) -> bool) {
+        let Ok(mut guard) = HELD.lock() else { return };
+        let Some(held) = guard.as_mut() else { return };
+        if !pred(held) {
+            return;
+        }
+        let still_ours = held
+            .clipboard
+            .get_text()
+            .map(|current| *Zeroizing::new(current) == *held.copied)
+            .unwrap_or(false);
+        if still_ours {
+            let _ = held.clipboard.clear();
+        }
+        *guard = None;
+    }
+}
+
+/// Copy `text` with clipboard-manager exclusion and clear it after
+/// `clear_after`.
+pub fn copy(text: &str, clear_after: Duration) -> Result<(), String> {
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    return native::copy(text, clear_after);
+    #[cfg(any(target_os = "android", target_os = "ios"))]
+    {
+        let _ = (text, clear_after);
+        Err("secure clipboard is not available on this platform".into())
+    }
+}
+
+/// Remove a secret copied with [`copy`] if it is still on the clipboard.
+pub fn clear() {
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    native::clear();
+}
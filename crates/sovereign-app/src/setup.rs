@@ -31,12 +31,80 @@ pub async fn create_db(config: &AppConfig) -> Result<SurrealGraphDB> {
             StorageMode::Persistent(resolved_str)
         }
     };
-    let db = SurrealGraphDB::new(mode).await?;
+    let profile = sovereign_core::profiles::active();
+    let db = SurrealGraphDB::new(mode)
+        .await?
+        .with_database(sovereign_core::profiles::database_name(&profile));
     db.connect().await?;
     db.init_schema().await?;
     Ok(db)
 }
 
+/// Every core skill plus the community skills (and, with `wasm-plugins`,
+/// WASM plugins) under `profile_dir/skills`, with the user's trusted keys,
+/// revocations, audit log and scratch space installed.
+pub fn skill_registry(profile_dir: &std::path::Path) -> sovereign_skills::SkillRegistry {
+    let mut registry = sovereign_skills::SkillRegistry::new();
+    registry.register(Box::new(sovereign_skills::skills::text_editor::TextEditorSkill));
+    registry.register(Box::new(sovereign_skills::skills::image::ImageSkill));
+    registry.register(Box::new(sovereign_skills::skills::pdf_export::PdfExportSkill));
+    registry.register(Box::new(sovereign_skills::skills::word_count::WordCountSkill));
+    registry.register(Box::new(sovereign_skills::skills::find_replace::FindReplaceSkill));
+    registry.register(Box::new(sovereign_skills::skills::search::SearchSkill));
+    registry.register(Box::new(sovereign_skills::skills::file_import::FileImportSkill));
+    registry.register(Box::new(sovereign_skills::skills::duplicate_document::DuplicateDocumentSkill));
+    registry.register(Box::new(sovereign_skills::skills::markdown_editor::MarkdownEditorSkill));
+    registry.register(Box::new(sovereign_skills::skills::video::VideoSkill));
+    registry.register(Box::new(sovereign_skills::skills::outline_extractor::OutlineExtractorSkill));
+    registry.register(Box::new(sovereign_skills::skills::link_checker::LinkCheckerSkill));
+    registry.register(Box::new(sovereign_skills::skills::pii_detector::PiiDetectorSkill));
+    registry.register(Box::new(sovereign_skills::skills::readability_score::ReadabilityScoreSkill));
+    registry.register(Box::new(sovereign_skills::skills::html_export::HtmlExportSkill));
+    registry.register(Box::new(sovereign_skills::skills::plaintext_export::PlaintextExportSkill));
+    registry.register(Box::new(sovereign_skills::skills::docx_export::DocxExportSkill));
+    registry.register(Box::new(sovereign_skills::skills::epub_export::EpubExportSkill));
+    registry.register(Box::new(sovereign_skills::skills::table_of_contents::TableOfContentsSkill));
+    registry.register(Box::new(sovereign_skills::skills::json_yaml_formatter::JsonYamlFormatterSkill));
+    registry.register(Box::new(sovereign_skills::skills::csv_to_md::CsvToMdSkill));
+    registry.register(Box::new(sovereign_skills::skills::redactor::RedactorSkill));
+    registry.register(Box::new(sovereign_skills::skills::backlink_map::BacklinkMapSkill));
+    registry.register(Box::new(sovereign_skills::skills::orphan_finder::OrphanFinderSkill));
+    registry.register(Box::new(sovereign_skills::skills::daily_journal::DailyJournalSkill));
+    registry.register(Box::new(sovereign_skills::skills::thread_summary::ThreadSummarySkill));
+    registry.register(Box::new(sovereign_skills::skills::translation::TranslationSkill));
+    tracing::info!("Registered {} core skills", registry.all_skills().len());
+
+    // Community skills under ~/.sovereign/skills. Trusted publisher keys go
+    // in first: each on-disk skill is verified against them at scan time,
+    // and unsigned/tampered skills are flagged (and capability-restricted)
+    // by the registry.
+    let trusted_keys_path = profile_dir.join("trusted_skill_keys.json");
+    match sovereign_skills::TrustedKeys::load(&trusted_keys_path) {
+        Ok(keys) => registry.set_trusted_keys(keys),
+        Err(e) => tracing::warn!("Failed to load trusted skill keys: {e}"),
+    }
+    match sovereign_skills::SkillPermissions::load(&profile_dir.join("skill_permissions.json")) {
+        Ok(perms) => registry.set_permissions(perms),
+        Err(e) => tracing::warn!("Failed to load skill permissions: {e}"),
+    }
+    registry.set_audit_log(sovereign_skills::SkillAuditLog::with_file(
+        profile_dir.join("skill_audit.jsonl"),
+    ));
+    registry.set_scratch_space(sovereign_skills::ScratchSpace::new(
+        profile_dir.join("skill-scratch"),
+    ));
+    let community_skills_dir = profile_dir.join("skills");
+    if let Err(e) = registry.scan_directory(&community_skills_dir) {
+        tracing::warn!("Failed to scan community skills: {e}");
+    }
+    #[cfg(feature = "wasm-plugins")]
+    match registry.load_wasm_skills(&community_skills_dir) {
+        Ok(n) => tracing::info!("Loaded {n} WASM skill plugins"),
+        Err(e) => tracing::warn!("Failed to load WASM skill plugins: {e}"),
+    }
+    registry
+}
+
 #[cfg(feature = "encryption")]
 pub fn crypto_dir() -> std::path::PathBuf {
     sovereign_core::sovereign_dir().join("crypto")
@@ -84,10 +152,30 @@ pub fn cli_kdf() -> sovereign_crypto::master_key::Kdf {
     }
 }
 
+/// Argon2id parameters for a new key store: calibrated to take about
+/// `kdf_target_ms` on this machine, or the fixed defaults when that is 0.
+/// The choice is recorded with the store, so unlock never re-calibrates.
+#[cfg(feature = "encryption")]
+pub fn new_store_kdf(
+    crypto: &sovereign_core::config::CryptoConfig,
+) -> sovereign_crypto::master_key::Kdf {
+    use sovereign_crypto::master_key::Kdf;
+    match crypto.kdf_target_ms {
+        0 => Kdf::current(),
+        ms => {
+            let kdf = Kdf::calibrate(std::time::Duration::from_millis(ms.into()));
+            tracing::info!("Calibrated passphrase KDF for {ms} ms: {kdf:?}");
+            kdf
+        }
+    }
+}
+
 /// Initialize the crypto subsystem: MasterKey → DeviceKey → KEK → KeyDatabase.
 /// Returns (DeviceKey, Kek, KeyDatabase) for use by EncryptedGraphDB and P2P.
 #[cfg(feature = "encryption")]
-pub fn init_crypto() -> Result<(
+pub fn init_crypto(
+    config: &AppConfig,
+) -> Result<(
     sovereign_crypto::device_key::DeviceKey,
     std::sync::Arc<tokio::sync::Mutex<sovereign_crypto::key_db::KeyDatabase>>,
     std::sync::Arc<sovereign_crypto::kek::Kek>,
@@ -120,14 +208,18 @@ pub fn init_crypto() -> Result<(
         anyhow::bail!("Passphrase cannot be empty");
     }
     // CRYPTO-001: stretch the passphrase with the recorded (version-aware)
-    // KDF — Argon2id for fresh dirs, LegacyHkdf only to unlock pre-marker
-    // stores, which are upgraded in place below.
-    let kdf = cli_kdf();
+    // KDF — calibrated Argon2id for fresh dirs, LegacyHkdf only to unlock
+    // pre-marker stores, which are upgraded in place below.
+    let kek_path = dir.join("kek.wrapped");
+    let kdf = if kek_path.exists() || cli_kdf_path().exists() {
+        cli_kdf()
+    } else {
+        new_store_kdf(&config.crypto)
+    };
     let master = MasterKey::derive(pass.as_bytes(), &salt, &kdf)?;
     let device_key = DeviceKey::derive(&master, &device_id)?;
 
     // Load or create KEK
-    let kek_path = dir.join("kek.wrapped");
     let kek = if kek_path.exists() {
         let wrapped_bytes = std::fs::read(&kek_path)?;
         let wrapped: sovereign_crypto::kek::WrappedKek = serde_json::from_slice(&wrapped_bytes)?;
@@ -151,7 +243,7 @@ pub fn init_crypto() -> Result<(
     // under an Argon2id-stretched key so brute-force hardness applies to
     // existing CLI stores too, then record the KDF.
     let device_key = if kdf == sovereign_crypto::master_key::Kdf::LegacyHkdf {
-        let current = sovereign_crypto::master_key::Kdf::current();
+        let current = new_store_kdf(&config.crypto);
         let new_master = MasterKey::derive(pass.as_bytes(), &salt, &current)?;
         let new_device_key = DeviceKey::derive(&new_master, &device_id)?;
         sovereign_crypto::fs_private::write_private(&kek_path, serde_json::to_vec(&kek.wrap(&new_device_key)?)?)?;
@@ -284,7 +376,7 @@ fn persona_key_db_filename(persona: sovereign_core::auth::PersonaKind, base: &st
 
 /// CRYPTO-001: persona-suffixed blind-index key filename.
 #[cfg(feature = "encryption")]
-fn persona_index_filename(persona: sovereign_core::auth::PersonaKind) -> &'static str {
+pub(crate) fn persona_index_filename(persona: sovereign_core::auth::PersonaKind) -> &'static str {
     match persona {
         sovereign_core::auth::PersonaKind::Primary => "index.key",
         sovereign_core::auth::PersonaKind::Duress => "index.duress.key",
@@ -0,0 +1,464 @@
+//! One-document shares (`.svshare`): a single document, the attachments
+//! picked for it, and an expiry date, sealed to a passphrase.
+//!
+//! The file is an age file (passphrase/scrypt) around a zip holding
+//! `share.json` (the document record and the share's metadata),
+//! `document.html` (the document rendered standalone, images inlined) and
+//! `blobs/<sha256>` for its media. Nothing goes through a server: the file
+//! travels however the sender likes, or as a QR code when it is small
+//! enough, and the passphrase travels separately.
+//!
+//! A recipient without Sovereign opens it with the stock tools
+//! (`age -d share.svshare > share.zip`, then `document.html`). Another
+//! Sovereign instance imports it into its "Shared with me" thread with
+//! `sovereign open-share` or from the UI. Expiry and the one-time rule are
+//! enforced by Sovereign, not by the cryptography: Sovereign refuses a
+//! share past its `expires_at`, and remembers the shares each profile has
+//! opened so the same file can't be imported twice. Anyone holding the file
+//! and the passphrase can still read it with `age`.
+
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sovereign_core::content::ContentFields;
+use sovereign_db::schema::{Document, Thread};
+use sovereign_db::GraphDB;
+use sovereign_skills::{CoreSkill, SkillContext, SkillDocument, SkillOutput};
+
+use crate::export_bundle::ExportedDocument;
+use crate::{media, thread_bundle};
+
+/// File extension of a share.
+pub const EXTENSION: &str = "svshare";
+
+/// Name of the manifest entry inside the share.
+pub const MANIFEST_NAME: &str = "share.json";
+
+/// The rendered document, for recipients without Sovereign.
+pub const VIEW_NAME: &str = "document.html";
+
+/// Bumped when the manifest layout changes incompatibly.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Marks a share carried as text, e.g. in a QR code.
+pub const PAYLOAD_PREFIX: &str = "sovereign-share:";
+
+/// Most characters a QR code holds in byte mode at the lowest error
+/// correction (version 40-L). Larger shares are files only.
+pub const QR_PAYLOAD_MAX: usize = 2953;
+
+/// Thread that opened shares land in.
+pub const INBOX_THREAD: &str = "Shared with me";
+
+/// Opened share ids, in the profile directory.
+const LEDGER_FILE: &str = "opened_shares.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShareManifest {
+    pub version: u32,
+    /// Identifies this share (not the document) for the one-time rule.
+    pub share_id: String,
+    pub shared_at: String,
+    /// RFC 3339; `None` for a share that never expires.
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    pub document: ExportedDocument,
+}
+
+impl ShareManifest {
+    /// Whether the share may no longer be opened. An unreadable expiry
+    /// counts as expired.
+    pub fn expired(&self, now: DateTime<Utc>) -> bool {
+        match &self.expires_at {
+            None => false,
+            Some(at) => !DateTime::parse_from_rfc3339(at).is_ok_and(|at| at > now),
+        }
+    }
+}
+
+/// A sealed share, ready to save or show.
+#[derive(Debug, Clone, Serialize)]
+pub struct Share {
+    pub file_name: String,
+    pub sealed: Vec<u8>,
+    /// The share as QR text, when it fits.
+    pub qr_payload: Option<String>,
+    pub expires_at: Option<String>,
+    /// Blobs the document lists that aren't on this device.
+    pub missing_media: usize,
+}
+
+/// What opening a share produced.
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenedShare {
+    pub document_id: String,
+    pub thread_id: String,
+    pub title: String,
+    pub shared_at: String,
+    pub expires_at: Option<String>,
+}
+
+/// Drop the attachments whose name isn't in `keep`. Images and videos are
+/// part of the document and always stay.
+pub fn keep_attachments(content: &str, keep: &[String]) -> String {
+    let mut fields = ContentFields::parse(content);
+    if fields.files.iter().all(|f| keep.contains(&f.name)) {
+        return content.to_string();
+    }
+    fields.files.retain(|f| keep.contains(&f.name));
+    fields.serialize()
+}
+
+/// Zip the manifest, the rendered view and the blobs (`sha256`, `bytes`).
+pub fn write_share(
+    manifest: &ShareManifest,
+    view: &[u8],
+    blobs: &[(String, Vec<u8>)],
+) -> Result<Vec<u8>> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = zip::write::FileOptions::default();
+    zip.start_file(MANIFEST_NAME, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(manifest)?)?;
+    zip.start_file(VIEW_NAME, options)?;
+    zip.write_all(view)?;
+    thread_bundle::write_blobs(&mut zip, blobs)?;
+    Ok(zip.finish()?.into_inner())
+}
+
+/// Read a share made by [`write_share`]: the manifest and its blobs.
+pub fn read_share(bytes: &[u8]) -> Result<(ShareManifest, Vec<(String, Vec<u8>)>)> {
+    let mut zip = zip::ZipArchive::new(Cursor::new(bytes)).context("not a document share")?;
+    let mut manifest = String::new();
+    zip.by_name(MANIFEST_NAME)
+        .with_context(|| format!("share has no {MANIFEST_NAME}"))?
+        .read_to_string(&mut manifest)?;
+    let manifest: ShareManifest = serde_json::from_str(&manifest)?;
+    if manifest.version > FORMAT_VERSION {
+        anyhow::bail!(
+            "share format v{} is newer than this build supports (v{FORMAT_VERSION})",
+            manifest.version
+        );
+    }
+    Ok((manifest, thread_bundle::read_blobs(&mut zip)?))
+}
+
+/// The sealed share as QR text, or `None` when it is too big for one code.
+pub fn to_payload(sealed: &[u8]) -> Option<String> {
+    let payload = format!(
+        "{PAYLOAD_PREFIX}{}",
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(sealed)
+    );
+    (payload.len() <= QR_PAYLOAD_MAX).then_some(payload)
+}
+
+/// The sealed share carried by QR text made by [`to_payload`].
+pub fn from_payload(payload: &str) -> Result<Vec<u8>> {
+    let encoded = payload
+        .trim()
+        .strip_prefix(PAYLOAD_PREFIX)
+        .context("not a Sovereign share payload")?;
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(encoded)
+        .context("share payload is damaged")
+}
+
+/// Seal a share's zip to `passphrase`.
+#[cfg(feature = "encryption")]
+pub fn seal(plain: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    use crate::export_bundle::SealTo;
+    crate::export_bundle::seal(plain, &SealTo::Passphrase(passphrase.to_string()))
+}
+
+/// Open a share sealed by [`seal`].
+#[cfg(feature = "encryption")]
+pub fn unseal(sealed: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    use crate::export_bundle::Unseal;
+    if !crate::export_bundle::is_age(sealed) {
+        anyhow::bail!("not a document share");
+    }
+    crate::export_bundle::unseal(sealed, &Unseal::Passphrase(passphrase.to_string()))
+        .context("wrong passphrase, or the share is damaged")
+}
+
+#[cfg(not(feature = "encryption"))]
+pub fn seal(_plain: &[u8], _passphrase: &str) -> Result<Vec<u8>> {
+    anyhow::bail!("sharing needs a build with the `encryption` feature")
+}
+
+#[cfg(not(feature = "encryption"))]
+pub fn unseal(_sealed: &[u8], _passphrase: &str) -> Result<Vec<u8>> {
+    anyhow::bail!("opening a share needs a build with the `encryption` feature")
+}
+
+/// The shares a profile has opened, by share id, with when.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Ledger {
+    pub opened: BTreeMap<String, String>,
+}
+
+impl Ledger {
+    /// Load from `dir`. A missing or unreadable file is an empty ledger.
+    pub fn load(dir: &Path) -> Self {
+        std::fs::read(dir.join(LEDGER_FILE))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join(LEDGER_FILE), serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, share_id: &str, now: DateTime<Utc>) {
+        self.opened.insert(share_id.to_string(), now.to_rfc3339());
+    }
+}
+
+/// Package `doc_id` with the attachments named in `attachments`, sealed to
+/// `passphrase`. `expires_in` of `None` makes a share that never expires.
+pub async fn create(
+    db: &dyn GraphDB,
+    doc_id: &str,
+    attachments: &[String],
+    expires_in: Option<Duration>,
+    passphrase: &str,
+    now: DateTime<Utc>,
+) -> Result<Share> {
+    if passphrase.is_empty() {
+        anyhow::bail!("passphrase cannot be empty");
+    }
+    let doc = db.get_document(doc_id).await?;
+    let content = keep_attachments(&doc.content, attachments);
+    let shared_at = now.to_rfc3339();
+    let share_id = {
+        let mut hash = Sha256::new();
+        for part in [doc_id, shared_at.as_str(), content.as_str()] {
+            hash.update(part.as_bytes());
+            hash.update([0]);
+        }
+        format!("{:x}", hash.finalize())[..32].to_string()
+    };
+    let expires_at = expires_in.map(|d| (now + d).to_rfc3339());
+
+    // Same policy as the UI: grant exactly what the skill declares.
+    let skill = sovereign_skills::skills::html_export::HtmlExportSkill;
+    let ctx = SkillContext {
+        granted: skill.required_capabilities().into_iter().collect(),
+        db: None,
+        llm: None,
+    };
+    let skill_doc = SkillDocument {
+        id: doc_id.to_string(),
+        title: doc.title.clone(),
+        content: ContentFields::parse(&content),
+    };
+    let SkillOutput::File { data: view, .. } = skill.execute("export", &skill_doc, "{}", &ctx)?
+    else {
+        anyhow::bail!("HTML export did not produce a file");
+    };
+
+    let dir = media::media_dir();
+    let mut blobs = Vec::new();
+    let mut missing_media = 0;
+    for hash in thread_bundle::blob_hashes(&content) {
+        match std::fs::read(dir.join(&hash)) {
+            Ok(data) => blobs.push((hash, data)),
+            Err(_) => missing_media += 1,
+        }
+    }
+
+    let manifest = ShareManifest {
+        version: FORMAT_VERSION,
+        share_id,
+        shared_at,
+        expires_at: expires_at.clone(),
+        document: ExportedDocument {
+            id: doc_id.to_string(),
+            thread_id: doc.thread_id,
+            title: doc.title.clone(),
+            content,
+            is_owned: doc.is_owned,
+            doc_type: doc.doc_type,
+            sensitivity: doc.sensitivity,
+            created_at: doc.created_at.to_rfc3339(),
+            modified_at: doc.modified_at.to_rfc3339(),
+        },
+    };
+    let sealed = seal(&write_share(&manifest, &view, &blobs)?, passphrase)?;
+    Ok(Share {
+        file_name: format!(
+            "{}.{EXTENSION}",
+            sovereign_skills::markdown_util::sanitize_filename(&doc.title)
+        ),
+        qr_payload: to_payload(&sealed),
+        sealed,
+        expires_at,
+        missing_media,
+    })
+}
+
+/// Open a sealed share into the [`INBOX_THREAD`] thread as an external
+/// document, and record it in the ledger kept in `ledger_dir`. Expired and
+/// already-opened shares are refused.
+pub async fn open(
+    db: &dyn GraphDB,
+    sealed: &[u8],
+    passphrase: &str,
+    ledger_dir: &Path,
+    now: DateTime<Utc>,
+) -> Result<OpenedShare> {
+    let (manifest, blobs) = read_share(&unseal(sealed, passphrase)?)?;
+    if manifest.expired(now) {
+        anyhow::bail!(
+            "this share expired on {}",
+            manifest.expires_at.as_deref().unwrap_or_default()
+        );
+    }
+    let mut ledger = Ledger::load(ledger_dir);
+    if let Some(at) = ledger.opened.get(&manifest.share_id) {
+        anyhow::bail!("this share was already opened on {at}");
+    }
+
+    for (_, data) in &blobs {
+        media::store_blob(data).map_err(anyhow::Error::msg)?;
+    }
+    let existing = db
+        .list_threads()
+        .await?
+        .into_iter()
+        .find(|t| t.name == INBOX_THREAD);
+    let thread = match existing {
+        Some(t) => t,
+        None => {
+            db.create_thread(Thread::new(
+                INBOX_THREAD.into(),
+                "Documents opened from shares".into(),
+            ))
+            .await?
+        }
+    };
+    let thread_id = thread
+        .id_string()
+        .ok_or_else(|| anyhow::anyhow!("thread missing ID"))?;
+
+    let shared = &manifest.document;
+    let mut doc = Document::new(shared.title.clone(), thread_id.clone(), false);
+    doc.doc_type = shared.doc_type;
+    doc.content = thread_bundle::relocate_blobs(&shared.content, &media::media_dir());
+    shared.restore_sensitivity(&mut doc);
+    if let Ok(t) = DateTime::parse_from_rfc3339(&shared.created_at) {
+        doc.created_at = t.into();
+    }
+    let created = db.create_document(doc).await?;
+    let document_id = created
+        .id_string()
+        .ok_or_else(|| anyhow::anyhow!("document missing ID"))?;
+
+    ledger.record(&manifest.share_id, now);
+    ledger.save(ledger_dir)?;
+    Ok(OpenedShare {
+        document_id,
+        thread_id,
+        title: created.title,
+        shared_at: manifest.shared_at,
+        expires_at: manifest.expires_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_db::schema::DocType;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn sample(expires_at: Option<&str>) -> ShareManifest {
+        ShareManifest {
+            version: FORMAT_VERSION,
+            share_id: "abc".into(),
+            shared_at: "2026-01-01T00:00:00Z".into(),
+            expires_at: expires_at.map(str::to_string),
+            document: ExportedDocument {
+                id: "document:1".into(),
+                thread_id: "thread:a".into(),
+                title: "Notes".into(),
+                content: r#"{"body":"hello","images":[]}"#.into(),
+                is_owned: true,
+                doc_type: DocType::Note,
+                sensitivity: Default::default(),
+                created_at: "2026-01-01T00:00:00Z".into(),
+                modified_at: "2026-01-01T00:00:00Z".into(),
+            },
+        }
+    }
+
+    #[test]
+    fn share_roundtrip() {
+        let manifest = sample(None);
+        let data = b"image bytes".to_vec();
+        let hash = format!("{:x}", Sha256::digest(&data));
+        let bytes = write_share(&manifest, b"<p>hello</p>", &[(hash.clone(), data)]).unwrap();
+        let (read, blobs) = read_share(&bytes).unwrap();
+        assert_eq!(read, manifest);
+        assert_eq!(blobs.len(), 1);
+        assert_eq!(blobs[0].0, hash);
+        assert!(read_share(b"not a zip").is_err());
+    }
+
+    #[test]
+    fn expiry_is_checked_against_now() {
+        let now = at("2026-03-01T00:00:00Z");
+        assert!(!sample(None).expired(now));
+        assert!(!sample(Some("2026-03-08T00:00:00Z")).expired(now));
+        assert!(sample(Some("2026-02-28T00:00:00Z")).expired(now));
+        assert!(sample(Some("next week")).expired(now));
+    }
+
+    #[test]
+    fn payloads_roundtrip_and_respect_the_qr_limit() {
+        let small = vec![7u8; 100];
+        let payload = to_payload(&small).unwrap();
+        assert!(payload.starts_with(PAYLOAD_PREFIX));
+        assert_eq!(from_payload(&format!(" {payload}\n")).unwrap(), small);
+        assert_eq!(to_payload(&vec![0u8; QR_PAYLOAD_MAX]), None);
+        assert!(from_payload("hello").is_err());
+    }
+
+    #[test]
+    fn only_chosen_attachments_are_kept() {
+        let content = r#"{"body":"b","images":[],"files":[
+            {"path":"/m/aa","name":"keep.pdf"},
+            {"path":"/m/bb","name":"drop.zip"}]}"#;
+        let kept = ContentFields::parse(&keep_attachments(content, &["keep.pdf".into()]));
+        let names: Vec<&str> = kept.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["keep.pdf"]);
+    }
+
+    #[test]
+    fn ledger_remembers_opened_shares() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ledger = Ledger::load(dir.path());
+        assert!(ledger.opened.is_empty());
+        ledger.record("abc", at("2026-03-01T00:00:00Z"));
+        ledger.save(dir.path()).unwrap();
+        assert!(Ledger::load(dir.path()).opened.contains_key("abc"));
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn sealed_share_needs_the_passphrase() {
+        let sealed = seal(b"zip bytes", "correct horse").unwrap();
+        assert_eq!(unseal(&sealed, "correct horse").unwrap(), b"zip bytes");
+        assert!(unseal(&sealed, "wrong").is_err());
+    }
+}
@@ -0,0 +1,85 @@
+//! `sovereign skill list` and `sovereign skill run`: the skill registry
+//! without the UI, mainly for testing plugins.
+//!
+//! The registry is loaded exactly as the app loads it, so community and
+//! WASM skills get the same signature checks, revocations and audit log.
+//! A run grants the skill what it declares, as the UI does, but has no
+//! model loaded: skills that need inference fail. Output is printed, never
+//! saved back to the document.
+
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use sovereign_core::config::AppConfig;
+use sovereign_core::content::ContentFields;
+use sovereign_db::GraphDB;
+use sovereign_skills::{SkillContext, SkillDocument};
+
+use crate::doc_ref;
+use crate::setup::{create_db, skill_registry};
+use crate::tauri_commands::documents::skill_trust;
+use crate::tauri_commands::SkillResultDto;
+
+pub fn list() -> Result<()> {
+    let registry = skill_registry(&sovereign_core::sovereign_dir());
+    for skill in registry.all_skills() {
+        let (trust, signer) = skill_trust(&registry, skill.name());
+        let trust = match signer {
+            Some(signer) => format!("{trust} by {signer}"),
+            None => trust,
+        };
+        let actions: Vec<String> = skill.actions().into_iter().map(|(id, _)| id).collect();
+        println!("{}\t{trust}\t{}", skill.name(), actions.join(", "));
+    }
+    println!("({} skills)", registry.all_skills().len());
+    Ok(())
+}
+
+pub async fn run(
+    config: &AppConfig,
+    name: String,
+    action: String,
+    doc: String,
+    params: Option<String>,
+) -> Result<()> {
+    let params = params.unwrap_or_else(|| "{}".into());
+    serde_json::from_str::<serde_json::Value>(&params).context("--params must be JSON")?;
+
+    let registry = skill_registry(&sovereign_core::sovereign_dir());
+    let Some(skill) = registry.find_skill(&name) else {
+        bail!("No skill named '{name}' (see `sovereign skill list`)");
+    };
+    let actions = skill.actions();
+    if !actions.iter().any(|(id, _)| *id == action) {
+        let ids: Vec<&str> = actions.iter().map(|(id, _)| id.as_str()).collect();
+        bail!(
+            "'{name}' has no action '{action}' (it has: {})",
+            ids.join(", ")
+        );
+    }
+    let granted = skill.required_capabilities().into_iter().collect();
+
+    let db = Arc::new(create_db(config).await?);
+    let doc_id = doc_ref::resolve(db.as_ref(), &doc).await?;
+    let doc = db.get_document(&doc_id).await?;
+    let skill_doc = SkillDocument {
+        id: doc_id,
+        title: doc.title,
+        content: ContentFields::parse(&doc.content),
+    };
+    let ctx = SkillContext {
+        granted,
+        db: Some(sovereign_skills::wrap_db(db)),
+        llm: None,
+    };
+    // WASM plugins run synchronously and may take a while.
+    let output = tokio::task::spawn_blocking(move || {
+        registry.execute_skill(&name, &action, &skill_doc, &params, &ctx)
+    })
+    .await??;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&SkillResultDto::from(output))?
+    );
+    Ok(())
+}
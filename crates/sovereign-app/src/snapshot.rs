@@ -0,0 +1,453 @@
+//! `sovereign snapshot`: restore points for the whole workspace, for
+//! disaster recovery beyond one document's history.
+//!
+//! A snapshot is a directory under `snapshots/` in the profile, named for
+//! when it was taken:
+//!   - `database.surql`: the database, exported whole (encrypted fields
+//!     stay encrypted);
+//!   - `crypto/`: the keys that ciphertext needs, and the media blobs,
+//!     hard-linked where the filesystem allows since a blob never changes
+//!     once written;
+//!   - `snapshot.json`: when and why it was taken, and what it holds.
+//!
+//! Rollback asks for the snapshot's name to be typed back, and snapshots
+//! the current state first so the rollback itself can be undone. Keys come
+//! back with the data, so the passphrase is the one in use when the
+//! snapshot was taken; this device's id stays. `snapshots.keep` and
+//! `snapshots.max_age_days` decide which snapshots go after each new one.
+//!
+//! The database is opened directly, so the app and the daemon must be
+//! closed.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sovereign_core::config::{AppConfig, SnapshotsConfig};
+use sovereign_db::surreal::SurrealGraphDB;
+use sovereign_db::GraphDB;
+
+use crate::setup::create_db;
+
+const DATABASE_FILE: &str = "database.surql";
+const MANIFEST_FILE: &str = "snapshot.json";
+const CRYPTO_DIR: &str = "crypto";
+/// The media blob store inside `crypto/`: linked, and merged on rollback.
+const BLOBS_DIR: &str = "sync_blobs";
+/// This device's identity, left alone by rollback.
+const DEVICE_ID_FILE: &str = "device_id";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub note: String,
+    pub documents: usize,
+    pub threads: usize,
+}
+
+/// One snapshot on disk.
+#[derive(Debug)]
+pub struct Snapshot {
+    pub name: String,
+    pub path: PathBuf,
+    pub manifest: Manifest,
+}
+
+/// Where this profile's snapshots live.
+pub fn snapshots_dir() -> PathBuf {
+    sovereign_core::sovereign_dir().join("snapshots")
+}
+
+fn crypto_dir() -> PathBuf {
+    sovereign_core::sovereign_dir().join(CRYPTO_DIR)
+}
+
+pub async fn create(config: &AppConfig, note: Option<String>) -> Result<()> {
+    let db = open_db(config).await?;
+    let root = snapshots_dir();
+    let now = Utc::now();
+    let snapshot = take(&db, &root, &crypto_dir(), note.unwrap_or_default(), now).await?;
+    println!(
+        "Created snapshot {} ({} documents, {} threads)",
+        snapshot.name, snapshot.manifest.documents, snapshot.manifest.threads
+    );
+    for name in prune(&root, &config.snapshots, now)? {
+        println!("Removed snapshot {name} (snapshots.keep / snapshots.max_age_days)");
+    }
+    Ok(())
+}
+
+pub fn list() -> Result<()> {
+    let root = snapshots_dir();
+    let snapshots = list_in(&root)?;
+    for s in &snapshots {
+        println!(
+            "{}\t{}\t{} documents\t{}",
+            s.name,
+            s.manifest.created_at.format("%Y-%m-%d %H:%M:%S"),
+            s.manifest.documents,
+            s.manifest.note
+        );
+    }
+    println!("({} snapshots in {})", snapshots.len(), root.display());
+    Ok(())
+}
+
+pub async fn rollback(config: &AppConfig, name: String) -> Result<()> {
+    let root = snapshots_dir();
+    let target = find(&root, &name)?;
+    eprintln!(
+        "This replaces the whole workspace (documents, threads, history, messages, \
+         contacts and keys) with snapshot {} from {}, which holds {} documents.",
+        target.name,
+        target.manifest.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+        target.manifest.documents
+    );
+    eprintln!(
+        "The passphrase goes back to the one in use then. Paired devices may sync \
+         newer changes back."
+    );
+    eprint!("Type the snapshot name to roll back: ");
+    std::io::stderr().flush()?;
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    if line.trim() != target.name {
+        bail!("Cancelled");
+    }
+
+    let db = open_db(config).await?;
+    let note = format!("before rollback to {}", target.name);
+    let before = take(&db, &root, &crypto_dir(), note, Utc::now()).await?;
+    restore(&db, &target, &crypto_dir())
+        .await
+        .with_context(|| {
+            format!(
+                "Rollback failed part way; `sovereign snapshot rollback {}` returns to \
+             the state before it",
+                before.name
+            )
+        })?;
+    println!(
+        "Rolled back to {}; the state before is kept as snapshot {}",
+        target.name, before.name
+    );
+    Ok(())
+}
+
+async fn open_db(config: &AppConfig) -> Result<SurrealGraphDB> {
+    if config.database.mode == "memory" {
+        bail!(
+            "The database is in memory (database.mode = \"memory\"); there is nothing to snapshot"
+        );
+    }
+    create_db(config)
+        .await
+        .context("Could not open the database; close Sovereign and stop `sovereign daemon` first")
+}
+
+/// Snapshot `db` and the key store at `crypto` into a new directory under
+/// `root`. It only appears there once complete.
+async fn take(
+    db: &SurrealGraphDB,
+    root: &Path,
+    crypto: &Path,
+    note: String,
+    now: DateTime<Utc>,
+) -> Result<Snapshot> {
+    std::fs::create_dir_all(root)?;
+    // Holds copies of the keys.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(root, std::fs::Permissions::from_mode(0o700))?;
+    }
+    let base = now.format("%Y%m%d-%H%M%S").to_string();
+    let mut name = base.clone();
+    let mut n = 1;
+    while root.join(&name).exists() {
+        n += 1;
+        name = format!("{base}-{n}");
+    }
+
+    let partial = root.join(format!(".{name}.partial"));
+    if partial.exists() {
+        std::fs::remove_dir_all(&partial)?;
+    }
+    let manifest = match fill(db, &partial, crypto, note, now).await {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&partial);
+            return Err(e);
+        }
+    };
+    let path = root.join(&name);
+    std::fs::rename(&partial, &path)?;
+    Ok(Snapshot {
+        name,
+        path,
+        manifest,
+    })
+}
+
+async fn fill(
+    db: &SurrealGraphDB,
+    dir: &Path,
+    crypto: &Path,
+    note: String,
+    now: DateTime<Utc>,
+) -> Result<Manifest> {
+    std::fs::create_dir_all(dir)?;
+    db.export_to(&dir.join(DATABASE_FILE)).await?;
+    if crypto.is_dir() {
+        let saved = dir.join(CRYPTO_DIR);
+        copy_tree(crypto, &saved, false, &[BLOBS_DIR])?;
+        let blobs = crypto.join(BLOBS_DIR);
+        if blobs.is_dir() {
+            copy_tree(&blobs, &saved.join(BLOBS_DIR), true, &[])?;
+        }
+    }
+    let manifest = Manifest {
+        created_at: now,
+        note,
+        documents: db.list_documents(None).await?.len(),
+        threads: db.list_threads().await?.len(),
+    };
+    std::fs::write(
+        dir.join(MANIFEST_FILE),
+        serde_json::to_vec_pretty(&manifest)?,
+    )?;
+    Ok(manifest)
+}
+
+/// Put `snapshot` back: the database, then the key store at `crypto`.
+/// Blobs are merged rather than replaced; `maintain` clears the unused
+/// ones.
+async fn restore(db: &SurrealGraphDB, snapshot: &Snapshot, crypto: &Path) -> Result<()> {
+    db.replace_from(&snapshot.path.join(DATABASE_FILE)).await?;
+    let saved = snapshot.path.join(CRYPTO_DIR);
+    if !saved.is_dir() {
+        return Ok(());
+    }
+    if crypto.is_dir() {
+        for entry in std::fs::read_dir(crypto)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            if name == BLOBS_DIR || name == DEVICE_ID_FILE {
+                continue;
+            }
+            if entry.file_type()?.is_dir() {
+                std::fs::remove_dir_all(entry.path())?;
+            } else {
+                std::fs::remove_file(entry.path())?;
+            }
+        }
+    }
+    copy_tree(&saved, crypto, false, &[BLOBS_DIR, DEVICE_ID_FILE])?;
+    let blobs = saved.join(BLOBS_DIR);
+    if blobs.is_dir() {
+        copy_tree(&blobs, &crypto.join(BLOBS_DIR), true, &[])?;
+    }
+    Ok(())
+}
+
+/// Copy `from` into `to`, leaving out the top-level entries in `skip` and
+/// files already there. With `link`, files are hard-linked where possible.
+fn copy_tree(from: &Path, to: &Path, link: bool, skip: &[&str]) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if skip.iter().any(|s| name == *s) {
+            continue;
+        }
+        let (src, dst) = (entry.path(), to.join(&name));
+        if entry.file_type()?.is_dir() {
+            copy_tree(&src, &dst, link, &[])?;
+        } else if !dst.exists() {
+            let linked = link && std::fs::hard_link(&src, &dst).is_ok();
+            if !linked {
+                std::fs::copy(&src, &dst)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The snapshots under `root`, newest first.
+fn list_in(root: &Path) -> Result<Vec<Snapshot>> {
+    let entries = match std::fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut snapshots = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') || !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let path = entry.path();
+        match read_manifest(&path) {
+            Ok(manifest) => snapshots.push(Snapshot {
+                name,
+                path,
+                manifest,
+            }),
+            Err(e) => tracing::warn!("Skipping snapshot {name}: {e}"),
+        }
+    }
+    snapshots.sort_by_key(|s| std::cmp::Reverse(s.manifest.created_at));
+    Ok(snapshots)
+}
+
+fn read_manifest(dir: &Path) -> Result<Manifest> {
+    let bytes = std::fs::read(dir.join(MANIFEST_FILE))?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+fn find(root: &Path, name: &str) -> Result<Snapshot> {
+    let name = name.trim();
+    list_in(root)?
+        .into_iter()
+        .find(|s| s.name == name)
+        .with_context(|| format!("No snapshot named '{name}'; see `sovereign snapshot list`"))
+}
+
+/// Remove the snapshots `policy` no longer keeps and return their names.
+/// The newest always stays.
+fn prune(root: &Path, policy: &SnapshotsConfig, now: DateTime<Utc>) -> Result<Vec<String>> {
+    let max_age = chrono::Duration::days(policy.max_age_days.into());
+    let mut removed = Vec::new();
+    for (i, s) in list_in(root)?.into_iter().enumerate().skip(1) {
+        let surplus = i >= policy.keep as usize;
+        let expired = policy.max_age_days > 0 && now - s.manifest.created_at > max_age;
+        if surplus || expired {
+            std::fs::remove_dir_all(&s.path)?;
+            removed.push(s.name);
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_db::schema::Document;
+    use sovereign_db::surreal::StorageMode;
+
+    async fn memory_db() -> SurrealGraphDB {
+        let db = SurrealGraphDB::new(StorageMode::Memory).await.unwrap();
+        db.connect().await.unwrap();
+        db.init_schema().await.unwrap();
+        db
+    }
+
+    fn titles(docs: Vec<Document>) -> Vec<String> {
+        docs.into_iter().map(|d| d.title).collect()
+    }
+
+    #[tokio::test]
+    async fn rollback_restores_database_and_keys_but_not_device_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let (root, crypto) = (dir.path().join("snapshots"), dir.path().join("crypto"));
+        std::fs::create_dir_all(crypto.join(BLOBS_DIR)).unwrap();
+        std::fs::write(crypto.join("keys.db"), "old keys").unwrap();
+        std::fs::write(crypto.join(DEVICE_ID_FILE), "device-1").unwrap();
+        std::fs::write(crypto.join(BLOBS_DIR).join("abc"), "blob").unwrap();
+
+        let db = memory_db().await;
+        db.create_document(Document::new("Kept".into(), "thread:t".into(), true))
+            .await
+            .unwrap();
+        let snapshot = take(&db, &root, &crypto, String::new(), Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(snapshot.manifest.documents, 1);
+
+        db.create_document(Document::new("Later".into(), "thread:t".into(), true))
+            .await
+            .unwrap();
+        std::fs::write(crypto.join("keys.db"), "new keys").unwrap();
+        std::fs::write(crypto.join("kek.wrapped"), "added later").unwrap();
+        std::fs::write(crypto.join(DEVICE_ID_FILE), "device-2").unwrap();
+        std::fs::remove_file(crypto.join(BLOBS_DIR).join("abc")).unwrap();
+
+        restore(&db, &snapshot, &crypto).await.unwrap();
+        assert_eq!(titles(db.list_documents(None).await.unwrap()), ["Kept"]);
+        assert_eq!(
+            std::fs::read_to_string(crypto.join("keys.db")).unwrap(),
+            "old keys"
+        );
+        assert!(!crypto.join("kek.wrapped").exists());
+        assert_eq!(
+            std::fs::read_to_string(crypto.join(DEVICE_ID_FILE)).unwrap(),
+            "device-2"
+        );
+        assert_eq!(
+            std::fs::read_to_string(crypto.join(BLOBS_DIR).join("abc")).unwrap(),
+            "blob"
+        );
+    }
+
+    #[tokio::test]
+    async fn snapshots_taken_the_same_second_get_distinct_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = memory_db().await;
+        let now = Utc::now();
+        let crypto = dir.path().join("crypto");
+        let a = take(&db, dir.path(), &crypto, String::new(), now)
+            .await
+            .unwrap();
+        let b = take(&db, dir.path(), &crypto, String::new(), now)
+            .await
+            .unwrap();
+        assert_ne!(a.name, b.name);
+        assert_eq!(list_in(dir.path()).unwrap().len(), 2);
+    }
+
+    fn fake_snapshot(root: &Path, name: &str, created_at: DateTime<Utc>) {
+        let dir = root.join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        let manifest = Manifest {
+            created_at,
+            note: String::new(),
+            documents: 0,
+            threads: 0,
+        };
+        std::fs::write(
+            dir.join(MANIFEST_FILE),
+            serde_json::to_vec(&manifest).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn retention_drops_surplus_and_expired_but_keeps_the_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = Utc::now();
+        for days in [1, 2, 40, 100] {
+            fake_snapshot(
+                dir.path(),
+                &format!("d{days}"),
+                now - chrono::Duration::days(days),
+            );
+        }
+
+        let policy = SnapshotsConfig {
+            keep: 3,
+            max_age_days: 30,
+        };
+        let mut removed = prune(dir.path(), &policy, now).unwrap();
+        removed.sort();
+        assert_eq!(removed, ["d100", "d40"]);
+
+        // Past max_age_days, the newest one still stays.
+        let later = now + chrono::Duration::days(365);
+        assert_eq!(prune(dir.path(), &policy, later).unwrap(), ["d2"]);
+        assert_eq!(list_in(dir.path()).unwrap()[0].name, "d1");
+    }
+}
@@ -0,0 +1,843 @@
+//! Importing notes and mail from other tools.
+//!
+//! Onboarding (and later the canvas) can pull in an Obsidian or Logseq
+//! vault (a folder of Markdown), an unzipped Notion "Markdown & CSV"
+//! export, or a maildir. `scan` lists the files a source holds,
+//! `thread_for` applies the user's mapping choice and `read_item` turns
+//! one file into a title and a Markdown body. Creating the threads and
+//! documents is left to the caller, which owns the database and the
+//! progress reporting.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use mailparse::{DispositionType, MailHeaderMap, ParsedMail};
+use serde::{Deserialize, Serialize};
+
+/// Files larger than this are skipped rather than loaded into a document.
+pub const MAX_FILE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Upper bound on the files one import will create documents for.
+pub const MAX_ITEMS: usize = 20_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    /// A folder of Markdown notes: an Obsidian or Logseq vault.
+    Markdown,
+    /// An unzipped Notion export (Markdown & CSV).
+    Notion,
+    /// A maildir, with Maildir++ subfolders (`.Sent`, `.Archive`).
+    Maildir,
+}
+
+/// How imported files are grouped into threads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThreadMapping {
+    /// Everything goes into one thread named after the source.
+    Single,
+    /// One thread per top-level folder (per mailbox for a maildir).
+    /// Files at the root go into the source's own thread.
+    PerFolder,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceItem {
+    pub path: PathBuf,
+    /// Top-level folder or mailbox, already cleaned up for display.
+    /// `None` for notes at the root of the source.
+    pub folder: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedDoc {
+    pub title: String,
+    pub body: String,
+    /// When the note was last changed, or when the mail was sent.
+    pub modified_at: Option<DateTime<Utc>>,
+}
+
+/// Folders that hold app state rather than notes.
+const SKIPPED_DIRS: &[&str] = &["logseq", "node_modules", "bak"];
+
+const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown"];
+
+/// List the files `root` holds for `kind`, sorted by path. Hidden
+/// folders (`.obsidian`, `.trash`) and symlinks are never followed.
+pub fn scan(kind: SourceKind, root: &Path) -> Result<Vec<SourceItem>> {
+    if !root.is_dir() {
+        anyhow::bail!("{} is not a folder", root.display());
+    }
+    let mut items = Vec::new();
+    match kind {
+        SourceKind::Markdown | SourceKind::Notion => {
+            walk_files(kind, MARKDOWN_EXTENSIONS, root, root, &mut items)?;
+        }
+        SourceKind::Maildir => {
+            if !root.join("cur").is_dir() && !root.join("new").is_dir() {
+                anyhow::bail!(
+                    "{} is not a maildir (no cur/ or new/ folder)",
+                    root.display()
+                );
+            }
+            collect_mailbox(root, Some("Inbox".to_string()), &mut items)?;
+            for entry in std::fs::read_dir(root)? {
+                let entry = entry?;
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if !entry.file_type()?.is_dir() || !name.starts_with('.') || name.len() < 2 {
+                    continue;
+                }
+                collect_mailbox(&entry.path(), Some(mailbox_name(&name)), &mut items)?;
+            }
+        }
+    }
+    items.sort_by(|a, b| a.path.cmp(&b.path));
+    items.truncate(MAX_ITEMS);
+    Ok(items)
+}
+
+fn walk_files(
+    kind: SourceKind,
+    extensions: &[&str],
+    root: &Path,
+    dir: &Path,
+    items: &mut Vec<SourceItem>,
+) -> Result<()> {
+    for entry in std::fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') || file_type.is_symlink() {
+            continue;
+        }
+        let path = entry.path();
+        if file_type.is_dir() {
+            if !SKIPPED_DIRS.contains(&name.as_str()) {
+                walk_files(kind, extensions, root, &path, items)?;
+            }
+            continue;
+        }
+        if !has_extension(&path, extensions) {
+            continue;
+        }
+        let folder = path
+            .strip_prefix(root)
+            .ok()
+            .and_then(|rel| {
+                let mut parts = rel.components();
+                let first = parts.next()?;
+                // Only files below a folder have one.
+                parts.next()?;
+                Some(first.as_os_str().to_string_lossy().into_owned())
+            })
+            .map(|f| clean_name(kind, &f));
+        items.push(SourceItem { path, folder });
+    }
+    Ok(())
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| extensions.iter().any(|x| e.eq_ignore_ascii_case(x)))
+}
+
+fn collect_mailbox(dir: &Path, folder: Option<String>, items: &mut Vec<SourceItem>) -> Result<()> {
+    for sub in ["cur", "new"] {
+        let Ok(entries) = std::fs::read_dir(dir.join(sub)) else {
+            continue;
+        };
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_type()?.is_file() {
+                items.push(SourceItem {
+                    path: entry.path(),
+                    folder: folder.clone(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `.Archive.2023` → `Archive/2023`.
+fn mailbox_name(dir_name: &str) -> String {
+    dir_name.trim_start_matches('.').replace('.', "/")
+}
+
+/// The thread name for the source as a whole: its folder name, or
+/// "Mail" for a maildir (whose folder is usually just `Maildir`).
+pub fn source_name(kind: SourceKind, root: &Path) -> String {
+    if kind == SourceKind::Maildir {
+        return "Mail".to_string();
+    }
+    root.file_name()
+        .map(|n| clean_name(kind, &n.to_string_lossy()))
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| "Imported".to_string())
+}
+
+/// The thread `item` goes into under `mapping`.
+pub fn thread_for(mapping: ThreadMapping, source: &str, item: &SourceItem) -> String {
+    match (mapping, &item.folder) {
+        (ThreadMapping::PerFolder, Some(folder)) => folder.clone(),
+        _ => source.to_string(),
+    }
+}
+
+/// Read one scanned file into a document.
+pub fn read_item(kind: SourceKind, item: &SourceItem) -> Result<ImportedDoc> {
+    let meta = std::fs::metadata(&item.path)?;
+    if meta.len() > MAX_FILE_BYTES {
+        anyhow::bail!("larger than {} MB", MAX_FILE_BYTES / (1024 * 1024));
+    }
+    let bytes = std::fs::read(&item.path)?;
+    match kind {
+        SourceKind::Maildir => Ok(parse_mail(&bytes)),
+        SourceKind::Markdown | SourceKind::Notion => {
+            let text = String::from_utf8_lossy(&bytes);
+            let stem = item
+                .path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let mut doc = parse_markdown(kind, &stem, &text);
+            doc.modified_at = meta.modified().ok().map(DateTime::<Utc>::from);
+            Ok(doc)
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Markdown
+// ---------------------------------------------------------------------------
+
+/// Tidy a file or folder name into a title. Notion appends a 32-hex page
+/// ID (`Roadmap 1f2e…`); Logseq writes `/` in page names as `___` and
+/// journal dates as `2024_01_15`.
+fn clean_name(kind: SourceKind, name: &str) -> String {
+    let mut name = name.to_string();
+    if kind == SourceKind::Notion {
+        if let Some((head, id)) = name.rsplit_once(' ') {
+            if id.len() == 32 && id.chars().all(|c| c.is_ascii_hexdigit()) {
+                name = head.to_string();
+            }
+        }
+    }
+    if kind == SourceKind::Markdown {
+        name = name.replace("___", "/").replace("%2F", "/");
+        let b = name.as_bytes();
+        if b.len() == 10
+            && b[4] == b'_'
+            && b[7] == b'_'
+            && name.chars().filter(|c| c.is_ascii_digit()).count() == 8
+        {
+            name = name.replace('_', "-");
+        }
+    }
+    name.trim().to_string()
+}
+
+fn parse_markdown(kind: SourceKind, stem: &str, text: &str) -> ImportedDoc {
+    let text = text.trim_start_matches('\u{feff}');
+    let (front_title, body) = split_frontmatter(text);
+    let mut title = front_title.unwrap_or_else(|| clean_name(kind, stem));
+    let mut body = body.to_string();
+    // Notion repeats the page title as the first heading.
+    if kind == SourceKind::Notion {
+        if let Some(rest) = body
+            .strip_prefix(&format!("# {title}"))
+            .filter(|r| r.is_empty() || r.starts_with(['\r', '\n']))
+        {
+            body = rest.trim_start_matches(['\r', '\n']).to_string();
+        }
+    }
+    if title.is_empty() {
+        title = "Untitled".to_string();
+    }
+    ImportedDoc {
+        title,
+        body,
+        modified_at: None,
+    }
+}
+
+/// Strip YAML frontmatter, returning its `title:` if it has one.
+fn split_frontmatter(text: &str) -> (Option<String>, &str) {
+    let Some(rest) = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+    else {
+        return (None, text);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let front = &rest[..offset];
+            let title = front.lines().find_map(|l| {
+                let value = l.strip_prefix("title:")?.trim().trim_matches(['"', '\'']);
+                (!value.is_empty()).then(|| value.to_string())
+            });
+            return (
+                title,
+                rest[offset + line.len()..].trim_start_matches(['\r', '\n']),
+            );
+        }
+        offset += line.len();
+    }
+    // Unterminated: not frontmatter after all.
+    (None, text)
+}
+
+// ---------------------------------------------------------------------------
+// Mail
+// ---------------------------------------------------------------------------
+
+fn parse_mail(bytes: &[u8]) -> ImportedDoc {
+    let Ok(mail) = mailparse::parse_mail(bytes) else {
+        return ImportedDoc {
+            title: "(no subject)".to_string(),
+            body: String::from_utf8_lossy(bytes).trim().to_string(),
+            modified_at: None,
+        };
+    };
+    let headers = mail.get_headers();
+    let title = headers
+        .get_first_value("Subject")
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| "(no subject)".to_string());
+    let sent = headers
+        .get_first_value("Date")
+        .and_then(|d| parse_mail_date(&d));
+    let text = mail_text(&mail).unwrap_or_default();
+
+    let mut body = String::new();
+    for name in ["From", "To", "Date"] {
+        if let Some(v) = headers.get_first_value(name) {
+            body.push_str(&format!("**{name}:** {v}  \n"));
+        }
+    }
+    body.push('\n');
+    body.push_str(text.trim());
+    ImportedDoc {
+        title,
+        body,
+        modified_at: sent,
+    }
+}
+
+/// The readable text of a message: the first text/plain part, else the
+/// first other text part (HTML with its tags stripped).
+fn mail_text(mail: &ParsedMail) -> Option<String> {
+    let mut parts = Vec::new();
+    text_parts(mail, &mut parts);
+    let part = parts
+        .iter()
+        .find(|p| p.ctype.mimetype == "text/plain")
+        .or_else(|| parts.first())?;
+    let text = part.get_body().ok()?;
+    Some(if part.ctype.mimetype == "text/html" {
+        strip_tags(&text)
+    } else {
+        text
+    })
+}
+
+/// The inline text parts of `part`, depth first.
+fn text_parts<'a>(part: &'a ParsedMail<'a>, out: &mut Vec<&'a ParsedMail<'a>>) {
+    if part.ctype.mimetype.starts_with("multipart/") {
+        for sub in &part.subparts {
+            text_parts(sub, out);
+        }
+    } else if part.ctype.mimetype.starts_with("text/")
+        && part.get_content_disposition().disposition != DispositionType::Attachment
+    {
+        out.push(part);
+    }
+}
+
+fn parse_mail_date(value: &str) -> Option<DateTime<Utc>> {
+    // Drop a trailing comment such as "(UTC)".
+    let value = match value.find('(') {
+        Some(i) => value[..i].trim(),
+        None => value.trim(),
+    };
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
+}
+
+// ---------------------------------------------------------------------------
+// Folder import (`sovereign import --from`)
+// ---------------------------------------------------------------------------
+
+/// How `sovereign import --from` reads a folder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FolderFormat {
+    /// An Obsidian vault: Markdown with frontmatter, where `[[wiki links]]`
+    /// become relationships.
+    Obsidian,
+    /// Markdown and text files taken as they are, titled by file name.
+    Plain,
+    /// JSON files each holding one document or an array of them.
+    Json,
+}
+
+impl std::str::FromStr for FolderFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "obsidian" => Ok(Self::Obsidian),
+            "plain" => Ok(Self::Plain),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "Unknown import format '{other}' (expected obsidian, plain or json)"
+            )),
+        }
+    }
+}
+
+/// A document read from a folder, and how it links to the others.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FolderDoc {
+    pub doc: ImportedDoc,
+    pub created_at: Option<DateTime<Utc>>,
+    /// Names a link can use for this document (file name, path in the
+    /// folder, title), matched case-insensitively.
+    pub names: Vec<String>,
+    /// Names this document links to.
+    pub links: Vec<String>,
+}
+
+/// A document in a `--format json` file. `links` holds the titles (or
+/// `id`s) of other documents in the import.
+#[derive(Debug, Deserialize)]
+struct JsonDoc {
+    #[serde(default)]
+    id: Option<String>,
+    title: String,
+    #[serde(default, alias = "content")]
+    body: String,
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    modified_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    links: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonFile {
+    Many(Vec<JsonDoc>),
+    One(JsonDoc),
+}
+
+/// Attachments an Obsidian note embeds with `![[…]]`; they aren't notes.
+const ATTACHMENT_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "svg", "webp", "bmp", "pdf", "mp3", "wav", "m4a", "ogg", "mp4",
+    "webm", "mov", "canvas",
+];
+
+/// Read every document under `root`, sorted by path. Files that can't be
+/// read are returned as `path: reason` instead of failing the import.
+pub fn read_folder(format: FolderFormat, root: &Path) -> Result<(Vec<FolderDoc>, Vec<String>)> {
+    if !root.is_dir() {
+        anyhow::bail!("{} is not a folder", root.display());
+    }
+    let extensions: &[&str] = match format {
+        FolderFormat::Obsidian => MARKDOWN_EXTENSIONS,
+        FolderFormat::Plain => &["md", "markdown", "txt"],
+        FolderFormat::Json => &["json"],
+    };
+    let mut items = Vec::new();
+    walk_files(SourceKind::Markdown, extensions, root, root, &mut items)?;
+    items.sort_by(|a, b| a.path.cmp(&b.path));
+    items.truncate(MAX_ITEMS);
+
+    let mut docs = Vec::new();
+    let mut skipped = Vec::new();
+    for item in &items {
+        match read_folder_item(format, root, item) {
+            Ok(read) => docs.extend(read),
+            Err(e) => skipped.push(format!("{}: {e}", item.path.display())),
+        }
+    }
+    Ok((docs, skipped))
+}
+
+fn read_folder_item(
+    format: FolderFormat,
+    root: &Path,
+    item: &SourceItem,
+) -> Result<Vec<FolderDoc>> {
+    let meta = std::fs::metadata(&item.path)?;
+    if meta.len() > MAX_FILE_BYTES {
+        anyhow::bail!("larger than {} MB", MAX_FILE_BYTES / (1024 * 1024));
+    }
+    let created_at = meta.created().ok().map(DateTime::<Utc>::from);
+    let stem = item
+        .path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    // `[[Projects/Roadmap]]` names a note by its path in the vault.
+    let rel_path = item
+        .path
+        .strip_prefix(root)
+        .unwrap_or(&item.path)
+        .with_extension("")
+        .to_string_lossy()
+        .replace('\\', "/");
+
+    match format {
+        FolderFormat::Obsidian => {
+            let doc = read_item(SourceKind::Markdown, item)?;
+            let links = wiki_links(&doc.body);
+            let names = vec![stem, rel_path, doc.title.clone()];
+            Ok(vec![FolderDoc {
+                doc,
+                created_at,
+                names,
+                links,
+            }])
+        }
+        FolderFormat::Plain => {
+            let body = String::from_utf8_lossy(&std::fs::read(&item.path)?).into_owned();
+            let title = if stem.trim().is_empty() {
+                "Untitled".to_string()
+            } else {
+                stem.clone()
+            };
+            Ok(vec![FolderDoc {
+                doc: ImportedDoc {
+                    title,
+                    body,
+                    modified_at: meta.modified().ok().map(DateTime::<Utc>::from),
+                },
+                created_at,
+                names: vec![stem, rel_path],
+                links: Vec::new(),
+            }])
+        }
+        FolderFormat::Json => {
+            let parsed: JsonFile = serde_json::from_slice(&std::fs::read(&item.path)?)?;
+            let entries = match parsed {
+                JsonFile::Many(docs) => docs,
+                JsonFile::One(doc) => vec![doc],
+            };
+            Ok(entries
+                .into_iter()
+                .map(|j| {
+                    let mut links = j.links;
+                    links.extend(wiki_links(&j.body));
+                    let names = j.id.into_iter().chain([j.title.clone()]).collect();
+                    FolderDoc {
+                        doc: ImportedDoc {
+                            title: j.title,
+                            body: j.body,
+                            modified_at: j.modified_at,
+                        },
+                        created_at: j.created_at,
+                        names,
+                        links,
+                    }
+                })
+                .collect())
+        }
+    }
+}
+
+/// The notes a Markdown body links to with `[[Note]]`, `[[Note|alias]]`
+/// or `[[Note#Heading]]`, in order of first mention. Embedded attachments
+/// are left out.
+pub fn wiki_links(body: &str) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("[[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("]]") else {
+            break;
+        };
+        let inner = &after[..end];
+        rest = &after[end + 2..];
+        if inner.contains('\n') {
+            continue;
+        }
+        let target = inner
+            .split(['|', '#', '^'])
+            .next()
+            .unwrap_or_default()
+            .trim();
+        let target = target.strip_suffix(".md").unwrap_or(target);
+        if target.is_empty()
+            || has_extension(Path::new(target), ATTACHMENT_EXTENSIONS)
+            || links.iter().any(|l| l == target)
+        {
+            continue;
+        }
+        links.push(target.to_string());
+    }
+    links
+}
+
+/// Match every link to the document it names, returning `(from, to)`
+/// index pairs without duplicates or self-links, and how many links named
+/// nothing in the import. The first document to claim a name keeps it.
+pub fn resolve_links(docs: &[FolderDoc]) -> (Vec<(usize, usize)>, usize) {
+    let mut by_name: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for (i, doc) in docs.iter().enumerate() {
+        for name in &doc.names {
+            by_name.entry(name.to_lowercase()).or_insert(i);
+        }
+    }
+    let mut seen = std::collections::HashSet::new();
+    let mut pairs = Vec::new();
+    let mut unresolved = 0;
+    for (from, doc) in docs.iter().enumerate() {
+        for link in &doc.links {
+            match by_name.get(&link.to_lowercase()) {
+                Some(&to) if to != from && seen.insert((from, to)) => pairs.push((from, to)),
+                Some(_) => {}
+                None => unresolved += 1,
+            }
+        }
+    }
+    (pairs, unresolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn scans_an_obsidian_vault() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "Inbox.md", "top");
+        write(
+            root,
+            "Projects/Roadmap.md",
+            "---\ntitle: \"Q3 roadmap\"\ntags: [a]\n---\n\nShip it",
+        );
+        write(root, "Projects/deep/Note.md", "deep");
+        write(root, "Projects/diagram.png", "");
+        write(root, ".obsidian/workspace.md", "state");
+
+        let items = scan(SourceKind::Markdown, root).unwrap();
+        let folders: Vec<_> = items.iter().map(|i| i.folder.as_deref()).collect();
+        assert_eq!(folders, vec![None, Some("Projects"), Some("Projects")]);
+
+        let note = read_item(SourceKind::Markdown, &items[2]).unwrap();
+        assert_eq!(note.title, "Note");
+        let doc = read_item(SourceKind::Markdown, &items[1]).unwrap();
+        assert_eq!(doc.title, "Q3 roadmap");
+        assert_eq!(doc.body, "Ship it");
+        assert!(doc.modified_at.is_some());
+
+        let source = source_name(SourceKind::Markdown, root);
+        assert_eq!(
+            thread_for(ThreadMapping::PerFolder, &source, &items[1]),
+            "Projects"
+        );
+        assert_eq!(
+            thread_for(ThreadMapping::PerFolder, &source, &items[0]),
+            source
+        );
+        assert_eq!(
+            thread_for(ThreadMapping::Single, &source, &items[1]),
+            source
+        );
+    }
+
+    #[test]
+    fn cleans_up_logseq_and_notion_names() {
+        assert_eq!(clean_name(SourceKind::Markdown, "2024_01_15"), "2024-01-15");
+        assert_eq!(
+            clean_name(SourceKind::Markdown, "work___meetings"),
+            "work/meetings"
+        );
+        assert_eq!(
+            clean_name(
+                SourceKind::Notion,
+                "Roadmap 1f2e3d4c5b6a79881f2e3d4c5b6a7988"
+            ),
+            "Roadmap"
+        );
+        assert_eq!(clean_name(SourceKind::Notion, "Roadmap v2"), "Roadmap v2");
+
+        let doc = parse_markdown(
+            SourceKind::Notion,
+            "Roadmap 1f2e3d4c5b6a79881f2e3d4c5b6a7988",
+            "# Roadmap\n\nStatus: Draft\n",
+        );
+        assert_eq!(doc.title, "Roadmap");
+        assert_eq!(doc.body, "Status: Draft\n");
+    }
+
+    #[test]
+    fn unterminated_frontmatter_is_body() {
+        let (title, body) = split_frontmatter("---\ntitle: x\nno end");
+        assert_eq!(title, None);
+        assert_eq!(body, "---\ntitle: x\nno end");
+    }
+
+    #[test]
+    fn scans_maildir_mailboxes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "cur/1:2,S", "Subject: a\n\nx");
+        write(root, "new/2", "Subject: b\n\ny");
+        write(root, ".Archive.2023/cur/3:2,S", "Subject: c\n\nz");
+        std::fs::create_dir_all(root.join("tmp")).unwrap();
+
+        let items = scan(SourceKind::Maildir, root).unwrap();
+        let mut folders: Vec<_> = items.iter().map(|i| i.folder.clone()).collect();
+        folders.sort();
+        assert_eq!(
+            folders,
+            vec![
+                Some("Archive/2023".to_string()),
+                Some("Inbox".to_string()),
+                Some("Inbox".to_string())
+            ]
+        );
+        assert_eq!(source_name(SourceKind::Maildir, root), "Mail");
+
+        let not_maildir = tempfile::tempdir().unwrap();
+        assert!(scan(SourceKind::Maildir, not_maildir.path()).is_err());
+    }
+
+    #[test]
+    fn parses_a_multipart_mail() {
+        let raw = concat!(
+            "From: Ana <ana@example.com>\r\n",
+            "Subject: =?UTF-8?B?Q2Fmw6k=?= =?UTF-8?Q?_plans?=\r\n",
+            "Date: Tue, 1 Aug 2023 10:00:00 +0200 (CEST)\r\n",
+            "Content-Type: multipart/alternative;\r\n boundary=\"b1\"\r\n",
+            "\r\n",
+            "--b1\r\n",
+            "Content-Type: text/html\r\n\r\n",
+            "<p>ignored</p>\r\n",
+            "--b1\r\n",
+            "Content-Type: text/plain; charset=utf-8\r\n",
+            "Content-Transfer-Encoding: quoted-printable\r\n\r\n",
+            "Meet at the caf=C3=A9 =\r\nat noon.\r\n",
+            "--b1--\r\n",
+        );
+        let doc = parse_mail(raw.as_bytes());
+        assert_eq!(doc.title, "Café plans");
+        assert!(doc.body.starts_with("**From:** Ana <ana@example.com>"));
+        assert!(doc.body.ends_with("Meet at the café at noon."));
+        assert_eq!(
+            doc.modified_at.unwrap().to_rfc3339(),
+            "2023-08-01T08:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn html_only_mail_is_stripped() {
+        let raw = "Subject: hi\nContent-Type: text/html\n\n<b>Hello</b> &amp; bye";
+        let doc = parse_mail(raw.as_bytes());
+        assert!(doc.body.ends_with("Hello & bye"));
+        assert_eq!(parse_mail(b"no headers at all").title, "(no subject)");
+    }
+
+    #[test]
+    fn extracts_wiki_links() {
+        let body = "See [[Roadmap]], [[Projects/Plan|the plan]] and [[Roadmap#Q3]].\n\
+                    ![[diagram.png]] [[Notes.md]] [[ ]] [[unclosed";
+        assert_eq!(wiki_links(body), vec!["Roadmap", "Projects/Plan", "Notes"]);
+    }
+
+    #[test]
+    fn resolves_links_by_name_path_and_title() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "Inbox.md",
+            "Start with [[roadmap]] and [[Missing note]].",
+        );
+        write(
+            root,
+            "Projects/Roadmap.md",
+            "---\ntitle: Q3 roadmap\n---\nBack to [[Inbox]], see [[Projects/Roadmap]].",
+        );
+        write(root, "Projects/Budget.md", "Part of [[Q3 roadmap]].");
+        write(root, "Projects/image.png", "");
+
+        let (docs, skipped) = read_folder(FolderFormat::Obsidian, root).unwrap();
+        assert!(skipped.is_empty());
+        let titles: Vec<_> = docs.iter().map(|d| d.doc.title.as_str()).collect();
+        assert_eq!(titles, vec!["Inbox", "Budget", "Q3 roadmap"]);
+        assert!(docs[0].doc.modified_at.is_some());
+
+        let (pairs, unresolved) = resolve_links(&docs);
+        assert_eq!(pairs, vec![(0, 2), (1, 2), (2, 0)]);
+        assert_eq!(unresolved, 1);
+    }
+
+    #[test]
+    fn reads_json_documents_and_plain_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "notes.json",
+            r#"[{"id": "a", "title": "Alpha", "content": "first",
+                 "created_at": "2021-03-04T05:06:07Z", "links": ["b"]},
+                {"id": "b", "title": "Beta", "body": "links [[Alpha]]"}]"#,
+        );
+        write(root, "broken.json", "{ nope");
+
+        let (docs, skipped) = read_folder(FolderFormat::Json, root).unwrap();
+        assert_eq!(skipped.len(), 1);
+        assert!(skipped[0].contains("broken.json"));
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].doc.body, "first");
+        assert_eq!(
+            docs[0].created_at.unwrap().to_rfc3339(),
+            "2021-03-04T05:06:07+00:00"
+        );
+        assert_eq!(resolve_links(&docs), (vec![(0, 1), (1, 0)], 0));
+
+        write(root, "todo.txt", "- [[not a link here]]");
+        let (docs, _) = read_folder(FolderFormat::Plain, root).unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].doc.title, "todo");
+        assert!(docs[0].links.is_empty());
+        assert_eq!(
+            "Obsidian".parse::<FolderFormat>(),
+            Ok(FolderFormat::Obsidian)
+        );
+        assert!("csv".parse::<FolderFormat>().is_err());
+    }
+}
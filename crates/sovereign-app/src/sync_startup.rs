@@ -25,6 +25,8 @@
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU8, Ordering};
 
+use sovereign_core::auth::PersonaKind;
+use sovereign_core::events::Publisher;
 use sovereign_core::interfaces::OrchestratorEvent;
 use sovereign_p2p::pairing::PairingManager;
 use sovereign_p2p::{ConnectivityState, P2pCommand, P2pConfig, P2pEvent, SovereignNode, SyncService};
@@ -43,6 +45,7 @@ fn p2p_config_from_app(app_p2p: &sovereign_core::config::P2pConfig) -> P2pConfig
         device_name: app_p2p.device_name.clone(),
         enable_mdns: app_p2p.enable_mdns,
         wifi_only: app_p2p.wifi_only,
+        serve_viewer: app_p2p.serve_viewer,
     }
 }
 
@@ -54,6 +57,7 @@ fn connectivity_from_u8(byte: u8) -> ConnectivityState {
         1 => ConnectivityState::Wifi,
         2 => ConnectivityState::Cellular,
         3 => ConnectivityState::Offline,
+        4 => ConnectivityState::Local,
         _ => ConnectivityState::Unknown,
     }
 }
@@ -63,6 +67,10 @@ fn connectivity_from_u8(byte: u8) -> ConnectivityState {
 const COMMAND_BUFFER: usize = 64;
 /// Channel buffer for P2P events flowing into the translator.
 const EVENT_BUFFER: usize = 256;
+/// A device seen again within this many seconds isn't re-stamped: mDNS
+/// rediscovers a present peer over and over, and each stamp rewrites the
+/// encrypted paired store.
+const SEEN_RESTAMP_SECS: i64 = 60;
 
 /// Bring up the P2P node, the event translator, and load the paired
 /// devices list. Idempotent — returns early if already started.
@@ -147,8 +155,23 @@ pub async fn start_p2p_node(state: &AppState) -> Result<(), String> {
         transport_key,
         keypair.clone(),
         version_store,
-    ));
+    )
+    // Document-body CRDT histories, sealed under a DeviceKey-derived key
+    // (they hold document text).
+    .with_crdt_store(sovereign_p2p::crdt::CrdtStore::load_or_default(
+        crate::setup::crypto_dir().join("sync_crdt.json"),
+        sovereign_p2p::crdt::derive_store_key(&p2p_identity_key),
+    ))
+    // Media fetched from peers for the documents they share.
+    .with_blob_store(sovereign_p2p::blob_store::BlobStore::open(
+        crate::media::media_dir(),
+    ))
+    // Work left over when a peer dropped mid-sync, resumed next session.
+    .with_sync_queue(sovereign_p2p::sync_queue::SyncQueue::load_or_default(
+        crate::setup::crypto_dir().join("sync_queue.json"),
+    )));
     sync_service.set_pair_keys(manager.pair_key_map());
+    sync_service.set_sync_policies(manager.sync_policy_map());
 
     // Channels.
     let (command_tx, command_rx) = mpsc::channel::<P2pCommand>(COMMAND_BUFFER);
@@ -189,9 +212,20 @@ pub async fn start_p2p_node(state: &AppState) -> Result<(), String> {
         .map_err(|e| format!("p2p listen: {e}"))?;
     tracing::info!("P2P node listening on {listen_addr}");
 
-    // Spawn the swarm event loop.
+    // Spawn the swarm event loop. At shutdown the node is told to stop
+    // after the commands already queued, and quitting waits for it.
+    let mut stopping = state.shutdown.listener("p2p");
+    let stop_tx = command_tx.clone();
     tauri::async_runtime::spawn(async move {
-        node.run().await;
+        let running = node.run();
+        tokio::pin!(running);
+        tokio::select! {
+            _ = &mut running => {}
+            _ = stopping.triggered() => {
+                let _ = stop_tx.send(P2pCommand::Shutdown).await;
+                running.await;
+            }
+        }
         tracing::info!("P2P node event loop exited");
     });
 
@@ -202,7 +236,7 @@ pub async fn start_p2p_node(state: &AppState) -> Result<(), String> {
     // process lifetime.
     let ctx = TranslatorCtx {
         command_tx: command_tx.clone(),
-        orch_tx: state.orch_tx.clone(),
+        orch_tx: state.bus.publisher(),
         connectivity: state.connectivity.clone(),
         wifi_only: state.config.p2p.wifi_only,
         pairing_manager: state.pairing_manager.clone(),
@@ -210,6 +244,9 @@ pub async fn start_p2p_node(state: &AppState) -> Result<(), String> {
         account_key: account_key.clone(),
         store_key,
         local_peer_id: local_peer_id.clone(),
+        sync_history: sync_history_log(),
+        encrypted_db: state.encrypted_db.read().await.clone(),
+        persona: *state.persona.read().await,
     };
     tauri::async_runtime::spawn(async move {
         spawn_event_translator(event_rx, ctx).await;
@@ -236,13 +273,33 @@ pub async fn start_p2p_node(state: &AppState) -> Result<(), String> {
     let _ = cmd_for_pairing
         .send(P2pCommand::UpdatePairedPeers { peer_ids: paired_ids })
         .await;
+    let _ = cmd_for_pairing
+        .send(P2pCommand::UpdatePausedPeers {
+            peer_ids: manager.paused_peers(),
+        })
+        .await;
+    let _ = cmd_for_pairing
+        .send(P2pCommand::UpdatePeerTrust {
+            trust: manager.trust_map(),
+        })
+        .await;
+    let _ = cmd_for_pairing
+        .send(P2pCommand::UpdateAddressBook {
+            addresses: manager.address_book(),
+        })
+        .await;
+    let _ = cmd_for_pairing
+        .send(P2pCommand::UpdateWipeGuardians {
+            peer_ids: crate::tauri_commands::backup::key_shard_holders(),
+        })
+        .await;
     *state.pairing_manager.write().await = Some(manager);
 
     Ok(())
 }
 
-/// Re-push the current paired-peer allow-list AND per-pair sealing keys
-/// to the running P2P node. Call this whenever the pairing list changes
+/// Re-push the current paired-peer allow-list, paused peers, trust tiers,
+/// address book, per-pair sealing keys and sync policies to the running P2P node. Call this whenever the pairing list changes
 /// at runtime (a device is paired or forgotten) so the node's [`P2P-001`]
 /// gate and the SyncService's pair-key map (P1.4 / P2P-005) stay in sync
 /// without requiring an app restart — a forgotten device loses its
@@ -253,7 +310,7 @@ pub async fn refresh_paired_peers(state: &AppState) {
         Some(tx) => tx,
         None => return,
     };
-    let (peer_ids, pair_keys) = {
+    let (peer_ids, paused, trust, addresses, pair_keys, policies) = {
         let guard = state.pairing_manager.read().await;
         match guard.as_ref() {
             Some(m) => (
@@ -261,26 +318,47 @@ pub async fn refresh_paired_peers(state: &AppState) {
                     .iter()
                     .map(|d| d.peer_id.clone())
                     .collect::<Vec<String>>(),
+                m.paused_peers(),
+                m.trust_map(),
+                m.address_book(),
                 m.pair_key_map(),
+                m.sync_policy_map(),
+            ),
+            None => (
+                Vec::new(),
+                Vec::new(),
+                std::collections::HashMap::new(),
+                std::collections::HashMap::new(),
+                std::collections::HashMap::new(),
+                std::collections::HashMap::new(),
             ),
-            None => (Vec::new(), std::collections::HashMap::new()),
         }
     };
     let _ = cmd_tx
         .send(P2pCommand::UpdatePairedPeers { peer_ids })
         .await;
+    let _ = cmd_tx
+        .send(P2pCommand::UpdatePausedPeers { peer_ids: paused })
+        .await;
+    let _ = cmd_tx.send(P2pCommand::UpdatePeerTrust { trust }).await;
+    let _ = cmd_tx
+        .send(P2pCommand::UpdateAddressBook { addresses })
+        .await;
     let _ = cmd_tx
         .send(P2pCommand::UpdatePairKeys {
             keys: sovereign_p2p::PairKeyMap(pair_keys),
         })
         .await;
+    let _ = cmd_tx
+        .send(P2pCommand::UpdateSyncPolicies { policies })
+        .await;
 }
 
 /// Everything the event translator needs to act on P2P events without
 /// touching `AppState` (which it can't hold across the spawn).
 struct TranslatorCtx {
     command_tx: mpsc::Sender<P2pCommand>,
-    orch_tx: std::sync::mpsc::Sender<OrchestratorEvent>,
+    orch_tx: Publisher<OrchestratorEvent>,
     connectivity: Arc<AtomicU8>,
     wifi_only: bool,
     pairing_manager:
@@ -289,12 +367,97 @@ struct TranslatorCtx {
     account_key: Arc<sovereign_crypto::account_key::AccountKey>,
     store_key: [u8; 32],
     local_peer_id: String,
+    sync_history: sovereign_p2p::sync_progress::SyncHistoryLog,
+    /// Where keys shared by a paired device are imported. Installed at
+    /// login, before the node starts.
+    encrypted_db: Option<Arc<sovereign_db::encrypted::EncryptedGraphDB>>,
+    /// The persona this node was started for; shared keys are only
+    /// imported by the primary one.
+    persona: Option<PersonaKind>,
+}
+
+/// The encryption layer whose keys may cross devices, either way. Only
+/// the primary persona's: a duress session's keys are the decoy's, and
+/// the real ones must never be imported into it either.
+pub(crate) fn key_exchange_db<T>(
+    persona: Option<PersonaKind>,
+    db: Option<T>,
+) -> Result<T, String> {
+    if persona != Some(PersonaKind::Primary) {
+        return Err("Key sharing is not available in this session".into());
+    }
+    db.ok_or_else(|| "Encryption is not active for this session".into())
+}
+
+/// The per-device sync history, appended to on every `SyncCompleted`.
+pub fn sync_history_log() -> sovereign_p2p::sync_progress::SyncHistoryLog {
+    sovereign_p2p::sync_progress::SyncHistoryLog::new(
+        crate::setup::crypto_dir().join("sync_history.log"),
+    )
+}
+
+fn core_progress(
+    p: sovereign_p2p::sync_progress::SyncProgress,
+) -> sovereign_core::interfaces::SyncProgress {
+    sovereign_core::interfaces::SyncProgress {
+        docs_to_pull: p.docs_to_pull,
+        docs_to_push: p.docs_to_push,
+        rows_to_pull: p.rows_to_pull,
+        rows_to_push: p.rows_to_push,
+        commits_received: p.commits_received,
+        commits_sent: p.commits_sent,
+        bytes_received: p.bytes_received,
+        bytes_sent: p.bytes_sent,
+        requests_done: p.requests_done,
+        requests_total: p.requests_total,
+        elapsed_ms: p.elapsed_ms,
+        eta_ms: p.eta_ms,
+    }
+}
+
+fn core_preview(
+    p: sovereign_p2p::sync_progress::SyncPreview,
+) -> sovereign_core::interfaces::SyncPreview {
+    sovereign_core::interfaces::SyncPreview {
+        docs_to_send: p.docs_to_send,
+        docs_to_receive: p.docs_to_receive,
+        conflicts: p.conflicts,
+        rows_to_send: p.rows_to_send,
+        rows_to_receive: p.rows_to_receive,
+        blobs_to_send: p.blobs_to_send,
+        blobs_to_receive: p.blobs_to_receive,
+        bytes_to_send: p.bytes_to_send,
+        bytes_to_receive: p.bytes_to_receive,
+    }
+}
+
+fn core_viewer_snapshot(
+    s: sovereign_p2p::protocol::viewer::ViewerSnapshot,
+) -> sovereign_core::interfaces::ViewerSnapshot {
+    sovereign_core::interfaces::ViewerSnapshot {
+        device_id: s.device_id,
+        generated_at: s.generated_at,
+        documents: s
+            .documents
+            .into_iter()
+            .map(|d| sovereign_core::interfaces::ViewerDocument {
+                doc_id: d.doc_id,
+                title: d.title,
+                content: d.content,
+                thread_id: d.thread_id,
+                modified_at: d.modified_at,
+            })
+            .collect(),
+        threads: s.threads.into_iter().map(|t| (t.thread_id, t.name)).collect(),
+        too_large: s.too_large,
+    }
 }
 
 /// Translate `P2pEvent`s into `OrchestratorEvent`s for the UI bridge,
 /// auto-trigger `StartSync` for any peer mDNS surfaces, collect the
 /// swarm's concrete listen addrs (pairing-offer dial hints), and persist
-/// P3.1 pairing completions.
+/// P3.1 pairing completions and paired devices' last-seen / last-sync
+/// times.
 ///
 /// The connectivity gate (Phase 4.2) suppresses the auto-trigger when
 /// the device reports cellular/offline and `wifi_only` is set. mDNS
@@ -321,6 +484,16 @@ async fn spawn_event_translator(mut event_rx: mpsc::Receiver<P2pEvent>, ctx: Tra
             }
         }
 
+        match event {
+            P2pEvent::PeerDiscovered { ref peer_id, .. } => {
+                note_device_activity(&ctx, peer_id, false).await
+            }
+            P2pEvent::SyncCompleted { ref peer_id, .. } => {
+                note_device_activity(&ctx, peer_id, true).await
+            }
+            _ => {}
+        }
+
         // Forward as OrchestratorEvent so tauri_events.rs can emit a
         // typed payload to the frontend.
         let orch_event = match event {
@@ -339,17 +512,57 @@ async fn spawn_event_translator(mut event_rx: mpsc::Receiver<P2pEvent>, ctx: Tra
                 peer_id,
                 status: "started".into(),
             }),
+            P2pEvent::SyncProgress { peer_id, progress } => {
+                Some(OrchestratorEvent::SyncProgress {
+                    peer_id,
+                    progress: core_progress(progress),
+                })
+            }
             P2pEvent::SyncCompleted {
                 peer_id,
                 docs_synced,
-            } => Some(OrchestratorEvent::SyncStatus {
-                peer_id,
-                status: format!("completed ({docs_synced} items)"),
-            }),
+                progress,
+            } => {
+                let record = sovereign_p2p::sync_progress::SyncHistoryRecord {
+                    at: chrono::Utc::now(),
+                    peer_id: peer_id.clone(),
+                    items_synced: docs_synced,
+                    progress,
+                };
+                if let Err(e) = ctx.sync_history.record(&record) {
+                    tracing::warn!("sync history write failed: {e}");
+                }
+                Some(OrchestratorEvent::SyncStatus {
+                    peer_id,
+                    status: format!("completed ({docs_synced} items)"),
+                })
+            }
             P2pEvent::SyncConflict {
                 doc_id,
                 description,
             } => Some(OrchestratorEvent::SyncConflict { doc_id, description }),
+            P2pEvent::ConflictDetails {
+                doc_id,
+                base,
+                local,
+                remote,
+                merged,
+                hunks,
+            } => Some(OrchestratorEvent::SyncConflictDetails {
+                doc_id,
+                base,
+                local,
+                remote,
+                merged,
+                hunks: hunks
+                    .into_iter()
+                    .map(|h| sovereign_core::interfaces::ConflictHunk {
+                        base: h.base,
+                        local: h.local,
+                        remote: h.remote,
+                    })
+                    .collect(),
+            }),
             P2pEvent::PairingCompleted { peer_id, device_name } => {
                 // P3.1: the node already registered the new device for
                 // this session (allow-list + pair key); persist it so it
@@ -400,6 +613,51 @@ async fn spawn_event_translator(mut event_rx: mpsc::Receiver<P2pEvent>, ctx: Tra
                 tracing::info!("Pairing requested from {peer_id} ({device_name})");
                 None
             }
+            P2pEvent::WipeCosignRequested { order_id, target_peer_id, issuer_peer_id } => {
+                Some(OrchestratorEvent::WipeCosignRequested {
+                    order_id,
+                    target_peer_id,
+                    issuer_peer_id,
+                })
+            }
+            P2pEvent::RemoteWipeOrdered { order_id, issuer_peer_id, cosigner_peer_id } => {
+                tracing::warn!(
+                    "Remote wipe {order_id} from {issuer_peer_id}, co-signed by {cosigner_peer_id}"
+                );
+                Some(OrchestratorEvent::RemoteWipeOrdered { order_id, issuer_peer_id })
+            }
+            P2pEvent::KeysReceived { peer_id, bundle } => match key_exchange_db(
+                ctx.persona,
+                ctx.encrypted_db.as_ref(),
+            ) {
+                Ok(db) => match db.import_key_escrow(&bundle).await {
+                    Ok(imported) => {
+                        tracing::info!("Imported {imported} key(s) shared by {peer_id}");
+                        Some(OrchestratorEvent::KeysImported { peer_id, imported })
+                    }
+                    Err(e) => {
+                        tracing::warn!("Importing keys from {peer_id} failed: {e}");
+                        None
+                    }
+                },
+                Err(e) => {
+                    tracing::warn!("Keys from {peer_id} dropped: {e}");
+                    None
+                }
+            },
+            P2pEvent::KeysShared { peer_id, accepted } => {
+                Some(OrchestratorEvent::KeysShared { peer_id, accepted })
+            }
+            P2pEvent::SyncPreview { peer_id, preview } => Some(OrchestratorEvent::SyncPreview {
+                peer_id,
+                preview: preview.map(core_preview),
+            }),
+            P2pEvent::ViewerSnapshot { peer_id, snapshot } => {
+                Some(OrchestratorEvent::ViewerSnapshot {
+                    peer_id,
+                    snapshot: snapshot.map(|s| core_viewer_snapshot(*s)),
+                })
+            }
         };
         if let Some(e) = orch_event {
             let _ = ctx.orch_tx.send(e);
@@ -447,7 +705,33 @@ async fn persist_paired_device(ctx: &TranslatorCtx, peer_id: &str, device_name:
     tracing::info!("Paired device persisted: {device_name} ({peer_id})");
 }
 
-/// Fire `StartSync` for every paired peer. Used by the periodic 5-min
+/// Stamp a paired device's last-seen (and, after a sync, last-sync)
+/// time in the encrypted paired store. Unpaired peers are ignored, and
+/// a sighting within [`SEEN_RESTAMP_SECS`] of the last one is dropped.
+async fn note_device_activity(ctx: &TranslatorCtx, peer_id: &str, synced: bool) {
+    let mut guard = ctx.pairing_manager.write().await;
+    let Some(manager) = guard.as_mut() else { return };
+    let last_seen = manager.get_device(peer_id).and_then(|d| d.last_seen.as_deref());
+    if !synced && seen_recently(last_seen, chrono::Utc::now()) {
+        return;
+    }
+    if manager.mark_seen(peer_id, synced) {
+        if let Err(e) = manager.save(&ctx.store_key) {
+            tracing::warn!("failed to persist last-seen for {peer_id}: {e}");
+        }
+    }
+}
+
+/// Whether `last_seen` (RFC 3339) is under [`SEEN_RESTAMP_SECS`] before `now`.
+fn seen_recently(last_seen: Option<&str>, now: chrono::DateTime<chrono::Utc>) -> bool {
+    last_seen
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .is_some_and(|t| {
+            now - t.with_timezone(&chrono::Utc) < chrono::Duration::seconds(SEEN_RESTAMP_SECS)
+        })
+}
+
+/// Fire `StartSync` for every paired peer that isn't paused. Used by the periodic 5-min
 /// poll task and by the `trigger_sync_now` Tauri command. A no-op when
 /// the P2P node hasn't started or when the connectivity gate
 /// (Phase 4.2) blocks (e.g. cellular + wifi_only on Android).
@@ -471,6 +755,9 @@ pub async fn trigger_sync_for_all_paired(state: &AppState) -> u32 {
     };
     let mut fired = 0u32;
     for device in manager.list_devices() {
+        if device.sync_paused || !device.trust.syncs() {
+            continue;
+        }
         if cmd_tx
             .try_send(P2pCommand::StartSync {
                 peer_id: device.peer_id.clone(),
@@ -482,3 +769,26 @@ pub async fn trigger_sync_for_all_paired(state: &AppState) -> u32 {
     }
     fired
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_cross_devices_only_in_the_primary_persona() {
+        assert_eq!(key_exchange_db(Some(PersonaKind::Primary), Some(1)), Ok(1));
+        assert!(key_exchange_db(Some(PersonaKind::Primary), None::<u8>).is_err());
+        assert!(key_exchange_db(Some(PersonaKind::Duress), Some(1)).is_err());
+        assert!(key_exchange_db(None, Some(1)).is_err());
+    }
+
+    #[test]
+    fn a_device_is_restamped_at_most_once_a_minute() {
+        let now = chrono::Utc::now();
+        let ago = |secs| Some((now - chrono::Duration::seconds(secs)).to_rfc3339());
+        assert!(seen_recently(ago(5).as_deref(), now));
+        assert!(!seen_recently(ago(SEEN_RESTAMP_SECS).as_deref(), now));
+        assert!(!seen_recently(None, now));
+        assert!(!seen_recently(Some("not a time"), now));
+    }
+}
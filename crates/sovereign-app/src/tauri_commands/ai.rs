@@ -32,6 +32,102 @@ pub async fn get_status(
     })
 }
 
+/// Backend half of the status bar: storage mode and the local models.
+/// P2P peers and skill jobs come from the frontend's event-driven stores,
+/// comms from `list_compose_channels`.
+#[tauri::command]
+pub async fn get_system_health(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<SystemHealthDto, String> {
+    state.require_unlocked(&webview).await?;
+    let config = state.current_config();
+
+    #[cfg(feature = "encryption")]
+    let encrypted = state.encrypted_db.read().await.is_some();
+    #[cfg(not(feature = "encryption"))]
+    let encrypted = false;
+    let db_mode = if encrypted {
+        "encrypted".to_string()
+    } else if config.database.mode == "memory" {
+        "memory".to_string()
+    } else {
+        "persistent".to_string()
+    };
+
+    let model = match &state.orchestrator {
+        Some(orch) => {
+            let (router, reasoning) = {
+                let a = state.model_assignments.lock().str_err()?;
+                (a.router.clone(), a.reasoning.clone())
+            };
+            let reasoning_loaded = orch.reasoning_loaded();
+            // llama.cpp offloads the weights, so the resident files' sizes
+            // are a fair lower bound; the KV cache comes on top.
+            let vram_estimate_mb = if config.ai.n_gpu_layers == 0 {
+                0.0
+            } else {
+                let dir = std::path::Path::new(&config.ai.model_dir);
+                let size_mb = |name: &str| {
+                    std::fs::metadata(dir.join(name))
+                        .map(|m| m.len() as f64 / (1024.0 * 1024.0))
+                        .unwrap_or(0.0)
+                };
+                size_mb(&router)
+                    + if reasoning_loaded == Some(true) { size_mb(&reasoning) } else { 0.0 }
+            };
+            Some(ModelHealthDto {
+                router,
+                reasoning,
+                reasoning_loaded,
+                vram_estimate_mb,
+            })
+        }
+        None => None,
+    };
+
+    Ok(SystemHealthDto { db_mode, model })
+}
+
+/// The local performance history for the Diagnostics panel: per-day
+/// timings of each metric over the last `days` days. Empty until metrics
+/// are turned on. Read from this device only; it is never transmitted.
+#[tauri::command]
+pub async fn get_metrics(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    days: u32,
+) -> Result<Vec<sovereign_core::metrics::MetricTrend>, String> {
+    state.require_unlocked(&webview).await?;
+    Ok(sovereign_core::metrics::trends(days))
+}
+
+/// Workspace statistics for the dashboard: totals, documents per thread,
+/// messages per channel, and the last `weeks` weeks of growth. Computed on
+/// request from the local graph.
+#[tauri::command]
+pub async fn get_workspace_stats(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    weeks: u32,
+) -> Result<sovereign_db::analytics::WorkspaceStats, String> {
+    state.require_unlocked(&webview).await?;
+    let weeks = weeks.clamp(1, 104) as usize;
+    sovereign_db::analytics::workspace_stats(state.db.as_ref(), weeks, Utc::now())
+        .await
+        .str_err()
+}
+
+/// Delete the local performance history.
+#[tauri::command]
+pub async fn clear_metrics(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    sovereign_core::metrics::clear().str_err()
+}
+
 /// SIDECAR-001/002: hand the provisioned jiminy sidecar token to the trusted
 /// webview so `vision.ts` can authenticate its cross-origin `/vision/*` fetches
 /// (the sidecars now require the bearer token regardless of Origin). Returns
@@ -52,14 +148,21 @@ pub fn get_jiminy_token() -> Option<String> {
 ///
 /// The response arrives asynchronously via Tauri events (chat-response,
 /// bubble-state, action-proposed, etc.) — this command only kicks off
-/// processing and returns immediately.
+/// processing and returns immediately. A typed canary phrase is acted on
+/// first (see `crate::canary`).
 #[tauri::command]
 pub async fn chat_message(
+    app: tauri::AppHandle,
     webview: tauri::Webview,
     state: State<'_, AppState>,
     message: String,
 ) -> Result<(), String> {
     state.require_unlocked(&webview).await?;
+    #[cfg(feature = "encryption")]
+    if crate::canary::intercept(&state, &app, &message).await {
+        return Ok(());
+    }
+    let _ = &app;
     let orch = state
         .orchestrator
         .as_ref()
@@ -69,11 +172,57 @@ pub async fn chat_message(
         .str_err()
 }
 
+/// Chat scrollback from the session log, oldest first.
+///
+/// PII tokens are resolved to masked samples so the panel never shows raw
+/// `[pii:<id>]` markers and loading history doesn't count as a reveal.
+/// Without the account key the canonical text is returned as-is.
+#[tauri::command]
+pub async fn get_chat_history(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<ChatHistoryEntryDto>, String> {
+    state.require_unlocked(&webview).await?;
+    let Some(orch) = state.orchestrator.as_ref() else {
+        return Ok(Vec::new());
+    };
+    let entries = orch.chat_history(limit.unwrap_or(100).min(500));
+    #[cfg(feature = "encryption")]
+    let account_key = state.account_key().await;
+
+    let mut out = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let text = entry.content.unwrap_or_default();
+        #[cfg(feature = "encryption")]
+        let text = match &account_key {
+            Some(ak) => {
+                sovereign_ai::pii::resolve::resolve_body(
+                    state.db.as_ref() as &dyn GraphDB,
+                    ak,
+                    &text,
+                    sovereign_ai::pii::resolve::AccessLevel::MaskedSample,
+                )
+                .await
+            }
+            None => text,
+        };
+        let role = if entry.entry_type == "chat_response" { "assistant" } else { "user" };
+        out.push(ChatHistoryEntryDto {
+            role: role.into(),
+            text,
+            ts: entry.ts,
+        });
+    }
+    Ok(out)
+}
+
 // ---------------------------------------------------------------------------
 // Search
 // ---------------------------------------------------------------------------
 
-/// Search documents by title (client-side quick filter).
+/// Search documents by title and content, or by tag when the query is a
+/// single `#tag` (client-side quick filter).
 #[tauri::command]
 pub async fn search_documents(
     webview: tauri::Webview,
@@ -81,11 +230,23 @@ pub async fn search_documents(
     query: String,
 ) -> Result<Vec<SearchHit>, String> {
     state.require_unlocked(&webview).await?;
-    let docs = state
-        .db
-        .search_documents_by_title(&query)
-        .await
-        .str_err()?;
+    let query = query.trim();
+    // Sealed documents are found by title only: a match in the body or
+    // its tags would give the content away.
+    let sealed = |d: &Document| d.sensitivity == Sensitivity::Sealed;
+    let docs = if query.starts_with('#') && !query.contains(char::is_whitespace) {
+        let mut docs = state.db.search_documents_by_tag(query).await.str_err()?;
+        docs.retain(|d| !sealed(d));
+        docs
+    } else {
+        let mut docs = state.db.search_documents_by_title(query).await.str_err()?;
+        for doc in state.db.search_documents(query).await.str_err()? {
+            if !sealed(&doc) && !docs.iter().any(|d| d.id == doc.id) {
+                docs.push(doc);
+            }
+        }
+        docs
+    };
 
     let results: Vec<SearchHit> = docs
         .into_iter()
@@ -96,7 +257,9 @@ pub async fn search_documents(
                 .as_ref()
                 .map(sovereign_db::schema::thing_to_raw)
                 .unwrap_or_default();
-            let snippet = if d.content.len() > 120 {
+            let snippet = if sealed(&d) {
+                String::new()
+            } else if d.content.len() > 120 {
                 format!("{}...", &d.content[..120])
             } else {
                 d.content.clone()
@@ -162,17 +325,32 @@ pub async fn reject_action(
         .str_err()
 }
 
-/// Accept a proactive suggestion.
+/// Accept a proactive suggestion and carry it out.
+///
+/// The acceptance is recorded for the adaptive suggestion thresholds first,
+/// then the orchestrator runs the action. Its events (`thread-created`,
+/// `milestone-created`, `adoption-started`, then `action-executed`) arrive
+/// while this command is pending.
 #[tauri::command]
 pub async fn accept_suggestion(
     webview: tauri::Webview,
     state: State<'_, AppState>,
     action: String,
+    target: Option<String>,
 ) -> Result<(), String> {
     state.require_unlocked(&webview).await?;
     state
         .feedback_tx
-        .send(FeedbackEvent::SuggestionAccepted { action })
+        .send(FeedbackEvent::SuggestionAccepted {
+            action: action.clone(),
+        })
+        .await
+        .str_err()?;
+    let orch = state
+        .orchestrator
+        .as_ref()
+        .ok_or_else(|| "AI orchestrator not available".to_string())?;
+    orch.run_suggestion(&action, target.as_deref())
         .await
         .str_err()
 }
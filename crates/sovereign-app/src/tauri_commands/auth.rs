@@ -4,11 +4,22 @@ use super::*;
 // Phase 4: Auth, Onboarding, Settings, Document deletion
 // ---------------------------------------------------------------------------
 
+/// Security-key recovery key, sealed under the primary KEK (see
+/// `sovereign_crypto::auth::SecondFactor`).
+#[cfg(feature = "encryption")]
+pub(crate) const SECOND_FACTOR_RECOVERY_FILE: &str = "second_factor_recovery.store";
+
 /// Authenticate a password against the AuthStore and install the
 /// resulting keys across AppState + the orchestrator. Used by both
 /// `validate_password` (login flow) and `complete_onboarding`
 /// (first-run flow). Returns the persona that authenticated.
 ///
+/// A store with an enrolled security key also needs the token's
+/// `hmac-secret` output (`second_factor`).
+///
+/// With `typing`, a primary login is also checked against the enrolled
+/// keystroke reference; the verdict is returned next to the persona.
+///
 /// Two keys are installed:
 ///   - `account_key` (user-scoped) — consumed by vault, PII reveal,
 ///     PII ingest, and the encrypted session log. Same value on every
@@ -20,12 +31,48 @@ async fn install_session(
     state: &AppState,
     auth_store: &sovereign_crypto::auth::AuthStore,
     password: &[u8],
-) -> Result<sovereign_crypto::auth::PersonaKind, String> {
-    let auth_result = auth_store
-        .authenticate(password)
-        .map_err(|_| "Invalid password".to_string())?;
+    second_factor: Option<&sovereign_crypto::auth::TokenSecret>,
+    typing: Option<&sovereign_crypto::keystroke::TypingProfile>,
+) -> Result<(sovereign_crypto::auth::PersonaKind, crate::keystroke_gate::Verdict), String> {
+    let auth_result = match second_factor {
+        Some(secret) => auth_store.authenticate_with_second_factor(password, secret),
+        None => auth_store.authenticate(password),
+    }
+    .map_err(|_| "Invalid password".to_string())?;
     let persona = auth_result.persona;
+
+    let verdict = match typing {
+        Some(typing)
+            if persona == sovereign_crypto::auth::PersonaKind::Primary
+                && state.config.crypto.keystroke_enabled =>
+        {
+            let crypto_dir = state.profile_dir.join("crypto");
+            let reference =
+                crate::keystroke_gate::load_reference(&crypto_dir, auth_result.kek.as_bytes());
+            crate::keystroke_gate::judge(reference.as_ref(), typing)
+        }
+        _ => crate::keystroke_gate::Verdict::Unverified,
+    };
+
+    // The security-key recovery key lives sealed under the KEK; hold it for
+    // the session so `backup_now` can hand shares of it to the guardians.
+    if auth_store.requires_second_factor() {
+        let path = state
+            .profile_dir
+            .join("crypto")
+            .join(SECOND_FACTOR_RECOVERY_FILE);
+        match sovereign_crypto::auth::SealedRecoveryKey::load(&path)
+            .and_then(|sealed| sealed.open(&auth_result.kek))
+        {
+            Ok(key) => *state.second_factor_recovery.write().await = Some(std::sync::Arc::new(key)),
+            Err(e) => tracing::warn!("security-key recovery key unavailable: {e}"),
+        }
+    }
     let device_key_arc = std::sync::Arc::new(auth_result.device_key);
+    let core_persona = match persona {
+        sovereign_crypto::auth::PersonaKind::Primary => sovereign_core::auth::PersonaKind::Primary,
+        sovereign_crypto::auth::PersonaKind::Duress => sovereign_core::auth::PersonaKind::Duress,
+    };
     let account_key_arc = std::sync::Arc::new(auth_result.account_key);
 
     // 1. Call complete_auth and discard the returned key_db / kek —
@@ -48,7 +95,10 @@ async fn install_session(
     // (each `*_nonce` field guards a per-field decrypt branch), so pre-login
     // plaintext rows (seed data, v0.0.5 desktop state) continue to read
     // correctly; new writes from this point on are encrypted.
-    {
+    //
+    // Evaluates to the KEK the session runs on: the one just unlocked, or
+    // the new one if an interrupted rotation is finished here.
+    let kek = {
         let kek_arc = std::sync::Arc::new(sovereign_crypto::kek::Kek::from_bytes(
             *auth_result.kek.as_bytes(),
         ));
@@ -60,14 +110,6 @@ async fn install_session(
         // persona-suffixed inside build_encrypted_db. Previously both personas
         // shared one raw DB + one key DB, so duress either failed to decrypt
         // (locking the decoy out) or exposed REAL data.
-        let core_persona = match persona {
-            sovereign_crypto::auth::PersonaKind::Primary => {
-                sovereign_core::auth::PersonaKind::Primary
-            }
-            sovereign_crypto::auth::PersonaKind::Duress => {
-                sovereign_core::auth::PersonaKind::Duress
-            }
-        };
         // SIDECHANNEL-001: time the persona-distinguishing DB setup so we can pad
         // it to a constant floor below. The primary persona reuses the already-
         // open boot DB; the duress persona opens + seeds a SEPARATE DB. Without
@@ -82,18 +124,9 @@ async fn install_session(
             // is the bootstrap reference held since init_backend.
             sovereign_core::auth::PersonaKind::Primary => state.db.raw_inner(),
             sovereign_core::auth::PersonaKind::Duress => {
-                let mut duress_config = state.config.clone();
-                duress_config.database.path =
-                    crate::setup::persona_db_path(&state.config, core_persona);
-                match crate::setup::create_db(&duress_config).await {
-                    Ok(ddb) => {
-                        // Seed plausible decoy data on first duress login
-                        // (seed_duress_db is idempotent — no-op once populated).
-                        if let Err(e) = crate::duress::seed_duress_db(&ddb).await {
-                            tracing::warn!("duress decoy seed failed (continuing): {e}");
-                        }
-                        std::sync::Arc::new(ddb)
-                    }
+                // Seeded with plausible decoy data on first duress login.
+                match crate::duress::open_duress_db(&state.config).await {
+                    Ok(ddb) => std::sync::Arc::new(ddb),
                     Err(e) => {
                         tracing::error!("duress profile DB open failed; aborting login: {e}");
                         return Err(format!("Could not open the duress profile ({e})"));
@@ -132,16 +165,66 @@ async fn install_session(
         if elapsed < DB_SETUP_FLOOR {
             tokio::time::sleep(DB_SETUP_FLOOR - elapsed).await;
         }
+        // A KEK rotation interrupted last session is finished before any
+        // row is read. On failure the journal stays for the next unlock.
+        let rotation = crate::key_rotation::KekRotation {
+            crypto_dir: &state.profile_dir.join("crypto"),
+            persona: core_persona,
+            account_key: &account_key_arc,
+            device_key: &device_key_arc,
+            old_kek: &auth_result.kek,
+            password,
+            second_factor,
+        };
+        let kek = match rotation.resume(&encrypted).await {
+            Ok(Some(new_kek)) => new_kek,
+            Ok(None) => std::sync::Arc::new(sovereign_crypto::kek::Kek::from_bytes(
+                *auth_result.kek.as_bytes(),
+            )),
+            Err(e) => {
+                tracing::error!("Resuming KEK rotation failed: {e}");
+                std::sync::Arc::new(sovereign_crypto::kek::Kek::from_bytes(
+                    *auth_result.kek.as_bytes(),
+                ))
+            }
+        };
+        *state.encrypted_db.write().await = Some(encrypted.clone());
+        // Index content left unindexed by older versions or the CLI
+        // migration, off the login path.
+        {
+            let encrypted = encrypted.clone();
+            tauri::async_runtime::spawn(async move {
+                match encrypted.reindex_documents().await {
+                    Ok(0) => {}
+                    Ok(n) => tracing::info!("Indexed {n} encrypted documents for search"),
+                    Err(e) => tracing::warn!("Document search index backfill failed: {e}"),
+                }
+            });
+        }
         state.db.swap(encrypted);
         tracing::info!("EncryptedGraphDB installed for {core_persona:?} persona");
-    }
+        kek
+    };
 
     // 3. Encryption is installed: mark the session unlocked by installing both
     //    keys into AppState. account_key serves vault / PII reveal / PII ingest;
     //    p2p_identity_key is consumed by the P2P startup hook below. Reaching
     //    this point means require_session_unlocked() will now return Ok.
+    *state.persona.write().await = Some(core_persona);
     state.set_account_key(account_key_arc.clone()).await;
     state.set_p2p_identity_key(device_key_arc.clone()).await;
+    crate::canary::load(state, kek.as_bytes());
+    {
+        let crypto_dir = state.profile_dir.join("crypto");
+        let path = crate::key_rotation::key_audit_path(&crypto_dir, core_persona);
+        let key = sovereign_crypto::key_audit::audit_key(&kek);
+        if let Err(e) = sovereign_crypto::key_audit::install(&path, *key) {
+            tracing::error!("Key audit log unavailable: {e}");
+        }
+        // Shared by both personas and in the clear; the per-persona sealed
+        // log replaces it.
+        let _ = std::fs::remove_file(crypto_dir.join(crate::key_rotation::LEGACY_AUDIT_LOG_FILE));
+    }
 
     // 3b. MODELTRUST-002: install the model-integrity unlock key + TOFU store
     //     path now that a session is unlocked. This enables trust-on-first-use
@@ -185,7 +268,11 @@ async fn install_session(
         }
     }
 
-    // 6. P2P startup (Phase 3c): bring up the libp2p node, install the
+    // 6. Crash recovery: documents saved but never committed because the
+    //    last run died get their commit now that the database is unlocked.
+    state.autocommit.lock().await.recover().await;
+
+    // 7. P2P startup (Phase 3c): bring up the libp2p node, install the
     //    command channel on AppState + orchestrator, load paired
     //    devices, and spawn the event translator. Idempotent — re-login
     //    is a no-op on the second pass. Best-effort: a P2P bring-up
@@ -197,7 +284,16 @@ async fn install_session(
         }
     }
 
-    Ok(persona)
+    // 8. Comms startup: poll configured messaging accounts and install the
+    //    send handle the inbox compose flow uses. Best-effort like P2P.
+    #[cfg(feature = "comms")]
+    {
+        if let Err(e) = crate::comms_startup::start_comms(state).await {
+            tracing::warn!("Comms startup failed (continuing without messaging): {e}");
+        }
+    }
+
+    Ok((persona, verdict))
 }
 
 /// Check whether the user needs onboarding or login.
@@ -211,103 +307,197 @@ pub async fn check_auth_state(state: State<'_, AppState>) -> Result<AuthCheckRes
     #[cfg(not(feature = "encryption"))]
     let needs_login = false;
 
+    #[cfg(feature = "encryption")]
+    let security_key_required = needs_login
+        && sovereign_crypto::auth::AuthStore::load(&state.profile_dir.join("crypto/auth.store"))
+            .is_ok_and(|store| store.requires_second_factor());
+
+    #[cfg(not(feature = "encryption"))]
+    let security_key_required = false;
+
+    #[cfg(feature = "encryption")]
+    let device_wiped = crate::remote_wipe::is_wiped(&state.profile_dir);
+
+    #[cfg(not(feature = "encryption"))]
+    let device_wiped = false;
+
     let _ = &state; // suppress unused warning in non-encryption build
 
     Ok(AuthCheckResult {
-        needs_onboarding: !onboarding_done,
+        needs_onboarding: !onboarding_done && !device_wiped,
         needs_login,
         crypto_enabled: cfg!(feature = "encryption"),
+        security_key_required,
+        device_wiped,
     })
 }
 
+/// Unlock a session with the passphrase: the login lockout, the security
+/// key when one is enrolled, then `install_session`. Shared by the login
+/// screen (`validate_password`) and `sovereign daemon`.
+#[cfg(feature = "encryption")]
+pub(crate) async fn unlock(
+    state: &AppState,
+    password: &[u8],
+    typing: Option<&sovereign_crypto::keystroke::TypingProfile>,
+) -> Result<(sovereign_crypto::auth::PersonaKind, crate::keystroke_gate::Verdict), String> {
+    let crypto_dir = state.profile_dir.join("crypto");
+    let auth_path = crypto_dir.join("auth.store");
+    let store = sovereign_crypto::auth::AuthStore::load(&auth_path)
+        .str_err()?;
+
+    // --- CRYPTO-002: server-side login lockout -------------------------------
+    // The login command historically forwarded max_login_attempts /
+    // lockout_seconds to the UI but enforced nothing in Rust, so scripted
+    // IPC could guess passwords unthrottled, amplifying the at-rest
+    // brute-force surface. We now enforce the lockout here, BEFORE touching
+    // the auth store.
+    //
+    // The tracker (`crypto/login_attempts.json`) is a plaintext file that
+    // defends against online/scripted guessing via IPC. An attacker with
+    // filesystem access already holds auth.store and attacks it directly
+    // (covered by the Argon2id at-rest fix CRYPTO-001), so FS-tamper of the
+    // counter is out of scope — clearing it only resets the online throttle.
+    let max = state.config.crypto.max_login_attempts;
+    let lockout_secs = state.config.crypto.lockout_seconds;
+    let mut attempts = crate::login_throttle::LoginAttempts::load(&crypto_dir);
+
+    match attempts.is_locked(max, lockout_secs) {
+        Some(remaining) => {
+            return Err(format!(
+                "Too many failed login attempts — locked for {remaining} seconds"
+            ));
+        }
+        None if max > 0 && attempts.failed_count >= max => {
+            // We hit the limit on a previous window, but that window has
+            // now fully elapsed (is_locked returned None). Clear the stale
+            // window so a fresh count starts on the next failure.
+            attempts.reset();
+            if let Err(e) = attempts.save(&crypto_dir) {
+                tracing::warn!("login_throttle: failed to persist window reset: {e}");
+            }
+        }
+        None => {}
+    }
+
+    // Constant per-attempt delay to throttle scripted guessing regardless
+    // of outcome.
+    tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+
+    // Security key: read the token's output before checking the
+    // password. A missing or untouched token is not a wrong guess, so it
+    // doesn't count against the lockout.
+    let token_secret = match &store.second_factor {
+        Some(factor) => Some(read_security_key(factor).await?),
+        None => None,
+    };
+
+    // authenticate (via install_session) returns Ok for BOTH the primary
+    // AND the duress persona — both are "success" and must reset the
+    // counter identically. The only difference is the downstream persona
+    // string the caller reports; the throttle path must NOT leak which one
+    // unlocked.
+    match install_session(state, &store, password, token_secret.as_ref(), typing).await {
+        Ok(unlocked) => {
+            attempts.reset();
+            if let Err(e) = attempts.save(&crypto_dir) {
+                tracing::warn!("login_throttle: failed to persist reset on success: {e}");
+            }
+            Ok(unlocked)
+        }
+        Err(e) => {
+            attempts.record_failure();
+            if let Err(save_err) = attempts.save(&crypto_dir) {
+                tracing::warn!("login_throttle: failed to persist failure: {save_err}");
+            }
+            // Return the existing auth error UNCHANGED.
+            Err(e)
+        }
+    }
+}
+
 /// Validate a password against the auth store and install the session.
 /// Returns persona ("primary" or "duress"). After this call returns Ok,
 /// AppState.device_key is populated and the orchestrator has its PII /
 /// session-log keys installed (vault, PII reveal, encrypted log work).
+///
+/// `keystrokes` are the passphrase's key timings. A primary login typed
+/// unlike the enrolled reference still unlocks, but locks again after
+/// `keystroke_reauth_minutes` (see `keystroke_gate`).
 #[tauri::command]
 pub async fn validate_password(
     state: State<'_, AppState>,
+    app: tauri::AppHandle,
     password: String,
     keystrokes: Vec<KeystrokeSampleDto>,
 ) -> Result<String, String> {
     #[cfg(feature = "encryption")]
     {
-        let _ = &keystrokes; // keystroke comparison deferred to future phase
-        let crypto_dir = state.profile_dir.join("crypto");
-        let auth_path = crypto_dir.join("auth.store");
-        let store = sovereign_crypto::auth::AuthStore::load(&auth_path)
-            .str_err()?;
-
-        // --- CRYPTO-002: server-side login lockout ---------------------------
-        // The login command historically forwarded max_login_attempts /
-        // lockout_seconds to the UI but enforced nothing in Rust, so scripted
-        // IPC could guess passwords unthrottled, amplifying the at-rest
-        // brute-force surface. We now enforce the lockout here, BEFORE touching
-        // the auth store.
-        //
-        // The tracker (`crypto/login_attempts.json`) is a plaintext file that
-        // defends against online/scripted guessing via IPC. An attacker with
-        // filesystem access already holds auth.store and attacks it directly
-        // (covered by the Argon2id at-rest fix CRYPTO-001), so FS-tamper of the
-        // counter is out of scope — clearing it only resets the online throttle.
-        let max = state.config.crypto.max_login_attempts;
-        let lockout_secs = state.config.crypto.lockout_seconds;
-        let mut attempts = crate::login_throttle::LoginAttempts::load(&crypto_dir);
-
-        match attempts.is_locked(max, lockout_secs) {
-            Some(remaining) => {
-                return Err(format!(
-                    "Too many failed login attempts — locked for {remaining} seconds"
-                ));
-            }
-            None if max > 0 && attempts.failed_count >= max => {
-                // We hit the limit on a previous window, but that window has
-                // now fully elapsed (is_locked returned None). Clear the stale
-                // window so a fresh count starts on the next failure.
-                attempts.reset();
-                if let Err(e) = attempts.save(&crypto_dir) {
-                    tracing::warn!("login_throttle: failed to persist window reset: {e}");
-                }
+        let typing = KeystrokeSampleDto::to_profile(&keystrokes);
+        let (persona, verdict) = unlock(&state, password.as_bytes(), Some(&typing)).await?;
+        let reauth_minutes = state.config.crypto.keystroke_reauth_minutes;
+        if verdict == crate::keystroke_gate::Verdict::Anomaly && reauth_minutes > 0 {
+            tracing::warn!(
+                "Passphrase typed unlike the enrolled rhythm; re-authentication in {reauth_minutes} min"
+            );
+            if let Some(session) = state.account_key().await {
+                crate::keystroke_gate::schedule_reauth(app, reauth_minutes, session);
             }
-            None => {}
         }
-
-        // Constant per-attempt delay to throttle scripted guessing regardless
-        // of outcome.
-        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
-
-        // authenticate (via install_session) returns Ok for BOTH the primary
-        // AND the duress persona — both are "success" and must reset the
-        // counter identically. The only difference is the downstream persona
-        // string we return; the throttle path must NOT leak which one unlocked.
-        match install_session(&state, &store, password.as_bytes()).await {
-            Ok(persona) => {
-                attempts.reset();
-                if let Err(e) = attempts.save(&crypto_dir) {
-                    tracing::warn!("login_throttle: failed to persist reset on success: {e}");
-                }
-                match persona {
-                    sovereign_crypto::auth::PersonaKind::Primary => Ok("primary".into()),
-                    sovereign_crypto::auth::PersonaKind::Duress => Ok("duress".into()),
-                }
-            }
-            Err(e) => {
-                attempts.record_failure();
-                if let Err(save_err) = attempts.save(&crypto_dir) {
-                    tracing::warn!("login_throttle: failed to persist failure: {save_err}");
-                }
-                // Return the existing auth error UNCHANGED.
-                Err(e)
-            }
+        match persona {
+            sovereign_crypto::auth::PersonaKind::Primary => Ok("primary".into()),
+            sovereign_crypto::auth::PersonaKind::Duress => Ok("duress".into()),
         }
     }
     #[cfg(not(feature = "encryption"))]
     {
-        let _ = (&state, &password, &keystrokes);
+        let _ = (&state, &app, &password, &keystrokes);
         Ok("primary".into())
     }
 }
 
+/// Forgotten passphrase: unlock the primary persona with the recovery
+/// phrase exported at onboarding and make `new_password` its passphrase
+/// from now on. The duress passphrase is unaffected.
+#[tauri::command]
+pub async fn restore_from_recovery_phrase(
+    state: State<'_, AppState>,
+    phrase: String,
+    new_password: String,
+) -> Result<(), String> {
+    #[cfg(feature = "encryption")]
+    {
+        let policy = sovereign_crypto::auth::PasswordPolicy::default_policy().validate(&new_password);
+        if !policy.valid {
+            return Err(policy.errors.join("; "));
+        }
+        let auth_path = state.profile_dir.join("crypto").join("auth.store");
+        let mut store = sovereign_crypto::auth::AuthStore::load(&auth_path).str_err()?;
+        if store.requires_second_factor() {
+            return Err(
+                "This store unlocks with a security key — use its recovery instead".to_string(),
+            );
+        }
+        let master = sovereign_crypto::recovery_phrase::from_phrase(&phrase).str_err()?;
+        store
+            .rebind_passphrase(&master, new_password.as_bytes())
+            .map_err(|e| match e {
+                sovereign_crypto::CryptoError::DecryptionFailed => {
+                    "This recovery phrase does not belong to this device".to_string()
+                }
+                e => e.to_string(),
+            })?;
+        store.save(&auth_path).str_err()?;
+        install_session(&state, &store, new_password.as_bytes(), None, None).await?;
+        Ok(())
+    }
+    #[cfg(not(feature = "encryption"))]
+    {
+        let _ = (&state, &phrase, &new_password);
+        Err("recovery phrases require a build with the encryption feature".to_string())
+    }
+}
+
 /// Validate a password against the password policy (strength/complexity).
 #[tauri::command]
 pub async fn validate_password_policy(
@@ -339,7 +529,7 @@ pub async fn validate_password_policy(
 pub async fn complete_onboarding(
     state: State<'_, AppState>,
     data: OnboardingData,
-) -> Result<(), String> {
+) -> Result<OnboardingResult, String> {
     let profile_dir = &state.profile_dir;
 
     // IPC-006: this is a BOOTSTRAP (pre-auth) command — without this guard a
@@ -369,7 +559,7 @@ pub async fn complete_onboarding(
 
     // Create crypto stores if encryption enabled and password provided
     #[cfg(feature = "encryption")]
-    if let Some(ref password) = data.password {
+    let recovery_phrase = if let Some(ref password) = data.password {
         let crypto_dir = profile_dir.join("crypto");
         std::fs::create_dir_all(&crypto_dir).str_err()?;
 
@@ -380,49 +570,71 @@ pub async fn complete_onboarding(
             .as_deref()
             .unwrap_or("duress-fallback-unused");
 
-        let auth_store = sovereign_crypto::auth::AuthStore::create(
+        let mut auth_store = sovereign_crypto::auth::AuthStore::create(
             password.as_bytes(),
             duress.as_bytes(),
             &salt,
             &device_id,
+            crate::setup::new_store_kdf(&state.config.crypto),
         )
         .str_err()?;
+
+        // The recovery phrase is handed back once, here, and kept nowhere.
+        // A security key seals the persona entries, so the phrase alone
+        // could not open them — it is only offered without one.
+        let recovery_phrase = if data.recovery_phrase && !data.use_security_key {
+            let master = auth_store.primary_master_key(password.as_bytes()).str_err()?;
+            Some(sovereign_crypto::recovery_phrase::to_phrase(&master).str_err()?)
+        } else {
+            None
+        };
+
+        // Bind the security key enrolled earlier in the wizard. The
+        // recovery key is kept sealed under the primary KEK until the next
+        // backup hands its shares to the guardians.
+        let enrollment = if data.use_security_key {
+            let enrollment = state.pending_security_key.lock().await.take().ok_or_else(|| {
+                "No security key enrolled — go back and touch your key".to_string()
+            })?;
+            let kek = auth_store.authenticate(password.as_bytes()).str_err()?.kek;
+            let recovery_key = auth_store.enroll_second_factor(&enrollment).str_err()?;
+            recovery_key
+                .seal(&kek)
+                .and_then(|sealed| sealed.save(&crypto_dir.join(SECOND_FACTOR_RECOVERY_FILE)))
+                .str_err()?;
+            Some(enrollment)
+        } else {
+            None
+        };
+        let token_secret = enrollment.as_ref().map(|e| &e.secret);
+
         auth_store
             .save(&crypto_dir.join("auth.store"))
             .str_err()?;
 
         // Install the session immediately so the user lands in a fully
         // unlocked state (vault, PII pipeline, encrypted session log).
-        install_session(&state, &auth_store, password.as_bytes()).await?;
+        install_session(&state, &auth_store, password.as_bytes(), token_secret, None).await?;
+        let authenticate = || match token_secret {
+            Some(secret) => auth_store.authenticate_with_second_factor(password.as_bytes(), secret),
+            None => auth_store.authenticate(password.as_bytes()),
+        };
 
-        // Save canary phrase if provided
-        if let Some(ref phrase) = data.canary_phrase {
-            if let Ok(auth_result) = auth_store.authenticate(password.as_bytes()) {
-                let canary =
-                    sovereign_crypto::canary::CanaryStore::encrypt(phrase, auth_result.kek.as_bytes())
-                        .str_err()?;
-                canary
-                    .save(&crypto_dir.join("canary.store"))
-                    .str_err()?;
+        // Save canary phrases if provided
+        let canary_rules = CanaryRuleDto::to_rules(&data.canary_rules)?;
+        if !canary_rules.is_empty() {
+            if let Ok(auth_result) = authenticate() {
+                crate::canary::save_rules(&state, &canary_rules, auth_result.kek.as_bytes())?;
             }
         }
 
         // Save keystroke reference if enrollment data provided
         if !data.keystrokes.is_empty() {
-            if let Ok(auth_result) = auth_store.authenticate(password.as_bytes()) {
+            if let Ok(auth_result) = authenticate() {
                 let profiles: Vec<sovereign_crypto::keystroke::TypingProfile> = data
                     .keystrokes
                     .iter()
-                    .map(|samples| sovereign_crypto::keystroke::TypingProfile {
-                        samples: samples
-                            .iter()
-                            .map(|s| sovereign_crypto::keystroke::KeystrokeSample {
-                                key: s.key.clone(),
-                                press_ms: s.press_ms,
-                                release_ms: s.release_ms,
-                            })
-                            .collect(),
-                    })
+                    .map(|samples| KeystrokeSampleDto::to_profile(samples))
                     .collect();
                 let reference =
                     sovereign_crypto::keystroke::KeystrokeReference::from_enrollments(&profiles);
@@ -431,12 +643,17 @@ pub async fn complete_onboarding(
                         .str_err()?;
                     let ks_json =
                         serde_json::to_string(&encrypted).str_err()?;
-                    std::fs::write(crypto_dir.join("keystroke.store"), ks_json)
+                    std::fs::write(crypto_dir.join(crate::keystroke_gate::STORE_FILE), ks_json)
                         .str_err()?;
                 }
             }
         }
-    }
+        recovery_phrase
+    } else {
+        None
+    };
+    #[cfg(not(feature = "encryption"))]
+    let recovery_phrase = None;
 
     // Seed sample data if requested
     if data.seed_sample_data {
@@ -460,6 +677,355 @@ pub async fn complete_onboarding(
     std::fs::write(profile_dir.join("onboarding_done"), "1")
         .str_err()?;
 
+    Ok(OnboardingResult { recovery_phrase })
+}
+
+// ---------------------------------------------------------------------------
+// Security key (FIDO2 hmac-secret) second factor
+// ---------------------------------------------------------------------------
+
+/// Ask the connected security key for the enrolled credential's output.
+/// Blocks on the user's touch, so it runs off the async runtime.
+#[cfg(feature = "encryption")]
+pub(crate) async fn read_security_key(
+    factor: &sovereign_crypto::auth::SecondFactor,
+) -> Result<sovereign_crypto::auth::TokenSecret, String> {
+    use sovereign_crypto::auth::HmacSecretAuthenticator;
+
+    let rp_id = factor.rp_id.clone();
+    let credential_id = factor.credential_id.clone();
+    let salt = factor.hmac_salt;
+    tokio::task::spawn_blocking(move || {
+        crate::fido2::Fido2Token::open(None)?.hmac_secret(&rp_id, &credential_id, &salt)
+    })
+    .await
+    .str_err()?
+    .map_err(|e| format!("Security key: {e}"))
+}
+
+/// List connected FIDO2 security keys (onboarding device picker).
+#[tauri::command]
+pub async fn list_security_keys() -> Result<Vec<SecurityKeyDto>, String> {
+    #[cfg(feature = "encryption")]
+    {
+        let devices = tokio::task::spawn_blocking(crate::fido2::list_devices)
+            .await
+            .str_err()?
+            .str_err()?;
+        Ok(devices
+            .into_iter()
+            .map(|d| SecurityKeyDto {
+                path: d.path,
+                name: d.name,
+            })
+            .collect())
+    }
+    #[cfg(not(feature = "encryption"))]
+    {
+        Ok(Vec::new())
+    }
+}
+
+/// Create the unlock credential on a security key during onboarding. The
+/// enrollment is held until `complete_onboarding` binds it to the new
+/// auth store (`use_security_key`).
+#[tauri::command]
+pub async fn enroll_security_key(
+    state: State<'_, AppState>,
+    device: Option<String>,
+) -> Result<(), String> {
+    // Bootstrap command: only meaningful before an auth store exists.
+    if state.profile_dir.join("crypto").join("auth.store").exists() {
+        return Err("Already onboarded — log in instead.".to_string());
+    }
+    #[cfg(feature = "encryption")]
+    {
+        let device_id = uuid::Uuid::new_v4().to_string();
+        let enrollment = tokio::task::spawn_blocking(move || {
+            let token = crate::fido2::Fido2Token::open(device.as_deref())?;
+            sovereign_crypto::auth::TokenEnrollment::create(
+                &token,
+                sovereign_crypto::auth::SECOND_FACTOR_RP_ID,
+                &device_id,
+            )
+        })
+        .await
+        .str_err()?
+        .map_err(|e| format!("Security key: {e}"))?;
+        *state.pending_security_key.lock().await = Some(enrollment);
+        Ok(())
+    }
+    #[cfg(not(feature = "encryption"))]
+    {
+        let _ = device;
+        Err("security keys require a build with the encryption feature".to_string())
+    }
+}
+
+/// Lost-token recovery: ask the backup guardians for their shares of the
+/// security-key recovery key. Once `threshold` guardians have released
+/// theirs (each enforces the usual approval + delay), the token's output is
+/// reconstructed, the password is checked, and the store goes back to
+/// password-only unlock. Until then this reports progress and can be
+/// called again.
+#[tauri::command]
+pub async fn recover_security_key(
+    state: State<'_, AppState>,
+    password: String,
+    guardian_addrs: Vec<String>,
+) -> Result<SecurityKeyRecoveryDto, String> {
+    #[cfg(all(feature = "encryption", feature = "p2p"))]
+    {
+        let crypto_dir = state.profile_dir.join("crypto");
+        let auth_path = crypto_dir.join("auth.store");
+        let mut store = sovereign_crypto::auth::AuthStore::load(&auth_path).str_err()?;
+        let Some(factor) = store.second_factor.as_ref() else {
+            return Err("No security key is enrolled".to_string());
+        };
+        let manifest = std::fs::read_to_string(crypto_dir.join("backup_manifest.json"))
+            .ok()
+            .and_then(|json| sovereign_p2p::backup::BackupManifest::from_json(&json).ok())
+            .ok_or_else(|| {
+                "No backup on this device — the recovery key is only held by backup guardians"
+                    .to_string()
+            })?;
+
+        let request_id = format!("sk-{}-{}", manifest.snapshot_id, manifest.epoch);
+        let mut payloads = Vec::new();
+        for addr in &guardian_addrs {
+            match sovereign_p2p::backup_client::request_guardian_shard(
+                addr,
+                &request_id,
+                &manifest.owner_tag,
+                manifest.epoch,
+                std::time::Duration::from_secs(20),
+            )
+            .await
+            {
+                Ok(Some(payload)) => payloads.push(payload),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("security-key recovery: guardian {addr}: {e}"),
+            }
+        }
+        let approvals = payloads.len() as u32;
+        let threshold = u32::from(manifest.key_threshold);
+        if approvals < threshold {
+            return Ok(SecurityKeyRecoveryDto {
+                recovered: false,
+                approvals,
+                threshold,
+                pending: guardian_addrs.len() as u32 - approvals,
+            });
+        }
+
+        let key = sovereign_p2p::backup_client::reconstruct_second_factor_key(
+            &payloads,
+            manifest.key_threshold,
+        )
+        .str_err()?
+        .ok_or_else(|| {
+            "The latest backup predates the security key — it holds no recovery shares"
+                .to_string()
+        })?;
+        let secret = factor
+            .recover_secret(&sovereign_crypto::auth::SecondFactorRecoveryKey::from_bytes(key))
+            .str_err()?;
+        store
+            .remove_second_factor(password.as_bytes(), &secret)
+            .map_err(|_| "Invalid password".to_string())?;
+        store.save(&auth_path).str_err()?;
+        let _ = std::fs::remove_file(crypto_dir.join(SECOND_FACTOR_RECOVERY_FILE));
+        tracing::info!("security key removed after guardian recovery");
+
+        Ok(SecurityKeyRecoveryDto {
+            recovered: true,
+            approvals,
+            threshold,
+            pending: 0,
+        })
+    }
+    #[cfg(not(all(feature = "encryption", feature = "p2p")))]
+    {
+        let _ = (&state, &password, &guardian_addrs);
+        Err("security-key recovery requires a build with the p2p feature".to_string())
+    }
+}
+
+/// Rotate the unlocked persona's KEK, e.g. after a suspected compromise.
+/// Every document key is re-wrapped under a fresh KEK and the new KEK is
+/// committed to the auth store; the password is unchanged. Emits
+/// `key-rotation-progress` when done.
+#[tauri::command]
+pub async fn rotate_kek(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    password: String,
+) -> Result<u64, String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "encryption")]
+    {
+        use tauri::Emitter;
+        let crypto_dir = state.profile_dir.join("crypto");
+        let store = sovereign_crypto::auth::AuthStore::load(&crypto_dir.join("auth.store"))
+            .str_err()?;
+        let token_secret = match &store.second_factor {
+            Some(factor) => Some(read_security_key(factor).await?),
+            None => None,
+        };
+        let auth = match &token_secret {
+            Some(secret) => store.authenticate_with_second_factor(password.as_bytes(), secret),
+            None => store.authenticate(password.as_bytes()),
+        }
+        .map_err(|_| "Invalid password".to_string())?;
+        let db = state
+            .encrypted_db
+            .read()
+            .await
+            .clone()
+            .ok_or("Encryption is not active for this session")?;
+
+        let rotation = crate::key_rotation::KekRotation {
+            crypto_dir: &crypto_dir,
+            persona: match auth.persona {
+                sovereign_crypto::auth::PersonaKind::Primary => {
+                    sovereign_core::auth::PersonaKind::Primary
+                }
+                sovereign_crypto::auth::PersonaKind::Duress => {
+                    sovereign_core::auth::PersonaKind::Duress
+                }
+            },
+            account_key: &auth.account_key,
+            device_key: &auth.device_key,
+            old_kek: &auth.kek,
+            password: password.as_bytes(),
+            second_factor: token_secret.as_ref(),
+        };
+        let keys = rotation.run(&db).await?;
+        tracing::info!("KEK rotated ({keys} document keys re-wrapped)");
+        let _ = app.emit(
+            "key-rotation-progress",
+            sovereign_db::encrypted::RotationProgress {
+                stage: "kek",
+                done: 1,
+                total: 1,
+            },
+        );
+        Ok(keys)
+    }
+    #[cfg(not(feature = "encryption"))]
+    {
+        let _ = (&app, &password);
+        Err("key rotation requires the encryption feature".to_string())
+    }
+}
+
+/// Give every entity in `scope` a new document key and re-encrypt its
+/// fields, one entity at a time, emitting `key-rotation-progress` after
+/// each. `scope` is `all` or an entity type (`documents`, `messages`, …);
+/// `document_id` narrows it to one document. Returns the number of
+/// entities re-keyed.
+#[tauri::command]
+pub async fn rotate_document_keys(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    scope: String,
+    document_id: Option<String>,
+) -> Result<u64, String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "encryption")]
+    {
+        use sovereign_crypto::rotation::{RotationKind, RotationRecord};
+        use sovereign_db::encrypted::RotationScope;
+        use tauri::Emitter;
+        let scope = match document_id {
+            Some(id) => RotationScope::Document(id),
+            None => serde_json::from_value::<RotationScope>(serde_json::Value::String(scope))
+                .map_err(|_| "Unknown rotation scope".to_string())?,
+        };
+        let db = state
+            .encrypted_db
+            .read()
+            .await
+            .clone()
+            .ok_or("Encryption is not active for this session")?;
+        let rotated = db
+            .rotate_document_keys(&scope, &|progress| {
+                let _ = app.emit("key-rotation-progress", progress);
+            })
+            .await
+            .str_err()?;
+
+        let record = RotationRecord::now(
+            RotationKind::DocumentKeys {
+                scope: scope.label(),
+            },
+            rotated,
+        );
+        match crate::key_rotation::session_audit_log(&state).await {
+            Some(log) => {
+                if let Err(e) = log.record(&record) {
+                    tracing::warn!("key rotation audit log write failed: {e}");
+                }
+            }
+            None => tracing::warn!("key rotation not logged: session locked"),
+        }
+        tracing::info!("Rotated document keys for {rotated} entities ({})", scope.label());
+        Ok(rotated)
+    }
+    #[cfg(not(feature = "encryption"))]
+    {
+        let _ = (&app, &scope, &document_id);
+        Err("key rotation requires the encryption feature".to_string())
+    }
+}
+
+/// Past key rotations, newest first.
+#[tauri::command]
+pub async fn get_key_rotation_log(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<Vec<KeyRotationEntryDto>, String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "encryption")]
+    {
+        use sovereign_crypto::rotation::RotationKind;
+        let Some(log) = crate::key_rotation::session_audit_log(&state).await else {
+            return Ok(Vec::new());
+        };
+        Ok(log
+            .entries()
+            .into_iter()
+            .rev()
+            .map(|r| KeyRotationEntryDto {
+                at: r.at.to_rfc3339(),
+                scope: match r.kind {
+                    RotationKind::Kek => "kek".to_string(),
+                    RotationKind::DocumentKeys { scope } => scope,
+                },
+                keys: r.keys,
+                resumed: r.resumed,
+            })
+            .collect())
+    }
+    #[cfg(not(feature = "encryption"))]
+    Ok(Vec::new())
+}
+
+/// Lock the session (Ctrl+L, the idle timer, or the "lock" intent): the
+/// in-memory keys are dropped and the password is needed again. Emits
+/// `session-locked` so every window shows the login screen.
+#[tauri::command]
+pub async fn lock_session(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    use tauri::Emitter;
+    crate::tauri_state::require_main_webview(&webview)?;
+    state.lock().await;
+    let _ = app.emit("session-locked", ());
     Ok(())
 }
 
@@ -477,6 +1043,10 @@ pub async fn get_profile(state: State<'_, AppState>) -> Result<UserProfileDto, S
             .trim_matches('"')
             .to_string(),
         display_name: profile.display_name,
+        editor_keymap: serde_json::to_string(&profile.editor_keymap)
+            .unwrap_or_else(|_| "\"standard\"".into())
+            .trim_matches('"')
+            .to_string(),
     })
 }
 
@@ -498,16 +1068,56 @@ pub async fn save_profile(
     if let Some(ref name) = data.display_name {
         profile.display_name = Some(name.clone());
     }
+    if let Some(ref keymap) = data.editor_keymap {
+        profile.editor_keymap = serde_json::from_str(&format!("\"{keymap}\""))
+            .map_err(|_| format!("unknown editor keymap: {keymap}"))?;
+    }
     profile
         .save(&state.profile_dir)
         .str_err()?;
     Ok(())
 }
 
+/// The user profiles on this machine, for the login screen's chooser.
+#[tauri::command]
+pub async fn list_profiles() -> Result<ProfilesDto, String> {
+    Ok(ProfilesDto {
+        active: sovereign_core::profiles::active(),
+        profiles: sovereign_core::profiles::Profiles::on_this_machine().list(),
+    })
+}
+
+/// Restart as another user profile. With `create`, `name` is a new
+/// profile to make first; the restarted app onboards it. Each profile has
+/// its own keys and database, so this process can't simply reopen: it
+/// shuts down and a fresh one starts as `name`.
+#[tauri::command]
+pub async fn switch_profile(
+    webview: tauri::Webview,
+    app: tauri::AppHandle,
+    name: String,
+    create: bool,
+) -> Result<(), String> {
+    use sovereign_core::profiles::{self, Profiles};
+
+    crate::tauri_state::require_main_webview(&webview)?;
+    let profiles = Profiles::on_this_machine();
+    if create {
+        profiles.create(&name).str_err()?;
+    } else if !profiles.exists(&name) {
+        return Err(format!("no profile named '{name}'"));
+    }
+    if name == profiles::active() {
+        return Ok(());
+    }
+    profiles.set_last_used(&name).str_err()?;
+    crate::relaunch_as_profile(&app, name)
+}
+
 /// Get the flattened application configuration.
 #[tauri::command]
 pub async fn get_config(state: State<'_, AppState>) -> Result<AppConfigDto, String> {
-    let config = &state.config;
+    let config = state.current_config();
     Ok(AppConfigDto {
         ai_model_dir: config.ai.model_dir.clone(),
         ai_router_model: config.ai.router_model.clone(),
@@ -519,6 +1129,134 @@ pub async fn get_config(state: State<'_, AppState>) -> Result<AppConfigDto, Stri
         crypto_keystroke_enabled: config.crypto.keystroke_enabled,
         crypto_max_login_attempts: config.crypto.max_login_attempts,
         crypto_lockout_seconds: config.crypto.lockout_seconds,
+        crypto_auto_lock_minutes: config.crypto.auto_lock_minutes,
         ui_theme: config.ui.theme.clone(),
+        ui_scale: config.ui.scale,
+        ui_editor_font_size: config.ui.editor_font_size,
+        ui_editor_font_family: config.ui.editor_font_family.clone(),
+        ui_shortcuts: config.ui.shortcuts.clone(),
+        voice_enabled: config.voice.enabled,
+        voice_source: config.voice.voice_source.clone(),
+        voice_jiminy_ws_url: config.voice.jiminy_ws_url.clone(),
+        voice_announce_reminders: config.voice.announce_reminders,
+        comms_enabled: config.comms.enabled,
+        comms_poll_interval_secs: config.comms.poll_interval_secs,
+        p2p_enabled: config.p2p.enabled,
+        p2p_device_name: config.p2p.device_name.clone(),
+        p2p_enable_mdns: config.p2p.enable_mdns,
+        p2p_wifi_only: config.p2p.wifi_only,
+        p2p_serve_viewer: config.p2p.serve_viewer,
+        skills_max_concurrent_jobs: config.skills.max_concurrent_jobs,
+        autonomy_auto_approve_after: config.autonomy.auto_approve_after,
+        metrics_enabled: config.metrics.enabled,
+        metrics_retention_days: config.metrics.retention_days,
+        relevance_enabled: config.relevance.enabled,
+        relevance_half_life_days: config.relevance.half_life_days,
+        config_path: sovereign_core::config::AppConfig::user_config_path()
+            .display()
+            .to_string(),
     })
 }
+
+/// Validate, save and publish a configuration change from Settings.
+///
+/// The new config is written to the user config file and handed to
+/// `config_reload`, which hot-applies what it can. The report lists the
+/// sections that still need a restart.
+#[tauri::command]
+pub async fn update_config(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    update: ConfigUpdateDto,
+) -> Result<ConfigApplyReportDto, String> {
+    state.require_unlocked(&webview).await?;
+    let mut next = state.current_config();
+    update.apply_to(&mut next);
+    apply_config(&state, next)
+}
+
+/// Validate, save and hot-apply `next`.
+fn apply_config(
+    state: &AppState,
+    next: sovereign_core::config::AppConfig,
+) -> Result<ConfigApplyReportDto, String> {
+    next.validate().map_err(|errors| errors.join("; "))?;
+    if next == state.current_config() {
+        return Ok(ConfigApplyReportDto { restart_required: Vec::new() });
+    }
+    next.save(&sovereign_core::config::AppConfig::user_config_path())
+        .str_err()?;
+    // Against the config this process started with, so a second save does
+    // not hide a restart the first one still needs.
+    let restart_required = state
+        .config
+        .restart_required(&next)
+        .into_iter()
+        .map(String::from)
+        .collect();
+    state.config_reload.send_replace(next);
+    Ok(ConfigApplyReportDto { restart_required })
+}
+
+/// Every optional subsystem: built in or not, switched on or not, and
+/// whether it is up this session.
+#[tauri::command]
+pub async fn list_features(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<Vec<FeatureDto>, String> {
+    use crate::features::Feature;
+
+    state.require_unlocked(&webview).await?;
+    let config = state.current_config();
+    let mut features = Vec::with_capacity(Feature::ALL.len());
+    for feature in Feature::ALL {
+        let enabled = feature.enabled(&config);
+        let healthy = if enabled { running(&state, feature).await } else { None };
+        features.push(FeatureDto {
+            id: feature.id().to_string(),
+            label: feature.label().to_string(),
+            compiled: feature.compiled(),
+            enabled,
+            toggleable: feature.toggleable(),
+            healthy,
+        });
+    }
+    Ok(features)
+}
+
+/// Whether `feature`'s runtime half is up, for those that have one to check.
+async fn running(state: &AppState, feature: crate::features::Feature) -> Option<bool> {
+    #[allow(unused_imports)]
+    use crate::features::Feature;
+
+    match feature {
+        #[cfg(feature = "encryption")]
+        Feature::Encryption => Some(state.encrypted_db.read().await.is_some()),
+        #[cfg(feature = "p2p")]
+        Feature::Sync => Some(state.p2p_command_tx().await.is_some()),
+        #[cfg(feature = "comms")]
+        Feature::Comms => Some(state.comms.read().await.is_some()),
+        _ => {
+            let _ = state;
+            None
+        }
+    }
+}
+
+/// Switch an optional subsystem on or off from Settings. Saved like any
+/// other config change; the report says whether it needs a restart.
+#[tauri::command]
+pub async fn set_feature_enabled(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    id: String,
+    enabled: bool,
+) -> Result<ConfigApplyReportDto, String> {
+    state.require_unlocked(&webview).await?;
+    let feature = crate::features::Feature::from_id(&id)
+        .ok_or_else(|| format!("unknown feature: {id}"))?;
+    let mut next = state.current_config();
+    feature.set_enabled(&mut next, enabled)?;
+    apply_config(&state, next)
+}
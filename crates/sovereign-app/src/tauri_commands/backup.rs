@@ -3,19 +3,23 @@
 //! `backup_now` runs P4.1 + P4.2 in one shot for the current paired
 //! fleet: snapshot → seal under a fresh key → Shamir-split the key →
 //! distribute fragments round-robin across paired peers and one key
-//! shard per paired peer (paired devices act as both hosts and
-//! guardians until a dedicated guardian-enrollment UX exists). The
-//! manifest is persisted locally; placement acks arrive async as
-//! `sync-status` events ("backup placed ...").
+//! shard per guardian (paired devices act as both hosts and guardians;
+//! the guardian policy, chosen in the Backup section of the device
+//! settings, picks how many of them get a shard and how many must approve
+//! a recovery). The manifest is persisted locally; placement acks arrive
+//! async as `sync-status` events ("backup placed ..."). `refresh_shards`
+//! rotates the shards by running the same flow for a new epoch.
 //!
 //! `approve_shard_release` / `deny_shard_release` are the guardian-side
 //! controls for incoming recovery requests (the 72h delay is enforced
 //! in the host store regardless). `backup_status` powers the Settings
 //! display: last manifest + what we host for others + pending releases.
 //!
-//! The recovery (restore) flow runs pre-onboarding like pairing and
+//! Recovery onto a blank device runs pre-onboarding like pairing and
 //! gets its own UX pass; the network + assembly primitives live in
-//! `sovereign_p2p::backup_client`.
+//! `sovereign_p2p::backup_client`. `restore_from_guardians` drives them
+//! for an unlocked profile whose data was lost (`sovereign
+//! initiate-recovery` through the daemon).
 
 use serde::Serialize;
 use tauri::State;
@@ -30,140 +34,271 @@ pub struct BackupNowResult {
     pub fragment_count: u8,
     pub hosts: u32,
     pub guardians: u32,
+    pub threshold: u8,
 }
 
 /// Snapshot + seal + split + distribute to the paired fleet.
+///
+/// `threshold` / `shares` set the guardian policy (any `threshold` of
+/// `shares` guardians reconstruct the backup key). Once the shares are on
+/// their way they are persisted and reused by later backups and
+/// [`refresh_shards`]; without them the saved policy applies, or a
+/// majority of the paired devices.
 #[tauri::command]
 pub async fn backup_now(
     webview: tauri::Webview,
     state: State<'_, AppState>,
+    threshold: Option<u8>,
+    shares: Option<u8>,
 ) -> Result<BackupNowResult, String> {
     state.require_unlocked(&webview).await?;
     #[cfg(feature = "p2p")]
     {
-        use base64::Engine;
+        let requested = requested_policy(threshold, shares)?;
+        return distribute_backup(&state, requested).await;
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = (&state, threshold, shares);
+        Err("backup requires a build with the p2p feature".to_string())
+    }
+}
 
-        let account_key = state
-            .account_key()
-            .await
-            .ok_or_else(|| "backup unavailable: account key not loaded".to_string())?;
-        let cmd_tx = state
-            .p2p_command_tx()
-            .await
-            .ok_or_else(|| "backup requires the P2P node — enable sync first".to_string())?;
-
-        // Paired devices act as hosts AND guardians (one key shard each).
-        let peer_ids: Vec<String> = {
-            let guard = state.pairing_manager.read().await;
-            match guard.as_ref() {
-                Some(m) => m.list_devices().iter().map(|d| d.peer_id.clone()).collect(),
-                None => Vec::new(),
-            }
-        };
-        if peer_ids.len() < 2 {
-            return Err(
-                "backup needs at least 2 paired devices (they hold the key shards — \
-                 a 2-of-2 split is the minimum)"
-                    .to_string(),
-            );
+/// Rotate the guardians' shards: a new backup generation under a fresh
+/// key, split with the saved policy across the currently paired devices.
+/// The backup key is never kept, so its existing shares cannot be
+/// re-randomised in place; instead hosts and guardians replace the old
+/// epoch, and any shard a revoked guardian kept only opens a snapshot
+/// nobody hosts anymore.
+#[tauri::command]
+pub async fn refresh_shards(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<BackupNowResult, String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        if !crate::setup::crypto_dir().join(MANIFEST_FILE).exists() {
+            return Err("no backup yet — run a backup first".to_string());
         }
-        // Majority threshold, at least 2: 2 devices → 2-of-2, 3 → 2-of-3,
-        // 5 → 3-of-5.
-        let threshold = ((peer_ids.len() / 2 + 1).max(2)).min(peer_ids.len()) as u8;
-
-        let device_id =
-            crate::setup::load_or_create_device_id().map_err(|e| format!("device id: {e}"))?;
-        let owner_tag = account_key.derive_backup_tag();
-        let salt = std::fs::read(crate::setup::crypto_dir().join("salt"))
-            .map_err(|e| format!("read salt: {e}"))?;
-
-        // Epoch: previous manifest + 1 (hosts keep only the newest).
-        let manifest_path = crate::setup::crypto_dir().join("backup_manifest.json");
-        let epoch = std::fs::read_to_string(&manifest_path)
-            .ok()
-            .and_then(|json| sovereign_p2p::backup::BackupManifest::from_json(&json).ok())
-            .map(|m| m.epoch + 1)
-            .unwrap_or(1);
-
-        let db: std::sync::Arc<dyn sovereign_db::GraphDB> = state.db.clone();
-        let prepared = sovereign_p2p::backup::prepare_backup(
-            db.as_ref(),
-            &device_id,
-            &owner_tag,
-            &salt,
-            epoch,
-            &peer_ids,
-            threshold,
-            sovereign_p2p::backup::DEFAULT_DATA_FRAGMENTS,
-            sovereign_p2p::backup::DEFAULT_PARITY_FRAGMENTS,
-        )
+        return distribute_backup(&state, None).await;
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = &state;
+        Err("backup requires a build with the p2p feature".to_string())
+    }
+}
+
+#[cfg(feature = "p2p")]
+const MANIFEST_FILE: &str = "backup_manifest.json";
+
+#[cfg(feature = "p2p")]
+fn guardian_policy_path() -> std::path::PathBuf {
+    crate::setup::crypto_dir().join("guardian_policy.json")
+}
+
+/// The guardian policy asked for with a backup. Both halves or neither:
+/// without them the saved policy stands.
+#[cfg(feature = "p2p")]
+pub(crate) fn requested_policy(
+    threshold: Option<u8>,
+    shares: Option<u8>,
+) -> Result<Option<sovereign_crypto::guardian::shamir::GuardianPolicy>, String> {
+    match (threshold, shares) {
+        (Some(threshold), Some(shares)) => {
+            sovereign_crypto::guardian::shamir::GuardianPolicy::new(threshold, shares)
+                .map(Some)
+                .str_err()
+        }
+        (None, None) => Ok(None),
+        _ => Err("set both the threshold and the share count".to_string()),
+    }
+}
+
+/// The saved guardian policy, if one was ever chosen.
+#[cfg(feature = "p2p")]
+fn saved_policy() -> Option<sovereign_crypto::guardian::shamir::GuardianPolicy> {
+    sovereign_crypto::guardian::shamir::GuardianPolicy::load(&guardian_policy_path())
+        .ok()
+        .flatten()
+}
+
+/// Whether the last backup gave `peer_id` a key shard — forgetting such a
+/// device calls for a [`refresh_shards`].
+#[cfg(feature = "p2p")]
+pub(crate) fn holds_key_shard(peer_id: &str) -> bool {
+    key_shard_holders().iter().any(|gid| gid == peer_id)
+}
+
+/// Guardians given a key shard by the last backup. They are also the
+/// devices allowed to co-sign a remote wipe of this one.
+#[cfg(feature = "p2p")]
+pub(crate) fn key_shard_holders() -> Vec<String> {
+    std::fs::read_to_string(crate::setup::crypto_dir().join(MANIFEST_FILE))
+        .ok()
+        .and_then(|json| sovereign_p2p::backup::BackupManifest::from_json(&json).ok())
+        .map(|m| m.guardian_shards.into_iter().map(|(gid, _)| gid).collect())
+        .unwrap_or_default()
+}
+
+/// Build the next backup generation and queue its fragments and key
+/// shards on the P2P node. `requested` replaces the saved guardian policy,
+/// and is saved only once every shard has been handed to the node.
+#[cfg(feature = "p2p")]
+pub(crate) async fn distribute_backup(
+    state: &AppState,
+    requested: Option<sovereign_crypto::guardian::shamir::GuardianPolicy>,
+) -> Result<BackupNowResult, String> {
+    use base64::Engine;
+    use sovereign_crypto::guardian::shamir::GuardianPolicy;
+
+    let account_key = state
+        .account_key()
         .await
-        .str_err()?;
+        .ok_or_else(|| "backup unavailable: account key not loaded".to_string())?;
+    let cmd_tx = state
+        .p2p_command_tx()
+        .await
+        .ok_or_else(|| "backup requires the P2P node — enable sync first".to_string())?;
 
-        // Persist the manifest before anything leaves this device.
-        sovereign_crypto::fs_private::write_private(
-            &manifest_path,
-            prepared.manifest.to_json().str_err()?,
-        )
-        .map_err(|e| format!("persist manifest: {e}"))?;
-
-        // Round-robin the fragments across the paired hosts and deliver
-        // one key shard per peer.
-        let manifest_json = prepared.manifest.to_json().str_err()?;
-        let salt_b64 = base64::engine::general_purpose::STANDARD.encode(&salt);
-        let fragment_count = prepared.fragments.len() as u8;
-        for (host_idx, peer_id) in peer_ids.iter().enumerate() {
-            let requests: Vec<sovereign_p2p::protocol::SovereignRequest> = prepared
-                .fragments
-                .iter()
-                .filter(|f| (f.index as usize) % peer_ids.len() == host_idx)
-                .map(|f| sovereign_p2p::protocol::SovereignRequest::StoreBackupFragment {
-                    owner_tag: owner_tag.clone(),
-                    snapshot_id: prepared.manifest.snapshot_id.clone(),
-                    epoch,
-                    manifest_json: manifest_json.clone(),
-                    salt_b64: salt_b64.clone(),
-                    index: f.index,
-                    fragment_b64: f.data_b64.clone(),
-                    digest: f.digest.clone(),
-                })
-                .collect();
-            if !requests.is_empty() {
-                cmd_tx
-                    .send(sovereign_p2p::P2pCommand::PlaceBackup {
-                        peer_id: peer_id.clone(),
-                        requests,
-                    })
-                    .await
-                    .map_err(|e| format!("queue placement: {e}"))?;
-            }
-            // One backup-key shard per paired peer (guardian role).
-            let (gid, payload_b64) = &prepared.guardian_payloads[host_idx];
+    // Paired devices act as hosts AND guardians (one key shard each, up
+    // to the policy's share count).
+    let peer_ids: Vec<String> = {
+        let guard = state.pairing_manager.read().await;
+        match guard.as_ref() {
+            Some(m) => m.list_devices().iter().map(|d| d.peer_id.clone()).collect(),
+            None => Vec::new(),
+        }
+    };
+    if peer_ids.len() < 2 {
+        return Err(
+            "backup needs at least 2 paired devices (they hold the key shards — \
+             a 2-of-2 split is the minimum)"
+                .to_string(),
+        );
+    }
+    let chosen = match requested {
+        Some(policy) => Some(policy),
+        None => GuardianPolicy::load(&guardian_policy_path()).str_err()?,
+    };
+    let policy = chosen
+        .unwrap_or_else(|| GuardianPolicy::majority(peer_ids.len()))
+        .fit(peer_ids.len())
+        .map_err(|_| {
+            "fewer paired devices than the guardian threshold — pair another device \
+             or choose a lower threshold"
+                .to_string()
+        })?;
+    let guardian_ids = &peer_ids[..policy.shares as usize];
+
+    let device_id =
+        crate::setup::load_or_create_device_id().map_err(|e| format!("device id: {e}"))?;
+    let owner_tag = account_key.derive_backup_tag();
+    let salt = std::fs::read(crate::setup::crypto_dir().join("salt"))
+        .map_err(|e| format!("read salt: {e}"))?;
+
+    // Epoch: previous manifest + 1 (hosts and guardians keep only the
+    // newest).
+    let manifest_path = crate::setup::crypto_dir().join(MANIFEST_FILE);
+    let epoch = std::fs::read_to_string(&manifest_path)
+        .ok()
+        .and_then(|json| sovereign_p2p::backup::BackupManifest::from_json(&json).ok())
+        .map(|m| m.epoch + 1)
+        .unwrap_or(1);
+
+    let db: std::sync::Arc<dyn sovereign_db::GraphDB> = state.db.clone();
+    let mut prepared = sovereign_p2p::backup::prepare_backup(
+        db.as_ref(),
+        &device_id,
+        &owner_tag,
+        &salt,
+        epoch,
+        guardian_ids,
+        policy.threshold,
+        sovereign_p2p::backup::DEFAULT_DATA_FRAGMENTS,
+        sovereign_p2p::backup::DEFAULT_PARITY_FRAGMENTS,
+    )
+    .await
+    .str_err()?;
+
+    // With a security key enrolled, the guardians also get shares of its
+    // recovery key (lost-token unlock, see `recover_security_key`).
+    if let Some(recovery_key) = state.second_factor_recovery.read().await.clone() {
+        prepared
+            .attach_second_factor_recovery(recovery_key.as_bytes())
+            .str_err()?;
+    }
+
+    // Persist the manifest before anything leaves this device.
+    sovereign_crypto::fs_private::write_private(
+        &manifest_path,
+        prepared.manifest.to_json().str_err()?,
+    )
+    .map_err(|e| format!("persist manifest: {e}"))?;
+
+    // Round-robin the fragments across the paired hosts.
+    let manifest_json = prepared.manifest.to_json().str_err()?;
+    let salt_b64 = base64::engine::general_purpose::STANDARD.encode(&salt);
+    let fragment_count = prepared.fragments.len() as u8;
+    for (host_idx, peer_id) in peer_ids.iter().enumerate() {
+        let requests: Vec<sovereign_p2p::protocol::SovereignRequest> = prepared
+            .fragments
+            .iter()
+            .filter(|f| (f.index as usize) % peer_ids.len() == host_idx)
+            .map(|f| sovereign_p2p::protocol::SovereignRequest::StoreBackupFragment {
+                owner_tag: owner_tag.clone(),
+                snapshot_id: prepared.manifest.snapshot_id.clone(),
+                epoch,
+                manifest_json: manifest_json.clone(),
+                salt_b64: salt_b64.clone(),
+                index: f.index,
+                fragment_b64: f.data_b64.clone(),
+                digest: f.digest.clone(),
+            })
+            .collect();
+        if !requests.is_empty() {
             cmd_tx
-                .send(sovereign_p2p::P2pCommand::DistributeShard {
+                .send(sovereign_p2p::P2pCommand::PlaceBackup {
                     peer_id: peer_id.clone(),
-                    shard_data: payload_b64.clone(),
-                    shard_id: format!("{}-{gid}", prepared.manifest.snapshot_id),
-                    for_user: owner_tag.clone(),
-                    epoch,
+                    requests,
                 })
                 .await
-                .map_err(|e| format!("queue shard delivery: {e}"))?;
+                .map_err(|e| format!("queue placement: {e}"))?;
         }
-
-        return Ok(BackupNowResult {
-            snapshot_id: prepared.manifest.snapshot_id.clone(),
-            epoch,
-            fragment_count,
-            hosts: peer_ids.len() as u32,
-            guardians: peer_ids.len() as u32,
-        });
     }
-    #[allow(unreachable_code)]
-    {
-        let _ = &state;
-        Err("backup requires a build with the p2p feature".to_string())
+    // One backup-key shard per guardian.
+    for (gid, payload_b64) in &prepared.guardian_payloads {
+        cmd_tx
+            .send(sovereign_p2p::P2pCommand::DistributeShard {
+                peer_id: gid.clone(),
+                shard_data: payload_b64.clone(),
+                shard_id: format!("{}-{gid}", prepared.manifest.snapshot_id),
+                for_user: owner_tag.clone(),
+                epoch,
+            })
+            .await
+            .map_err(|e| format!("queue shard delivery: {e}"))?;
+    }
+    if let Some(policy) = requested {
+        policy.save(&guardian_policy_path()).str_err()?;
     }
+    // The new shard holders are the devices allowed to co-sign a remote
+    // wipe of this one.
+    let _ = cmd_tx
+        .send(sovereign_p2p::P2pCommand::UpdateWipeGuardians {
+            peer_ids: guardian_ids.to_vec(),
+        })
+        .await;
+
+    Ok(BackupNowResult {
+        snapshot_id: prepared.manifest.snapshot_id.clone(),
+        epoch,
+        fragment_count,
+        hosts: peer_ids.len() as u32,
+        guardians: guardian_ids.len() as u32,
+        threshold: policy.threshold,
+    })
 }
 
 #[derive(Serialize)]
@@ -191,6 +326,10 @@ pub struct BackupStatusDto {
     pub hosting_enabled: bool,
     pub hosting: Vec<HostedForOtherDto>,
     pub pending_releases: Vec<PendingReleaseDto>,
+    /// The saved guardian policy: any `threshold` of `shares` guardians
+    /// reconstruct the backup key. `None` until one is chosen.
+    pub threshold: Option<u8>,
+    pub shares: Option<u8>,
 }
 
 /// Settings display: last backup + reciprocity accounting + pending
@@ -204,7 +343,7 @@ pub async fn backup_status(
     #[cfg(feature = "p2p")]
     {
         let last_manifest_json =
-            std::fs::read_to_string(crate::setup::crypto_dir().join("backup_manifest.json")).ok();
+            std::fs::read_to_string(crate::setup::crypto_dir().join(MANIFEST_FILE)).ok();
         let host = state.backup_host.read().await.clone();
         let (hosting, pending_releases) = match host.as_ref() {
             Some(h) => (
@@ -218,23 +357,18 @@ pub async fn backup_status(
                         total_bytes: a.total_bytes,
                     })
                     .collect(),
-                h.pending_release_requests()
-                    .into_iter()
-                    .map(|s| PendingReleaseDto {
-                        for_user: s.for_user,
-                        epoch: s.epoch,
-                        requested_at: s.release_requested_at,
-                        request_id: s.release_request_id,
-                    })
-                    .collect(),
+                pending_releases(h),
             ),
             None => (Vec::new(), Vec::new()),
         };
+        let policy = saved_policy();
         return Ok(BackupStatusDto {
             last_manifest_json,
             hosting_enabled: host.is_some(),
             hosting,
             pending_releases,
+            threshold: policy.map(|p| p.threshold),
+            shares: policy.map(|p| p.shares),
         });
     }
     #[allow(unreachable_code)]
@@ -245,10 +379,26 @@ pub async fn backup_status(
             hosting_enabled: false,
             hosting: Vec::new(),
             pending_releases: Vec::new(),
+            threshold: None,
+            shares: None,
         })
     }
 }
 
+/// Guardian-side: recovery requests waiting for this device's approval.
+#[cfg(feature = "p2p")]
+pub(crate) fn pending_releases(host: &sovereign_p2p::BackupHost) -> Vec<PendingReleaseDto> {
+    host.pending_release_requests()
+        .into_iter()
+        .map(|s| PendingReleaseDto {
+            for_user: s.for_user,
+            epoch: s.epoch,
+            requested_at: s.release_requested_at,
+            request_id: s.release_request_id,
+        })
+        .collect()
+}
+
 /// Guardian-side: approve releasing the key shard we hold for `for_user`
 /// to a pending recovery. The 72h delay still applies on top.
 #[tauri::command]
@@ -297,3 +447,154 @@ pub async fn deny_shard_release(
         Err("backup requires a build with the p2p feature".to_string())
     }
 }
+
+#[derive(Serialize)]
+pub struct RestoreProgressDto {
+    /// Rows written back from the backup; `None` until enough guardians
+    /// have released their shards.
+    pub restored: Option<u64>,
+    pub approvals: u32,
+    pub threshold: u32,
+}
+
+/// Restore this profile's last backup from the paired fleet: ask the
+/// guardians at `guardian_addrs` for their key shards (each enforces its
+/// approval + delay) and, once the threshold have released theirs, fetch
+/// fragments from `host_addrs`, reassemble and write the snapshot back.
+/// Rows still present are left alone. Until then it reports progress and
+/// can be called again. Addresses are multiaddrs ending in `/p2p/<peer>`;
+/// empty lists mean the manually entered addresses of the guardians /
+/// all paired devices.
+#[cfg(feature = "p2p")]
+pub(crate) async fn restore_from_guardians(
+    state: &AppState,
+    guardian_addrs: Vec<String>,
+    host_addrs: Vec<String>,
+) -> Result<RestoreProgressDto, String> {
+    use sovereign_p2p::backup_client;
+
+    let manifest = std::fs::read_to_string(crate::setup::crypto_dir().join(MANIFEST_FILE))
+        .ok()
+        .and_then(|json| sovereign_p2p::backup::BackupManifest::from_json(&json).ok())
+        .ok_or_else(|| "no backup on this device to restore".to_string())?;
+    let guardian_ids: Vec<String> = manifest
+        .guardian_shards
+        .iter()
+        .map(|(gid, _)| gid.clone())
+        .collect();
+    let guardian_addrs = if guardian_addrs.is_empty() {
+        paired_addresses(state, Some(&guardian_ids)).await
+    } else {
+        guardian_addrs
+    };
+    let host_addrs = if host_addrs.is_empty() {
+        paired_addresses(state, None).await
+    } else {
+        host_addrs
+    };
+    if guardian_addrs.is_empty() {
+        return Err("no guardian addresses known — give them explicitly".to_string());
+    }
+
+    let timeout = std::time::Duration::from_secs(20);
+    let request_id = format!("restore-{}-{}", manifest.snapshot_id, manifest.epoch);
+    let mut payloads = Vec::new();
+    for addr in &guardian_addrs {
+        match backup_client::request_guardian_shard(
+            addr,
+            &request_id,
+            &manifest.owner_tag,
+            manifest.epoch,
+            timeout,
+        )
+        .await
+        {
+            Ok(Some(payload)) => payloads.push(payload),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("backup restore: guardian {addr}: {e}"),
+        }
+    }
+    let approvals = payloads.len() as u32;
+    let threshold = u32::from(manifest.key_threshold);
+    if approvals < threshold {
+        return Ok(RestoreProgressDto {
+            restored: None,
+            approvals,
+            threshold,
+        });
+    }
+
+    let needed = manifest.data_fragments as usize;
+    let mut fragments: Vec<sovereign_p2p::backup::BackupFragment> = Vec::new();
+    for addr in &host_addrs {
+        if fragments.len() >= needed {
+            break;
+        }
+        let hosted =
+            match backup_client::list_backups(addr, Some(manifest.owner_tag.clone()), timeout)
+                .await
+            {
+                Ok(hosted) => hosted,
+                Err(e) => {
+                    tracing::warn!("backup restore: host {addr}: {e}");
+                    continue;
+                }
+            };
+        let indices: Vec<u8> = hosted
+            .iter()
+            .filter(|b| b.snapshot_id == manifest.snapshot_id)
+            .flat_map(|b| b.fragment_indices.iter().copied())
+            .filter(|i| !fragments.iter().any(|f| f.index == *i))
+            .collect();
+        if indices.is_empty() {
+            continue;
+        }
+        match backup_client::fetch_fragments(
+            addr,
+            &manifest.owner_tag,
+            &manifest.snapshot_id,
+            &indices,
+            &manifest,
+            timeout,
+        )
+        .await
+        {
+            Ok(fetched) => fragments.extend(fetched),
+            Err(e) => tracing::warn!("backup restore: host {addr}: {e}"),
+        }
+    }
+    if fragments.len() < needed {
+        return Err(format!(
+            "only {} of the {needed} backup fragments needed could be fetched — \
+             bring more paired devices online",
+            fragments.len()
+        ));
+    }
+
+    let snapshot = backup_client::assemble_snapshot(&manifest, &fragments, &payloads).str_err()?;
+    let db: std::sync::Arc<dyn sovereign_db::GraphDB> = state.db.clone();
+    let written = sovereign_p2p::backup::restore_snapshot(db.as_ref(), &snapshot)
+        .await
+        .str_err()?;
+    tracing::info!("restored {written} rows from backup {}", manifest.snapshot_id);
+    Ok(RestoreProgressDto {
+        restored: Some(written),
+        approvals,
+        threshold,
+    })
+}
+
+/// Manually entered addresses of the paired devices (or of `only` those).
+#[cfg(feature = "p2p")]
+async fn paired_addresses(state: &AppState, only: Option<&[String]>) -> Vec<String> {
+    let guard = state.pairing_manager.read().await;
+    let Some(manager) = guard.as_ref() else {
+        return Vec::new();
+    };
+    manager
+        .list_devices()
+        .into_iter()
+        .filter(|d| only.is_none_or(|ids| ids.contains(&d.peer_id)))
+        .flat_map(|d| d.addresses.clone())
+        .collect()
+}
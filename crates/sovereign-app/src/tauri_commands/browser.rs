@@ -427,6 +427,7 @@ pub async fn save_web_page(
     .to_string();
 
     let mut doc = Document::new(title, tid.clone(), false);
+    doc.doc_type = DocType::WebClip;
     doc.content = content_json;
     doc.source_url = Some(url);
     doc.reliability_classification = classification.clone();
@@ -449,6 +450,7 @@ pub async fn save_web_page(
         title: created.title,
         thread_id: tid,
         is_owned: false,
+        doc_type: created.doc_type,
         spatial_x: created.spatial_x,
         spatial_y: created.spatial_y,
         created_at: created.created_at.to_rfc3339(),
@@ -456,6 +458,10 @@ pub async fn save_web_page(
         reliability_classification: created.reliability_classification,
         reliability_score: created.reliability_score,
         source_url: created.source_url,
+        tags: Vec::new(),
+        open_tasks: 0,
+        favorite: false,
+        sensitivity: Sensitivity::Normal,
     })
 }
 
@@ -467,7 +473,11 @@ pub async fn reassess_reliability(
     doc_id: String,
 ) -> Result<ReliabilityResultDto, String> {
     state.require_unlocked(&webview).await?;
-    let doc = state.db.get_document(&doc_id).await.str_err()?;
+    let doc = crate::sealed::readable(&state, &doc_id).await?;
+    // The assessment is the assistant's; it may only read normal documents.
+    if !doc.ai_may_read() {
+        return Err(format!("'{}' is marked sensitive", doc.title));
+    }
 
     // Parse content JSON to extract body text
     let body = if let Ok(v) = serde_json::from_str::<serde_json::Value>(&doc.content) {
@@ -1,9 +1,13 @@
 use super::*;
+use sovereign_core::window_session::WindowSession;
 
 // ---------------------------------------------------------------------------
 // Canvas (Phase 3)
 // ---------------------------------------------------------------------------
 
+/// Tags per card; the filter bar only needs the common ones.
+const CANVAS_TAG_CAP: usize = 16;
+
 /// Bulk-load all data needed for the spatial canvas.
 #[tauri::command]
 pub async fn canvas_load(
@@ -33,11 +37,27 @@ pub async fn canvas_load(
             .into_iter()
             .map(|d| {
                 let id = d.id.as_ref().map(sovereign_db::schema::thing_to_raw).unwrap_or_default();
+                // A sealed card shows nothing drawn from its body.
+                let sealed = d.sensitivity == Sensitivity::Sealed;
+                let tags = if sealed {
+                    Vec::new()
+                } else {
+                    sovereign_core::content::hashtags(
+                        &sovereign_core::content::indexable_text(&d.content),
+                        CANVAS_TAG_CAP,
+                    )
+                };
+                let open_tasks = if sealed {
+                    0
+                } else {
+                    sovereign_db::tasks::open_task_count(&d)
+                };
                 CanvasDocDto {
                     id,
                     title: d.title,
                     thread_id: d.thread_id,
                     is_owned: d.is_owned,
+                    doc_type: d.doc_type,
                     spatial_x: d.spatial_x,
                     spatial_y: d.spatial_y,
                     created_at: d.created_at.to_rfc3339(),
@@ -45,6 +65,10 @@ pub async fn canvas_load(
                     reliability_classification: d.reliability_classification,
                     reliability_score: d.reliability_score,
                     source_url: d.source_url,
+                    tags,
+                    open_tasks,
+                    favorite: d.favorite,
+                    sensitivity: d.sensitivity,
                 }
             })
             .collect(),
@@ -209,3 +233,46 @@ pub async fn canvas_load_messages(
     Ok(result)
 }
 
+
+/// Open panels and camera from the last run, for restoring on launch.
+#[tauri::command]
+pub async fn get_window_session(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<WindowSession, String> {
+    state.require_unlocked(&webview).await?;
+    Ok(WindowSession::load(&state.profile_dir))
+}
+
+/// Persist open panels and camera. The frontend calls this (debounced)
+/// whenever the layout changes, so the last write before exit wins.
+#[tauri::command]
+pub async fn save_window_session(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    session: WindowSession,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    session.save(&state.profile_dir).str_err()
+}
+
+/// Whether frame timings are wanted, so the frontend knows to time its
+/// frames: the app was started with `--trace-profile` or local metrics are on.
+#[tauri::command]
+pub fn profiling_enabled() -> bool {
+    sovereign_core::lifecycle::profiling_active() || sovereign_core::metrics::enabled()
+}
+
+/// Record frame timings from the webview in the profile and metrics, as
+/// `render.frame`.
+#[tauri::command]
+pub fn report_frame_times(frames: Vec<FrameTimeDto>) {
+    for frame in frames {
+        tracing::debug!(
+            target: sovereign_core::profiling::PERF_TARGET,
+            name = "render.frame",
+            duration_us = (frame.duration_ms.max(0.0) * 1000.0) as u64,
+            ago_us = (frame.ago_ms.max(0.0) * 1000.0) as u64
+        );
+    }
+}
@@ -46,7 +46,15 @@ pub async fn get_contact_detail(
 ) -> Result<ContactDetailDto, String> {
     state.require_unlocked(&webview).await?;
     let contact = state.db.get_contact(&id).await.str_err()?;
-    let all_convs = state.db.list_conversations(None).await.str_err()?;
+    contact_detail(state.db.as_ref(), contact).await
+}
+
+async fn contact_detail(
+    db: &dyn GraphDB,
+    contact: sovereign_db::schema::Contact,
+) -> Result<ContactDetailDto, String> {
+    let id = contact.id.as_ref().map(sovereign_db::schema::thing_to_raw).unwrap_or_default();
+    let all_convs = db.list_conversations(None).await.str_err()?;
 
     let contact_convs: Vec<ConversationDto> = all_convs
         .into_iter()
@@ -64,11 +72,10 @@ pub async fn get_contact_detail(
         })
         .collect();
 
-    let cid = contact.id.as_ref().map(sovereign_db::schema::thing_to_raw).unwrap_or_default();
     Ok(ContactDetailDto {
-        id: cid,
+        id,
         name: contact.name,
-        avatar: contact.avatar,
+        avatar: contact.avatar.filter(|a| !a.is_empty()),
         notes: contact.notes,
         addresses: contact
             .addresses
@@ -80,10 +87,106 @@ pub async fn get_contact_detail(
                 is_primary: a.is_primary,
             })
             .collect(),
+        is_owned: contact.is_owned,
         conversations: contact_convs,
     })
 }
 
+/// A validated contact form.
+struct ContactForm {
+    name: String,
+    avatar: Option<String>,
+    notes: String,
+    addresses: Vec<sovereign_db::schema::ChannelAddress>,
+    is_owned: bool,
+}
+
+fn parse_contact_form(form: ContactFormDto) -> Result<ContactForm, String> {
+    let name = form.name.trim().to_string();
+    if name.is_empty() {
+        return Err("A contact needs a name".into());
+    }
+    let avatar = form.avatar.filter(|a| !a.is_empty());
+    if let Some(a) = &avatar {
+        // Only stored images; a remote URL would be fetched on every render.
+        let hash = a.strip_prefix("media:").unwrap_or_default();
+        if hash.len() != 64 || !hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+            return Err("Avatar must be a stored image".into());
+        }
+    }
+    let mut addresses = Vec::with_capacity(form.addresses.len());
+    for a in form.addresses {
+        let address = a.address.trim().to_string();
+        if address.is_empty() {
+            return Err("Addresses must not be empty".into());
+        }
+        addresses.push(sovereign_db::schema::ChannelAddress {
+            channel: a.channel.parse()?,
+            address,
+            display_name: a.display_name.filter(|d| !d.trim().is_empty()),
+            is_primary: a.is_primary,
+        });
+    }
+    Ok(ContactForm {
+        name,
+        avatar,
+        notes: form.notes,
+        addresses,
+        is_owned: form.is_owned,
+    })
+}
+
+/// Create a contact from the contact form.
+#[tauri::command]
+pub async fn create_contact(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    form: ContactFormDto,
+) -> Result<ContactDetailDto, String> {
+    state.require_unlocked(&webview).await?;
+    let form = parse_contact_form(form)?;
+    let mut contact = sovereign_db::schema::Contact::new(form.name, form.is_owned);
+    contact.avatar = form.avatar;
+    contact.notes = form.notes;
+    contact.addresses = form.addresses;
+    let created = state.db.create_contact(contact).await.str_err()?;
+    let id = created.id_string().unwrap_or_default();
+    // Re-read so the detail carries decrypted fields.
+    let contact = state.db.get_contact(&id).await.str_err()?;
+    contact_detail(state.db.as_ref(), contact).await
+}
+
+/// Save the contact form over an existing contact. Addresses are replaced
+/// as a whole, so removing a row in the form removes the address.
+#[tauri::command]
+pub async fn update_contact(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    id: String,
+    form: ContactFormDto,
+) -> Result<ContactDetailDto, String> {
+    state.require_unlocked(&webview).await?;
+    let form = parse_contact_form(form)?;
+    let current = state.db.get_contact(&id).await.str_err()?;
+    // Empty string clears the avatar (None means "keep" to update_contact).
+    state
+        .db
+        .update_contact(
+            &id,
+            Some(&form.name),
+            Some(&form.notes),
+            Some(form.avatar.as_deref().unwrap_or("")),
+        )
+        .await
+        .str_err()?;
+    state.db.set_contact_addresses(&id, form.addresses).await.str_err()?;
+    if current.is_owned != form.is_owned {
+        state.db.set_contact_owned(&id, form.is_owned).await.str_err()?;
+    }
+    let contact = state.db.get_contact(&id).await.str_err()?;
+    contact_detail(state.db.as_ref(), contact).await
+}
+
 /// List conversations, optionally filtered by contact participant.
 #[tauri::command]
 pub async fn list_conversations(
@@ -150,6 +253,7 @@ pub async fn list_messages(
                 body: m.body,
                 sent_at: m.sent_at.to_rfc3339(),
                 read_status: format!("{:?}", m.read_status),
+                attachment_doc_ids: m.attachment_doc_ids,
             }
         })
         .collect())
@@ -171,6 +275,237 @@ pub async fn mark_message_read(
     Ok(())
 }
 
+/// Translate a message body with the local model, for inline display in the
+/// inbox. Nothing is stored and nothing leaves the device.
+#[tauri::command]
+pub async fn translate_message(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    id: String,
+    language: String,
+) -> Result<String, String> {
+    state.require_unlocked(&webview).await?;
+    let llm = state
+        .skill_llm
+        .clone()
+        .ok_or("Translation needs a loaded AI model")?;
+    let msg = state.db.get_message(&id).await.str_err()?;
+    let language = language.trim().to_string();
+    if language.is_empty() {
+        return Err("No target language set".into());
+    }
+    tokio::task::spawn_blocking(move || {
+        sovereign_skills::skills::translation::translate_text(llm.as_ref(), &msg.body, &language)
+    })
+    .await
+    .str_err()?
+    .str_err()
+}
+
+/// Keep a message for later: copy it into an external document in the
+/// "Saved messages" thread and put that in the reading queue.
+#[tauri::command]
+pub async fn read_message_later(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<DocSummary, String> {
+    state.require_unlocked(&webview).await?;
+    let msg = state.db.get_message(&id).await.str_err()?;
+    let title = match msg.subject.as_deref().map(str::trim) {
+        Some(subject) if !subject.is_empty() => subject.to_string(),
+        _ => {
+            let sender = state
+                .db
+                .get_contact(&msg.from_contact_id)
+                .await
+                .map(|c| c.name)
+                .unwrap_or_else(|_| "unknown sender".into());
+            format!("Message from {sender}")
+        }
+    };
+
+    let thread_id = match state
+        .db
+        .find_thread_by_name("Saved messages")
+        .await
+        .str_err()?
+    {
+        Some(t) => t.id_string().unwrap_or_default(),
+        None => {
+            let thread = Thread::new("Saved messages".into(), "Messages kept for reading".into());
+            let created = state.db.create_thread(thread).await.str_err()?;
+            created.id_string().unwrap_or_default()
+        }
+    };
+
+    let mut doc = Document::new(title, thread_id, false);
+    doc.content = serde_json::json!({ "body": msg.body, "images": [], "videos": [] }).to_string();
+    let created = state.db.create_document(doc).await.str_err()?;
+    let doc_id = created.id_string().unwrap_or_default();
+    state
+        .db
+        .set_read_later(&doc_id, Some(Utc::now()))
+        .await
+        .str_err()?;
+    Ok(super::documents::doc_summary(created))
+}
+
+/// Messaging channels the compose flow can send through, with whether each
+/// is connected right now. Empty when comms is off or has no usable account.
+#[tauri::command]
+pub async fn list_compose_channels(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<Vec<ComposeChannelDto>, String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "comms")]
+    {
+        let Some(handle) = state.comms.read().await.clone() else {
+            return Ok(Vec::new());
+        };
+        let statuses = handle.channel_status().await.str_err()?;
+        return Ok(statuses
+            .into_iter()
+            .map(|(channel, status)| ComposeChannelDto {
+                channel: channel.to_string(),
+                connected: status == sovereign_comms::ChannelStatus::Connected,
+                error: match status {
+                    sovereign_comms::ChannelStatus::Error(e) => Some(e),
+                    _ => None,
+                },
+            })
+            .collect());
+    }
+    #[cfg(not(feature = "comms"))]
+    Ok(Vec::new())
+}
+
+/// Start a new conversation with a contact: create the Conversation, send
+/// the first message through the comms sync loop, and record it as an
+/// outgoing Message. The conversation is removed again if the send fails.
+#[tauri::command]
+pub async fn compose_message(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    data: ComposeMessageDto,
+) -> Result<ConversationDto, String> {
+    state.require_unlocked(&webview).await?;
+    let channel: sovereign_db::schema::ChannelType = data.channel.parse()?;
+    let body = data.body.trim().to_string();
+    if body.is_empty() {
+        return Err("Message is empty".into());
+    }
+    // Only email carries a subject; other channels would drop it silently.
+    let subject = match channel {
+        sovereign_db::schema::ChannelType::Email => {
+            data.subject.map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+        }
+        _ => None,
+    };
+
+    let contact = state.db.get_contact(&data.contact_id).await.str_err()?;
+    let address = contact
+        .addresses
+        .iter()
+        .filter(|a| a.channel == channel)
+        .max_by_key(|a| a.is_primary)
+        .map(|a| a.address.clone())
+        .ok_or_else(|| format!("{} has no {channel} address", contact.name))?;
+
+    #[cfg(feature = "comms")]
+    {
+        let handle = state
+            .comms
+            .read()
+            .await
+            .clone()
+            .ok_or("Messaging is not set up")?;
+        let available = handle
+            .channel_status()
+            .await
+            .str_err()?
+            .into_iter()
+            .any(|(c, s)| c == channel && s == sovereign_comms::ChannelStatus::Connected);
+        if !available {
+            return Err(format!("The {channel} account is not connected"));
+        }
+
+        let title = subject.clone().unwrap_or_else(|| contact.name.clone());
+        let conversation = sovereign_db::schema::Conversation::new(
+            title,
+            channel.clone(),
+            vec![data.contact_id.clone()],
+        );
+        let conversation = state.db.create_conversation(conversation).await.str_err()?;
+        let conv_id = conversation.id_string().unwrap_or_default();
+
+        let outgoing = sovereign_comms::OutgoingMessage {
+            to: vec![address],
+            subject: subject.clone(),
+            body: body.clone(),
+            body_html: None,
+            in_reply_to: None,
+            conversation_id: Some(conv_id.clone()),
+        };
+        let external_id = match handle.send(channel.clone(), outgoing).await {
+            Ok(id) => id,
+            Err(e) => {
+                if let Err(del) = state.db.delete_conversation(&conv_id).await {
+                    tracing::warn!("Failed to remove unsent conversation {conv_id}: {del}");
+                }
+                return Err(e.to_string());
+            }
+        };
+
+        // Email records its own outbound copy; the other channels don't.
+        let recorded = state.db.list_messages(&conv_id, None, 1).await.str_err()?;
+        if recorded.is_empty() {
+            let from = state
+                .db
+                .list_contacts()
+                .await
+                .str_err()?
+                .into_iter()
+                .find(|c| c.is_owned)
+                .and_then(|c| c.id_string())
+                .unwrap_or_default();
+            let mut msg = sovereign_db::schema::Message::new(
+                conv_id.clone(),
+                channel,
+                MessageDirection::Outbound,
+                from,
+                vec![data.contact_id],
+                body,
+            );
+            msg.subject = subject;
+            msg.read_status = ReadStatus::Read;
+            msg.external_id = Some(external_id);
+            state.db.create_message(msg).await.str_err()?;
+            state
+                .db
+                .update_conversation_last_message_at(&conv_id, Utc::now())
+                .await
+                .str_err()?;
+        }
+
+        let conversation = state.db.get_conversation(&conv_id).await.str_err()?;
+        return Ok(ConversationDto {
+            id: conv_id,
+            title: conversation.title,
+            channel: conversation.channel.to_string(),
+            participant_ids: conversation.participant_contact_ids,
+            unread_count: conversation.unread_count,
+            last_message_at: conversation.last_message_at.map(|t| t.to_rfc3339()),
+        });
+    }
+    #[cfg(not(feature = "comms"))]
+    {
+        let _ = (address, subject, body);
+        Err("Comms feature not enabled".into())
+    }
+}
+
 /// Create a relationship between two documents.
 #[tauri::command]
 pub async fn create_relationship(
@@ -201,3 +536,40 @@ pub async fn create_relationship(
     Ok(())
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form(name: &str, avatar: Option<&str>, channel: &str, address: &str) -> ContactFormDto {
+        ContactFormDto {
+            name: name.into(),
+            avatar: avatar.map(String::from),
+            notes: String::new(),
+            addresses: vec![ChannelAddressDto {
+                channel: channel.into(),
+                address: address.into(),
+                display_name: Some("  ".into()),
+                is_primary: true,
+            }],
+            is_owned: false,
+        }
+    }
+
+    #[test]
+    fn contact_form_is_validated() {
+        let hash = "ab".repeat(32);
+        let ok = parse_contact_form(form(" Ada ", Some(&format!("media:{hash}")), "signal", " +4412 ")).unwrap();
+        assert_eq!(ok.name, "Ada");
+        assert_eq!(ok.addresses[0].address, "+4412");
+        assert_eq!(ok.addresses[0].channel, sovereign_db::schema::ChannelType::Signal);
+        assert!(ok.addresses[0].display_name.is_none());
+        assert!(parse_contact_form(form("Ada", Some(""), "email", "a@b.c")).unwrap().avatar.is_none());
+
+        assert!(parse_contact_form(form("  ", None, "email", "a@b.c")).is_err());
+        assert!(parse_contact_form(form("Ada", None, "email", " ")).is_err());
+        assert!(parse_contact_form(form("Ada", None, "", "a@b.c")).is_err());
+        assert!(parse_contact_form(form("Ada", Some("https://x/y.png"), "email", "a@b.c")).is_err());
+        assert!(parse_contact_form(form("Ada", Some(&format!("media:{}", hash.to_uppercase())), "email", "a@b.c")).is_err());
+    }
+}
@@ -1,4 +1,6 @@
 use super::*;
+use crate::edit_journal::{EditJournal, JournalEntry};
+use crate::source_import::{self, SourceKind, ThreadMapping};
 
 // ---------------------------------------------------------------------------
 // Documents
@@ -18,20 +20,21 @@ pub async fn list_documents(
         .await
         .str_err()?;
 
-    Ok(docs
-        .into_iter()
-        .map(|d| DocSummary {
-            id: d
-                .id
-                .as_ref()
-                .map(sovereign_db::schema::thing_to_raw)
-                .unwrap_or_default(),
-            title: d.title,
-            thread_id: d.thread_id,
-            is_owned: d.is_owned,
-            modified_at: d.modified_at.to_rfc3339(),
-        })
-        .collect())
+    Ok(docs.into_iter().map(doc_summary).collect())
+}
+
+pub(crate) fn doc_summary(d: Document) -> DocSummary {
+    DocSummary {
+        id: d
+            .id
+            .as_ref()
+            .map(sovereign_db::schema::thing_to_raw)
+            .unwrap_or_default(),
+        title: d.title,
+        thread_id: d.thread_id,
+        is_owned: d.is_owned,
+        modified_at: d.modified_at.to_rfc3339(),
+    }
 }
 
 /// List all threads.
@@ -43,18 +46,19 @@ pub async fn list_threads(
     state.require_unlocked(&webview).await?;
     let threads = state.db.list_threads().await.str_err()?;
 
-    Ok(threads
-        .into_iter()
-        .map(|t| ThreadSummary {
-            id: t
-                .id
-                .as_ref()
-                .map(sovereign_db::schema::thing_to_raw)
-                .unwrap_or_default(),
-            name: t.name,
-            description: t.description,
-        })
-        .collect())
+    Ok(threads.into_iter().map(thread_summary).collect())
+}
+
+pub(crate) fn thread_summary(t: Thread) -> ThreadSummary {
+    ThreadSummary {
+        id: t
+            .id
+            .as_ref()
+            .map(sovereign_db::schema::thing_to_raw)
+            .unwrap_or_default(),
+        name: t.name,
+        description: t.description,
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -93,7 +97,7 @@ pub async fn get_theme(state: State<'_, AppState>) -> Result<String, String> {
 // Helpers
 // ---------------------------------------------------------------------------
 
-fn to_full_document(doc: Document) -> FullDocument {
+pub(crate) fn to_full_document(doc: Document) -> FullDocument {
     let id = doc
         .id
         .as_ref()
@@ -122,10 +126,22 @@ fn to_full_document(doc: Document) -> FullDocument {
                 thumbnail_path: v.thumbnail_path,
             })
             .collect(),
+        files: fields
+            .files
+            .into_iter()
+            .map(|f| ContentFileDto {
+                path: f.path,
+                name: f.name,
+            })
+            .collect(),
         thread_id: doc.thread_id,
         is_owned: doc.is_owned,
+        doc_type: doc.doc_type,
         created_at: doc.created_at.to_rfc3339(),
         modified_at: doc.modified_at.to_rfc3339(),
+        read_later: doc.read_later.is_some(),
+        favorite: doc.favorite,
+        sensitivity: doc.sensitivity,
     }
 }
 
@@ -141,10 +157,75 @@ pub async fn get_document(
     id: String,
 ) -> Result<FullDocument, String> {
     state.require_unlocked(&webview).await?;
-    let doc = state.db.get_document(&id).await.str_err()?;
+    let doc = crate::sealed::readable(&state, &id).await?;
+    state.relevance.record(&id);
     Ok(to_full_document(doc))
 }
 
+/// Documents pointing at this one: `[[links]]` and `@mentions` in their
+/// bodies, and relationships made any other way.
+#[tauri::command]
+pub async fn list_backlinks(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Vec<sovereign_db::links::Backlink>, String> {
+    state.require_unlocked(&webview).await?;
+    sovereign_db::links::backlinks(state.db.as_ref(), &id)
+        .await
+        .str_err()
+}
+
+/// Put a document in the reading queue, or take it out.
+#[tauri::command]
+pub async fn set_read_later(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    id: String,
+    queued: bool,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    let at = queued.then(Utc::now);
+    state.db.set_read_later(&id, at).await.str_err()
+}
+
+/// The reading queue, in the order documents were added to it.
+#[tauri::command]
+pub async fn list_reading_queue(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<Vec<ReadingQueueEntryDto>, String> {
+    use sovereign_skills::skills::word_count::reading_minutes;
+
+    state.require_unlocked(&webview).await?;
+    let docs = state.db.list_documents(None).await.str_err()?;
+    let mut queued: Vec<_> = docs
+        .into_iter()
+        .filter_map(|d| d.read_later.map(|at| (at, d)))
+        .collect();
+    queued.sort_by_key(|(at, _)| *at);
+
+    Ok(queued
+        .into_iter()
+        .map(|(at, d)| {
+            let id = d.id_string().unwrap_or_default();
+            let words = ContentFields::parse(&d.content)
+                .body
+                .split_whitespace()
+                .count();
+            ReadingQueueEntryDto {
+                id,
+                title: d.title,
+                thread_id: d.thread_id,
+                doc_type: d.doc_type,
+                queued_at: at.to_rfc3339(),
+                words,
+                reading_minutes: reading_minutes(words),
+            }
+        })
+        .collect())
+}
+
 /// Save document content (title + body + images + videos).
 #[tauri::command]
 pub async fn save_document(
@@ -162,10 +243,14 @@ pub async fn save_document(
     // too. The helper short-circuits gracefully when no account_key is
     // available or the document was already scanned — see
     // `pii_ingest::maybe_ingest_document_body` for the policy.
-    let body = crate::pii_ingest::maybe_ingest_document_body(&state, &id, &body).await?;
+    let stored_body = crate::pii_ingest::maybe_ingest_document_body(&state, &id, &body).await?;
+    let previous = state.db.get_document(&id).await.str_err()?;
+    // The editor doesn't send attached files; they change only through
+    // attach_file / remove_attachment.
+    let files = ContentFields::parse(&previous.content).files;
 
     let fields = ContentFields {
-        body,
+        body: stored_body,
         images: images
             .into_iter()
             .map(|i| sovereign_core::content::ContentImage {
@@ -182,6 +267,7 @@ pub async fn save_document(
                 thumbnail_path: v.thumbnail_path,
             })
             .collect(),
+        files,
     };
     let content_json = fields.serialize();
     state
@@ -190,9 +276,179 @@ pub async fn save_document(
         .await
         .str_err()?;
     state.autocommit.lock().await.record_edit(&id);
+    state.relevance.record(&id);
+    // Saved, so nothing left to recover.
+    EditJournal::open(&state.profile_dir).remove(&id);
+
+    // Links are read from the body as typed, before PII tokenization.
+    // The save has landed either way, so a failure here is only logged.
+    if let Err(e) = sovereign_db::links::sync_links(state.db.as_ref(), &id, &body).await {
+        tracing::warn!("Failed to update links from {id}: {e}");
+    }
+    if previous.title != title {
+        let renamed =
+            sovereign_db::links::retitle_links(state.db.as_ref(), &id, &previous.title, &title);
+        if let Err(e) = renamed.await {
+            tracing::warn!("Failed to rewrite links to {id} after rename: {e}");
+        }
+    }
     Ok(())
 }
 
+/// Change what kind of document this is (`note`, `spreadsheet`, …).
+#[tauri::command]
+pub async fn set_document_type(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    id: String,
+    doc_type: String,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    let doc_type =
+        DocType::parse(&doc_type).ok_or_else(|| format!("Unknown document type: {doc_type}"))?;
+    state
+        .db
+        .update_document_type(&id, doc_type)
+        .await
+        .str_err()
+}
+
+/// Pin a document to the taskbar as a favorite, or unpin it. Pins sync to
+/// the other devices like any other row.
+#[tauri::command]
+pub async fn set_favorite(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    id: String,
+    favorite: bool,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    state
+        .db
+        .set_favorite(&id, favorite, Utc::now())
+        .await
+        .str_err()
+}
+
+/// Set how carefully a document is handled. A sealed document must be
+/// unsealed first, so the seal can't be lifted without the password.
+/// The level syncs to the other devices like a pin.
+#[tauri::command]
+pub async fn set_document_sensitivity(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    id: String,
+    sensitivity: Sensitivity,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    crate::sealed::readable(&state, &id).await?;
+    state
+        .db
+        .set_sensitivity(&id, sensitivity, Utc::now())
+        .await
+        .str_err()
+}
+
+/// Open a sealed document for a few minutes after checking the password
+/// again (see `crate::sealed`).
+#[tauri::command]
+pub async fn unseal_document(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    id: String,
+    password: String,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "encryption")]
+    crate::sealed::reauthenticate(&state, &password).await?;
+    // Without encryption there is no password to ask for.
+    #[cfg(not(feature = "encryption"))]
+    let _ = &password;
+    state.unsealed.open(&id);
+    Ok(())
+}
+
+/// Apply one change (tags, thread, type, favorite, reading queue, archive)
+/// to every selected document, all or nothing.
+#[tauri::command]
+pub async fn bulk_edit_documents(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    ids: Vec<String>,
+    edit: crate::bulk_edit::BulkEdit,
+) -> Result<crate::bulk_edit::BulkEditReport, String> {
+    state.require_unlocked(&webview).await?;
+    crate::bulk_edit::apply(state.db.as_ref(), &ids, &edit)
+        .await
+        .map_err(|e| format!("{e:#}"))
+}
+
+/// Undo a bulk edit from the revert list its report carried.
+#[tauri::command]
+pub async fn revert_bulk_edit(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    steps: Vec<crate::bulk_edit::Revert>,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    crate::bulk_edit::revert(state.db.as_ref(), steps)
+        .await
+        .str_err()
+}
+
+/// Reminders, soonest first, fired ones included; only `doc_id`'s when
+/// given.
+#[tauri::command]
+pub async fn list_reminders(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    doc_id: Option<String>,
+) -> Result<Vec<ReminderDto>, String> {
+    state.require_unlocked(&webview).await?;
+    let reminders = state
+        .db
+        .list_reminders(doc_id.as_deref())
+        .await
+        .str_err()?;
+    Ok(reminders.into_iter().map(ReminderDto::from).collect())
+}
+
+/// Remind the user about a document, or one of its checklist items, at
+/// `due_at` (RFC 3339). `crate::reminders` delivers it. An empty `text`
+/// falls back to the document's title.
+#[tauri::command]
+pub async fn create_reminder(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    doc_id: String,
+    text: String,
+    due_at: String,
+    task_index: Option<usize>,
+) -> Result<ReminderDto, String> {
+    state.require_unlocked(&webview).await?;
+    let due_at = chrono::DateTime::parse_from_rfc3339(&due_at)
+        .str_err()?
+        .with_timezone(&Utc);
+    let text = match text.trim() {
+        "" => state.db.get_document(&doc_id).await.str_err()?.title,
+        t => t.to_string(),
+    };
+    let mut reminder = sovereign_db::schema::Reminder::new(doc_id, text, due_at);
+    reminder.task_index = task_index;
+    let created = state.db.create_reminder(reminder).await.str_err()?;
+    Ok(created.into())
+}
+
+#[tauri::command]
+pub async fn delete_reminder(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    state.db.delete_reminder(&id).await.str_err()
+}
+
 /// Create a new document and return its ID.
 #[tauri::command]
 pub async fn create_document(
@@ -227,6 +483,79 @@ pub async fn close_document(
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Crash recovery
+// ---------------------------------------------------------------------------
+
+/// Journal a panel's unsaved title and body, so a crash before the next
+/// save doesn't lose them. `save_document` clears the entry.
+#[tauri::command]
+pub async fn journal_edit(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    id: String,
+    title: String,
+    body: String,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    let entry = JournalEntry {
+        doc_id: id,
+        title,
+        body,
+        written_at: chrono::Utc::now(),
+    };
+    EditJournal::for_session(&state).await.write(&entry).str_err()
+}
+
+/// Unsaved edits a previous run left in the journal, oldest first. Entries
+/// for deleted documents, or matching what was saved after all, are
+/// cleared instead of offered; those for sealed documents wait until the
+/// document is unsealed.
+#[tauri::command]
+pub async fn list_recoverable_edits(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<Vec<RecoverableEditDto>, String> {
+    state.require_unlocked(&webview).await?;
+    let journal = EditJournal::for_session(&state).await;
+    let mut edits = Vec::new();
+    for entry in journal.entries() {
+        let saved = match state.db.get_document(&entry.doc_id).await {
+            Ok(doc) if crate::sealed::check(&state, &doc).is_err() => continue,
+            Ok(doc) => to_full_document(doc),
+            Err(_) => {
+                journal.remove(&entry.doc_id);
+                continue;
+            }
+        };
+        if saved.title == entry.title && saved.body == entry.body {
+            journal.remove(&entry.doc_id);
+            continue;
+        }
+        edits.push(RecoverableEditDto {
+            doc_id: entry.doc_id,
+            title: entry.title,
+            body: entry.body,
+            journaled_at: entry.written_at.to_rfc3339(),
+            saved_title: saved.title,
+            saved_at: saved.modified_at,
+        });
+    }
+    Ok(edits)
+}
+
+/// Drop a document's journaled edits without applying them.
+#[tauri::command]
+pub async fn discard_journaled_edit(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    EditJournal::open(&state.profile_dir).remove(&id);
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Version history
 // ---------------------------------------------------------------------------
@@ -239,6 +568,7 @@ pub async fn list_commits(
     doc_id: String,
 ) -> Result<Vec<CommitSummaryDto>, String> {
     state.require_unlocked(&webview).await?;
+    crate::sealed::readable(&state, &doc_id).await?;
     let commits = state
         .db
         .list_document_commits(&doc_id)
@@ -253,6 +583,7 @@ pub async fn list_commits(
             } else {
                 c.snapshot.content.clone()
             };
+            let (signed_by, signature_status) = commit_signer(&c);
             CommitSummaryDto {
                 id: c
                     .id
@@ -263,11 +594,99 @@ pub async fn list_commits(
                 timestamp: c.timestamp.to_rfc3339(),
                 snapshot_title: c.snapshot.title,
                 snapshot_preview: preview,
+                signed_by,
+                signature_status: signature_status.to_string(),
             }
         })
         .collect())
 }
 
+/// Which device signed a commit, and whether the signature holds.
+fn commit_signer(commit: &sovereign_db::schema::Commit) -> (Option<String>, &'static str) {
+    #[cfg(feature = "encryption")]
+    {
+        let status = match sovereign_db::encrypted::commit_device_signature_ok(commit) {
+            None => "unsigned",
+            Some(true) => "valid",
+            Some(false) => "invalid",
+        };
+        let public_key = commit.device_public_key.clone();
+        #[cfg(feature = "p2p")]
+        let public_key = public_key.map(|pk| {
+            sovereign_p2p::identity::peer_id_from_public_key_b64(&pk).unwrap_or(pk)
+        });
+        return (public_key, status);
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = commit;
+        (None, "unsigned")
+    }
+}
+
+/// Full title and body of one commit's snapshot.
+#[tauri::command]
+pub async fn get_commit_snapshot(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    doc_id: String,
+    commit_id: String,
+) -> Result<CommitSnapshotDto, String> {
+    state.require_unlocked(&webview).await?;
+    crate::sealed::readable(&state, &doc_id).await?;
+    let commit = state.db.get_commit(&commit_id).await.str_err()?;
+    if commit.document_id != doc_id {
+        return Err(format!("commit {commit_id} does not belong to {doc_id}"));
+    }
+    Ok(CommitSnapshotDto {
+        title: commit.snapshot.title,
+        body: ContentFields::parse(&commit.snapshot.content).body,
+    })
+}
+
+/// Everything that touched a document, newest first: commits, commits
+/// synced in from other devices, skill audit entries and related messages.
+#[tauri::command]
+pub async fn document_activity(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    doc_id: String,
+) -> Result<Vec<crate::activity::ActivityEntry>, String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "encryption")]
+    let local_device = super::pairing::peer_id_from_state(&state).await;
+    #[cfg(not(feature = "encryption"))]
+    let local_device = String::new();
+    #[cfg(feature = "p2p")]
+    let device_names: std::collections::HashMap<String, String> =
+        super::pairing::paired_devices(&state)
+            .await
+            .into_iter()
+            .map(|d| (d.peer_id, d.device_name))
+            .collect();
+    #[cfg(not(feature = "p2p"))]
+    let device_names = std::collections::HashMap::new();
+    let skill_audit = state.skill_registry.audit_log().recent(usize::MAX);
+
+    let ctx = crate::activity::ActivityContext {
+        local_device: Some(local_device.as_str()).filter(|d| !d.is_empty()),
+        device_names: &device_names,
+        skill_audit: &skill_audit,
+        signer: verified_signer,
+    };
+    crate::activity::document_activity(state.db.as_ref(), &doc_id, &ctx)
+        .await
+        .str_err()
+}
+
+/// The device behind a commit, only when its signature checks out.
+fn verified_signer(commit: &sovereign_db::schema::Commit) -> Option<String> {
+    match commit_signer(commit) {
+        (signer, "valid") => signer,
+        _ => None,
+    }
+}
+
 /// Restore a document to a specific commit.
 #[tauri::command]
 pub async fn restore_commit(
@@ -277,6 +696,7 @@ pub async fn restore_commit(
     commit_id: String,
 ) -> Result<FullDocument, String> {
     state.require_unlocked(&webview).await?;
+    crate::sealed::readable(&state, &doc_id).await?;
     let doc = state
         .db
         .restore_document(&doc_id, &commit_id)
@@ -285,35 +705,280 @@ pub async fn restore_commit(
     Ok(to_full_document(doc))
 }
 
+/// Store an image pasted into the editor (base64 of the file bytes).
+#[tauri::command]
+pub async fn store_pasted_image(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    data_base64: String,
+) -> Result<PastedImageDto, String> {
+    use base64::Engine;
+    state.require_unlocked(&webview).await?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data_base64.as_bytes())
+        .map_err(|e| format!("bad image data: {e}"))?;
+    let (hash, path) = crate::media::store_image(&bytes)?;
+    Ok(PastedImageDto {
+        hash,
+        path: path.display().to_string(),
+    })
+}
+
+/// Name to show for an attachment listed in `fields`, or None when `path`
+/// isn't one of the document's attachments. Commands that read attachment
+/// bytes go through this so the webview can't name arbitrary files.
+fn attachment_name(fields: &ContentFields, path: &str) -> Option<String> {
+    let file_name = || {
+        std::path::Path::new(path)
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    };
+    let or_file_name = |s: &str| if s.is_empty() { file_name() } else { s.to_string() };
+    if let Some(f) = fields.files.iter().find(|f| f.path == path) {
+        return Some(or_file_name(&f.name));
+    }
+    if let Some(i) = fields.images.iter().find(|i| i.path == path) {
+        return Some(or_file_name(&i.caption));
+    }
+    fields
+        .videos
+        .iter()
+        .find(|v| v.path == path)
+        .map(|v| or_file_name(&v.caption))
+}
+
+async fn read_listed_attachment(
+    state: &AppState,
+    doc_id: &str,
+    path: &str,
+) -> Result<(String, Vec<u8>), String> {
+    let doc = crate::sealed::readable(state, doc_id).await?;
+    let name = attachment_name(&ContentFields::parse(&doc.content), path)
+        .ok_or("Not an attachment of this document")?;
+    let size = std::fs::metadata(path).str_err()?.len();
+    if size > crate::media::MAX_ATTACHMENT_BYTES as u64 {
+        return Err("Attachment is too large to open".into());
+    }
+    let bytes = tokio::fs::read(path).await.str_err()?;
+    Ok((name, bytes))
+}
+
+/// Attach a file to a document (base64 of the file bytes).
+#[tauri::command]
+pub async fn attach_file(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    doc_id: String,
+    name: String,
+    data_base64: String,
+) -> Result<ContentFileDto, String> {
+    use base64::Engine;
+    state.require_unlocked(&webview).await?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data_base64.as_bytes())
+        .map_err(|e| format!("bad file data: {e}"))?;
+    let (_, path) = crate::media::store_attachment(&bytes)?;
+    let file = sovereign_core::content::ContentFile {
+        path: path.display().to_string(),
+        name: name.trim().chars().filter(|c| !c.is_control()).take(255).collect(),
+    };
+    let doc = state.db.get_document(&doc_id).await.str_err()?;
+    let mut fields = ContentFields::parse(&doc.content);
+    if !fields.files.iter().any(|f| f.path == file.path) {
+        fields.files.push(file.clone());
+        state
+            .db
+            .update_document(&doc_id, None, Some(&fields.serialize()))
+            .await
+            .str_err()?;
+        state.autocommit.lock().await.record_edit(&doc_id);
+    }
+    Ok(ContentFileDto {
+        path: file.path,
+        name: file.name,
+    })
+}
+
+/// Remove an attached file from a document. The stored bytes stay in the
+/// blob store; other documents may share them.
+#[tauri::command]
+pub async fn remove_attachment(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    doc_id: String,
+    path: String,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    let doc = state.db.get_document(&doc_id).await.str_err()?;
+    let mut fields = ContentFields::parse(&doc.content);
+    let before = fields.files.len();
+    fields.files.retain(|f| f.path != path);
+    if fields.files.len() != before {
+        state
+            .db
+            .update_document(&doc_id, None, Some(&fields.serialize()))
+            .await
+            .str_err()?;
+        state.autocommit.lock().await.record_edit(&doc_id);
+    }
+    Ok(())
+}
+
+/// Read one of a document's attachments, for the viewer and "Save as".
+#[tauri::command]
+pub async fn read_attachment(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    doc_id: String,
+    path: String,
+) -> Result<AttachmentDataDto, String> {
+    use base64::Engine;
+    state.require_unlocked(&webview).await?;
+    let (name, bytes) = read_listed_attachment(&state, &doc_id, &path).await?;
+    Ok(AttachmentDataDto {
+        name,
+        mime: crate::media::sniff_attachment(&bytes).to_string(),
+        data_base64: base64::engine::general_purpose::STANDARD.encode(&bytes),
+    })
+}
+
+/// Seal a document and the chosen attachments into a `.svshare` file.
+/// `expires_in_days` of 0 makes a share that never expires.
+#[tauri::command]
+pub async fn share_document(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    id: String,
+    passphrase: String,
+    attachments: Vec<String>,
+    expires_in_days: u32,
+) -> Result<DocumentShareDto, String> {
+    use base64::Engine;
+    state.require_unlocked(&webview).await?;
+    crate::sealed::readable(&state, &id).await?;
+    let expires_in = (expires_in_days > 0).then(|| chrono::Duration::days(expires_in_days.into()));
+    let share = crate::share_bundle::create(
+        state.db.as_ref(),
+        &id,
+        &attachments,
+        expires_in,
+        &passphrase,
+        chrono::Utc::now(),
+    )
+    .await
+    .str_err()?;
+    Ok(DocumentShareDto {
+        file_name: share.file_name,
+        data_base64: base64::engine::general_purpose::STANDARD.encode(&share.sealed),
+        qr_payload: share.qr_payload,
+        expires_at: share.expires_at,
+        missing_media: share.missing_media,
+    })
+}
+
+/// Open a share received as a file (base64 of its bytes) or as QR text
+/// into the "Shared with me" thread.
+#[tauri::command]
+pub async fn open_share(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    data_base64: Option<String>,
+    payload: Option<String>,
+    passphrase: String,
+) -> Result<crate::share_bundle::OpenedShare, String> {
+    use base64::Engine;
+    state.require_unlocked(&webview).await?;
+    let sealed = match (data_base64, payload) {
+        (_, Some(payload)) => crate::share_bundle::from_payload(&payload).str_err()?,
+        (Some(data), None) => base64::engine::general_purpose::STANDARD
+            .decode(data.as_bytes())
+            .map_err(|e| format!("Invalid base64: {e}"))?,
+        (None, None) => return Err("No share given".into()),
+    };
+    crate::share_bundle::open(
+        state.db.as_ref(),
+        &sealed,
+        &passphrase,
+        &state.profile_dir,
+        chrono::Utc::now(),
+    )
+    .await
+    .str_err()
+}
+
+/// Text of the first `pages` pages of an attached PDF.
+#[tauri::command]
+pub async fn pdf_preview(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    doc_id: String,
+    path: String,
+    pages: u32,
+) -> Result<Vec<String>, String> {
+    state.require_unlocked(&webview).await?;
+    let (_, bytes) = read_listed_attachment(&state, &doc_id, &path).await?;
+    if crate::media::sniff_attachment(&bytes) != "application/pdf" {
+        return Err("Not a PDF".into());
+    }
+    let pages = pages.clamp(1, 10) as usize;
+    // pdf-extract panics on some malformed files; the join error covers it.
+    tokio::task::spawn_blocking(move || {
+        pdf_extract::extract_text_from_mem_by_pages(&bytes)
+            .map(|all| all.into_iter().take(pages).collect::<Vec<_>>())
+    })
+    .await
+    .map_err(|_| "Could not read this PDF".to_string())?
+    .map_err(|e| format!("Could not read this PDF: {e}"))
+}
+
 // ---------------------------------------------------------------------------
 // Skills
 // ---------------------------------------------------------------------------
 
-/// List skills applicable to a document (based on file extension in title).
+/// Map a skill's registry verification status to the `SkillInfo` trust
+/// label and signer name.
+pub(crate) fn skill_trust(
+    registry: &sovereign_skills::SkillRegistry,
+    name: &str,
+) -> (String, Option<String>) {
+    use sovereign_skills::SkillVerification;
+    match registry.verification(name) {
+        None => ("builtin".into(), None),
+        Some(SkillVerification::Verified { signer }) => ("verified".into(), Some(signer.clone())),
+        Some(SkillVerification::UntrustedSigner { .. }) => ("untrusted".into(), None),
+        Some(SkillVerification::Unsigned) => ("unsigned".into(), None),
+        Some(SkillVerification::Tampered) => ("tampered".into(), None),
+    }
+}
+
+/// List skills applicable to a document of the given type (`note`,
+/// `spreadsheet`, …; unknown names fall back to a note).
 #[tauri::command]
 pub async fn list_skills_for_doc(
     webview: tauri::Webview,
     state: State<'_, AppState>,
-    doc_title: String,
+    doc_type: String,
 ) -> Result<Vec<SkillInfo>, String> {
     state.require_unlocked(&webview).await?;
-    let ext = doc_title
-        .rsplit('.')
-        .next()
-        .unwrap_or("")
-        .to_lowercase();
-    let skills = state.skill_registry.skills_for_file_type(&ext);
+    let doc_type = DocType::parse(&doc_type).unwrap_or_default();
+    let skills = state.skill_registry.skills_for_doc_type(doc_type);
     Ok(skills
         .into_iter()
-        .map(|(name, actions)| SkillInfo {
-            skill_name: name.to_string(),
-            actions: actions
-                .into_iter()
-                .map(|(id, label)| SkillActionInfo {
-                    action_id: id,
-                    label,
-                })
-                .collect(),
+        .map(|(name, actions)| {
+            let (trust, signer) = skill_trust(&state.skill_registry, name);
+            SkillInfo {
+                skill_name: name.to_string(),
+                actions: actions
+                    .into_iter()
+                    .map(|(id, label)| SkillActionInfo {
+                        action_id: id,
+                        label,
+                    })
+                    .collect(),
+                trust,
+                signer,
+            }
         })
         .collect())
 }
@@ -329,7 +994,7 @@ pub async fn execute_skill(
     params: String,
 ) -> Result<SkillResultDto, String> {
     state.require_unlocked(&webview).await?;
-    let doc = state.db.get_document(&doc_id).await.str_err()?;
+    let doc = crate::sealed::readable(&state, &doc_id).await?;
     let fields = ContentFields::parse(&doc.content);
     let skill_doc = SkillDocument {
         id: doc_id,
@@ -347,79 +1012,18 @@ pub async fn execute_skill(
         db: Some(state.skill_db.clone()),
         llm: state.skill_llm.clone(),
     };
-    let output = state
+    // Runs on the registry's job queue so concurrent executions are bounded
+    // and show up (and can be cancelled) in the jobs panel.
+    let job = state
         .skill_registry
-        .execute_skill(&skill_name, &action, &skill_doc, &params, &ctx)
+        .submit_job(&skill_name, &action, skill_doc, params, ctx)
+        .str_err()?;
+    let output = tokio::task::spawn_blocking(move || job.wait())
+        .await
+        .str_err()?
         .str_err()?;
 
-    match output {
-        sovereign_skills::traits::SkillOutput::ContentUpdate(cf) => Ok(SkillResultDto {
-            kind: "content_update".into(),
-            body: Some(cf.body),
-            images: Some(
-                cf.images
-                    .into_iter()
-                    .map(|i| ContentImageDto {
-                        path: i.path,
-                        caption: i.caption,
-                    })
-                    .collect(),
-            ),
-            videos: Some(
-                cf.videos
-                    .into_iter()
-                    .map(|v| ContentVideoDto {
-                        path: v.path,
-                        caption: v.caption,
-                        duration_secs: v.duration_secs,
-                        thumbnail_path: v.thumbnail_path,
-                    })
-                    .collect(),
-            ),
-            file_name: None,
-            file_mime: None,
-            file_data_base64: None,
-            structured_kind: None,
-            structured_json: None,
-        }),
-        sovereign_skills::traits::SkillOutput::File { name, mime_type, data } => {
-            use base64::Engine;
-            let b64 = base64::engine::general_purpose::STANDARD.encode(&data);
-            Ok(SkillResultDto {
-                kind: "file".into(),
-                body: None,
-                images: None,
-                videos: None,
-                file_name: Some(name),
-                file_mime: Some(mime_type),
-                file_data_base64: Some(b64),
-                structured_kind: None,
-                structured_json: None,
-            })
-        }
-        sovereign_skills::traits::SkillOutput::StructuredData { kind, json } => Ok(SkillResultDto {
-            kind: "structured_data".into(),
-            body: None,
-            images: None,
-            videos: None,
-            file_name: None,
-            file_mime: None,
-            file_data_base64: None,
-            structured_kind: Some(kind),
-            structured_json: Some(json),
-        }),
-        sovereign_skills::traits::SkillOutput::None => Ok(SkillResultDto {
-            kind: "none".into(),
-            body: None,
-            images: None,
-            videos: None,
-            file_name: None,
-            file_mime: None,
-            file_data_base64: None,
-            structured_kind: None,
-            structured_json: None,
-        }),
-    }
+    Ok(SkillResultDto::from(output))
 }
 
 /// List all registered skills and their actions.
@@ -432,16 +1036,21 @@ pub async fn list_all_skills(
     let skills = state.skill_registry.all_skills();
     Ok(skills
         .iter()
-        .map(|s| SkillInfo {
-            skill_name: s.name().to_string(),
-            actions: s
-                .actions()
-                .into_iter()
-                .map(|(id, label)| SkillActionInfo {
-                    action_id: id,
-                    label,
-                })
-                .collect(),
+        .map(|s| {
+            let (trust, signer) = skill_trust(&state.skill_registry, s.name());
+            SkillInfo {
+                skill_name: s.name().to_string(),
+                actions: s
+                    .actions()
+                    .into_iter()
+                    .map(|(id, label)| SkillActionInfo {
+                        action_id: id,
+                        label,
+                    })
+                    .collect(),
+                trust,
+                signer,
+            }
         })
         .collect())
 }
@@ -466,36 +1075,46 @@ pub async fn delete_document(
 // Phase 5: File import
 // ---------------------------------------------------------------------------
 
-/// Import a file from the local filesystem as a new document.
-#[tauri::command]
-pub async fn import_file(
-    webview: tauri::Webview,
-    state: State<'_, AppState>,
-    file_path: String,
-    thread_id: Option<String>,
-) -> Result<CanvasDocDto, String> {
-    state.require_unlocked(&webview).await?;
-
-    // IPC-001: contain the import path. Canonicalize the requested path
-    // (resolving symlinks + `..`) and reject anything that escapes the
-    // user's home directory. `std::fs::canonicalize` errors if the path
-    // doesn't exist, which also covers the previous existence check.
-    let canonical = std::fs::canonicalize(&file_path)
-        .map_err(|e| format!("File not found or inaccessible: {file_path}: {e}"))?;
+/// IPC-001: contain an import path. Canonicalize the requested path
+/// (resolving symlinks + `..`) and reject anything that escapes the
+/// user's document folders. `std::fs::canonicalize` errors if the path
+/// doesn't exist, which also covers the existence check.
+fn contain_import_path(requested: &str) -> Result<std::path::PathBuf, String> {
+    let home = sovereign_core::home_dir();
+    // Typed paths (the onboarding import) may start with `~/`.
+    let expanded = match requested.strip_prefix("~/") {
+        Some(rest) => home.join(rest),
+        None => std::path::PathBuf::from(requested),
+    };
+    let canonical = std::fs::canonicalize(&expanded)
+        .map_err(|e| format!("File not found or inaccessible: {requested}: {e}"))?;
     // Default-deny: only allow imports from the user's standard document
     // folders. Confining to $HOME is NOT enough — auth.store, salt
     // (~/.sovereign/crypto), ~/.ssh keys, and other dotfile secrets all
     // live under $HOME; Documents/Downloads/Desktop excludes every dotdir.
-    let home = sovereign_core::home_dir();
     let allowed_roots: Vec<std::path::PathBuf> = ["Documents", "Downloads", "Desktop"]
         .iter()
         .filter_map(|d| std::fs::canonicalize(home.join(d)).ok())
         .collect();
     if !allowed_roots.iter().any(|root| canonical.starts_with(root)) {
         return Err(format!(
-            "Import rejected: '{file_path}' is outside the allowed import folders (Documents, Downloads, Desktop)"
+            "Import rejected: '{requested}' is outside the allowed import folders (Documents, Downloads, Desktop)"
         ));
     }
+    Ok(canonical)
+}
+
+/// Import a file from the local filesystem as a new document.
+#[tauri::command]
+pub async fn import_file(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    file_path: String,
+    thread_id: Option<String>,
+) -> Result<CanvasDocDto, String> {
+    state.require_unlocked(&webview).await?;
+
+    let canonical = contain_import_path(&file_path)?;
     let path = canonical.as_path();
 
     let title = path
@@ -508,7 +1127,14 @@ pub async fn import_file(
         .map_err(|e| format!("Failed to read file: {e}"))?;
 
     let tid = thread_id.unwrap_or_else(|| "thread:default".to_string());
-    let doc = Document::new(title, tid.clone(), true);
+    let mut doc = Document::new(title, tid.clone(), true);
+    if let Some(doc_type) = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .and_then(DocType::from_extension)
+    {
+        doc.doc_type = doc_type;
+    }
     let created = state.db.create_document(doc).await.str_err()?;
     let id = created
         .id
@@ -534,6 +1160,7 @@ pub async fn import_file(
         title: created.title,
         thread_id: tid,
         is_owned: true,
+        doc_type: created.doc_type,
         spatial_x: created.spatial_x,
         spatial_y: created.spatial_y,
         created_at: created.created_at.to_rfc3339(),
@@ -541,6 +1168,176 @@ pub async fn import_file(
         reliability_classification: None,
         reliability_score: None,
         source_url: None,
+        tags: Vec::new(),
+        open_tasks: 0,
+        favorite: false,
+        sensitivity: Sensitivity::Normal,
     })
 }
 
+/// Scan an Obsidian/Logseq vault, Notion export or maildir and report
+/// how many documents each thread would get under `mapping`.
+#[tauri::command]
+pub async fn preview_source_import(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    kind: SourceKind,
+    path: String,
+    mapping: ThreadMapping,
+) -> Result<SourceImportPreviewDto, String> {
+    state.require_unlocked(&webview).await?;
+    let root = contain_import_path(&path)?;
+    let source_name = source_import::source_name(kind, &root);
+    let items = tokio::task::spawn_blocking(move || source_import::scan(kind, &root))
+        .await
+        .str_err()?
+        .str_err()?;
+
+    let mut threads: Vec<ImportThreadCountDto> = Vec::new();
+    for item in &items {
+        let name = source_import::thread_for(mapping, &source_name, item);
+        match threads.iter_mut().find(|t| t.name == name) {
+            Some(t) => t.documents += 1,
+            None => threads.push(ImportThreadCountDto { name, documents: 1 }),
+        }
+    }
+    Ok(SourceImportPreviewDto {
+        source_name,
+        total: items.len(),
+        threads,
+    })
+}
+
+/// Import a source as threads and documents, emitting
+/// `source-import-progress` as it goes. Threads that already exist by
+/// name are reused; files that can't be read are skipped and reported.
+#[tauri::command]
+pub async fn import_source(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    kind: SourceKind,
+    path: String,
+    mapping: ThreadMapping,
+) -> Result<SourceImportReportDto, String> {
+    use tauri::Emitter;
+    state.require_unlocked(&webview).await?;
+    let root = contain_import_path(&path)?;
+    let source_name = source_import::source_name(kind, &root);
+    let items = tokio::task::spawn_blocking(move || source_import::scan(kind, &root))
+        .await
+        .str_err()?
+        .str_err()?;
+
+    let mut thread_ids: std::collections::HashMap<String, String> = state
+        .db
+        .list_threads()
+        .await
+        .str_err()?
+        .into_iter()
+        .filter_map(|t| Some((t.name.clone(), t.id_string()?)))
+        .collect();
+    let mut report = SourceImportReportDto {
+        threads_created: 0,
+        documents: 0,
+        skipped: Vec::new(),
+    };
+    let total = items.len();
+
+    for (i, item) in items.iter().enumerate() {
+        let display = item.path.display().to_string();
+        if i % 25 == 0 {
+            let _ = app.emit(
+                "source-import-progress",
+                SourceImportProgressPayload {
+                    done: i,
+                    total,
+                    current: display.clone(),
+                },
+            );
+        }
+        let imported = match source_import::read_item(kind, item) {
+            Ok(doc) => doc,
+            Err(e) => {
+                report.skipped.push(format!("{display}: {e}"));
+                continue;
+            }
+        };
+
+        let thread_name = source_import::thread_for(mapping, &source_name, item);
+        let thread_id = match thread_ids.get(&thread_name) {
+            Some(id) => id.clone(),
+            None => {
+                let created = state
+                    .db
+                    .create_thread(Thread::new(
+                        thread_name.clone(),
+                        format!("Imported from {source_name}"),
+                    ))
+                    .await
+                    .str_err()?;
+                let id = created.id_string().ok_or("thread missing ID")?;
+                thread_ids.insert(thread_name, id.clone());
+                report.threads_created += 1;
+                id
+            }
+        };
+
+        let mut doc = Document::new(imported.title, thread_id, true);
+        if let Some(at) = imported.modified_at {
+            doc.created_at = at;
+            doc.modified_at = at;
+        }
+        let created = state.db.create_document(doc).await.str_err()?;
+        let id = created.id_string().unwrap_or_default();
+        // PII-002: same ingest as a single-file import.
+        let body =
+            crate::pii_ingest::maybe_ingest_document_body(&state, &id, &imported.body).await?;
+        let content = ContentFields {
+            body,
+            ..Default::default()
+        }
+        .serialize();
+        state
+            .db
+            .update_document(&id, None, Some(&content))
+            .await
+            .str_err()?;
+        report.documents += 1;
+    }
+
+    let _ = app.emit(
+        "source-import-progress",
+        SourceImportProgressPayload {
+            done: total,
+            total,
+            current: String::new(),
+        },
+    );
+    tracing::info!(
+        "Imported {} documents into {} new threads from {source_name} ({} skipped)",
+        report.documents,
+        report.threads_created,
+        report.skipped.len()
+    );
+    Ok(report)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_core::content::{ContentFile, ContentImage};
+
+    #[test]
+    fn only_listed_paths_are_attachments() {
+        let fields = ContentFields {
+            images: vec![ContentImage { path: "/blobs/aa".into(), caption: String::new() }],
+            files: vec![ContentFile { path: "/blobs/bb".into(), name: "report.pdf".into() }],
+            ..Default::default()
+        };
+        assert_eq!(attachment_name(&fields, "/blobs/bb").as_deref(), Some("report.pdf"));
+        assert_eq!(attachment_name(&fields, "/blobs/aa").as_deref(), Some("aa"));
+        assert_eq!(attachment_name(&fields, "/etc/passwd"), None);
+    }
+}
@@ -0,0 +1,75 @@
+//! Meeting mode commands.
+//!
+//! The frontend polls `meetings_starting` once a minute; when a calendar
+//! event is about to start it offers to record the meeting, transcribes the
+//! audio through `voice_transcribe_buffer`, and saves the result with
+//! `save_meeting_notes` (see `crate::meeting_notes`).
+//!
+//! The calendar is the `[caldav]` account in `~/.sovereign/comms.toml`; its
+//! password comes from `SOVEREIGN_CALDAV_PASSWORD`, like the other comms
+//! secrets. Without the `comms-caldav` feature, a configured account or the
+//! password, no meeting is ever starting.
+
+use tauri::State;
+
+use crate::err::ToStringErr;
+use crate::meeting_notes::{self, Meeting, SavedNotes};
+use crate::tauri_state::AppState;
+
+/// How long after its start an event is still offered for recording.
+#[cfg(feature = "comms-caldav")]
+const GRACE_MINUTES: i64 = 10;
+
+/// Calendar events starting now, soonest first.
+#[tauri::command]
+pub async fn meetings_starting(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<Vec<Meeting>, String> {
+    state.require_unlocked(&webview).await?;
+
+    #[cfg(feature = "comms-caldav")]
+    {
+        use chrono::{Duration, Utc};
+        use sovereign_comms::calendar::CalDavClient;
+
+        let Some(caldav) = crate::comms_startup::load_comms_config()?.and_then(|c| c.caldav) else {
+            return Ok(Vec::new());
+        };
+        let Ok(password) = std::env::var("SOVEREIGN_CALDAV_PASSWORD") else {
+            return Ok(Vec::new());
+        };
+        let now = Utc::now();
+        let lead = Duration::minutes(i64::from(caldav.meeting_lead_minutes));
+        let grace = Duration::minutes(GRACE_MINUTES);
+        let client = CalDavClient::new(caldav, password);
+        let mut events: Vec<_> = client
+            .events_between(now - grace, now + lead)
+            .await
+            .str_err()?
+            .into_iter()
+            .filter(|e| e.starting(now, lead, grace))
+            .collect();
+        events.sort_by_key(|e| e.start);
+        Ok(events.into_iter().map(Meeting::from).collect())
+    }
+    #[cfg(not(feature = "comms-caldav"))]
+    Ok(Vec::new())
+}
+
+/// Save a recorded meeting's transcript as a meeting-notes document.
+#[tauri::command]
+pub async fn save_meeting_notes(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    meeting: Meeting,
+    transcript: String,
+) -> Result<SavedNotes, String> {
+    state.require_unlocked(&webview).await?;
+    if transcript.trim().is_empty() {
+        return Err("Nothing was transcribed".into());
+    }
+    meeting_notes::save(state.db.as_ref(), &meeting, &transcript)
+        .await
+        .str_err()
+}
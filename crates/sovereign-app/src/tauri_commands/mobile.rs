@@ -1,6 +1,7 @@
 /// Mobile-specific Tauri commands:
 ///   - voice_transcribe_buffer  Web Audio API PCM → Whisper STT
 ///   - receive_shared_content   Save content arriving from OS share sheet
+///   - proximity_link_up/down   Hotspot / Wi-Fi Direct / Bluetooth links
 
 use serde::Deserialize;
 use sovereign_db::GraphDB;
@@ -127,6 +128,7 @@ pub async fn receive_shared_content(
             body,
             images: vec![],
             videos: vec![],
+            files: vec![],
         };
         state
             .db
@@ -149,7 +151,8 @@ pub async fn receive_shared_content(
 /// poll.
 ///
 /// Accepted values (case-insensitive): `"wifi"`, `"cellular"`,
-/// `"offline"`, `"unknown"`. Anything else is treated as `unknown`.
+/// `"offline"`, `"local"`, `"unknown"`. Anything else is treated as
+/// `unknown`.
 #[tauri::command]
 pub async fn set_connectivity_state(
     webview: tauri::Webview,
@@ -167,6 +170,7 @@ pub async fn set_connectivity_state(
             "wifi" => ConnectivityState::Wifi,
             "cellular" => ConnectivityState::Cellular,
             "offline" => ConnectivityState::Offline,
+            "local" => ConnectivityState::Local,
             _ => ConnectivityState::Unknown,
         };
         state.set_connectivity_state(parsed);
@@ -198,6 +202,7 @@ pub async fn get_connectivity_state(
             ConnectivityState::Wifi => "wifi",
             ConnectivityState::Cellular => "cellular",
             ConnectivityState::Offline => "offline",
+            ConnectivityState::Local => "local",
             ConnectivityState::Unknown => "unknown",
         };
         Ok(s.into())
@@ -208,3 +213,106 @@ pub async fn get_connectivity_state(
         Ok("unknown".into())
     }
 }
+
+// ---------------------------------------------------------------------------
+// Proximity links (sync with no shared network)
+// ---------------------------------------------------------------------------
+
+/// A direct link to a nearby device, as the platform reports it. Mirrors
+/// `sovereign_p2p::proximity::ProximityLink`.
+#[derive(Deserialize)]
+pub struct ProximityLinkDto {
+    /// `"hotspot"`, `"wifi_direct"` or `"bluetooth_pan"`.
+    pub kind: String,
+    pub local_ip: Option<String>,
+    /// `ip:port` of the device at the other end, if known.
+    pub peer: Option<String>,
+}
+
+/// Called from the Devices settings once the user has joined a nearby
+/// device's hotspot, Wi-Fi Direct group or Bluetooth PAN in the OS. The
+/// P2P node dials the other end and syncs with it; with no other
+/// network, connectivity becomes `local` so the sync gate lets it
+/// through.
+#[tauri::command]
+pub async fn proximity_link_up(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    link: ProximityLinkDto,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        use sovereign_p2p::proximity::{ProximityKind, ProximityLink};
+        use sovereign_p2p::ConnectivityState;
+        let kind = match link.kind.as_str() {
+            "hotspot" => ProximityKind::Hotspot,
+            "wifi_direct" => ProximityKind::WifiDirect,
+            "bluetooth_pan" => ProximityKind::BluetoothPan,
+            other => return Err(format!("unknown proximity link kind {other:?}")),
+        };
+        let parse_opt = |s: Option<&str>| s.map(str::trim).filter(|s| !s.is_empty());
+        let link = ProximityLink {
+            kind,
+            local_ip: parse_opt(link.local_ip.as_deref())
+                .map(|ip| ip.parse().map_err(|_| format!("invalid local address {ip:?}")))
+                .transpose()?,
+            peer: parse_opt(link.peer.as_deref())
+                .map(|p| p.parse().map_err(|_| format!("expected ip:port, got {p:?}")))
+                .transpose()?,
+        };
+        let cmd_tx = state
+            .p2p_command_tx()
+            .await
+            .ok_or_else(|| "P2P is not running".to_string())?;
+        if let Ok(mut current) = state.proximity_link.write() {
+            *current = Some(link.clone());
+        }
+        if matches!(
+            state.connectivity_state(),
+            ConnectivityState::Offline | ConnectivityState::Unknown
+        ) {
+            state.set_connectivity_state(ConnectivityState::Local);
+        }
+        cmd_tx
+            .send(sovereign_p2p::P2pCommand::ProximityLinkUp { link })
+            .await
+            .map_err(|_| "P2P node is not running".to_string())?;
+        return Ok(());
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = (&state, &link);
+        Err("proximity links require the p2p feature".to_string())
+    }
+}
+
+/// The proximity link went away.
+#[tauri::command]
+pub async fn proximity_link_down(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        use sovereign_p2p::ConnectivityState;
+        if let Ok(mut current) = state.proximity_link.write() {
+            *current = None;
+        }
+        if state.connectivity_state() == ConnectivityState::Local {
+            state.set_connectivity_state(ConnectivityState::Offline);
+        }
+        if let Some(cmd_tx) = state.p2p_command_tx().await {
+            let _ = cmd_tx
+                .send(sovereign_p2p::P2pCommand::ProximityLinkDown)
+                .await;
+        }
+        return Ok(());
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = &state;
+        Ok(())
+    }
+}
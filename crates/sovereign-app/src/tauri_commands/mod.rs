@@ -6,10 +6,13 @@ pub mod browser;
 pub mod canvas;
 pub mod contacts;
 pub mod documents;
+pub mod meetings;
+pub mod windows;
 pub mod mobile;
 #[cfg(feature = "encryption")]
 pub mod pairing;
 pub mod pii;
+pub mod skills;
 pub mod suggestions;
 pub mod threads;
 pub mod voice;
@@ -22,8 +25,10 @@ use sovereign_core::content::ContentFields;
 use sovereign_core::interfaces::{FeedbackEvent, OrchestratorEvent};
 use sovereign_core::security::ActionDecision;
 use sovereign_db::GraphDB;
-use sovereign_db::schema::{Document, MessageDirection, ReadStatus, RelationType, Thread};
-use sovereign_skills::traits::{SkillContext, SkillDocument};
+use sovereign_db::schema::{
+    DocType, Document, MessageDirection, ReadStatus, RelationType, Sensitivity, Thread,
+};
+use sovereign_skills::traits::{SkillContext, SkillDocument, SkillOutput};
 use tauri::State;
 
 use crate::err::ToStringErr;
@@ -68,6 +73,34 @@ pub struct AppStatus {
     pub orchestrator_available: bool,
 }
 
+/// One turn of persisted chat scrollback.
+#[derive(Serialize)]
+pub struct ChatHistoryEntryDto {
+    /// "user" or "assistant".
+    pub role: String,
+    pub text: String,
+    /// RFC 3339 timestamp from the session log.
+    pub ts: String,
+}
+
+#[derive(Serialize)]
+pub struct SystemHealthDto {
+    /// "memory", "persistent" or "encrypted".
+    pub db_mode: String,
+    /// `None` when no model could be loaded at startup.
+    pub model: Option<ModelHealthDto>,
+}
+
+#[derive(Serialize)]
+pub struct ModelHealthDto {
+    pub router: String,
+    pub reasoning: String,
+    /// `None` while the model is busy generating.
+    pub reasoning_loaded: Option<bool>,
+    /// Size of the model weights offloaded to the GPU; 0 on CPU.
+    pub vram_estimate_mb: f64,
+}
+
 #[derive(Serialize)]
 pub struct DocSummary {
     pub id: String,
@@ -98,10 +131,63 @@ pub struct FullDocument {
     pub body: String,
     pub images: Vec<ContentImageDto>,
     pub videos: Vec<ContentVideoDto>,
+    pub files: Vec<ContentFileDto>,
     pub thread_id: String,
     pub is_owned: bool,
+    pub doc_type: DocType,
     pub created_at: String,
     pub modified_at: String,
+    pub read_later: bool,
+    pub favorite: bool,
+    pub sensitivity: Sensitivity,
+}
+
+/// A document waiting in the reading queue.
+#[derive(Serialize)]
+pub struct ReadingQueueEntryDto {
+    pub id: String,
+    pub title: String,
+    pub thread_id: String,
+    pub doc_type: DocType,
+    pub queued_at: String,
+    pub words: usize,
+    pub reading_minutes: u64,
+}
+
+/// A reminder on a document; `task_index` when it is about one of the
+/// document's checklist items.
+#[derive(Serialize)]
+pub struct ReminderDto {
+    pub id: String,
+    pub doc_id: String,
+    pub task_index: Option<usize>,
+    pub text: String,
+    pub due_at: String,
+    pub fired_at: Option<String>,
+}
+
+impl From<sovereign_db::schema::Reminder> for ReminderDto {
+    fn from(r: sovereign_db::schema::Reminder) -> Self {
+        Self {
+            id: r.id_string().unwrap_or_default(),
+            doc_id: r.document_id,
+            task_index: r.task_index,
+            text: r.text,
+            due_at: r.due_at.to_rfc3339(),
+            fired_at: r.fired_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+/// Unsaved edits journaled before a crash, next to what was saved.
+#[derive(Serialize)]
+pub struct RecoverableEditDto {
+    pub doc_id: String,
+    pub title: String,
+    pub body: String,
+    pub journaled_at: String,
+    pub saved_title: String,
+    pub saved_at: String,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -118,6 +204,31 @@ pub struct ContentVideoDto {
     pub thumbnail_path: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ContentFileDto {
+    pub path: String,
+    pub name: String,
+}
+
+/// An attachment's bytes, for the viewer and for saving a copy.
+#[derive(Serialize)]
+pub struct AttachmentDataDto {
+    pub name: String,
+    pub mime: String,
+    pub data_base64: String,
+}
+
+/// A sealed document share, for "Save as" and the QR code.
+#[derive(Serialize)]
+pub struct DocumentShareDto {
+    pub file_name: String,
+    pub data_base64: String,
+    /// `sovereign-share:…` text when the share fits in one QR code.
+    pub qr_payload: Option<String>,
+    pub expires_at: Option<String>,
+    pub missing_media: usize,
+}
+
 #[derive(Serialize)]
 pub struct CommitSummaryDto {
     pub id: String,
@@ -125,12 +236,38 @@ pub struct CommitSummaryDto {
     pub timestamp: String,
     pub snapshot_title: String,
     pub snapshot_preview: String,
+    /// PeerId of the device that signed the commit (its base64 public key
+    /// in builds without P2P). `None` for unsigned, older commits.
+    pub signed_by: Option<String>,
+    /// `"valid"`, `"invalid"` or `"unsigned"`.
+    pub signature_status: String,
+}
+
+/// An image stored by `store_pasted_image`.
+#[derive(Serialize)]
+pub struct PastedImageDto {
+    /// SHA-256; the body refers to the image as `media:<hash>`.
+    pub hash: String,
+    /// Local path, for the document's `images` list.
+    pub path: String,
+}
+
+/// A commit's full snapshot, for diffing against the current body.
+#[derive(Serialize)]
+pub struct CommitSnapshotDto {
+    pub title: String,
+    pub body: String,
 }
 
 #[derive(Serialize)]
 pub struct SkillInfo {
     pub skill_name: String,
     pub actions: Vec<SkillActionInfo>,
+    /// Signature status: "builtin", "verified", "untrusted", "unsigned",
+    /// or "tampered". Drives the badge in the skills panel.
+    pub trust: String,
+    /// Trusted publisher name, set only when `trust == "verified"`.
+    pub signer: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -152,6 +289,80 @@ pub struct SkillResultDto {
     pub structured_json: Option<String>,
 }
 
+/// What `execute_skill` hands the frontend, and `sovereign skill run` prints.
+impl From<SkillOutput> for SkillResultDto {
+    fn from(output: SkillOutput) -> Self {
+        match output {
+            SkillOutput::ContentUpdate(cf) => Self {
+                kind: "content_update".into(),
+                body: Some(cf.body),
+                images: Some(
+                    cf.images
+                        .into_iter()
+                        .map(|i| ContentImageDto {
+                            path: i.path,
+                            caption: i.caption,
+                        })
+                        .collect(),
+                ),
+                videos: Some(
+                    cf.videos
+                        .into_iter()
+                        .map(|v| ContentVideoDto {
+                            path: v.path,
+                            caption: v.caption,
+                            duration_secs: v.duration_secs,
+                            thumbnail_path: v.thumbnail_path,
+                        })
+                        .collect(),
+                ),
+                file_name: None,
+                file_mime: None,
+                file_data_base64: None,
+                structured_kind: None,
+                structured_json: None,
+            },
+            SkillOutput::File { name, mime_type, data } => {
+                use base64::Engine;
+                let b64 = base64::engine::general_purpose::STANDARD.encode(&data);
+                Self {
+                    kind: "file".into(),
+                    body: None,
+                    images: None,
+                    videos: None,
+                    file_name: Some(name),
+                    file_mime: Some(mime_type),
+                    file_data_base64: Some(b64),
+                    structured_kind: None,
+                    structured_json: None,
+                }
+            }
+            SkillOutput::StructuredData { kind, json } => Self {
+                kind: "structured_data".into(),
+                body: None,
+                images: None,
+                videos: None,
+                file_name: None,
+                file_mime: None,
+                file_data_base64: None,
+                structured_kind: Some(kind),
+                structured_json: Some(json),
+            },
+            SkillOutput::None => Self {
+                kind: "none".into(),
+                body: None,
+                images: None,
+                videos: None,
+                file_name: None,
+                file_mime: None,
+                file_data_base64: None,
+                structured_kind: None,
+                structured_json: None,
+            },
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub struct ModelEntryDto {
     pub filename: String,
@@ -189,6 +400,7 @@ pub struct CanvasDocDto {
     pub title: String,
     pub thread_id: String,
     pub is_owned: bool,
+    pub doc_type: DocType,
     pub spatial_x: f32,
     pub spatial_y: f32,
     pub created_at: String,
@@ -196,6 +408,44 @@ pub struct CanvasDocDto {
     pub reliability_classification: Option<String>,
     pub reliability_score: Option<f32>,
     pub source_url: Option<String>,
+    /// `#tag` markers in the body, for the canvas filter chips.
+    pub tags: Vec<String>,
+    /// Unticked checklist items, for the card's task badge.
+    pub open_tasks: usize,
+    /// Pinned to the taskbar.
+    pub favorite: bool,
+    /// Sealed cards ask for the password before opening.
+    pub sensitivity: Sensitivity,
+}
+
+/// What importing a source would create, per target thread.
+#[derive(Serialize)]
+pub struct SourceImportPreviewDto {
+    pub source_name: String,
+    pub total: usize,
+    pub threads: Vec<ImportThreadCountDto>,
+}
+
+#[derive(Serialize)]
+pub struct ImportThreadCountDto {
+    pub name: String,
+    pub documents: usize,
+}
+
+#[derive(Serialize)]
+pub struct SourceImportReportDto {
+    /// Threads created (existing threads of the same name are reused).
+    pub threads_created: usize,
+    pub documents: usize,
+    /// `path: reason` for each file that could not be imported.
+    pub skipped: Vec<String>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct SourceImportProgressPayload {
+    pub done: usize,
+    pub total: usize,
+    pub current: String,
 }
 
 #[derive(Serialize)]
@@ -231,10 +481,22 @@ pub struct ContactDetailDto {
     pub avatar: Option<String>,
     pub notes: String,
     pub addresses: Vec<ChannelAddressDto>,
+    pub is_owned: bool,
     pub conversations: Vec<ConversationDto>,
 }
 
-#[derive(Serialize)]
+/// The contact create/edit form. `avatar` is a `media:<sha256>` reference
+/// from `store_pasted_image`, or None for the initial.
+#[derive(Deserialize)]
+pub struct ContactFormDto {
+    pub name: String,
+    pub avatar: Option<String>,
+    pub notes: String,
+    pub addresses: Vec<ChannelAddressDto>,
+    pub is_owned: bool,
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct ChannelAddressDto {
     pub channel: String,
     pub address: String,
@@ -242,6 +504,23 @@ pub struct ChannelAddressDto {
     pub is_primary: bool,
 }
 
+#[derive(Serialize)]
+pub struct ComposeChannelDto {
+    pub channel: String,
+    pub connected: bool,
+    pub error: Option<String>,
+}
+
+/// A new conversation from the inbox compose form. `subject` is used for
+/// email only.
+#[derive(Deserialize)]
+pub struct ComposeMessageDto {
+    pub contact_id: String,
+    pub channel: String,
+    pub subject: Option<String>,
+    pub body: String,
+}
+
 #[derive(Serialize)]
 pub struct ConversationDto {
     pub id: String,
@@ -262,6 +541,9 @@ pub struct MessageDto {
     pub body: String,
     pub sent_at: String,
     pub read_status: String,
+    /// Documents attached to the message; their media shows in the
+    /// attachment strip.
+    pub attachment_doc_ids: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -280,6 +562,33 @@ pub struct AuthCheckResult {
     pub needs_onboarding: bool,
     pub needs_login: bool,
     pub crypto_enabled: bool,
+    /// Unlocking also needs the enrolled FIDO2 security key.
+    pub security_key_required: bool,
+    /// The device was remotely wiped; there is nothing left to unlock.
+    pub device_wiped: bool,
+}
+
+#[derive(Serialize)]
+pub struct SecurityKeyDto {
+    pub path: String,
+    pub name: String,
+}
+
+#[derive(Serialize)]
+pub struct SecurityKeyRecoveryDto {
+    pub recovered: bool,
+    pub approvals: u32,
+    pub threshold: u32,
+    pub pending: u32,
+}
+
+#[derive(Serialize)]
+pub struct KeyRotationEntryDto {
+    pub at: String,
+    /// `"kek"`, or the document-key scope that was rotated.
+    pub scope: String,
+    pub keys: u64,
+    pub resumed: bool,
 }
 
 #[derive(Serialize)]
@@ -295,6 +604,8 @@ pub struct UserProfileDto {
     pub nickname: Option<String>,
     pub bubble_style: String,
     pub display_name: Option<String>,
+    /// `"standard"`, `"vim"` or `"emacs"`.
+    pub editor_keymap: String,
 }
 
 #[derive(Deserialize)]
@@ -302,6 +613,15 @@ pub struct SaveProfileDto {
     pub nickname: Option<String>,
     pub bubble_style: Option<String>,
     pub display_name: Option<String>,
+    pub editor_keymap: Option<String>,
+}
+
+/// The user profiles on this machine (see `sovereign_core::profiles`).
+#[derive(Serialize)]
+pub struct ProfilesDto {
+    pub active: String,
+    /// The default profile first, then the rest by name.
+    pub profiles: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -316,7 +636,158 @@ pub struct AppConfigDto {
     pub crypto_keystroke_enabled: bool,
     pub crypto_max_login_attempts: u32,
     pub crypto_lockout_seconds: u32,
+    pub crypto_auto_lock_minutes: u32,
     pub ui_theme: String,
+    pub ui_scale: f64,
+    pub ui_editor_font_size: u32,
+    pub ui_editor_font_family: String,
+    pub ui_shortcuts: std::collections::BTreeMap<String, String>,
+    pub voice_enabled: bool,
+    pub voice_source: String,
+    pub voice_jiminy_ws_url: String,
+    pub voice_announce_reminders: bool,
+    pub comms_enabled: bool,
+    pub comms_poll_interval_secs: u64,
+    pub p2p_enabled: bool,
+    pub p2p_device_name: String,
+    pub p2p_enable_mdns: bool,
+    pub p2p_wifi_only: bool,
+    pub p2p_serve_viewer: bool,
+    pub skills_max_concurrent_jobs: usize,
+    pub autonomy_auto_approve_after: u32,
+    pub metrics_enabled: bool,
+    pub metrics_retention_days: u32,
+    pub relevance_enabled: bool,
+    pub relevance_half_life_days: u32,
+    /// Where `update_config` writes.
+    pub config_path: String,
+}
+
+/// Fields Settings may change. `None` keeps the current value. Crypto and
+/// database settings are deliberately absent: they are not editable from
+/// the UI.
+#[derive(Deserialize, Default)]
+pub struct ConfigUpdateDto {
+    pub ai_model_dir: Option<String>,
+    pub ai_n_gpu_layers: Option<i32>,
+    pub ai_n_ctx: Option<u32>,
+    pub ai_prompt_format: Option<String>,
+    pub ui_theme: Option<String>,
+    pub ui_scale: Option<f64>,
+    pub ui_editor_font_size: Option<u32>,
+    pub ui_editor_font_family: Option<String>,
+    pub ui_shortcuts: Option<std::collections::BTreeMap<String, String>>,
+    pub voice_enabled: Option<bool>,
+    pub voice_source: Option<String>,
+    pub voice_jiminy_ws_url: Option<String>,
+    pub voice_announce_reminders: Option<bool>,
+    pub comms_enabled: Option<bool>,
+    pub comms_poll_interval_secs: Option<u64>,
+    pub p2p_enabled: Option<bool>,
+    pub p2p_device_name: Option<String>,
+    pub p2p_enable_mdns: Option<bool>,
+    pub p2p_wifi_only: Option<bool>,
+    pub p2p_serve_viewer: Option<bool>,
+    pub skills_max_concurrent_jobs: Option<usize>,
+    pub autonomy_auto_approve_after: Option<u32>,
+    pub metrics_enabled: Option<bool>,
+    pub metrics_retention_days: Option<u32>,
+    pub relevance_enabled: Option<bool>,
+    pub relevance_half_life_days: Option<u32>,
+}
+
+impl ConfigUpdateDto {
+    pub fn apply_to(self, config: &mut sovereign_core::config::AppConfig) {
+        fn set<T>(slot: &mut T, value: Option<T>) {
+            if let Some(v) = value {
+                *slot = v;
+            }
+        }
+        set(&mut config.ai.model_dir, self.ai_model_dir);
+        set(&mut config.ai.n_gpu_layers, self.ai_n_gpu_layers);
+        set(&mut config.ai.n_ctx, self.ai_n_ctx);
+        set(&mut config.ai.prompt_format, self.ai_prompt_format);
+        set(&mut config.ui.theme, self.ui_theme);
+        set(&mut config.ui.scale, self.ui_scale);
+        set(&mut config.ui.editor_font_size, self.ui_editor_font_size);
+        set(&mut config.ui.editor_font_family, self.ui_editor_font_family);
+        set(&mut config.ui.shortcuts, self.ui_shortcuts);
+        set(&mut config.voice.enabled, self.voice_enabled);
+        set(&mut config.voice.voice_source, self.voice_source);
+        set(&mut config.voice.jiminy_ws_url, self.voice_jiminy_ws_url);
+        set(&mut config.voice.announce_reminders, self.voice_announce_reminders);
+        set(&mut config.comms.enabled, self.comms_enabled);
+        set(&mut config.comms.poll_interval_secs, self.comms_poll_interval_secs);
+        set(&mut config.p2p.enabled, self.p2p_enabled);
+        set(&mut config.p2p.device_name, self.p2p_device_name);
+        set(&mut config.p2p.enable_mdns, self.p2p_enable_mdns);
+        set(&mut config.p2p.wifi_only, self.p2p_wifi_only);
+        set(&mut config.p2p.serve_viewer, self.p2p_serve_viewer);
+        set(&mut config.skills.max_concurrent_jobs, self.skills_max_concurrent_jobs);
+        set(&mut config.autonomy.auto_approve_after, self.autonomy_auto_approve_after);
+        set(&mut config.metrics.enabled, self.metrics_enabled);
+        set(&mut config.metrics.retention_days, self.metrics_retention_days);
+        set(&mut config.relevance.enabled, self.relevance_enabled);
+        set(&mut config.relevance.half_life_days, self.relevance_half_life_days);
+    }
+}
+
+/// A frame the webview drew, reported by the frame profiler.
+#[derive(Deserialize)]
+pub struct FrameTimeDto {
+    pub duration_ms: f64,
+    /// How long before the report the frame ended.
+    pub ago_ms: f64,
+}
+
+/// One optional subsystem, as `crate::features::Feature` reports it.
+#[derive(Serialize)]
+pub struct FeatureDto {
+    pub id: String,
+    pub label: String,
+    /// Built into this binary.
+    pub compiled: bool,
+    /// Compiled and switched on in the saved config.
+    pub enabled: bool,
+    /// Settings may switch it on and off.
+    pub toggleable: bool,
+    /// Whether it is running this session; `None` when it is off or has
+    /// nothing to check.
+    pub healthy: Option<bool>,
+}
+
+/// What `update_config` did: saved and hot-applied, plus the sections
+/// that only take effect after a restart.
+#[derive(Serialize)]
+pub struct ConfigApplyReportDto {
+    pub restart_required: Vec<String>,
+}
+
+/// `action` is one of `lockdown`, `silent_alert`, `decoy`, `wipe`.
+#[derive(Deserialize)]
+#[allow(dead_code)] // only read with encryption feature
+pub struct CanaryRuleDto {
+    pub phrase: String,
+    pub action: String,
+}
+
+#[cfg(feature = "encryption")]
+impl CanaryRuleDto {
+    pub fn to_rules(
+        dtos: &[CanaryRuleDto],
+    ) -> Result<Vec<sovereign_crypto::canary::CanaryRule>, String> {
+        dtos.iter()
+            .filter(|d| !d.phrase.trim().is_empty())
+            .map(|d| {
+                let action = serde_json::from_value(serde_json::Value::String(d.action.clone()))
+                    .map_err(|_| format!("unknown canary action '{}'", d.action))?;
+                Ok(sovereign_crypto::canary::CanaryRule {
+                    phrase: d.phrase.trim().to_string(),
+                    action,
+                })
+            })
+            .collect()
+    }
 }
 
 #[derive(Deserialize)]
@@ -327,8 +798,22 @@ pub struct OnboardingData {
     pub seed_sample_data: bool,
     pub password: Option<String>,
     pub duress_password: Option<String>,
-    pub canary_phrase: Option<String>,
+    /// Canary phrases and what each one triggers.
+    #[serde(default)]
+    pub canary_rules: Vec<CanaryRuleDto>,
     pub keystrokes: Vec<Vec<KeystrokeSampleDto>>,
+    /// Bind the security key enrolled via `enroll_security_key`.
+    #[serde(default)]
+    pub use_security_key: bool,
+    /// Return the master key as a recovery phrase (passphrase-only stores).
+    #[serde(default)]
+    pub recovery_phrase: bool,
+}
+
+#[derive(Serialize)]
+pub struct OnboardingResult {
+    /// 24-word recovery phrase, to show once. Never stored.
+    pub recovery_phrase: Option<String>,
 }
 
 #[derive(Deserialize, Clone)]
@@ -339,3 +824,19 @@ pub struct KeystrokeSampleDto {
     pub release_ms: u64,
 }
 
+#[cfg(feature = "encryption")]
+impl KeystrokeSampleDto {
+    pub fn to_profile(samples: &[KeystrokeSampleDto]) -> sovereign_crypto::keystroke::TypingProfile {
+        sovereign_crypto::keystroke::TypingProfile {
+            samples: samples
+                .iter()
+                .map(|s| sovereign_crypto::keystroke::KeystrokeSample {
+                    key: s.key.clone(),
+                    press_ms: s.press_ms,
+                    release_ms: s.release_ms,
+                })
+                .collect(),
+        }
+    }
+}
+
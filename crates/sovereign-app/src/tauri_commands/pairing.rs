@@ -5,9 +5,14 @@
 //!     The PairPayload (salt + AccountKey + this device's PeerId/name)
 //!     is stored in `state.pending_pairing` so the existing device can
 //!     later confirm the new device on first contact (Phase 3 work).
-//!   - `forget_paired_device` — removes a peer from the paired list.
+//!   - `revoke_paired_device` — removes a peer from the paired list and
+//!     cuts it off in the running node.
 //!   - `list_paired_devices`, `get_local_peer_id` — read-side helpers
 //!     for the Settings panel.
+//!   - `get_sync_history` — recent sync sessions with one device.
+//!   - `set_device_sync_policy`, `rename_paired_device`,
+//!     `set_device_sync_paused`, `set_device_trust`,
+//!     `set_device_addresses` — per-device edits from the Devices panel.
 //!
 //! New-device side:
 //!   - `consume_pair_qr_preview` — decrypts the QR with the user-typed
@@ -67,6 +72,10 @@ pub struct PairPayloadPreviewDto {
 pub struct CompletePairedOnboardingInput {
     pub qr_payload_b64: String,
     pub pin: String,
+    /// Where the source device can be reached (`ip:port` or multiaddr),
+    /// for networks where mDNS can't find it. Optional.
+    #[serde(default)]
+    pub source_address: Option<String>,
     /// New local passphrase for THIS device. Different from the
     /// existing device's passphrase is fine — it only protects the
     /// wrapping of the imported AccountKey on this device.
@@ -75,7 +84,8 @@ pub struct CompletePairedOnboardingInput {
     /// Standard onboarding inputs.
     pub nickname: Option<String>,
     pub bubble_style: Option<String>,
-    pub canary_phrase: Option<String>,
+    #[serde(default)]
+    pub canary_rules: Vec<super::CanaryRuleDto>,
     pub seed_sample_data: bool,
 }
 
@@ -84,6 +94,102 @@ pub struct PairedDeviceDto {
     pub peer_id: String,
     pub device_name: String,
     pub paired_at: String,
+    pub last_seen: Option<String>,
+    pub last_sync: Option<String>,
+    pub sync_paused: bool,
+    pub trust: PeerTrustDto,
+    /// Manually entered addresses, as canonical multiaddrs.
+    pub addresses: Vec<String>,
+    pub sync_policy: SyncPolicyDto,
+}
+
+/// One finished sync session with a paired device.
+#[derive(Serialize)]
+pub struct SyncHistoryEntryDto {
+    pub at: String,
+    pub items_synced: u32,
+    pub docs_pulled: u32,
+    pub docs_pushed: u32,
+    pub commits_received: u32,
+    pub commits_sent: u32,
+    pub bytes_received: u64,
+    pub bytes_sent: u64,
+    pub duration_ms: u64,
+}
+
+/// What this device syncs to one paired device. Mirrors
+/// `sovereign_p2p::SyncPolicy`; empty `include_threads` = every thread.
+#[derive(Serialize, Deserialize, Default)]
+pub struct SyncPolicyDto {
+    #[serde(default)]
+    pub include_threads: Vec<String>,
+    #[serde(default)]
+    pub exclude_threads: Vec<String>,
+    #[serde(default)]
+    pub exclude_external: bool,
+    #[serde(default)]
+    pub max_document_bytes: Option<u64>,
+    #[serde(default)]
+    pub exclude_documents: Vec<String>,
+}
+
+#[cfg(feature = "p2p")]
+impl From<sovereign_p2p::SyncPolicy> for SyncPolicyDto {
+    fn from(p: sovereign_p2p::SyncPolicy) -> Self {
+        Self {
+            include_threads: p.include_threads,
+            exclude_threads: p.exclude_threads,
+            exclude_external: p.exclude_external,
+            max_document_bytes: p.max_document_bytes,
+            exclude_documents: p.exclude_documents,
+        }
+    }
+}
+
+#[cfg(feature = "p2p")]
+impl From<SyncPolicyDto> for sovereign_p2p::SyncPolicy {
+    fn from(p: SyncPolicyDto) -> Self {
+        Self {
+            include_threads: p.include_threads,
+            exclude_threads: p.exclude_threads,
+            exclude_external: p.exclude_external,
+            max_document_bytes: p.max_document_bytes,
+            exclude_documents: p.exclude_documents,
+        }
+    }
+}
+
+/// Trust tier of a paired device. Mirrors `sovereign_p2p::pairing::PeerTrust`.
+#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerTrustDto {
+    #[default]
+    Full,
+    ReadOnly,
+    GuardianOnly,
+}
+
+#[cfg(feature = "p2p")]
+impl From<sovereign_p2p::pairing::PeerTrust> for PeerTrustDto {
+    fn from(t: sovereign_p2p::pairing::PeerTrust) -> Self {
+        use sovereign_p2p::pairing::PeerTrust;
+        match t {
+            PeerTrust::Full => Self::Full,
+            PeerTrust::ReadOnly => Self::ReadOnly,
+            PeerTrust::GuardianOnly => Self::GuardianOnly,
+        }
+    }
+}
+
+#[cfg(feature = "p2p")]
+impl From<PeerTrustDto> for sovereign_p2p::pairing::PeerTrust {
+    fn from(t: PeerTrustDto) -> Self {
+        match t {
+            PeerTrustDto::Full => Self::Full,
+            PeerTrustDto::ReadOnly => Self::ReadOnly,
+            PeerTrustDto::GuardianOnly => Self::GuardianOnly,
+        }
+    }
 }
 
 /// Existing device → frontend: produce a pairing QR + code (P3.1).
@@ -101,67 +207,7 @@ pub async fn generate_pair_qr(
     crate::tauri_state::require_main_webview(&webview)?;
     #[cfg(feature = "p2p")]
     {
-        let account_key = state
-            .account_key()
-            .await
-            .ok_or_else(|| "pairing unavailable: account key not loaded".to_string())?;
-        let cmd_tx = state.p2p_command_tx().await.ok_or_else(|| {
-            "pairing requires the P2P node — enable sync in Settings first".to_string()
-        })?;
-        let source_peer_id = peer_id_from_state(&state).await;
-        if source_peer_id.is_empty() {
-            return Err("pairing unavailable: p2p identity not loaded".to_string());
-        }
-        let source_device_name = state.config.p2p.device_name.clone();
-        // The MasterKey salt is released to the new device during the
-        // handshake (it used to travel in the QR).
-        let salt = std::fs::read(crate::setup::crypto_dir().join("salt"))
-            .map_err(|e| format!("read salt: {e}"))?;
-        // Concrete listen addrs collected by the event translator; the
-        // new device falls back to mDNS discovery when empty.
-        let addrs = state
-            .p2p_listen_addrs
-            .read()
-            .map(|a| a.clone())
-            .unwrap_or_default();
-
-        let offer = sovereign_p2p::PairingOffer::new(
-            source_peer_id,
-            source_device_name.clone(),
-            addrs,
-            sovereign_p2p::pairing_offer::OFFER_TTL_SECONDS,
-        );
-        let code = pp::generate_pairing_code();
-
-        // Argon2id stretch (~0.5 s) off the async runtime.
-        let offer_for_kdf = offer.clone();
-        let code_for_kdf = code.clone();
-        let handshake_key = tauri::async_runtime::spawn_blocking(move || {
-            sovereign_p2p::pairing_offer::derive_handshake_key(&code_for_kdf, &offer_for_kdf)
-        })
-        .await
-        .map_err(|e| format!("kdf task: {e}"))?
-        .str_err()?;
-
-        cmd_tx
-            .send(sovereign_p2p::P2pCommand::SetPairingOffer {
-                offer: Box::new(sovereign_p2p::ActivePairingOffer::new(
-                    offer.offer_id.clone(),
-                    handshake_key,
-                    offer.expires_at,
-                    salt,
-                    *account_key.as_bytes(),
-                    source_device_name,
-                )),
-            })
-            .await
-            .map_err(|e| format!("arm pairing offer: {e}"))?;
-
-        return Ok(GeneratePairQrResult {
-            qr_payload_b64: offer.encode().str_err()?,
-            pin: code,
-            expires_at: offer.expires_at,
-        });
+        return arm_pairing_offer(&state).await;
     }
     #[allow(unreachable_code)]
     {
@@ -170,6 +216,71 @@ pub async fn generate_pair_qr(
     }
 }
 
+/// Arm the P2P node with a fresh pairing offer and return what the new
+/// device needs: the offer to scan and the code to type. Shared with the
+/// daemon's `pairing.offer`.
+#[cfg(feature = "p2p")]
+pub(crate) async fn arm_pairing_offer(state: &AppState) -> Result<GeneratePairQrResult, String> {
+    let account_key = state
+        .account_key()
+        .await
+        .ok_or_else(|| "pairing unavailable: account key not loaded".to_string())?;
+    let cmd_tx = state.p2p_command_tx().await.ok_or_else(|| {
+        "pairing requires the P2P node — enable sync in Settings first".to_string()
+    })?;
+    let source_peer_id = peer_id_from_state(state).await;
+    if source_peer_id.is_empty() {
+        return Err("pairing unavailable: p2p identity not loaded".to_string());
+    }
+    let source_device_name = state.config.p2p.device_name.clone();
+    // The MasterKey salt is released to the new device during the
+    // handshake (it used to travel in the QR).
+    let salt = std::fs::read(crate::setup::crypto_dir().join("salt"))
+        .map_err(|e| format!("read salt: {e}"))?;
+    // Concrete listen addrs collected by the event translator (link
+    // addresses first); the new device falls back to mDNS discovery
+    // when empty.
+    let addrs = state.listen_addrs();
+
+    let offer = sovereign_p2p::PairingOffer::new(
+        source_peer_id,
+        source_device_name.clone(),
+        addrs,
+        sovereign_p2p::pairing_offer::OFFER_TTL_SECONDS,
+    );
+    let code = pp::generate_pairing_code();
+
+    // Argon2id stretch (~0.5 s) off the async runtime.
+    let offer_for_kdf = offer.clone();
+    let code_for_kdf = code.clone();
+    let handshake_key = tauri::async_runtime::spawn_blocking(move || {
+        sovereign_p2p::pairing_offer::derive_handshake_key(&code_for_kdf, &offer_for_kdf)
+    })
+    .await
+    .map_err(|e| format!("kdf task: {e}"))?
+    .str_err()?;
+
+    cmd_tx
+        .send(sovereign_p2p::P2pCommand::SetPairingOffer {
+            offer: Box::new(sovereign_p2p::ActivePairingOffer::new(
+                offer.offer_id.clone(),
+                handshake_key,
+                offer.expires_at,
+                salt,
+                *account_key.as_bytes(),
+                source_device_name,
+            )),
+        })
+        .await
+        .map_err(|e| format!("arm pairing offer: {e}"))?;
+
+    Ok(GeneratePairQrResult {
+        qr_payload_b64: offer.encode().str_err()?,
+        pin: code,
+        expires_at: offer.expires_at,
+    })
+}
+
 /// New device: decode-only preview of a pairing QR. Returns metadata
 /// for a confirmation screen ("you are about to pair with 'Alice's
 /// laptop'"). Doesn't persist anything; that's `complete_onboarding_paired`.
@@ -238,7 +349,18 @@ pub async fn complete_onboarding_paired(
 
         // 1. Decode the scanned offer (plaintext — the QR carries no
         //    secrets since P3.1) and mint this device's id up front.
-        let offer = sovereign_p2p::PairingOffer::decode(&input.qr_payload_b64).str_err()?;
+        let mut offer = sovereign_p2p::PairingOffer::decode(&input.qr_payload_b64).str_err()?;
+        // A typed address goes first, ahead of the offer's own hints.
+        let source_address = match input.source_address.as_deref().map(str::trim) {
+            Some(a) if !a.is_empty() => Some(
+                sovereign_p2p::address_book::normalize_peer_address(a, &offer.source_peer_id)
+                    .str_err()?,
+            ),
+            _ => None,
+        };
+        if let Some(ref address) = source_address {
+            offer.addrs.insert(0, address.clone());
+        }
         let crypto_dir = profile_dir.join("crypto");
         std::fs::create_dir_all(&crypto_dir).str_err()?;
         let device_id = crate::setup::load_or_create_device_id().str_err()?;
@@ -289,6 +411,7 @@ pub async fn complete_onboarding_paired(
             &outcome.secrets.salt,
             &device_id,
             &imported_account_key,
+            crate::setup::new_store_kdf(&state.config.crypto),
         )
         .str_err()?;
         auth_store
@@ -311,17 +434,11 @@ pub async fn complete_onboarding_paired(
         }
         profile.save(profile_dir).str_err()?;
 
-        // 5. Optional canary phrase — same shape as fresh onboarding.
-        if let Some(ref phrase) = input.canary_phrase {
+        // 5. Optional canary phrases — same shape as fresh onboarding.
+        let canary_rules = super::CanaryRuleDto::to_rules(&input.canary_rules)?;
+        if !canary_rules.is_empty() {
             if let Ok(auth_result) = auth_store.authenticate(input.password.as_bytes()) {
-                let canary = sovereign_crypto::canary::CanaryStore::encrypt(
-                    phrase,
-                    auth_result.kek.as_bytes(),
-                )
-                .str_err()?;
-                canary
-                    .save(&crypto_dir.join("canary.store"))
-                    .str_err()?;
+                crate::canary::save_rules(&state, &canary_rules, auth_result.kek.as_bytes())?;
             }
         }
 
@@ -340,11 +457,14 @@ pub async fn complete_onboarding_paired(
         let mut manager = sovereign_p2p::pairing::PairingManager::new(
             crypto_dir.join("paired_devices.json"),
         );
-        manager.add_device(sovereign_p2p::pairing::PairedDevice::with_key(
+        let mut source = sovereign_p2p::pairing::PairedDevice::with_key(
             offer.source_peer_id.clone(),
             outcome.secrets.source_device_name.clone(),
             pair_key,
-        ));
+        );
+        // An address that was needed to pair will be needed to sync.
+        source.addresses.extend(source_address);
+        manager.add_device(source);
         if let Err(e) = manager.save(&store_key) {
             tracing::warn!("Failed to persist paired_devices.json: {e}");
         }
@@ -376,25 +496,324 @@ pub async fn list_paired_devices(
     state.require_unlocked(&webview).await?;
     #[cfg(feature = "p2p")]
     {
-        if let Some(ref manager) = *state.pairing_manager.read().await {
-            return Ok(manager
+        return Ok(paired_devices(&state).await);
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = &state;
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(feature = "p2p")]
+pub(crate) async fn paired_devices(state: &AppState) -> Vec<PairedDeviceDto> {
+    let guard = state.pairing_manager.read().await;
+    let Some(manager) = guard.as_ref() else {
+        return Vec::new();
+    };
+    manager
+        .list_devices()
+        .into_iter()
+        .map(|d| PairedDeviceDto {
+            peer_id: d.peer_id.clone(),
+            device_name: d.device_name.clone(),
+            paired_at: d.paired_at.clone(),
+            last_seen: d.last_seen.clone(),
+            last_sync: d.last_sync.clone(),
+            sync_paused: d.sync_paused,
+            trust: d.trust.into(),
+            addresses: d.addresses.clone(),
+            sync_policy: d.sync_policy.clone().into(),
+        })
+        .collect()
+}
+
+/// The latest sync sessions with `peer_id`, newest first (at most
+/// `limit`, default 20).
+#[tauri::command]
+pub async fn get_sync_history(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    peer_id: String,
+    limit: Option<usize>,
+) -> Result<Vec<SyncHistoryEntryDto>, String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        return Ok(crate::sync_startup::sync_history_log()
+            .for_peer(&peer_id, limit.unwrap_or(20))
+            .into_iter()
+            .map(|r| SyncHistoryEntryDto {
+                at: r.at.to_rfc3339(),
+                items_synced: r.items_synced,
+                docs_pulled: r.progress.docs_to_pull,
+                docs_pushed: r.progress.docs_to_push,
+                commits_received: r.progress.commits_received,
+                commits_sent: r.progress.commits_sent,
+                bytes_received: r.progress.bytes_received,
+                bytes_sent: r.progress.bytes_sent,
+                duration_ms: r.progress.elapsed_ms,
+            })
+            .collect());
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = (&state, &peer_id, limit);
+        Ok(Vec::new())
+    }
+}
+
+/// Replace what this device syncs to `peer_id` (threads, external
+/// documents, size cap). Takes effect on the next sync with that device.
+#[tauri::command]
+pub async fn set_device_sync_policy(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    peer_id: String,
+    policy: SyncPolicyDto,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        return edit_paired_device(&state, &peer_id, |m| {
+            m.set_sync_policy(&peer_id, policy.into())
+        })
+        .await;
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = (&state, &peer_id, &policy);
+        Err("sync policies require the p2p feature".to_string())
+    }
+}
+
+/// Keep a document off exactly the paired devices in `peer_ids`, and send
+/// it to the others again, by editing each device's sync policy.
+#[tauri::command]
+pub async fn set_document_sync_exclusions(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    doc_id: String,
+    peer_ids: Vec<String>,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        return edit_paired_device(&state, &doc_id, |m| {
+            let policies: Vec<(String, sovereign_p2p::SyncPolicy)> = m
                 .list_devices()
                 .into_iter()
-                .map(|d| PairedDeviceDto {
-                    peer_id: d.peer_id.clone(),
-                    device_name: d.device_name.clone(),
-                    paired_at: d.paired_at.clone(),
-                })
-                .collect());
+                .map(|d| (d.peer_id.clone(), d.sync_policy.clone()))
+                .collect();
+            for (peer_id, mut policy) in policies {
+                policy.exclude_documents.retain(|id| *id != doc_id);
+                if peer_ids.contains(&peer_id) {
+                    policy.exclude_documents.push(doc_id.clone());
+                }
+                m.set_sync_policy(&peer_id, policy);
+            }
+            true
+        })
+        .await;
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = (&state, &doc_id, &peer_ids);
+        Err("sync policies require the p2p feature".to_string())
+    }
+}
+
+/// Give a paired device a new display name.
+#[tauri::command]
+pub async fn rename_paired_device(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    peer_id: String,
+    device_name: String,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    let device_name = device_name.trim().to_string();
+    if device_name.is_empty() {
+        return Err("device name cannot be empty".into());
+    }
+    #[cfg(feature = "p2p")]
+    {
+        return edit_paired_device(&state, &peer_id, |m| m.rename_device(&peer_id, device_name))
+            .await;
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = (&state, &peer_id, &device_name);
+        Err("device management requires the p2p feature".to_string())
+    }
+}
+
+/// Pause or resume sync with one paired device. A paused device stays
+/// paired — remote wipe and key-shard backup keep working — but the node
+/// neither starts nor serves syncs with it until it is resumed.
+#[tauri::command]
+pub async fn set_device_sync_paused(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    peer_id: String,
+    paused: bool,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        edit_paired_device(&state, &peer_id, |m| m.set_sync_paused(&peer_id, paused)).await?;
+        if !paused {
+            crate::sync_startup::trigger_sync_for_all_paired(&state).await;
         }
+        return Ok(());
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = (&state, &peer_id, paused);
+        Err("device management requires the p2p feature".to_string())
     }
-    let _ = &state;
-    Ok(Vec::new())
 }
 
-/// Remove a paired device from this device's records.
+/// Set how far this device trusts a paired device: full sync, a
+/// read-only replica (sent everything, its pushes rejected) or
+/// guardian-only (backup shards and remote wipe, no document sync).
 #[tauri::command]
-pub async fn forget_paired_device(
+pub async fn set_device_trust(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    peer_id: String,
+    trust: PeerTrustDto,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        edit_paired_device(&state, &peer_id, |m| m.set_trust(&peer_id, trust.into())).await?;
+        if !matches!(trust, PeerTrustDto::GuardianOnly) {
+            crate::sync_startup::trigger_sync_for_all_paired(&state).await;
+        }
+        return Ok(());
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = (&state, &peer_id, trust);
+        Err("device management requires the p2p feature".to_string())
+    }
+}
+
+/// Replace the addresses this device dials to reach a paired device when
+/// mDNS can't find it. Each entry is `ip:port` or a multiaddr; they are
+/// stored as canonical multiaddrs and redialed until the device answers.
+#[tauri::command]
+pub async fn set_device_addresses(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    peer_id: String,
+    addresses: Vec<String>,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        let addresses = addresses
+            .iter()
+            .map(|a| a.trim())
+            .filter(|a| !a.is_empty())
+            .map(|a| sovereign_p2p::address_book::normalize_peer_address(a, &peer_id))
+            .collect::<Result<Vec<String>, _>>()
+            .str_err()?;
+        return edit_paired_device(&state, &peer_id, |m| m.set_addresses(&peer_id, addresses))
+            .await;
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = (&state, &peer_id, &addresses);
+        Err("device management requires the p2p feature".to_string())
+    }
+}
+
+/// Share this device's encryption keys with a paired device, so content
+/// encrypted here (backups, exports) opens there too. The UI calls this
+/// only after the user confirmed; the keys travel sealed to that
+/// device's identity key. Returns how many keys were sent; the outcome
+/// arrives as a `keys-shared` event.
+#[tauri::command]
+pub async fn share_device_keys(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    peer_id: String,
+) -> Result<u32, String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        let paired = state
+            .pairing_manager
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(|m| m.is_paired(&peer_id));
+        if !paired {
+            return Err(format!("{peer_id} is not a paired device"));
+        }
+        let cmd_tx = state
+            .p2p_command_tx()
+            .await
+            .ok_or_else(|| "P2P is not running".to_string())?;
+        let db = crate::sync_startup::key_exchange_db(
+            *state.persona.read().await,
+            state.encrypted_db.read().await.clone(),
+        )?;
+        let bundle = db.export_key_escrow().await.map_err(|e| e.to_string())?;
+        let count = bundle.key_count() as u32;
+        cmd_tx
+            .send(sovereign_p2p::P2pCommand::ShareKeys {
+                peer_id,
+                bundle: Box::new(bundle),
+            })
+            .await
+            .map_err(|_| "P2P node is not running".to_string())?;
+        return Ok(count);
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = (&state, &peer_id);
+        Err("device management requires the p2p feature".to_string())
+    }
+}
+
+/// Apply `edit` to the paired-device store, persist it, and re-push the
+/// pairing state to the running node. `edit` returns false when the
+/// peer isn't paired.
+#[cfg(feature = "p2p")]
+pub(crate) async fn edit_paired_device(
+    state: &AppState,
+    peer_id: &str,
+    edit: impl FnOnce(&mut sovereign_p2p::pairing::PairingManager) -> bool,
+) -> Result<(), String> {
+    {
+        let identity_key = state
+            .p2p_identity_key()
+            .await
+            .ok_or_else(|| "p2p identity key not loaded".to_string())?;
+        let store_key = sovereign_p2p::pairing::derive_store_key(&identity_key);
+        let mut guard = state.pairing_manager.write().await;
+        let manager = guard
+            .as_mut()
+            .ok_or_else(|| "paired devices not loaded".to_string())?;
+        if !edit(manager) {
+            return Err(format!("{peer_id} is not a paired device"));
+        }
+        manager
+            .save(&store_key)
+            .map_err(|e| format!("save paired_devices.json: {e}"))?;
+    } // drop the write guard before refreshing (which read-locks it)
+    crate::sync_startup::refresh_paired_peers(state).await;
+    Ok(())
+}
+
+/// Revoke a paired device: remove it from this device's records and cut
+/// it off in the running node (allow-list, sealing key, open
+/// connections). If it held a backup-key shard, the shards are rotated.
+#[tauri::command]
+pub async fn revoke_paired_device(
     webview: tauri::Webview,
     state: State<'_, AppState>,
     peer_id: String,
@@ -418,10 +837,24 @@ pub async fn forget_paired_device(
                     .map_err(|e| format!("save paired_devices.json: {e}"))?;
             }
         } // drop the write guard before refreshing (which read-locks it)
-          // P2P-001 + P2P-005: push the shrunken allow-list and pair-key
-          // map to the running node so the forgotten device can neither
-          // sync nor be sealed for this session.
+        // Drop any live session and connection first, then push the
+        // shrunken allow-list and pair-key map (P2P-001 + P2P-005) so the
+        // revoked device can neither sync nor be sealed for this session.
+        if let Some(cmd_tx) = state.p2p_command_tx().await {
+            let _ = cmd_tx
+                .send(sovereign_p2p::P2pCommand::RevokePeer {
+                    peer_id: peer_id.clone(),
+                })
+                .await;
+        }
         crate::sync_startup::refresh_paired_peers(&state).await;
+        // A revoked device may still hold a backup-key shard: rotate the
+        // shards so what it kept no longer counts toward a recovery.
+        if super::backup::holds_key_shard(&peer_id) {
+            if let Err(e) = super::backup::distribute_backup(&state, None).await {
+                tracing::warn!("shard refresh after revoking {peer_id} failed: {e}");
+            }
+        }
         return Ok(());
     }
     #[allow(unreachable_code)]
@@ -453,6 +886,116 @@ pub async fn trigger_sync_now(
     }
 }
 
+/// Ask a paired device for its manifest and report what syncing with it
+/// would move, without transferring anything. The answer arrives as a
+/// `sync-preview` event.
+#[tauri::command]
+pub async fn preview_sync(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    peer_id: String,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        let cmd_tx = state
+            .p2p_command_tx()
+            .await
+            .ok_or_else(|| "sync preview requires the P2P node — enable sync first".to_string())?;
+        return cmd_tx
+            .send(sovereign_p2p::P2pCommand::PreviewSync { peer_id })
+            .await
+            .map_err(|e| format!("queue sync preview: {e}"));
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = (&state, &peer_id);
+        Err("sync preview requires the p2p feature".to_string())
+    }
+}
+
+/// Fetch a paired device's read-only companion snapshot (it must have
+/// `serve_viewer` on). The answer arrives as a `viewer-snapshot` event.
+#[tauri::command]
+pub async fn fetch_viewer_snapshot(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    peer_id: String,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        let cmd_tx = state
+            .p2p_command_tx()
+            .await
+            .ok_or_else(|| "viewer snapshots require the P2P node — enable sync first".to_string())?;
+        return cmd_tx
+            .send(sovereign_p2p::P2pCommand::FetchViewerSnapshot { peer_id })
+            .await
+            .map_err(|e| format!("queue viewer snapshot: {e}"));
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = (&state, &peer_id);
+        Err("viewer snapshots require the p2p feature".to_string())
+    }
+}
+
+/// Ask for a lost or stolen paired device to be wiped. The order is
+/// signed here and sent to the other paired devices; it reaches the
+/// target only once one of the target's guardians approves it.
+#[tauri::command]
+pub async fn remote_wipe_device(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    peer_id: String,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        let cmd_tx = state
+            .p2p_command_tx()
+            .await
+            .ok_or_else(|| "remote wipe requires the P2P node — enable sync first".to_string())?;
+        return cmd_tx
+            .send(sovereign_p2p::P2pCommand::IssueRemoteWipe { target_peer_id: peer_id })
+            .await
+            .map_err(|e| format!("queue remote wipe: {e}"));
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = (&state, &peer_id);
+        Err("remote wipe requires the p2p feature".to_string())
+    }
+}
+
+/// Co-sign a wipe order this device was asked to approve as guardian
+/// (see the `wipe-cosign-requested` event).
+#[tauri::command]
+pub async fn approve_remote_wipe(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    order_id: String,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        let cmd_tx = state
+            .p2p_command_tx()
+            .await
+            .ok_or_else(|| "P2P node not running".to_string())?;
+        return cmd_tx
+            .send(sovereign_p2p::P2pCommand::ApproveRemoteWipe { order_id })
+            .await
+            .map_err(|e| format!("queue wipe approval: {e}"));
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = (&state, &order_id);
+        Err("remote wipe requires the p2p feature".to_string())
+    }
+}
+
 /// Disarm the active pairing offer (the user closed the pairing panel).
 /// No-op when nothing is armed or P2P isn't running.
 #[tauri::command]
@@ -485,6 +1028,7 @@ pub struct P2pSettingsDto {
     pub device_name: String,
     pub enable_mdns: bool,
     pub wifi_only: bool,
+    pub serve_viewer: bool,
     /// Whether the node is actually running this session.
     pub running: bool,
 }
@@ -506,6 +1050,7 @@ pub async fn get_p2p_settings(
             device_name: state.config.p2p.device_name.clone(),
             enable_mdns: state.config.p2p.enable_mdns,
             wifi_only: state.config.p2p.wifi_only,
+            serve_viewer: state.config.p2p.serve_viewer,
             running: state.p2p_command_tx().await.is_some(),
         });
     }
@@ -518,6 +1063,7 @@ pub async fn get_p2p_settings(
             device_name: String::new(),
             enable_mdns: false,
             wifi_only: false,
+            serve_viewer: false,
             running: false,
         })
     }
@@ -548,6 +1094,40 @@ pub async fn resolve_sync_conflict_keep_mine(
     Ok(())
 }
 
+/// Resolve a body merge conflict with the text the user settled on in
+/// the conflict panel. The resolution is committed to the document's
+/// version history, and recorded in its CRDT history on the next sync so
+/// peers take it as an ordinary edit.
+#[tauri::command]
+pub async fn resolve_sync_merge(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    doc_id: String,
+    body: String,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    let doc = state.db.get_document(&doc_id).await.str_err()?;
+    let mut fields = sovereign_core::content::ContentFields::parse(&doc.content);
+    if fields.body != body {
+        fields.body = body;
+        state
+            .db
+            .update_document(&doc_id, None, Some(&fields.serialize()))
+            .await
+            .map_err(|e| format!("update document: {e}"))?;
+    }
+    state
+        .db
+        .commit_document(&doc_id, "Resolve sync conflict")
+        .await
+        .map_err(|e| format!("commit document: {e}"))?;
+    #[cfg(feature = "p2p")]
+    {
+        crate::sync_startup::trigger_sync_for_all_paired(&state).await;
+    }
+    Ok(())
+}
+
 /// Return this device's libp2p PeerId for display in the Settings
 /// panel. Empty string if the p2p identity isn't loaded yet OR the
 /// build doesn't include the p2p feature.
@@ -560,8 +1140,28 @@ pub async fn get_local_peer_id(
     Ok(peer_id_from_state(&state).await)
 }
 
+/// The addresses this device listens on, for typing into another
+/// device's address book when mDNS can't connect them. Empty until the
+/// P2P node is listening.
+#[tauri::command]
+pub async fn get_local_addresses(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    state.require_unlocked(&webview).await?;
+    #[cfg(feature = "p2p")]
+    {
+        return Ok(state.listen_addrs());
+    }
+    #[allow(unreachable_code)]
+    {
+        let _ = &state;
+        Ok(Vec::new())
+    }
+}
+
 #[cfg(feature = "p2p")]
-async fn peer_id_from_state(state: &AppState) -> String {
+pub(crate) async fn peer_id_from_state(state: &AppState) -> String {
     match state.p2p_identity_key().await {
         Some(p2p_key) => match sovereign_p2p::identity::derive_keypair(&p2p_key) {
             Ok(kp) => kp.public().to_peer_id().to_string(),
@@ -572,6 +1172,6 @@ async fn peer_id_from_state(state: &AppState) -> String {
 }
 
 #[cfg(not(feature = "p2p"))]
-async fn peer_id_from_state(_state: &AppState) -> String {
+pub(crate) async fn peer_id_from_state(_state: &AppState) -> String {
     String::new()
 }
@@ -547,6 +547,25 @@ pub async fn generate_password(_policy: serde_json::Value) -> Result<String, Str
     Err("Password generation requires the encryption feature to be enabled at build time".to_string())
 }
 
+/// Copy a secret to the clipboard, hidden from clipboard managers where the
+/// platform allows and cleared after `clear_after_secs` (default 30).
+/// Works before unlock so onboarding can copy the recovery phrase.
+#[tauri::command]
+pub async fn secure_copy(text: String, clear_after_secs: Option<u64>) -> Result<(), String> {
+    let clear_after = clear_after_secs
+        .map(std::time::Duration::from_secs)
+        .unwrap_or(crate::secure_clipboard::DEFAULT_CLEAR_AFTER);
+    let text = zeroize::Zeroizing::new(text);
+    crate::secure_clipboard::copy(&text, clear_after)
+}
+
+/// Clear a secret copied with `secure_copy` if it is still on the clipboard.
+#[tauri::command]
+pub async fn clear_secure_clipboard() -> Result<(), String> {
+    crate::secure_clipboard::clear();
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Signup capture (8d) — high-level multi-write command
 // ---------------------------------------------------------------------------
@@ -862,7 +881,7 @@ pub async fn resolve_pii_tokens(
 
     let body = match source_kind.as_str() {
         "document" => {
-            let doc = state.db.get_document(&source_id).await.str_err()?;
+            let doc = crate::sealed::readable(&state, &source_id).await?;
             match access_level {
                 AccessLevel::RawOriginal => {
                     let enc = doc.body_raw_encrypted.as_deref().ok_or_else(|| {
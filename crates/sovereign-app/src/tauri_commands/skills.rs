@@ -0,0 +1,202 @@
+use super::*;
+
+use sovereign_skills::manifest::Capability;
+
+// ---------------------------------------------------------------------------
+// Skill permissions — per-skill capability revocation + audit trail
+// ---------------------------------------------------------------------------
+
+/// Audit entries returned per skill on the permissions page.
+const AUDIT_PAGE_SIZE: usize = 100;
+
+#[derive(Serialize)]
+pub struct SkillCapabilityDto {
+    /// snake_case capability name, e.g. `read_document`.
+    pub capability: String,
+    /// `false` once the user has revoked it.
+    pub enabled: bool,
+    pub high_risk: bool,
+}
+
+#[derive(Serialize)]
+pub struct SkillAuditDto {
+    pub timestamp: String,
+    pub capability: String,
+    pub operation: String,
+    pub target: Option<String>,
+    pub allowed: bool,
+}
+
+#[derive(Serialize)]
+pub struct SkillPermissionsDto {
+    pub skill_name: String,
+    pub capabilities: Vec<SkillCapabilityDto>,
+    pub audit: Vec<SkillAuditDto>,
+}
+
+fn capability_name(cap: &Capability) -> String {
+    serde_json::to_value(cap)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{cap:?}"))
+}
+
+fn parse_capability(name: &str) -> Result<Capability, String> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|_| format!("Unknown capability: {name}"))
+}
+
+/// Declared capabilities of a skill, which of them the user has revoked,
+/// and its recent capability use.
+#[tauri::command]
+pub async fn get_skill_permissions(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    skill_name: String,
+) -> Result<SkillPermissionsDto, String> {
+    state.require_unlocked(&webview).await?;
+    let registry = &state.skill_registry;
+    let skill = registry
+        .find_skill(&skill_name)
+        .ok_or_else(|| format!("Skill '{skill_name}' not found"))?;
+    let revoked = registry.permissions().revoked_for(&skill_name);
+
+    let capabilities = skill
+        .required_capabilities()
+        .iter()
+        .map(|cap| SkillCapabilityDto {
+            capability: capability_name(cap),
+            enabled: !revoked.contains(cap),
+            high_risk: cap.is_high_risk(),
+        })
+        .collect();
+    let audit = registry
+        .audit_log()
+        .entries_for(&skill_name, AUDIT_PAGE_SIZE)
+        .into_iter()
+        .map(|e| SkillAuditDto {
+            timestamp: e.timestamp.to_rfc3339(),
+            capability: capability_name(&e.capability),
+            operation: e.operation,
+            target: e.target,
+            allowed: e.allowed,
+        })
+        .collect();
+
+    Ok(SkillPermissionsDto {
+        skill_name,
+        capabilities,
+        audit,
+    })
+}
+
+/// Revoke (`enabled = false`) or restore a capability for one skill.
+/// Applies immediately, including to WASM skills that are mid-execution.
+#[tauri::command]
+pub async fn set_skill_capability(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    skill_name: String,
+    capability: String,
+    enabled: bool,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    let cap = parse_capability(&capability)?;
+    let permissions = state.skill_registry.permissions();
+    if enabled {
+        permissions.restore(&skill_name, &cap).str_err()
+    } else {
+        tracing::info!("Revoking {capability} from skill '{skill_name}'");
+        permissions.revoke(&skill_name, cap).str_err()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Skill jobs — queued / running / finished executions
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct SkillJobDto {
+    pub id: u64,
+    pub skill_name: String,
+    pub action: String,
+    pub doc_id: String,
+    /// `queued`, `running`, `done`, `failed` or `cancelled`.
+    pub status: sovereign_skills::JobStatus,
+    pub error: Option<String>,
+    pub queued_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+impl From<sovereign_skills::SkillJob> for SkillJobDto {
+    fn from(job: sovereign_skills::SkillJob) -> Self {
+        Self {
+            id: job.id,
+            skill_name: job.skill,
+            action: job.action,
+            doc_id: job.doc_id,
+            status: job.status,
+            error: job.error,
+            queued_at: job.queued_at.to_rfc3339(),
+            started_at: job.started_at.map(|t| t.to_rfc3339()),
+            finished_at: job.finished_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+/// Every tracked skill job, newest first.
+#[tauri::command]
+pub async fn list_skill_jobs(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<Vec<SkillJobDto>, String> {
+    state.require_unlocked(&webview).await?;
+    Ok(state
+        .skill_registry
+        .jobs()
+        .jobs()
+        .into_iter()
+        .map(SkillJobDto::from)
+        .collect())
+}
+
+/// Cancel a queued or running job. Returns false if it had already finished.
+#[tauri::command]
+pub async fn cancel_skill_job(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    job_id: u64,
+) -> Result<bool, String> {
+    state.require_unlocked(&webview).await?;
+    Ok(state.skill_registry.jobs().cancel(job_id))
+}
+
+/// Drop finished jobs from the jobs panel.
+#[tauri::command]
+pub async fn clear_finished_skill_jobs(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    state.skill_registry.jobs().clear_finished();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capability_names_roundtrip() {
+        for cap in [
+            Capability::ReadDocument,
+            Capability::WriteAllDocuments,
+            Capability::LlmInference,
+        ] {
+            assert_eq!(parse_capability(&capability_name(&cap)).unwrap(), cap);
+        }
+        assert_eq!(capability_name(&Capability::ReadDocument), "read_document");
+        assert!(parse_capability("root_access").is_err());
+    }
+}
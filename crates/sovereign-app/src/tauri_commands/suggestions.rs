@@ -68,7 +68,7 @@ pub async fn accept_link_suggestion(
         .await
         .str_err()?;
 
-    let _ = state.orch_tx.send(OrchestratorEvent::LinkSuggestionResolved {
+    let _ = state.bus.publish(OrchestratorEvent::LinkSuggestionResolved {
         suggestion_id: id,
         accepted: true,
     });
@@ -89,7 +89,7 @@ pub async fn dismiss_link_suggestion(
         .await
         .str_err()?;
 
-    let _ = state.orch_tx.send(OrchestratorEvent::LinkSuggestionResolved {
+    let _ = state.bus.publish(OrchestratorEvent::LinkSuggestionResolved {
         suggestion_id: id,
         accepted: false,
     });
@@ -2,42 +2,35 @@
 //!
 //! The speech-to-text loop itself runs autonomously on the voice pipeline
 //! thread (wake-word triggered, see `sovereign_ai::voice::VoicePipeline`),
-//! and surfaces its state to the frontend via the `voice-event` Tauri emit
+//! and surfaces its state to the frontend as `VoiceEvent`s on the event bus
 //! (see `tauri_events::spawn_voice_forwarder`). These commands give the
-//! frontend an explicit push-to-talk affordance: they emit a synthetic
-//! `voice-event` so the mic button can reflect listening/idle immediately.
+//! frontend an explicit push-to-talk affordance: they publish a synthetic
+//! voice event so the mic button can reflect listening/idle immediately.
 
-use tauri::Emitter;
+use sovereign_ai::VoiceEvent;
+use tauri::State;
 
-use crate::tauri_events::VoiceEventPayload;
+use crate::tauri_state::AppState;
 
 /// Signal that the user wants to start voice input (push-to-talk).
 ///
-/// Emits a `voice-event` `{kind:"listening"}` so the frontend reflects the
-/// listening state. The actual capture/transcription continues to be driven
+/// Publishes `ListeningStarted` (a `voice-event` `{kind:"listening"}` for
+/// the frontend). The actual capture/transcription continues to be driven
 /// by the wake-word pipeline; this is the explicit UI entry point.
 #[tauri::command]
-pub async fn start_listening(app: tauri::AppHandle) -> Result<(), String> {
-    app.emit(
-        "voice-event",
-        VoiceEventPayload { kind: "listening".into(), text: None },
-    )
-    .map_err(|e| e.to_string())?;
+pub async fn start_listening(state: State<'_, AppState>) -> Result<(), String> {
+    state.bus.publish(VoiceEvent::ListeningStarted);
     tracing::info!("start_listening requested");
     Ok(())
 }
 
 /// Signal that the user wants to stop voice input.
 ///
-/// Emits a `voice-event` `{kind:"idle"}` so the frontend returns the mic
-/// button to its idle state.
+/// Publishes `ListeningStopped` (a `voice-event` `{kind:"idle"}`) so the
+/// frontend returns the mic button to its idle state.
 #[tauri::command]
-pub async fn stop_listening(app: tauri::AppHandle) -> Result<(), String> {
-    app.emit(
-        "voice-event",
-        VoiceEventPayload { kind: "idle".into(), text: None },
-    )
-    .map_err(|e| e.to_string())?;
+pub async fn stop_listening(state: State<'_, AppState>) -> Result<(), String> {
+    state.bus.publish(VoiceEvent::ListeningStopped);
     tracing::info!("stop_listening requested");
     Ok(())
 }
@@ -0,0 +1,59 @@
+use super::*;
+use crate::detached::{DetachedPanel, PanelKind};
+
+// ---------------------------------------------------------------------------
+// Detached panel windows (see crate::detached)
+// ---------------------------------------------------------------------------
+
+/// Pop a document panel or the inbox out into its own window. Focuses the
+/// existing window if that panel is already detached.
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+#[tauri::command]
+pub async fn detach_panel(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    kind: PanelKind,
+    id: String,
+    title: String,
+) -> Result<(), String> {
+    state.require_unlocked(&webview).await?;
+    let panel = DetachedPanel { kind, id };
+    // Must spawn on a separate thread to avoid deadlock on Windows
+    tokio::task::spawn_blocking(move || crate::detached::open_window(&app, panel, &title))
+        .await
+        .str_err()?
+}
+
+/// What the calling window shows, or `None` for the main window. Holds
+/// no content, so it is answered before login too.
+#[tauri::command]
+pub async fn detached_panel_info(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+) -> Result<Option<DetachedPanel>, String> {
+    Ok(state.detached_panels.get(webview.label()))
+}
+
+/// Close the calling detached window. With `dock`, the main window puts
+/// the panel back in place.
+#[tauri::command]
+pub async fn close_detached_panel(
+    webview: tauri::Webview,
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    dock: bool,
+) -> Result<(), String> {
+    use tauri::Manager;
+    let label = webview.label().to_string();
+    if !crate::detached::is_detached_label(&label) {
+        return Err("Not a detached panel window".into());
+    }
+    if dock {
+        state.detached_panels.set_dock(&label);
+    }
+    match app.get_webview_window(&label) {
+        Some(window) => window.close().str_err(),
+        None => Ok(()),
+    }
+}
@@ -12,10 +12,12 @@ use tauri::Emitter;
 // Serializable event payloads
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize)]
-pub struct ChatResponsePayload {
-    pub text: String,
-}
+// The payloads daemon clients can rely on are part of the SDK.
+pub use sovereign_sdk::events::{
+    ActionExecutedPayload, ActionProposedPayload, ActionRejectedPayload, ChatResponsePayload,
+    ContactCreatedPayload, DocumentChangedPayload, DocumentCreatedPayload, DocumentMovedPayload,
+    NewMessagesPayload, ThreadDeletedPayload, ThreadRenamedPayload,
+};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct OpenPanelPayload {
@@ -28,27 +30,6 @@ pub struct BubbleStatePayload {
     pub state: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct ActionProposedPayload {
-    pub action: String,
-    pub level: String,
-    pub description: String,
-    pub doc_id: Option<String>,
-    pub thread_id: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize)]
-pub struct ActionExecutedPayload {
-    pub action: String,
-    pub success: bool,
-}
-
-#[derive(Debug, Clone, Serialize)]
-pub struct ActionRejectedPayload {
-    pub action: String,
-    pub reason: String,
-}
-
 #[derive(Debug, Clone, Serialize)]
 pub struct SearchResultsPayload {
     pub query: String,
@@ -59,6 +40,7 @@ pub struct SearchResultsPayload {
 pub struct SuggestionPayload {
     pub text: String,
     pub action: String,
+    pub target: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -69,46 +51,41 @@ pub struct SkillResultPayload {
     pub data: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct DocumentCreatedPayload {
-    pub doc_id: String,
-    pub title: String,
-    pub thread_id: String,
-}
-
 #[derive(Debug, Clone, Serialize)]
 pub struct GenericPayload {
     pub message: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
-pub struct ThreadRenamedPayload {
+pub struct MilestoneCreatedPayload {
+    pub milestone_id: String,
+    pub title: String,
     pub thread_id: String,
-    pub name: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
-pub struct ThreadDeletedPayload {
-    pub thread_id: String,
+pub struct ReminderSetPayload {
+    pub reminder_id: String,
+    pub doc_id: String,
+    pub text: String,
+    pub due_at: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
-pub struct DocumentMovedPayload {
+pub struct ReminderDuePayload {
+    pub reminder_id: String,
     pub doc_id: String,
-    pub new_thread_id: String,
+    pub text: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
-pub struct NewMessagesPayload {
-    pub channel: String,
-    pub count: u32,
-    pub conversation_id: String,
+pub struct AdoptionStartedPayload {
+    pub doc_id: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
-pub struct ContactCreatedPayload {
-    pub contact_id: String,
-    pub name: String,
+pub struct KeyUsageAlertPayload {
+    pub text: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -176,18 +153,124 @@ pub struct SyncStatusPayload {
     pub status: String,
 }
 
+/// One `sync-progress` tick for a running session with `peer_id`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncProgressPayload {
+    pub peer_id: String,
+    pub docs_to_pull: u32,
+    pub docs_to_push: u32,
+    pub rows_to_pull: u32,
+    pub rows_to_push: u32,
+    pub commits_received: u32,
+    pub commits_sent: u32,
+    pub bytes_received: u64,
+    pub bytes_sent: u64,
+    pub requests_done: u32,
+    pub requests_total: u32,
+    pub elapsed_ms: u64,
+    pub eta_ms: Option<u64>,
+}
+
+/// Answer to `preview_sync`: what syncing with `peer_id` would move, or
+/// `None` when the peer couldn't be asked.
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncPreviewPayload {
+    pub peer_id: String,
+    pub preview: Option<SyncPreviewCounts>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncPreviewCounts {
+    pub docs_to_send: u32,
+    pub docs_to_receive: u32,
+    pub conflicts: u32,
+    pub rows_to_send: u32,
+    pub rows_to_receive: u32,
+    pub blobs_to_send: u32,
+    pub blobs_to_receive: u32,
+    pub bytes_to_send: u64,
+    pub bytes_to_receive: u64,
+}
+
+/// Answer to `fetch_viewer_snapshot`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ViewerSnapshotPayload {
+    pub peer_id: String,
+    pub snapshot: Option<ViewerSnapshotBody>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ViewerSnapshotBody {
+    pub device_id: String,
+    pub generated_at: String,
+    pub documents: Vec<ViewerDocumentPayload>,
+    pub threads: Vec<ViewerThreadPayload>,
+    pub too_large: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ViewerDocumentPayload {
+    pub doc_id: String,
+    pub title: String,
+    pub content: String,
+    pub thread_id: String,
+    pub modified_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ViewerThreadPayload {
+    pub thread_id: String,
+    pub name: String,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct SyncConflictPayload {
     pub doc_id: String,
     pub description: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncConflictDetailsPayload {
+    pub doc_id: String,
+    pub base: Option<String>,
+    pub local: String,
+    pub remote: String,
+    pub merged: String,
+    pub hunks: Vec<ConflictHunkPayload>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictHunkPayload {
+    pub base: String,
+    pub local: String,
+    pub remote: String,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct DevicePairedPayload {
     pub device_id: String,
     pub device_name: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct KeysImportedPayload {
+    pub peer_id: String,
+    pub imported: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KeysSharedPayload {
+    pub peer_id: String,
+    pub accepted: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WipeCosignRequestedPayload {
+    pub order_id: String,
+    pub target_peer_id: String,
+    pub issuer_peer_id: String,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct PairingFailedPayload {
     pub reason: String,
@@ -253,9 +336,9 @@ pub fn spawn_event_forwarder(
                     );
                 }
 
-                OrchestratorEvent::Suggestion { text, action } => {
-                    let _ =
-                        app_handle.emit("suggestion", SuggestionPayload { text, action });
+                OrchestratorEvent::Suggestion { text, action, target } => {
+                    let _ = app_handle
+                        .emit("suggestion", SuggestionPayload { text, action, target });
                 }
 
                 OrchestratorEvent::SkillResult {
@@ -355,18 +438,31 @@ pub fn spawn_event_forwarder(
                     );
                 }
 
-                // Phase 3: Communications
-                OrchestratorEvent::NewMessagesReceived { channel, count, conversation_id } => {
+                OrchestratorEvent::MilestoneCreated { milestone_id, title, thread_id } => {
                     let _ = app_handle.emit(
-                        "new-messages",
-                        NewMessagesPayload { channel, count, conversation_id },
+                        "milestone-created",
+                        MilestoneCreatedPayload { milestone_id, title, thread_id },
                     );
                 }
 
-                OrchestratorEvent::ContactCreated { contact_id, name } => {
+                OrchestratorEvent::ReminderSet { reminder_id, doc_id, text, due_at } => {
                     let _ = app_handle.emit(
-                        "contact-created",
-                        ContactCreatedPayload { contact_id, name },
+                        "reminder-set",
+                        ReminderSetPayload { reminder_id, doc_id, text, due_at },
+                    );
+                }
+
+                OrchestratorEvent::ReminderDue { reminder_id, doc_id, text } => {
+                    let _ = app_handle.emit(
+                        "reminder-due",
+                        ReminderDuePayload { reminder_id, doc_id, text },
+                    );
+                }
+
+                OrchestratorEvent::AdoptionStarted { doc_id } => {
+                    let _ = app_handle.emit(
+                        "adoption-started",
+                        AdoptionStartedPayload { doc_id },
                     );
                 }
 
@@ -417,6 +513,15 @@ pub fn spawn_event_forwarder(
                     let _ = app_handle.emit("open-panel", OpenPanelPayload { name });
                 }
 
+                OrchestratorEvent::LockRequested => {
+                    use tauri::Manager;
+                    let handle = app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        handle.state::<crate::tauri_state::AppState>().lock().await;
+                        let _ = handle.emit("session-locked", ());
+                    });
+                }
+
                 // P2P sync events (Phase 3c)
                 OrchestratorEvent::DeviceDiscovered { device_id, device_name } => {
                     let _ = app_handle.emit(
@@ -430,12 +535,97 @@ pub fn spawn_event_forwarder(
                         SyncStatusPayload { peer_id, status },
                     );
                 }
+                OrchestratorEvent::SyncProgress { peer_id, progress: p } => {
+                    let _ = app_handle.emit(
+                        "sync-progress",
+                        SyncProgressPayload {
+                            peer_id,
+                            docs_to_pull: p.docs_to_pull,
+                            docs_to_push: p.docs_to_push,
+                            rows_to_pull: p.rows_to_pull,
+                            rows_to_push: p.rows_to_push,
+                            commits_received: p.commits_received,
+                            commits_sent: p.commits_sent,
+                            bytes_received: p.bytes_received,
+                            bytes_sent: p.bytes_sent,
+                            requests_done: p.requests_done,
+                            requests_total: p.requests_total,
+                            elapsed_ms: p.elapsed_ms,
+                            eta_ms: p.eta_ms,
+                        },
+                    );
+                }
+                OrchestratorEvent::SyncPreview { peer_id, preview } => {
+                    let preview = preview.map(|p| SyncPreviewCounts {
+                        docs_to_send: p.docs_to_send,
+                        docs_to_receive: p.docs_to_receive,
+                        conflicts: p.conflicts,
+                        rows_to_send: p.rows_to_send,
+                        rows_to_receive: p.rows_to_receive,
+                        blobs_to_send: p.blobs_to_send,
+                        blobs_to_receive: p.blobs_to_receive,
+                        bytes_to_send: p.bytes_to_send,
+                        bytes_to_receive: p.bytes_to_receive,
+                    });
+                    let _ = app_handle.emit("sync-preview", SyncPreviewPayload { peer_id, preview });
+                }
+                OrchestratorEvent::ViewerSnapshot { peer_id, snapshot } => {
+                    let snapshot = snapshot.map(|s| ViewerSnapshotBody {
+                        device_id: s.device_id,
+                        generated_at: s.generated_at,
+                        documents: s
+                            .documents
+                            .into_iter()
+                            .map(|d| ViewerDocumentPayload {
+                                doc_id: d.doc_id,
+                                title: d.title,
+                                content: d.content,
+                                thread_id: d.thread_id,
+                                modified_at: d.modified_at,
+                            })
+                            .collect(),
+                        threads: s
+                            .threads
+                            .into_iter()
+                            .map(|(thread_id, name)| ViewerThreadPayload { thread_id, name })
+                            .collect(),
+                        too_large: s.too_large,
+                    });
+                    let _ = app_handle.emit("viewer-snapshot", ViewerSnapshotPayload { peer_id, snapshot });
+                }
                 OrchestratorEvent::SyncConflict { doc_id, description } => {
                     let _ = app_handle.emit(
                         "sync-conflict",
                         SyncConflictPayload { doc_id, description },
                     );
                 }
+                OrchestratorEvent::SyncConflictDetails {
+                    doc_id,
+                    base,
+                    local,
+                    remote,
+                    merged,
+                    hunks,
+                } => {
+                    let _ = app_handle.emit(
+                        "sync-conflict-details",
+                        SyncConflictDetailsPayload {
+                            doc_id,
+                            base,
+                            local,
+                            remote,
+                            merged,
+                            hunks: hunks
+                                .into_iter()
+                                .map(|h| ConflictHunkPayload {
+                                    base: h.base,
+                                    local: h.local,
+                                    remote: h.remote,
+                                })
+                                .collect(),
+                        },
+                    );
+                }
                 OrchestratorEvent::DevicePaired { device_id, device_name } => {
                     let _ = app_handle.emit(
                         "device-paired",
@@ -448,6 +638,45 @@ pub fn spawn_event_forwarder(
                         PairingFailedPayload { reason, offer_dead },
                     );
                 }
+                OrchestratorEvent::WipeCosignRequested {
+                    order_id,
+                    target_peer_id,
+                    issuer_peer_id,
+                } => {
+                    let _ = app_handle.emit(
+                        "wipe-cosign-requested",
+                        WipeCosignRequestedPayload {
+                            order_id,
+                            target_peer_id,
+                            issuer_peer_id,
+                        },
+                    );
+                }
+                #[cfg(feature = "p2p")]
+                OrchestratorEvent::RemoteWipeOrdered { order_id, issuer_peer_id } => {
+                    use tauri::Manager;
+                    let handle = app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let state = handle.state::<crate::tauri_state::AppState>();
+                        if crate::remote_wipe::execute_order(&state, &order_id, &issuer_peer_id)
+                            .await
+                        {
+                            let _ = handle.emit("device-wiped", ());
+                        }
+                    });
+                }
+                OrchestratorEvent::KeysImported { peer_id, imported } => {
+                    let _ = app_handle.emit(
+                        "keys-imported",
+                        KeysImportedPayload { peer_id, imported },
+                    );
+                }
+                OrchestratorEvent::KeysShared { peer_id, accepted } => {
+                    let _ = app_handle.emit("keys-shared", KeysSharedPayload { peer_id, accepted });
+                }
+                OrchestratorEvent::KeyUsageAlert { text } => {
+                    let _ = app_handle.emit("key-usage-alert", KeyUsageAlertPayload { text });
+                }
 
                 // All other events: log but don't emit
                 other => {
@@ -472,10 +701,41 @@ pub struct VoiceEventPayload {
     pub text: Option<String>,
 }
 
-/// Spawn a background thread that forwards `VoiceEvent`s from the voice
-/// pipeline to the Tauri frontend via `app_handle.emit("voice-event", ..)`.
-/// Mirrors `spawn_event_forwarder`; surfaces voice-pipeline state to the
-/// Taskbar mic button.
+/// Spawn a background thread that forwards comms sync events to the Tauri
+/// frontend. Mirrors `spawn_event_forwarder`; sync errors are already
+/// logged by the sync engine.
+#[cfg(feature = "comms")]
+pub fn spawn_comms_forwarder(
+    app_handle: tauri::AppHandle,
+    comms_rx: std::sync::mpsc::Receiver<sovereign_comms::CommsEvent>,
+) {
+    use sovereign_comms::CommsEvent;
+    std::thread::spawn(move || {
+        while let Ok(event) = comms_rx.recv() {
+            match event {
+                CommsEvent::NewMessages { channel, count, conversation_id } => {
+                    let _ = app_handle.emit(
+                        "new-messages",
+                        NewMessagesPayload { channel: channel.to_string(), count, conversation_id },
+                    );
+                }
+                CommsEvent::ContactDiscovered { contact_id, name } => {
+                    let _ = app_handle.emit(
+                        "contact-created",
+                        ContactCreatedPayload { contact_id, name },
+                    );
+                }
+                CommsEvent::SyncComplete { .. } | CommsEvent::SyncError { .. } => {}
+            }
+        }
+        tracing::info!("Comms forwarder stopped (channel closed)");
+    });
+}
+
+/// Spawn a background thread that forwards `VoiceEvent`s (voice pipeline,
+/// push-to-talk, gesture listening) to the Tauri frontend via
+/// `app_handle.emit("voice-event", ..)`. Mirrors `spawn_event_forwarder`;
+/// surfaces voice state to the Taskbar mic button.
 pub fn spawn_voice_forwarder(
     app_handle: tauri::AppHandle,
     voice_rx: std::sync::mpsc::Receiver<sovereign_ai::VoiceEvent>,
@@ -1,12 +1,12 @@
 use std::sync::{Arc, Mutex};
 
 use sovereign_core::config::AppConfig;
-use sovereign_core::interfaces::OrchestratorEvent;
 use sovereign_core::security::ActionDecision;
 use sovereign_core::interfaces::FeedbackEvent;
 use sovereign_db::layered::LayeredGraphDB;
 
 /// Runtime model assignment (router + reasoning filenames).
+#[derive(Clone)]
 pub struct ModelAssignments {
     pub router: String,
     pub reasoning: String,
@@ -24,7 +24,12 @@ pub struct AppState {
     /// transition. See `crates/sovereign-db/src/layered.rs`.
     pub db: Arc<LayeredGraphDB>,
     pub orchestrator: Option<Arc<sovereign_ai::Orchestrator>>,
+    /// The configuration the app started with. Settings edits go
+    /// through `config_reload`; read `current_config()` for live values.
     pub config: AppConfig,
+    /// Config-reload channel: `update_config` publishes the saved config
+    /// here and `config_reload::spawn` hot-applies what it can.
+    pub config_reload: tokio::sync::watch::Sender<AppConfig>,
     pub skill_registry: Arc<sovereign_skills::SkillRegistry>,
     pub skill_db: Arc<dyn sovereign_skills::SkillDbAccess>,
     /// LLM access for skills that declare `Capability::LlmInference`.
@@ -32,16 +37,26 @@ pub struct AppState {
     pub skill_llm: Option<Arc<dyn sovereign_skills::SkillLlmAccess>>,
     pub decision_tx: tokio::sync::mpsc::Sender<ActionDecision>,
     pub feedback_tx: tokio::sync::mpsc::Sender<FeedbackEvent>,
-    /// Sender end for orchestrator events (for forwarding to Tauri events).
-    pub orch_tx: std::sync::mpsc::Sender<OrchestratorEvent>,
+    /// Event bus shared by the orchestrator, voice, comms and the UI and
+    /// daemon forwarders.
+    pub bus: Arc<sovereign_core::events::EventBus>,
     /// Current UI theme ("dark" or "light").
     pub theme: Mutex<String>,
     /// Auto-commit engine for document edits.
     pub autocommit: Arc<tokio::sync::Mutex<sovereign_ai::AutoCommitEngine>>,
+    /// Winds background work down when the app quits; long-running tasks
+    /// take a listener from it.
+    pub shutdown: sovereign_core::lifecycle::Shutdown,
     /// Current model assignments (router + reasoning filenames).
     pub model_assignments: Mutex<ModelAssignments>,
     /// User profile directory path (~/.sovereign).
     pub profile_dir: std::path::PathBuf,
+    /// Panels popped out into their own windows, by window label.
+    pub detached_panels: crate::detached::DetachedPanels,
+    /// Documents opened or saved lately, for `crate::relevance`.
+    pub relevance: crate::relevance::Touches,
+    /// Sealed documents unsealed lately, for `crate::sealed`.
+    pub unsealed: crate::sealed::Unsealed,
     /// User-scoped key for PII vault, body_raw, and session-log encryption.
     /// None until the user completes onboarding or logs in (post-login the
     /// lock holds Some). Same value on every paired device, so encrypted
@@ -65,6 +80,29 @@ pub struct AppState {
     #[cfg(feature = "encryption")]
     pub pending_pairing:
         tokio::sync::RwLock<Option<(sovereign_crypto::pair_payload::PairPayload, String)>>,
+    /// Security key enrolled during onboarding (`enroll_security_key`),
+    /// held until `complete_onboarding` binds it to the new auth store.
+    #[cfg(feature = "encryption")]
+    pub pending_security_key:
+        tokio::sync::Mutex<Option<sovereign_crypto::auth::TokenEnrollment>>,
+    /// Security-key recovery key, unsealed with the KEK at login when a
+    /// token is enrolled. `backup_now` splits it across the guardians.
+    #[cfg(feature = "encryption")]
+    pub second_factor_recovery:
+        tokio::sync::RwLock<Option<Arc<sovereign_crypto::auth::SecondFactorRecoveryKey>>>,
+    /// Which persona the current session unlocked. `None` while locked;
+    /// decoy mode sets it to `Duress`.
+    #[cfg(feature = "encryption")]
+    pub persona: tokio::sync::RwLock<Option<sovereign_core::auth::PersonaKind>>,
+    /// Canary phrases armed for this session (primary persona only).
+    #[cfg(feature = "encryption")]
+    pub canary: Mutex<Option<sovereign_crypto::canary::CanarySet>>,
+    /// The encryption layer installed at login, also reachable through `db`.
+    /// Held separately for key rotation, which the `GraphDB` trait doesn't
+    /// expose.
+    #[cfg(feature = "encryption")]
+    pub encrypted_db:
+        tokio::sync::RwLock<Option<Arc<sovereign_db::encrypted::EncryptedGraphDB>>>,
     /// P2P command sender for queueing `StartSync` / `PairDevice` /
     /// `Shutdown` from Tauri commands and the periodic poll task.
     /// `None` until `install_session` runs the post-login P2P startup.
@@ -99,19 +137,37 @@ pub struct AppState {
     #[cfg(feature = "p2p")]
     pub connectivity:
         std::sync::Arc<std::sync::atomic::AtomicU8>,
+    /// The direct link to a nearby device the platform reported, if any
+    /// (`proximity_link_up`). Pairing offers list its addresses first.
+    #[cfg(feature = "p2p")]
+    pub proximity_link: std::sync::RwLock<Option<sovereign_p2p::proximity::ProximityLink>>,
     /// Whisper STT engine for mobile voice-to-text (Web Audio API → Whisper).
     /// Populated when voice-stt feature is enabled and whisper model exists.
     /// Desktop uses the cpal-based VoicePipeline instead.
+    /// Handle to the running comms sync loop. `None` until
+    /// `install_session` starts it, or when comms is disabled or has no
+    /// usable account.
+    #[cfg(feature = "comms")]
+    pub comms: tokio::sync::RwLock<Option<sovereign_comms::CommsHandle>>,
     #[cfg(feature = "voice-stt")]
     pub stt_engine: Option<Arc<tokio::sync::Mutex<sovereign_ai::voice::stt::SttEngine>>>,
 }
 
+impl AppState {
+    /// The configuration as last saved from Settings (or loaded at
+    /// startup). Cheap enough for commands; clones the whole struct.
+    pub fn current_config(&self) -> AppConfig {
+        self.config_reload.borrow().clone()
+    }
+}
+
 /// IPC-005: data commands may only be invoked from the trusted main
-/// webview. The embedded `browser` webview renders arbitrary external
-/// pages; even while the session is unlocked it must never reach data
-/// commands through the IPC bridge.
+/// webview (or a panel detached from it, which loads the same app bundle).
+/// The embedded `browser` webview renders arbitrary external pages; even
+/// while the session is unlocked it must never reach data commands
+/// through the IPC bridge.
 pub fn require_main_webview(caller: &tauri::Webview) -> Result<(), String> {
-    if caller.label() == "main" {
+    if caller.label() == "main" || crate::detached::is_detached_label(caller.label()) {
         Ok(())
     } else {
         Err(format!("Command not available to webview '{}'", caller.label()))
@@ -132,6 +188,8 @@ impl AppState {
     pub async fn require_session_unlocked(&self) -> Result<(), String> {
         Ok(())
     }
+
+    pub async fn lock(&self) {}
 }
 
 #[cfg(feature = "encryption")]
@@ -181,6 +239,68 @@ impl AppState {
     pub async fn set_p2p_identity_key(&self, key: Arc<sovereign_crypto::device_key::DeviceKey>) {
         *self.p2p_identity_key.write().await = Some(key);
     }
+
+    /// Undo `install_session`: drop every session key held here and by the
+    /// orchestrator, stop comms and the P2P node, and point `db` back at the
+    /// raw bootstrap store so nothing decrypts until the next login. The key
+    /// types zeroize on drop, which happens once the last `Arc` goes.
+    pub async fn lock(&self) {
+        // The gate closes first, so no gated command starts mid-teardown.
+        *self.account_key.write().await = None;
+        *self.persona.write().await = None;
+        *self.p2p_identity_key.write().await = None;
+        *self.second_factor_recovery.write().await = None;
+        *self.pending_pairing.write().await = None;
+        *self.encrypted_db.write().await = None;
+        // Sync stops before the swap so no poll writes to the raw store.
+        self.drop_session_secrets().await;
+        self.db.swap(self.db.raw_inner());
+        tracing::info!("Session locked");
+    }
+
+    /// Canary decoy mode: the session stays open but now runs on the
+    /// duress persona's decoy database. The real keys are dropped as on
+    /// lock; a throwaway account key keeps the unlocked gate open.
+    pub async fn enter_decoy(&self) -> Result<(), String> {
+        let decoy = crate::duress::open_duress_db(&self.config)
+            .await
+            .map_err(|e| format!("open decoy profile: {e}"))?;
+        let throwaway = sovereign_crypto::account_key::AccountKey::from_bytes(rand::random());
+        *self.account_key.write().await = Some(Arc::new(throwaway));
+        *self.persona.write().await = Some(sovereign_core::auth::PersonaKind::Duress);
+        *self.p2p_identity_key.write().await = None;
+        *self.second_factor_recovery.write().await = None;
+        *self.pending_pairing.write().await = None;
+        *self.encrypted_db.write().await = None;
+        self.drop_session_secrets().await;
+        self.db.swap(Arc::new(decoy));
+        tracing::info!("Session switched to decoy profile");
+        Ok(())
+    }
+
+    /// Teardown shared by lock and decoy mode: forget what the unlocked
+    /// session derived and stop syncing.
+    async fn drop_session_secrets(&self) {
+        if let Some(ref orch) = self.orchestrator {
+            orch.clear_session_keys();
+        }
+        self.unsealed.close_all();
+        sovereign_ai::model_integrity::clear_unlock_key();
+        crate::canary::disarm(self);
+        sovereign_crypto::key_audit::uninstall();
+        crate::secure_clipboard::clear();
+
+        // The next `install_session` starts comms again with its own key.
+        #[cfg(feature = "comms")]
+        if let Some(comms) = self.comms.write().await.take() {
+            comms.stop().await;
+        }
+
+        #[cfg(feature = "p2p")]
+        if let Some(tx) = self.p2p_command_tx.write().await.take() {
+            let _ = tx.send(sovereign_p2p::P2pCommand::Shutdown).await;
+        }
+    }
 }
 
 #[cfg(feature = "p2p")]
@@ -200,6 +320,21 @@ impl AppState {
         *self.p2p_command_tx.write().await = Some(tx);
     }
 
+    /// The node's concrete listen addresses, those on the current
+    /// proximity link first so a nearby device with no other network
+    /// dials the one it can reach.
+    pub fn listen_addrs(&self) -> Vec<String> {
+        let mut addrs = self
+            .p2p_listen_addrs
+            .read()
+            .map(|a| a.clone())
+            .unwrap_or_default();
+        if let Some(link) = self.proximity_link.read().ok().and_then(|l| l.clone()) {
+            link.sort_link_first(&mut addrs);
+        }
+        addrs
+    }
+
     /// Snapshot the current connectivity state. Lock-free atomic read.
     pub fn connectivity_state(&self) -> sovereign_p2p::ConnectivityState {
         connectivity_from_u8(
@@ -224,6 +359,7 @@ fn connectivity_to_u8(state: sovereign_p2p::ConnectivityState) -> u8 {
         ConnectivityState::Wifi => 1,
         ConnectivityState::Cellular => 2,
         ConnectivityState::Offline => 3,
+        ConnectivityState::Local => 4,
     }
 }
 
@@ -234,6 +370,7 @@ fn connectivity_from_u8(byte: u8) -> sovereign_p2p::ConnectivityState {
         1 => ConnectivityState::Wifi,
         2 => ConnectivityState::Cellular,
         3 => ConnectivityState::Offline,
+        4 => ConnectivityState::Local,
         _ => ConnectivityState::Unknown,
     }
 }
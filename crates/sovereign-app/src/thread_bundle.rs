@@ -0,0 +1,243 @@
+//! Portable single-thread bundles (`.svt`).
+//!
+//! `sovereign thread export` writes one thread as a zip: `thread.json`
+//! holds the thread, its documents with their version history, and the
+//! relationships between them; `blobs/<sha256>` carries every image,
+//! video and attachment the documents list. Encrypted bundles are age
+//! files, as for workspace archives. `sovereign import-thread` reads it
+//! back into another instance.
+//!
+//! History is replayed on import rather than copied: each snapshot is
+//! written and committed in order, so the chain is re-signed by the
+//! importing device and its commits get fresh ids and timestamps. The
+//! messages and the order survive.
+
+use std::io::{Cursor, Read, Write};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sovereign_core::content::ContentFields;
+use sovereign_db::schema::RelationType;
+
+use crate::export_bundle::{ExportedDocument, ExportedThread};
+use crate::media;
+
+/// Name of the manifest entry inside the bundle.
+pub const MANIFEST_NAME: &str = "thread.json";
+
+/// Directory of media blobs inside the bundle.
+const BLOB_DIR: &str = "blobs/";
+
+/// Bumped when the manifest layout changes incompatibly.
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThreadBundle {
+    pub version: u32,
+    pub exported_at: String,
+    pub thread: ExportedThread,
+    pub documents: Vec<BundledDocument>,
+    #[serde(default)]
+    pub relationships: Vec<BundledRelationship>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundledDocument {
+    #[serde(flatten)]
+    pub document: ExportedDocument,
+    /// Version history, oldest first.
+    #[serde(default)]
+    pub commits: Vec<BundledCommit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundledCommit {
+    pub message: String,
+    pub timestamp: String,
+    pub title: String,
+    /// Raw content JSON of the snapshot.
+    pub content: String,
+}
+
+/// A link between two documents of the bundle, by their exported ids.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundledRelationship {
+    pub from: String,
+    pub to: String,
+    pub relation_type: RelationType,
+    pub strength: f32,
+}
+
+/// Hashes of the blobs a document's content lists (images, video
+/// thumbnails, attachments).
+pub fn blob_hashes(content: &str) -> Vec<String> {
+    let mut hashes: Vec<String> = media::content_paths(content)
+        .iter()
+        .filter_map(|p| media::blob_hash(p).map(str::to_string))
+        .collect();
+    hashes.sort();
+    hashes.dedup();
+    hashes
+}
+
+/// Point the blob paths in `content` at `dir`, where the importing device
+/// keeps its blobs. Paths that aren't hash-named are left alone, and so is
+/// content that lists no blobs.
+pub fn relocate_blobs(content: &str, dir: &std::path::Path) -> String {
+    if blob_hashes(content).is_empty() {
+        return content.to_string();
+    }
+    let mut fields = ContentFields::parse(content);
+    let relocate = |path: &mut String| {
+        if let Some(hash) = media::blob_hash(path) {
+            *path = dir.join(hash).to_string_lossy().into_owned();
+        }
+    };
+    fields.images.iter_mut().for_each(|i| relocate(&mut i.path));
+    for v in &mut fields.videos {
+        relocate(&mut v.path);
+        if let Some(thumb) = v.thumbnail_path.as_mut() {
+            relocate(thumb);
+        }
+    }
+    fields.files.iter_mut().for_each(|f| relocate(&mut f.path));
+    fields.serialize()
+}
+
+/// Zip the manifest together with the blobs (`sha256`, `bytes`).
+pub fn write_bundle(bundle: &ThreadBundle, blobs: &[(String, Vec<u8>)]) -> Result<Vec<u8>> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = zip::write::FileOptions::default();
+    zip.start_file(MANIFEST_NAME, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(bundle)?)?;
+    write_blobs(&mut zip, blobs)?;
+    Ok(zip.finish()?.into_inner())
+}
+
+/// Read a bundle made by [`write_bundle`]: the manifest and its blobs.
+pub fn read_bundle(bytes: &[u8]) -> Result<(ThreadBundle, Vec<(String, Vec<u8>)>)> {
+    let mut zip = zip::ZipArchive::new(Cursor::new(bytes)).context("not a thread bundle")?;
+    let mut manifest = String::new();
+    zip.by_name(MANIFEST_NAME)
+        .with_context(|| format!("bundle has no {MANIFEST_NAME}"))?
+        .read_to_string(&mut manifest)?;
+    let bundle: ThreadBundle = serde_json::from_str(&manifest)?;
+    if bundle.version > FORMAT_VERSION {
+        anyhow::bail!(
+            "bundle format v{} is newer than this build supports (v{FORMAT_VERSION})",
+            bundle.version
+        );
+    }
+    Ok((bundle, read_blobs(&mut zip)?))
+}
+
+/// Zip the blobs (`sha256`, `bytes`) into `blobs/`.
+pub(crate) fn write_blobs<W: Write + std::io::Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    blobs: &[(String, Vec<u8>)],
+) -> Result<()> {
+    for (hash, data) in blobs {
+        zip.start_file(
+            format!("{BLOB_DIR}{hash}"),
+            zip::write::FileOptions::default(),
+        )?;
+        zip.write_all(data)?;
+    }
+    Ok(())
+}
+
+/// The blobs under `blobs/`. A blob whose bytes don't match its name is an
+/// error.
+pub(crate) fn read_blobs<R: Read + std::io::Seek>(
+    zip: &mut zip::ZipArchive<R>,
+) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut blobs = Vec::new();
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i)?;
+        let Some(hash) = entry.name().strip_prefix(BLOB_DIR).map(str::to_string) else {
+            continue;
+        };
+        if !media::valid_hash(&hash) {
+            continue;
+        }
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        if format!("{:x}", Sha256::digest(&data)) != hash {
+            anyhow::bail!("blob {hash} in the bundle is corrupt");
+        }
+        blobs.push((hash, data));
+    }
+    Ok(blobs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(content: String) -> ThreadBundle {
+        ThreadBundle {
+            version: FORMAT_VERSION,
+            exported_at: "2026-01-01T00:00:00Z".into(),
+            thread: ExportedThread {
+                id: "thread:a".into(),
+                name: "Research".into(),
+                description: String::new(),
+            },
+            documents: vec![BundledDocument {
+                document: ExportedDocument {
+                    id: "document:1".into(),
+                    thread_id: "thread:a".into(),
+                    title: "Notes".into(),
+                    content: content.clone(),
+                    is_owned: true,
+                    doc_type: Default::default(),
+                    sensitivity: Default::default(),
+                    created_at: "2026-01-01T00:00:00Z".into(),
+                    modified_at: "2026-01-02T00:00:00Z".into(),
+                },
+                commits: vec![BundledCommit {
+                    message: "first".into(),
+                    timestamp: "2026-01-01T00:00:00Z".into(),
+                    title: "Notes".into(),
+                    content,
+                }],
+            }],
+            relationships: vec![BundledRelationship {
+                from: "document:1".into(),
+                to: "document:1".into(),
+                relation_type: RelationType::References,
+                strength: 0.8,
+            }],
+        }
+    }
+
+    #[test]
+    fn bundle_roundtrip_with_blobs() {
+        let image = b"\x89PNG\r\n\x1a\nfake".to_vec();
+        let hash = format!("{:x}", Sha256::digest(&image));
+        let content = format!(
+            r#"{{"body":"![](media:{hash})","images":[{{"path":"/home/a/.sovereign/crypto/sync_blobs/{hash}","caption":""}}]}}"#
+        );
+        assert_eq!(blob_hashes(&content), vec![hash.clone()]);
+
+        let bundle = sample(content.clone());
+        let bytes = write_bundle(&bundle, &[(hash.clone(), image.clone())]).unwrap();
+        let (read, blobs) = read_bundle(&bytes).unwrap();
+        assert_eq!(read, bundle);
+        assert_eq!(blobs, vec![(hash.clone(), image)]);
+
+        let moved = relocate_blobs(&content, std::path::Path::new("/srv/blobs"));
+        let fields = ContentFields::parse(&moved);
+        assert_eq!(fields.images[0].path, format!("/srv/blobs/{hash}"));
+        assert_eq!(fields.body, format!("![](media:{hash})"));
+    }
+
+    #[test]
+    fn rejects_corrupt_blob_and_garbage() {
+        let hash = format!("{:x}", Sha256::digest(b"real"));
+        let bytes = write_bundle(&sample(String::new()), &[(hash, b"forged".to_vec())]).unwrap();
+        assert!(read_bundle(&bytes).is_err());
+        assert!(read_bundle(b"not a zip").is_err());
+    }
+}
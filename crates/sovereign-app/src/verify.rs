@@ -0,0 +1,403 @@
+//! `sovereign verify`: prove the data on this device is intact.
+//!
+//! Where `doctor` checks that an install works, `verify` checks the data
+//! itself, and reads all of it:
+//!   - every document (live and in the trash) decrypts with the keys this
+//!     passphrase unlocks;
+//!   - every document's version history chains from its head and each
+//!     commit's device MAC and Ed25519 signature verify;
+//!   - the session log's hash chain is unbroken and not shorter than its
+//!     MAC'd anchor;
+//!   - every stored media blob still hashes to its name.
+//!
+//! Nothing is repaired. Unlocking counts against the login lockout like any
+//! other attempt.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use sha2::{Digest, Sha256};
+use sovereign_core::config::AppConfig;
+use sovereign_crypto::auth::{AuthStore, AuthSuccess, PersonaKind};
+use sovereign_db::encrypted::EncryptedGraphDB;
+use sovereign_db::schema::Commit;
+use sovereign_db::GraphDB;
+
+use crate::doctor::Report;
+use crate::setup::create_db;
+
+/// Individual problems listed per area before the rest are summarized.
+const MAX_LISTED: usize = 20;
+
+pub async fn run(config: &AppConfig) -> Result<Report> {
+    let profile_dir = sovereign_core::sovereign_dir();
+    let auth = unlock(config, &profile_dir.join("crypto")).await?;
+    let mut report = Report::default();
+
+    // The persona's own database, as login opens it.
+    let raw: Arc<dyn GraphDB> = match auth.persona {
+        PersonaKind::Primary => Arc::new(create_db(config).await?),
+        PersonaKind::Duress => Arc::new(crate::duress::open_duress_db(config).await?),
+    };
+    let core_persona = match auth.persona {
+        PersonaKind::Primary => sovereign_core::auth::PersonaKind::Primary,
+        PersonaKind::Duress => sovereign_core::auth::PersonaKind::Duress,
+    };
+    let db = crate::setup::build_encrypted_db(
+        raw.clone(),
+        Arc::new(auth.device_key),
+        Arc::new(auth.kek),
+        core_persona,
+    )?;
+
+    check_documents(raw.as_ref(), &db, &mut report).await?;
+
+    #[cfg(feature = "encrypted-log")]
+    check_session_log(
+        &profile_dir.join("orchestrator"),
+        &crate::setup::derive_session_log_key(&auth.account_key),
+        &mut report,
+    );
+    #[cfg(not(feature = "encrypted-log"))]
+    report.skip(
+        "Log",
+        "session log encryption is not compiled into this build",
+    );
+
+    check_blobs(&crate::media::media_dir(), &mut report);
+    Ok(report)
+}
+
+/// Ask for the passphrase (and security key) and open the auth store,
+/// honouring the login lockout.
+pub(crate) async fn unlock(config: &AppConfig, crypto_dir: &Path) -> Result<AuthSuccess> {
+    let store_path = crypto_dir.join("auth.store");
+    if !store_path.exists() {
+        bail!("No account on this device yet; complete onboarding in the app first");
+    }
+    let store = AuthStore::load(&store_path)?;
+    let mut attempts = crate::login_throttle::LoginAttempts::load(crypto_dir);
+    if let Some(remaining) = attempts.is_locked(
+        config.crypto.max_login_attempts,
+        config.crypto.lockout_seconds,
+    ) {
+        bail!("Login is locked out for another {remaining}s after failed attempts");
+    }
+
+    let passphrase = zeroize::Zeroizing::new(rpassword::prompt_password("Sovereign passphrase: ")?);
+    let result = match &store.second_factor {
+        Some(factor) => {
+            eprintln!("Touch your security key when it blinks.");
+            let secret = crate::tauri_commands::auth::read_security_key(factor)
+                .await
+                .map_err(anyhow::Error::msg)?;
+            store.authenticate_with_second_factor(passphrase.as_bytes(), &secret)
+        }
+        None => store.authenticate(passphrase.as_bytes()),
+    };
+    let auth = match result {
+        Ok(auth) => {
+            attempts.reset();
+            auth
+        }
+        Err(_) => {
+            attempts.record_failure();
+            let _ = attempts.save(crypto_dir);
+            bail!("The passphrase was not accepted");
+        }
+    };
+    let _ = attempts.save(crypto_dir);
+    Ok(auth)
+}
+
+/// Decrypt every document and check its version history.
+async fn check_documents(
+    raw: &dyn GraphDB,
+    db: &EncryptedGraphDB,
+    report: &mut Report,
+) -> Result<()> {
+    let mut docs = raw.list_documents(None).await?;
+    docs.extend(raw.list_deleted_documents().await?);
+
+    let mut unreadable = Vec::new();
+    let mut broken = Vec::new();
+    let mut commits_checked = 0;
+    for doc in &docs {
+        let id = doc.id_string().unwrap_or_default();
+        if let Err(e) = db.get_document(&id).await {
+            unreadable.push(format!("{id} does not decrypt: {e}"));
+        }
+        let commits = raw.list_document_commits(&id).await?;
+        commits_checked += commits.len();
+        broken.extend(history_problems(
+            &id,
+            doc.head_commit.as_deref(),
+            &commits,
+            |c| db.commit_verifies(c),
+        ));
+    }
+
+    if unreadable.is_empty() {
+        report.ok("Documents", format!("{} documents decrypt", docs.len()));
+    } else {
+        list_failures(
+            report,
+            "Documents",
+            &unreadable,
+            "restore crypto/keys*.db from a backup, or re-sync the documents from a paired device",
+        );
+    }
+    if broken.is_empty() {
+        report.ok(
+            "History",
+            format!("{commits_checked} commits verify and chain from their heads"),
+        );
+    } else {
+        list_failures(
+            report,
+            "History",
+            &broken,
+            "the version history was altered outside the app; don't restore from the commits named",
+        );
+    }
+    Ok(())
+}
+
+/// Problems with one document's history: commits failing `verifies`,
+/// commits filed under another document, and breaks in the parent chain
+/// from `head`. A chain ending at a missing parent is history compacted by
+/// `sovereign commits compact`, not a break — unless commits older than
+/// the gap are still there.
+fn history_problems(
+    doc_id: &str,
+    head: Option<&str>,
+    commits: &[Commit],
+    verifies: impl Fn(&Commit) -> bool,
+) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut by_id = HashMap::new();
+    for commit in commits {
+        let id = commit.id_string().unwrap_or_default();
+        if commit.document_id != doc_id {
+            problems.push(format!(
+                "{doc_id}: commit {id} belongs to {}",
+                commit.document_id
+            ));
+        }
+        if !verifies(commit) {
+            problems.push(format!("{doc_id}: commit {id} fails its integrity check"));
+        }
+        by_id.insert(id, commit);
+    }
+    if commits.is_empty() {
+        return problems;
+    }
+
+    let mut on_chain = HashSet::new();
+    let mut next = head.map(str::to_string);
+    if next.is_none() {
+        problems.push(format!("{doc_id}: has commits but no head commit"));
+    }
+    while let Some(id) = next {
+        let Some(commit) = by_id.get(&id) else {
+            if on_chain.is_empty() {
+                problems.push(format!("{doc_id}: head commit {id} is missing"));
+            }
+            break;
+        };
+        if !on_chain.insert(id.clone()) {
+            problems.push(format!("{doc_id}: commit {id} is its own ancestor"));
+            break;
+        }
+        next = commit.parent_commit.clone();
+    }
+    let cut_off = by_id.len() - on_chain.len();
+    if cut_off > 0 && !on_chain.is_empty() {
+        problems.push(format!(
+            "{doc_id}: {cut_off} commit(s) are cut off from the head"
+        ));
+    }
+    problems
+}
+
+/// Check the session log's hash chain and its anchor against truncation.
+#[cfg(feature = "encrypted-log")]
+fn check_session_log(dir: &Path, key: &[u8; 32], report: &mut Report) {
+    use sovereign_ai::encrypted_log::{self, AnchorStatus};
+
+    let path = dir.join("session_log.jsonl");
+    let lines: Vec<String> = match std::fs::read_to_string(&path) {
+        Ok(text) => text.lines().map(str::to_string).collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            report.skip("Log", "no session log yet");
+            return;
+        }
+        Err(e) => {
+            report.fail(
+                "Log",
+                format!("{} can't be read: {e}", path.display()),
+                "check the file's permissions",
+            );
+            return;
+        }
+    };
+    let tampered =
+        "the session log was altered; the assistant ignores its history until it rotates";
+    if let Err(e) = encrypted_log::verify_chain(&lines) {
+        report.fail("Log", format!("hash chain broken: {e}"), tampered);
+        return;
+    }
+    let encrypted = lines.iter().any(|l| encrypted_log::is_encrypted_line(l));
+    match encrypted_log::read_chain_anchor(&dir.join("session_log.anchor"), key) {
+        AnchorStatus::Valid { count, head } => {
+            if let Err(e) = encrypted_log::check_no_truncation(&lines, count, &head) {
+                report.fail("Log", e.to_string(), tampered);
+                return;
+            }
+        }
+        AnchorStatus::Forged => {
+            report.fail("Log", "the chain anchor's MAC does not verify", tampered);
+            return;
+        }
+        AnchorStatus::Missing if encrypted => {
+            report.fail("Log", "the chain anchor is missing", tampered);
+            return;
+        }
+        AnchorStatus::Missing => {}
+    }
+    report.ok(
+        "Log",
+        format!("session log chain of {} entries is intact", lines.len()),
+    );
+}
+
+/// Check every stored media blob hashes to its file name. Partial
+/// transfers (`.part`) and other files are left alone.
+fn check_blobs(dir: &Path, report: &mut Report) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        report.skip("Blobs", "no media stored yet");
+        return;
+    };
+    let mut checked = 0;
+    let mut bad = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !is_hash(&name) {
+            continue;
+        }
+        checked += 1;
+        match std::fs::read(entry.path()) {
+            Ok(bytes) if format!("{:x}", Sha256::digest(&bytes)) == name => {}
+            Ok(_) => bad.push(format!("{name} does not match its content hash")),
+            Err(e) => bad.push(format!("{name} can't be read: {e}")),
+        }
+    }
+    if bad.is_empty() {
+        report.ok("Blobs", format!("{checked} media blobs match their hashes"));
+    } else {
+        list_failures(
+            report,
+            "Blobs",
+            &bad,
+            "delete the blobs named; they are fetched again from the peer that shared them",
+        );
+    }
+}
+
+fn is_hash(name: &str) -> bool {
+    name.len() == 64 && name.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn list_failures(report: &mut Report, area: &'static str, problems: &[String], fix: &str) {
+    for p in problems.iter().take(MAX_LISTED) {
+        report.fail(area, p.clone(), fix);
+    }
+    if problems.len() > MAX_LISTED {
+        report.fail(
+            area,
+            format!("… and {} more", problems.len() - MAX_LISTED),
+            fix,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_db::schema::{raw_to_thing, DocumentSnapshot};
+
+    fn commit(id: &str, parent: Option<&str>) -> Commit {
+        Commit {
+            id: raw_to_thing(&format!("commit:{id}")),
+            document_id: "document:d".into(),
+            parent_commit: parent.map(|p| format!("commit:{p}")),
+            message: String::new(),
+            timestamp: chrono::Utc::now(),
+            snapshot: DocumentSnapshot {
+                document_id: "document:d".into(),
+                title: String::new(),
+                content: String::new(),
+            },
+            signature: None,
+            device_public_key: None,
+            device_signature: None,
+        }
+    }
+
+    #[test]
+    fn history_chains_from_the_head() {
+        let chain = [
+            commit("a", None),
+            commit("b", Some("a")),
+            commit("c", Some("b")),
+        ];
+        let ok = |_: &Commit| true;
+        assert!(history_problems("document:d", Some("commit:c"), &chain, ok).is_empty());
+        // Compacted: the oldest kept commit's parent is gone.
+        assert!(history_problems("document:d", Some("commit:c"), &chain[1..], ok).is_empty());
+
+        // A commit dropped from the middle leaves older ones cut off.
+        let gap = [chain[0].clone(), chain[2].clone()];
+        let problems = history_problems("document:d", Some("commit:c"), &gap, ok);
+        assert_eq!(
+            problems,
+            ["document:d: 1 commit(s) are cut off from the head"]
+        );
+
+        let problems = history_problems("document:d", Some("commit:z"), &chain, ok);
+        assert_eq!(problems, ["document:d: head commit commit:z is missing"]);
+
+        let loop_ = [commit("a", Some("b")), commit("b", Some("a"))];
+        let problems = history_problems("document:d", Some("commit:a"), &loop_, ok);
+        assert_eq!(
+            problems,
+            ["document:d: commit commit:a is its own ancestor"]
+        );
+
+        let forged = |c: &Commit| c.id_string().as_deref() != Some("commit:b");
+        let problems = history_problems("document:d", Some("commit:c"), &chain, forged);
+        assert_eq!(
+            problems,
+            ["document:d: commit commit:b fails its integrity check"]
+        );
+    }
+
+    #[test]
+    fn blobs_must_match_their_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = b"image bytes";
+        let name = format!("{:x}", Sha256::digest(good));
+        std::fs::write(dir.path().join(&name), good).unwrap();
+        std::fs::write(dir.path().join(format!("{name}.part")), b"partial").unwrap();
+        let mut report = Report::default();
+        check_blobs(dir.path(), &mut report);
+        assert_eq!(report.failures(), 0);
+
+        std::fs::write(dir.path().join(&name), b"swapped").unwrap();
+        let mut report = Report::default();
+        check_blobs(dir.path(), &mut report);
+        assert_eq!(report.failures(), 1);
+    }
+}
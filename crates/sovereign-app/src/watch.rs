@@ -0,0 +1,101 @@
+//! `sovereign watch`: the daemon's event stream on stdout, one JSON object
+//! per line, for notification bridges and scripts:
+//!
+//! `{"at":"2026-…Z","category":"db","event":"document-created","payload":{…}}`
+//!
+//! `--events db,sync` keeps only those categories (see
+//! [`sovereign_sdk::events::category`]); the default is everything. The
+//! stream ends when the daemon stops.
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+use sovereign_core::config::AppConfig;
+use sovereign_sdk::events::{category, CATEGORIES};
+
+use crate::daemon_client::{Client, Incoming};
+
+pub async fn run(
+    config: &AppConfig,
+    socket: Option<PathBuf>,
+    events: Option<String>,
+) -> Result<()> {
+    let wanted = parse_categories(events.as_deref())?;
+    let mut client = Client::connect_or_start(config, socket).await?;
+    client.call("subscribe", Value::Null).await?;
+
+    let mut stdout = std::io::stdout().lock();
+    while let Some(incoming) = client.next().await? {
+        let Incoming::Event { event, payload } = incoming else {
+            continue;
+        };
+        let category = category(&event);
+        if !wanted.contains(category) {
+            continue;
+        }
+        let line = json!({
+            "at": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            "category": category,
+            "event": event,
+            "payload": payload,
+        });
+        // A closed pipe (`watch | head`) ends the watch quietly.
+        if writeln!(stdout, "{line}")
+            .and_then(|_| stdout.flush())
+            .is_err()
+        {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// `db,sync` → those categories; `None` → all of them.
+fn parse_categories(list: Option<&str>) -> Result<HashSet<&'static str>> {
+    let Some(list) = list else {
+        return Ok(CATEGORIES.into_iter().collect());
+    };
+    let mut wanted = HashSet::new();
+    for name in list.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        match CATEGORIES.iter().find(|c| c.eq_ignore_ascii_case(name)) {
+            Some(c) => {
+                wanted.insert(*c);
+            }
+            None => bail!(
+                "Unknown event category '{name}' (expected {})",
+                CATEGORIES.join(", ")
+            ),
+        }
+    }
+    if wanted.is_empty() {
+        bail!("--events needs at least one category");
+    }
+    Ok(wanted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn categories_parse_and_cover_the_daemon_events() {
+        let wanted = parse_categories(Some("db, Sync")).unwrap();
+        assert_eq!(wanted, HashSet::from(["db", "sync"]));
+        assert_eq!(parse_categories(None).unwrap().len(), CATEGORIES.len());
+        assert!(parse_categories(Some("db,disk")).is_err());
+        assert!(parse_categories(Some(",")).is_err());
+
+        for event in [
+            "document-created",
+            "sync-conflict",
+            "new-messages",
+            "session-locked",
+        ] {
+            assert!(CATEGORIES.contains(&category(event)), "{event}");
+        }
+        assert_eq!(category("chat-response"), "ai");
+    }
+}
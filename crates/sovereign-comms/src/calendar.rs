@@ -0,0 +1,488 @@
+//! Calendar events from iCalendar data, and a CalDAV client to fetch them.
+//!
+//! Events are read on demand rather than stored: the meeting mode only asks
+//! "what is starting now?", so [`CalDavClient::events_between`] queries the
+//! server for a short window and [`parse_ics`] turns the returned VEVENTs
+//! into [`CalendarEvent`]s. The server expands recurring events
+//! (`<C:expand>`), so each occurrence arrives as its own VEVENT.
+//!
+//! Times with a `TZID` are taken as local time: the app carries no
+//! timezone database, and a personal calendar is almost always in the
+//! device's own zone.
+
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// One content line: name, parameters, raw value.
+type Property = (String, Vec<(String, String)>, String);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attendee {
+    /// Lowercased address, without `mailto:`.
+    pub email: String,
+    /// The `CN` parameter, when the calendar has one.
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub summary: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// A date-only event (`VALUE=DATE`); never a meeting to record.
+    pub all_day: bool,
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Organizer first, then attendees, without duplicates.
+    #[serde(default)]
+    pub attendees: Vec<Attendee>,
+}
+
+impl CalendarEvent {
+    /// Whether the event starts within `lead` before or `grace` after
+    /// `now` — the window in which to offer recording it.
+    pub fn starting(&self, now: DateTime<Utc>, lead: Duration, grace: Duration) -> bool {
+        !self.all_day && self.start - lead <= now && now <= self.start + grace && now < self.end
+    }
+}
+
+/// The events in iCalendar text. Cancelled events and VEVENTs without a
+/// start are skipped; an event without an end lasts an hour (a day when
+/// all-day).
+pub fn parse_ics(ics: &str) -> Vec<CalendarEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<Vec<Property>> = None;
+    for line in unfold(ics) {
+        let Some((name, params, value)) = property(&line) else {
+            continue;
+        };
+        match (name.as_str(), value.as_str()) {
+            ("BEGIN", "VEVENT") => current = Some(Vec::new()),
+            ("END", "VEVENT") => {
+                if let Some(props) = current.take() {
+                    events.extend(event(props));
+                }
+            }
+            _ => {
+                if let Some(props) = current.as_mut() {
+                    props.push((name, params, value));
+                }
+            }
+        }
+    }
+    events
+}
+
+/// Lines with their folded continuations (leading space or tab) joined.
+fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in ics.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+/// `NAME;PARAM=VALUE;…:value` split into its name (uppercased), its
+/// parameters (names uppercased, quotes removed) and its raw value.
+fn property(line: &str) -> Option<Property> {
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(i, c)| {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        (c == ':' && !quoted).then_some(i)
+    })?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| {
+            (
+                k.trim().to_ascii_uppercase(),
+                v.trim_matches('"').to_string(),
+            )
+        })
+        .collect();
+    Some((name, params, value.to_string()))
+}
+
+fn param<'a>(params: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|(k, _)| k == name)
+        .map(|(_, v)| v.as_str())
+}
+
+/// An iCalendar TEXT value with its escapes undone.
+fn text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+/// A DATE or DATE-TIME value, and whether it was a bare date.
+fn when(params: &[(String, String)], value: &str) -> Option<(DateTime<Utc>, bool)> {
+    let value = value.trim();
+    if param(params, "VALUE") == Some("DATE") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        let midnight = date.and_hms_opt(0, 0, 0)?;
+        return Some((local(midnight)?, true));
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        let at = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some((Utc.from_utc_datetime(&at), false));
+    }
+    let at = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    Some((local(at)?, false))
+}
+
+fn local(at: NaiveDateTime) -> Option<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&at)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+fn attendee(params: &[(String, String)], value: &str) -> Option<Attendee> {
+    let email = value.trim();
+    let email = email
+        .get(..7)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("mailto:"))
+        .map_or(email, |_| &email[7..]);
+    if !email.contains('@') {
+        return None;
+    }
+    Some(Attendee {
+        email: email.to_ascii_lowercase(),
+        name: param(params, "CN")
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(str::to_string),
+    })
+}
+
+fn event(props: Vec<Property>) -> Option<CalendarEvent> {
+    let mut uid = String::new();
+    let mut summary = String::new();
+    let mut start = None;
+    let mut end = None;
+    let mut duration = None;
+    let mut location = None;
+    let mut description = None;
+    let mut organizer = None;
+    let mut attendees: Vec<Attendee> = Vec::new();
+    let mut recurrence = None;
+    for (name, params, value) in props {
+        match name.as_str() {
+            "UID" => uid = value,
+            "SUMMARY" => summary = text(&value),
+            "DTSTART" => start = when(&params, &value),
+            "DTEND" => end = when(&params, &value).map(|(at, _)| at),
+            "DURATION" => duration = iso_duration(&value),
+            "LOCATION" => location = Some(text(&value)).filter(|l| !l.is_empty()),
+            "DESCRIPTION" => description = Some(text(&value)).filter(|d| !d.is_empty()),
+            "ORGANIZER" => organizer = attendee(&params, &value),
+            "ATTENDEE" => attendees.extend(attendee(&params, &value)),
+            "RECURRENCE-ID" => recurrence = Some(value),
+            "STATUS" if value.eq_ignore_ascii_case("CANCELLED") => return None,
+            _ => {}
+        }
+    }
+    let (start, all_day) = start?;
+    let end = end
+        .or_else(|| duration.map(|d| start + d))
+        .unwrap_or_else(|| {
+            start
+                + if all_day {
+                    Duration::days(1)
+                } else {
+                    Duration::hours(1)
+                }
+        });
+    if let Some(organizer) = organizer {
+        attendees.insert(0, organizer);
+    }
+    let mut seen = std::collections::HashSet::new();
+    attendees.retain(|a| seen.insert(a.email.clone()));
+    // Occurrences of a recurring event share its UID.
+    if let Some(recurrence) = recurrence {
+        uid = format!("{uid}/{recurrence}");
+    }
+    Some(CalendarEvent {
+        uid,
+        summary,
+        start,
+        end,
+        all_day,
+        location,
+        description,
+        attendees,
+    })
+}
+
+/// An RFC 5545 DURATION such as `PT1H30M` or `P1D`. Negative durations
+/// are not meaningful for an event's length.
+fn iso_duration(value: &str) -> Option<Duration> {
+    let rest = value.trim().strip_prefix('+').unwrap_or(value.trim());
+    let rest = rest.strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                total += match unit {
+                    'W' => Duration::weeks(n),
+                    'D' => Duration::days(n),
+                    'H' => Duration::hours(n),
+                    'M' => Duration::minutes(n),
+                    'S' => Duration::seconds(n),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    Some(total)
+}
+
+/// The iCalendar text in each `calendar-data` element of a CalDAV
+/// multistatus response, whatever namespace prefix the server uses.
+pub fn calendar_data(xml: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut rest = xml;
+    while let Some(open) = find_tag(rest, "calendar-data") {
+        let after = &rest[open..];
+        let Some(gt) = after.find('>') else { break };
+        if after[..gt].ends_with('/') {
+            rest = &after[gt + 1..];
+            continue;
+        }
+        let body = &after[gt + 1..];
+        let Some(close) = body
+            .find("calendar-data>")
+            .and_then(|i| body[..i].rfind("</"))
+        else {
+            break;
+        };
+        out.push(unescape_xml(&body[..close]));
+        rest = &body[close..];
+        rest = &rest[rest.find('>').map_or(rest.len(), |i| i + 1)..];
+    }
+    out
+}
+
+/// Offset of the next start tag whose local name is `local`.
+fn find_tag(xml: &str, local: &str) -> Option<usize> {
+    let mut from = 0;
+    while let Some(i) = xml[from..].find('<') {
+        let start = from + i;
+        let name: String = xml[start + 1..]
+            .chars()
+            .take_while(|c| !c.is_whitespace() && *c != '>' && *c != '/')
+            .collect();
+        if name.rsplit(':').next() == Some(local) && !name.is_empty() {
+            return Some(start);
+        }
+        from = start + 1;
+    }
+    None
+}
+
+fn unescape_xml(s: &str) -> String {
+    let s = s.trim();
+    if let Some(cdata) = s
+        .strip_prefix("<![CDATA[")
+        .and_then(|c| c.strip_suffix("]]>"))
+    {
+        return cdata.to_string();
+    }
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#13;", "\r")
+        .replace("&amp;", "&")
+}
+
+/// A CalDAV calendar collection, read with basic auth.
+#[cfg(feature = "caldav")]
+pub struct CalDavClient {
+    config: crate::config::CalDavAccountConfig,
+    password: zeroize::Zeroizing<String>,
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "caldav")]
+impl CalDavClient {
+    pub fn new(config: crate::config::CalDavAccountConfig, password: String) -> Self {
+        Self {
+            config,
+            password: zeroize::Zeroizing::new(password),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Events overlapping `start..end`, recurring ones expanded.
+    pub async fn events_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<CalendarEvent>, crate::CommsError> {
+        use crate::CommsError;
+
+        let range = format!(
+            r#"start="{}" end="{}""#,
+            start.format("%Y%m%dT%H%M%SZ"),
+            end.format("%Y%m%dT%H%M%SZ")
+        );
+        let query = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<C:calendar-query xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:prop><C:calendar-data><C:expand {range}/></C:calendar-data></D:prop>
+  <C:filter><C:comp-filter name="VCALENDAR"><C:comp-filter name="VEVENT">
+    <C:time-range {range}/>
+  </C:comp-filter></C:comp-filter></C:filter>
+</C:calendar-query>"#
+        );
+        let method =
+            reqwest::Method::from_bytes(b"REPORT").map_err(|e| CommsError::Other(e.to_string()))?;
+        let response = self
+            .client
+            .request(method, &self.config.url)
+            .basic_auth(&self.config.username, Some(self.password.as_str()))
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(query)
+            .send()
+            .await
+            .map_err(|e| CommsError::FetchFailed(format!("CalDAV request failed: {e}")))?;
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            return Err(CommsError::AuthFailed(format!(
+                "CalDAV server returned {status}"
+            )));
+        }
+        if !status.is_success() {
+            return Err(CommsError::FetchFailed(format!(
+                "CalDAV server returned {status}"
+            )));
+        }
+        let body = response
+            .text()
+            .await
+            .map_err(|e| CommsError::FetchFailed(format!("CalDAV response: {e}")))?;
+        Ok(calendar_data(&body)
+            .iter()
+            .flat_map(|ics| parse_ics(ics))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICS: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+UID:weekly-1\r\n\
+SUMMARY:Budget review\\, Q2\r\n\
+DTSTART:20260317T090000Z\r\n\
+DURATION:PT45M\r\n\
+LOCATION:Room 4\r\n\
+ORGANIZER;CN=\"Ana Ruiz\":mailto:Ana@Example.com\r\n\
+ATTENDEE;CN=Ben;ROLE=REQ-PARTICIPANT:mailto:ben@example.com\r\n\
+ATTENDEE;CN=Ana Ruiz:mailto:ana@example.com\r\n\
+DESCRIPTION:Agenda:\\n- numbers\\n- hiring plan for the new\r\n\
+\x20\x20team\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:cancelled\r\n\
+STATUS:CANCELLED\r\n\
+DTSTART:20260317T100000Z\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:holiday\r\n\
+SUMMARY:Holiday\r\n\
+DTSTART;VALUE=DATE:20260318\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_events_attendees_and_durations() {
+        let events = parse_ics(ICS);
+        assert_eq!(events.len(), 2, "the cancelled event is skipped");
+        let budget = &events[0];
+        assert_eq!(budget.summary, "Budget review, Q2");
+        assert_eq!(budget.start, at("2026-03-17T09:00:00Z"));
+        assert_eq!(budget.end, at("2026-03-17T09:45:00Z"));
+        assert_eq!(budget.location.as_deref(), Some("Room 4"));
+        assert_eq!(
+            budget.description.as_deref(),
+            Some("Agenda:\n- numbers\n- hiring plan for the new team")
+        );
+        let emails: Vec<&str> = budget.attendees.iter().map(|a| a.email.as_str()).collect();
+        assert_eq!(emails, ["ana@example.com", "ben@example.com"]);
+        assert_eq!(budget.attendees[0].name.as_deref(), Some("Ana Ruiz"));
+        assert!(events[1].all_day);
+    }
+
+    #[test]
+    fn starting_window_brackets_the_start() {
+        let event = &parse_ics(ICS)[0];
+        let lead = Duration::minutes(5);
+        let grace = Duration::minutes(10);
+        assert!(!event.starting(at("2026-03-17T08:50:00Z"), lead, grace));
+        assert!(event.starting(at("2026-03-17T08:56:00Z"), lead, grace));
+        assert!(event.starting(at("2026-03-17T09:08:00Z"), lead, grace));
+        assert!(!event.starting(at("2026-03-17T09:20:00Z"), lead, grace));
+        assert!(!parse_ics(ICS)[1].starting(at("2026-03-18T00:00:00Z"), lead, grace));
+    }
+
+    #[test]
+    fn extracts_calendar_data_from_multistatus() {
+        let xml = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav">
+ <d:response><d:propstat><d:prop>
+  <cal:calendar-data>BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:a
+SUMMARY:R&amp;D sync
+DTSTART:20260317T090000Z
+END:VEVENT
+END:VCALENDAR
+</cal:calendar-data>
+ </d:prop></d:propstat></d:response>
+ <d:response><d:propstat><d:prop><cal:calendar-data/></d:prop></d:propstat></d:response>
+</d:multistatus>"#;
+        let data = calendar_data(xml);
+        assert_eq!(data.len(), 1);
+        assert_eq!(parse_ics(&data[0])[0].summary, "R&D sync");
+    }
+}
@@ -13,6 +13,8 @@ pub struct CommsConfig {
     pub signal: Option<SignalAccountConfig>,
     #[serde(default)]
     pub whatsapp: Option<WhatsAppAccountConfig>,
+    #[serde(default)]
+    pub caldav: Option<CalDavAccountConfig>,
 }
 
 fn default_poll_interval() -> u64 {
@@ -27,6 +29,7 @@ impl Default for CommsConfig {
             email: None,
             signal: None,
             whatsapp: None,
+            caldav: None,
         }
     }
 }
@@ -104,6 +107,22 @@ fn default_whatsapp_api_version() -> String {
     "v21.0".into()
 }
 
+/// CalDAV calendar, read for the meeting mode.
+/// Password is NOT stored here — use the environment variable.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CalDavAccountConfig {
+    /// URL of the calendar collection (not the server root).
+    pub url: String,
+    pub username: String,
+    /// Minutes before an event starts to offer recording it.
+    #[serde(default = "default_meeting_lead_minutes")]
+    pub meeting_lead_minutes: u32,
+}
+
+fn default_meeting_lead_minutes() -> u32 {
+    5
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,6 +135,7 @@ mod tests {
         assert!(cfg.email.is_none());
         assert!(cfg.signal.is_none());
         assert!(cfg.whatsapp.is_none());
+        assert!(cfg.caldav.is_none());
     }
 
     #[test]
@@ -1,3 +1,4 @@
+pub mod calendar;
 pub mod channel;
 pub mod channels;
 pub mod config;
@@ -6,7 +7,10 @@ pub mod pii_hook;
 pub mod sync_engine;
 
 pub use channel::{ChannelStatus, CommunicationChannel, OutgoingMessage, SyncResult};
-pub use config::{CommsConfig, EmailAccountConfig, SignalAccountConfig, WhatsAppAccountConfig};
+pub use config::{
+    CalDavAccountConfig, CommsConfig, EmailAccountConfig, SignalAccountConfig,
+    WhatsAppAccountConfig,
+};
 pub use error::CommsError;
 pub use pii_hook::{ContactIngestHook, MessageIngestHook, ShareIngestHook};
-pub use sync_engine::{CommsEvent, CommsSync};
+pub use sync_engine::{CommsEvent, CommsHandle, CommsSync};
@@ -1,12 +1,14 @@
 use std::time::Duration;
 
+use sovereign_core::events::Publisher;
+use sovereign_core::lifecycle::ShutdownListener;
 use sovereign_db::schema::ChannelType;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 
-use crate::channel::{CommunicationChannel, SyncResult};
+use crate::channel::{ChannelStatus, CommunicationChannel, OutgoingMessage, SyncResult};
 use crate::error::CommsError;
 
-/// Events emitted by the communications sync engine.
+/// Events the communications sync engine publishes on the event bus.
 #[derive(Debug, Clone)]
 pub enum CommsEvent {
     NewMessages {
@@ -28,22 +30,95 @@ pub enum CommsEvent {
     },
 }
 
+/// Requests a [`CommsHandle`] makes of the running sync loop.
+enum CommsCommand {
+    Send {
+        channel: ChannelType,
+        msg: OutgoingMessage,
+        reply: oneshot::Sender<Result<String, CommsError>>,
+    },
+    Status {
+        reply: oneshot::Sender<Vec<(ChannelType, ChannelStatus)>>,
+    },
+    SetPollInterval(Duration),
+    /// Stop polling and disconnect; `done` is answered once stopped.
+    Stop { done: oneshot::Sender<()> },
+}
+
+/// Cloneable handle to a [`CommsSync`] that has been moved into its
+/// [`run`](CommsSync::run) task. Requests are served between sync cycles.
+#[derive(Clone)]
+pub struct CommsHandle {
+    tx: mpsc::Sender<CommsCommand>,
+}
+
+impl CommsHandle {
+    /// Connection status of every registered channel.
+    pub async fn channel_status(&self) -> Result<Vec<(ChannelType, ChannelStatus)>, CommsError> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(CommsCommand::Status { reply })
+            .await
+            .map_err(|_| CommsError::Other("comms sync stopped".into()))?;
+        rx.await.map_err(|_| CommsError::Other("comms sync stopped".into()))
+    }
+
+    /// Send `msg` through the registered channel of type `channel`.
+    pub async fn send(&self, channel: ChannelType, msg: OutgoingMessage) -> Result<String, CommsError> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(CommsCommand::Send { channel, msg, reply })
+            .await
+            .map_err(|_| CommsError::Other("comms sync stopped".into()))?;
+        rx.await.map_err(|_| CommsError::Other("comms sync stopped".into()))?
+    }
+
+    /// Poll every `secs` from now on; the next cycle is a full interval
+    /// away. Doesn't wait, so config reloads can call it from any thread.
+    pub fn set_poll_interval(&self, secs: u64) -> Result<(), CommsError> {
+        self.tx
+            .try_send(CommsCommand::SetPollInterval(Duration::from_secs(secs)))
+            .map_err(|e| CommsError::Other(format!("comms sync unavailable: {e}")))
+    }
+
+    /// Stop the sync loop and wait until its channels are disconnected, so
+    /// nothing more is polled or written. Already stopped is fine.
+    pub async fn stop(&self) {
+        let (done, rx) = oneshot::channel();
+        if self.tx.send(CommsCommand::Stop { done }).await.is_ok() {
+            let _ = rx.await;
+        }
+    }
+}
+
 /// Periodic sync engine that polls registered communication channels.
 pub struct CommsSync {
     channels: Vec<Box<dyn CommunicationChannel>>,
-    event_tx: mpsc::Sender<CommsEvent>,
+    event_tx: Publisher<CommsEvent>,
     poll_interval: Duration,
+    cmd_tx: mpsc::Sender<CommsCommand>,
+    cmd_rx: mpsc::Receiver<CommsCommand>,
+}
+
+fn emit_sync_error(tx: &Publisher<CommsEvent>, channel: ChannelType, error: &(dyn std::fmt::Display + Send + Sync)) {
+    tx.send(CommsEvent::SyncError {
+        channel,
+        error: error.to_string(),
+    });
 }
 
 impl CommsSync {
     pub fn new(
-        event_tx: mpsc::Sender<CommsEvent>,
+        event_tx: Publisher<CommsEvent>,
         poll_interval_secs: u64,
     ) -> Self {
+        let (cmd_tx, cmd_rx) = mpsc::channel(16);
         Self {
             channels: Vec::new(),
             event_tx,
             poll_interval: Duration::from_secs(poll_interval_secs),
+            cmd_tx,
+            cmd_rx,
         }
     }
 
@@ -52,6 +127,11 @@ impl CommsSync {
         self.channels.push(channel);
     }
 
+    /// A handle for sending through this engine once it is running.
+    pub fn handle(&self) -> CommsHandle {
+        CommsHandle { tx: self.cmd_tx.clone() }
+    }
+
     /// Connect all registered channels.
     pub async fn connect_all(&mut self) -> Vec<Result<(), CommsError>> {
         let mut results = Vec::new();
@@ -61,59 +141,125 @@ impl CommsSync {
         results
     }
 
-    /// Run the sync loop. This blocks and should be spawned as a tokio task.
-    pub async fn run(mut self) {
-        async fn emit_sync_error(tx: &mpsc::Sender<CommsEvent>, channel: ChannelType, error: &(dyn std::fmt::Display + Send + Sync)) {
-            let _ = tx.send(CommsEvent::SyncError {
-                channel,
-                error: error.to_string(),
-            }).await;
+    /// Connection status of every registered channel.
+    pub fn channel_status(&self) -> Vec<(ChannelType, ChannelStatus)> {
+        self.channels.iter().map(|ch| (ch.channel_type(), ch.status())).collect()
+    }
+
+    /// Send `msg` through the first connected channel of type `channel`.
+    pub async fn send(&self, channel: &ChannelType, msg: &OutgoingMessage) -> Result<String, CommsError> {
+        let mut candidates = self.channels.iter().filter(|ch| &ch.channel_type() == channel).peekable();
+        if candidates.peek().is_none() {
+            return Err(CommsError::NotConnected(format!("no {channel} account configured")));
+        }
+        match candidates.find(|ch| ch.status() == ChannelStatus::Connected) {
+            Some(ch) => ch.send_message(msg).await,
+            None => Err(CommsError::NotConnected(channel.to_string())),
+        }
+    }
+
+    async fn handle_command(&mut self, cmd: CommsCommand) {
+        match cmd {
+            CommsCommand::Send { channel, msg, reply } => {
+                let _ = reply.send(self.send(&channel, &msg).await);
+            }
+            CommsCommand::Status { reply } => {
+                let _ = reply.send(self.channel_status());
+            }
+            CommsCommand::SetPollInterval(period) => {
+                tracing::info!("Comms poll interval now {}s", period.as_secs());
+                self.poll_interval = period;
+            }
+            // Only reached while already stopping; dropping `done` answers it.
+            CommsCommand::Stop { .. } => {}
+        }
+    }
+
+    async fn sync_all(&mut self) {
+        for ch in &mut self.channels {
+            match ch.sync().await {
+                Ok(result) => {
+                    if result.new_messages > 0 || result.new_contacts > 0 {
+                        tracing::info!(
+                            "Sync {:?}: {} new msgs, {} new contacts",
+                            ch.channel_type(),
+                            result.new_messages,
+                            result.new_contacts,
+                        );
+                    }
+                    self.event_tx.send(CommsEvent::SyncComplete {
+                        channel: ch.channel_type(),
+                        result,
+                    });
+                }
+                Err(e) => {
+                    tracing::error!("Sync {:?} failed: {e}", ch.channel_type());
+                    emit_sync_error(&self.event_tx, ch.channel_type(), &e);
+                }
+            }
         }
+    }
 
+    /// Run the sync loop until `shutdown` fires or [`CommsHandle::stop`] is
+    /// called. This blocks and should be spawned as a tokio task. Take a
+    /// [`handle`](Self::handle) first to send while it runs.
+    pub async fn run(mut self, mut shutdown: ShutdownListener) {
         // Initial connect
         for ch in &mut self.channels {
             if let Err(e) = ch.connect().await {
                 tracing::warn!("Channel {:?} connect failed: {e}", ch.channel_type());
-                emit_sync_error(&self.event_tx, ch.channel_type(), &e).await;
+                emit_sync_error(&self.event_tx, ch.channel_type(), &e);
             }
         }
 
         let mut interval = tokio::time::interval(self.poll_interval);
+        let mut stopped = None;
         loop {
-            interval.tick().await;
-
-            for ch in &mut self.channels {
-                match ch.sync().await {
-                    Ok(result) => {
-                        if result.new_messages > 0 || result.new_contacts > 0 {
-                            tracing::info!(
-                                "Sync {:?}: {} new msgs, {} new contacts",
-                                ch.channel_type(),
-                                result.new_messages,
-                                result.new_contacts,
-                            );
-                        }
-                        let _ = self.event_tx.send(CommsEvent::SyncComplete {
-                            channel: ch.channel_type(),
-                            result,
-                        }).await;
+            tokio::select! {
+                _ = shutdown.triggered() => break,
+                _ = interval.tick() => self.sync_all().await,
+                Some(cmd) = self.cmd_rx.recv() => match cmd {
+                    CommsCommand::Stop { done } => {
+                        stopped = Some(done);
+                        break;
                     }
-                    Err(e) => {
-                        tracing::error!("Sync {:?} failed: {e}", ch.channel_type());
-                        emit_sync_error(&self.event_tx, ch.channel_type(), &e).await;
-                    }
-                }
+                    cmd => self.handle_command(cmd).await,
+                },
             }
+            if interval.period() != self.poll_interval {
+                let start = tokio::time::Instant::now() + self.poll_interval;
+                interval = tokio::time::interval_at(start, self.poll_interval);
+            }
+        }
+        self.stop().await;
+        if let Some(done) = stopped {
+            let _ = done.send(());
         }
     }
+
+    /// Serve the requests already queued, so no accepted send is lost, then
+    /// disconnect every channel. Requests made afterwards fail.
+    async fn stop(&mut self) {
+        self.cmd_rx.close();
+        while let Some(cmd) = self.cmd_rx.recv().await {
+            self.handle_command(cmd).await;
+        }
+        for ch in &mut self.channels {
+            if let Err(e) = ch.disconnect().await {
+                tracing::warn!("Channel {:?} disconnect failed: {e}", ch.channel_type());
+            }
+        }
+        tracing::info!("Comms sync stopped");
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::channel::{ChannelStatus, OutgoingMessage, SyncResult};
     use async_trait::async_trait;
     use chrono::{DateTime, Utc};
+    use sovereign_core::events::EventBus;
+    use sovereign_core::lifecycle::Shutdown;
     use sovereign_db::schema::{Contact, Message};
     use std::sync::{Arc, Mutex};
 
@@ -122,6 +268,7 @@ mod tests {
         ctype: ChannelType,
         should_fail: bool,
         connect_calls: Arc<Mutex<u32>>,
+        sync_calls: Arc<Mutex<u32>>,
     }
 
     impl MockChannel {
@@ -130,6 +277,7 @@ mod tests {
                 ctype,
                 should_fail: false,
                 connect_calls: Arc::new(Mutex::new(0)),
+                sync_calls: Arc::new(Mutex::new(0)),
             }
         }
 
@@ -138,6 +286,7 @@ mod tests {
                 ctype,
                 should_fail: true,
                 connect_calls: Arc::new(Mutex::new(0)),
+                sync_calls: Arc::new(Mutex::new(0)),
             }
         }
     }
@@ -156,7 +305,11 @@ mod tests {
             Ok(())
         }
         fn status(&self) -> ChannelStatus {
-            ChannelStatus::Connected
+            if self.should_fail {
+                ChannelStatus::Error("mock failure".into())
+            } else {
+                ChannelStatus::Connected
+            }
         }
         fn channel_type(&self) -> ChannelType {
             self.ctype.clone()
@@ -168,6 +321,7 @@ mod tests {
             Ok("mock-id".into())
         }
         async fn sync(&mut self) -> Result<SyncResult, CommsError> {
+            *self.sync_calls.lock().unwrap() += 1;
             Ok(SyncResult { new_messages: 0, updated_conversations: 0, new_contacts: 0 })
         }
         async fn resolve_contact(&self, address: &str) -> Result<Contact, CommsError> {
@@ -191,16 +345,26 @@ mod tests {
 
     #[tokio::test]
     async fn comms_sync_new() {
-        let (tx, _rx) = mpsc::channel(16);
-        let sync = CommsSync::new(tx, 60);
+        let bus = EventBus::new();
+        let sync = CommsSync::new(bus.publisher(), 60);
         assert!(sync.channels.is_empty());
         assert_eq!(sync.poll_interval, Duration::from_secs(60));
     }
 
+    #[tokio::test]
+    async fn poll_interval_changes_through_the_handle() {
+        let bus = EventBus::new();
+        let mut sync = CommsSync::new(bus.publisher(), 300);
+        sync.handle().set_poll_interval(60).unwrap();
+        let cmd = sync.cmd_rx.try_recv().unwrap();
+        sync.handle_command(cmd).await;
+        assert_eq!(sync.poll_interval, Duration::from_secs(60));
+    }
+
     #[tokio::test]
     async fn connect_all_succeeds_with_mock() {
-        let (tx, _rx) = mpsc::channel(16);
-        let mut sync = CommsSync::new(tx, 60);
+        let bus = EventBus::new();
+        let mut sync = CommsSync::new(bus.publisher(), 60);
 
         let ch1 = MockChannel::ok(ChannelType::Email);
         let ch1_calls = ch1.connect_calls.clone();
@@ -215,8 +379,8 @@ mod tests {
 
     #[tokio::test]
     async fn connect_all_captures_error() {
-        let (tx, _rx) = mpsc::channel(16);
-        let mut sync = CommsSync::new(tx, 60);
+        let bus = EventBus::new();
+        let mut sync = CommsSync::new(bus.publisher(), 60);
 
         let ch_ok = MockChannel::ok(ChannelType::Email);
         let ch_fail = MockChannel::failing(ChannelType::Email);
@@ -229,4 +393,114 @@ mod tests {
         assert!(results[0].is_ok());
         assert!(results[1].is_err());
     }
+
+    fn outgoing() -> OutgoingMessage {
+        OutgoingMessage {
+            to: vec!["alice@example.com".into()],
+            subject: None,
+            body: "Hello".into(),
+            body_html: None,
+            in_reply_to: None,
+            conversation_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn send_checks_channel_availability() {
+        let bus = EventBus::new();
+        let mut sync = CommsSync::new(bus.publisher(), 60);
+        sync.add_channel(Box::new(MockChannel::failing(ChannelType::Signal)));
+        sync.add_channel(Box::new(MockChannel::ok(ChannelType::Email)));
+
+        assert_eq!(sync.send(&ChannelType::Email, &outgoing()).await.unwrap(), "mock-id");
+        assert!(matches!(
+            sync.send(&ChannelType::Signal, &outgoing()).await,
+            Err(CommsError::NotConnected(_))
+        ));
+        assert!(matches!(
+            sync.send(&ChannelType::Sms, &outgoing()).await,
+            Err(CommsError::NotConnected(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn handle_sends_through_running_loop() {
+        let bus = EventBus::new();
+        let mut sync = CommsSync::new(bus.publisher(), 3600);
+        sync.add_channel(Box::new(MockChannel::ok(ChannelType::Email)));
+        let handle = sync.handle();
+        let task = tokio::spawn(sync.run(Shutdown::new().listener("comms")));
+
+        let status = handle.channel_status().await.unwrap();
+        assert_eq!(status, vec![(ChannelType::Email, ChannelStatus::Connected)]);
+        assert_eq!(handle.send(ChannelType::Email, outgoing()).await.unwrap(), "mock-id");
+
+        task.abort();
+        let _ = task.await;
+        assert!(handle.channel_status().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn failed_connect_is_published_on_the_bus() {
+        let bus = EventBus::new();
+        let events = bus.subscribe::<CommsEvent>();
+        let mut sync = CommsSync::new(bus.publisher(), 3600);
+        sync.add_channel(Box::new(MockChannel::failing(ChannelType::Email)));
+        let handle = sync.handle();
+        let task = tokio::spawn(sync.run(Shutdown::new().listener("comms")));
+
+        // Answered once the initial connect has run.
+        handle.channel_status().await.unwrap();
+        assert!(matches!(
+            events.try_recv(),
+            Ok(CommsEvent::SyncError { channel: ChannelType::Email, .. })
+        ));
+        task.abort();
+    }
+
+    #[tokio::test]
+    async fn shutdown_serves_queued_sends_then_stops() {
+        let bus = EventBus::new();
+        let mut sync = CommsSync::new(bus.publisher(), 3600);
+        sync.add_channel(Box::new(MockChannel::ok(ChannelType::Email)));
+        let handle = sync.handle();
+        let shutdown = Shutdown::new();
+        let listener = shutdown.listener("comms");
+
+        // Accepted before shutdown, not yet served.
+        let (reply, sent) = oneshot::channel();
+        handle
+            .tx
+            .try_send(CommsCommand::Send { channel: ChannelType::Email, msg: outgoing(), reply })
+            .unwrap();
+        let task = tokio::spawn(sync.run(listener));
+
+        assert!(shutdown.run(Duration::from_secs(5)).await.is_empty());
+        task.await.unwrap();
+        assert_eq!(sent.await.unwrap().unwrap(), "mock-id");
+        assert!(handle.channel_status().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn stop_ends_polling_before_returning() {
+        let bus = EventBus::new();
+        let mut sync = CommsSync::new(bus.publisher(), 3600);
+        let channel = MockChannel::ok(ChannelType::Email);
+        let syncs = channel.sync_calls.clone();
+        sync.add_channel(Box::new(channel));
+        let handle = sync.handle();
+        let task = tokio::spawn(sync.run(Shutdown::new().listener("comms")));
+
+        // The first poll runs at once; served requests come after it.
+        handle.channel_status().await.unwrap();
+        assert_eq!(*syncs.lock().unwrap(), 1);
+
+        // As on lock: once `stop` returns the loop is gone, so no later
+        // poll can write through a database swapped in after it.
+        handle.stop().await;
+        task.await.unwrap();
+        assert!(handle.channel_status().await.is_err());
+        assert_eq!(*syncs.lock().unwrap(), 1);
+        handle.stop().await;
+    }
 }
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AppConfig {
     #[serde(default)]
     pub database: DatabaseConfig,
@@ -19,9 +20,19 @@ pub struct AppConfig {
     pub p2p: P2pConfig,
     #[serde(default)]
     pub comms: CommsAppConfig,
+    #[serde(default)]
+    pub skills: SkillsConfig,
+    #[serde(default)]
+    pub autonomy: AutonomyConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub relevance: RelevanceConfig,
+    #[serde(default)]
+    pub snapshots: SnapshotsConfig,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct DatabaseConfig {
     pub mode: String,
@@ -37,12 +48,22 @@ impl Default for DatabaseConfig {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct UiConfig {
     pub theme: String,
     pub default_width: i32,
     pub default_height: i32,
+    /// Zoom factor for the whole interface, 0.75 to 2.0.
+    pub scale: f64,
+    /// Document editor font size in CSS pixels, before `scale`.
+    pub editor_font_size: u32,
+    /// CSS `font-family` list for the document editor.
+    pub editor_font_family: String,
+    /// Keyboard shortcut overrides, action id → combo such as `"Ctrl+Shift+F"`.
+    /// An empty combo unbinds the action; unlisted actions keep the
+    /// frontend's default binding.
+    pub shortcuts: BTreeMap<String, String>,
 }
 
 impl Default for UiConfig {
@@ -51,11 +72,36 @@ impl Default for UiConfig {
             theme: "dark".into(),
             default_width: 1280,
             default_height: 720,
+            scale: 1.0,
+            editor_font_size: 14,
+            editor_font_family: "'SF Mono', 'Fira Code', monospace".into(),
+            shortcuts: BTreeMap::new(),
+        }
+    }
+}
+
+/// `Ctrl+Alt+Shift+<key>`, modifiers optional and each at most once, or
+/// empty for "unbound". Which keys exist is the frontend's business; this
+/// only keeps the config to the shape it parses.
+fn is_valid_shortcut(combo: &str) -> bool {
+    if combo.is_empty() {
+        return true;
+    }
+    let mut parts: Vec<&str> = combo.split('+').collect();
+    let key = parts.pop().unwrap_or_default();
+    let mut seen = Vec::new();
+    for m in parts {
+        if !["Ctrl", "Alt", "Shift"].contains(&m) || seen.contains(&m) {
+            return false;
         }
+        seen.push(m);
     }
+    !key.is_empty()
+        && key.chars().count() <= 12
+        && !key.chars().any(|c| c.is_whitespace() || c.is_control())
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct AiConfig {
     pub model_dir: String,
@@ -63,10 +109,16 @@ pub struct AiConfig {
     pub reasoning_model: String,
     pub n_gpu_layers: i32,
     pub n_ctx: u32,
-    /// Prompt format: "chatml" (default), "mistral", "llama3".
+    /// Prompt format: "chatml" (default), "qwen3", "mistral", "llama3",
+    /// or an alias; see [`PROMPT_FORMATS`].
     pub prompt_format: String,
 }
 
+/// Every `ai.prompt_format` the prompt formatter parses, aliases included
+/// ("chatml-qwen3" for "qwen3", "llama" for "llama3").
+pub const PROMPT_FORMATS: &[&str] =
+    &["chatml", "qwen3", "chatml-qwen3", "mistral", "llama3", "llama"];
+
 impl Default for AiConfig {
     fn default() -> Self {
         Self {
@@ -80,7 +132,7 @@ impl Default for AiConfig {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct VoiceConfig {
     pub enabled: bool,
@@ -93,6 +145,8 @@ pub struct VoiceConfig {
     pub piper_binary: String,
     pub piper_model: String,
     pub piper_config: String,
+    /// Speak reminders aloud through Piper when they come due.
+    pub announce_reminders: bool,
 }
 
 impl Default for VoiceConfig {
@@ -106,13 +160,14 @@ impl Default for VoiceConfig {
             piper_binary: "piper".into(),
             piper_model: String::new(),
             piper_config: String::new(),
+            announce_reminders: false,
         }
     }
 }
 
 /// Vision integration: the jiminy-vision service (gestures + windowed VLM scene
 /// understanding) that feeds the orchestrator context and the camera UI.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct VisionConfig {
     /// Whether the vision integration is active (poller + camera UI).
@@ -137,7 +192,7 @@ impl Default for VisionConfig {
 }
 
 /// Encryption-at-rest configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct CryptoConfig {
     /// Whether encryption is enabled.
@@ -154,6 +209,12 @@ pub struct CryptoConfig {
     pub max_login_attempts: u32,
     /// Seconds the account is locked after exceeding max attempts.
     pub lockout_seconds: u32,
+    /// Minutes without input before the session locks itself (0 = never).
+    pub auto_lock_minutes: u32,
+    /// Milliseconds a passphrase derivation should take on this machine.
+    /// Argon2id parameters for new key stores are calibrated to it
+    /// (0 = fixed defaults, no calibration).
+    pub kdf_target_ms: u32,
 }
 
 impl Default for CryptoConfig {
@@ -166,12 +227,14 @@ impl Default for CryptoConfig {
             keystroke_reauth_minutes: 30,
             max_login_attempts: 10,
             lockout_seconds: 300,
+            auto_lock_minutes: 15,
+            kdf_target_ms: 1000,
         }
     }
 }
 
 /// P2P networking configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct P2pConfig {
     /// Whether P2P networking is enabled.
@@ -191,6 +254,9 @@ pub struct P2pConfig {
     /// false on desktop. The actual gating happens in
     /// `sovereign_p2p::ConnectivityState::allows_auto_sync`.
     pub wifi_only: bool,
+    /// Serve a read-only snapshot to paired companion viewers. Off by
+    /// default.
+    pub serve_viewer: bool,
     /// Opt-in to hosting other users' encrypted backup fragments and
     /// guardian key shards (P4.2). Off by default.
     pub backup_host_enabled: bool,
@@ -211,6 +277,7 @@ impl Default for P2pConfig {
             device_name: "Sovereign Device".into(),
             enable_mdns: true,
             wifi_only: cfg!(target_os = "android"),
+            serve_viewer: false,
             backup_host_enabled: false,
             backup_quota_mb: 64,
         }
@@ -218,7 +285,7 @@ impl Default for P2pConfig {
 }
 
 /// Communications configuration (email, messaging, etc.)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct CommsAppConfig {
     pub enabled: bool,
@@ -234,6 +301,96 @@ impl Default for CommsAppConfig {
     }
 }
 
+/// Skill execution configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SkillsConfig {
+    /// Skill jobs allowed to run at once; the rest wait in the job queue.
+    pub max_concurrent_jobs: usize,
+}
+
+impl Default for SkillsConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_jobs: 2,
+        }
+    }
+}
+
+/// How much the assistant may do without asking.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutonomyConfig {
+    /// Consecutive approvals of the same Modify-level action before it
+    /// is auto-approved.
+    pub auto_approve_after: u32,
+}
+
+impl Default for AutonomyConfig {
+    fn default() -> Self {
+        Self {
+            auto_approve_after: 5,
+        }
+    }
+}
+
+/// On-device performance metrics (see `crate::metrics`). Off unless the
+/// user turns them on; they never leave the machine either way.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    /// Days of history kept; older days are dropped.
+    pub retention_days: u32,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_days: 30,
+        }
+    }
+}
+
+/// Relationship strength upkeep (see `sovereign_app::relevance`): edges
+/// whose documents are used together grow stronger, idle ones fade.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RelevanceConfig {
+    pub enabled: bool,
+    /// Days for an idle edge's strength to halve.
+    pub half_life_days: u32,
+}
+
+impl Default for RelevanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            half_life_days: 90,
+        }
+    }
+}
+
+/// Retention for `sovereign snapshot`: after each new snapshot, all but
+/// the newest `keep` go, and so do those older than `max_age_days` (0 =
+/// no age limit). The newest snapshot is always kept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnapshotsConfig {
+    pub keep: u32,
+    pub max_age_days: u32,
+}
+
+impl Default for SnapshotsConfig {
+    fn default() -> Self {
+        Self {
+            keep: 10,
+            max_age_days: 0,
+        }
+    }
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -245,11 +402,165 @@ impl Default for AppConfig {
             crypto: CryptoConfig::default(),
             p2p: P2pConfig::default(),
             comms: CommsAppConfig::default(),
+            skills: SkillsConfig::default(),
+            autonomy: AutonomyConfig::default(),
+            metrics: MetricsConfig::default(),
+            relevance: RelevanceConfig::default(),
+            snapshots: SnapshotsConfig::default(),
         }
     }
 }
 
 impl AppConfig {
+    /// Where in-app edits are saved. Its settings override the project
+    /// `config/default.toml` (see [`layer_paths`](Self::layer_paths)).
+    pub fn user_config_path() -> std::path::PathBuf {
+        crate::sovereign_dir().join("config.toml")
+    }
+
+    /// Write this configuration as TOML, replacing `path` atomically.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("toml.tmp");
+        std::fs::write(&tmp, toml::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Check the values a user can edit in Settings. Returns every
+    /// problem found, one message per field.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let errors = self.field_errors();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.into_iter().map(|(key, problem)| format!("{key} {problem}")).collect())
+        }
+    }
+
+    /// [`validate`](Self::validate)'s findings as `(dotted key, problem)`.
+    fn field_errors(&self) -> Vec<(String, String)> {
+        let mut errors: Vec<(String, String)> = Vec::new();
+        if self.ai.model_dir.trim().is_empty() {
+            errors.push(("ai.model_dir".into(), "must not be empty".into()));
+        }
+        if self.ai.n_gpu_layers < -1 {
+            errors.push(("ai.n_gpu_layers".into(), "must be -1 (all) or more".into()));
+        }
+        if !(512..=131_072).contains(&self.ai.n_ctx) {
+            errors.push(("ai.n_ctx".into(), "must be between 512 and 131072".into()));
+        }
+        if !PROMPT_FORMATS.contains(&self.ai.prompt_format.to_lowercase().as_str()) {
+            errors.push((
+                "ai.prompt_format".into(),
+                format!("must be one of {}", PROMPT_FORMATS.join(", ")),
+            ));
+        }
+        if !["cpal", "jiminy"].contains(&self.voice.voice_source.as_str()) {
+            errors.push(("voice.voice_source".into(), "must be cpal or jiminy".into()));
+        }
+        if self.voice.voice_source == "jiminy" && !self.voice.jiminy_ws_url.starts_with("ws") {
+            errors.push((
+                "voice.jiminy_ws_url".into(),
+                "must be a ws:// or wss:// URL".into(),
+            ));
+        }
+        if !["dark", "light"].contains(&self.ui.theme.as_str()) {
+            errors.push(("ui.theme".into(), "must be dark or light".into()));
+        }
+        if !(0.75..=2.0).contains(&self.ui.scale) {
+            errors.push(("ui.scale".into(), "must be between 0.75 and 2.0".into()));
+        }
+        if !(10..=32).contains(&self.ui.editor_font_size) {
+            errors.push(("ui.editor_font_size".into(), "must be between 10 and 32".into()));
+        }
+        // Goes into a CSS custom property: a family list, nothing more.
+        let family = self.ui.editor_font_family.trim();
+        if family.is_empty()
+            || family.len() > 200
+            || family.chars().any(|c| c.is_control() || matches!(c, ';' | '{' | '}' | '\\'))
+        {
+            errors.push(("ui.editor_font_family".into(), "must be a font-family list".into()));
+        }
+        for (action, combo) in &self.ui.shortcuts {
+            let id_ok = !action.is_empty()
+                && action.len() <= 40
+                && action.chars().all(|c| c.is_ascii_lowercase() || c == '_' || c == '.');
+            if !id_ok || !is_valid_shortcut(combo) {
+                errors.push((
+                    format!("ui.shortcuts.{action}"),
+                    "is not a valid shortcut".into(),
+                ));
+            }
+        }
+        if self.comms.poll_interval_secs < 30 {
+            errors.push(("comms.poll_interval_secs".into(), "must be at least 30".into()));
+        }
+        let name = self.p2p.device_name.trim();
+        if name.is_empty() || name.chars().count() > 64 {
+            errors.push(("p2p.device_name".into(), "must be 1 to 64 characters".into()));
+        }
+        if self.p2p.backup_quota_mb == 0 {
+            errors.push(("p2p.backup_quota_mb".into(), "must be at least 1".into()));
+        }
+        if !(1..=16).contains(&self.skills.max_concurrent_jobs) {
+            errors.push((
+                "skills.max_concurrent_jobs".into(),
+                "must be between 1 and 16".into(),
+            ));
+        }
+        if self.autonomy.auto_approve_after == 0 {
+            errors.push(("autonomy.auto_approve_after".into(), "must be at least 1".into()));
+        }
+        if !(1..=365).contains(&self.metrics.retention_days) {
+            errors.push(("metrics.retention_days".into(), "must be between 1 and 365".into()));
+        }
+        if !(7..=3650).contains(&self.relevance.half_life_days) {
+            errors.push((
+                "relevance.half_life_days".into(),
+                "must be between 7 and 3650".into(),
+            ));
+        }
+        if !(1..=1000).contains(&self.snapshots.keep) {
+            errors.push(("snapshots.keep".into(), "must be between 1 and 1000".into()));
+        }
+        errors
+    }
+
+    /// The sections changed between `self` and `new` that only take
+    /// effect after a restart. Theme, scale, editor font, shortcuts, voice,
+    /// the comms poll interval, skill job concurrency, autonomy, metrics,
+    /// relevance and snapshot retention are applied live.
+    pub fn restart_required(&self, new: &AppConfig) -> Vec<&'static str> {
+        let mut sections = Vec::new();
+        if self.database != new.database {
+            sections.push("database");
+        }
+        if self.ai != new.ai {
+            sections.push("ai");
+        }
+        if self.vision != new.vision {
+            sections.push("vision");
+        }
+        if self.crypto != new.crypto {
+            sections.push("crypto");
+        }
+        if self.p2p != new.p2p {
+            sections.push("p2p");
+        }
+        if self.comms.enabled != new.comms.enabled {
+            sections.push("comms");
+        }
+        if (self.ui.default_width, self.ui.default_height)
+            != (new.ui.default_width, new.ui.default_height)
+        {
+            sections.push("ui");
+        }
+        sections
+    }
+
     /// Load configuration from a TOML file.
     pub fn load(path: &Path) -> anyhow::Result<Self> {
         let content = std::fs::read_to_string(path)?;
@@ -257,56 +568,105 @@ impl AppConfig {
         Ok(config)
     }
 
-    /// Load config with fallback chain: explicit path → CWD → project root → hardcoded defaults.
-    /// Relative paths in `ai.model_dir` are resolved against the directory that contained the
-    /// config file (or the project root for hardcoded defaults).
-    pub fn load_or_default(explicit_path: Option<&Path>) -> Self {
-        let mut cfg = None;
-        let mut config_root: Option<std::path::PathBuf> = None;
-
-        if let Some(path) = explicit_path {
-            match Self::load(path) {
-                Ok(c) => {
-                    config_root = path.parent().and_then(|p| p.parent()).map(|p| p.to_path_buf());
-                    cfg = Some(c);
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to load config from {}: {e}", path.display());
-                }
+    /// Parse the text of one config file, reporting every problem with
+    /// where it is: a syntax or type error (parsing stops at the first),
+    /// keys that aren't settings, and values out of range. Settings the
+    /// text leaves out take their defaults.
+    pub fn parse_checked(text: &str) -> Result<Self, Vec<ConfigIssue>> {
+        match Self::check(text) {
+            (Some((config, _)), issues) if issues.is_empty() => Ok(config),
+            (_, issues) => Err(issues),
+        }
+    }
+
+    /// [`parse_checked`](Self::parse_checked), also handing back the raw
+    /// table when the text parsed and type-checked, whatever else is wrong.
+    fn check(text: &str) -> (Option<(Self, toml::Table)>, Vec<ConfigIssue>) {
+        let parsed = toml::from_str::<Self>(text)
+            .and_then(|config| toml::from_str::<toml::Table>(text).map(|raw| (config, raw)));
+        let (config, raw) = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                let at = e.span().map(|span| line_col(text, span.start));
+                return (None, vec![ConfigIssue::new(at, None, e.message().trim())]);
+            }
+        };
+        let mut issues = Vec::new();
+        if let Ok(known) = toml::Value::try_from(&config) {
+            for key in unknown_keys(&toml::Value::Table(raw.clone()), &known) {
+                issues.push(ConfigIssue::new(locate(text, &key), Some(key), "is not a setting"));
             }
         }
+        for (key, problem) in config.field_errors() {
+            issues.push(ConfigIssue::new(locate(text, &key), Some(key), problem));
+        }
+        (Some((config, raw)), issues)
+    }
 
-        if cfg.is_none() {
-            // Search for config/default.toml relative to CWD, then project root
-            let candidates = Self::config_search_paths();
-            for path in &candidates {
-                if path.exists() {
-                    match Self::load(path) {
-                        Ok(c) => {
-                            // config_root = grandparent of config file (project root)
-                            config_root =
-                                path.parent().and_then(|p| p.parent()).map(|p| p.to_path_buf());
-                            tracing::info!("Loaded config from {}", path.display());
-                            cfg = Some(c);
-                            break;
-                        }
-                        Err(e) => {
-                            tracing::warn!(
-                                "Failed to load config from {}: {e}",
-                                path.display()
-                            );
-                        }
-                    }
-                }
+    /// The files [`load_layered`](Self::load_layered) reads, lowest
+    /// precedence first: the project `config/default.toml`, the user
+    /// config Settings saves to, then `explicit` (`--config`).
+    pub fn layer_paths(explicit: Option<&Path>) -> Vec<PathBuf> {
+        let mut paths = Self::config_search_paths();
+        paths.push(Self::user_config_path());
+        if let Some(path) = explicit {
+            if !paths.iter().any(|p| p == path) {
+                paths.push(path.to_path_buf());
             }
         }
+        paths
+    }
 
-        let root = config_root.unwrap_or_else(|| Self::project_root());
+    /// The hardcoded defaults overlaid with each existing file of
+    /// [`layer_paths`](Self::layer_paths) in turn, so a file only needs
+    /// the settings it changes. A file that doesn't parse or sets a value
+    /// of the wrong type is skipped; every problem found in any file is
+    /// returned alongside. A relative `ai.model_dir` resolves against the
+    /// project root, or for an explicit file against the directory above
+    /// the one holding it.
+    pub fn load_layered(explicit: Option<&Path>) -> (Self, Vec<ConfigIssue>) {
+        let mut merged = toml::Table::new();
+        let mut issues = Vec::new();
+        let mut root = Self::project_root();
+        let user = Self::user_config_path();
+        for path in Self::layer_paths(explicit) {
+            if !path.exists() {
+                continue;
+            }
+            let (layer, mut found) = match std::fs::read_to_string(&path) {
+                Ok(text) => Self::check(&text),
+                Err(e) => (None, vec![ConfigIssue::new(None, None, e.to_string())]),
+            };
+            for issue in &mut found {
+                issue.file = Some(path.clone());
+            }
+            issues.append(&mut found);
+            let Some((_, raw)) = layer else {
+                continue;
+            };
+            tracing::info!("Loaded config from {}", path.display());
+            let sets_model_dir = raw
+                .get("ai")
+                .and_then(|ai| ai.get("model_dir"))
+                .is_some();
+            // Settings edits saved by the app still resolve against the
+            // project root.
+            if sets_model_dir && path != user {
+                if let Some(dir) = path.parent().and_then(|p| p.parent()) {
+                    root = dir.to_path_buf();
+                }
+            }
+            merge(&mut merged, raw);
+        }
 
-        let mut cfg = cfg.unwrap_or_else(|| {
-            tracing::info!("Using hardcoded default configuration");
-            Self::default()
-        });
+        let mut cfg: Self = match toml::Value::Table(merged).try_into() {
+            Ok(cfg) => cfg,
+            Err(e) => {
+                issues.push(ConfigIssue::new(None, None, e.to_string()));
+                tracing::info!("Using hardcoded default configuration");
+                Self::default()
+            }
+        };
 
         // Resolve relative model_dir against the project root
         let model_path = Path::new(&cfg.ai.model_dir);
@@ -316,6 +676,15 @@ impl AppConfig {
             tracing::info!("Resolved model_dir to {}", cfg.ai.model_dir);
         }
 
+        (cfg, issues)
+    }
+
+    /// [`load_layered`](Self::load_layered), logging the problems found.
+    pub fn load_or_default(explicit_path: Option<&Path>) -> Self {
+        let (cfg, issues) = Self::load_layered(explicit_path);
+        for issue in &issues {
+            tracing::warn!("Config: {issue}");
+        }
         cfg
     }
 
@@ -354,6 +723,136 @@ impl AppConfig {
     }
 }
 
+/// One problem in a config file, located as precisely as the file allows.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigIssue {
+    pub file: Option<PathBuf>,
+    /// 1-based, like an editor's.
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    /// The dotted setting at fault, e.g. `ui.scale`.
+    pub key: Option<String>,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    fn new(at: Option<(usize, usize)>, key: Option<String>, message: impl Into<String>) -> Self {
+        Self {
+            file: None,
+            line: at.map(|(line, _)| line),
+            column: at.map(|(_, column)| column),
+            key,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, "{line}:{column}:")?;
+        }
+        if self.file.is_some() || self.line.is_some() {
+            f.write_str(" ")?;
+        }
+        if let Some(key) = &self.key {
+            write!(f, "{key} ")?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+/// Dotted keys in `raw` (a config file) that didn't survive parsing into
+/// `known` (the parsed config serialized back).
+pub fn unknown_keys(raw: &toml::Value, known: &toml::Value) -> Vec<String> {
+    fn walk(prefix: &str, raw: &toml::Value, known: &toml::Value, out: &mut Vec<String>) {
+        let (toml::Value::Table(raw), toml::Value::Table(known)) = (raw, known) else {
+            return;
+        };
+        for (k, v) in raw {
+            let key = if prefix.is_empty() {
+                k.clone()
+            } else {
+                format!("{prefix}.{k}")
+            };
+            match known.get(k) {
+                Some(kv) => walk(&key, v, kv, out),
+                None => out.push(key),
+            }
+        }
+    }
+    let mut out = Vec::new();
+    walk("", raw, known, &mut out);
+    out
+}
+
+/// Overlay `over` onto `base`, merging tables key by key.
+fn merge(base: &mut toml::Table, over: toml::Table) {
+    for (key, value) in over {
+        match value {
+            toml::Value::Table(inner) if base.get(&key).is_some_and(toml::Value::is_table) => {
+                if let Some(toml::Value::Table(existing)) = base.get_mut(&key) {
+                    merge(existing, inner);
+                }
+            }
+            value => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// 1-based line and column of byte `offset` in `text`.
+fn line_col(text: &str, offset: usize) -> (usize, usize) {
+    let before = text.get(..offset).unwrap_or(text);
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+}
+
+/// Where dotted `key` is set, or its table opened, in `text`. Reads
+/// `[table]` headers and `key = value` lines, which is all `save` writes
+/// and what hand-edited config files use.
+fn locate(text: &str, key: &str) -> Option<(usize, usize)> {
+    let mut table = String::new();
+    for (n, line) in text.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') {
+            continue;
+        }
+        let here = if let Some(header) = trimmed.strip_prefix('[') {
+            let Some(end) = header.find(']') else {
+                continue;
+            };
+            table = plain_key(&header[..end]);
+            table.clone()
+        } else if let Some((k, _)) = trimmed.split_once('=') {
+            if table.is_empty() {
+                plain_key(k)
+            } else {
+                format!("{table}.{}", plain_key(k))
+            }
+        } else {
+            continue;
+        };
+        if here == key {
+            let column = line[..line.len() - trimmed.len()].chars().count() + 1;
+            return Some((n + 1, column));
+        }
+    }
+    None
+}
+
+/// A TOML key as written (`ui . "theme"`) in plain dotted form.
+fn plain_key(key: &str) -> String {
+    key.split('.')
+        .map(|part| part.trim().trim_matches(|c| c == '"' || c == '\''))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -371,4 +870,132 @@ mod tests {
     fn app_config_default_includes_vision() {
         assert_eq!(AppConfig::default().vision.window_seconds, 300.0);
     }
+
+    #[test]
+    fn default_config_validates() {
+        assert_eq!(AppConfig::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_reports_every_bad_field() {
+        let mut cfg = AppConfig::default();
+        cfg.ai.n_ctx = 16;
+        cfg.ui.theme = "neon".into();
+        cfg.skills.max_concurrent_jobs = 0;
+        let errors = cfg.validate().unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].starts_with("ai.n_ctx"));
+    }
+
+    #[test]
+    fn validate_accepts_every_parsed_prompt_format() {
+        let mut cfg = AppConfig::default();
+        for format in PROMPT_FORMATS {
+            cfg.ai.prompt_format = format.to_string();
+            assert!(cfg.validate().is_ok(), "{format}");
+        }
+        cfg.ai.prompt_format = "alpaca".into();
+        assert!(cfg.validate().unwrap_err()[0].starts_with("ai.prompt_format"));
+    }
+
+    #[test]
+    fn validate_bounds_ui_scale_and_editor_font() {
+        let mut cfg = AppConfig::default();
+        assert!(cfg.validate().is_ok());
+        cfg.ui.scale = 3.0;
+        cfg.ui.editor_font_size = 8;
+        cfg.ui.editor_font_family = "serif; color: red".into();
+        let errors = cfg.validate().unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().all(|e| e.starts_with("ui.")));
+        cfg.ui.scale = 1.5;
+        cfg.ui.editor_font_size = 18;
+        cfg.ui.editor_font_family = "\"JetBrains Mono\", monospace".into();
+        assert!(cfg.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_checks_shortcut_shape() {
+        let mut cfg = AppConfig::default();
+        cfg.ui.shortcuts.insert("search".into(), "Ctrl+Shift+F".into());
+        cfg.ui.shortcuts.insert("inbox".into(), "".into());
+        cfg.ui.shortcuts.insert("shortcuts".into(), "?".into());
+        assert!(cfg.validate().is_ok());
+        cfg.ui.shortcuts.insert("lock".into(), "Ctrl+Ctrl+L".into());
+        cfg.ui.shortcuts.insert("chat".into(), "Hyper+N".into());
+        cfg.ui.shortcuts.insert("Bad Id".into(), "Ctrl+B".into());
+        cfg.ui.shortcuts.insert("undo".into(), "Ctrl+".into());
+        let errors = cfg.validate().unwrap_err();
+        assert_eq!(errors.len(), 4);
+        assert!(errors.iter().all(|e| e.starts_with("ui.shortcuts.")));
+    }
+
+    #[test]
+    fn live_settings_need_no_restart() {
+        let old = AppConfig::default();
+        let mut new = old.clone();
+        new.ui.theme = "light".into();
+        new.skills.max_concurrent_jobs = 4;
+        new.autonomy.auto_approve_after = 2;
+        new.ui.scale = 1.25;
+        new.ui.editor_font_size = 16;
+        new.ui.shortcuts.insert("search".into(), "Ctrl+K".into());
+        new.voice.enabled = true;
+        new.comms.poll_interval_secs = 600;
+        new.metrics.enabled = true;
+        new.relevance.enabled = false;
+        assert!(old.restart_required(&new).is_empty());
+        new.ai.n_gpu_layers = 0;
+        new.p2p.device_name = "Desk".into();
+        assert_eq!(old.restart_required(&new), vec!["ai", "p2p"]);
+    }
+
+    #[test]
+    fn parse_checked_locates_each_problem() {
+        let text = "[ui]\ntheme = \"dark\"\n  them = \"light\"\nscale = 5.0\n\n[extra]\na = 1\n";
+        let mut found: Vec<_> = AppConfig::parse_checked(text)
+            .unwrap_err()
+            .into_iter()
+            .map(|i| (i.key.unwrap(), i.line.unwrap(), i.column.unwrap()))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                ("extra".to_string(), 6, 1),
+                ("ui.scale".to_string(), 4, 1),
+                ("ui.them".to_string(), 3, 3),
+            ]
+        );
+
+        let wrong_type = "[ui]\nscale = 1.0\neditor_font_size = \"big\"\n";
+        let issues = AppConfig::parse_checked(wrong_type).unwrap_err();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(3));
+        assert!(AppConfig::parse_checked("[ui]\ntheme = \"light\"\n").is_ok());
+    }
+
+    #[test]
+    fn layers_override_only_what_they_set() {
+        let base = "[ui]\ntheme = \"dark\"\nscale = 1.5\n[ui.shortcuts]\nsearch = \"Ctrl+K\"\n";
+        let over = "[ui]\ntheme = \"light\"\n[ui.shortcuts]\nlock = \"Ctrl+L\"\n";
+        let mut merged: toml::Table = toml::from_str(base).unwrap();
+        merge(&mut merged, toml::from_str(over).unwrap());
+        let cfg: AppConfig = toml::Value::Table(merged).try_into().unwrap();
+        assert_eq!(cfg.ui.theme, "light");
+        assert_eq!(cfg.ui.scale, 1.5);
+        assert_eq!(cfg.ui.shortcuts.len(), 2);
+        assert_eq!(cfg.ai, AiConfig::default());
+    }
+
+    #[test]
+    fn saved_config_loads_back() {
+        let dir = std::env::temp_dir().join("sovereign_config_save");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("config.toml");
+        let mut cfg = AppConfig::default();
+        cfg.autonomy.auto_approve_after = 3;
+        cfg.save(&path).unwrap();
+        assert_eq!(AppConfig::load(&path).unwrap(), cfg);
+    }
 }
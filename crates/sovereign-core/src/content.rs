@@ -1,49 +1,49 @@
-use serde::{Deserialize, Serialize};
-
-#[derive(Serialize, Deserialize, Default, Clone, Debug)]
-pub struct ContentFields {
-    #[serde(default)]
-    pub body: String,
-    #[serde(default)]
-    pub images: Vec<ContentImage>,
-    #[serde(default)]
-    pub videos: Vec<ContentVideo>,
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
-pub struct ContentImage {
-    pub path: String,
-    #[serde(default)]
-    pub caption: String,
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
-pub struct ContentVideo {
-    pub path: String,
-    #[serde(default)]
-    pub caption: String,
-    #[serde(default)]
-    pub duration_secs: Option<f64>,
-    #[serde(default)]
-    pub thumbnail_path: Option<String>,
-}
-
-impl ContentFields {
-    pub fn parse(json: &str) -> Self {
-        match serde_json::from_str(json) {
-            Ok(cf) => cf,
-            Err(e) => {
-                if !json.is_empty() {
-                    tracing::warn!("ContentFields parse failed (falling back to default): {e}");
+pub use sovereign_sdk::blocks;
+pub use sovereign_sdk::content::{ContentFields, ContentFile, ContentImage, ContentVideo};
+
+/// `#tag` markers in a document's text, for the canvas tag filter and the
+/// encrypted tag index.
+///
+/// A tag is a `#` that opens the text or follows whitespace, then
+/// alphanumerics, `-` and `_`; lowercased, deduped, at most `max_tags`.
+/// Headings are not tags.
+pub fn hashtags(text: &str, max_tags: usize) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    let mut prev_is_space = true;
+    for (i, c) in text.char_indices() {
+        if c == '#' && prev_is_space {
+            let rest = &text[i + 1..];
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+                .unwrap_or(rest.len());
+            let name = rest[..end].to_lowercase();
+            if !name.is_empty() && !out.contains(&name) {
+                out.push(name);
+                if out.len() >= max_tags {
+                    break;
                 }
-                Self::default()
             }
         }
+        prev_is_space = c.is_whitespace();
     }
+    out
+}
 
-    pub fn serialize(&self) -> String {
-        serde_json::to_string(self).unwrap_or_default()
+/// The searchable text of a document's content JSON: the body plus image and
+/// video captions. Content that is not the usual JSON is taken as is.
+pub fn indexable_text(content: &str) -> String {
+    let Ok(serde_json::Value::Object(fields)) = serde_json::from_str(content) else {
+        return content.to_string();
+    };
+    let mut text = fields.get("body").and_then(|b| b.as_str()).unwrap_or_default().to_string();
+    for media in ["images", "videos"] {
+        let captions = fields.get(media).and_then(|m| m.as_array()).into_iter().flatten();
+        for caption in captions.filter_map(|m| m.get("caption").and_then(|c| c.as_str())) {
+            text.push('\n');
+            text.push_str(caption);
+        }
     }
+    text
 }
 
 #[cfg(test)]
@@ -51,59 +51,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn parse_valid_json() {
-        let json = r##"{"body": "Hello World", "images": [{"path": "/tmp/a.png", "caption": "test"}]}"##;
-        let cf = ContentFields::parse(json);
-        assert_eq!(cf.body, "Hello World");
-        assert_eq!(cf.images.len(), 1);
-        assert_eq!(cf.images[0].path, "/tmp/a.png");
-        assert_eq!(cf.images[0].caption, "test");
-        assert!(cf.videos.is_empty());
-    }
-
-    #[test]
-    fn parse_empty_or_invalid_returns_default() {
-        let cf = ContentFields::parse("");
-        assert_eq!(cf.body, "");
-        assert!(cf.images.is_empty());
-        assert!(cf.videos.is_empty());
-
-        let cf = ContentFields::parse("not json");
-        assert_eq!(cf.body, "");
-        assert!(cf.images.is_empty());
-    }
-
-    #[test]
-    fn serialize_roundtrip() {
-        let cf = ContentFields {
-            body: "Hello".to_string(),
-            images: vec![ContentImage {
-                path: "/tmp/img.png".to_string(),
-                caption: "Cap".to_string(),
-            }],
-            videos: vec![ContentVideo {
-                path: "/tmp/vid.mp4".to_string(),
-                caption: "Demo".to_string(),
-                duration_secs: Some(120.5),
-                thumbnail_path: None,
-            }],
-        };
-        let json = cf.serialize();
-        let cf2 = ContentFields::parse(&json);
-        assert_eq!(cf2.body, "Hello");
-        assert_eq!(cf2.images.len(), 1);
-        assert_eq!(cf2.images[0].path, "/tmp/img.png");
-        assert_eq!(cf2.videos.len(), 1);
-        assert_eq!(cf2.videos[0].path, "/tmp/vid.mp4");
-        assert_eq!(cf2.videos[0].caption, "Demo");
-        assert_eq!(cf2.videos[0].duration_secs, Some(120.5));
-    }
-
-    #[test]
-    fn backward_compatible_without_videos() {
-        let json = r#"{"body":"old doc","images":[]}"#;
-        let cf = ContentFields::parse(json);
-        assert_eq!(cf.body, "old doc");
-        assert!(cf.videos.is_empty());
+    fn hashtags_skip_headings_and_dedupe() {
+        let body = "# Title\n#Rust and #rust, mid#word #a-b_c\n#";
+        assert_eq!(hashtags(body, 10), vec!["rust", "a-b_c"]);
+        assert_eq!(hashtags(body, 1), vec!["rust"]);
     }
 }
@@ -0,0 +1,165 @@
+//! In-process publish/subscribe for typed events.
+//!
+//! Crates publish plain event values on one shared [`EventBus`]: the
+//! orchestrator its `OrchestratorEvent`s, the voice pipeline its
+//! `VoiceEvent`s, comms sync its `CommsEvent`s. Each subscriber to a type
+//! gets its own copy of every event of that type on a channel, so a new
+//! consumer (the UI forwarder, the daemon, the Jiminy bridge) subscribes
+//! rather than adding a channel and a thread to fan it out.
+//!
+//! Publishing never blocks. Events published while nobody subscribes to
+//! their type are dropped, so consumers subscribe during startup, before
+//! the publishers run.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+/// Anything `Clone + Send + 'static` can be published.
+pub trait Event: Clone + Send + 'static {}
+
+impl<E: Clone + Send + 'static> Event for E {}
+
+/// The subscribers of one event type.
+struct Topic<E> {
+    subscribers: Vec<Sender<E>>,
+}
+
+#[derive(Default)]
+pub struct EventBus {
+    /// `Topic<E>` per event type.
+    topics: Mutex<HashMap<TypeId, Box<dyn Any + Send>>>,
+}
+
+impl EventBus {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// A new subscription to every `E` published from now on. The channel
+    /// closes when the bus is dropped; dropping the receiver unsubscribes.
+    pub fn subscribe<E: Event>(&self) -> Receiver<E> {
+        let (tx, rx) = mpsc::channel();
+        let mut topics = self.topics.lock().unwrap_or_else(|p| p.into_inner());
+        topics
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| {
+                Box::new(Topic::<E> {
+                    subscribers: Vec::new(),
+                })
+            })
+            .downcast_mut::<Topic<E>>()
+            .expect("topic keyed by its event type")
+            .subscribers
+            .push(tx);
+        rx
+    }
+
+    /// Deliver `event` to every subscriber of `E`. Returns how many got it.
+    pub fn publish<E: Event>(&self, event: E) -> usize {
+        let mut topics = self.topics.lock().unwrap_or_else(|p| p.into_inner());
+        let Some(topic) = topics
+            .get_mut(&TypeId::of::<E>())
+            .and_then(|t| t.downcast_mut::<Topic<E>>())
+        else {
+            return 0;
+        };
+        // Each live subscriber but the last gets a clone; gone ones are
+        // pruned as we go.
+        let mut event = Some(event);
+        let last = topic.subscribers.len();
+        let mut delivered = 0;
+        let mut i = 0;
+        topic.subscribers.retain(|tx| {
+            i += 1;
+            let copy = if i == last {
+                event.take()
+            } else {
+                event.clone()
+            };
+            let alive = copy.is_some_and(|e| tx.send(e).is_ok());
+            delivered += usize::from(alive);
+            alive
+        });
+        delivered
+    }
+
+    /// A handle that publishes `E` on this bus, for code that only emits
+    /// one kind of event.
+    pub fn publisher<E: Event>(self: &Arc<Self>) -> Publisher<E> {
+        Publisher {
+            bus: self.clone(),
+            _event: PhantomData,
+        }
+    }
+}
+
+/// Publishes events of one type on an [`EventBus`].
+pub struct Publisher<E> {
+    bus: Arc<EventBus>,
+    _event: PhantomData<fn(E)>,
+}
+
+impl<E> Clone for Publisher<E> {
+    fn clone(&self) -> Self {
+        Self {
+            bus: self.bus.clone(),
+            _event: PhantomData,
+        }
+    }
+}
+
+impl<E: Event> Publisher<E> {
+    /// [`EventBus::publish`].
+    pub fn send(&self, event: E) -> usize {
+        self.bus.publish(event)
+    }
+
+    pub fn bus(&self) -> &Arc<EventBus> {
+        &self.bus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Ping(u32);
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Pong;
+
+    #[test]
+    fn every_subscriber_gets_each_event_of_its_type() {
+        let bus = EventBus::new();
+        assert_eq!(bus.publish(Ping(0)), 0, "nobody listening yet");
+
+        let a = bus.subscribe::<Ping>();
+        let b = bus.subscribe::<Ping>();
+        let pongs = bus.subscribe::<Pong>();
+        let ping = bus.publisher::<Ping>();
+        assert_eq!(ping.send(Ping(1)), 2);
+        assert_eq!(bus.publish(Pong), 1);
+
+        assert_eq!(a.try_recv(), Ok(Ping(1)));
+        assert_eq!(b.try_recv(), Ok(Ping(1)));
+        assert!(a.try_recv().is_err());
+        assert_eq!(pongs.try_recv(), Ok(Pong));
+        assert!(pongs.try_recv().is_err());
+    }
+
+    #[test]
+    fn dropped_receivers_unsubscribe_and_dropping_the_bus_closes() {
+        let bus = EventBus::new();
+        let kept = bus.subscribe::<Ping>();
+        drop(bus.subscribe::<Ping>());
+        assert_eq!(bus.publish(Ping(1)), 1);
+        assert_eq!(kept.recv(), Ok(Ping(1)));
+
+        drop(bus);
+        assert!(kept.recv().is_err());
+    }
+}
@@ -32,18 +32,52 @@ pub enum OrchestratorEvent {
     AdoptionStarted { doc_id: String },
     MilestoneCreated { milestone_id: String, title: String, thread_id: String },
     MilestonesListed { thread_id: String, milestones: Vec<MilestoneSummary> },
-    Suggestion { text: String, action: String },
+    /// A reminder was set from chat. `due_at` is RFC 3339.
+    ReminderSet { reminder_id: String, doc_id: String, text: String, due_at: String },
+    /// A reminder came due; published once by the app's reminder scheduler.
+    ReminderDue { reminder_id: String, doc_id: String, text: String },
+    /// `target` is what accepting the suggestion acts on (a thread name,
+    /// a document title, `"<title> on <thread>"` for a milestone, a JSON
+    /// `FilingTarget` for filing a document).
+    Suggestion { text: String, action: String, target: Option<String> },
     VersionHistory { doc_id: String, commits: Vec<CommitSummary> },
     SkillResult { skill: String, action: String, kind: String, data: String },
     // P2P sync events
     SyncStatus { peer_id: String, status: String },
     SyncConflict { doc_id: String, description: String },
+    /// Concurrent edits to the same lines of a document were merged; the
+    /// versions are for a three-way review. The merged text is applied.
+    SyncConflictDetails {
+        doc_id: String,
+        base: Option<String>,
+        local: String,
+        remote: String,
+        merged: String,
+        hunks: Vec<ConflictHunk>,
+    },
+    /// A sync session with a peer resolved another request.
+    SyncProgress { peer_id: String, progress: SyncProgress },
+    /// What a sync with `peer_id` would move; `None` if the peer couldn't
+    /// be asked.
+    SyncPreview { peer_id: String, preview: Option<SyncPreview> },
+    /// A paired device's read-only companion snapshot; `None` if it
+    /// couldn't be fetched.
+    ViewerSnapshot { peer_id: String, snapshot: Option<ViewerSnapshot> },
     DeviceDiscovered { device_id: String, device_name: String },
     DevicePaired { device_id: String, device_name: String },
     /// A P3.1 pairing handshake attempt failed. `offer_dead` means the
     /// offer self-destructed (expired or attempts exhausted) and the
     /// pairing QR must be regenerated.
     PairingFailed { reason: String, offer_dead: bool },
+    /// A paired device asks this device, as guardian, to co-sign a remote
+    /// wipe of `target_peer_id`. Needs the user's approval.
+    WipeCosignRequested { order_id: String, target_peer_id: String, issuer_peer_id: String },
+    /// A verified remote wipe order for this device arrived.
+    RemoteWipeOrdered { order_id: String, issuer_peer_id: String },
+    /// A paired device shared its encryption keys; `imported` are new here.
+    KeysImported { peer_id: String, imported: u64 },
+    /// Sharing keys with a paired device finished.
+    KeysShared { peer_id: String, accepted: bool },
     // Guardian events
     GuardianEnrolled { guardian_id: String, name: String },
     GuardianDropped { guardian_id: String, reason: String },
@@ -53,16 +87,14 @@ pub enum OrchestratorEvent {
     RecoveryAborted { reason: String },
     // Encryption events
     EncryptionEnabled,
+    /// Unusual key usage found in the key audit log.
+    KeyUsageAlert { text: String },
     MigrationProgress { encrypted: u32, total: u32 },
     MigrationComplete,
     // Document creation
     DocumentCreated { doc_id: String, title: String, thread_id: String },
-    // Communications events
-    NewMessagesReceived { channel: String, count: u32, conversation_id: String },
-    MessageSent { channel: String, message_id: String },
-    CommsSyncComplete { channel: String, new_messages: u32 },
-    CommsSyncError { channel: String, error: String },
-    ContactCreated { contact_id: String, name: String },
+    // Communications events travel as `sovereign_comms::CommsEvent` on
+    // the event bus.
     // Chat response from LLM
     ChatResponse { text: String },
     // Web browsing events
@@ -84,6 +116,9 @@ pub enum OrchestratorEvent {
     /// Toggle a frontend UI panel. `name` is one of:
     /// "pii_dashboard", "models", "inbox", "browser", "settings".
     OpenPanel { name: String },
+    /// Lock the session now ("lock" intent): keys are dropped and the login
+    /// screen shown.
+    LockRequested,
 }
 
 /// Lightweight milestone summary for milestone events.
@@ -103,6 +138,69 @@ pub struct CommitSummary {
     pub timestamp: String,
 }
 
+/// One region of a sync conflict. `local == remote` where the devices
+/// agree; otherwise the user picks between them.
+#[derive(Debug, Clone)]
+pub struct ConflictHunk {
+    pub base: String,
+    pub local: String,
+    pub remote: String,
+}
+
+/// Where a sync session with one peer stands: the manifest diff, what
+/// has moved each way, and a rough ETA.
+#[derive(Debug, Clone, Default)]
+pub struct SyncProgress {
+    pub docs_to_pull: u32,
+    pub docs_to_push: u32,
+    pub rows_to_pull: u32,
+    pub rows_to_push: u32,
+    pub commits_received: u32,
+    pub commits_sent: u32,
+    pub bytes_received: u64,
+    pub bytes_sent: u64,
+    pub requests_done: u32,
+    pub requests_total: u32,
+    pub elapsed_ms: u64,
+    pub eta_ms: Option<u64>,
+}
+
+/// What a sync with one peer would move, computed from the manifests
+/// before any data is transferred. Byte totals are estimates.
+#[derive(Debug, Clone, Default)]
+pub struct SyncPreview {
+    pub docs_to_send: u32,
+    pub docs_to_receive: u32,
+    pub conflicts: u32,
+    pub rows_to_send: u32,
+    pub rows_to_receive: u32,
+    pub blobs_to_send: u32,
+    pub blobs_to_receive: u32,
+    pub bytes_to_send: u64,
+    pub bytes_to_receive: u64,
+}
+
+/// A read-only snapshot served by a paired device to a companion viewer.
+#[derive(Debug, Clone, Default)]
+pub struct ViewerSnapshot {
+    pub device_id: String,
+    pub generated_at: String,
+    pub documents: Vec<ViewerDocument>,
+    /// `(thread_id, name)` pairs.
+    pub threads: Vec<(String, String)>,
+    /// Titles of documents too large to send to a viewer.
+    pub too_large: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ViewerDocument {
+    pub doc_id: String,
+    pub title: String,
+    pub content: String,
+    pub thread_id: String,
+    pub modified_at: String,
+}
+
 /// Feedback events sent from the UI back to the orchestrator
 /// when a user accepts or dismisses a proactive suggestion.
 #[derive(Debug, Clone)]
@@ -174,12 +272,14 @@ mod tests {
         let event = OrchestratorEvent::Suggestion {
             text: "Try organizing your docs".into(),
             action: "create_thread".into(),
+            target: Some("General".into()),
         };
         let cloned = event.clone();
         match cloned {
-            OrchestratorEvent::Suggestion { text, action } => {
+            OrchestratorEvent::Suggestion { text, action, target } => {
                 assert_eq!(text, "Try organizing your docs");
                 assert_eq!(action, "create_thread");
+                assert_eq!(target.as_deref(), Some("General"));
             }
             _ => panic!("wrong variant"),
         }
@@ -1,10 +1,15 @@
 pub mod auth;
 pub mod config;
 pub mod content;
+pub mod events;
 pub mod interfaces;
 pub mod lifecycle;
+pub mod metrics;
 pub mod profile;
+pub mod profiles;
+pub mod profiling;
 pub mod security;
+pub mod window_session;
 
 /// Cross-platform home directory: checks `USERPROFILE` (Windows) then `HOME` (Unix).
 pub fn home_dir() -> std::path::PathBuf {
@@ -14,15 +19,21 @@ pub fn home_dir() -> std::path::PathBuf {
     std::path::PathBuf::from(home)
 }
 
-/// Sovereign data root.
+/// Sovereign data root, shared by every profile on the machine.
 ///
 /// Resolution:
 /// 1. `SOVEREIGN_DATA_DIR` env var if set (mobile entrypoint sets this to
 ///    `app.path().app_data_dir()` before any sovereign code runs).
 /// 2. Desktop default: `~/.sovereign`.
-pub fn sovereign_dir() -> std::path::PathBuf {
+pub fn data_root() -> std::path::PathBuf {
     if let Ok(dir) = std::env::var("SOVEREIGN_DATA_DIR") {
         return std::path::PathBuf::from(dir);
     }
     home_dir().join(".sovereign")
 }
+
+/// The active profile's data directory: the data root itself for the
+/// default profile, `profiles/<name>` beneath it otherwise.
+pub fn sovereign_dir() -> std::path::PathBuf {
+    profiles::Profiles::on_this_machine().dir(&profiles::active())
+}
@@ -1,16 +1,110 @@
-use tracing_subscriber::EnvFilter;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-/// Initialize tracing with env filter support.
+use tokio::sync::{watch, Notify};
+
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+use crate::metrics::{self, MetricsLayer};
+use crate::profiling::{self, ProfileGuard};
+
+/// The open profile, finished by [`finish_profile`].
+static PROFILE: Mutex<Option<ProfileGuard>> = Mutex::new(None);
+
+/// Span timing requested on the command line.
+#[derive(Debug, Default, Clone)]
+pub struct TraceOptions {
+    /// Log every timed span's duration to stderr as it closes.
+    pub trace_spans: bool,
+    /// Write span timings to this file (see [`profiling`]).
+    pub profile: Option<PathBuf>,
+}
+
+fn env_filter() -> EnvFilter {
+    EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new("info,rfd::backend::xdg_desktop_portal=off"))
+}
+
+fn log_layer<S>() -> impl Layer<S>
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_filter(env_filter())
+}
+
+fn metrics_layer<S>() -> impl Layer<S>
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    MetricsLayer.with_filter(filter_fn(metrics::is_measured))
+}
+
+/// Initialize tracing with env filter support, and the local metrics
+/// layer (idle until `metrics::set_enabled`).
 ///
 /// Set `RUST_LOG=debug` for verbose output, defaults to `info`.
 pub fn init_tracing() {
-    tracing_subscriber::fmt()
-        .with_writer(std::io::stderr)
-        .with_env_filter(
-            EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| EnvFilter::new("info,rfd::backend::xdg_desktop_portal=off")),
-        )
+    tracing_subscriber::registry()
+        .with(log_layer())
+        .with(metrics_layer())
+        .init();
+}
+
+/// [`init_tracing`], plus the span timing `options` ask for. Timed spans
+/// are debug-level and `RUST_LOG` doesn't hide them from the profile or
+/// the span log, which only see spans; ordinary logging is unchanged.
+/// Call [`finish_profile`] before exiting to complete the profile file.
+pub fn init_tracing_with(options: &TraceOptions) -> std::io::Result<()> {
+    if !options.trace_spans && options.profile.is_none() {
+        init_tracing();
+        return Ok(());
+    }
+    let (profile, guard) = match &options.profile {
+        Some(path) => {
+            let (layer, guard) = profiling::profile_layer(path)?;
+            (
+                Some(layer.with_filter(filter_fn(profiling::is_profiled))),
+                Some(guard),
+            )
+        }
+        None => (None, None),
+    };
+    let span_log = options.trace_spans.then(|| {
+        tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_filter(filter_fn(|m| m.is_span() && profiling::is_profiled(m)))
+    });
+    tracing_subscriber::registry()
+        .with(log_layer())
+        .with(metrics_layer())
+        .with(span_log)
+        .with(profile)
         .init();
+    *PROFILE.lock().unwrap_or_else(|p| p.into_inner()) = guard;
+    Ok(())
+}
+
+/// Whether a profile is being recorded.
+pub fn profiling_active() -> bool {
+    PROFILE.lock().unwrap_or_else(|p| p.into_inner()).is_some()
+}
+
+/// Write out the profile started by [`init_tracing_with`], if any. Spans
+/// closing afterwards aren't recorded; calling it again does nothing.
+pub fn finish_profile() {
+    PROFILE.lock().unwrap_or_else(|p| p.into_inner()).take();
 }
 
 pub fn log_startup() {
@@ -20,3 +114,215 @@ pub fn log_startup() {
 pub fn log_shutdown() {
     tracing::info!("Sovereign GE shutting down");
 }
+
+type HookFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+type Hook = Box<dyn FnOnce() -> HookFuture + Send>;
+
+/// Coordinates quitting, so background work isn't cut off mid-write.
+///
+/// Subsystems that must wind down cleanly (comms sync finishing its queued
+/// sends, the P2P node, auto-commit) each hold a [`ShutdownListener`] and
+/// stop when it fires; whatever only needs saving at the end registers an
+/// [`on_shutdown`](Self::on_shutdown) hook. [`run`](Self::run) drives the
+/// whole thing once. Clones share the same state.
+#[derive(Clone)]
+pub struct Shutdown {
+    inner: Arc<ShutdownInner>,
+}
+
+struct ShutdownInner {
+    trigger: watch::Sender<bool>,
+    /// Listeners not yet dropped, by id, with their subsystem's name.
+    running: Mutex<BTreeMap<u64, &'static str>>,
+    next_id: AtomicU64,
+    /// Woken whenever a listener is dropped.
+    stopped: Notify,
+    hooks: Mutex<Vec<(&'static str, Hook)>>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(ShutdownInner {
+                trigger: watch::channel(false).0,
+                running: Mutex::new(BTreeMap::new()),
+                next_id: AtomicU64::new(0),
+                stopped: Notify::new(),
+                hooks: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Register the subsystem `name`. [`run`](Self::run) waits for it until
+    /// the returned listener is dropped, so drop it once the subsystem has
+    /// finished its last write.
+    pub fn listener(&self, name: &'static str) -> ShutdownListener {
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        self.inner
+            .running
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .insert(id, name);
+        ShutdownListener {
+            id,
+            trigger: self.inner.trigger.subscribe(),
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// Run `hook` at shutdown, after the listeners have stopped (or been
+    /// given up on). Hooks run one at a time, in the order registered.
+    pub fn on_shutdown<F, Fut>(&self, name: &'static str, hook: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let hook: Hook = Box::new(move || -> HookFuture { Box::pin(hook()) });
+        self.inner
+            .hooks
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .push((name, hook));
+    }
+
+    /// Whether [`run`](Self::run) has started.
+    pub fn is_triggered(&self) -> bool {
+        *self.inner.trigger.borrow()
+    }
+
+    /// Signal every listener, wait up to `grace` for them to stop, then run
+    /// the hooks, again for at most `grace` in all. Returns the subsystems
+    /// and hooks that didn't finish in time; they are logged and abandoned.
+    /// Only the first call does anything.
+    pub async fn run(&self, grace: Duration) -> Vec<&'static str> {
+        if self.inner.trigger.send_replace(true) {
+            return Vec::new();
+        }
+        log_shutdown();
+
+        let mut stalled = Vec::new();
+        let deadline = tokio::time::Instant::now() + grace;
+        loop {
+            let stopped = self.inner.stopped.notified();
+            let running: Vec<&'static str> = self
+                .inner
+                .running
+                .lock()
+                .unwrap_or_else(|p| p.into_inner())
+                .values()
+                .copied()
+                .collect();
+            if running.is_empty() {
+                break;
+            }
+            if tokio::time::timeout_at(deadline, stopped).await.is_err() {
+                tracing::warn!("Shutdown: gave up waiting for {}", running.join(", "));
+                stalled.extend(running);
+                break;
+            }
+        }
+
+        let hooks =
+            std::mem::take(&mut *self.inner.hooks.lock().unwrap_or_else(|p| p.into_inner()));
+        let deadline = tokio::time::Instant::now() + grace;
+        for (name, hook) in hooks {
+            if tokio::time::timeout_at(deadline, hook()).await.is_err() {
+                tracing::warn!("Shutdown: {name} did not finish in time");
+                stalled.push(name);
+            }
+        }
+        stalled
+    }
+}
+
+/// A subsystem's view of a [`Shutdown`]: it learns when to stop here, and
+/// counts as running until this is dropped.
+pub struct ShutdownListener {
+    id: u64,
+    trigger: watch::Receiver<bool>,
+    inner: Arc<ShutdownInner>,
+}
+
+impl ShutdownListener {
+    /// Resolves once shutdown has started. Cancel-safe, for `select!`.
+    pub async fn triggered(&mut self) {
+        // The sender lives in `inner`, which this listener keeps alive.
+        let _ = self.trigger.wait_for(|fired| *fired).await;
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        *self.trigger.borrow()
+    }
+}
+
+impl Drop for ShutdownListener {
+    fn drop(&mut self) {
+        self.inner
+            .running
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .remove(&self.id);
+        self.inner.stopped.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+
+    #[tokio::test]
+    async fn waits_for_listeners_then_runs_hooks() {
+        let shutdown = Shutdown::new();
+        let flushed = Arc::new(AtomicBool::new(false));
+        let committed = Arc::new(AtomicBool::new(false));
+
+        let mut listener = shutdown.listener("autocommit");
+        let done = committed.clone();
+        let task = tokio::spawn(async move {
+            listener.triggered().await;
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            done.store(true, Ordering::SeqCst);
+        });
+        let (seen, flag) = (committed.clone(), flushed.clone());
+        shutdown.on_shutdown("metrics", move || async move {
+            // Hooks run after the listeners are done.
+            assert!(seen.load(Ordering::SeqCst));
+            flag.store(true, Ordering::SeqCst);
+        });
+
+        assert!(shutdown.run(Duration::from_secs(5)).await.is_empty());
+        assert!(flushed.load(Ordering::SeqCst));
+        task.await.unwrap();
+        assert!(shutdown.is_triggered());
+        // Only the first run does anything.
+        assert!(shutdown.run(Duration::from_secs(5)).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn reports_what_did_not_stop_in_time() {
+        let shutdown = Shutdown::new();
+        let _stuck = shutdown.listener("p2p");
+        let gone = shutdown.listener("comms");
+        drop(gone);
+        shutdown.on_shutdown("slow", || std::future::pending());
+
+        let stalled = shutdown.run(Duration::from_millis(20)).await;
+        assert_eq!(stalled, vec!["p2p", "slow"]);
+    }
+
+    #[tokio::test]
+    async fn listener_taken_after_shutdown_started_sees_it() {
+        let shutdown = Shutdown::new();
+        shutdown.run(Duration::from_secs(1)).await;
+        let mut late = shutdown.listener("late");
+        assert!(late.is_triggered());
+        late.triggered().await;
+    }
+}
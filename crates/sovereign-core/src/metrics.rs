@@ -0,0 +1,390 @@
+//! On-device performance metrics: frame times, LLM latency, sync
+//! durations and database query timings, kept as daily histograms so a
+//! slowdown after an update shows up as a trend.
+//!
+//! [`MetricsLayer`] times the same work the profiler does (see
+//! [`profiling`]): `db.*`, `llm.*` and `sync.*` spans, from creation to
+//! close, and [`PERF_TARGET`] events such as `render.frame`. It records
+//! nothing until [`set_enabled`] turns it on (`[metrics] enabled`), so
+//! the spans cost nothing while it is off.
+//!
+//! The history lives in `metrics.json` under the data directory and is
+//! written by [`flush`]. It is never transmitted: not synced to paired
+//! devices, not part of backups or exports, not sent anywhere.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Metadata, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use crate::profiling::{self, PerfFields, PERF_TARGET};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static RECORDER: Mutex<Recorder> = Mutex::new(Recorder::new());
+
+/// Where the history is kept.
+pub fn default_path() -> PathBuf {
+    crate::sovereign_dir().join("metrics.json")
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Start or stop recording. Turning it off keeps the history.
+pub fn set_enabled(on: bool) {
+    ENABLED.store(on, Ordering::Relaxed);
+}
+
+/// Whether [`MetricsLayer`] looks at `metadata`: what the profiler
+/// records, while metrics are on.
+pub fn is_measured(metadata: &Metadata<'_>) -> bool {
+    enabled() && profiling::is_profiled(metadata)
+}
+
+/// Load the history at `path`, keeping `retention_days` days of it, and
+/// write there from now on. Anything recorded before is kept.
+pub fn open(path: &Path, retention_days: u32) {
+    let stored = match std::fs::read_to_string(path) {
+        Ok(text) => serde_json::from_str::<History>(&text).unwrap_or_else(|e| {
+            tracing::warn!(
+                "Ignoring unreadable metrics history {}: {e}",
+                path.display()
+            );
+            History::default()
+        }),
+        Err(_) => History::default(),
+    };
+    let mut recorder = lock();
+    let recorded = std::mem::replace(&mut recorder.history, stored);
+    recorder.history.merge(recorded);
+    recorder.path = Some(path.to_path_buf());
+    recorder.retention_days = retention_days;
+    recorder.history.prune(today(), retention_days);
+}
+
+/// Change how many days of history are kept.
+pub fn set_retention(days: u32) {
+    let mut recorder = lock();
+    if recorder.retention_days == days {
+        return;
+    }
+    recorder.retention_days = days;
+    let kept = recorder.history.days.len();
+    recorder.history.prune(today(), days);
+    recorder.dirty |= recorder.history.days.len() != kept;
+}
+
+/// Add one measurement of `name`, if metrics are on.
+pub fn record(name: &str, duration: Duration) {
+    if !enabled() {
+        return;
+    }
+    let mut recorder = lock();
+    recorder.history.record(today(), name, duration);
+    recorder.dirty = true;
+}
+
+/// Write the history out if it changed since the last flush. Does
+/// nothing before [`open`].
+pub fn flush() -> std::io::Result<()> {
+    let mut recorder = lock();
+    let Some(path) = recorder.path.clone() else {
+        return Ok(());
+    };
+    if !recorder.dirty {
+        return Ok(());
+    }
+    let today = today();
+    let days = recorder.retention_days;
+    recorder.history.prune(today, days);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(&recorder.history)?)?;
+    std::fs::rename(&tmp, &path)?;
+    recorder.dirty = false;
+    Ok(())
+}
+
+/// Forget the whole history, on disk too.
+pub fn clear() -> std::io::Result<()> {
+    let mut recorder = lock();
+    recorder.history = History::default();
+    recorder.dirty = false;
+    match &recorder.path {
+        Some(path) => match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        },
+        None => Ok(()),
+    }
+}
+
+/// Per-day figures for every metric seen in the last `days` days, by name.
+pub fn trends(days: u32) -> Vec<MetricTrend> {
+    lock().history.trends(today(), days)
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Recorder> {
+    RECORDER.lock().unwrap_or_else(|p| p.into_inner())
+}
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+struct Recorder {
+    history: History,
+    path: Option<PathBuf>,
+    retention_days: u32,
+    /// Recorded since the last flush.
+    dirty: bool,
+}
+
+impl Recorder {
+    const fn new() -> Self {
+        Self {
+            history: History {
+                days: BTreeMap::new(),
+            },
+            path: None,
+            retention_days: 30,
+            dirty: false,
+        }
+    }
+}
+
+/// Durations of one metric on one day, in power-of-two buckets of
+/// microseconds: bucket `i` holds durations below `2^i` µs (and at least
+/// `2^(i-1)`). Mergeable and a few hundred bytes, at the cost of
+/// percentiles being estimates.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    pub count: u64,
+    pub sum_us: u64,
+    pub max_us: u64,
+    buckets: Vec<u64>,
+}
+
+impl Histogram {
+    pub fn record(&mut self, us: u64) {
+        let bucket = (u64::BITS - us.leading_zeros()) as usize;
+        if self.buckets.len() <= bucket {
+            self.buckets.resize(bucket + 1, 0);
+        }
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.sum_us = self.sum_us.saturating_add(us);
+        self.max_us = self.max_us.max(us);
+    }
+
+    fn merge(&mut self, other: &Histogram) {
+        if self.buckets.len() < other.buckets.len() {
+            self.buckets.resize(other.buckets.len(), 0);
+        }
+        for (mine, theirs) in self.buckets.iter_mut().zip(&other.buckets) {
+            *mine += theirs;
+        }
+        self.count += other.count;
+        self.sum_us = self.sum_us.saturating_add(other.sum_us);
+        self.max_us = self.max_us.max(other.max_us);
+    }
+
+    /// Upper estimate of the `q` quantile (0.0 to 1.0), in µs: the top of
+    /// the bucket it falls in, capped at the largest value seen.
+    pub fn quantile(&self, q: f64) -> u64 {
+        let rank = ((self.count as f64 * q).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bucket, &n) in self.buckets.iter().enumerate() {
+            seen += n;
+            if seen >= rank {
+                let top = 1u64.checked_shl(bucket as u32).map_or(u64::MAX, |b| b - 1);
+                return top.min(self.max_us);
+            }
+        }
+        self.max_us
+    }
+
+    pub fn mean_us(&self) -> u64 {
+        self.sum_us.checked_div(self.count).unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct History {
+    days: BTreeMap<NaiveDate, BTreeMap<String, Histogram>>,
+}
+
+impl History {
+    fn record(&mut self, day: NaiveDate, name: &str, duration: Duration) {
+        let us = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+        let metrics = self.days.entry(day).or_default();
+        match metrics.get_mut(name) {
+            Some(histogram) => histogram.record(us),
+            None => metrics.entry(name.to_string()).or_default().record(us),
+        }
+    }
+
+    fn merge(&mut self, other: History) {
+        for (day, metrics) in other.days {
+            let mine = self.days.entry(day).or_default();
+            for (name, histogram) in metrics {
+                mine.entry(name).or_default().merge(&histogram);
+            }
+        }
+    }
+
+    /// Drop days more than `retention_days` before `today`.
+    fn prune(&mut self, today: NaiveDate, retention_days: u32) {
+        let Some(first) = today.checked_sub_days(chrono::Days::new(retention_days.into())) else {
+            return;
+        };
+        self.days.retain(|day, _| *day > first);
+    }
+
+    fn trends(&self, today: NaiveDate, days: u32) -> Vec<MetricTrend> {
+        let first = today
+            .checked_sub_days(chrono::Days::new(days.into()))
+            .unwrap_or(NaiveDate::MIN);
+        let mut trends: BTreeMap<&str, Vec<DayStats>> = BTreeMap::new();
+        for (day, metrics) in self.days.range(first.succ_opt().unwrap_or(first)..) {
+            for (name, h) in metrics {
+                trends.entry(name).or_default().push(DayStats {
+                    date: *day,
+                    count: h.count,
+                    mean_ms: ms(h.mean_us()),
+                    p50_ms: ms(h.quantile(0.5)),
+                    p95_ms: ms(h.quantile(0.95)),
+                    max_ms: ms(h.max_us),
+                });
+            }
+        }
+        trends
+            .into_iter()
+            .map(|(name, days)| MetricTrend {
+                name: name.to_string(),
+                days,
+            })
+            .collect()
+    }
+}
+
+fn ms(us: u64) -> f64 {
+    us as f64 / 1000.0
+}
+
+/// One metric over time, oldest day first.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricTrend {
+    /// `db.list_documents`, `llm.generate`, `render.frame`, ….
+    pub name: String,
+    pub days: Vec<DayStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DayStats {
+    pub date: NaiveDate,
+    pub count: u64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Span extension: when the span was created.
+struct Opened(Instant);
+
+/// Records timings into the metrics history. Install it filtered with
+/// [`is_measured`].
+pub struct MetricsLayer;
+
+impl<S> Layer<S> for MetricsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(Opened(Instant::now()));
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else { return };
+        let Some(opened) = span.extensions().get::<Opened>().map(|o| o.0) else {
+            return;
+        };
+        record(span.name(), opened.elapsed());
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().target() != PERF_TARGET {
+            return;
+        }
+        let mut fields = PerfFields::default();
+        event.record(&mut fields);
+        if let Some(name) = fields.name {
+            record(&name, Duration::from_micros(fields.duration_us));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    #[test]
+    fn histogram_quantiles_bound_the_samples() {
+        let mut h = Histogram::default();
+        for us in [900, 1_000, 1_100, 1_200, 40_000] {
+            h.record(us);
+        }
+        assert_eq!(h.count, 5);
+        assert_eq!(h.mean_us(), 8_840);
+        // 1_100 lies in [1024, 2048): the median estimate is that bucket's top.
+        assert_eq!(h.quantile(0.5), 2_047);
+        assert_eq!(h.quantile(0.95), 40_000);
+        assert_eq!(Histogram::default().quantile(0.5), 0);
+        let mut huge = Histogram::default();
+        huge.record(u64::MAX);
+        assert_eq!(huge.quantile(0.5), u64::MAX);
+    }
+
+    #[test]
+    fn history_trends_prunes_and_round_trips() {
+        let mut history = History::default();
+        history.record(day(1), "db.get_document", Duration::from_millis(2));
+        history.record(day(9), "db.get_document", Duration::from_millis(4));
+        history.record(day(9), "render.frame", Duration::from_micros(16_600));
+
+        let trends = history.trends(day(10), 7);
+        assert_eq!(trends.len(), 2);
+        assert_eq!(trends[0].name, "db.get_document");
+        assert_eq!(trends[0].days.len(), 1);
+        assert_eq!(trends[0].days[0].date, day(9));
+        assert_eq!(trends[0].days[0].max_ms, 4.0);
+
+        let json = serde_json::to_string(&history).unwrap();
+        let mut back: History = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, history);
+
+        back.merge(history);
+        assert_eq!(back.days[&day(9)]["render.frame"].count, 2);
+        back.prune(day(10), 7);
+        assert_eq!(back.days.keys().copied().collect::<Vec<_>>(), [day(9)]);
+    }
+}
@@ -12,7 +12,7 @@ const PROFILE_FILENAME: &str = "user_profile.json";
 /// Write `bytes` to `path`, restricted to the owner on Unix (0600). On Windows
 /// the per-user profile directory ACL already scopes access (same posture as
 /// the crypto stores' fs_private). (ATREST-004)
-fn write_owner_only(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+pub(crate) fn write_owner_only(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
     use std::io::Write;
     let mut opts = std::fs::OpenOptions::new();
     opts.write(true).create(true).truncate(true);
@@ -80,6 +80,21 @@ impl BubbleStyle {
     }
 }
 
+// ── Editor keymap ───────────────────────────────────────────────────────
+
+/// Key bindings in the document editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EditorKeymap {
+    /// Plain text-field keys.
+    #[default]
+    Standard,
+    /// Modal editing: normal / insert / visual with core motions and operators.
+    Vim,
+    /// Emacs control- and meta-key movement and kill/yank.
+    Emacs,
+}
+
 // ── Designation generation ──────────────────────────────────────────────
 
 /// Alphanumeric pool (no ambiguous chars: no I, L, O, 0, 1).
@@ -133,6 +148,9 @@ pub struct UserProfile {
     /// UI theme: "dark" or "light". Persisted across sessions.
     #[serde(default = "default_theme")]
     pub theme: String,
+    /// Key bindings in the document editor.
+    #[serde(default)]
+    pub editor_keymap: EditorKeymap,
     pub created: String,
     pub last_updated: String,
     pub interaction_patterns: InteractionPatterns,
@@ -240,6 +258,7 @@ impl UserProfile {
             bubble_style: BubbleStyle::default(),
             display_name: None,
             theme: default_theme(),
+            editor_keymap: EditorKeymap::default(),
             created: now.clone(),
             last_updated: now,
             interaction_patterns: InteractionPatterns {
@@ -323,6 +342,7 @@ mod tests {
         let mut p = UserProfile::default_new();
         p.skill_preferences
             .insert("text_editing".into(), "markdown-editor".into());
+        p.editor_keymap = EditorKeymap::Vim;
         p.save(&dir).unwrap();
 
         let loaded = UserProfile::load(&dir).unwrap();
@@ -331,6 +351,7 @@ mod tests {
             loaded.skill_preferences.get("text_editing").unwrap(),
             "markdown-editor"
         );
+        assert_eq!(loaded.editor_keymap, EditorKeymap::Vim);
         let _ = std::fs::remove_dir_all(&dir);
     }
 
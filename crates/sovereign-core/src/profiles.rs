@@ -0,0 +1,223 @@
+//! Several people on one machine. Each profile is its own data directory —
+//! config, key material, database, orchestrator state — under one shared
+//! root:
+//!
+//! ```text
+//! ~/.sovereign/                  the default profile (installs from before
+//!                                profiles keep working unchanged)
+//! ~/.sovereign/profiles.json     which profile the app opened last
+//! ~/.sovereign/profiles/<name>/  every other profile
+//! ```
+//!
+//! A process picks its profile once, before anything touches the disk
+//! (`--profile`, else the last one opened), and carries it in
+//! `SOVEREIGN_PROFILE` so the daemon and other child processes inherit it.
+//! Switching profiles restarts the app.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// The profile at the root of the data directory.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Environment variable holding the active profile's name.
+pub const PROFILE_ENV: &str = "SOVEREIGN_PROFILE";
+
+const PROFILES_DIR: &str = "profiles";
+const REGISTRY_FILE: &str = "profiles.json";
+const MAX_NAME_LEN: usize = 32;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Registry {
+    #[serde(default)]
+    last_used: Option<String>,
+}
+
+/// Lowercase letters, digits, `-` and `_`, at most 32 of them: safe as a
+/// directory name everywhere and as part of a database name.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+/// The profile this process runs as.
+pub fn active() -> String {
+    std::env::var(PROFILE_ENV)
+        .ok()
+        .filter(|name| is_valid_name(name))
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// Make `name` this process's profile, creating it if it is new. Call it
+/// before anything reads [`crate::sovereign_dir`], while the process is
+/// still single-threaded.
+pub fn activate(name: &str) -> anyhow::Result<()> {
+    let profiles = Profiles::on_this_machine();
+    if !profiles.exists(name) {
+        profiles.create(name)?;
+        tracing::info!("Created profile '{name}'");
+    }
+    std::env::set_var(PROFILE_ENV, name);
+    Ok(())
+}
+
+/// The SurrealDB database a profile's rows live in. The default profile
+/// keeps the original `main`.
+pub fn database_name(name: &str) -> String {
+    if name == DEFAULT_PROFILE {
+        "main".to_string()
+    } else {
+        format!("profile_{}", name.replace('-', "_"))
+    }
+}
+
+/// The profiles under one data root.
+pub struct Profiles {
+    root: PathBuf,
+}
+
+impl Profiles {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// The profiles in [`crate::data_root`].
+    pub fn on_this_machine() -> Self {
+        Self::new(crate::data_root())
+    }
+
+    /// Where `name` keeps its data.
+    pub fn dir(&self, name: &str) -> PathBuf {
+        if name == DEFAULT_PROFILE {
+            self.root.clone()
+        } else {
+            self.root.join(PROFILES_DIR).join(name)
+        }
+    }
+
+    pub fn exists(&self, name: &str) -> bool {
+        name == DEFAULT_PROFILE || (is_valid_name(name) && self.dir(name).is_dir())
+    }
+
+    /// Every profile, the default first and the rest by name.
+    pub fn list(&self) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(self.root.join(PROFILES_DIR))
+            .map(|dir| {
+                dir.flatten()
+                    .filter(|e| e.path().is_dir())
+                    .filter_map(|e| e.file_name().into_string().ok())
+                    .filter(|name| is_valid_name(name) && name != DEFAULT_PROFILE)
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names.insert(0, DEFAULT_PROFILE.to_string());
+        names
+    }
+
+    /// Create a new, empty profile; the app onboards it on first launch.
+    pub fn create(&self, name: &str) -> anyhow::Result<PathBuf> {
+        if !is_valid_name(name) {
+            anyhow::bail!(
+                "profile names are 1 to {MAX_NAME_LEN} lowercase letters, digits, '-' or '_'"
+            );
+        }
+        if self.exists(name) {
+            anyhow::bail!("a profile named '{name}' already exists");
+        }
+        let dir = self.dir(name);
+        std::fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    /// The profile the app opened last, if it still exists.
+    pub fn last_used(&self) -> String {
+        std::fs::read_to_string(self.root.join(REGISTRY_FILE))
+            .ok()
+            .and_then(|json| serde_json::from_str::<Registry>(&json).ok())
+            .and_then(|r| r.last_used)
+            .filter(|name| self.exists(name))
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+    }
+
+    /// Open `name` on the next launch without `--profile`.
+    pub fn set_last_used(&self, name: &str) -> anyhow::Result<()> {
+        let registry = Registry {
+            last_used: Some(name.to_string()),
+        };
+        std::fs::create_dir_all(&self.root)?;
+        std::fs::write(
+            self.root.join(REGISTRY_FILE),
+            serde_json::to_string_pretty(&registry)?,
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("sovereign_profiles_{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn names_are_restricted() {
+        assert!(is_valid_name("alice"));
+        assert!(is_valid_name("kid-2_b"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("Alice"));
+        assert!(!is_valid_name("../escape"));
+        assert!(!is_valid_name(&"a".repeat(33)));
+    }
+
+    #[test]
+    fn default_lives_at_the_root_and_others_beneath_it() {
+        let root = temp_root("dirs");
+        let profiles = Profiles::new(root.clone());
+        assert_eq!(profiles.dir(DEFAULT_PROFILE), root);
+        assert_eq!(profiles.dir("alice"), root.join("profiles").join("alice"));
+        assert_eq!(database_name(DEFAULT_PROFILE), "main");
+        assert_eq!(database_name("kid-2"), "profile_kid_2");
+    }
+
+    #[test]
+    fn create_and_list() {
+        let root = temp_root("list");
+        let profiles = Profiles::new(root.clone());
+        assert_eq!(profiles.list(), [DEFAULT_PROFILE]);
+
+        profiles.create("zoe").unwrap();
+        profiles.create("alice").unwrap();
+        assert!(profiles.create("alice").is_err());
+        assert!(profiles.create(DEFAULT_PROFILE).is_err());
+        assert!(profiles.create("Bad Name").is_err());
+        std::fs::create_dir_all(root.join("profiles").join("Not A Profile")).unwrap();
+
+        assert_eq!(profiles.list(), [DEFAULT_PROFILE, "alice", "zoe"]);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn last_used_falls_back_to_default() {
+        let root = temp_root("last_used");
+        let profiles = Profiles::new(root.clone());
+        assert_eq!(profiles.last_used(), DEFAULT_PROFILE);
+
+        profiles.create("alice").unwrap();
+        profiles.set_last_used("alice").unwrap();
+        assert_eq!(profiles.last_used(), "alice");
+
+        // Deleted since: back to the default.
+        std::fs::remove_dir_all(profiles.dir("alice")).unwrap();
+        assert_eq!(profiles.last_used(), DEFAULT_PROFILE);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
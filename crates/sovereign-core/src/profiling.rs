@@ -0,0 +1,354 @@
+//! Span timing for `--trace-spans` and `--trace-profile`.
+//!
+//! Work worth timing is marked with ordinary debug-level tracing spans,
+//! named by area: `db.*` (database calls), `llm.*` (model loads and
+//! generation), `sync.*` (sync message handling). [`ProfileLayer`] records
+//! each stretch of time a span is entered. Work that doesn't fit in one
+//! span — a sync round spread over many network messages, a frame measured
+//! in the webview — is reported once it's over as an event with target
+//! [`PERF_TARGET`] and `name`, `duration_us` and optionally `ago_us`
+//! (how long before the event it ended) fields.
+//!
+//! The profile is written as Chrome trace-event JSON (chrome://tracing or
+//! Perfetto), or as folded stacks (`db.list_documents;llm.generate 1200`,
+//! self time in µs) for inferno / flamegraph.pl when the file ends in
+//! `.folded`. The file is complete once the [`ProfileGuard`] drops; spans
+//! ending after that are left out.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::field::{Field, Visit};
+use tracing::span::Id;
+use tracing::{Event, Metadata, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Target of events that report an already-finished piece of work.
+pub const PERF_TARGET: &str = "sovereign_perf";
+
+/// Whether `metadata` is something the profile records: a span from one of
+/// our crates, or a [`PERF_TARGET`] event.
+pub fn is_profiled(metadata: &Metadata<'_>) -> bool {
+    (metadata.is_span() && metadata.target().starts_with("sovereign"))
+        || metadata.target() == PERF_TARGET
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileFormat {
+    ChromeTrace,
+    Folded,
+}
+
+impl ProfileFormat {
+    /// Folded stacks for `.folded` files, a Chrome trace otherwise.
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("folded") => Self::Folded,
+            _ => Self::ChromeTrace,
+        }
+    }
+}
+
+/// One finished stretch of work.
+struct Slice<'a> {
+    name: &'a str,
+    category: &'a str,
+    /// Enclosing span names, root first, ending with `name`.
+    stack: String,
+    start: Instant,
+    duration: Duration,
+    /// Time spent in child spans, left out of the folded self time.
+    children: Duration,
+}
+
+struct Sink {
+    out: BufWriter<File>,
+    format: ProfileFormat,
+    epoch: Instant,
+    events: u64,
+    folded: BTreeMap<String, u64>,
+    finished: bool,
+}
+
+impl Sink {
+    fn record(&mut self, slice: Slice<'_>) {
+        if self.finished {
+            return;
+        }
+        match self.format {
+            ProfileFormat::ChromeTrace => {
+                let event = serde_json::json!({
+                    "name": slice.name,
+                    "cat": slice.category,
+                    "ph": "X",
+                    "ts": slice.start.saturating_duration_since(self.epoch).as_micros() as u64,
+                    "dur": slice.duration.as_micros() as u64,
+                    "pid": std::process::id(),
+                    "tid": thread_id(),
+                });
+                let sep = if self.events == 0 { "[\n" } else { ",\n" };
+                let _ = write!(self.out, "{sep}{event}");
+            }
+            ProfileFormat::Folded => {
+                let own = slice.duration.saturating_sub(slice.children);
+                *self.folded.entry(slice.stack).or_default() += own.as_micros() as u64;
+            }
+        }
+        self.events += 1;
+    }
+
+    fn finish(&mut self) -> std::io::Result<()> {
+        if std::mem::replace(&mut self.finished, true) {
+            return Ok(());
+        }
+        match self.format {
+            ProfileFormat::ChromeTrace if self.events == 0 => writeln!(self.out, "[]")?,
+            ProfileFormat::ChromeTrace => writeln!(self.out, "\n]")?,
+            ProfileFormat::Folded => {
+                for (stack, micros) in std::mem::take(&mut self.folded) {
+                    writeln!(self.out, "{stack} {micros}")?;
+                }
+            }
+        }
+        self.out.flush()
+    }
+}
+
+/// Small stable ids for the threads seen, in order of first use.
+fn thread_id() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    thread_local! {
+        static ID: u64 = NEXT.fetch_add(1, Ordering::Relaxed);
+    }
+    ID.with(|id| *id)
+}
+
+/// Span extension: when the current entries began and how long children
+/// ran inside the span since it was last left.
+#[derive(Default)]
+struct Timing {
+    entered: Vec<Instant>,
+    children: Duration,
+}
+
+/// Records span timings into the profile file. Build with [`profile_layer`].
+pub struct ProfileLayer {
+    sink: Arc<Mutex<Sink>>,
+}
+
+/// Finishes the profile file when dropped.
+pub struct ProfileGuard {
+    sink: Arc<Mutex<Sink>>,
+}
+
+impl Drop for ProfileGuard {
+    fn drop(&mut self) {
+        let mut sink = self.sink.lock().unwrap_or_else(|p| p.into_inner());
+        if let Err(e) = sink.finish() {
+            eprintln!("warning: could not finish the profile: {e}");
+        }
+    }
+}
+
+/// A layer writing to `path` in the format its extension asks for, and the
+/// guard that completes the file.
+pub fn profile_layer(path: &Path) -> std::io::Result<(ProfileLayer, ProfileGuard)> {
+    let sink = Arc::new(Mutex::new(Sink {
+        out: BufWriter::new(File::create(path)?),
+        format: ProfileFormat::for_path(path),
+        epoch: Instant::now(),
+        events: 0,
+        folded: BTreeMap::new(),
+        finished: false,
+    }));
+    Ok((ProfileLayer { sink: sink.clone() }, ProfileGuard { sink }))
+}
+
+impl ProfileLayer {
+    fn record(&self, slice: Slice<'_>) {
+        self.sink
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .record(slice);
+    }
+}
+
+/// The area a span belongs to: its name up to the first `.`.
+fn category(name: &str) -> &str {
+    name.split_once('.').map_or(name, |(area, _)| area)
+}
+
+impl<S> Layer<S> for ProfileLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        match extensions.get_mut::<Timing>() {
+            Some(timing) => timing.entered.push(Instant::now()),
+            None => extensions.insert(Timing {
+                entered: vec![Instant::now()],
+                children: Duration::ZERO,
+            }),
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let (start, children) = {
+            let mut extensions = span.extensions_mut();
+            let Some(timing) = extensions.get_mut::<Timing>() else {
+                return;
+            };
+            let Some(start) = timing.entered.pop() else {
+                return;
+            };
+            (start, std::mem::take(&mut timing.children))
+        };
+        let duration = start.elapsed();
+        if let Some(parent) = span.parent() {
+            if let Some(timing) = parent.extensions_mut().get_mut::<Timing>() {
+                timing.children += duration;
+            }
+        }
+        let stack: Vec<&str> = span.scope().from_root().map(|s| s.name()).collect();
+        self.record(Slice {
+            name: span.name(),
+            category: category(span.name()),
+            stack: stack.join(";"),
+            start,
+            duration,
+            children,
+        });
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if event.metadata().target() != PERF_TARGET {
+            return;
+        }
+        let mut fields = PerfFields::default();
+        event.record(&mut fields);
+        let Some(name) = fields.name else { return };
+        let duration = Duration::from_micros(fields.duration_us);
+        let end = Instant::now()
+            .checked_sub(Duration::from_micros(fields.ago_us))
+            .unwrap_or_else(Instant::now);
+        let mut stack: Vec<&str> = ctx
+            .event_scope(event)
+            .map(|scope| scope.from_root().map(|s| s.name()).collect())
+            .unwrap_or_default();
+        stack.push(&name);
+        self.record(Slice {
+            name: &name,
+            category: category(&name),
+            stack: stack.join(";"),
+            start: end.checked_sub(duration).unwrap_or(end),
+            duration,
+            children: Duration::ZERO,
+        });
+    }
+}
+
+/// The fields of a [`PERF_TARGET`] event.
+#[derive(Default)]
+pub(crate) struct PerfFields {
+    pub(crate) name: Option<String>,
+    pub(crate) duration_us: u64,
+    pub(crate) ago_us: u64,
+}
+
+impl Visit for PerfFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "name" {
+            self.name = Some(value.to_string());
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "duration_us" => self.duration_us = value,
+            "ago_us" => self.ago_us = value,
+            _ => {}
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record_u64(field, value.max(0) as u64);
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn profile(extension: &str) -> String {
+        let path =
+            std::env::temp_dir().join(format!("profile-{}.{extension}", uuid::Uuid::new_v4()));
+        let (layer, guard) = profile_layer(&path).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            let outer = tracing::debug_span!("db.list_documents");
+            let _outer = outer.enter();
+            {
+                let inner = tracing::debug_span!("db.decrypt");
+                let _inner = inner.enter();
+                std::thread::sleep(Duration::from_millis(2));
+            }
+            tracing::debug!(target: PERF_TARGET, name = "render.frame", duration_us = 16_000u64);
+        });
+        drop(guard);
+        let out = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        out
+    }
+
+    #[test]
+    fn chrome_trace_is_a_json_array_of_complete_events() {
+        let events: Vec<serde_json::Value> = serde_json::from_str(&profile("json")).unwrap();
+        let names: Vec<&str> = events.iter().map(|e| e["name"].as_str().unwrap()).collect();
+        assert_eq!(names, ["db.decrypt", "render.frame", "db.list_documents"]);
+        assert!(events.iter().all(|e| e["ph"] == "X"));
+        assert_eq!(events[0]["cat"], "db");
+        assert_eq!(events[1]["dur"], 16_000);
+        assert!(events[2]["dur"].as_u64().unwrap() >= events[0]["dur"].as_u64().unwrap());
+    }
+
+    #[test]
+    fn folded_stacks_use_self_time() {
+        let out = profile("folded");
+        let lines: BTreeMap<&str, u64> = out
+            .lines()
+            .map(|l| {
+                let (stack, micros) = l.rsplit_once(' ').unwrap();
+                (stack, micros.parse().unwrap())
+            })
+            .collect();
+        assert!(lines["db.list_documents;db.decrypt"] >= 2_000);
+        assert_eq!(lines["db.list_documents;render.frame"], 16_000);
+        // The outer span's own time excludes the time spent in db.decrypt.
+        assert!(lines["db.list_documents"] < lines["db.list_documents;db.decrypt"]);
+    }
+
+    #[test]
+    fn format_follows_extension() {
+        assert_eq!(
+            ProfileFormat::for_path(Path::new("a.folded")),
+            ProfileFormat::Folded
+        );
+        assert_eq!(
+            ProfileFormat::for_path(Path::new("a.json")),
+            ProfileFormat::ChromeTrace
+        );
+    }
+}
@@ -52,13 +52,15 @@ pub enum ActionDecision {
 pub fn action_level(action: &str) -> ActionLevel {
     match action {
         "search" | "open" | "navigate" | "history" | "summarize" | "word_count"
-        | "list_models" | "list_milestones" | "chat"
+        | "list_models" | "list_milestones" | "tasks_due" | "list_reminders" | "chat"
         | "sync_device" | "list_guardians" | "sync_status" | "list_devices"
         // UI panel toggles — read-only state changes the user can dismiss.
         | "open_pii_dashboard" | "open_models" | "open_inbox" | "browse"
-        | "open_settings" => ActionLevel::Observe,
-        "annotate" | "tag" | "bookmark" => ActionLevel::Annotate,
-        "create_document" | "create_thread" | "rename_thread" | "move_document"
+        | "open_settings"
+        // Locking only takes access away.
+        | "lock_session" => ActionLevel::Observe,
+        "annotate" | "tag" | "bookmark" | "set_reminder" => ActionLevel::Annotate,
+        "create_document" | "create_thread" | "rename_thread" | "move_document" | "file_document"
         | "restore" | "edit" | "find_replace" | "duplicate" | "import_file"
         | "swap_model" | "merge_threads" | "split_thread" | "adopt"
         | "create_milestone" | "delete_milestone" => ActionLevel::Modify,
@@ -79,6 +81,140 @@ pub fn authorize(level: ActionLevel) -> ActionDecision {
     }
 }
 
+/// Who is acting on a document.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Principal {
+    /// The account this database belongs to, on any of its paired devices.
+    #[default]
+    Local,
+    /// Another sovereign user, by the peer id they share from.
+    Peer(String),
+}
+
+impl std::fmt::Display for Principal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Local => write!(f, "this account"),
+            Self::Peer(id) => write!(f, "peer {id}"),
+        }
+    }
+}
+
+/// What a collaborator may do with a shared document, least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum AccessLevel {
+    /// View the document.
+    Read = 1,
+    /// View it and add metadata (tags, annotations).
+    Annotate = 2,
+    /// Change its content and structure.
+    Edit = 3,
+}
+
+impl AccessLevel {
+    /// Whether this level covers an action. Sending a document on and
+    /// destroying it stay with the owner whatever the level.
+    pub fn allows(self, action: ActionLevel) -> bool {
+        match action {
+            ActionLevel::Observe => true,
+            ActionLevel::Annotate => self >= AccessLevel::Annotate,
+            ActionLevel::Modify => self >= AccessLevel::Edit,
+            ActionLevel::Transmit | ActionLevel::Destruct => false,
+        }
+    }
+}
+
+/// A principal granted access to a document they don't own.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Collaborator {
+    pub principal: Principal,
+    pub level: AccessLevel,
+}
+
+/// Who owns a document and who else may touch it. The default — owned by
+/// this account, nobody else listed — is every document that was never
+/// shared, which is all of them until single-document sharing lands.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentPermissions {
+    #[serde(default)]
+    pub owner: Principal,
+    #[serde(default)]
+    pub collaborators: Vec<Collaborator>,
+}
+
+/// An action refused by a document's permissions.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{principal} may not {action:?} this document")]
+pub struct PermissionDenied {
+    pub principal: Principal,
+    pub action: ActionLevel,
+}
+
+impl DocumentPermissions {
+    /// Permissions for a document `owner` shared with this account.
+    pub fn owned_by(owner: Principal) -> Self {
+        Self {
+            owner,
+            collaborators: Vec::new(),
+        }
+    }
+
+    pub fn is_owner(&self, principal: &Principal) -> bool {
+        self.owner == *principal
+    }
+
+    /// The collaborator level granted to `principal`, if any. The owner
+    /// has no level: they can do everything.
+    pub fn level_of(&self, principal: &Principal) -> Option<AccessLevel> {
+        self.collaborators
+            .iter()
+            .find(|c| c.principal == *principal)
+            .map(|c| c.level)
+    }
+
+    /// Whether `principal` may take an action of this gravity.
+    pub fn permits(&self, principal: &Principal, action: ActionLevel) -> bool {
+        self.is_owner(principal) || self.level_of(principal).is_some_and(|l| l.allows(action))
+    }
+
+    /// [`Self::permits`] as a `Result`, for write paths to `?` on.
+    pub fn check(
+        &self,
+        principal: &Principal,
+        action: ActionLevel,
+    ) -> Result<(), PermissionDenied> {
+        if self.permits(principal, action) {
+            Ok(())
+        } else {
+            Err(PermissionDenied {
+                principal: principal.clone(),
+                action,
+            })
+        }
+    }
+
+    /// Give `principal` `level`, replacing what they had. The owner
+    /// already has full access and is left alone.
+    pub fn grant(&mut self, principal: Principal, level: AccessLevel) {
+        if self.is_owner(&principal) {
+            return;
+        }
+        match self
+            .collaborators
+            .iter_mut()
+            .find(|c| c.principal == principal)
+        {
+            Some(c) => c.level = level,
+            None => self.collaborators.push(Collaborator { principal, level }),
+        }
+    }
+
+    /// Take `principal`'s access away.
+    pub fn revoke(&mut self, principal: &Principal) {
+        self.collaborators.retain(|c| c.principal != *principal);
+    }
+}
+
 /// Visual state of the AI bubble, driven by orchestrator activity.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BubbleVisualState {
@@ -154,6 +290,13 @@ mod tests {
         assert_eq!(action_level("create_milestone"), ActionLevel::Modify);
         assert_eq!(action_level("delete_milestone"), ActionLevel::Modify);
         assert_eq!(action_level("list_milestones"), ActionLevel::Observe);
+        assert_eq!(action_level("tasks_due"), ActionLevel::Observe);
+    }
+
+    #[test]
+    fn action_level_reminder_ops() {
+        assert_eq!(action_level("set_reminder"), ActionLevel::Annotate);
+        assert_eq!(action_level("list_reminders"), ActionLevel::Observe);
     }
 
     #[test]
@@ -250,6 +393,59 @@ mod tests {
         assert_eq!(action_level("revoke_guardian"), ActionLevel::Destruct);
     }
 
+    #[test]
+    fn owner_can_do_everything() {
+        let perms = DocumentPermissions::default();
+        assert!(perms.permits(&Principal::Local, ActionLevel::Destruct));
+        let alice = Principal::Peer("alice".into());
+        assert!(!perms.permits(&alice, ActionLevel::Observe));
+
+        let shared = DocumentPermissions::owned_by(alice.clone());
+        assert!(shared.permits(&alice, ActionLevel::Transmit));
+        assert!(!shared.permits(&Principal::Local, ActionLevel::Observe));
+    }
+
+    #[test]
+    fn collaborator_levels_cover_actions_up_to_their_gravity() {
+        let alice = Principal::Peer("alice".into());
+        let mut perms = DocumentPermissions::owned_by(alice);
+        perms.grant(Principal::Local, AccessLevel::Read);
+        assert!(perms.permits(&Principal::Local, ActionLevel::Observe));
+        assert!(!perms.permits(&Principal::Local, ActionLevel::Annotate));
+
+        perms.grant(Principal::Local, AccessLevel::Annotate);
+        assert_eq!(perms.collaborators.len(), 1);
+        assert!(perms.permits(&Principal::Local, ActionLevel::Annotate));
+        assert!(!perms.permits(&Principal::Local, ActionLevel::Modify));
+
+        perms.grant(Principal::Local, AccessLevel::Edit);
+        assert!(perms.permits(&Principal::Local, ActionLevel::Modify));
+        assert_eq!(
+            perms.check(&Principal::Local, ActionLevel::Destruct),
+            Err(PermissionDenied {
+                principal: Principal::Local,
+                action: ActionLevel::Destruct,
+            })
+        );
+
+        perms.revoke(&Principal::Local);
+        assert!(!perms.permits(&Principal::Local, ActionLevel::Observe));
+    }
+
+    #[test]
+    fn granting_the_owner_is_a_no_op() {
+        let mut perms = DocumentPermissions::default();
+        perms.grant(Principal::Local, AccessLevel::Read);
+        assert!(perms.collaborators.is_empty());
+        assert!(perms.permits(&Principal::Local, ActionLevel::Modify));
+    }
+
+    #[test]
+    fn permissions_default_when_missing() {
+        let perms: DocumentPermissions = serde_json::from_str("{}").unwrap();
+        assert_eq!(perms, DocumentPermissions::default());
+    }
+
     #[test]
     fn action_decision_equality() {
         assert_eq!(ActionDecision::Approve, ActionDecision::Approve);
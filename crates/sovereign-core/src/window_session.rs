@@ -0,0 +1,120 @@
+//! Window session — which panels were open, where, and where the canvas
+//! camera was looking, so a relaunch picks up where the user left off.
+//!
+//! Holds record IDs and geometry only, never titles or bodies; the panels
+//! re-fetch their content through the normal (decrypting) commands.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::profile::write_owner_only;
+
+const SESSION_FILENAME: &str = "window_session.json";
+
+/// Canvas pan and zoom.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CameraState {
+    pub pan_x: f64,
+    pub pan_y: f64,
+    pub zoom: f64,
+}
+
+/// One open document panel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocPanelState {
+    pub doc_id: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// The open contact panel and the conversation it was showing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContactPanelState {
+    pub contact_id: String,
+    #[serde(default)]
+    pub conversation_id: Option<String>,
+}
+
+/// Everything restored on launch. Fields default so sessions written by
+/// older builds still load.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindowSession {
+    #[serde(default)]
+    pub camera: Option<CameraState>,
+    /// Bottom to top, so reopening in order rebuilds the stacking.
+    #[serde(default)]
+    pub doc_panels: Vec<DocPanelState>,
+    #[serde(default)]
+    pub contact_panel: Option<ContactPanelState>,
+    #[serde(default)]
+    pub inbox_open: bool,
+}
+
+impl WindowSession {
+    /// Load from `dir/window_session.json`. A missing or unreadable file
+    /// is an empty session — losing the layout is never worth an error.
+    pub fn load(dir: &Path) -> Self {
+        std::fs::read(dir.join(SESSION_FILENAME))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save to `dir/window_session.json`.
+    pub fn save(&self, dir: &Path) -> anyhow::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        let json = serde_json::to_string_pretty(self)?;
+        write_owner_only(&dir.join(SESSION_FILENAME), json.as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("sovereign_window_session_{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn save_and_load_roundtrip() {
+        let dir = test_dir("roundtrip");
+        let session = WindowSession {
+            camera: Some(CameraState { pan_x: -120.0, pan_y: 40.5, zoom: 0.75 }),
+            doc_panels: vec![DocPanelState {
+                doc_id: "document:abc".into(),
+                x: 120.0,
+                y: 80.0,
+                width: 680.0,
+                height: 520.0,
+            }],
+            contact_panel: Some(ContactPanelState {
+                contact_id: "contact:x".into(),
+                conversation_id: None,
+            }),
+            inbox_open: true,
+        };
+        session.save(&dir).unwrap();
+        assert_eq!(WindowSession::load(&dir), session);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn missing_or_corrupt_file_is_empty_session() {
+        let dir = test_dir("corrupt");
+        assert_eq!(WindowSession::load(&dir), WindowSession::default());
+        std::fs::write(dir.join(SESSION_FILENAME), b"{not json").unwrap();
+        assert_eq!(WindowSession::load(&dir), WindowSession::default());
+        std::fs::write(dir.join(SESSION_FILENAME), b"{}").unwrap();
+        assert_eq!(WindowSession::load(&dir), WindowSession::default());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
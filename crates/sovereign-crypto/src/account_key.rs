@@ -104,6 +104,18 @@ impl AccountKey {
         out.iter().map(|b| format!("{b:02x}")).collect()
     }
 
+    /// Derive the key the rotation audit log
+    /// ([`RotationAuditLog`](crate::rotation::RotationAuditLog)) is sealed
+    /// under. From the AccountKey rather than the KEK, so a KEK rotation
+    /// doesn't have to re-seal the log recording it.
+    pub fn derive_rotation_log_key(&self) -> [u8; KEY_SIZE] {
+        let hk = Hkdf::<Sha256>::new(None, &self.bytes);
+        let mut out = [0u8; KEY_SIZE];
+        hk.expand(b"sovereign-rotation-log-key:v1", &mut out)
+            .expect("32 bytes is within HKDF output limit");
+        out
+    }
+
     /// Access the raw key bytes.
     pub fn as_bytes(&self) -> &[u8; KEY_SIZE] {
         &self.bytes
@@ -1,8 +1,11 @@
+use hkdf::Hkdf;
 use rand::{Rng, RngExt};
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::account_key::{AccountKey, WrappedAccountKey};
-use crate::aead::{self, NONCE_SIZE};
+use crate::aead::{self, KEY_SIZE, NONCE_SIZE};
 use crate::device_key::DeviceKey;
 use crate::error::{CryptoError, CryptoResult};
 use crate::kek::{Kek, WrappedKek};
@@ -99,11 +102,42 @@ pub struct AuthStore {
     /// the store always unlocks with the KDF it was created under. Stores
     /// written before this field existed (v<=0.0.6, all HKDF) deserialize
     /// to [`Kdf::LegacyHkdf`] via the serde default and still open; new
-    /// stores use [`Kdf::current`] (Argon2id). See CRYPTO-001.
+    /// stores use Argon2id, with parameters from [`Kdf::calibrate`].
+    /// See CRYPTO-001.
     #[serde(default = "Kdf::legacy")]
     pub kdf: Kdf,
-    /// Always exactly 2 entries, randomized order.
+    /// Always exactly 2 entries, randomized order — or empty when a
+    /// second factor is enrolled, in which case both entries live sealed
+    /// inside [`SecondFactor`].
     pub personas: Vec<PersonaEntry>,
+    /// FIDO2 hardware token required (in addition to the passphrase) to
+    /// unlock. Absent for passphrase-only stores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub second_factor: Option<SecondFactor>,
+    /// The primary persona's MasterKey sealed under a new passphrase, set
+    /// by a recovery-phrase restore (see [`AuthStore::rebind_passphrase`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rebound_primary: Option<ReboundPassphrase>,
+}
+
+/// A MasterKey sealed under a key stretched from a passphrase that does
+/// not derive it. Lets a user who restored with the recovery phrase pick a
+/// new passphrase while the MasterKey — and so the DeviceKey, KEK and P2P
+/// identity hanging off it — stays the same.
+#[derive(Serialize, Deserialize)]
+pub struct ReboundPassphrase {
+    ciphertext: Vec<u8>,
+    nonce: [u8; NONCE_SIZE],
+}
+
+const REBOUND_PASSPHRASE_INFO: &[u8] = b"sovereign-rebound-passphrase-v1";
+
+fn rebound_key(stretched: &MasterKey) -> CryptoResult<[u8; KEY_SIZE]> {
+    let hk = Hkdf::<Sha256>::new(None, stretched.as_bytes());
+    let mut key = [0u8; KEY_SIZE];
+    hk.expand(REBOUND_PASSPHRASE_INFO, &mut key)
+        .map_err(|e| CryptoError::DerivationFailed(e.to_string()))?;
+    Ok(key)
 }
 
 /// One persona: a probe ciphertext + a wrapped KEK.
@@ -137,14 +171,15 @@ pub struct AuthSuccess {
 }
 
 impl AuthStore {
-    /// Create a new AuthStore with primary + duress personas.
+    /// Create a new AuthStore with primary + duress personas, both
+    /// stretched with `kdf` (normally [`Kdf::calibrate`]).
     pub fn create(
         primary_passphrase: &[u8],
         duress_passphrase: &[u8],
         salt: &[u8],
         device_id: &str,
+        kdf: Kdf,
     ) -> CryptoResult<Self> {
-        let kdf = Kdf::current();
         let primary_entry =
             Self::build_entry(primary_passphrase, salt, device_id, PRIMARY_PROBE, &kdf)?;
         let duress_entry =
@@ -161,23 +196,59 @@ impl AuthStore {
             device_id: device_id.to_string(),
             kdf,
             personas,
+            second_factor: None,
+            rebound_primary: None,
         })
     }
 
     /// Try to authenticate with a passphrase.
     /// Derives keys, tries each persona probe. Returns the matching persona,
     /// DeviceKey, and unwrapped KEK on success.
+    ///
+    /// Fails with [`CryptoError::SecondFactorRequired`] when a hardware
+    /// token is enrolled; use [`Self::authenticate_with_second_factor`].
     pub fn authenticate(&self, passphrase: &[u8]) -> CryptoResult<AuthSuccess> {
-        let master = MasterKey::derive(passphrase, &self.salt, &self.kdf)?;
-        let device_key = DeviceKey::derive(&master, &self.device_id)?;
-        // Fallback AccountKey: user-scoped, derived from MasterKey alone.
-        // Used when the persona has no `wrapped_account_key` (v0.0.4
-        // stores). When present, the stored AccountKey wins so paired
-        // devices keep their imported key even though the local passphrase
-        // would derive a different one.
-        let derived_account_key = AccountKey::derive(&master)?;
-
-        for entry in &self.personas {
+        if self.second_factor.is_some() {
+            return Err(CryptoError::SecondFactorRequired);
+        }
+        self.authenticate_entries(&self.personas, passphrase)
+    }
+
+    fn authenticate_entries(
+        &self,
+        personas: &[PersonaEntry],
+        passphrase: &[u8],
+    ) -> CryptoResult<AuthSuccess> {
+        for master in self.candidate_masters(passphrase)? {
+            if let Some(success) = Self::unlock_entries(personas, &master, &self.device_id)? {
+                return Ok(success);
+            }
+        }
+        Err(CryptoError::DecryptionFailed)
+    }
+
+    /// The MasterKeys `passphrase` can stand for: the one it derives, plus
+    /// the rebound primary MasterKey when it is the rebound passphrase.
+    fn candidate_masters(&self, passphrase: &[u8]) -> CryptoResult<Vec<MasterKey>> {
+        let derived = MasterKey::derive(passphrase, &self.salt, &self.kdf)?;
+        let rebound = match &self.rebound_primary {
+            Some(r) => aead::decrypt(&r.ciphertext, &r.nonce, &rebound_key(&derived)?)
+                .ok()
+                .and_then(|bytes| <[u8; KEY_SIZE]>::try_from(bytes.as_slice()).ok())
+                .map(MasterKey::from_bytes),
+            None => None,
+        };
+        Ok(std::iter::once(derived).chain(rebound).collect())
+    }
+
+    /// Try each persona's probe under `master`. `Ok(None)` when none opens.
+    fn unlock_entries(
+        personas: &[PersonaEntry],
+        master: &MasterKey,
+        device_id: &str,
+    ) -> CryptoResult<Option<AuthSuccess>> {
+        let device_key = DeviceKey::derive(master, device_id)?;
+        for entry in personas {
             if let Ok(plaintext) = aead::decrypt(
                 &entry.probe_ciphertext,
                 &entry.probe_nonce,
@@ -191,21 +262,24 @@ impl AuthStore {
                     continue;
                 };
                 let kek = Kek::unwrap(&entry.wrapped_kek, &device_key)?;
+                // Fallback AccountKey: user-scoped, derived from MasterKey
+                // alone. Used when the persona has no `wrapped_account_key`
+                // (v0.0.4 stores). When present, the stored AccountKey wins
+                // so paired devices keep their imported key even though the
+                // local passphrase would derive a different one.
                 let account_key = match &entry.wrapped_account_key {
                     Some(wrapped) => AccountKey::unwrap_with(wrapped, &device_key)?,
-                    None => AccountKey::derive(&master)?,
+                    None => AccountKey::derive(master)?,
                 };
-                let _ = &derived_account_key; // suppress unused warning when wrapped path is taken
-                return Ok(AuthSuccess {
+                return Ok(Some(AuthSuccess {
                     persona,
                     device_key,
                     account_key,
                     kek,
-                });
+                }));
             }
         }
-
-        Err(CryptoError::DecryptionFailed)
+        Ok(None)
     }
 
     /// Save to disk as JSON.
@@ -286,8 +360,8 @@ impl AuthStore {
         salt: &[u8],
         device_id: &str,
         imported_account_key: &AccountKey,
+        kdf: Kdf,
     ) -> CryptoResult<Self> {
-        let kdf = Kdf::current();
         let primary_entry = Self::build_entry_with_account_key(
             primary_passphrase,
             salt,
@@ -309,10 +383,394 @@ impl AuthStore {
             device_id: device_id.to_string(),
             kdf,
             personas,
+            second_factor: None,
+            rebound_primary: None,
+        })
+    }
+}
+
+// ── Second factor (FIDO2 hmac-secret) ────────────────────────────────
+
+/// Length of a FIDO2 `hmac-secret` salt and output.
+pub const HMAC_SECRET_LEN: usize = 32;
+
+/// Relying-party id the unlock credential is created under.
+pub const SECOND_FACTOR_RP_ID: &str = "sovereign.local";
+
+const SECOND_FACTOR_INFO: &[u8] = b"sovereign-auth-second-factor-v1";
+
+/// A hardware token implementing the CTAP2 `hmac-secret` extension. The
+/// token mixes a per-credential secret that never leaves it with the salt
+/// we send, so the output can only be reproduced with the token present
+/// (and touched). The transport (USB HID, NFC) is the implementor's concern.
+pub trait HmacSecretAuthenticator {
+    /// Create a credential for `rp_id` with `hmac-secret` enabled and
+    /// return its credential id.
+    fn make_credential(&self, rp_id: &str, user_id: &[u8]) -> CryptoResult<Vec<u8>>;
+
+    /// Evaluate `hmac-secret` for `credential_id` over `salt`.
+    fn hmac_secret(
+        &self,
+        rp_id: &str,
+        credential_id: &[u8],
+        salt: &[u8; HMAC_SECRET_LEN],
+    ) -> CryptoResult<TokenSecret>;
+}
+
+/// Output of the token's `hmac-secret` evaluation.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct TokenSecret {
+    bytes: [u8; HMAC_SECRET_LEN],
+}
+
+impl TokenSecret {
+    pub fn from_bytes(bytes: [u8; HMAC_SECRET_LEN]) -> Self {
+        Self { bytes }
+    }
+
+    pub fn as_bytes(&self) -> &[u8; HMAC_SECRET_LEN] {
+        &self.bytes
+    }
+}
+
+/// Random key that can stand in for the token when it is lost. It is
+/// meant to be Shamir-split across the user's guardians (see
+/// `guardian::shamir::split_secret`) and never stored whole.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct SecondFactorRecoveryKey {
+    bytes: [u8; KEY_SIZE],
+}
+
+impl SecondFactorRecoveryKey {
+    fn generate() -> Self {
+        let mut bytes = [0u8; KEY_SIZE];
+        rand::rng().fill_bytes(&mut bytes);
+        Self { bytes }
+    }
+
+    pub fn from_bytes(bytes: [u8; KEY_SIZE]) -> Self {
+        Self { bytes }
+    }
+
+    pub fn as_bytes(&self) -> &[u8; KEY_SIZE] {
+        &self.bytes
+    }
+
+    /// Seal under the KEK so the unlocked app can hand shares to the
+    /// guardians later (at backup time). The KEK is itself behind the
+    /// token, so the sealed copy adds no way around it.
+    pub fn seal(&self, kek: &Kek) -> CryptoResult<SealedRecoveryKey> {
+        let (ciphertext, nonce) = aead::encrypt(&self.bytes, kek.as_bytes())?;
+        Ok(SealedRecoveryKey { ciphertext, nonce })
+    }
+}
+
+/// [`SecondFactorRecoveryKey`] at rest, sealed under the KEK.
+#[derive(Serialize, Deserialize)]
+pub struct SealedRecoveryKey {
+    ciphertext: Vec<u8>,
+    nonce: [u8; NONCE_SIZE],
+}
+
+impl SealedRecoveryKey {
+    pub fn open(&self, kek: &Kek) -> CryptoResult<SecondFactorRecoveryKey> {
+        let plaintext = aead::decrypt(&self.ciphertext, &self.nonce, kek.as_bytes())?;
+        let bytes: [u8; KEY_SIZE] = plaintext
+            .as_slice()
+            .try_into()
+            .map_err(|_| CryptoError::InvalidKeyLength { expected: KEY_SIZE, got: plaintext.len() })?;
+        Ok(SecondFactorRecoveryKey::from_bytes(bytes))
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> CryptoResult<()> {
+        let json = serde_json::to_vec(self).map_err(|e| CryptoError::Serialization(e.to_string()))?;
+        crate::fs_private::write_private(path, json).map_err(|e| CryptoError::KeyDbIo(e.to_string()))
+    }
+
+    pub fn load(path: &std::path::Path) -> CryptoResult<Self> {
+        let bytes = std::fs::read(path).map_err(|e| CryptoError::KeyDbIo(e.to_string()))?;
+        serde_json::from_slice(&bytes).map_err(|e| CryptoError::Serialization(e.to_string()))
+    }
+}
+
+/// A credential made during enrollment, before it is bound to a store.
+pub struct TokenEnrollment {
+    pub credential_id: Vec<u8>,
+    pub rp_id: String,
+    pub hmac_salt: [u8; HMAC_SECRET_LEN],
+    pub secret: TokenSecret,
+}
+
+impl TokenEnrollment {
+    /// Create an unlock credential on `token` and evaluate it once (two
+    /// touches on most tokens).
+    pub fn create(
+        token: &dyn HmacSecretAuthenticator,
+        rp_id: &str,
+        device_id: &str,
+    ) -> CryptoResult<Self> {
+        let credential_id = token.make_credential(rp_id, device_id.as_bytes())?;
+        let mut hmac_salt = [0u8; HMAC_SECRET_LEN];
+        rand::rng().fill_bytes(&mut hmac_salt);
+        let secret = token.hmac_secret(rp_id, &credential_id, &hmac_salt)?;
+        Ok(Self {
+            credential_id,
+            rp_id: rp_id.to_string(),
+            hmac_salt,
+            secret,
         })
     }
 }
 
+/// An enrolled hardware token. Both persona entries are sealed under a
+/// key derived from the token's `hmac-secret` output, so the passphrase
+/// alone can neither unlock the store nor be brute-forced offline against
+/// it — and the sealed blob doesn't reveal which entry is which.
+#[derive(Serialize, Deserialize)]
+pub struct SecondFactor {
+    pub credential_id: Vec<u8>,
+    pub rp_id: String,
+    pub hmac_salt: [u8; HMAC_SECRET_LEN],
+    sealed_personas: Vec<u8>,
+    sealed_nonce: [u8; NONCE_SIZE],
+    /// The token output sealed under the guardian-held recovery key.
+    recovery_ciphertext: Vec<u8>,
+    recovery_nonce: [u8; NONCE_SIZE],
+}
+
+impl SecondFactor {
+    /// Reproduce the token's output from the recovery key reassembled
+    /// from guardian shards.
+    pub fn recover_secret(&self, key: &SecondFactorRecoveryKey) -> CryptoResult<TokenSecret> {
+        let plaintext = aead::decrypt(&self.recovery_ciphertext, &self.recovery_nonce, key.as_bytes())?;
+        let bytes: [u8; HMAC_SECRET_LEN] = plaintext
+            .as_slice()
+            .try_into()
+            .map_err(|_| CryptoError::SecondFactor("recovered secret has wrong length".into()))?;
+        Ok(TokenSecret::from_bytes(bytes))
+    }
+
+    fn open_personas(&self, secret: &TokenSecret) -> CryptoResult<Vec<PersonaEntry>> {
+        let key = seal_key(secret, &self.hmac_salt)?;
+        let json = aead::decrypt(&self.sealed_personas, &self.sealed_nonce, &key)?;
+        serde_json::from_slice(&json).map_err(|e| CryptoError::Serialization(e.to_string()))
+    }
+
+    fn reseal_personas(
+        &mut self,
+        secret: &TokenSecret,
+        personas: &[PersonaEntry],
+    ) -> CryptoResult<()> {
+        let json = serde_json::to_vec(personas)
+            .map_err(|e| CryptoError::Serialization(e.to_string()))?;
+        let key = seal_key(secret, &self.hmac_salt)?;
+        (self.sealed_personas, self.sealed_nonce) = aead::encrypt(&json, &key)?;
+        Ok(())
+    }
+}
+
+fn seal_key(
+    secret: &TokenSecret,
+    hmac_salt: &[u8; HMAC_SECRET_LEN],
+) -> CryptoResult<[u8; KEY_SIZE]> {
+    let hk = Hkdf::<Sha256>::new(Some(hmac_salt), secret.as_bytes());
+    let mut key = [0u8; KEY_SIZE];
+    hk.expand(SECOND_FACTOR_INFO, &mut key)
+        .map_err(|e| CryptoError::DerivationFailed(e.to_string()))?;
+    Ok(key)
+}
+
+impl AuthStore {
+    pub fn requires_second_factor(&self) -> bool {
+        self.second_factor.is_some()
+    }
+
+    /// Require `enrollment`'s token for every future unlock. Returns the
+    /// recovery key for the guardians; it is not kept anywhere else.
+    pub fn enroll_second_factor(
+        &mut self,
+        enrollment: &TokenEnrollment,
+    ) -> CryptoResult<SecondFactorRecoveryKey> {
+        if self.second_factor.is_some() {
+            return Err(CryptoError::SecondFactor("a security key is already enrolled".into()));
+        }
+        let json = serde_json::to_vec(&self.personas)
+            .map_err(|e| CryptoError::Serialization(e.to_string()))?;
+        let key = seal_key(&enrollment.secret, &enrollment.hmac_salt)?;
+        let (sealed_personas, sealed_nonce) = aead::encrypt(&json, &key)?;
+
+        let recovery_key = SecondFactorRecoveryKey::generate();
+        let (recovery_ciphertext, recovery_nonce) =
+            aead::encrypt(enrollment.secret.as_bytes(), recovery_key.as_bytes())?;
+
+        self.second_factor = Some(SecondFactor {
+            credential_id: enrollment.credential_id.clone(),
+            rp_id: enrollment.rp_id.clone(),
+            hmac_salt: enrollment.hmac_salt,
+            sealed_personas,
+            sealed_nonce,
+            recovery_ciphertext,
+            recovery_nonce,
+        });
+        self.personas.clear();
+        Ok(recovery_key)
+    }
+
+    /// Authenticate with the passphrase plus the token's (or the
+    /// recovered) `hmac-secret` output.
+    pub fn authenticate_with_second_factor(
+        &self,
+        passphrase: &[u8],
+        secret: &TokenSecret,
+    ) -> CryptoResult<AuthSuccess> {
+        let Some(factor) = &self.second_factor else {
+            return self.authenticate(passphrase);
+        };
+        let personas = factor.open_personas(secret)?;
+        self.authenticate_entries(&personas, passphrase)
+    }
+
+    /// Go back to passphrase-only unlock, e.g. after recovering from a lost
+    /// token. The passphrase is checked first so a recovered secret alone
+    /// can't strip the factor.
+    pub fn remove_second_factor(
+        &mut self,
+        passphrase: &[u8],
+        secret: &TokenSecret,
+    ) -> CryptoResult<()> {
+        let Some(factor) = &self.second_factor else {
+            return Ok(());
+        };
+        let personas = factor.open_personas(secret)?;
+        self.authenticate_entries(&personas, passphrase)?;
+        self.personas = personas;
+        self.second_factor = None;
+        Ok(())
+    }
+}
+
+// ── KEK rotation ─────────────────────────────────────────────────────
+
+impl AuthStore {
+    /// Replace the KEK wrapped in the persona that `passphrase` (plus the
+    /// token output, when a security key is enrolled) unlocks. The other
+    /// persona's entry is untouched. Callers must already have re-wrapped
+    /// everything protected by the old KEK — this is the commit point of a
+    /// rotation.
+    pub fn replace_kek(
+        &mut self,
+        passphrase: &[u8],
+        second_factor: Option<&TokenSecret>,
+        new_kek: &Kek,
+    ) -> CryptoResult<PersonaKind> {
+        let mut personas = match (&self.second_factor, second_factor) {
+            (Some(factor), Some(secret)) => factor.open_personas(secret)?,
+            (Some(_), None) => return Err(CryptoError::SecondFactorRequired),
+            (None, _) => std::mem::take(&mut self.personas),
+        };
+        let result = self.candidate_masters(passphrase).and_then(|masters| {
+            Self::rewrap_persona_kek(&self.device_id, &masters, &mut personas, new_kek)
+        });
+        match (&mut self.second_factor, second_factor) {
+            (Some(factor), Some(secret)) if result.is_ok() => {
+                factor.reseal_personas(secret, &personas)?
+            }
+            (Some(_), _) => {}
+            (None, _) => self.personas = personas,
+        }
+        result
+    }
+
+    fn rewrap_persona_kek(
+        device_id: &str,
+        masters: &[MasterKey],
+        personas: &mut [PersonaEntry],
+        new_kek: &Kek,
+    ) -> CryptoResult<PersonaKind> {
+        for master in masters {
+            let device_key = DeviceKey::derive(master, device_id)?;
+            for entry in personas.iter_mut() {
+                let Ok(probe) = aead::decrypt(
+                    &entry.probe_ciphertext,
+                    &entry.probe_nonce,
+                    device_key.as_bytes(),
+                ) else {
+                    continue;
+                };
+                let persona = if probe == PRIMARY_PROBE {
+                    PersonaKind::Primary
+                } else if probe == DURESS_PROBE {
+                    PersonaKind::Duress
+                } else {
+                    continue;
+                };
+                entry.wrapped_kek = new_kek.wrap(&device_key)?;
+                return Ok(persona);
+            }
+        }
+        Err(CryptoError::DecryptionFailed)
+    }
+}
+
+// ── Recovery phrase ──────────────────────────────────────────────────
+
+impl AuthStore {
+    /// The primary persona's MasterKey, for the onboarding recovery-phrase
+    /// export. Fails unless `passphrase` unlocks the primary persona.
+    pub fn primary_master_key(&self, passphrase: &[u8]) -> CryptoResult<MasterKey> {
+        let personas = self.passphrase_personas()?;
+        for master in self.candidate_masters(passphrase)? {
+            if let Some(success) = Self::unlock_entries(personas, &master, &self.device_id)? {
+                if success.persona == PersonaKind::Primary {
+                    return Ok(master);
+                }
+            }
+        }
+        Err(CryptoError::DecryptionFailed)
+    }
+
+    /// Unlock the primary persona with a MasterKey restored from the
+    /// recovery phrase, bypassing the passphrase.
+    pub fn authenticate_with_master_key(&self, master: &MasterKey) -> CryptoResult<AuthSuccess> {
+        match Self::unlock_entries(self.passphrase_personas()?, master, &self.device_id)? {
+            Some(success) if success.persona == PersonaKind::Primary => Ok(success),
+            _ => Err(CryptoError::DecryptionFailed),
+        }
+    }
+
+    /// Make `new_passphrase` unlock the primary persona from now on, given
+    /// its MasterKey (restored from the recovery phrase). The MasterKey is
+    /// sealed under the new passphrase rather than re-derived, so nothing
+    /// keyed off it has to change. Rejects the duress passphrase.
+    pub fn rebind_passphrase(
+        &mut self,
+        master: &MasterKey,
+        new_passphrase: &[u8],
+    ) -> CryptoResult<()> {
+        self.authenticate_with_master_key(master)?;
+        let stretched = MasterKey::derive(new_passphrase, &self.salt, &self.kdf)?;
+        if Self::unlock_entries(self.passphrase_personas()?, &stretched, &self.device_id)?
+            .is_some_and(|s| s.persona == PersonaKind::Duress)
+        {
+            return Err(CryptoError::DerivationFailed(
+                "the new passphrase must differ from the duress passphrase".into(),
+            ));
+        }
+        let (ciphertext, nonce) = aead::encrypt(master.as_bytes(), &rebound_key(&stretched)?)?;
+        self.rebound_primary = Some(ReboundPassphrase { ciphertext, nonce });
+        Ok(())
+    }
+
+    /// The persona entries a passphrase alone reaches: not those sealed
+    /// behind a security key.
+    fn passphrase_personas(&self) -> CryptoResult<&[PersonaEntry]> {
+        if self.second_factor.is_some() {
+            return Err(CryptoError::SecondFactorRequired);
+        }
+        Ok(&self.personas)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -367,6 +825,7 @@ mod tests {
             b"Duress!Pass5678",
             TEST_SALT,
             TEST_DEVICE,
+            Kdf::current(),
         )
         .unwrap();
 
@@ -381,6 +840,7 @@ mod tests {
             b"Duress!Pass5678",
             TEST_SALT,
             TEST_DEVICE,
+            Kdf::current(),
         )
         .unwrap();
 
@@ -395,6 +855,7 @@ mod tests {
             b"Duress!Pass5678",
             TEST_SALT,
             TEST_DEVICE,
+            Kdf::current(),
         )
         .unwrap();
 
@@ -411,6 +872,7 @@ mod tests {
             b"Duress!Pass5678",
             TEST_SALT,
             TEST_DEVICE,
+            Kdf::current(),
         )
         .unwrap();
         store.save(&path).unwrap();
@@ -430,6 +892,7 @@ mod tests {
             b"Duress!Pass5678",
             TEST_SALT,
             TEST_DEVICE,
+            Kdf::current(),
         )
         .unwrap();
         assert_eq!(store.personas.len(), 2);
@@ -442,6 +905,7 @@ mod tests {
             b"Duress!Pass5678",
             TEST_SALT,
             TEST_DEVICE,
+            Kdf::current(),
         )
         .unwrap();
         assert_ne!(store.personas[0].label, store.personas[1].label);
@@ -454,6 +918,7 @@ mod tests {
             b"Duress!Pass5678",
             TEST_SALT,
             TEST_DEVICE,
+            Kdf::current(),
         )
         .unwrap();
         // Both personas must have the wrapped_account_key field populated
@@ -475,6 +940,7 @@ mod tests {
             TEST_SALT,
             TEST_DEVICE,
             &imported_ak,
+            Kdf::current(),
         )
         .unwrap();
 
@@ -510,6 +976,7 @@ mod tests {
             TEST_SALT,
             TEST_DEVICE,
             &imported_ak,
+            Kdf::current(),
         )
         .unwrap();
         store.save(&path).unwrap();
@@ -547,6 +1014,8 @@ mod tests {
                 )
                 .unwrap(),
             ],
+            second_factor: None,
+            rebound_primary: None,
         };
         for entry in store.personas.iter_mut() {
             entry.wrapped_account_key = None;
@@ -575,9 +1044,9 @@ mod tests {
         // independently on two laptops sharing the same secrets (the
         // pre-pairing legacy path).
         let store_a =
-            AuthStore::create(pw, duress, SHARED_SALT, "device-AAA").unwrap();
+            AuthStore::create(pw, duress, SHARED_SALT, "device-AAA", Kdf::current()).unwrap();
         let store_b =
-            AuthStore::create(pw, duress, SHARED_SALT, "device-BBB").unwrap();
+            AuthStore::create(pw, duress, SHARED_SALT, "device-BBB", Kdf::current()).unwrap();
 
         let auth_a = store_a.authenticate(pw).unwrap();
         let auth_b = store_b.authenticate(pw).unwrap();
@@ -605,11 +1074,39 @@ mod tests {
             b"Duress!Pass5678",
             TEST_SALT,
             TEST_DEVICE,
+            Kdf::current(),
         )
         .unwrap();
         assert!(matches!(store.kdf, Kdf::Argon2id { .. }));
     }
 
+    #[test]
+    fn calibrated_params_are_stored_and_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("auth.store");
+        let kdf = Kdf::Argon2id {
+            m_cost_kib: 8 * 1024,
+            t_cost: 2,
+            p_cost: 1,
+        };
+        AuthStore::create(
+            b"Primary!Pass1234",
+            b"Duress!Pass5678",
+            TEST_SALT,
+            TEST_DEVICE,
+            kdf.clone(),
+        )
+        .unwrap()
+        .save(&path)
+        .unwrap();
+
+        // Unlock re-reads the params from the store, not today's defaults.
+        let loaded = AuthStore::load(&path).unwrap();
+        assert_eq!(loaded.kdf, kdf);
+        let auth = loaded.authenticate(b"Primary!Pass1234").unwrap();
+        assert_eq!(auth.persona, PersonaKind::Primary);
+    }
+
     #[test]
     fn legacy_hkdf_store_still_unlocks() {
         // A pre-v0.0.7 store: personas built under HKDF, kdf=LegacyHkdf.
@@ -626,6 +1123,8 @@ mod tests {
             device_id: TEST_DEVICE.to_string(),
             kdf,
             personas: vec![primary, duress],
+            second_factor: None,
+            rebound_primary: None,
         };
         assert_eq!(
             store.authenticate(b"Primary!Pass1234").unwrap().persona,
@@ -649,6 +1148,8 @@ mod tests {
             device_id: TEST_DEVICE.to_string(),
             kdf,
             personas: vec![primary, duress],
+            second_factor: None,
+            rebound_primary: None,
         };
         let mut json: serde_json::Value =
             serde_json::from_slice(&serde_json::to_vec(&store).unwrap()).unwrap();
@@ -668,6 +1169,7 @@ mod tests {
             b"Duress!Pass5678",
             TEST_SALT,
             TEST_DEVICE,
+            Kdf::current(),
         )
         .unwrap();
 
@@ -675,4 +1177,215 @@ mod tests {
         let duress = store.authenticate(b"Duress!Pass5678").unwrap();
         assert_ne!(primary.kek.as_bytes(), duress.kek.as_bytes());
     }
+
+    /// Software stand-in for a token: HMAC-SHA256 under a fixed secret.
+    struct FakeToken([u8; 32]);
+
+    impl HmacSecretAuthenticator for FakeToken {
+        fn make_credential(&self, _rp_id: &str, user_id: &[u8]) -> CryptoResult<Vec<u8>> {
+            Ok([b"cred-".as_slice(), user_id].concat())
+        }
+
+        fn hmac_secret(
+            &self,
+            _rp_id: &str,
+            credential_id: &[u8],
+            salt: &[u8; HMAC_SECRET_LEN],
+        ) -> CryptoResult<TokenSecret> {
+            use hmac::{Hmac, Mac};
+            let mut mac = Hmac::<Sha256>::new_from_slice(&self.0).unwrap();
+            mac.update(credential_id);
+            mac.update(salt);
+            Ok(TokenSecret::from_bytes(mac.finalize().into_bytes().into()))
+        }
+    }
+
+    fn enrolled_store() -> (AuthStore, SecondFactorRecoveryKey) {
+        let mut store = AuthStore::create(
+            b"Primary!Pass1234",
+            b"Duress!Pass5678",
+            TEST_SALT,
+            TEST_DEVICE,
+            Kdf::current(),
+        )
+        .unwrap();
+        let enrollment =
+            TokenEnrollment::create(&FakeToken([7; 32]), SECOND_FACTOR_RP_ID, TEST_DEVICE).unwrap();
+        let recovery = store.enroll_second_factor(&enrollment).unwrap();
+        (store, recovery)
+    }
+
+    fn token_output(store: &AuthStore, token: &FakeToken) -> TokenSecret {
+        let factor = store.second_factor.as_ref().unwrap();
+        token
+            .hmac_secret(&factor.rp_id, &factor.credential_id, &factor.hmac_salt)
+            .unwrap()
+    }
+
+    #[test]
+    fn second_factor_required_after_enrollment() {
+        let (store, _) = enrolled_store();
+        assert!(store.requires_second_factor());
+        assert!(store.personas.is_empty());
+        assert!(matches!(
+            store.authenticate(b"Primary!Pass1234"),
+            Err(CryptoError::SecondFactorRequired)
+        ));
+
+        let secret = token_output(&store, &FakeToken([7; 32]));
+        let primary = store
+            .authenticate_with_second_factor(b"Primary!Pass1234", &secret)
+            .unwrap();
+        assert_eq!(primary.persona, PersonaKind::Primary);
+        let duress = store
+            .authenticate_with_second_factor(b"Duress!Pass5678", &secret)
+            .unwrap();
+        assert_eq!(duress.persona, PersonaKind::Duress);
+        assert!(store
+            .authenticate_with_second_factor(b"WrongPassword99!", &secret)
+            .is_err());
+    }
+
+    #[test]
+    fn second_factor_wrong_token_fails() {
+        let (store, _) = enrolled_store();
+        let other = token_output(&store, &FakeToken([8; 32]));
+        assert!(store
+            .authenticate_with_second_factor(b"Primary!Pass1234", &other)
+            .is_err());
+    }
+
+    #[test]
+    fn second_factor_survives_save_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("auth.store");
+        let (store, _) = enrolled_store();
+        store.save(&path).unwrap();
+
+        let loaded = AuthStore::load(&path).unwrap();
+        let secret = token_output(&loaded, &FakeToken([7; 32]));
+        let result = loaded
+            .authenticate_with_second_factor(b"Primary!Pass1234", &secret)
+            .unwrap();
+        assert_eq!(result.persona, PersonaKind::Primary);
+    }
+
+    #[test]
+    fn recovery_key_replaces_lost_token() {
+        let (mut store, recovery) = enrolled_store();
+        let factor = store.second_factor.as_ref().unwrap();
+        let secret = factor.recover_secret(&recovery).unwrap();
+        assert!(factor
+            .recover_secret(&SecondFactorRecoveryKey::from_bytes([0; 32]))
+            .is_err());
+
+        // A recovered secret alone can't strip the factor.
+        assert!(store.remove_second_factor(b"WrongPassword99!", &secret).is_err());
+        assert!(store.requires_second_factor());
+
+        // The KEK-sealed copy round-trips.
+        let kek = store
+            .authenticate_with_second_factor(b"Primary!Pass1234", &secret)
+            .unwrap()
+            .kek;
+        let sealed = recovery.seal(&kek).unwrap();
+        assert_eq!(sealed.open(&kek).unwrap().as_bytes(), recovery.as_bytes());
+
+        store.remove_second_factor(b"Primary!Pass1234", &secret).unwrap();
+        assert!(!store.requires_second_factor());
+        assert_eq!(store.personas.len(), 2);
+        assert_eq!(
+            store.authenticate(b"Primary!Pass1234").unwrap().persona,
+            PersonaKind::Primary
+        );
+    }
+
+    #[test]
+    fn replace_kek_rewraps_only_the_unlocked_persona() {
+        let mut store = AuthStore::create(
+            b"Primary!Pass1234",
+            b"Duress!Pass5678",
+            TEST_SALT,
+            TEST_DEVICE,
+            Kdf::current(),
+        )
+        .unwrap();
+        let duress_kek = store.authenticate(b"Duress!Pass5678").unwrap().kek;
+        let new_kek = Kek::generate();
+
+        assert!(store.replace_kek(b"WrongPassword99!", None, &new_kek).is_err());
+        assert_eq!(store.personas.len(), 2);
+        let persona = store.replace_kek(b"Primary!Pass1234", None, &new_kek).unwrap();
+        assert_eq!(persona, PersonaKind::Primary);
+        let primary = store.authenticate(b"Primary!Pass1234").unwrap();
+        assert_eq!(primary.kek.as_bytes(), new_kek.as_bytes());
+        let duress = store.authenticate(b"Duress!Pass5678").unwrap();
+        assert_eq!(duress.kek.as_bytes(), duress_kek.as_bytes());
+
+        // With a security key, the sealed entries are re-sealed.
+        let (mut store, _) = enrolled_store();
+        let secret = token_output(&store, &FakeToken([7; 32]));
+        assert!(matches!(
+            store.replace_kek(b"Primary!Pass1234", None, &new_kek),
+            Err(CryptoError::SecondFactorRequired)
+        ));
+        store
+            .replace_kek(b"Primary!Pass1234", Some(&secret), &new_kek)
+            .unwrap();
+        let primary = store
+            .authenticate_with_second_factor(b"Primary!Pass1234", &secret)
+            .unwrap();
+        assert_eq!(primary.kek.as_bytes(), new_kek.as_bytes());
+    }
+
+    #[test]
+    fn recovery_master_key_unlocks_and_rebinds_passphrase() {
+        let mut store = AuthStore::create(
+            b"Primary!Pass1234",
+            b"Duress!Pass5678",
+            TEST_SALT,
+            TEST_DEVICE,
+            Kdf::current(),
+        )
+        .unwrap();
+        let master = store.primary_master_key(b"Primary!Pass1234").unwrap();
+        assert!(store.primary_master_key(b"Duress!Pass5678").is_err());
+        let before = store.authenticate(b"Primary!Pass1234").unwrap();
+
+        let restored = store.authenticate_with_master_key(&master).unwrap();
+        assert_eq!(restored.persona, PersonaKind::Primary);
+        assert_eq!(restored.kek.as_bytes(), before.kek.as_bytes());
+
+        assert!(store.rebind_passphrase(&master, b"Duress!Pass5678").is_err());
+        store.rebind_passphrase(&master, b"Brand!NewPass99").unwrap();
+
+        // The new passphrase reaches the same keys; duress is untouched.
+        let after = store.authenticate(b"Brand!NewPass99").unwrap();
+        assert_eq!(after.persona, PersonaKind::Primary);
+        assert_eq!(after.device_key.as_bytes(), before.device_key.as_bytes());
+        assert_eq!(after.kek.as_bytes(), before.kek.as_bytes());
+        assert_eq!(
+            store.authenticate(b"Duress!Pass5678").unwrap().persona,
+            PersonaKind::Duress
+        );
+
+        // KEK rotation commits through the rebound passphrase too.
+        let new_kek = Kek::generate();
+        store.replace_kek(b"Brand!NewPass99", None, &new_kek).unwrap();
+        let rotated = store.authenticate(b"Brand!NewPass99").unwrap();
+        assert_eq!(rotated.kek.as_bytes(), new_kek.as_bytes());
+    }
+
+    #[test]
+    fn wrong_master_key_does_not_unlock() {
+        let store = AuthStore::create(
+            b"Primary!Pass1234",
+            b"Duress!Pass5678",
+            TEST_SALT,
+            TEST_DEVICE,
+            Kdf::current(),
+        )
+        .unwrap();
+        assert!(store.authenticate_with_master_key(&MasterKey::generate()).is_err());
+    }
 }
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use zeroize::{Zeroize, ZeroizeOnDrop};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
 use crate::aead::{self, KEY_SIZE, NONCE_SIZE};
 use crate::error::{CryptoError, CryptoResult};
@@ -83,6 +83,71 @@ impl Drop for CanaryDetector {
     }
 }
 
+// ── Multiple phrases ─────────────────────────────────────────────────
+
+/// What happens when a canary phrase is typed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CanaryAction {
+    /// Lock the session and show the login screen.
+    #[default]
+    Lockdown,
+    /// Quietly record an alert document (it syncs to paired devices) and
+    /// carry on as if nothing happened.
+    SilentAlert,
+    /// Swap the session over to the decoy profile.
+    Decoy,
+    /// Destroy the keys on this device.
+    Wipe,
+}
+
+/// One configured phrase and its action.
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct CanaryRule {
+    pub phrase: String,
+    #[zeroize(skip)]
+    pub action: CanaryAction,
+}
+
+/// Watches typed text for several canary phrases at once.
+pub struct CanarySet {
+    detectors: Vec<(CanaryDetector, CanaryAction)>,
+}
+
+impl CanarySet {
+    /// Empty phrases are dropped.
+    pub fn new(rules: &[CanaryRule]) -> Self {
+        Self {
+            detectors: rules
+                .iter()
+                .filter(|r| !r.phrase.is_empty())
+                .map(|r| (CanaryDetector::new(r.phrase.clone()), r.action))
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.detectors.is_empty()
+    }
+
+    /// Feed a single character. Every detector sees it (so no buffer falls
+    /// behind); if several phrases end here, the first configured wins.
+    pub fn feed_char(&mut self, ch: char) -> Option<CanaryAction> {
+        let mut hit = None;
+        for (detector, action) in &mut self.detectors {
+            if detector.feed_char(ch) && hit.is_none() {
+                hit = Some(*action);
+            }
+        }
+        hit
+    }
+
+    /// Feed a string. Returns the action of the first phrase completed.
+    pub fn feed_str(&mut self, text: &str) -> Option<CanaryAction> {
+        text.chars().find_map(|ch| self.feed_char(ch))
+    }
+}
+
 // ── Encrypted storage ────────────────────────────────────────────────
 
 /// Canary phrase encrypted for disk persistence.
@@ -105,6 +170,26 @@ impl CanaryStore {
         String::from_utf8(plaintext).map_err(|e| CryptoError::Serialization(e.to_string()))
     }
 
+    /// Encrypt a set of phrases with their actions.
+    pub fn encrypt_rules(rules: &[CanaryRule], key: &[u8; KEY_SIZE]) -> CryptoResult<Self> {
+        let json = Zeroizing::new(
+            serde_json::to_string(rules).map_err(|e| CryptoError::Serialization(e.to_string()))?,
+        );
+        Self::encrypt(&json, key)
+    }
+
+    /// Decrypt the configured phrases. A store written before actions
+    /// existed holds a bare phrase, which keeps its lockdown behaviour.
+    pub fn decrypt_rules(&self, key: &[u8; KEY_SIZE]) -> CryptoResult<Vec<CanaryRule>> {
+        let plaintext = Zeroizing::new(self.decrypt(key)?);
+        Ok(serde_json::from_str(&plaintext).unwrap_or_else(|_| {
+            vec![CanaryRule {
+                phrase: plaintext.to_string(),
+                action: CanaryAction::Lockdown,
+            }]
+        }))
+    }
+
     /// Save to disk as JSON.
     pub fn save(&self, path: &std::path::Path) -> CryptoResult<()> {
         let json = serde_json::to_vec(self)
@@ -226,6 +311,42 @@ mod tests {
         assert!(store.decrypt(&wrong_key).is_err());
     }
 
+    #[test]
+    fn canary_set_reports_the_matching_action() {
+        let mut set = CanarySet::new(&[
+            CanaryRule { phrase: "blue heron".into(), action: CanaryAction::SilentAlert },
+            CanaryRule { phrase: String::new(), action: CanaryAction::Wipe },
+            CanaryRule { phrase: "heron".into(), action: CanaryAction::Decoy },
+        ]);
+        assert!(!set.is_empty());
+        assert_eq!(set.feed_str("I saw a grey heron"), Some(CanaryAction::Decoy));
+        // Both phrases end on the same character: the first rule wins.
+        assert_eq!(set.feed_str(" and a blue heron"), Some(CanaryAction::SilentAlert));
+        assert_eq!(set.feed_str("nothing here"), None);
+        assert!(CanarySet::new(&[]).is_empty());
+    }
+
+    #[test]
+    fn canary_store_rules_roundtrip_and_legacy_phrase() {
+        let key = [42u8; KEY_SIZE];
+        let rules = vec![
+            CanaryRule { phrase: "alpha".into(), action: CanaryAction::Wipe },
+            CanaryRule { phrase: "beta".into(), action: CanaryAction::Decoy },
+        ];
+        let store = CanaryStore::encrypt_rules(&rules, &key).unwrap();
+        let back = store.decrypt_rules(&key).unwrap();
+        assert_eq!(back.len(), 2);
+        assert_eq!(back[0].phrase, "alpha");
+        assert_eq!(back[1].action, CanaryAction::Decoy);
+
+        // A single-phrase store from before rules existed.
+        let legacy = CanaryStore::encrypt("old phrase", &key).unwrap();
+        let back = legacy.decrypt_rules(&key).unwrap();
+        assert_eq!(back.len(), 1);
+        assert_eq!(back[0].phrase, "old phrase");
+        assert_eq!(back[0].action, CanaryAction::Lockdown);
+    }
+
     #[test]
     fn canary_store_save_load_roundtrip() {
         let dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,118 @@
+//! Ed25519 device signatures over local commit records.
+//!
+//! The signing key is the device's P2P identity key (same HKDF derivation
+//! from the DeviceKey as `sovereign_p2p::identity::derive_keypair`), so a
+//! commit's public key maps to the PeerId of the device that made it. Unlike
+//! the device MAC in [`crate::mac`], any device can check these signatures,
+//! which lets a user prove which device produced a snapshot.
+
+use base64::Engine;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use hkdf::Hkdf;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::device_key::DeviceKey;
+
+/// HKDF info shared with the libp2p identity derivation.
+const IDENTITY_INFO: &[u8] = b"sovereign-p2p-identity";
+
+/// Domain-separation tag for commit signatures.
+const COMMIT_SIG_DOMAIN: &[u8] = b"sovereign-commit-signature:v1";
+
+fn b64() -> base64::engine::GeneralPurpose {
+    base64::engine::general_purpose::STANDARD
+}
+
+/// The Ed25519 secret seed behind this device's P2P identity.
+pub fn identity_seed(device_key: &DeviceKey) -> Zeroizing<[u8; 32]> {
+    let hk = Hkdf::<Sha256>::new(None, device_key.as_bytes());
+    let mut seed = Zeroizing::new([0u8; 32]);
+    hk.expand(IDENTITY_INFO, seed.as_mut())
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    seed
+}
+
+/// Signs commit records as this device.
+pub struct DeviceSigner {
+    inner: SigningKey,
+}
+
+impl DeviceSigner {
+    pub fn new(device_key: &DeviceKey) -> Self {
+        Self {
+            inner: SigningKey::from_bytes(&identity_seed(device_key)),
+        }
+    }
+
+    /// Base64 Ed25519 public key.
+    pub fn public_key_b64(&self) -> String {
+        b64().encode(self.inner.verifying_key().to_bytes())
+    }
+
+    /// Base64 signature over `data` (domain-separated).
+    pub fn sign_commit(&self, data: &[u8]) -> String {
+        let mut msg = Vec::with_capacity(COMMIT_SIG_DOMAIN.len() + data.len());
+        msg.extend_from_slice(COMMIT_SIG_DOMAIN);
+        msg.extend_from_slice(data);
+        b64().encode(self.inner.sign(&msg).to_bytes())
+    }
+}
+
+/// Check a commit signature made by [`DeviceSigner::sign_commit`].
+/// Malformed keys or signatures fail verification.
+pub fn verify_commit_signature(public_key_b64: &str, data: &[u8], signature_b64: &str) -> bool {
+    let Some(key) = b64()
+        .decode(public_key_b64)
+        .ok()
+        .and_then(|b| <[u8; 32]>::try_from(b).ok())
+        .and_then(|b| VerifyingKey::from_bytes(&b).ok())
+    else {
+        return false;
+    };
+    let Some(sig) = b64()
+        .decode(signature_b64)
+        .ok()
+        .and_then(|b| <[u8; 64]>::try_from(b).ok())
+        .map(|b| Signature::from_bytes(&b))
+    else {
+        return false;
+    };
+    let mut msg = Vec::with_capacity(COMMIT_SIG_DOMAIN.len() + data.len());
+    msg.extend_from_slice(COMMIT_SIG_DOMAIN);
+    msg.extend_from_slice(data);
+    key.verify_strict(&msg, &sig).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device_key(byte: u8) -> DeviceKey {
+        let mk = crate::master_key::MasterKey::from_bytes([byte; 32]);
+        DeviceKey::derive(&mk, "device-signing-test").unwrap()
+    }
+
+    #[test]
+    fn signature_verifies_and_binds_data_and_key() {
+        let signer = DeviceSigner::new(&device_key(1));
+        let pk = signer.public_key_b64();
+        let sig = signer.sign_commit(b"commit fields");
+
+        assert!(verify_commit_signature(&pk, b"commit fields", &sig));
+        assert!(!verify_commit_signature(&pk, b"commit fieldz", &sig));
+
+        let other = DeviceSigner::new(&device_key(2)).public_key_b64();
+        assert!(!verify_commit_signature(&other, b"commit fields", &sig));
+        assert!(!verify_commit_signature("not base64!", b"commit fields", &sig));
+        assert!(!verify_commit_signature(&pk, b"commit fields", "AAAA"));
+    }
+
+    #[test]
+    fn signer_is_deterministic_per_device() {
+        assert_eq!(
+            DeviceSigner::new(&device_key(3)).public_key_b64(),
+            DeviceSigner::new(&device_key(3)).public_key_b64()
+        );
+    }
+}
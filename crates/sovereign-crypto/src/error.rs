@@ -38,6 +38,15 @@ pub enum CryptoError {
     #[error("Pair payload error: {0}")]
     PairPayload(String),
 
+    #[error("Signature error: {0}")]
+    Signature(String),
+
+    #[error("This store requires a security key to unlock")]
+    SecondFactorRequired,
+
+    #[error("Security key error: {0}")]
+    SecondFactor(String),
+
     #[cfg(feature = "guardian")]
     #[error("Shamir reconstruction failed: need at least {threshold} shards, got {got}")]
     InsufficientShards { threshold: u8, got: usize },
@@ -45,6 +54,10 @@ pub enum CryptoError {
     #[cfg(feature = "guardian")]
     #[error("Recovery error: {0}")]
     RecoveryError(String),
+
+    #[cfg(feature = "recovery-phrase")]
+    #[error("Invalid recovery phrase: {0}")]
+    RecoveryPhrase(String),
 }
 
 pub type CryptoResult<T> = Result<T, CryptoError>;
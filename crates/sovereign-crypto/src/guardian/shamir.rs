@@ -1,4 +1,5 @@
 use blahaj::{Share, Sharks};
+use serde::{Deserialize, Serialize};
 
 use crate::error::{CryptoError, CryptoResult};
 use crate::master_key::MasterKey;
@@ -8,6 +9,83 @@ pub const DEFAULT_TOTAL_SHARES: usize = 5;
 /// Default threshold for reconstruction.
 pub const DEFAULT_THRESHOLD: u8 = 3;
 
+/// How a secret is split across the guardians: `shares` shares, any
+/// `threshold` of which reconstruct. Chosen at enrollment and persisted, so
+/// a later shard refresh splits the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuardianPolicy {
+    pub threshold: u8,
+    pub shares: u8,
+}
+
+impl Default for GuardianPolicy {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_THRESHOLD,
+            shares: DEFAULT_TOTAL_SHARES as u8,
+        }
+    }
+}
+
+impl GuardianPolicy {
+    /// A validated policy: `2 <= threshold <= shares`.
+    pub fn new(threshold: u8, shares: u8) -> CryptoResult<Self> {
+        if threshold < 2 {
+            return Err(CryptoError::RecoveryError("threshold must be >= 2".into()));
+        }
+        if shares < threshold {
+            return Err(CryptoError::RecoveryError(format!(
+                "total ({shares}) must be >= threshold ({threshold})"
+            )));
+        }
+        Ok(Self { threshold, shares })
+    }
+
+    /// Majority of `guardians`, at least 2: 2 → 2-of-2, 3 → 2-of-3,
+    /// 5 → 3-of-5. Used when no policy was chosen at enrollment.
+    pub fn majority(guardians: usize) -> Self {
+        let shares = guardians.min(u8::MAX as usize) as u8;
+        Self {
+            threshold: (shares / 2 + 1).max(2).min(shares),
+            shares,
+        }
+    }
+
+    /// This policy applied to the `available` guardians: the share count
+    /// shrinks to what is available (e.g. after a revocation), the
+    /// threshold never does.
+    pub fn fit(&self, available: usize) -> CryptoResult<Self> {
+        let shares = self.shares.min(available.min(u8::MAX as usize) as u8);
+        if shares < self.threshold {
+            return Err(CryptoError::InsufficientShards {
+                threshold: self.threshold,
+                got: available,
+            });
+        }
+        Ok(Self {
+            threshold: self.threshold,
+            shares,
+        })
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> CryptoResult<()> {
+        let json =
+            serde_json::to_vec(self).map_err(|e| CryptoError::Serialization(e.to_string()))?;
+        crate::fs_private::write_private(path, json).map_err(|e| CryptoError::KeyDbIo(e.to_string()))
+    }
+
+    /// The persisted policy, or `None` when none was chosen yet.
+    pub fn load(path: &std::path::Path) -> CryptoResult<Option<Self>> {
+        match std::fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| CryptoError::Serialization(e.to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(CryptoError::KeyDbIo(e.to_string())),
+        }
+    }
+}
+
 /// Split a master key into Shamir shares.
 ///
 /// Returns `total` shares; any `threshold` of them can reconstruct the key.
@@ -182,4 +260,32 @@ mod tests {
         let mk = MasterKey::generate();
         assert!(split_master_key(&mk, 4, 3).is_err());
     }
+
+    #[test]
+    fn policy_validation_and_majority() {
+        assert!(GuardianPolicy::new(1, 3).is_err());
+        assert!(GuardianPolicy::new(4, 3).is_err());
+        assert_eq!(GuardianPolicy::new(2, 4).unwrap().shares, 4);
+        assert_eq!(GuardianPolicy::majority(2), GuardianPolicy::new(2, 2).unwrap());
+        assert_eq!(GuardianPolicy::majority(3), GuardianPolicy::new(2, 3).unwrap());
+        assert_eq!(GuardianPolicy::majority(5), GuardianPolicy::default());
+    }
+
+    #[test]
+    fn policy_fit_shrinks_shares_not_threshold() {
+        let policy = GuardianPolicy::new(3, 5).unwrap();
+        assert_eq!(policy.fit(7).unwrap(), policy);
+        assert_eq!(policy.fit(4).unwrap(), GuardianPolicy::new(3, 4).unwrap());
+        assert!(policy.fit(2).is_err());
+    }
+
+    #[test]
+    fn policy_save_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("guardian_policy.json");
+        assert!(GuardianPolicy::load(&path).unwrap().is_none());
+        let policy = GuardianPolicy::new(2, 4).unwrap();
+        policy.save(&path).unwrap();
+        assert_eq!(GuardianPolicy::load(&path).unwrap(), Some(policy));
+    }
 }
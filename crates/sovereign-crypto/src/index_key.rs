@@ -63,6 +63,12 @@ impl IndexKey {
         base64::engine::general_purpose::STANDARD.encode(result)
     }
 
+    /// Blind-index hash of a tag name (see [`tags`]). Tags hash in their own
+    /// namespace, so `#rust` and the word "rust" never match each other.
+    pub fn hash_tag(&self, tag: &str) -> String {
+        self.hash_token(format!("tag:{tag}").as_bytes())
+    }
+
     /// Load + decrypt an IndexKey file. File format mirrors KeyDatabase:
     /// `nonce (24 bytes) || ciphertext(JSON{WrappedDocumentKey})`.
     /// The outer layer is encrypted by DeviceKey; the inner WrappedDocumentKey
@@ -154,6 +160,12 @@ pub fn tokenize(text: &str, max_tokens: usize) -> Vec<String> {
     out
 }
 
+/// Extract `#tag` markers from plaintext for the tag blind index, by the
+/// rule of [`sovereign_core::content::hashtags`].
+pub fn tags(text: &str, max_tags: usize) -> Vec<String> {
+    sovereign_core::content::hashtags(text, max_tags)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -251,4 +263,15 @@ mod tests {
         let toks = tokenize(&text, 10);
         assert_eq!(toks.len(), 10);
     }
+
+    #[test]
+    fn tags_extracted_and_namespaced() {
+        let found = tags("# Heading\nPlan for #Q3 and #road-map, not a#tag. #q3 again", 10);
+        assert_eq!(found, vec!["q3".to_string(), "road-map".to_string()]);
+        assert_eq!(tags("#a #b #c", 2).len(), 2);
+
+        let k = IndexKey::generate();
+        assert_ne!(k.hash_tag("rust"), k.hash_token(b"rust"));
+        assert_eq!(k.hash_tag("rust"), k.hash_tag("rust"));
+    }
 }
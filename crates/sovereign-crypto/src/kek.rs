@@ -42,6 +42,7 @@ impl Kek {
     /// Unwrap a stored KEK using a DeviceKey.
     pub fn unwrap(wrapped: &WrappedKek, device_key: &DeviceKey) -> CryptoResult<Self> {
         let bytes_vec = aead::decrypt(&wrapped.ciphertext, &wrapped.nonce, device_key.as_bytes())?;
+        crate::key_audit::record(crate::key_audit::KeyEvent::KekUnwrap);
         let mut bytes = [0u8; KEY_SIZE];
         bytes.copy_from_slice(&bytes_vec);
         Ok(Self { bytes })
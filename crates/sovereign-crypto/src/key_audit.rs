@@ -0,0 +1,647 @@
+//! Tamper-evident log of key usage.
+//!
+//! Every KEK unwrap and every document-key unwrap from a [`KeyDatabase`]
+//! is recorded to an append-only JSONL file. Each entry carries an
+//! HMAC-SHA256 over the previous entry's MAC and its own fields, so editing,
+//! dropping or reordering lines breaks the chain at that point; without the
+//! audit key an attacker can't re-forge the tail.
+//!
+//! Recording goes through a process-wide sink. Events raised before the
+//! sink is installed (the KEK unwrap at login happens before the audit key
+//! exists) are buffered and written on [`install`]. Repeated unwraps of the
+//! same document within [`REPEAT_WINDOW_SECS`] collapse into one entry —
+//! a canvas refresh would otherwise log every document each time.
+//!
+//! [`detect_anomalies`] scans the entries for bulk decryption during quiet
+//! hours; the orchestrator runs it periodically and raises an alert.
+//! Maintenance rotates a large log with [`rotate_installed`]; the archive
+//! keeps its own chain and the new log's first entry links to it. A KEK
+//! rotation changes the audit key, so it re-chains the log and its
+//! archives under the new one with [`rekey`].
+//!
+//! [`KeyDatabase`]: crate::key_db::KeyDatabase
+
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use chrono::{DateTime, Duration, TimeZone, Timelike, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::error::{CryptoError, CryptoResult};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// MAC the first entry chains from.
+const GENESIS: &str = "genesis";
+
+/// Repeat unwraps of one document inside this window are logged once.
+pub const REPEAT_WINDOW_SECS: i64 = 60;
+
+/// Events held in memory before the sink is installed.
+const PENDING_CAP: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum KeyEvent {
+    KekUnwrap,
+    DocumentKeyUnwrap { doc_id: String },
+    /// First entry of a log continued from an archived one: names the
+    /// archive and carries its last MAC, so the two chains stay linked.
+    LogRotated { archive: String, tail_mac: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub at: DateTime<Utc>,
+    pub event: KeyEvent,
+    /// Hex HMAC of the previous entry (or `"genesis"`).
+    pub prev: String,
+    /// Hex HMAC over `prev` and this entry's fields.
+    pub mac: String,
+}
+
+/// The log's MAC key, derived from the KEK so only an unlocked session
+/// can extend or check the chain.
+pub fn audit_key(kek: &crate::kek::Kek) -> Zeroizing<[u8; 32]> {
+    let hk = hkdf::Hkdf::<Sha256>::new(None, kek.as_bytes());
+    let mut key = Zeroizing::new([0u8; 32]);
+    hk.expand(b"sovereign-key-audit", key.as_mut())
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    key
+}
+
+fn entry_mac(key: &[u8; 32], seq: u64, at: &DateTime<Utc>, event: &KeyEvent, prev: &str) -> String {
+    let mut mac = <HmacSha256 as Mac>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(prev.as_bytes());
+    mac.update(&seq.to_le_bytes());
+    mac.update(at.to_rfc3339().as_bytes());
+    mac.update(&serde_json::to_vec(event).unwrap_or_default());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// An open audit log, positioned at the end of its chain.
+pub struct KeyAuditLog {
+    path: PathBuf,
+    key: Zeroizing<[u8; 32]>,
+    next_seq: u64,
+    prev: String,
+}
+
+impl KeyAuditLog {
+    /// Open (or create) the log. The existing chain is verified first, so a
+    /// tampered log is reported instead of being silently extended.
+    pub fn open(path: &Path, key: [u8; 32]) -> CryptoResult<Self> {
+        let entries = if path.exists() { read_verified(path, &key)? } else { Vec::new() };
+        let (next_seq, prev) = match entries.last() {
+            Some(last) => (last.seq + 1, last.mac.clone()),
+            None => (0, GENESIS.to_string()),
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            key: Zeroizing::new(key),
+            next_seq,
+            prev,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&mut self, event: KeyEvent, at: DateTime<Utc>) -> CryptoResult<()> {
+        let mac = entry_mac(&self.key, self.next_seq, &at, &event, &self.prev);
+        let entry = AuditEntry {
+            seq: self.next_seq,
+            at,
+            event,
+            prev: std::mem::replace(&mut self.prev, mac.clone()),
+            mac,
+        };
+        let mut line = serde_json::to_string(&entry).map_err(|e| CryptoError::Serialization(e.to_string()))?;
+        line.push('\n');
+
+        let mut options = std::fs::OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options
+            .open(&self.path)
+            .and_then(|mut f| f.write_all(line.as_bytes()))
+            .map_err(|e| CryptoError::KeyDbIo(e.to_string()))?;
+        self.next_seq += 1;
+        Ok(())
+    }
+
+    /// Size of the log file in bytes (0 if not yet written).
+    pub fn len_bytes(&self) -> u64 {
+        std::fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0)
+    }
+
+    /// Move the log aside as `<stem>.<timestamp>.jsonl` and start a fresh
+    /// chain whose first entry links back to the archive's tail. The chain
+    /// is verified first, so a tampered log is never archived as good.
+    /// Returns the archive's path.
+    pub fn rotate(&mut self, at: DateTime<Utc>) -> CryptoResult<PathBuf> {
+        let tail_mac = read_verified(&self.path, &self.key)?
+            .last()
+            .map_or_else(|| GENESIS.to_string(), |e| e.mac.clone());
+        let stem = self
+            .path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("key_audit");
+        let archive = self
+            .path
+            .with_file_name(format!("{stem}.{}.jsonl", at.format("%Y%m%dT%H%M%S")));
+        if archive.exists() {
+            return Err(CryptoError::KeyDbIo(format!("{} already exists", archive.display())));
+        }
+        std::fs::rename(&self.path, &archive).map_err(|e| CryptoError::KeyDbIo(e.to_string()))?;
+        self.next_seq = 0;
+        self.prev = GENESIS.to_string();
+        let name = archive
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.append(KeyEvent::LogRotated { archive: name, tail_mac }, at)?;
+        Ok(archive)
+    }
+
+    /// Verified entries recorded at or after `since`.
+    pub fn entries_since(&self, since: DateTime<Utc>) -> CryptoResult<Vec<AuditEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(read_verified(&self.path, &self.key)?
+            .into_iter()
+            .filter(|e| e.at >= since)
+            .collect())
+    }
+}
+
+/// Read and verify the whole chain. Fails at the first broken link.
+pub fn read_verified(path: &Path, key: &[u8; 32]) -> CryptoResult<Vec<AuditEntry>> {
+    let file = std::fs::File::open(path).map_err(|e| CryptoError::KeyDbIo(e.to_string()))?;
+    let mut prev = GENESIS.to_string();
+    let mut entries = Vec::new();
+    for (i, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| CryptoError::KeyDbIo(e.to_string()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: AuditEntry =
+            serde_json::from_str(&line).map_err(|e| CryptoError::Serialization(e.to_string()))?;
+        let expected = entry_mac(key, entry.seq, &entry.at, &entry.event, &prev);
+        if entry.seq != entries.len() as u64 || entry.prev != prev || entry.mac != expected {
+            return Err(CryptoError::Serialization(format!(
+                "key audit chain broken at line {}",
+                i + 1
+            )));
+        }
+        prev = entry.mac.clone();
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Rewrite one chain under `key`, as read from `entries`. Returns the new
+/// tail MAC. `tails` maps archive names to their re-chained tails, so a
+/// `LogRotated` entry keeps pointing at its archive.
+fn rewrite_chain(
+    path: &Path,
+    entries: Vec<AuditEntry>,
+    key: &[u8; 32],
+    tails: &HashMap<String, String>,
+) -> CryptoResult<String> {
+    let mut prev = GENESIS.to_string();
+    let mut out = String::new();
+    for mut entry in entries {
+        if let KeyEvent::LogRotated { archive, tail_mac } = &mut entry.event {
+            if let Some(tail) = tails.get(archive.as_str()) {
+                *tail_mac = tail.clone();
+            }
+        }
+        entry.mac = entry_mac(key, entry.seq, &entry.at, &entry.event, &prev);
+        entry.prev = std::mem::replace(&mut prev, entry.mac.clone());
+        out.push_str(&serde_json::to_string(&entry).map_err(|e| CryptoError::Serialization(e.to_string()))?);
+        out.push('\n');
+    }
+    crate::fs_private::write_private(path, out).map_err(|e| CryptoError::KeyDbIo(e.to_string()))?;
+    Ok(prev)
+}
+
+/// The archives [`KeyAuditLog::rotate`] left beside `path`, oldest first.
+fn archives_of(path: &Path) -> Vec<PathBuf> {
+    let (Some(dir), Some(stem)) = (path.parent(), path.file_stem().and_then(|s| s.to_str())) else {
+        return Vec::new();
+    };
+    let mut found: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            // `<stem>.<%Y%m%dT%H%M%S>.jsonl` — not another persona's log.
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix(stem)?.strip_prefix('.')?.strip_suffix(".jsonl"))
+                .is_some_and(|ts| ts.len() == 15 && ts.bytes().all(|b| b.is_ascii_digit() || b == b'T'))
+        })
+        .collect();
+    found.sort();
+    found
+}
+
+/// Re-chain the log at `path` and its archives from `old` to `new` (a KEK
+/// rotation). A chain already under `new` — an interrupted earlier pass —
+/// is left as is. If the log is the installed one, the sink continues
+/// under `new`. A chain valid under neither key is reported, not touched.
+pub fn rekey(path: &Path, old: &[u8; 32], new: &[u8; 32]) -> CryptoResult<()> {
+    with_sink(|sink| {
+        let installed = sink.log.as_ref().is_some_and(|log| log.path == path);
+        let mut tails = HashMap::new();
+        let mut files = archives_of(path);
+        files.push(path.to_path_buf());
+        for file in files.iter().filter(|f| f.exists()) {
+            let tail = match read_verified(file, new) {
+                Ok(entries) => entries.last().map_or_else(|| GENESIS.to_string(), |e| e.mac.clone()),
+                Err(_) => rewrite_chain(file, read_verified(file, old)?, new, &tails)?,
+            };
+            if let Some(name) = file.file_name().and_then(|n| n.to_str()) {
+                tails.insert(name.to_string(), tail);
+            }
+        }
+        if installed {
+            sink.log = Some(KeyAuditLog::open(path, *new)?);
+        }
+        Ok(())
+    })
+}
+
+// ── Process-wide sink ──────────────────────────────────────────────
+
+struct Sink {
+    log: Option<KeyAuditLog>,
+    pending: Vec<(KeyEvent, DateTime<Utc>)>,
+    last_seen: HashMap<String, DateTime<Utc>>,
+}
+
+static SINK: LazyLock<Mutex<Sink>> = LazyLock::new(|| {
+    Mutex::new(Sink {
+        log: None,
+        pending: Vec::new(),
+        last_seen: HashMap::new(),
+    })
+});
+
+fn with_sink<T>(f: impl FnOnce(&mut Sink) -> T) -> T {
+    let mut guard = SINK.lock().unwrap_or_else(|p| p.into_inner());
+    f(&mut guard)
+}
+
+/// Start writing to `path`, flushing anything recorded before now.
+pub fn install(path: &Path, key: [u8; 32]) -> CryptoResult<()> {
+    let mut log = KeyAuditLog::open(path, key)?;
+    with_sink(|sink| {
+        for (event, at) in sink.pending.drain(..) {
+            let _ = log.append(event, at);
+        }
+        sink.log = Some(log);
+    });
+    Ok(())
+}
+
+/// Stop writing (session lock). Later events are buffered again.
+pub fn uninstall() {
+    with_sink(|sink| {
+        sink.log = None;
+        sink.last_seen.clear();
+    });
+}
+
+/// Verified entries since `since` from the installed log; empty when no log
+/// is installed.
+pub fn recent_entries(since: DateTime<Utc>) -> CryptoResult<Vec<AuditEntry>> {
+    with_sink(|sink| match &sink.log {
+        Some(log) => log.entries_since(since),
+        None => Ok(Vec::new()),
+    })
+}
+
+/// Rotate the installed log once it has grown past `max_bytes`. Returns
+/// the archive's path, or `None` when no log is installed or it is still
+/// small enough.
+pub fn rotate_installed(max_bytes: u64) -> CryptoResult<Option<PathBuf>> {
+    with_sink(|sink| match sink.log.as_mut() {
+        Some(log) if log.len_bytes() > max_bytes => log.rotate(Utc::now()).map(Some),
+        _ => Ok(None),
+    })
+}
+
+/// Record a key use. Never fails the caller: a write error is dropped
+/// rather than blocking decryption.
+pub fn record(event: KeyEvent) {
+    let now = Utc::now();
+    with_sink(|sink| {
+        if let KeyEvent::DocumentKeyUnwrap { doc_id } = &event {
+            if let Some(last) = sink.last_seen.get(doc_id) {
+                if now - *last < Duration::seconds(REPEAT_WINDOW_SECS) {
+                    return;
+                }
+            }
+            if sink.last_seen.len() > 10_000 {
+                sink.last_seen
+                    .retain(|_, t| now - *t < Duration::seconds(REPEAT_WINDOW_SECS));
+            }
+            sink.last_seen.insert(doc_id.clone(), now);
+        }
+        match sink.log.as_mut() {
+            Some(log) => {
+                let _ = log.append(event, now);
+            }
+            None if sink.pending.len() < PENDING_CAP => sink.pending.push((event, now)),
+            None => {}
+        }
+    });
+}
+
+// ── Anomaly detection ──────────────────────────────────────────────
+
+/// What counts as suspicious key usage.
+#[derive(Debug, Clone)]
+pub struct AnomalyPolicy {
+    /// Local hours (0–23) considered quiet, start inclusive, end exclusive.
+    /// The range may wrap midnight (e.g. 23..6).
+    pub quiet_hours: (u32, u32),
+    /// Document-key unwraps inside `window` that count as bulk.
+    pub bulk_threshold: usize,
+    pub window: Duration,
+}
+
+impl Default for AnomalyPolicy {
+    fn default() -> Self {
+        Self {
+            quiet_hours: (0, 6),
+            bulk_threshold: 25,
+            window: Duration::minutes(10),
+        }
+    }
+}
+
+impl AnomalyPolicy {
+    fn is_quiet(&self, hour: u32) -> bool {
+        let (start, end) = self.quiet_hours;
+        if start <= end {
+            (start..end).contains(&hour)
+        } else {
+            hour >= start || hour < end
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KeyAnomaly {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub document_keys: usize,
+}
+
+impl KeyAnomaly {
+    pub fn describe<Tz: TimeZone>(&self, tz: &Tz) -> String
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        format!(
+            "{} document keys were unwrapped between {} and {} — outside your usual hours. \
+             If this wasn't you, lock the app and rotate your keys.",
+            self.document_keys,
+            self.started_at.with_timezone(tz).format("%Y-%m-%d %H:%M"),
+            self.ended_at.with_timezone(tz).format("%H:%M"),
+        )
+    }
+}
+
+/// Bursts of at least `bulk_threshold` document-key unwraps within
+/// `window`, starting in quiet hours (local to `tz`). Overlapping bursts
+/// merge into one anomaly.
+pub fn detect_anomalies<Tz: TimeZone>(
+    entries: &[AuditEntry],
+    policy: &AnomalyPolicy,
+    tz: &Tz,
+) -> Vec<KeyAnomaly> {
+    let times: Vec<DateTime<Utc>> = entries
+        .iter()
+        .filter(|e| matches!(e.event, KeyEvent::DocumentKeyUnwrap { .. }))
+        .map(|e| e.at)
+        .collect();
+
+    // (first, last) indices into `times` of each burst found so far.
+    let mut bursts: Vec<(usize, usize)> = Vec::new();
+    let mut end = 0;
+    for start in 0..times.len() {
+        if !policy.is_quiet(times[start].with_timezone(tz).hour()) {
+            continue;
+        }
+        end = end.max(start);
+        while end + 1 < times.len() && times[end + 1] - times[start] <= policy.window {
+            end += 1;
+        }
+        if end + 1 - start < policy.bulk_threshold {
+            continue;
+        }
+        match bursts.last_mut() {
+            Some(last) if last.1 >= start => last.1 = end,
+            _ => bursts.push((start, end)),
+        }
+    }
+    bursts
+        .into_iter()
+        .map(|(first, last)| KeyAnomaly {
+            started_at: times[first],
+            ended_at: times[last],
+            document_keys: last + 1 - first,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    const KEY: [u8; 32] = [7u8; 32];
+
+    fn doc(i: usize) -> KeyEvent {
+        KeyEvent::DocumentKeyUnwrap { doc_id: format!("document:{i}") }
+    }
+
+    #[test]
+    fn chain_verifies_and_resumes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key_audit.jsonl");
+        let t = Utc::now();
+
+        let mut log = KeyAuditLog::open(&path, KEY).unwrap();
+        log.append(KeyEvent::KekUnwrap, t).unwrap();
+        log.append(doc(1), t).unwrap();
+        drop(log);
+
+        let mut log = KeyAuditLog::open(&path, KEY).unwrap();
+        log.append(doc(2), t).unwrap();
+        let entries = read_verified(&path, &KEY).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[2].seq, 2);
+        assert_eq!(entries[0].event, KeyEvent::KekUnwrap);
+    }
+
+    #[test]
+    fn tampering_is_detected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key_audit.jsonl");
+        let mut log = KeyAuditLog::open(&path, KEY).unwrap();
+        for i in 0..3 {
+            log.append(doc(i), Utc::now()).unwrap();
+        }
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        // Dropped line.
+        std::fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        assert!(read_verified(&path, &KEY).is_err());
+
+        // Edited field.
+        std::fs::write(&path, text.replace("document:1", "document:9")).unwrap();
+        assert!(read_verified(&path, &KEY).is_err());
+        assert!(KeyAuditLog::open(&path, KEY).is_err());
+
+        // Wrong key.
+        std::fs::write(&path, &text).unwrap();
+        assert!(read_verified(&path, &[8u8; 32]).is_err());
+        assert!(read_verified(&path, &KEY).is_ok());
+    }
+
+    #[test]
+    fn rotation_archives_and_links_chains() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key_audit.jsonl");
+        let mut log = KeyAuditLog::open(&path, KEY).unwrap();
+        log.append(KeyEvent::KekUnwrap, Utc::now()).unwrap();
+        log.append(doc(1), Utc::now()).unwrap();
+        let tail = read_verified(&path, &KEY).unwrap()[1].mac.clone();
+
+        let archive = log.rotate(Utc::now()).unwrap();
+        assert_eq!(read_verified(&archive, &KEY).unwrap().len(), 2);
+        log.append(doc(2), Utc::now()).unwrap();
+        let entries = read_verified(&path, &KEY).unwrap();
+        assert_eq!(entries.len(), 2);
+        let KeyEvent::LogRotated { archive: name, tail_mac } = &entries[0].event else {
+            panic!("first entry after rotation is not LogRotated");
+        };
+        assert_eq!(tail_mac, &tail);
+        assert_eq!(archive.file_name().unwrap().to_str().unwrap(), name);
+
+        // Reopening continues the new chain.
+        let mut log = KeyAuditLog::open(&path, KEY).unwrap();
+        log.append(doc(3), Utc::now()).unwrap();
+        assert_eq!(read_verified(&path, &KEY).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn rekeyed_log_opens_under_the_new_key() {
+        const NEW: [u8; 32] = [9u8; 32];
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key_audit.jsonl");
+        let mut log = KeyAuditLog::open(&path, KEY).unwrap();
+        log.append(KeyEvent::KekUnwrap, Utc::now()).unwrap();
+        let archive = log.rotate(Utc::now()).unwrap();
+        log.append(doc(1), Utc::now()).unwrap();
+        drop(log);
+        // Another persona's log beside it is not an archive of this one.
+        let duress = dir.path().join("key_audit.duress.jsonl");
+        KeyAuditLog::open(&duress, KEY).unwrap().append(doc(2), Utc::now()).unwrap();
+
+        rekey(&path, &KEY, &NEW).unwrap();
+        assert!(KeyAuditLog::open(&path, KEY).is_err());
+        let mut log = KeyAuditLog::open(&path, NEW).unwrap();
+        log.append(doc(3), Utc::now()).unwrap();
+        let entries = read_verified(&path, &NEW).unwrap();
+        assert_eq!(entries.len(), 3);
+        let archived = read_verified(&archive, &NEW).unwrap();
+        let KeyEvent::LogRotated { tail_mac, .. } = &entries[0].event else {
+            panic!("first entry after rotation is not LogRotated");
+        };
+        assert_eq!(tail_mac, &archived.last().unwrap().mac);
+        assert!(read_verified(&duress, &KEY).is_ok());
+
+        // Running it again (a resumed rotation) changes nothing.
+        let before = std::fs::read_to_string(&path).unwrap();
+        rekey(&path, &KEY, &NEW).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), before);
+    }
+
+    fn entries_at(times: &[DateTime<Utc>]) -> Vec<AuditEntry> {
+        times
+            .iter()
+            .enumerate()
+            .map(|(i, at)| AuditEntry {
+                seq: i as u64,
+                at: *at,
+                event: doc(i),
+                prev: String::new(),
+                mac: String::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bulk_in_quiet_hours_is_flagged() {
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+        let policy = AnomalyPolicy { bulk_threshold: 5, ..Default::default() };
+        // 03:00 local = 01:00 UTC.
+        let night = Utc.with_ymd_and_hms(2026, 3, 1, 1, 0, 0).unwrap();
+        let times: Vec<_> = (0..8).map(|i| night + Duration::seconds(i * 10)).collect();
+
+        let found = detect_anomalies(&entries_at(&times), &policy, &tz);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].document_keys, 8);
+        assert_eq!(found[0].started_at, night);
+        assert!(found[0].describe(&tz).contains("03:00"));
+    }
+
+    #[test]
+    fn daytime_or_sparse_use_is_not_flagged() {
+        let tz = FixedOffset::east_opt(0).unwrap();
+        let policy = AnomalyPolicy { bulk_threshold: 5, ..Default::default() };
+
+        let day = Utc.with_ymd_and_hms(2026, 3, 1, 14, 0, 0).unwrap();
+        let burst: Vec<_> = (0..20).map(|i| day + Duration::seconds(i)).collect();
+        assert!(detect_anomalies(&entries_at(&burst), &policy, &tz).is_empty());
+
+        let night = Utc.with_ymd_and_hms(2026, 3, 1, 2, 0, 0).unwrap();
+        let sparse: Vec<_> = (0..20).map(|i| night + Duration::minutes(i * 5)).collect();
+        assert!(detect_anomalies(&entries_at(&sparse), &policy, &tz).is_empty());
+    }
+
+    #[test]
+    fn quiet_hours_can_wrap_midnight() {
+        let policy = AnomalyPolicy { quiet_hours: (23, 6), ..Default::default() };
+        assert!(policy.is_quiet(23));
+        assert!(policy.is_quiet(2));
+        assert!(!policy.is_quiet(6));
+        assert!(!policy.is_quiet(12));
+    }
+}
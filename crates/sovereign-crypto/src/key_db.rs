@@ -6,6 +6,7 @@ use crate::device_key::DeviceKey;
 use crate::document_key::{DocumentKey, WrappedDocumentKey};
 use crate::error::{CryptoError, CryptoResult};
 use crate::kek::Kek;
+use crate::key_escrow::{EscrowedKey, EscrowedKeyDb};
 
 /// Persistent key database storing wrapped document keys.
 ///
@@ -141,10 +142,130 @@ impl KeyDatabase {
     /// Unwrap the current document key for a document.
     pub fn unwrap_current(&self, doc_id: &str, kek: &Kek) -> CryptoResult<DocumentKey> {
         let wrapped = self.get_current(doc_id)?;
-        DocumentKey::unwrap(wrapped, kek)
+        let key = DocumentKey::unwrap(wrapped, kek)?;
+        record_access(doc_id);
+        Ok(key)
+    }
+
+    /// Unwrap every key held for a document, newest first. Rows written
+    /// before a rotation (and version-history snapshots) stay readable
+    /// under the older epochs.
+    pub fn unwrap_all(&self, doc_id: &str, kek: &Kek) -> CryptoResult<Vec<DocumentKey>> {
+        let keys = self
+            .entries
+            .get(doc_id)
+            .ok_or_else(|| CryptoError::KeyNotFound(doc_id.to_string()))?;
+        let unwrapped = keys
+            .iter()
+            .rev()
+            .map(|wrapped| DocumentKey::unwrap(wrapped, kek))
+            .collect::<CryptoResult<Vec<_>>>()?;
+        record_access(doc_id);
+        Ok(unwrapped)
+    }
+
+    /// IDs of every document with stored keys.
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Rotate a document's key: mint a new key at the next epoch, which
+    /// becomes current. Older keys are kept for decryption.
+    pub fn rotate_key(&mut self, doc_id: &str, kek: &Kek) -> CryptoResult<DocumentKey> {
+        let epoch = self
+            .entries
+            .get(doc_id)
+            .and_then(|keys| keys.iter().map(|k| k.epoch).max())
+            .ok_or_else(|| CryptoError::KeyNotFound(doc_id.to_string()))?
+            + 1;
+        self.create_document_key(doc_id, kek, epoch)
+    }
+
+    /// Re-wrap every key (all epochs) from `old_kek` to `new_kek`, for KEK
+    /// rotation. Keys that already unwrap under `new_kek` are left as they
+    /// are, so an interrupted rotation can simply be run again. Nothing
+    /// changes unless every key re-wraps. Returns the number re-wrapped.
+    pub fn rewrap_all(&mut self, old_kek: &Kek, new_kek: &Kek) -> CryptoResult<usize> {
+        let mut rewrapped = 0;
+        let mut entries = HashMap::with_capacity(self.entries.len());
+        for (doc_id, keys) in &self.entries {
+            let mut out = Vec::with_capacity(keys.len());
+            for wrapped in keys {
+                match DocumentKey::unwrap(wrapped, old_kek) {
+                    Ok(key) => {
+                        out.push(key.wrap(new_kek, wrapped.epoch)?);
+                        rewrapped += 1;
+                    }
+                    Err(_) => {
+                        DocumentKey::unwrap(wrapped, new_kek)?;
+                        out.push(wrapped.clone());
+                    }
+                }
+            }
+            entries.insert(doc_id.clone(), out);
+        }
+        self.entries = entries;
+        Ok(rewrapped)
+    }
+
+    /// Unwrap every key (all epochs) for escrow to another device.
+    pub fn export_keys(&self, kek: &Kek) -> CryptoResult<EscrowedKeyDb> {
+        let mut out = EscrowedKeyDb::with_capacity(self.entries.len());
+        for (doc_id, keys) in &self.entries {
+            let mut unwrapped = Vec::with_capacity(keys.len());
+            for wrapped in keys {
+                let key = DocumentKey::unwrap(wrapped, kek)?;
+                unwrapped.push(EscrowedKey {
+                    epoch: wrapped.epoch,
+                    key: *key.as_bytes(),
+                });
+            }
+            record_access(doc_id);
+            out.insert(doc_id.clone(), unwrapped);
+        }
+        Ok(out)
+    }
+
+    /// Take in keys escrowed by another device, wrapped under our `kek`.
+    /// An entity we have no key for adopts the sender's keys as they are.
+    /// One we already have keeps its current key; the sender's go in as
+    /// older keys, which decryption falls back to. Keys already held are
+    /// skipped, so importing twice is harmless. Returns the number added.
+    pub fn import_keys(&mut self, keys: &EscrowedKeyDb, kek: &Kek) -> CryptoResult<usize> {
+        let mut added = 0;
+        for (doc_id, escrowed) in keys {
+            let mut held: Vec<[u8; crate::aead::KEY_SIZE]> = match self.entries.get(doc_id) {
+                Some(existing) => existing
+                    .iter()
+                    .map(|w| DocumentKey::unwrap(w, kek).map(|k| *k.as_bytes()))
+                    .collect::<CryptoResult<_>>()?,
+                None => Vec::new(),
+            };
+            let mut fresh = Vec::new();
+            for e in escrowed {
+                if !held.contains(&e.key) {
+                    fresh.push(DocumentKey::from_bytes(e.key).wrap(kek, e.epoch)?);
+                }
+            }
+            zeroize::Zeroize::zeroize(&mut held);
+            if fresh.is_empty() {
+                continue;
+            }
+            added += fresh.len();
+            let entry = self.entries.entry(doc_id.clone()).or_default();
+            // Ahead of ours: the last entry stays the current key.
+            entry.splice(0..0, fresh);
+        }
+        Ok(added)
     }
 }
 
+fn record_access(doc_id: &str) {
+    crate::key_audit::record(crate::key_audit::KeyEvent::DocumentKeyUnwrap {
+        doc_id: doc_id.to_string(),
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,6 +354,72 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn rotate_key_keeps_old_epochs() {
+        let (_, kek) = test_keys();
+        let mut db = KeyDatabase::new(scratch_path("test-keys.db"));
+        let old = db.create_document_key("doc:1", &kek, 1).unwrap();
+        let new = db.rotate_key("doc:1", &kek).unwrap();
+        assert_eq!(db.get_current("doc:1").unwrap().epoch, 2);
+        assert_ne!(old.as_bytes(), new.as_bytes());
+
+        let all = db.unwrap_all("doc:1", &kek).unwrap();
+        assert_eq!(all[0].as_bytes(), new.as_bytes());
+        assert_eq!(all[1].as_bytes(), old.as_bytes());
+        assert!(db.rotate_key("doc:missing", &kek).is_err());
+    }
+
+    #[test]
+    fn rewrap_all_is_resumable() {
+        let (_, old_kek) = test_keys();
+        let new_kek = Kek::generate();
+        let mut db = KeyDatabase::new(scratch_path("test-keys.db"));
+        let k1 = db.create_document_key("doc:1", &old_kek, 1).unwrap();
+        let k2 = db.rotate_key("doc:1", &old_kek).unwrap();
+        let k3 = db.create_document_key("doc:2", &old_kek, 1).unwrap();
+
+        assert_eq!(db.rewrap_all(&old_kek, &new_kek).unwrap(), 3);
+        assert!(db.unwrap_current("doc:1", &old_kek).is_err());
+        let all = db.unwrap_all("doc:1", &new_kek).unwrap();
+        assert_eq!(all[0].as_bytes(), k2.as_bytes());
+        assert_eq!(all[1].as_bytes(), k1.as_bytes());
+        assert_eq!(db.unwrap_current("doc:2", &new_kek).unwrap().as_bytes(), k3.as_bytes());
+
+        // Running it again (an interrupted rotation resumed) is a no-op.
+        assert_eq!(db.rewrap_all(&old_kek, &new_kek).unwrap(), 0);
+
+        // A key under neither KEK aborts without touching anything.
+        db.create_document_key("doc:3", &Kek::generate(), 1).unwrap();
+        assert!(db.rewrap_all(&old_kek, &new_kek).is_err());
+        assert!(db.unwrap_current("doc:2", &new_kek).is_ok());
+    }
+
+    #[test]
+    fn escrowed_keys_import_under_own_kek() {
+        let (_, source_kek) = test_keys();
+        let mut source = KeyDatabase::new(scratch_path("test-keys.db"));
+        let s1 = source.create_document_key("doc:1", &source_kek, 1).unwrap();
+        let s2 = source.rotate_key("doc:1", &source_kek).unwrap();
+        let s3 = source.create_document_key("doc:2", &source_kek, 1).unwrap();
+        let escrow = source.export_keys(&source_kek).unwrap();
+
+        let kek = Kek::generate();
+        let mut db = KeyDatabase::new(scratch_path("test-keys.db"));
+        let own = db.create_document_key("doc:2", &kek, 1).unwrap();
+        assert_eq!(db.import_keys(&escrow, &kek).unwrap(), 3);
+
+        // A new entity adopts the sender's keys, current included.
+        let doc1 = db.unwrap_all("doc:1", &kek).unwrap();
+        assert_eq!(doc1[0].as_bytes(), s2.as_bytes());
+        assert_eq!(doc1[1].as_bytes(), s1.as_bytes());
+        // An existing one keeps its own current key.
+        let doc2 = db.unwrap_all("doc:2", &kek).unwrap();
+        assert_eq!(doc2[0].as_bytes(), own.as_bytes());
+        assert_eq!(doc2[1].as_bytes(), s3.as_bytes());
+
+        assert_eq!(db.import_keys(&escrow, &kek).unwrap(), 0, "idempotent");
+    }
+
     #[test]
     fn len_and_contains() {
         let (_, kek) = test_keys();
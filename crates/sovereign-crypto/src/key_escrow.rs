@@ -0,0 +1,205 @@
+//! Document-key escrow between paired devices.
+//!
+//! Every device keeps its own [`KeyDatabase`](crate::key_db::KeyDatabase)s,
+//! wrapped under its own KEK. Pairing shares the AccountKey but not those
+//! keys, so content encrypted on one device (an encrypted backup, an
+//! export) can't be opened on another. After the user confirms on the
+//! existing device, it unwraps its key databases into a
+//! [`KeyEscrowBundle`] and seals that to the new device's identity key;
+//! the new device opens it and re-wraps every key under its own KEK.
+//!
+//! Sealing is ECIES over the devices' Ed25519 identities (the keys behind
+//! their libp2p PeerIds), converted to X25519:
+//!
+//! - a fresh ephemeral X25519 key per envelope,
+//! - `HKDF-SHA256(shared secret, salt = ephemeral ‖ recipient,
+//!   info = label ‖ context)` → a one-off XChaCha20-Poly1305 key,
+//! - `context` binds the sender and recipient peer ids, so an envelope
+//!   can't be replayed by another device or opened as meant for another.
+//!
+//! Only the recipient's identity seed opens an envelope; a paired peer
+//! relaying it learns nothing.
+
+use std::collections::{BTreeMap, HashMap};
+
+use hkdf::Hkdf;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::aead::{self, KEY_SIZE, NONCE_SIZE};
+use crate::error::{CryptoError, CryptoResult};
+
+const ESCROW_VERSION: u8 = 1;
+const ESCROW_INFO: &[u8] = b"sovereign-key-escrow-v1";
+
+/// One unwrapped key of an entity, with its rotation epoch.
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct EscrowedKey {
+    pub epoch: u32,
+    pub key: [u8; KEY_SIZE],
+}
+
+/// The unwrapped contents of one key database: entity id → keys, oldest
+/// first.
+pub type EscrowedKeyDb = HashMap<String, Vec<EscrowedKey>>;
+
+/// The unwrapped contents of a device's key databases, by database name
+/// (`documents`, `messages`, …). Keys are zeroized on drop.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct KeyEscrowBundle {
+    pub dbs: BTreeMap<String, EscrowedKeyDb>,
+}
+
+impl KeyEscrowBundle {
+    /// Total keys across every database.
+    pub fn key_count(&self) -> usize {
+        self.dbs.values().flat_map(|db| db.values()).map(Vec::len).sum()
+    }
+}
+
+// Manual Debug: never print key bytes.
+impl std::fmt::Debug for KeyEscrowBundle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyEscrowBundle")
+            .field("dbs", &self.dbs.keys().collect::<Vec<_>>())
+            .field("keys", &self.key_count())
+            .finish()
+    }
+}
+
+/// A [`KeyEscrowBundle`] sealed to one device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyEscrowEnvelope {
+    pub schema_version: u8,
+    /// The sender's ephemeral X25519 public key.
+    pub ephemeral_public: [u8; 32],
+    pub ciphertext: Vec<u8>,
+    pub nonce: [u8; NONCE_SIZE],
+}
+
+/// Seal `bundle` to the device whose Ed25519 identity public key is
+/// `recipient_public`. `context` must match what the recipient passes to
+/// [`open_bundle`] (the app uses sender and recipient peer ids).
+pub fn seal_bundle(
+    bundle: &KeyEscrowBundle,
+    recipient_public: &[u8; 32],
+    context: &[u8],
+) -> CryptoResult<KeyEscrowEnvelope> {
+    let recipient = x25519_dalek::PublicKey::from(
+        ed25519_dalek::VerifyingKey::from_bytes(recipient_public)
+            .map_err(|e| CryptoError::Signature(format!("bad recipient key: {e}")))?
+            .to_montgomery()
+            .to_bytes(),
+    );
+    // A fresh secret per envelope, used once; `StaticSecret` only because
+    // it can be built from our own RNG's bytes.
+    let mut seed = [0u8; 32];
+    rand::rng().fill_bytes(&mut seed);
+    let ephemeral = x25519_dalek::StaticSecret::from(seed);
+    seed.zeroize();
+    let ephemeral_public = x25519_dalek::PublicKey::from(&ephemeral).to_bytes();
+    let shared = ephemeral.diffie_hellman(&recipient);
+    if !shared.was_contributory() {
+        return Err(CryptoError::DerivationFailed("low-order recipient key".into()));
+    }
+    let key = envelope_key(shared.as_bytes(), &ephemeral_public, recipient.as_bytes(), context)?;
+
+    let mut plaintext =
+        serde_json::to_vec(bundle).map_err(|e| CryptoError::Serialization(e.to_string()))?;
+    let sealed = aead::encrypt(&plaintext, &key);
+    plaintext.zeroize();
+    let (ciphertext, nonce) = sealed?;
+    Ok(KeyEscrowEnvelope {
+        schema_version: ESCROW_VERSION,
+        ephemeral_public,
+        ciphertext,
+        nonce,
+    })
+}
+
+/// Open an envelope sealed to this device, given its Ed25519 identity
+/// seed. Fails on a wrong recipient, a different `context`, or tampering.
+pub fn open_bundle(
+    envelope: &KeyEscrowEnvelope,
+    own_seed: &[u8; 32],
+    context: &[u8],
+) -> CryptoResult<KeyEscrowBundle> {
+    if envelope.schema_version != ESCROW_VERSION {
+        return Err(CryptoError::Serialization(format!(
+            "unsupported key escrow version {}",
+            envelope.schema_version
+        )));
+    }
+    let signing = ed25519_dalek::SigningKey::from_bytes(own_seed);
+    let secret = x25519_dalek::StaticSecret::from(signing.to_scalar_bytes());
+    let own_public = signing.verifying_key().to_montgomery().to_bytes();
+    let shared = secret.diffie_hellman(&x25519_dalek::PublicKey::from(envelope.ephemeral_public));
+    if !shared.was_contributory() {
+        return Err(CryptoError::DecryptionFailed);
+    }
+    let key = envelope_key(shared.as_bytes(), &envelope.ephemeral_public, &own_public, context)?;
+    let mut plaintext = aead::decrypt(&envelope.ciphertext, &envelope.nonce, &key)?;
+    let bundle = serde_json::from_slice(&plaintext)
+        .map_err(|e| CryptoError::Serialization(e.to_string()));
+    plaintext.zeroize();
+    bundle
+}
+
+fn envelope_key(
+    shared: &[u8; 32],
+    ephemeral_public: &[u8; 32],
+    recipient_public: &[u8; 32],
+    context: &[u8],
+) -> CryptoResult<[u8; KEY_SIZE]> {
+    let mut salt = [0u8; 64];
+    salt[..32].copy_from_slice(ephemeral_public);
+    salt[32..].copy_from_slice(recipient_public);
+    let info = [ESCROW_INFO, context].concat();
+    let mut key = [0u8; KEY_SIZE];
+    Hkdf::<Sha256>::new(Some(&salt), shared)
+        .expand(&info, &mut key)
+        .map_err(|e| CryptoError::DerivationFailed(e.to_string()))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(seed: u8) -> ([u8; 32], [u8; 32]) {
+        let signing = ed25519_dalek::SigningKey::from_bytes(&[seed; 32]);
+        ([seed; 32], signing.verifying_key().to_bytes())
+    }
+
+    fn bundle() -> KeyEscrowBundle {
+        let mut docs = EscrowedKeyDb::new();
+        docs.insert(
+            "document:a".into(),
+            vec![EscrowedKey { epoch: 1, key: [7; 32] }, EscrowedKey { epoch: 2, key: [8; 32] }],
+        );
+        KeyEscrowBundle {
+            dbs: BTreeMap::from([("documents".to_string(), docs)]),
+        }
+    }
+
+    #[test]
+    fn seal_and_open_roundtrip() {
+        let (seed, public) = identity(1);
+        let envelope = seal_bundle(&bundle(), &public, b"src|dst").unwrap();
+        let opened = open_bundle(&envelope, &seed, b"src|dst").unwrap();
+        assert_eq!(opened.key_count(), 2);
+        assert_eq!(opened.dbs["documents"]["document:a"][1].key, [8; 32]);
+        assert!(!format!("{opened:?}").contains("8, 8"), "Debug redacts keys");
+    }
+
+    #[test]
+    fn only_the_recipient_in_context_opens() {
+        let (_, public) = identity(1);
+        let (other_seed, _) = identity(2);
+        let envelope = seal_bundle(&bundle(), &public, b"src|dst").unwrap();
+        assert!(open_bundle(&envelope, &other_seed, b"src|dst").is_err());
+        assert!(open_bundle(&envelope, &[1; 32], b"evil|dst").is_err());
+    }
+}
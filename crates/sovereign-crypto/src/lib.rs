@@ -3,17 +3,22 @@ pub mod aead;
 pub mod auth;
 pub mod canary;
 pub mod device_key;
+pub mod device_signing;
 pub mod pair_payload;
 pub mod document_key;
 pub mod error;
 pub mod fs_private;
 pub mod index_key;
 pub mod kek;
+pub mod key_audit;
 pub mod key_db;
+pub mod key_escrow;
 pub mod keystroke;
 pub mod mac;
 pub mod master_key;
 pub mod password_gen;
+pub mod rotation;
+pub mod skill_signing;
 pub mod vault;
 
 pub mod migration;
@@ -21,6 +26,9 @@ pub mod migration;
 #[cfg(feature = "guardian")]
 pub mod guardian;
 
+#[cfg(feature = "recovery-phrase")]
+pub mod recovery_phrase;
+
 pub use error::{CryptoError, CryptoResult};
 
 /// A fresh cryptographically-random 32-byte value as lowercase hex (64 chars).
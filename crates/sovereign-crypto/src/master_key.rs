@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use hkdf::Hkdf;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
@@ -22,10 +24,16 @@ pub enum Kdf {
     Argon2id { m_cost_kib: u32, t_cost: u32, p_cost: u32 },
 }
 
+/// Upper bounds for [`Kdf::calibrate`]: past 1 GiB an unlock risks failing
+/// on a machine with less free memory than the one that created the store.
+const CALIBRATION_MAX_M_COST_KIB: u32 = 1024 * 1024;
+const CALIBRATION_MAX_T_COST: u32 = 16;
+
 impl Kdf {
-    /// The KDF + params used for every NEW auth store. ~64 MiB / 3 passes
-    /// makes offline brute-force of the passphrase prohibitively expensive
-    /// (hundreds of ms per guess vs microseconds for HKDF).
+    /// The minimum KDF + params for every NEW auth store, and the fallback
+    /// when calibration is skipped. ~64 MiB / 3 passes makes offline
+    /// brute-force of the passphrase prohibitively expensive (hundreds of
+    /// ms per guess vs microseconds for HKDF).
     pub fn current() -> Self {
         Kdf::Argon2id {
             m_cost_kib: 64 * 1024,
@@ -39,6 +47,63 @@ impl Kdf {
     pub fn legacy() -> Self {
         Kdf::LegacyHkdf
     }
+
+    /// Benchmark Argon2id on this machine and pick the parameters whose
+    /// derivation takes about `target`. Memory is doubled first (it is
+    /// what makes GPU cracking expensive), then passes fill the remaining
+    /// time. Never weaker than [`Kdf::current`].
+    ///
+    /// The result is stored in the auth store next to the salt, so a later
+    /// unlock re-derives with exactly these parameters even after the
+    /// defaults change or the store moves to a faster machine.
+    pub fn calibrate(target: Duration) -> Self {
+        let salt: [u8; 16] = rand::random();
+        Self::calibrate_with(target, |m_cost_kib| {
+            let kdf = Kdf::Argon2id {
+                m_cost_kib,
+                t_cost: 1,
+                p_cost: 1,
+            };
+            let start = Instant::now();
+            MasterKey::derive(b"sovereign-kdf-calibration", &salt, &kdf)
+                .ok()
+                .map(|_| start.elapsed())
+        })
+    }
+
+    /// [`Kdf::calibrate`] with the single-pass timing injected.
+    /// `single_pass` returns `None` when the memory cannot be allocated.
+    fn calibrate_with(target: Duration, mut single_pass: impl FnMut(u32) -> Option<Duration>) -> Self {
+        let Kdf::Argon2id {
+            m_cost_kib: min_m,
+            t_cost: min_t,
+            p_cost,
+        } = Self::current()
+        else {
+            unreachable!("current KDF is Argon2id")
+        };
+        let Some(mut pass) = single_pass(min_m) else {
+            return Self::current();
+        };
+        let mut m_cost_kib = min_m;
+        // Grow memory while the minimum number of passes still fits.
+        while pass * min_t * 2 <= target && m_cost_kib * 2 <= CALIBRATION_MAX_M_COST_KIB {
+            match single_pass(m_cost_kib * 2) {
+                Some(elapsed) => {
+                    m_cost_kib *= 2;
+                    pass = elapsed;
+                }
+                None => break,
+            }
+        }
+        let passes = target.as_nanos() / pass.as_nanos().max(1);
+        let t_cost = (passes as u32).clamp(min_t, CALIBRATION_MAX_T_COST);
+        Kdf::Argon2id {
+            m_cost_kib,
+            t_cost,
+            p_cost,
+        }
+    }
 }
 
 /// The root of the key hierarchy. 256-bit master secret.
@@ -185,4 +250,41 @@ mod tests {
         let direct = MasterKey::from_passphrase(b"pw", salt).unwrap();
         assert_eq!(legacy.as_bytes(), direct.as_bytes());
     }
+
+    #[test]
+    fn calibration_grows_memory_then_passes() {
+        // 1 ms per MiB per pass: 64 MiB → 64 ms, 256 MiB → 256 ms.
+        let per_mib = |m_cost_kib: u32| Some(Duration::from_millis(u64::from(m_cost_kib / 1024)));
+        let kdf = Kdf::calibrate_with(Duration::from_secs(1), per_mib);
+        // 256 MiB × 3 passes ≤ 1 s, but 512 MiB × 3 would not be.
+        assert_eq!(
+            kdf,
+            Kdf::Argon2id {
+                m_cost_kib: 256 * 1024,
+                t_cost: 3,
+                p_cost: 1
+            }
+        );
+    }
+
+    #[test]
+    fn calibration_never_goes_below_current() {
+        let slow = |_| Some(Duration::from_secs(5));
+        assert_eq!(Kdf::calibrate_with(Duration::from_secs(1), slow), Kdf::current());
+        let fails = |_| None;
+        assert_eq!(Kdf::calibrate_with(Duration::from_secs(1), fails), Kdf::current());
+    }
+
+    #[test]
+    fn calibration_is_capped() {
+        let instant = |_| Some(Duration::from_nanos(1));
+        assert_eq!(
+            Kdf::calibrate_with(Duration::from_secs(1), instant),
+            Kdf::Argon2id {
+                m_cost_kib: CALIBRATION_MAX_M_COST_KIB,
+                t_cost: CALIBRATION_MAX_T_COST,
+                p_cost: 1
+            }
+        );
+    }
 }
@@ -0,0 +1,89 @@
+//! BIP39 recovery phrase for the MasterKey: the solo-user alternative to
+//! guardian recovery. The 32-byte key is shown once, as 24 English words,
+//! at onboarding and never written anywhere by Sovereign. Typing the words
+//! back in restores the MasterKey, which unlocks the primary persona
+//! without the passphrase (see [`AuthStore::rebind_passphrase`]).
+//!
+//! [`AuthStore::rebind_passphrase`]: crate::auth::AuthStore::rebind_passphrase
+
+use bip39::Mnemonic;
+
+use crate::aead::KEY_SIZE;
+use crate::error::{CryptoError, CryptoResult};
+use crate::master_key::MasterKey;
+
+/// Words in a phrase: 256 bits of key + 8 checksum bits, 11 bits a word.
+pub const WORD_COUNT: usize = 24;
+
+/// The 24-word phrase for `master`.
+pub fn to_phrase(master: &MasterKey) -> CryptoResult<String> {
+    Mnemonic::from_entropy(master.as_bytes())
+        .map(|m| m.to_string())
+        .map_err(|e| CryptoError::RecoveryPhrase(e.to_string()))
+}
+
+/// The MasterKey a phrase encodes. Case and extra whitespace are ignored;
+/// a mistyped word fails the BIP39 checksum rather than yielding a wrong
+/// key.
+pub fn from_phrase(phrase: &str) -> CryptoResult<MasterKey> {
+    let normalized = phrase
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+    if normalized.len() != WORD_COUNT {
+        return Err(CryptoError::RecoveryPhrase(format!(
+            "expected {WORD_COUNT} words, got {}",
+            normalized.len()
+        )));
+    }
+    let mnemonic = Mnemonic::parse_normalized(&normalized.join(" "))
+        .map_err(|e| CryptoError::RecoveryPhrase(e.to_string()))?;
+    let bytes: [u8; KEY_SIZE] = mnemonic
+        .to_entropy()
+        .try_into()
+        .map_err(|e: Vec<u8>| CryptoError::InvalidKeyLength {
+            expected: KEY_SIZE,
+            got: e.len(),
+        })?;
+    Ok(MasterKey::from_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phrase_roundtrip() {
+        let mk = MasterKey::generate();
+        let phrase = to_phrase(&mk).unwrap();
+        assert_eq!(phrase.split(' ').count(), WORD_COUNT);
+        let back = from_phrase(&phrase).unwrap();
+        assert_eq!(back.as_bytes(), mk.as_bytes());
+    }
+
+    #[test]
+    fn phrase_ignores_case_and_spacing() {
+        let mk = MasterKey::generate();
+        let phrase = to_phrase(&mk).unwrap();
+        let sloppy = format!("  {}\n", phrase.to_uppercase().replace(' ', "   "));
+        assert_eq!(from_phrase(&sloppy).unwrap().as_bytes(), mk.as_bytes());
+    }
+
+    #[test]
+    fn matches_bip39_test_vector() {
+        // All-zero 256-bit entropy, from the BIP39 reference vectors.
+        let phrase = to_phrase(&MasterKey::from_bytes([0u8; KEY_SIZE])).unwrap();
+        assert_eq!(phrase, format!("{}art", "abandon ".repeat(23)));
+    }
+
+    #[test]
+    fn mistyped_or_short_phrase_rejected() {
+        let valid = format!("{}art", "abandon ".repeat(23));
+        assert!(from_phrase(&valid).is_ok());
+        // Wrong checksum word.
+        assert!(from_phrase(&"abandon ".repeat(24)).is_err());
+        // Missing word, unknown word.
+        assert!(from_phrase(&"abandon ".repeat(23)).is_err());
+        assert!(from_phrase(&format!("{}artt", "abandon ".repeat(23))).is_err());
+    }
+}
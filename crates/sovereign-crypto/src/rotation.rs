@@ -0,0 +1,239 @@
+//! Key rotation bookkeeping: the journal that makes a KEK rotation
+//! resumable, and the append-only, encrypted rotation audit log.
+//!
+//! A KEK rotation re-wraps several files (the per-entity key databases, the
+//! blind-index key, KEK-sealed stores) before committing the new KEK to the
+//! auth store. The new KEK is journaled first, wrapped under the DeviceKey,
+//! so a rotation cut short by a crash is finished at the next unlock: every
+//! re-wrap step accepts data that is already under the new KEK
+//! (see [`KeyDatabase::rewrap_all`](crate::key_db::KeyDatabase::rewrap_all)).
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::aead::{self, KEY_SIZE, NONCE_SIZE};
+use crate::device_key::DeviceKey;
+use crate::error::{CryptoError, CryptoResult};
+use crate::kek::{Kek, WrappedKek};
+
+/// A KEK rotation that has started but not yet been committed to the auth
+/// store.
+#[derive(Serialize, Deserialize)]
+pub struct PendingKekRotation {
+    wrapped_kek: WrappedKek,
+    pub started_at: DateTime<Utc>,
+}
+
+impl PendingKekRotation {
+    pub fn new(new_kek: &Kek, device_key: &DeviceKey) -> CryptoResult<Self> {
+        Ok(Self {
+            wrapped_kek: new_kek.wrap(device_key)?,
+            started_at: Utc::now(),
+        })
+    }
+
+    /// The KEK being rotated to.
+    pub fn new_kek(&self, device_key: &DeviceKey) -> CryptoResult<Kek> {
+        Kek::unwrap(&self.wrapped_kek, device_key)
+    }
+
+    pub fn save(&self, path: &Path) -> CryptoResult<()> {
+        let json =
+            serde_json::to_vec(self).map_err(|e| CryptoError::Serialization(e.to_string()))?;
+        crate::fs_private::write_private(path, json).map_err(|e| CryptoError::KeyDbIo(e.to_string()))
+    }
+
+    /// The journaled rotation, or `None` when none is in progress.
+    pub fn load(path: &Path) -> CryptoResult<Option<Self>> {
+        match std::fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| CryptoError::Serialization(e.to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(CryptoError::KeyDbIo(e.to_string())),
+        }
+    }
+
+    /// Remove the journal once the rotation is committed.
+    pub fn clear(path: &Path) -> CryptoResult<()> {
+        match std::fs::remove_file(path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(CryptoError::KeyDbIo(e.to_string())),
+        }
+    }
+}
+
+/// What a rotation replaced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RotationKind {
+    /// The key-encryption key; every document key was re-wrapped.
+    Kek,
+    /// Per-entity document keys; the affected rows were re-encrypted.
+    DocumentKeys { scope: String },
+}
+
+/// One entry in the rotation audit log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationRecord {
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub kind: RotationKind,
+    /// Keys re-wrapped (KEK) or entities re-encrypted (document keys).
+    pub keys: u64,
+    /// Finished at unlock after being interrupted.
+    #[serde(default)]
+    pub resumed: bool,
+}
+
+impl RotationRecord {
+    pub fn now(kind: RotationKind, keys: u64) -> Self {
+        Self {
+            at: Utc::now(),
+            kind,
+            keys,
+            resumed: false,
+        }
+    }
+}
+
+/// Append-only log of key rotations, one sealed record per line. Records
+/// carry no key material, only what was rotated and when, and are
+/// encrypted under a session key so the file reveals only how many there
+/// are.
+#[derive(Clone)]
+pub struct RotationAuditLog {
+    path: PathBuf,
+    key: Zeroizing<[u8; KEY_SIZE]>,
+}
+
+impl RotationAuditLog {
+    pub fn new(path: PathBuf, key: [u8; KEY_SIZE]) -> Self {
+        Self {
+            path,
+            key: Zeroizing::new(key),
+        }
+    }
+
+    pub fn record(&self, record: &RotationRecord) -> CryptoResult<()> {
+        let json =
+            serde_json::to_vec(record).map_err(|e| CryptoError::Serialization(e.to_string()))?;
+        let (ciphertext, nonce) = aead::encrypt(&json, &self.key)?;
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        let mut line = base64::engine::general_purpose::STANDARD
+            .encode(sealed)
+            .into_bytes();
+        line.push(b'\n');
+        let mut options = std::fs::OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options
+            .open(&self.path)
+            .and_then(|mut f| f.write_all(&line))
+            .map_err(|e| CryptoError::KeyDbIo(e.to_string()))
+    }
+
+    /// All records, oldest first. Lines that don't open under this key
+    /// are skipped.
+    pub fn entries(&self) -> Vec<RotationRecord> {
+        std::fs::read_to_string(&self.path)
+            .map(|text| text.lines().filter_map(|line| self.open(line)).collect())
+            .unwrap_or_default()
+    }
+
+    fn open(&self, line: &str) -> Option<RotationRecord> {
+        let sealed = base64::engine::general_purpose::STANDARD.decode(line).ok()?;
+        if sealed.len() < NONCE_SIZE {
+            return None;
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_SIZE);
+        let json = aead::decrypt(ciphertext, nonce.try_into().ok()?, &self.key).ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+}
+
+impl std::fmt::Debug for RotationAuditLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RotationAuditLog")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::master_key::MasterKey;
+
+    #[test]
+    fn pending_rotation_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kek.rotating");
+        let mk = MasterKey::from_passphrase(b"test", b"salt").unwrap();
+        let device_key = DeviceKey::derive(&mk, "dev-01").unwrap();
+        let kek = Kek::generate();
+
+        assert!(PendingKekRotation::load(&path).unwrap().is_none());
+        PendingKekRotation::new(&kek, &device_key)
+            .unwrap()
+            .save(&path)
+            .unwrap();
+        let pending = PendingKekRotation::load(&path).unwrap().unwrap();
+        assert_eq!(pending.new_kek(&device_key).unwrap().as_bytes(), kek.as_bytes());
+
+        PendingKekRotation::clear(&path).unwrap();
+        PendingKekRotation::clear(&path).unwrap();
+        assert!(PendingKekRotation::load(&path).unwrap().is_none());
+    }
+
+    #[test]
+    fn audit_log_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = RotationAuditLog::new(dir.path().join("key_rotation.log"), [7; KEY_SIZE]);
+        assert!(log.entries().is_empty());
+        log.record(&RotationRecord::now(RotationKind::Kek, 12)).unwrap();
+        log.record(&RotationRecord::now(
+            RotationKind::DocumentKeys {
+                scope: "documents".into(),
+            },
+            3,
+        ))
+        .unwrap();
+
+        let entries = log.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].kind, RotationKind::Kek);
+        assert_eq!(entries[0].keys, 12);
+        assert_eq!(
+            entries[1].kind,
+            RotationKind::DocumentKeys {
+                scope: "documents".into()
+            }
+        );
+        let raw = std::fs::read_to_string(dir.path().join("key_rotation.log")).unwrap();
+        assert_eq!(raw.lines().count(), 2);
+        assert!(!raw.contains("documents"));
+    }
+
+    #[test]
+    fn audit_log_opens_only_under_its_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key_rotation.log");
+        RotationAuditLog::new(path.clone(), [7; KEY_SIZE])
+            .record(&RotationRecord::now(RotationKind::Kek, 1))
+            .unwrap();
+        assert!(RotationAuditLog::new(path.clone(), [8; KEY_SIZE]).entries().is_empty());
+        assert_eq!(RotationAuditLog::new(path, [7; KEY_SIZE]).entries().len(), 1);
+    }
+}
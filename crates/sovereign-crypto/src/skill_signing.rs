@@ -0,0 +1,383 @@
+//! Ed25519 signing of skill packages and the trusted-publisher key store.
+//!
+//! A skill package is its `skill.json` manifest plus (for plugins) the
+//! `.wasm` component next to it. The publisher signs a SHA-256 digest over
+//! both, and the signature ships as `skill.sig` in the skill directory.
+//! At registry scan time the host recomputes the digest and checks it
+//! against the keys the user has chosen to trust, so an edited manifest
+//! (e.g. a capability quietly added) or a swapped binary shows up as
+//! tampered rather than silently loading.
+
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{CryptoError, CryptoResult};
+
+/// Domain-separation tag for the skill package digest.
+const SKILL_SIG_DOMAIN: &[u8] = b"sovereign-skill-signature:v1";
+
+/// File name of the detached signature inside a skill directory.
+pub const SKILL_SIG_FILE: &str = "skill.sig";
+
+fn b64() -> base64::engine::GeneralPurpose {
+    base64::engine::general_purpose::STANDARD
+}
+
+/// SHA-256 over the domain tag and the length-prefixed manifest and wasm
+/// bytes. Length prefixes stop bytes from migrating between the two parts.
+/// `wasm` is `None` for manifest-only skills.
+pub fn skill_digest(manifest: &[u8], wasm: Option<&[u8]>) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(SKILL_SIG_DOMAIN);
+    hasher.update((manifest.len() as u64).to_le_bytes());
+    hasher.update(manifest);
+    let wasm = wasm.unwrap_or_default();
+    hasher.update((wasm.len() as u64).to_le_bytes());
+    hasher.update(wasm);
+    hasher.finalize().into()
+}
+
+/// Detached signature over a skill package, stored as `skill.sig` (JSON).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SkillSignature {
+    /// Base64 Ed25519 public key of the signer.
+    pub public_key: String,
+    /// Base64 Ed25519 signature over [`skill_digest`].
+    pub signature: String,
+}
+
+impl SkillSignature {
+    pub fn load(path: &Path) -> CryptoResult<Self> {
+        let data = std::fs::read(path).map_err(|e| CryptoError::KeyDbIo(e.to_string()))?;
+        serde_json::from_slice(&data).map_err(|e| CryptoError::Serialization(e.to_string()))
+    }
+
+    pub fn save(&self, path: &Path) -> CryptoResult<()> {
+        let json = serde_json::to_vec_pretty(self)
+            .map_err(|e| CryptoError::Serialization(e.to_string()))?;
+        std::fs::write(path, json).map_err(|e| CryptoError::KeyDbIo(e.to_string()))
+    }
+}
+
+/// A publisher's skill-signing key. Held by skill authors, never by the host.
+pub struct SkillSigningKey {
+    inner: SigningKey,
+}
+
+impl SkillSigningKey {
+    /// Generate a fresh random signing key.
+    pub fn generate() -> Self {
+        use rand::Rng;
+        let mut seed = [0u8; 32];
+        rand::rng().fill_bytes(&mut seed);
+        let inner = SigningKey::from_bytes(&seed);
+        zeroize::Zeroize::zeroize(&mut seed);
+        Self { inner }
+    }
+
+    /// Rebuild a signing key from its 32-byte seed.
+    pub fn from_bytes(seed: &[u8; 32]) -> Self {
+        Self {
+            inner: SigningKey::from_bytes(seed),
+        }
+    }
+
+    /// Base64 public key, as listed in a [`TrustedKeys`] store.
+    pub fn public_key_b64(&self) -> String {
+        b64().encode(self.inner.verifying_key().to_bytes())
+    }
+
+    /// Sign a skill package.
+    pub fn sign(&self, manifest: &[u8], wasm: Option<&[u8]>) -> SkillSignature {
+        let sig = self.inner.sign(&skill_digest(manifest, wasm));
+        SkillSignature {
+            public_key: self.public_key_b64(),
+            signature: b64().encode(sig.to_bytes()),
+        }
+    }
+}
+
+impl std::fmt::Debug for SkillSigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SkillSigningKey")
+            .field("public_key", &self.public_key_b64())
+            .finish()
+    }
+}
+
+/// A publisher key the user has chosen to trust.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TrustedKey {
+    /// Human-readable publisher name shown in the skills panel.
+    pub name: String,
+    /// Base64 Ed25519 public key.
+    pub public_key: String,
+    pub added_at: i64,
+}
+
+/// Persistent list of trusted skill publisher keys.
+///
+/// Public keys are not secret, so the file is plain JSON; it is still
+/// written owner-only so another local user can't add themselves.
+#[derive(Debug, Default)]
+pub struct TrustedKeys {
+    keys: Vec<TrustedKey>,
+    path: Option<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TrustedKeysContents {
+    keys: Vec<TrustedKey>,
+}
+
+impl TrustedKeys {
+    /// An empty store bound to `path` (not yet written).
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            keys: Vec::new(),
+            path: Some(path),
+        }
+    }
+
+    /// Load from `path`; a missing file yields an empty store.
+    pub fn load(path: &Path) -> CryptoResult<Self> {
+        if !path.exists() {
+            return Ok(Self::new(path.to_path_buf()));
+        }
+        let data = std::fs::read(path).map_err(|e| CryptoError::KeyDbIo(e.to_string()))?;
+        let contents: TrustedKeysContents = serde_json::from_slice(&data)
+            .map_err(|e| CryptoError::Serialization(e.to_string()))?;
+        Ok(Self {
+            keys: contents.keys,
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    pub fn save(&self) -> CryptoResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let json = serde_json::to_vec_pretty(&TrustedKeysContents {
+            keys: self.keys.clone(),
+        })
+        .map_err(|e| CryptoError::Serialization(e.to_string()))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| CryptoError::KeyDbIo(e.to_string()))?;
+        }
+        crate::fs_private::write_private(path, json).map_err(|e| CryptoError::KeyDbIo(e.to_string()))
+    }
+
+    /// Trust a publisher key. Rejects malformed keys; re-adding an existing
+    /// key only updates its display name.
+    pub fn add(&mut self, name: &str, public_key_b64: &str) -> CryptoResult<()> {
+        parse_public_key(public_key_b64)?;
+        if let Some(existing) = self.keys.iter_mut().find(|k| k.public_key == public_key_b64) {
+            existing.name = name.to_string();
+            return Ok(());
+        }
+        self.keys.push(TrustedKey {
+            name: name.to_string(),
+            public_key: public_key_b64.to_string(),
+            added_at: chrono::Utc::now().timestamp_millis(),
+        });
+        Ok(())
+    }
+
+    /// Stop trusting a key. Returns whether it was present.
+    pub fn remove(&mut self, public_key_b64: &str) -> bool {
+        let before = self.keys.len();
+        self.keys.retain(|k| k.public_key != public_key_b64);
+        self.keys.len() != before
+    }
+
+    pub fn find(&self, public_key_b64: &str) -> Option<&TrustedKey> {
+        self.keys.iter().find(|k| k.public_key == public_key_b64)
+    }
+
+    pub fn keys(&self) -> &[TrustedKey] {
+        &self.keys
+    }
+}
+
+fn parse_public_key(public_key_b64: &str) -> CryptoResult<VerifyingKey> {
+    let bytes = b64()
+        .decode(public_key_b64)
+        .map_err(|e| CryptoError::Base64(e.to_string()))?;
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|v: Vec<u8>| CryptoError::InvalidKeyLength { expected: 32, got: v.len() })?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| CryptoError::Signature(e.to_string()))
+}
+
+/// Outcome of checking a skill package against the trusted-keys store.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SkillVerification {
+    /// Signature valid and the signer is trusted.
+    Verified { signer: String },
+    /// Signature valid, but the signer is not in the trusted-keys store.
+    UntrustedSigner { public_key: String },
+    /// No `skill.sig` shipped with the skill.
+    Unsigned,
+    /// A signature is present but does not match the package bytes — the
+    /// manifest or binary was modified after signing, or the file is garbage.
+    Tampered,
+}
+
+impl SkillVerification {
+    pub fn is_verified(&self) -> bool {
+        matches!(self, SkillVerification::Verified { .. })
+    }
+}
+
+/// Verify a skill package. Signature validity is checked before trust, so
+/// a modified package signed by an unknown key still reports `Tampered`.
+pub fn verify_skill(
+    manifest: &[u8],
+    wasm: Option<&[u8]>,
+    signature: Option<&SkillSignature>,
+    trusted: &TrustedKeys,
+) -> SkillVerification {
+    let Some(sig) = signature else {
+        return SkillVerification::Unsigned;
+    };
+    let Ok(key) = parse_public_key(&sig.public_key) else {
+        return SkillVerification::Tampered;
+    };
+    let sig_bytes: Option<[u8; 64]> = b64()
+        .decode(&sig.signature)
+        .ok()
+        .and_then(|v| v.try_into().ok());
+    let Some(sig_bytes) = sig_bytes else {
+        return SkillVerification::Tampered;
+    };
+    let signature = Signature::from_bytes(&sig_bytes);
+    if key
+        .verify_strict(&skill_digest(manifest, wasm), &signature)
+        .is_err()
+    {
+        return SkillVerification::Tampered;
+    }
+    match trusted.find(&sig.public_key) {
+        Some(k) => SkillVerification::Verified {
+            signer: k.name.clone(),
+        },
+        None => SkillVerification::UntrustedSigner {
+            public_key: sig.public_key.clone(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &[u8] = br#"{"name":"demo","capabilities":["read_document"]}"#;
+    const WASM: &[u8] = b"\0asm fake component";
+
+    fn trusting(key: &SkillSigningKey) -> TrustedKeys {
+        let mut trusted = TrustedKeys::default();
+        trusted.add("Demo Publisher", &key.public_key_b64()).unwrap();
+        trusted
+    }
+
+    #[test]
+    fn signed_by_trusted_key_verifies() {
+        let key = SkillSigningKey::generate();
+        let sig = key.sign(MANIFEST, Some(WASM));
+        let result = verify_skill(MANIFEST, Some(WASM), Some(&sig), &trusting(&key));
+        assert_eq!(
+            result,
+            SkillVerification::Verified {
+                signer: "Demo Publisher".into()
+            }
+        );
+    }
+
+    #[test]
+    fn missing_signature_is_unsigned() {
+        let result = verify_skill(MANIFEST, None, None, &TrustedKeys::default());
+        assert_eq!(result, SkillVerification::Unsigned);
+    }
+
+    #[test]
+    fn edited_manifest_or_binary_is_tampered() {
+        let key = SkillSigningKey::generate();
+        let trusted = trusting(&key);
+        let sig = key.sign(MANIFEST, Some(WASM));
+
+        let widened = br#"{"name":"demo","capabilities":["network"]}"#;
+        assert_eq!(
+            verify_skill(widened, Some(WASM), Some(&sig), &trusted),
+            SkillVerification::Tampered
+        );
+        assert_eq!(
+            verify_skill(MANIFEST, Some(b"other binary"), Some(&sig), &trusted),
+            SkillVerification::Tampered
+        );
+        assert_eq!(
+            verify_skill(MANIFEST, None, Some(&sig), &trusted),
+            SkillVerification::Tampered
+        );
+    }
+
+    #[test]
+    fn valid_signature_from_unknown_key_is_untrusted() {
+        let key = SkillSigningKey::generate();
+        let sig = key.sign(MANIFEST, None);
+        let result = verify_skill(MANIFEST, None, Some(&sig), &TrustedKeys::default());
+        assert_eq!(
+            result,
+            SkillVerification::UntrustedSigner {
+                public_key: key.public_key_b64()
+            }
+        );
+    }
+
+    #[test]
+    fn garbage_signature_is_tampered() {
+        let sig = SkillSignature {
+            public_key: "not base64!".into(),
+            signature: "AAAA".into(),
+        };
+        assert_eq!(
+            verify_skill(MANIFEST, None, Some(&sig), &TrustedKeys::default()),
+            SkillVerification::Tampered
+        );
+    }
+
+    #[test]
+    fn trusted_keys_add_remove_and_reject_malformed() {
+        let key = SkillSigningKey::generate();
+        let mut trusted = TrustedKeys::default();
+        assert!(trusted.add("bad", "AAAA").is_err());
+
+        trusted.add("A", &key.public_key_b64()).unwrap();
+        trusted.add("A renamed", &key.public_key_b64()).unwrap();
+        assert_eq!(trusted.keys().len(), 1);
+        assert_eq!(trusted.find(&key.public_key_b64()).unwrap().name, "A renamed");
+
+        assert!(trusted.remove(&key.public_key_b64()));
+        assert!(!trusted.remove(&key.public_key_b64()));
+        assert!(trusted.keys().is_empty());
+    }
+
+    #[test]
+    fn trusted_keys_save_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trusted_skill_keys.json");
+        let key = SkillSigningKey::from_bytes(&[7u8; 32]);
+
+        let mut trusted = TrustedKeys::load(&path).unwrap();
+        assert!(trusted.keys().is_empty());
+        trusted.add("Seven", &key.public_key_b64()).unwrap();
+        trusted.save().unwrap();
+
+        let reloaded = TrustedKeys::load(&path).unwrap();
+        assert_eq!(reloaded.keys(), trusted.keys());
+    }
+}
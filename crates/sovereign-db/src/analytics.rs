@@ -0,0 +1,285 @@
+//! Workspace statistics for the dashboard: how the graph has grown, week
+//! by week, and where it is concentrated.
+//!
+//! Like [`crate::tasks`], this is a projection computed on read through a
+//! [`GraphDB`], so it needs no extra tables and sees decrypted data the
+//! same way every other reader does. It counts rows; it never looks at
+//! what they say.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::DbResult;
+use crate::schema::MessageDirection;
+use crate::traits::GraphDB;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Totals {
+    pub documents: usize,
+    pub owned_documents: usize,
+    pub external_documents: usize,
+    pub threads: usize,
+    pub commits: usize,
+    pub messages: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThreadCount {
+    pub thread_id: String,
+    pub thread_name: String,
+    pub documents: usize,
+    pub owned: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelCount {
+    pub channel: String,
+    pub sent: usize,
+    pub received: usize,
+}
+
+/// One week, Monday to Sunday (UTC).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeekStats {
+    pub week_start: NaiveDate,
+    pub documents_created: usize,
+    pub owned_created: usize,
+    /// Live documents that existed by the end of the week.
+    pub documents_total: usize,
+    pub commits: usize,
+    /// Messages sent or received that week, by channel.
+    pub messages: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceStats {
+    pub totals: Totals,
+    /// Largest thread first.
+    pub documents_per_thread: Vec<ThreadCount>,
+    /// Busiest channel first.
+    pub messages_per_channel: Vec<ChannelCount>,
+    /// Oldest week first, ending with the week containing `now`.
+    pub weeks: Vec<WeekStats>,
+}
+
+/// The Monday starting `date`'s week.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Statistics over every live document, thread, commit and message, with
+/// the last `weeks` weeks (up to and including `now`'s) broken out.
+pub async fn workspace_stats(
+    db: &dyn GraphDB,
+    weeks: usize,
+    now: DateTime<Utc>,
+) -> DbResult<WorkspaceStats> {
+    let documents = db.list_documents(None).await?;
+    let threads = db.list_threads().await?;
+    let messages = db.list_all_messages().await?;
+
+    let current = week_start(now.date_naive());
+    let first = current - Duration::weeks(weeks.saturating_sub(1) as i64);
+    let mut series: Vec<WeekStats> = (0..weeks)
+        .map(|i| WeekStats {
+            week_start: first + Duration::weeks(i as i64),
+            documents_created: 0,
+            owned_created: 0,
+            documents_total: 0,
+            commits: 0,
+            messages: BTreeMap::new(),
+        })
+        .collect();
+    // The week `at` falls in, if it is inside the window.
+    let bucket = |at: DateTime<Utc>| {
+        let start = week_start(at.date_naive());
+        if start < first || start > current {
+            return None;
+        }
+        Some((start - first).num_weeks() as usize)
+    };
+
+    let mut totals = Totals {
+        documents: documents.len(),
+        threads: threads.len(),
+        messages: messages.len(),
+        ..Totals::default()
+    };
+    let mut per_thread: HashMap<&str, (usize, usize)> = HashMap::new();
+    let mut before_window = 0;
+    for doc in &documents {
+        if doc.is_owned {
+            totals.owned_documents += 1;
+        } else {
+            totals.external_documents += 1;
+        }
+        let entry = per_thread.entry(doc.thread_id.as_str()).or_default();
+        entry.0 += 1;
+        entry.1 += usize::from(doc.is_owned);
+        match bucket(doc.created_at) {
+            Some(i) => {
+                series[i].documents_created += 1;
+                series[i].owned_created += usize::from(doc.is_owned);
+            }
+            None if week_start(doc.created_at.date_naive()) < first => before_window += 1,
+            None => {}
+        }
+
+        let Some(id) = doc.id_string() else { continue };
+        let commits = db.list_document_commits(&id).await?;
+        totals.commits += commits.len();
+        for commit in commits {
+            if let Some(i) = bucket(commit.timestamp) {
+                series[i].commits += 1;
+            }
+        }
+    }
+    let mut running = before_window;
+    for week in &mut series {
+        running += week.documents_created;
+        week.documents_total = running;
+    }
+
+    let thread_names: HashMap<String, &str> = threads
+        .iter()
+        .filter_map(|t| Some((t.id_string()?, t.name.as_str())))
+        .collect();
+    let mut documents_per_thread: Vec<ThreadCount> = per_thread
+        .into_iter()
+        .map(|(thread_id, (documents, owned))| ThreadCount {
+            thread_id: thread_id.to_string(),
+            thread_name: thread_names
+                .get(thread_id)
+                .copied()
+                .unwrap_or("")
+                .to_string(),
+            documents,
+            owned,
+        })
+        .collect();
+    documents_per_thread.sort_by(|a, b| {
+        b.documents
+            .cmp(&a.documents)
+            .then_with(|| a.thread_name.cmp(&b.thread_name))
+    });
+
+    let mut per_channel: BTreeMap<String, ChannelCount> = BTreeMap::new();
+    for message in &messages {
+        let channel = message.channel.to_string();
+        let count = per_channel
+            .entry(channel.clone())
+            .or_insert_with(|| ChannelCount {
+                channel: channel.clone(),
+                sent: 0,
+                received: 0,
+            });
+        match message.direction {
+            MessageDirection::Outbound => count.sent += 1,
+            MessageDirection::Inbound => count.received += 1,
+        }
+        if let Some(i) = bucket(message.sent_at) {
+            *series[i].messages.entry(channel).or_default() += 1;
+        }
+    }
+    let mut messages_per_channel: Vec<ChannelCount> = per_channel.into_values().collect();
+    messages_per_channel.sort_by_key(|c| std::cmp::Reverse(c.sent + c.received));
+
+    Ok(WorkspaceStats {
+        totals,
+        documents_per_thread,
+        messages_per_channel,
+        weeks: series,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockGraphDB;
+    use crate::schema::{ChannelType, Document, Message, Thread};
+
+    fn at(date: &str) -> DateTime<Utc> {
+        format!("{date}T12:00:00Z").parse().unwrap()
+    }
+
+    #[test]
+    fn weeks_start_on_monday() {
+        let sunday = NaiveDate::from_ymd_opt(2026, 3, 8).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        assert_eq!(week_start(sunday), monday);
+        assert_eq!(week_start(monday), monday);
+    }
+
+    #[tokio::test]
+    async fn counts_growth_threads_and_channels() {
+        let db = MockGraphDB::new();
+        let thread = db
+            .create_thread(Thread::new("Research".into(), String::new()))
+            .await
+            .unwrap();
+        let thread_id = thread.id_string().unwrap();
+        for (created, owned) in [
+            ("2026-01-05", true),
+            ("2026-02-23", true),
+            ("2026-03-04", false),
+        ] {
+            let mut doc = Document::new("Doc".into(), thread_id.clone(), owned);
+            doc.created_at = at(created);
+            db.create_document(doc).await.unwrap();
+        }
+        let mut other = Document::new("Loose".into(), "thread:other".into(), true);
+        other.created_at = at("2026-03-05");
+        let other = db.create_document(other).await.unwrap();
+        db.commit_document(&other.id_string().unwrap(), "first")
+            .await
+            .unwrap();
+        for (channel, direction, sent) in [
+            (ChannelType::Email, MessageDirection::Inbound, "2026-03-03"),
+            (ChannelType::Email, MessageDirection::Outbound, "2026-02-24"),
+            (ChannelType::Signal, MessageDirection::Inbound, "2026-03-03"),
+        ] {
+            let mut message = Message::new(
+                "conversation:c".into(),
+                channel,
+                direction,
+                "contact:a".into(),
+                Vec::new(),
+                "hi".into(),
+            );
+            message.sent_at = at(sent);
+            db.create_message(message).await.unwrap();
+        }
+
+        let stats = workspace_stats(&db, 2, at("2026-03-06")).await.unwrap();
+
+        assert_eq!(stats.totals.documents, 4);
+        assert_eq!(stats.totals.owned_documents, 3);
+        assert_eq!(stats.totals.external_documents, 1);
+        assert_eq!(stats.totals.messages, 3);
+        assert_eq!(stats.totals.commits, 1);
+
+        let weeks: Vec<(NaiveDate, usize, usize)> = stats
+            .weeks
+            .iter()
+            .map(|w| (w.week_start, w.documents_created, w.documents_total))
+            .collect();
+        assert_eq!(
+            weeks,
+            [
+                (NaiveDate::from_ymd_opt(2026, 2, 23).unwrap(), 1, 2),
+                (NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(), 2, 4),
+            ]
+        );
+        assert_eq!(stats.weeks[0].messages["email"], 1);
+        assert_eq!(stats.weeks[1].messages.len(), 2);
+
+        assert_eq!(stats.documents_per_thread[0].thread_name, "Research");
+        assert_eq!(stats.documents_per_thread[0].documents, 3);
+        assert_eq!(stats.documents_per_thread[0].owned, 2);
+        assert_eq!(stats.messages_per_channel[0].channel, "email");
+        assert_eq!(stats.messages_per_channel[0].sent, 1);
+        assert_eq!(stats.messages_per_channel[0].received, 1);
+    }
+}
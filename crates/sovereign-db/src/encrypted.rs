@@ -8,18 +8,22 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use base64::Engine;
+use serde::{Deserialize, Serialize};
 use sovereign_crypto::aead;
 use sovereign_crypto::device_key::DeviceKey;
+use sovereign_crypto::document_key::DocumentKey;
 use sovereign_crypto::index_key::{self, IndexKey};
 use sovereign_crypto::kek::Kek;
 use sovereign_crypto::key_db::KeyDatabase;
+use sovereign_crypto::key_escrow::KeyEscrowBundle;
 use tokio::sync::RwLock;
 
 use crate::error::{DbError, DbResult};
 use crate::schema::{
-    ChannelType, Commit, Contact, Conversation, Document, Entity, EntityKind, Message, Milestone,
-    PiiRecord, ReadStatus, RelatedTo, RelationType, ReviewState, ShareRecord, SourceRef,
-    SuggestedLink, SuggestionSource, SuggestionStatus, Thread,
+    ActionLevel, ChannelType, Commit, Contact, Conversation, DocType, Document,
+    DocumentPermissions, Entity, EntityKind, Message, Milestone, PiiRecord, Principal, ReadStatus,
+    RelatedTo, RelationType, Reminder, ReviewState, ShareRecord, SourceRef, SuggestedLink,
+    SuggestionSource, SuggestionStatus, Thread,
 };
 use crate::traits::GraphDB;
 
@@ -27,6 +31,13 @@ use crate::traits::GraphDB;
 /// on pathological inputs; chosen wide enough not to truncate normal chat.
 const MESSAGE_TOKEN_CAP: usize = 256;
 
+/// Cap on content tokens per document. Documents run far longer than chat
+/// messages; past this, later words are simply not searchable.
+const DOCUMENT_TOKEN_CAP: usize = 4096;
+
+/// Cap on `#tags` indexed per document.
+const DOCUMENT_TAG_CAP: usize = 64;
+
 /// A GraphDB wrapper that encrypts/decrypts content transparently.
 ///
 /// Per-entity-type key databases (separate `KeyDatabase` files, separate
@@ -41,7 +52,8 @@ pub struct EncryptedGraphDB {
     conversations_key_db: Arc<RwLock<KeyDatabase>>,
     contacts_key_db: Arc<RwLock<KeyDatabase>>,
     share_records_key_db: Arc<RwLock<KeyDatabase>>,
-    kek: Arc<Kek>,
+    /// Swapped by [`Self::rotate_kek`]. Always taken AFTER a key DB lock.
+    kek: RwLock<Arc<Kek>>,
     index_key: Arc<IndexKey>,
     /// Needed to persist `KeyDatabase` files: each key DB is encrypted at rest
     /// under the DeviceKey on every save. Without this, keys minted at runtime
@@ -72,7 +84,7 @@ impl EncryptedGraphDB {
             conversations_key_db,
             contacts_key_db,
             share_records_key_db,
-            kek,
+            kek: RwLock::new(kek),
             index_key,
             device_key,
         }
@@ -93,9 +105,10 @@ impl EncryptedGraphDB {
         plaintext: &[u8],
     ) -> DbResult<(String, String)> {
         let mut kdb = key_db.write().await;
+        let kek = self.kek.read().await;
         let (entity_key, minted) = if kdb.contains(entity_id) {
             (
-                kdb.unwrap_current(entity_id, &self.kek)
+                kdb.unwrap_current(entity_id, &kek)
                     .map_err(|e| DbError::Query(format!("key unwrap failed: {e}")))?,
                 false,
             )
@@ -105,7 +118,7 @@ impl EncryptedGraphDB {
                 .map(|keys| keys.len() as u32 + 1)
                 .unwrap_or(1);
             (
-                kdb.create_document_key(entity_id, &self.kek, epoch)
+                kdb.create_document_key(entity_id, &kek, epoch)
                     .map_err(|e| DbError::Query(format!("key creation failed: {e}")))?,
                 true,
             )
@@ -124,15 +137,15 @@ impl EncryptedGraphDB {
             })?;
         }
 
-        let (ciphertext, nonce) = aead::encrypt(plaintext, entity_key.as_bytes())
-            .map_err(|e| DbError::Query(format!("encryption failed: {e}")))?;
-
-        let b64 = base64::engine::general_purpose::STANDARD;
-        Ok((b64.encode(&ciphertext), b64.encode(&nonce)))
+        seal(&entity_key, plaintext)
     }
 
     /// Decrypt content with the given key DB. `nonce_b64` must be a 24-byte
     /// XChaCha20 nonce. Returns the plaintext as a UTF-8 String.
+    ///
+    /// Tries the entity's current key first, then older epochs: rows can
+    /// still be under a pre-rotation key (a rotation cut short, a write that
+    /// raced it, or a version-history snapshot).
     async fn decrypt_with(
         &self,
         key_db: &Arc<RwLock<KeyDatabase>>,
@@ -140,30 +153,18 @@ impl EncryptedGraphDB {
         ciphertext_b64: &str,
         nonce_b64: &str,
     ) -> DbResult<String> {
-        let b64 = base64::engine::general_purpose::STANDARD;
-        let ciphertext = b64.decode(ciphertext_b64)
-            .map_err(|e| DbError::Query(format!("base64 decode ciphertext: {e}")))?;
-        let nonce_bytes = b64.decode(nonce_b64)
-            .map_err(|e| DbError::Query(format!("base64 decode nonce: {e}")))?;
-
-        if nonce_bytes.len() != 24 {
-            return Err(DbError::Query(format!(
-                "invalid nonce length: expected 24, got {}",
-                nonce_bytes.len()
-            )));
-        }
-        let mut nonce = [0u8; 24];
-        nonce.copy_from_slice(&nonce_bytes);
-
         let kdb = key_db.read().await;
-        let entity_key = kdb.unwrap_current(entity_id, &self.kek)
+        let kek = self.kek.read().await;
+        let entity_key = kdb.unwrap_current(entity_id, &kek)
             .map_err(|e| DbError::Query(format!("key unwrap failed: {e}")))?;
-
-        let plaintext = aead::decrypt(&ciphertext, &nonce, entity_key.as_bytes())
-            .map_err(|e| DbError::Query(format!("decryption failed: {e}")))?;
-
-        String::from_utf8(plaintext)
-            .map_err(|e| DbError::Query(format!("UTF-8 decode failed: {e}")))
+        match open(&[entity_key], ciphertext_b64, nonce_b64) {
+            Err(e) if kdb.get_all(entity_id).is_some_and(|keys| keys.len() > 1) => {
+                let keys = kdb.unwrap_all(entity_id, &kek)
+                    .map_err(|e| DbError::Query(format!("key unwrap failed: {e}")))?;
+                open(&keys[1..], ciphertext_b64, nonce_b64).map_err(|_| e)
+            }
+            result => result,
+        }
     }
 
     // -- Document key-db shims (preserve existing call sites) --
@@ -215,6 +216,22 @@ impl EncryptedGraphDB {
         tokens.iter().map(|t| self.index_key.hash_token(t.as_bytes())).collect()
     }
 
+    /// Blind-index hashes for a document's plaintext content: its words plus
+    /// its `#tags`, the latter in their own hash namespace.
+    fn content_token_hashes(&self, content: &str) -> Vec<String> {
+        let text = sovereign_core::content::indexable_text(content);
+        let mut hashes: Vec<String> = index_key::tokenize(&text, DOCUMENT_TOKEN_CAP)
+            .iter()
+            .map(|t| self.index_key.hash_token(t.as_bytes()))
+            .collect();
+        hashes.extend(
+            index_key::tags(&text, DOCUMENT_TAG_CAP)
+                .iter()
+                .map(|t| self.index_key.hash_tag(t)),
+        );
+        hashes
+    }
+
     /// Decrypt a document's content and title (if encrypted). Idempotent on
     /// rows with no nonces set (treated as plaintext / legacy).
     async fn decrypt_document(&self, mut doc: Document) -> DbResult<Document> {
@@ -231,6 +248,7 @@ impl EncryptedGraphDB {
             doc.title = self.decrypt_content(&doc_id, &doc.title, &nonce).await?;
         }
         doc.title_token_hashes.clear();
+        doc.content_token_hashes.clear();
         Ok(doc)
     }
 
@@ -345,26 +363,10 @@ impl EncryptedGraphDB {
             .await
     }
 
-    /// AUTOCOMMIT-001: canonical bytes a commit's tamper-evidence MAC covers.
-    /// Excludes the `signature` field itself and the timestamp (display metadata
-    /// that need not round-trip byte-exactly); binds the document id, parent,
-    /// message and the snapshot — the tamper-relevant content. Length-prefixed.
+    /// AUTOCOMMIT-001: canonical bytes a commit's tamper-evidence MAC covers
+    /// (see [`Commit::signed_bytes`]).
     fn commit_mac_bytes(commit: &Commit) -> Vec<u8> {
-        let mut out = Vec::with_capacity(128 + commit.snapshot.content.len());
-        out.extend_from_slice(b"sovereign-commit-mac-fields:v1");
-        for field in [
-            commit.document_id.as_str(),
-            commit.parent_commit.as_deref().unwrap_or(""),
-            commit.message.as_str(),
-            commit.snapshot.document_id.as_str(),
-            commit.snapshot.title.as_str(),
-            commit.snapshot.content.as_str(),
-        ] {
-            out.extend_from_slice(&(field.len() as u32).to_le_bytes());
-            out.extend_from_slice(field.as_bytes());
-        }
-        out.push(commit.parent_commit.is_some() as u8);
-        out
+        commit.signed_bytes()
     }
 
     /// True if the commit's MAC verifies — or it's a legacy unsigned commit
@@ -382,9 +384,15 @@ impl EncryptedGraphDB {
         }
     }
 
+    /// Whether a commit passes its integrity checks: the device MAC and the
+    /// Ed25519 device signature, each where present.
+    pub fn commit_verifies(&self, commit: &Commit) -> bool {
+        self.commit_mac_ok(commit) && commit_device_signature_ok(commit) != Some(false)
+    }
+
     /// Log if a signed commit fails its integrity check (local tampering).
     fn warn_if_commit_tampered(&self, commit: &Commit) {
-        if !self.commit_mac_ok(commit) {
+        if !self.commit_verifies(commit) {
             tracing::error!(
                 "commit {} integrity check FAILED — possible local version-history tampering (AUTOCOMMIT-001)",
                 commit.id_string().unwrap_or_default()
@@ -424,6 +432,442 @@ impl EncryptedGraphDB {
     }
 }
 
+/// Encrypt under `key`; returns base64 `(ciphertext, nonce)`.
+fn seal(key: &DocumentKey, plaintext: &[u8]) -> DbResult<(String, String)> {
+    let (ciphertext, nonce) = aead::encrypt(plaintext, key.as_bytes())
+        .map_err(|e| DbError::Query(format!("encryption failed: {e}")))?;
+    let b64 = base64::engine::general_purpose::STANDARD;
+    Ok((b64.encode(&ciphertext), b64.encode(&nonce)))
+}
+
+/// Decrypt base64 ciphertext with the first of `keys` that authenticates.
+fn open(keys: &[DocumentKey], ciphertext_b64: &str, nonce_b64: &str) -> DbResult<String> {
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let ciphertext = b64.decode(ciphertext_b64)
+        .map_err(|e| DbError::Query(format!("base64 decode ciphertext: {e}")))?;
+    let nonce_bytes = b64.decode(nonce_b64)
+        .map_err(|e| DbError::Query(format!("base64 decode nonce: {e}")))?;
+
+    if nonce_bytes.len() != 24 {
+        return Err(DbError::Query(format!(
+            "invalid nonce length: expected 24, got {}",
+            nonce_bytes.len()
+        )));
+    }
+    let mut nonce = [0u8; 24];
+    nonce.copy_from_slice(&nonce_bytes);
+
+    let plaintext = keys
+        .iter()
+        .find_map(|key| aead::decrypt(&ciphertext, &nonce, key.as_bytes()).ok())
+        .ok_or_else(|| DbError::Query("decryption failed".into()))?;
+
+    String::from_utf8(plaintext)
+        .map_err(|e| DbError::Query(format!("UTF-8 decode failed: {e}")))
+}
+
+// -- Key rotation --
+
+/// What [`EncryptedGraphDB::rotate_document_keys`] re-keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationScope {
+    All,
+    Documents,
+    Threads,
+    Messages,
+    Contacts,
+    Conversations,
+    ShareRecords,
+    /// One document, by id.
+    Document(String),
+}
+
+impl RotationScope {
+    /// Short label for logs and the rotation audit trail.
+    pub fn label(&self) -> String {
+        match self {
+            // One document's id stays out of the audit trail.
+            Self::Document(_) => "document".into(),
+            other => serde_json::to_value(other)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default(),
+        }
+    }
+
+    fn kinds(&self) -> &'static [KeyedKind] {
+        use KeyedKind::*;
+        match self {
+            Self::All => &[Documents, Threads, Messages, Contacts, Conversations, ShareRecords],
+            Self::Documents | Self::Document(_) => &[Documents],
+            Self::Threads => &[Threads],
+            Self::Messages => &[Messages],
+            Self::Contacts => &[Contacts],
+            Self::Conversations => &[Conversations],
+            Self::ShareRecords => &[ShareRecords],
+        }
+    }
+}
+
+/// Reported after each entity during a document-key rotation.
+#[derive(Debug, Clone, Serialize)]
+pub struct RotationProgress {
+    /// Entity type being re-keyed ("documents", "messages", ...).
+    pub stage: &'static str,
+    pub done: u32,
+    pub total: u32,
+}
+
+/// The entity types with their own key database.
+#[derive(Debug, Clone, Copy)]
+enum KeyedKind {
+    Documents,
+    Threads,
+    Messages,
+    Contacts,
+    Conversations,
+    ShareRecords,
+}
+
+impl KeyedKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Documents => "documents",
+            Self::Threads => "threads",
+            Self::Messages => "messages",
+            Self::Contacts => "contacts",
+            Self::Conversations => "conversations",
+            Self::ShareRecords => "share_records",
+        }
+    }
+}
+
+/// Check a commit's Ed25519 device signature. `None` for commits made
+/// before device signing; a half-signed commit counts as invalid.
+pub fn commit_device_signature_ok(commit: &Commit) -> Option<bool> {
+    match (&commit.device_public_key, &commit.device_signature) {
+        (None, None) => None,
+        (Some(pk), Some(sig)) => Some(sovereign_crypto::device_signing::verify_commit_signature(
+            pk,
+            &commit.signed_bytes(),
+            sig,
+        )),
+        _ => Some(false),
+    }
+}
+
+/// A row that was deleted since its key was minted is skipped, not an error.
+fn existing<T>(row: DbResult<T>) -> DbResult<Option<T>> {
+    match row {
+        Ok(row) => Ok(Some(row)),
+        Err(DbError::NotFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Decrypt a field if it carries a nonce, else take it as stored
+/// (plaintext legacy rows get encrypted by the rotation).
+fn field(keys: &[DocumentKey], value: &str, nonce: Option<&str>) -> DbResult<String> {
+    match nonce {
+        Some(nonce) => open(keys, value, nonce),
+        None => Ok(value.to_string()),
+    }
+}
+
+impl EncryptedGraphDB {
+    fn keyed(&self, kind: KeyedKind) -> &Arc<RwLock<KeyDatabase>> {
+        match kind {
+            KeyedKind::Documents => &self.key_db,
+            KeyedKind::Threads => &self.threads_key_db,
+            KeyedKind::Messages => &self.messages_key_db,
+            KeyedKind::Contacts => &self.contacts_key_db,
+            KeyedKind::Conversations => &self.conversations_key_db,
+            KeyedKind::ShareRecords => &self.share_records_key_db,
+        }
+    }
+
+    /// Rotate the KEK: re-wrap every key in the six key databases under
+    /// `new_kek`, persist them, and use `new_kek` from then on. Holds every
+    /// key DB lock for the duration, so no key is minted under the old KEK
+    /// meanwhile. Returns the number of keys re-wrapped.
+    ///
+    /// The caller journals `new_kek` beforehand and commits it to the auth
+    /// store afterwards; if a save fails here the journal lets the next
+    /// unlock finish the job.
+    pub async fn rotate_kek(&self, new_kek: Arc<Kek>) -> DbResult<u64> {
+        let mut kdbs = Vec::with_capacity(6);
+        for kdb in [
+            &self.key_db,
+            &self.messages_key_db,
+            &self.threads_key_db,
+            &self.conversations_key_db,
+            &self.contacts_key_db,
+            &self.share_records_key_db,
+        ] {
+            kdbs.push(kdb.write().await);
+        }
+        let mut kek = self.kek.write().await;
+
+        // Re-wrap everything in memory first; undo on failure so the
+        // databases and the KEK in use never disagree.
+        let mut rewrapped = 0u64;
+        for i in 0..kdbs.len() {
+            match kdbs[i].rewrap_all(&kek, &new_kek) {
+                Ok(n) => rewrapped += n as u64,
+                Err(e) => {
+                    for done in &mut kdbs[..i] {
+                        let _ = done.rewrap_all(&new_kek, &kek);
+                    }
+                    return Err(DbError::Query(format!("KEK rotation: re-wrap failed: {e}")));
+                }
+            }
+        }
+        *kek = new_kek;
+
+        for kdb in &kdbs {
+            kdb.save(&self.device_key)
+                .map_err(|e| DbError::Query(format!("KEK rotation: key DB save failed: {e}")))?;
+        }
+        Ok(rewrapped)
+    }
+
+    /// Rotate per-entity keys in `scope`: each entity gets a new key and
+    /// its encrypted fields are re-encrypted under it, one entity at a time
+    /// (`progress` is called after each). Old keys are kept, so version
+    /// history and rows a crash left behind still decrypt. Returns the
+    /// number of entities re-keyed.
+    ///
+    /// Each entity is read, re-keyed and written back while holding its key
+    /// DB's write lock. A concurrent edit encrypts under that same lock, so
+    /// it lands either before the read or after the write and is never
+    /// overwritten with stale content.
+    pub async fn rotate_document_keys(
+        &self,
+        scope: &RotationScope,
+        progress: &(dyn Fn(RotationProgress) + Send + Sync),
+    ) -> DbResult<u64> {
+        let mut rotated = 0u64;
+        for &kind in scope.kinds() {
+            let ids: Vec<String> = match scope {
+                RotationScope::Document(id) => vec![id.clone()],
+                _ => self.keyed(kind).read().await.ids().map(str::to_string).collect(),
+            };
+            let total = ids.len() as u32;
+            for (i, id) in ids.iter().enumerate() {
+                if self.rotate_entity_key(kind, id).await? {
+                    rotated += 1;
+                }
+                progress(RotationProgress {
+                    stage: kind.as_str(),
+                    done: i as u32 + 1,
+                    total,
+                });
+            }
+        }
+        Ok(rotated)
+    }
+
+    /// Unwrap every key in the six key databases, for escrow to a newly
+    /// paired device (see [`sovereign_crypto::key_escrow`]).
+    pub async fn export_key_escrow(&self) -> DbResult<KeyEscrowBundle> {
+        let mut bundle = KeyEscrowBundle::default();
+        for &kind in RotationScope::All.kinds() {
+            // Key DB before KEK, the order every writer takes them in.
+            let kdb = self.keyed(kind).read().await;
+            let kek = self.kek.read().await;
+            let keys = kdb
+                .export_keys(&kek)
+                .map_err(|e| DbError::Query(format!("key escrow export failed: {e}")))?;
+            bundle.dbs.insert(kind.as_str().to_string(), keys);
+        }
+        Ok(bundle)
+    }
+
+    /// Wrap keys escrowed by a paired device under our KEK and persist
+    /// them; entities we already hold keep their current key. Unknown
+    /// database names are ignored. Returns the number of keys added.
+    pub async fn import_key_escrow(&self, bundle: &KeyEscrowBundle) -> DbResult<u64> {
+        let mut added = 0u64;
+        for &kind in RotationScope::All.kinds() {
+            let Some(keys) = bundle.dbs.get(kind.as_str()) else { continue };
+            let mut kdb = self.keyed(kind).write().await;
+            let kek = self.kek.read().await;
+            let n = kdb
+                .import_keys(keys, &kek)
+                .map_err(|e| DbError::Query(format!("key escrow import failed: {e}")))?;
+            if n > 0 {
+                kdb.save(&self.device_key)
+                    .map_err(|e| DbError::Query(format!("key DB save failed: {e}")))?;
+                added += n as u64;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Build the content blind index for encrypted documents written before
+    /// it existed. Rows that already carry hashes, or are still plaintext,
+    /// are skipped, so this is cheap to run at every unlock. Returns the
+    /// number of documents indexed.
+    pub async fn reindex_documents(&self) -> DbResult<u64> {
+        let pending: Vec<String> = self
+            .inner
+            .list_documents(None)
+            .await?
+            .into_iter()
+            .filter(|d| d.encryption_nonce.is_some() && d.content_token_hashes.is_empty())
+            .filter_map(|d| d.id_string())
+            .collect();
+        let mut indexed = 0u64;
+        for id in pending {
+            // Same lock as a content write, so the row cannot change between
+            // the read and the write-back below.
+            let kdb = self.key_db.write().await;
+            let kek = self.kek.read().await;
+            let Some(doc) = existing(self.inner.get_document(&id).await)? else { continue };
+            let Some(nonce) = doc.encryption_nonce.as_deref() else { continue };
+            if !doc.content_token_hashes.is_empty() || !kdb.contains(&id) {
+                continue;
+            }
+            let keys = kdb.unwrap_all(&id, &kek)
+                .map_err(|e| DbError::Query(format!("key unwrap failed: {e}")))?;
+            let content = open(&keys, &doc.content, nonce)?;
+            let hashes = self.content_token_hashes(&content);
+            if hashes.is_empty() {
+                continue;
+            }
+            self.inner
+                .set_document_content_encryption(&id, &doc.content, nonce, &hashes)
+                .await?;
+            indexed += 1;
+        }
+        Ok(indexed)
+    }
+
+    /// Re-key one entity. `Ok(false)` if it has no key or no longer exists.
+    async fn rotate_entity_key(&self, kind: KeyedKind, id: &str) -> DbResult<bool> {
+        let mut kdb = self.keyed(kind).write().await;
+        let kek = self.kek.read().await;
+        if !kdb.contains(id) {
+            return Ok(false);
+        }
+        let old = kdb.unwrap_all(id, &kek)
+            .map_err(|e| DbError::Query(format!("key unwrap failed: {e}")))?;
+        // The new key is persisted before any row is written under it.
+        let mut rotate = || -> DbResult<DocumentKey> {
+            let key = kdb.rotate_key(id, &kek)
+                .map_err(|e| DbError::Query(format!("key rotation failed: {e}")))?;
+            kdb.save(&self.device_key)
+                .map_err(|e| DbError::Query(format!("key DB save failed after rotating {id}: {e}")))?;
+            Ok(key)
+        };
+
+        match kind {
+            KeyedKind::Documents => {
+                let Some(doc) = existing(self.inner.get_document(id).await)? else { return Ok(false) };
+                let content = field(&old, &doc.content, doc.encryption_nonce.as_deref())?;
+                let title = field(&old, &doc.title, doc.title_nonce.as_deref())?;
+                let key = rotate()?;
+                let (ct, nonce) = seal(&key, content.as_bytes())?;
+                self.inner
+                    .set_document_content_encryption(id, &ct, &nonce, &self.content_token_hashes(&content))
+                    .await?;
+                let (ct, nonce) = seal(&key, title.as_bytes())?;
+                self.inner
+                    .set_document_title_encryption(id, &ct, &nonce, &self.token_hashes(&title))
+                    .await?;
+            }
+            KeyedKind::Threads => {
+                let Some(thread) = existing(self.inner.get_thread(id).await)? else { return Ok(false) };
+                let name = field(&old, &thread.name, thread.name_nonce.as_deref())?;
+                let desc = field(&old, &thread.description, thread.description_nonce.as_deref())?;
+                let key = rotate()?;
+                let (name_ct, name_nonce) = seal(&key, name.as_bytes())?;
+                let (desc_ct, desc_nonce) = seal(&key, desc.as_bytes())?;
+                self.inner
+                    .set_thread_encryption(
+                        id, &name_ct, &name_nonce, &desc_ct, &desc_nonce,
+                        &self.token_hashes(&name),
+                    )
+                    .await?;
+            }
+            KeyedKind::Messages => {
+                let Some(msg) = existing(self.inner.get_message(id).await)? else { return Ok(false) };
+                let body = field(&old, &msg.body, msg.body_nonce.as_deref())?;
+                let subject = msg.subject.as_deref()
+                    .map(|s| field(&old, s, msg.subject_nonce.as_deref()))
+                    .transpose()?;
+                let html = msg.body_html.as_deref()
+                    .map(|h| field(&old, h, msg.body_html_nonce.as_deref()))
+                    .transpose()?;
+                let key = rotate()?;
+                let combined = match &subject {
+                    Some(s) => format!("{s} {body}"),
+                    None => body.clone(),
+                };
+                let (body_ct, body_nonce) = seal(&key, body.as_bytes())?;
+                let subject_enc = subject.map(|s| seal(&key, s.as_bytes())).transpose()?;
+                let html_enc = html.map(|h| seal(&key, h.as_bytes())).transpose()?;
+                self.inner
+                    .set_message_encryption(
+                        id,
+                        &body_ct,
+                        &body_nonce,
+                        subject_enc.as_ref().map(|(ct, _)| ct.as_str()),
+                        subject_enc.as_ref().map(|(_, n)| n.as_str()),
+                        html_enc.as_ref().map(|(ct, _)| ct.as_str()),
+                        html_enc.as_ref().map(|(_, n)| n.as_str()),
+                        &self.token_hashes(&combined),
+                    )
+                    .await?;
+            }
+            KeyedKind::Contacts => {
+                let Some(contact) = existing(self.inner.get_contact(id).await)? else { return Ok(false) };
+                let name = field(&old, &contact.name, contact.name_nonce.as_deref())?;
+                let notes = field(&old, &contact.notes, contact.encryption_nonce.as_deref())?;
+                let addresses = match (&contact.addresses_encrypted, &contact.addresses_nonce) {
+                    (Some(ct), Some(nonce)) => Some(open(&old, ct, nonce)?),
+                    _ if !contact.addresses.is_empty() => Some(
+                        serde_json::to_string(&contact.addresses)
+                            .map_err(|e| DbError::Query(format!("serialize contact addresses: {e}")))?,
+                    ),
+                    _ => None,
+                };
+                let key = rotate()?;
+                let (ct, nonce) = seal(&key, name.as_bytes())?;
+                self.inner.set_contact_name_encryption(id, &ct, &nonce).await?;
+                if !notes.is_empty() {
+                    let (ct, nonce) = seal(&key, notes.as_bytes())?;
+                    self.inner.set_contact_notes_encryption(id, &ct, &nonce).await?;
+                }
+                if let Some(json) = addresses {
+                    let (ct, nonce) = seal(&key, json.as_bytes())?;
+                    self.inner.set_contact_addresses_encryption(id, &ct, &nonce).await?;
+                }
+            }
+            KeyedKind::Conversations => {
+                let Some(conv) = existing(self.inner.get_conversation(id).await)? else { return Ok(false) };
+                let title = field(&old, &conv.title, conv.title_nonce.as_deref())?;
+                let key = rotate()?;
+                let (ct, nonce) = seal(&key, title.as_bytes())?;
+                self.inner.set_conversation_title_encryption(id, &ct, &nonce).await?;
+            }
+            KeyedKind::ShareRecords => {
+                let Some(rec) = existing(self.inner.get_share_record(id).await)? else { return Ok(false) };
+                let url = rec.via_url.as_deref()
+                    .map(|u| field(&old, u, rec.via_url_nonce.as_deref()))
+                    .transpose()?;
+                let key = rotate()?;
+                if let Some(url) = url {
+                    let (ct, nonce) = seal(&key, url.as_bytes())?;
+                    self.inner.set_share_record_via_url_encryption(id, &ct, &nonce).await?;
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
 #[async_trait]
 impl GraphDB for EncryptedGraphDB {
     async fn connect(&self) -> DbResult<()> {
@@ -447,7 +891,10 @@ impl GraphDB for EncryptedGraphDB {
         // nonce the row would read back as raw ciphertext (decrypt_document
         // treats nonce-less rows as plaintext/legacy).
         let (encrypted_content, content_nonce) = self.encrypt_content(&doc_id, &created.content).await?;
-        self.inner.set_document_content_encryption(&doc_id, &encrypted_content, &content_nonce).await?;
+        let content_hashes = self.content_token_hashes(&created.content);
+        self.inner.set_document_content_encryption(
+            &doc_id, &encrypted_content, &content_nonce, &content_hashes,
+        ).await?;
 
         // Encrypt title separately and write through the dedicated setter, which
         // also stores the blind-index token hashes for search.
@@ -480,8 +927,9 @@ impl GraphDB for EncryptedGraphDB {
         }
 
         let (ct, content_nonce) = self.encrypt_content(&doc_id, &plain_content).await?;
+        let content_hashes = self.content_token_hashes(&plain_content);
         self.inner
-            .set_document_content_encryption(&doc_id, &ct, &content_nonce)
+            .set_document_content_encryption(&doc_id, &ct, &content_nonce, &content_hashes)
             .await?;
 
         let (title_ct, title_nonce) =
@@ -493,6 +941,27 @@ impl GraphDB for EncryptedGraphDB {
         Ok(true)
     }
 
+    async fn create_documents(&self, docs: Vec<Document>) -> DbResult<Vec<Document>> {
+        // One plaintext insert for the batch, then each row is overwritten
+        // with ciphertext exactly as in create_document.
+        let title_hashes: Vec<Vec<String>> = docs.iter().map(|d| self.token_hashes(&d.title)).collect();
+        let created = self.inner.create_documents(docs).await?;
+        for (doc, title_hashes) in created.iter().zip(&title_hashes) {
+            let doc_id = doc.id_string().unwrap_or_default();
+            let (ct, content_nonce) = self.encrypt_content(&doc_id, &doc.content).await?;
+            let content_hashes = self.content_token_hashes(&doc.content);
+            self.inner
+                .set_document_content_encryption(&doc_id, &ct, &content_nonce, &content_hashes)
+                .await?;
+            let (title_ct, title_nonce) =
+                self.encrypt_with(&self.key_db, &doc_id, doc.title.as_bytes()).await?;
+            self.inner
+                .set_document_title_encryption(&doc_id, &title_ct, &title_nonce, title_hashes)
+                .await?;
+        }
+        Ok(created)
+    }
+
     async fn get_document(&self, id: &str) -> DbResult<Document> {
         let doc = self.inner.get_document(id).await?;
         self.decrypt_document(doc).await
@@ -509,10 +978,17 @@ impl GraphDB for EncryptedGraphDB {
         title: Option<&str>,
         content: Option<&str>,
     ) -> DbResult<Document> {
+        // The setters below write before the inner update_document gets to
+        // check, so check here.
+        if title.is_some() || content.is_some() {
+            let current = self.inner.get_document(id).await?;
+            current.permissions.check(&Principal::Local, ActionLevel::Modify)?;
+        }
         if let Some(plaintext) = content {
             // Persist ciphertext and nonce together (see create_document).
             let (ct, nonce) = self.encrypt_content(id, plaintext).await?;
-            self.inner.set_document_content_encryption(id, &ct, &nonce).await?;
+            let hashes = self.content_token_hashes(plaintext);
+            self.inner.set_document_content_encryption(id, &ct, &nonce, &hashes).await?;
         }
 
         // Bump modified_at and fetch the row (field writes happened above).
@@ -540,6 +1016,44 @@ impl GraphDB for EncryptedGraphDB {
         self.inner.update_document_position(id, x, y).await
     }
 
+    async fn update_document_type(&self, id: &str, doc_type: DocType) -> DbResult<()> {
+        self.inner.update_document_type(id, doc_type).await
+    }
+
+    async fn set_read_later(
+        &self,
+        id: &str,
+        at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> DbResult<()> {
+        self.inner.set_read_later(id, at).await
+    }
+
+    async fn set_favorite(
+        &self,
+        id: &str,
+        favorite: bool,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> DbResult<()> {
+        self.inner.set_favorite(id, favorite, at).await
+    }
+
+    async fn set_sensitivity(
+        &self,
+        id: &str,
+        sensitivity: crate::schema::Sensitivity,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> DbResult<()> {
+        self.inner.set_sensitivity(id, sensitivity, at).await
+    }
+
+    async fn set_document_permissions(
+        &self,
+        id: &str,
+        permissions: DocumentPermissions,
+    ) -> DbResult<()> {
+        self.inner.set_document_permissions(id, permissions).await
+    }
+
     async fn search_documents_by_title(&self, query: &str) -> DbResult<Vec<Document>> {
         // Phase 2b: titles are encrypted, so the plaintext CONTAINS path can no
         // longer hit anything. Tokenize the query and route through the
@@ -560,6 +1074,35 @@ impl GraphDB for EncryptedGraphDB {
         self.decrypt_documents(docs).await
     }
 
+    async fn search_documents(&self, query: &str) -> DbResult<Vec<Document>> {
+        // Content is encrypted: match the query's tokens against the
+        // blind index instead of scanning (and decrypting) every row.
+        let hashes = self.token_hashes(query);
+        if hashes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let docs = self.inner.search_documents_by_content_token_hashes(&hashes).await?;
+        self.decrypt_documents(docs).await
+    }
+
+    async fn search_documents_by_tag(&self, tag: &str) -> DbResult<Vec<Document>> {
+        let tag = tag.trim_start_matches('#').to_lowercase();
+        if tag.is_empty() {
+            return Ok(Vec::new());
+        }
+        let hashes = [self.index_key.hash_tag(&tag)];
+        let docs = self.inner.search_documents_by_content_token_hashes(&hashes).await?;
+        self.decrypt_documents(docs).await
+    }
+
+    async fn search_documents_by_content_token_hashes(
+        &self,
+        hashes: &[String],
+    ) -> DbResult<Vec<Document>> {
+        let docs = self.inner.search_documents_by_content_token_hashes(hashes).await?;
+        self.decrypt_documents(docs).await
+    }
+
     async fn set_document_title_encryption(
         &self,
         id: &str,
@@ -577,9 +1120,10 @@ impl GraphDB for EncryptedGraphDB {
         id: &str,
         content_ciphertext: &str,
         content_nonce: &str,
+        content_token_hashes: &[String],
     ) -> DbResult<()> {
         self.inner.set_document_content_encryption(
-            id, content_ciphertext, content_nonce,
+            id, content_ciphertext, content_nonce, content_token_hashes,
         ).await
     }
 
@@ -741,6 +1285,10 @@ impl GraphDB for EncryptedGraphDB {
         self.inner.create_relationship(from_id, to_id, relation_type, strength).await
     }
 
+    async fn create_body_link(&self, from_id: &str, to_id: &str) -> DbResult<RelatedTo> {
+        self.inner.create_body_link(from_id, to_id).await
+    }
+
     async fn list_outgoing_relationships(&self, doc_id: &str) -> DbResult<Vec<RelatedTo>> {
         self.inner.list_outgoing_relationships(doc_id).await
     }
@@ -753,6 +1301,22 @@ impl GraphDB for EncryptedGraphDB {
         self.inner.list_all_relationships().await
     }
 
+    async fn delete_relationship(&self, id: &str) -> DbResult<()> {
+        self.inner.delete_relationship(id).await
+    }
+
+    async fn set_relationship_strength(
+        &self,
+        id: &str,
+        strength: f32,
+        reinforced: bool,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> DbResult<()> {
+        self.inner
+            .set_relationship_strength(id, strength, reinforced, at)
+            .await
+    }
+
     async fn traverse(&self, doc_id: &str, depth: u32, limit: u32) -> DbResult<Vec<Document>> {
         let docs = self.inner.traverse(doc_id, depth, limit).await?;
         self.decrypt_documents(docs).await
@@ -826,6 +1390,16 @@ impl GraphDB for EncryptedGraphDB {
         self.inner.restore_soft_deleted_thread(id).await
     }
 
+    async fn list_deleted_documents(&self) -> DbResult<Vec<Document>> {
+        let docs = self.inner.list_deleted_documents().await?;
+        self.decrypt_documents(docs).await
+    }
+
+    async fn list_deleted_threads(&self) -> DbResult<Vec<Thread>> {
+        let threads = self.inner.list_deleted_threads().await?;
+        self.decrypt_threads(threads).await
+    }
+
     async fn purge_deleted(&self, max_age: std::time::Duration) -> DbResult<u64> {
         self.inner.purge_deleted(max_age).await
     }
@@ -841,6 +1415,16 @@ impl GraphDB for EncryptedGraphDB {
         let mac = sovereign_crypto::mac::device_mac(&self.device_key, &Self::commit_mac_bytes(&created));
         self.inner.set_commit_signature(&commit_id, &mac).await?;
         created.signature = Some(mac);
+        // ...and an Ed25519 signature under the device's P2P identity key,
+        // which any device can check, naming which one made the snapshot.
+        let signer = sovereign_crypto::device_signing::DeviceSigner::new(&self.device_key);
+        let public_key = signer.public_key_b64();
+        let device_signature = signer.sign_commit(&created.signed_bytes());
+        self.inner
+            .set_commit_device_signature(&commit_id, &public_key, &device_signature)
+            .await?;
+        created.device_public_key = Some(public_key);
+        created.device_signature = Some(device_signature);
         Ok(created)
     }
 
@@ -852,6 +1436,10 @@ impl GraphDB for EncryptedGraphDB {
         Ok(commits)
     }
 
+    async fn prune_document_commits(&self, doc_id: &str, keep: usize) -> DbResult<u64> {
+        self.inner.prune_document_commits(doc_id, keep).await
+    }
+
     async fn get_commit(&self, commit_id: &str) -> DbResult<Commit> {
         let commit = self.inner.get_commit(commit_id).await?;
         self.warn_if_commit_tampered(&commit);
@@ -864,7 +1452,7 @@ impl GraphDB for EncryptedGraphDB {
         // live document. (Legacy unsigned commits are tolerated; see
         // commit_mac_ok.)
         let commit = self.inner.get_commit(commit_id).await?;
-        if !self.commit_mac_ok(&commit) {
+        if !self.commit_verifies(&commit) {
             return Err(DbError::Query(format!(
                 "refusing to restore from commit {commit_id}: integrity check failed (tampered or forged)"
             )));
@@ -877,6 +1465,17 @@ impl GraphDB for EncryptedGraphDB {
         self.inner.set_commit_signature(commit_id, signature).await
     }
 
+    async fn set_commit_device_signature(
+        &self,
+        commit_id: &str,
+        public_key: &str,
+        signature: &str,
+    ) -> DbResult<()> {
+        self.inner
+            .set_commit_device_signature(commit_id, public_key, signature)
+            .await
+    }
+
     async fn create_milestone(&self, milestone: Milestone) -> DbResult<Milestone> {
         self.inner.create_milestone(milestone).await
     }
@@ -893,6 +1492,35 @@ impl GraphDB for EncryptedGraphDB {
         self.inner.delete_milestone(id).await
     }
 
+    // Reminder text is a short user-written label, stored alongside the
+    // document id like milestones are.
+    async fn create_reminder(&self, reminder: Reminder) -> DbResult<Reminder> {
+        self.inner.create_reminder(reminder).await
+    }
+
+    async fn list_reminders(&self, document_id: Option<&str>) -> DbResult<Vec<Reminder>> {
+        self.inner.list_reminders(document_id).await
+    }
+
+    async fn list_due_reminders(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> DbResult<Vec<Reminder>> {
+        self.inner.list_due_reminders(now).await
+    }
+
+    async fn mark_reminder_fired(
+        &self,
+        id: &str,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> DbResult<()> {
+        self.inner.mark_reminder_fired(id, at).await
+    }
+
+    async fn delete_reminder(&self, id: &str) -> DbResult<()> {
+        self.inner.delete_reminder(id).await
+    }
+
     // -- Contacts: encrypt name (new in 2b) + notes (existed pre-2b, now under contacts key DB) ---
 
     async fn create_contact(&self, contact: Contact) -> DbResult<Contact> {
@@ -999,6 +1627,24 @@ impl GraphDB for EncryptedGraphDB {
         Ok(contact)
     }
 
+    async fn set_contact_addresses(
+        &self,
+        id: &str,
+        addresses: Vec<crate::schema::ChannelAddress>,
+    ) -> DbResult<Contact> {
+        // Clear the old blob first (an empty list encrypts to nothing),
+        // then store the new set encrypted.
+        self.inner.set_contact_addresses(id, Vec::new()).await?;
+        self.encrypt_contact_addresses(id, &addresses).await?;
+        let fresh = self.inner.get_contact(id).await?;
+        self.decrypt_contact(fresh).await
+    }
+
+    async fn set_contact_owned(&self, id: &str, is_owned: bool) -> DbResult<Contact> {
+        let updated = self.inner.set_contact_owned(id, is_owned).await?;
+        self.decrypt_contact(updated).await
+    }
+
     async fn set_contact_name_encryption(
         &self,
         id: &str,
@@ -1718,7 +2364,7 @@ mod tests {
             conversations_key_db: Arc::new(RwLock::new(KeyDatabase::new(n.join("p2p-cdwi-conv.db")))),
             contacts_key_db: Arc::new(RwLock::new(KeyDatabase::new(n.join("p2p-cdwi-con.db")))),
             share_records_key_db: Arc::new(RwLock::new(KeyDatabase::new(n.join("p2p-cdwi-shr.db")))),
-            kek: Arc::new(Kek::from_bytes(*kek.as_bytes())),
+            kek: RwLock::new(Arc::new(Kek::from_bytes(*kek.as_bytes()))),
             index_key: Arc::new(IndexKey::generate()),
             device_key: Arc::new(test_device_key()),
         }
@@ -1767,7 +2413,7 @@ mod tests {
             conversations_key_db: Arc::new(RwLock::new(KeyDatabase::new(std::env::temp_dir().join("test-encrypted-db-convkeys-a.db")))),
             contacts_key_db: Arc::new(RwLock::new(KeyDatabase::new(std::env::temp_dir().join("test-encrypted-db-conkeys-a.db")))),
             share_records_key_db: Arc::new(RwLock::new(KeyDatabase::new(std::env::temp_dir().join("test-encrypted-db-shrkeys-a.db")))),
-            kek: Arc::new(Kek::from_bytes(*kek.as_bytes())),
+            kek: RwLock::new(Arc::new(Kek::from_bytes(*kek.as_bytes()))),
             index_key: Arc::new(IndexKey::generate()),
             device_key: Arc::new(test_device_key()),
         };
@@ -1795,7 +2441,7 @@ mod tests {
             conversations_key_db: Arc::new(RwLock::new(KeyDatabase::new(std::env::temp_dir().join("test-encrypted-db-convkeys-b.db")))),
             contacts_key_db: Arc::new(RwLock::new(KeyDatabase::new(std::env::temp_dir().join("test-encrypted-db-conkeys-b.db")))),
             share_records_key_db: Arc::new(RwLock::new(KeyDatabase::new(std::env::temp_dir().join("test-encrypted-db-shrkeys-b.db")))),
-            kek: Arc::new(Kek::from_bytes(*kek.as_bytes())),
+            kek: RwLock::new(Arc::new(Kek::from_bytes(*kek.as_bytes()))),
             index_key: Arc::new(IndexKey::generate()),
             device_key: Arc::new(test_device_key()),
         };
@@ -1825,7 +2471,7 @@ mod tests {
             conversations_key_db: conv_db1.clone(),
             contacts_key_db: con_db1.clone(),
             share_records_key_db: shr_db1.clone(),
-            kek: Arc::new(Kek::from_bytes(*kek1.as_bytes())),
+            kek: RwLock::new(Arc::new(Kek::from_bytes(*kek1.as_bytes()))),
             index_key: Arc::new(IndexKey::generate()),
             device_key: Arc::new(test_device_key()),
         };
@@ -1841,7 +2487,7 @@ mod tests {
             conversations_key_db: conv_db1.clone(),
             contacts_key_db: con_db1.clone(),
             share_records_key_db: shr_db1.clone(),
-            kek: Arc::new(Kek::from_bytes(*kek2.as_bytes())),
+            kek: RwLock::new(Arc::new(Kek::from_bytes(*kek2.as_bytes()))),
             index_key: Arc::new(IndexKey::generate()),
             device_key: Arc::new(test_device_key()),
         };
@@ -1859,15 +2505,24 @@ mod tests {
         async fn init_schema(&self) -> DbResult<()> { Ok(()) }
         async fn create_document(&self, doc: Document) -> DbResult<Document> { Ok(doc) }
         async fn create_document_with_id(&self, _doc: Document) -> DbResult<bool> { Ok(true) }
+        async fn create_documents(&self, docs: Vec<Document>) -> DbResult<Vec<Document>> { Ok(docs) }
         async fn get_document(&self, _id: &str) -> DbResult<Document> { Err(DbError::NotFound("mock".into())) }
         async fn list_documents(&self, _thread_id: Option<&str>) -> DbResult<Vec<Document>> { Ok(vec![]) }
         async fn update_document(&self, _id: &str, _title: Option<&str>, _content: Option<&str>) -> DbResult<Document> { Err(DbError::NotFound("mock".into())) }
         async fn delete_document(&self, _id: &str) -> DbResult<()> { Ok(()) }
         async fn update_document_position(&self, _id: &str, _x: f32, _y: f32) -> DbResult<()> { Ok(()) }
+        async fn update_document_type(&self, _id: &str, _doc_type: DocType) -> DbResult<()> { Ok(()) }
+        async fn set_read_later(&self, _id: &str, _at: Option<chrono::DateTime<chrono::Utc>>) -> DbResult<()> { Ok(()) }
+        async fn set_favorite(&self, _id: &str, _favorite: bool, _at: chrono::DateTime<chrono::Utc>) -> DbResult<()> { Ok(()) }
+        async fn set_sensitivity(&self, _id: &str, _sensitivity: crate::schema::Sensitivity, _at: chrono::DateTime<chrono::Utc>) -> DbResult<()> { Ok(()) }
+        async fn set_document_permissions(&self, _id: &str, _permissions: DocumentPermissions) -> DbResult<()> { Ok(()) }
         async fn search_documents_by_title(&self, _query: &str) -> DbResult<Vec<Document>> { Ok(vec![]) }
         async fn search_documents_by_title_token_hashes(&self, _hashes: &[String]) -> DbResult<Vec<Document>> { Ok(vec![]) }
+        async fn search_documents(&self, _query: &str) -> DbResult<Vec<Document>> { Ok(vec![]) }
+        async fn search_documents_by_tag(&self, _tag: &str) -> DbResult<Vec<Document>> { Ok(vec![]) }
+        async fn search_documents_by_content_token_hashes(&self, _hashes: &[String]) -> DbResult<Vec<Document>> { Ok(vec![]) }
         async fn set_document_title_encryption(&self, _id: &str, _title_ciphertext: &str, _title_nonce: &str, _title_token_hashes: &[String]) -> DbResult<()> { Ok(()) }
-        async fn set_document_content_encryption(&self, _id: &str, _content_ciphertext: &str, _content_nonce: &str) -> DbResult<()> { Ok(()) }
+        async fn set_document_content_encryption(&self, _id: &str, _content_ciphertext: &str, _content_nonce: &str, _content_token_hashes: &[String]) -> DbResult<()> { Ok(()) }
         async fn update_document_reliability(&self, _id: &str, _source_url: Option<&str>, _classification: Option<&str>, _score: Option<f32>, _assessment_json: Option<&str>) -> DbResult<Document> { Err(DbError::NotFound("mock".into())) }
         async fn create_suggested_link(&self, _from_id: &str, _to_id: &str, _relation_type: RelationType, _strength: f32, _rationale: &str, _source: SuggestionSource) -> DbResult<SuggestedLink> { Err(DbError::NotFound("mock".into())) }
         async fn list_pending_suggestions(&self) -> DbResult<Vec<SuggestedLink>> { Ok(vec![]) }
@@ -1884,9 +2539,12 @@ mod tests {
         async fn set_thread_encryption(&self, _id: &str, _name_ciphertext: &str, _name_nonce: &str, _description_ciphertext: &str, _description_nonce: &str, _name_token_hashes: &[String]) -> DbResult<()> { Ok(()) }
         async fn move_document_to_thread(&self, _doc_id: &str, _new_thread_id: &str) -> DbResult<Document> { Err(DbError::NotFound("mock".into())) }
         async fn create_relationship(&self, _from_id: &str, _to_id: &str, _relation_type: RelationType, _strength: f32) -> DbResult<RelatedTo> { Err(DbError::NotFound("mock".into())) }
+        async fn create_body_link(&self, _from_id: &str, _to_id: &str) -> DbResult<RelatedTo> { Err(DbError::NotFound("mock".into())) }
         async fn list_outgoing_relationships(&self, _doc_id: &str) -> DbResult<Vec<RelatedTo>> { Ok(vec![]) }
         async fn list_incoming_relationships(&self, _doc_id: &str) -> DbResult<Vec<RelatedTo>> { Ok(vec![]) }
         async fn list_all_relationships(&self) -> DbResult<Vec<RelatedTo>> { Ok(vec![]) }
+        async fn delete_relationship(&self, _id: &str) -> DbResult<()> { Ok(()) }
+        async fn set_relationship_strength(&self, _id: &str, _strength: f32, _reinforced: bool, _at: chrono::DateTime<chrono::Utc>) -> DbResult<()> { Ok(()) }
         async fn traverse(&self, _doc_id: &str, _depth: u32, _limit: u32) -> DbResult<Vec<Document>> { Ok(vec![]) }
         async fn adopt_document(&self, _id: &str) -> DbResult<Document> { Err(DbError::NotFound("mock".into())) }
         async fn merge_threads(&self, _target_id: &str, _source_id: &str) -> DbResult<()> { Ok(()) }
@@ -1895,16 +2553,25 @@ mod tests {
         async fn restore_soft_deleted_document(&self, _id: &str) -> DbResult<Document> { Err(DbError::NotFound("mock".into())) }
         async fn soft_delete_thread(&self, _id: &str) -> DbResult<()> { Ok(()) }
         async fn restore_soft_deleted_thread(&self, _id: &str) -> DbResult<Thread> { Err(DbError::NotFound("mock".into())) }
+        async fn list_deleted_documents(&self) -> DbResult<Vec<Document>> { Ok(vec![]) }
+        async fn list_deleted_threads(&self) -> DbResult<Vec<Thread>> { Ok(vec![]) }
         async fn purge_deleted(&self, _max_age: std::time::Duration) -> DbResult<u64> { Ok(0) }
         async fn commit_document(&self, _doc_id: &str, _message: &str) -> DbResult<Commit> { Err(DbError::NotFound("mock".into())) }
         async fn list_document_commits(&self, _doc_id: &str) -> DbResult<Vec<Commit>> { Ok(vec![]) }
+        async fn prune_document_commits(&self, _doc_id: &str, _keep: usize) -> DbResult<u64> { Ok(0) }
         async fn get_commit(&self, _commit_id: &str) -> DbResult<Commit> { Err(DbError::NotFound("mock".into())) }
         async fn restore_document(&self, _doc_id: &str, _commit_id: &str) -> DbResult<Document> { Err(DbError::NotFound("mock".into())) }
         async fn set_commit_signature(&self, _commit_id: &str, _signature: &str) -> DbResult<()> { Ok(()) }
+        async fn set_commit_device_signature(&self, _commit_id: &str, _public_key: &str, _signature: &str) -> DbResult<()> { Ok(()) }
         async fn create_milestone(&self, milestone: Milestone) -> DbResult<Milestone> { Ok(milestone) }
         async fn list_milestones(&self, _thread_id: &str) -> DbResult<Vec<Milestone>> { Ok(vec![]) }
         async fn list_all_milestones(&self) -> DbResult<Vec<Milestone>> { Ok(vec![]) }
         async fn delete_milestone(&self, _id: &str) -> DbResult<()> { Ok(()) }
+        async fn create_reminder(&self, reminder: Reminder) -> DbResult<Reminder> { Ok(reminder) }
+        async fn list_reminders(&self, _document_id: Option<&str>) -> DbResult<Vec<Reminder>> { Ok(vec![]) }
+        async fn list_due_reminders(&self, _now: chrono::DateTime<chrono::Utc>) -> DbResult<Vec<Reminder>> { Ok(vec![]) }
+        async fn mark_reminder_fired(&self, _id: &str, _at: chrono::DateTime<chrono::Utc>) -> DbResult<()> { Ok(()) }
+        async fn delete_reminder(&self, _id: &str) -> DbResult<()> { Ok(()) }
         // Contacts
         async fn create_contact(&self, contact: Contact) -> DbResult<Contact> { Ok(contact) }
         async fn get_contact(&self, _id: &str) -> DbResult<Contact> { Err(DbError::NotFound("mock".into())) }
@@ -1914,6 +2581,8 @@ mod tests {
         async fn soft_delete_contact(&self, _id: &str) -> DbResult<()> { Ok(()) }
         async fn find_contact_by_address(&self, _address: &str) -> DbResult<Option<Contact>> { Ok(None) }
         async fn add_contact_address(&self, _contact_id: &str, _address: crate::schema::ChannelAddress) -> DbResult<Contact> { Err(DbError::NotFound("mock".into())) }
+        async fn set_contact_addresses(&self, _id: &str, _addresses: Vec<crate::schema::ChannelAddress>) -> DbResult<Contact> { Err(DbError::NotFound("mock".into())) }
+        async fn set_contact_owned(&self, _id: &str, _is_owned: bool) -> DbResult<Contact> { Err(DbError::NotFound("mock".into())) }
         async fn set_contact_name_encryption(&self, _id: &str, _name_ciphertext: &str, _name_nonce: &str) -> DbResult<()> { Ok(()) }
         async fn set_contact_addresses_encryption(&self, _id: &str, _ciphertext: &str, _nonce: &str) -> DbResult<()> { Ok(()) }
         async fn set_contact_notes_encryption(&self, _id: &str, _notes_ciphertext: &str, _notes_nonce: &str) -> DbResult<()> { Ok(()) }
@@ -2010,7 +2679,7 @@ mod tests {
             conversations_key_db: mk_kdb("conv"),
             contacts_key_db: mk_kdb("con"),
             share_records_key_db: mk_kdb("shr"),
-            kek: Arc::new(Kek::from_bytes(*kek.as_bytes())),
+            kek: RwLock::new(Arc::new(Kek::from_bytes(*kek.as_bytes()))),
             index_key: Arc::new(IndexKey::generate()),
             device_key: Arc::new(test_device_key()),
         };
@@ -2188,6 +2857,60 @@ mod tests {
         assert!(miss.is_empty());
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn document_content_and_tag_search() {
+        let (inner, edb) = build_encrypted_db("doc-content");
+        let mut doc = Document::new("Notes".into(), "thread:1".into(), true);
+        doc.content = r##"{"body":"Ship the migration before #launch","images":[{"path":"a.png","caption":"whiteboard sketch"}]}"##.into();
+        let d1 = edb.create_document(doc).await.unwrap();
+        let id = d1.id_string().unwrap();
+
+        let raw = inner.get_document(&id).await.unwrap();
+        assert!(!raw.content.contains("migration"), "content at rest must be ciphertext");
+        assert!(!raw.content_token_hashes.is_empty());
+
+        // All tokens must match; captions are indexed; the JSON keys are not.
+        assert_eq!(edb.search_documents("migration ship").await.unwrap().len(), 1);
+        assert_eq!(edb.search_documents("whiteboard").await.unwrap().len(), 1);
+        assert!(edb.search_documents("migration submarine").await.unwrap().is_empty());
+        assert!(edb.search_documents("images").await.unwrap().is_empty());
+
+        // Tags live in their own namespace: "#launch" is a tag, "launch" a word.
+        let hits = edb.search_documents_by_tag("#Launch").await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].content.contains("#launch"));
+        assert!(hits[0].content_token_hashes.is_empty(), "decrypted view hides the index");
+        assert!(edb.search_documents_by_tag("ship").await.unwrap().is_empty());
+
+        // Updating content re-indexes it.
+        edb.update_document(&id, None, Some(r#"{"body":"Postponed #later","images":[]}"#))
+            .await
+            .unwrap();
+        assert!(edb.search_documents("migration").await.unwrap().is_empty());
+        assert_eq!(edb.search_documents_by_tag("later").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn reindex_documents_backfills_missing_content_index() {
+        let (inner, edb) = build_encrypted_db("doc-reindex");
+        let mut doc = Document::new("Old".into(), "thread:1".into(), true);
+        doc.content = r#"{"body":"legacy archive","images":[]}"#.into();
+        let id = edb.create_document(doc).await.unwrap().id_string().unwrap();
+
+        // Simulate a row encrypted before the content index existed.
+        let raw = inner.get_document(&id).await.unwrap();
+        inner
+            .set_document_content_encryption(&id, &raw.content, raw.encryption_nonce.as_deref().unwrap(), &[])
+            .await
+            .unwrap();
+        assert!(edb.search_documents("archive").await.unwrap().is_empty());
+
+        assert_eq!(edb.reindex_documents().await.unwrap(), 1);
+        assert_eq!(edb.search_documents("archive").await.unwrap().len(), 1);
+        assert_eq!(edb.get_document(&id).await.unwrap().content, r#"{"body":"legacy archive","images":[]}"#);
+        assert_eq!(edb.reindex_documents().await.unwrap(), 0);
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn document_content_roundtrip_persists_nonce() {
         let (inner, edb) = build_encrypted_db("doc-content");
@@ -2391,6 +3114,31 @@ mod tests {
         assert!(edb.commit_mac_ok(&legacy), "legacy unsigned commit is tolerated");
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn commits_carry_a_verifiable_device_signature() {
+        let (_inner, edb) = build_encrypted_db("commit-device-sig");
+        let doc = edb
+            .create_document(Document::new("D".into(), "thread:t".into(), true))
+            .await
+            .unwrap();
+        let doc_id = doc.id_string().unwrap();
+        edb.commit_document(&doc_id, "snapshot").await.unwrap();
+
+        let stored = edb.list_document_commits(&doc_id).await.unwrap().remove(0);
+        assert!(stored.device_public_key.is_some());
+        assert_eq!(commit_device_signature_ok(&stored), Some(true));
+
+        let mut tampered = stored.clone();
+        tampered.message = "forged".into();
+        assert_eq!(commit_device_signature_ok(&tampered), Some(false));
+
+        let mut stripped = stored.clone();
+        stripped.device_signature = None;
+        assert_eq!(commit_device_signature_ok(&stripped), Some(false));
+        stripped.device_public_key = None;
+        assert_eq!(commit_device_signature_ok(&stripped), None);
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn contact_update_re_encrypts_fields() {
         let (_, edb) = build_encrypted_db("contact-update");
@@ -2522,7 +3270,7 @@ mod tests {
             conversations_key_db: Arc::new(RwLock::new(KeyDatabase::new(dir.join("keys.conv.db")))),
             contacts_key_db: Arc::new(RwLock::new(KeyDatabase::new(dir.join("keys.contacts.db")))),
             share_records_key_db: Arc::new(RwLock::new(KeyDatabase::new(dir.join("keys.shares.db")))),
-            kek: Arc::new(Kek::from_bytes(*kek.as_bytes())),
+            kek: RwLock::new(Arc::new(Kek::from_bytes(*kek.as_bytes()))),
             index_key: Arc::new(IndexKey::generate()),
             device_key: dk_arc,
         };
@@ -2578,7 +3326,7 @@ mod tests {
             conversations_key_db: Arc::new(RwLock::new(KeyDatabase::new(dir.join("keys.conv.db")))),
             contacts_key_db: Arc::new(RwLock::new(KeyDatabase::new(dir.join("keys.contacts.db")))),
             share_records_key_db: Arc::new(RwLock::new(KeyDatabase::new(dir.join("keys.shares.db")))),
-            kek: Arc::new(Kek::from_bytes(kek_bytes)),
+            kek: RwLock::new(Arc::new(Kek::from_bytes(kek_bytes))),
             index_key: Arc::new(IndexKey::generate()), // index key is irrelevant for raw decrypt
             device_key: Arc::new(test_device_key()),
         };
@@ -2604,4 +3352,65 @@ mod tests {
 
         let _ = std::fs::remove_dir_all(&dir);
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn rotate_document_keys_reencrypts_rows() {
+        let (inner, edb) = build_encrypted_db("rotate-docs");
+        let doc = edb.create_document(Document::new("Ledger".into(), "default".into(), false)).await.unwrap();
+        let doc_id = doc.id_string().unwrap();
+        let thread = edb.create_thread(Thread::new("Taxes".into(), "2026".into())).await.unwrap();
+        let thread_id = thread.id_string().unwrap();
+        let before = inner.get_document(&doc_id).await.unwrap();
+
+        let steps = std::sync::Mutex::new(Vec::new());
+        let rotated = edb
+            .rotate_document_keys(&RotationScope::Documents, &|p| steps.lock().unwrap().push(p.done))
+            .await
+            .unwrap();
+        assert_eq!(rotated, 1);
+        assert_eq!(*steps.lock().unwrap(), vec![1]);
+
+        // New ciphertext under a second epoch; the old key is kept.
+        let after = inner.get_document(&doc_id).await.unwrap();
+        assert_ne!(after.title, before.title);
+        assert_eq!(edb.key_db.read().await.get_all(&doc_id).unwrap().len(), 2);
+        assert_eq!(edb.get_document(&doc_id).await.unwrap().title, "Ledger");
+        assert!(!edb.search_documents_by_title("ledger").await.unwrap().is_empty());
+
+        // Out of scope: untouched.
+        assert_eq!(edb.threads_key_db.read().await.get_all(&thread_id).unwrap().len(), 1);
+
+        // A row still under the previous epoch decrypts through the fallback.
+        let old_kdb = edb.key_db.read().await;
+        let kek = edb.kek.read().await;
+        let old_key = old_kdb.unwrap_all(&doc_id, &kek).unwrap().pop().unwrap();
+        drop((old_kdb, kek));
+        let (ct, nonce) = seal(&old_key, b"stale").unwrap();
+        assert_eq!(edb.decrypt_content(&doc_id, &ct, &nonce).await.unwrap(), "stale");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn rotate_kek_rewraps_every_key_db() {
+        let (_inner, edb) = build_encrypted_db("rotate-kek");
+        let doc = edb.create_document(Document::new("Will".into(), "default".into(), false)).await.unwrap();
+        let doc_id = doc.id_string().unwrap();
+        let c = edb.create_contact(Contact::new("Bob".into(), false)).await.unwrap();
+        let contact_id = c.id_string().unwrap();
+
+        let new_kek = Arc::new(Kek::generate());
+        assert_eq!(edb.rotate_kek(new_kek.clone()).await.unwrap(), 2);
+
+        assert!(edb.key_db.read().await.unwrap_current(&doc_id, &new_kek).is_ok());
+        assert!(edb.contacts_key_db.read().await.unwrap_current(&contact_id, &new_kek).is_ok());
+        assert_eq!(edb.get_document(&doc_id).await.unwrap().title, "Will");
+        assert_eq!(edb.get_contact(&contact_id).await.unwrap().name, "Bob");
+
+        // Keys minted afterwards are wrapped under the new KEK too.
+        let later = edb.create_document(Document::new("Codicil".into(), "default".into(), false)).await.unwrap();
+        let later_id = later.id_string().unwrap();
+        assert!(edb.key_db.read().await.unwrap_current(&later_id, &new_kek).is_ok());
+
+        // Rotating again to the same KEK re-wraps nothing.
+        assert_eq!(edb.rotate_kek(new_kek).await.unwrap(), 0);
+    }
 }
@@ -20,6 +20,9 @@ pub enum DbError {
     #[error("Serialization error: {0}")]
     Serialization(String),
 
+    #[error("Permission denied: {0}")]
+    PermissionDenied(#[from] sovereign_core::security::PermissionDenied),
+
     #[error("SurrealDB error: {0}")]
     Surreal(#[from] surrealdb::Error),
 }
@@ -24,9 +24,10 @@ use chrono::{DateTime, Utc};
 
 use crate::error::DbResult;
 use crate::schema::{
-    ChannelType, Commit, Contact, Conversation, Document, Entity, EntityKind, Message, Milestone,
-    PiiRecord, ReadStatus, RelatedTo, RelationType, ReviewState, ShareRecord, SourceRef,
-    SuggestedLink, SuggestionSource, SuggestionStatus, Thread,
+    ChannelType, Commit, Contact, Conversation, DocType, Document, DocumentPermissions, Entity,
+    EntityKind, Message, Milestone, PiiRecord, ReadStatus, RelatedTo, RelationType, Reminder,
+    ReviewState, Sensitivity, ShareRecord, SourceRef, SuggestedLink, SuggestionSource,
+    SuggestionStatus, Thread,
 };
 use crate::traits::GraphDB;
 
@@ -86,18 +87,27 @@ impl GraphDB for ArcWrapper {
 
     async fn create_document(&self, doc: Document) -> DbResult<Document> { self.0.create_document(doc).await }
     async fn create_document_with_id(&self, doc: Document) -> DbResult<bool> { self.0.create_document_with_id(doc).await }
+    async fn create_documents(&self, docs: Vec<Document>) -> DbResult<Vec<Document>> { self.0.create_documents(docs).await }
     async fn get_document(&self, id: &str) -> DbResult<Document> { self.0.get_document(id).await }
     async fn list_documents(&self, thread_id: Option<&str>) -> DbResult<Vec<Document>> { self.0.list_documents(thread_id).await }
     async fn update_document(&self, id: &str, title: Option<&str>, content: Option<&str>) -> DbResult<Document> { self.0.update_document(id, title, content).await }
     async fn delete_document(&self, id: &str) -> DbResult<()> { self.0.delete_document(id).await }
     async fn update_document_position(&self, id: &str, x: f32, y: f32) -> DbResult<()> { self.0.update_document_position(id, x, y).await }
+    async fn update_document_type(&self, id: &str, doc_type: DocType) -> DbResult<()> { self.0.update_document_type(id, doc_type).await }
+    async fn set_read_later(&self, id: &str, at: Option<DateTime<Utc>>) -> DbResult<()> { self.0.set_read_later(id, at).await }
+    async fn set_favorite(&self, id: &str, favorite: bool, at: DateTime<Utc>) -> DbResult<()> { self.0.set_favorite(id, favorite, at).await }
+    async fn set_sensitivity(&self, id: &str, sensitivity: Sensitivity, at: DateTime<Utc>) -> DbResult<()> { self.0.set_sensitivity(id, sensitivity, at).await }
+    async fn set_document_permissions(&self, id: &str, permissions: DocumentPermissions) -> DbResult<()> { self.0.set_document_permissions(id, permissions).await }
     async fn search_documents_by_title(&self, query: &str) -> DbResult<Vec<Document>> { self.0.search_documents_by_title(query).await }
     async fn search_documents_by_title_token_hashes(&self, hashes: &[String]) -> DbResult<Vec<Document>> { self.0.search_documents_by_title_token_hashes(hashes).await }
+    async fn search_documents(&self, query: &str) -> DbResult<Vec<Document>> { self.0.search_documents(query).await }
+    async fn search_documents_by_tag(&self, tag: &str) -> DbResult<Vec<Document>> { self.0.search_documents_by_tag(tag).await }
+    async fn search_documents_by_content_token_hashes(&self, hashes: &[String]) -> DbResult<Vec<Document>> { self.0.search_documents_by_content_token_hashes(hashes).await }
     async fn set_document_title_encryption(&self, id: &str, title_ciphertext: &str, title_nonce: &str, title_token_hashes: &[String]) -> DbResult<()> {
         self.0.set_document_title_encryption(id, title_ciphertext, title_nonce, title_token_hashes).await
     }
-    async fn set_document_content_encryption(&self, id: &str, content_ciphertext: &str, content_nonce: &str) -> DbResult<()> {
-        self.0.set_document_content_encryption(id, content_ciphertext, content_nonce).await
+    async fn set_document_content_encryption(&self, id: &str, content_ciphertext: &str, content_nonce: &str, content_token_hashes: &[String]) -> DbResult<()> {
+        self.0.set_document_content_encryption(id, content_ciphertext, content_nonce, content_token_hashes).await
     }
     async fn update_document_reliability(&self, id: &str, source_url: Option<&str>, classification: Option<&str>, score: Option<f32>, assessment_json: Option<&str>) -> DbResult<Document> {
         self.0.update_document_reliability(id, source_url, classification, score, assessment_json).await
@@ -116,9 +126,12 @@ impl GraphDB for ArcWrapper {
     async fn move_document_to_thread(&self, doc_id: &str, new_thread_id: &str) -> DbResult<Document> { self.0.move_document_to_thread(doc_id, new_thread_id).await }
 
     async fn create_relationship(&self, from_id: &str, to_id: &str, relation_type: RelationType, strength: f32) -> DbResult<RelatedTo> { self.0.create_relationship(from_id, to_id, relation_type, strength).await }
+    async fn create_body_link(&self, from_id: &str, to_id: &str) -> DbResult<RelatedTo> { self.0.create_body_link(from_id, to_id).await }
     async fn list_outgoing_relationships(&self, doc_id: &str) -> DbResult<Vec<RelatedTo>> { self.0.list_outgoing_relationships(doc_id).await }
     async fn list_incoming_relationships(&self, doc_id: &str) -> DbResult<Vec<RelatedTo>> { self.0.list_incoming_relationships(doc_id).await }
     async fn list_all_relationships(&self) -> DbResult<Vec<RelatedTo>> { self.0.list_all_relationships().await }
+    async fn delete_relationship(&self, id: &str) -> DbResult<()> { self.0.delete_relationship(id).await }
+    async fn set_relationship_strength(&self, id: &str, strength: f32, reinforced: bool, at: DateTime<Utc>) -> DbResult<()> { self.0.set_relationship_strength(id, strength, reinforced, at).await }
     async fn traverse(&self, doc_id: &str, depth: u32, limit: u32) -> DbResult<Vec<Document>> { self.0.traverse(doc_id, depth, limit).await }
 
     async fn create_suggested_link(&self, from_id: &str, to_id: &str, relation_type: RelationType, strength: f32, rationale: &str, source: SuggestionSource) -> DbResult<SuggestedLink> {
@@ -138,18 +151,27 @@ impl GraphDB for ArcWrapper {
     async fn restore_soft_deleted_document(&self, id: &str) -> DbResult<Document> { self.0.restore_soft_deleted_document(id).await }
     async fn soft_delete_thread(&self, id: &str) -> DbResult<()> { self.0.soft_delete_thread(id).await }
     async fn restore_soft_deleted_thread(&self, id: &str) -> DbResult<Thread> { self.0.restore_soft_deleted_thread(id).await }
+    async fn list_deleted_documents(&self) -> DbResult<Vec<Document>> { self.0.list_deleted_documents().await }
+    async fn list_deleted_threads(&self) -> DbResult<Vec<Thread>> { self.0.list_deleted_threads().await }
     async fn purge_deleted(&self, max_age: std::time::Duration) -> DbResult<u64> { self.0.purge_deleted(max_age).await }
 
     async fn commit_document(&self, doc_id: &str, message: &str) -> DbResult<Commit> { self.0.commit_document(doc_id, message).await }
     async fn list_document_commits(&self, doc_id: &str) -> DbResult<Vec<Commit>> { self.0.list_document_commits(doc_id).await }
+    async fn prune_document_commits(&self, doc_id: &str, keep: usize) -> DbResult<u64> { self.0.prune_document_commits(doc_id, keep).await }
     async fn get_commit(&self, commit_id: &str) -> DbResult<Commit> { self.0.get_commit(commit_id).await }
     async fn restore_document(&self, doc_id: &str, commit_id: &str) -> DbResult<Document> { self.0.restore_document(doc_id, commit_id).await }
     async fn set_commit_signature(&self, commit_id: &str, signature: &str) -> DbResult<()> { self.0.set_commit_signature(commit_id, signature).await }
+    async fn set_commit_device_signature(&self, commit_id: &str, public_key: &str, signature: &str) -> DbResult<()> { self.0.set_commit_device_signature(commit_id, public_key, signature).await }
 
     async fn create_milestone(&self, milestone: Milestone) -> DbResult<Milestone> { self.0.create_milestone(milestone).await }
     async fn list_milestones(&self, thread_id: &str) -> DbResult<Vec<Milestone>> { self.0.list_milestones(thread_id).await }
     async fn list_all_milestones(&self) -> DbResult<Vec<Milestone>> { self.0.list_all_milestones().await }
     async fn delete_milestone(&self, id: &str) -> DbResult<()> { self.0.delete_milestone(id).await }
+    async fn create_reminder(&self, reminder: Reminder) -> DbResult<Reminder> { self.0.create_reminder(reminder).await }
+    async fn list_reminders(&self, document_id: Option<&str>) -> DbResult<Vec<Reminder>> { self.0.list_reminders(document_id).await }
+    async fn list_due_reminders(&self, now: DateTime<Utc>) -> DbResult<Vec<Reminder>> { self.0.list_due_reminders(now).await }
+    async fn mark_reminder_fired(&self, id: &str, at: DateTime<Utc>) -> DbResult<()> { self.0.mark_reminder_fired(id, at).await }
+    async fn delete_reminder(&self, id: &str) -> DbResult<()> { self.0.delete_reminder(id).await }
 
     async fn create_contact(&self, contact: Contact) -> DbResult<Contact> { self.0.create_contact(contact).await }
     async fn get_contact(&self, id: &str) -> DbResult<Contact> { self.0.get_contact(id).await }
@@ -162,6 +184,8 @@ impl GraphDB for ArcWrapper {
     async fn soft_delete_contact(&self, id: &str) -> DbResult<()> { self.0.soft_delete_contact(id).await }
     async fn find_contact_by_address(&self, address: &str) -> DbResult<Option<Contact>> { self.0.find_contact_by_address(address).await }
     async fn add_contact_address(&self, contact_id: &str, address: crate::schema::ChannelAddress) -> DbResult<Contact> { self.0.add_contact_address(contact_id, address).await }
+    async fn set_contact_addresses(&self, id: &str, addresses: Vec<crate::schema::ChannelAddress>) -> DbResult<Contact> { self.0.set_contact_addresses(id, addresses).await }
+    async fn set_contact_owned(&self, id: &str, is_owned: bool) -> DbResult<Contact> { self.0.set_contact_owned(id, is_owned).await }
 
     async fn create_message(&self, message: Message) -> DbResult<Message> { self.0.create_message(message).await }
     async fn get_message(&self, id: &str) -> DbResult<Message> { self.0.get_message(id).await }
@@ -234,18 +258,27 @@ impl GraphDB for LayeredGraphDB {
 
     async fn create_document(&self, doc: Document) -> DbResult<Document> { self.current().create_document(doc).await }
     async fn create_document_with_id(&self, doc: Document) -> DbResult<bool> { self.current().create_document_with_id(doc).await }
+    async fn create_documents(&self, docs: Vec<Document>) -> DbResult<Vec<Document>> { self.current().create_documents(docs).await }
     async fn get_document(&self, id: &str) -> DbResult<Document> { self.current().get_document(id).await }
     async fn list_documents(&self, thread_id: Option<&str>) -> DbResult<Vec<Document>> { self.current().list_documents(thread_id).await }
     async fn update_document(&self, id: &str, title: Option<&str>, content: Option<&str>) -> DbResult<Document> { self.current().update_document(id, title, content).await }
     async fn delete_document(&self, id: &str) -> DbResult<()> { self.current().delete_document(id).await }
     async fn update_document_position(&self, id: &str, x: f32, y: f32) -> DbResult<()> { self.current().update_document_position(id, x, y).await }
+    async fn update_document_type(&self, id: &str, doc_type: DocType) -> DbResult<()> { self.current().update_document_type(id, doc_type).await }
+    async fn set_read_later(&self, id: &str, at: Option<DateTime<Utc>>) -> DbResult<()> { self.current().set_read_later(id, at).await }
+    async fn set_favorite(&self, id: &str, favorite: bool, at: DateTime<Utc>) -> DbResult<()> { self.current().set_favorite(id, favorite, at).await }
+    async fn set_sensitivity(&self, id: &str, sensitivity: Sensitivity, at: DateTime<Utc>) -> DbResult<()> { self.current().set_sensitivity(id, sensitivity, at).await }
+    async fn set_document_permissions(&self, id: &str, permissions: DocumentPermissions) -> DbResult<()> { self.current().set_document_permissions(id, permissions).await }
     async fn search_documents_by_title(&self, query: &str) -> DbResult<Vec<Document>> { self.current().search_documents_by_title(query).await }
     async fn search_documents_by_title_token_hashes(&self, hashes: &[String]) -> DbResult<Vec<Document>> { self.current().search_documents_by_title_token_hashes(hashes).await }
+    async fn search_documents(&self, query: &str) -> DbResult<Vec<Document>> { self.current().search_documents(query).await }
+    async fn search_documents_by_tag(&self, tag: &str) -> DbResult<Vec<Document>> { self.current().search_documents_by_tag(tag).await }
+    async fn search_documents_by_content_token_hashes(&self, hashes: &[String]) -> DbResult<Vec<Document>> { self.current().search_documents_by_content_token_hashes(hashes).await }
     async fn set_document_title_encryption(&self, id: &str, title_ciphertext: &str, title_nonce: &str, title_token_hashes: &[String]) -> DbResult<()> {
         self.current().set_document_title_encryption(id, title_ciphertext, title_nonce, title_token_hashes).await
     }
-    async fn set_document_content_encryption(&self, id: &str, content_ciphertext: &str, content_nonce: &str) -> DbResult<()> {
-        self.current().set_document_content_encryption(id, content_ciphertext, content_nonce).await
+    async fn set_document_content_encryption(&self, id: &str, content_ciphertext: &str, content_nonce: &str, content_token_hashes: &[String]) -> DbResult<()> {
+        self.current().set_document_content_encryption(id, content_ciphertext, content_nonce, content_token_hashes).await
     }
     async fn update_document_reliability(&self, id: &str, source_url: Option<&str>, classification: Option<&str>, score: Option<f32>, assessment_json: Option<&str>) -> DbResult<Document> {
         self.current().update_document_reliability(id, source_url, classification, score, assessment_json).await
@@ -264,9 +297,12 @@ impl GraphDB for LayeredGraphDB {
     async fn move_document_to_thread(&self, doc_id: &str, new_thread_id: &str) -> DbResult<Document> { self.current().move_document_to_thread(doc_id, new_thread_id).await }
 
     async fn create_relationship(&self, from_id: &str, to_id: &str, relation_type: RelationType, strength: f32) -> DbResult<RelatedTo> { self.current().create_relationship(from_id, to_id, relation_type, strength).await }
+    async fn create_body_link(&self, from_id: &str, to_id: &str) -> DbResult<RelatedTo> { self.current().create_body_link(from_id, to_id).await }
     async fn list_outgoing_relationships(&self, doc_id: &str) -> DbResult<Vec<RelatedTo>> { self.current().list_outgoing_relationships(doc_id).await }
     async fn list_incoming_relationships(&self, doc_id: &str) -> DbResult<Vec<RelatedTo>> { self.current().list_incoming_relationships(doc_id).await }
     async fn list_all_relationships(&self) -> DbResult<Vec<RelatedTo>> { self.current().list_all_relationships().await }
+    async fn delete_relationship(&self, id: &str) -> DbResult<()> { self.current().delete_relationship(id).await }
+    async fn set_relationship_strength(&self, id: &str, strength: f32, reinforced: bool, at: DateTime<Utc>) -> DbResult<()> { self.current().set_relationship_strength(id, strength, reinforced, at).await }
     async fn traverse(&self, doc_id: &str, depth: u32, limit: u32) -> DbResult<Vec<Document>> { self.current().traverse(doc_id, depth, limit).await }
 
     async fn create_suggested_link(&self, from_id: &str, to_id: &str, relation_type: RelationType, strength: f32, rationale: &str, source: SuggestionSource) -> DbResult<SuggestedLink> {
@@ -286,18 +322,27 @@ impl GraphDB for LayeredGraphDB {
     async fn restore_soft_deleted_document(&self, id: &str) -> DbResult<Document> { self.current().restore_soft_deleted_document(id).await }
     async fn soft_delete_thread(&self, id: &str) -> DbResult<()> { self.current().soft_delete_thread(id).await }
     async fn restore_soft_deleted_thread(&self, id: &str) -> DbResult<Thread> { self.current().restore_soft_deleted_thread(id).await }
+    async fn list_deleted_documents(&self) -> DbResult<Vec<Document>> { self.current().list_deleted_documents().await }
+    async fn list_deleted_threads(&self) -> DbResult<Vec<Thread>> { self.current().list_deleted_threads().await }
     async fn purge_deleted(&self, max_age: std::time::Duration) -> DbResult<u64> { self.current().purge_deleted(max_age).await }
 
     async fn commit_document(&self, doc_id: &str, message: &str) -> DbResult<Commit> { self.current().commit_document(doc_id, message).await }
     async fn list_document_commits(&self, doc_id: &str) -> DbResult<Vec<Commit>> { self.current().list_document_commits(doc_id).await }
+    async fn prune_document_commits(&self, doc_id: &str, keep: usize) -> DbResult<u64> { self.current().prune_document_commits(doc_id, keep).await }
     async fn get_commit(&self, commit_id: &str) -> DbResult<Commit> { self.current().get_commit(commit_id).await }
     async fn restore_document(&self, doc_id: &str, commit_id: &str) -> DbResult<Document> { self.current().restore_document(doc_id, commit_id).await }
     async fn set_commit_signature(&self, commit_id: &str, signature: &str) -> DbResult<()> { self.current().set_commit_signature(commit_id, signature).await }
+    async fn set_commit_device_signature(&self, commit_id: &str, public_key: &str, signature: &str) -> DbResult<()> { self.current().set_commit_device_signature(commit_id, public_key, signature).await }
 
     async fn create_milestone(&self, milestone: Milestone) -> DbResult<Milestone> { self.current().create_milestone(milestone).await }
     async fn list_milestones(&self, thread_id: &str) -> DbResult<Vec<Milestone>> { self.current().list_milestones(thread_id).await }
     async fn list_all_milestones(&self) -> DbResult<Vec<Milestone>> { self.current().list_all_milestones().await }
     async fn delete_milestone(&self, id: &str) -> DbResult<()> { self.current().delete_milestone(id).await }
+    async fn create_reminder(&self, reminder: Reminder) -> DbResult<Reminder> { self.current().create_reminder(reminder).await }
+    async fn list_reminders(&self, document_id: Option<&str>) -> DbResult<Vec<Reminder>> { self.current().list_reminders(document_id).await }
+    async fn list_due_reminders(&self, now: DateTime<Utc>) -> DbResult<Vec<Reminder>> { self.current().list_due_reminders(now).await }
+    async fn mark_reminder_fired(&self, id: &str, at: DateTime<Utc>) -> DbResult<()> { self.current().mark_reminder_fired(id, at).await }
+    async fn delete_reminder(&self, id: &str) -> DbResult<()> { self.current().delete_reminder(id).await }
 
     async fn create_contact(&self, contact: Contact) -> DbResult<Contact> { self.current().create_contact(contact).await }
     async fn get_contact(&self, id: &str) -> DbResult<Contact> { self.current().get_contact(id).await }
@@ -310,6 +355,8 @@ impl GraphDB for LayeredGraphDB {
     async fn soft_delete_contact(&self, id: &str) -> DbResult<()> { self.current().soft_delete_contact(id).await }
     async fn find_contact_by_address(&self, address: &str) -> DbResult<Option<Contact>> { self.current().find_contact_by_address(address).await }
     async fn add_contact_address(&self, contact_id: &str, address: crate::schema::ChannelAddress) -> DbResult<Contact> { self.current().add_contact_address(contact_id, address).await }
+    async fn set_contact_addresses(&self, id: &str, addresses: Vec<crate::schema::ChannelAddress>) -> DbResult<Contact> { self.current().set_contact_addresses(id, addresses).await }
+    async fn set_contact_owned(&self, id: &str, is_owned: bool) -> DbResult<Contact> { self.current().set_contact_owned(id, is_owned).await }
 
     async fn create_message(&self, message: Message) -> DbResult<Message> { self.current().create_message(message).await }
     async fn get_message(&self, id: &str) -> DbResult<Message> { self.current().get_message(id).await }
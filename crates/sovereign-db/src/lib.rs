@@ -1,7 +1,11 @@
+pub mod analytics;
 pub mod error;
 pub mod layered;
+pub mod links;
 pub mod schema;
+pub mod sdk;
 pub mod surreal;
+pub mod tasks;
 pub mod traits;
 
 #[cfg(feature = "encryption")]
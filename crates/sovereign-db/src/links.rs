@@ -0,0 +1,288 @@
+//! Keeps the graph in step with the links written in bodies.
+//!
+//! Every `[[Title]]` or `@mention` that names another live document gets
+//! a `References` edge (see `sovereign_sdk::links` for the syntax). The
+//! edges are marked [`RelatedTo::from_body`]; [`sync_links`] adds and
+//! removes only those, so edges made by hand or accepted from suggestions
+//! are never touched. When a document is renamed, [`retitle_links`]
+//! rewrites the links pointing at it so they keep resolving, and
+//! [`backlinks`] lists what points at it.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sovereign_sdk::content::ContentFields;
+use sovereign_sdk::links;
+
+use crate::error::{DbError, DbResult};
+use crate::schema::{thing_to_raw, RelatedTo, RelationType};
+use crate::traits::GraphDB;
+
+/// What [`sync_links`] changed, as target document ids.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LinkSync {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// A document pointing at another one, by any incoming edge.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Backlink {
+    pub relationship_id: String,
+    pub document_id: String,
+    pub title: String,
+    pub thread_id: String,
+    pub relation_type: RelationType,
+    pub strength: f32,
+    pub from_body: bool,
+    /// For a link in the body, the line it is written on.
+    pub excerpt: Option<String>,
+}
+
+const EXCERPT_CHARS: usize = 160;
+
+/// Match the links in `body` (the body of `doc_id`) to documents and
+/// bring its body-link edges in line. Links naming no document, or the
+/// document itself, are left alone; where two documents share a title
+/// the more recently modified one wins.
+pub async fn sync_links(db: &dyn GraphDB, doc_id: &str, body: &str) -> DbResult<LinkSync> {
+    let written = links::parse(body);
+    let mut targets: HashSet<String> = HashSet::new();
+    if !written.is_empty() {
+        let mut by_key: HashMap<String, (String, DateTime<Utc>)> = HashMap::new();
+        for doc in db.list_documents(None).await? {
+            let Some(id) = doc.id_string() else { continue };
+            let key = links::key(&doc.title);
+            if id == doc_id || key.is_empty() {
+                continue;
+            }
+            let entry = by_key
+                .entry(key)
+                .or_insert_with(|| (id.clone(), doc.modified_at));
+            if doc.modified_at > entry.1 {
+                *entry = (id, doc.modified_at);
+            }
+        }
+        targets = written
+            .iter()
+            .filter_map(|link| by_key.get(&links::key(&link.target)))
+            .map(|(id, _)| id.clone())
+            .collect();
+    }
+
+    let mut sync = LinkSync::default();
+    let mut linked: HashSet<String> = HashSet::new();
+    for rel in db.list_outgoing_relationships(doc_id).await? {
+        let Some(to) = rel.out.as_ref().map(thing_to_raw) else {
+            continue;
+        };
+        if rel.from_body && !targets.contains(&to) {
+            if let Some(id) = rel.id_string() {
+                db.delete_relationship(&id).await?;
+                sync.removed.push(to);
+            }
+        } else if rel.relation_type == RelationType::References {
+            linked.insert(to);
+        }
+    }
+    let mut new: Vec<String> = targets.difference(&linked).cloned().collect();
+    new.sort();
+    for to in new {
+        db.create_body_link(doc_id, &to).await?;
+        sync.added.push(to);
+    }
+    Ok(sync)
+}
+
+/// `doc_id` was renamed from `old_title` to `new_title`: rewrite the links
+/// to it in the documents that have them. Returns the documents changed;
+/// ones this account may not edit keep the old title.
+pub async fn retitle_links(
+    db: &dyn GraphDB,
+    doc_id: &str,
+    old_title: &str,
+    new_title: &str,
+) -> DbResult<Vec<String>> {
+    if links::key(old_title) == links::key(new_title) {
+        return Ok(Vec::new());
+    }
+    let mut changed = Vec::new();
+    for rel in db.list_incoming_relationships(doc_id).await? {
+        let Some(from) = rel.in_.as_ref().map(thing_to_raw).filter(|_| rel.from_body) else {
+            continue;
+        };
+        let doc = db.get_document(&from).await?;
+        let mut fields = ContentFields::parse(&doc.content);
+        let Some(body) = links::retitle(&fields.body, old_title, new_title) else {
+            continue;
+        };
+        fields.body = body;
+        match db
+            .update_document(&from, None, Some(&fields.serialize()))
+            .await
+        {
+            Ok(_) => changed.push(from),
+            Err(DbError::PermissionDenied(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(changed)
+}
+
+/// The live documents with an edge into `doc_id`, newest edge first.
+/// Edges from deleted or missing documents are skipped.
+pub async fn backlinks(db: &dyn GraphDB, doc_id: &str) -> DbResult<Vec<Backlink>> {
+    let target = db.get_document(doc_id).await?;
+    let target_key = links::key(&target.title);
+    let mut incoming = db.list_incoming_relationships(doc_id).await?;
+    incoming.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    let mut out = Vec::new();
+    for rel in incoming {
+        let (Some(relationship_id), Some(from)) =
+            (rel.id_string(), rel.in_.as_ref().map(thing_to_raw))
+        else {
+            continue;
+        };
+        let doc = match db.get_document(&from).await {
+            Ok(doc) if doc.deleted_at.is_none() => doc,
+            Ok(_) | Err(DbError::NotFound(_)) => continue,
+            Err(e) => return Err(e),
+        };
+        let excerpt = rel
+            .from_body
+            .then(|| link_line(&ContentFields::parse(&doc.content).body, &target_key))
+            .flatten();
+        out.push(Backlink {
+            relationship_id,
+            document_id: from,
+            title: doc.title,
+            thread_id: doc.thread_id,
+            relation_type: rel.relation_type,
+            strength: rel.strength,
+            from_body: rel.from_body,
+            excerpt,
+        });
+    }
+    Ok(out)
+}
+
+/// The trimmed line holding the body's first link whose target has `key`.
+fn link_line(body: &str, key: &str) -> Option<String> {
+    let link = links::parse(body)
+        .into_iter()
+        .find(|l| links::key(&l.target) == key)?;
+    let start = body[..link.span.start].rfind('\n').map_or(0, |i| i + 1);
+    let end = body[link.span.end..]
+        .find('\n')
+        .map_or(body.len(), |i| link.span.end + i);
+    let line = body[start..end].trim();
+    if line.chars().count() <= EXCERPT_CHARS {
+        return Some(line.to_string());
+    }
+    let cut: String = line.chars().take(EXCERPT_CHARS).collect();
+    Some(format!("{}…", cut.trim_end()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockGraphDB;
+    use crate::schema::Document;
+
+    async fn doc(db: &MockGraphDB, title: &str, body: &str) -> String {
+        let mut doc = Document::new(title.into(), "thread:t".into(), true);
+        doc.content = ContentFields {
+            body: body.into(),
+            ..Default::default()
+        }
+        .serialize();
+        db.create_document(doc).await.unwrap().id_string().unwrap()
+    }
+
+    #[tokio::test]
+    async fn sync_adds_and_removes_only_body_links() {
+        let db = MockGraphDB::new();
+        let notes = doc(&db, "Meeting notes", "").await;
+        let apollo = doc(&db, "Project Apollo", "").await;
+        let budget = doc(&db, "Budget", "").await;
+        db.create_relationship(&notes, &budget, RelationType::Supports, 0.5)
+            .await
+            .unwrap();
+
+        let body = "About [[project apollo]], see @budget and [[Nowhere]].";
+        let sync = sync_links(&db, &notes, body).await.unwrap();
+        let mut added = sync.added.clone();
+        added.sort();
+        let mut expected = vec![apollo.clone(), budget.clone()];
+        expected.sort();
+        assert_eq!(added, expected);
+        // Saving again changes nothing.
+        assert_eq!(
+            sync_links(&db, &notes, body).await.unwrap(),
+            LinkSync::default()
+        );
+
+        let sync = sync_links(&db, &notes, "Only @Project-Apollo now.")
+            .await
+            .unwrap();
+        assert_eq!(sync.removed, [budget.clone()]);
+        let left: Vec<(String, bool)> = db
+            .list_outgoing_relationships(&notes)
+            .await
+            .unwrap()
+            .iter()
+            .map(|r| (thing_to_raw(r.out.as_ref().unwrap()), r.from_body))
+            .collect();
+        assert_eq!(left.len(), 2);
+        assert!(left.contains(&(apollo, true)));
+        assert!(left.contains(&(budget, false)), "the manual edge stays");
+    }
+
+    #[tokio::test]
+    async fn retitle_rewrites_linking_bodies() {
+        let db = MockGraphDB::new();
+        let target = doc(&db, "Old Plan", "").await;
+        let source = doc(&db, "Log", "Per [[Old Plan|the plan]] and @old-plan.").await;
+        sync_links(&db, &source, "Per [[Old Plan|the plan]] and @old-plan.")
+            .await
+            .unwrap();
+
+        let changed = retitle_links(&db, &target, "Old Plan", "Launch Plan")
+            .await
+            .unwrap();
+        assert_eq!(changed, [source.clone()]);
+        let body = ContentFields::parse(&db.get_document(&source).await.unwrap().content).body;
+        assert_eq!(body, "Per [[Launch Plan|the plan]] and @Launch-Plan.");
+    }
+
+    #[tokio::test]
+    async fn backlinks_list_every_incoming_edge() {
+        let db = MockGraphDB::new();
+        let target = doc(&db, "Budget", "").await;
+        let body = "# Log\nWe agreed on the [[Budget]] today.\nMore later.";
+        let linking = doc(&db, "Log", body).await;
+        let manual = doc(&db, "Review", "").await;
+        let gone = doc(&db, "Old", "").await;
+        sync_links(&db, &linking, body).await.unwrap();
+        db.create_relationship(&manual, &target, RelationType::Supports, 0.8)
+            .await
+            .unwrap();
+        db.create_relationship(&gone, &target, RelationType::References, 0.5)
+            .await
+            .unwrap();
+        db.soft_delete_document(&gone).await.unwrap();
+
+        let found = backlinks(&db, &target).await.unwrap();
+        assert_eq!(found.len(), 2);
+        let from_body = found.iter().find(|b| b.document_id == linking).unwrap();
+        assert!(from_body.from_body);
+        assert_eq!(
+            from_body.excerpt.as_deref(),
+            Some("We agreed on the [[Budget]] today.")
+        );
+        let by_hand = found.iter().find(|b| b.document_id == manual).unwrap();
+        assert_eq!(by_hand.relation_type, RelationType::Supports);
+        assert_eq!(by_hand.excerpt, None);
+    }
+}
@@ -26,6 +26,7 @@ pub struct MockGraphDB {
     pii_records: RwLock<HashMap<String, PiiRecord>>,
     share_records: RwLock<HashMap<String, ShareRecord>>,
     milestones: RwLock<HashMap<String, Milestone>>,
+    reminders: RwLock<HashMap<String, Reminder>>,
     next_id: AtomicU64,
 }
 
@@ -44,6 +45,7 @@ impl MockGraphDB {
             pii_records: RwLock::new(HashMap::new()),
             share_records: RwLock::new(HashMap::new()),
             milestones: RwLock::new(HashMap::new()),
+            reminders: RwLock::new(HashMap::new()),
             next_id: AtomicU64::new(1),
         }
     }
@@ -83,6 +85,14 @@ impl GraphDB for MockGraphDB {
         Ok(true)
     }
 
+    async fn create_documents(&self, docs: Vec<Document>) -> DbResult<Vec<Document>> {
+        let mut created = Vec::with_capacity(docs.len());
+        for doc in docs {
+            created.push(self.create_document(doc).await?);
+        }
+        Ok(created)
+    }
+
     async fn get_document(&self, id: &str) -> DbResult<Document> {
         self.documents.read().unwrap()
             .get(id)
@@ -104,6 +114,7 @@ impl GraphDB for MockGraphDB {
     async fn update_document(&self, id: &str, title: Option<&str>, content: Option<&str>) -> DbResult<Document> {
         let mut docs = self.documents.write().unwrap();
         let doc = docs.get_mut(id).ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        doc.permissions.check(&Principal::Local, ActionLevel::Modify)?;
         if let Some(t) = title { doc.title = t.to_string(); }
         if let Some(c) = content { doc.content = c.to_string(); }
         doc.modified_at = Utc::now();
@@ -138,8 +149,59 @@ impl GraphDB for MockGraphDB {
         Ok(())
     }
 
+    async fn update_document_type(&self, id: &str, doc_type: DocType) -> DbResult<()> {
+        let mut docs = self.documents.write().unwrap();
+        let doc = docs.get_mut(id).ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        doc.permissions.check(&Principal::Local, ActionLevel::Modify)?;
+        doc.doc_type = doc_type;
+        Ok(())
+    }
+
+    async fn set_read_later(&self, id: &str, at: Option<DateTime<Utc>>) -> DbResult<()> {
+        let mut docs = self.documents.write().unwrap();
+        let doc = docs.get_mut(id).ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        doc.permissions.check(&Principal::Local, ActionLevel::Annotate)?;
+        doc.read_later = at;
+        Ok(())
+    }
+
+    async fn set_favorite(&self, id: &str, favorite: bool, at: DateTime<Utc>) -> DbResult<()> {
+        let mut docs = self.documents.write().unwrap();
+        let doc = docs.get_mut(id).ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        doc.permissions.check(&Principal::Local, ActionLevel::Annotate)?;
+        doc.favorite = favorite;
+        doc.favorite_at = Some(at);
+        Ok(())
+    }
+
+    async fn set_sensitivity(
+        &self,
+        id: &str,
+        sensitivity: Sensitivity,
+        at: DateTime<Utc>,
+    ) -> DbResult<()> {
+        let mut docs = self.documents.write().unwrap();
+        let doc = docs.get_mut(id).ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        doc.permissions.check(&Principal::Local, ActionLevel::Annotate)?;
+        doc.sensitivity = sensitivity;
+        doc.sensitivity_at = Some(at);
+        Ok(())
+    }
+
+    async fn set_document_permissions(&self, id: &str, permissions: DocumentPermissions) -> DbResult<()> {
+        let mut docs = self.documents.write().unwrap();
+        let doc = docs.get_mut(id).ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        doc.permissions.check(&Principal::Local, ActionLevel::Transmit)?;
+        doc.permissions = permissions;
+        Ok(())
+    }
+
     async fn delete_document(&self, id: &str) -> DbResult<()> {
-        self.documents.write().unwrap().remove(id);
+        let mut docs = self.documents.write().unwrap();
+        if let Some(doc) = docs.get(id) {
+            doc.permissions.check(&Principal::Local, ActionLevel::Destruct)?;
+        }
+        docs.remove(id);
         Ok(())
     }
 
@@ -176,6 +238,51 @@ impl GraphDB for MockGraphDB {
         Ok(result)
     }
 
+    async fn search_documents(&self, query: &str) -> DbResult<Vec<Document>> {
+        let q = query.to_lowercase();
+        let docs = self.documents.read().unwrap();
+        let mut result: Vec<Document> = docs.values()
+            .filter(|d| d.deleted_at.is_none())
+            .filter(|d| d.content.to_lowercase().contains(&q))
+            .cloned()
+            .collect();
+        result.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        if result.len() > 20 { result.truncate(20); }
+        Ok(result)
+    }
+
+    async fn search_documents_by_tag(&self, tag: &str) -> DbResult<Vec<Document>> {
+        let tag = tag.trim_start_matches('#').to_lowercase();
+        let docs = self.documents.read().unwrap();
+        let mut result: Vec<Document> = docs.values()
+            .filter(|d| d.deleted_at.is_none() && d.has_tag(&tag))
+            .cloned()
+            .collect();
+        result.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        if result.len() > 20 { result.truncate(20); }
+        Ok(result)
+    }
+
+    async fn search_documents_by_content_token_hashes(
+        &self,
+        hashes: &[String],
+    ) -> DbResult<Vec<Document>> {
+        if hashes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let docs = self.documents.read().unwrap();
+        let mut result: Vec<Document> = docs.values()
+            .filter(|d| {
+                d.deleted_at.is_none()
+                    && hashes.iter().all(|h| d.content_token_hashes.contains(h))
+            })
+            .cloned()
+            .collect();
+        result.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        if result.len() > 20 { result.truncate(20); }
+        Ok(result)
+    }
+
     async fn set_document_title_encryption(
         &self,
         id: &str,
@@ -197,12 +304,14 @@ impl GraphDB for MockGraphDB {
         id: &str,
         content_ciphertext: &str,
         content_nonce: &str,
+        content_token_hashes: &[String],
     ) -> DbResult<()> {
         let mut docs = self.documents.write().unwrap();
         let doc = docs.get_mut(id)
             .ok_or_else(|| DbError::NotFound(id.to_string()))?;
         doc.content = content_ciphertext.to_string();
         doc.encryption_nonce = Some(content_nonce.to_string());
+        doc.content_token_hashes = content_token_hashes.to_vec();
         Ok(())
     }
 
@@ -291,6 +400,7 @@ impl GraphDB for MockGraphDB {
     async fn move_document_to_thread(&self, doc_id: &str, new_thread_id: &str) -> DbResult<Document> {
         let mut docs = self.documents.write().unwrap();
         let doc = docs.get_mut(doc_id).ok_or_else(|| DbError::NotFound(doc_id.to_string()))?;
+        doc.permissions.check(&Principal::Local, ActionLevel::Modify)?;
         doc.thread_id = new_thread_id.to_string();
         doc.modified_at = Utc::now();
         Ok(doc.clone())
@@ -306,16 +416,61 @@ impl GraphDB for MockGraphDB {
             relation_type,
             strength,
             created_at: Utc::now(),
+            from_body: false,
+            reinforced_at: None,
+            adjusted_at: None,
         };
         self.relationships.write().unwrap().push(rel.clone());
         Ok(rel)
     }
 
-    async fn list_outgoing_relationships(&self, _doc_id: &str) -> DbResult<Vec<RelatedTo>> { Ok(vec![]) }
-    async fn list_incoming_relationships(&self, _doc_id: &str) -> DbResult<Vec<RelatedTo>> { Ok(vec![]) }
+    async fn create_body_link(&self, from_id: &str, to_id: &str) -> DbResult<RelatedTo> {
+        let rel = RelatedTo {
+            id: Some(Self::make_thing("related_to", &self.next_key())),
+            in_: raw_to_thing(from_id),
+            out: raw_to_thing(to_id),
+            relation_type: RelationType::References,
+            strength: RelatedTo::BODY_LINK_STRENGTH,
+            created_at: Utc::now(),
+            from_body: true,
+            reinforced_at: None,
+            adjusted_at: None,
+        };
+        self.relationships.write().unwrap().push(rel.clone());
+        Ok(rel)
+    }
+
+    async fn list_outgoing_relationships(&self, doc_id: &str) -> DbResult<Vec<RelatedTo>> {
+        let rels = self.relationships.read().unwrap();
+        Ok(rels.iter().filter(|r| r.in_.as_ref().map(thing_to_raw).as_deref() == Some(doc_id)).cloned().collect())
+    }
+    async fn list_incoming_relationships(&self, doc_id: &str) -> DbResult<Vec<RelatedTo>> {
+        let rels = self.relationships.read().unwrap();
+        Ok(rels.iter().filter(|r| r.out.as_ref().map(thing_to_raw).as_deref() == Some(doc_id)).cloned().collect())
+    }
     async fn list_all_relationships(&self) -> DbResult<Vec<RelatedTo>> {
         Ok(self.relationships.read().unwrap().clone())
     }
+    async fn delete_relationship(&self, id: &str) -> DbResult<()> {
+        self.relationships
+            .write()
+            .unwrap()
+            .retain(|r| r.id.as_ref().map(thing_to_raw).as_deref() != Some(id));
+        Ok(())
+    }
+    async fn set_relationship_strength(&self, id: &str, strength: f32, reinforced: bool, at: DateTime<Utc>) -> DbResult<()> {
+        let mut rels = self.relationships.write().unwrap();
+        let rel = rels
+            .iter_mut()
+            .find(|r| r.id.as_ref().map(thing_to_raw).as_deref() == Some(id))
+            .ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        rel.strength = strength;
+        rel.adjusted_at = Some(at);
+        if reinforced {
+            rel.reinforced_at = Some(at);
+        }
+        Ok(())
+    }
     async fn traverse(&self, _doc_id: &str, _depth: u32, _limit: u32) -> DbResult<Vec<Document>> { Ok(vec![]) }
 
     // -- Suggested Links ---
@@ -440,6 +595,7 @@ impl GraphDB for MockGraphDB {
     async fn soft_delete_document(&self, id: &str) -> DbResult<()> {
         let mut docs = self.documents.write().unwrap();
         if let Some(doc) = docs.get_mut(id) {
+            doc.permissions.check(&Principal::Local, ActionLevel::Destruct)?;
             doc.deleted_at = Some(Utc::now().to_rfc3339());
         }
         Ok(())
@@ -467,7 +623,36 @@ impl GraphDB for MockGraphDB {
         Ok(thread.clone())
     }
 
-    async fn purge_deleted(&self, _max_age: std::time::Duration) -> DbResult<u64> { Ok(0) }
+    async fn list_deleted_documents(&self) -> DbResult<Vec<Document>> {
+        let docs = self.documents.read().unwrap();
+        let mut result: Vec<Document> = docs.values()
+            .filter(|d| d.deleted_at.is_some())
+            .cloned()
+            .collect();
+        result.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+        Ok(result)
+    }
+
+    async fn list_deleted_threads(&self) -> DbResult<Vec<Thread>> {
+        let threads = self.threads.read().unwrap();
+        let mut result: Vec<Thread> = threads.values()
+            .filter(|t| t.deleted_at.is_some())
+            .cloned()
+            .collect();
+        result.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+        Ok(result)
+    }
+
+    async fn purge_deleted(&self, max_age: std::time::Duration) -> DbResult<u64> {
+        let cutoff = (Utc::now() - chrono::Duration::seconds(max_age.as_secs() as i64)).to_rfc3339();
+        let expired = |deleted_at: &Option<String>| deleted_at.as_ref().is_some_and(|at| *at <= cutoff);
+        let mut docs = self.documents.write().unwrap();
+        let mut threads = self.threads.write().unwrap();
+        let before = docs.len() + threads.len();
+        docs.retain(|_, d| !expired(&d.deleted_at));
+        threads.retain(|_, t| !expired(&t.deleted_at));
+        Ok((before - docs.len() - threads.len()) as u64)
+    }
 
     async fn commit_document(&self, doc_id: &str, message: &str) -> DbResult<Commit> {
         let docs = self.documents.read().unwrap();
@@ -497,6 +682,8 @@ impl GraphDB for MockGraphDB {
             },
             timestamp: Utc::now(),
             signature: None,
+            device_public_key: None,
+            device_signature: None,
         };
 
         let mut commits = self.commits.write().unwrap();
@@ -519,6 +706,21 @@ impl GraphDB for MockGraphDB {
         Ok(result)
     }
 
+    async fn prune_document_commits(&self, doc_id: &str, keep: usize) -> DbResult<u64> {
+        let head = self.documents.read().unwrap().get(doc_id).and_then(|d| d.head_commit.clone());
+        let mut commits = self.commits.write().unwrap();
+        let Some(doc_commits) = commits.get_mut(doc_id) else { return Ok(0) };
+        // Stored oldest first.
+        let cutoff = doc_commits.len().saturating_sub(keep);
+        let before = doc_commits.len();
+        let mut index = 0;
+        doc_commits.retain(|c| {
+            index += 1;
+            index > cutoff || c.id.as_ref().map(thing_to_raw) == head
+        });
+        Ok((before - doc_commits.len()) as u64)
+    }
+
     async fn get_commit(&self, commit_id: &str) -> DbResult<Commit> {
         let commits = self.commits.read().unwrap();
         for doc_commits in commits.values() {
@@ -535,6 +737,7 @@ impl GraphDB for MockGraphDB {
         let commit = self.get_commit(commit_id).await?;
         let mut docs = self.documents.write().unwrap();
         let doc = docs.get_mut(doc_id).ok_or_else(|| DbError::NotFound(doc_id.to_string()))?;
+        doc.permissions.check(&Principal::Local, ActionLevel::Modify)?;
         doc.title = commit.snapshot.title;
         doc.content = commit.snapshot.content;
         Ok(doc.clone())
@@ -553,6 +756,25 @@ impl GraphDB for MockGraphDB {
         Err(DbError::NotFound(commit_id.to_string()))
     }
 
+    async fn set_commit_device_signature(
+        &self,
+        commit_id: &str,
+        public_key: &str,
+        signature: &str,
+    ) -> DbResult<()> {
+        let mut commits = self.commits.write().unwrap();
+        for list in commits.values_mut() {
+            for c in list.iter_mut() {
+                if c.id.as_ref().map(thing_to_raw).as_deref() == Some(commit_id) {
+                    c.device_public_key = Some(public_key.to_string());
+                    c.device_signature = Some(signature.to_string());
+                    return Ok(());
+                }
+            }
+        }
+        Err(DbError::NotFound(commit_id.to_string()))
+    }
+
     async fn create_milestone(&self, mut milestone: Milestone) -> DbResult<Milestone> {
         let key = self.next_key();
         let thing = Self::make_thing("milestone", &key);
@@ -584,6 +806,53 @@ impl GraphDB for MockGraphDB {
         Ok(())
     }
 
+    async fn create_reminder(&self, mut reminder: Reminder) -> DbResult<Reminder> {
+        {
+            let docs = self.documents.read().unwrap();
+            let doc = docs.get(&reminder.document_id)
+                .ok_or_else(|| DbError::NotFound(reminder.document_id.clone()))?;
+            doc.permissions.check(&Principal::Local, ActionLevel::Annotate)?;
+        }
+        let key = self.next_key();
+        let thing = Self::make_thing("reminder", &key);
+        let id_str = thing_to_raw(&thing);
+        reminder.id = Some(thing);
+        self.reminders.write().unwrap().insert(id_str, reminder.clone());
+        Ok(reminder)
+    }
+
+    async fn list_reminders(&self, document_id: Option<&str>) -> DbResult<Vec<Reminder>> {
+        let rs = self.reminders.read().unwrap();
+        let mut out: Vec<Reminder> = rs.values()
+            .filter(|r| document_id.is_none_or(|d| r.document_id == d))
+            .cloned()
+            .collect();
+        out.sort_by(|a, b| a.due_at.cmp(&b.due_at));
+        Ok(out)
+    }
+
+    async fn list_due_reminders(&self, now: DateTime<Utc>) -> DbResult<Vec<Reminder>> {
+        let rs = self.reminders.read().unwrap();
+        let mut out: Vec<Reminder> = rs.values()
+            .filter(|r| r.fired_at.is_none() && r.due_at <= now)
+            .cloned()
+            .collect();
+        out.sort_by(|a, b| a.due_at.cmp(&b.due_at));
+        Ok(out)
+    }
+
+    async fn mark_reminder_fired(&self, id: &str, at: DateTime<Utc>) -> DbResult<()> {
+        let mut rs = self.reminders.write().unwrap();
+        let reminder = rs.get_mut(id).ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        reminder.fired_at = Some(at);
+        Ok(())
+    }
+
+    async fn delete_reminder(&self, id: &str) -> DbResult<()> {
+        self.reminders.write().unwrap().remove(id);
+        Ok(())
+    }
+
     async fn create_contact(&self, mut contact: Contact) -> DbResult<Contact> {
         let key = self.next_key();
         let thing = Self::make_thing("contact", &key);
@@ -608,11 +877,12 @@ impl GraphDB for MockGraphDB {
             .collect())
     }
 
-    async fn update_contact(&self, id: &str, name: Option<&str>, notes: Option<&str>, _avatar: Option<&str>) -> DbResult<Contact> {
+    async fn update_contact(&self, id: &str, name: Option<&str>, notes: Option<&str>, avatar: Option<&str>) -> DbResult<Contact> {
         let mut contacts = self.contacts.write().unwrap();
         let contact = contacts.get_mut(id).ok_or_else(|| DbError::NotFound(id.to_string()))?;
         if let Some(n) = name { contact.name = n.to_string(); }
         if let Some(n) = notes { contact.notes = n.to_string(); }
+        if let Some(a) = avatar { contact.avatar = Some(a.to_string()); }
         Ok(contact.clone())
     }
 
@@ -687,6 +957,22 @@ impl GraphDB for MockGraphDB {
         Ok(contact.clone())
     }
 
+    async fn set_contact_addresses(&self, id: &str, addresses: Vec<ChannelAddress>) -> DbResult<Contact> {
+        let mut contacts = self.contacts.write().unwrap();
+        let contact = contacts.get_mut(id).ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        contact.addresses = addresses;
+        contact.addresses_encrypted = None;
+        contact.addresses_nonce = None;
+        Ok(contact.clone())
+    }
+
+    async fn set_contact_owned(&self, id: &str, is_owned: bool) -> DbResult<Contact> {
+        let mut contacts = self.contacts.write().unwrap();
+        let contact = contacts.get_mut(id).ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        contact.is_owned = is_owned;
+        Ok(contact.clone())
+    }
+
     async fn create_message(&self, mut message: Message) -> DbResult<Message> {
         let key = self.next_key();
         let thing = Self::make_thing("message", &key);
@@ -1381,6 +1667,22 @@ mod tests {
         assert!(empty.is_empty());
     }
 
+    #[tokio::test]
+    async fn mock_search_documents_by_content_and_tag() {
+        let db = MockGraphDB::new();
+        let mut d1 = Document::new("Plan".into(), "thread:1".into(), true);
+        d1.content = r#"{"body":"Draft the Roadmap #planning","images":[]}"#.into();
+        db.create_document(d1).await.unwrap();
+        db.create_document(Document::new("Empty".into(), "thread:1".into(), true)).await.unwrap();
+
+        assert_eq!(db.search_documents("roadmap").await.unwrap().len(), 1);
+        assert!(db.search_documents("budget").await.unwrap().is_empty());
+        assert_eq!(db.search_documents_by_tag("Planning").await.unwrap().len(), 1);
+        assert_eq!(db.search_documents_by_tag("#planning").await.unwrap().len(), 1);
+        assert!(db.search_documents_by_tag("draft").await.unwrap().is_empty());
+        assert!(db.search_documents_by_tag("plan").await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn mock_find_thread_by_name() {
         let db = MockGraphDB::new();
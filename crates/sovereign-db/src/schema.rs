@@ -5,6 +5,13 @@ use serde::{Deserialize, Serialize};
 // Re-exported so id-handling code in dependents (e.g. sovereign-p2p's
 // id-preserving sync creates) can name the type without a surrealdb dep.
 pub use surrealdb::sql::Thing;
+// The document type is part of the public API; the row stores it as is.
+pub use sovereign_sdk::DocType;
+// Permissions are checked on the write paths below the trait, so callers
+// build and inspect them through this crate.
+pub use sovereign_core::security::{
+    AccessLevel, ActionLevel, Collaborator, DocumentPermissions, Principal,
+};
 
 /// Format a Thing ID as "table:key" without backtick escaping.
 ///
@@ -35,6 +42,13 @@ pub struct Document {
     pub content: String,
     pub thread_id: String,
     pub is_owned: bool,
+    /// Rows from before document types read as notes.
+    #[serde(default)]
+    pub doc_type: DocType,
+    /// Owner and collaborators. Rows from before sharing are this
+    /// account's alone.
+    #[serde(default)]
+    pub permissions: DocumentPermissions,
     pub created_at: DateTime<Utc>,
     pub modified_at: DateTime<Utc>,
     pub spatial_x: f32,
@@ -56,6 +70,10 @@ pub struct Document {
     /// `search_documents_by_title`. Empty when title is plaintext.
     #[serde(default)]
     pub title_token_hashes: Vec<String>,
+    /// Per-DB HMAC hashes of content tokens and `#tags`, used for blind-index
+    /// full-text and tag search. Empty when content is plaintext.
+    #[serde(default)]
+    pub content_token_hashes: Vec<String>,
     /// Source URL if this document was fetched from the web.
     #[serde(default)]
     pub source_url: Option<String>,
@@ -84,6 +102,39 @@ pub struct Document {
     /// the document has not yet been scanned.
     #[serde(default)]
     pub pii_scanned_at: Option<DateTime<Utc>>,
+    /// When the document was put in the reading queue. None means it
+    /// isn't queued.
+    #[serde(default)]
+    pub read_later: Option<DateTime<Utc>>,
+    /// Pinned to the taskbar and shown under the canvas Favorites filter.
+    #[serde(default)]
+    pub favorite: bool,
+    /// When `favorite` last changed, on whichever device changed it. Sync
+    /// resolves pins and unpins by it; None means never pinned.
+    #[serde(default)]
+    pub favorite_at: Option<DateTime<Utc>>,
+    /// How carefully the document is handled: what the assistant may read
+    /// and whether opening it asks for the password again.
+    #[serde(default)]
+    pub sensitivity: Sensitivity,
+    /// When `sensitivity` last changed; sync resolves it like `favorite_at`.
+    #[serde(default)]
+    pub sensitivity_at: Option<DateTime<Utc>>,
+}
+
+/// Handling level of a document, ordered from least to most guarded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sensitivity {
+    /// Handled like any other document.
+    #[default]
+    Normal,
+    /// Listed and searchable, but its content is kept out of the
+    /// assistant's context and suggestions.
+    Sensitive,
+    /// Left out of the assistant entirely, and opened only after
+    /// re-entering the password.
+    Sealed,
 }
 
 /// Thread (project/topic grouping)
@@ -124,9 +175,27 @@ pub struct RelatedTo {
     pub relation_type: RelationType,
     pub strength: f32,
     pub created_at: DateTime<Utc>,
+    /// Made for a `[[link]]` or `@mention` in the source's body, and
+    /// removed again when the link is (see `crate::links`).
+    #[serde(default)]
+    pub from_body: bool,
+    /// Last time its two documents were opened or edited together.
+    #[serde(default)]
+    pub reinforced_at: Option<DateTime<Utc>>,
+    /// Last time its strength was reinforced or decayed.
+    #[serde(default)]
+    pub adjusted_at: Option<DateTime<Utc>>,
 }
 
 impl RelatedTo {
+    /// Strength of edges made for links written in a body: the author
+    /// put them there, so they are as strong as edges get.
+    pub const BODY_LINK_STRENGTH: f32 = 1.0;
+
+    /// Stale edges decay no lower than this, and graph traversal skips
+    /// edges at or below it.
+    pub const FADED_STRENGTH: f32 = 0.05;
+
     pub fn id_string(&self) -> Option<String> {
         self.id.as_ref().map(|t| thing_to_raw(t))
     }
@@ -249,6 +318,15 @@ pub struct Commit {
     /// skipped for those.
     #[serde(default)]
     pub signature: Option<String>,
+    /// Base64 Ed25519 public key of the device that made the commit (its
+    /// P2P identity key, so it maps to that device's PeerId). `None` on
+    /// commits made before device signing.
+    #[serde(default)]
+    pub device_public_key: Option<String>,
+    /// Base64 Ed25519 signature by `device_public_key` over
+    /// [`Commit::signed_bytes`].
+    #[serde(default)]
+    pub device_signature: Option<String>,
 }
 
 impl Document {
@@ -260,6 +338,8 @@ impl Document {
             content: r#"{"body":"","images":[]}"#.to_string(),
             thread_id,
             is_owned,
+            doc_type: DocType::default(),
+            permissions: DocumentPermissions::default(),
             created_at: now,
             modified_at: now,
             spatial_x: 0.0,
@@ -269,6 +349,7 @@ impl Document {
             encryption_nonce: None,
             title_nonce: None,
             title_token_hashes: Vec::new(),
+            content_token_hashes: Vec::new(),
             source_url: None,
             reliability_classification: None,
             reliability_score: None,
@@ -277,18 +358,68 @@ impl Document {
             body_raw_encrypted: None,
             body_raw_nonce: None,
             pii_scanned_at: None,
+            read_later: None,
+            favorite: false,
+            favorite_at: None,
+            sensitivity: Sensitivity::Normal,
+            sensitivity_at: None,
         }
     }
 
     pub fn id_string(&self) -> Option<String> {
         self.id.as_ref().map(|t| thing_to_raw(t))
     }
+
+    /// Whether the assistant may read the content: into its context, its
+    /// tools' results and its suggestions.
+    pub fn ai_may_read(&self) -> bool {
+        self.sensitivity == Sensitivity::Normal
+    }
+
+    /// Whether the assistant may know the document exists at all.
+    pub fn ai_may_see(&self) -> bool {
+        self.sensitivity != Sensitivity::Sealed
+    }
+
+    /// Whether the body or a caption carries `#tag` as a whole tag, by the
+    /// rule of [`sovereign_core::content::hashtags`]: `#plan` is not in
+    /// `#planning`. Reads the same text the tag index does. `tag` is
+    /// lowercase, without the `#`.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let text = sovereign_core::content::indexable_text(&self.content);
+        sovereign_core::content::hashtags(&text, usize::MAX)
+            .iter()
+            .any(|t| t == tag)
+    }
 }
 
 impl Commit {
     pub fn id_string(&self) -> Option<String> {
         self.id.as_ref().map(|t| thing_to_raw(t))
     }
+
+    /// Canonical bytes the commit MAC and device signature cover. Excludes
+    /// the signatures themselves and the timestamp (display metadata that
+    /// need not round-trip byte-exactly); binds the document id, parent,
+    /// message and the snapshot — the tamper-relevant content.
+    /// Length-prefixed.
+    pub fn signed_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(128 + self.snapshot.content.len());
+        out.extend_from_slice(b"sovereign-commit-mac-fields:v1");
+        for field in [
+            self.document_id.as_str(),
+            self.parent_commit.as_deref().unwrap_or(""),
+            self.message.as_str(),
+            self.snapshot.document_id.as_str(),
+            self.snapshot.title.as_str(),
+            self.snapshot.content.as_str(),
+        ] {
+            out.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            out.extend_from_slice(field.as_bytes());
+        }
+        out.push(self.parent_commit.is_some() as u8);
+        out
+    }
 }
 
 /// A timeline milestone marking a significant point in a thread's history.
@@ -318,6 +449,43 @@ impl Milestone {
     }
 }
 
+/// A reminder attached to a document, or to one checklist item in it.
+/// Fired once by the app's scheduler when `due_at` passes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reminder {
+    pub id: Option<Thing>,
+    pub document_id: String,
+    /// Checklist item index (see `tasks::Task::index`) when the reminder
+    /// is about a task rather than the whole document.
+    #[serde(default)]
+    pub task_index: Option<usize>,
+    pub text: String,
+    pub due_at: DateTime<Utc>,
+    /// Set once the reminder has been delivered; fired reminders are kept
+    /// so the document panel can show them until deleted.
+    #[serde(default)]
+    pub fired_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Reminder {
+    pub fn new(document_id: String, text: String, due_at: DateTime<Utc>) -> Self {
+        Self {
+            id: None,
+            document_id,
+            task_index: None,
+            text,
+            due_at,
+            fired_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn id_string(&self) -> Option<String> {
+        self.id.as_ref().map(|t| thing_to_raw(t))
+    }
+}
+
 impl Thread {
     pub fn new(name: String, description: String) -> Self {
         let now = Utc::now();
@@ -368,6 +536,25 @@ impl std::fmt::Display for ChannelType {
     }
 }
 
+/// Inverse of `Display`.
+impl std::str::FromStr for ChannelType {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "email" => Ok(Self::Email),
+            "sms" => Ok(Self::Sms),
+            "signal" => Ok(Self::Signal),
+            "whatsapp" => Ok(Self::WhatsApp),
+            "matrix" => Ok(Self::Matrix),
+            "phone" => Ok(Self::Phone),
+            other => match other.strip_prefix("custom:") {
+                Some(name) if !name.trim().is_empty() => Ok(Self::Custom(s[7..].trim().to_string())),
+                _ => Err(format!("Unknown channel: {s}")),
+            },
+        }
+    }
+}
+
 /// Message read status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -1055,4 +1242,19 @@ mod tests {
         assert!(doc.body_raw_encrypted.is_none());
         assert!(doc.pii_scanned_at.is_none());
     }
+
+    #[test]
+    fn channel_type_parses_its_display_form() {
+        for ch in [
+            ChannelType::Email,
+            ChannelType::WhatsApp,
+            ChannelType::Phone,
+            ChannelType::Custom("Threema".into()),
+        ] {
+            assert_eq!(ch.to_string().parse::<ChannelType>().unwrap(), ch);
+        }
+        assert_eq!("SMS".parse::<ChannelType>().unwrap(), ChannelType::Sms);
+        assert!("custom:".parse::<ChannelType>().is_err());
+        assert!("pigeon".parse::<ChannelType>().is_err());
+    }
 }
@@ -0,0 +1,163 @@
+//! [`SdkStore`]: the SDK's [`DocumentStore`] over a [`GraphDB`], and the
+//! conversions from the schema's rows to the SDK's models.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sovereign_sdk::content::ContentFields;
+use sovereign_sdk::store::{DocumentStore, StoreError, StoreResult};
+
+use crate::error::DbError;
+use crate::schema::{thing_to_raw, Document, Thread};
+use crate::traits::GraphDB;
+
+impl From<Document> for sovereign_sdk::Document {
+    fn from(doc: Document) -> Self {
+        Self {
+            id: doc.id.as_ref().map(thing_to_raw).unwrap_or_default(),
+            content: ContentFields::parse(&doc.content),
+            title: doc.title,
+            thread_id: doc.thread_id,
+            is_owned: doc.is_owned,
+            doc_type: doc.doc_type,
+            created_at: doc.created_at,
+            modified_at: doc.modified_at,
+        }
+    }
+}
+
+impl From<Thread> for sovereign_sdk::Thread {
+    fn from(thread: Thread) -> Self {
+        Self {
+            id: thread.id.as_ref().map(thing_to_raw).unwrap_or_default(),
+            name: thread.name,
+            description: thread.description,
+            created_at: thread.created_at,
+            modified_at: thread.modified_at,
+        }
+    }
+}
+
+impl From<DbError> for StoreError {
+    fn from(e: DbError) -> Self {
+        match e {
+            DbError::NotFound(what) => StoreError::NotFound(what),
+            other => StoreError::Backend(other.to_string()),
+        }
+    }
+}
+
+/// A [`DocumentStore`] backed by `db`. Over an encrypted database it
+/// reads and writes plaintext like any other caller of `db`.
+pub struct SdkStore {
+    db: Arc<dyn GraphDB>,
+}
+
+impl SdkStore {
+    pub fn new(db: Arc<dyn GraphDB>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl DocumentStore for SdkStore {
+    async fn get_document(&self, id: &str) -> StoreResult<sovereign_sdk::Document> {
+        Ok(self.db.get_document(id).await?.into())
+    }
+
+    async fn list_documents(
+        &self,
+        thread_id: Option<&str>,
+    ) -> StoreResult<Vec<sovereign_sdk::Document>> {
+        let docs = self.db.list_documents(thread_id).await?;
+        Ok(docs.into_iter().map(Into::into).collect())
+    }
+
+    async fn search_documents(&self, query: &str) -> StoreResult<Vec<sovereign_sdk::Document>> {
+        let docs = self.db.search_documents(query).await?;
+        Ok(docs.into_iter().map(Into::into).collect())
+    }
+
+    async fn create_document(
+        &self,
+        title: &str,
+        thread_id: &str,
+        content: &ContentFields,
+    ) -> StoreResult<sovereign_sdk::Document> {
+        let mut doc = Document::new(title.to_string(), thread_id.to_string(), true);
+        doc.content = content.serialize();
+        Ok(self.db.create_document(doc).await?.into())
+    }
+
+    async fn update_document(
+        &self,
+        id: &str,
+        title: Option<&str>,
+        content: Option<&ContentFields>,
+    ) -> StoreResult<sovereign_sdk::Document> {
+        let content = content.map(ContentFields::serialize);
+        let doc = self
+            .db
+            .update_document(id, title, content.as_deref())
+            .await?;
+        Ok(doc.into())
+    }
+
+    async fn trash_document(&self, id: &str) -> StoreResult<()> {
+        Ok(self.db.soft_delete_document(id).await?)
+    }
+
+    async fn list_threads(&self) -> StoreResult<Vec<sovereign_sdk::Thread>> {
+        let threads = self.db.list_threads().await?;
+        Ok(threads.into_iter().map(Into::into).collect())
+    }
+
+    async fn create_thread(
+        &self,
+        name: &str,
+        description: &str,
+    ) -> StoreResult<sovereign_sdk::Thread> {
+        let thread = Thread::new(name.to_string(), description.to_string());
+        Ok(self.db.create_thread(thread).await?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockGraphDB;
+
+    #[tokio::test]
+    async fn store_round_trips_through_the_database() {
+        let store = SdkStore::new(Arc::new(MockGraphDB::new()));
+        let thread = store.create_thread("Research", "").await.unwrap();
+        let content = ContentFields {
+            body: "# Notes".into(),
+            ..Default::default()
+        };
+        let doc = store
+            .create_document("Notes", &thread.id, &content)
+            .await
+            .unwrap();
+        assert!(doc.id.starts_with("document:"));
+        assert_eq!(doc.content.body, "# Notes");
+
+        let renamed = store
+            .update_document(&doc.id, Some("Field notes"), None)
+            .await
+            .unwrap();
+        assert_eq!(renamed.title, "Field notes");
+        assert_eq!(renamed.content, content);
+        assert_eq!(
+            store.list_documents(Some(&thread.id)).await.unwrap().len(),
+            1
+        );
+
+        store.trash_document(&doc.id).await.unwrap();
+        assert!(store.list_documents(None).await.unwrap().is_empty());
+        assert!(matches!(
+            store.get_document("document:missing").await,
+            Err(StoreError::NotFound(_))
+        ));
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use surrealdb::engine::local::{Db, Mem};
@@ -7,13 +9,14 @@ use surrealdb::engine::local::RocksDb;
 use surrealdb::engine::local::SurrealKv;
 use surrealdb::sql::Thing;
 use surrealdb::Surreal;
+use tracing::instrument;
 
 use crate::error::{DbError, DbResult};
 use crate::schema::{
-    ChannelType, Commit, Contact, Conversation, Document, DocumentSnapshot,
-    Entity, EntityKind, Message, Milestone, PiiRecord, ReadStatus, RelatedTo, RelationType,
-    ReviewState, ShareRecord, SourceRef, SuggestedLink, SuggestionSource,
-    SuggestionStatus, Thread,
+    ActionLevel, ChannelType, Commit, Contact, Conversation, DocType, Document,
+    DocumentPermissions, DocumentSnapshot, Entity, EntityKind, Message, Milestone, PiiRecord,
+    Principal, ReadStatus, RelatedTo, RelationType, Reminder, ReviewState, Sensitivity,
+    ShareRecord, SourceRef, SuggestedLink, SuggestionSource, SuggestionStatus, Thread,
 };
 use crate::traits::GraphDB;
 
@@ -26,6 +29,7 @@ pub enum StorageMode {
 /// SurrealDB implementation of the GraphDB trait
 pub struct SurrealGraphDB {
     db: Surreal<Db>,
+    database: String,
 }
 
 impl SurrealGraphDB {
@@ -55,7 +59,88 @@ impl SurrealGraphDB {
                 Surreal::new::<Mem>(()).await?
             }
         };
-        Ok(Self { db })
+        Ok(Self {
+            db,
+            database: "main".into(),
+        })
+    }
+
+    /// Keep this handle's rows in `database` rather than `main`, so profiles
+    /// sharing a store never see each other's data.
+    pub fn with_database(mut self, database: impl Into<String>) -> Self {
+        self.database = database.into();
+        self
+    }
+
+    /// Write this handle's database, definitions and records, to `path`
+    /// as SurrealQL. Encrypted fields stay encrypted.
+    pub async fn export_to(&self, path: &Path) -> DbResult<()> {
+        self.db.export(path).await?;
+        Ok(())
+    }
+
+    /// Replace this handle's database with one written by
+    /// [`export_to`](Self::export_to). The current contents are dropped
+    /// first, so a failed import leaves the database partly restored.
+    pub async fn replace_from(&self, path: &Path) -> DbResult<()> {
+        self.db
+            .query(format!("REMOVE DATABASE IF EXISTS `{}`", self.database))
+            .await?
+            .check()?;
+        self.connect().await?;
+        self.db.import(path).await?;
+        self.init_schema().await
+    }
+
+    /// Refuse `action` on a document this account may not take it on. A
+    /// missing document is left for the write itself to report.
+    async fn check_document_access(&self, id: &str, action: ActionLevel) -> DbResult<()> {
+        let (table, key) = parse_and_validate(id, "document")?;
+        let doc: Option<Document> = self.db.select((table, key)).await?;
+        if let Some(doc) = doc {
+            doc.permissions.check(&Principal::Local, action)?;
+        }
+        Ok(())
+    }
+
+    /// `RELATE` two records with a `related_to` edge.
+    async fn relate(
+        &self,
+        from_id: &str,
+        to_id: &str,
+        relation_type: RelationType,
+        strength: f32,
+        from_body: bool,
+    ) -> DbResult<RelatedTo> {
+        let now = Utc::now();
+        let relation_type_str = relation_type.to_string();
+
+        // Parse "table:id" strings into Thing records for RELATE
+        let from = id_to_thing(from_id);
+        let to = id_to_thing(to_id);
+
+        let mut result = self
+            .db
+            .query(
+                "RELATE $from->related_to->$to SET \
+                 relation_type = $rtype, \
+                 strength = $strength, \
+                 from_body = $from_body, \
+                 created_at = $created_at \
+                 RETURN AFTER",
+            )
+            .bind(("from", from))
+            .bind(("to", to))
+            .bind(("rtype", relation_type_str))
+            .bind(("strength", strength))
+            .bind(("from_body", from_body))
+            .bind(("created_at", now))
+            .await?;
+
+        let rels: Vec<RelatedTo> = result.take(0)?;
+        rels.into_iter()
+            .next()
+            .ok_or_else(|| DbError::Query("Failed to create relationship".into()))
     }
 }
 
@@ -90,7 +175,7 @@ impl GraphDB for SurrealGraphDB {
     async fn connect(&self) -> DbResult<()> {
         self.db
             .use_ns("sovereign")
-            .use_db("main")
+            .use_db(&self.database)
             .await
             .map_err(|e| DbError::Connection(e.to_string()))
     }
@@ -105,6 +190,8 @@ impl GraphDB for SurrealGraphDB {
             DEFINE INDEX IF NOT EXISTS idx_commit_doc ON commit FIELDS document_id;\
             DEFINE INDEX IF NOT EXISTS idx_contact_name ON contact FIELDS name;\
             DEFINE INDEX IF NOT EXISTS idx_milestone_thread ON milestone FIELDS thread_id;\
+            DEFINE INDEX IF NOT EXISTS idx_reminder_doc ON reminder FIELDS document_id;\
+            DEFINE INDEX IF NOT EXISTS idx_reminder_due ON reminder FIELDS due_at;\
             DEFINE INDEX IF NOT EXISTS idx_message_conversation ON message FIELDS conversation_id;\
             DEFINE INDEX IF NOT EXISTS idx_message_sent_at ON message FIELDS sent_at;\
             DEFINE INDEX IF NOT EXISTS idx_message_from ON message FIELDS from_contact_id;\
@@ -140,6 +227,7 @@ impl GraphDB for SurrealGraphDB {
 
     // -- Documents ---
 
+    #[instrument(name = "db.create_document", level = "debug", skip_all)]
     async fn create_document(&self, doc: Document) -> DbResult<Document> {
         let created: Option<Document> = self.db.create("document").content(doc).await?;
         created.ok_or_else(|| DbError::Query("Failed to create document".into()))
@@ -162,12 +250,29 @@ impl GraphDB for SurrealGraphDB {
         Ok(true)
     }
 
+    async fn create_documents(&self, docs: Vec<Document>) -> DbResult<Vec<Document>> {
+        if docs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let count = docs.len();
+        let created: Vec<Document> = self.db.insert("document").content(docs).await?;
+        if created.len() != count {
+            return Err(DbError::Query(format!(
+                "Batch insert created {} of {count} documents",
+                created.len()
+            )));
+        }
+        Ok(created)
+    }
+
+    #[instrument(name = "db.get_document", level = "debug", skip_all)]
     async fn get_document(&self, id: &str) -> DbResult<Document> {
         let (table, key) = parse_and_validate(id, "document")?;
         let doc: Option<Document> = self.db.select((table, key)).await?;
         doc.ok_or_else(|| DbError::NotFound(id.to_string()))
     }
 
+    #[instrument(name = "db.list_documents", level = "debug", skip_all)]
     async fn list_documents(&self, thread_id: Option<&str>) -> DbResult<Vec<Document>> {
         match thread_id {
             Some(tid) => {
@@ -191,6 +296,7 @@ impl GraphDB for SurrealGraphDB {
         }
     }
 
+    #[instrument(name = "db.search_documents_by_title", level = "debug", skip_all)]
     async fn search_documents_by_title(&self, query: &str) -> DbResult<Vec<Document>> {
         let q = query.to_string();
         let mut result = self
@@ -202,6 +308,7 @@ impl GraphDB for SurrealGraphDB {
         Ok(docs)
     }
 
+    #[instrument(name = "db.search_documents_by_title_token_hashes", level = "debug", skip_all)]
     async fn search_documents_by_title_token_hashes(
         &self,
         hashes: &[String],
@@ -219,6 +326,50 @@ impl GraphDB for SurrealGraphDB {
         Ok(docs)
     }
 
+    #[instrument(name = "db.search_documents", level = "debug", skip_all)]
+    async fn search_documents(&self, query: &str) -> DbResult<Vec<Document>> {
+        let q = query.to_string();
+        let mut result = self
+            .db
+            .query("SELECT * FROM document WHERE deleted_at IS NONE AND string::lowercase(content) CONTAINS string::lowercase($q) ORDER BY created_at DESC LIMIT 20")
+            .bind(("q", q))
+            .await?;
+        let docs: Vec<Document> = result.take(0)?;
+        Ok(docs)
+    }
+
+    #[instrument(name = "db.search_documents_by_tag", level = "debug", skip_all)]
+    async fn search_documents_by_tag(&self, tag: &str) -> DbResult<Vec<Document>> {
+        let tag = tag.trim_start_matches('#').to_lowercase();
+        // CONTAINS narrows to candidates; `#plan` also matches `#planning`,
+        // so whole tags are checked on the parsed body.
+        let mut result = self
+            .db
+            .query("SELECT * FROM document WHERE deleted_at IS NONE AND string::lowercase(content) CONTAINS $tag ORDER BY created_at DESC")
+            .bind(("tag", format!("#{tag}")))
+            .await?;
+        let docs: Vec<Document> = result.take(0)?;
+        Ok(docs.into_iter().filter(|d| d.has_tag(&tag)).take(20).collect())
+    }
+
+    #[instrument(name = "db.search_documents_by_content_token_hashes", level = "debug", skip_all)]
+    async fn search_documents_by_content_token_hashes(
+        &self,
+        hashes: &[String],
+    ) -> DbResult<Vec<Document>> {
+        if hashes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let hashes_vec: Vec<String> = hashes.to_vec();
+        let mut result = self
+            .db
+            .query("SELECT * FROM document WHERE deleted_at IS NONE AND content_token_hashes CONTAINSALL $hashes ORDER BY created_at DESC LIMIT 20")
+            .bind(("hashes", hashes_vec))
+            .await?;
+        let docs: Vec<Document> = result.take(0)?;
+        Ok(docs)
+    }
+
     async fn set_document_title_encryption(
         &self,
         id: &str,
@@ -249,23 +400,27 @@ impl GraphDB for SurrealGraphDB {
         id: &str,
         content_ciphertext: &str,
         content_nonce: &str,
+        content_token_hashes: &[String],
     ) -> DbResult<()> {
         let (table, key) = parse_and_validate(id, "document")?;
         let _: Option<Document> = self
             .db
             .query(
                 "UPDATE type::thing($table, $key) SET \
-                 content = $content, encryption_nonce = $nonce",
+                 content = $content, encryption_nonce = $nonce, \
+                 content_token_hashes = $hashes",
             )
             .bind(("table", table.to_string()))
             .bind(("key", key.to_string()))
             .bind(("content", content_ciphertext.to_string()))
             .bind(("nonce", content_nonce.to_string()))
+            .bind(("hashes", content_token_hashes.to_vec()))
             .await?
             .take(0)?;
         Ok(())
     }
 
+    #[instrument(name = "db.update_document", level = "debug", skip_all)]
     async fn update_document(
         &self,
         id: &str,
@@ -277,6 +432,7 @@ impl GraphDB for SurrealGraphDB {
         // Fetch current document
         let current: Option<Document> = self.db.select((table, key)).await?;
         let mut doc = current.ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        doc.permissions.check(&Principal::Local, ActionLevel::Modify)?;
 
         if let Some(t) = title {
             doc.title = t.to_string();
@@ -334,7 +490,71 @@ impl GraphDB for SurrealGraphDB {
         Ok(())
     }
 
+    async fn update_document_type(&self, id: &str, doc_type: DocType) -> DbResult<()> {
+        self.check_document_access(id, ActionLevel::Modify).await?;
+        self.db
+            .query("UPDATE $id SET doc_type = $doc_type")
+            .bind(("id", id.to_string()))
+            .bind(("doc_type", doc_type))
+            .await?;
+        Ok(())
+    }
+
+    async fn set_read_later(&self, id: &str, at: Option<DateTime<Utc>>) -> DbResult<()> {
+        self.check_document_access(id, ActionLevel::Annotate).await?;
+        let (table, key) = parse_and_validate(id, "document")?;
+        let patch = serde_json::json!({ "read_later": at });
+        let updated: Option<Document> = self.db.update((table, key)).merge(patch).await?;
+        if updated.is_none() {
+            return Err(DbError::NotFound(id.to_string()));
+        }
+        Ok(())
+    }
+
+    async fn set_favorite(&self, id: &str, favorite: bool, at: DateTime<Utc>) -> DbResult<()> {
+        self.check_document_access(id, ActionLevel::Annotate).await?;
+        let (table, key) = parse_and_validate(id, "document")?;
+        let patch = serde_json::json!({ "favorite": favorite, "favorite_at": at });
+        let updated: Option<Document> = self.db.update((table, key)).merge(patch).await?;
+        if updated.is_none() {
+            return Err(DbError::NotFound(id.to_string()));
+        }
+        Ok(())
+    }
+
+    async fn set_sensitivity(
+        &self,
+        id: &str,
+        sensitivity: Sensitivity,
+        at: DateTime<Utc>,
+    ) -> DbResult<()> {
+        self.check_document_access(id, ActionLevel::Annotate).await?;
+        let (table, key) = parse_and_validate(id, "document")?;
+        let patch = serde_json::json!({ "sensitivity": sensitivity, "sensitivity_at": at });
+        let updated: Option<Document> = self.db.update((table, key)).merge(patch).await?;
+        if updated.is_none() {
+            return Err(DbError::NotFound(id.to_string()));
+        }
+        Ok(())
+    }
+
+    async fn set_document_permissions(
+        &self,
+        id: &str,
+        permissions: DocumentPermissions,
+    ) -> DbResult<()> {
+        self.check_document_access(id, ActionLevel::Transmit).await?;
+        self.db
+            .query("UPDATE $id SET permissions = $permissions")
+            .bind(("id", id.to_string()))
+            .bind(("permissions", permissions))
+            .await?;
+        Ok(())
+    }
+
+    #[instrument(name = "db.delete_document", level = "debug", skip_all)]
     async fn delete_document(&self, id: &str) -> DbResult<()> {
+        self.check_document_access(id, ActionLevel::Destruct).await?;
         let (table, key) = parse_and_validate(id, "document")?;
         let _: Option<Document> = self.db.delete((table, key)).await?;
         Ok(())
@@ -347,12 +567,14 @@ impl GraphDB for SurrealGraphDB {
         created.ok_or_else(|| DbError::Query("Failed to create thread".into()))
     }
 
+    #[instrument(name = "db.get_thread", level = "debug", skip_all)]
     async fn get_thread(&self, id: &str) -> DbResult<Thread> {
         let (table, key) = parse_and_validate(id, "thread")?;
         let thread: Option<Thread> = self.db.select((table, key)).await?;
         thread.ok_or_else(|| DbError::NotFound(id.to_string()))
     }
 
+    #[instrument(name = "db.list_threads", level = "debug", skip_all)]
     async fn list_threads(&self) -> DbResult<Vec<Thread>> {
         let mut result = self
             .db
@@ -420,6 +642,7 @@ impl GraphDB for SurrealGraphDB {
         Ok(())
     }
 
+    #[instrument(name = "db.update_thread", level = "debug", skip_all)]
     async fn update_thread(
         &self,
         id: &str,
@@ -449,11 +672,13 @@ impl GraphDB for SurrealGraphDB {
         Ok(())
     }
 
+    #[instrument(name = "db.move_document_to_thread", level = "debug", skip_all)]
     async fn move_document_to_thread(
         &self,
         doc_id: &str,
         new_thread_id: &str,
     ) -> DbResult<Document> {
+        self.check_document_access(doc_id, ActionLevel::Modify).await?;
         let (table, key) = parse_and_validate(doc_id, "document")?;
 
         let updated: Option<Document> = self.db
@@ -527,7 +752,9 @@ impl GraphDB for SurrealGraphDB {
 
     // -- Soft delete ---
 
+    #[instrument(name = "db.soft_delete_document", level = "debug", skip_all)]
     async fn soft_delete_document(&self, id: &str) -> DbResult<()> {
+        self.check_document_access(id, ActionLevel::Destruct).await?;
         let (table, key) = parse_and_validate(id, "document")?;
         let result: Option<Document> = self.db
             .update((table, key))
@@ -569,6 +796,26 @@ impl GraphDB for SurrealGraphDB {
         updated.ok_or_else(|| DbError::NotFound(id.to_string()))
     }
 
+    #[instrument(name = "db.list_deleted_documents", level = "debug", skip_all)]
+    async fn list_deleted_documents(&self) -> DbResult<Vec<Document>> {
+        let mut result = self
+            .db
+            .query("SELECT * FROM document WHERE deleted_at IS NOT NONE ORDER BY deleted_at DESC")
+            .await?;
+        let docs: Vec<Document> = result.take(0)?;
+        Ok(docs)
+    }
+
+    async fn list_deleted_threads(&self) -> DbResult<Vec<Thread>> {
+        let mut result = self
+            .db
+            .query("SELECT * FROM thread WHERE deleted_at IS NOT NONE ORDER BY deleted_at DESC")
+            .await?;
+        let threads: Vec<Thread> = result.take(0)?;
+        Ok(threads)
+    }
+
+    #[instrument(name = "db.purge_deleted", level = "debug", skip_all)]
     async fn purge_deleted(&self, max_age: std::time::Duration) -> DbResult<u64> {
         let cutoff =
             Utc::now() - chrono::Duration::seconds(max_age.as_secs() as i64);
@@ -620,8 +867,62 @@ impl GraphDB for SurrealGraphDB {
         Ok(())
     }
 
+    // -- Reminders ---
+
+    async fn create_reminder(&self, reminder: Reminder) -> DbResult<Reminder> {
+        self.check_document_access(&reminder.document_id, ActionLevel::Annotate)
+            .await?;
+        let created: Option<Reminder> = self.db.create("reminder").content(reminder).await?;
+        created.ok_or_else(|| DbError::Query("Failed to create reminder".into()))
+    }
+
+    async fn list_reminders(&self, document_id: Option<&str>) -> DbResult<Vec<Reminder>> {
+        let mut result = match document_id {
+            Some(doc_id) => {
+                self.db
+                    .query("SELECT * FROM reminder WHERE document_id = $did ORDER BY due_at ASC")
+                    .bind(("did", doc_id.to_string()))
+                    .await?
+            }
+            None => {
+                self.db
+                    .query("SELECT * FROM reminder ORDER BY due_at ASC")
+                    .await?
+            }
+        };
+        let reminders: Vec<Reminder> = result.take(0)?;
+        Ok(reminders)
+    }
+
+    async fn list_due_reminders(&self, now: DateTime<Utc>) -> DbResult<Vec<Reminder>> {
+        let mut result = self
+            .db
+            .query("SELECT * FROM reminder WHERE fired_at IS NONE AND due_at <= $now ORDER BY due_at ASC")
+            .bind(("now", now))
+            .await?;
+        let reminders: Vec<Reminder> = result.take(0)?;
+        Ok(reminders)
+    }
+
+    async fn mark_reminder_fired(&self, id: &str, at: DateTime<Utc>) -> DbResult<()> {
+        let (table, key) = parse_and_validate(id, "reminder")?;
+        let patch = serde_json::json!({ "fired_at": at });
+        let updated: Option<Reminder> = self.db.update((table, key)).merge(patch).await?;
+        if updated.is_none() {
+            return Err(DbError::NotFound(id.to_string()));
+        }
+        Ok(())
+    }
+
+    async fn delete_reminder(&self, id: &str) -> DbResult<()> {
+        let (table, key) = parse_and_validate(id, "reminder")?;
+        let _: Option<Reminder> = self.db.delete((table, key)).await?;
+        Ok(())
+    }
+
     // -- Relationships ---
 
+    #[instrument(name = "db.create_relationship", level = "debug", skip_all)]
     async fn create_relationship(
         &self,
         from_id: &str,
@@ -629,35 +930,23 @@ impl GraphDB for SurrealGraphDB {
         relation_type: RelationType,
         strength: f32,
     ) -> DbResult<RelatedTo> {
-        let now = Utc::now();
-        let relation_type_str = relation_type.to_string();
-
-        // Parse "table:id" strings into Thing records for RELATE
-        let from = id_to_thing(from_id);
-        let to = id_to_thing(to_id);
-
-        let mut result = self
-            .db
-            .query(
-                "RELATE $from->related_to->$to SET \
-                 relation_type = $rtype, \
-                 strength = $strength, \
-                 created_at = $created_at \
-                 RETURN AFTER",
-            )
-            .bind(("from", from))
-            .bind(("to", to))
-            .bind(("rtype", relation_type_str))
-            .bind(("strength", strength))
-            .bind(("created_at", now))
-            .await?;
+        self.relate(from_id, to_id, relation_type, strength, false)
+            .await
+    }
 
-        let rels: Vec<RelatedTo> = result.take(0)?;
-        rels.into_iter()
-            .next()
-            .ok_or_else(|| DbError::Query("Failed to create relationship".into()))
+    #[instrument(name = "db.create_body_link", level = "debug", skip_all)]
+    async fn create_body_link(&self, from_id: &str, to_id: &str) -> DbResult<RelatedTo> {
+        self.relate(
+            from_id,
+            to_id,
+            RelationType::References,
+            RelatedTo::BODY_LINK_STRENGTH,
+            true,
+        )
+        .await
     }
 
+    #[instrument(name = "db.list_outgoing_relationships", level = "debug", skip_all)]
     async fn list_outgoing_relationships(&self, doc_id: &str) -> DbResult<Vec<RelatedTo>> {
         let doc = id_to_thing(doc_id);
         let mut result = self
@@ -672,6 +961,7 @@ impl GraphDB for SurrealGraphDB {
         Ok(nested.into_iter().flatten().collect())
     }
 
+    #[instrument(name = "db.list_incoming_relationships", level = "debug", skip_all)]
     async fn list_incoming_relationships(&self, doc_id: &str) -> DbResult<Vec<RelatedTo>> {
         let doc = id_to_thing(doc_id);
         let mut result = self
@@ -683,20 +973,48 @@ impl GraphDB for SurrealGraphDB {
         Ok(nested.into_iter().flatten().collect())
     }
 
+    #[instrument(name = "db.list_all_relationships", level = "debug", skip_all)]
     async fn list_all_relationships(&self) -> DbResult<Vec<RelatedTo>> {
         let mut result = self.db.query("SELECT * FROM related_to").await?;
         let rels: Vec<RelatedTo> = result.take(0)?;
         Ok(rels)
     }
 
+    async fn delete_relationship(&self, id: &str) -> DbResult<()> {
+        let (table, key) = parse_and_validate(id, "related_to")?;
+        let _: Option<RelatedTo> = self.db.delete((table, key)).await?;
+        Ok(())
+    }
+
+    async fn set_relationship_strength(
+        &self,
+        id: &str,
+        strength: f32,
+        reinforced: bool,
+        at: DateTime<Utc>,
+    ) -> DbResult<()> {
+        let (table, key) = parse_and_validate(id, "related_to")?;
+        let mut patch = serde_json::json!({ "strength": strength, "adjusted_at": at });
+        if reinforced {
+            patch["reinforced_at"] = serde_json::json!(at);
+        }
+        let updated: Option<RelatedTo> = self.db.update((table, key)).merge(patch).await?;
+        if updated.is_none() {
+            return Err(DbError::NotFound(id.to_string()));
+        }
+        Ok(())
+    }
+
+    #[instrument(name = "db.traverse", level = "debug", skip_all)]
     async fn traverse(&self, doc_id: &str, depth: u32, limit: u32) -> DbResult<Vec<Document>> {
-        let arrow_path = "->related_to->document".repeat(depth as usize);
+        let arrow_path = "->(related_to WHERE strength > $faded)->document".repeat(depth as usize);
         let query = format!("SELECT {arrow_path} FROM $id LIMIT $lim");
         let id = doc_id.to_string();
         let mut result = self
             .db
             .query(&query)
             .bind(("id", id))
+            .bind(("faded", RelatedTo::FADED_STRENGTH))
             .bind(("lim", limit))
             .await?;
         let docs: Vec<Document> = result.take(0)?;
@@ -840,6 +1158,7 @@ impl GraphDB for SurrealGraphDB {
 
     // -- Version control ---
 
+    #[instrument(name = "db.commit_document", level = "debug", skip_all)]
     async fn commit_document(&self, doc_id: &str, message: &str) -> DbResult<Commit> {
         let doc = self.get_document(doc_id).await?;
 
@@ -857,6 +1176,8 @@ impl GraphDB for SurrealGraphDB {
             timestamp: Utc::now(),
             snapshot,
             signature: None,
+            device_public_key: None,
+            device_signature: None,
         };
 
         let created: Option<Commit> = self.db.create("commit").content(commit).await?;
@@ -873,6 +1194,7 @@ impl GraphDB for SurrealGraphDB {
         Ok(created)
     }
 
+    #[instrument(name = "db.list_document_commits", level = "debug", skip_all)]
     async fn list_document_commits(&self, doc_id: &str) -> DbResult<Vec<Commit>> {
         let doc_id_owned = doc_id.to_string();
         let mut result = self
@@ -884,12 +1206,34 @@ impl GraphDB for SurrealGraphDB {
         Ok(commits)
     }
 
+    #[instrument(name = "db.prune_document_commits", level = "debug", skip_all)]
+    async fn prune_document_commits(&self, doc_id: &str, keep: usize) -> DbResult<u64> {
+        let (table, key) = parse_and_validate(doc_id, "document")?;
+        let doc: Option<Document> = self.db.select((table, key)).await?;
+        let head = doc
+            .ok_or_else(|| DbError::NotFound(doc_id.to_string()))?
+            .head_commit;
+
+        let mut removed = 0;
+        for commit in self.list_document_commits(doc_id).await?.into_iter().skip(keep) {
+            let Some(id) = commit.id_string() else { continue };
+            if Some(&id) == head.as_ref() {
+                continue;
+            }
+            let (table, key) = parse_and_validate(&id, "commit")?;
+            let _: Option<Commit> = self.db.delete((table, key)).await?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
     async fn get_commit(&self, commit_id: &str) -> DbResult<Commit> {
         let (table, key) = parse_and_validate(commit_id, "commit")?;
         let commit: Option<Commit> = self.db.select((table, key)).await?;
         commit.ok_or_else(|| DbError::NotFound(commit_id.to_string()))
     }
 
+    #[instrument(name = "db.restore_document", level = "debug", skip_all)]
     async fn restore_document(&self, doc_id: &str, commit_id: &str) -> DbResult<Document> {
         let commit = self.get_commit(commit_id).await?;
 
@@ -897,6 +1241,7 @@ impl GraphDB for SurrealGraphDB {
         let (table, key) = parse_and_validate(doc_id, "document")?;
         let current: Option<Document> = self.db.select((table, key)).await?;
         let mut doc = current.ok_or_else(|| DbError::NotFound(doc_id.to_string()))?;
+        doc.permissions.check(&Principal::Local, ActionLevel::Modify)?;
         doc.title = commit.snapshot.title.clone();
         doc.content = commit.snapshot.content.clone();
         doc.modified_at = Utc::now();
@@ -924,6 +1269,25 @@ impl GraphDB for SurrealGraphDB {
         Ok(())
     }
 
+    async fn set_commit_device_signature(
+        &self,
+        commit_id: &str,
+        public_key: &str,
+        signature: &str,
+    ) -> DbResult<()> {
+        let (table, key) = parse_and_validate(commit_id, "commit")?;
+        let _: Option<Commit> = self
+            .db
+            .query("UPDATE type::thing($table, $key) SET device_public_key = $pk, device_signature = $sig")
+            .bind(("table", table.to_string()))
+            .bind(("key", key.to_string()))
+            .bind(("pk", public_key.to_string()))
+            .bind(("sig", signature.to_string()))
+            .await?
+            .take(0)?;
+        Ok(())
+    }
+
     // -- Contacts ---
 
     async fn create_contact(&self, contact: Contact) -> DbResult<Contact> {
@@ -937,6 +1301,7 @@ impl GraphDB for SurrealGraphDB {
         contact.ok_or_else(|| DbError::NotFound(id.to_string()))
     }
 
+    #[instrument(name = "db.list_contacts", level = "debug", skip_all)]
     async fn list_contacts(&self) -> DbResult<Vec<Contact>> {
         let mut result = self
             .db
@@ -1074,6 +1439,36 @@ impl GraphDB for SurrealGraphDB {
         updated.ok_or_else(|| DbError::Query("Failed to add contact address".into()))
     }
 
+    async fn set_contact_addresses(
+        &self,
+        id: &str,
+        addresses: Vec<crate::schema::ChannelAddress>,
+    ) -> DbResult<Contact> {
+        let (table, key) = parse_and_validate(id, "contact")?;
+        let current: Option<Contact> = self.db.select((table, key)).await?;
+        let mut contact = current.ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        // Plaintext replaces any encrypted blob; the encrypted layer
+        // re-encrypts right after.
+        contact.addresses = addresses;
+        contact.addresses_encrypted = None;
+        contact.addresses_nonce = None;
+        contact.modified_at = Utc::now();
+
+        let updated: Option<Contact> = self.db.update((table, key)).content(contact).await?;
+        updated.ok_or_else(|| DbError::Query("Failed to set contact addresses".into()))
+    }
+
+    async fn set_contact_owned(&self, id: &str, is_owned: bool) -> DbResult<Contact> {
+        let (table, key) = parse_and_validate(id, "contact")?;
+        let current: Option<Contact> = self.db.select((table, key)).await?;
+        let mut contact = current.ok_or_else(|| DbError::NotFound(id.to_string()))?;
+        contact.is_owned = is_owned;
+        contact.modified_at = Utc::now();
+
+        let updated: Option<Contact> = self.db.update((table, key)).content(contact).await?;
+        updated.ok_or_else(|| DbError::Query("Failed to update contact".into()))
+    }
+
     // -- Messages ---
 
     async fn create_message(&self, message: Message) -> DbResult<Message> {
@@ -1087,6 +1482,7 @@ impl GraphDB for SurrealGraphDB {
         message.ok_or_else(|| DbError::NotFound(id.to_string()))
     }
 
+    #[instrument(name = "db.list_messages", level = "debug", skip_all)]
     async fn list_messages(
         &self,
         conversation_id: &str,
@@ -1164,6 +1560,7 @@ impl GraphDB for SurrealGraphDB {
         Ok(msgs)
     }
 
+    #[instrument(name = "db.search_messages", level = "debug", skip_all)]
     async fn search_messages(&self, query: &str) -> DbResult<Vec<Message>> {
         let q = query.to_string();
         let mut result = self
@@ -1831,7 +2228,8 @@ impl GraphDB for SurrealGraphDB {
             .query(
                 "INSERT RELATION INTO related_to {
                     id: $id, in: $in, out: $out,
-                    relation_type: $rtype, strength: $strength, created_at: $created_at
+                    relation_type: $rtype, strength: $strength, created_at: $created_at,
+                    from_body: $from_body
                 }",
             )
             .bind(("id", Thing::from((table.to_string(), key.to_string()))))
@@ -1840,6 +2238,7 @@ impl GraphDB for SurrealGraphDB {
             .bind(("rtype", rel.relation_type.to_string()))
             .bind(("strength", rel.strength))
             .bind(("created_at", rel.created_at))
+            .bind(("from_body", rel.from_body))
             .await?
             .check()?;
         Ok(true)
@@ -1983,6 +2382,23 @@ mod tests {
         assert_eq!(docs.len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_search_documents_by_whole_tag() {
+        let db = setup_db().await;
+        let mut planning = Document::new("Q3".into(), "thread:test".into(), true);
+        planning.content = r#"{"body":"Roadmap #planning","images":[]}"#.into();
+        db.create_document(planning).await.unwrap();
+        let mut plan = Document::new("Trip".into(), "thread:test".into(), true);
+        plan.content = r#"{"body":"Tickets #Plan, hotel next","images":[]}"#.into();
+        db.create_document(plan).await.unwrap();
+
+        let found = db.search_documents_by_tag("#plan").await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].title, "Trip");
+        assert_eq!(db.search_documents_by_tag("planning").await.unwrap()[0].title, "Q3");
+        assert!(db.search_documents_by_tag("plann").await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_list_documents_by_thread() {
         let db = setup_db().await;
@@ -2011,6 +2427,107 @@ mod tests {
         assert_eq!(updated.content, "New content");
     }
 
+    #[tokio::test]
+    async fn test_read_later_is_set_and_cleared() {
+        let db = setup_db().await;
+        let doc = Document::new("Long read".into(), "thread:t".into(), false);
+        let created = db.create_document(doc).await.unwrap();
+        let id = created.id_string().unwrap();
+
+        let at = Utc::now();
+        db.set_read_later(&id, Some(at)).await.unwrap();
+        let queued = db.get_document(&id).await.unwrap();
+        assert_eq!(queued.read_later, Some(at));
+        assert_eq!(queued.modified_at, created.modified_at);
+
+        db.set_read_later(&id, None).await.unwrap();
+        assert_eq!(db.get_document(&id).await.unwrap().read_later, None);
+        assert!(db.set_read_later("document:missing", None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_favorite_is_pinned_and_unpinned() {
+        let db = setup_db().await;
+        let doc = Document::new("Roadmap".into(), "thread:t".into(), true);
+        let created = db.create_document(doc).await.unwrap();
+        let id = created.id_string().unwrap();
+        assert!(!created.favorite);
+
+        let pinned_at = Utc::now();
+        db.set_favorite(&id, true, pinned_at).await.unwrap();
+        let pinned = db.get_document(&id).await.unwrap();
+        assert!(pinned.favorite);
+        assert_eq!(pinned.favorite_at, Some(pinned_at));
+        assert_eq!(pinned.modified_at, created.modified_at);
+
+        let unpinned_at = Utc::now();
+        db.set_favorite(&id, false, unpinned_at).await.unwrap();
+        let unpinned = db.get_document(&id).await.unwrap();
+        assert!(!unpinned.favorite);
+        assert_eq!(unpinned.favorite_at, Some(unpinned_at));
+    }
+
+    #[tokio::test]
+    async fn test_sensitivity_is_set_without_touching_modified_at() {
+        let db = setup_db().await;
+        let doc = Document::new("Diagnosis".into(), "thread:t".into(), true);
+        let created = db.create_document(doc).await.unwrap();
+        let id = created.id_string().unwrap();
+        assert_eq!(created.sensitivity, Sensitivity::Normal);
+        assert!(created.ai_may_read());
+
+        let at = Utc::now();
+        db.set_sensitivity(&id, Sensitivity::Sealed, at).await.unwrap();
+        let sealed = db.get_document(&id).await.unwrap();
+        assert_eq!(sealed.sensitivity, Sensitivity::Sealed);
+        assert_eq!(sealed.sensitivity_at, Some(at));
+        assert_eq!(sealed.modified_at, created.modified_at);
+        assert!(!sealed.ai_may_see());
+        assert!(db
+            .set_sensitivity("document:missing", Sensitivity::Normal, at)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_permissions_gate_document_writes() {
+        let db = setup_db().await;
+        let doc = Document::new("Shared".into(), "thread:t".into(), false);
+        let id = db.create_document(doc).await.unwrap().id_string().unwrap();
+
+        let mut perms = DocumentPermissions::owned_by(Principal::Peer("alice".into()));
+        perms.grant(Principal::Local, crate::schema::AccessLevel::Read);
+        db.set_document_permissions(&id, perms.clone()).await.unwrap();
+        assert_eq!(db.get_document(&id).await.unwrap().permissions, perms);
+
+        fn denied<T>(r: DbResult<T>) -> bool {
+            matches!(r, Err(DbError::PermissionDenied(_)))
+        }
+        assert!(denied(db.update_document(&id, Some("Mine now"), None).await));
+        assert!(denied(db.update_document_type(&id, DocType::Spec).await));
+        assert!(denied(db.set_read_later(&id, Some(Utc::now())).await));
+        assert!(denied(db.set_favorite(&id, true, Utc::now()).await));
+        assert!(denied(
+            db.set_sensitivity(&id, Sensitivity::Sealed, Utc::now())
+                .await
+        ));
+        assert!(denied(
+            db.create_reminder(Reminder::new(id.clone(), "Later".into(), Utc::now()))
+                .await
+        ));
+        assert!(denied(db.move_document_to_thread(&id, "thread:u").await));
+        assert!(denied(db.soft_delete_document(&id).await));
+        assert!(denied(db.delete_document(&id).await));
+        // Only the owner may re-share.
+        assert!(denied(
+            db.set_document_permissions(&id, DocumentPermissions::default()).await
+        ));
+        assert_eq!(db.get_document(&id).await.unwrap().title, "Shared");
+
+        // Position is this device's layout, not the document's.
+        db.update_document_position(&id, 4.0, 2.0).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_delete_document() {
         let db = setup_db().await;
@@ -2133,6 +2650,28 @@ mod tests {
         assert_eq!(fetched.snapshot.title, "G");
     }
 
+    #[tokio::test]
+    async fn test_prune_document_commits_keeps_newest() {
+        let db = setup_db().await;
+        let doc = Document::new("P".into(), "thread:t".into(), true);
+        let created = db.create_document(doc).await.unwrap();
+        let doc_id = created.id_string().unwrap();
+
+        for msg in ["One", "Two", "Three", "Four"] {
+            db.commit_document(&doc_id, msg).await.unwrap();
+        }
+
+        assert_eq!(db.prune_document_commits(&doc_id, 2).await.unwrap(), 2);
+        let commits = db.list_document_commits(&doc_id).await.unwrap();
+        let messages: Vec<&str> = commits.iter().map(|c| c.message.as_str()).collect();
+        assert_eq!(messages, ["Four", "Three"]);
+        assert_eq!(db.get_document(&doc_id).await.unwrap().head_commit, commits[0].id_string());
+
+        // keep = 0 still spares the head commit.
+        assert_eq!(db.prune_document_commits(&doc_id, 0).await.unwrap(), 1);
+        assert_eq!(db.list_document_commits(&doc_id).await.unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_restore_document() {
         let db = setup_db().await;
@@ -2420,6 +2959,35 @@ mod tests {
         assert!(milestones.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_reminders_come_due_and_fire_once() {
+        let db = setup_db().await;
+        let doc = Document::new("Budget Overview".into(), "thread:t".into(), true);
+        let doc_id = db.create_document(doc).await.unwrap().id_string().unwrap();
+
+        let now = Utc::now();
+        let soon = now - chrono::Duration::minutes(5);
+        let later = now + chrono::Duration::days(2);
+        let past = Reminder::new(doc_id.clone(), "Review budget".into(), soon);
+        let future = Reminder::new(doc_id.clone(), "Send budget".into(), later);
+        let past_id = db.create_reminder(past).await.unwrap().id_string().unwrap();
+        db.create_reminder(future).await.unwrap();
+
+        let listed = db.list_reminders(Some(&doc_id)).await.unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].text, "Review budget");
+
+        let due = db.list_due_reminders(now).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id_string().unwrap(), past_id);
+
+        db.mark_reminder_fired(&past_id, now).await.unwrap();
+        assert!(db.list_due_reminders(now).await.unwrap().is_empty());
+
+        db.delete_reminder(&past_id).await.unwrap();
+        assert_eq!(db.list_reminders(None).await.unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_purge_deleted() {
         let db = setup_db().await;
@@ -2445,6 +3013,22 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_list_deleted_documents_and_threads() {
+        let db = setup_db().await;
+        let kept = db.create_document(Document::new("Kept".into(), "thread:t".into(), true)).await.unwrap();
+        let binned = db.create_document(Document::new("Binned".into(), "thread:t".into(), true)).await.unwrap();
+        let thread = db.create_thread(Thread::new("Old".into(), "".into())).await.unwrap();
+        db.soft_delete_document(&binned.id_string().unwrap()).await.unwrap();
+        db.soft_delete_thread(&thread.id_string().unwrap()).await.unwrap();
+
+        let docs = db.list_deleted_documents().await.unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].title, "Binned");
+        assert_ne!(docs[0].id, kept.id);
+        assert_eq!(db.list_deleted_threads().await.unwrap()[0].name, "Old");
+    }
+
     // -- Contact tests ---
 
     #[tokio::test]
@@ -2523,6 +3107,33 @@ mod tests {
         assert!(not_found.is_none());
     }
 
+    #[tokio::test]
+    async fn test_set_contact_addresses_and_owned() {
+        use crate::schema::{ChannelAddress, ChannelType};
+        let db = setup_db().await;
+        let created = db.create_contact(Contact::new("Alice".into(), false)).await.unwrap();
+        let id = created.id_string().unwrap();
+        let email = |a: &str| ChannelAddress {
+            channel: ChannelType::Email,
+            address: a.into(),
+            display_name: None,
+            is_primary: false,
+        };
+        db.add_contact_address(&id, email("old@example.com")).await.unwrap();
+
+        let updated = db
+            .set_contact_addresses(&id, vec![email("new@example.com")])
+            .await
+            .unwrap();
+        assert_eq!(updated.addresses.len(), 1);
+        assert_eq!(updated.addresses[0].address, "new@example.com");
+        assert!(db.find_contact_by_address("old@example.com").await.unwrap().is_none());
+
+        let owned = db.set_contact_owned(&id, true).await.unwrap();
+        assert!(owned.is_owned);
+        assert!(db.get_contact(&id).await.unwrap().is_owned);
+    }
+
     #[tokio::test]
     async fn test_delete_contact() {
         let db = setup_db().await;
@@ -2845,6 +3456,9 @@ mod tests {
             relation_type: RelationType::References,
             strength: 0.9,
             created_at: Utc::now(),
+            from_body: false,
+            reinforced_at: None,
+            adjusted_at: None,
         };
         assert!(db.create_relationship_with_id(rel.clone()).await.unwrap());
         // Idempotent on replay.
@@ -0,0 +1,167 @@
+//! The `tasks` projection: every checklist item across the workspace,
+//! with its due date, status and source document.
+//!
+//! Tasks live in document bodies (see `sovereign_sdk::blocks`); this
+//! derives them on read instead of storing a second copy, so they can't
+//! drift from the documents, and encrypted bodies are read through the
+//! same [`GraphDB`] that decrypts them.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sovereign_sdk::blocks;
+use sovereign_sdk::content::ContentFields;
+
+use crate::error::DbResult;
+use crate::schema::Document;
+use crate::traits::GraphDB;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Open,
+    Done,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    pub document_id: String,
+    pub document_title: String,
+    pub thread_id: String,
+    /// Position among the document's checklist items; what
+    /// `blocks::set_checked` takes to tick it.
+    pub index: usize,
+    pub text: String,
+    pub due: Option<NaiveDate>,
+    pub status: TaskStatus,
+}
+
+/// Which tasks [`list_tasks`] returns. The default is every task.
+#[derive(Debug, Clone, Default)]
+pub struct TaskFilter {
+    pub status: Option<TaskStatus>,
+    /// Only tasks due on or before this date (overdue ones included).
+    pub due_by: Option<NaiveDate>,
+}
+
+impl TaskFilter {
+    /// Open tasks due on or before `date`.
+    pub fn open_due_by(date: NaiveDate) -> Self {
+        Self {
+            status: Some(TaskStatus::Open),
+            due_by: Some(date),
+        }
+    }
+
+    fn matches(&self, task: &Task) -> bool {
+        self.status.is_none_or(|s| s == task.status)
+            && self
+                .due_by
+                .is_none_or(|by| task.due.is_some_and(|due| due <= by))
+    }
+}
+
+/// The tasks in one document.
+pub fn document_tasks(doc: &Document) -> Vec<Task> {
+    let document_id = doc.id_string().unwrap_or_default();
+    blocks::tasks(&ContentFields::parse(&doc.content).body)
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| Task {
+            document_id: document_id.clone(),
+            document_title: doc.title.clone(),
+            thread_id: doc.thread_id.clone(),
+            index,
+            due: item.due(),
+            status: if item.checked {
+                TaskStatus::Done
+            } else {
+                TaskStatus::Open
+            },
+            text: item.text,
+        })
+        .collect()
+}
+
+/// Open tasks in one document, for the canvas badge.
+pub fn open_task_count(doc: &Document) -> usize {
+    blocks::tasks(&ContentFields::parse(&doc.content).body)
+        .iter()
+        .filter(|item| !item.checked)
+        .count()
+}
+
+/// Tasks across all live documents that match `filter`: dated ones
+/// first, soonest due first, then by document and position.
+pub async fn list_tasks(db: &dyn GraphDB, filter: &TaskFilter) -> DbResult<Vec<Task>> {
+    let mut tasks: Vec<Task> = db
+        .list_documents(None)
+        .await?
+        .iter()
+        .flat_map(document_tasks)
+        .filter(|t| filter.matches(t))
+        .collect();
+    tasks.sort_by(|a, b| {
+        (a.due.is_none(), a.due, &a.document_title, a.index).cmp(&(
+            b.due.is_none(),
+            b.due,
+            &b.document_title,
+            b.index,
+        ))
+    });
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockGraphDB;
+
+    fn doc(title: &str, body: &str) -> Document {
+        let mut doc = Document::new(title.into(), "thread:t".into(), true);
+        doc.content = ContentFields {
+            body: body.into(),
+            ..Default::default()
+        }
+        .serialize();
+        doc
+    }
+
+    #[tokio::test]
+    async fn list_tasks_filters_and_orders_by_due_date() {
+        let db = MockGraphDB::new();
+        db.create_document(doc(
+            "Home",
+            "- [ ] plants due:2026-03-04\n- [x] bins due:2026-03-01\n- [ ] someday",
+        ))
+        .await
+        .unwrap();
+        db.create_document(doc(
+            "Work",
+            "- [ ] report due:2026-03-02\n- [ ] review due:2026-03-20",
+        ))
+        .await
+        .unwrap();
+
+        let all = list_tasks(&db, &TaskFilter::default()).await.unwrap();
+        let texts: Vec<&str> = all.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            [
+                "bins due:2026-03-01",
+                "report due:2026-03-02",
+                "plants due:2026-03-04",
+                "review due:2026-03-20",
+                "someday"
+            ]
+        );
+
+        let week = NaiveDate::from_ymd_opt(2026, 3, 7).unwrap();
+        let due = list_tasks(&db, &TaskFilter::open_due_by(week))
+            .await
+            .unwrap();
+        assert_eq!(due.len(), 2);
+        assert_eq!(due[0].document_title, "Work");
+        assert_eq!(due[1].index, 0);
+        assert_eq!(open_task_count(&doc("x", "- [ ] a\n- [x] b")), 1);
+    }
+}
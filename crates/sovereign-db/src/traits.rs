@@ -3,9 +3,10 @@ use chrono::{DateTime, Utc};
 
 use crate::error::DbResult;
 use crate::schema::{
-    ChannelType, Commit, Contact, Conversation, Document, Entity, EntityKind, Message, Milestone,
-    PiiRecord, ReadStatus, RelatedTo, RelationType, ReviewState, ShareRecord, SourceRef,
-    SuggestedLink, SuggestionSource, SuggestionStatus, Thread,
+    ChannelType, Commit, Contact, Conversation, DocType, Document, DocumentPermissions, Entity,
+    EntityKind, Message, Milestone, PiiRecord, ReadStatus, RelatedTo, RelationType, Reminder,
+    ReviewState, Sensitivity, ShareRecord, SourceRef, SuggestedLink, SuggestionSource,
+    SuggestionStatus, Thread,
 };
 
 /// Core database abstraction for the Sovereign GE document graph.
@@ -29,6 +30,10 @@ pub trait GraphDB: Send + Sync {
     /// exists, `Ok(true)` if newly inserted. The `doc.id` must be set.
     async fn create_document_with_id(&self, doc: Document) -> DbResult<bool>;
 
+    /// Insert several new documents in one write (bulk import). Returns
+    /// them with their ids, in the order given.
+    async fn create_documents(&self, docs: Vec<Document>) -> DbResult<Vec<Document>>;
+
     async fn get_document(&self, id: &str) -> DbResult<Document>;
     async fn list_documents(&self, thread_id: Option<&str>) -> DbResult<Vec<Document>>;
     async fn update_document(
@@ -42,6 +47,41 @@ pub trait GraphDB: Send + Sync {
     /// Update a document's spatial canvas position.
     async fn update_document_position(&self, id: &str, x: f32, y: f32) -> DbResult<()>;
 
+    /// Change a document's type. Leaves `modified_at` alone: the content
+    /// didn't change.
+    async fn update_document_type(&self, id: &str, doc_type: DocType) -> DbResult<()>;
+
+    /// Put a document in the reading queue as of `at`, or take it out
+    /// with `None`. A personal annotation: needs `Annotate`, and leaves
+    /// `modified_at` alone.
+    async fn set_read_later(&self, id: &str, at: Option<DateTime<Utc>>) -> DbResult<()>;
+
+    /// Pin a document as a favorite or unpin it, as of `at` (sync passes
+    /// the time the change was made on the other device). Like the
+    /// reading queue, needs `Annotate` and leaves `modified_at` alone.
+    async fn set_favorite(&self, id: &str, favorite: bool, at: DateTime<Utc>) -> DbResult<()>;
+
+    /// Set how carefully a document is handled, as of `at`. Needs
+    /// `Annotate` and leaves `modified_at` alone, like a favorite.
+    async fn set_sensitivity(
+        &self,
+        id: &str,
+        sensitivity: Sensitivity,
+        at: DateTime<Utc>,
+    ) -> DbResult<()>;
+
+    /// Replace a document's owner and collaborators. Only the owner may.
+    ///
+    /// Content write paths (`update_document`, `update_document_type`,
+    /// `move_document_to_thread`, `restore_document`) need this account to
+    /// hold `Edit`; `delete_document` and `soft_delete_document` need it to
+    /// be the owner. Refusals are `DbError::PermissionDenied`.
+    async fn set_document_permissions(
+        &self,
+        id: &str,
+        permissions: DocumentPermissions,
+    ) -> DbResult<()>;
+
     /// Search documents by title (case-insensitive substring match).
     /// On `EncryptedGraphDB`, tokenizes + hashes the query and delegates to
     /// `search_documents_by_title_token_hashes`. On raw `SurrealGraphDB`,
@@ -54,6 +94,22 @@ pub trait GraphDB: Send + Sync {
         hashes: &[String],
     ) -> DbResult<Vec<Document>>;
 
+    /// Full-text search over document content.
+    /// On `EncryptedGraphDB`, tokenizes + hashes the query and delegates to
+    /// `search_documents_by_content_token_hashes` (every token must occur).
+    /// On raw `SurrealGraphDB`, does a case-insensitive substring match.
+    async fn search_documents(&self, query: &str) -> DbResult<Vec<Document>>;
+
+    /// Documents whose content carries `#tag` (case-insensitive, `#` optional
+    /// in `tag`). Encrypted content is matched through the tag blind index.
+    async fn search_documents_by_tag(&self, tag: &str) -> DbResult<Vec<Document>>;
+
+    /// Blind-index lookup over `document.content_token_hashes` (CONTAINSALL).
+    async fn search_documents_by_content_token_hashes(
+        &self,
+        hashes: &[String],
+    ) -> DbResult<Vec<Document>>;
+
     /// Internal setter used by `EncryptedGraphDB::create_document` /
     /// `update_document` to write back encrypted title fields. The id-passed-in
     /// must already exist in the DB.
@@ -67,12 +123,14 @@ pub trait GraphDB: Send + Sync {
 
     /// Internal setter used by `EncryptedGraphDB` to write back encrypted
     /// document content together with its nonce — without the nonce persisted
-    /// alongside, the ciphertext is unreadable on decrypt.
+    /// alongside, the ciphertext is unreadable on decrypt — and the
+    /// content_token_hashes index array.
     async fn set_document_content_encryption(
         &self,
         id: &str,
         content_ciphertext: &str,
         content_nonce: &str,
+        content_token_hashes: &[String],
     ) -> DbResult<()>;
 
     /// Update a document's reliability assessment fields.
@@ -138,6 +196,11 @@ pub trait GraphDB: Send + Sync {
         strength: f32,
     ) -> DbResult<RelatedTo>;
 
+    /// Create a `References` edge for a link written in `from_id`'s body,
+    /// marked [`RelatedTo::from_body`]. Only `crate::links` should call
+    /// this: it deletes such edges once the link is gone.
+    async fn create_body_link(&self, from_id: &str, to_id: &str) -> DbResult<RelatedTo>;
+
     /// List edges where this document is the source (outgoing).
     async fn list_outgoing_relationships(&self, doc_id: &str) -> DbResult<Vec<RelatedTo>>;
 
@@ -147,7 +210,22 @@ pub trait GraphDB: Send + Sync {
     /// List all relationships in the database.
     async fn list_all_relationships(&self) -> DbResult<Vec<RelatedTo>>;
 
-    /// Traverse the graph from a document, returning connected documents up to `depth` hops.
+    /// Remove one relationship edge.
+    async fn delete_relationship(&self, id: &str) -> DbResult<()>;
+
+    /// Set an edge's strength and stamp `adjusted_at` with `at`; with
+    /// `reinforced`, also `reinforced_at`.
+    async fn set_relationship_strength(
+        &self,
+        id: &str,
+        strength: f32,
+        reinforced: bool,
+        at: DateTime<Utc>,
+    ) -> DbResult<()>;
+
+    /// Traverse the graph from a document, returning connected documents up
+    /// to `depth` hops. Edges faded to [`RelatedTo::FADED_STRENGTH`] are
+    /// not followed.
     async fn traverse(&self, doc_id: &str, depth: u32, limit: u32) -> DbResult<Vec<Document>>;
 
     // -- Suggested Links (AI-created, separate from user relationships) ---
@@ -212,6 +290,12 @@ pub trait GraphDB: Send + Sync {
     /// Restore a soft-deleted thread (clear deleted_at).
     async fn restore_soft_deleted_thread(&self, id: &str) -> DbResult<Thread>;
 
+    /// Soft-deleted documents (the trash), most recently deleted first.
+    async fn list_deleted_documents(&self) -> DbResult<Vec<Document>>;
+
+    /// Soft-deleted threads, most recently deleted first.
+    async fn list_deleted_threads(&self) -> DbResult<Vec<Thread>>;
+
     /// Permanently remove records whose deleted_at is older than `max_age`.
     async fn purge_deleted(&self, max_age: std::time::Duration) -> DbResult<u64>;
 
@@ -223,6 +307,11 @@ pub trait GraphDB: Send + Sync {
     /// List commits for a specific document, most recent first.
     async fn list_document_commits(&self, doc_id: &str) -> DbResult<Vec<Commit>>;
 
+    /// Delete all but the `keep` most recent commits of a document. The
+    /// head commit is always kept; the oldest survivor keeps its now-dangling
+    /// parent id. Returns how many commits were removed.
+    async fn prune_document_commits(&self, doc_id: &str, keep: usize) -> DbResult<u64>;
+
     /// Get a single commit by ID.
     async fn get_commit(&self, commit_id: &str) -> DbResult<Commit>;
 
@@ -232,6 +321,14 @@ pub trait GraphDB: Send + Sync {
     /// AUTOCOMMIT-001: store the tamper-evidence MAC on a commit row.
     async fn set_commit_signature(&self, commit_id: &str, signature: &str) -> DbResult<()>;
 
+    /// Store the Ed25519 device public key and signature on a commit row.
+    async fn set_commit_device_signature(
+        &self,
+        commit_id: &str,
+        public_key: &str,
+        signature: &str,
+    ) -> DbResult<()>;
+
     // -- Milestones ---
 
     /// Create a milestone on a thread's timeline.
@@ -246,6 +343,23 @@ pub trait GraphDB: Send + Sync {
     /// Delete a milestone by ID.
     async fn delete_milestone(&self, id: &str) -> DbResult<()>;
 
+    // -- Reminders ---
+
+    /// Create a reminder on a document.
+    async fn create_reminder(&self, reminder: Reminder) -> DbResult<Reminder>;
+
+    /// List reminders, soonest first. `None` lists every document's.
+    async fn list_reminders(&self, document_id: Option<&str>) -> DbResult<Vec<Reminder>>;
+
+    /// Unfired reminders whose due time is at or before `now`.
+    async fn list_due_reminders(&self, now: DateTime<Utc>) -> DbResult<Vec<Reminder>>;
+
+    /// Record that a reminder has been delivered.
+    async fn mark_reminder_fired(&self, id: &str, at: DateTime<Utc>) -> DbResult<()>;
+
+    /// Delete a reminder by ID.
+    async fn delete_reminder(&self, id: &str) -> DbResult<()>;
+
     // -- Contacts ---
 
     /// Create a new contact.
@@ -312,6 +426,17 @@ pub trait GraphDB: Send + Sync {
         address: crate::schema::ChannelAddress,
     ) -> DbResult<Contact>;
 
+    /// Replace all of a contact's addresses — the contact form's edits and
+    /// removals. `add_contact_address` only ever appends.
+    async fn set_contact_addresses(
+        &self,
+        id: &str,
+        addresses: Vec<crate::schema::ChannelAddress>,
+    ) -> DbResult<Contact>;
+
+    /// Mark a contact as one of the user's own identities, or not.
+    async fn set_contact_owned(&self, id: &str, is_owned: bool) -> DbResult<Contact>;
+
     // -- Messages ---
 
     /// Create a new message.
@@ -0,0 +1,101 @@
+//! Manual peer addresses: the discovery fallback for networks that block
+//! mDNS multicast (guest Wi-Fi, corporate LANs, some VPNs).
+//!
+//! The user types where a paired device can be reached — `ip:port` or a
+//! full multiaddr — and it is kept with that device's pairing record.
+//! The node adds those addresses to the swarm and redials any paired
+//! device it isn't connected to every [`RECONNECT_INTERVAL`]; once the
+//! connection is up, sync starts as it would after mDNS discovery. The
+//! device being dialed needs a fixed `listen_port` for this to keep
+//! working across restarts.
+//!
+//! The same parsing serves pairing: a new device that can't discover
+//! the source over mDNS can be given its address alongside the QR.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use libp2p::multiaddr::Protocol;
+use libp2p::{Multiaddr, PeerId};
+
+use crate::error::{P2pError, P2pResult};
+
+/// How often the node redials address-book peers it isn't connected to.
+pub const RECONNECT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Parse a user-entered peer address into a dialable multiaddr.
+///
+/// Accepts `ip:port` (`192.168.1.20:4001`, `[fe80::1]:4001`), taken as
+/// QUIC — the only transport the node speaks — or any multiaddr. With
+/// `peer`, the result ends in `/p2p/<peer>` so the handshake
+/// authenticates whoever answers; a multiaddr naming another peer is
+/// rejected.
+pub fn parse_peer_address(input: &str, peer: Option<PeerId>) -> P2pResult<Multiaddr> {
+    let input = input.trim();
+    let mut addr: Multiaddr = if input.starts_with('/') {
+        input
+            .parse()
+            .map_err(|e| P2pError::DialError(format!("invalid multiaddr {input}: {e}")))?
+    } else {
+        let socket: SocketAddr = input.parse().map_err(|_| {
+            P2pError::DialError(format!("expected ip:port or a multiaddr, got {input:?}"))
+        })?;
+        Multiaddr::empty()
+            .with(socket.ip().into())
+            .with(Protocol::Udp(socket.port()))
+            .with(Protocol::QuicV1)
+    };
+    if let Some(peer) = peer {
+        match addr.iter().last() {
+            Some(Protocol::P2p(named)) if named != peer => {
+                return Err(P2pError::DialError(format!(
+                    "{input} belongs to {named}, not {peer}"
+                )));
+            }
+            Some(Protocol::P2p(_)) => {}
+            _ => addr.push(Protocol::P2p(peer)),
+        }
+    }
+    Ok(addr)
+}
+
+/// [`parse_peer_address`] for callers holding peer ids as strings: the
+/// canonical multiaddr to store, ending in `/p2p/<peer_id>`.
+pub fn normalize_peer_address(input: &str, peer_id: &str) -> P2pResult<String> {
+    let peer: PeerId = peer_id
+        .parse()
+        .map_err(|e| P2pError::DialError(format!("invalid peer id {peer_id}: {e}")))?;
+    parse_peer_address(input, Some(peer)).map(|a| a.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn socket_addresses_become_quic_multiaddrs() {
+        let peer = PeerId::random();
+        let addr = parse_peer_address(" 192.168.1.20:4001 ", Some(peer)).unwrap();
+        assert_eq!(
+            addr.to_string(),
+            format!("/ip4/192.168.1.20/udp/4001/quic-v1/p2p/{peer}")
+        );
+        let v6 = parse_peer_address("[fe80::1]:4001", None).unwrap();
+        assert_eq!(v6.to_string(), "/ip6/fe80::1/udp/4001/quic-v1");
+    }
+
+    #[test]
+    fn multiaddrs_must_name_the_expected_peer() {
+        let peer = PeerId::random();
+        let bare = "/ip4/10.0.0.2/udp/4001/quic-v1";
+        let addr = parse_peer_address(bare, Some(peer)).unwrap();
+        assert_eq!(addr.to_string(), format!("{bare}/p2p/{peer}"));
+        assert!(parse_peer_address(&addr.to_string(), Some(peer)).is_ok());
+        assert!(parse_peer_address(&addr.to_string(), Some(PeerId::random())).is_err());
+        assert!(parse_peer_address("my-laptop.local:4001", None).is_err());
+        assert_eq!(
+            normalize_peer_address("10.0.0.2:4001", &peer.to_string()).unwrap(),
+            format!("{bare}/p2p/{peer}")
+        );
+    }
+}
@@ -395,6 +395,10 @@ pub struct BackupGuardianPayload {
     pub key_share_b64: String,
     pub salt_b64: String,
     pub manifest_json: String,
+    /// Share of the security-key recovery key, when the owner unlocks with
+    /// a FIDO2 token — lets the guardians stand in for a lost token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub second_factor_share_b64: Option<String>,
 }
 
 impl BackupGuardianPayload {
@@ -407,6 +411,7 @@ impl BackupGuardianPayload {
             key_share_b64: base64::engine::general_purpose::STANDARD.encode(key_share),
             salt_b64: base64::engine::general_purpose::STANDARD.encode(salt),
             manifest_json,
+            second_factor_share_b64: None,
         }
     }
 
@@ -436,6 +441,30 @@ pub struct PreparedBackup {
     pub guardian_payloads: Vec<(String, String)>,
 }
 
+impl PreparedBackup {
+    /// Split the security-key recovery key across the same guardians, with
+    /// the same threshold as the backup key, adding one share to each
+    /// guardian payload.
+    pub fn attach_second_factor_recovery(&mut self, recovery_key: &[u8; 32]) -> P2pResult<()> {
+        use base64::Engine;
+        let shares = sovereign_crypto::guardian::shamir::split_secret(
+            recovery_key,
+            self.manifest.key_threshold,
+            self.guardian_payloads.len(),
+        )
+        .map_err(|e| P2pError::SyncError(format!("recovery key split: {e}")))?;
+        for ((_, encoded), share) in self.guardian_payloads.iter_mut().zip(shares.iter()) {
+            let mut payload = BackupGuardianPayload::decode(encoded)?;
+            payload.second_factor_share_b64 = Some(
+                base64::engine::general_purpose::STANDARD
+                    .encode(sovereign_crypto::guardian::shamir::share_to_bytes(share)),
+            );
+            *encoded = payload.encode()?;
+        }
+        Ok(())
+    }
+}
+
 /// P4.1 end-to-end: snapshot → seal under a fresh key → erasure-code →
 /// Shamir-split the key across `guardian_ids` (threshold `key_threshold`).
 /// The backup key is dropped (zeroized) before returning — after this,
@@ -676,4 +705,42 @@ mod tests {
         let again = restore_snapshot(&fresh, &snapshot).await.unwrap();
         assert_eq!(again, 0);
     }
+
+    #[tokio::test]
+    async fn second_factor_recovery_key_rides_on_guardian_payloads() {
+        let db = seeded_db().await;
+        let guardians: Vec<String> = (1..=3).map(|i| format!("guardian-{i}")).collect();
+        let mut prepared = prepare_backup(
+            db.as_ref(),
+            "device-1",
+            "ownertag123",
+            b"master-salt",
+            1,
+            &guardians,
+            2,
+            2,
+            1,
+        )
+        .await
+        .unwrap();
+        prepared.attach_second_factor_recovery(&[9u8; 32]).unwrap();
+
+        let payloads: Vec<_> = prepared.guardian_payloads[1..]
+            .iter()
+            .map(|(_, b64)| BackupGuardianPayload::decode(b64).unwrap())
+            .collect();
+        assert!(payloads.iter().all(|p| p.second_factor_share_b64.is_some()));
+        let key = crate::backup_client::reconstruct_second_factor_key(&payloads, 2).unwrap();
+        assert_eq!(key, Some([9u8; 32]));
+
+        // Payloads from before the field existed decode without it.
+        let mut plain = payloads[0].clone();
+        plain.second_factor_share_b64 = None;
+        let json = serde_json::to_string(&plain).unwrap();
+        assert!(!json.contains("second_factor"));
+        assert_eq!(
+            crate::backup_client::reconstruct_second_factor_key(&[plain], 2).unwrap(),
+            None
+        );
+    }
 }
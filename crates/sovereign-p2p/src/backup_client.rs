@@ -238,6 +238,34 @@ pub async fn request_guardian_shard(
     }
 }
 
+/// Reassemble the security-key recovery key from guardian payloads (a
+/// lost-token unlock, see `AuthStore::remove_second_factor`). `Ok(None)`
+/// if the payloads carry no second-factor shares.
+pub fn reconstruct_second_factor_key(
+    guardian_payloads: &[BackupGuardianPayload],
+    threshold: u8,
+) -> P2pResult<Option<[u8; 32]>> {
+    use base64::Engine;
+
+    let shares = guardian_payloads
+        .iter()
+        .filter_map(|p| p.second_factor_share_b64.as_deref())
+        .map(|b64| {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(b64)
+                .map_err(|e| P2pError::SyncError(format!("recovery share base64: {e}")))?;
+            sovereign_crypto::guardian::shamir::share_from_bytes(&bytes)
+                .map_err(|e| P2pError::SyncError(format!("recovery share decode: {e}")))
+        })
+        .collect::<P2pResult<Vec<_>>>()?;
+    if shares.is_empty() {
+        return Ok(None);
+    }
+    sovereign_crypto::guardian::shamir::reconstruct_secret(&shares, threshold)
+        .map(Some)
+        .map_err(|e| P2pError::SyncError(format!("recovery key reconstruction: {e}")))
+}
+
 /// Offline final assembly (P4.3 step 5): digest-checked reassembly of
 /// the ciphertext, backup-key reconstruction from the guardian
 /// payloads, and unsealing. Verifies the ciphertext digest against the
@@ -1,9 +1,23 @@
+use libp2p::multiaddr::Protocol;
 use libp2p::swarm::behaviour::toggle::Toggle;
 use libp2p::swarm::NetworkBehaviour;
+use libp2p::{Multiaddr, PeerId};
 
 use crate::protocol::{SovereignRequest, SovereignResponse};
 
+/// Rendezvous namespace Sovereign devices register under. Registrations
+/// from unpaired peers are ignored, so a shared server leaks nothing but
+/// the fact that a PeerId runs Sovereign.
+pub const RENDEZVOUS_NAMESPACE: &str = "sovereign";
+
 /// Composite network behaviour for Sovereign GE.
+///
+/// Off the LAN, devices meet through the configured rendezvous server,
+/// which also serves as a circuit relay: each node holds a reservation
+/// there, a peer dials it through the relay, and DCUtR then tries to punch
+/// a direct QUIC path. A relayed connection is only a byte pipe — Noise
+/// runs end to end between the two devices, and the sync payloads keep
+/// their own encryption on top.
 #[derive(NetworkBehaviour)]
 pub struct SovereignBehaviour {
     /// mDNS LAN discovery. Wrapped in `Toggle` so it can be disabled via
@@ -11,6 +25,43 @@ pub struct SovereignBehaviour {
     /// and no automatic peer discovery; pairing must use another path.
     pub mdns: Toggle<libp2p::mdns::tokio::Behaviour>,
     pub rendezvous: libp2p::rendezvous::client::Behaviour,
+    pub relay_client: libp2p::relay::client::Behaviour,
+    pub dcutr: libp2p::dcutr::Behaviour,
     pub request_response: libp2p::request_response::cbor::Behaviour<SovereignRequest, SovereignResponse>,
     pub identify: libp2p::identify::Behaviour,
 }
+
+/// Address at which `peer` is reachable through the relay at `relay_addr`
+/// (which must end in the relay's `/p2p/<id>`).
+pub fn relayed_addr(relay_addr: &Multiaddr, peer: PeerId) -> Multiaddr {
+    relay_addr
+        .clone()
+        .with(Protocol::P2pCircuit)
+        .with(Protocol::P2p(peer))
+}
+
+/// Whether a connection goes through a relay rather than directly.
+pub fn is_relayed(addr: &Multiaddr) -> bool {
+    addr.iter().any(|p| p == Protocol::P2pCircuit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relayed_addr_appends_circuit_and_target() {
+        let relay_id = PeerId::random();
+        let target = PeerId::random();
+        let relay: Multiaddr = format!("/ip4/203.0.113.7/udp/4001/quic-v1/p2p/{relay_id}")
+            .parse()
+            .unwrap();
+        let addr = relayed_addr(&relay, target);
+        assert_eq!(
+            addr.to_string(),
+            format!("/ip4/203.0.113.7/udp/4001/quic-v1/p2p/{relay_id}/p2p-circuit/p2p/{target}")
+        );
+        assert!(is_relayed(&addr));
+        assert!(!is_relayed(&relay));
+    }
+}
@@ -0,0 +1,445 @@
+//! Content-addressed store for the media documents point at.
+//!
+//! Document content references images and videos by filesystem path. The
+//! file itself never travels with the document: sync ships a [`BlobRef`]
+//! (path, SHA-256, size) per media file and the bytes follow separately,
+//! chunked, through `GetBlobs` / `PushBlobs`.
+//!
+//! Every file the store reads or serves is `<dir>/<sha256>`: media pasted
+//! or attached here is written there under its hash, and bytes fetched
+//! from a peer end up there too. A document path names a blob either by
+//! its file name (the hash) or through an alias — the remote path a peer's
+//! document used, recorded with the hash it stood for. Any other path is
+//! never opened, whatever a document says: document content comes from
+//! peers, and a path like `~/.ssh/id_ed25519` must not become something
+//! this device hashes and serves.
+//!
+//! Transfers are resumable: incoming bytes are appended to
+//! `<dir>/<sha256>.part`, a chunk is only accepted at the current end of
+//! that file, and the blob becomes visible only after the whole file hashes
+//! to its name.
+
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{P2pError, P2pResult};
+
+/// Bytes per transferred chunk.
+pub const CHUNK_BYTES: u64 = 256 * 1024;
+
+/// Largest blob accepted from a peer (1 GiB).
+pub const MAX_BLOB_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// A media file referenced by a synced document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobRef {
+    /// Path as written in the document content.
+    pub path: String,
+    /// Lowercase hex SHA-256 of the file.
+    pub hash: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AliasEntry {
+    hash: String,
+    size: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BlobIndex {
+    /// Path used by a peer's document → the blob it refers to.
+    #[serde(default)]
+    aliases: HashMap<String, AliasEntry>,
+}
+
+/// Media paths in a document's content JSON: image, video and attached
+/// file paths plus video thumbnails.
+pub fn media_paths(content: &str) -> Vec<String> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    for list in ["images", "videos", "files"] {
+        for item in value.get(list).and_then(|v| v.as_array()).into_iter().flatten() {
+            for field in ["path", "thumbnail_path"] {
+                if let Some(p) = item.get(field).and_then(|v| v.as_str()) {
+                    if !p.is_empty() && !out.iter().any(|o| o == p) {
+                        out.push(p.to_string());
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Whether `hash` looks like a SHA-256 hex digest. Hashes arrive over the
+/// network and name files, so nothing else is accepted.
+fn valid_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn hash_file(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// The hash a path names by its file name, if it is one.
+fn hash_named(path: &str) -> Option<&str> {
+    let name = Path::new(path).file_name()?.to_str()?;
+    valid_hash(name).then_some(name)
+}
+
+fn io_err(what: &str, e: std::io::Error) -> P2pError {
+    P2pError::SyncError(format!("blob store {what}: {e}"))
+}
+
+/// Interior mutability (std Mutex, never held across an await) so the sync
+/// service can use it from `&self`.
+pub struct BlobStore {
+    /// `None` for a store that holds nothing: it describes, serves and
+    /// accepts no blobs.
+    dir: Option<PathBuf>,
+    index: Mutex<BlobIndex>,
+}
+
+impl BlobStore {
+    /// Open (or initialize) the store under `dir`.
+    pub fn open(dir: PathBuf) -> Self {
+        let index = std::fs::read_to_string(dir.join("index.json"))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self {
+            dir: Some(dir),
+            index: Mutex::new(index),
+        }
+    }
+
+    /// A store that keeps nothing on disk (tests, or a node without a
+    /// data directory). No media is shared and incoming blobs are refused.
+    pub fn ephemeral() -> Self {
+        Self {
+            dir: None,
+            index: Mutex::new(BlobIndex::default()),
+        }
+    }
+
+    fn save(&self, index: &BlobIndex) -> P2pResult<()> {
+        let Some(dir) = &self.dir else { return Ok(()) };
+        std::fs::create_dir_all(dir).map_err(|e| io_err("dir", e))?;
+        let json = serde_json::to_string(index)
+            .map_err(|e| P2pError::SyncError(format!("blob index encode: {e}")))?;
+        sovereign_crypto::fs_private::write_private(&dir.join("index.json"), json)
+            .map_err(|e| io_err("index write", e))
+    }
+
+    fn blob_path(&self, hash: &str) -> Option<PathBuf> {
+        self.dir.as_ref().map(|d| d.join(hash))
+    }
+
+    fn part_path(&self, hash: &str) -> Option<PathBuf> {
+        self.dir.as_ref().map(|d| d.join(format!("{hash}.part")))
+    }
+
+    /// The blob a document's `path` names, when it is held here: by the
+    /// hash in its file name, else by a recorded alias. Only files in the
+    /// store's directory qualify.
+    fn local_blob(&self, path: &str) -> Option<(String, PathBuf)> {
+        let hash = match hash_named(path) {
+            Some(hash) => hash.to_string(),
+            None => self
+                .index
+                .lock()
+                .expect("blob index lock poisoned")
+                .aliases
+                .get(path)?
+                .hash
+                .clone(),
+        };
+        let file = self.blob_path(&hash).filter(|p| p.is_file())?;
+        Some((hash, file))
+    }
+
+    /// Describe the media at `path` for a peer. `None` when there is
+    /// nothing to send — including any path outside the store.
+    pub fn describe(&self, path: &str) -> Option<BlobRef> {
+        let (hash, file) = self.local_blob(path)?;
+        let size = std::fs::metadata(file).ok()?.len();
+        Some(BlobRef {
+            path: path.to_string(),
+            hash,
+            size,
+        })
+    }
+
+    /// Remember which blob a peer's document means by `path`. The alias
+    /// only ever points into the store: the path itself is never opened.
+    pub fn record_alias(&self, blob: &BlobRef) -> P2pResult<()> {
+        if !valid_hash(&blob.hash) {
+            return Ok(());
+        }
+        let mut index = self.index.lock().expect("blob index lock poisoned");
+        let entry = AliasEntry {
+            hash: blob.hash.clone(),
+            size: blob.size,
+        };
+        if index
+            .aliases
+            .get(&blob.path)
+            .is_some_and(|a| a.hash == entry.hash)
+        {
+            return Ok(());
+        }
+        index.aliases.insert(blob.path.clone(), entry);
+        self.save(&index)
+    }
+
+    /// Where the file a document calls `path` lives on this device.
+    pub fn resolve(&self, path: &str) -> Option<PathBuf> {
+        self.local_blob(path).map(|(_, file)| file)
+    }
+
+    /// The blob a peer's document path refers to, if one was recorded.
+    /// Lets maintenance tell received blobs still in use from orphans.
+    pub fn aliased_hash(&self, path: &str) -> Option<String> {
+        self.index
+            .lock()
+            .expect("blob index lock poisoned")
+            .aliases
+            .get(path)
+            .map(|a| a.hash.clone())
+    }
+
+    /// The complete file holding `hash`, if this device has one.
+    fn locate(&self, hash: &str) -> Option<PathBuf> {
+        if !valid_hash(hash) {
+            return None;
+        }
+        self.blob_path(hash).filter(|p| p.is_file())
+    }
+
+    /// Whether the full blob is available here.
+    pub fn has(&self, hash: &str) -> bool {
+        self.locate(hash).is_some()
+    }
+
+    /// Bytes of `hash` already received (partial or complete).
+    pub fn received_len(&self, hash: &str) -> u64 {
+        if let Some(p) = self.locate(hash) {
+            return std::fs::metadata(p).map(|m| m.len()).unwrap_or_default();
+        }
+        self.part_path(hash)
+            .and_then(|p| std::fs::metadata(p).ok())
+            .map(|m| m.len())
+            .unwrap_or_default()
+    }
+
+    /// Blobs referenced by received documents that aren't here yet, with
+    /// their sizes.
+    pub fn missing(&self) -> Vec<(String, u64)> {
+        let aliases: Vec<AliasEntry> = self
+            .index
+            .lock()
+            .expect("blob index lock poisoned")
+            .aliases
+            .values()
+            .cloned()
+            .collect();
+        let mut out: Vec<(String, u64)> = Vec::new();
+        for a in aliases {
+            if !out.iter().any(|(h, _)| *h == a.hash) && !self.has(&a.hash) {
+                out.push((a.hash, a.size));
+            }
+        }
+        out
+    }
+
+    /// Read up to `max` bytes of `hash` from `offset`. Returns the bytes
+    /// and the blob's total size; `None` when the blob isn't here.
+    pub fn read_chunk(&self, hash: &str, offset: u64, max: u64) -> P2pResult<Option<(Vec<u8>, u64)>> {
+        let Some(path) = self.locate(hash) else {
+            return Ok(None);
+        };
+        let mut file = std::fs::File::open(&path).map_err(|e| io_err("open", e))?;
+        let total = file.metadata().map_err(|e| io_err("stat", e))?.len();
+        if offset > total {
+            return Ok(Some((Vec::new(), total)));
+        }
+        file.seek(SeekFrom::Start(offset)).map_err(|e| io_err("seek", e))?;
+        let mut buf = Vec::with_capacity(max.min(total - offset) as usize);
+        file.take(max)
+            .read_to_end(&mut buf)
+            .map_err(|e| io_err("read", e))?;
+        Ok(Some((buf, total)))
+    }
+
+    /// Append a received chunk. A chunk is only written at the current end
+    /// of the partial file, so a resent or out-of-order chunk is a no-op.
+    /// Returns how many bytes of the blob are now held; equal to `total`
+    /// once it is complete and verified. A blob whose bytes don't hash to
+    /// its name is discarded.
+    pub fn write_chunk(&self, hash: &str, total: u64, offset: u64, data: &[u8]) -> P2pResult<u64> {
+        if !valid_hash(hash) {
+            return Err(P2pError::SyncError(format!("bad blob hash {hash:?}")));
+        }
+        if total > MAX_BLOB_BYTES {
+            return Err(P2pError::SyncError(format!(
+                "blob {hash} is {total} bytes; limit is {MAX_BLOB_BYTES}"
+            )));
+        }
+        if self.has(hash) {
+            return Ok(total);
+        }
+        let (Some(part), Some(dest)) = (self.part_path(hash), self.blob_path(hash)) else {
+            return Err(P2pError::SyncError("no blob directory; refusing blob".into()));
+        };
+        let held = std::fs::metadata(&part).map(|m| m.len()).unwrap_or_default();
+        if offset != held || held + data.len() as u64 > total {
+            return Ok(held);
+        }
+        if let Some(dir) = &self.dir {
+            std::fs::create_dir_all(dir).map_err(|e| io_err("dir", e))?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&part)
+            .map_err(|e| io_err("open part", e))?;
+        file.write_all(data).map_err(|e| io_err("write part", e))?;
+        let held = held + data.len() as u64;
+        if held < total {
+            return Ok(held);
+        }
+        drop(file);
+        if hash_file(&part).map_err(|e| io_err("verify", e))? != hash {
+            let _ = std::fs::remove_file(&part);
+            return Err(P2pError::SyncError(format!(
+                "blob {hash} failed verification; discarded"
+            )));
+        }
+        std::fs::rename(&part, &dest).map_err(|e| io_err("finalize", e))?;
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "sovereign-blob-{tag}-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn sha(data: &[u8]) -> String {
+        crate::backup::sha256_hex(data)
+    }
+
+    #[test]
+    fn media_paths_cover_images_videos_thumbnails_and_files() {
+        let content = r#"{"body":"x","images":[{"path":"/a.png"},{"path":"/a.png"}],
+            "videos":[{"path":"/v.mp4","thumbnail_path":"/v.jpg"}],
+            "files":[{"path":"/f.pdf","name":"Invoice.pdf"}]}"#;
+        assert_eq!(media_paths(content), vec!["/a.png", "/v.mp4", "/v.jpg", "/f.pdf"]);
+        assert!(media_paths("not json").is_empty());
+    }
+
+    #[test]
+    fn chunked_transfer_resumes_and_verifies() {
+        let src_dir = temp_dir("src");
+        let data: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+        let file = src_dir.join(sha(&data));
+        std::fs::write(&file, &data).unwrap();
+        let sender = BlobStore::open(src_dir);
+        let blob = sender.describe(file.to_str().unwrap()).unwrap();
+        assert_eq!(blob.hash, sha(&data));
+
+        let dir = temp_dir("dst");
+        let remote_path = "/elsewhere/photo.png".to_string();
+        let receiver = BlobStore::open(dir.clone());
+        receiver
+            .record_alias(&BlobRef {
+                path: remote_path.clone(),
+                ..blob.clone()
+            })
+            .unwrap();
+        assert_eq!(receiver.missing(), vec![(blob.hash.clone(), 1000)]);
+
+        let (first, total) = sender.read_chunk(&blob.hash, 0, 400).unwrap().unwrap();
+        assert_eq!(receiver.write_chunk(&blob.hash, total, 0, &first).unwrap(), 400);
+        // A duplicate chunk doesn't advance the transfer.
+        assert_eq!(receiver.write_chunk(&blob.hash, total, 0, &first).unwrap(), 400);
+
+        // Reopen: the partial survives, the transfer resumes where it left off.
+        let receiver = BlobStore::open(dir);
+        let at = receiver.received_len(&blob.hash);
+        assert_eq!(at, 400);
+        let (rest, _) = sender.read_chunk(&blob.hash, at, CHUNK_BYTES).unwrap().unwrap();
+        assert_eq!(receiver.write_chunk(&blob.hash, total, at, &rest).unwrap(), total);
+        assert!(receiver.has(&blob.hash));
+        assert!(receiver.missing().is_empty());
+        let local = receiver.resolve(&remote_path).unwrap();
+        assert_eq!(std::fs::read(local).unwrap(), data);
+    }
+
+    #[test]
+    fn files_outside_the_store_are_never_described_or_served() {
+        let outside = temp_dir("outside");
+        let secret = outside.join("id_ed25519");
+        std::fs::write(&secret, b"private key").unwrap();
+        let hash = sha(b"private key");
+        // Even named by its hash, a file elsewhere isn't the store's.
+        let named = outside.join(&hash);
+        std::fs::write(&named, b"private key").unwrap();
+
+        let store = BlobStore::open(temp_dir("store"));
+        assert!(store.describe(secret.to_str().unwrap()).is_none());
+        assert!(store.describe(named.to_str().unwrap()).is_none());
+        // A peer's alias for the path doesn't open it either.
+        store
+            .record_alias(&BlobRef {
+                path: secret.to_str().unwrap().to_string(),
+                hash: hash.clone(),
+                size: 11,
+            })
+            .unwrap();
+        assert!(store.describe(secret.to_str().unwrap()).is_none());
+        assert!(store.resolve(secret.to_str().unwrap()).is_none());
+        assert!(store.read_chunk(&hash, 0, CHUNK_BYTES).unwrap().is_none());
+        assert!(BlobStore::ephemeral().describe(named.to_str().unwrap()).is_none());
+    }
+
+    #[test]
+    fn corrupt_blob_is_discarded() {
+        let receiver = BlobStore::open(temp_dir("bad"));
+        let hash = sha(b"expected");
+        assert!(receiver.write_chunk(&hash, 8, 0, b"tampered").is_err());
+        assert!(!receiver.has(&hash));
+        assert_eq!(receiver.received_len(&hash), 0);
+        assert!(receiver.write_chunk("../../etc/passwd", 1, 0, b"x").is_err());
+    }
+}
@@ -9,7 +9,10 @@ pub struct P2pConfig {
     /// Port to listen on (0 = random).
     #[serde(default = "default_listen_port")]
     pub listen_port: u16,
-    /// Optional rendezvous server address for WAN discovery.
+    /// Optional rendezvous server for sync across networks, as a multiaddr
+    /// ending in `/p2p/<server peer id>`. The server must also run a
+    /// circuit relay: devices reserve a slot there, reach each other
+    /// through it and then try to hole-punch a direct connection.
     #[serde(default)]
     pub rendezvous_server: Option<String>,
     /// Human-readable device name shown to peers.
@@ -27,6 +30,11 @@ pub struct P2pConfig {
     /// connectivity callback wired by the Android plugin (Phase 4.1).
     #[serde(default = "default_wifi_only")]
     pub wifi_only: bool,
+    /// Answer paired peers' `GetViewerSnapshot` with a read-only
+    /// snapshot (companion viewer, see `protocol::viewer`). Off by
+    /// default.
+    #[serde(default)]
+    pub serve_viewer: bool,
 }
 
 fn default_listen_port() -> u16 {
@@ -59,6 +67,10 @@ pub enum ConnectivityState {
     Cellular,
     /// No network at all. Suppress all sync activity.
     Offline,
+    /// No shared network, but a direct link to a nearby device is up
+    /// (see [`crate::proximity`]). Unmetered; sync only reaches that
+    /// device.
+    Local,
     /// We haven't received a callback yet, or the platform doesn't
     /// distinguish — treat optimistically as Wifi for desktop.
     #[default]
@@ -76,7 +88,7 @@ impl ConnectivityState {
             return self != Self::Offline;
         }
         match self {
-            Self::Wifi => true,
+            Self::Wifi | Self::Local => true,
             Self::Cellular | Self::Offline => false,
             // Mobile: hold until first connectivity callback. Desktop:
             // wifi_only is false by default, so this branch is rare.
@@ -85,6 +97,18 @@ impl ConnectivityState {
     }
 }
 
+impl P2pConfig {
+    /// The rendezvous/relay server's PeerId and address, if one is
+    /// configured and well-formed.
+    pub fn rendezvous_peer(&self) -> Option<(libp2p::PeerId, libp2p::Multiaddr)> {
+        let addr: libp2p::Multiaddr = self.rendezvous_server.as_deref()?.trim().parse().ok()?;
+        match addr.iter().last()? {
+            libp2p::multiaddr::Protocol::P2p(peer_id) => Some((peer_id, addr)),
+            _ => None,
+        }
+    }
+}
+
 impl Default for P2pConfig {
     fn default() -> Self {
         Self {
@@ -94,6 +118,7 @@ impl Default for P2pConfig {
             device_name: default_device_name(),
             enable_mdns: default_enable_mdns(),
             wifi_only: default_wifi_only(),
+            serve_viewer: false,
         }
     }
 }
@@ -119,6 +144,7 @@ mod tests {
             device_name: "My Laptop".into(),
             enable_mdns: false,
             wifi_only: true,
+            serve_viewer: true,
         };
         let json = serde_json::to_string(&cfg).unwrap();
         let back: P2pConfig = serde_json::from_str(&json).unwrap();
@@ -126,6 +152,24 @@ mod tests {
         assert_eq!(back.listen_port, 4001);
         assert!(back.wifi_only);
         assert!(!back.enable_mdns);
+        assert!(back.serve_viewer);
+    }
+
+    #[test]
+    fn rendezvous_peer_needs_a_peer_id() {
+        let id = libp2p::PeerId::random();
+        let mut cfg = P2pConfig {
+            rendezvous_server: Some(format!("/ip4/203.0.113.7/udp/4001/quic-v1/p2p/{id}")),
+            ..P2pConfig::default()
+        };
+        let (peer, addr) = cfg.rendezvous_peer().unwrap();
+        assert_eq!(peer, id);
+        assert!(addr.to_string().ends_with(&id.to_string()));
+
+        cfg.rendezvous_server = Some("/ip4/203.0.113.7/udp/4001/quic-v1".into());
+        assert!(cfg.rendezvous_peer().is_none());
+        cfg.rendezvous_server = Some("not an address".into());
+        assert!(cfg.rendezvous_peer().is_none());
     }
 
     #[test]
@@ -169,12 +213,14 @@ mod tests {
         assert!(ConnectivityState::Cellular.allows_auto_sync(false));
         assert!(ConnectivityState::Unknown.allows_auto_sync(false));
         assert!(!ConnectivityState::Offline.allows_auto_sync(false));
+        assert!(ConnectivityState::Local.allows_auto_sync(false));
     }
 
     #[test]
     fn allows_auto_sync_when_wifi_only_on() {
         // With wifi_only, only Wi-Fi (and Unknown on desktop) sync.
         assert!(ConnectivityState::Wifi.allows_auto_sync(true));
+        assert!(ConnectivityState::Local.allows_auto_sync(true));
         assert!(!ConnectivityState::Cellular.allows_auto_sync(true));
         assert!(!ConnectivityState::Offline.allows_auto_sync(true));
         // Unknown: optimistic on desktop, conservative on Android.
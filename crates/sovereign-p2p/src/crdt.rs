@@ -0,0 +1,775 @@
+//! Conflict-free merging of document bodies.
+//!
+//! Each document's `body` is mirrored in a yrs text CRDT. Its state travels
+//! inside the sealed snapshot, so when two devices edited the same document
+//! between syncs the receiver merges both edits instead of keeping only the
+//! newer copy. Local edits never go through this module; they are folded in
+//! lazily as a line diff against the CRDT text whenever the document is sent
+//! or merged.
+//!
+//! Histories are identified by a **root**: the client id of the single
+//! insert that seeded them, derived from the seeding text. Two devices that
+//! bootstrap from the same text therefore share a history. When the roots
+//! differ (a document edited on both sides before either had CRDT state),
+//! both devices keep the history with the lower root and take the newer
+//! body, then treat it as a conflict for the user to review.
+//!
+//! A merge that still touched the same lines on both sides is reported as a
+//! [`MergeConflict`]; the merged text is kept (so devices converge) and the
+//! UI offers a three-way review, split into regions by [`conflict_hunks`].
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use yrs::updates::decoder::Decode;
+use yrs::{Doc, GetString, ReadTxn, StateVector, Text, Transact, Update};
+
+use crate::error::{P2pError, P2pResult};
+use crate::identity::P2pIdentityKey;
+
+/// Name of the shared text inside each yrs document.
+const BODY: &str = "body";
+
+/// Above this many line pairs the diff gives up on LCS and replaces the
+/// changed region wholesale.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// yrs client ids must fit in 53 bits (JavaScript interop).
+const CLIENT_ID_MASK: u64 = (1 << 53) - 1;
+
+fn b64() -> base64::engine::GeneralPurpose {
+    base64::engine::general_purpose::STANDARD
+}
+
+fn hash_id(domain: &[u8], data: &[u8]) -> u64 {
+    let mut h = Sha256::new();
+    h.update(domain);
+    h.update(data);
+    let digest = h.finalize();
+    let mut b = [0u8; 8];
+    b.copy_from_slice(&digest[..8]);
+    (u64::from_le_bytes(b) & CLIENT_ID_MASK).max(1)
+}
+
+/// The yrs client id this device writes under.
+pub fn client_id(device_id: &str) -> u64 {
+    hash_id(b"sovereign-crdt-client:", device_id.as_bytes())
+}
+
+fn root_id(text: &str) -> u64 {
+    hash_id(b"sovereign-crdt-root:", text.as_bytes())
+}
+
+/// The `body` field of a document's content JSON.
+pub fn body_of(content: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(content)
+        .ok()
+        .and_then(|v| v.get("body").and_then(|b| b.as_str()).map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// `content` with its `body` replaced, every other field untouched.
+pub fn with_body(content: &str, body: &str) -> String {
+    let mut value = serde_json::from_str::<serde_json::Value>(content)
+        .ok()
+        .filter(|v| v.is_object())
+        .unwrap_or_else(|| serde_json::json!({}));
+    value["body"] = serde_json::Value::String(body.to_string());
+    value.to_string()
+}
+
+/// CRDT state as carried in a sync snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrdtPayload {
+    pub root: u64,
+    /// Base64 yrs v1 update holding the whole history.
+    pub state: String,
+}
+
+/// A document body as a yrs text CRDT.
+pub struct BodyCrdt {
+    doc: Doc,
+    root: u64,
+}
+
+impl BodyCrdt {
+    fn empty(client_id: u64, root: u64) -> Self {
+        let doc = Doc::with_client_id(client_id);
+        doc.get_or_insert_text(BODY);
+        Self { doc, root }
+    }
+
+    /// Seed a history from `text`. Seeding is deterministic, so devices that
+    /// start from the same text end up with the same history.
+    pub fn bootstrap(text: &str, client_id: u64) -> Self {
+        let root = root_id(text);
+        let seed = Doc::with_client_id(root);
+        let body = seed.get_or_insert_text(BODY);
+        if !text.is_empty() {
+            body.insert(&mut seed.transact_mut(), 0, text);
+        }
+        let update = seed.transact().encode_state_as_update_v1(&StateVector::default());
+        let crdt = Self::empty(client_id, root);
+        crdt.apply(&update)
+            .expect("an update produced by yrs decodes");
+        crdt
+    }
+
+    /// Open a history received from a peer or read from the store.
+    pub fn load(payload: &CrdtPayload, client_id: u64) -> P2pResult<Self> {
+        let crdt = Self::empty(client_id, payload.root);
+        crdt.apply(&decode_state(&payload.state)?)?;
+        Ok(crdt)
+    }
+
+    pub fn root(&self) -> u64 {
+        self.root
+    }
+
+    fn apply(&self, update: &[u8]) -> P2pResult<()> {
+        let update = Update::decode_v1(update)
+            .map_err(|e| P2pError::SyncError(format!("crdt update decode: {e}")))?;
+        self.doc
+            .transact_mut()
+            .apply_update(update)
+            .map_err(|e| P2pError::SyncError(format!("crdt update apply: {e}")))
+    }
+
+    pub fn payload(&self) -> CrdtPayload {
+        let state = self
+            .doc
+            .transact()
+            .encode_state_as_update_v1(&StateVector::default());
+        CrdtPayload {
+            root: self.root,
+            state: b64().encode(state),
+        }
+    }
+
+    pub fn text(&self) -> String {
+        let body = self.doc.get_or_insert_text(BODY);
+        let txn = self.doc.transact();
+        body.get_string(&txn)
+    }
+
+    /// Record a local edit: turn the CRDT text into `new` with a line diff,
+    /// so edits elsewhere in the document stay mergeable.
+    pub fn set_text(&mut self, new: &str) {
+        let old = self.text();
+        if old == new {
+            return;
+        }
+        let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+        let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+        let old_offsets = line_offsets(&old_lines);
+        let body = self.doc.get_or_insert_text(BODY);
+        let mut txn = self.doc.transact_mut();
+        // Back to front, so earlier offsets stay valid.
+        for hunk in line_diff(&old_lines, &new_lines).into_iter().rev() {
+            let at = old_offsets[hunk.old.start] as u32;
+            let removed = (old_offsets[hunk.old.end] - old_offsets[hunk.old.start]) as u32;
+            if removed > 0 {
+                body.remove_range(&mut txn, at, removed);
+            }
+            let inserted = new_lines[hunk.new.clone()].concat();
+            if !inserted.is_empty() {
+                body.insert(&mut txn, at, &inserted);
+            }
+        }
+    }
+
+    /// Fold a peer's history into ours.
+    pub fn merge(&mut self, remote: &CrdtPayload) -> P2pResult<()> {
+        if remote.root != self.root {
+            return Err(P2pError::SyncError("crdt histories are unrelated".into()));
+        }
+        self.apply(&decode_state(&remote.state)?)
+    }
+}
+
+fn decode_state(state: &str) -> P2pResult<Vec<u8>> {
+    b64()
+        .decode(state)
+        .map_err(|e| P2pError::SyncError(format!("crdt state base64: {e}")))
+}
+
+/// Byte offset of each line start, plus the end of the text.
+fn line_offsets(lines: &[&str]) -> Vec<usize> {
+    let mut out = Vec::with_capacity(lines.len() + 1);
+    let mut at = 0;
+    out.push(0);
+    for l in lines {
+        at += l.len();
+        out.push(at);
+    }
+    out
+}
+
+/// A replaced block of lines: `old` in the old text becomes `new`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Hunk {
+    old: std::ops::Range<usize>,
+    new: std::ops::Range<usize>,
+}
+
+/// Line-level diff (LCS), in ascending order.
+fn line_diff(old: &[&str], new: &[&str]) -> Vec<Hunk> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (a, b) = (&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]);
+    if a.is_empty() && b.is_empty() {
+        return Vec::new();
+    }
+    if a.is_empty() || b.is_empty() || a.len() * b.len() > MAX_DIFF_CELLS {
+        return vec![Hunk {
+            old: prefix..prefix + a.len(),
+            new: prefix..prefix + b.len(),
+        }];
+    }
+
+    // lcs[i][j] = LCS length of a[i..] and b[j..].
+    let w = b.len() + 1;
+    let mut lcs = vec![0u32; (a.len() + 1) * w];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i * w + j] = if a[i] == b[j] {
+                lcs[(i + 1) * w + j + 1] + 1
+            } else {
+                lcs[(i + 1) * w + j].max(lcs[i * w + j + 1])
+            };
+        }
+    }
+
+    let mut hunks = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let mut open: Option<(usize, usize)> = None;
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            if let Some((oi, oj)) = open.take() {
+                hunks.push(Hunk {
+                    old: prefix + oi..prefix + i,
+                    new: prefix + oj..prefix + j,
+                });
+            }
+            i += 1;
+            j += 1;
+            continue;
+        }
+        open.get_or_insert((i, j));
+        if j < b.len() && (i == a.len() || lcs[i * w + j + 1] >= lcs[(i + 1) * w + j]) {
+            j += 1;
+        } else {
+            i += 1;
+        }
+    }
+    if let Some((oi, oj)) = open {
+        hunks.push(Hunk {
+            old: prefix + oi..prefix + a.len(),
+            new: prefix + oj..prefix + b.len(),
+        });
+    }
+    hunks
+}
+
+/// Whether the two sides changed overlapping lines of `base`. Without a
+/// base, any divergence that the merge didn't resolve to one side counts.
+fn edits_overlap(base: Option<&str>, local: &str, remote: &str, merged: &str) -> bool {
+    if local == remote {
+        return false;
+    }
+    let Some(base) = base else {
+        return merged != local && merged != remote;
+    };
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let local_lines: Vec<&str> = local.split_inclusive('\n').collect();
+    let remote_lines: Vec<&str> = remote.split_inclusive('\n').collect();
+    let ours = line_diff(&base_lines, &local_lines);
+    let theirs = line_diff(&base_lines, &remote_lines);
+    ours.iter().any(|o| theirs.iter().any(|t| touches(&o.old, &t.old)))
+}
+
+/// Whether two edited ranges of the base collide. An insertion (empty
+/// range) collides with another insertion at the same point or with a
+/// replacement it falls inside.
+fn touches(x: &std::ops::Range<usize>, y: &std::ops::Range<usize>) -> bool {
+    match (x.is_empty(), y.is_empty()) {
+        (true, true) => x.start == y.start,
+        (true, false) => y.start < x.start && x.start < y.end,
+        (false, true) => x.start < y.start && y.start < x.end,
+        (false, false) => x.start < y.end && y.start < x.end,
+    }
+}
+
+/// One region of a three-way merge. Where the sides agree (unchanged, or
+/// changed on one side only) `local == remote` and holds the settled text;
+/// otherwise both sides rewrote the same base lines and the user picks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictHunk {
+    pub base: String,
+    pub local: String,
+    pub remote: String,
+}
+
+impl ConflictHunk {
+    pub fn is_conflict(&self) -> bool {
+        self.local != self.remote
+    }
+}
+
+/// Split `local` and `remote` into regions against `base` (diff3-style),
+/// so a review UI can settle each conflicting region on its own. Without a
+/// base the whole body is a single region.
+pub fn conflict_hunks(base: Option<&str>, local: &str, remote: &str) -> Vec<ConflictHunk> {
+    let Some(base) = base else {
+        return vec![ConflictHunk {
+            base: String::new(),
+            local: local.to_string(),
+            remote: remote.to_string(),
+        }];
+    };
+    let b: Vec<&str> = base.split_inclusive('\n').collect();
+    let sides: [Vec<&str>; 2] = [
+        local.split_inclusive('\n').collect(),
+        remote.split_inclusive('\n').collect(),
+    ];
+    let diffs = [line_diff(&b, &sides[0]), line_diff(&b, &sides[1])];
+    let mut edits: Vec<(usize, &Hunk)> = diffs
+        .iter()
+        .enumerate()
+        .flat_map(|(side, hunks)| hunks.iter().map(move |h| (side, h)))
+        .collect();
+    // Insertions sort ahead of a replacement starting at the same line.
+    edits.sort_by_key(|(_, h)| (h.old.start, !h.old.is_empty()));
+
+    let mut out: Vec<ConflictHunk> = Vec::new();
+    let mut push = |hunk: ConflictHunk| match out.last_mut() {
+        Some(last) if !last.is_conflict() && !hunk.is_conflict() => {
+            last.base.push_str(&hunk.base);
+            last.local.push_str(&hunk.local);
+            last.remote.push_str(&hunk.remote);
+        }
+        _ => out.push(hunk),
+    };
+    // Lines each side has gained so far relative to the base.
+    let mut shift = [0isize; 2];
+    let mut at = 0;
+    let mut i = 0;
+    while i < edits.len() {
+        let (side, h) = edits[i];
+        let mut region = h.old.clone();
+        let mut grown = [0isize; 2];
+        let mut changed = [false; 2];
+        grown[side] = h.new.len() as isize - h.old.len() as isize;
+        changed[side] = true;
+        i += 1;
+        while let Some(&(side, h)) = edits.get(i).filter(|(_, h)| touches(&h.old, &region)) {
+            region = region.start.min(h.old.start)..region.end.max(h.old.end);
+            grown[side] += h.new.len() as isize - h.old.len() as isize;
+            changed[side] = true;
+            i += 1;
+        }
+        let same = b[at..region.start].concat();
+        push(ConflictHunk {
+            base: same.clone(),
+            local: same.clone(),
+            remote: same,
+        });
+        let text = |side: usize| {
+            let start = (region.start as isize + shift[side]) as usize;
+            let end = (region.end as isize + shift[side] + grown[side]) as usize;
+            sides[side][start..end].concat()
+        };
+        let base_text = b[region.clone()].concat();
+        let (local, remote) = (text(0), text(1));
+        push(match changed {
+            [true, true] => ConflictHunk {
+                base: base_text,
+                local,
+                remote,
+            },
+            [true, false] => ConflictHunk {
+                base: base_text,
+                remote: local.clone(),
+                local,
+            },
+            _ => ConflictHunk {
+                base: base_text,
+                local: remote.clone(),
+                remote,
+            },
+        });
+        shift[0] += grown[0];
+        shift[1] += grown[1];
+        at = region.end;
+    }
+    let rest = b[at..].concat();
+    push(ConflictHunk {
+        base: rest.clone(),
+        local: rest.clone(),
+        remote: rest,
+    });
+    out.retain(|h| !(h.base.is_empty() && h.local.is_empty() && h.remote.is_empty()));
+    out
+}
+
+/// Both sides of a merge that touched the same lines, for the three-way
+/// review. The merged text is already applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    pub doc_id: String,
+    /// Body at this device's previous exchange of the document, if known.
+    pub base: Option<String>,
+    pub local: String,
+    pub remote: String,
+    pub merged: String,
+    /// `local`/`remote` split per region, see [`conflict_hunks`].
+    pub hunks: Vec<ConflictHunk>,
+}
+
+/// Outcome of [`merge_body`].
+pub struct Merged {
+    pub crdt: BodyCrdt,
+    pub body: String,
+    /// Whether the edits still need the user's eye.
+    pub conflict: bool,
+}
+
+/// Merge a peer's body into ours. `local` must already reflect the local
+/// body (see [`CrdtStore::local`]); `remote_newer` settles unrelated
+/// histories.
+pub fn merge_body(
+    mut local: BodyCrdt,
+    local_body: &str,
+    remote: &CrdtPayload,
+    remote_body: &str,
+    remote_newer: bool,
+    base: Option<&str>,
+    client_id: u64,
+) -> P2pResult<Merged> {
+    if local.root() == remote.root {
+        local.merge(remote)?;
+        let body = local.text();
+        let conflict = edits_overlap(base, local_body, remote_body, &body);
+        return Ok(Merged {
+            crdt: local,
+            body,
+            conflict,
+        });
+    }
+    // Unrelated histories: both ends keep the lower root so they agree.
+    let mut crdt = if remote.root < local.root() {
+        BodyCrdt::load(remote, client_id)?
+    } else {
+        local
+    };
+    let winner = if remote_newer { remote_body } else { local_body };
+    crdt.set_text(winner);
+    Ok(Merged {
+        crdt,
+        body: winner.to_string(),
+        conflict: local_body != remote_body,
+    })
+}
+
+/// Derive the at-rest key for the CRDT store from the device identity key.
+/// Domain-separated from the paired-store key derived from the same key.
+pub fn derive_store_key(identity_key: &P2pIdentityKey) -> [u8; 32] {
+    let hk = Hkdf::<Sha256>::new(None, identity_key.as_bytes());
+    let mut out = [0u8; 32];
+    hk.expand(b"sovereign-crdt-store-key:v1", &mut out)
+        .expect("32 bytes is within HKDF output limit");
+    out
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoreEntry {
+    root: u64,
+    state: String,
+    /// Body at the last exchange — the base for three-way review. `None`
+    /// until a state has been exchanged.
+    base: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedStore {
+    v: u8,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Per-document CRDT histories. The states hold document text, so the file
+/// is AEAD-sealed like `paired_devices.json`. A `None` path is ephemeral
+/// (tests).
+pub struct CrdtStore {
+    entries: HashMap<String, StoreEntry>,
+    path: Option<PathBuf>,
+    key: [u8; 32],
+}
+
+impl CrdtStore {
+    pub fn ephemeral() -> Self {
+        Self {
+            entries: HashMap::new(),
+            path: None,
+            key: [0u8; 32],
+        }
+    }
+
+    /// Load from `path`, starting empty if it is missing or unreadable (the
+    /// histories rebuild from current text on the next sync).
+    pub fn load_or_default(path: PathBuf, key: [u8; 32]) -> Self {
+        let entries = match Self::read(&path, &key) {
+            Ok(e) => e,
+            Err(e) => {
+                if path.exists() {
+                    tracing::warn!("CRDT store unreadable ({e}); starting fresh");
+                }
+                HashMap::new()
+            }
+        };
+        Self {
+            entries,
+            path: Some(path),
+            key,
+        }
+    }
+
+    fn read(path: &Path, key: &[u8; 32]) -> P2pResult<HashMap<String, StoreEntry>> {
+        let raw = std::fs::read_to_string(path).map_err(|e| P2pError::SyncError(e.to_string()))?;
+        let wrapper: EncryptedStore =
+            serde_json::from_str(&raw).map_err(|e| P2pError::SyncError(e.to_string()))?;
+        let ciphertext = decode_state(&wrapper.ciphertext)?;
+        let nonce: [u8; 24] = decode_state(&wrapper.nonce)?
+            .try_into()
+            .map_err(|_| P2pError::SyncError("CRDT store nonce length".into()))?;
+        let plain = sovereign_crypto::aead::decrypt(&ciphertext, &nonce, key)
+            .map_err(|e| P2pError::SyncError(format!("CRDT store decrypt: {e}")))?;
+        serde_json::from_slice(&plain).map_err(|e| P2pError::SyncError(e.to_string()))
+    }
+
+    pub fn save(&self) -> P2pResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let json =
+            serde_json::to_vec(&self.entries).map_err(|e| P2pError::SyncError(e.to_string()))?;
+        let (ciphertext, nonce) = sovereign_crypto::aead::encrypt(&json, &self.key)
+            .map_err(|e| P2pError::SyncError(format!("CRDT store encrypt: {e}")))?;
+        let wrapper = EncryptedStore {
+            v: 1,
+            nonce: b64().encode(nonce),
+            ciphertext: b64().encode(&ciphertext),
+        };
+        let out =
+            serde_json::to_string(&wrapper).map_err(|e| P2pError::SyncError(e.to_string()))?;
+        sovereign_crypto::fs_private::write_private(path, out)
+            .map_err(|e| P2pError::SyncError(e.to_string()))
+    }
+
+    /// This device's history for `doc_id`, with `body` (the current local
+    /// text) folded in as a local edit. Seeds a history if there is none.
+    pub fn local(&self, doc_id: &str, body: &str, client_id: u64) -> P2pResult<BodyCrdt> {
+        let mut crdt = match self.entries.get(doc_id) {
+            Some(e) => BodyCrdt::load(
+                &CrdtPayload {
+                    root: e.root,
+                    state: e.state.clone(),
+                },
+                client_id,
+            )?,
+            None => return Ok(BodyCrdt::bootstrap(body, client_id)),
+        };
+        crdt.set_text(body);
+        Ok(crdt)
+    }
+
+    /// Whether a history exists for `doc_id`.
+    pub fn contains(&self, doc_id: &str) -> bool {
+        self.entries.contains_key(doc_id)
+    }
+
+    /// Body at the last exchange of `doc_id`.
+    pub fn base(&self, doc_id: &str) -> Option<&str> {
+        self.entries.get(doc_id).and_then(|e| e.base.as_deref())
+    }
+
+    /// Remember `crdt` as the state just exchanged for `doc_id`.
+    pub fn record(&mut self, doc_id: &str, crdt: &BodyCrdt) {
+        let payload = crdt.payload();
+        self.entries.insert(
+            doc_id.to_string(),
+            StoreEntry {
+                root: payload.root,
+                state: payload.state,
+                base: Some(crdt.text()),
+            },
+        );
+    }
+
+    /// Keep `crdt` as this device's history for `doc_id` without moving
+    /// the base: the state is about to be sent, not yet exchanged.
+    pub fn update_local(&mut self, doc_id: &str, crdt: &BodyCrdt) {
+        let payload = crdt.payload();
+        let base = self.entries.remove(doc_id).and_then(|e| e.base);
+        self.entries.insert(
+            doc_id.to_string(),
+            StoreEntry {
+                root: payload.root,
+                state: payload.state,
+                base,
+            },
+        );
+    }
+
+    /// Mark `body` as exchanged for `doc_id`, once the peer has it.
+    pub fn set_base(&mut self, doc_id: &str, body: String) {
+        if let Some(entry) = self.entries.get_mut(doc_id) {
+            entry.base = Some(body);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: u64 = 101;
+    const B: u64 = 202;
+
+    #[test]
+    fn same_text_bootstraps_to_the_same_history() {
+        let a = BodyCrdt::bootstrap("one\ntwo\n", A);
+        let mut b = BodyCrdt::bootstrap("one\ntwo\n", B);
+        assert_eq!(a.root(), b.root());
+        b.merge(&a.payload()).unwrap();
+        assert_eq!(b.text(), "one\ntwo\n", "identical seeds must not duplicate");
+    }
+
+    #[test]
+    fn edits_to_different_lines_merge_cleanly() {
+        let base = "title\nalpha\nbeta\ngamma\n";
+        let mut a = BodyCrdt::bootstrap(base, A);
+        let mut b = BodyCrdt::bootstrap(base, B);
+        a.set_text("title\nALPHA\nbeta\ngamma\n");
+        b.set_text("title\nalpha\nbeta\ngamma\ndelta\n");
+        let a_body = a.text();
+        let b_body = b.text();
+
+        let merged = merge_body(a, &a_body, &b.payload(), &b_body, true, Some(base), A).unwrap();
+        assert_eq!(merged.body, "title\nALPHA\nbeta\ngamma\ndelta\n");
+        assert!(!merged.conflict);
+
+        // The other side converges on the same text.
+        let back = merge_body(b, &b_body, &merged.crdt.payload(), &merged.body, true, Some(base), B)
+            .unwrap();
+        assert_eq!(back.body, merged.body);
+    }
+
+    #[test]
+    fn edits_to_the_same_line_are_flagged() {
+        let base = "keep\nchange me\n";
+        let mut a = BodyCrdt::bootstrap(base, A);
+        let mut b = BodyCrdt::bootstrap(base, B);
+        a.set_text("keep\nchanged here\n");
+        b.set_text("keep\nchanged there\n");
+        let (a_body, b_body) = (a.text(), b.text());
+        let merged = merge_body(a, &a_body, &b.payload(), &b_body, false, Some(base), A).unwrap();
+        assert!(merged.conflict);
+        assert!(merged.body.contains("here") && merged.body.contains("there"));
+    }
+
+    #[test]
+    fn unrelated_histories_agree_on_root_and_newest_body() {
+        let a = BodyCrdt::bootstrap("laptop text", A);
+        let b = BodyCrdt::bootstrap("phone text", B);
+        let lower = a.root().min(b.root());
+        let (ap, bp) = (a.payload(), b.payload());
+
+        let on_a = merge_body(a, "laptop text", &bp, "phone text", true, None, A).unwrap();
+        let on_b = merge_body(b, "phone text", &ap, "laptop text", false, None, B).unwrap();
+        assert_eq!(on_a.body, "phone text");
+        assert_eq!(on_b.body, "phone text");
+        assert_eq!(on_a.crdt.root(), lower);
+        assert_eq!(on_b.crdt.root(), lower);
+        assert!(on_a.conflict && on_b.conflict);
+    }
+
+    #[test]
+    fn hunks_isolate_the_conflicting_region() {
+        let hunks = conflict_hunks(Some("a\nb\nc\n"), "a\nB1\nc\n", "a\nB2\nc\nd\n");
+        assert_eq!(hunks.len(), 3);
+        assert!(!hunks[0].is_conflict());
+        assert_eq!(hunks[0].local, "a\n");
+        assert!(hunks[1].is_conflict());
+        assert_eq!(
+            (hunks[1].base.as_str(), hunks[1].local.as_str(), hunks[1].remote.as_str()),
+            ("b\n", "B1\n", "B2\n")
+        );
+        // One-sided insertion is settled, not a conflict.
+        assert!(!hunks[2].is_conflict());
+        assert_eq!(hunks[2].local, "c\nd\n");
+    }
+
+    #[test]
+    fn hunks_without_conflict_or_base() {
+        let clean = conflict_hunks(Some("1\n2\n3\n"), "0\n1\n2\n3\n", "1\n2\nC\n");
+        assert_eq!(clean.len(), 1);
+        assert_eq!(clean[0].local, "0\n1\n2\nC\n");
+        assert!(!clean[0].is_conflict());
+
+        let whole = conflict_hunks(None, "mine", "theirs");
+        assert_eq!(whole.len(), 1);
+        assert!(whole[0].is_conflict());
+    }
+
+    #[test]
+    fn multibyte_text_diffs_on_char_boundaries() {
+        let mut c = BodyCrdt::bootstrap("héllo\nwörld\n", A);
+        c.set_text("héllo\nwörld 🌍\nnew\n");
+        assert_eq!(c.text(), "héllo\nwörld 🌍\nnew\n");
+        c.set_text("");
+        assert_eq!(c.text(), "");
+    }
+
+    #[test]
+    fn body_helpers_keep_other_fields() {
+        let content = r#"{"body":"hi","images":[{"path":"a.png"}]}"#;
+        assert_eq!(body_of(content), "hi");
+        let updated = with_body(content, "bye");
+        assert_eq!(body_of(&updated), "bye");
+        assert!(updated.contains("a.png"));
+        assert_eq!(body_of("not json"), "");
+    }
+
+    #[test]
+    fn store_roundtrips_encrypted() {
+        let dir = std::env::temp_dir().join("sovereign-p2p-test-crdt-store");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("crdt.json");
+        let key = [5u8; 32];
+
+        let mut store = CrdtStore::load_or_default(path.clone(), key);
+        let crdt = store.local("document:1", "secret body", A).unwrap();
+        store.record("document:1", &crdt);
+        store.save().unwrap();
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("secret"));
+
+        let store = CrdtStore::load_or_default(path.clone(), key);
+        assert_eq!(store.base("document:1"), Some("secret body"));
+        let reopened = store.local("document:1", "secret body!", A).unwrap();
+        assert_eq!(reopened.text(), "secret body!");
+        assert!(!CrdtStore::load_or_default(path, [6u8; 32]).contains("document:1"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -29,6 +29,9 @@ pub enum P2pError {
     #[error("Sync error: {0}")]
     SyncError(String),
 
+    #[error("Remote wipe rejected: {0}")]
+    RemoteWipe(String),
+
     #[error("Channel closed")]
     ChannelClosed,
 
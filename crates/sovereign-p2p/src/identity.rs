@@ -1,6 +1,3 @@
-use hkdf::Hkdf;
-use sha2::Sha256;
-
 use crate::error::{P2pError, P2pResult};
 
 /// The per-device key used to derive this device's libp2p PeerId.
@@ -14,11 +11,10 @@ pub type P2pIdentityKey = sovereign_crypto::device_key::DeviceKey;
 /// Derive a deterministic Ed25519 libp2p Keypair from a P2pIdentityKey via HKDF.
 ///
 /// This ensures the same device always gets the same PeerId.
+/// The seed comes from `sovereign_crypto::device_signing`, which signs
+/// commit records with the same key.
 pub fn derive_keypair(identity_key: &P2pIdentityKey) -> P2pResult<libp2p::identity::Keypair> {
-    let hk = Hkdf::<Sha256>::new(None, identity_key.as_bytes());
-    let mut seed = [0u8; 32];
-    hk.expand(b"sovereign-p2p-identity", &mut seed)
-        .map_err(|e| P2pError::Identity(e.to_string()))?;
+    let seed = *sovereign_crypto::device_signing::identity_seed(identity_key);
 
     let keypair = libp2p::identity::Keypair::ed25519_from_bytes(seed)
         .map_err(|e: libp2p::identity::DecodingError| P2pError::Identity(e.to_string()))?;
@@ -26,6 +22,15 @@ pub fn derive_keypair(identity_key: &P2pIdentityKey) -> P2pResult<libp2p::identi
     Ok(keypair)
 }
 
+/// The PeerId behind a base64 Ed25519 public key, e.g. a commit's
+/// `device_public_key`.
+pub fn peer_id_from_public_key_b64(public_key_b64: &str) -> Option<String> {
+    use base64::Engine;
+    let bytes = base64::engine::general_purpose::STANDARD.decode(public_key_b64).ok()?;
+    let key = libp2p::identity::ed25519::PublicKey::try_from_bytes(&bytes).ok()?;
+    Some(libp2p::identity::PublicKey::from(key).to_peer_id().to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,6 +50,20 @@ mod tests {
         assert_eq!(kp1.public().to_peer_id(), kp2.public().to_peer_id());
     }
 
+    #[test]
+    fn keypair_matches_commit_signer() {
+        use base64::Engine;
+        let dk = test_device_key();
+        let kp = derive_keypair(&dk).unwrap();
+        let public = kp.public().try_into_ed25519().unwrap().to_bytes();
+        let signer_key = sovereign_crypto::device_signing::DeviceSigner::new(&dk).public_key_b64();
+        assert_eq!(base64::engine::general_purpose::STANDARD.encode(public), signer_key);
+        assert_eq!(
+            peer_id_from_public_key_b64(&signer_key),
+            Some(kp.public().to_peer_id().to_string())
+        );
+    }
+
     #[test]
     fn different_device_keys_differ() {
         let mk = MasterKey::from_passphrase(b"test", b"salt").unwrap();
@@ -1,8 +1,11 @@
+pub mod address_book;
 pub mod backup;
 pub mod backup_client;
 pub mod backup_host;
 pub mod behaviour;
+pub mod blob_store;
 pub mod config;
+pub mod crdt;
 pub mod error;
 pub mod identity;
 pub mod node;
@@ -10,7 +13,11 @@ pub mod pairing;
 pub mod pairing_client;
 pub mod pairing_offer;
 pub mod protocol;
+pub mod proximity;
 pub mod sync_engine;
+pub mod sync_policy;
+pub mod sync_progress;
+pub mod sync_queue;
 pub mod sync_service;
 pub mod version_store;
 
@@ -19,5 +26,6 @@ pub use config::{ConnectivityState, P2pConfig};
 pub use error::{P2pError, P2pResult};
 pub use node::{ActivePairingOffer, P2pCommand, P2pEvent, PairKeyMap, SovereignNode};
 pub use pairing_offer::PairingOffer;
+pub use sync_policy::SyncPolicy;
 pub use sync_service::SyncService;
 pub use version_store::VersionStore;
@@ -1,23 +1,31 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use libp2p::futures::StreamExt;
-use libp2p::request_response::{self, OutboundRequestId, ProtocolSupport};
+use libp2p::request_response::{self, InboundRequestId, OutboundRequestId, ProtocolSupport};
 use rand::Rng as _;
 use libp2p::swarm::SwarmEvent;
 use libp2p::{Multiaddr, PeerId, StreamProtocol, Swarm};
 use tokio::sync::mpsc;
+use sovereign_crypto::key_escrow::{self, KeyEscrowBundle, KeyEscrowEnvelope};
 use tracing::{info, warn};
 
-use crate::behaviour::{SovereignBehaviour, SovereignBehaviourEvent};
+use crate::address_book::{self, RECONNECT_INTERVAL};
+use crate::behaviour::{self, SovereignBehaviour, SovereignBehaviourEvent};
 use crate::config::P2pConfig;
 use crate::error::{P2pError, P2pResult};
+use crate::pairing::PeerTrust;
+use crate::proximity::ProximityLink;
 use crate::protocol::manifest::{EncryptedManifest, SyncManifest};
-use crate::protocol::sync::SyncTable;
+use crate::protocol::sync::{BlobWant, Compression, SyncTable};
+use crate::protocol::viewer::ViewerSnapshot;
+use crate::protocol::wipe::RemoteWipeOrder;
 use crate::protocol::{SovereignRequest, SovereignResponse};
 use crate::sync_engine;
-use crate::sync_service::SyncService;
+use crate::sync_progress::{SyncPreview, SyncProgress};
+use crate::sync_queue::PendingSync;
+use crate::sync_service::{public_key_from_peer_id, SyncService};
 
 pub(crate) const PROTOCOL_NAME: &str = "/sovereign/sync/1";
 
@@ -27,8 +35,27 @@ pub enum P2pEvent {
     PeerDiscovered { peer_id: String, device_name: Option<String> },
     PeerLost { peer_id: String },
     SyncStarted { peer_id: String },
-    SyncCompleted { peer_id: String, docs_synced: u32 },
+    /// A sync session resolved one more request; `progress` is the
+    /// session so far.
+    SyncProgress { peer_id: String, progress: SyncProgress },
+    /// `progress` holds the session's final transfer statistics.
+    SyncCompleted {
+        peer_id: String,
+        docs_synced: u32,
+        progress: SyncProgress,
+    },
     SyncConflict { doc_id: String, description: String },
+    /// Concurrent edits to the same lines of a document body were merged;
+    /// the merged text is already applied, the versions and per-region
+    /// hunks are for the conflict panel.
+    ConflictDetails {
+        doc_id: String,
+        base: Option<String>,
+        local: String,
+        remote: String,
+        merged: String,
+        hunks: Vec<crate::crdt::ConflictHunk>,
+    },
     ShardReceived { shard_id: String, from_peer: String },
     PairingRequested { peer_id: String, device_name: String },
     PairingCompleted { peer_id: String, device_name: String },
@@ -45,6 +72,25 @@ pub enum P2pEvent {
     /// The app collects these so pairing offers can carry real dial
     /// hints instead of relying on mDNS discovery (P3.1).
     ListenAddr { address: String },
+    /// A paired device asked this guardian to co-sign a remote wipe of
+    /// another paired device. Nothing is signed until the user approves
+    /// (`P2pCommand::ApproveRemoteWipe`).
+    WipeCosignRequested { order_id: String, target_peer_id: String, issuer_peer_id: String },
+    /// A verified, guardian co-signed wipe order for THIS device arrived.
+    /// The app must destroy its keys.
+    RemoteWipeOrdered { order_id: String, issuer_peer_id: String, cosigner_peer_id: String },
+    /// A paired device sent its document keys (`P2pCommand::ShareKeys` on
+    /// its side). The app wraps them under its own KEK.
+    KeysReceived { peer_id: String, bundle: Box<KeyEscrowBundle> },
+    /// A `ShareKeys` delivery resolved; `accepted` is false if the peer
+    /// refused it or couldn't be reached.
+    KeysShared { peer_id: String, accepted: bool },
+    /// Answer to `P2pCommand::PreviewSync`. `None` when the peer couldn't
+    /// be reached or its manifest was rejected.
+    SyncPreview { peer_id: String, preview: Option<SyncPreview> },
+    /// Answer to `P2pCommand::FetchViewerSnapshot`. `None` when the peer
+    /// couldn't be reached, doesn't serve viewers, or refused us.
+    ViewerSnapshot { peer_id: String, snapshot: Option<Box<ViewerSnapshot>> },
 }
 
 /// Per-pair sealing keys (P1.4 / P2P-005) wrapped so the Debug impl on
@@ -150,12 +196,41 @@ impl std::fmt::Debug for ActivePairingOffer {
 #[derive(Debug)]
 pub enum P2pCommand {
     StartSync { peer_id: String },
+    /// Fetch the peer's manifest and report what a sync would move
+    /// (`P2pEvent::SyncPreview`) without transferring any data.
+    PreviewSync { peer_id: String },
+    /// Fetch a paired peer's read-only companion snapshot
+    /// (`P2pEvent::ViewerSnapshot`).
+    FetchViewerSnapshot { peer_id: String },
     PairDevice { peer_id: String },
     /// Replace the node's paired-peer allow-list. The app sends this at
     /// P2P startup (from the persisted PairingManager) and whenever a
     /// device is paired/unpaired. Only peers in this set may be served
     /// sync data or be sync-initiated against — see P2P-001.
     UpdatePairedPeers { peer_ids: Vec<String> },
+    /// Replace the set of paired peers whose sync the user paused. They
+    /// stay on the allow-list (wipe and shard traffic still flows) but
+    /// sync requests are refused both ways.
+    UpdatePausedPeers { peer_ids: Vec<String> },
+    /// Replace the trust tiers of paired peers; peers not listed get
+    /// full trust. Read-only peers' pushes are refused and nothing is
+    /// pulled from them; guardian-only peers get no document sync.
+    UpdatePeerTrust { trust: HashMap<String, PeerTrust> },
+    /// Replace the manual address book: `peer_id → addresses` as the
+    /// user typed them (see [`crate::address_book`]). The node dials
+    /// them when mDNS can't find the peer, and keeps redialing.
+    UpdateAddressBook { addresses: HashMap<String, Vec<String>> },
+    /// The platform raised a direct link to a nearby device (hotspot,
+    /// Wi-Fi Direct, Bluetooth PAN — see [`crate::proximity`]). The node
+    /// dials the other end if known and syncs with whichever paired
+    /// device connects over it.
+    ProximityLinkUp { link: ProximityLink },
+    /// The proximity link is gone.
+    ProximityLinkDown,
+    /// The user revoked a paired device: drop it from the allow-list,
+    /// abandon any sync session with it and close its connections, so
+    /// nothing more is exchanged before the next `UpdatePairedPeers`.
+    RevokePeer { peer_id: String },
     /// Place backup fragments on one host peer (P4.2): sends one
     /// `StoreBackupFragment` per entry and emits `BackupPlaced` when
     /// every ack (or failure) has arrived.
@@ -168,6 +243,11 @@ pub enum P2pCommand {
     /// unpaired device loses its sealing key in the same breath as its
     /// allow-list entry.
     UpdatePairKeys { keys: PairKeyMap },
+    /// Replace the per-device sync policies (what each paired peer is
+    /// sent). Peers missing from the map get everything.
+    UpdateSyncPolicies {
+        policies: HashMap<String, crate::sync_policy::SyncPolicy>,
+    },
     /// Arm the node with a pairing offer (P3.1). The node answers the
     /// PairHello/PairProof/PairComplete handshake against it and emits
     /// `PairingCompleted` when a new device finishes. Replaces any
@@ -185,6 +265,21 @@ pub enum P2pCommand {
         epoch: u32,
     },
     SendRequest { peer_id: PeerId, request: SovereignRequest },
+    /// Replace the guardians allowed to co-sign a wipe of this device
+    /// (the peers holding its key shards). Empty = remote wipe disabled.
+    UpdateWipeGuardians { peer_ids: Vec<String> },
+    /// Sign a wipe order for a paired device and ask every other paired
+    /// device to co-sign it as guardian.
+    IssueRemoteWipe { target_peer_id: String },
+    /// The user approved a pending `WipeCosignRequested`: co-sign the
+    /// order and deliver it to its target (retried on reconnect).
+    ApproveRemoteWipe { order_id: String },
+    /// The app carried out a `RemoteWipeOrdered`: answer the issuer's
+    /// held `RemoteWipe` request with whether the wipe went through.
+    ReportWipe { order_id: String, wiped: bool },
+    /// Seal `bundle` to a paired device's identity key and deliver it.
+    /// Only after the user confirmed sharing keys with that device.
+    ShareKeys { peer_id: String, bundle: Box<KeyEscrowBundle> },
     /// Dial a peer's multiaddr directly (bypassing mDNS discovery).
     /// Used for tests and for explicit "connect to address" UI flows.
     /// `address` should be a full Multiaddr including the `/p2p/<peer_id>`
@@ -208,9 +303,36 @@ struct PeerSyncState {
     docs_synced: u32,
     /// Sum of `apply_rows` written counts across the session.
     rows_synced: u32,
-    /// Number of document conflicts surfaced during this session
-    /// (each is also emitted as a `P2pEvent::SyncConflict`).
+    /// Number of document merges needing review during this session
+    /// (each is also emitted as a `P2pEvent::ConflictDetails`).
     conflicts: u32,
+    /// Blobs the peer advertised in its manifest — the only ones worth
+    /// asking it for.
+    remote_blobs: HashSet<String>,
+    /// Blobs the peer said it can't serve; not asked for again this
+    /// session.
+    blobs_unavailable: HashSet<String>,
+    /// A `GetBlobs` is in flight. One at a time: each response brings
+    /// the next round.
+    blob_pull_inflight: bool,
+    /// Blobs still to push, waiting for room in a `PushBlobs`.
+    blob_push: Vec<BlobWant>,
+    /// Offsets sent in the last `PushBlobs`, to spot an ack that made no
+    /// progress.
+    blob_push_sent: HashMap<String, u64>,
+    /// Diff size, transfer counters and timing, reported as
+    /// `SyncProgress` events.
+    progress: SyncProgress,
+    /// When `StartSync` opened the session.
+    started_at: Option<Instant>,
+    /// Work carried by requests still awaiting an answer.
+    outstanding: HashMap<OutboundRequestId, PendingSync>,
+    /// Work whose request failed (typically the peer dropping). Queued
+    /// when the session ends, and resumed by the next one.
+    interrupted: PendingSync,
+    /// Commit, document and row ids re-requested from the queue at the
+    /// start of the session; the manifest follow-ups skip them.
+    resumed: HashSet<String>,
 }
 
 /// What kind of in-flight request an `OutboundRequestId` corresponds
@@ -227,8 +349,22 @@ enum InflightKind {
     Rows(SyncTable),
     /// `PushRows` — expecting a `PushAck` with per-row counts.
     PushRowsAck,
+    /// `GetBlobs` — response is `Blobs { chunks, .. }`, stored locally.
+    Blobs,
+    /// `PushBlobs` — expecting a `BlobAck` with resume points.
+    PushBlobsAck,
     /// `StoreBackupFragment` — expecting a `BackupStored` ack (P4.2).
     BackupStoreAck,
+    /// `RemoteWipe` delivery — expecting a `WipeAck`.
+    WipeDelivery,
+    /// `DeliverKeys` — expecting a `KeysAck`.
+    KeysDelivery,
+    /// `GetManifest` for a `PreviewSync` — diffed and reported, nothing
+    /// follows. Not part of a session.
+    Preview,
+    /// `GetViewerSnapshot` — opened, and once the last page is in,
+    /// reported. Not part of a session.
+    Viewer,
 }
 
 /// Per-peer bookkeeping for an in-flight backup placement job.
@@ -248,6 +384,11 @@ pub struct SovereignNode {
     /// Outbound request id → (peer, kind). Lets the response handler
     /// route follow-ups and finalize the session.
     inflight: HashMap<OutboundRequestId, (PeerId, InflightKind)>,
+    /// Viewer snapshot pages received so far, per peer, while more follow.
+    viewer_pages: HashMap<PeerId, ViewerSnapshot>,
+    /// Inbound `GetCommits` whose response is on its way. Once it is sent,
+    /// the states in it become merge bases ([`SyncService::delivered`]).
+    served_commits: HashSet<InboundRequestId>,
     /// Per-peer aggregate counters for the active sync session.
     sessions: HashMap<PeerId, PeerSyncState>,
     /// Allow-list of paired peer-id strings. A code-level HARD BARRIER
@@ -258,6 +399,17 @@ pub struct SovereignNode {
     /// from the single-threaded event loop via `UpdatePairedPeers`, so a
     /// plain `HashSet` (no lock) is sufficient.
     paired_peers: HashSet<String>,
+    /// Paired peers whose sync is paused (`UpdatePausedPeers`).
+    paused_peers: HashSet<String>,
+    /// Paired peers below full trust (`UpdatePeerTrust`).
+    peer_trust: HashMap<String, PeerTrust>,
+    /// Manually entered addresses of paired peers (`UpdateAddressBook`).
+    address_book: HashMap<PeerId, Vec<Multiaddr>>,
+    /// Address-book peers dialed by the reconnect timer; a connection
+    /// to one starts a sync, as mDNS discovery would.
+    redialed: HashSet<PeerId>,
+    /// The current proximity link, if the platform raised one.
+    proximity: Option<ProximityLink>,
     /// Per-peer high-water mark of manifest `generated_at` timestamps
     /// (P2P-003 replay guard) — see `check_manifest_freshness`.
     manifest_seen: HashMap<PeerId, chrono::DateTime<chrono::Utc>>,
@@ -269,6 +421,25 @@ pub struct SovereignNode {
     backup_host: Option<Arc<crate::backup_host::BackupHost>>,
     /// In-flight backup placement jobs per peer (P4.2).
     backup_jobs: HashMap<PeerId, BackupJob>,
+    /// Signs wipe orders (issuer) and co-signatures (guardian).
+    keypair: libp2p::identity::Keypair,
+    /// Guardians that may co-sign a wipe of this device.
+    wipe_guardians: HashSet<String>,
+    /// Orders awaiting this (guardian) user's approval, by order id.
+    wipe_cosign_requests: HashMap<String, RemoteWipeOrder>,
+    /// Co-signed orders not yet acknowledged by their target. In memory
+    /// only: a restart before delivery needs a fresh approval.
+    outbound_wipes: HashMap<PeerId, RemoteWipeOrder>,
+    /// Verified `RemoteWipe` requests (target side), answered once the app
+    /// sends `ReportWipe`. Dropped unanswered on shutdown, which the issuer
+    /// sees as a failed delivery and retries.
+    pending_wipe_acks:
+        HashMap<String, libp2p::request_response::ResponseChannel<SovereignResponse>>,
+    /// Rendezvous server doubling as circuit relay, for sync across
+    /// networks. None = LAN only.
+    relay: Option<(PeerId, Multiaddr)>,
+    /// Whether `GetViewerSnapshot` is answered (`P2pConfig::serve_viewer`).
+    serve_viewer: bool,
 }
 
 impl SovereignNode {
@@ -288,10 +459,17 @@ impl SovereignNode {
             peer_id, enable_mdns
         );
 
+        let relay = config.rendezvous_peer();
+        if config.rendezvous_server.is_some() && relay.is_none() {
+            warn!("Ignoring rendezvous_server: expected a multiaddr ending in /p2p/<peer id>");
+        }
+
         let swarm = libp2p::SwarmBuilder::with_existing_identity(keypair.clone())
             .with_tokio()
             .with_quic()
-            .with_behaviour(|key| {
+            .with_relay_client(libp2p::noise::Config::new, libp2p::yamux::Config::default)
+            .map_err(|e| P2pError::Transport(e.to_string()))?
+            .with_behaviour(|key, relay_client| {
                 // mDNS is toggleable (P2P-006). When disabled, the node does
                 // no LAN multicast / auto-discovery at all.
                 let mdns = if enable_mdns {
@@ -334,6 +512,8 @@ impl SovereignNode {
                 Ok(SovereignBehaviour {
                     mdns,
                     rendezvous,
+                    relay_client,
+                    dcutr: libp2p::dcutr::Behaviour::new(peer_id),
                     request_response,
                     identify,
                 })
@@ -350,12 +530,26 @@ impl SovereignNode {
             command_rx,
             sync_service,
             inflight: HashMap::new(),
+            viewer_pages: HashMap::new(),
+            served_commits: HashSet::new(),
             sessions: HashMap::new(),
             paired_peers: HashSet::new(),
+            paused_peers: HashSet::new(),
+            peer_trust: HashMap::new(),
+            address_book: HashMap::new(),
+            redialed: HashSet::new(),
+            proximity: None,
             manifest_seen: HashMap::new(),
             pairing_offer: None,
             backup_host,
             backup_jobs: HashMap::new(),
+            keypair,
+            wipe_guardians: HashSet::new(),
+            wipe_cosign_requests: HashMap::new(),
+            outbound_wipes: HashMap::new(),
+            pending_wipe_acks: HashMap::new(),
+            relay,
+            serve_viewer: config.serve_viewer,
         })
     }
 
@@ -372,13 +566,25 @@ impl SovereignNode {
 
         info!("Listening with id {:?}", listen_id);
 
+        // Reserve a slot on the relay so peers off the LAN can reach us.
+        if let Some((relay_peer, relay_addr)) = self.relay.clone() {
+            self.swarm.add_peer_address(relay_peer, relay_addr.clone());
+            let circuit = relay_addr.with(libp2p::multiaddr::Protocol::P2pCircuit);
+            match self.swarm.listen_on(circuit) {
+                Ok(_) => info!("Requesting relay reservation on {relay_peer}"),
+                Err(e) => warn!("Relay reservation on {relay_peer} failed: {e}"),
+            }
+        }
+
         Ok(addr)
     }
 
     /// Run the event loop. Blocks until shutdown.
     pub async fn run(mut self) {
+        let mut reconnect = tokio::time::interval(RECONNECT_INTERVAL);
         loop {
             tokio::select! {
+                _ = reconnect.tick() => self.redial_address_book(),
                 swarm_event = self.swarm.select_next_some() => {
                     self.handle_swarm_event(swarm_event).await;
                 }
@@ -402,6 +608,12 @@ impl SovereignNode {
             }
             SwarmEvent::NewListenAddr { address, .. } => {
                 info!("Listening on {}", address);
+                if behaviour::is_relayed(&address) {
+                    // Our relayed address is what we publish at the
+                    // rendezvous point.
+                    self.swarm.add_external_address(address.clone());
+                    self.register_at_rendezvous();
+                }
                 let _ = self
                     .event_tx
                     .send(P2pEvent::ListenAddr {
@@ -409,8 +621,27 @@ impl SovereignNode {
                     })
                     .await;
             }
-            SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+            SwarmEvent::ConnectionEstablished {
+                peer_id, endpoint, ..
+            } => {
                 info!("Connected to {}", peer_id);
+                if let Some(order) = self.outbound_wipes.get(&peer_id).cloned() {
+                    self.deliver_wipe(peer_id, order);
+                }
+                // A peer that dropped mid-sync is back: pick up where the
+                // interrupted session left off. A peer found through the
+                // address book syncs like one mDNS discovered.
+                // So does one that just reached us over a proximity link.
+                let redialed = self.redialed.remove(&peer_id);
+                let nearby = self
+                    .proximity
+                    .as_ref()
+                    .is_some_and(|link| link.carries(endpoint.get_remote_address()));
+                if !self.sessions.contains_key(&peer_id)
+                    && (redialed || nearby || self.sync_service.has_pending(&peer_id.to_string()))
+                {
+                    self.start_sync(peer_id.to_string()).await;
+                }
             }
             SwarmEvent::ConnectionClosed { peer_id, .. } => {
                 info!("Disconnected from {}", peer_id);
@@ -451,8 +682,24 @@ impl SovereignNode {
                 }
             }
             SovereignBehaviourEvent::Rendezvous(event) => {
-                tracing::debug!("Rendezvous event: {:?}", event);
+                self.handle_rendezvous_event(event).await;
+            }
+            SovereignBehaviourEvent::RelayClient(event) => {
+                if let libp2p::relay::client::Event::ReservationReqAccepted { relay_peer_id, .. } =
+                    event
+                {
+                    info!("Relay reservation accepted by {relay_peer_id}");
+                } else {
+                    tracing::debug!("Relay client event: {:?}", event);
+                }
             }
+            SovereignBehaviourEvent::Dcutr(event) => match event.result {
+                Ok(_) => info!("Hole punch to {} succeeded; direct connection", event.remote_peer_id),
+                Err(e) => info!(
+                    "Hole punch to {} failed, staying on the relay: {e}",
+                    event.remote_peer_id
+                ),
+            },
         }
     }
 
@@ -465,8 +712,13 @@ impl SovereignNode {
             Event::Message { peer, message, .. } => {
                 use libp2p::request_response::Message;
                 match message {
-                    Message::Request { request, channel, .. } => {
+                    Message::Request { request_id, request, channel } => {
                         info!("Request from {}: {:?}", peer, std::mem::discriminant(&request));
+                        let serves_commits = matches!(request, SovereignRequest::GetCommits { .. });
+                        let wipe_order = match &request {
+                            SovereignRequest::RemoteWipe(order) => Some(order.order_id.clone()),
+                            _ => None,
+                        };
                         // P3.1: the pairing handshake is handled by the node
                         // itself (it owns the offer state) and is allowed from
                         // unpaired peers by design — that's the whole point.
@@ -491,6 +743,34 @@ impl SovereignNode {
                             SovereignResponse::Error {
                                 message: "peer not paired".into(),
                             }
+                        } else if self.is_sync_request(&request)
+                            && self.paused_peers.contains(&peer.to_string())
+                        {
+                            SovereignResponse::Error {
+                                message: "sync paused".into(),
+                            }
+                        } else if let Some(reason) =
+                            trust_refusal(self.trust_of(&peer.to_string()), &request)
+                        {
+                            warn!(
+                                "Rejecting {:?} from {peer}: {reason}",
+                                std::mem::discriminant(&request)
+                            );
+                            SovereignResponse::Error {
+                                message: reason.into(),
+                            }
+                        } else if matches!(request, SovereignRequest::GetViewerSnapshot { .. })
+                            && !self.serve_viewer
+                        {
+                            SovereignResponse::Error {
+                                message: "viewer snapshots not served".into(),
+                            }
+                        } else if let Some(resp) =
+                            self.handle_wipe_request(peer, &request).await
+                        {
+                            resp
+                        } else if let SovereignRequest::DeliverKeys(envelope) = &request {
+                            self.handle_key_delivery(peer, envelope).await
                         } else {
                             process_request(
                                 request,
@@ -501,13 +781,32 @@ impl SovereignNode {
                             )
                             .await
                         };
-                        if self.swarm.behaviour_mut().request_response.send_response(channel, response).is_err() {
+                        if let (Some(order_id), SovereignResponse::WipeAck { accepted: true }) =
+                            (wipe_order, &response)
+                        {
+                            // Answered by `ReportWipe` once the wipe ran.
+                            self.pending_wipe_acks.insert(order_id, channel);
+                        } else if self.swarm.behaviour_mut().request_response.send_response(channel, response).is_err() {
                             warn!("Failed to send response to {}", peer);
+                            if serves_commits {
+                                self.sync_service.undelivered(&peer);
+                            }
+                        } else if serves_commits {
+                            self.served_commits.insert(request_id);
                         }
                     }
                     Message::Response { request_id, response } => {
                         match self.inflight.remove(&request_id) {
+                            Some((peer_id, InflightKind::Preview)) => {
+                                self.handle_preview_response(peer_id, response).await;
+                            }
+                            Some((peer_id, InflightKind::Viewer)) => {
+                                self.handle_viewer_response(peer_id, response).await;
+                            }
                             Some((peer_id, kind)) => {
+                                if let Some(s) = self.sessions.get_mut(&peer_id) {
+                                    s.outstanding.remove(&request_id);
+                                }
                                 self.handle_sync_response(peer_id, kind, response).await;
                             }
                             None => {
@@ -526,16 +825,59 @@ impl SovereignNode {
                 if let Some((peer_id, kind)) = self.inflight.remove(&request_id) {
                     if matches!(kind, InflightKind::BackupStoreAck) {
                         self.note_backup_ack(&peer_id, false).await;
+                    } else if matches!(kind, InflightKind::WipeDelivery) {
+                        // Kept in outbound_wipes; retried on reconnect.
+                    } else if matches!(kind, InflightKind::Preview) {
+                        let _ = self
+                            .event_tx
+                            .send(P2pEvent::SyncPreview {
+                                peer_id: peer_id.to_string(),
+                                preview: None,
+                            })
+                            .await;
+                    } else if matches!(kind, InflightKind::Viewer) {
+                        self.viewer_pages.remove(&peer_id);
+                        let _ = self
+                            .event_tx
+                            .send(P2pEvent::ViewerSnapshot {
+                                peer_id: peer_id.to_string(),
+                                snapshot: None,
+                            })
+                            .await;
+                    } else if matches!(kind, InflightKind::KeysDelivery) {
+                        let _ = self
+                            .event_tx
+                            .send(P2pEvent::KeysShared {
+                                peer_id: peer_id.to_string(),
+                                accepted: false,
+                            })
+                            .await;
                     } else {
+                        if matches!(kind, InflightKind::PushCommitsAck) {
+                            self.sync_service.undelivered(&peer_id);
+                        }
+                        if let Some(s) = self.sessions.get_mut(&peer_id) {
+                            if let Some(work) = s.outstanding.remove(&request_id) {
+                                s.interrupted.merge(work);
+                            }
+                        }
                         self.decrement_pending(&peer_id).await;
                     }
                 }
             }
-            Event::InboundFailure { peer, error, .. } => {
+            Event::InboundFailure { peer, request_id, error, .. } => {
                 warn!("Inbound request from {} failed: {:?}", peer, error);
+                if self.served_commits.remove(&request_id) {
+                    self.sync_service.undelivered(&peer);
+                }
             }
-            Event::ResponseSent { peer, .. } => {
+            Event::ResponseSent { peer, request_id, .. } => {
                 tracing::debug!("Response sent to {}", peer);
+                if self.served_commits.remove(&request_id) {
+                    if let Err(e) = self.sync_service.delivered(&peer) {
+                        warn!("Saving CRDT histories after serving {peer} failed: {e}");
+                    }
+                }
             }
         }
     }
@@ -547,22 +889,36 @@ impl SovereignNode {
         kind: InflightKind,
         response: SovereignResponse,
     ) {
+        if let Some(s) = self.sessions.get_mut(&peer_id) {
+            s.progress.bytes_received += response.payload_bytes();
+            if let SovereignResponse::Commits { commits } = &response {
+                s.progress.commits_received += commits.len() as u32;
+            }
+        }
         match (kind, response) {
             (InflightKind::Manifest, SovereignResponse::Manifest(em)) => {
                 self.handle_manifest_response(peer_id, em).await;
             }
             (InflightKind::Commits, SovereignResponse::Commits { commits }) => {
-                match self.sync_service.apply_commits(commits, &peer_id).await {
+                let applied = self.sync_service.apply_commits(commits, &peer_id).await;
+                let conflicts = report_merge_conflicts(&self.sync_service, &self.event_tx).await;
+                match applied {
                     Ok(n) => {
                         if let Some(s) = self.sessions.get_mut(&peer_id) {
                             s.docs_synced += n;
+                            s.conflicts += conflicts;
                         }
                     }
                     Err(e) => warn!("apply_commits from {peer_id} failed: {e}"),
                 }
+                // The documents may reference media we don't have yet.
+                self.request_missing_blobs(peer_id);
             }
             (InflightKind::PushCommitsAck, SovereignResponse::Ok) => {
                 // Peer accepted; their apply_commits result isn't reported back.
+                if let Err(e) = self.sync_service.delivered(&peer_id) {
+                    warn!("Saving CRDT histories after push to {peer_id} failed: {e}");
+                }
             }
             (InflightKind::Rows(table), SovereignResponse::Rows { table: t, rows }) => {
                 if t == table {
@@ -590,6 +946,34 @@ impl SovereignNode {
                     s.rows_synced += written;
                 }
             }
+            (InflightKind::Blobs, SovereignResponse::Blobs { chunks, unavailable }) => {
+                let progressed = !chunks.is_empty();
+                if let Err(e) = self.sync_service.apply_blob_chunks(chunks, &peer_id) {
+                    warn!("storing blobs from {peer_id} failed: {e}");
+                }
+                if let Some(s) = self.sessions.get_mut(&peer_id) {
+                    s.blob_pull_inflight = false;
+                    s.blobs_unavailable.extend(unavailable);
+                }
+                if progressed {
+                    self.request_missing_blobs(peer_id);
+                }
+            }
+            (InflightKind::PushBlobsAck, SovereignResponse::BlobAck { wants }) => {
+                // Continue only blobs the peer advanced on; a repeated
+                // offset means it won't take more.
+                let wants = match self.sessions.get_mut(&peer_id) {
+                    Some(s) => {
+                        let sent = std::mem::take(&mut s.blob_push_sent);
+                        wants
+                            .into_iter()
+                            .filter(|w| sent.get(&w.hash).is_some_and(|at| *at != w.offset))
+                            .collect()
+                    }
+                    None => Vec::new(),
+                };
+                self.push_blobs(peer_id, wants).await;
+            }
             (InflightKind::BackupStoreAck, SovereignResponse::BackupStored { accepted }) => {
                 self.note_backup_ack(&peer_id, accepted).await;
                 return; // not part of a sync session
@@ -599,8 +983,34 @@ impl SovereignNode {
                 self.note_backup_ack(&peer_id, false).await;
                 return;
             }
-            (_, SovereignResponse::Error { message }) => {
+            (InflightKind::WipeDelivery, response) => {
+                // A rejection or a failed wipe will not change on retry:
+                // drop the order.
+                if !matches!(response, SovereignResponse::WipeAck { accepted: true }) {
+                    warn!("Remote wipe refused or failed on {peer_id}: {:?}", response);
+                }
+                self.outbound_wipes.remove(&peer_id);
+                return; // not part of a sync session
+            }
+            (InflightKind::KeysDelivery, response) => {
+                let accepted = matches!(response, SovereignResponse::KeysAck { accepted: true });
+                if !accepted {
+                    warn!("Key delivery refused by {peer_id}: {:?}", response);
+                }
+                let _ = self
+                    .event_tx
+                    .send(P2pEvent::KeysShared {
+                        peer_id: peer_id.to_string(),
+                        accepted,
+                    })
+                    .await;
+                return; // not part of a sync session
+            }
+            (kind, SovereignResponse::Error { message }) => {
                 warn!("Peer {peer_id} returned error: {message}");
+                if matches!(kind, InflightKind::PushCommitsAck) {
+                    self.sync_service.undelivered(&peer_id);
+                }
             }
             (kind, response) => {
                 tracing::debug!(
@@ -631,6 +1041,119 @@ impl SovereignNode {
         Ok(())
     }
 
+    /// Report a `PreviewSync` result for `peer_id`.
+    async fn handle_preview_response(&mut self, peer_id: PeerId, response: SovereignResponse) {
+        let preview = match self.preview_from(&peer_id, response).await {
+            Ok(p) => Some(p),
+            Err(e) => {
+                warn!("Sync preview with {peer_id} failed: {e}");
+                None
+            }
+        };
+        let _ = self
+            .event_tx
+            .send(P2pEvent::SyncPreview {
+                peer_id: peer_id.to_string(),
+                preview,
+            })
+            .await;
+    }
+
+    /// Collect a page of a `FetchViewerSnapshot` result for `peer_id`,
+    /// asking for the next one or reporting the whole snapshot.
+    async fn handle_viewer_response(&mut self, peer_id: PeerId, response: SovereignResponse) {
+        let received = self.viewer_pages.remove(&peer_id);
+        let requested = received.as_ref().and_then(|s| s.next_after.clone());
+        let page = match response {
+            SovereignResponse::ViewerSnapshot(sealed) => {
+                match self.sync_service.open_viewer_snapshot(&peer_id, &sealed) {
+                    // Each page has to start past the last one, or a peer
+                    // could keep us asking forever.
+                    Ok(s) if s.next_after.is_some() && s.next_after <= requested => {
+                        warn!("Viewer snapshot from {peer_id} rejected: its pages don't advance");
+                        None
+                    }
+                    Ok(s) => Some(s),
+                    Err(e) => {
+                        warn!("Viewer snapshot from {peer_id} rejected: {e}");
+                        None
+                    }
+                }
+            }
+            SovereignResponse::Error { message } => {
+                warn!("Viewer snapshot from {peer_id} refused: {message}");
+                None
+            }
+            other => {
+                warn!(
+                    "Unexpected viewer response from {peer_id}: {:?}",
+                    std::mem::discriminant(&other)
+                );
+                None
+            }
+        };
+        let snapshot = page.map(|page| match received {
+            Some(mut s) => {
+                s.append(page);
+                s
+            }
+            None => page,
+        });
+        match snapshot {
+            Some(s) if s.next_after.is_some() => {
+                let after = s.next_after.clone();
+                let req_id = self
+                    .swarm
+                    .behaviour_mut()
+                    .request_response
+                    .send_request(&peer_id, SovereignRequest::GetViewerSnapshot { after });
+                self.inflight.insert(req_id, (peer_id, InflightKind::Viewer));
+                self.viewer_pages.insert(peer_id, s);
+            }
+            snapshot => {
+                let _ = self
+                    .event_tx
+                    .send(P2pEvent::ViewerSnapshot {
+                        peer_id: peer_id.to_string(),
+                        snapshot: snapshot.map(Box::new),
+                    })
+                    .await;
+            }
+        }
+    }
+
+    /// Diff a previewed peer's manifest against what we'd offer it. The
+    /// manifest goes through the same decryption and replay checks as a
+    /// session's; read-only peers preview as receive-nothing, like the
+    /// session they'd get.
+    async fn preview_from(
+        &mut self,
+        peer_id: &PeerId,
+        response: SovereignResponse,
+    ) -> Result<SyncPreview, String> {
+        let encrypted = match response {
+            SovereignResponse::Manifest(encrypted) => encrypted,
+            SovereignResponse::Error { message } => return Err(message),
+            other => return Err(format!("unexpected response {:?}", std::mem::discriminant(&other))),
+        };
+        let remote = SyncManifest::decrypt(&encrypted, self.sync_service.transport_key())
+            .map_err(|e| format!("manifest decrypt: {e}"))?;
+        self.check_manifest_freshness(peer_id, &remote.generated_at)?;
+        let local = self
+            .sync_service
+            .build_manifest_for(peer_id)
+            .await
+            .map_err(|e| format!("local manifest: {e}"))?;
+        let mut preview = sync_engine::preview(&local, &remote);
+        if !self.trust_of(&peer_id.to_string()).accepts_writes() {
+            preview.docs_to_receive = 0;
+            preview.rows_to_receive = 0;
+            preview.blobs_to_receive = 0;
+            preview.bytes_to_receive = 0;
+        }
+        Ok(preview)
+    }
+
     /// Decode a manifest response, diff against local, and dispatch the
     /// follow-up `GetCommits` / `PushCommits` / `GetRows` / `PushRows`
     /// requests. Each follow-up bumps `pending_responses` so the session
@@ -657,6 +1180,8 @@ impl SovereignNode {
             warn!("Rejecting manifest from {peer_id}: {reason}");
             return;
         }
+        // What we push from here on is compressed if the peer can open it.
+        self.sync_service.set_peer_codecs(&peer_id, &remote.codecs);
         let local = match self.sync_service.build_manifest().await {
             Ok(m) => m,
             Err(e) => {
@@ -666,11 +1191,32 @@ impl SovereignNode {
         };
 
         // ----- Documents (commit-chain track) -----
-        let doc_diff = sync_engine::compute_diff(&local, &remote);
+        let mut doc_diff = sync_engine::compute_diff(&local, &remote);
+        let mut row_diffs = sync_engine::compute_all_row_diffs(&local, &remote);
+        // A read-only replica only ever receives.
+        if !self.trust_of(&peer_id.to_string()).accepts_writes() {
+            doc_diff.need_from_remote.clear();
+            for rd in row_diffs.values_mut() {
+                rd.need_from_remote.clear();
+            }
+        }
+        if let Some(s) = self.sessions.get_mut(&peer_id) {
+            let p = &mut s.progress;
+            p.docs_to_pull = doc_diff.need_from_remote.len() as u32;
+            p.docs_to_push = doc_diff.push_to_remote.len() as u32;
+            p.rows_to_pull = row_diffs.values().map(|rd| rd.need_from_remote.len() as u32).sum();
+            p.rows_to_push = row_diffs.values().map(|rd| rd.push_to_remote.len() as u32).sum();
+        }
 
         // Fetch commits we need from the remote. For each doc we don't
         // have or that the remote is ahead on, ask for the head_commit;
         // the chain will be replayed in order by `apply_commits`.
+        // Work resumed from the sync queue is already on its way.
+        let resumed = self
+            .sessions
+            .get(&peer_id)
+            .map(|s| s.resumed.clone())
+            .unwrap_or_default();
         if !doc_diff.need_from_remote.is_empty() {
             let commit_ids: Vec<String> = doc_diff
                 .need_from_remote
@@ -682,12 +1228,16 @@ impl SovereignNode {
                         .find(|e| &e.doc_id == doc_id)
                         .and_then(|e| e.head_commit.clone())
                 })
+                .filter(|id| !resumed.contains(id))
                 .collect();
             if !commit_ids.is_empty() {
                 self.send_session_request(
                     peer_id,
                     InflightKind::Commits,
-                    SovereignRequest::GetCommits { commit_ids },
+                    SovereignRequest::GetCommits {
+                        commit_ids,
+                        codecs: Compression::SUPPORTED.to_vec(),
+                    },
                 );
             }
         }
@@ -695,7 +1245,7 @@ impl SovereignNode {
         // Push commits the remote needs from us.
         if !doc_diff.push_to_remote.is_empty() {
             let mut commits = Vec::new();
-            for doc_id in &doc_diff.push_to_remote {
+            for doc_id in doc_diff.push_to_remote.iter().filter(|d| !resumed.contains(*d)) {
                 let since = remote
                     .documents
                     .iter()
@@ -718,26 +1268,44 @@ impl SovereignNode {
             }
         }
 
-        // Surface document conflicts to the UI; LWW tables resolve silently.
-        for conflict in doc_diff.conflicts {
-            let _ = self
-                .event_tx
-                .send(P2pEvent::SyncConflict {
-                    doc_id: conflict.doc_id.clone(),
-                    description: format!(
-                        "diverged: local commits={}, remote commits={}",
-                        conflict.local_commit_count, conflict.remote_commit_count
-                    ),
-                })
-                .await;
+        // Diverged documents (`doc_diff.conflicts`) need no UI here: the
+        // winner's state carries its CRDT history and the receiving side
+        // merges both edits. Only merges that touched the same lines are
+        // surfaced, as `MergeConflict`s from `apply_commits`.
+        if !doc_diff.conflicts.is_empty() {
+            tracing::debug!(
+                "{} document(s) diverged from {peer_id}; merging",
+                doc_diff.conflicts.len()
+            );
+        }
+
+        // ----- Media blobs -----
+        // Peers without blob sync send no list; leave them be.
+        if let Some(remote_blobs) = remote.blobs.clone() {
+            let remote_blobs: HashSet<String> = remote_blobs.into_iter().collect();
+            let push: Vec<BlobWant> = match self.sync_service.shared_blobs(&peer_id).await {
+                Ok(shared) => shared
+                    .into_iter()
+                    .filter(|h| !remote_blobs.contains(h))
+                    .map(|hash| BlobWant { hash, offset: 0 })
+                    .collect(),
+                Err(e) => {
+                    warn!("Failed to list shared blobs: {e}");
+                    Vec::new()
+                }
+            };
             if let Some(s) = self.sessions.get_mut(&peer_id) {
-                s.conflicts += 1;
+                s.remote_blobs = remote_blobs;
             }
+            // Resumes downloads a previous session left unfinished.
+            self.request_missing_blobs(peer_id);
+            self.push_blobs(peer_id, push).await;
         }
 
         // ----- Row-level tables (LWW) -----
-        let row_diffs = sync_engine::compute_all_row_diffs(&local, &remote);
-        for (table, rd) in row_diffs {
+        for (table, mut rd) in row_diffs {
+            rd.need_from_remote.retain(|id| !resumed.contains(id));
+            rd.push_to_remote.retain(|id| !resumed.contains(id));
             if !rd.need_from_remote.is_empty() {
                 self.send_session_request(
                     peer_id,
@@ -745,6 +1313,7 @@ impl SovereignNode {
                     SovereignRequest::GetRows {
                         table,
                         ids: rd.need_from_remote,
+                        codecs: Compression::SUPPORTED.to_vec(),
                     },
                 );
             }
@@ -766,6 +1335,61 @@ impl SovereignNode {
         }
     }
 
+    /// Ask the peer for the next round of media we're missing, if any and
+    /// if no request is already out.
+    fn request_missing_blobs(&mut self, peer_id: PeerId) {
+        let Some(s) = self.sessions.get(&peer_id) else { return };
+        if s.blob_pull_inflight || !self.trust_of(&peer_id.to_string()).accepts_writes() {
+            return;
+        }
+        let wants: Vec<BlobWant> = self
+            .sync_service
+            .missing_blobs()
+            .into_iter()
+            .filter(|w| s.remote_blobs.contains(&w.hash) && !s.blobs_unavailable.contains(&w.hash))
+            .collect();
+        if wants.is_empty() {
+            return;
+        }
+        if let Some(s) = self.sessions.get_mut(&peer_id) {
+            s.blob_pull_inflight = true;
+        }
+        self.send_session_request(peer_id, InflightKind::Blobs, SovereignRequest::GetBlobs { wants });
+    }
+
+    /// Push the next chunks of `wants` plus whatever is queued, as much
+    /// as fits in one message; the rest waits for the ack.
+    async fn push_blobs(&mut self, peer_id: PeerId, mut wants: Vec<BlobWant>) {
+        let Some(s) = self.sessions.get_mut(&peer_id) else { return };
+        wants.append(&mut s.blob_push);
+        if wants.is_empty() {
+            return;
+        }
+        let (chunks, unavailable) = match self.sync_service.seal_blob_chunks(&wants, &peer_id).await {
+            Ok(r) => r,
+            Err(e) => {
+                warn!("sealing blobs for {peer_id} failed: {e}");
+                return;
+            }
+        };
+        let Some(s) = self.sessions.get_mut(&peer_id) else { return };
+        s.blob_push = wants
+            .into_iter()
+            .filter(|w| {
+                !unavailable.contains(&w.hash) && !chunks.iter().any(|c| c.hash == w.hash)
+            })
+            .collect();
+        if chunks.is_empty() {
+            return;
+        }
+        s.blob_push_sent = chunks.iter().map(|c| (c.hash.clone(), c.offset)).collect();
+        self.send_session_request(
+            peer_id,
+            InflightKind::PushBlobsAck,
+            SovereignRequest::PushBlobs { chunks },
+        );
+    }
+
     /// Issue an outbound request inside an existing sync session,
     /// recording the inflight bookkeeping so the response handler can
     /// route the eventual reply.
@@ -775,6 +1399,15 @@ impl SovereignNode {
         kind: InflightKind,
         request: SovereignRequest,
     ) {
+        let work = PendingSync::from_request(&request);
+        if let Some(s) = self.sessions.get_mut(&peer_id) {
+            s.pending_responses += 1;
+            s.progress.requests_total += 1;
+            s.progress.bytes_sent += request.payload_bytes();
+            if let SovereignRequest::PushCommits { commits } = &request {
+                s.progress.commits_sent += commits.len() as u32;
+            }
+        }
         let req_id = self
             .swarm
             .behaviour_mut()
@@ -782,7 +1415,136 @@ impl SovereignNode {
             .send_request(&peer_id, request);
         self.inflight.insert(req_id, (peer_id, kind));
         if let Some(s) = self.sessions.get_mut(&peer_id) {
-            s.pending_responses += 1;
+            if !work.is_empty() {
+                s.outstanding.insert(req_id, work);
+            }
+        }
+    }
+
+    /// Open a sync session with a paired, unpaused peer: ask for its
+    /// manifest and re-send whatever an interrupted session left queued.
+    async fn start_sync(&mut self, peer_id: String) {
+        // P2P-001: never initiate sync against an unpaired peer,
+        // even if the app auto-triggered on mDNS discovery.
+        if !self.paired_peers.contains(&peer_id) {
+            tracing::debug!("Skipping StartSync for unpaired peer {peer_id} (P2P-001)");
+            return;
+        }
+        if self.paused_peers.contains(&peer_id) {
+            tracing::debug!("Skipping StartSync for {peer_id}: sync paused");
+            return;
+        }
+        if !self.trust_of(&peer_id).syncs() {
+            tracing::debug!("Skipping StartSync for {peer_id}: guardian-only");
+            return;
+        }
+        let pid = match peer_id.parse::<PeerId>() {
+            Ok(p) => p,
+            Err(_) => {
+                warn!("Invalid peer ID for StartSync: {peer_id}");
+                return;
+            }
+        };
+        if self.sessions.contains_key(&pid) {
+            tracing::debug!("Sync already in flight for {pid}, skipping duplicate trigger");
+            return;
+        }
+        let _ = self
+            .event_tx
+            .send(P2pEvent::SyncStarted {
+                peer_id: peer_id.clone(),
+            })
+            .await;
+        self.sessions.insert(
+            pid,
+            PeerSyncState {
+                started_at: Some(Instant::now()),
+                ..Default::default()
+            },
+        );
+        self.send_session_request(pid, InflightKind::Manifest, SovereignRequest::GetManifest);
+        self.resume_pending(pid).await;
+    }
+
+    /// Re-send the work queued when the last session with `peer_id` was
+    /// cut short. Pushes are rebuilt from the current local state, so
+    /// edits made in the meantime go along.
+    async fn resume_pending(&mut self, peer_id: PeerId) {
+        let work = match self.sync_service.take_pending(&peer_id.to_string()) {
+            Ok(Some(work)) => work,
+            Ok(None) => return,
+            Err(e) => {
+                warn!("Failed to read the sync queue for {peer_id}: {e}");
+                return;
+            }
+        };
+        info!("Resuming interrupted sync with {peer_id}");
+        let mut work = work;
+        if !self.trust_of(&peer_id.to_string()).accepts_writes() {
+            work.pull_commits.clear();
+            work.pull_rows.clear();
+        }
+        let mut resumed = HashSet::new();
+
+        if !work.pull_commits.is_empty() {
+            resumed.extend(work.pull_commits.iter().cloned());
+            self.send_session_request(
+                peer_id,
+                InflightKind::Commits,
+                SovereignRequest::GetCommits {
+                    commit_ids: work.pull_commits,
+                    codecs: Compression::SUPPORTED.to_vec(),
+                },
+            );
+        }
+        let mut commits = Vec::new();
+        for push in &work.push_docs {
+            resumed.insert(push.doc_id.clone());
+            match self
+                .sync_service
+                .get_commits_since(&push.doc_id, push.since.as_deref(), &peer_id)
+                .await
+            {
+                Ok(cs) => commits.extend(cs),
+                Err(e) => warn!("Failed to resume push of {}: {e}", push.doc_id),
+            }
+        }
+        if !commits.is_empty() {
+            self.send_session_request(
+                peer_id,
+                InflightKind::PushCommitsAck,
+                SovereignRequest::PushCommits { commits },
+            );
+        }
+        for rows in work.pull_rows {
+            resumed.extend(rows.ids.iter().cloned());
+            self.send_session_request(
+                peer_id,
+                InflightKind::Rows(rows.table),
+                SovereignRequest::GetRows {
+                    table: rows.table,
+                    ids: rows.ids,
+                    codecs: Compression::SUPPORTED.to_vec(),
+                },
+            );
+        }
+        for rows in work.push_rows {
+            resumed.extend(rows.ids.iter().cloned());
+            match self.sync_service.get_rows(rows.table, &rows.ids, &peer_id).await {
+                Ok(sealed) if !sealed.is_empty() => self.send_session_request(
+                    peer_id,
+                    InflightKind::PushRowsAck,
+                    SovereignRequest::PushRows {
+                        table: rows.table,
+                        rows: sealed,
+                    },
+                ),
+                Ok(_) => {}
+                Err(e) => warn!("Failed to resume push of {:?} rows: {e}", rows.table),
+            }
+        }
+        if let Some(s) = self.sessions.get_mut(&peer_id) {
+            s.resumed = resumed;
         }
     }
 
@@ -814,30 +1576,51 @@ impl SovereignNode {
         }
     }
 
-    /// Decrement the pending-response counter for a peer's session and,
-    /// if it hits zero, emit `SyncCompleted` and remove the session.
+    /// Decrement the pending-response counter for a peer's session and
+    /// emit `SyncProgress` — or, if it hits zero, `SyncCompleted`, and
+    /// remove the session.
     async fn decrement_pending(&mut self, peer_id: &PeerId) {
-        let mut completed = None;
-        if let Some(state) = self.sessions.get_mut(peer_id) {
-            if state.pending_responses > 0 {
-                state.pending_responses -= 1;
-            }
-            if state.pending_responses == 0 {
-                completed = Some((state.docs_synced, state.rows_synced));
-            }
-        }
-        if let Some((docs, rows)) = completed {
-            self.sessions.remove(peer_id);
-            // Surface a single combined "items synced" count. Document
-            // commits and LWW rows are both content the user cares about.
+        let Some(state) = self.sessions.get_mut(peer_id) else { return };
+        state.pending_responses = state.pending_responses.saturating_sub(1);
+        state.progress.requests_done += 1;
+        let elapsed = state.started_at.map(|t| t.elapsed()).unwrap_or_default();
+        state.progress.update_timing(elapsed);
+        let progress = state.progress.clone();
+        if state.pending_responses > 0 {
             let _ = self
                 .event_tx
-                .send(P2pEvent::SyncCompleted {
+                .send(P2pEvent::SyncProgress {
                     peer_id: peer_id.to_string(),
-                    docs_synced: docs + rows,
+                    progress,
                 })
                 .await;
+            return;
         }
+        // Surface a single combined "items synced" count. Document
+        // commits and LWW rows are both content the user cares about.
+        let docs_synced = state.docs_synced + state.rows_synced;
+        let interrupted = std::mem::take(&mut state.interrupted);
+        self.sessions.remove(peer_id);
+        // A round spans many messages, so it's timed here rather than by a span.
+        tracing::debug!(
+            target: "sovereign_perf",
+            name = "sync.round",
+            duration_us = elapsed.as_micros() as u64
+        );
+        if !interrupted.is_empty() {
+            info!("Sync with {peer_id} was interrupted; queued the rest for the next session");
+            if let Err(e) = self.sync_service.enqueue_pending(&peer_id.to_string(), interrupted) {
+                warn!("Failed to persist the sync queue: {e}");
+            }
+        }
+        let _ = self
+            .event_tx
+            .send(P2pEvent::SyncCompleted {
+                peer_id: peer_id.to_string(),
+                docs_synced,
+                progress,
+            })
+            .await;
     }
 
     async fn handle_command(&mut self, cmd: P2pCommand) {
@@ -870,12 +1653,90 @@ impl SovereignNode {
                 let n = peer_ids.len();
                 self.paired_peers = peer_ids.into_iter().collect();
                 info!("Paired-peer allow-list updated: {n} peer(s)");
+                self.add_relayed_routes();
+            }
+            P2pCommand::UpdatePausedPeers { peer_ids } => {
+                let n = peer_ids.len();
+                self.paused_peers = peer_ids.into_iter().collect();
+                info!("Sync paused for {n} peer(s)");
+            }
+            P2pCommand::UpdateAddressBook { addresses } => {
+                self.address_book.clear();
+                for (peer_id, entries) in addresses {
+                    let Ok(pid) = peer_id.parse::<PeerId>() else {
+                        warn!("Invalid peer ID in address book: {peer_id}");
+                        continue;
+                    };
+                    let addrs: Vec<Multiaddr> = entries
+                        .iter()
+                        .filter_map(|a| match address_book::parse_peer_address(a, Some(pid)) {
+                            Ok(addr) => Some(addr),
+                            Err(e) => {
+                                warn!("Skipping address of {pid}: {e}");
+                                None
+                            }
+                        })
+                        .collect();
+                    for addr in &addrs {
+                        self.swarm.add_peer_address(pid, addr.clone());
+                    }
+                    if !addrs.is_empty() {
+                        self.address_book.insert(pid, addrs);
+                    }
+                }
+                info!("Address book updated: {} peer(s)", self.address_book.len());
+                self.redial_address_book();
+            }
+            P2pCommand::ProximityLinkUp { link } => {
+                info!("Proximity link up ({:?})", link.kind);
+                if let Some(addr) = link.peer_addr() {
+                    match self.swarm.dial(addr.clone()) {
+                        Ok(()) => info!("Dialing nearby device at {addr}"),
+                        Err(e) => warn!("Dial over proximity link failed: {e}"),
+                    }
+                }
+                self.proximity = Some(link);
+            }
+            P2pCommand::ProximityLinkDown => {
+                if let Some(link) = self.proximity.take() {
+                    info!("Proximity link down ({:?})", link.kind);
+                }
+            }
+            P2pCommand::UpdatePeerTrust { trust } => {
+                let n = trust.len();
+                self.peer_trust = trust;
+                info!("Peer trust updated: {n} peer(s) below full trust");
+            }
+            P2pCommand::RevokePeer { peer_id } => {
+                self.paired_peers.remove(&peer_id);
+                self.paused_peers.remove(&peer_id);
+                self.peer_trust.remove(&peer_id);
+                self.wipe_guardians.remove(&peer_id);
+                let Ok(pid) = peer_id.parse::<PeerId>() else {
+                    warn!("Invalid peer ID for RevokePeer: {peer_id}");
+                    return;
+                };
+                self.address_book.remove(&pid);
+                self.redialed.remove(&pid);
+                self.sessions.remove(&pid);
+                self.manifest_seen.remove(&pid);
+                if let Err(e) = self.sync_service.drop_pending(&peer_id) {
+                    warn!("Failed to clear the sync queue for {pid}: {e}");
+                }
+                self.inflight.retain(|_, (p, _)| *p != pid);
+                let _ = self.swarm.disconnect_peer_id(pid);
+                info!("Revoked paired device {pid}");
             }
             P2pCommand::UpdatePairKeys { keys } => {
                 let n = keys.0.len();
                 self.sync_service.set_pair_keys(keys.0);
                 info!("Per-pair sealing keys updated: {n} key(s)");
             }
+            P2pCommand::UpdateSyncPolicies { policies } => {
+                let n = policies.len();
+                self.sync_service.set_sync_policies(policies);
+                info!("Sync policies updated: {n} restricted device(s)");
+            }
             P2pCommand::PlaceBackup { peer_id, requests } => {
                 let pid = match peer_id.parse::<PeerId>() {
                     Ok(p) => p,
@@ -915,34 +1776,154 @@ impl SovereignNode {
                 }
             }
             P2pCommand::StartSync { peer_id } => {
-                // P2P-001: never initiate sync against an unpaired peer,
-                // even if the app auto-triggered on mDNS discovery.
-                if !self.paired_peers.contains(&peer_id) {
-                    tracing::debug!("Skipping StartSync for unpaired peer {peer_id} (P2P-001)");
+                self.start_sync(peer_id).await;
+            }
+            P2pCommand::PreviewSync { peer_id } => {
+                // Same gate as StartSync: only paired peers that sync.
+                let syncs =
+                    self.paired_peers.contains(&peer_id) && self.trust_of(&peer_id).syncs();
+                let pid = match peer_id.parse::<PeerId>() {
+                    Ok(pid) if syncs => pid,
+                    _ => {
+                        warn!("Refusing to preview sync with {peer_id}: not a syncing paired peer");
+                        let _ = self
+                            .event_tx
+                            .send(P2pEvent::SyncPreview { peer_id, preview: None })
+                            .await;
+                        return;
+                    }
+                };
+                let req_id = self
+                    .swarm
+                    .behaviour_mut()
+                    .request_response
+                    .send_request(&pid, SovereignRequest::GetManifest);
+                self.inflight.insert(req_id, (pid, InflightKind::Preview));
+            }
+            P2pCommand::FetchViewerSnapshot { peer_id } => {
+                let pid = match peer_id.parse::<PeerId>() {
+                    Ok(pid) if self.paired_peers.contains(&peer_id) => pid,
+                    _ => {
+                        warn!("Refusing to fetch a viewer snapshot from {peer_id}: not paired");
+                        let _ = self
+                            .event_tx
+                            .send(P2pEvent::ViewerSnapshot { peer_id, snapshot: None })
+                            .await;
+                        return;
+                    }
+                };
+                let req_id = self
+                    .swarm
+                    .behaviour_mut()
+                    .request_response
+                    .send_request(&pid, SovereignRequest::GetViewerSnapshot { after: None });
+                self.viewer_pages.remove(&pid);
+                self.inflight.insert(req_id, (pid, InflightKind::Viewer));
+            }
+            P2pCommand::PairDevice { peer_id } => {
+                info!("Pairing with device: {}", peer_id);
+            }
+            P2pCommand::UpdateWipeGuardians { peer_ids } => {
+                let n = peer_ids.len();
+                self.wipe_guardians = peer_ids.into_iter().collect();
+                info!("Remote-wipe guardians updated: {n} peer(s)");
+            }
+            P2pCommand::IssueRemoteWipe { target_peer_id } => {
+                if !self.paired_peers.contains(&target_peer_id) {
+                    warn!("Refusing remote wipe of unpaired peer {target_peer_id}");
                     return;
                 }
-                let pid = match peer_id.parse::<PeerId>() {
+                let order = match RemoteWipeOrder::issue(target_peer_id.clone(), &self.keypair) {
+                    Ok(o) => o,
+                    Err(e) => {
+                        warn!("Remote wipe order failed: {e}");
+                        return;
+                    }
+                };
+                let guardians: Vec<PeerId> = self
+                    .paired_peers
+                    .iter()
+                    .filter(|p| **p != target_peer_id)
+                    .filter_map(|p| p.parse().ok())
+                    .collect();
+                for pid in &guardians {
+                    self.swarm
+                        .behaviour_mut()
+                        .request_response
+                        .send_request(pid, SovereignRequest::CosignWipe(order.clone()));
+                }
+                info!(
+                    "Remote wipe {} of {target_peer_id} sent to {} guardian(s) for co-signing",
+                    order.order_id,
+                    guardians.len()
+                );
+            }
+            P2pCommand::ApproveRemoteWipe { order_id } => {
+                let Some(mut order) = self.wipe_cosign_requests.remove(&order_id) else {
+                    warn!("No pending remote wipe {order_id}");
+                    return;
+                };
+                let target = match order.target_peer_id.parse::<PeerId>() {
                     Ok(p) => p,
                     Err(_) => {
-                        warn!("Invalid peer ID for StartSync: {peer_id}");
+                        warn!("Invalid wipe target {}", order.target_peer_id);
                         return;
                     }
                 };
-                if self.sessions.contains_key(&pid) {
-                    tracing::debug!("Sync already in flight for {pid}, skipping duplicate trigger");
+                if let Err(e) = order.cosign(&self.keypair) {
+                    warn!("Remote wipe co-sign failed: {e}");
                     return;
                 }
-                let _ = self
-                    .event_tx
-                    .send(P2pEvent::SyncStarted {
-                        peer_id: peer_id.clone(),
-                    })
-                    .await;
-                self.sessions.insert(pid, PeerSyncState::default());
-                self.send_session_request(pid, InflightKind::Manifest, SovereignRequest::GetManifest);
+                info!("Remote wipe {order_id} co-signed; delivering to {target}");
+                self.outbound_wipes.insert(target, order.clone());
+                self.deliver_wipe(target, order);
             }
-            P2pCommand::PairDevice { peer_id } => {
-                info!("Pairing with device: {}", peer_id);
+            P2pCommand::ReportWipe { order_id, wiped } => {
+                let Some(channel) = self.pending_wipe_acks.remove(&order_id) else {
+                    warn!("No held remote wipe {order_id} to answer");
+                    return;
+                };
+                let ack = SovereignResponse::WipeAck { accepted: wiped };
+                if self.swarm.behaviour_mut().request_response.send_response(channel, ack).is_err() {
+                    warn!("Could not tell the issuer how remote wipe {order_id} went");
+                }
+            }
+            P2pCommand::ShareKeys { peer_id, bundle } => {
+                if !self.paired_peers.contains(&peer_id) {
+                    warn!("Refusing to share keys with unpaired peer {peer_id}");
+                    return;
+                }
+                if !self.trust_of(&peer_id).syncs() {
+                    warn!("Refusing to share keys with guardian-only peer {peer_id}");
+                    return;
+                }
+                let Ok(pid) = peer_id.parse::<PeerId>() else {
+                    warn!("Invalid peer ID for ShareKeys: {peer_id}");
+                    return;
+                };
+                let sealed = public_key_from_peer_id(&pid)
+                    .and_then(|k| k.try_into_ed25519().ok())
+                    .ok_or_else(|| "peer id carries no Ed25519 key".to_string())
+                    .and_then(|recipient| {
+                        key_escrow::seal_bundle(
+                            &bundle,
+                            &recipient.to_bytes(),
+                            &escrow_context(&self.local_peer_id(), &pid),
+                        )
+                        .map_err(|e| e.to_string())
+                    });
+                match sealed {
+                    Ok(envelope) => {
+                        let req_id = self
+                            .swarm
+                            .behaviour_mut()
+                            .request_response
+                            .send_request(&pid, SovereignRequest::DeliverKeys(envelope));
+                        self.inflight.insert(req_id, (pid, InflightKind::KeysDelivery));
+                        info!("Sharing {} key(s) with {pid}", bundle.key_count());
+                    }
+                    Err(e) => warn!("Sealing keys for {pid} failed: {e}"),
+                }
             }
             P2pCommand::Dial { address } => {
                 match address.parse::<Multiaddr>() {
@@ -960,6 +1941,209 @@ impl SovereignNode {
         }
     }
 
+    /// Publish our relayed address and look up where paired devices are.
+    fn register_at_rendezvous(&mut self) {
+        let Some((relay_peer, _)) = self.relay.clone() else { return };
+        let namespace = libp2p::rendezvous::Namespace::from_static(behaviour::RENDEZVOUS_NAMESPACE);
+        let rendezvous = &mut self.swarm.behaviour_mut().rendezvous;
+        if let Err(e) = rendezvous.register(namespace.clone(), relay_peer, None) {
+            warn!("Rendezvous registration failed: {e}");
+        }
+        rendezvous.discover(Some(namespace), None, None, relay_peer);
+    }
+
+    /// Every paired device is also reachable through our relay (if it holds
+    /// a reservation there). Dialing by PeerId then falls back to the
+    /// circuit when no direct address works.
+    /// Dial every paired address-book peer we aren't connected to. Runs
+    /// on [`RECONNECT_INTERVAL`] and whenever the address book changes.
+    fn redial_address_book(&mut self) {
+        let targets: Vec<(PeerId, Vec<Multiaddr>)> = self
+            .address_book
+            .iter()
+            .filter(|(pid, _)| {
+                self.paired_peers.contains(&pid.to_string()) && !self.swarm.is_connected(pid)
+            })
+            .map(|(pid, addrs)| (*pid, addrs.clone()))
+            .collect();
+        for (pid, addrs) in targets {
+            let opts = libp2p::swarm::dial_opts::DialOpts::peer_id(pid)
+                .addresses(addrs)
+                .build();
+            match self.swarm.dial(opts) {
+                Ok(()) => {
+                    tracing::debug!("Redialing {pid} from the address book");
+                    self.redialed.insert(pid);
+                }
+                Err(e) => tracing::debug!("Redial of {pid} failed: {e}"),
+            }
+        }
+    }
+
+    fn add_relayed_routes(&mut self) {
+        let Some((relay_peer, relay_addr)) = self.relay.clone() else { return };
+        for peer in self.paired_peers.iter().filter_map(|p| p.parse::<PeerId>().ok()) {
+            if peer != relay_peer {
+                self.swarm
+                    .add_peer_address(peer, behaviour::relayed_addr(&relay_addr, peer));
+            }
+        }
+    }
+
+    async fn handle_rendezvous_event(&mut self, event: libp2p::rendezvous::client::Event) {
+        use libp2p::rendezvous::client::Event;
+        match event {
+            Event::Registered { namespace, ttl, .. } => {
+                info!("Registered at rendezvous under {namespace} for {ttl}s");
+            }
+            Event::RegisterFailed { error, .. } => warn!("Rendezvous registration refused: {error:?}"),
+            Event::Discovered { registrations, .. } => {
+                // Only paired devices matter; anyone else registered under
+                // the namespace is ignored.
+                for reg in registrations {
+                    let peer = reg.record.peer_id();
+                    if peer == *self.swarm.local_peer_id()
+                        || !self.paired_peers.contains(&peer.to_string())
+                    {
+                        continue;
+                    }
+                    for addr in reg.record.addresses() {
+                        self.swarm.add_peer_address(peer, addr.clone());
+                    }
+                    info!("Rendezvous found paired peer {peer}");
+                    let _ = self
+                        .event_tx
+                        .send(P2pEvent::PeerDiscovered {
+                            peer_id: peer.to_string(),
+                            device_name: None,
+                        })
+                        .await;
+                }
+            }
+            Event::DiscoverFailed { error, .. } => warn!("Rendezvous discovery failed: {error:?}"),
+            Event::Expired { peer } => tracing::debug!("Rendezvous registration of {peer} expired"),
+        }
+    }
+
+    fn deliver_wipe(&mut self, target: PeerId, order: RemoteWipeOrder) {
+        let req_id = self
+            .swarm
+            .behaviour_mut()
+            .request_response
+            .send_request(&target, SovereignRequest::RemoteWipe(order));
+        self.inflight.insert(req_id, (target, InflightKind::WipeDelivery));
+    }
+
+    /// Handle the two remote-wipe verbs, or return `None` for any other
+    /// request. Only paired peers get here (P2P-001 gate). As guardian:
+    /// queue a `CosignWipe` for the user's approval. As target: verify a
+    /// `RemoteWipe` against the paired and guardian lists and tell the
+    /// app to wipe.
+    async fn handle_wipe_request(
+        &mut self,
+        peer: PeerId,
+        request: &SovereignRequest,
+    ) -> Option<SovereignResponse> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let accepted = match request {
+            SovereignRequest::CosignWipe(order) => {
+                let checked = if order.issuer_peer_id != peer.to_string() {
+                    Err(P2pError::RemoteWipe("sender is not the issuer".into()))
+                } else {
+                    order.verify_issuer(&self.paired_peers, now)
+                };
+                match checked {
+                    Ok(()) => {
+                        let _ = self
+                            .event_tx
+                            .send(P2pEvent::WipeCosignRequested {
+                                order_id: order.order_id.clone(),
+                                target_peer_id: order.target_peer_id.clone(),
+                                issuer_peer_id: order.issuer_peer_id.clone(),
+                            })
+                            .await;
+                        self.wipe_cosign_requests
+                            .insert(order.order_id.clone(), order.clone());
+                        true
+                    }
+                    Err(e) => {
+                        warn!("Ignoring co-sign request from {peer}: {e}");
+                        false
+                    }
+                }
+            }
+            SovereignRequest::RemoteWipe(order) => {
+                let local = self.local_peer_id().to_string();
+                match order.verify(&local, &self.paired_peers, &self.wipe_guardians, now) {
+                    Ok(()) => {
+                        warn!(
+                            "REMOTE WIPE {} ordered by {}, co-signed by guardian",
+                            order.order_id, order.issuer_peer_id
+                        );
+                        let _ = self
+                            .event_tx
+                            .send(P2pEvent::RemoteWipeOrdered {
+                                order_id: order.order_id.clone(),
+                                issuer_peer_id: order.issuer_peer_id.clone(),
+                                cosigner_peer_id: order.cosigner_peer_id.clone().unwrap_or_default(),
+                            })
+                            .await;
+                        true
+                    }
+                    Err(e) => {
+                        warn!("Rejecting remote wipe from {peer}: {e}");
+                        false
+                    }
+                }
+            }
+            _ => return None,
+        };
+        Some(SovereignResponse::WipeAck { accepted })
+    }
+
+    /// Open a `DeliverKeys` envelope sealed to this device and pass the
+    /// keys to the app. Only paired peers get here (P2P-001 gate).
+    async fn handle_key_delivery(
+        &mut self,
+        peer: PeerId,
+        envelope: &KeyEscrowEnvelope,
+    ) -> SovereignResponse {
+        let opened = self
+            .keypair
+            .clone()
+            .try_into_ed25519()
+            .map_err(|e| e.to_string())
+            .and_then(|kp| {
+                use zeroize::Zeroize;
+                let mut seed = [0u8; 32];
+                seed.copy_from_slice(kp.secret().as_ref());
+                let opened = key_escrow::open_bundle(
+                    envelope,
+                    &seed,
+                    &escrow_context(&peer, &self.local_peer_id()),
+                );
+                seed.zeroize();
+                opened.map_err(|e| e.to_string())
+            });
+        match opened {
+            Ok(bundle) => {
+                info!("Received {} key(s) from {peer}", bundle.key_count());
+                let _ = self
+                    .event_tx
+                    .send(P2pEvent::KeysReceived {
+                        peer_id: peer.to_string(),
+                        bundle: Box::new(bundle),
+                    })
+                    .await;
+                SovereignResponse::KeysAck { accepted: true }
+            }
+            Err(e) => {
+                warn!("Rejecting key delivery from {peer}: {e}");
+                SovereignResponse::KeysAck { accepted: false }
+            }
+        }
+    }
+
     /// Get the local peer ID.
     pub fn local_peer_id(&self) -> PeerId {
         *self.swarm.local_peer_id()
@@ -1189,6 +2373,11 @@ impl SovereignNode {
         }
     }
 
+    /// The trust tier the user gave a paired peer; full sync if unset.
+    fn trust_of(&self, peer_id: &str) -> PeerTrust {
+        self.peer_trust.get(peer_id).copied().unwrap_or_default()
+    }
+
     /// Whether a request reads or writes synced DB state, and so must be
     /// gated behind pairing (P2P-001). Pairing-handshake and guardian
     /// shard requests are intentionally allowed pre-pairing so a new
@@ -1201,6 +2390,8 @@ impl SovereignNode {
                 | SovereignRequest::PushCommits { .. }
                 | SovereignRequest::GetRows { .. }
                 | SovereignRequest::PushRows { .. }
+                | SovereignRequest::GetBlobs { .. }
+                | SovereignRequest::PushBlobs { .. }
                 | SovereignRequest::PushManifest(_)
                 // P4.2: accepting storage (fragments / guardian shards) is
                 // a commitment made to KNOWN peers — gating it also kills
@@ -1211,10 +2402,34 @@ impl SovereignNode {
                 // by approval + delay on the host side.
                 | SovereignRequest::StoreBackupFragment { .. }
                 | SovereignRequest::DeliverShard(_)
+                // Wipe orders only ever travel between paired devices.
+                | SovereignRequest::CosignWipe(_)
+                | SovereignRequest::RemoteWipe(_)
+                | SovereignRequest::DeliverKeys(_)
+                | SovereignRequest::GetViewerSnapshot { .. }
         )
     }
 }
 
+/// Forward the merges `apply_commits` flagged for review. Returns how many.
+async fn report_merge_conflicts(sync_service: &SyncService, event_tx: &mpsc::Sender<P2pEvent>) -> u32 {
+    let conflicts = sync_service.take_merge_conflicts();
+    let n = conflicts.len() as u32;
+    for c in conflicts {
+        let _ = event_tx
+            .send(P2pEvent::ConflictDetails {
+                doc_id: c.doc_id,
+                base: c.base,
+                local: c.local,
+                remote: c.remote,
+                merged: c.merged,
+                hunks: c.hunks,
+            })
+            .await;
+    }
+    n
+}
+
 /// Process a request without borrowing the node (avoids Send issues with Swarm).
 /// `peer` is the verified libp2p sender — `apply_rows` checks every row's
 /// envelope signature against the key embedded in it (P1.3 / P2P-003).
@@ -1227,7 +2442,8 @@ async fn process_request(
     backup_host: Option<&crate::backup_host::BackupHost>,
 ) -> SovereignResponse {
     match request {
-        SovereignRequest::GetManifest => match sync_service.build_manifest().await {
+        // Each peer sees only what its sync policy lets it have.
+        SovereignRequest::GetManifest => match sync_service.build_manifest_for(&peer).await {
             // P2P-002: seal the manifest under the per-account transport key.
             Ok(manifest) => match manifest.encrypt(sync_service.transport_key()) {
                 Ok(em) => SovereignResponse::Manifest(em),
@@ -1245,7 +2461,20 @@ async fn process_request(
                 }
             }
         },
-        SovereignRequest::GetCommits { commit_ids } => {
+        SovereignRequest::GetViewerSnapshot { after } => match sync_service
+            .viewer_snapshot_for(&peer, after.as_deref())
+            .await
+        {
+            Ok(sealed) => SovereignResponse::ViewerSnapshot(sealed),
+            Err(e) => {
+                warn!("Failed to build viewer snapshot: {e}");
+                SovereignResponse::Error {
+                    message: format!("viewer snapshot failed: {e}"),
+                }
+            }
+        },
+        SovereignRequest::GetCommits { commit_ids, codecs } => {
+            sync_service.set_peer_codecs(&peer, &codecs);
             match sync_service.get_commits(&commit_ids, &peer).await {
                 Ok(commits) => SovereignResponse::Commits { commits },
                 Err(e) => {
@@ -1257,7 +2486,9 @@ async fn process_request(
             }
         }
         SovereignRequest::PushCommits { commits } => {
-            match sync_service.apply_commits(commits, &peer).await {
+            let applied = sync_service.apply_commits(commits, &peer).await;
+            report_merge_conflicts(sync_service, event_tx).await;
+            match applied {
                 Ok(_n) => SovereignResponse::Ok,
                 Err(e) => {
                     warn!("Failed to apply commits: {e}");
@@ -1267,7 +2498,8 @@ async fn process_request(
                 }
             }
         }
-        SovereignRequest::GetRows { table, ids } => {
+        SovereignRequest::GetRows { table, ids, codecs } => {
+            sync_service.set_peer_codecs(&peer, &codecs);
             match sync_service.get_rows(table, &ids, &peer).await {
                 Ok(rows) => SovereignResponse::Rows { table, rows },
                 Err(e) => {
@@ -1289,6 +2521,28 @@ async fn process_request(
                 }
             }
         }
+        SovereignRequest::GetBlobs { wants } => {
+            match sync_service.seal_blob_chunks(&wants, &peer).await {
+                Ok((chunks, unavailable)) => SovereignResponse::Blobs { chunks, unavailable },
+                Err(e) => {
+                    warn!("Failed to read blobs: {e}");
+                    SovereignResponse::Error {
+                        message: format!("get_blobs failed: {e}"),
+                    }
+                }
+            }
+        }
+        SovereignRequest::PushBlobs { chunks } => {
+            match sync_service.apply_blob_chunks(chunks, &peer) {
+                Ok(wants) => SovereignResponse::BlobAck { wants },
+                Err(e) => {
+                    warn!("Failed to store blobs: {e}");
+                    SovereignResponse::Error {
+                        message: format!("push_blobs failed: {e}"),
+                    }
+                }
+            }
+        }
         SovereignRequest::DeliverShard(delivery) => {
             // P4: store the guardian shard for real (hosting opt-in).
             let accepted = match backup_host {
@@ -1426,9 +2680,25 @@ async fn process_request(
                 reason: "pairing not available".into(),
             }
         }
+        SovereignRequest::CosignWipe(_) | SovereignRequest::RemoteWipe(_) => {
+            // Handled by SovereignNode::handle_wipe_request for paired
+            // peers; anyone else is refused by the P2P-001 gate first.
+            SovereignResponse::WipeAck { accepted: false }
+        }
+        SovereignRequest::DeliverKeys(_) => {
+            // Handled by SovereignNode::handle_key_delivery, same gate.
+            SovereignResponse::KeysAck { accepted: false }
+        }
     }
 }
 
+/// Binds a key-escrow envelope to its sender and recipient, so it opens
+/// only on the device it was sealed for and only as coming from its
+/// sender.
+fn escrow_context(sender: &PeerId, recipient: &PeerId) -> Vec<u8> {
+    format!("{sender}|{recipient}").into_bytes()
+}
+
 /// Reject manifests that are stale (a captured ciphertext replayed later),
 /// from the future beyond a small clock-skew allowance (forged timestamp),
 /// or not strictly newer than the last manifest accepted from the same peer
@@ -1462,6 +2732,77 @@ fn validate_manifest_timestamp(
     Ok(ts)
 }
 
+/// Why a paired peer with `trust` may not make `request`, if it may not.
+/// Backup and wipe traffic is never refused on trust.
+fn trust_refusal(trust: PeerTrust, request: &SovereignRequest) -> Option<&'static str> {
+    let document_sync = matches!(
+        request,
+        SovereignRequest::GetManifest
+            | SovereignRequest::GetCommits { .. }
+            | SovereignRequest::PushCommits { .. }
+            | SovereignRequest::GetRows { .. }
+            | SovereignRequest::PushRows { .. }
+            | SovereignRequest::GetBlobs { .. }
+            | SovereignRequest::PushBlobs { .. }
+            | SovereignRequest::PushManifest(_)
+            | SovereignRequest::GetViewerSnapshot { .. }
+            | SovereignRequest::DeliverKeys(_)
+    );
+    let push = matches!(
+        request,
+        SovereignRequest::PushCommits { .. }
+            | SovereignRequest::PushRows { .. }
+            | SovereignRequest::PushBlobs { .. }
+    );
+    if document_sync && !trust.syncs() {
+        Some("peer is guardian-only")
+    } else if push && !trust.accepts_writes() {
+        Some("peer is read-only")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod trust_tests {
+    use super::*;
+
+    #[test]
+    fn read_only_peer_pushes_are_refused() {
+        let push = SovereignRequest::PushCommits { commits: vec![] };
+        assert_eq!(trust_refusal(PeerTrust::ReadOnly, &push), Some("peer is read-only"));
+        assert_eq!(trust_refusal(PeerTrust::Full, &push), None);
+        assert_eq!(trust_refusal(PeerTrust::ReadOnly, &SovereignRequest::GetManifest), None);
+    }
+
+    #[test]
+    fn guardian_only_peer_gets_no_document_sync() {
+        let refused = trust_refusal(PeerTrust::GuardianOnly, &SovereignRequest::GetManifest);
+        assert_eq!(refused, Some("peer is guardian-only"));
+        let backups = SovereignRequest::ListBackups { owner_tag: None };
+        assert_eq!(trust_refusal(PeerTrust::GuardianOnly, &backups), None);
+    }
+
+    #[test]
+    fn guardian_only_peer_cannot_deliver_keys() {
+        let keys = SovereignRequest::DeliverKeys(KeyEscrowEnvelope {
+            schema_version: 1,
+            ephemeral_public: [0; 32],
+            ciphertext: vec![],
+            nonce: Default::default(),
+        });
+        assert_eq!(trust_refusal(PeerTrust::GuardianOnly, &keys), Some("peer is guardian-only"));
+        assert_eq!(trust_refusal(PeerTrust::ReadOnly, &keys), None);
+    }
+
+    #[test]
+    fn viewer_snapshot_follows_document_trust() {
+        let req = SovereignRequest::GetViewerSnapshot { after: None };
+        assert_eq!(trust_refusal(PeerTrust::ReadOnly, &req), None);
+        assert_eq!(trust_refusal(PeerTrust::GuardianOnly, &req), Some("peer is guardian-only"));
+    }
+}
+
 #[cfg(test)]
 mod freshness_tests {
     use super::validate_manifest_timestamp;
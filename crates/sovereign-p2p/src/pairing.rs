@@ -18,6 +18,7 @@ use sovereign_crypto::account_key::AccountKey;
 
 use crate::error::{P2pError, P2pResult};
 use crate::identity::P2pIdentityKey;
+use crate::sync_policy::SyncPolicy;
 
 /// A paired device record.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +33,60 @@ pub struct PairedDevice {
     pub pair_key_b64: String,
     /// ISO-8601 timestamp of pairing.
     pub paired_at: String,
+    /// What this device sends to the peer. Absent in older stores, which
+    /// means "everything".
+    #[serde(default, skip_serializing_if = "SyncPolicy::is_unrestricted")]
+    pub sync_policy: SyncPolicy,
+    /// ISO-8601 timestamp of the last time the peer was seen on the
+    /// network (discovered or finished a sync).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<String>,
+    /// ISO-8601 timestamp of the last completed sync session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_sync: Option<String>,
+    /// Sync with this device is paused: it stays paired (remote wipe and
+    /// key-shard backup still work) but no documents move either way.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sync_paused: bool,
+    /// How far this device trusts the peer. Absent in older stores,
+    /// which means full sync.
+    #[serde(default, skip_serializing_if = "PeerTrust::is_full")]
+    pub trust: PeerTrust,
+    /// Addresses the user entered for the peer (see
+    /// [`crate::address_book`]), dialed when mDNS can't find it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub addresses: Vec<String>,
+}
+
+/// Trust tier of a paired device, chosen by the user.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerTrust {
+    /// Documents sync both ways.
+    #[default]
+    Full,
+    /// A replica: it is sent everything, but nothing it pushes is
+    /// applied here.
+    ReadOnly,
+    /// Holds backup shards and can approve a remote wipe; no documents
+    /// move either way.
+    GuardianOnly,
+}
+
+impl PeerTrust {
+    pub fn is_full(&self) -> bool {
+        *self == PeerTrust::Full
+    }
+
+    /// Whether documents sync with the peer at all.
+    pub fn syncs(self) -> bool {
+        self != PeerTrust::GuardianOnly
+    }
+
+    /// Whether the peer's changes are applied here.
+    pub fn accepts_writes(self) -> bool {
+        self == PeerTrust::Full
+    }
 }
 
 /// On-disk wrapper for the encrypted paired-device store (P1.4). The
@@ -63,6 +118,12 @@ impl PairedDevice {
             device_name,
             pair_key_b64: base64::engine::general_purpose::STANDARD.encode(pair_key),
             paired_at: chrono::Utc::now().to_rfc3339(),
+            sync_policy: SyncPolicy::default(),
+            last_seen: None,
+            last_sync: None,
+            sync_paused: false,
+            trust: PeerTrust::Full,
+            addresses: Vec::new(),
         }
     }
 }
@@ -101,6 +162,119 @@ impl PairingManager {
         self.devices.values().collect()
     }
 
+    /// Replace the sync policy for a paired device. Returns false if the
+    /// peer isn't paired.
+    pub fn set_sync_policy(&mut self, peer_id: &str, policy: SyncPolicy) -> bool {
+        match self.devices.get_mut(peer_id) {
+            Some(d) => {
+                d.sync_policy = policy;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Rename a paired device. Returns false if the peer isn't paired.
+    pub fn rename_device(&mut self, peer_id: &str, device_name: String) -> bool {
+        match self.devices.get_mut(peer_id) {
+            Some(d) => {
+                d.device_name = device_name;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Pause or resume sync with a paired device. Returns false if the
+    /// peer isn't paired.
+    pub fn set_sync_paused(&mut self, peer_id: &str, paused: bool) -> bool {
+        match self.devices.get_mut(peer_id) {
+            Some(d) => {
+                d.sync_paused = paused;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Peer ids of every device whose sync is paused, for
+    /// `P2pCommand::UpdatePausedPeers`.
+    pub fn paused_peers(&self) -> Vec<String> {
+        self.devices
+            .values()
+            .filter(|d| d.sync_paused)
+            .map(|d| d.peer_id.clone())
+            .collect()
+    }
+
+    /// Set the trust tier of a paired device. Returns false if the peer
+    /// isn't paired.
+    pub fn set_trust(&mut self, peer_id: &str, trust: PeerTrust) -> bool {
+        match self.devices.get_mut(peer_id) {
+            Some(d) => {
+                d.trust = trust;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// `peer_id → trust` for every device below full trust, for
+    /// `P2pCommand::UpdatePeerTrust`.
+    pub fn trust_map(&self) -> HashMap<String, PeerTrust> {
+        self.devices
+            .values()
+            .filter(|d| !d.trust.is_full())
+            .map(|d| (d.peer_id.clone(), d.trust))
+            .collect()
+    }
+
+    /// Replace the manual addresses of a paired device. Returns false if
+    /// the peer isn't paired.
+    pub fn set_addresses(&mut self, peer_id: &str, addresses: Vec<String>) -> bool {
+        match self.devices.get_mut(peer_id) {
+            Some(d) => {
+                d.addresses = addresses;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// `peer_id → addresses` for every device with manual addresses, for
+    /// `P2pCommand::UpdateAddressBook`.
+    pub fn address_book(&self) -> HashMap<String, Vec<String>> {
+        self.devices
+            .values()
+            .filter(|d| !d.addresses.is_empty())
+            .map(|d| (d.peer_id.clone(), d.addresses.clone()))
+            .collect()
+    }
+
+    /// Stamp a paired device as seen now — and as synced, if `synced`.
+    /// Returns false if the peer isn't paired.
+    pub fn mark_seen(&mut self, peer_id: &str, synced: bool) -> bool {
+        let Some(d) = self.devices.get_mut(peer_id) else {
+            return false;
+        };
+        let now = chrono::Utc::now().to_rfc3339();
+        if synced {
+            d.last_sync = Some(now.clone());
+        }
+        d.last_seen = Some(now);
+        true
+    }
+
+    /// `peer_id → policy` for every device with a restricted policy, for
+    /// [`crate::SyncService::set_sync_policies`].
+    pub fn sync_policy_map(&self) -> HashMap<String, SyncPolicy> {
+        self.devices
+            .values()
+            .filter(|d| !d.sync_policy.is_unrestricted())
+            .map(|d| (d.peer_id.clone(), d.sync_policy.clone()))
+            .collect()
+    }
+
     /// Check if a peer is paired.
     pub fn is_paired(&self, peer_id: &str) -> bool {
         self.devices.contains_key(peer_id)
@@ -256,6 +430,12 @@ mod tests {
             device_name: name.into(),
             pair_key_b64: pair_key_b64.into(),
             paired_at: "2026-01-01T00:00:00Z".into(),
+            sync_policy: SyncPolicy::default(),
+            last_seen: None,
+            last_sync: None,
+            sync_paused: false,
+            trust: PeerTrust::Full,
+            addresses: Vec::new(),
         }
     }
 
@@ -299,6 +479,69 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn sync_policy_persists_with_device() {
+        let dir = std::env::temp_dir().join("sovereign-p2p-test-pairing-policy");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("paired.json");
+        let store_key = [0x33u8; 32];
+
+        let policy = SyncPolicy {
+            include_threads: vec!["thread:work".into()],
+            exclude_external: true,
+            ..Default::default()
+        };
+        let mut pm = PairingManager::new(path.clone());
+        pm.add_device(device("peer-phone", "Phone", "key"));
+        pm.add_device(device("peer-laptop", "Laptop", "key"));
+        assert!(pm.set_sync_policy("peer-phone", policy.clone()));
+        assert!(!pm.set_sync_policy("peer-unknown", policy.clone()));
+        pm.save(&store_key).unwrap();
+
+        let pm2 = PairingManager::load(&path, &store_key).unwrap();
+        let map = pm2.sync_policy_map();
+        assert_eq!(map.len(), 1, "unrestricted devices are left out");
+        assert_eq!(map.get("peer-phone"), Some(&policy));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn device_state_persists_and_pauses() {
+        let dir = std::env::temp_dir().join("sovereign-p2p-test-pairing-state");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("paired.json");
+        let store_key = [0x33u8; 32];
+
+        let mut pm = PairingManager::new(path.clone());
+        pm.add_device(device("peer-phone", "Phone", "key"));
+        pm.add_device(device("peer-laptop", "Laptop", "key"));
+        assert!(pm.rename_device("peer-phone", "Work phone".into()));
+        assert!(pm.set_sync_paused("peer-laptop", true));
+        assert!(pm.set_trust("peer-laptop", PeerTrust::ReadOnly));
+        assert!(pm.set_addresses("peer-phone", vec!["192.168.1.20:4001".into()]));
+        assert!(pm.mark_seen("peer-phone", true));
+        assert!(!pm.mark_seen("peer-unknown", false));
+        pm.save(&store_key).unwrap();
+
+        let pm2 = PairingManager::load(&path, &store_key).unwrap();
+        let phone = pm2.get_device("peer-phone").unwrap();
+        assert_eq!(phone.device_name, "Work phone");
+        assert!(phone.last_sync.is_some());
+        assert_eq!(phone.last_seen, phone.last_sync);
+        assert_eq!(pm2.paused_peers(), vec!["peer-laptop".to_string()]);
+        assert_eq!(
+            pm2.trust_map(),
+            HashMap::from([("peer-laptop".to_string(), PeerTrust::ReadOnly)])
+        );
+        assert_eq!(pm2.address_book()["peer-phone"], vec!["192.168.1.20:4001"]);
+        assert!(pm2.get_device("peer-laptop").unwrap().last_seen.is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn legacy_plaintext_store_still_loads() {
         let dir = std::env::temp_dir().join("sovereign-p2p-test-pairing-legacy");
@@ -16,6 +16,10 @@ pub struct DocumentManifestEntry {
     /// Soft-delete marker (ISO-8601 string), `None` for active rows.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub deleted_at: Option<String>,
+    /// Plaintext size of title + content in bytes, for sync previews.
+    /// 0 from peers that don't report it.
+    #[serde(default)]
+    pub size: u64,
 }
 
 /// A sync manifest entry for a single thread.
@@ -119,6 +123,22 @@ pub struct SyncManifest {
     /// back to `created_at` — status changes must propagate). P2.
     #[serde(default)]
     pub suggested_links: Vec<RowManifestEntry>,
+    /// Per-document pin entries, for documents ever pinned (LWW field is
+    /// `favorite_at`, so unpins propagate too).
+    #[serde(default)]
+    pub favorites: Vec<RowManifestEntry>,
+    /// Per-document sensitivity entries, for documents whose level was
+    /// ever set (LWW field is `sensitivity_at`).
+    #[serde(default)]
+    pub sensitivities: Vec<RowManifestEntry>,
+    /// SHA-256 of every media blob this device holds for documents the
+    /// recipient may see. `None` from peers without blob sync.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blobs: Option<Vec<String>>,
+    /// Codecs this device can open in commit and row bodies. Empty from
+    /// peers without compression.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub codecs: Vec<super::sync::Compression>,
 }
 
 /// An encrypted sync manifest for wire transport.
@@ -147,6 +167,10 @@ impl SyncManifest {
             milestones: Vec::new(),
             relationships: Vec::new(),
             suggested_links: Vec::new(),
+            favorites: Vec::new(),
+            sensitivities: Vec::new(),
+            blobs: None,
+            codecs: super::sync::Compression::SUPPORTED.to_vec(),
         }
     }
 
@@ -219,6 +243,7 @@ mod tests {
             content_hash: "deadbeef".into(),
             modified_at: "2026-01-01T00:00:00Z".into(),
             deleted_at: None,
+            size: 0,
         });
 
         let pair_key = [42u8; 32];
@@ -1,6 +1,8 @@
 pub mod guardian;
 pub mod manifest;
 pub mod sync;
+pub mod viewer;
+pub mod wipe;
 
 use serde::{Deserialize, Serialize};
 
@@ -12,19 +14,31 @@ pub enum SovereignRequest {
     /// Push a sync manifest to a peer.
     PushManifest(manifest::EncryptedManifest),
     /// Request specific commits by ID.
-    GetCommits { commit_ids: Vec<String> },
+    GetCommits {
+        commit_ids: Vec<String>,
+        /// Codecs the requester can open in the returned bodies.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        codecs: Vec<sync::Compression>,
+    },
     /// Push encrypted commits to a peer.
     PushCommits { commits: Vec<sync::EncryptedCommit> },
     /// Request specific rows from a non-document table.
     GetRows {
         table: sync::SyncTable,
         ids: Vec<String>,
+        /// Codecs the requester can open in the returned bodies.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        codecs: Vec<sync::Compression>,
     },
     /// Push encrypted rows to a peer (LWW resolution server-side).
     PushRows {
         table: sync::SyncTable,
         rows: Vec<sync::EncryptedRow>,
     },
+    /// Request media blob bytes, each from its resume offset.
+    GetBlobs { wants: Vec<sync::BlobWant> },
+    /// Push media blob chunks to a peer.
+    PushBlobs { chunks: Vec<sync::EncryptedBlobChunk> },
     /// Guardian shard delivery.
     DeliverShard(guardian::ShardDeliveryRequest),
     /// Request a shard for recovery.
@@ -66,6 +80,19 @@ pub enum SovereignRequest {
         device_name: String,
         mac: Vec<u8>,
     },
+    /// Ask a guardian to co-sign a remote wipe order (paired peers only).
+    /// The guardian's user must approve before it signs.
+    CosignWipe(wipe::RemoteWipeOrder),
+    /// Deliver a co-signed wipe order to its target (paired peers only).
+    RemoteWipe(wipe::RemoteWipeOrder),
+    /// Hand a newly paired device this device's key databases, sealed to
+    /// its identity key (paired peers only; see
+    /// `sovereign_crypto::key_escrow`).
+    DeliverKeys(sovereign_crypto::key_escrow::KeyEscrowEnvelope),
+    /// Ask for a page of the read-only companion snapshot (paired peers
+    /// only, and only when the responder serves it; see [`viewer`]).
+    /// `after` is the previous page's `next_after`; `None` for the first.
+    GetViewerSnapshot { after: Option<String> },
 }
 
 /// Top-level response type for the Sovereign sync protocol.
@@ -84,6 +111,16 @@ pub enum SovereignResponse {
     },
     /// Acknowledgement of a PushRows with per-row write/skip counts.
     PushAck { written: u32, skipped: u32 },
+    /// Chunks in response to `GetBlobs`. `unavailable` lists requested
+    /// blobs this peer can't or won't serve; wants missing from both were
+    /// deferred to a later request.
+    Blobs {
+        chunks: Vec<sync::EncryptedBlobChunk>,
+        unavailable: Vec<String>,
+    },
+    /// Ack for `PushBlobs`: where to resume each pushed blob that isn't
+    /// complete yet.
+    BlobAck { wants: Vec<sync::BlobWant> },
     /// Shard delivery acknowledgement.
     ShardAck { accepted: bool },
     /// Shard for recovery.
@@ -108,6 +145,65 @@ pub enum SovereignResponse {
     /// Pairing rejected (bad offer/proof/mac, expired, or attempts
     /// exhausted).
     PairRejected { reason: String },
+    /// Ack for `CosignWipe` (queued for approval) or `RemoteWipe`
+    /// (verified and executing).
+    WipeAck { accepted: bool },
+    /// Ack for `DeliverKeys`: the envelope opened and was handed to the
+    /// app for import.
+    KeysAck { accepted: bool },
+    /// Snapshot in response to `GetViewerSnapshot`, sealed under the
+    /// requester's pair key.
+    ViewerSnapshot(viewer::EncryptedViewerSnapshot),
+}
+
+/// Sealed sync content carried by a list of commits, rows or chunks, in
+/// encoded bytes — what sync progress counts as "transferred".
+fn commits_bytes(commits: &[sync::EncryptedCommit]) -> u64 {
+    commits
+        .iter()
+        .map(|c| (c.encrypted_snapshot.len() + c.nonce.len()) as u64)
+        .sum()
+}
+
+fn rows_bytes(rows: &[sync::EncryptedRow]) -> u64 {
+    rows.iter()
+        .map(|r| (r.ciphertext.len() + r.nonce.len()) as u64)
+        .sum()
+}
+
+fn chunks_bytes(chunks: &[sync::EncryptedBlobChunk]) -> u64 {
+    chunks
+        .iter()
+        .map(|c| (c.ciphertext.len() + c.nonce.len()) as u64)
+        .sum()
+}
+
+impl SovereignRequest {
+    /// Size of the sealed sync content this request carries (0 for
+    /// requests that only ask for something).
+    pub fn payload_bytes(&self) -> u64 {
+        match self {
+            Self::PushManifest(m) => (m.ciphertext.len() + m.nonce.len()) as u64,
+            Self::PushCommits { commits } => commits_bytes(commits),
+            Self::PushRows { rows, .. } => rows_bytes(rows),
+            Self::PushBlobs { chunks } => chunks_bytes(chunks),
+            _ => 0,
+        }
+    }
+}
+
+impl SovereignResponse {
+    /// Size of the sealed sync content this response carries (0 for
+    /// acks and non-sync responses).
+    pub fn payload_bytes(&self) -> u64 {
+        match self {
+            Self::Manifest(m) => (m.ciphertext.len() + m.nonce.len()) as u64,
+            Self::Commits { commits } => commits_bytes(commits),
+            Self::Rows { rows, .. } => rows_bytes(rows),
+            Self::Blobs { chunks, .. } => chunks_bytes(chunks),
+            _ => 0,
+        }
+    }
 }
 
 /// Wire view of one hosted backup (P4): the public manifest + salt and
@@ -150,4 +246,26 @@ mod tests {
         let json = serde_json::to_string(&resp).unwrap();
         assert!(json.contains("not found"));
     }
+
+    #[test]
+    fn payload_bytes_counts_sealed_content_only() {
+        let chunk = sync::EncryptedBlobChunk {
+            hash: "h".repeat(64),
+            offset: 0,
+            total: 6,
+            ciphertext: "c".repeat(100),
+            nonce: "n".repeat(16),
+        };
+        let push = SovereignRequest::PushBlobs {
+            chunks: vec![chunk.clone(), chunk.clone()],
+        };
+        assert_eq!(push.payload_bytes(), 232);
+        let resp = SovereignResponse::Blobs {
+            chunks: vec![chunk],
+            unavailable: vec!["x".into()],
+        };
+        assert_eq!(resp.payload_bytes(), 116);
+        assert_eq!(SovereignRequest::GetManifest.payload_bytes(), 0);
+        assert_eq!(SovereignResponse::PushAck { written: 3, skipped: 0 }.payload_bytes(), 0);
+    }
 }
@@ -1,5 +1,72 @@
 use serde::{Deserialize, Serialize};
 
+use crate::error::{P2pError, P2pResult};
+
+/// Payloads smaller than this go out uncompressed — a zstd frame header
+/// would eat most of the gain.
+const COMPRESS_MIN_BYTES: usize = 256;
+
+/// Upper bound on a decompressed envelope, so a hostile peer can't make
+/// us inflate a few kilobytes into gigabytes.
+const MAX_DECOMPRESSED_BYTES: usize = 64 * 1024 * 1024;
+
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Codec applied to a commit or row body before it is sealed.
+///
+/// Peers advertise the codecs they can open — in their manifest
+/// (`SyncManifest::codecs`) and in pull requests (`GetCommits`,
+/// `GetRows`) — and the sender compresses only for a peer that listed
+/// one, so older builds keep receiving plain JSON. The receiver doesn't
+/// need to be told: a zstd frame starts with its magic number, JSON
+/// never does, and the check runs on the AEAD-authenticated plaintext.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Compression {
+    #[default]
+    None,
+    Zstd,
+    /// A codec from a newer build; never chosen.
+    #[serde(other)]
+    Unknown,
+}
+
+impl Compression {
+    /// Codecs this build can open.
+    pub const SUPPORTED: &'static [Compression] = &[Compression::Zstd];
+
+    /// The codec to use for a peer that advertised `offered`.
+    pub fn negotiate(offered: &[Compression]) -> Compression {
+        if offered.contains(&Compression::Zstd) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+
+    /// Compress an envelope body ahead of sealing. Bodies that are small
+    /// or don't shrink are returned as they are.
+    pub fn pack(self, plaintext: Vec<u8>) -> Vec<u8> {
+        if self != Compression::Zstd || plaintext.len() < COMPRESS_MIN_BYTES {
+            return plaintext;
+        }
+        match zstd::bulk::compress(&plaintext, zstd::DEFAULT_COMPRESSION_LEVEL) {
+            Ok(packed) if packed.len() < plaintext.len() => packed,
+            _ => plaintext,
+        }
+    }
+}
+
+/// Undo [`Compression::pack`] on an unsealed body: zstd frames are
+/// inflated, anything else is returned untouched.
+pub fn unpack(plaintext: Vec<u8>) -> P2pResult<Vec<u8>> {
+    if !plaintext.starts_with(&ZSTD_MAGIC) {
+        return Ok(plaintext);
+    }
+    zstd::bulk::decompress(&plaintext, MAX_DECOMPRESSED_BYTES)
+        .map_err(|e| P2pError::SyncError(format!("decompress sync payload: {e}")))
+}
+
 /// Tables that participate in the row-level sync protocol. Documents
 /// have their own commit-chain track via `EncryptedCommit`; everything
 /// else moves through `EncryptedRow`.
@@ -16,6 +83,11 @@ pub enum SyncTable {
     Milestone,
     Relationship,
     SuggestedLink,
+    /// Document pins, keyed by document id. The document itself stays on
+    /// the commit track; pinning isn't an edit to it.
+    Favorite,
+    /// Document sensitivity levels, keyed by document id, like pins.
+    Sensitivity,
 }
 
 impl SyncTable {
@@ -31,6 +103,8 @@ impl SyncTable {
             SyncTable::Milestone => "milestone",
             SyncTable::Relationship => "related_to",
             SyncTable::SuggestedLink => "suggested_link",
+            SyncTable::Favorite => "favorite",
+            SyncTable::Sensitivity => "sensitivity",
         }
     }
 }
@@ -99,6 +173,29 @@ pub struct EncryptedCommit {
     pub signature: String,
 }
 
+/// Resume point of a blob transfer: the receiver already holds the first
+/// `offset` bytes of `hash`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobWant {
+    pub hash: String,
+    pub offset: u64,
+}
+
+/// One slice of a media blob, AEAD-sealed under the pair key like the
+/// commit and row envelopes. Integrity of the whole blob is checked
+/// against `hash` once the last slice lands.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedBlobChunk {
+    pub hash: String,
+    pub offset: u64,
+    /// Size of the whole blob.
+    pub total: u64,
+    /// Base64-encoded ciphertext of the slice.
+    pub ciphertext: String,
+    /// Base64-encoded nonce.
+    pub nonce: String,
+}
+
 /// Result of comparing two manifests.
 #[derive(Debug, Clone)]
 pub struct SyncDiff {
@@ -172,6 +269,24 @@ mod tests {
         assert!(with_need.has_work());
     }
 
+    #[test]
+    fn compression_roundtrip_and_negotiation() {
+        let body = serde_json::to_vec(&vec!["the same paragraph again"; 200]).unwrap();
+        let packed = Compression::Zstd.pack(body.clone());
+        assert!(packed.len() < body.len() / 4);
+        assert_eq!(unpack(packed).unwrap(), body);
+
+        // Plain JSON, small bodies and uncompressing peers pass through.
+        assert_eq!(Compression::None.pack(body.clone()), body);
+        assert_eq!(unpack(body.clone()).unwrap(), body);
+        assert_eq!(Compression::Zstd.pack(b"{}".to_vec()), b"{}");
+
+        assert_eq!(Compression::negotiate(Compression::SUPPORTED), Compression::Zstd);
+        assert_eq!(Compression::negotiate(&[]), Compression::None);
+        let offered: Vec<Compression> = serde_json::from_str(r#"["brotli","zstd"]"#).unwrap();
+        assert_eq!(offered, [Compression::Unknown, Compression::Zstd]);
+    }
+
     #[test]
     fn sync_conflict_serde() {
         let conflict = SyncConflict {
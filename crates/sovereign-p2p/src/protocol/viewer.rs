@@ -0,0 +1,166 @@
+//! Read-only snapshot served to a companion viewer.
+//!
+//! A phone or tablet that only needs to *read* the workspace doesn't
+//! have to run the commit-chain sync: it pairs through the normal
+//! handshake (which gives both sides the per-pair key), and then asks
+//! for a `GetViewerSnapshot`. The answer is the current state of every
+//! document and thread its sync policy allows, sealed under that pair
+//! key. Nothing the viewer sends is ever applied here — the endpoint has
+//! no write verb.
+//!
+//! A response has to fit the transport's message limit, so the snapshot
+//! comes in pages of at most [`MAX_PAGE_BYTES`] of documents, in id order.
+//! Each page names where the next one starts; the requester keeps asking
+//! until a page has no successor. A document too big for a page of its
+//! own is named in `too_large` rather than silently left out.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{P2pError, P2pResult};
+
+/// Serialized document bytes per page. Sealed and base64-encoded, a full
+/// page stays well under the 10 MiB response limit.
+pub const MAX_PAGE_BYTES: usize = 4 * 1024 * 1024;
+
+/// One document as a viewer sees it: current title and content only.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViewerDocument {
+    pub doc_id: String,
+    pub title: String,
+    pub content: String,
+    pub thread_id: String,
+    pub modified_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViewerThread {
+    pub thread_id: String,
+    pub name: String,
+}
+
+/// Everything a viewer is shown, as of `generated_at`: one page of it on
+/// the wire, all pages once [`Self::append`]ed together.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViewerSnapshot {
+    pub device_id: String,
+    pub generated_at: String,
+    pub documents: Vec<ViewerDocument>,
+    pub threads: Vec<ViewerThread>,
+    /// Titles of documents left out because they alone exceed a page.
+    #[serde(default)]
+    pub too_large: Vec<String>,
+    /// Id of the last document considered for this page, when more
+    /// follow; ask again with it as `after`.
+    #[serde(default)]
+    pub next_after: Option<String>,
+}
+
+/// A `ViewerSnapshot` sealed under the pair key of the viewer it was
+/// built for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedViewerSnapshot {
+    /// Base64-encoded ciphertext.
+    pub ciphertext: String,
+    /// Base64-encoded nonce.
+    pub nonce: String,
+}
+
+impl ViewerSnapshot {
+    pub fn new(device_id: String) -> Self {
+        Self {
+            device_id,
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            documents: Vec::new(),
+            threads: Vec::new(),
+            too_large: Vec::new(),
+            next_after: None,
+        }
+    }
+
+    /// Add the next page to the pages received so far.
+    pub fn append(&mut self, page: ViewerSnapshot) {
+        self.documents.extend(page.documents);
+        self.threads.extend(page.threads);
+        self.too_large.extend(page.too_large);
+        self.next_after = page.next_after;
+    }
+
+    /// Seal for the viewer holding `pair_key`.
+    pub fn seal(&self, pair_key: &[u8; 32]) -> P2pResult<EncryptedViewerSnapshot> {
+        use base64::Engine;
+        let json = serde_json::to_vec(self)
+            .map_err(|e| P2pError::SyncError(format!("viewer snapshot serialize: {e}")))?;
+        let (ciphertext, nonce) = sovereign_crypto::aead::encrypt(&json, pair_key)
+            .map_err(|e| P2pError::SyncError(format!("viewer snapshot encrypt: {e}")))?;
+        Ok(EncryptedViewerSnapshot {
+            ciphertext: base64::engine::general_purpose::STANDARD.encode(&ciphertext),
+            nonce: base64::engine::general_purpose::STANDARD.encode(nonce),
+        })
+    }
+
+    /// Open a snapshot sealed by [`Self::seal`].
+    pub fn open(sealed: &EncryptedViewerSnapshot, pair_key: &[u8; 32]) -> P2pResult<Self> {
+        use base64::Engine;
+        let decode = |s: &str| {
+            base64::engine::general_purpose::STANDARD
+                .decode(s)
+                .map_err(|e| P2pError::SyncError(format!("viewer snapshot base64: {e}")))
+        };
+        let ciphertext = decode(&sealed.ciphertext)?;
+        let nonce: [u8; 24] = decode(&sealed.nonce)?
+            .try_into()
+            .map_err(|_| P2pError::SyncError("viewer snapshot nonce wrong length".into()))?;
+        let json = sovereign_crypto::aead::decrypt(&ciphertext, &nonce, pair_key)
+            .map_err(|e| P2pError::SyncError(format!("viewer snapshot decrypt: {e}")))?;
+        serde_json::from_slice(&json)
+            .map_err(|e| P2pError::SyncError(format!("viewer snapshot parse: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_snapshot_opens_only_with_its_pair_key() {
+        let mut snapshot = ViewerSnapshot::new("laptop".into());
+        snapshot.threads.push(ViewerThread {
+            thread_id: "thread:1".into(),
+            name: "Research".into(),
+        });
+        snapshot.documents.push(ViewerDocument {
+            doc_id: "document:1".into(),
+            title: "Notes".into(),
+            content: "read me on the train".into(),
+            thread_id: "thread:1".into(),
+            modified_at: "2026-01-01T00:00:00Z".into(),
+        });
+
+        let sealed = snapshot.seal(&[7u8; 32]).unwrap();
+        assert!(!sealed.ciphertext.contains("train"));
+        assert_eq!(ViewerSnapshot::open(&sealed, &[7u8; 32]).unwrap(), snapshot);
+        assert!(ViewerSnapshot::open(&sealed, &[8u8; 32]).is_err());
+    }
+
+    #[test]
+    fn pages_append_into_one_snapshot() {
+        let doc = |id: &str| ViewerDocument {
+            doc_id: id.into(),
+            title: id.into(),
+            content: String::new(),
+            thread_id: "thread:1".into(),
+            modified_at: "2026-01-01T00:00:00Z".into(),
+        };
+        let mut first = ViewerSnapshot::new("laptop".into());
+        first.documents.push(doc("document:a"));
+        first.next_after = Some("document:a".into());
+        let mut second = ViewerSnapshot::new("laptop".into());
+        second.documents.push(doc("document:b"));
+        second.too_large.push("Scan archive".into());
+
+        first.append(second);
+        assert_eq!(first.documents.len(), 2);
+        assert_eq!(first.too_large, ["Scan archive"]);
+        assert_eq!(first.next_after, None);
+    }
+}
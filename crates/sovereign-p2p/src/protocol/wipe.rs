@@ -0,0 +1,271 @@
+//! Remote wipe orders for a lost or stolen device.
+//!
+//! An order names one target device and needs two signatures over the
+//! same bytes: the paired device that issued it, and a guardian that
+//! co-signed it after its own user approved. The target acts on an order
+//! only when both check out against its own paired-peer and guardian
+//! lists, so neither a single compromised device nor a LAN stranger can
+//! wipe it.
+//!
+//! Flow: issuer → guardian `CosignWipe`; the guardian's user approves;
+//! guardian → target `RemoteWipe`, retried whenever the target connects.
+
+use std::collections::HashSet;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as B64URL;
+use base64::Engine;
+use libp2p::identity::Keypair;
+use libp2p::PeerId;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{P2pError, P2pResult};
+use crate::sync_service::public_key_from_peer_id;
+
+/// How long an order stays valid. A lost device may stay offline for a
+/// while; past this the owner issues a fresh one.
+pub const ORDER_TTL_MS: i64 = 7 * 24 * 60 * 60 * 1000;
+
+/// Clock-skew allowance for orders stamped slightly in the future.
+const MAX_FUTURE_SKEW_MS: i64 = 5 * 60 * 1000;
+
+/// A signed instruction to wipe `target_peer_id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteWipeOrder {
+    pub order_id: String,
+    pub target_peer_id: String,
+    pub issuer_peer_id: String,
+    /// Unix milliseconds.
+    pub issued_at: i64,
+    pub issuer_signature: Vec<u8>,
+    /// Set by the guardian on approval.
+    #[serde(default)]
+    pub cosigner_peer_id: Option<String>,
+    #[serde(default)]
+    pub cosigner_signature: Vec<u8>,
+}
+
+impl RemoteWipeOrder {
+    /// A new order for `target_peer_id`, signed by the issuing device.
+    pub fn issue(target_peer_id: String, issuer: &Keypair) -> P2pResult<Self> {
+        let mut id_bytes = [0u8; 16];
+        rand::rng().fill_bytes(&mut id_bytes);
+        let mut order = Self {
+            order_id: B64URL.encode(id_bytes),
+            target_peer_id,
+            issuer_peer_id: issuer.public().to_peer_id().to_string(),
+            issued_at: chrono::Utc::now().timestamp_millis(),
+            issuer_signature: Vec::new(),
+            cosigner_peer_id: None,
+            cosigner_signature: Vec::new(),
+        };
+        order.issuer_signature = issuer
+            .sign(&order.signing_bytes())
+            .map_err(|e| P2pError::RemoteWipe(format!("sign: {e}")))?;
+        Ok(order)
+    }
+
+    /// Add the guardian's co-signature.
+    pub fn cosign(&mut self, guardian: &Keypair) -> P2pResult<()> {
+        self.cosigner_signature = guardian
+            .sign(&self.signing_bytes())
+            .map_err(|e| P2pError::RemoteWipe(format!("cosign: {e}")))?;
+        self.cosigner_peer_id = Some(guardian.public().to_peer_id().to_string());
+        Ok(())
+    }
+
+    /// Canonical bytes both parties sign: the order id, target, issuer and
+    /// timestamp, length-prefixed under a domain tag.
+    fn signing_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(160);
+        out.extend_from_slice(b"sovereign-remote-wipe:v1");
+        for field in [
+            self.order_id.as_str(),
+            self.target_peer_id.as_str(),
+            self.issuer_peer_id.as_str(),
+        ] {
+            out.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            out.extend_from_slice(field.as_bytes());
+        }
+        out.extend_from_slice(&self.issued_at.to_le_bytes());
+        out
+    }
+
+    /// Checks a guardian makes before asking its user to co-sign: the
+    /// order is fresh, issued by a paired device, aimed at another paired
+    /// device, and carries a valid issuer signature.
+    pub fn verify_issuer(&self, paired_peers: &HashSet<String>, now_ms: i64) -> P2pResult<()> {
+        if self.issued_at > now_ms + MAX_FUTURE_SKEW_MS || now_ms - self.issued_at > ORDER_TTL_MS {
+            return Err(P2pError::RemoteWipe("order expired".into()));
+        }
+        if self.issuer_peer_id == self.target_peer_id {
+            return Err(P2pError::RemoteWipe("issuer cannot target itself".into()));
+        }
+        if !paired_peers.contains(&self.issuer_peer_id) {
+            return Err(P2pError::RemoteWipe("issuer is not a paired device".into()));
+        }
+        if !paired_peers.contains(&self.target_peer_id) {
+            return Err(P2pError::RemoteWipe("target is not a paired device".into()));
+        }
+        if !signed_by(&self.issuer_peer_id, &self.signing_bytes(), &self.issuer_signature) {
+            return Err(P2pError::RemoteWipe("bad issuer signature".into()));
+        }
+        Ok(())
+    }
+
+    /// Full check on the target: addressed to `local_peer_id`, issuer
+    /// paired, and co-signed by one of `guardians` other than the issuer.
+    /// An empty guardian list rejects every order.
+    pub fn verify(
+        &self,
+        local_peer_id: &str,
+        paired_peers: &HashSet<String>,
+        guardians: &HashSet<String>,
+        now_ms: i64,
+    ) -> P2pResult<()> {
+        if self.target_peer_id != local_peer_id {
+            return Err(P2pError::RemoteWipe("order is for another device".into()));
+        }
+        let mut with_self = paired_peers.clone();
+        with_self.insert(local_peer_id.to_string());
+        self.verify_issuer(&with_self, now_ms)?;
+
+        let cosigner = self
+            .cosigner_peer_id
+            .as_deref()
+            .ok_or_else(|| P2pError::RemoteWipe("missing guardian co-signature".into()))?;
+        if cosigner == self.issuer_peer_id || cosigner == local_peer_id {
+            return Err(P2pError::RemoteWipe("co-signer must be a separate guardian".into()));
+        }
+        if !guardians.contains(cosigner) {
+            return Err(P2pError::RemoteWipe("co-signer is not a guardian".into()));
+        }
+        if !signed_by(cosigner, &self.signing_bytes(), &self.cosigner_signature) {
+            return Err(P2pError::RemoteWipe("bad guardian signature".into()));
+        }
+        Ok(())
+    }
+}
+
+fn signed_by(peer_id: &str, message: &[u8], signature: &[u8]) -> bool {
+    peer_id
+        .parse::<PeerId>()
+        .ok()
+        .and_then(|p| public_key_from_peer_id(&p))
+        .is_some_and(|key| key.verify(message, signature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Devices {
+        issuer: Keypair,
+        guardian: Keypair,
+        target: Keypair,
+    }
+
+    impl Devices {
+        fn new() -> Self {
+            Self {
+                issuer: Keypair::generate_ed25519(),
+                guardian: Keypair::generate_ed25519(),
+                target: Keypair::generate_ed25519(),
+            }
+        }
+
+        fn id(k: &Keypair) -> String {
+            k.public().to_peer_id().to_string()
+        }
+
+        /// Paired peers as seen from the target.
+        fn target_paired(&self) -> HashSet<String> {
+            [Self::id(&self.issuer), Self::id(&self.guardian)].into()
+        }
+
+        fn guardians(&self) -> HashSet<String> {
+            [Self::id(&self.guardian)].into()
+        }
+
+        fn order(&self) -> RemoteWipeOrder {
+            let mut order = RemoteWipeOrder::issue(Self::id(&self.target), &self.issuer).unwrap();
+            order.cosign(&self.guardian).unwrap();
+            order
+        }
+
+        fn verify(&self, order: &RemoteWipeOrder) -> P2pResult<()> {
+            order.verify(
+                &Self::id(&self.target),
+                &self.target_paired(),
+                &self.guardians(),
+                chrono::Utc::now().timestamp_millis(),
+            )
+        }
+    }
+
+    #[test]
+    fn cosigned_order_verifies_on_target() {
+        let d = Devices::new();
+        let order = d.order();
+        d.verify(&order).unwrap();
+
+        let json = serde_json::to_string(&order).unwrap();
+        let back: RemoteWipeOrder = serde_json::from_str(&json).unwrap();
+        d.verify(&back).unwrap();
+    }
+
+    #[test]
+    fn guardian_checks_issuer_before_cosigning() {
+        let d = Devices::new();
+        let order = RemoteWipeOrder::issue(Devices::id(&d.target), &d.issuer).unwrap();
+        let now = chrono::Utc::now().timestamp_millis();
+        let paired: HashSet<String> = [Devices::id(&d.issuer), Devices::id(&d.target)].into();
+        order.verify_issuer(&paired, now).unwrap();
+
+        let stranger_only: HashSet<String> = [Devices::id(&d.target)].into();
+        assert!(order.verify_issuer(&stranger_only, now).is_err());
+    }
+
+    #[test]
+    fn missing_or_foreign_cosignature_is_rejected() {
+        let d = Devices::new();
+        let uncosigned = RemoteWipeOrder::issue(Devices::id(&d.target), &d.issuer).unwrap();
+        assert!(d.verify(&uncosigned).is_err());
+
+        // Co-signed by the issuer itself.
+        let mut self_cosigned = uncosigned.clone();
+        self_cosigned.cosign(&d.issuer).unwrap();
+        assert!(d.verify(&self_cosigned).is_err());
+
+        // Co-signed by a device that is not a guardian.
+        let mut outsider = uncosigned.clone();
+        outsider.cosign(&Keypair::generate_ed25519()).unwrap();
+        assert!(d.verify(&outsider).is_err());
+
+        // No guardians configured: nothing can authorize a wipe.
+        let order = d.order();
+        let now = chrono::Utc::now().timestamp_millis();
+        assert!(order
+            .verify(&Devices::id(&d.target), &d.target_paired(), &HashSet::new(), now)
+            .is_err());
+    }
+
+    #[test]
+    fn tampered_wrong_target_or_stale_order_is_rejected() {
+        let d = Devices::new();
+
+        let mut retargeted = d.order();
+        retargeted.target_peer_id = Devices::id(&Keypair::generate_ed25519());
+        assert!(d.verify(&retargeted).is_err());
+
+        let mut backdated = d.order();
+        backdated.issued_at -= 1;
+        assert!(d.verify(&backdated).is_err());
+
+        let order = d.order();
+        let later = order.issued_at + ORDER_TTL_MS + 1;
+        assert!(order
+            .verify(&Devices::id(&d.target), &d.target_paired(), &d.guardians(), later)
+            .is_err());
+    }
+}
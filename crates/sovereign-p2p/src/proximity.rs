@@ -0,0 +1,121 @@
+//! Proximity links: sync, pairing and guardian-shard delivery between two
+//! devices that share no network.
+//!
+//! Neither Bluetooth nor Wi-Fi Direct is a libp2p transport, but each can
+//! raise an IP link between two nearby devices:
+//!
+//! - [`ProximityKind::Hotspot`]: one device opens a local-only hotspot
+//!   (Android `LocalOnlyHotspot`, a desktop hosted network) and the other
+//!   joins it;
+//! - [`ProximityKind::WifiDirect`]: a Wi-Fi Direct (P2P) group;
+//! - [`ProximityKind::BluetoothPan`]: Bluetooth tethering (PAN/BNEP).
+//!
+//! The user brings the link up in the OS and reports it from the "Nearby
+//! device" form in the Devices settings, as a [`ProximityLink`]; the node
+//! then speaks its usual QUIC + Noise over it. Its wildcard
+//! listener already covers the new interface (the link's listen address
+//! shows up as a `ListenAddr` event, so pairing offers carry it), and
+//! [`crate::P2pCommand::ProximityLinkUp`] dials the device at the other
+//! end and starts a sync once it answers. Everything above the socket —
+//! the paired-peer gate, trust tiers, per-pair sealing — is unchanged.
+//!
+//! Radio setup (opening the hotspot, exchanging its SSID and passphrase,
+//! Bluetooth bonding) stays with the OS; this module only describes the
+//! link.
+
+use std::net::{IpAddr, SocketAddr};
+
+use libp2p::multiaddr::Protocol;
+use libp2p::Multiaddr;
+use serde::{Deserialize, Serialize};
+
+/// How a proximity link was raised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProximityKind {
+    Hotspot,
+    WifiDirect,
+    BluetoothPan,
+}
+
+/// A direct IP link to a nearby device, as reported by the platform.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProximityLink {
+    pub kind: ProximityKind,
+    /// This device's address on the link, when the platform knows it.
+    #[serde(default)]
+    pub local_ip: Option<IpAddr>,
+    /// Where the device at the other end listens (`ip:port`), when
+    /// known — e.g. typed from its screen, or the hotspot gateway plus
+    /// its listen port. Without it the node waits to be dialed (or for
+    /// mDNS, which often works on a hotspot).
+    #[serde(default)]
+    pub peer: Option<SocketAddr>,
+}
+
+impl ProximityLink {
+    /// The QUIC multiaddr of the device at the other end, if known.
+    pub fn peer_addr(&self) -> Option<Multiaddr> {
+        self.peer.map(|socket| {
+            Multiaddr::empty()
+                .with(socket.ip().into())
+                .with(Protocol::Udp(socket.port()))
+                .with(Protocol::QuicV1)
+        })
+    }
+
+    /// Whether `addr` lies on this link: same IPv4 /24 (the subnet
+    /// hotspots, Wi-Fi Direct groups and PAN hand out) as either end.
+    pub fn carries(&self, addr: &Multiaddr) -> bool {
+        let Some(Protocol::Ip4(ip)) = addr.iter().next() else {
+            return false;
+        };
+        [self.local_ip, self.peer.map(|p| p.ip())]
+            .into_iter()
+            .flatten()
+            .any(|end| match end {
+                IpAddr::V4(end) => end.octets()[..3] == ip.octets()[..3],
+                IpAddr::V6(_) => false,
+            })
+    }
+
+    /// Reorder listen addresses (as strings, the way pairing offers carry
+    /// them) so those on this link come first.
+    pub fn sort_link_first(&self, addrs: &mut [String]) {
+        addrs.sort_by_key(|a| !a.parse::<Multiaddr>().is_ok_and(|m| self.carries(&m)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_addresses() {
+        let link = ProximityLink {
+            kind: ProximityKind::Hotspot,
+            local_ip: Some("192.168.43.17".parse().unwrap()),
+            peer: Some("192.168.43.1:4001".parse().unwrap()),
+        };
+        assert_eq!(
+            link.peer_addr().unwrap().to_string(),
+            "/ip4/192.168.43.1/udp/4001/quic-v1"
+        );
+        let on_link: Multiaddr = "/ip4/192.168.43.17/udp/50211/quic-v1".parse().unwrap();
+        let lan: Multiaddr = "/ip4/10.0.0.4/udp/50211/quic-v1".parse().unwrap();
+        assert!(link.carries(&on_link));
+        assert!(!link.carries(&lan));
+
+        let mut addrs = vec![lan.to_string(), on_link.to_string()];
+        link.sort_link_first(&mut addrs);
+        assert_eq!(addrs, [on_link.to_string(), lan.to_string()]);
+    }
+
+    #[test]
+    fn link_without_peer_waits_to_be_dialed() {
+        let json = r#"{"kind":"bluetooth_pan","local_ip":"192.168.44.2"}"#;
+        let link: ProximityLink = serde_json::from_str(json).unwrap();
+        assert_eq!(link.kind, ProximityKind::BluetoothPan);
+        assert!(link.peer_addr().is_none());
+    }
+}
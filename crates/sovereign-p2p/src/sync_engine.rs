@@ -5,6 +5,11 @@ use crate::protocol::manifest::{
     ShareRecordManifestEntry, SyncManifest, ThreadManifestEntry,
 };
 use crate::protocol::sync::{SyncConflict, SyncDiff, SyncTable};
+use crate::sync_progress::SyncPreview;
+
+/// Assumed size of one table row in a [`preview`]; manifests don't carry
+/// row sizes, and most rows (threads, contacts, messages) are small.
+pub const ROW_ESTIMATE_BYTES: u64 = 1024;
 
 /// Per-table row diff used for the LWW row protocol. Documents have
 /// their own commit-aware diff via `compute_diff` / `SyncDiff`.
@@ -282,6 +287,8 @@ pub fn compute_all_row_diffs(
         (SyncTable::Milestone, &local.milestones, &remote.milestones),
         (SyncTable::Relationship, &local.relationships, &remote.relationships),
         (SyncTable::SuggestedLink, &local.suggested_links, &remote.suggested_links),
+        (SyncTable::Favorite, &local.favorites, &remote.favorites),
+        (SyncTable::Sensitivity, &local.sensitivities, &remote.sensitivities),
     ] {
         let d = compute_generic_row_diff(locals, remotes);
         if d.has_work() {
@@ -292,6 +299,45 @@ pub fn compute_all_row_diffs(
     out
 }
 
+/// What syncing `local` with `remote` would move, from the manifests
+/// alone: the same diff a session computes, counted instead of fetched.
+/// Byte totals are estimates — document sizes come from the manifests,
+/// rows count as [`ROW_ESTIMATE_BYTES`] each, and media isn't sized.
+pub fn preview(local: &SyncManifest, remote: &SyncManifest) -> SyncPreview {
+    let doc_diff = compute_diff(local, remote);
+    let row_diffs = compute_all_row_diffs(local, remote);
+    let size_of = |manifest: &SyncManifest, ids: &[String]| -> u64 {
+        manifest
+            .documents
+            .iter()
+            .filter(|e| ids.contains(&e.doc_id))
+            .map(|e| e.size)
+            .sum()
+    };
+    let rows_to_receive: u32 = row_diffs.values().map(|d| d.need_from_remote.len() as u32).sum();
+    let rows_to_send: u32 = row_diffs.values().map(|d| d.push_to_remote.len() as u32).sum();
+    let (blobs_to_receive, blobs_to_send) = match (&local.blobs, &remote.blobs) {
+        (Some(ours), Some(theirs)) => (
+            theirs.iter().filter(|h| !ours.contains(h)).count() as u32,
+            ours.iter().filter(|h| !theirs.contains(h)).count() as u32,
+        ),
+        _ => (0, 0),
+    };
+    SyncPreview {
+        docs_to_receive: doc_diff.need_from_remote.len() as u32,
+        docs_to_send: doc_diff.push_to_remote.len() as u32,
+        conflicts: doc_diff.conflicts.len() as u32,
+        rows_to_receive,
+        rows_to_send,
+        blobs_to_receive,
+        blobs_to_send,
+        bytes_to_receive: size_of(remote, &doc_diff.need_from_remote)
+            + u64::from(rows_to_receive) * ROW_ESTIMATE_BYTES,
+        bytes_to_send: size_of(local, &doc_diff.push_to_remote)
+            + u64::from(rows_to_send) * ROW_ESTIMATE_BYTES,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -315,6 +361,7 @@ mod tests {
             content_hash: hash.into(),
             modified_at: modified_at.into(),
             deleted_at: None,
+            size: 0,
         }
     }
 
@@ -517,4 +564,25 @@ mod tests {
         assert_eq!(diffs.len(), 1);
         assert!(diffs.contains_key(&SyncTable::Thread));
     }
+
+    #[test]
+    fn preview_counts_and_sizes_the_diff() {
+        let mut ours = entry("doc:mine", Some("c:1"), 1, "h1");
+        ours.size = 4_000;
+        let mut theirs = entry("doc:theirs", Some("c:2"), 1, "h2");
+        theirs.size = 10_000;
+        let mut local = manifest_with("dev-1", vec![ours, entry("doc:both", None, 1, "h3")]);
+        let mut remote = manifest_with("dev-2", vec![theirs, entry("doc:both", None, 1, "h3")]);
+        remote.threads.push(thread_entry("thread:1", "2026-01-01T00:00:00Z", "hashA"));
+        local.blobs = Some(vec!["b1".into()]);
+        remote.blobs = Some(vec!["b1".into(), "b2".into(), "b3".into()]);
+
+        let p = preview(&local, &remote);
+        assert_eq!((p.docs_to_send, p.docs_to_receive, p.conflicts), (1, 1, 0));
+        assert_eq!((p.rows_to_send, p.rows_to_receive), (0, 1));
+        assert_eq!((p.blobs_to_send, p.blobs_to_receive), (0, 2));
+        assert_eq!(p.bytes_to_send, 4_000);
+        assert_eq!(p.bytes_to_receive, 10_000 + ROW_ESTIMATE_BYTES);
+        assert!(!preview(&local, &local).has_work());
+    }
 }
@@ -0,0 +1,115 @@
+//! Per-device selective sync.
+//!
+//! Each paired-device record carries a [`SyncPolicy`] describing what THIS
+//! device sends to that peer. The default sends everything; a phone-class
+//! device can be limited to a few threads, kept free of external (imported)
+//! documents, capped by document size, or denied particular documents. The policy is applied when the
+//! manifest for that peer is built — so the peer never learns the filtered
+//! documents exist — and again when serving commits and thread rows, so a
+//! peer can't fetch around it by asking for ids directly.
+//!
+//! Filtering only narrows what we send. Documents the peer creates on its
+//! side still sync back in full.
+
+use serde::{Deserialize, Serialize};
+use sovereign_db::schema::Document;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncPolicy {
+    /// Thread ids to send. Empty = every thread.
+    #[serde(default)]
+    pub include_threads: Vec<String>,
+    /// Thread ids never sent. Wins over `include_threads`.
+    #[serde(default)]
+    pub exclude_threads: Vec<String>,
+    /// Skip documents that weren't authored here (`is_owned == false`).
+    #[serde(default)]
+    pub exclude_external: bool,
+    /// Skip documents whose content is larger than this many bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_document_bytes: Option<u64>,
+    /// Document ids never sent, whatever their thread.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_documents: Vec<String>,
+}
+
+impl SyncPolicy {
+    /// True when the policy filters nothing.
+    pub fn is_unrestricted(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether a thread (and its row) may be sent.
+    pub fn allows_thread(&self, thread_id: &str) -> bool {
+        if self.exclude_threads.iter().any(|t| t == thread_id) {
+            return false;
+        }
+        self.include_threads.is_empty() || self.include_threads.iter().any(|t| t == thread_id)
+    }
+
+    /// Whether a document may be sent.
+    pub fn allows_document(&self, doc: &Document) -> bool {
+        if self.exclude_external && !doc.is_owned {
+            return false;
+        }
+        if self
+            .max_document_bytes
+            .is_some_and(|max| doc.content.len() as u64 > max)
+        {
+            return false;
+        }
+        if doc
+            .id_string()
+            .is_some_and(|id| self.exclude_documents.contains(&id))
+        {
+            return false;
+        }
+        self.allows_thread(&doc.thread_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_db::schema::Thing;
+
+    fn doc(id: &str, thread_id: &str, is_owned: bool, content_len: usize) -> Document {
+        let mut doc = Document::new("Doc".into(), thread_id.into(), is_owned);
+        doc.id = Some(Thing::from(("document", id)));
+        doc.content = "x".repeat(content_len);
+        doc
+    }
+
+    #[test]
+    fn default_allows_everything() {
+        let p = SyncPolicy::default();
+        assert!(p.is_unrestricted());
+        assert!(p.allows_thread("thread:a"));
+        assert!(p.allows_document(&doc("a", "thread:a", false, 10_000_000)));
+    }
+
+    #[test]
+    fn include_exclude_external_and_size() {
+        let p = SyncPolicy {
+            include_threads: vec!["thread:a".into(), "thread:b".into()],
+            exclude_threads: vec!["thread:b".into()],
+            exclude_external: true,
+            max_document_bytes: Some(1024),
+            exclude_documents: vec!["document:secret".into()],
+        };
+        assert!(!p.is_unrestricted());
+        assert!(p.allows_thread("thread:a"));
+        assert!(!p.allows_thread("thread:b"));
+        assert!(!p.allows_thread("thread:c"));
+        assert!(p.allows_document(&doc("a", "thread:a", true, 1024)));
+        assert!(!p.allows_document(&doc("a", "thread:a", true, 1025)));
+        assert!(!p.allows_document(&doc("a", "thread:a", false, 10)));
+        assert!(!p.allows_document(&doc("secret", "thread:a", true, 10)));
+    }
+
+    #[test]
+    fn missing_fields_deserialize_to_default() {
+        let p: SyncPolicy = serde_json::from_str("{}").unwrap();
+        assert_eq!(p, SyncPolicy::default());
+    }
+}
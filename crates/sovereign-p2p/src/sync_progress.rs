@@ -0,0 +1,231 @@
+//! Sync progress and per-device sync history.
+//!
+//! The node keeps a [`SyncProgress`] per active session: the manifest
+//! diff (what each side is missing), commits and sealed bytes moved each
+//! way, and how many of the session's requests have resolved. It emits a
+//! snapshot as `P2pEvent::SyncProgress` after every resolved request and
+//! the final one with `SyncCompleted`, which the app appends to the
+//! [`SyncHistoryLog`].
+//!
+//! A [`SyncPreview`] is the same diff computed ahead of time, so the
+//! user can see what a large sync would move before starting it.
+//!
+//! The ETA is a rough extrapolation from requests resolved so far. A
+//! session's request count grows as the manifest diff fans out into
+//! follow-ups and media is pulled round by round, so early estimates run
+//! short.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{P2pError, P2pResult};
+
+/// Records kept in the history log; older ones are dropped on the next
+/// write past twice this many.
+pub const MAX_HISTORY_RECORDS: usize = 500;
+
+/// A snapshot of one sync session with a peer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncProgress {
+    /// Documents the peer has that we lack or are behind on.
+    pub docs_to_pull: u32,
+    /// Documents we have that the peer lacks or is behind on.
+    pub docs_to_push: u32,
+    /// Table rows to fetch from the peer.
+    pub rows_to_pull: u32,
+    /// Table rows to send to the peer.
+    pub rows_to_push: u32,
+    pub commits_received: u32,
+    pub commits_sent: u32,
+    /// Sealed content received (commits, rows, media chunks, manifest).
+    pub bytes_received: u64,
+    /// Sealed content sent.
+    pub bytes_sent: u64,
+    /// Session requests resolved (answered or failed).
+    pub requests_done: u32,
+    /// Session requests issued so far.
+    pub requests_total: u32,
+    pub elapsed_ms: u64,
+    /// Estimated time left; None until a request has resolved.
+    pub eta_ms: Option<u64>,
+}
+
+impl SyncProgress {
+    /// Stamp the elapsed time and extrapolate the ETA from the share of
+    /// requests resolved.
+    pub fn update_timing(&mut self, elapsed: Duration) {
+        self.elapsed_ms = elapsed.as_millis() as u64;
+        self.eta_ms = match (self.requests_done, self.requests_total) {
+            (0, _) => None,
+            (done, total) if done >= total => Some(0),
+            (done, total) => {
+                Some(self.elapsed_ms * u64::from(total - done) / u64::from(done))
+            }
+        };
+    }
+}
+
+/// What a sync with a peer would move, computed from both manifests
+/// without transferring anything (`P2pCommand::PreviewSync`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncPreview {
+    pub docs_to_send: u32,
+    pub docs_to_receive: u32,
+    /// Documents edited on both sides at the same time; one side's
+    /// version wins, and the merge shows up for review.
+    pub conflicts: u32,
+    pub rows_to_send: u32,
+    pub rows_to_receive: u32,
+    pub blobs_to_send: u32,
+    pub blobs_to_receive: u32,
+    /// Estimated plaintext bytes; media is not included.
+    pub bytes_to_send: u64,
+    pub bytes_to_receive: u64,
+}
+
+impl SyncPreview {
+    /// Whether a sync would change anything on either side.
+    pub fn has_work(&self) -> bool {
+        self.docs_to_send + self.docs_to_receive + self.rows_to_send + self.rows_to_receive > 0
+            || self.blobs_to_send + self.blobs_to_receive > 0
+    }
+}
+
+/// One finished sync session, as kept in the history log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncHistoryRecord {
+    pub at: DateTime<Utc>,
+    pub peer_id: String,
+    /// Documents and rows applied on either side.
+    pub items_synced: u32,
+    #[serde(flatten)]
+    pub progress: SyncProgress,
+}
+
+/// Append-only JSON-lines log of finished sync sessions, for the
+/// per-device history in the Devices panel. Records carry counts and
+/// sizes only, never content.
+#[derive(Debug, Clone)]
+pub struct SyncHistoryLog {
+    path: PathBuf,
+}
+
+impl SyncHistoryLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn record(&self, record: &SyncHistoryRecord) -> P2pResult<()> {
+        let mut line =
+            serde_json::to_vec(record).map_err(|e| P2pError::SyncError(e.to_string()))?;
+        line.push(b'\n');
+        let mut options = std::fs::OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options
+            .open(&self.path)
+            .and_then(|mut f| f.write_all(&line))
+            .map_err(|e| P2pError::SyncError(format!("write sync history: {e}")))?;
+
+        let entries = self.entries();
+        if entries.len() > 2 * MAX_HISTORY_RECORDS {
+            self.rewrite(&entries[entries.len() - MAX_HISTORY_RECORDS..])?;
+        }
+        Ok(())
+    }
+
+    /// All records, oldest first. Unreadable lines are skipped.
+    pub fn entries(&self) -> Vec<SyncHistoryRecord> {
+        std::fs::read_to_string(&self.path)
+            .map(|text| {
+                text.lines()
+                    .filter_map(|line| serde_json::from_str(line).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The latest `limit` sessions with one peer, newest first.
+    pub fn for_peer(&self, peer_id: &str, limit: usize) -> Vec<SyncHistoryRecord> {
+        self.entries()
+            .into_iter()
+            .rev()
+            .filter(|r| r.peer_id == peer_id)
+            .take(limit)
+            .collect()
+    }
+
+    fn rewrite(&self, keep: &[SyncHistoryRecord]) -> P2pResult<()> {
+        let mut out = Vec::new();
+        for record in keep {
+            serde_json::to_writer(&mut out, record)
+                .map_err(|e| P2pError::SyncError(e.to_string()))?;
+            out.push(b'\n');
+        }
+        sovereign_crypto::fs_private::write_private(&self.path, out)
+            .map_err(|e| P2pError::SyncError(format!("compact sync history: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eta_extrapolates_from_resolved_requests() {
+        let mut p = SyncProgress {
+            requests_total: 4,
+            ..Default::default()
+        };
+        p.update_timing(Duration::from_millis(500));
+        assert_eq!(p.eta_ms, None);
+
+        p.requests_done = 1;
+        p.update_timing(Duration::from_millis(500));
+        assert_eq!(p.elapsed_ms, 500);
+        assert_eq!(p.eta_ms, Some(1500));
+
+        p.requests_done = 4;
+        p.update_timing(Duration::from_millis(2000));
+        assert_eq!(p.eta_ms, Some(0));
+    }
+
+    #[test]
+    fn history_filters_by_peer_newest_first() {
+        let dir = std::env::temp_dir().join("sovereign-p2p-test-sync-history");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let log = SyncHistoryLog::new(dir.join("sync_history.log"));
+        assert!(log.entries().is_empty());
+
+        for (peer, items) in [("peer-a", 1), ("peer-b", 2), ("peer-a", 3)] {
+            log.record(&SyncHistoryRecord {
+                at: Utc::now(),
+                peer_id: peer.into(),
+                items_synced: items,
+                progress: SyncProgress {
+                    bytes_received: 1024,
+                    ..Default::default()
+                },
+            })
+            .unwrap();
+        }
+
+        let a = log.for_peer("peer-a", 10);
+        assert_eq!(a.len(), 2);
+        assert_eq!(a[0].items_synced, 3);
+        assert_eq!(a[1].progress.bytes_received, 1024);
+        assert_eq!(log.for_peer("peer-a", 1).len(), 1);
+        assert!(log.for_peer("peer-c", 10).is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
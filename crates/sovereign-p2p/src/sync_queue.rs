@@ -0,0 +1,335 @@
+//! Offline sync queue: work a session still owed a peer when it ended
+//! early, kept across restarts so the next session resumes it.
+//!
+//! When a peer drops mid-sync, libp2p fails every request still in flight
+//! to it. The node collects what those requests were carrying — commits
+//! asked for, documents and rows being pushed, rows being fetched — as a
+//! [`PendingSync`] and queues it here when the session ends. The next
+//! session with that peer (started by discovery, the periodic poll, or the
+//! peer reconnecting) re-sends the queued work straight away, alongside
+//! the usual manifest exchange; the manifest diff skips what the resumed
+//! requests already cover.
+//!
+//! Re-fetching can deliver commits that did land before the drop, so the
+//! queue also remembers the ids of recently applied commits and
+//! [`crate::SyncService::apply_commits`] skips them.
+//!
+//! Media needs none of this: `.part` files already resume from their
+//! length (see [`crate::blob_store`]).
+//!
+//! Persistence is a single JSON file written through
+//! [`sovereign_crypto::fs_private::write_private`]. It holds ids only —
+//! no content — so, like the version store, it is not encrypted. A `None`
+//! path makes the queue ephemeral (tests).
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{P2pError, P2pResult};
+use crate::protocol::sync::SyncTable;
+use crate::protocol::SovereignRequest;
+
+/// Applied commit ids remembered for de-duplication; the oldest are
+/// forgotten first.
+pub const MAX_APPLIED_COMMITS: usize = 10_000;
+
+/// A document to push again: everything after `since`, the peer's head
+/// when the interrupted session diffed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingPush {
+    pub doc_id: String,
+    pub since: Option<String>,
+}
+
+/// Row ids of one table still to fetch or send.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingRows {
+    pub table: SyncTable,
+    pub ids: Vec<String>,
+}
+
+/// What an interrupted session still owed one peer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingSync {
+    /// Commit ids asked for with `GetCommits` and never received.
+    #[serde(default)]
+    pub pull_commits: Vec<String>,
+    #[serde(default)]
+    pub push_docs: Vec<PendingPush>,
+    #[serde(default)]
+    pub pull_rows: Vec<PendingRows>,
+    #[serde(default)]
+    pub push_rows: Vec<PendingRows>,
+}
+
+impl PendingSync {
+    /// The work a session request carries; empty for requests that
+    /// aren't worth resuming (the manifest is fetched fresh anyway, and
+    /// media resumes on its own).
+    pub fn from_request(request: &SovereignRequest) -> Self {
+        let mut work = Self::default();
+        match request {
+            SovereignRequest::GetCommits { commit_ids, .. } => {
+                work.pull_commits = commit_ids.clone();
+            }
+            SovereignRequest::PushCommits { commits } => {
+                // Per document, the commit whose parent isn't in the push
+                // is the first one; its parent is the peer's head.
+                let ids: HashSet<&str> = commits.iter().map(|c| c.commit_id.as_str()).collect();
+                for c in commits {
+                    let first = c.parent_commit.as_deref().is_none_or(|p| !ids.contains(p));
+                    if first && !work.push_docs.iter().any(|p| p.doc_id == c.document_id) {
+                        work.push_docs.push(PendingPush {
+                            doc_id: c.document_id.clone(),
+                            since: c.parent_commit.clone(),
+                        });
+                    }
+                }
+            }
+            SovereignRequest::GetRows { table, ids, .. } => work.pull_rows.push(PendingRows {
+                table: *table,
+                ids: ids.clone(),
+            }),
+            SovereignRequest::PushRows { table, rows } => work.push_rows.push(PendingRows {
+                table: *table,
+                ids: rows.iter().map(|r| r.id.clone()).collect(),
+            }),
+            _ => {}
+        }
+        work
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pull_commits.is_empty()
+            && self.push_docs.is_empty()
+            && self.pull_rows.is_empty()
+            && self.push_rows.is_empty()
+    }
+
+    /// Fold `other` in, without duplicates.
+    pub fn merge(&mut self, other: PendingSync) {
+        for id in other.pull_commits {
+            if !self.pull_commits.contains(&id) {
+                self.pull_commits.push(id);
+            }
+        }
+        for push in other.push_docs {
+            // An older `since` covers a newer one for the same document.
+            if !self.push_docs.iter().any(|p| p.doc_id == push.doc_id) {
+                self.push_docs.push(push);
+            }
+        }
+        merge_rows(&mut self.pull_rows, other.pull_rows);
+        merge_rows(&mut self.push_rows, other.push_rows);
+    }
+}
+
+fn merge_rows(into: &mut Vec<PendingRows>, from: Vec<PendingRows>) {
+    for rows in from {
+        match into.iter_mut().find(|r| r.table == rows.table) {
+            Some(existing) => {
+                for id in rows.ids {
+                    if !existing.ids.contains(&id) {
+                        existing.ids.push(id);
+                    }
+                }
+            }
+            None => into.push(rows),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct QueueData {
+    /// `peer_id → work owed to it`.
+    pending: HashMap<String, PendingSync>,
+    /// Recently applied commit ids, oldest first.
+    applied: VecDeque<String>,
+}
+
+/// Persistent per-peer queue of interrupted sync work.
+#[derive(Debug)]
+pub struct SyncQueue {
+    data: QueueData,
+    /// Mirrors `data.applied` for lookups.
+    applied: HashSet<String>,
+    path: Option<PathBuf>,
+}
+
+impl SyncQueue {
+    /// An ephemeral queue that never touches disk.
+    pub fn ephemeral() -> Self {
+        Self {
+            data: QueueData::default(),
+            applied: HashSet::new(),
+            path: None,
+        }
+    }
+
+    /// Load from `path`, or start empty if the file is missing/invalid.
+    /// Losing the queue only costs a full re-diff on the next sync.
+    pub fn load_or_default(path: PathBuf) -> Self {
+        let data: QueueData = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self {
+            applied: data.applied.iter().cloned().collect(),
+            data,
+            path: Some(path),
+        }
+    }
+
+    /// Persist to disk (atomic, owner-only). No-op for ephemeral queues.
+    pub fn save(&self) -> P2pResult<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let json = serde_json::to_string(&self.data)
+            .map_err(|e| P2pError::SyncError(format!("sync queue serialize: {e}")))?;
+        sovereign_crypto::fs_private::write_private(path, json)
+            .map_err(|e| P2pError::SyncError(format!("sync queue write: {e}")))
+    }
+
+    /// Queue `work` for `peer_id`, on top of anything already queued.
+    pub fn enqueue(&mut self, peer_id: &str, work: PendingSync) {
+        if work.is_empty() {
+            return;
+        }
+        self.data
+            .pending
+            .entry(peer_id.to_string())
+            .or_default()
+            .merge(work);
+    }
+
+    /// Take the work queued for `peer_id`, leaving nothing behind.
+    pub fn take(&mut self, peer_id: &str) -> Option<PendingSync> {
+        self.data.pending.remove(peer_id)
+    }
+
+    pub fn has_pending(&self, peer_id: &str) -> bool {
+        self.data.pending.contains_key(peer_id)
+    }
+
+    pub fn is_applied(&self, commit_id: &str) -> bool {
+        self.applied.contains(commit_id)
+    }
+
+    /// Remember `commit_id` as applied, forgetting the oldest past
+    /// [`MAX_APPLIED_COMMITS`].
+    pub fn mark_applied(&mut self, commit_id: &str) {
+        if !self.applied.insert(commit_id.to_string()) {
+            return;
+        }
+        self.data.applied.push_back(commit_id.to_string());
+        while self.data.applied.len() > MAX_APPLIED_COMMITS {
+            if let Some(old) = self.data.applied.pop_front() {
+                self.applied.remove(&old);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::sync::EncryptedCommit;
+
+    fn commit(id: &str, doc: &str, parent: Option<&str>) -> EncryptedCommit {
+        EncryptedCommit {
+            commit_id: id.into(),
+            document_id: doc.into(),
+            parent_commit: parent.map(Into::into),
+            encrypted_snapshot: String::new(),
+            nonce: String::new(),
+            message: String::new(),
+            timestamp: "2026-01-01T00:00:00Z".into(),
+            version_device: String::new(),
+            signature: String::new(),
+        }
+    }
+
+    #[test]
+    fn push_resumes_from_the_peer_head() {
+        let request = SovereignRequest::PushCommits {
+            commits: vec![
+                commit("c2", "doc:a", Some("c1")),
+                commit("c3", "doc:a", Some("c2")),
+                commit("n1", "doc:b", None),
+            ],
+        };
+        let work = PendingSync::from_request(&request);
+        assert_eq!(
+            work.push_docs,
+            vec![
+                PendingPush {
+                    doc_id: "doc:a".into(),
+                    since: Some("c1".into())
+                },
+                PendingPush {
+                    doc_id: "doc:b".into(),
+                    since: None
+                },
+            ]
+        );
+        assert!(PendingSync::from_request(&SovereignRequest::GetManifest).is_empty());
+    }
+
+    #[test]
+    fn queue_merges_and_survives_reload() {
+        let dir = std::env::temp_dir().join("sovereign-p2p-test-sync-queue");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sync_queue.json");
+
+        let mut queue = SyncQueue::load_or_default(path.clone());
+        queue.enqueue(
+            "peer-a",
+            PendingSync::from_request(&SovereignRequest::GetCommits {
+                commit_ids: vec!["c1".into()],
+                codecs: vec![],
+            }),
+        );
+        queue.enqueue(
+            "peer-a",
+            PendingSync::from_request(&SovereignRequest::GetRows {
+                table: SyncTable::Thread,
+                ids: vec!["thread:1".into()],
+                codecs: vec![],
+            }),
+        );
+        queue.enqueue(
+            "peer-a",
+            PendingSync::from_request(&SovereignRequest::GetCommits {
+                commit_ids: vec!["c1".into(), "c2".into()],
+                codecs: vec![],
+            }),
+        );
+        queue.enqueue("peer-b", PendingSync::default());
+        queue.mark_applied("c0");
+        queue.save().unwrap();
+
+        let mut reloaded = SyncQueue::load_or_default(path);
+        assert!(!reloaded.has_pending("peer-b"), "empty work isn't queued");
+        assert!(reloaded.is_applied("c0"));
+        let work = reloaded.take("peer-a").unwrap();
+        assert_eq!(work.pull_commits, vec!["c1".to_string(), "c2".to_string()]);
+        assert_eq!(work.pull_rows.len(), 1);
+        assert!(reloaded.take("peer-a").is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn applied_ids_are_bounded() {
+        let mut queue = SyncQueue::ephemeral();
+        for i in 0..=MAX_APPLIED_COMMITS {
+            queue.mark_applied(&format!("c{i}"));
+        }
+        assert!(!queue.is_applied("c0"), "oldest id is forgotten");
+        assert!(queue.is_applied(&format!("c{MAX_APPLIED_COMMITS}")));
+    }
+}
@@ -2,21 +2,33 @@ use std::sync::{Arc, Mutex};
 
 use libp2p::identity::{Keypair, PublicKey};
 use libp2p::PeerId;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use sovereign_db::schema::{
-    Contact, Conversation, Document, Entity, Message, Milestone, PiiRecord, RelatedTo,
-    ShareRecord, SuggestedLink, Thread,
+    ActionLevel, Contact, Conversation, Document, Entity, Message, Milestone, PiiRecord,
+    Principal, RelatedTo, Sensitivity, ShareRecord, SuggestedLink, Thread,
 };
 #[cfg(test)]
 use sovereign_db::schema::Commit;
 use sovereign_db::GraphDB;
+use tracing::instrument;
 
 use crate::error::{P2pError, P2pResult};
 use crate::protocol::manifest::{
     DocumentManifestEntry, EntityManifestEntry, PiiRecordManifestEntry, RowManifestEntry,
     ShareRecordManifestEntry, SyncManifest, ThreadManifestEntry,
 };
-use crate::protocol::sync::{EncryptedCommit, EncryptedRow, SyncTable};
+use crate::protocol::sync::{
+    self as wire, BlobWant, Compression, EncryptedBlobChunk, EncryptedCommit, EncryptedRow,
+    SyncTable,
+};
+use crate::protocol::viewer::{
+    self, EncryptedViewerSnapshot, ViewerDocument, ViewerSnapshot, ViewerThread,
+};
+use crate::blob_store::{self, BlobRef, BlobStore};
+use crate::crdt::{self, CrdtPayload, CrdtStore, MergeConflict};
+use crate::sync_policy::SyncPolicy;
+use crate::sync_queue::{PendingSync, SyncQueue};
 use crate::version_store::{RowVersion, VersionStore};
 
 /// Maximum tolerated clock skew into the future for a remote row's LWW
@@ -26,6 +38,10 @@ use crate::version_store::{RowVersion, VersionStore};
 /// own clock. The deep fix (signed monotonic counters) is deferred.
 const MAX_FUTURE_SKEW: chrono::Duration = chrono::Duration::hours(24);
 
+/// Upper bound on blob bytes carried by one `Blobs` / `PushBlobs` message,
+/// so a large video can't monopolize the connection.
+const MAX_BLOB_MESSAGE_BYTES: u64 = 4 * blob_store::CHUNK_BYTES;
+
 /// Middleware between the P2P networking layer and the database.
 ///
 /// Owns an `Arc<dyn GraphDB>` and exposes async methods for building
@@ -54,6 +70,127 @@ pub struct SyncService {
     /// [`Self::set_pair_keys`]. A peer with no entry fails CLOSED — no
     /// data can be sealed for or unsealed from it.
     pair_keys: std::sync::RwLock<std::collections::HashMap<String, [u8; 32]>>,
+    /// `peer_id → policy` for peers that get a filtered view of this
+    /// device (see [`crate::sync_policy`]). Peers without an entry get
+    /// everything.
+    policies: std::sync::RwLock<std::collections::HashMap<String, SyncPolicy>>,
+    /// `peer_id → codec` negotiated from what the peer advertised (see
+    /// [`Compression`]). Peers without an entry get uncompressed bodies.
+    compression: std::sync::RwLock<std::collections::HashMap<String, Compression>>,
+    /// Per-document CRDT histories of the body (see [`crate::crdt`]).
+    /// std Mutex — never held across an await.
+    crdt: Mutex<CrdtStore>,
+    /// This device's yrs client id, derived from `device_id`.
+    crdt_client: u64,
+    /// Merges that touched the same lines on both sides, waiting for the
+    /// node to surface them ([`Self::take_merge_conflicts`]).
+    merge_conflicts: Mutex<Vec<MergeConflict>>,
+    /// `peer → (doc_id, body)` sent to that peer and not yet known to have
+    /// arrived. Each becomes the merge base on [`Self::delivered`].
+    sent_bases: Mutex<std::collections::HashMap<PeerId, Vec<(String, String)>>>,
+    /// Peers whose document states have carried a valid head-commit
+    /// signature; one arriving without it afterwards is flagged.
+    signing_peers: Mutex<std::collections::HashSet<PeerId>>,
+    /// Media referenced by documents (see [`crate::blob_store`]).
+    blobs: BlobStore,
+    /// Interrupted sync work and recently applied commit ids (see
+    /// [`crate::sync_queue`]). std Mutex — never held across an await.
+    queue: Mutex<SyncQueue>,
+}
+
+/// What a document envelope carries: the snapshot plus the body's CRDT
+/// history. Flattened, so peers without CRDT support still read the
+/// snapshot and ignore the rest.
+#[derive(Serialize, Deserialize)]
+struct SyncedSnapshot {
+    #[serde(flatten)]
+    snapshot: sovereign_db::schema::DocumentSnapshot,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    crdt: Option<CrdtPayload>,
+    /// The media the content points at; bytes follow via `GetBlobs`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    blobs: Vec<BlobRef>,
+    /// The sender's device signature on its head commit, when it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    head: Option<SignedHead>,
+}
+
+/// A head commit's device signature (`sovereign_crypto::device_signing`)
+/// and the fields it covers, so the receiver can check which device made
+/// the history a document state comes from.
+#[derive(Serialize, Deserialize)]
+struct SignedHead {
+    parent_commit: Option<String>,
+    message: String,
+    public_key: String,
+    signature: String,
+    /// The committed snapshot, when the state sent has moved on from it
+    /// (edits not committed yet). `None` = the state sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    snapshot: Option<sovereign_db::schema::DocumentSnapshot>,
+}
+
+impl SignedHead {
+    /// `None` for a commit made before device signing.
+    fn of(commit: &Commit, sent: &sovereign_db::schema::DocumentSnapshot) -> Option<Self> {
+        let moved_on =
+            commit.snapshot.title != sent.title || commit.snapshot.content != sent.content;
+        Some(Self {
+            parent_commit: commit.parent_commit.clone(),
+            message: commit.message.clone(),
+            public_key: commit.device_public_key.clone()?,
+            signature: commit.device_signature.clone()?,
+            snapshot: moved_on.then(|| commit.snapshot.clone()),
+        })
+    }
+
+    /// Whether this is `sender`'s valid signature on a commit of
+    /// `document_id`. The key must be the one behind the sender's PeerId,
+    /// i.e. the paired device's.
+    fn verifies(
+        &self,
+        document_id: &str,
+        sent: &sovereign_db::schema::DocumentSnapshot,
+        sender: &str,
+    ) -> bool {
+        let signer = crate::identity::peer_id_from_public_key_b64(&self.public_key);
+        if signer.as_deref() != Some(sender) {
+            return false;
+        }
+        let commit = Commit {
+            id: None,
+            document_id: document_id.to_string(),
+            parent_commit: self.parent_commit.clone(),
+            message: self.message.clone(),
+            timestamp: chrono::Utc::now(),
+            snapshot: self.snapshot.clone().unwrap_or_else(|| sent.clone()),
+            signature: None,
+            device_public_key: None,
+            device_signature: None,
+        };
+        sovereign_crypto::device_signing::verify_commit_signature(
+            &self.public_key,
+            &commit.signed_bytes(),
+            &self.signature,
+        )
+    }
+}
+
+/// A document's pin as it travels in a `SyncTable::Favorite` row, whose
+/// id is the document id.
+#[derive(Serialize, Deserialize)]
+struct FavoriteRow {
+    favorite: bool,
+    favorite_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A document's sensitivity level as it travels in a
+/// `SyncTable::Sensitivity` row, whose id is the document id. Applying one
+/// only ever raises the level: lowering it is done on each device.
+#[derive(Serialize, Deserialize)]
+struct SensitivityRow {
+    sensitivity: Sensitivity,
+    sensitivity_at: chrono::DateTime<chrono::Utc>,
 }
 
 impl SyncService {
@@ -65,15 +202,95 @@ impl SyncService {
         versions: VersionStore,
     ) -> Self {
         Self {
+            crdt_client: crdt::client_id(&device_id),
             db,
             device_id,
             transport_key,
             keypair,
             versions: Mutex::new(versions),
             pair_keys: std::sync::RwLock::new(std::collections::HashMap::new()),
+            policies: std::sync::RwLock::new(std::collections::HashMap::new()),
+            compression: std::sync::RwLock::new(std::collections::HashMap::new()),
+            crdt: Mutex::new(CrdtStore::ephemeral()),
+            merge_conflicts: Mutex::new(Vec::new()),
+            sent_bases: Mutex::new(std::collections::HashMap::new()),
+            signing_peers: Mutex::new(std::collections::HashSet::new()),
+            blobs: BlobStore::ephemeral(),
+            queue: Mutex::new(SyncQueue::ephemeral()),
         }
     }
 
+    /// Persist CRDT histories in `store` instead of keeping them in memory.
+    pub fn with_crdt_store(self, store: CrdtStore) -> Self {
+        *self.crdt.lock().expect("crdt store lock poisoned") = store;
+        self
+    }
+
+    /// Keep received media in `store`. Without one, blobs are still served
+    /// from local files but nothing is accepted from peers.
+    pub fn with_blob_store(mut self, store: BlobStore) -> Self {
+        self.blobs = store;
+        self
+    }
+
+    /// Persist interrupted sync work in `queue` so it survives a restart.
+    pub fn with_sync_queue(self, queue: SyncQueue) -> Self {
+        *self.queue.lock().expect("sync queue lock poisoned") = queue;
+        self
+    }
+
+    /// Queue `work` left over from a session with `peer_id` and persist it.
+    pub fn enqueue_pending(&self, peer_id: &str, work: PendingSync) -> P2pResult<()> {
+        let mut queue = self.queue.lock().expect("sync queue lock poisoned");
+        queue.enqueue(peer_id, work);
+        queue.save()
+    }
+
+    /// Take the work queued for `peer_id`, if any.
+    pub fn take_pending(&self, peer_id: &str) -> P2pResult<Option<PendingSync>> {
+        let mut queue = self.queue.lock().expect("sync queue lock poisoned");
+        let work = queue.take(peer_id);
+        if work.is_some() {
+            queue.save()?;
+        }
+        Ok(work)
+    }
+
+    pub fn has_pending(&self, peer_id: &str) -> bool {
+        self.queue.lock().expect("sync queue lock poisoned").has_pending(peer_id)
+    }
+
+    /// Forget the work queued for a revoked peer.
+    pub fn drop_pending(&self, peer_id: &str) -> P2pResult<()> {
+        self.take_pending(peer_id).map(|_| ())
+    }
+
+    /// Drain the merge conflicts found since the last call.
+    pub fn take_merge_conflicts(&self) -> Vec<MergeConflict> {
+        std::mem::take(&mut *self.merge_conflicts.lock().expect("merge conflict lock poisoned"))
+    }
+
+    /// `peer` has the document states last sent to it: their bodies become
+    /// the merge base, and the histories are written once for the batch.
+    pub fn delivered(&self, peer: &PeerId) -> P2pResult<()> {
+        let sent = self
+            .sent_bases
+            .lock()
+            .expect("sent bases lock poisoned")
+            .remove(peer)
+            .unwrap_or_default();
+        let mut store = self.crdt.lock().expect("crdt store lock poisoned");
+        for (doc_id, body) in sent {
+            store.set_base(&doc_id, body);
+        }
+        store.save()
+    }
+
+    /// The states sent to `peer` didn't arrive; the base stays where it was.
+    pub fn undelivered(&self, peer: &PeerId) {
+        self.sent_bases.lock().expect("sent bases lock poisoned").remove(peer);
+    }
+
     /// The per-account sync transport key (P2P-002). Used by the node to
     /// encrypt/decrypt the manifest envelope.
     pub fn transport_key(&self) -> &[u8; 32] {
@@ -96,6 +313,41 @@ impl SyncService {
             .insert(peer_id, key);
     }
 
+    /// Replace the per-device sync policies. Called at startup and
+    /// whenever the user edits a device's policy.
+    pub fn set_sync_policies(&self, policies: std::collections::HashMap<String, SyncPolicy>) {
+        *self.policies.write().expect("sync policy lock poisoned") = policies;
+    }
+
+    /// What may be sent to `peer`.
+    fn policy_for(&self, peer: &PeerId) -> SyncPolicy {
+        self.policies
+            .read()
+            .expect("sync policy lock poisoned")
+            .get(&peer.to_string())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Record the codecs `peer` advertised in its manifest or a pull
+    /// request; commits and rows sealed for it are compressed from then on
+    /// if it can open them.
+    pub fn set_peer_codecs(&self, peer: &PeerId, offered: &[Compression]) {
+        self.compression
+            .write()
+            .expect("compression lock poisoned")
+            .insert(peer.to_string(), Compression::negotiate(offered));
+    }
+
+    fn compression_for(&self, peer: &PeerId) -> Compression {
+        self.compression
+            .read()
+            .expect("compression lock poisoned")
+            .get(&peer.to_string())
+            .copied()
+            .unwrap_or_default()
+    }
+
     /// The sealing key for envelopes exchanged with `peer`. Fails closed:
     /// without a pair key no row or commit can leave for — or be accepted
     /// from — that peer.
@@ -116,6 +368,100 @@ impl SyncService {
     /// Documents track via commit chain; threads/entities/pii_records/
     /// share_records use the row-level last-writer-wins protocol.
     pub async fn build_manifest(&self) -> P2pResult<SyncManifest> {
+        self.build_filtered_manifest(&SyncPolicy::default()).await
+    }
+
+    /// The manifest served to `peer`: [`Self::build_manifest`] narrowed by
+    /// that peer's sync policy, so filtered documents and threads never
+    /// show up on its side.
+    #[instrument(name = "sync.build_manifest_for", level = "debug", skip_all)]
+    pub async fn build_manifest_for(&self, peer: &PeerId) -> P2pResult<SyncManifest> {
+        self.build_filtered_manifest(&self.policy_for(peer)).await
+    }
+
+    /// A page of the read-only companion snapshot for `peer`: live
+    /// documents and threads its sync policy allows, sealed under its pair
+    /// key. Documents follow `after` in id order, up to
+    /// [`viewer::MAX_PAGE_BYTES`]; threads come with the first page.
+    pub async fn viewer_snapshot_for(
+        &self,
+        peer: &PeerId,
+        after: Option<&str>,
+    ) -> P2pResult<EncryptedViewerSnapshot> {
+        let key = self.pair_key_for(peer)?;
+        let policy = self.policy_for(peer);
+        let mut snapshot = ViewerSnapshot::new(self.device_id.clone());
+        let threads = if after.is_none() {
+            self.db
+                .list_threads()
+                .await
+                .map_err(|e| P2pError::SyncError(format!("failed to list threads: {e}")))?
+        } else {
+            Vec::new()
+        };
+        for t in &threads {
+            let Some(id) = t.id_string() else { continue };
+            if t.deleted_at.is_some() || !policy.allows_thread(&id) {
+                continue;
+            }
+            snapshot.threads.push(ViewerThread {
+                thread_id: id,
+                name: t.name.clone(),
+            });
+        }
+        let mut docs: Vec<_> = self
+            .db
+            .list_documents(None)
+            .await
+            .map_err(|e| P2pError::SyncError(format!("failed to list documents: {e}")))?
+            .into_iter()
+            .filter_map(|d| Some((d.id_string()?, d)))
+            .filter(|(id, _)| after.is_none_or(|a| id.as_str() > a))
+            .collect();
+        docs.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut used = 0;
+        let mut last = None;
+        for (doc_id, doc) in docs {
+            // The viewer shows content without asking for a password, so
+            // sealed documents never go into it.
+            if doc.deleted_at.is_some()
+                || doc.sensitivity == Sensitivity::Sealed
+                || !policy.allows_document(&doc)
+            {
+                continue;
+            }
+            let entry = ViewerDocument {
+                doc_id: doc_id.clone(),
+                title: doc.title,
+                content: doc.content,
+                thread_id: doc.thread_id,
+                modified_at: doc.modified_at.to_rfc3339(),
+            };
+            let size = serde_json::to_vec(&entry).map(|v| v.len()).unwrap_or(usize::MAX);
+            if size > viewer::MAX_PAGE_BYTES {
+                snapshot.too_large.push(entry.title);
+            } else if used + size > viewer::MAX_PAGE_BYTES {
+                snapshot.next_after = last;
+                break;
+            } else {
+                used += size;
+                snapshot.documents.push(entry);
+            }
+            last = Some(doc_id);
+        }
+        snapshot.seal(&key)
+    }
+
+    /// Open a companion snapshot `peer` served us.
+    pub fn open_viewer_snapshot(
+        &self,
+        peer: &PeerId,
+        sealed: &EncryptedViewerSnapshot,
+    ) -> P2pResult<ViewerSnapshot> {
+        ViewerSnapshot::open(sealed, &self.pair_key_for(peer)?)
+    }
+
+    async fn build_filtered_manifest(&self, policy: &SyncPolicy) -> P2pResult<SyncManifest> {
         let mut manifest = SyncManifest::new(self.device_id.clone());
 
         // --- Documents (commit-chain tracked) ---
@@ -129,6 +475,25 @@ impl SyncService {
                 Some(id) => id,
                 None => continue,
             };
+            if !policy.allows_document(&doc) {
+                continue;
+            }
+            if let Some(at) = doc.favorite_at {
+                manifest.favorites.push(RowManifestEntry {
+                    id: doc_id.clone(),
+                    modified_at: at.to_rfc3339(),
+                    content_hash: hash_favorite(doc.favorite),
+                    deleted_at: None,
+                });
+            }
+            if let Some(at) = doc.sensitivity_at {
+                manifest.sensitivities.push(RowManifestEntry {
+                    id: doc_id.clone(),
+                    modified_at: at.to_rfc3339(),
+                    content_hash: hash_sensitivity(doc.sensitivity),
+                    deleted_at: None,
+                });
+            }
             let commits = self
                 .db
                 .list_document_commits(&doc_id)
@@ -141,8 +506,10 @@ impl SyncService {
                 content_hash: content_hash(&doc.content),
                 modified_at: doc.modified_at.to_rfc3339(),
                 deleted_at: doc.deleted_at.clone(),
+                size: (doc.title.len() + doc.content.len()) as u64,
             });
         }
+        manifest.blobs = Some(self.shared_blob_hashes(&docs, policy));
 
         // --- Threads ---
         let threads = self
@@ -155,6 +522,9 @@ impl SyncService {
                 Some(id) => id,
                 None => continue,
             };
+            if !policy.allows_thread(&id) {
+                continue;
+            }
             manifest.threads.push(ThreadManifestEntry {
                 thread_id: id,
                 modified_at: t.modified_at.to_rfc3339(),
@@ -360,12 +730,15 @@ impl SyncService {
     /// under the pair key for that peer (P1.4 / P2P-005). The receiver
     /// re-encrypts under its own local keys. `commit_ids` are the remote
     /// manifest's per-document head commits; each maps back to its document.
+    #[instrument(name = "sync.get_commits", level = "debug", skip_all)]
     pub async fn get_commits(
         &self,
         commit_ids: &[String],
         peer: &PeerId,
     ) -> P2pResult<Vec<EncryptedCommit>> {
         let key = self.pair_key_for(peer)?;
+        let codec = self.compression_for(peer);
+        let policy = self.policy_for(peer);
         let mut result = Vec::with_capacity(commit_ids.len());
         for commit_id in commit_ids {
             let commit = self
@@ -373,9 +746,28 @@ impl SyncService {
                 .get_commit(commit_id)
                 .await
                 .map_err(|e| P2pError::SyncError(format!("failed to get commit {commit_id}: {e}")))?;
-            result.push(
-                self.seal_document_state(&commit.document_id, Some(commit_id), &key)
-                    .await?,
+            // A head commit whose device signature no longer verifies was
+            // tampered with locally; don't hand it on as our history.
+            if let (Some(pk), Some(sig)) = (&commit.device_public_key, &commit.device_signature) {
+                if !sovereign_crypto::device_signing::verify_commit_signature(
+                    pk,
+                    &commit.signed_bytes(),
+                    sig,
+                ) {
+                    tracing::warn!("not syncing commit {commit_id}: device signature invalid");
+                    continue;
+                }
+            }
+            result.extend(
+                self.seal_document_state(
+                    &commit.document_id,
+                    Some((commit_id, &commit)),
+                    peer,
+                    &key,
+                    codec,
+                    &policy,
+                )
+                .await?,
             );
         }
         Ok(result)
@@ -394,43 +786,91 @@ impl SyncService {
         peer: &PeerId,
     ) -> P2pResult<Vec<EncryptedCommit>> {
         let key = self.pair_key_for(peer)?;
-        Ok(vec![self.seal_document_state(doc_id, None, &key).await?])
+        let codec = self.compression_for(peer);
+        let policy = self.policy_for(peer);
+        Ok(self
+            .seal_document_state(doc_id, None, peer, &key, codec, &policy)
+            .await?
+            .into_iter()
+            .collect())
     }
 
     /// Read a document's current decrypted state and seal it as an
-    /// `EncryptedCommit` under the given pair key.
+    /// `EncryptedCommit` under the given pair key, compressed with `codec`
+    /// first, along with the head commit's device signature. `None` when
+    /// the recipient's sync policy filters the document out.
+    ///
+    /// The body only becomes the merge base once `peer` has it (see
+    /// [`Self::delivered`]); nothing is written to disk here.
     async fn seal_document_state(
         &self,
         doc_id: &str,
-        head_commit: Option<&str>,
+        head: Option<(&str, &Commit)>,
+        peer: &PeerId,
         key: &[u8; 32],
-    ) -> P2pResult<EncryptedCommit> {
+        codec: Compression,
+        policy: &SyncPolicy,
+    ) -> P2pResult<Option<EncryptedCommit>> {
         let doc = self
             .db
             .get_document(doc_id)
             .await
             .map_err(|e| P2pError::SyncError(format!("get_document {doc_id}: {e}")))?;
-        let snapshot = sovereign_db::schema::DocumentSnapshot {
+        if !policy.allows_document(&doc) {
+            tracing::debug!("not sending {doc_id}: filtered by the peer's sync policy");
+            return Ok(None);
+        }
+        let blobs = blob_store::media_paths(&doc.content)
+            .iter()
+            .filter_map(|path| self.blobs.describe(path))
+            .collect();
+        // Fold any local edits into the body's history and ship it along.
+        let crdt = {
+            let mut store = self.crdt.lock().expect("crdt store lock poisoned");
+            let history = store.local(doc_id, &crdt::body_of(&doc.content), self.crdt_client)?;
+            store.update_local(doc_id, &history);
+            self.sent_bases
+                .lock()
+                .expect("sent bases lock poisoned")
+                .entry(*peer)
+                .or_default()
+                .push((doc_id.to_string(), history.text()));
+            history.payload()
+        };
+        let state = sovereign_db::schema::DocumentSnapshot {
             document_id: doc_id.to_string(),
             title: doc.title,
             content: doc.content,
         };
+        let snapshot = SyncedSnapshot {
+            head: head.and_then(|(_, commit)| SignedHead::of(commit, &state)),
+            snapshot: state,
+            crdt: Some(crdt),
+            blobs,
+        };
         let mut commit = seal_snapshot(
-            head_commit.unwrap_or("").to_string(),
+            head.map_or("", |(id, _)| id).to_string(),
             doc_id.to_string(),
             doc.modified_at.to_rfc3339(),
             snapshot,
             key,
+            codec,
         )?;
         // AUTOCOMMIT-001 / P2P-001: stamp our authoring identity (PeerId) and
         // sign the envelope so the receiver can verify authorship — a paired
         // peer can't forge a document update "from" another device.
         commit.version_device = self.device_id.clone();
         sign_commit(&mut commit, &self.keypair)?;
-        Ok(commit)
+        Ok(Some(commit))
     }
 
-    /// Apply received document states to the local database (content-LWW).
+    /// Apply received document states to the local database.
+    ///
+    /// Bodies that arrive with a CRDT history are merged with ours, so
+    /// concurrent edits on both devices survive; the rest of the content
+    /// and the title follow the newer side. Merges that touched the same
+    /// lines are queued for [`Self::take_merge_conflicts`]. States from
+    /// peers without CRDT support are taken content-LWW as before.
     ///
     /// The envelopes must unseal under the pair key shared with `sender`
     /// (P1.4 / P2P-005) — a peer we hold no pair key for is refused. The
@@ -441,6 +881,7 @@ impl SyncService {
     /// an existing one is overwritten with the newer content. Plaintext
     /// content is re-encrypted at rest by the db's encrypting layer. Returns
     /// the number of documents written.
+    #[instrument(name = "sync.apply_commits", level = "debug", skip_all)]
     pub async fn apply_commits(
         &self,
         commits: Vec<EncryptedCommit>,
@@ -456,7 +897,14 @@ impl SyncService {
             }
         };
         let sender_id = sender.to_string();
+        let sender_policy = self.policy_for(sender);
+        // Holding a pair key makes the sender one of this account's own
+        // devices, so it may do what this account may.
+        let principal = Principal::Local;
         let mut docs_updated = std::collections::HashSet::new();
+        // Commits handled in this batch, remembered so a resumed session
+        // re-delivering them doesn't merge them a second time.
+        let mut handled = Vec::new();
 
         // Apply in timestamp order so the newest state lands last.
         let mut sorted = commits;
@@ -500,16 +948,110 @@ impl SyncService {
                     continue;
                 }
             }
+            let SyncedSnapshot {
+                snapshot,
+                crdt: remote_crdt,
+                blobs,
+                head,
+            } = open_snapshot(ec, &key)?;
+            // The head commit's device signature must be the sender's own.
+            // A sender that signed before and now names a head without a
+            // signature is flagged; its commit may predate signing.
+            match &head {
+                Some(head) if !head.verifies(&ec.document_id, &snapshot, &sender_id) => {
+                    tracing::warn!(
+                        "rejecting commit {} for doc {}: head commit device signature invalid",
+                        ec.commit_id,
+                        ec.document_id
+                    );
+                    continue;
+                }
+                Some(_) => {
+                    self.signing_peers
+                        .lock()
+                        .expect("signing peers lock poisoned")
+                        .insert(*sender);
+                }
+                None if !ec.commit_id.is_empty()
+                    && self
+                        .signing_peers
+                        .lock()
+                        .expect("signing peers lock poisoned")
+                        .contains(sender) =>
+                {
+                    tracing::warn!(
+                        "commit {} for doc {} from {sender} carries no device signature",
+                        ec.commit_id,
+                        ec.document_id
+                    );
+                }
+                None => {}
+            }
+            // Document states carry no commit id of their own, so an
+            // envelope is known by what it delivers.
+            let delivery = delivery_id(ec, &snapshot);
+            if self
+                .queue
+                .lock()
+                .expect("sync queue lock poisoned")
+                .is_applied(&delivery)
+            {
+                tracing::debug!("skipping state of {}: already applied", ec.document_id);
+                continue;
+            }
+            handled.push(delivery);
+            // Learn where the sender's media paths point; the bytes are
+            // fetched afterwards (`missing_blobs`).
+            for blob in &blobs {
+                self.blobs.record_alias(blob)?;
+            }
 
-            let snapshot = transport_to_snapshot(ec, &key)?;
-
-            if self.db.get_document(&ec.document_id).await.is_ok() {
+            if let Ok(existing) = self.db.get_document(&ec.document_id).await {
+                // A document shared with us read-only stays as its owner
+                // wrote it; skip it rather than fail the whole batch.
+                if !existing.permissions.permits(&principal, ActionLevel::Modify) {
+                    tracing::warn!(
+                        "rejecting commit {} for doc {}: {principal} may not edit it",
+                        ec.commit_id,
+                        ec.document_id
+                    );
+                    continue;
+                }
+                // A document we filter out for this sender is missing from
+                // the manifest it diffed against, so it pushes whatever copy
+                // it has — possibly one from before the policy. Only take it
+                // if it is actually newer than ours.
+                if !sender_policy.allows_document(&existing) && chrono::DateTime::parse_from_rfc3339(&ec.timestamp)
+                    .is_ok_and(|ts| ts.with_timezone(&chrono::Utc) <= existing.modified_at)
+                {
+                    continue;
+                }
+                let (title, content) = match &remote_crdt {
+                    Some(remote) => {
+                        let remote_newer = chrono::DateTime::parse_from_rfc3339(&ec.timestamp)
+                            .is_ok_and(|ts| ts.with_timezone(&chrono::Utc) > existing.modified_at);
+                        let merged = self.merge_body(
+                            &ec.document_id,
+                            &existing.content,
+                            &snapshot.content,
+                            remote,
+                            remote_newer,
+                        )?;
+                        let (title, frame) = if remote_newer {
+                            (snapshot.title, snapshot.content)
+                        } else {
+                            (existing.title.clone(), existing.content.clone())
+                        };
+                        (title, crdt::with_body(&frame, &merged))
+                    }
+                    None => (snapshot.title, snapshot.content),
+                };
+                if title == existing.title && content == existing.content {
+                    // Nothing new — e.g. the peer's edits were already merged.
+                    continue;
+                }
                 self.db
-                    .update_document(
-                        &ec.document_id,
-                        Some(&snapshot.title),
-                        Some(&snapshot.content),
-                    )
+                    .update_document(&ec.document_id, Some(&title), Some(&content))
                     .await
                     .map_err(|e| P2pError::SyncError(format!("failed to update doc: {e}")))?;
             } else {
@@ -525,13 +1067,194 @@ impl SyncService {
                     .create_document_with_id(doc)
                     .await
                     .map_err(|e| P2pError::SyncError(format!("failed to create doc: {e}")))?;
+                // Adopt the sender's history so later edits merge.
+                if let Some(remote) = &remote_crdt {
+                    let history = crdt::BodyCrdt::load(remote, self.crdt_client)?;
+                    self.crdt
+                        .lock()
+                        .expect("crdt store lock poisoned")
+                        .record(&ec.document_id, &history);
+                }
             }
             docs_updated.insert(ec.document_id.clone());
         }
 
+        if !handled.is_empty() {
+            // Histories merged or adopted above are written once per batch.
+            self.crdt.lock().expect("crdt store lock poisoned").save()?;
+            let mut queue = self.queue.lock().expect("sync queue lock poisoned");
+            for id in &handled {
+                queue.mark_applied(id);
+            }
+            queue.save()?;
+        }
         Ok(docs_updated.len() as u32)
     }
 
+    /// Merge a received body into the local one and remember the result.
+    /// Returns the merged body.
+    fn merge_body(
+        &self,
+        doc_id: &str,
+        local_content: &str,
+        remote_content: &str,
+        remote: &CrdtPayload,
+        remote_newer: bool,
+    ) -> P2pResult<String> {
+        let local_body = crdt::body_of(local_content);
+        let remote_body = crdt::body_of(remote_content);
+        let mut store = self.crdt.lock().expect("crdt store lock poisoned");
+        let base = store.base(doc_id).map(str::to_string);
+        let local = store.local(doc_id, &local_body, self.crdt_client)?;
+        let merged = crdt::merge_body(
+            local,
+            &local_body,
+            remote,
+            &remote_body,
+            remote_newer,
+            base.as_deref(),
+            self.crdt_client,
+        )?;
+        store.record(doc_id, &merged.crdt);
+        drop(store);
+        if merged.conflict {
+            tracing::info!("merged concurrent edits to {doc_id}; same lines changed on both sides");
+            self.merge_conflicts
+                .lock()
+                .expect("merge conflict lock poisoned")
+                .push(MergeConflict {
+                    doc_id: doc_id.to_string(),
+                    hunks: crdt::conflict_hunks(base.as_deref(), &local_body, &remote_body),
+                    base,
+                    local: local_body,
+                    remote: remote_body,
+                    merged: merged.body.clone(),
+                });
+        }
+        Ok(merged.body)
+    }
+
+    // ----- Media blobs -----
+
+    /// Hashes of the media held here for the documents `policy` lets
+    /// through. Only these are advertised to, or served to, that peer.
+    fn shared_blob_hashes(&self, docs: &[Document], policy: &SyncPolicy) -> Vec<String> {
+        let mut hashes: Vec<String> = docs
+            .iter()
+            .filter(|d| policy.allows_document(d))
+            .flat_map(|d| blob_store::media_paths(&d.content))
+            .filter_map(|path| self.blobs.describe(&path))
+            .map(|b| b.hash)
+            .collect();
+        hashes.sort();
+        hashes.dedup();
+        hashes
+    }
+
+    /// Hashes of the media `peer` may receive from this device.
+    pub async fn shared_blobs(&self, peer: &PeerId) -> P2pResult<Vec<String>> {
+        let docs = self
+            .db
+            .list_documents(None)
+            .await
+            .map_err(|e| P2pError::SyncError(format!("failed to list documents: {e}")))?;
+        Ok(self.shared_blob_hashes(&docs, &self.policy_for(peer)))
+    }
+
+    /// Media referenced by received documents that hasn't fully arrived,
+    /// each with its resume offset.
+    pub fn missing_blobs(&self) -> Vec<BlobWant> {
+        self.blobs
+            .missing()
+            .into_iter()
+            .map(|(hash, _)| BlobWant {
+                offset: self.blobs.received_len(&hash),
+                hash,
+            })
+            .collect()
+    }
+
+    /// Read and seal the next chunk of each wanted blob for `peer`, up to
+    /// [`MAX_BLOB_MESSAGE_BYTES`] in total. Returns the chunks and the
+    /// hashes that can't be served — not held here, or not shared with
+    /// `peer`. Wants in neither list were deferred for lack of room.
+    #[instrument(name = "sync.seal_blob_chunks", level = "debug", skip_all)]
+    pub async fn seal_blob_chunks(
+        &self,
+        wants: &[BlobWant],
+        peer: &PeerId,
+    ) -> P2pResult<(Vec<EncryptedBlobChunk>, Vec<String>)> {
+        use base64::Engine;
+        let key = self.pair_key_for(peer)?;
+        let shared = self.shared_blobs(peer).await?;
+        let mut chunks = Vec::new();
+        let mut unavailable = Vec::new();
+        let mut budget = MAX_BLOB_MESSAGE_BYTES;
+        for want in wants {
+            if budget == 0 {
+                break;
+            }
+            if shared.binary_search(&want.hash).is_err() {
+                unavailable.push(want.hash.clone());
+                continue;
+            }
+            let max = budget.min(blob_store::CHUNK_BYTES);
+            let Some((data, total)) = self.blobs.read_chunk(&want.hash, want.offset, max)? else {
+                unavailable.push(want.hash.clone());
+                continue;
+            };
+            let (ciphertext, nonce) = sovereign_crypto::aead::encrypt(&data, &key)
+                .map_err(|e| P2pError::SyncError(format!("blob encrypt: {e}")))?;
+            budget -= data.len() as u64;
+            chunks.push(EncryptedBlobChunk {
+                hash: want.hash.clone(),
+                offset: want.offset,
+                total,
+                ciphertext: base64::engine::general_purpose::STANDARD.encode(&ciphertext),
+                nonce: base64::engine::general_purpose::STANDARD.encode(nonce),
+            });
+        }
+        Ok((chunks, unavailable))
+    }
+
+    /// Store received blob chunks from `sender`. Returns the resume point
+    /// of every blob in the batch that is still incomplete. A chunk that
+    /// doesn't unseal, or a blob that fails its hash check, is dropped
+    /// (and refetched from scratch on a later sync).
+    #[instrument(name = "sync.apply_blob_chunks", level = "debug", skip_all)]
+    pub fn apply_blob_chunks(
+        &self,
+        chunks: Vec<EncryptedBlobChunk>,
+        sender: &PeerId,
+    ) -> P2pResult<Vec<BlobWant>> {
+        let key = self.pair_key_for(sender)?;
+        let mut resume: Vec<BlobWant> = Vec::new();
+        for chunk in chunks {
+            let data = match open_blob_chunk(&chunk, &key) {
+                Ok(d) => d,
+                Err(e) => {
+                    tracing::warn!("dropping blob chunk {} from {sender}: {e}", chunk.hash);
+                    continue;
+                }
+            };
+            let held = match self.blobs.write_chunk(&chunk.hash, chunk.total, chunk.offset, &data) {
+                Ok(held) => held,
+                Err(e) => {
+                    tracing::warn!("blob {} from {sender}: {e}", chunk.hash);
+                    continue;
+                }
+            };
+            resume.retain(|w| w.hash != chunk.hash);
+            if held < chunk.total {
+                resume.push(BlobWant {
+                    hash: chunk.hash,
+                    offset: held,
+                });
+            }
+        }
+        Ok(resume)
+    }
+
     // ----- Row-level sync (non-document tables, Phase 3 v0.0.5) -----
 
     /// Fetch rows from the local DB and package them as `EncryptedRow`s
@@ -539,6 +1262,7 @@ impl SyncService {
     /// P2P-005), stamped with this device's Lamport version for the row
     /// (P1.3 — a locally-edited row gets the next counter), and
     /// Ed25519-signed with the device identity key.
+    #[instrument(name = "sync.get_rows", level = "debug", skip_all)]
     pub async fn get_rows(
         &self,
         table: SyncTable,
@@ -546,53 +1270,74 @@ impl SyncService {
         peer: &PeerId,
     ) -> P2pResult<Vec<EncryptedRow>> {
         let key = self.pair_key_for(peer)?;
+        let codec = self.compression_for(peer);
+        let policy = self.policy_for(peer);
         let mut out = Vec::with_capacity(ids.len());
         for id in ids {
             let (mut row, content_hash) = match table {
                 SyncTable::Thread => match self.db.get_thread(id).await {
-                    Ok(t) => (row_from_thread(&t, &key)?, hash_thread(&t)),
+                    Ok(_) if !policy.allows_thread(id) => continue,
+                    Ok(t) => (row_from_thread(&t, &key, codec)?, hash_thread(&t)),
                     Err(_) => continue,
                 },
                 SyncTable::Entity => match self.db.get_entity(id).await {
-                    Ok(e) => (row_from_entity(&e, &key)?, hash_entity(&e)),
+                    Ok(e) => (row_from_entity(&e, &key, codec)?, hash_entity(&e)),
                     Err(_) => continue,
                 },
                 SyncTable::PiiRecord => match self.db.get_pii_record(id).await {
                     Ok(r) => (
-                        row_from_pii_record(&r, &key)?,
+                        row_from_pii_record(&r, &key, codec)?,
                         hash_pii_record(&r),
                     ),
                     Err(_) => continue,
                 },
                 SyncTable::ShareRecord => match self.db.get_share_record(id).await {
                     Ok(s) => (
-                        row_from_share_record(&s, &key)?,
+                        row_from_share_record(&s, &key, codec)?,
                         hash_share_record(&s),
                     ),
                     Err(_) => continue,
                 },
                 SyncTable::Contact => match self.db.get_contact(id).await {
-                    Ok(c) => (row_from_contact(&c, &key)?, hash_contact(&c)),
+                    Ok(c) => (row_from_contact(&c, &key, codec)?, hash_contact(&c)),
                     Err(_) => continue,
                 },
                 SyncTable::Message => match self.db.get_message(id).await {
-                    Ok(m) => (row_from_message(&m, &key)?, hash_message(&m)),
+                    Ok(m) => (row_from_message(&m, &key, codec)?, hash_message(&m)),
                     Err(_) => continue,
                 },
                 SyncTable::Conversation => match self.db.get_conversation(id).await {
-                    Ok(v) => (row_from_conversation(&v, &key)?, hash_conversation(&v)),
+                    Ok(v) => (row_from_conversation(&v, &key, codec)?, hash_conversation(&v)),
                     Err(_) => continue,
                 },
                 SyncTable::Milestone => match self.db.get_milestone(id).await {
-                    Ok(ms) => (row_from_milestone(&ms, &key)?, hash_milestone(&ms)),
+                    Ok(ms) => (row_from_milestone(&ms, &key, codec)?, hash_milestone(&ms)),
                     Err(_) => continue,
                 },
                 SyncTable::Relationship => match self.db.get_relationship(id).await {
-                    Ok(r) => (row_from_relationship(&r, &key)?, hash_relationship(&r)),
+                    Ok(r) => (row_from_relationship(&r, &key, codec)?, hash_relationship(&r)),
                     Err(_) => continue,
                 },
                 SyncTable::SuggestedLink => match self.db.get_suggested_link(id).await {
-                    Ok(l) => (row_from_suggested_link(&l, &key)?, hash_suggested_link(&l)),
+                    Ok(l) => (row_from_suggested_link(&l, &key, codec)?, hash_suggested_link(&l)),
+                    Err(_) => continue,
+                },
+                SyncTable::Favorite => match self.db.get_document(id).await {
+                    Ok(d) if !policy.allows_document(&d) => {
+                        continue
+                    }
+                    Ok(d) => match row_from_favorite(id, &d, &key, codec)? {
+                        Some(row) => (row, hash_favorite(d.favorite)),
+                        None => continue,
+                    },
+                    Err(_) => continue,
+                },
+                SyncTable::Sensitivity => match self.db.get_document(id).await {
+                    Ok(d) if !policy.allows_document(&d) => continue,
+                    Ok(d) => match row_from_sensitivity(id, &d, &key, codec)? {
+                        Some(row) => (row, hash_sensitivity(d.sensitivity)),
+                        None => continue,
+                    },
                     Err(_) => continue,
                 },
             };
@@ -625,6 +1370,7 @@ impl SyncService {
     /// `(version_counter, version_device)` stamp, not by `modified_at`;
     /// the 24h future-skew bound on timestamps stays as belt-and-
     /// suspenders. Returns (written, skipped).
+    #[instrument(name = "sync.apply_rows", level = "debug", skip_all)]
     pub async fn apply_rows(
         &self,
         table: SyncTable,
@@ -683,6 +1429,8 @@ impl SyncService {
                 SyncTable::Milestone => self.apply_milestone_row(&row, &key).await,
                 SyncTable::Relationship => self.apply_relationship_row(&row, &key).await,
                 SyncTable::SuggestedLink => self.apply_suggested_link_row(&row, &key).await,
+                SyncTable::Favorite => self.apply_favorite_row(&row, &key).await,
+                SyncTable::Sensitivity => self.apply_sensitivity_row(&row, &key).await,
             };
             match result {
                 Ok(true) => written += 1,
@@ -1302,6 +2050,102 @@ impl SyncService {
         }
     }
 
+    async fn apply_favorite_row(&self, row: &EncryptedRow, key: &[u8; 32]) -> P2pResult<bool> {
+        let remote: FavoriteRow = decode_row_inner(row, key)?;
+
+        if remote.favorite_at > chrono::Utc::now() + MAX_FUTURE_SKEW {
+            tracing::warn!(
+                "rejecting favorite row {}: favorite_at beyond max future skew",
+                row.id
+            );
+            return Ok(false);
+        }
+
+        // The document arrives on the commit track; until it has, there
+        // is nothing to pin and the next round tries again.
+        let Ok(local) = self.db.get_document(&row.id).await else {
+            return Ok(false);
+        };
+        let version = if local.favorite_at.is_some() {
+            let winner = self.resolve_row_version(
+                &row.id,
+                row.version_counter,
+                &row.version_device,
+                hash_favorite(remote.favorite),
+                &hash_favorite(local.favorite),
+            );
+            let Some(version) = winner else {
+                return Ok(false);
+            };
+            version
+        } else {
+            // Never pinned here: no local choice to defend.
+            RowVersion {
+                counter: row.version_counter,
+                device_id: row.version_device.clone(),
+                content_hash: hash_favorite(remote.favorite),
+            }
+        };
+        self.db
+            .set_favorite(&row.id, remote.favorite, remote.favorite_at)
+            .await
+            .map_err(|e| P2pError::SyncError(format!("set_favorite: {e}")))?;
+        self.record_row_applied(&row.id, version);
+        Ok(true)
+    }
+
+    async fn apply_sensitivity_row(&self, row: &EncryptedRow, key: &[u8; 32]) -> P2pResult<bool> {
+        let remote: SensitivityRow = decode_row_inner(row, key)?;
+
+        if remote.sensitivity_at > chrono::Utc::now() + MAX_FUTURE_SKEW {
+            tracing::warn!(
+                "rejecting sensitivity row {}: sensitivity_at beyond max future skew",
+                row.id
+            );
+            return Ok(false);
+        }
+
+        // As with pins, the document has to have arrived first.
+        let Ok(local) = self.db.get_document(&row.id).await else {
+            return Ok(false);
+        };
+        // A paired device must not be able to unseal a document here.
+        if remote.sensitivity < local.sensitivity {
+            tracing::info!(
+                "ignoring sensitivity row {}: a peer cannot lower it from {:?} to {:?}",
+                row.id,
+                local.sensitivity,
+                remote.sensitivity
+            );
+            return Ok(false);
+        }
+        let version = if local.sensitivity_at.is_some() {
+            let winner = self.resolve_row_version(
+                &row.id,
+                row.version_counter,
+                &row.version_device,
+                hash_sensitivity(remote.sensitivity),
+                &hash_sensitivity(local.sensitivity),
+            );
+            let Some(version) = winner else {
+                return Ok(false);
+            };
+            version
+        } else {
+            RowVersion {
+                counter: row.version_counter,
+                device_id: row.version_device.clone(),
+                content_hash: hash_sensitivity(remote.sensitivity),
+            }
+        };
+        self.db
+            .set_sensitivity(&row.id, remote.sensitivity, remote.sensitivity_at)
+            .await
+            .map_err(|e| P2pError::SyncError(format!("set_sensitivity: {e}")))?;
+        self.record_row_applied(&row.id, version);
+        Ok(true)
+    }
+
     /// Get the device ID for this sync service.
     pub fn device_id(&self) -> &str {
         &self.device_id
@@ -1319,46 +2163,80 @@ fn envelope_thing(raw_id: &str) -> P2pResult<sovereign_db::schema::Thing> {
 
 // --- Row encode/decode helpers (Phase 3 plaintext-marker shape) ---
 
-fn row_from_thread(t: &Thread, key: &[u8; 32]) -> P2pResult<EncryptedRow> {
-    encode_row(t.id_string().unwrap_or_default(), t, t.modified_at.to_rfc3339(), t.deleted_at.clone(), key)
+fn row_from_thread(t: &Thread, key: &[u8; 32], codec: Compression) -> P2pResult<EncryptedRow> {
+    encode_row(t.id_string().unwrap_or_default(), t, t.modified_at.to_rfc3339(), t.deleted_at.clone(), key, codec)
 }
 
-fn row_from_entity(e: &Entity, key: &[u8; 32]) -> P2pResult<EncryptedRow> {
-    encode_row(e.id_string().unwrap_or_default(), e, e.modified_at.to_rfc3339(), e.deleted_at.clone(), key)
+fn row_from_entity(e: &Entity, key: &[u8; 32], codec: Compression) -> P2pResult<EncryptedRow> {
+    encode_row(e.id_string().unwrap_or_default(), e, e.modified_at.to_rfc3339(), e.deleted_at.clone(), key, codec)
 }
 
-fn row_from_pii_record(r: &PiiRecord, key: &[u8; 32]) -> P2pResult<EncryptedRow> {
-    encode_row(r.id_string().unwrap_or_default(), r, r.discovered_at.to_rfc3339(), r.deleted_at.clone(), key)
+fn row_from_pii_record(r: &PiiRecord, key: &[u8; 32], codec: Compression) -> P2pResult<EncryptedRow> {
+    encode_row(r.id_string().unwrap_or_default(), r, r.discovered_at.to_rfc3339(), r.deleted_at.clone(), key, codec)
 }
 
-fn row_from_share_record(s: &ShareRecord, key: &[u8; 32]) -> P2pResult<EncryptedRow> {
-    encode_row(s.id_string().unwrap_or_default(), s, s.shared_at.to_rfc3339(), None, key)
+fn row_from_share_record(s: &ShareRecord, key: &[u8; 32], codec: Compression) -> P2pResult<EncryptedRow> {
+    encode_row(s.id_string().unwrap_or_default(), s, s.shared_at.to_rfc3339(), None, key, codec)
 }
 
-fn row_from_contact(c: &Contact, key: &[u8; 32]) -> P2pResult<EncryptedRow> {
-    encode_row(c.id_string().unwrap_or_default(), c, c.modified_at.to_rfc3339(), c.deleted_at.clone(), key)
+fn row_from_contact(c: &Contact, key: &[u8; 32], codec: Compression) -> P2pResult<EncryptedRow> {
+    encode_row(c.id_string().unwrap_or_default(), c, c.modified_at.to_rfc3339(), c.deleted_at.clone(), key, codec)
 }
 
-fn row_from_message(m: &Message, key: &[u8; 32]) -> P2pResult<EncryptedRow> {
-    encode_row(m.id_string().unwrap_or_default(), m, m.created_at.to_rfc3339(), m.deleted_at.clone(), key)
+fn row_from_message(m: &Message, key: &[u8; 32], codec: Compression) -> P2pResult<EncryptedRow> {
+    encode_row(m.id_string().unwrap_or_default(), m, m.created_at.to_rfc3339(), m.deleted_at.clone(), key, codec)
 }
 
-fn row_from_conversation(v: &Conversation, key: &[u8; 32]) -> P2pResult<EncryptedRow> {
+fn row_from_conversation(v: &Conversation, key: &[u8; 32], codec: Compression) -> P2pResult<EncryptedRow> {
     let lww = v.last_message_at.unwrap_or(v.created_at).to_rfc3339();
-    encode_row(v.id_string().unwrap_or_default(), v, lww, v.deleted_at.clone(), key)
+    encode_row(v.id_string().unwrap_or_default(), v, lww, v.deleted_at.clone(), key, codec)
 }
 
-fn row_from_milestone(ms: &Milestone, key: &[u8; 32]) -> P2pResult<EncryptedRow> {
-    encode_row(ms.id_string().unwrap_or_default(), ms, ms.timestamp.to_rfc3339(), None, key)
+fn row_from_milestone(ms: &Milestone, key: &[u8; 32], codec: Compression) -> P2pResult<EncryptedRow> {
+    encode_row(ms.id_string().unwrap_or_default(), ms, ms.timestamp.to_rfc3339(), None, key, codec)
 }
 
-fn row_from_relationship(r: &RelatedTo, key: &[u8; 32]) -> P2pResult<EncryptedRow> {
-    encode_row(r.id_string().unwrap_or_default(), r, r.created_at.to_rfc3339(), None, key)
+fn row_from_relationship(r: &RelatedTo, key: &[u8; 32], codec: Compression) -> P2pResult<EncryptedRow> {
+    encode_row(r.id_string().unwrap_or_default(), r, r.created_at.to_rfc3339(), None, key, codec)
 }
 
-fn row_from_suggested_link(l: &SuggestedLink, key: &[u8; 32]) -> P2pResult<EncryptedRow> {
+fn row_from_suggested_link(l: &SuggestedLink, key: &[u8; 32], codec: Compression) -> P2pResult<EncryptedRow> {
     let lww = l.resolved_at.unwrap_or(l.created_at).to_rfc3339();
-    encode_row(l.id_string().unwrap_or_default(), l, lww, None, key)
+    encode_row(l.id_string().unwrap_or_default(), l, lww, None, key, codec)
+}
+
+/// The pin of a document that was ever pinned; `None` otherwise.
+fn row_from_favorite(
+    id: &str,
+    d: &Document,
+    key: &[u8; 32],
+    codec: Compression,
+) -> P2pResult<Option<EncryptedRow>> {
+    let Some(favorite_at) = d.favorite_at else {
+        return Ok(None);
+    };
+    let pin = FavoriteRow {
+        favorite: d.favorite,
+        favorite_at,
+    };
+    encode_row(id.to_string(), &pin, favorite_at.to_rfc3339(), None, key, codec).map(Some)
+}
+
+/// The level of a document whose level was ever set; `None` otherwise.
+fn row_from_sensitivity(
+    id: &str,
+    d: &Document,
+    key: &[u8; 32],
+    codec: Compression,
+) -> P2pResult<Option<EncryptedRow>> {
+    let Some(sensitivity_at) = d.sensitivity_at else {
+        return Ok(None);
+    };
+    let level = SensitivityRow {
+        sensitivity: d.sensitivity,
+        sensitivity_at,
+    };
+    encode_row(id.to_string(), &level, sensitivity_at.to_rfc3339(), None, key, codec).map(Some)
 }
 
 /// AEAD-seal a row's JSON under the transport key (P2P-002). The body is
@@ -1371,11 +2249,12 @@ fn encode_row<T: serde::Serialize>(
     modified_at: String,
     deleted_at: Option<String>,
     key: &[u8; 32],
+    codec: Compression,
 ) -> P2pResult<EncryptedRow> {
     use base64::Engine;
     let json = serde_json::to_vec(row)
         .map_err(|e| P2pError::SyncError(format!("row serialize: {e}")))?;
-    let (ciphertext, nonce) = sovereign_crypto::aead::encrypt(&json, key)
+    let (ciphertext, nonce) = sovereign_crypto::aead::encrypt(&codec.pack(json), key)
         .map_err(|e| P2pError::SyncError(format!("row encrypt: {e}")))?;
     Ok(EncryptedRow {
         id,
@@ -1534,6 +2413,7 @@ fn decode_row_inner<T: serde::de::DeserializeOwned>(
     nonce.copy_from_slice(&nonce_bytes);
     let plaintext = sovereign_crypto::aead::decrypt(&ciphertext, &nonce, key)
         .map_err(|e| P2pError::SyncError(format!("row decrypt: {e}")))?;
+    let plaintext = wire::unpack(plaintext)?;
     serde_json::from_slice(&plaintext)
         .map_err(|e| P2pError::SyncError(format!("row decode: {e}")))
 }
@@ -1731,6 +2611,31 @@ fn hash_suggested_link(l: &SuggestedLink) -> String {
     format!("{:x}", h.finalize())
 }
 
+fn hash_favorite(favorite: bool) -> String {
+    let mut h = Sha256::new();
+    h.update(b"favorite:");
+    h.update([u8::from(favorite)]);
+    format!("{:x}", h.finalize())
+}
+
+fn hash_sensitivity(sensitivity: Sensitivity) -> String {
+    let mut h = Sha256::new();
+    h.update(b"sensitivity:");
+    h.update(format!("{sensitivity:?}").as_bytes());
+    format!("{:x}", h.finalize())
+}
+
+/// Identity of one delivered document state: the document, its timestamp
+/// and a hash of the title and content. A resent envelope maps to the same
+/// id; a later edit, even under the same head commit, doesn't.
+fn delivery_id(ec: &EncryptedCommit, snapshot: &sovereign_db::schema::DocumentSnapshot) -> String {
+    let mut h = Sha256::new();
+    h.update(snapshot.title.as_bytes());
+    h.update([0]);
+    h.update(snapshot.content.as_bytes());
+    format!("{}@{}:{:x}", ec.document_id, ec.timestamp, h.finalize())
+}
+
 /// AEAD-seal a (plaintext) document snapshot under the transport key for
 /// the wire. `commit_id`/`document_id`/`timestamp` stay in the clear as
 /// metadata; the title + content are sealed (P2P-002).
@@ -1738,13 +2643,14 @@ fn seal_snapshot(
     commit_id: String,
     document_id: String,
     timestamp: String,
-    snapshot: sovereign_db::schema::DocumentSnapshot,
+    snapshot: impl Serialize,
     key: &[u8; 32],
+    codec: Compression,
 ) -> P2pResult<EncryptedCommit> {
     use base64::Engine;
     let snapshot_json = serde_json::to_vec(&snapshot)
         .map_err(|e| P2pError::SyncError(format!("snapshot serialize: {e}")))?;
-    let (ciphertext, nonce) = sovereign_crypto::aead::encrypt(&snapshot_json, key)
+    let (ciphertext, nonce) = sovereign_crypto::aead::encrypt(&codec.pack(snapshot_json), key)
         .map_err(|e| P2pError::SyncError(format!("snapshot encrypt: {e}")))?;
     Ok(EncryptedCommit {
         commit_id,
@@ -1785,10 +2691,21 @@ fn commit_to_transport(commit: &Commit, key: &[u8; 32]) -> P2pResult<EncryptedCo
 
 /// Decrypt a transport EncryptedCommit back to a DocumentSnapshot.
 /// Rejects the old empty-nonce plaintext shape (P2P-002).
+#[cfg(test)]
 fn transport_to_snapshot(
     ec: &EncryptedCommit,
     key: &[u8; 32],
 ) -> P2pResult<sovereign_db::schema::DocumentSnapshot> {
+    open_snapshot(ec, key)
+}
+
+/// Decrypt a transport EncryptedCommit into `T` (a snapshot, with or
+/// without its CRDT history). Rejects the old empty-nonce plaintext shape
+/// (P2P-002).
+fn open_snapshot<T: serde::de::DeserializeOwned>(
+    ec: &EncryptedCommit,
+    key: &[u8; 32],
+) -> P2pResult<T> {
     use base64::Engine;
     if ec.nonce.is_empty() {
         return Err(P2pError::SyncError(
@@ -1812,10 +2729,25 @@ fn transport_to_snapshot(
     nonce.copy_from_slice(&nonce_bytes);
     let plaintext = sovereign_crypto::aead::decrypt(&ciphertext, &nonce, key)
         .map_err(|e| P2pError::SyncError(format!("snapshot decrypt: {e}")))?;
+    let plaintext = wire::unpack(plaintext)?;
     serde_json::from_slice(&plaintext)
         .map_err(|e| P2pError::SyncError(format!("snapshot decode: {e}")))
 }
 
+fn open_blob_chunk(chunk: &EncryptedBlobChunk, key: &[u8; 32]) -> P2pResult<Vec<u8>> {
+    use base64::Engine;
+    let ciphertext = base64::engine::general_purpose::STANDARD
+        .decode(&chunk.ciphertext)
+        .map_err(|e| P2pError::SyncError(format!("blob base64 ct: {e}")))?;
+    let nonce: [u8; 24] = base64::engine::general_purpose::STANDARD
+        .decode(&chunk.nonce)
+        .map_err(|e| P2pError::SyncError(format!("blob base64 nonce: {e}")))?
+        .try_into()
+        .map_err(|_| P2pError::SyncError("blob nonce wrong length".into()))?;
+    sovereign_crypto::aead::decrypt(&ciphertext, &nonce, key)
+        .map_err(|e| P2pError::SyncError(format!("blob decrypt: {e}")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1908,6 +2840,136 @@ mod tests {
         assert!(manifest.share_records.is_empty());
     }
 
+    #[tokio::test]
+    async fn manifest_and_commits_follow_peer_sync_policy() {
+        let (db, svc) = mock_sync_service();
+        let work = db.create_thread(Thread::new("Work".into(), "".into())).await.unwrap();
+        let work_id = work.id_string().unwrap();
+        let home = db.create_thread(Thread::new("Home".into(), "".into())).await.unwrap();
+        let home_id = home.id_string().unwrap();
+
+        db.create_document(Document::new("Plan".into(), work_id.clone(), true)).await.unwrap();
+        db.create_document(Document::new("Clipped".into(), work_id.clone(), false)).await.unwrap();
+        let private = db
+            .create_document(Document::new("Diary".into(), home_id.clone(), true))
+            .await
+            .unwrap();
+
+        let phone = remote_peer();
+        register_peer(&svc, &phone);
+        let mut policies = std::collections::HashMap::new();
+        policies.insert(
+            phone.to_string(),
+            SyncPolicy {
+                include_threads: vec![work_id.clone()],
+                exclude_external: true,
+                ..Default::default()
+            },
+        );
+        svc.set_sync_policies(policies);
+
+        let full = svc.build_manifest().await.unwrap();
+        assert_eq!(full.documents.len(), 3);
+        assert_eq!(full.threads.len(), 2);
+
+        let filtered = svc.build_manifest_for(&phone).await.unwrap();
+        assert_eq!(filtered.documents.len(), 1);
+        assert_eq!(filtered.threads.len(), 1);
+        assert_eq!(filtered.threads[0].thread_id, work_id);
+
+        // Asking for a filtered document or thread by id yields nothing.
+        let private_id = private.id_string().unwrap();
+        assert!(svc.get_commits_since(&private_id, None, &phone).await.unwrap().is_empty());
+        let rows = svc
+            .get_rows(SyncTable::Thread, &[home_id, work_id], &phone)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+
+        // Other peers still get everything.
+        let laptop = test_keypair(0xD8).public().to_peer_id();
+        assert_eq!(svc.build_manifest_for(&laptop).await.unwrap().documents.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn excluded_documents_and_sealed_ones_stay_off_the_peer() {
+        let (db, svc) = mock_sync_service();
+        let plan = db
+            .create_document(Document::new("Plan".into(), "thread:t".into(), true))
+            .await
+            .unwrap();
+        let diary = db
+            .create_document(Document::new("Diary".into(), "thread:t".into(), true))
+            .await
+            .unwrap();
+        let diary_id = diary.id_string().unwrap();
+        db.set_sensitivity(&plan.id_string().unwrap(), Sensitivity::Sealed, chrono::Utc::now())
+            .await
+            .unwrap();
+
+        let phone = remote_peer();
+        register_peer(&svc, &phone);
+        let mut policies = std::collections::HashMap::new();
+        policies.insert(
+            phone.to_string(),
+            SyncPolicy {
+                exclude_documents: vec![diary_id.clone()],
+                ..Default::default()
+            },
+        );
+        svc.set_sync_policies(policies);
+
+        // The sealed document still syncs, with its level; the excluded one doesn't.
+        let filtered = svc.build_manifest_for(&phone).await.unwrap();
+        assert_eq!(filtered.documents.len(), 1);
+        assert_eq!(filtered.sensitivities.len(), 1);
+        assert!(svc.get_commits_since(&diary_id, None, &phone).await.unwrap().is_empty());
+
+        // The companion viewer gets neither.
+        let sealed = svc.viewer_snapshot_for(&phone, None).await.unwrap();
+        let snapshot = svc.open_viewer_snapshot(&phone, &sealed).unwrap();
+        assert!(snapshot.documents.is_empty());
+    }
+
+    #[tokio::test]
+    async fn viewer_snapshot_comes_in_pages_and_names_what_does_not_fit() {
+        let (db, svc) = mock_sync_service();
+        let mb = "x".repeat(1024 * 1024);
+        for i in 0..6 {
+            let mut doc = Document::new(format!("Chapter {i}"), "thread:t".into(), true);
+            doc.content = mb.clone();
+            db.create_document(doc).await.unwrap();
+        }
+        let mut huge = Document::new("Scan archive".into(), "thread:t".into(), true);
+        huge.content = "x".repeat(viewer::MAX_PAGE_BYTES + 1);
+        db.create_document(huge).await.unwrap();
+        let phone = remote_peer();
+        register_peer(&svc, &phone);
+
+        let mut snapshot: Option<ViewerSnapshot> = None;
+        let mut after = None;
+        let mut pages = 0;
+        loop {
+            let sealed = svc.viewer_snapshot_for(&phone, after.as_deref()).await.unwrap();
+            assert!(sealed.ciphertext.len() < 10 * 1024 * 1024);
+            let page = svc.open_viewer_snapshot(&phone, &sealed).unwrap();
+            after = page.next_after.clone();
+            match snapshot.as_mut() {
+                Some(s) => s.append(page),
+                None => snapshot = Some(page),
+            }
+            pages += 1;
+            if after.is_none() {
+                break;
+            }
+            assert!(pages < 10, "pages must make progress");
+        }
+        let snapshot = snapshot.unwrap();
+        assert!(pages >= 2);
+        assert_eq!(snapshot.documents.len(), 6);
+        assert_eq!(snapshot.too_large, ["Scan archive"]);
+    }
+
     #[tokio::test]
     async fn is_ancestor_true_for_parent() {
         let (db, svc) = mock_sync_service();
@@ -2013,6 +3075,7 @@ mod tests {
                 content: "shared body".into(),
             },
             &TEST_PAIR_KEY,
+            Compression::None,
         )
         .unwrap();
 
@@ -2035,6 +3098,7 @@ mod tests {
                 content: "shared body v2".into(),
             },
             &TEST_PAIR_KEY,
+            Compression::None,
         )
         .unwrap();
         svc.apply_commits(vec![sign_commit_as(ec2, 0xD9)], &peer).await.unwrap();
@@ -2045,6 +3109,113 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn head_commit_signatures_must_be_the_senders() {
+        use sovereign_crypto::device_key::DeviceKey;
+        use sovereign_crypto::device_signing::DeviceSigner;
+        use sovereign_db::schema::DocumentSnapshot;
+
+        let mk = sovereign_crypto::master_key::MasterKey::from_bytes([3; 32]);
+        let device_key = DeviceKey::derive(&mk, "sender").unwrap();
+        let sender_keys = crate::identity::derive_keypair(&device_key).unwrap();
+        let sender = sender_keys.public().to_peer_id();
+        let (db, svc) = mock_sync_service();
+        register_peer(&svc, &sender);
+
+        let state = |title: &str| DocumentSnapshot {
+            document_id: "document:signed".into(),
+            title: title.into(),
+            content: "body".into(),
+        };
+        let signed_by = |signer: &DeviceSigner, mut commit: Commit| {
+            commit.device_public_key = Some(signer.public_key_b64());
+            commit.device_signature = Some(signer.sign_commit(&commit.signed_bytes()));
+            commit
+        };
+        let envelope = |sent: DocumentSnapshot, head: &Commit, at: &str| {
+            let payload = SyncedSnapshot {
+                head: SignedHead::of(head, &sent),
+                snapshot: sent,
+                crdt: None,
+                blobs: vec![],
+            };
+            let mut ec = seal_snapshot(
+                "commit:head".into(),
+                "document:signed".into(),
+                at.into(),
+                payload,
+                &TEST_PAIR_KEY,
+                Compression::None,
+            )
+            .unwrap();
+            ec.version_device = sender.to_string();
+            sign_commit(&mut ec, &sender_keys).unwrap();
+            ec
+        };
+        let head = signed_by(
+            &DeviceSigner::new(&device_key),
+            Commit {
+                id: None,
+                document_id: "document:signed".into(),
+                parent_commit: None,
+                message: "edit".into(),
+                timestamp: chrono::Utc::now(),
+                snapshot: state("Signed"),
+                signature: None,
+                device_public_key: None,
+                device_signature: None,
+            },
+        );
+
+        // A state that has moved on from its signed head still verifies.
+        let ec = envelope(state("Edited"), &head, "2026-02-01T00:00:00Z");
+        assert_eq!(svc.apply_commits(vec![ec], &sender).await.unwrap(), 1);
+
+        // A head signed by some other device is refused...
+        let other = DeviceSigner::new(&DeviceKey::derive(&mk, "other").unwrap());
+        let forged = signed_by(&other, head.clone());
+        let ec = envelope(state("Forged"), &forged, "2026-02-02T00:00:00Z");
+        assert_eq!(svc.apply_commits(vec![ec], &sender).await.unwrap(), 0);
+
+        // ...and so is one whose signed fields were changed.
+        let mut altered = head.clone();
+        altered.message = "rewritten".into();
+        let ec = envelope(state("Altered"), &altered, "2026-02-03T00:00:00Z");
+        assert_eq!(svc.apply_commits(vec![ec], &sender).await.unwrap(), 0);
+
+        assert_eq!(db.get_document("document:signed").await.unwrap().title, "Edited");
+    }
+
+    #[tokio::test]
+    async fn compressed_commits_apply_like_plain_ones() {
+        let (db, svc) = mock_sync_service();
+        let peer = remote_peer();
+        register_peer(&svc, &peer);
+        let body = "A paragraph that repeats, as text does.\n".repeat(500);
+        let seal = |codec| {
+            seal_snapshot(
+                "commit:z".into(),
+                "document:zipped".into(),
+                "2026-02-01T00:00:00Z".into(),
+                sovereign_db::schema::DocumentSnapshot {
+                    document_id: "document:zipped".into(),
+                    title: "Zipped".into(),
+                    content: body.clone(),
+                },
+                &TEST_PAIR_KEY,
+                codec,
+            )
+            .unwrap()
+        };
+        let plain = seal(Compression::None);
+        let packed = seal(Compression::Zstd);
+        assert!(packed.encrypted_snapshot.len() * 10 < plain.encrypted_snapshot.len());
+
+        let n = svc.apply_commits(vec![sign_commit_as(packed, 0xD9)], &peer).await.unwrap();
+        assert_eq!(n, 1);
+        assert_eq!(db.get_document("document:zipped").await.unwrap().content, body);
+    }
+
     #[tokio::test]
     async fn apply_commits_creates_new_doc() {
         let (db, svc) = mock_sync_service();
@@ -2066,6 +3237,8 @@ mod tests {
                     content: "synced content".into(),
                 },
                 signature: None,
+                device_public_key: None,
+                device_signature: None,
             },
             &[7u8; 32],
         )
@@ -2103,6 +3276,8 @@ mod tests {
                     content: "updated content".into(),
                 },
                 signature: None,
+                device_public_key: None,
+                device_signature: None,
             },
             &[7u8; 32],
         )
@@ -2117,6 +3292,47 @@ mod tests {
         assert_eq!(updated.content, "updated content");
     }
 
+    #[tokio::test]
+    async fn apply_commits_skips_documents_this_account_may_not_edit() {
+        use sovereign_db::schema::{AccessLevel, DocumentPermissions};
+
+        let (db, svc) = mock_sync_service();
+        let peer = remote_peer();
+        register_peer(&svc, &peer);
+        let doc = db
+            .create_document(Document::new("Theirs".into(), "thread:t".into(), false))
+            .await
+            .unwrap();
+        let doc_id = doc.id_string().unwrap();
+        let mut perms = DocumentPermissions::owned_by(Principal::Peer("alice".into()));
+        perms.grant(Principal::Local, AccessLevel::Annotate);
+        db.set_document_permissions(&doc_id, perms).await.unwrap();
+
+        let ec = commit_to_transport(
+            &Commit {
+                id: None,
+                document_id: doc_id.clone(),
+                parent_commit: None,
+                message: "sync update".into(),
+                timestamp: chrono::Utc::now(),
+                snapshot: sovereign_db::schema::DocumentSnapshot {
+                    document_id: doc_id.clone(),
+                    title: "Edited anyway".into(),
+                    content: "edited content".into(),
+                },
+                signature: None,
+                device_public_key: None,
+                device_signature: None,
+            },
+            &[7u8; 32],
+        )
+        .unwrap();
+
+        let count = svc.apply_commits(vec![sign_commit_as(ec, 0xD9)], &peer).await.unwrap();
+        assert_eq!(count, 0);
+        assert_eq!(db.get_document(&doc_id).await.unwrap().title, "Theirs");
+    }
+
     #[tokio::test]
     async fn apply_commits_rejects_unsigned_forged_author_and_wrong_signer() {
         // AUTOCOMMIT-001 / P2P-001: commit-envelope authentication.
@@ -2135,6 +3351,7 @@ mod tests {
                     content: "x".into(),
                 },
                 &TEST_PAIR_KEY,
+                Compression::None,
             )
             .unwrap()
         }
@@ -2191,6 +3408,8 @@ mod tests {
                 content: r#"{"body":"hello","images":[]}"#.into(),
             },
             signature: None,
+            device_public_key: None,
+            device_signature: None,
         };
 
         let key = [9u8; 32];
@@ -2224,6 +3443,7 @@ mod tests {
             "2026-01-01T00:00:00Z".into(),
             None,
             &key,
+            Compression::None,
         )
         .unwrap();
         assert!(!row.nonce.is_empty(), "row must carry a real nonce");
@@ -2267,7 +3487,7 @@ mod tests {
         let mut forged = db.get_thread(&tid).await.unwrap();
         forged.name = "Forged".into();
         forged.modified_at = chrono::Utc::now() + chrono::Duration::hours(48);
-        let row = stamp(row_from_thread(&forged, &[7u8; 32]).unwrap(), 99, "device-2");
+        let row = stamp(row_from_thread(&forged, &[7u8; 32], Compression::None).unwrap(), 99, "device-2");
 
         let applied = svc.apply_thread_row(&row, &TEST_PAIR_KEY).await.unwrap();
         assert!(!applied, "far-future row must be rejected (skipped)");
@@ -2290,7 +3510,7 @@ mod tests {
         let mut newer = db.get_thread(&tid).await.unwrap();
         newer.name = "Updated".into();
         newer.modified_at = chrono::Utc::now() + chrono::Duration::seconds(5);
-        let row = stamp(row_from_thread(&newer, &[7u8; 32]).unwrap(), 99, "device-2");
+        let row = stamp(row_from_thread(&newer, &[7u8; 32], Compression::None).unwrap(), 99, "device-2");
 
         let applied = svc.apply_thread_row(&row, &TEST_PAIR_KEY).await.unwrap();
         assert!(applied, "higher-version row should apply");
@@ -2314,7 +3534,7 @@ mod tests {
         newer.name = "Acme Corp".into();
         newer.domains = vec!["acme.com".into()];
         newer.modified_at = chrono::Utc::now() + chrono::Duration::seconds(5);
-        let row = stamp(row_from_entity(&newer, &[7u8; 32]).unwrap(), 99, "device-2");
+        let row = stamp(row_from_entity(&newer, &[7u8; 32], Compression::None).unwrap(), 99, "device-2");
 
         let applied = svc.apply_entity_row(&row, &TEST_PAIR_KEY).await.unwrap();
         assert!(applied, "newer entity row should apply (P1.1: update_entity)");
@@ -2337,7 +3557,7 @@ mod tests {
         let mut forged = db.get_entity(&eid).await.unwrap();
         forged.name = "Forged".into();
         forged.modified_at = chrono::Utc::now() + chrono::Duration::hours(48);
-        let row = stamp(row_from_entity(&forged, &[7u8; 32]).unwrap(), 99, "device-2");
+        let row = stamp(row_from_entity(&forged, &[7u8; 32], Compression::None).unwrap(), 99, "device-2");
 
         let applied = svc.apply_entity_row(&row, &TEST_PAIR_KEY).await.unwrap();
         assert!(!applied, "far-future entity row must be rejected");
@@ -2375,7 +3595,7 @@ mod tests {
         let mut forged = db.get_pii_record(&pid).await.unwrap();
         forged.value_encrypted = "ENC_FORGED".into();
         forged.discovered_at = chrono::Utc::now() + chrono::Duration::hours(48);
-        let row = stamp(row_from_pii_record(&forged, &[7u8; 32]).unwrap(), 99, "device-2");
+        let row = stamp(row_from_pii_record(&forged, &[7u8; 32], Compression::None).unwrap(), 99, "device-2");
 
         let applied = svc.apply_pii_record_row(&row, &TEST_PAIR_KEY).await.unwrap();
         assert!(!applied, "far-future pii row must be rejected (skipped)");
@@ -2401,7 +3621,7 @@ mod tests {
         let mut remote = db.get_thread(&tid).await.unwrap();
         remote.name = "From remote".into();
         remote.modified_at = chrono::Utc::now() - chrono::Duration::hours(3);
-        let row = stamp(row_from_thread(&remote, &[7u8; 32]).unwrap(), 50, "device-2");
+        let row = stamp(row_from_thread(&remote, &[7u8; 32], Compression::None).unwrap(), 50, "device-2");
         assert!(svc.apply_thread_row(&row, &TEST_PAIR_KEY).await.unwrap());
         assert_eq!(db.get_thread(&tid).await.unwrap().name, "From remote");
 
@@ -2410,7 +3630,7 @@ mod tests {
         let mut forged = db.get_thread(&tid).await.unwrap();
         forged.name = "Timestamp forger".into();
         forged.modified_at = chrono::Utc::now() + chrono::Duration::seconds(30);
-        let row = row_from_thread(&forged, &[7u8; 32]).unwrap(); // counter 0
+        let row = row_from_thread(&forged, &[7u8; 32], Compression::None).unwrap(); // counter 0
         assert!(!svc.apply_thread_row(&row, &TEST_PAIR_KEY).await.unwrap());
         assert_eq!(db.get_thread(&tid).await.unwrap().name, "From remote");
     }
@@ -2426,7 +3646,7 @@ mod tests {
 
         let mut v5 = db.get_thread(&tid).await.unwrap();
         v5.name = "Fifth write".into();
-        let row_v5 = stamp(row_from_thread(&v5, &[7u8; 32]).unwrap(), 5, "device-2");
+        let row_v5 = stamp(row_from_thread(&v5, &[7u8; 32], Compression::None).unwrap(), 5, "device-2");
         assert!(svc.apply_thread_row(&row_v5, &TEST_PAIR_KEY).await.unwrap());
 
         // Exact replay of the same row: same stamp → not strictly greater
@@ -2439,7 +3659,7 @@ mod tests {
         // A stale (rolled-back) earlier write from the same device.
         let mut v4 = db.get_thread(&tid).await.unwrap();
         v4.name = "Fourth write (rollback attempt)".into();
-        let row_v4 = stamp(row_from_thread(&v4, &[7u8; 32]).unwrap(), 4, "device-2");
+        let row_v4 = stamp(row_from_thread(&v4, &[7u8; 32], Compression::None).unwrap(), 4, "device-2");
         assert!(
             !svc.apply_thread_row(&row_v4, &TEST_PAIR_KEY).await.unwrap(),
             "stale row must be rejected"
@@ -2459,7 +3679,7 @@ mod tests {
         // Apply a remote write at counter 10.
         let mut remote = db.get_thread(&tid).await.unwrap();
         remote.name = "Remote v10".into();
-        let row = stamp(row_from_thread(&remote, &[7u8; 32]).unwrap(), 10, "device-2");
+        let row = stamp(row_from_thread(&remote, &[7u8; 32], Compression::None).unwrap(), 10, "device-2");
         assert!(svc.apply_thread_row(&row, &TEST_PAIR_KEY).await.unwrap());
 
         // User edits locally — the lazy stamp must merge ABOVE counter 10,
@@ -2526,7 +3746,7 @@ mod tests {
         // 3. Wrong signer: signed with a key that is NOT the one embedded
         // in the claimed sender peer id.
         let t2 = Thread::new("Imposter".into(), String::new());
-        let mut forged = row_from_thread(&t2, &[7u8; 32]).unwrap();
+        let mut forged = row_from_thread(&t2, &[7u8; 32], Compression::None).unwrap();
         forged.id = "thread:imposter".into();
         forged.version_counter = 9;
         forged.version_device = "device-x".into();
@@ -2561,8 +3781,8 @@ mod tests {
 
         // A honestly signs a row for B's thread id, but forges the version
         // stamp: a ghost device with the maximum counter.
-        let mut forged =
-            row_from_thread(&Thread::new("PWNED".into(), String::new()), &TEST_PAIR_KEY).unwrap();
+        let pwned = Thread::new("PWNED".into(), String::new());
+        let mut forged = row_from_thread(&pwned, &TEST_PAIR_KEY, Compression::None).unwrap();
         forged.id = tid.clone();
         forged.version_counter = u64::MAX;
         forged.version_device = "ghost-device-never-existed".into();
@@ -2611,11 +3831,244 @@ mod tests {
         assert!(db_b.list_threads().await.unwrap().is_empty());
     }
 
+    #[tokio::test]
+    async fn successive_pushes_apply_and_redelivery_does_not() {
+        let (db_a, svc_a) = mock_sync_service_with(0xA1, "device-a");
+        let (db_b, svc_b) = mock_sync_service_with(0xB2, "device-b");
+        let a_peer = test_keypair(0xA1).public().to_peer_id();
+        let b_peer = test_keypair(0xB2).public().to_peer_id();
+        register_peer(&svc_a, &b_peer);
+        register_peer(&svc_b, &a_peer);
+
+        let content = |body: &str| crdt::with_body(r#"{"images":[]}"#, body);
+        let mut doc = Document::new("Notes".into(), "thread:t".into(), true);
+        doc.id = sovereign_db::schema::raw_to_thing("document:notes");
+        doc.content = content("one\n");
+        db_a.create_document_with_id(doc).await.unwrap();
+        let sent = svc_a.get_commits_since("document:notes", None, &b_peer).await.unwrap();
+        assert_eq!(svc_b.apply_commits(sent, &a_peer).await.unwrap(), 1);
+
+        for body in ["one\ntwo\n", "one\ntwo\nthree\n"] {
+            db_a.update_document("document:notes", None, Some(&content(body)))
+                .await
+                .unwrap();
+            let sent = svc_a.get_commits_since("document:notes", None, &b_peer).await.unwrap();
+            assert_eq!(svc_b.apply_commits(sent.clone(), &a_peer).await.unwrap(), 1);
+            let on_b = db_b.get_document("document:notes").await.unwrap();
+            assert_eq!(crdt::body_of(&on_b.content), body);
+
+            // The same envelopes again, as a resumed session would resend them.
+            assert_eq!(svc_b.apply_commits(sent, &a_peer).await.unwrap(), 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_body_edits_merge_through_crdt() {
+        let (db_a, svc_a) = mock_sync_service_with(0xA1, "device-a");
+        let (db_b, svc_b) = mock_sync_service_with(0xB2, "device-b");
+        let a_peer = test_keypair(0xA1).public().to_peer_id();
+        let b_peer = test_keypair(0xB2).public().to_peer_id();
+        register_peer(&svc_a, &b_peer);
+        register_peer(&svc_b, &a_peer);
+
+        let content = |body: &str| crdt::with_body(r#"{"images":[]}"#, body);
+        let mut doc = Document::new("Shared".into(), "thread:t".into(), true);
+        doc.id = sovereign_db::schema::raw_to_thing("document:shared");
+        doc.content = content("intro\nmiddle\nend\n");
+        db_a.create_document_with_id(doc).await.unwrap();
+
+        // First sync: B receives the document and A's history.
+        let sent = svc_a.get_commits_since("document:shared", None, &b_peer).await.unwrap();
+        assert_eq!(svc_b.apply_commits(sent, &a_peer).await.unwrap(), 1);
+
+        // Both edit different lines before the next sync.
+        db_a.update_document("document:shared", None, Some(&content("INTRO\nmiddle\nend\n")))
+            .await
+            .unwrap();
+        db_b.update_document("document:shared", None, Some(&content("intro\nmiddle\nEND\n")))
+            .await
+            .unwrap();
+
+        let sent = svc_a.get_commits_since("document:shared", None, &b_peer).await.unwrap();
+        assert_eq!(svc_b.apply_commits(sent, &a_peer).await.unwrap(), 1);
+        let merged = db_b.get_document("document:shared").await.unwrap();
+        assert_eq!(crdt::body_of(&merged.content), "INTRO\nmiddle\nEND\n");
+        assert!(svc_b.take_merge_conflicts().is_empty());
+
+        // And back: A ends up with the same text.
+        let sent = svc_b.get_commits_since("document:shared", None, &a_peer).await.unwrap();
+        svc_a.apply_commits(sent, &b_peer).await.unwrap();
+        let on_a = db_a.get_document("document:shared").await.unwrap();
+        assert_eq!(crdt::body_of(&on_a.content), "INTRO\nmiddle\nEND\n");
+
+        // Editing the same line on both sides still merges, but is flagged.
+        db_a.update_document("document:shared", None, Some(&content("INTRO\nleft\nEND\n")))
+            .await
+            .unwrap();
+        db_b.update_document("document:shared", None, Some(&content("INTRO\nright\nEND\n")))
+            .await
+            .unwrap();
+        let sent = svc_a.get_commits_since("document:shared", None, &b_peer).await.unwrap();
+        svc_b.apply_commits(sent, &a_peer).await.unwrap();
+        let conflicts = svc_b.take_merge_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].local, "INTRO\nright\nEND\n");
+        assert_eq!(conflicts[0].remote, "INTRO\nleft\nEND\n");
+        assert_eq!(conflicts[0].base.as_deref(), Some("INTRO\nmiddle\nEND\n"));
+    }
+
+    #[tokio::test]
+    async fn a_sent_state_becomes_the_base_only_once_delivered() {
+        let (db_a, svc_a) = mock_sync_service_with(0xA1, "device-a");
+        let b_peer = test_keypair(0xB2).public().to_peer_id();
+        register_peer(&svc_a, &b_peer);
+        let base = |svc: &SyncService| {
+            svc.crdt.lock().unwrap().base("document:draft").map(str::to_string)
+        };
+
+        let mut doc = Document::new("Draft".into(), "thread:t".into(), true);
+        doc.id = sovereign_db::schema::raw_to_thing("document:draft");
+        doc.content = crdt::with_body(r#"{"images":[]}"#, "first\n");
+        db_a.create_document_with_id(doc).await.unwrap();
+
+        // Lost on the way: the base stays unset.
+        svc_a.get_commits_since("document:draft", None, &b_peer).await.unwrap();
+        assert_eq!(base(&svc_a), None);
+        svc_a.undelivered(&b_peer);
+        svc_a.delivered(&b_peer).unwrap();
+        assert_eq!(base(&svc_a), None);
+
+        // Acknowledged: now it is what both sides last agreed on.
+        svc_a.get_commits_since("document:draft", None, &b_peer).await.unwrap();
+        svc_a.delivered(&b_peer).unwrap();
+        assert_eq!(base(&svc_a).as_deref(), Some("first\n"));
+    }
+
+    #[tokio::test]
+    async fn media_follows_the_document_in_chunks() {
+        let (db_a, svc_a) = mock_sync_service_with(0xA1, "device-a");
+        let (_db_b, svc_b) = mock_sync_service_with(0xB2, "device-b");
+        let dir = std::env::temp_dir().join(format!(
+            "sovereign-blob-sync-test-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let svc_a = svc_a.with_blob_store(BlobStore::open(dir.join("a-blobs")));
+        let svc_b = svc_b.with_blob_store(BlobStore::open(dir.join("b-blobs")));
+        let a_peer = test_keypair(0xA1).public().to_peer_id();
+        let b_peer = test_keypair(0xB2).public().to_peer_id();
+        register_peer(&svc_a, &b_peer);
+        register_peer(&svc_b, &a_peer);
+
+        // Bigger than one message, so the transfer takes several rounds.
+        let bytes: Vec<u8> = (0..MAX_BLOB_MESSAGE_BYTES + 1000).map(|i| (i % 253) as u8).collect();
+        let hash = crate::backup::sha256_hex(&bytes);
+        std::fs::create_dir_all(dir.join("a-blobs")).unwrap();
+        let photo = dir.join("a-blobs").join(&hash);
+        std::fs::write(&photo, &bytes).unwrap();
+        // A file outside A's store is listed too, and must never be shipped.
+        let secret = dir.join("id_ed25519");
+        std::fs::write(&secret, b"private key").unwrap();
+        let mut doc = Document::new("Trip".into(), "thread:t".into(), true);
+        doc.id = sovereign_db::schema::raw_to_thing("document:trip");
+        doc.content = serde_json::json!({
+            "body": "",
+            "images": [{ "path": photo.to_str().unwrap() }, { "path": secret.to_str().unwrap() }]
+        })
+        .to_string();
+        db_a.create_document_with_id(doc).await.unwrap();
+        assert_eq!(svc_a.shared_blobs(&b_peer).await.unwrap(), vec![hash.clone()]);
+
+        let sent = svc_a.get_commits_since("document:trip", None, &b_peer).await.unwrap();
+        svc_b.apply_commits(sent, &a_peer).await.unwrap();
+        assert!(svc_b.blobs.resolve(photo.to_str().unwrap()).is_none());
+        assert_eq!(svc_b.missing_blobs().len(), 1);
+
+        // The outside file's hash is refused even when asked for directly.
+        let secret_hash = crate::backup::sha256_hex(b"private key");
+        let want = BlobWant { hash: secret_hash.clone(), offset: 0 };
+        let (_, unavailable) = svc_a.seal_blob_chunks(&[want], &b_peer).await.unwrap();
+        assert_eq!(unavailable, vec![secret_hash]);
+
+        // Hashes no document shares with B are refused.
+        let stray = BlobWant { hash: "0".repeat(64), offset: 0 };
+        let (_, unavailable) = svc_a.seal_blob_chunks(&[stray], &b_peer).await.unwrap();
+        assert_eq!(unavailable, vec!["0".repeat(64)]);
+
+        let mut rounds = 0;
+        while !svc_b.missing_blobs().is_empty() {
+            let (chunks, unavailable) = svc_a
+                .seal_blob_chunks(&svc_b.missing_blobs(), &b_peer)
+                .await
+                .unwrap();
+            assert!(unavailable.is_empty());
+            svc_b.apply_blob_chunks(chunks, &a_peer).unwrap();
+            rounds += 1;
+            assert!(rounds < 10, "transfer must make progress");
+        }
+        assert!(rounds >= 2);
+        let local = svc_b.blobs.resolve(photo.to_str().unwrap()).unwrap();
+        assert!(local.starts_with(dir.join("b-blobs")));
+        assert_eq!(std::fs::read(local).unwrap(), bytes);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn attachments_follow_the_document() {
+        let (db_a, svc_a) = mock_sync_service_with(0xA1, "device-a");
+        let (_db_b, svc_b) = mock_sync_service_with(0xB2, "device-b");
+        let dir = std::env::temp_dir().join(format!(
+            "sovereign-attachment-sync-test-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        std::fs::create_dir_all(dir.join("a-blobs")).unwrap();
+        let svc_a = svc_a.with_blob_store(BlobStore::open(dir.join("a-blobs")));
+        let svc_b = svc_b.with_blob_store(BlobStore::open(dir.join("b-blobs")));
+        let a_peer = test_keypair(0xA1).public().to_peer_id();
+        let b_peer = test_keypair(0xB2).public().to_peer_id();
+        register_peer(&svc_a, &b_peer);
+        register_peer(&svc_b, &a_peer);
+
+        let bytes = b"%PDF-1.7 invoice".to_vec();
+        let hash = crate::backup::sha256_hex(&bytes);
+        let file = dir.join("a-blobs").join(&hash);
+        std::fs::write(&file, &bytes).unwrap();
+        let mut doc = Document::new("Invoices".into(), "thread:t".into(), true);
+        doc.id = sovereign_db::schema::raw_to_thing("document:invoices");
+        doc.content = serde_json::json!({
+            "body": "",
+            "files": [{ "path": file.to_str().unwrap(), "name": "Invoice.pdf" }]
+        })
+        .to_string();
+        db_a.create_document_with_id(doc).await.unwrap();
+        assert_eq!(svc_a.shared_blobs(&b_peer).await.unwrap(), vec![hash.clone()]);
+
+        let sent = svc_a.get_commits_since("document:invoices", None, &b_peer).await.unwrap();
+        svc_b.apply_commits(sent, &a_peer).await.unwrap();
+        assert_eq!(svc_b.missing_blobs().len(), 1);
+        let (chunks, unavailable) = svc_a
+            .seal_blob_chunks(&svc_b.missing_blobs(), &b_peer)
+            .await
+            .unwrap();
+        assert!(unavailable.is_empty());
+        svc_b.apply_blob_chunks(chunks, &a_peer).unwrap();
+        assert!(svc_b.missing_blobs().is_empty());
+        let local = svc_b.blobs.resolve(file.to_str().unwrap()).unwrap();
+        assert_eq!(std::fs::read(local).unwrap(), bytes);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn row_sig_binds_table_and_delete_marker() {
         let key = [7u8; 32];
         let t = Thread::new("X".into(), String::new());
-        let mut row = row_from_thread(&t, &key).unwrap();
+        let mut row = row_from_thread(&t, &key, Compression::None).unwrap();
         row.version_counter = 3;
         row.version_device = "device-a".into();
         let kp = test_keypair(0xA1);
@@ -2685,6 +4138,8 @@ mod tests {
             (SyncTable::Milestone, m.milestones.iter().map(|e| e.id.clone()).collect()),
             (SyncTable::Relationship, m.relationships.iter().map(|e| e.id.clone()).collect()),
             (SyncTable::SuggestedLink, m.suggested_links.iter().map(|e| e.id.clone()).collect()),
+            (SyncTable::Favorite, m.favorites.iter().map(|e| e.id.clone()).collect()),
+            (SyncTable::Sensitivity, m.sensitivities.iter().map(|e| e.id.clone()).collect()),
         ]
     }
 
@@ -2817,7 +4272,8 @@ mod tests {
 
         // A deleted-contact row soft-deletes B's copy.
         p.db_a.soft_delete_contact(&cid).await.unwrap();
-        let row = row_from_contact(&p.db_a.get_contact(&cid).await.unwrap(), &TEST_PAIR_KEY).unwrap();
+        let contact = p.db_a.get_contact(&cid).await.unwrap();
+        let row = row_from_contact(&contact, &TEST_PAIR_KEY, Compression::None).unwrap();
         // P2P-001: this row goes through apply_rows as sender A, so its
         // version_device must be A's verified peer id.
         let mut row = stamp(row, 99, &p.a_peer.to_string());
@@ -2918,6 +4374,74 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn favorites_sync_pins_and_unpins() {
+        let p = sync_pair();
+        let doc = p
+            .db_a
+            .create_document(Document::new("Roadmap".into(), "thread:t".into(), true))
+            .await
+            .unwrap();
+        let did = doc.id_string().unwrap();
+        // The document itself reaches B on the commit track.
+        p.db_b.create_document_with_id(doc).await.unwrap();
+        assert!(p.svc_a.build_manifest().await.unwrap().favorites.is_empty());
+
+        // Pinned on A: B picks the pin up without the document changing.
+        p.db_a.set_favorite(&did, true, chrono::Utc::now()).await.unwrap();
+        let manifest_a = p.svc_a.build_manifest().await.unwrap();
+        let manifest_b = p.svc_b.build_manifest().await.unwrap();
+        let diffs = crate::sync_engine::compute_all_row_diffs(&manifest_b, &manifest_a);
+        assert_eq!(diffs[&SyncTable::Favorite].need_from_remote, vec![did.clone()]);
+        assert!(crate::sync_engine::compute_diff(&manifest_b, &manifest_a).need_from_remote.is_empty());
+        let (w, _) = pull_table(&p, SyncTable::Favorite, &[did.clone()]).await;
+        assert_eq!(w, 1);
+        assert!(p.db_b.get_document(&did).await.unwrap().favorite);
+
+        // Unpinned on B: the unpin beats A's older pin.
+        p.db_b.set_favorite(&did, false, chrono::Utc::now()).await.unwrap();
+        let rows = p.svc_b.get_rows(SyncTable::Favorite, &[did.clone()], &p.a_peer).await.unwrap();
+        let (w, _) = p.svc_a.apply_rows(SyncTable::Favorite, rows, &p.b_peer).await.unwrap();
+        assert_eq!(w, 1);
+        assert!(!p.db_a.get_document(&did).await.unwrap().favorite);
+
+        // Replaying A's side now changes nothing on B.
+        let (w, _) = pull_table(&p, SyncTable::Favorite, &[did.clone()]).await;
+        assert_eq!(w, 0);
+        assert!(!p.db_b.get_document(&did).await.unwrap().favorite);
+    }
+
+    #[tokio::test]
+    async fn sensitivity_syncs_up_but_never_down() {
+        let p = sync_pair();
+        let doc = p
+            .db_a
+            .create_document(Document::new("Diagnosis".into(), "thread:t".into(), true))
+            .await
+            .unwrap();
+        let did = doc.id_string().unwrap();
+        p.db_b.create_document_with_id(doc).await.unwrap();
+        assert!(p.svc_a.build_manifest().await.unwrap().sensitivities.is_empty());
+
+        p.db_a
+            .set_sensitivity(&did, Sensitivity::Sealed, chrono::Utc::now())
+            .await
+            .unwrap();
+        let (w, _) = pull_table(&p, SyncTable::Sensitivity, &[did.clone()]).await;
+        assert_eq!(w, 1);
+        assert_eq!(p.db_b.get_document(&did).await.unwrap().sensitivity, Sensitivity::Sealed);
+
+        // Lowered on B later: a peer can't unseal it on A.
+        p.db_b
+            .set_sensitivity(&did, Sensitivity::Normal, chrono::Utc::now())
+            .await
+            .unwrap();
+        let rows = p.svc_b.get_rows(SyncTable::Sensitivity, &[did.clone()], &p.a_peer).await.unwrap();
+        let (w, _) = p.svc_a.apply_rows(SyncTable::Sensitivity, rows, &p.b_peer).await.unwrap();
+        assert_eq!(w, 0);
+        assert_eq!(p.db_a.get_document(&did).await.unwrap().sensitivity, Sensitivity::Sealed);
+    }
+
     #[tokio::test]
     async fn milestones_and_relationships_are_append_only() {
         let p = sync_pair();
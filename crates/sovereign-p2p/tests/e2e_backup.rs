@@ -80,6 +80,7 @@ async fn spawn_node(device_id: &str, seed: [u8; 32], with_host: bool) -> Harness
         device_name: device_id.into(),
         enable_mdns: false,
         wifi_only: false,
+        serve_viewer: false,
     };
     let peer_id = kp.public().to_peer_id();
     let mut node = SovereignNode::new(&cfg, kp, event_tx, cmd_rx, svc, host.clone())
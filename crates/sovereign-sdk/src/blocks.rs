@@ -0,0 +1,645 @@
+//! Structured blocks inside a Markdown body: tables, task checklists,
+//! callouts and embeds of other documents.
+//!
+//! The body stays the stored form. [`parse`] lifts the blocks it
+//! recognizes out of it and keeps everything else as [`Block::Text`];
+//! [`render`] writes blocks back as Markdown. A parsed block remembers
+//! the text it came from and renders it unchanged until the block is
+//! edited, so `render(&parse(body)) == body` for every body.
+//!
+//! The syntax is the one GitHub and Obsidian use:
+//!
+//! ```text
+//! | Name | Qty |        - [ ] open task       > [!NOTE] Title
+//! | :--- | --: |        - [x] done task       > callout body
+//! | Tea  | 2   |          - [ ] nested
+//!
+//! ![[document:abc123]]  (a line of its own: embed that document)
+//! ```
+//!
+//! A checklist item is a task; `due:2026-03-01` (or the Obsidian Tasks
+//! `📅 2026-03-01`) anywhere in its text gives it a due date. Lines
+//! inside fenced code blocks are never lifted.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Block {
+    /// Markdown this module doesn't model, verbatim.
+    Text {
+        text: String,
+    },
+    Table(Table),
+    Checklist(Checklist),
+    Callout(Callout),
+    Embed(Embed),
+}
+
+/// The text a parsed block came from. Equality ignores it: two blocks
+/// with the same content are equal however they were written.
+#[derive(Clone, Debug, Default)]
+struct Source(Option<String>);
+
+impl PartialEq for Source {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Align {
+    #[default]
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+/// A pipe table. Cells are Markdown source, with `|` escaped as `\|`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Table {
+    pub header: Vec<String>,
+    /// One entry per header cell.
+    pub align: Vec<Align>,
+    pub rows: Vec<Vec<String>>,
+    #[serde(skip)]
+    source: Source,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Checklist {
+    pub items: Vec<ChecklistItem>,
+    #[serde(skip)]
+    source: Source,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ChecklistItem {
+    pub checked: bool,
+    pub text: String,
+    /// Nesting level; 0 is the outermost list.
+    #[serde(default)]
+    pub depth: usize,
+}
+
+impl ChecklistItem {
+    /// The date of the item's `due:` or `📅` marker, if it has one.
+    pub fn due(&self) -> Option<NaiveDate> {
+        ["due:", "📅"].iter().find_map(|marker| {
+            let at = self.text.find(marker)?;
+            let date = self.text[at + marker.len()..].trim_start().get(..10)?;
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CalloutKind {
+    Note,
+    Tip,
+    Important,
+    Warning,
+    Caution,
+}
+
+impl CalloutKind {
+    pub const ALL: [CalloutKind; 5] = [
+        CalloutKind::Note,
+        CalloutKind::Tip,
+        CalloutKind::Important,
+        CalloutKind::Warning,
+        CalloutKind::Caution,
+    ];
+
+    /// The marker between `[!` and `]`.
+    pub fn marker(self) -> &'static str {
+        match self {
+            CalloutKind::Note => "NOTE",
+            CalloutKind::Tip => "TIP",
+            CalloutKind::Important => "IMPORTANT",
+            CalloutKind::Warning => "WARNING",
+            CalloutKind::Caution => "CAUTION",
+        }
+    }
+
+    fn from_marker(s: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|k| k.marker().eq_ignore_ascii_case(s))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Callout {
+    pub kind: CalloutKind,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Markdown, without the `> ` prefixes.
+    #[serde(default)]
+    pub body: String,
+    #[serde(skip)]
+    source: Source,
+}
+
+/// Another document shown inline, by its `document:…` id.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Embed {
+    pub document_id: String,
+    #[serde(skip)]
+    source: Source,
+}
+
+impl Table {
+    pub fn new(header: Vec<String>, rows: Vec<Vec<String>>) -> Self {
+        let align = vec![Align::None; header.len()];
+        Self {
+            header,
+            align,
+            rows,
+            source: Source::default(),
+        }
+    }
+}
+
+impl Checklist {
+    pub fn new(items: Vec<ChecklistItem>) -> Self {
+        Self {
+            items,
+            source: Source::default(),
+        }
+    }
+}
+
+impl Callout {
+    pub fn new(kind: CalloutKind, title: Option<String>, body: impl Into<String>) -> Self {
+        Self {
+            kind,
+            title,
+            body: body.into(),
+            source: Source::default(),
+        }
+    }
+}
+
+impl Embed {
+    pub fn new(document_id: impl Into<String>) -> Self {
+        Self {
+            document_id: document_id.into(),
+            source: Source::default(),
+        }
+    }
+}
+
+/// Split a body into blocks.
+pub fn parse(body: &str) -> Vec<Block> {
+    let lines: Vec<&str> = body.split('\n').collect();
+    let mut blocks = Vec::new();
+    let mut text: Vec<&str> = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if let Some(open) = fence {
+            if line.trim_start().starts_with(open) {
+                fence = None;
+            }
+            text.push(line);
+            i += 1;
+            continue;
+        }
+        if let Some(open) = fence_marker(line) {
+            fence = Some(open);
+            text.push(line);
+            i += 1;
+            continue;
+        }
+        match lift(&lines[i..]) {
+            Some((block, used)) => {
+                if !text.is_empty() {
+                    blocks.push(Block::Text {
+                        text: text.join("\n"),
+                    });
+                    text.clear();
+                }
+                blocks.push(block);
+                i += used;
+            }
+            None => {
+                text.push(line);
+                i += 1;
+            }
+        }
+    }
+    if !text.is_empty() || blocks.is_empty() {
+        blocks.push(Block::Text {
+            text: text.join("\n"),
+        });
+    }
+    blocks
+}
+
+/// Write blocks back as a Markdown body.
+pub fn render(blocks: &[Block]) -> String {
+    blocks
+        .iter()
+        .map(render_block)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The ids of the documents a body embeds, in order, without repeats.
+pub fn embeds(body: &str) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for block in parse(body) {
+        if let Block::Embed(e) = block {
+            if !out.contains(&e.document_id) {
+                out.push(e.document_id);
+            }
+        }
+    }
+    out
+}
+
+/// Every checklist item of the body in order, the numbering
+/// [`set_checked`] uses.
+pub fn tasks(body: &str) -> Vec<ChecklistItem> {
+    parse(body)
+        .into_iter()
+        .filter_map(|b| match b {
+            Block::Checklist(c) => Some(c.items),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+/// Tick or untick the `index`th checklist item of the body, counting
+/// across all checklists in order. Only that item's box changes; the
+/// rest of the body is left byte for byte. `None` when there is no such
+/// item.
+pub fn set_checked(body: &str, index: usize, checked: bool) -> Option<String> {
+    let mut first_line = 0;
+    let mut remaining = index;
+    let mut target = None;
+    for block in parse(body) {
+        if let Block::Checklist(c) = &block {
+            if remaining < c.items.len() {
+                target = Some(first_line + remaining);
+                break;
+            }
+            remaining -= c.items.len();
+        }
+        first_line += render_block(&block).matches('\n').count() + 1;
+    }
+    let mut lines: Vec<String> = body.split('\n').map(str::to_string).collect();
+    let line = lines.get_mut(target?)?;
+    if checklist_item(line)?.checked != checked {
+        // The first `[` on an item line opens its box.
+        let open = line.find('[')?;
+        line.replace_range(open + 1..open + 2, if checked { "x" } else { " " });
+    }
+    Some(lines.join("\n"))
+}
+
+fn render_block(block: &Block) -> String {
+    let source = match block {
+        Block::Text { text } => return text.clone(),
+        Block::Table(t) => &t.source,
+        Block::Checklist(c) => &c.source,
+        Block::Callout(c) => &c.source,
+        Block::Embed(e) => &e.source,
+    };
+    // Reuse the original text while it still says the same thing.
+    if let Some(src) = &source.0 {
+        let lines: Vec<&str> = src.split('\n').collect();
+        if lift(&lines).is_some_and(|(b, used)| used == lines.len() && b == *block) {
+            return src.clone();
+        }
+    }
+    match block {
+        Block::Text { .. } => unreachable!(),
+        Block::Table(t) => render_table(t),
+        Block::Checklist(c) => render_checklist(c),
+        Block::Callout(c) => render_callout(c),
+        Block::Embed(e) => format!("![[{}]]", e.document_id),
+    }
+}
+
+/// The block starting at `lines[0]` and how many lines it spans.
+fn lift(lines: &[&str]) -> Option<(Block, usize)> {
+    let (block, used) = lift_embed(lines)
+        .or_else(|| lift_checklist(lines))
+        .or_else(|| lift_callout(lines))
+        .or_else(|| lift_table(lines))?;
+    let source = Source(Some(lines[..used].join("\n")));
+    let block = match block {
+        Block::Table(t) => Block::Table(Table { source, ..t }),
+        Block::Checklist(c) => Block::Checklist(Checklist { source, ..c }),
+        Block::Callout(c) => Block::Callout(Callout { source, ..c }),
+        Block::Embed(e) => Block::Embed(Embed { source, ..e }),
+        text @ Block::Text { .. } => text,
+    };
+    Some((block, used))
+}
+
+/// The closing marker for a line that opens a fenced code block.
+pub(crate) fn fence_marker(line: &str) -> Option<&'static str> {
+    let t = line.trim_start();
+    if t.starts_with("```") {
+        Some("```")
+    } else if t.starts_with("~~~") {
+        Some("~~~")
+    } else {
+        None
+    }
+}
+
+fn lift_embed(lines: &[&str]) -> Option<(Block, usize)> {
+    let id = lines[0].strip_prefix("![[")?.strip_suffix("]]")?;
+    if !id.starts_with("document:") || id.contains(['[', ']']) {
+        return None;
+    }
+    Some((Block::Embed(Embed::new(id)), 1))
+}
+
+fn checklist_item(line: &str) -> Option<ChecklistItem> {
+    let rest = line.trim_start_matches(' ');
+    let indent = line.len() - rest.len();
+    let rest = rest.strip_prefix(['-', '*', '+'])?.strip_prefix(' ')?;
+    let (checked, rest) = if let Some(r) = rest.strip_prefix("[ ]") {
+        (false, r)
+    } else if let Some(r) = rest
+        .strip_prefix("[x]")
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        (true, r)
+    } else {
+        return None;
+    };
+    let text = if rest.is_empty() {
+        ""
+    } else {
+        rest.strip_prefix(' ')?
+    };
+    Some(ChecklistItem {
+        checked,
+        text: text.to_string(),
+        depth: indent / 2,
+    })
+}
+
+fn lift_checklist(lines: &[&str]) -> Option<(Block, usize)> {
+    let items: Vec<ChecklistItem> = lines.iter().map_while(|l| checklist_item(l)).collect();
+    if items.is_empty() {
+        return None;
+    }
+    let used = items.len();
+    Some((Block::Checklist(Checklist::new(items)), used))
+}
+
+fn render_checklist(c: &Checklist) -> String {
+    c.items
+        .iter()
+        .map(|item| {
+            let mark = if item.checked { "[x]" } else { "[ ]" };
+            let indent = "  ".repeat(item.depth);
+            if item.text.is_empty() {
+                format!("{indent}- {mark}")
+            } else {
+                format!("{indent}- {mark} {}", item.text)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn lift_callout(lines: &[&str]) -> Option<(Block, usize)> {
+    let head = lines[0].strip_prefix("> [!")?;
+    let (marker, title) = head.split_once(']')?;
+    let kind = CalloutKind::from_marker(marker)?;
+    let title = title.trim();
+    let title = (!title.is_empty()).then(|| title.to_string());
+    let body: Vec<&str> = lines[1..]
+        .iter()
+        .map_while(|l| l.strip_prefix("> ").or_else(|| l.strip_prefix('>')))
+        .collect();
+    let used = 1 + body.len();
+    Some((
+        Block::Callout(Callout::new(kind, title, body.join("\n"))),
+        used,
+    ))
+}
+
+fn render_callout(c: &Callout) -> String {
+    let mut out = format!("> [!{}]", c.kind.marker());
+    if let Some(title) = &c.title {
+        out.push(' ');
+        out.push_str(title);
+    }
+    if !c.body.is_empty() {
+        for line in c.body.split('\n') {
+            out.push('\n');
+            if line.is_empty() {
+                out.push('>');
+            } else {
+                out.push_str("> ");
+                out.push_str(line);
+            }
+        }
+    }
+    out
+}
+
+/// The cells of a table row: split on unescaped pipes, outer pipes dropped.
+fn cells(line: &str) -> Vec<String> {
+    let t = line.trim();
+    let t = t.strip_prefix('|').unwrap_or(t);
+    let t = match t.strip_suffix('|') {
+        Some(inner) if !inner.ends_with('\\') => inner,
+        _ => t,
+    };
+    let mut out = Vec::new();
+    let mut cell = String::new();
+    let mut escaped = false;
+    for c in t.chars() {
+        if c == '|' && !escaped {
+            out.push(cell.trim().to_string());
+            cell.clear();
+        } else {
+            cell.push(c);
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    out.push(cell.trim().to_string());
+    out
+}
+
+fn alignments(line: &str) -> Option<Vec<Align>> {
+    if !line.contains('|') {
+        return None;
+    }
+    cells(line)
+        .iter()
+        .map(|c| {
+            let left = c.starts_with(':');
+            let right = c.ends_with(':');
+            let dashes = c.trim_matches(':');
+            if dashes.is_empty() || !dashes.chars().all(|ch| ch == '-') {
+                return None;
+            }
+            Some(match (left, right) {
+                (true, true) => Align::Center,
+                (true, false) => Align::Left,
+                (false, true) => Align::Right,
+                (false, false) => Align::None,
+            })
+        })
+        .collect()
+}
+
+fn lift_table(lines: &[&str]) -> Option<(Block, usize)> {
+    if lines.len() < 2 || !lines[0].contains('|') {
+        return None;
+    }
+    let header = cells(lines[0]);
+    let align = alignments(lines[1])?;
+    if align.len() != header.len() {
+        return None;
+    }
+    let rows: Vec<Vec<String>> = lines[2..]
+        .iter()
+        .take_while(|l| l.contains('|') && !l.trim().is_empty())
+        .map(|l| cells(l))
+        .collect();
+    let used = 2 + rows.len();
+    Some((
+        Block::Table(Table {
+            header,
+            align,
+            rows,
+            source: Source::default(),
+        }),
+        used,
+    ))
+}
+
+fn render_table(t: &Table) -> String {
+    let row = |cells: &[String]| format!("| {} |", cells.join(" | "));
+    let delimiter: Vec<String> = (0..t.header.len())
+        .map(|i| {
+            match t.align.get(i).copied().unwrap_or_default() {
+                Align::None => "---",
+                Align::Left => ":---",
+                Align::Center => ":---:",
+                Align::Right => "---:",
+            }
+            .to_string()
+        })
+        .collect();
+    let mut out = vec![row(&t.header), row(&delimiter)];
+    out.extend(t.rows.iter().map(|r| row(r)));
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "# Plan\n\n\
+        |Item|  Qty |\n|:--|--:|\n| Tea | 2 |\n\n\
+        * [ ] buy tea\n  - [X] boil water\n\n\
+        > [!warning] Hot\n> Mind the kettle.\n>\n> Really.\n\n\
+        ![[document:abc]]\n\
+        ```\n- [ ] not a task\n```\n";
+
+    #[test]
+    fn parse_lifts_each_kind_and_renders_back_unchanged() {
+        let blocks = parse(DOC);
+        let kinds: Vec<&str> = blocks
+            .iter()
+            .map(|b| match b {
+                Block::Text { .. } => "text",
+                Block::Table(_) => "table",
+                Block::Checklist(_) => "checklist",
+                Block::Callout(_) => "callout",
+                Block::Embed(_) => "embed",
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                "text",
+                "table",
+                "text",
+                "checklist",
+                "text",
+                "callout",
+                "text",
+                "embed",
+                "text"
+            ]
+        );
+        let Block::Table(t) = &blocks[1] else {
+            unreachable!()
+        };
+        assert_eq!(t.header, ["Item", "Qty"]);
+        assert_eq!(t.align, [Align::Left, Align::Right]);
+        assert_eq!(t.rows, [["Tea", "2"]]);
+        let Block::Callout(c) = &blocks[5] else {
+            unreachable!()
+        };
+        assert_eq!(c.kind, CalloutKind::Warning);
+        assert_eq!(c.title.as_deref(), Some("Hot"));
+        assert_eq!(c.body, "Mind the kettle.\n\nReally.");
+        assert_eq!(render(&blocks), DOC);
+        assert_eq!(render(&parse("")), "");
+    }
+
+    #[test]
+    fn edited_blocks_render_canonically() {
+        let mut blocks = parse(DOC);
+        if let Block::Table(t) = &mut blocks[1] {
+            t.rows.push(vec!["Milk".into(), "1".into()]);
+        }
+        let out = render(&blocks);
+        assert!(out.contains("| Item | Qty |\n| :--- | ---: |\n| Tea | 2 |\n| Milk | 1 |\n"));
+        assert_eq!(parse(&out), blocks);
+    }
+
+    #[test]
+    fn set_checked_touches_only_that_item() {
+        let out = set_checked(DOC, 1, false).unwrap();
+        assert_eq!(out, DOC.replace("- [X] boil", "- [ ] boil"));
+        assert_eq!(set_checked(DOC, 1, true).unwrap(), DOC);
+        assert!(set_checked(DOC, 2, true).is_none());
+        assert_eq!(embeds(DOC), ["document:abc"]);
+    }
+
+    #[test]
+    fn tasks_read_due_markers() {
+        let body =
+            "- [ ] file taxes due:2026-04-15\n- [ ] renew 📅 2026-05-01\n- [x] no date due:soon";
+        let due: Vec<_> = tasks(body).iter().map(ChecklistItem::due).collect();
+        assert_eq!(
+            due,
+            [
+                NaiveDate::from_ymd_opt(2026, 4, 15),
+                NaiveDate::from_ymd_opt(2026, 5, 1),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn blocks_serialize_without_their_source() {
+        let block = Block::Embed(Embed::new("document:x"));
+        let json = serde_json::to_string(&block).unwrap();
+        assert_eq!(json, r#"{"type":"embed","document_id":"document:x"}"#);
+        let back: Block = serde_json::from_str(&json).unwrap();
+        assert_eq!(render(&[back]), "![[document:x]]");
+    }
+}
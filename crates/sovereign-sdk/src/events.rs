@@ -0,0 +1,151 @@
+//! Notifications a subscribed daemon client receives, named and shaped
+//! like the app's frontend events. Each arrives as JSON-RPC
+//! `{"method":"event","params":{"event":…,"payload":…}}`; `params` is an
+//! [`Event`]. The payload types below are the stable ones; events not
+//! listed carry payloads that may still change.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Every [`category`], for filtering.
+pub const CATEGORIES: [&str; 5] = ["db", "sync", "comms", "ai", "session"];
+
+/// One notification: the event name and its payload (`null` for events
+/// that carry none).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    pub event: String,
+    #[serde(default)]
+    pub payload: Value,
+}
+
+impl Event {
+    pub fn category(&self) -> &'static str {
+        category(&self.event)
+    }
+
+    /// The payload as `T`, e.g. a [`DocumentCreatedPayload`] for
+    /// `document-created`.
+    pub fn payload_as<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        T::deserialize(&self.payload)
+    }
+}
+
+/// The group an event belongs to: `db`, `sync`, `comms`, `ai`, or
+/// `session` for locks, wipes, security alerts and config reloads.
+pub fn category(event: &str) -> &'static str {
+    match event {
+        "document-created"
+        | "document-updated"
+        | "document-deleted"
+        | "document-moved"
+        | "thread-created"
+        | "thread-renamed"
+        | "thread-deleted"
+        | "maintenance-finished" => "db",
+        "sync-status" | "sync-conflict" | "device-paired" | "pairing-failed" => "sync",
+        "new-messages" | "contact-created" => "comms",
+        "session-locked" | "device-wiped" | "decoy-mode" | "key-usage-alert"
+        | "injection-detected" | "config-reloaded" | "config-invalid" => "session",
+        _ => "ai",
+    }
+}
+
+/// `chat-response`: the assistant's reply to a chat message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatResponsePayload {
+    pub text: String,
+}
+
+/// `action-proposed`: the assistant wants to act and waits for approval.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionProposedPayload {
+    pub action: String,
+    pub level: String,
+    pub description: String,
+    pub doc_id: Option<String>,
+    pub thread_id: Option<String>,
+}
+
+/// `action-executed`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionExecutedPayload {
+    pub action: String,
+    pub success: bool,
+}
+
+/// `action-rejected`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionRejectedPayload {
+    pub action: String,
+    pub reason: String,
+}
+
+/// `document-created`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentCreatedPayload {
+    pub doc_id: String,
+    pub title: String,
+    pub thread_id: String,
+}
+
+/// `document-updated` and `document-deleted`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentChangedPayload {
+    pub doc_id: String,
+}
+
+/// `document-moved`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentMovedPayload {
+    pub doc_id: String,
+    pub new_thread_id: String,
+}
+
+/// `thread-renamed`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThreadRenamedPayload {
+    pub thread_id: String,
+    pub name: String,
+}
+
+/// `thread-deleted`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThreadDeletedPayload {
+    pub thread_id: String,
+}
+
+/// `new-messages`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewMessagesPayload {
+    pub channel: String,
+    pub count: u32,
+    pub conversation_id: String,
+}
+
+/// `contact-created`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContactCreatedPayload {
+    pub contact_id: String,
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_decode_from_notification_params() {
+        let params = r#"{"event":"document-created","payload":{"doc_id":"document:1","title":"Notes","thread_id":"thread:1"}}"#;
+        let event: Event = serde_json::from_str(params).unwrap();
+        assert_eq!(event.category(), "db");
+        let created: DocumentCreatedPayload = event.payload_as().unwrap();
+        assert_eq!(created.title, "Notes");
+        assert!(event.payload_as::<ChatResponsePayload>().is_err());
+
+        let bare: Event = serde_json::from_str(r#"{"event":"session-locked"}"#).unwrap();
+        assert_eq!(bare.payload, Value::Null);
+        assert_eq!(bare.category(), "session");
+    }
+}
@@ -0,0 +1,33 @@
+//! The public API of Sovereign GE, for skills built outside this
+//! repository and for alternative frontends.
+//!
+//! This crate follows semver: a breaking change to anything public here
+//! means a new major version. It depends on no other Sovereign crate;
+//! the internal crates (`sovereign-core`, `sovereign-db`,
+//! `sovereign-skills`, …) re-export these types and build on them, and
+//! make no such promise about the rest of their API.
+//!
+//! - [`content`]: a document's body and attachments.
+//! - [`blocks`]: tables, checklists, callouts and embeds in a body.
+//! - [`links`]: `[[wiki-links]]` and `@mentions` of other documents.
+//! - [`model`]: [`Document`], [`Thread`] and [`DocType`] as the API hands
+//!   them out.
+//! - [`store`]: [`DocumentStore`], the document and thread operations.
+//! - [`skill`]: [`CoreSkill`] and what a skill is given and returns.
+//! - [`events`]: the notifications the daemon streams to subscribers.
+
+pub mod blocks;
+pub mod content;
+pub mod events;
+pub mod links;
+pub mod model;
+pub mod skill;
+pub mod store;
+
+pub use content::ContentFields;
+pub use events::Event;
+pub use model::{DocType, Document, Thread};
+pub use skill::{
+    Capability, CoreSkill, SkillContext, SkillDbAccess, SkillDocument, SkillLlmAccess, SkillOutput,
+};
+pub use store::{DocumentStore, StoreError, StoreResult};
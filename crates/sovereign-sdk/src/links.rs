@@ -0,0 +1,229 @@
+//! Links to other documents written into a body: `[[Title]]` wiki-links
+//! (`[[Title|shown text]]` to show something else) and `@mentions`.
+//!
+//! A link names a document by title rather than id, so the body still
+//! reads as plain Markdown. This module only finds links and rewrites
+//! them when a title changes; resolving titles to documents is the
+//! store's job. A mention is a single word, so [`key`] folds case and
+//! treats spaces, `-` and `_` alike: `@project-apollo` names "Project
+//! Apollo".
+//!
+//! Links inside fenced or inline code are ignored, as are `![[…]]`
+//! embeds and the `@` of an email address.
+
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::blocks::fence_marker;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    Wiki,
+    Mention,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Link {
+    pub kind: LinkKind,
+    /// The title as written, trimmed.
+    pub target: String,
+    /// A wiki-link's text after `|`, verbatim.
+    pub alias: Option<String>,
+    /// Where the whole link (brackets or `@` included) sits in the body.
+    pub span: Range<usize>,
+}
+
+/// Every link in the body, in order.
+pub fn parse(body: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut offset = 0;
+    for line in body.split('\n') {
+        if let Some(open) = fence {
+            if line.trim_start().starts_with(open) {
+                fence = None;
+            }
+        } else if let Some(open) = fence_marker(line) {
+            fence = Some(open);
+        } else {
+            scan_line(line, offset, &mut links);
+        }
+        offset += line.len() + 1;
+    }
+    links
+}
+
+/// What a title or link target is compared by: lowercase, with each run
+/// of spaces, `-` and `_` made a single `-`.
+pub fn key(title: &str) -> String {
+    let mut out = String::with_capacity(title.len());
+    let mut gap = false;
+    for c in title.trim().chars() {
+        if c.is_whitespace() || c == '-' || c == '_' {
+            gap = true;
+            continue;
+        }
+        if gap && !out.is_empty() {
+            out.push('-');
+        }
+        gap = false;
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// Point the body's links to `old_title` at `new_title` instead. Aliases
+/// are kept, and mentions stay single words. `None` when no link changed.
+pub fn retitle(body: &str, old_title: &str, new_title: &str) -> Option<String> {
+    let old = key(old_title);
+    let mut out = String::with_capacity(body.len());
+    let mut last = 0;
+    let mut changed = false;
+    for link in parse(body) {
+        if key(&link.target) != old {
+            continue;
+        }
+        out.push_str(&body[last..link.span.start]);
+        match (link.kind, &link.alias) {
+            (LinkKind::Wiki, Some(alias)) => {
+                out.push_str(&format!("[[{}|{alias}]]", new_title.trim()))
+            }
+            (LinkKind::Wiki, None) => out.push_str(&format!("[[{}]]", new_title.trim())),
+            (LinkKind::Mention, _) => {
+                let word: Vec<&str> = new_title.split_whitespace().collect();
+                out.push('@');
+                out.push_str(&word.join("-"));
+            }
+        }
+        last = link.span.end;
+        changed = true;
+    }
+    if !changed {
+        return None;
+    }
+    out.push_str(&body[last..]);
+    Some(out)
+}
+
+fn is_mention_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+fn scan_line(line: &str, offset: usize, links: &mut Vec<Link>) {
+    let mut in_code = false;
+    let mut i = 0;
+    while let Some(c) = line[i..].chars().next() {
+        if c == '`' {
+            in_code = !in_code;
+        } else if !in_code {
+            if let Some((link, used)) = link_at(line, i) {
+                links.push(Link {
+                    span: offset + i..offset + i + used,
+                    ..link
+                });
+                i += used;
+                continue;
+            }
+        }
+        i += c.len_utf8();
+    }
+}
+
+/// The link starting at byte `i` of the line, and how many bytes it spans.
+fn link_at(line: &str, i: usize) -> Option<(Link, usize)> {
+    let rest = &line[i..];
+    let before = line[..i].chars().next_back();
+    if rest.starts_with("[[") && before != Some('!') {
+        return wiki_link(rest);
+    }
+    if !rest.starts_with('@') || before.is_some_and(is_mention_char) {
+        return None;
+    }
+    let word = rest[1..]
+        .split(|c: char| !is_mention_char(c))
+        .next()?
+        .trim_end_matches(['-', '_']);
+    if word.is_empty() {
+        return None;
+    }
+    let link = Link {
+        kind: LinkKind::Mention,
+        target: word.to_string(),
+        alias: None,
+        span: 0..0,
+    };
+    Some((link, 1 + word.len()))
+}
+
+/// A `[[…]]` at the start of `rest`.
+fn wiki_link(rest: &str) -> Option<(Link, usize)> {
+    let inner = &rest[2..rest.find("]]")?];
+    if inner.contains(['[', ']']) {
+        return None;
+    }
+    let (target, alias) = match inner.split_once('|') {
+        Some((target, alias)) => (target, Some(alias.to_string())),
+        None => (inner, None),
+    };
+    let target = target.trim();
+    if target.is_empty() {
+        return None;
+    }
+    let link = Link {
+        kind: LinkKind::Wiki,
+        target: target.to_string(),
+        alias,
+        span: 0..0,
+    };
+    Some((link, inner.len() + 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets(body: &str) -> Vec<(LinkKind, String)> {
+        parse(body)
+            .into_iter()
+            .map(|l| (l.kind, l.target))
+            .collect()
+    }
+
+    #[test]
+    fn finds_wiki_links_and_mentions() {
+        let body = "See [[Project Apollo]] and [[ Budget |the money]].\n\
+                    Ask @research-notes, not me@example.com.\n\
+                    ![[document:abc]] `[[in code]]`\n\
+                    ```\n@fenced [[Fenced]]\n```\n[[]] [[a [b]]";
+        assert_eq!(
+            targets(body),
+            [
+                (LinkKind::Wiki, "Project Apollo".to_string()),
+                (LinkKind::Wiki, "Budget".to_string()),
+                (LinkKind::Mention, "research-notes".to_string()),
+            ]
+        );
+        let budget = &parse(body)[1];
+        assert_eq!(budget.alias.as_deref(), Some("the money"));
+        assert_eq!(&body[budget.span.clone()], "[[ Budget |the money]]");
+    }
+
+    #[test]
+    fn keys_fold_case_and_separators() {
+        assert_eq!(key("Project  Apollo"), "project-apollo");
+        assert_eq!(key("project_apollo"), "project-apollo");
+        assert_eq!(key(" Émile's -- notes "), "émile's-notes");
+    }
+
+    #[test]
+    fn retitle_rewrites_only_matching_links() {
+        let body = "[[Old Name]], [[old name|see here]], @Old-Name and [[Other]].";
+        assert_eq!(
+            retitle(body, "Old Name", "New Plan").unwrap(),
+            "[[New Plan]], [[New Plan|see here]], @New-Plan and [[Other]]."
+        );
+        assert_eq!(retitle(body, "Missing", "New"), None);
+    }
+}
@@ -0,0 +1,128 @@
+//! Documents and threads as the API hands them out: plain data with
+//! string ids, decrypted, without the storage bookkeeping (sync heads,
+//! encryption nonces, search indexes) the database keeps alongside.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::content::ContentFields;
+
+/// What kind of document this is. Picks the skills offered for it, how
+/// its panel opens, its glyph on the canvas, and is set on import from
+/// the source file's type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DocType {
+    #[default]
+    Note,
+    Spec,
+    Spreadsheet,
+    Image,
+    Pdf,
+    WebClip,
+    Transcript,
+}
+
+impl DocType {
+    pub const ALL: [DocType; 7] = [
+        DocType::Note,
+        DocType::Spec,
+        DocType::Spreadsheet,
+        DocType::Image,
+        DocType::Pdf,
+        DocType::WebClip,
+        DocType::Transcript,
+    ];
+
+    /// The stored and wire name: `note`, `web-clip`, ….
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DocType::Note => "note",
+            DocType::Spec => "spec",
+            DocType::Spreadsheet => "spreadsheet",
+            DocType::Image => "image",
+            DocType::Pdf => "pdf",
+            DocType::WebClip => "web-clip",
+            DocType::Transcript => "transcript",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.as_str() == s)
+    }
+
+    /// The file types whose skills apply (matched against
+    /// `CoreSkill::file_types`). The body is Markdown whatever the type,
+    /// so `md` is always among them.
+    pub fn file_types(self) -> &'static [&'static str] {
+        match self {
+            DocType::Note => &["md", "txt"],
+            DocType::Spec => &["md", "markdown", "json", "yaml"],
+            DocType::Spreadsheet => &["md", "csv"],
+            DocType::Image => &["md", "png", "jpg"],
+            DocType::Pdf => &["md", "pdf"],
+            DocType::WebClip => &["md", "html"],
+            DocType::Transcript => &["md", "txt"],
+        }
+    }
+
+    /// The type a file imported with this extension becomes. `None` for
+    /// extensions that don't say (plain Markdown and text are notes).
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        Some(match ext.to_ascii_lowercase().as_str() {
+            "md" | "markdown" | "txt" => DocType::Note,
+            "csv" | "tsv" | "xlsx" | "ods" => DocType::Spreadsheet,
+            "png" | "jpg" | "jpeg" | "gif" | "webp" | "svg" => DocType::Image,
+            "pdf" => DocType::Pdf,
+            "html" | "htm" => DocType::WebClip,
+            "vtt" | "srt" => DocType::Transcript,
+            _ => return None,
+        })
+    }
+}
+
+impl std::fmt::Display for DocType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Document {
+    /// `document:<key>`.
+    pub id: String,
+    pub title: String,
+    pub thread_id: String,
+    pub content: ContentFields,
+    /// Written by the user rather than imported or received.
+    pub is_owned: bool,
+    #[serde(default)]
+    pub doc_type: DocType,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Thread {
+    /// `thread:<key>`.
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doc_type_names_round_trip() {
+        for t in DocType::ALL {
+            assert_eq!(DocType::parse(t.as_str()), Some(t));
+            assert_eq!(serde_json::to_string(&t).unwrap(), format!("\"{t}\""));
+        }
+        assert_eq!(DocType::from_extension("HTM"), Some(DocType::WebClip));
+        assert_eq!(DocType::from_extension("rs"), None);
+    }
+}
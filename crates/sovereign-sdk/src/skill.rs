@@ -0,0 +1,177 @@
+//! What a skill implements, what it is given, and what it returns.
+//!
+//! Skills compiled into the app implement [`CoreSkill`]; the registry
+//! checks a skill's [`required_capabilities`](CoreSkill::required_capabilities)
+//! against what the user granted before every call, and hands over only
+//! the narrow [`SkillDbAccess`] and [`SkillLlmAccess`] interfaces those
+//! grants allow. WASM plugins get the same types through the WIT
+//! interface in `skills/word-count-wasm/wit`.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::content::ContentFields;
+
+/// Capabilities a skill may request. Enforced by the registry at execution time.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    ReadDocument,
+    WriteDocument,
+    ReadAllDocuments,
+    WriteAllDocuments,
+    ReadFilesystem,
+    WriteFilesystem,
+    Network,
+    /// Run inference against the local LLM (router or reasoning model).
+    /// Granted via SkillContext.llm; not yet exposed across the WIT/WASM boundary.
+    LlmInference,
+    /// Read/write a private per-skill scratch directory (see
+    /// `sovereign_skills::scratch`).
+    /// Never the user's real filesystem, so not high-risk.
+    FileAccess,
+}
+
+impl Capability {
+    /// Capabilities that reach beyond the invoked document or off the
+    /// device. Only granted to built-in skills and signature-verified
+    /// plugins; unsigned or untrusted skills run without them.
+    pub fn is_high_risk(&self) -> bool {
+        matches!(
+            self,
+            Capability::WriteAllDocuments
+                | Capability::ReadFilesystem
+                | Capability::WriteFilesystem
+                | Capability::Network
+        )
+    }
+}
+
+/// A document passed to a skill for execution.
+#[derive(Debug, Clone)]
+pub struct SkillDocument {
+    pub id: String,
+    pub title: String,
+    pub content: ContentFields,
+}
+
+/// Output from a skill execution.
+#[derive(Debug, Clone)]
+pub enum SkillOutput {
+    /// Updated content to save back
+    ContentUpdate(ContentFields),
+    /// Binary file to save (e.g., PDF)
+    File {
+        name: String,
+        mime_type: String,
+        data: Vec<u8>,
+    },
+    /// Nothing (action had side effects only)
+    None,
+    /// Structured data result (e.g., search results, word count stats).
+    /// `kind` discriminates the payload; `json` is the serialized data.
+    StructuredData { kind: String, json: String },
+}
+
+/// Narrow DB interface exposed to skills.
+/// Skills never see the full database — only this subset.
+pub trait SkillDbAccess: Send + Sync {
+    /// Search documents matching query. Returns (id, title, snippet).
+    fn search_documents(&self, query: &str) -> anyhow::Result<Vec<(String, String, String)>>;
+    /// Get a single document by ID. Returns (title, thread_id, content).
+    fn get_document(&self, id: &str) -> anyhow::Result<(String, String, String)>;
+    /// List documents, optionally filtered by thread. Returns (id, title).
+    fn list_documents(&self, thread_id: Option<&str>) -> anyhow::Result<Vec<(String, String)>>;
+    /// Create a new document, returns the document ID.
+    fn create_document(
+        &self,
+        title: &str,
+        thread_id: &str,
+        content: &str,
+    ) -> anyhow::Result<String>;
+
+    /// List outgoing relationships from a document.
+    /// Returns (relation_type, target_id) for each edge where this document is the source.
+    fn list_relationships(&self, doc_id: &str) -> anyhow::Result<Vec<(String, String)>>;
+
+    /// List backlinks pointing to a document.
+    /// Returns (source_id, relation_type) for each edge where this document is the target.
+    fn list_backlinks(&self, doc_id: &str) -> anyhow::Result<Vec<(String, String)>>;
+
+    /// List all documents with their incoming and outgoing link counts.
+    /// Returns (id, title, in_degree, out_degree). Used by Orphan Finder
+    /// to identify documents nothing links to (in_degree == 0).
+    fn list_all_documents_with_link_counts(
+        &self,
+    ) -> anyhow::Result<Vec<(String, String, u32, u32)>>;
+
+    /// Find a thread by name (case-insensitive substring match) and return
+    /// its id. If no match is found, create a new thread with the given
+    /// name and description and return its id. Used by skills that target
+    /// a well-known thread (e.g. Daily Journal -> "Journal").
+    fn find_or_create_thread(&self, name: &str, description: &str) -> anyhow::Result<String>;
+
+    /// Open tasks due on or before `by`, overdue ones included, soonest
+    /// first. Returns (document title, task text, due date). Hosts
+    /// without a task projection return none.
+    fn list_due_tasks(
+        &self,
+        by: chrono::NaiveDate,
+    ) -> anyhow::Result<Vec<(String, String, chrono::NaiveDate)>> {
+        let _ = by;
+        Ok(Vec::new())
+    }
+}
+
+/// Narrow LLM interface exposed to skills.
+/// Skills never see the orchestrator — only this single inference call.
+pub trait SkillLlmAccess: Send + Sync {
+    /// Run inference against the currently loaded model.
+    /// `max_tokens` caps the response length. Returns the generated text
+    /// stripped of any model-specific control tokens.
+    fn generate(&self, prompt: &str, max_tokens: u32) -> anyhow::Result<String>;
+}
+
+/// Resources available to a skill during execution.
+/// The registry checks that required_capabilities() is a subset of granted.
+pub struct SkillContext {
+    pub granted: HashSet<Capability>,
+    pub db: Option<Arc<dyn SkillDbAccess>>,
+    pub llm: Option<Arc<dyn SkillLlmAccess>>,
+}
+
+/// Trait for core skills that are compiled into the Sovereign GE binary.
+///
+/// Core skills use direct Rust trait calls (no IPC).
+/// Community/sideloaded skills will use IPC instead.
+pub trait CoreSkill: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Capabilities this skill requires to function.
+    fn required_capabilities(&self) -> Vec<Capability>;
+
+    fn activate(&mut self) -> anyhow::Result<()>;
+    fn deactivate(&mut self) -> anyhow::Result<()>;
+
+    /// Execute a skill action on a document.
+    /// `params` is action-specific (e.g., image path for "add image").
+    fn execute(
+        &self,
+        action: &str,
+        doc: &SkillDocument,
+        params: &str,
+        ctx: &SkillContext,
+    ) -> anyhow::Result<SkillOutput>;
+
+    /// List available actions this skill provides.
+    /// Returns vec of (action_id, display_label).
+    fn actions(&self) -> Vec<(String, String)>;
+
+    /// File extensions this skill applies to (e.g. `["md", "txt"]`).
+    /// Empty means universal — the skill works on any document type.
+    fn file_types(&self) -> Vec<String> {
+        vec![]
+    }
+}
@@ -0,0 +1,56 @@
+//! [`DocumentStore`]: the document and thread operations a frontend or
+//! integration needs, without the rest of the database.
+//!
+//! In process, `sovereign_db::sdk::SdkStore` implements it over the
+//! app's database. Out of process, the daemon's `documents.*` and
+//! `threads.*` JSON-RPC methods offer the same operations.
+
+use async_trait::async_trait;
+
+use crate::content::ContentFields;
+use crate::model::{Document, Thread};
+
+#[derive(Debug, thiserror::Error)]
+pub enum StoreError {
+    #[error("not found: {0}")]
+    NotFound(String),
+    /// The store refused or failed; the message says why.
+    #[error("{0}")]
+    Backend(String),
+}
+
+pub type StoreResult<T> = Result<T, StoreError>;
+
+#[async_trait]
+pub trait DocumentStore: Send + Sync {
+    async fn get_document(&self, id: &str) -> StoreResult<Document>;
+
+    /// Documents not in the trash, optionally only those of one thread.
+    async fn list_documents(&self, thread_id: Option<&str>) -> StoreResult<Vec<Document>>;
+
+    /// Documents whose content matches `query`.
+    async fn search_documents(&self, query: &str) -> StoreResult<Vec<Document>>;
+
+    /// A new document owned by the user.
+    async fn create_document(
+        &self,
+        title: &str,
+        thread_id: &str,
+        content: &ContentFields,
+    ) -> StoreResult<Document>;
+
+    /// Replace the title and/or content; `None` leaves it as it is.
+    async fn update_document(
+        &self,
+        id: &str,
+        title: Option<&str>,
+        content: Option<&ContentFields>,
+    ) -> StoreResult<Document>;
+
+    /// Move a document to the trash, where it can still be restored.
+    async fn trash_document(&self, id: &str) -> StoreResult<()>;
+
+    async fn list_threads(&self) -> StoreResult<Vec<Thread>>;
+
+    async fn create_thread(&self, name: &str, description: &str) -> StoreResult<Thread>;
+}
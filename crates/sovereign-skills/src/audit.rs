@@ -0,0 +1,487 @@
+//! Per-skill capability audit trail and user revocations.
+//!
+//! Every DB call a skill makes goes through [`AuditedDb`], which records the
+//! operation and its target (document / thread id — never content) and
+//! refuses it if the user has revoked every capability that would authorize
+//! it. Revocations live in a shared [`SkillPermissions`] handle that the
+//! registry and the WASM host bridge both consult on every call, so a revoke
+//! from the permissions page applies to the next host call, not the next run.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::manifest::Capability;
+use crate::traits::{SkillDbAccess, SkillLlmAccess};
+
+/// In-memory entries kept for the permissions page. Older entries remain in
+/// the JSONL file on disk.
+const MAX_IN_MEMORY: usize = 1000;
+
+/// One recorded capability use (or refused attempt) by a skill.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SkillAuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub skill: String,
+    /// Capability that authorized (or would have authorized) the operation.
+    pub capability: Capability,
+    /// Operation name, e.g. `get_document`, `create_document`, `content_update`.
+    pub operation: String,
+    /// Record id or query the operation targeted, if any.
+    pub target: Option<String>,
+    /// `false` when the call was refused (capability revoked or not granted).
+    pub allowed: bool,
+}
+
+/// Append-only audit log of skill capability use. Cheap to clone — all
+/// clones share the same buffer and file.
+#[derive(Clone, Default)]
+pub struct SkillAuditLog {
+    inner: Arc<Mutex<AuditInner>>,
+}
+
+#[derive(Default)]
+struct AuditInner {
+    recent: VecDeque<SkillAuditEntry>,
+    path: Option<PathBuf>,
+}
+
+impl SkillAuditLog {
+    /// In-memory only (tests, or before the profile dir is known).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Log that also appends every entry to `path` as JSONL.
+    pub fn with_file(path: PathBuf) -> Self {
+        let log = Self::default();
+        log.inner.lock().unwrap().path = Some(path);
+        log
+    }
+
+    pub fn record(
+        &self,
+        skill: &str,
+        capability: Capability,
+        operation: &str,
+        target: Option<&str>,
+        allowed: bool,
+    ) {
+        let entry = SkillAuditEntry {
+            timestamp: Utc::now(),
+            skill: skill.to_string(),
+            capability,
+            operation: operation.to_string(),
+            target: target.map(str::to_string),
+            allowed,
+        };
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(path) = &inner.path {
+            if let Err(e) = append_jsonl(path, &entry) {
+                tracing::warn!("Failed to write skill audit entry: {e}");
+            }
+        }
+        if inner.recent.len() == MAX_IN_MEMORY {
+            inner.recent.pop_front();
+        }
+        inner.recent.push_back(entry);
+    }
+
+    /// Most recent entries for one skill, newest first.
+    pub fn entries_for(&self, skill: &str, limit: usize) -> Vec<SkillAuditEntry> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .recent
+            .iter()
+            .rev()
+            .filter(|e| e.skill == skill)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Most recent entries across all skills, newest first.
+    pub fn recent(&self, limit: usize) -> Vec<SkillAuditEntry> {
+        let inner = self.inner.lock().unwrap();
+        inner.recent.iter().rev().take(limit).cloned().collect()
+    }
+}
+
+fn append_jsonl(path: &Path, entry: &SkillAuditEntry) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// Capabilities the user has revoked, per skill. Cheap to clone — clones
+/// share state, so a revoke is visible to in-flight executions at once.
+#[derive(Clone, Default)]
+pub struct SkillPermissions {
+    revoked: Arc<RwLock<HashMap<String, HashSet<Capability>>>>,
+    path: Option<Arc<PathBuf>>,
+}
+
+impl SkillPermissions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load revocations from `path`; a missing file means nothing revoked.
+    /// Later changes are persisted back to the same file.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let revoked = if path.exists() {
+            serde_json::from_slice(&std::fs::read(path)?)?
+        } else {
+            HashMap::new()
+        };
+        Ok(Self {
+            revoked: Arc::new(RwLock::new(revoked)),
+            path: Some(Arc::new(path.to_path_buf())),
+        })
+    }
+
+    pub fn is_revoked(&self, skill: &str, cap: &Capability) -> bool {
+        self.revoked
+            .read()
+            .unwrap()
+            .get(skill)
+            .is_some_and(|caps| caps.contains(cap))
+    }
+
+    pub fn revoked_for(&self, skill: &str) -> HashSet<Capability> {
+        self.revoked
+            .read()
+            .unwrap()
+            .get(skill)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn revoke(&self, skill: &str, cap: Capability) -> anyhow::Result<()> {
+        self.revoked
+            .write()
+            .unwrap()
+            .entry(skill.to_string())
+            .or_default()
+            .insert(cap);
+        self.save()
+    }
+
+    /// Re-grant a previously revoked capability.
+    pub fn restore(&self, skill: &str, cap: &Capability) -> anyhow::Result<()> {
+        {
+            let mut revoked = self.revoked.write().unwrap();
+            if let Some(caps) = revoked.get_mut(skill) {
+                caps.remove(cap);
+                if caps.is_empty() {
+                    revoked.remove(skill);
+                }
+            }
+        }
+        self.save()
+    }
+
+    fn save(&self) -> anyhow::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let json = serde_json::to_vec_pretty(&*self.revoked.read().unwrap())?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        sovereign_crypto::fs_private::write_private(path, json)?;
+        Ok(())
+    }
+}
+
+/// [`SkillDbAccess`] decorator that enforces live revocations and records
+/// every call in the audit log. The registry wraps `SkillContext::db` in
+/// one of these for each execution.
+pub struct AuditedDb {
+    inner: Arc<dyn SkillDbAccess>,
+    skill: String,
+    granted: HashSet<Capability>,
+    permissions: SkillPermissions,
+    audit: SkillAuditLog,
+}
+
+impl AuditedDb {
+    pub fn new(
+        inner: Arc<dyn SkillDbAccess>,
+        skill: &str,
+        granted: HashSet<Capability>,
+        permissions: SkillPermissions,
+        audit: SkillAuditLog,
+    ) -> Self {
+        Self {
+            inner,
+            skill: skill.to_string(),
+            granted,
+            permissions,
+            audit,
+        }
+    }
+
+    /// Authorize `operation` if any of `caps` is granted and not revoked,
+    /// and record the outcome.
+    fn authorize(&self, caps: &[Capability], operation: &str, target: Option<&str>) -> anyhow::Result<()> {
+        let usable = caps
+            .iter()
+            .find(|c| self.granted.contains(c) && !self.permissions.is_revoked(&self.skill, c));
+        match usable {
+            Some(cap) => {
+                self.audit.record(&self.skill, cap.clone(), operation, target, true);
+                Ok(())
+            }
+            None => {
+                self.audit.record(&self.skill, caps[0].clone(), operation, target, false);
+                anyhow::bail!(
+                    "Skill '{}' may not {operation}: requires one of {caps:?} (not granted or revoked)",
+                    self.skill
+                )
+            }
+        }
+    }
+}
+
+impl SkillDbAccess for AuditedDb {
+    fn search_documents(&self, query: &str) -> anyhow::Result<Vec<(String, String, String)>> {
+        self.authorize(&[Capability::ReadAllDocuments], "search_documents", Some(query))?;
+        self.inner.search_documents(query)
+    }
+
+    fn get_document(&self, id: &str) -> anyhow::Result<(String, String, String)> {
+        self.authorize(
+            &[Capability::ReadDocument, Capability::ReadAllDocuments],
+            "get_document",
+            Some(id),
+        )?;
+        self.inner.get_document(id)
+    }
+
+    fn list_documents(&self, thread_id: Option<&str>) -> anyhow::Result<Vec<(String, String)>> {
+        self.authorize(&[Capability::ReadAllDocuments], "list_documents", thread_id)?;
+        self.inner.list_documents(thread_id)
+    }
+
+    fn create_document(&self, title: &str, thread_id: &str, content: &str) -> anyhow::Result<String> {
+        self.authorize(
+            &[Capability::WriteDocument, Capability::WriteAllDocuments],
+            "create_document",
+            Some(thread_id),
+        )?;
+        self.inner.create_document(title, thread_id, content)
+    }
+
+    fn list_relationships(&self, doc_id: &str) -> anyhow::Result<Vec<(String, String)>> {
+        self.authorize(
+            &[Capability::ReadDocument, Capability::ReadAllDocuments],
+            "list_relationships",
+            Some(doc_id),
+        )?;
+        self.inner.list_relationships(doc_id)
+    }
+
+    fn list_backlinks(&self, doc_id: &str) -> anyhow::Result<Vec<(String, String)>> {
+        self.authorize(
+            &[Capability::ReadDocument, Capability::ReadAllDocuments],
+            "list_backlinks",
+            Some(doc_id),
+        )?;
+        self.inner.list_backlinks(doc_id)
+    }
+
+    fn list_all_documents_with_link_counts(
+        &self,
+    ) -> anyhow::Result<Vec<(String, String, u32, u32)>> {
+        self.authorize(&[Capability::ReadAllDocuments], "list_all_documents_with_link_counts", None)?;
+        self.inner.list_all_documents_with_link_counts()
+    }
+
+    fn find_or_create_thread(&self, name: &str, description: &str) -> anyhow::Result<String> {
+        self.authorize(
+            &[Capability::WriteDocument, Capability::WriteAllDocuments],
+            "find_or_create_thread",
+            Some(name),
+        )?;
+        self.inner.find_or_create_thread(name, description)
+    }
+
+    fn list_due_tasks(
+        &self,
+        by: chrono::NaiveDate,
+    ) -> anyhow::Result<Vec<(String, String, chrono::NaiveDate)>> {
+        self.authorize(&[Capability::ReadAllDocuments], "list_due_tasks", None)?;
+        self.inner.list_due_tasks(by)
+    }
+}
+
+/// [`SkillLlmAccess`] decorator: same revoke check and audit trail as
+/// [`AuditedDb`], for the `LlmInference` capability.
+pub struct AuditedLlm {
+    inner: Arc<dyn SkillLlmAccess>,
+    skill: String,
+    granted: bool,
+    permissions: SkillPermissions,
+    audit: SkillAuditLog,
+}
+
+impl AuditedLlm {
+    pub fn new(
+        inner: Arc<dyn SkillLlmAccess>,
+        skill: &str,
+        granted: bool,
+        permissions: SkillPermissions,
+        audit: SkillAuditLog,
+    ) -> Self {
+        Self {
+            inner,
+            skill: skill.to_string(),
+            granted,
+            permissions,
+            audit,
+        }
+    }
+}
+
+impl SkillLlmAccess for AuditedLlm {
+    fn generate(&self, prompt: &str, max_tokens: u32) -> anyhow::Result<String> {
+        let allowed = self.granted
+            && !self
+                .permissions
+                .is_revoked(&self.skill, &Capability::LlmInference);
+        self.audit
+            .record(&self.skill, Capability::LlmInference, "generate", None, allowed);
+        if !allowed {
+            anyhow::bail!(
+                "Skill '{}' may not run LLM inference (not granted or revoked)",
+                self.skill
+            );
+        }
+        self.inner.generate(prompt, max_tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StubDb;
+
+    impl SkillDbAccess for StubDb {
+        fn search_documents(&self, _q: &str) -> anyhow::Result<Vec<(String, String, String)>> {
+            Ok(vec![])
+        }
+        fn get_document(&self, _id: &str) -> anyhow::Result<(String, String, String)> {
+            Ok(("T".into(), "thread:1".into(), "body".into()))
+        }
+        fn list_documents(&self, _t: Option<&str>) -> anyhow::Result<Vec<(String, String)>> {
+            Ok(vec![])
+        }
+        fn create_document(&self, _t: &str, _th: &str, _c: &str) -> anyhow::Result<String> {
+            Ok("document:new".into())
+        }
+        fn list_relationships(&self, _d: &str) -> anyhow::Result<Vec<(String, String)>> {
+            Ok(vec![])
+        }
+        fn list_backlinks(&self, _d: &str) -> anyhow::Result<Vec<(String, String)>> {
+            Ok(vec![])
+        }
+        fn list_all_documents_with_link_counts(
+            &self,
+        ) -> anyhow::Result<Vec<(String, String, u32, u32)>> {
+            Ok(vec![])
+        }
+        fn find_or_create_thread(&self, _n: &str, _d: &str) -> anyhow::Result<String> {
+            Ok("thread:1".into())
+        }
+    }
+
+    fn audited(granted: &[Capability], perms: &SkillPermissions, log: &SkillAuditLog) -> AuditedDb {
+        AuditedDb::new(
+            Arc::new(StubDb),
+            "demo",
+            granted.iter().cloned().collect(),
+            perms.clone(),
+            log.clone(),
+        )
+    }
+
+    #[test]
+    fn reads_and_writes_are_recorded_with_targets() {
+        let perms = SkillPermissions::new();
+        let log = SkillAuditLog::new();
+        let db = audited(&[Capability::ReadDocument, Capability::WriteDocument], &perms, &log);
+
+        db.get_document("document:a").unwrap();
+        db.create_document("New", "thread:1", "x").unwrap();
+
+        let entries = log.entries_for("demo", 10);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].operation, "create_document");
+        assert_eq!(entries[0].capability, Capability::WriteDocument);
+        assert_eq!(entries[0].target.as_deref(), Some("thread:1"));
+        assert_eq!(entries[1].operation, "get_document");
+        assert_eq!(entries[1].target.as_deref(), Some("document:a"));
+        assert!(entries.iter().all(|e| e.allowed));
+    }
+
+    #[test]
+    fn revocation_applies_to_the_next_call() {
+        let perms = SkillPermissions::new();
+        let log = SkillAuditLog::new();
+        let db = audited(&[Capability::ReadDocument], &perms, &log);
+
+        assert!(db.get_document("document:a").is_ok());
+        perms.revoke("demo", Capability::ReadDocument).unwrap();
+        assert!(db.get_document("document:a").is_err());
+        assert!(!log.entries_for("demo", 1)[0].allowed);
+
+        perms.restore("demo", &Capability::ReadDocument).unwrap();
+        assert!(db.get_document("document:a").is_ok());
+    }
+
+    #[test]
+    fn ungranted_capability_is_refused() {
+        let db = audited(&[Capability::ReadDocument], &SkillPermissions::new(), &SkillAuditLog::new());
+        assert!(db.search_documents("q").is_err());
+        assert!(db.create_document("t", "thread:1", "c").is_err());
+    }
+
+    #[test]
+    fn permissions_persist_across_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("skill_permissions.json");
+
+        let perms = SkillPermissions::load(&path).unwrap();
+        perms.revoke("demo", Capability::Network).unwrap();
+
+        let reloaded = SkillPermissions::load(&path).unwrap();
+        assert!(reloaded.is_revoked("demo", &Capability::Network));
+        assert!(!reloaded.is_revoked("other", &Capability::Network));
+    }
+
+    #[test]
+    fn audit_file_gets_one_line_per_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("skill_audit.jsonl");
+        let log = SkillAuditLog::with_file(path.clone());
+        log.record("demo", Capability::ReadDocument, "get_document", Some("document:a"), true);
+        log.record("demo", Capability::WriteDocument, "content_update", Some("document:a"), true);
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(text.lines().count(), 2);
+        let first: SkillAuditEntry = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+        assert_eq!(first.operation, "get_document");
+    }
+}
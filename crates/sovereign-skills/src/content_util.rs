@@ -10,13 +10,14 @@ use sovereign_core::content::ContentFields;
 use crate::traits::SkillDocument;
 
 /// Build a `ContentFields` that replaces only the body, preserving the
-/// document's images and videos verbatim. Use as the payload for
+/// document's images, videos and files verbatim. Use as the payload for
 /// `SkillOutput::ContentUpdate`.
 pub fn replace_body(doc: &SkillDocument, body: String) -> ContentFields {
     ContentFields {
         body,
         images: doc.content.images.clone(),
         videos: doc.content.videos.clone(),
+        files: doc.content.files.clone(),
     }
 }
 
@@ -34,6 +35,7 @@ mod tests {
                 body: "old".into(),
                 images: vec![ContentImage { path: "img.png".into(), caption: String::new() }],
                 videos: vec![],
+                files: vec![],
             },
         };
         let new = replace_body(&doc, "new".into());
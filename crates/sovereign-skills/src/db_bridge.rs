@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use sovereign_db::schema::thing_to_raw;
+use sovereign_db::tasks::{self, TaskFilter};
 use sovereign_db::GraphDB;
 
 use crate::traits::SkillDbAccess;
@@ -10,6 +11,10 @@ use crate::traits::SkillDbAccess;
 /// DB into the synchronous skill interface via `block_in_place`. Generic so
 /// it works for both raw `SurrealGraphDB` and the `LayeredGraphDB` indirection
 /// used at runtime to swap in `EncryptedGraphDB` post-login.
+///
+/// Skills run on the assistant's behalf as much as the user's, so they get
+/// the assistant's view of sensitivity: sealed documents don't exist here,
+/// and sensitive ones are listed by title but their content is withheld.
 impl<T: GraphDB + Send + Sync> SkillDbAccess for T {
     fn search_documents(&self, query: &str) -> anyhow::Result<Vec<(String, String, String)>> {
         let query_lower = query.to_lowercase();
@@ -19,13 +24,18 @@ impl<T: GraphDB + Send + Sync> SkillDbAccess for T {
 
         let results = docs
             .iter()
+            .filter(|d| d.ai_may_see())
             .filter(|d| {
                 d.title.to_lowercase().contains(&query_lower)
-                    || d.content.to_lowercase().contains(&query_lower)
+                    || (d.ai_may_read() && d.content.to_lowercase().contains(&query_lower))
             })
             .map(|d| {
                 let id = d.id_string().unwrap_or_default();
-                let snippet: String = d.content.chars().take(100).collect();
+                let snippet: String = if d.ai_may_read() {
+                    d.content.chars().take(100).collect()
+                } else {
+                    String::new()
+                };
                 (id, d.title.clone(), snippet)
             })
             .collect();
@@ -37,6 +47,12 @@ impl<T: GraphDB + Send + Sync> SkillDbAccess for T {
         let doc = tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current().block_on(GraphDB::get_document(self, id))
         })?;
+        if !doc.ai_may_see() {
+            anyhow::bail!("Document not found: {id}");
+        }
+        if !doc.ai_may_read() {
+            anyhow::bail!("'{}' is marked sensitive", doc.title);
+        }
         Ok((doc.title, doc.thread_id, doc.content))
     }
 
@@ -47,6 +63,7 @@ impl<T: GraphDB + Send + Sync> SkillDbAccess for T {
         })?;
         let results = docs
             .iter()
+            .filter(|d| d.ai_may_see())
             .map(|d| (d.id_string().unwrap_or_default(), d.title.clone()))
             .collect();
         Ok(results)
@@ -150,6 +167,7 @@ impl<T: GraphDB + Send + Sync> SkillDbAccess for T {
 
         Ok(docs
             .iter()
+            .filter(|d| d.ai_may_see())
             .map(|d| {
                 let id = d.id_string().unwrap_or_default();
                 let in_d = in_count.get(&id).copied().unwrap_or(0);
@@ -158,6 +176,20 @@ impl<T: GraphDB + Send + Sync> SkillDbAccess for T {
             })
             .collect())
     }
+
+    fn list_due_tasks(
+        &self,
+        by: chrono::NaiveDate,
+    ) -> anyhow::Result<Vec<(String, String, chrono::NaiveDate)>> {
+        let filter = TaskFilter::open_due_by(by);
+        let tasks = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(tasks::list_tasks(self, &filter))
+        })?;
+        Ok(tasks
+            .into_iter()
+            .filter_map(|t| Some((t.document_title, t.text, t.due?)))
+            .collect())
+    }
 }
 
 /// Helper to coerce any `Arc<T: GraphDB>` into `Arc<dyn SkillDbAccess>`.
@@ -165,3 +197,48 @@ impl<T: GraphDB + Send + Sync> SkillDbAccess for T {
 pub fn wrap_db<T: GraphDB + Send + Sync + 'static>(db: Arc<T>) -> Arc<dyn SkillDbAccess> {
     db
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_db::schema::{Document, Sensitivity};
+    use sovereign_db::surreal::{StorageMode, SurrealGraphDB};
+
+    async fn doc_with(db: &SurrealGraphDB, title: &str, sensitivity: Sensitivity) -> String {
+        let mut doc = Document::new(title.into(), "thread:t".into(), true);
+        doc.content = format!("{title} notes about the garden");
+        let id = db.create_document(doc).await.unwrap().id_string().unwrap();
+        db.set_sensitivity(&id, sensitivity, chrono::Utc::now()).await.unwrap();
+        id
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn skills_get_the_assistants_view_of_sensitivity() {
+        let db = SurrealGraphDB::new(StorageMode::Memory).await.unwrap();
+        db.connect().await.unwrap();
+        db.init_schema().await.unwrap();
+        let normal = doc_with(&db, "Normal", Sensitivity::Normal).await;
+        let sensitive = doc_with(&db, "Sensitive", Sensitivity::Sensitive).await;
+        let sealed = doc_with(&db, "Sealed", Sensitivity::Sealed).await;
+        let skills: &dyn SkillDbAccess = &db;
+
+        let mut listed: Vec<String> =
+            skills.list_documents(None).unwrap().into_iter().map(|(_, t)| t).collect();
+        listed.sort();
+        assert_eq!(listed, ["Normal", "Sensitive"]);
+
+        // Content only matches, and only shows, where the assistant may read it.
+        let hits = skills.search_documents("garden").unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, normal);
+        let by_title = skills.search_documents("sensitive").unwrap();
+        assert_eq!(by_title.len(), 1);
+        assert_eq!(by_title[0].2, "");
+        assert!(skills.search_documents("sealed").unwrap().is_empty());
+
+        assert!(skills.get_document(&normal).is_ok());
+        assert!(skills.get_document(&sensitive).is_err());
+        assert!(skills.get_document(&sealed).is_err());
+        assert_eq!(skills.list_all_documents_with_link_counts().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,366 @@
+//! Skill job queue: bounded concurrency, status tracking and cancellation.
+//!
+//! Every skill execution started from the UI goes through the registry's
+//! [`JobQueue`] so long-running skills (PDF export, translation, …) neither
+//! block each other nor race unboundedly. At most `max_concurrent` jobs run
+//! at once, each on its own thread; the rest wait in FIFO order.
+//!
+//! Cancelling a queued job removes it before it starts. `CoreSkill::execute`
+//! is synchronous and can't be interrupted, so cancelling a running job
+//! marks it cancelled and discards its output when it returns — a skill's
+//! `ContentUpdate` is never applied after the user cancelled it.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::traits::SkillOutput;
+
+/// Concurrency used when the config doesn't set one.
+pub const DEFAULT_MAX_CONCURRENT_JOBS: usize = 2;
+
+/// Finished jobs kept for the jobs panel; older ones are forgotten.
+const FINISHED_JOB_RETENTION: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Done | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// Snapshot of one skill execution.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillJob {
+    pub id: u64,
+    pub skill: String,
+    pub action: String,
+    pub doc_id: String,
+    pub status: JobStatus,
+    pub error: Option<String>,
+    pub queued_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+type JobResult = anyhow::Result<SkillOutput>;
+type Task = Box<dyn FnOnce() -> JobResult + Send>;
+
+/// Receives the output of a submitted job.
+pub struct JobHandle {
+    id: u64,
+    rx: mpsc::Receiver<JobResult>,
+}
+
+impl JobHandle {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Block until the job finishes (or is cancelled).
+    pub fn wait(self) -> JobResult {
+        let id = self.id;
+        self.rx
+            .recv()
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("Skill job {id} was dropped")))
+    }
+}
+
+struct Pending {
+    id: u64,
+    task: Task,
+    reply: mpsc::Sender<JobResult>,
+}
+
+struct State {
+    max_concurrent: usize,
+    running: usize,
+    next_id: u64,
+    queue: VecDeque<Pending>,
+    jobs: BTreeMap<u64, SkillJob>,
+}
+
+/// FIFO queue of skill executions with a concurrency limit. Cheap to clone;
+/// clones share the same queue.
+#[derive(Clone)]
+pub struct JobQueue {
+    state: Arc<Mutex<State>>,
+}
+
+impl JobQueue {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                max_concurrent: max_concurrent.max(1),
+                running: 0,
+                next_id: 1,
+                queue: VecDeque::new(),
+                jobs: BTreeMap::new(),
+            })),
+        }
+    }
+
+    pub fn max_concurrent(&self) -> usize {
+        self.state.lock().unwrap().max_concurrent
+    }
+
+    /// Change the concurrency limit. Raising it starts queued jobs right
+    /// away; lowering it lets running jobs finish.
+    pub fn set_max_concurrent(&self, max_concurrent: usize) {
+        self.state.lock().unwrap().max_concurrent = max_concurrent.max(1);
+        self.dispatch();
+    }
+
+    /// Queue `task` as a job for `skill`/`action` on `doc_id`.
+    pub fn submit<F>(&self, skill: &str, action: &str, doc_id: &str, task: F) -> JobHandle
+    where
+        F: FnOnce() -> JobResult + Send + 'static,
+    {
+        let (reply, rx) = mpsc::channel();
+        let id = {
+            let mut state = self.state.lock().unwrap();
+            let id = state.next_id;
+            state.next_id += 1;
+            state.jobs.insert(
+                id,
+                SkillJob {
+                    id,
+                    skill: skill.to_string(),
+                    action: action.to_string(),
+                    doc_id: doc_id.to_string(),
+                    status: JobStatus::Queued,
+                    error: None,
+                    queued_at: Utc::now(),
+                    started_at: None,
+                    finished_at: None,
+                },
+            );
+            state.queue.push_back(Pending {
+                id,
+                task: Box::new(task),
+                reply,
+            });
+            id
+        };
+        self.dispatch();
+        JobHandle { id, rx }
+    }
+
+    /// All known jobs, newest first.
+    pub fn jobs(&self) -> Vec<SkillJob> {
+        self.state.lock().unwrap().jobs.values().rev().cloned().collect()
+    }
+
+    pub fn job(&self, id: u64) -> Option<SkillJob> {
+        self.state.lock().unwrap().jobs.get(&id).cloned()
+    }
+
+    /// Cancel a queued or running job. Returns false if the job is unknown
+    /// or already finished.
+    pub fn cancel(&self, id: u64) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let Some(job) = state.jobs.get_mut(&id) else {
+            return false;
+        };
+        match job.status {
+            JobStatus::Queued => {
+                job.status = JobStatus::Cancelled;
+                job.finished_at = Some(Utc::now());
+                if let Some(pos) = state.queue.iter().position(|p| p.id == id) {
+                    if let Some(pending) = state.queue.remove(pos) {
+                        let _ = pending.reply.send(Err(cancelled_error(id)));
+                    }
+                }
+                true
+            }
+            JobStatus::Running => {
+                // The output is discarded in `finish`.
+                job.status = JobStatus::Cancelled;
+                job.finished_at = Some(Utc::now());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Forget every finished job.
+    pub fn clear_finished(&self) {
+        self.state
+            .lock()
+            .unwrap()
+            .jobs
+            .retain(|_, job| !job.status.is_finished());
+    }
+
+    /// Start queued jobs while there is spare concurrency.
+    fn dispatch(&self) {
+        let mut state = self.state.lock().unwrap();
+        while state.running < state.max_concurrent {
+            let Some(pending) = state.queue.pop_front() else {
+                break;
+            };
+            state.running += 1;
+            if let Some(job) = state.jobs.get_mut(&pending.id) {
+                job.status = JobStatus::Running;
+                job.started_at = Some(Utc::now());
+            }
+            let queue = self.clone();
+            std::thread::spawn(move || {
+                let Pending { id, task, reply } = pending;
+                let result = catch_unwind(AssertUnwindSafe(task)).unwrap_or_else(|_| {
+                    Err(anyhow::anyhow!("Skill job {id} panicked"))
+                });
+                queue.finish(id, result, reply);
+            });
+        }
+    }
+
+    fn finish(&self, id: u64, result: JobResult, reply: mpsc::Sender<JobResult>) {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.running -= 1;
+            let cancelled = match state.jobs.get_mut(&id) {
+                Some(job) if job.status == JobStatus::Cancelled => true,
+                Some(job) => {
+                    job.finished_at = Some(Utc::now());
+                    match &result {
+                        Ok(_) => job.status = JobStatus::Done,
+                        Err(e) => {
+                            job.status = JobStatus::Failed;
+                            job.error = Some(e.to_string());
+                        }
+                    }
+                    false
+                }
+                None => false,
+            };
+            let _ = reply.send(if cancelled { Err(cancelled_error(id)) } else { result });
+            prune_finished(&mut state.jobs);
+        }
+        self.dispatch();
+    }
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_JOBS)
+    }
+}
+
+fn cancelled_error(id: u64) -> anyhow::Error {
+    anyhow::anyhow!("Skill job {id} was cancelled")
+}
+
+fn prune_finished(jobs: &mut BTreeMap<u64, SkillJob>) {
+    let finished: Vec<u64> = jobs
+        .values()
+        .filter(|j| j.status.is_finished())
+        .map(|j| j.id)
+        .collect();
+    if finished.len() > FINISHED_JOB_RETENTION {
+        for id in &finished[..finished.len() - FINISHED_JOB_RETENTION] {
+            jobs.remove(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::Receiver;
+    use std::time::Duration;
+
+    /// A task that blocks until released through the returned sender.
+    fn gated() -> (mpsc::Sender<()>, impl FnOnce() -> JobResult + Send + 'static) {
+        let (tx, rx): (mpsc::Sender<()>, Receiver<()>) = mpsc::channel();
+        let task = move || {
+            let _ = rx.recv_timeout(Duration::from_secs(5));
+            Ok(SkillOutput::None)
+        };
+        (tx, task)
+    }
+
+    fn wait_for(queue: &JobQueue, id: u64, status: JobStatus) {
+        for _ in 0..500 {
+            if queue.job(id).map(|j| j.status) == Some(status) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        panic!("job {id} never reached {status:?}");
+    }
+
+    #[test]
+    fn respects_concurrency_limit() {
+        let queue = JobQueue::new(1);
+        let (release_a, task_a) = gated();
+        let (release_b, task_b) = gated();
+        let a = queue.submit("s", "run", "doc:1", task_a);
+        let b = queue.submit("s", "run", "doc:2", task_b);
+
+        wait_for(&queue, a.id(), JobStatus::Running);
+        assert_eq!(queue.job(b.id()).unwrap().status, JobStatus::Queued);
+
+        release_a.send(()).unwrap();
+        assert!(a.wait().is_ok());
+        wait_for(&queue, b.id(), JobStatus::Running);
+        release_b.send(()).unwrap();
+        let b_id = b.id();
+        assert!(b.wait().is_ok());
+        assert_eq!(queue.job(b_id).unwrap().status, JobStatus::Done);
+    }
+
+    #[test]
+    fn failures_and_panics_are_recorded() {
+        let queue = JobQueue::new(2);
+        let failing = queue.submit("s", "run", "doc:1", || anyhow::bail!("boom"));
+        let id = failing.id();
+        assert!(failing.wait().is_err());
+        let job = queue.job(id).unwrap();
+        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(job.error.as_deref(), Some("boom"));
+
+        let panicking = queue.submit("s", "run", "doc:1", || panic!("oops"));
+        let id = panicking.id();
+        assert!(panicking.wait().is_err());
+        assert_eq!(queue.job(id).unwrap().status, JobStatus::Failed);
+
+        // The panicked job released its slot.
+        assert!(queue.submit("s", "run", "doc:1", || Ok(SkillOutput::None)).wait().is_ok());
+    }
+
+    #[test]
+    fn cancel_queued_and_running_jobs() {
+        let queue = JobQueue::new(1);
+        let (release, task) = gated();
+        let running = queue.submit("s", "run", "doc:1", task);
+        let queued = queue.submit("s", "run", "doc:2", || Ok(SkillOutput::None));
+        wait_for(&queue, running.id(), JobStatus::Running);
+
+        assert!(queue.cancel(queued.id()));
+        assert!(queued.wait().is_err());
+
+        assert!(queue.cancel(running.id()));
+        release.send(()).unwrap();
+        let id = running.id();
+        assert!(running.wait().unwrap_err().to_string().contains("cancelled"));
+        assert_eq!(queue.job(id).unwrap().status, JobStatus::Cancelled);
+        assert!(!queue.cancel(id));
+
+        queue.clear_finished();
+        assert!(queue.jobs().is_empty());
+    }
+}
@@ -1,8 +1,13 @@
+pub mod audit;
 pub mod content_util;
 pub mod db_bridge;
+pub mod export_util;
+pub mod jobs;
 pub mod manifest;
 pub mod markdown_util;
 pub mod registry;
+pub mod scratch;
+pub mod signing;
 pub mod skills;
 #[cfg(test)]
 pub mod test_util;
@@ -10,7 +15,11 @@ pub mod traits;
 #[cfg(feature = "wasm-plugins")]
 pub mod wasm;
 
+pub use audit::{SkillAuditEntry, SkillAuditLog, SkillPermissions};
 pub use db_bridge::wrap_db;
+pub use jobs::{JobHandle, JobQueue, JobStatus, SkillJob};
 pub use manifest::{Capability, SkillManifest};
 pub use registry::SkillRegistry;
+pub use scratch::ScratchSpace;
+pub use sovereign_crypto::skill_signing::{SkillVerification, TrustedKeys};
 pub use traits::{CoreSkill, SkillContext, SkillDbAccess, SkillDocument, SkillLlmAccess, SkillOutput};
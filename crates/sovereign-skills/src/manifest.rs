@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -8,21 +9,7 @@ pub enum SkillType {
     Community,
 }
 
-/// Capabilities a skill may request. Enforced by the registry at execution time.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
-#[serde(rename_all = "snake_case")]
-pub enum Capability {
-    ReadDocument,
-    WriteDocument,
-    ReadAllDocuments,
-    WriteAllDocuments,
-    ReadFilesystem,
-    WriteFilesystem,
-    Network,
-    /// Run inference against the local LLM (router or reasoning model).
-    /// Granted via SkillContext.llm; not yet exposed across the WIT/WASM boundary.
-    LlmInference,
-}
+pub use sovereign_sdk::skill::Capability;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkillManifest {
@@ -33,6 +20,27 @@ pub struct SkillManifest {
     pub skill_type: SkillType,
     pub capabilities: Vec<Capability>,
     pub file_types: Vec<String>,
+    /// Optional per-action metadata. Used to expose actions as chat agent
+    /// tools; actions not listed here still run, with no declared parameters.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<ActionSpec>,
+    /// File name of the skill's WASM component inside its directory.
+    /// Manifest-only skills leave it out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+}
+
+/// Manifest description of one skill action.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ActionSpec {
+    /// Matches the action id returned by `CoreSkill::actions()`.
+    pub id: String,
+    #[serde(default)]
+    pub description: String,
+    /// Parameter name → human-readable description. Passed to the skill as
+    /// a JSON object in `params`.
+    #[serde(default)]
+    pub parameters: BTreeMap<String, String>,
 }
 
 impl SkillManifest {
@@ -46,6 +54,22 @@ impl SkillManifest {
         let manifest: SkillManifest = serde_json::from_str(json)?;
         Ok(manifest)
     }
+
+    /// Path of the declared module inside `skill_dir`. Anything but a plain
+    /// file name is refused, so a manifest cannot point outside its skill.
+    pub fn module_path(&self, skill_dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+        let Some(module) = &self.module else {
+            return Ok(None);
+        };
+        let mut parts = Path::new(module).components();
+        let plain = matches!(parts.next(), Some(Component::Normal(_))) && parts.next().is_none();
+        anyhow::ensure!(plain, "skill module '{module}' is not a file name");
+        Ok(Some(skill_dir.join(module)))
+    }
+
+    pub fn action(&self, id: &str) -> Option<&ActionSpec> {
+        self.actions.iter().find(|a| a.id == id)
+    }
 }
 
 #[cfg(test)]
@@ -88,6 +112,51 @@ mod tests {
         assert_eq!(manifest.capabilities, vec![Capability::WriteFilesystem]);
     }
 
+    #[test]
+    fn test_parse_action_specs() {
+        let json = r#"{
+            "name": "Find & Replace",
+            "version": "0.1.0",
+            "description": "Find and replace text",
+            "author": "Sovereign GE",
+            "skill_type": "core",
+            "capabilities": ["read_document", "write_document"],
+            "file_types": [],
+            "actions": [{
+                "id": "find_replace",
+                "description": "Replace every occurrence of a string",
+                "parameters": {"find": "text to find", "replace": "replacement text"}
+            }]
+        }"#;
+        let manifest = SkillManifest::from_json(json).unwrap();
+        let action = manifest.action("find_replace").unwrap();
+        assert_eq!(action.parameters.len(), 2);
+        assert_eq!(action.parameters["find"], "text to find");
+        assert!(manifest.action("missing").is_none());
+    }
+
+    #[test]
+    fn test_module_path_stays_in_skill_dir() {
+        let json = r#"{"name":"M","version":"1","description":"d","author":"a","skill_type":"community","capabilities":[],"file_types":[],"module":"m.wasm"}"#;
+        let mut manifest = SkillManifest::from_json(json).unwrap();
+        let dir = Path::new("/skills/m");
+        assert_eq!(manifest.module_path(dir).unwrap(), Some(dir.join("m.wasm")));
+        for bad in ["../other/m.wasm", "/tmp/m.wasm", "sub/m.wasm", ".."] {
+            manifest.module = Some(bad.into());
+            assert!(manifest.module_path(dir).is_err(), "{bad}");
+        }
+        manifest.module = None;
+        assert_eq!(manifest.module_path(dir).unwrap(), None);
+    }
+
+    #[test]
+    fn test_file_access_is_not_high_risk() {
+        let caps: Vec<Capability> = serde_json::from_str(r#"["file_access"]"#).unwrap();
+        assert_eq!(caps, vec![Capability::FileAccess]);
+        assert!(!Capability::FileAccess.is_high_risk());
+        assert!(Capability::WriteFilesystem.is_high_risk());
+    }
+
     #[test]
     fn test_parse_missing_fields() {
         let json = r#"{"name": "Incomplete"}"#;
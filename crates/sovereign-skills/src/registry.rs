@@ -1,19 +1,109 @@
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::Arc;
 
-use crate::manifest::SkillManifest;
-use crate::traits::{CoreSkill, SkillContext, SkillDocument, SkillOutput};
+use sovereign_crypto::skill_signing::{SkillVerification, TrustedKeys};
+use sovereign_sdk::DocType;
+
+use crate::audit::{AuditedDb, AuditedLlm, SkillAuditLog, SkillPermissions};
+use crate::jobs::{JobHandle, JobQueue};
+use crate::manifest::{Capability, SkillManifest};
+use crate::scratch::ScratchSpace;
+use crate::signing::verify_skill_dir;
+use crate::traits::{
+    CoreSkill, SkillContext, SkillDbAccess, SkillDocument, SkillLlmAccess, SkillOutput,
+};
 
 pub struct SkillRegistry {
     manifests: Vec<SkillManifest>,
+    /// Skill directory name → index into `manifests`. Skill directories are
+    /// named after the skill (`skills/word-count/`), which is how a loaded
+    /// skill finds its manifest.
+    manifest_dirs: HashMap<String, usize>,
     skills: Vec<Box<dyn CoreSkill>>,
+    /// Publisher keys used to verify skill packages at scan time.
+    trusted_keys: TrustedKeys,
+    /// Signature status of every skill loaded from disk, keyed by skill name.
+    /// Core skills compiled into the binary have no entry.
+    verification: HashMap<String, SkillVerification>,
+    /// User revocations, shared with every WASM skill's host bridge.
+    permissions: SkillPermissions,
+    /// Record of every capability use by every skill.
+    audit: SkillAuditLog,
+    /// Scratch directories for WASM plugins holding `FileAccess`.
+    scratch: Option<ScratchSpace>,
+    /// Queue every UI-triggered execution goes through.
+    jobs: JobQueue,
 }
 
 impl SkillRegistry {
     pub fn new() -> Self {
         Self {
             manifests: Vec::new(),
+            manifest_dirs: HashMap::new(),
             skills: Vec::new(),
+            trusted_keys: TrustedKeys::default(),
+            verification: HashMap::new(),
+            permissions: SkillPermissions::new(),
+            audit: SkillAuditLog::new(),
+            scratch: None,
+            jobs: JobQueue::default(),
+        }
+    }
+
+    /// Install the persisted revocation set. Call before loading WASM
+    /// skills — each plugin keeps the handle it was loaded with.
+    pub fn set_permissions(&mut self, permissions: SkillPermissions) {
+        self.permissions = permissions;
+    }
+
+    pub fn permissions(&self) -> &SkillPermissions {
+        &self.permissions
+    }
+
+    pub fn set_audit_log(&mut self, audit: SkillAuditLog) {
+        self.audit = audit;
+    }
+
+    pub fn audit_log(&self) -> &SkillAuditLog {
+        &self.audit
+    }
+
+    /// Set the scratch root handed to WASM plugins. Must be called before
+    /// `load_wasm_skills`.
+    pub fn set_scratch_space(&mut self, scratch: ScratchSpace) {
+        self.scratch = Some(scratch);
+    }
+
+    pub fn scratch_space(&self) -> Option<&ScratchSpace> {
+        self.scratch.as_ref()
+    }
+
+    /// Install the trusted publisher keys. Must be called before scanning —
+    /// verification results are computed once, at scan time.
+    pub fn set_trusted_keys(&mut self, keys: TrustedKeys) {
+        self.trusted_keys = keys;
+    }
+
+    /// Signature status of a skill loaded from disk. `None` for built-in
+    /// core skills, which ship inside the signed application binary.
+    pub fn verification(&self, name: &str) -> Option<&SkillVerification> {
+        self.verification.get(name)
+    }
+
+    fn record_verification(&mut self, name: &str, status: SkillVerification) {
+        match &status {
+            SkillVerification::Verified { signer } => {
+                tracing::info!("Skill '{name}' signature verified (signer: {signer})");
+            }
+            SkillVerification::Tampered => {
+                tracing::warn!("Skill '{name}' failed signature verification — refusing to run it");
+            }
+            SkillVerification::Unsigned | SkillVerification::UntrustedSigner { .. } => {
+                tracing::info!("Skill '{name}' is not signed by a trusted key; high-risk capabilities withheld");
+            }
         }
+        self.verification.insert(name.to_string(), status);
     }
 
     /// Walk a directory looking for `skill.json` files and parse them.
@@ -28,6 +118,10 @@ impl SkillRegistry {
                 match SkillManifest::load(&skill_path) {
                     Ok(manifest) => {
                         tracing::info!("Loaded skill: {} v{}", manifest.name, manifest.version);
+                        let status = verify_skill_dir(&entry.path(), &self.trusted_keys);
+                        self.record_verification(&manifest.name, status);
+                        let dir_name = entry.file_name().to_string_lossy().to_string();
+                        self.manifest_dirs.insert(dir_name, self.manifests.len());
                         self.manifests.push(manifest);
                     }
                     Err(e) => {
@@ -46,6 +140,13 @@ impl SkillRegistry {
         &self.manifests
     }
 
+    /// The manifest scanned from the skill's own directory, if any.
+    pub fn manifest_for(&self, skill_name: &str) -> Option<&SkillManifest> {
+        self.manifest_dirs
+            .get(skill_name)
+            .and_then(|&i| self.manifests.get(i))
+    }
+
     /// Register a core skill instance.
     pub fn register(&mut self, skill: Box<dyn CoreSkill>) {
         self.skills.push(skill);
@@ -65,7 +166,16 @@ impl SkillRegistry {
     /// Type-matched skills come first, then universal ones (empty `file_types()`).
     /// Each entry is `(skill_name, actions)`.
     pub fn skills_for_file_type(&self, ext: &str) -> Vec<(&str, Vec<(String, String)>)> {
-        let ext_lower = ext.to_lowercase();
+        self.skills_matching(&[&ext.to_lowercase()])
+    }
+
+    /// Return skills relevant to a document of the given type: those
+    /// handling any of the type's file types, then universal ones.
+    pub fn skills_for_doc_type(&self, doc_type: DocType) -> Vec<(&str, Vec<(String, String)>)> {
+        self.skills_matching(doc_type.file_types())
+    }
+
+    fn skills_matching(&self, types: &[&str]) -> Vec<(&str, Vec<(String, String)>)> {
         let mut matched = Vec::new();
         let mut universal = Vec::new();
 
@@ -73,7 +183,7 @@ impl SkillRegistry {
             let ftypes = skill.file_types();
             if ftypes.is_empty() {
                 universal.push((skill.name(), skill.actions()));
-            } else if ftypes.iter().any(|ft| ft == &ext_lower) {
+            } else if ftypes.iter().any(|ft| types.contains(&ft.as_str())) {
                 matched.push((skill.name(), skill.actions()));
             }
         }
@@ -87,13 +197,18 @@ impl SkillRegistry {
     #[cfg(feature = "wasm-plugins")]
     pub fn load_wasm_skills(&mut self, dir: &std::path::Path) -> anyhow::Result<usize> {
         let runner = crate::wasm::WasmSkillRunner::new()?;
-        let discovered = runner.discover_skills(dir);
+        let discovered = runner.discover_skills(dir, &self.trusted_keys);
         let mut loaded = 0;
 
         for (dir_name, result) in discovered {
             match result {
-                Ok(skill) => {
+                Ok((mut skill, status)) => {
                     tracing::info!("Loaded WASM skill: {} (from {})", skill.name(), dir_name);
+                    skill.set_permissions(self.permissions.clone());
+                    if let Some(scratch) = &self.scratch {
+                        skill.set_scratch_space(scratch.clone());
+                    }
+                    self.record_verification(skill.name(), status);
                     self.register(Box::new(skill));
                     loaded += 1;
                 }
@@ -106,8 +221,36 @@ impl SkillRegistry {
         Ok(loaded)
     }
 
+    pub fn jobs(&self) -> &JobQueue {
+        &self.jobs
+    }
+
+    /// Queue an execution on the job queue; the returned handle yields the
+    /// same result `execute_skill` would. Unknown skills fail immediately
+    /// rather than showing up as a failed job.
+    pub fn submit_job(
+        self: &Arc<Self>,
+        name: &str,
+        action: &str,
+        doc: SkillDocument,
+        params: String,
+        ctx: SkillContext,
+    ) -> anyhow::Result<JobHandle> {
+        if self.find_skill(name).is_none() {
+            anyhow::bail!("Skill '{}' not found", name);
+        }
+        let registry = self.clone();
+        let (skill, act) = (name.to_string(), action.to_string());
+        let doc_id = doc.id.clone();
+        Ok(self.jobs.submit(name, action, &doc_id, move || {
+            registry.execute_skill(&skill, &act, &doc, &params, &ctx)
+        }))
+    }
+
     /// Execute a skill with capability enforcement.
     /// Returns an error if the skill requires capabilities not granted by the context.
+    /// Tampered skills never run; unsigned or untrusted ones run with every
+    /// high-risk capability stripped from the context.
     pub fn execute_skill(
         &self,
         name: &str,
@@ -120,21 +263,99 @@ impl SkillRegistry {
             .find_skill(name)
             .ok_or_else(|| anyhow::anyhow!("Skill '{}' not found", name))?;
 
+        let verified = match self.verification.get(name) {
+            Some(SkillVerification::Tampered) => anyhow::bail!(
+                "Skill '{}' failed signature verification (tampered); refusing to run it",
+                name
+            ),
+            Some(status) if !status.is_verified() => {
+                let high_risk: Vec<_> = skill
+                    .required_capabilities()
+                    .into_iter()
+                    .filter(|c| c.is_high_risk())
+                    .collect();
+                if !high_risk.is_empty() {
+                    anyhow::bail!(
+                        "Skill '{}' is not signed by a trusted publisher; high-risk capabilities {:?} are only granted to verified skills",
+                        name,
+                        high_risk
+                    );
+                }
+                false
+            }
+            _ => true,
+        };
+
+        // Effective grant: the caller's grant minus anything the user revoked
+        // on the permissions page (and minus high-risk capabilities for skills
+        // that aren't signature-verified).
+        let revoked = self.permissions.revoked_for(name);
+        let granted: HashSet<Capability> = ctx
+            .granted
+            .iter()
+            .filter(|c| !revoked.contains(c) && (verified || !c.is_high_risk()))
+            .cloned()
+            .collect();
+
         let required = skill.required_capabilities();
         let missing: Vec<_> = required
             .iter()
-            .filter(|c| !ctx.granted.contains(c))
+            .filter(|c| !granted.contains(c))
             .collect();
+        if let Some(cap) = missing.iter().find(|c| revoked.contains(c)) {
+            self.audit
+                .record(name, (*cap).clone(), &format!("execute:{action}"), Some(&doc.id), false);
+            anyhow::bail!(
+                "Skill '{}' requires {:?}, which has been revoked in its permissions",
+                name,
+                cap
+            );
+        }
         if !missing.is_empty() {
             anyhow::bail!(
                 "Skill '{}' requires capabilities {:?} but context only grants {:?}",
                 name,
                 missing,
-                ctx.granted
+                granted
             );
         }
 
-        skill.execute(action, doc, params, ctx)
+        // Every DB / LLM call the skill makes is checked against live
+        // revocations and recorded in the audit log.
+        let db = ctx.db.as_ref().map(|db| {
+            Arc::new(AuditedDb::new(
+                db.clone(),
+                name,
+                granted.clone(),
+                self.permissions.clone(),
+                self.audit.clone(),
+            )) as Arc<dyn SkillDbAccess>
+        });
+        let llm = ctx.llm.as_ref().map(|llm| {
+            Arc::new(AuditedLlm::new(
+                llm.clone(),
+                name,
+                granted.contains(&Capability::LlmInference),
+                self.permissions.clone(),
+                self.audit.clone(),
+            )) as Arc<dyn SkillLlmAccess>
+        });
+        let ctx = SkillContext { granted, db, llm };
+
+        // The invoked document is always handed to the skill.
+        self.audit.record(
+            name,
+            Capability::ReadDocument,
+            &format!("execute:{action}"),
+            Some(&doc.id),
+            true,
+        );
+        let output = skill.execute(action, doc, params, &ctx)?;
+        if matches!(output, SkillOutput::ContentUpdate(_)) {
+            self.audit
+                .record(name, Capability::WriteDocument, "content_update", Some(&doc.id), true);
+        }
+        Ok(output)
     }
 }
 
@@ -147,7 +368,6 @@ impl Default for SkillRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::manifest::Capability;
     use std::path::PathBuf;
 
     struct DummySkill(&'static str);
@@ -201,6 +421,8 @@ mod tests {
             skills_dir,
             registry.manifests().len()
         );
+        let fr = registry.manifest_for("find-replace").unwrap();
+        assert!(fr.action("find_replace").is_some());
     }
 
     #[test]
@@ -210,6 +432,7 @@ mod tests {
             .scan_directory(std::path::Path::new("/nonexistent"))
             .unwrap();
         assert_eq!(registry.manifests().len(), 0);
+        assert!(registry.manifest_for("word-count").is_none());
     }
 
     #[test]
@@ -233,6 +456,54 @@ mod tests {
         assert_eq!(registry.all_skills().len(), 2);
     }
 
+    #[test]
+    fn test_skills_for_doc_type_matches_any_of_its_file_types() {
+        struct CsvOnly;
+        impl CoreSkill for CsvOnly {
+            fn name(&self) -> &str {
+                "csv-only"
+            }
+            fn required_capabilities(&self) -> Vec<Capability> {
+                vec![]
+            }
+            fn activate(&mut self) -> anyhow::Result<()> {
+                Ok(())
+            }
+            fn deactivate(&mut self) -> anyhow::Result<()> {
+                Ok(())
+            }
+            fn execute(
+                &self,
+                _action: &str,
+                _doc: &SkillDocument,
+                _params: &str,
+                _ctx: &SkillContext,
+            ) -> anyhow::Result<SkillOutput> {
+                Ok(SkillOutput::None)
+            }
+            fn actions(&self) -> Vec<(String, String)> {
+                vec![]
+            }
+            fn file_types(&self) -> Vec<String> {
+                vec!["csv".into()]
+            }
+        }
+
+        let mut registry = SkillRegistry::new();
+        registry.register(Box::new(DummySkill("universal")));
+        registry.register(Box::new(CsvOnly));
+
+        let names = |doc_type| -> Vec<String> {
+            registry
+                .skills_for_doc_type(doc_type)
+                .into_iter()
+                .map(|(name, _)| name.to_string())
+                .collect()
+        };
+        assert_eq!(names(DocType::Spreadsheet), ["csv-only", "universal"]);
+        assert_eq!(names(DocType::Note), ["universal"]);
+    }
+
     #[test]
     fn test_execute_skill_grants_sufficient() {
         let mut registry = SkillRegistry::new();
@@ -274,6 +545,41 @@ mod tests {
         assert!(msg.contains("requires capabilities"));
     }
 
+    #[test]
+    fn test_submit_job_runs_through_queue() {
+        let mut registry = SkillRegistry::new();
+        registry.register(Box::new(DummySkill("test")));
+        let registry = Arc::new(registry);
+
+        let doc = SkillDocument {
+            id: "document:1".into(),
+            title: "T".into(),
+            content: sovereign_core::content::ContentFields::default(),
+        };
+        let ctx = SkillContext {
+            granted: [Capability::ReadDocument].into_iter().collect(),
+            db: None,
+            llm: None,
+        };
+        let handle = registry
+            .submit_job("test", "any", doc.clone(), String::new(), ctx)
+            .unwrap();
+        let id = handle.id();
+        assert!(handle.wait().is_ok());
+        let job = registry.jobs().job(id).unwrap();
+        assert_eq!(job.status, crate::jobs::JobStatus::Done);
+        assert_eq!(job.doc_id, "document:1");
+
+        let ctx = SkillContext {
+            granted: std::collections::HashSet::new(),
+            db: None,
+            llm: None,
+        };
+        assert!(registry
+            .submit_job("nonexistent", "any", doc, String::new(), ctx)
+            .is_err());
+    }
+
     #[test]
     fn test_execute_skill_not_found() {
         let registry = SkillRegistry::new();
@@ -292,6 +598,174 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
 
+    struct NetworkSkill;
+
+    impl CoreSkill for NetworkSkill {
+        fn name(&self) -> &str {
+            "Signed"
+        }
+        fn required_capabilities(&self) -> Vec<Capability> {
+            vec![Capability::Network]
+        }
+        fn activate(&mut self) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn deactivate(&mut self) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn execute(
+            &self,
+            _action: &str,
+            _doc: &SkillDocument,
+            _params: &str,
+            _ctx: &SkillContext,
+        ) -> anyhow::Result<SkillOutput> {
+            Ok(SkillOutput::None)
+        }
+        fn actions(&self) -> Vec<(String, String)> {
+            vec![]
+        }
+    }
+
+    fn network_ctx() -> SkillContext {
+        SkillContext {
+            granted: [Capability::Network].into_iter().collect(),
+            db: None,
+            llm: None,
+        }
+    }
+
+    fn doc() -> SkillDocument {
+        SkillDocument {
+            id: "document:1".into(),
+            title: "T".into(),
+            content: sovereign_core::content::ContentFields::default(),
+        }
+    }
+
+    /// Write a `skills/<dir>/skill.json` for a skill named "Signed",
+    /// optionally signed by `key`.
+    fn write_skill_dir(
+        root: &std::path::Path,
+        key: Option<&sovereign_crypto::skill_signing::SkillSigningKey>,
+    ) {
+        let dir = root.join("signed");
+        std::fs::create_dir_all(&dir).unwrap();
+        let manifest = r#"{"name":"Signed","version":"1.0.0","description":"d","author":"a","skill_type":"community","capabilities":["network"],"file_types":[]}"#;
+        std::fs::write(dir.join("skill.json"), manifest).unwrap();
+        if let Some(key) = key {
+            key.sign(manifest.as_bytes(), None)
+                .save(&dir.join("skill.sig"))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_core_skills_have_no_verification_entry() {
+        let mut registry = SkillRegistry::new();
+        registry.register(Box::new(NetworkSkill));
+        assert!(registry.verification("Signed").is_none());
+        assert!(registry
+            .execute_skill("Signed", "any", &doc(), "", &network_ctx())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_unsigned_skill_refused_high_risk_capability() {
+        let root = tempfile::tempdir().unwrap();
+        write_skill_dir(root.path(), None);
+
+        let mut registry = SkillRegistry::new();
+        registry.scan_directory(root.path()).unwrap();
+        registry.register(Box::new(NetworkSkill));
+        assert_eq!(
+            registry.verification("Signed"),
+            Some(&SkillVerification::Unsigned)
+        );
+
+        let err = registry
+            .execute_skill("Signed", "any", &doc(), "", &network_ctx())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("only granted to verified skills"), "{err}");
+    }
+
+    #[test]
+    fn test_verified_skill_keeps_high_risk_capability() {
+        use sovereign_crypto::skill_signing::SkillSigningKey;
+
+        let root = tempfile::tempdir().unwrap();
+        let key = SkillSigningKey::generate();
+        write_skill_dir(root.path(), Some(&key));
+        let mut trusted = TrustedKeys::default();
+        trusted.add("Acme", &key.public_key_b64()).unwrap();
+
+        let mut registry = SkillRegistry::new();
+        registry.set_trusted_keys(trusted);
+        registry.scan_directory(root.path()).unwrap();
+        registry.register(Box::new(NetworkSkill));
+        assert!(registry.verification("Signed").unwrap().is_verified());
+        assert!(registry
+            .execute_skill("Signed", "any", &doc(), "", &network_ctx())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_tampered_skill_never_runs() {
+        use sovereign_crypto::skill_signing::SkillSigningKey;
+
+        let root = tempfile::tempdir().unwrap();
+        let key = SkillSigningKey::generate();
+        write_skill_dir(root.path(), Some(&key));
+        // Widen the manifest after signing.
+        let path = root.path().join("signed/skill.json");
+        let edited = std::fs::read_to_string(&path)
+            .unwrap()
+            .replace(r#""network""#, r#""network","write_filesystem""#);
+        std::fs::write(&path, edited).unwrap();
+
+        let mut registry = SkillRegistry::new();
+        registry.scan_directory(root.path()).unwrap();
+        registry.register(Box::new(NetworkSkill));
+        assert_eq!(
+            registry.verification("Signed"),
+            Some(&SkillVerification::Tampered)
+        );
+        let err = registry
+            .execute_skill("Signed", "any", &doc(), "", &network_ctx())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("tampered"), "{err}");
+    }
+
+    #[test]
+    fn test_revoked_capability_blocks_execution_and_is_audited() {
+        let mut registry = SkillRegistry::new();
+        registry.register(Box::new(DummySkill("test")));
+        let ctx = SkillContext {
+            granted: [Capability::ReadDocument].into_iter().collect(),
+            db: None,
+            llm: None,
+        };
+
+        assert!(registry.execute_skill("test", "run", &doc(), "", &ctx).is_ok());
+        registry
+            .permissions()
+            .revoke("test", Capability::ReadDocument)
+            .unwrap();
+        let err = registry
+            .execute_skill("test", "run", &doc(), "", &ctx)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("revoked"), "{err}");
+
+        let entries = registry.audit_log().entries_for("test", 10);
+        assert_eq!(entries.len(), 2);
+        assert!(!entries[0].allowed);
+        assert!(entries[1].allowed);
+        assert_eq!(entries[1].target.as_deref(), Some("document:1"));
+    }
+
     #[test]
     fn test_registry_with_all_core_skills() {
         use crate::skills::text_editor::TextEditorSkill;
@@ -310,6 +784,8 @@ mod tests {
         use crate::skills::readability_score::ReadabilityScoreSkill;
         use crate::skills::html_export::HtmlExportSkill;
         use crate::skills::plaintext_export::PlaintextExportSkill;
+        use crate::skills::docx_export::DocxExportSkill;
+        use crate::skills::epub_export::EpubExportSkill;
         use crate::skills::table_of_contents::TableOfContentsSkill;
         use crate::skills::json_yaml_formatter::JsonYamlFormatterSkill;
         use crate::skills::csv_to_md::CsvToMdSkill;
@@ -318,6 +794,7 @@ mod tests {
         use crate::skills::orphan_finder::OrphanFinderSkill;
         use crate::skills::daily_journal::DailyJournalSkill;
         use crate::skills::thread_summary::ThreadSummarySkill;
+        use crate::skills::translation::TranslationSkill;
 
         let mut registry = SkillRegistry::new();
         registry.register(Box::new(TextEditorSkill));
@@ -336,6 +813,8 @@ mod tests {
         registry.register(Box::new(ReadabilityScoreSkill));
         registry.register(Box::new(HtmlExportSkill));
         registry.register(Box::new(PlaintextExportSkill));
+        registry.register(Box::new(DocxExportSkill));
+        registry.register(Box::new(EpubExportSkill));
         registry.register(Box::new(TableOfContentsSkill));
         registry.register(Box::new(JsonYamlFormatterSkill));
         registry.register(Box::new(CsvToMdSkill));
@@ -344,8 +823,9 @@ mod tests {
         registry.register(Box::new(OrphanFinderSkill));
         registry.register(Box::new(DailyJournalSkill));
         registry.register(Box::new(ThreadSummarySkill));
+        registry.register(Box::new(TranslationSkill));
 
-        assert_eq!(registry.all_skills().len(), 24);
+        assert_eq!(registry.all_skills().len(), 27);
         assert!(registry.find_skill("text-editor").is_some());
         assert!(registry.find_skill("image").is_some());
         assert!(registry.find_skill("pdf-export").is_some());
@@ -362,6 +842,8 @@ mod tests {
         assert!(registry.find_skill("readability-score").is_some());
         assert!(registry.find_skill("html-export").is_some());
         assert!(registry.find_skill("plaintext-export").is_some());
+        assert!(registry.find_skill("docx-export").is_some());
+        assert!(registry.find_skill("epub-export").is_some());
         assert!(registry.find_skill("table-of-contents").is_some());
         assert!(registry.find_skill("json-yaml-formatter").is_some());
         assert!(registry.find_skill("csv-to-md").is_some());
@@ -370,5 +852,6 @@ mod tests {
         assert!(registry.find_skill("orphan-finder").is_some());
         assert!(registry.find_skill("daily-journal").is_some());
         assert!(registry.find_skill("thread-summary").is_some());
+        assert!(registry.find_skill("translation").is_some());
     }
 }
@@ -0,0 +1,136 @@
+//! Per-skill scratch directories backing the `FileAccess` capability.
+//!
+//! A skill holding `FileAccess` sees exactly one directory — its own
+//! `<root>/<skill-name>/` — preopened as `/scratch` inside the WASM sandbox.
+//! It never sees the user's home directory or another skill's scratch space.
+//! Files a skill wants to hand back (e.g. a large export) are returned by
+//! relative path and read out by the host via [`ScratchSpace::take_file`],
+//! then delivered through the normal `SkillOutput::File` save dialog.
+
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Guest-visible mount point of the scratch directory.
+pub const SCRATCH_GUEST_PATH: &str = "/scratch";
+
+/// Root under which every skill gets its own scratch directory.
+#[derive(Debug, Clone)]
+pub struct ScratchSpace {
+    root: PathBuf,
+}
+
+impl ScratchSpace {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Directory name for a skill: anything outside `[A-Za-z0-9_-]` becomes
+    /// `_`, so a hostile skill name can't climb out of the root.
+    fn dir_name(skill: &str) -> String {
+        let name: String = skill
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        if name.is_empty() { "_".into() } else { name }
+    }
+
+    /// The skill's scratch directory, created (owner-only) if missing.
+    pub fn dir_for(&self, skill: &str) -> io::Result<PathBuf> {
+        let dir = self.root.join(Self::dir_name(skill));
+        std::fs::create_dir_all(&dir)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700))?;
+        }
+        Ok(dir)
+    }
+
+    /// Read and remove a file the skill left in its scratch directory.
+    ///
+    /// `path` is as the guest saw it: relative, or absolute under
+    /// [`SCRATCH_GUEST_PATH`]. Parent components and symlinks that resolve
+    /// outside the skill's directory are rejected.
+    pub fn take_file(&self, skill: &str, path: &str) -> anyhow::Result<Vec<u8>> {
+        let rel = Path::new(path);
+        let rel = rel.strip_prefix(SCRATCH_GUEST_PATH).unwrap_or(rel);
+        if rel.as_os_str().is_empty()
+            || rel.components().any(|c| !matches!(c, Component::Normal(_)))
+        {
+            anyhow::bail!("Invalid scratch path '{path}'");
+        }
+
+        let dir = self.dir_for(skill)?.canonicalize()?;
+        let full = dir.join(rel).canonicalize()?;
+        if !full.starts_with(&dir) || !full.is_file() {
+            anyhow::bail!("Scratch path '{path}' is outside the skill's directory");
+        }
+        let data = std::fs::read(&full)?;
+        std::fs::remove_file(&full)?;
+        Ok(data)
+    }
+
+    /// Delete everything in a skill's scratch directory.
+    pub fn clear(&self, skill: &str) -> io::Result<()> {
+        let dir = self.root.join(Self::dir_name(skill));
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dir_for_confines_skill_names() {
+        let tmp = tempfile::tempdir().unwrap();
+        let scratch = ScratchSpace::new(tmp.path().to_path_buf());
+        let dir = scratch.dir_for("../../etc").unwrap();
+        assert!(dir.starts_with(tmp.path()));
+        assert_eq!(dir.file_name().unwrap(), "______etc");
+        assert!(dir.is_dir());
+    }
+
+    #[test]
+    fn take_file_reads_and_removes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let scratch = ScratchSpace::new(tmp.path().to_path_buf());
+        let dir = scratch.dir_for("exporter").unwrap();
+        std::fs::create_dir(dir.join("out")).unwrap();
+        std::fs::write(dir.join("out/doc.epub"), b"epub").unwrap();
+
+        let data = scratch.take_file("exporter", "/scratch/out/doc.epub").unwrap();
+        assert_eq!(data, b"epub");
+        assert!(!dir.join("out/doc.epub").exists());
+    }
+
+    #[test]
+    fn take_file_rejects_escapes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let scratch = ScratchSpace::new(tmp.path().join("scratch"));
+        std::fs::create_dir_all(tmp.path().join("scratch")).unwrap();
+        std::fs::write(tmp.path().join("secret.txt"), b"secret").unwrap();
+        scratch.dir_for("other").unwrap();
+        std::fs::write(tmp.path().join("scratch/other/theirs.txt"), b"x").unwrap();
+
+        assert!(scratch.take_file("a", "../../secret.txt").is_err());
+        assert!(scratch.take_file("a", "../other/theirs.txt").is_err());
+        assert!(scratch.take_file("a", "/etc/passwd").is_err());
+        assert!(scratch.take_file("a", "").is_err());
+
+        #[cfg(unix)]
+        {
+            let dir = scratch.dir_for("a").unwrap();
+            std::os::unix::fs::symlink(tmp.path().join("secret.txt"), dir.join("link")).unwrap();
+            assert!(scratch.take_file("a", "link").is_err());
+            assert!(tmp.path().join("secret.txt").exists());
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Scan-time verification of skill packages against the user's trusted
+//! publisher keys. The crypto lives in `sovereign_crypto::skill_signing`;
+//! this module only knows the on-disk layout of a skill directory.
+
+use std::path::Path;
+
+use sovereign_crypto::skill_signing::{self, SkillSignature, SkillVerification, TrustedKeys};
+
+use crate::manifest::SkillManifest;
+
+/// A skill directory read once: the manifest, the module it declares, and
+/// the signature verdict over exactly those bytes.
+pub struct SkillPackage {
+    pub manifest: SkillManifest,
+    /// The declared module's bytes. Instantiate these rather than the file,
+    /// so what runs is what was verified.
+    pub wasm: Option<Vec<u8>>,
+    pub verification: SkillVerification,
+}
+
+/// Read `skill.json`, the module its `module` field names, and `skill.sig`,
+/// and verify the manifest and module bytes against the signature. An
+/// unreadable or unparseable signature file counts as tampered — a skill
+/// that ships a signature must ship a valid one.
+pub fn read_skill_dir(skill_dir: &Path, trusted: &TrustedKeys) -> anyhow::Result<SkillPackage> {
+    let manifest_bytes = std::fs::read(skill_dir.join("skill.json"))?;
+    let manifest: SkillManifest = serde_json::from_slice(&manifest_bytes)?;
+    let wasm = match manifest.module_path(skill_dir)? {
+        Some(path) => Some(std::fs::read(path)?),
+        None => None,
+    };
+
+    let sig_path = skill_dir.join(skill_signing::SKILL_SIG_FILE);
+    let verification = if !sig_path.exists() {
+        SkillVerification::Unsigned
+    } else {
+        match SkillSignature::load(&sig_path) {
+            Ok(sig) => {
+                skill_signing::verify_skill(&manifest_bytes, wasm.as_deref(), Some(&sig), trusted)
+            }
+            Err(_) => SkillVerification::Tampered,
+        }
+    };
+
+    Ok(SkillPackage {
+        manifest,
+        wasm,
+        verification,
+    })
+}
+
+/// The signature verdict for a skill directory. A signed skill whose
+/// manifest or declared module cannot be read is tampered; an unsigned one
+/// is simply unsigned.
+pub fn verify_skill_dir(skill_dir: &Path, trusted: &TrustedKeys) -> SkillVerification {
+    match read_skill_dir(skill_dir, trusted) {
+        Ok(package) => package.verification,
+        Err(_) if skill_dir.join(skill_signing::SKILL_SIG_FILE).exists() => {
+            SkillVerification::Tampered
+        }
+        Err(_) => SkillVerification::Unsigned,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sovereign_crypto::skill_signing::SkillSigningKey;
+
+    const MANIFEST: &str = r#"{"name":"Signed","version":"1.0.0","description":"d","author":"a","skill_type":"community","capabilities":["network"],"file_types":[],"module":"plugin.wasm"}"#;
+
+    fn write_skill(dir: &Path, key: Option<&SkillSigningKey>) {
+        std::fs::write(dir.join("skill.json"), MANIFEST).unwrap();
+        std::fs::write(dir.join("plugin.wasm"), b"\0asm").unwrap();
+        if let Some(key) = key {
+            key.sign(MANIFEST.as_bytes(), Some(b"\0asm"))
+                .save(&dir.join(skill_signing::SKILL_SIG_FILE))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn signed_dir_verifies_against_trusted_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = SkillSigningKey::generate();
+        write_skill(dir.path(), Some(&key));
+        let mut trusted = TrustedKeys::default();
+        trusted.add("Acme", &key.public_key_b64()).unwrap();
+
+        assert!(verify_skill_dir(dir.path(), &trusted).is_verified());
+    }
+
+    #[test]
+    fn unsigned_and_tampered_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        write_skill(dir.path(), None);
+        assert_eq!(
+            verify_skill_dir(dir.path(), &TrustedKeys::default()),
+            SkillVerification::Unsigned
+        );
+
+        let key = SkillSigningKey::generate();
+        write_skill(dir.path(), Some(&key));
+        std::fs::write(dir.path().join("plugin.wasm"), b"\0asm-swapped").unwrap();
+        assert_eq!(
+            verify_skill_dir(dir.path(), &TrustedKeys::default()),
+            SkillVerification::Tampered
+        );
+    }
+
+    #[test]
+    fn only_the_declared_module_is_read_and_verified() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = SkillSigningKey::generate();
+        write_skill(dir.path(), Some(&key));
+        // A stray module the manifest does not name changes nothing.
+        std::fs::write(dir.path().join("aaa.wasm"), b"\0asm-stray").unwrap();
+        let mut trusted = TrustedKeys::default();
+        trusted.add("Acme", &key.public_key_b64()).unwrap();
+
+        let package = read_skill_dir(dir.path(), &trusted).unwrap();
+        assert!(package.verification.is_verified());
+        assert_eq!(package.wasm.as_deref(), Some(&b"\0asm"[..]));
+
+        // A signed skill whose declared module is gone is tampered.
+        std::fs::remove_file(dir.path().join("plugin.wasm")).unwrap();
+        assert_eq!(verify_skill_dir(dir.path(), &trusted), SkillVerification::Tampered);
+    }
+}
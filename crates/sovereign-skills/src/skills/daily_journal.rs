@@ -1,4 +1,4 @@
-use chrono::Utc;
+use chrono::{Days, NaiveDate, Utc};
 
 use crate::manifest::Capability;
 use crate::traits::{CoreSkill, SkillContext, SkillDocument, SkillOutput};
@@ -40,7 +40,8 @@ impl CoreSkill for DailyJournalSkill {
                     .as_ref()
                     .ok_or_else(|| anyhow::anyhow!("Daily Journal requires database access"))?;
 
-                let date_title = Utc::now().format("%Y-%m-%d").to_string();
+                let today = Utc::now().date_naive();
+                let date_title = today.format("%Y-%m-%d").to_string();
                 let thread_id = db.find_or_create_thread(
                     JOURNAL_THREAD_NAME,
                     JOURNAL_THREAD_DESCRIPTION,
@@ -49,7 +50,13 @@ impl CoreSkill for DailyJournalSkill {
                 let (existed, doc_id) = match find_existing(db, &thread_id, &date_title)? {
                     Some(id) => (true, id),
                     None => {
-                        let initial_body = format!("# {date_title}\n\n");
+                        // Tasks are only a digest here; the journal needs
+                        // no read grant, so without one the section is left out.
+                        let due = db
+                            .list_due_tasks(today + Days::new(7))
+                            .unwrap_or_default();
+                        let initial_body =
+                            format!("# {date_title}\n\n{}", due_section(&due, today));
                         (
                             false,
                             db.create_document(&date_title, &thread_id, &initial_body)?,
@@ -83,6 +90,21 @@ impl CoreSkill for DailyJournalSkill {
     }
 }
 
+/// A "Due this week" list of the open tasks, plain bullets rather than
+/// checkboxes so the journal doesn't repeat them as tasks of its own.
+fn due_section(due: &[(String, String, NaiveDate)], today: NaiveDate) -> String {
+    if due.is_empty() {
+        return String::new();
+    }
+    let mut out = String::from("## Due this week\n\n");
+    for (title, text, date) in due {
+        let when = if *date < today { "overdue since" } else { "due" };
+        out.push_str(&format!("- {text} — *{title}*, {when} {date}\n"));
+    }
+    out.push('\n');
+    out
+}
+
 fn find_existing(
     db: &std::sync::Arc<dyn crate::traits::SkillDbAccess>,
     thread_id: &str,
@@ -233,6 +255,21 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn due_section_lists_tasks_as_plain_bullets() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        let due = vec![
+            ("Home".to_string(), "bins".to_string(), NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()),
+            ("Work".to_string(), "report".to_string(), NaiveDate::from_ymd_opt(2026, 3, 6).unwrap()),
+        ];
+        assert_eq!(
+            due_section(&due, today),
+            "## Due this week\n\n- bins — *Home*, overdue since 2026-03-01\n\
+             - report — *Work*, due 2026-03-06\n\n"
+        );
+        assert_eq!(due_section(&[], today), "");
+    }
+
     #[test]
     fn unknown_action_errors() {
         let db = Arc::new(MockDb::new());
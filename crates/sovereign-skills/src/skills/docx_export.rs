@@ -0,0 +1,620 @@
+use std::io::{Cursor, Write};
+
+use pulldown_cmark::{Event, HeadingLevel, Parser, Tag, TagEnd};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::export_util::{
+    is_local_image_ref, load_image, load_images, markdown_options, xml_escape, EmbeddedImage,
+};
+use crate::manifest::Capability;
+use crate::markdown_util::sanitize_filename;
+use crate::traits::{CoreSkill, SkillContext, SkillDocument, SkillOutput};
+
+const DOCX_MIME: &str = "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+
+/// Widest an embedded image may be: the text width of a Letter/A4 page
+/// with default margins (6 in), in EMU.
+const MAX_IMAGE_WIDTH_EMU: u64 = 6 * 914_400;
+/// EMU per pixel at 96 dpi.
+const EMU_PER_PX: u64 = 9525;
+
+pub struct DocxExportSkill;
+
+impl CoreSkill for DocxExportSkill {
+    fn name(&self) -> &str {
+        "docx-export"
+    }
+
+    fn required_capabilities(&self) -> Vec<Capability> {
+        // ReadFilesystem: images are read from disk and embedded.
+        vec![Capability::ReadDocument, Capability::ReadFilesystem]
+    }
+
+    fn activate(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn deactivate(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn execute(
+        &self,
+        action: &str,
+        doc: &SkillDocument,
+        _params: &str,
+        _ctx: &SkillContext,
+    ) -> anyhow::Result<SkillOutput> {
+        match action {
+            "export" => {
+                let data = build_docx(
+                    &doc.title,
+                    &doc.content.body,
+                    &load_images(&doc.content.images),
+                )?;
+                let safe_name = sanitize_filename(&doc.title);
+                Ok(SkillOutput::File {
+                    name: format!("{safe_name}.docx"),
+                    mime_type: DOCX_MIME.into(),
+                    data,
+                })
+            }
+            _ => anyhow::bail!("Unknown action: {action}"),
+        }
+    }
+
+    fn actions(&self) -> Vec<(String, String)> {
+        vec![("export".into(), "Export as Word (DOCX)".into())]
+    }
+}
+
+/// Assemble the `.docx` package: the document body plus the parts Word
+/// requires to open it (content types, relationships, styles).
+fn build_docx(title: &str, markdown: &str, attached: &[EmbeddedImage]) -> anyhow::Result<Vec<u8>> {
+    let mut body = DocxBody::default();
+    body.paragraph(Some("Title"), &text_run(title, &RunStyle::default()));
+    body.render_markdown(markdown);
+    for img in attached {
+        body.image_paragraph(img, &img.caption);
+        if !img.caption.is_empty() {
+            body.paragraph(
+                Some("Caption"),
+                &text_run(&img.caption, &RunStyle::default()),
+            );
+        }
+    }
+
+    let document = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing"><w:body>{}<w:sectPr><w:pgSz w:w="12240" w:h="15840"/><w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1440" w:header="720" w:footer="720" w:gutter="0"/></w:sectPr></w:body></w:document>"#,
+        body.xml
+    );
+
+    let mut rels = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>"#,
+    );
+    for (id, media) in &body.media {
+        rels.push_str(&format!(
+            r#"<Relationship Id="{id}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="media/{}"/>"#,
+            media.0
+        ));
+    }
+    for (id, url) in &body.links {
+        rels.push_str(&format!(
+            r#"<Relationship Id="{id}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="{}" TargetMode="External"/>"#,
+            xml_escape(url)
+        ));
+    }
+    rels.push_str("</Relationships>");
+
+    let core = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><dc:title>{}</dc:title><dcterms:created xsi:type="dcterms:W3CDTF">{}</dcterms:created></cp:coreProperties>"#,
+        xml_escape(title),
+        chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ")
+    );
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let opts = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    zip.start_file("[Content_Types].xml", opts)?;
+    zip.write_all(CONTENT_TYPES.as_bytes())?;
+    zip.start_file("_rels/.rels", opts)?;
+    zip.write_all(PACKAGE_RELS.as_bytes())?;
+    zip.start_file("docProps/core.xml", opts)?;
+    zip.write_all(core.as_bytes())?;
+    zip.start_file("word/document.xml", opts)?;
+    zip.write_all(document.as_bytes())?;
+    zip.start_file("word/styles.xml", opts)?;
+    zip.write_all(STYLES.as_bytes())?;
+    zip.start_file("word/_rels/document.xml.rels", opts)?;
+    zip.write_all(rels.as_bytes())?;
+    for (name, data) in body.media.iter().map(|(_, m)| m) {
+        zip.start_file(format!("word/media/{name}"), opts)?;
+        zip.write_all(data)?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+#[derive(Default, Clone)]
+struct RunStyle {
+    bold: bool,
+    italic: bool,
+    strike: bool,
+    code: bool,
+    link: bool,
+}
+
+fn text_run(text: &str, style: &RunStyle) -> String {
+    let mut props = String::new();
+    if style.link {
+        props.push_str(r#"<w:rStyle w:val="Hyperlink"/>"#);
+    }
+    if style.code {
+        props.push_str(r#"<w:rFonts w:ascii="Consolas" w:hAnsi="Consolas" w:cs="Consolas"/>"#);
+    }
+    if style.bold {
+        props.push_str("<w:b/>");
+    }
+    if style.italic {
+        props.push_str("<w:i/>");
+    }
+    if style.strike {
+        props.push_str("<w:strike/>");
+    }
+    let props = if props.is_empty() {
+        props
+    } else {
+        format!("<w:rPr>{props}</w:rPr>")
+    };
+    format!(
+        r#"<w:r>{props}<w:t xml:space="preserve">{}</w:t></w:r>"#,
+        xml_escape(text)
+    )
+}
+
+/// An open paragraph: its style, indentation and accumulated runs.
+struct OpenParagraph {
+    style: Option<&'static str>,
+    indent_twips: u32,
+    runs: String,
+    /// False while `runs` holds at most a list item's bullet.
+    has_content: bool,
+}
+
+/// Streaming markdown → WordprocessingML converter.
+#[derive(Default)]
+struct DocxBody {
+    xml: String,
+    para: Option<OpenParagraph>,
+    run: RunStyle,
+    /// Next counter per nested list; `None` for bullet lists.
+    lists: Vec<Option<u64>>,
+    quote_depth: u32,
+    in_code_block: bool,
+    in_table_head: bool,
+    /// A body image being emitted: its alt text is collected until the
+    /// image ends. `None` inside means the image couldn't be embedded and
+    /// its alt text is rendered as plain text instead.
+    image: Option<Option<EmbeddedImage>>,
+    image_alt: String,
+    /// (relationship id, (file name, bytes)) for `word/media/`.
+    media: Vec<(String, (String, Vec<u8>))>,
+    /// (relationship id, URL) for external hyperlinks.
+    links: Vec<(String, String)>,
+}
+
+impl DocxBody {
+    /// Relationship ids: `rId1` is the styles part.
+    fn next_rel_id(&self) -> String {
+        format!("rId{}", self.media.len() + self.links.len() + 2)
+    }
+
+    fn paragraph(&mut self, style: Option<&'static str>, runs: &str) {
+        self.flush();
+        self.para = Some(OpenParagraph {
+            style,
+            indent_twips: 0,
+            runs: runs.to_string(),
+            has_content: true,
+        });
+        self.flush();
+    }
+
+    /// Start a paragraph. An open one without content yet (a list item's,
+    /// a table cell's) is reused so its bullet and indentation survive a
+    /// nested `Paragraph` start.
+    fn open(&mut self, style: Option<&'static str>) {
+        if let Some(p) = &mut self.para {
+            if !p.has_content {
+                p.style = p.style.or(style);
+                return;
+            }
+        }
+        self.flush();
+        let depth = self.lists.len() as u32;
+        let style = style.or(match (depth, self.quote_depth) {
+            (0, 0) => None,
+            (0, _) => Some("Quote"),
+            _ => Some("ListParagraph"),
+        });
+        self.para = Some(OpenParagraph {
+            style,
+            indent_twips: 360 * depth,
+            runs: String::new(),
+            has_content: false,
+        });
+    }
+
+    fn flush(&mut self) {
+        let Some(p) = self.para.take() else {
+            return;
+        };
+        let mut props = String::new();
+        if let Some(style) = p.style {
+            props.push_str(&format!(r#"<w:pStyle w:val="{style}"/>"#));
+        }
+        if p.indent_twips > 0 {
+            props.push_str(&format!(r#"<w:ind w:left="{}"/>"#, p.indent_twips));
+        }
+        let props = if props.is_empty() {
+            props
+        } else {
+            format!("<w:pPr>{props}</w:pPr>")
+        };
+        self.xml.push_str(&format!("<w:p>{props}{}</w:p>", p.runs));
+    }
+
+    fn push_runs(&mut self, runs: &str) {
+        if self.para.is_none() {
+            self.open(None);
+        }
+        if let Some(p) = &mut self.para {
+            p.runs.push_str(runs);
+            p.has_content = true;
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.image.is_some() {
+            self.image_alt.push_str(text);
+            return;
+        }
+        if self.in_code_block {
+            // One paragraph per code line keeps the layout in Word.
+            let mut lines = text.split('\n').peekable();
+            while let Some(line) = lines.next() {
+                let last = lines.peek().is_none();
+                if last && line.is_empty() {
+                    // Text after the final newline: nothing to start.
+                    break;
+                }
+                if self.para.is_none() {
+                    self.open(Some("Code"));
+                }
+                if !line.is_empty() {
+                    let run = text_run(line, &self.run.clone());
+                    self.push_runs(&run);
+                }
+                if !last {
+                    self.flush();
+                }
+            }
+            return;
+        }
+        let run = text_run(text, &self.run.clone());
+        self.push_runs(&run);
+    }
+
+    /// Embed an image as an inline drawing in its own paragraph.
+    fn image_paragraph(&mut self, img: &EmbeddedImage, alt: &str) {
+        self.flush();
+        self.open(None);
+        let run = self.drawing_run(img, alt);
+        self.push_runs(&run);
+        self.flush();
+    }
+
+    fn drawing_run(&mut self, img: &EmbeddedImage, alt: &str) -> String {
+        let rel_id = self.next_rel_id();
+        let n = self.media.len() + 1;
+        let file_name = format!("image{n}.{}", img.extension);
+        self.media
+            .push((rel_id.clone(), (file_name.clone(), img.data.clone())));
+
+        let (cx, cy) = match img.dimensions() {
+            Some((w, h)) if w > 0 && h > 0 => {
+                let (w, h) = (u64::from(w) * EMU_PER_PX, u64::from(h) * EMU_PER_PX);
+                if w > MAX_IMAGE_WIDTH_EMU {
+                    (MAX_IMAGE_WIDTH_EMU, h * MAX_IMAGE_WIDTH_EMU / w)
+                } else {
+                    (w, h)
+                }
+            }
+            _ => (MAX_IMAGE_WIDTH_EMU, MAX_IMAGE_WIDTH_EMU * 3 / 4),
+        };
+        let alt = xml_escape(alt);
+        format!(
+            r#"<w:r><w:drawing><wp:inline distT="0" distB="0" distL="0" distR="0"><wp:extent cx="{cx}" cy="{cy}"/><wp:docPr id="{n}" name="Picture {n}" descr="{alt}"/><a:graphic xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/picture"><pic:pic xmlns:pic="http://schemas.openxmlformats.org/drawingml/2006/picture"><pic:nvPicPr><pic:cNvPr id="{n}" name="{file_name}"/><pic:cNvPicPr/></pic:nvPicPr><pic:blipFill><a:blip r:embed="{rel_id}"/><a:stretch><a:fillRect/></a:stretch></pic:blipFill><pic:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="{cx}" cy="{cy}"/></a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></pic:spPr></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>"#
+        )
+    }
+
+    fn render_markdown(&mut self, markdown: &str) {
+        for event in Parser::new_ext(markdown, markdown_options()) {
+            match event {
+                Event::Start(Tag::Paragraph) => self.open(None),
+                Event::End(TagEnd::Paragraph) => self.flush(),
+                Event::Start(Tag::Heading { level, .. }) => self.open(Some(heading_style(level))),
+                Event::End(TagEnd::Heading(_)) => self.flush(),
+                Event::Start(Tag::BlockQuote(_)) => {
+                    self.flush();
+                    self.quote_depth += 1;
+                }
+                Event::End(TagEnd::BlockQuote(_)) => {
+                    self.flush();
+                    self.quote_depth = self.quote_depth.saturating_sub(1);
+                }
+                Event::Start(Tag::CodeBlock(_)) => {
+                    self.flush();
+                    self.in_code_block = true;
+                }
+                Event::End(TagEnd::CodeBlock) => {
+                    self.flush();
+                    self.in_code_block = false;
+                }
+                Event::Start(Tag::List(start)) => {
+                    self.flush();
+                    self.lists.push(start);
+                }
+                Event::End(TagEnd::List(_)) => {
+                    self.flush();
+                    self.lists.pop();
+                }
+                Event::Start(Tag::Item) => {
+                    self.flush();
+                    let depth = self.lists.len() as u32;
+                    let prefix = match self.lists.last_mut() {
+                        Some(Some(n)) => {
+                            let p = format!("{n}. ");
+                            *n += 1;
+                            p
+                        }
+                        _ => "• ".to_string(),
+                    };
+                    self.para = Some(OpenParagraph {
+                        style: Some("ListParagraph"),
+                        indent_twips: 360 * depth,
+                        runs: text_run(&prefix, &RunStyle::default()),
+                        has_content: false,
+                    });
+                }
+                Event::End(TagEnd::Item) => self.flush(),
+                Event::Start(Tag::Table(_)) => {
+                    self.flush();
+                    self.xml.push_str(
+                        r#"<w:tbl><w:tblPr><w:tblStyle w:val="TableGrid"/><w:tblW w:w="0" w:type="auto"/></w:tblPr>"#,
+                    );
+                }
+                Event::End(TagEnd::Table) => self.xml.push_str("</w:tbl>"),
+                Event::Start(Tag::TableHead) => {
+                    self.in_table_head = true;
+                    self.run.bold = true;
+                    self.xml.push_str("<w:tr>");
+                }
+                Event::End(TagEnd::TableHead) => {
+                    self.in_table_head = false;
+                    self.run.bold = false;
+                    self.xml.push_str("</w:tr>");
+                }
+                Event::Start(Tag::TableRow) => self.xml.push_str("<w:tr>"),
+                Event::End(TagEnd::TableRow) => self.xml.push_str("</w:tr>"),
+                Event::Start(Tag::TableCell) => {
+                    self.xml.push_str("<w:tc>");
+                    self.para = Some(OpenParagraph {
+                        style: None,
+                        indent_twips: 0,
+                        runs: String::new(),
+                        has_content: false,
+                    });
+                }
+                Event::End(TagEnd::TableCell) => {
+                    self.flush();
+                    self.xml.push_str("</w:tc>");
+                }
+                Event::Start(Tag::Emphasis) => self.run.italic = true,
+                Event::End(TagEnd::Emphasis) => self.run.italic = false,
+                Event::Start(Tag::Strong) => self.run.bold = true,
+                Event::End(TagEnd::Strong) => self.run.bold = self.in_table_head,
+                Event::Start(Tag::Strikethrough) => self.run.strike = true,
+                Event::End(TagEnd::Strikethrough) => self.run.strike = false,
+                Event::Start(Tag::Link { dest_url, .. }) => {
+                    let id = self.next_rel_id();
+                    self.links.push((id.clone(), dest_url.to_string()));
+                    self.push_runs(&format!(r#"<w:hyperlink r:id="{id}">"#));
+                    self.run.link = true;
+                }
+                Event::End(TagEnd::Link) => {
+                    self.run.link = false;
+                    self.push_runs("</w:hyperlink>");
+                }
+                Event::Start(Tag::Image { dest_url, .. }) => {
+                    let img = is_local_image_ref(&dest_url)
+                        .then(|| load_image(&dest_url, ""))
+                        .flatten()
+                        .filter(docx_supports);
+                    self.image = Some(img);
+                    self.image_alt.clear();
+                }
+                Event::End(TagEnd::Image) => {
+                    let alt = std::mem::take(&mut self.image_alt);
+                    match self.image.take().flatten() {
+                        Some(img) => {
+                            let run = self.drawing_run(&img, &alt);
+                            self.push_runs(&run);
+                        }
+                        None => {
+                            let style = RunStyle {
+                                italic: true,
+                                ..self.run.clone()
+                            };
+                            self.push_runs(&text_run(&format!("[{alt}]"), &style));
+                        }
+                    }
+                }
+                Event::Start(Tag::FootnoteDefinition(label)) => {
+                    self.open(None);
+                    self.text(&format!("[{label}] "));
+                }
+                Event::End(TagEnd::FootnoteDefinition) => self.flush(),
+                Event::Text(text) => self.text(&text),
+                Event::Code(code) => {
+                    let style = RunStyle {
+                        code: true,
+                        ..self.run.clone()
+                    };
+                    self.push_runs(&text_run(&code, &style));
+                }
+                Event::Html(raw) | Event::InlineHtml(raw) => self.text(&raw),
+                Event::FootnoteReference(label) => self.text(&format!("[{label}]")),
+                Event::SoftBreak => self.text(" "),
+                Event::HardBreak => self.push_runs("<w:r><w:br/></w:r>"),
+                Event::Rule => {
+                    self.flush();
+                    self.xml.push_str(
+                        r#"<w:p><w:pPr><w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="auto"/></w:pBdr></w:pPr></w:p>"#,
+                    );
+                }
+                Event::TaskListMarker(done) => self.text(if done { "☑ " } else { "☐ " }),
+                _ => {}
+            }
+        }
+        self.flush();
+    }
+}
+
+fn heading_style(level: HeadingLevel) -> &'static str {
+    match level {
+        HeadingLevel::H1 => "Heading1",
+        HeadingLevel::H2 => "Heading2",
+        HeadingLevel::H3 => "Heading3",
+        HeadingLevel::H4 => "Heading4",
+        HeadingLevel::H5 => "Heading5",
+        HeadingLevel::H6 => "Heading6",
+    }
+}
+
+/// Word renders PNG, JPEG and GIF; WebP and SVG fall back to alt text.
+fn docx_supports(img: &EmbeddedImage) -> bool {
+    matches!(img.extension, "png" | "jpg" | "gif")
+}
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Default Extension="png" ContentType="image/png"/><Default Extension="jpg" ContentType="image/jpeg"/><Default Extension="gif" ContentType="image/gif"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/><Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/><Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/></Types>"#;
+
+const PACKAGE_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/></Relationships>"#;
+
+const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri" w:cs="Calibri"/><w:sz w:val="22"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after="160" w:line="276" w:lineRule="auto"/></w:pPr></w:pPrDefault></w:docDefaults><w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style><w:style w:type="paragraph" w:styleId="Title"><w:name w:val="Title"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:spacing w:after="240"/></w:pPr><w:rPr><w:b/><w:sz w:val="48"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="360" w:after="120"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/><w:sz w:val="36"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="120"/><w:outlineLvl w:val="1"/></w:pPr><w:rPr><w:b/><w:sz w:val="30"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading3"><w:name w:val="heading 3"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="80"/><w:outlineLvl w:val="2"/></w:pPr><w:rPr><w:b/><w:sz w:val="26"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading4"><w:name w:val="heading 4"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:outlineLvl w:val="3"/></w:pPr><w:rPr><w:b/><w:i/><w:sz w:val="24"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading5"><w:name w:val="heading 5"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:outlineLvl w:val="4"/></w:pPr><w:rPr><w:b/><w:sz w:val="22"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading6"><w:name w:val="heading 6"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:outlineLvl w:val="5"/></w:pPr><w:rPr><w:i/><w:sz w:val="22"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Quote"><w:name w:val="Quote"/><w:basedOn w:val="Normal"/><w:pPr><w:ind w:left="720"/></w:pPr><w:rPr><w:i/><w:color w:val="555555"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Code"><w:name w:val="Code"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="0" w:line="240" w:lineRule="auto"/><w:shd w:val="clear" w:color="auto" w:fill="F5F5F5"/></w:pPr><w:rPr><w:rFonts w:ascii="Consolas" w:hAnsi="Consolas" w:cs="Consolas"/><w:sz w:val="20"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="ListParagraph"><w:name w:val="List Paragraph"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="40"/></w:pPr></w:style><w:style w:type="paragraph" w:styleId="Caption"><w:name w:val="caption"/><w:basedOn w:val="Normal"/><w:rPr><w:i/><w:color w:val="666666"/><w:sz w:val="18"/></w:rPr></w:style><w:style w:type="character" w:styleId="Hyperlink"><w:name w:val="Hyperlink"/><w:rPr><w:color w:val="0066CC"/><w:u w:val="single"/></w:rPr></w:style><w:style w:type="table" w:styleId="TableGrid"><w:name w:val="Table Grid"/><w:tblPr><w:tblBorders><w:top w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:left w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:bottom w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:right w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideH w:val="single" w:sz="4" w:space="0" w:color="auto"/><w:insideV w:val="single" w:sz="4" w:space="0" w:color="auto"/></w:tblBorders><w:tblCellMar><w:left w:w="108" w:type="dxa"/><w:right w:w="108" w:type="dxa"/></w:tblCellMar></w:tblPr></w:style></w:styles>"#;
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+    use crate::export_util::test_images::write_png;
+    use crate::test_util::{dummy_ctx, make_doc_with_title};
+
+    fn read_part(docx: &[u8], name: &str) -> String {
+        let mut zip = zip::ZipArchive::new(Cursor::new(docx)).unwrap();
+        let mut out = String::new();
+        zip.by_name(name).unwrap().read_to_string(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn exports_a_valid_package() {
+        let doc = make_doc_with_title(
+            "Report",
+            "# Intro\n\nSome **bold** and *italic* & <stuff>.\n\n- one\n- two\n\n1. first\n2. second\n\n```\nlet x = 1;\nlet y = 2;\n```\n\n[site](https://example.com)",
+        );
+        let SkillOutput::File {
+            name,
+            mime_type,
+            data,
+        } = DocxExportSkill
+            .execute("export", &doc, "", &dummy_ctx())
+            .unwrap()
+        else {
+            panic!("expected File");
+        };
+        assert_eq!(name, "Report.docx");
+        assert_eq!(mime_type, DOCX_MIME);
+
+        let xml = read_part(&data, "word/document.xml");
+        assert!(xml.contains(r#"<w:pStyle w:val="Title"/>"#));
+        assert!(xml.contains(r#"<w:pStyle w:val="Heading1"/>"#));
+        assert!(xml.contains("<w:b/></w:rPr><w:t xml:space=\"preserve\">bold</w:t>"));
+        // Raw HTML comes through as escaped text.
+        assert!(xml.contains("&amp; </w:t>"));
+        assert!(xml.contains(">&lt;stuff&gt;</w:t>"));
+        assert!(xml.contains(">• </w:t>"));
+        assert!(xml.contains(">2. </w:t>"));
+        assert_eq!(xml.matches(r#"<w:pStyle w:val="Code"/>"#).count(), 2);
+        assert!(xml.contains(r#"<w:hyperlink r:id="rId2">"#));
+
+        let rels = read_part(&data, "word/_rels/document.xml.rels");
+        assert!(rels.contains(r#"Target="https://example.com" TargetMode="External""#));
+        assert!(read_part(&data, "[Content_Types].xml").contains("/word/document.xml"));
+    }
+
+    #[test]
+    fn embeds_images() {
+        let tmp = tempfile::tempdir().unwrap();
+        let png = write_png(tmp.path());
+        let mut doc = make_doc_with_title(
+            "Pics",
+            &format!("Inline ![dot]({png}) and ![gone](/nope.png)"),
+        );
+        doc.content
+            .images
+            .push(sovereign_core::content::ContentImage {
+                path: png,
+                caption: "Attached".into(),
+            });
+        let SkillOutput::File { data, .. } = DocxExportSkill
+            .execute("export", &doc, "", &dummy_ctx())
+            .unwrap()
+        else {
+            panic!("expected File");
+        };
+
+        let xml = read_part(&data, "word/document.xml");
+        assert_eq!(xml.matches("<w:drawing>").count(), 2);
+        assert!(xml.contains(r#"descr="dot""#));
+        assert!(xml.contains("[gone]"));
+        assert!(xml.contains(r#"<w:pStyle w:val="Caption"/>"#));
+        // 1×1 px at 96 dpi.
+        assert!(xml.contains(r#"<wp:extent cx="9525" cy="9525"/>"#));
+
+        let mut zip = zip::ZipArchive::new(Cursor::new(&data)).unwrap();
+        assert!(zip.by_name("word/media/image1.png").is_ok());
+        assert!(zip.by_name("word/media/image2.png").is_ok());
+    }
+
+    #[test]
+    fn tables_become_word_tables() {
+        let doc = make_doc_with_title("T", "|a|b|\n|-|-|\n|1|2|");
+        let SkillOutput::File { data, .. } = DocxExportSkill
+            .execute("export", &doc, "", &dummy_ctx())
+            .unwrap()
+        else {
+            panic!("expected File");
+        };
+        let xml = read_part(&data, "word/document.xml");
+        assert!(xml.contains("<w:tbl>"));
+        assert_eq!(xml.matches("<w:tr>").count(), 2);
+        assert_eq!(xml.matches("<w:tc>").count(), 4);
+    }
+}
@@ -0,0 +1,400 @@
+use std::io::{Cursor, Write};
+
+use sovereign_core::content::ContentFields;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::export_util::{
+    is_local_image_ref, load_image, load_images, render_markdown_html, xml_escape, EmbeddedImage,
+};
+use crate::manifest::Capability;
+use crate::markdown_util::sanitize_filename;
+use crate::traits::{CoreSkill, SkillContext, SkillDocument, SkillOutput};
+
+const DEFAULT_AUTHOR: &str = "Sovereign GE User";
+
+pub struct EpubExportSkill;
+
+impl CoreSkill for EpubExportSkill {
+    fn name(&self) -> &str {
+        "epub-export"
+    }
+
+    fn required_capabilities(&self) -> Vec<Capability> {
+        // ReadAllDocuments: `export_thread` reads every document in the
+        // thread. ReadFilesystem: images are read from disk and embedded.
+        vec![
+            Capability::ReadDocument,
+            Capability::ReadAllDocuments,
+            Capability::ReadFilesystem,
+        ]
+    }
+
+    fn activate(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn deactivate(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn execute(
+        &self,
+        action: &str,
+        doc: &SkillDocument,
+        params: &str,
+        ctx: &SkillContext,
+    ) -> anyhow::Result<SkillOutput> {
+        let meta = BookParams::parse(params);
+        let (title, book) = match action {
+            "export" => {
+                let title = meta.title.unwrap_or_else(|| doc.title.clone());
+                let mut book = EpubBook::default();
+                book.add_chapter(&doc.title, &doc.content);
+                (title, book)
+            }
+            "export_thread" => {
+                let db = ctx
+                    .db
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("Thread export requires database access"))?;
+                let (_title, thread_id, _content) = db.get_document(&doc.id)?;
+                let mut book = EpubBook::default();
+                for (id, chapter_title) in db.list_documents(Some(&thread_id))? {
+                    let (_, _, content) = db.get_document(&id)?;
+                    book.add_chapter(&chapter_title, &ContentFields::parse(&content));
+                }
+                if book.chapters.is_empty() {
+                    anyhow::bail!("Thread has no documents to export");
+                }
+                (meta.title.unwrap_or_else(|| doc.title.clone()), book)
+            }
+            _ => anyhow::bail!("Unknown action: {action}"),
+        };
+
+        let author = meta.author.unwrap_or_else(|| DEFAULT_AUTHOR.to_string());
+        let data = book.package(&doc.id, &title, &author)?;
+        Ok(SkillOutput::File {
+            name: format!("{}.epub", sanitize_filename(&title)),
+            mime_type: "application/epub+zip".into(),
+            data,
+        })
+    }
+
+    fn actions(&self) -> Vec<(String, String)> {
+        vec![
+            ("export".into(), "Export as EPUB".into()),
+            ("export_thread".into(), "Export Thread as EPUB".into()),
+        ]
+    }
+}
+
+/// Optional `{"title": "...", "author": "..."}` params. Anything else
+/// (including the UI's default `{}`) means "use the defaults".
+#[derive(Default, serde::Deserialize)]
+struct BookParams {
+    title: Option<String>,
+    author: Option<String>,
+}
+
+impl BookParams {
+    fn parse(params: &str) -> Self {
+        let mut p: Self = serde_json::from_str(params).unwrap_or_default();
+        p.title = p.title.filter(|t| !t.trim().is_empty());
+        p.author = p.author.filter(|a| !a.trim().is_empty());
+        p
+    }
+}
+
+/// An EPUB 3 book under construction: one XHTML chapter per document.
+#[derive(Default)]
+struct EpubBook {
+    /// (chapter title, XHTML body fragment)
+    chapters: Vec<(String, String)>,
+    /// (file name under `images/`, image)
+    images: Vec<(String, EmbeddedImage)>,
+}
+
+impl EpubBook {
+    fn add_image(&mut self, img: EmbeddedImage) -> String {
+        let name = format!("image-{}.{}", self.images.len() + 1, img.extension);
+        self.images.push((name.clone(), img));
+        format!("images/{name}")
+    }
+
+    fn add_chapter(&mut self, title: &str, content: &ContentFields) {
+        // EPUB content documents are XHTML: raw HTML is escaped, images
+        // are copied into the package.
+        let mut body = render_markdown_html(&content.body, true, |src| {
+            let img = is_local_image_ref(src)
+                .then(|| load_image(src, ""))
+                .flatten()?;
+            Some(self.add_image(img))
+        });
+        for img in load_images(&content.images) {
+            let caption = img.caption.clone();
+            let href = self.add_image(img);
+            body.push_str(&format!(
+                r#"<figure><img src="{href}" alt="{alt}"/>{cap}</figure>"#,
+                alt = xml_escape(&caption),
+                cap = if caption.is_empty() {
+                    String::new()
+                } else {
+                    format!("<figcaption>{}</figcaption>", xml_escape(&caption))
+                }
+            ));
+            body.push('\n');
+        }
+        self.chapters.push((title.to_string(), body));
+    }
+
+    fn package(&self, id: &str, title: &str, author: &str) -> anyhow::Result<Vec<u8>> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        // The mimetype entry must come first and be stored uncompressed.
+        zip.start_file(
+            "mimetype",
+            FileOptions::default().compression_method(CompressionMethod::Stored),
+        )?;
+        zip.write_all(b"application/epub+zip")?;
+
+        let opts = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        zip.start_file("META-INF/container.xml", opts)?;
+        zip.write_all(CONTAINER_XML.as_bytes())?;
+        zip.start_file("OEBPS/content.opf", opts)?;
+        zip.write_all(self.opf(id, title, author).as_bytes())?;
+        zip.start_file("OEBPS/nav.xhtml", opts)?;
+        zip.write_all(self.nav(title).as_bytes())?;
+        zip.start_file("OEBPS/style.css", opts)?;
+        zip.write_all(STYLE_CSS.as_bytes())?;
+        for (i, (chapter_title, body)) in self.chapters.iter().enumerate() {
+            zip.start_file(format!("OEBPS/chapter-{}.xhtml", i + 1), opts)?;
+            zip.write_all(
+                xhtml_page(
+                    chapter_title,
+                    &format!("<h1>{}</h1>\n{body}", xml_escape(chapter_title)),
+                )
+                .as_bytes(),
+            )?;
+        }
+        for (name, img) in &self.images {
+            zip.start_file(format!("OEBPS/images/{name}"), opts)?;
+            zip.write_all(&img.data)?;
+        }
+        Ok(zip.finish()?.into_inner())
+    }
+
+    fn opf(&self, id: &str, title: &str, author: &str) -> String {
+        let mut manifest = String::from(
+            r#"<item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/><item id="css" href="style.css" media-type="text/css"/>"#,
+        );
+        let mut spine = String::new();
+        for i in 1..=self.chapters.len() {
+            manifest.push_str(&format!(
+                r#"<item id="chapter-{i}" href="chapter-{i}.xhtml" media-type="application/xhtml+xml"/>"#
+            ));
+            spine.push_str(&format!(r#"<itemref idref="chapter-{i}"/>"#));
+        }
+        for (i, (name, img)) in self.images.iter().enumerate() {
+            manifest.push_str(&format!(
+                r#"<item id="image-{}" href="images/{name}" media-type="{}"/>"#,
+                i + 1,
+                img.mime_type
+            ));
+        }
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="book-id">
+<metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+<dc:identifier id="book-id">urn:sovereign:{id}</dc:identifier>
+<dc:title>{title}</dc:title>
+<dc:creator>{author}</dc:creator>
+<dc:language>en</dc:language>
+<meta property="dcterms:modified">{modified}</meta>
+</metadata>
+<manifest>{manifest}</manifest>
+<spine>{spine}</spine>
+</package>
+"#,
+            id = xml_escape(id),
+            title = xml_escape(title),
+            author = xml_escape(author),
+            modified = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ"),
+        )
+    }
+
+    fn nav(&self, title: &str) -> String {
+        let items: String = self
+            .chapters
+            .iter()
+            .enumerate()
+            .map(|(i, (t, _))| {
+                format!(
+                    r#"<li><a href="chapter-{}.xhtml">{}</a></li>"#,
+                    i + 1,
+                    xml_escape(t)
+                )
+            })
+            .collect();
+        xhtml_page(
+            title,
+            &format!(r#"<nav epub:type="toc" id="toc"><h1>Contents</h1><ol>{items}</ol></nav>"#),
+        )
+    }
+}
+
+fn xhtml_page(title: &str, body: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops" lang="en" xml:lang="en">
+<head>
+<meta charset="utf-8"/>
+<title>{}</title>
+<link rel="stylesheet" type="text/css" href="style.css"/>
+</head>
+<body>
+{body}
+</body>
+</html>
+"#,
+        xml_escape(title)
+    )
+}
+
+const CONTAINER_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+<rootfiles><rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/></rootfiles>
+</container>
+"#;
+
+const STYLE_CSS: &str = "body { font-family: serif; line-height: 1.5; margin: 0 5%; }
+h1, h2, h3, h4, h5, h6 { font-family: sans-serif; line-height: 1.2; }
+pre { white-space: pre-wrap; font-size: 0.9em; background: #f5f5f5; padding: 0.5em; }
+code { font-family: monospace; }
+blockquote { margin-left: 1em; padding-left: 1em; border-left: 3px solid #ccc; color: #555; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; }
+img { max-width: 100%; }
+figcaption { font-size: 0.9em; color: #666; }
+";
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::export_util::test_images::write_png;
+    use crate::test_util::{ctx_with_db, dummy_ctx, make_doc_with_title};
+    use crate::traits::SkillDbAccess;
+
+    fn entries(epub: &[u8]) -> Vec<String> {
+        let zip = zip::ZipArchive::new(Cursor::new(epub)).unwrap();
+        zip.file_names().map(str::to_string).collect()
+    }
+
+    fn read_part(epub: &[u8], name: &str) -> String {
+        let mut zip = zip::ZipArchive::new(Cursor::new(epub)).unwrap();
+        let mut out = String::new();
+        zip.by_name(name).unwrap().read_to_string(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn exports_single_document() {
+        let tmp = tempfile::tempdir().unwrap();
+        let png = write_png(tmp.path());
+        let doc = make_doc_with_title("Novel", &format!("Hello <b>raw</b>\n\n![cover]({png})"));
+        let SkillOutput::File {
+            name,
+            mime_type,
+            data,
+        } = EpubExportSkill
+            .execute("export", &doc, r#"{"author": "Ada"}"#, &dummy_ctx())
+            .unwrap()
+        else {
+            panic!("expected File");
+        };
+        assert_eq!(name, "Novel.epub");
+        assert_eq!(mime_type, "application/epub+zip");
+
+        // mimetype first, stored, exact content.
+        let mut zip = zip::ZipArchive::new(Cursor::new(&data)).unwrap();
+        let first = zip.by_index(0).unwrap();
+        assert_eq!(first.name(), "mimetype");
+        assert_eq!(first.compression(), CompressionMethod::Stored);
+        drop(first);
+
+        let chapter = read_part(&data, "OEBPS/chapter-1.xhtml");
+        assert!(chapter.contains("<h1>Novel</h1>"));
+        assert!(chapter.contains("&lt;b&gt;raw&lt;/b&gt;"));
+        assert!(chapter.contains(r#"src="images/image-1.png""#));
+        assert!(!chapter.contains(&png));
+
+        let opf = read_part(&data, "OEBPS/content.opf");
+        assert!(opf.contains("<dc:creator>Ada</dc:creator>"));
+        assert!(opf.contains(r#"href="images/image-1.png" media-type="image/png""#));
+        assert!(entries(&data).contains(&"OEBPS/images/image-1.png".to_string()));
+    }
+
+    struct ThreadDb;
+
+    impl SkillDbAccess for ThreadDb {
+        fn search_documents(&self, _: &str) -> anyhow::Result<Vec<(String, String, String)>> {
+            Ok(vec![])
+        }
+        fn get_document(&self, id: &str) -> anyhow::Result<(String, String, String)> {
+            let body = format!("Body of {id}");
+            let content = ContentFields {
+                body,
+                ..Default::default()
+            }
+            .serialize();
+            Ok((id.to_string(), "thread:t1".into(), content))
+        }
+        fn list_documents(&self, thread: Option<&str>) -> anyhow::Result<Vec<(String, String)>> {
+            assert_eq!(thread, Some("thread:t1"));
+            Ok(vec![
+                ("document:a".into(), "Chapter A".into()),
+                ("document:b".into(), "Chapter B".into()),
+            ])
+        }
+        fn create_document(&self, _: &str, _: &str, _: &str) -> anyhow::Result<String> {
+            unimplemented!()
+        }
+        fn list_relationships(&self, _: &str) -> anyhow::Result<Vec<(String, String)>> {
+            Ok(vec![])
+        }
+        fn list_backlinks(&self, _: &str) -> anyhow::Result<Vec<(String, String)>> {
+            Ok(vec![])
+        }
+        fn list_all_documents_with_link_counts(
+            &self,
+        ) -> anyhow::Result<Vec<(String, String, u32, u32)>> {
+            Ok(vec![])
+        }
+        fn find_or_create_thread(&self, _: &str, _: &str) -> anyhow::Result<String> {
+            Ok("thread:t1".into())
+        }
+    }
+
+    #[test]
+    fn exports_thread_as_chapters() {
+        let ctx = ctx_with_db(
+            [Capability::ReadDocument, Capability::ReadAllDocuments],
+            Arc::new(ThreadDb),
+        );
+        let doc = make_doc_with_title("Chapter A", "");
+        let SkillOutput::File { data, .. } = EpubExportSkill
+            .execute("export_thread", &doc, r#"{"title": "The Book"}"#, &ctx)
+            .unwrap()
+        else {
+            panic!("expected File");
+        };
+        let nav = read_part(&data, "OEBPS/nav.xhtml");
+        assert!(nav.contains(r#"<a href="chapter-1.xhtml">Chapter A</a>"#));
+        assert!(nav.contains(r#"<a href="chapter-2.xhtml">Chapter B</a>"#));
+        assert!(read_part(&data, "OEBPS/chapter-2.xhtml").contains("Body of document:b"));
+        assert!(read_part(&data, "OEBPS/content.opf").contains("<dc:title>The Book</dc:title>"));
+    }
+}
@@ -1,5 +1,4 @@
-use pulldown_cmark::{html, Options, Parser};
-
+use crate::export_util::{is_local_image_ref, load_image, load_images, render_markdown_html};
 use crate::manifest::Capability;
 use crate::markdown_util::sanitize_filename;
 use crate::traits::{CoreSkill, SkillContext, SkillDocument, SkillOutput};
@@ -12,7 +11,8 @@ impl CoreSkill for HtmlExportSkill {
     }
 
     fn required_capabilities(&self) -> Vec<Capability> {
-        vec![Capability::ReadDocument]
+        // ReadFilesystem: images are read from disk and inlined.
+        vec![Capability::ReadDocument, Capability::ReadFilesystem]
     }
 
     fn activate(&mut self) -> anyhow::Result<()> {
@@ -32,7 +32,8 @@ impl CoreSkill for HtmlExportSkill {
     ) -> anyhow::Result<SkillOutput> {
         match action {
             "export" => {
-                let body_html = render_html(&doc.content.body);
+                let mut body_html = render_html(&doc.content.body);
+                body_html.push_str(&render_figures(&doc.content.images));
                 let document = wrap_standalone(&doc.title, &body_html);
                 let safe_name = sanitize_filename(&doc.title);
                 Ok(SkillOutput::File {
@@ -48,22 +49,36 @@ impl CoreSkill for HtmlExportSkill {
     fn actions(&self) -> Vec<(String, String)> {
         vec![("export".into(), "Export as HTML".into())]
     }
-
-    fn file_types(&self) -> Vec<String> {
-        vec!["md".into(), "markdown".into()]
-    }
 }
 
+/// Render the body, inlining local images as `data:` URIs so the file is
+/// standalone.
 fn render_html(markdown: &str) -> String {
-    let mut opts = Options::empty();
-    opts.insert(Options::ENABLE_TABLES);
-    opts.insert(Options::ENABLE_STRIKETHROUGH);
-    opts.insert(Options::ENABLE_TASKLISTS);
-    opts.insert(Options::ENABLE_FOOTNOTES);
-    let parser = Parser::new_ext(markdown, opts);
-    let mut out = String::new();
-    html::push_html(&mut out, parser);
-    out
+    render_markdown_html(markdown, false, |src| {
+        is_local_image_ref(src)
+            .then(|| load_image(src, ""))
+            .flatten()
+            .map(|img| img.data_uri())
+    })
+}
+
+/// The document's attached images as inlined `<figure>`s.
+fn render_figures(images: &[sovereign_core::content::ContentImage]) -> String {
+    load_images(images)
+        .iter()
+        .map(|img| {
+            let caption = if img.caption.is_empty() {
+                String::new()
+            } else {
+                format!("<figcaption>{}</figcaption>", html_escape(&img.caption))
+            };
+            format!(
+                "<figure><img src=\"{}\" alt=\"{}\">{caption}</figure>\n",
+                img.data_uri(),
+                html_escape(&img.caption)
+            )
+        })
+        .collect()
 }
 
 fn wrap_standalone(title: &str, body_html: &str) -> String {
@@ -100,6 +115,8 @@ blockquote {{
 table {{ border-collapse: collapse; }}
 th, td {{ border: 1px solid #ddd; padding: 0.4em 0.8em; }}
 img {{ max-width: 100%; height: auto; }}
+figure {{ margin: 1.5em 0; }}
+figcaption {{ color: #666; font-size: 0.9em; }}
 a {{ color: #0066cc; }}
 </style>
 </head>
@@ -152,6 +169,27 @@ mod tests {
         assert!(html.contains("<del>struck</del>"));
     }
 
+    #[test]
+    fn inlines_local_images() {
+        let tmp = tempfile::tempdir().unwrap();
+        let png = crate::export_util::test_images::write_png(tmp.path());
+        let mut doc = make_doc_with_title("Pics", &format!("![pixel]({png})"));
+        doc.content.images.push(sovereign_core::content::ContentImage {
+            path: png.clone(),
+            caption: "Attached".into(),
+        });
+        let SkillOutput::File { data, .. } = HtmlExportSkill
+            .execute("export", &doc, "", &dummy_ctx())
+            .unwrap()
+        else {
+            panic!("expected File");
+        };
+        let html = String::from_utf8(data).unwrap();
+        assert!(!html.contains(&png), "local path leaked into export");
+        assert_eq!(html.matches("data:image/png;base64,").count(), 2);
+        assert!(html.contains("<figcaption>Attached</figcaption>"));
+    }
+
     #[test]
     fn html_escapes_title() {
         let escaped = html_escape("Hello <world> & \"friends\"");
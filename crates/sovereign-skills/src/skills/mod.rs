@@ -1,7 +1,9 @@
 pub mod backlink_map;
 pub mod csv_to_md;
 pub mod daily_journal;
+pub mod docx_export;
 pub mod duplicate_document;
+pub mod epub_export;
 pub mod file_import;
 pub mod find_replace;
 pub mod html_export;
@@ -20,5 +22,6 @@ pub mod search;
 pub mod table_of_contents;
 pub mod text_editor;
 pub mod thread_summary;
+pub mod translation;
 pub mod video;
 pub mod word_count;
@@ -47,10 +47,6 @@ impl CoreSkill for PlaintextExportSkill {
     fn actions(&self) -> Vec<(String, String)> {
         vec![("export".into(), "Export as Plain Text".into())]
     }
-
-    fn file_types(&self) -> Vec<String> {
-        vec!["md".into(), "markdown".into()]
-    }
 }
 
 fn render_plaintext(markdown: &str) -> String {
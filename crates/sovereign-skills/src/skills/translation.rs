@@ -0,0 +1,281 @@
+use crate::manifest::Capability;
+use crate::traits::{CoreSkill, SkillContext, SkillDocument, SkillLlmAccess, SkillOutput};
+
+pub struct TranslationSkill;
+
+/// Chunk size (chars) per generation call. Small local models lose the
+/// thread on long inputs and the response is capped by `MAX_RESPONSE_TOKENS`,
+/// so long documents are translated a few paragraphs at a time.
+const CHUNK_CHAR_CAP: usize = 1500;
+
+const MAX_RESPONSE_TOKENS: u32 = 800;
+
+impl CoreSkill for TranslationSkill {
+    fn name(&self) -> &str {
+        "translation"
+    }
+
+    fn required_capabilities(&self) -> Vec<Capability> {
+        // WriteDocument: the translation is saved as a new document in the
+        // current thread, leaving the original untouched.
+        vec![
+            Capability::ReadDocument,
+            Capability::WriteDocument,
+            Capability::LlmInference,
+        ]
+    }
+
+    fn activate(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn deactivate(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn execute(
+        &self,
+        action: &str,
+        doc: &SkillDocument,
+        params: &str,
+        ctx: &SkillContext,
+    ) -> anyhow::Result<SkillOutput> {
+        match action {
+            "translate" => {
+                let language = parse_language(params)?;
+                let db = ctx
+                    .db
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("Translation requires database access"))?;
+                let llm = ctx
+                    .llm
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("Translation requires LLM access"))?;
+
+                let body = translate_text(llm.as_ref(), &doc.content.body, &language)?;
+                let (_title, thread_id, _content) = db.get_document(&doc.id)?;
+                let new_title = format!("{} ({language})", doc.title);
+                let content = sovereign_core::content::ContentFields {
+                    body,
+                    images: doc.content.images.clone(),
+                    videos: doc.content.videos.clone(),
+                    files: doc.content.files.clone(),
+                }
+                .serialize();
+                let new_id = db.create_document(&new_title, &thread_id, &content)?;
+
+                let json = serde_json::json!({
+                    "doc_id": new_id,
+                    "title": new_title,
+                    "language": language,
+                    "original_id": doc.id,
+                });
+                Ok(SkillOutput::StructuredData {
+                    kind: "translation_result".into(),
+                    json: json.to_string(),
+                })
+            }
+            _ => anyhow::bail!("Unknown action: {action}"),
+        }
+    }
+
+    fn actions(&self) -> Vec<(String, String)> {
+        vec![("translate".into(), "Translate Document".into())]
+    }
+}
+
+/// Target language from `params`: either a bare name (`"French"`) or
+/// `{"language": "French"}`.
+fn parse_language(params: &str) -> anyhow::Result<String> {
+    let raw = params.trim();
+    let language = match serde_json::from_str::<serde_json::Value>(raw) {
+        Ok(v) => v
+            .get("language")
+            .and_then(|l| l.as_str())
+            .map(str::to_string)
+            .unwrap_or_default(),
+        Err(_) => raw.to_string(),
+    };
+    let language = language.trim();
+    if language.is_empty() {
+        anyhow::bail!("Translation needs a target language (e.g. \"French\")");
+    }
+    if language.len() > 40 || language.contains('\n') {
+        anyhow::bail!("Invalid target language: {language}");
+    }
+    Ok(language.to_string())
+}
+
+/// Translate `text` into `language` with the local model, chunk by chunk.
+/// Shared with the inbox's inline message translation.
+pub fn translate_text(
+    llm: &dyn SkillLlmAccess,
+    text: &str,
+    language: &str,
+) -> anyhow::Result<String> {
+    let mut out = Vec::new();
+    for chunk in chunk_paragraphs(text, CHUNK_CHAR_CAP) {
+        if chunk.trim().is_empty() {
+            continue;
+        }
+        let prompt = format!(
+            "Translate the text between the markers into {language}. Preserve \
+             markdown formatting, line breaks, names, numbers and URLs. Output \
+             only the translation, with no commentary.\n\n\
+             <<<TEXT\n{chunk}\nTEXT>>>\n\nTranslation:\n"
+        );
+        let translated = llm.generate(&prompt, MAX_RESPONSE_TOKENS)?;
+        out.push(translated.trim().to_string());
+    }
+    Ok(out.join("\n\n"))
+}
+
+/// Group paragraphs (blank-line separated) into chunks of at most `cap`
+/// chars. A single oversized paragraph is split on char boundaries.
+fn chunk_paragraphs(text: &str, cap: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for para in text.split("\n\n") {
+        let para_len = para.chars().count();
+        if para_len > cap {
+            if !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+            }
+            let chars: Vec<char> = para.chars().collect();
+            chunks.extend(chars.chunks(cap).map(|c| c.iter().collect::<String>()));
+            continue;
+        }
+        if !current.is_empty() && current.chars().count() + 2 + para_len > cap {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(para);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::test_util::{ctx_with_db_and_llm, make_doc_with_title};
+    use crate::traits::SkillDbAccess;
+
+    #[derive(Default)]
+    struct StubDb {
+        created: Mutex<Vec<(String, String, String)>>,
+    }
+
+    impl SkillDbAccess for StubDb {
+        fn search_documents(&self, _: &str) -> anyhow::Result<Vec<(String, String, String)>> {
+            Ok(vec![])
+        }
+        fn get_document(&self, _: &str) -> anyhow::Result<(String, String, String)> {
+            Ok(("Notes".into(), "thread:t1".into(), String::new()))
+        }
+        fn list_documents(&self, _: Option<&str>) -> anyhow::Result<Vec<(String, String)>> {
+            Ok(vec![])
+        }
+        fn create_document(&self, title: &str, thread_id: &str, content: &str) -> anyhow::Result<String> {
+            self.created
+                .lock()
+                .unwrap()
+                .push((title.into(), thread_id.into(), content.into()));
+            Ok("document:new".into())
+        }
+        fn list_relationships(&self, _: &str) -> anyhow::Result<Vec<(String, String)>> {
+            Ok(vec![])
+        }
+        fn list_backlinks(&self, _: &str) -> anyhow::Result<Vec<(String, String)>> {
+            Ok(vec![])
+        }
+        fn list_all_documents_with_link_counts(
+            &self,
+        ) -> anyhow::Result<Vec<(String, String, u32, u32)>> {
+            Ok(vec![])
+        }
+        fn find_or_create_thread(&self, _: &str, _: &str) -> anyhow::Result<String> {
+            Ok("thread:t1".into())
+        }
+    }
+
+    /// Echoes the marked text back upper-cased, and counts calls.
+    #[derive(Default)]
+    struct UpperLlm {
+        calls: Mutex<u32>,
+    }
+
+    impl SkillLlmAccess for UpperLlm {
+        fn generate(&self, prompt: &str, _max_tokens: u32) -> anyhow::Result<String> {
+            *self.calls.lock().unwrap() += 1;
+            let start = prompt.find("<<<TEXT\n").unwrap() + "<<<TEXT\n".len();
+            let end = prompt.find("\nTEXT>>>").unwrap();
+            Ok(prompt[start..end].to_uppercase())
+        }
+    }
+
+    fn caps() -> [Capability; 3] {
+        [
+            Capability::ReadDocument,
+            Capability::WriteDocument,
+            Capability::LlmInference,
+        ]
+    }
+
+    #[test]
+    fn translates_into_new_document() {
+        let db = Arc::new(StubDb::default());
+        let llm = Arc::new(UpperLlm::default());
+        let ctx = ctx_with_db_and_llm(caps(), db.clone(), llm);
+        let doc = make_doc_with_title("Notes", "hello\n\nworld");
+
+        let out = TranslationSkill
+            .execute("translate", &doc, r#"{"language": "French"}"#, &ctx)
+            .unwrap();
+        let SkillOutput::StructuredData { kind, json } = out else {
+            panic!("expected StructuredData");
+        };
+        assert_eq!(kind, "translation_result");
+        let v: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(v["doc_id"], "document:new");
+        assert_eq!(v["title"], "Notes (French)");
+
+        let created = db.created.lock().unwrap();
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].1, "thread:t1");
+        let body = sovereign_core::content::ContentFields::parse(&created[0].2).body;
+        assert_eq!(body, "HELLO\n\nWORLD");
+    }
+
+    #[test]
+    fn requires_a_language() {
+        assert!(parse_language("").is_err());
+        assert!(parse_language(r#"{"language": " "}"#).is_err());
+        assert_eq!(parse_language("German").unwrap(), "German");
+        assert_eq!(parse_language(r#"{"language":"Italian"}"#).unwrap(), "Italian");
+    }
+
+    #[test]
+    fn long_text_is_chunked() {
+        let llm = UpperLlm::default();
+        let para = "a".repeat(CHUNK_CHAR_CAP / 2 + 10);
+        let text = format!("{para}\n\n{para}\n\n{para}");
+        let out = translate_text(&llm, &text, "Spanish").unwrap();
+        assert_eq!(*llm.calls.lock().unwrap(), 3);
+        assert_eq!(out, text.to_uppercase());
+    }
+
+    #[test]
+    fn oversized_paragraph_splits_on_char_boundaries() {
+        let text = "é".repeat(CHUNK_CHAR_CAP * 2 + 1);
+        let chunks = chunk_paragraphs(&text, CHUNK_CHAR_CAP);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.concat(), text);
+    }
+}
@@ -57,6 +57,7 @@ impl CoreSkill for VideoSkill {
                     body: doc.content.body.clone(),
                     images: doc.content.images.clone(),
                     videos,
+                    files: doc.content.files.clone(),
                 }))
             }
             "remove" => {
@@ -76,6 +77,7 @@ impl CoreSkill for VideoSkill {
                     body: doc.content.body.clone(),
                     images: doc.content.images.clone(),
                     videos,
+                    files: doc.content.files.clone(),
                 }))
             }
             "play" => {
@@ -131,6 +133,7 @@ mod tests {
                         thumbnail_path: None,
                     },
                 ],
+                files: vec![],
             },
         }
     }
@@ -1,6 +1,14 @@
 use crate::manifest::Capability;
 use crate::traits::{CoreSkill, SkillContext, SkillDocument, SkillOutput};
 
+/// Reading speed behind `reading_time_min` and the reading queue.
+pub const WORDS_PER_MINUTE: usize = 200;
+
+/// Minutes needed to read `words` words, rounded up.
+pub fn reading_minutes(words: usize) -> u64 {
+    words.div_ceil(WORDS_PER_MINUTE) as u64
+}
+
 pub struct WordCountSkill;
 
 impl CoreSkill for WordCountSkill {
@@ -33,7 +41,7 @@ impl CoreSkill for WordCountSkill {
                 let words = body.split_whitespace().count();
                 let characters = body.chars().count();
                 let lines = if body.is_empty() { 0 } else { body.lines().count() };
-                let reading_time_min = (words as f64 / 200.0).ceil() as u64;
+                let reading_time_min = reading_minutes(words);
 
                 let json = serde_json::json!({
                     "words": words,
@@ -0,0 +1,265 @@
+//! `skill-plugin` world versioning and adapters for older plugins.
+//!
+//! The WIT package is versioned (`sovereign:skill@X.Y.Z`) and every
+//! interface a component imports carries that version in its name
+//! (`sovereign:skill/host-db@0.1.0`), which is how a loaded plugin's world
+//! is detected. Plugins built against an older world are run through that
+//! world's own bindings; the adapters here bridge its host imports to the
+//! current implementation and convert its outputs into current types, so the
+//! rest of the host only ever deals with the latest world.
+//!
+//! Supporting a new world version: bump `wit/skill.wit`, copy the previous
+//! file to `wit/compat/v<old>/skill.wit`, add a bindings module and adapter
+//! below, and a [`WorldVersion`] variant.
+
+use std::collections::HashSet;
+
+use wasmtime::component::Component;
+use wasmtime::Engine;
+
+use crate::manifest::Capability;
+use crate::wasm::host_bridge::{sovereign as current, PluginState};
+
+/// `skill-plugin` world versions this host can load, newest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldVersion {
+    /// Adds the `file-access` capability and `scratch-file` output.
+    V0_2,
+    /// The original world.
+    V0_1,
+}
+
+impl WorldVersion {
+    pub const CURRENT: Self = Self::V0_2;
+    pub const SUPPORTED: [Self; 2] = [Self::V0_2, Self::V0_1];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::V0_2 => "0.2.0",
+            Self::V0_1 => "0.1.0",
+        }
+    }
+
+    /// Parse a world version. Patch releases never change the world's
+    /// shape, so only major.minor is significant.
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.split('.');
+        let major = parts.next()?;
+        let minor = parts.next()?;
+        Self::SUPPORTED
+            .into_iter()
+            .find(|v| v.as_str().split('.').take(2).eq([major, minor]))
+    }
+
+    /// Version from an imported interface name such as
+    /// `sovereign:skill/host-db@0.1.0`. `Ok(None)` for unrelated imports.
+    fn from_import_name(name: &str) -> anyhow::Result<Option<Self>> {
+        let Some(rest) = name.strip_prefix("sovereign:skill/") else {
+            return Ok(None);
+        };
+        let Some((_, version)) = rest.split_once('@') else {
+            return Ok(None);
+        };
+        match Self::parse(version) {
+            Some(v) => Ok(Some(v)),
+            None => anyhow::bail!(
+                "plugin targets skill-plugin world {version}; this host supports {}",
+                supported_list()
+            ),
+        }
+    }
+
+    /// Detect the world a component was built against from its imports.
+    /// `Ok(None)` if it imports nothing from `sovereign:skill` (a plugin that
+    /// never calls the host), in which case the caller probes each version.
+    pub fn detect(engine: &Engine, component: &Component) -> anyhow::Result<Option<Self>> {
+        for (name, _) in component.component_type().imports(engine) {
+            if let Some(version) = Self::from_import_name(name)? {
+                return Ok(Some(version));
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn supported_list() -> String {
+    WorldVersion::SUPPORTED
+        .iter()
+        .map(|v| v.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Bindings for `sovereign:skill@0.1.0`.
+pub(crate) mod v0_1 {
+    wasmtime::component::bindgen!({
+        world: "skill-plugin",
+        path: "wit/compat/v0.1.0",
+    });
+}
+
+use v0_1::sovereign::skill as old;
+
+impl old::types::Host for PluginState {}
+
+/// 0.1.0's host-db is identical to the current one; delegate and convert.
+impl old::host_db::Host for PluginState {
+    fn search_documents(&mut self, query: String) -> Result<Vec<old::types::SearchResult>, String> {
+        current::skill::host_db::Host::search_documents(self, query).map(|results| {
+            results
+                .into_iter()
+                .map(|r| old::types::SearchResult {
+                    id: r.id,
+                    title: r.title,
+                    snippet: r.snippet,
+                })
+                .collect()
+        })
+    }
+
+    fn get_document(&mut self, id: String) -> Result<old::types::DocDetail, String> {
+        current::skill::host_db::Host::get_document(self, id).map(|d| old::types::DocDetail {
+            title: d.title,
+            thread_id: d.thread_id,
+            content: d.content,
+        })
+    }
+
+    fn list_documents(
+        &mut self,
+        thread_id: Option<String>,
+    ) -> Result<Vec<old::types::DocEntry>, String> {
+        current::skill::host_db::Host::list_documents(self, thread_id).map(|docs| {
+            docs.into_iter()
+                .map(|d| old::types::DocEntry {
+                    id: d.id,
+                    title: d.title,
+                })
+                .collect()
+        })
+    }
+
+    fn create_document(
+        &mut self,
+        title: String,
+        thread_id: String,
+        content: String,
+    ) -> Result<String, String> {
+        current::skill::host_db::Host::create_document(self, title, thread_id, content)
+    }
+}
+
+pub(crate) fn v0_1_cap_to_capability(c: old::types::Capability) -> Capability {
+    use old::types::Capability as OldCap;
+    match c {
+        OldCap::ReadDocument => Capability::ReadDocument,
+        OldCap::WriteDocument => Capability::WriteDocument,
+        OldCap::ReadAllDocuments => Capability::ReadAllDocuments,
+        OldCap::WriteAllDocuments => Capability::WriteAllDocuments,
+        OldCap::ReadFilesystem => Capability::ReadFilesystem,
+        OldCap::WriteFilesystem => Capability::WriteFilesystem,
+        OldCap::Network => Capability::Network,
+    }
+}
+
+/// Granted capabilities as 0.1.0 knows them; newer ones are dropped.
+pub(crate) fn capabilities_to_v0_1(granted: &HashSet<Capability>) -> Vec<old::types::Capability> {
+    use old::types::Capability as OldCap;
+    granted
+        .iter()
+        .filter_map(|c| match c {
+            Capability::ReadDocument => Some(OldCap::ReadDocument),
+            Capability::WriteDocument => Some(OldCap::WriteDocument),
+            Capability::ReadAllDocuments => Some(OldCap::ReadAllDocuments),
+            Capability::WriteAllDocuments => Some(OldCap::WriteAllDocuments),
+            Capability::ReadFilesystem => Some(OldCap::ReadFilesystem),
+            Capability::WriteFilesystem => Some(OldCap::WriteFilesystem),
+            Capability::Network => Some(OldCap::Network),
+            Capability::FileAccess | Capability::LlmInference => None,
+        })
+        .collect()
+}
+
+pub(crate) fn v0_1_doc(
+    doc: &current::skill::types::SkillDocument,
+) -> old::types::SkillDocument {
+    old::types::SkillDocument {
+        id: doc.id.clone(),
+        title: doc.title.clone(),
+        body: doc.body.clone(),
+    }
+}
+
+pub(crate) fn v0_1_output_to_current(
+    output: old::types::SkillOutput,
+) -> current::skill::types::SkillOutput {
+    use current::skill::types as cur;
+    match output {
+        old::types::SkillOutput::ContentUpdate(body) => cur::SkillOutput::ContentUpdate(body),
+        old::types::SkillOutput::File(f) => cur::SkillOutput::File(cur::FileOutput {
+            name: f.name,
+            mime_type: f.mime_type,
+            data: f.data,
+        }),
+        old::types::SkillOutput::None => cur::SkillOutput::None,
+        old::types::SkillOutput::StructuredData(sd) => {
+            cur::SkillOutput::StructuredData(cur::StructuredOutput {
+                kind: sd.kind,
+                json: sd.json,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_supported_versions() {
+        assert_eq!(WorldVersion::parse("0.1.0"), Some(WorldVersion::V0_1));
+        assert_eq!(WorldVersion::parse("0.2.3"), Some(WorldVersion::V0_2));
+        assert_eq!(WorldVersion::parse("0.3.0"), None);
+        assert_eq!(WorldVersion::parse("garbage"), None);
+        assert_eq!(WorldVersion::CURRENT, WorldVersion::SUPPORTED[0]);
+    }
+
+    #[test]
+    fn version_from_import_names() {
+        assert_eq!(
+            WorldVersion::from_import_name("sovereign:skill/host-db@0.1.0").unwrap(),
+            Some(WorldVersion::V0_1)
+        );
+        assert_eq!(
+            WorldVersion::from_import_name("sovereign:skill/types@0.2.0").unwrap(),
+            Some(WorldVersion::V0_2)
+        );
+        assert_eq!(
+            WorldVersion::from_import_name("wasi:io/streams@0.2.6").unwrap(),
+            None
+        );
+        let err = WorldVersion::from_import_name("sovereign:skill/host-db@0.9.0").unwrap_err();
+        assert!(err.to_string().contains("0.9.0"), "{err}");
+    }
+
+    #[test]
+    fn v0_1_outputs_convert() {
+        let out = v0_1_output_to_current(old::types::SkillOutput::StructuredData(
+            old::types::StructuredOutput {
+                kind: "word_count".into(),
+                json: "{}".into(),
+            },
+        ));
+        assert!(matches!(
+            out,
+            current::skill::types::SkillOutput::StructuredData(ref sd) if sd.kind == "word_count"
+        ));
+
+        let granted: HashSet<_> = [Capability::ReadDocument, Capability::FileAccess]
+            .into_iter()
+            .collect();
+        let caps = capabilities_to_v0_1(&granted);
+        assert_eq!(caps.len(), 1);
+        assert_eq!(v0_1_cap_to_capability(caps[0]), Capability::ReadDocument);
+    }
+}
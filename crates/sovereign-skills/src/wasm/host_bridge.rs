@@ -1,8 +1,11 @@
 use std::collections::HashSet;
+use std::path::Path;
 use std::sync::Arc;
-use wasmtime::component::bindgen;
+use wasmtime::component::{bindgen, ResourceTable};
 use wasmtime::StoreLimits;
+use wasmtime_wasi::{DirPerms, FilePerms, WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView};
 
+use crate::audit::SkillPermissions;
 use crate::manifest::Capability as ManifestCap;
 use crate::traits::SkillDbAccess;
 
@@ -35,13 +38,53 @@ pub(crate) struct PluginState {
     /// hold the corresponding all-documents capability.
     pub scope_doc: Option<String>,
     pub scope_thread: Option<String>,
+    /// Skill name, for looking up user revocations.
+    pub skill_name: String,
+    /// Live revocation set shared with the registry. Checked on every host
+    /// call, so revoking a capability on the permissions page takes effect
+    /// mid-execution, not just on the next invocation.
+    pub permissions: SkillPermissions,
+    /// WASI context. Preopens nothing unless the skill holds `FileAccess`,
+    /// in which case only its own scratch directory is mounted.
+    pub wasi: WasiCtx,
+    pub table: ResourceTable,
+}
+
+/// Build the WASI context for one execution. No env, args, network or
+/// inherited stdio; `scratch` (if any) is the sole preopened directory,
+/// mounted at [`crate::scratch::SCRATCH_GUEST_PATH`].
+pub(crate) fn wasi_ctx(scratch: Option<&Path>) -> anyhow::Result<WasiCtx> {
+    let mut builder = WasiCtxBuilder::new();
+    if let Some(dir) = scratch {
+        builder.preopened_dir(
+            dir,
+            crate::scratch::SCRATCH_GUEST_PATH,
+            DirPerms::all(),
+            FilePerms::all(),
+        )?;
+    }
+    Ok(builder.build())
+}
+
+impl WasiView for PluginState {
+    fn ctx(&mut self) -> WasiCtxView<'_> {
+        WasiCtxView {
+            ctx: &mut self.wasi,
+            table: &mut self.table,
+        }
+    }
 }
 
 impl PluginState {
+    /// Granted for this execution and not since revoked by the user.
+    fn holds(&self, cap: &ManifestCap) -> bool {
+        self.granted.contains(cap) && !self.permissions.is_revoked(&self.skill_name, cap)
+    }
+
     /// Require any one of `caps` to be granted (e.g. a single-document read
     /// is covered by either ReadDocument or ReadAllDocuments).
     fn require_any(&self, caps: &[ManifestCap]) -> Result<(), String> {
-        if caps.iter().any(|c| self.granted.contains(c)) {
+        if caps.iter().any(|c| self.holds(c)) {
             Ok(())
         } else {
             Err(format!(
@@ -63,10 +106,10 @@ impl PluginState {
         target: &str,
         what: &str,
     ) -> Result<(), String> {
-        if self.granted.contains(&all_cap) {
+        if self.holds(&all_cap) {
             return Ok(());
         }
-        if self.granted.contains(&one_cap) {
+        if self.holds(&one_cap) {
             return match scope {
                 Some(s) if s == target => Ok(()),
                 Some(_) => Err(format!(
@@ -185,6 +228,10 @@ mod tests {
             limits: StoreLimits::default(),
             scope_doc: Some(SCOPE_DOC.to_string()),
             scope_thread: Some(SCOPE_THREAD.to_string()),
+            skill_name: "test-skill".into(),
+            permissions: SkillPermissions::new(),
+            wasi: wasi_ctx(None).unwrap(),
+            table: ResourceTable::new(),
         }
     }
 
@@ -266,10 +313,24 @@ mod tests {
             limits: StoreLimits::default(),
             scope_doc: None,
             scope_thread: None,
+            skill_name: "test-skill".into(),
+            permissions: SkillPermissions::new(),
+            wasi: wasi_ctx(None).unwrap(),
+            table: ResourceTable::new(),
         };
         let err = s
             .create_document("t".into(), "thread:x".into(), "c".into())
             .unwrap_err();
         assert!(err.contains("no thread scope"), "{err}");
     }
+
+    #[test]
+    fn revocation_applies_to_next_host_call() {
+        let mut s = state_with(&[ManifestCap::ReadAllDocuments]);
+        assert!(s.search_documents("x".into()).unwrap_err().contains("No DB access"));
+        s.permissions
+            .revoke("test-skill", ManifestCap::ReadAllDocuments)
+            .unwrap();
+        assert!(s.search_documents("x".into()).unwrap_err().contains("capability"));
+    }
 }
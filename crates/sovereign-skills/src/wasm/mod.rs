@@ -1,6 +1,8 @@
+mod compat;
 mod host_bridge;
 mod runner;
 mod wasm_skill;
 
+pub use compat::WorldVersion;
 pub use runner::{WasmLimits, WasmSkillRunner};
 pub use wasm_skill::WasmSkill;
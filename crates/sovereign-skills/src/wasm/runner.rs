@@ -2,6 +2,8 @@ use std::path::Path;
 use wasmtime::component::Component;
 use wasmtime::{Config, Engine};
 
+use sovereign_crypto::skill_signing::{SkillVerification, TrustedKeys};
+
 use crate::wasm::wasm_skill::WasmSkill;
 
 /// Default resource limits for WASM plugin execution.
@@ -62,9 +64,14 @@ impl WasmSkillRunner {
         WasmSkill::new(self.engine.clone(), component, self.limits.clone())
     }
 
-    /// Scan a directory for WASM skill plugins.
-    /// Looks for subdirectories containing both a `skill.json` and a `.wasm` file.
-    pub fn discover_skills(&self, dir: &Path) -> Vec<(String, anyhow::Result<WasmSkill>)> {
+    /// Scan a directory for WASM skill plugins: subdirectories whose
+    /// `skill.json` declares a `module`. Each component is built from the
+    /// same bytes its signature verdict covers.
+    pub fn discover_skills(
+        &self,
+        dir: &Path,
+        trusted: &TrustedKeys,
+    ) -> Vec<(String, anyhow::Result<(WasmSkill, SkillVerification)>)> {
         let mut results = Vec::new();
         if !dir.is_dir() {
             return results;
@@ -85,28 +92,20 @@ impl WasmSkillRunner {
                 continue;
             }
 
-            // Find the first .wasm file in the directory
-            let wasm_file = std::fs::read_dir(&skill_dir)
-                .ok()
-                .and_then(|entries| {
-                    entries
-                        .flatten()
-                        .find(|e| {
-                            e.path()
-                                .extension()
-                                .map(|ext| ext == "wasm")
-                                .unwrap_or(false)
-                        })
-                        .map(|e| e.path())
-                });
-
-            if let Some(wasm_path) = wasm_file {
-                let dir_name = skill_dir
-                    .file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy()
-                    .to_string();
-                results.push((dir_name, self.load_skill(&wasm_path)));
+            let dir_name = skill_dir
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            match crate::signing::read_skill_dir(&skill_dir, trusted) {
+                Ok(package) => {
+                    let Some(wasm) = package.wasm else {
+                        continue;
+                    };
+                    let skill = self.load_skill_from_bytes(&wasm);
+                    results.push((dir_name, skill.map(|s| (s, package.verification))));
+                }
+                Err(e) => results.push((dir_name, Err(e))),
             }
         }
 
@@ -145,7 +144,7 @@ mod tests {
     #[test]
     fn test_discover_empty_dir() {
         let runner = WasmSkillRunner::new().unwrap();
-        let results = runner.discover_skills(Path::new("/nonexistent"));
+        let results = runner.discover_skills(Path::new("/nonexistent"), &TrustedKeys::default());
         assert!(results.is_empty());
     }
 }
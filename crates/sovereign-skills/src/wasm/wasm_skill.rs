@@ -1,11 +1,16 @@
-use wasmtime::component::{Component, HasSelf, Linker};
+use std::collections::HashSet;
+
+use wasmtime::component::{Component, HasSelf, Linker, ResourceTable};
 use wasmtime::{Engine, Store, StoreLimitsBuilder};
 
 use sovereign_core::content::ContentFields;
 
+use crate::audit::SkillPermissions;
 use crate::manifest::Capability;
+use crate::scratch::ScratchSpace;
 use crate::traits::{CoreSkill, SkillContext, SkillDocument, SkillOutput};
-use crate::wasm::host_bridge::{PluginState, SkillPlugin};
+use crate::wasm::compat::{self, WorldVersion};
+use crate::wasm::host_bridge::{wasi_ctx, PluginState, SkillPlugin};
 use crate::wasm::runner::WasmLimits;
 
 /// A WASM skill that implements CoreSkill by delegating to a
@@ -18,6 +23,13 @@ pub struct WasmSkill {
     engine: Engine,
     component: Component,
     limits: WasmLimits,
+    /// The `skill-plugin` world the component was built against.
+    world: WorldVersion,
+    /// User revocations, installed by the registry at load time.
+    permissions: SkillPermissions,
+    /// Backing store for `FileAccess`; without one the capability grants
+    /// no directory at all.
+    scratch: Option<ScratchSpace>,
     // Cached metadata
     cached_name: String,
     cached_capabilities: Vec<Capability>,
@@ -27,18 +39,59 @@ pub struct WasmSkill {
 
 impl WasmSkill {
     /// Load a WASM component and cache its metadata.
+    ///
+    /// The world version is detected from the component's imports; a
+    /// plugin that imports nothing from the host is tried against each
+    /// supported world, newest first.
     pub(crate) fn new(
         engine: Engine,
         component: Component,
         limits: WasmLimits,
     ) -> anyhow::Result<Self> {
-        // Instantiate once to read metadata exports, then discard the Store.
-        let mut linker = Linker::new(&engine);
-        SkillPlugin::add_to_linker::<PluginState, HasSelf<PluginState>>(
-            &mut linker,
-            |state| state,
-        )?;
+        let candidates = match WorldVersion::detect(&engine, &component)? {
+            Some(version) => vec![version],
+            None => WorldVersion::SUPPORTED.to_vec(),
+        };
+
+        let mut last_err = None;
+        for world in candidates {
+            match Self::load_metadata(&engine, &component, &limits, world) {
+                Ok(meta) => {
+                    if world != WorldVersion::CURRENT {
+                        tracing::info!(
+                            "WASM skill '{}' targets skill-plugin@{}; running through the compatibility adapter",
+                            meta.name,
+                            world.as_str()
+                        );
+                    }
+                    return Ok(Self {
+                        engine,
+                        component,
+                        limits,
+                        world,
+                        permissions: SkillPermissions::new(),
+                        scratch: None,
+                        cached_name: meta.name,
+                        cached_capabilities: meta.capabilities,
+                        cached_actions: meta.actions,
+                        cached_file_types: meta.file_types,
+                    });
+                }
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err
+            .unwrap_or_else(|| anyhow::anyhow!("no skill-plugin world to try"))
+            .context("component does not implement a supported skill-plugin world"))
+    }
 
+    /// Instantiate once to read metadata exports, then discard the Store.
+    fn load_metadata(
+        engine: &Engine,
+        component: &Component,
+        limits: &WasmLimits,
+        world: WorldVersion,
+    ) -> anyhow::Result<PluginMetadata> {
         let store_limits = StoreLimitsBuilder::new()
             .memory_size(limits.memory_bytes)
             .instances(limits.max_instances)
@@ -50,29 +103,41 @@ impl WasmSkill {
             limits: store_limits,
             scope_doc: None,
             scope_thread: None,
+            skill_name: String::new(),
+            permissions: SkillPermissions::new(),
+            wasi: wasi_ctx(None)?,
+            table: ResourceTable::new(),
         };
-        let mut store = Store::new(&engine, state);
+        let mut store = Store::new(engine, state);
         store.limiter(|s| &mut s.limits);
         store.set_fuel(10_000_000)?; // Generous fuel for metadata calls
 
-        let bindings = SkillPlugin::instantiate(&mut store, &component, &linker)?;
+        PluginBindings::instantiate(world, engine, component, &mut store)?.metadata(&mut store)
+    }
+
+    /// The `skill-plugin` world version this plugin was built against.
+    pub fn world_version(&self) -> WorldVersion {
+        self.world
+    }
 
-        let name = bindings.call_name(&mut store)?;
-        let wit_caps = bindings.call_required_capabilities(&mut store)?;
-        let actions = bindings.call_actions(&mut store)?;
-        let file_types = bindings.call_file_types(&mut store)?;
+    /// Share the registry's revocation set with this plugin's host bridge.
+    pub fn set_permissions(&mut self, permissions: SkillPermissions) {
+        self.permissions = permissions;
+    }
 
-        let capabilities = wit_caps.into_iter().map(wit_cap_to_capability).collect();
+    /// Root under which this plugin's `FileAccess` scratch directory lives.
+    pub fn set_scratch_space(&mut self, scratch: ScratchSpace) {
+        self.scratch = Some(scratch);
+    }
 
-        Ok(Self {
-            engine,
-            component,
-            limits,
-            cached_name: name,
-            cached_capabilities: capabilities,
-            cached_actions: actions,
-            cached_file_types: file_types,
-        })
+    /// The plugin's scratch directory, if it may use one this execution.
+    fn scratch_dir(&self, ctx: &SkillContext) -> anyhow::Result<Option<std::path::PathBuf>> {
+        let held = ctx.granted.contains(&Capability::FileAccess)
+            && !self.permissions.is_revoked(&self.cached_name, &Capability::FileAccess);
+        match (&self.scratch, held) {
+            (Some(scratch), true) => Ok(Some(scratch.dir_for(&self.cached_name)?)),
+            _ => Ok(None),
+        }
     }
 
     /// Execute the WASM component with a fresh Store.
@@ -83,12 +148,6 @@ impl WasmSkill {
         params: &str,
         ctx: &SkillContext,
     ) -> anyhow::Result<SkillOutput> {
-        let mut linker = Linker::new(&self.engine);
-        SkillPlugin::add_to_linker::<PluginState, HasSelf<PluginState>>(
-            &mut linker,
-            |state| state,
-        )?;
-
         let store_limits = StoreLimitsBuilder::new()
             .memory_size(self.limits.memory_bytes)
             .instances(self.limits.max_instances)
@@ -110,12 +169,17 @@ impl WasmSkill {
             limits: store_limits,
             scope_doc: Some(doc.id.clone()),
             scope_thread,
+            skill_name: self.cached_name.clone(),
+            permissions: self.permissions.clone(),
+            wasi: wasi_ctx(self.scratch_dir(ctx)?.as_deref())?,
+            table: ResourceTable::new(),
         };
         let mut store = Store::new(&self.engine, state);
         store.limiter(|s| &mut s.limits);
         store.set_fuel(self.limits.fuel)?;
 
-        let bindings = SkillPlugin::instantiate(&mut store, &self.component, &linker)?;
+        let bindings =
+            PluginBindings::instantiate(self.world, &self.engine, &self.component, &mut store)?;
 
         // Convert SkillDocument to WIT type (body only)
         let wit_doc = crate::wasm::host_bridge::sovereign::skill::types::SkillDocument {
@@ -124,12 +188,23 @@ impl WasmSkill {
             body: doc.content.body.clone(),
         };
 
-        // Convert granted capabilities to WIT enum
-        let wit_caps: Vec<_> = ctx.granted.iter().filter_map(capability_to_wit).collect();
-
-        let result = bindings.call_execute(&mut store, action, &wit_doc, params, &wit_caps)?;
+        let result = bindings.execute(&mut store, action, &wit_doc, params, &ctx.granted)?;
 
+        use crate::wasm::host_bridge::sovereign::skill::types::SkillOutput as WitOutput;
         match result {
+            // Scratch files are only reachable while FileAccess is still held.
+            Ok(WitOutput::ScratchFile(f)) => {
+                let scratch = match (&self.scratch, self.scratch_dir(ctx)?) {
+                    (Some(scratch), Some(_)) => scratch,
+                    _ => anyhow::bail!("WASM skill returned a scratch file without FileAccess"),
+                };
+                let data = scratch.take_file(&self.cached_name, &f.path)?;
+                Ok(SkillOutput::File {
+                    name: f.name,
+                    mime_type: f.mime_type,
+                    data,
+                })
+            }
             Ok(output) => Ok(wit_output_to_skill_output(output)),
             Err(msg) => anyhow::bail!("WASM skill error: {msg}"),
         }
@@ -172,6 +247,99 @@ impl CoreSkill for WasmSkill {
     }
 }
 
+/// Metadata exports, common to every world version.
+struct PluginMetadata {
+    name: String,
+    capabilities: Vec<Capability>,
+    actions: Vec<(String, String)>,
+    file_types: Vec<String>,
+}
+
+/// An instantiated plugin, bound through its own world's bindings. Older
+/// worlds are adapted to the current types at this boundary.
+enum PluginBindings {
+    Current(SkillPlugin),
+    V0_1(compat::v0_1::SkillPlugin),
+}
+
+impl PluginBindings {
+    fn instantiate(
+        world: WorldVersion,
+        engine: &Engine,
+        component: &Component,
+        store: &mut Store<PluginState>,
+    ) -> anyhow::Result<Self> {
+        let mut linker = Linker::new(engine);
+        wasmtime_wasi::p2::add_to_linker_sync(&mut linker)?;
+        match world {
+            WorldVersion::V0_2 => {
+                SkillPlugin::add_to_linker::<PluginState, HasSelf<PluginState>>(
+                    &mut linker,
+                    |state| state,
+                )?;
+                Ok(Self::Current(SkillPlugin::instantiate(store, component, &linker)?))
+            }
+            WorldVersion::V0_1 => {
+                compat::v0_1::SkillPlugin::add_to_linker::<PluginState, HasSelf<PluginState>>(
+                    &mut linker,
+                    |state| state,
+                )?;
+                Ok(Self::V0_1(compat::v0_1::SkillPlugin::instantiate(
+                    store, component, &linker,
+                )?))
+            }
+        }
+    }
+
+    fn metadata(&self, store: &mut Store<PluginState>) -> anyhow::Result<PluginMetadata> {
+        match self {
+            Self::Current(b) => Ok(PluginMetadata {
+                name: b.call_name(&mut *store)?,
+                capabilities: b
+                    .call_required_capabilities(&mut *store)?
+                    .into_iter()
+                    .map(wit_cap_to_capability)
+                    .collect(),
+                actions: b.call_actions(&mut *store)?,
+                file_types: b.call_file_types(&mut *store)?,
+            }),
+            Self::V0_1(b) => Ok(PluginMetadata {
+                name: b.call_name(&mut *store)?,
+                capabilities: b
+                    .call_required_capabilities(&mut *store)?
+                    .into_iter()
+                    .map(compat::v0_1_cap_to_capability)
+                    .collect(),
+                actions: b.call_actions(&mut *store)?,
+                file_types: b.call_file_types(&mut *store)?,
+            }),
+        }
+    }
+
+    /// Run `execute`, returning the guest's result as current WIT types.
+    fn execute(
+        &self,
+        store: &mut Store<PluginState>,
+        action: &str,
+        doc: &crate::wasm::host_bridge::sovereign::skill::types::SkillDocument,
+        params: &str,
+        granted: &HashSet<Capability>,
+    ) -> anyhow::Result<Result<crate::wasm::host_bridge::sovereign::skill::types::SkillOutput, String>>
+    {
+        match self {
+            Self::Current(b) => {
+                let caps: Vec<_> = granted.iter().filter_map(capability_to_wit).collect();
+                b.call_execute(store, action, doc, params, &caps)
+            }
+            Self::V0_1(b) => {
+                let caps = compat::capabilities_to_v0_1(granted);
+                let result = b.call_execute(store, action, &compat::v0_1_doc(doc), params, &caps)?;
+                Ok(result.map(compat::v0_1_output_to_current))
+            }
+        }
+    }
+}
+
 // --- Conversion helpers ---
 
 fn wit_cap_to_capability(
@@ -186,6 +354,7 @@ fn wit_cap_to_capability(
         WitCap::ReadFilesystem => Capability::ReadFilesystem,
         WitCap::WriteFilesystem => Capability::WriteFilesystem,
         WitCap::Network => Capability::Network,
+        WitCap::FileAccess => Capability::FileAccess,
     }
 }
 
@@ -201,6 +370,7 @@ fn capability_to_wit(
         Capability::ReadFilesystem => WitCap::ReadFilesystem,
         Capability::WriteFilesystem => WitCap::WriteFilesystem,
         Capability::Network => WitCap::Network,
+        Capability::FileAccess => WitCap::FileAccess,
         // No WIT representation: LLM access is host-mediated and never
         // exposed to WASM guests, so the capability is not forwarded.
         Capability::LlmInference => return None,
@@ -228,5 +398,7 @@ fn wit_output_to_skill_output(
             kind: sd.kind,
             json: sd.json,
         },
+        // Resolved against the scratch directory in `run_execute`.
+        WitOutput::ScratchFile(_) => SkillOutput::None,
     }
 }
@@ -1,6 +1,7 @@
 /** Typed wrappers around Tauri invoke() calls. */
 
 import { invoke } from '@tauri-apps/api/core';
+import type { Keymap } from '$lib/utils/editorKeymap';
 
 export interface AppStatus {
 	documents: number;
@@ -9,6 +10,27 @@ export interface AppStatus {
 	orchestrator_available: boolean;
 }
 
+export interface ModelHealth {
+	router: string;
+	reasoning: string;
+	/** null while the model is busy generating. */
+	reasoning_loaded: boolean | null;
+	/** Size of the weights offloaded to the GPU; 0 on CPU. */
+	vram_estimate_mb: number;
+}
+
+export interface SystemHealth {
+	db_mode: 'memory' | 'persistent' | 'encrypted';
+	model: ModelHealth | null;
+}
+
+/** One persisted chat turn; `ts` is RFC 3339. */
+export interface ChatHistoryEntry {
+	role: 'user' | 'assistant';
+	text: string;
+	ts: string;
+}
+
 export interface DocSummary {
 	id: string;
 	title: string;
@@ -29,16 +51,87 @@ export interface SearchHit {
 	snippet: string;
 }
 
+/** `sovereign_sdk::DocType`, by its wire name. */
+export type DocType = 'note' | 'spec' | 'spreadsheet' | 'image' | 'pdf' | 'web-clip' | 'transcript';
+
+/** How carefully a document is handled:
+ *  - `normal`: like any other.
+ *  - `sensitive`: its content stays out of the assistant's context and
+ *    suggestions.
+ *  - `sealed`: hidden from the assistant, and opened only after the
+ *    password is entered again. */
+export type Sensitivity = 'normal' | 'sensitive' | 'sealed';
+
 export interface FullDocument {
 	id: string;
 	title: string;
 	body: string;
 	images: ContentImageDto[];
 	videos: ContentVideoDto[];
+	files: ContentFileDto[];
 	thread_id: string;
 	is_owned: boolean;
+	doc_type: DocType;
 	created_at: string;
 	modified_at: string;
+	read_later: boolean;
+	favorite: boolean;
+	sensitivity: Sensitivity;
+}
+
+export interface ReadingQueueEntry {
+	id: string;
+	title: string;
+	thread_id: string;
+	doc_type: DocType;
+	queued_at: string;
+	words: number;
+	reading_minutes: number;
+}
+
+export type ActivityKind = 'created' | 'commit' | 'sync' | 'audit' | 'message';
+
+/** One line of a document's Activity view. */
+export interface ActivityEntry {
+	at: string;
+	kind: ActivityKind;
+	summary: string;
+	/** Device, skill or correspondent behind the entry. */
+	actor: string | null;
+	/** The commit or message the entry stands for. */
+	ref_id: string | null;
+}
+
+/** One change for many documents; unset fields are left alone. */
+export interface BulkEdit {
+	add_tags?: string[];
+	remove_tags?: string[];
+	thread_id?: string | null;
+	doc_type?: DocType | null;
+	favorite?: boolean | null;
+	read_later?: boolean | null;
+	/** Move to the trash (restorable). */
+	archive?: boolean;
+}
+
+/** One write of a bulk edit and how to take it back; opaque to the UI. */
+export type BulkEditRevert = { op: string; doc: string } & Record<string, unknown>;
+
+export interface BulkEditReport {
+	/** Documents at least one write changed. */
+	changed: number;
+	/** Hand back to `revertBulkEdit` to undo the whole edit. */
+	revert: BulkEditRevert[];
+}
+
+export interface Reminder {
+	id: string;
+	doc_id: string;
+	/** Index of the task line the reminder points at, if any. */
+	task_index: number | null;
+	text: string;
+	due_at: string;
+	fired_at: string | null;
 }
 
 export interface ContentImageDto {
@@ -53,17 +146,80 @@ export interface ContentVideoDto {
 	thumbnail_path: string | null;
 }
 
+export interface ContentFileDto {
+	path: string;
+	name: string;
+}
+
+export interface AttachmentDataDto {
+	name: string;
+	mime: string;
+	data_base64: string;
+}
+
 export interface CommitSummary {
 	id: string;
 	message: string;
 	timestamp: string;
 	snapshot_title: string;
 	snapshot_preview: string;
+	/** PeerId of the device that signed the commit; null if unsigned. */
+	signed_by: string | null;
+	signature_status: 'valid' | 'invalid' | 'unsigned';
 }
 
+/** A commit's full snapshot (the summary only carries a preview). */
+export interface CommitSnapshot {
+	title: string;
+	body: string;
+}
+
+/** Signature status of a skill package (see sovereign-crypto skill_signing). */
+export type SkillTrust = 'builtin' | 'verified' | 'untrusted' | 'unsigned' | 'tampered';
+
 export interface SkillInfo {
 	skill_name: string;
 	actions: SkillActionInfo[];
+	trust: SkillTrust;
+	/** Trusted publisher name, only set when `trust === 'verified'`. */
+	signer?: string | null;
+}
+
+export interface SkillCapabilityInfo {
+	/** snake_case capability name, e.g. `read_document`. */
+	capability: string;
+	/** `false` once the user has revoked it. */
+	enabled: boolean;
+	high_risk: boolean;
+}
+
+export interface SkillAuditEntry {
+	timestamp: string;
+	capability: string;
+	operation: string;
+	target?: string | null;
+	allowed: boolean;
+}
+
+export interface SkillPermissions {
+	skill_name: string;
+	capabilities: SkillCapabilityInfo[];
+	/** Most recent first. */
+	audit: SkillAuditEntry[];
+}
+
+export type SkillJobStatus = 'queued' | 'running' | 'done' | 'failed' | 'cancelled';
+
+export interface SkillJob {
+	id: number;
+	skill_name: string;
+	action: string;
+	doc_id: string;
+	status: SkillJobStatus;
+	error: string | null;
+	queued_at: string;
+	started_at: string | null;
+	finished_at: string | null;
 }
 
 export interface SkillActionInfo {
@@ -92,9 +248,79 @@ export interface ModelEntry {
 
 // Health / status
 export const getStatus = () => invoke<AppStatus>('get_status');
+export const getSystemHealth = () => invoke<SystemHealth>('get_system_health');
+
+/** One day of a metric, in milliseconds; p50/p95 are bucket estimates. */
+export interface DayStats {
+	date: string;
+	count: number;
+	mean_ms: number;
+	p50_ms: number;
+	p95_ms: number;
+	max_ms: number;
+}
+
+/** A locally recorded metric (`db.query`, `llm.generate`, `render.frame`,
+ *  ...) with its recorded days, oldest first. */
+export interface MetricTrend {
+	name: string;
+	days: DayStats[];
+}
+
+export const getMetrics = (days: number) => invoke<MetricTrend[]>('get_metrics', { days });
+export const clearMetrics = () => invoke<void>('clear_metrics');
+
+// Workspace statistics (`sovereign_db::analytics`)
+export interface WorkspaceTotals {
+	documents: number;
+	owned_documents: number;
+	external_documents: number;
+	threads: number;
+	commits: number;
+	messages: number;
+}
+
+export interface ThreadCount {
+	thread_id: string;
+	thread_name: string;
+	documents: number;
+	owned: number;
+}
+
+export interface ChannelCount {
+	channel: string;
+	sent: number;
+	received: number;
+}
+
+/** One week, Monday to Sunday (UTC). */
+export interface WeekStats {
+	/** `YYYY-MM-DD`, a Monday. */
+	week_start: string;
+	documents_created: number;
+	owned_created: number;
+	/** Documents that existed by the end of the week. */
+	documents_total: number;
+	commits: number;
+	/** Messages that week by channel. */
+	messages: Record<string, number>;
+}
+
+export interface WorkspaceStats {
+	totals: WorkspaceTotals;
+	documents_per_thread: ThreadCount[];
+	messages_per_channel: ChannelCount[];
+	/** Oldest first, ending with the current week. */
+	weeks: WeekStats[];
+}
+
+export const getWorkspaceStats = (weeks: number) =>
+	invoke<WorkspaceStats>('get_workspace_stats', { weeks });
 
 // Chat
 export const chatMessage = (message: string) => invoke<void>('chat_message', { message });
+export const getChatHistory = (limit?: number) =>
+	invoke<ChatHistoryEntry[]>('get_chat_history', { limit: limit ?? null });
 
 // Search
 export const searchDocuments = (query: string) => invoke<SearchHit[]>('search_documents', { query });
@@ -104,12 +330,40 @@ export const searchQuery = (query: string) => invoke<void>('search_query', { que
 export const approveAction = () => invoke<void>('approve_action');
 export const rejectAction = (reason: string) => invoke<void>('reject_action', { reason });
 
+// Proactive suggestions
+export const acceptSuggestion = (action: string, target: string | null) =>
+	invoke<void>('accept_suggestion', { action, target });
+export const dismissSuggestion = (action: string) => invoke<void>('dismiss_suggestion', { action });
+
 // Theme
 export const toggleTheme = () => invoke<string>('toggle_theme');
 export const getTheme = () => invoke<string>('get_theme');
 
 // Document CRUD
 export const getDocument = (id: string) => invoke<FullDocument>('get_document', { id });
+export const listBacklinks = (id: string) => invoke<BacklinkDto[]>('list_backlinks', { id });
+export const setReadLater = (id: string, queued: boolean) =>
+	invoke<void>('set_read_later', { id, queued });
+export const listReadingQueue = () => invoke<ReadingQueueEntry[]>('list_reading_queue');
+export const documentActivity = (docId: string) =>
+	invoke<ActivityEntry[]>('document_activity', { docId });
+export const listReminders = (docId?: string) =>
+	invoke<Reminder[]>('list_reminders', { docId: docId ?? null });
+export const createReminder = (docId: string, text: string, dueAt: string, taskIndex?: number) =>
+	invoke<Reminder>('create_reminder', { docId, text, dueAt, taskIndex: taskIndex ?? null });
+export const deleteReminder = (id: string) => invoke<void>('delete_reminder', { id });
+export const setFavorite = (id: string, favorite: boolean) =>
+	invoke<void>('set_favorite', { id, favorite });
+/** A sealed document must be unsealed before its level can change. */
+export const setDocumentSensitivity = (id: string, sensitivity: Sensitivity) =>
+	invoke<void>('set_document_sensitivity', { id, sensitivity });
+/** Check the password again and open a sealed document for a few minutes. */
+export const unsealDocument = (id: string, password: string) =>
+	invoke<void>('unseal_document', { id, password });
+export const bulkEditDocuments = (ids: string[], edit: BulkEdit) =>
+	invoke<BulkEditReport>('bulk_edit_documents', { ids, edit });
+export const revertBulkEdit = (steps: BulkEditRevert[]) =>
+	invoke<void>('revert_bulk_edit', { steps });
 export const saveDocument = (
 	id: string,
 	title: string,
@@ -117,21 +371,82 @@ export const saveDocument = (
 	images: ContentImageDto[],
 	videos: ContentVideoDto[]
 ) => invoke<void>('save_document', { id, title, body, images, videos });
+export const setDocumentType = (id: string, docType: DocType) =>
+	invoke<void>('set_document_type', { id, docType });
 export const createDocument = (title: string, threadId: string) =>
 	invoke<string>('create_document', { title, threadId });
 export const closeDocument = (id: string) => invoke<void>('close_document', { id });
 
+/** Unsaved edits a crashed run journaled, next to the saved title. */
+export interface RecoverableEdit {
+	doc_id: string;
+	title: string;
+	body: string;
+	journaled_at: string;
+	saved_title: string;
+	saved_at: string;
+}
+
+// Crash recovery
+export const journalEdit = (id: string, title: string, body: string) =>
+	invoke<void>('journal_edit', { id, title, body });
+export const listRecoverableEdits = () => invoke<RecoverableEdit[]>('list_recoverable_edits');
+export const discardJournaledEdit = (id: string) =>
+	invoke<void>('discard_journaled_edit', { id });
+
 // Version history
 export const listCommits = (docId: string) => invoke<CommitSummary[]>('list_commits', { docId });
 export const restoreCommit = (docId: string, commitId: string) =>
 	invoke<FullDocument>('restore_commit', { docId, commitId });
+export const getCommitSnapshot = (docId: string, commitId: string) =>
+	invoke<CommitSnapshot>('get_commit_snapshot', { docId, commitId });
+
+export interface PastedImage {
+	/** SHA-256; reference it in the body as `media:<hash>`. */
+	hash: string;
+	/** Local path, for the document's `images` list. */
+	path: string;
+}
+export const storePastedImage = (dataBase64: string) =>
+	invoke<PastedImage>('store_pasted_image', { dataBase64 });
+export const attachFile = (docId: string, name: string, dataBase64: string) =>
+	invoke<ContentFileDto>('attach_file', { docId, name, dataBase64 });
+export const removeAttachment = (docId: string, path: string) =>
+	invoke<void>('remove_attachment', { docId, path });
+export const readAttachment = (docId: string, path: string) =>
+	invoke<AttachmentDataDto>('read_attachment', { docId, path });
+export const pdfPreview = (docId: string, path: string, pages: number = 3) =>
+	invoke<string[]>('pdf_preview', { docId, path, pages });
+export const shareDocument = (
+	id: string,
+	passphrase: string,
+	attachments: string[],
+	expiresInDays: number
+) =>
+	invoke<DocumentShareDto>('share_document', { id, passphrase, attachments, expiresInDays });
+export const openShare = (
+	share: { dataBase64: string } | { payload: string },
+	passphrase: string
+) => invoke<OpenedShareDto>('open_share', { ...share, passphrase });
+
+// Meeting mode: calendar events starting now, and their recorded notes.
+export const meetingsStarting = () => invoke<Meeting[]>('meetings_starting');
+export const saveMeetingNotes = (meeting: Meeting, transcript: string) =>
+	invoke<SavedNotesDto>('save_meeting_notes', { meeting, transcript });
 
 // Skills
-export const listSkillsForDoc = (docTitle: string) =>
-	invoke<SkillInfo[]>('list_skills_for_doc', { docTitle });
+export const listSkillsForDoc = (docType: DocType) =>
+	invoke<SkillInfo[]>('list_skills_for_doc', { docType });
 export const executeSkill = (skillName: string, action: string, docId: string, params: string) =>
 	invoke<SkillResultDto>('execute_skill', { skillName, action, docId, params });
 export const listAllSkills = () => invoke<SkillInfo[]>('list_all_skills');
+export const getSkillPermissions = (skillName: string) =>
+	invoke<SkillPermissions>('get_skill_permissions', { skillName });
+export const setSkillCapability = (skillName: string, capability: string, enabled: boolean) =>
+	invoke<void>('set_skill_capability', { skillName, capability, enabled });
+export const listSkillJobs = () => invoke<SkillJob[]>('list_skill_jobs');
+export const cancelSkillJob = (jobId: number) => invoke<boolean>('cancel_skill_job', { jobId });
+export const clearFinishedSkillJobs = () => invoke<void>('clear_finished_skill_jobs');
 
 // Model management
 export const scanModels = () => invoke<ModelEntry[]>('scan_models');
@@ -148,6 +463,7 @@ export interface CanvasDocDto {
 	title: string;
 	thread_id: string;
 	is_owned: boolean;
+	doc_type: DocType;
 	spatial_x: number;
 	spatial_y: number;
 	created_at: string;
@@ -155,6 +471,14 @@ export interface CanvasDocDto {
 	reliability_classification: string | null;
 	reliability_score: number | null;
 	source_url: string | null;
+	/** `#tag` markers in the body. */
+	tags: string[];
+	/** Unticked checklist items in the body. */
+	open_tasks: number;
+	/** Pinned to the taskbar. */
+	favorite: boolean;
+	/** Sealed cards ask for the password before opening. */
+	sensitivity: Sensitivity;
 }
 
 export interface ThreadDto {
@@ -164,6 +488,56 @@ export interface ThreadDto {
 	created_at: string;
 }
 
+/** A document pointing at another one (`sovereign_db::links::Backlink`). */
+export interface BacklinkDto {
+	relationship_id: string;
+	document_id: string;
+	title: string;
+	thread_id: string;
+	relation_type: string;
+	strength: number;
+	/** Made for a `[[link]]` or `@mention` in that document's body. */
+	from_body: boolean;
+	/** The line the link is written on. */
+	excerpt: string | null;
+}
+
+/** A sealed `.svshare` file for one document. */
+export interface DocumentShareDto {
+	file_name: string;
+	data_base64: string;
+	/** `sovereign-share:…` text when the share fits in one QR code. */
+	qr_payload: string | null;
+	expires_at: string | null;
+	/** Media the document lists that isn't on this device. */
+	missing_media: number;
+}
+
+export interface OpenedShareDto {
+	document_id: string;
+	thread_id: string;
+	title: string;
+	shared_at: string;
+	expires_at: string | null;
+}
+
+/** A calendar event offered for recording (`meetings_starting`). */
+export interface Meeting {
+	uid: string;
+	summary: string;
+	start: string;
+	end: string;
+	location: string | null;
+	attendees: { email: string; name: string | null }[];
+}
+
+export interface SavedNotesDto {
+	document_id: string;
+	thread_id: string;
+	action_items: number;
+	linked_contacts: number;
+}
+
 export interface RelationshipDto {
 	id: string;
 	from_doc_id: string;
@@ -186,9 +560,18 @@ export interface ContactDetailDto {
 	avatar: string | null;
 	notes: string;
 	addresses: ChannelAddressDto[];
+	is_owned: boolean;
 	conversations: ConversationDto[];
 }
 
+export interface ContactFormDto {
+	name: string;
+	avatar: string | null;
+	notes: string;
+	addresses: ChannelAddressDto[];
+	is_owned: boolean;
+}
+
 export interface ChannelAddressDto {
 	channel: string;
 	address: string;
@@ -196,6 +579,20 @@ export interface ChannelAddressDto {
 	is_primary: boolean;
 }
 
+export interface ComposeChannelDto {
+	channel: string;
+	connected: boolean;
+	error: string | null;
+}
+
+export interface ComposeMessageDto {
+	contact_id: string;
+	channel: string;
+	/** Email only. */
+	subject: string | null;
+	body: string;
+}
+
 export interface ConversationDto {
 	id: string;
 	title: string;
@@ -214,6 +611,7 @@ export interface MessageDto {
 	body: string;
 	sent_at: string;
 	read_status: string;
+	attachment_doc_ids: string[];
 }
 
 export interface MilestoneDto {
@@ -243,12 +641,45 @@ export interface CanvasData {
 	messages: CanvasMessageDto[];
 }
 
+/** Open panels and canvas camera, persisted across launches. */
+export interface WindowSession {
+	camera: { pan_x: number; pan_y: number; zoom: number } | null;
+	/** Bottom to top. */
+	doc_panels: { doc_id: string; x: number; y: number; width: number; height: number }[];
+	contact_panel: { contact_id: string; conversation_id: string | null } | null;
+	inbox_open: boolean;
+}
+
 // Canvas
 export const canvasLoad = () => invoke<CanvasData>('canvas_load');
 export const updateDocumentPosition = (id: string, x: number, y: number) =>
 	invoke<void>('update_document_position', { id, x, y });
 export const canvasLoadMessages = (tMin: string, tMax: string, limit?: number) =>
 	invoke<CanvasMessageDto[]>('canvas_load_messages', { tMin, tMax, limit: limit ?? 200 });
+export const getWindowSession = () => invoke<WindowSession>('get_window_session');
+export const saveWindowSession = (session: WindowSession) =>
+	invoke<void>('save_window_session', { session });
+
+// Frame timing for `--trace-profile`
+export interface FrameTime {
+	duration_ms: number;
+	/** How long before the report the frame ended. */
+	ago_ms: number;
+}
+export const profilingEnabled = () => invoke<boolean>('profiling_enabled');
+export const reportFrameTimes = (frames: FrameTime[]) =>
+	invoke<void>('report_frame_times', { frames });
+
+// Detached panel windows
+export interface DetachedPanel {
+	kind: 'document' | 'inbox';
+	/** Document ID; empty for the inbox. */
+	id: string;
+}
+export const detachPanel = (kind: DetachedPanel['kind'], id: string, title: string) =>
+	invoke<void>('detach_panel', { kind, id, title });
+export const detachedPanelInfo = () => invoke<DetachedPanel | null>('detached_panel_info');
+export const closeDetachedPanel = (dock: boolean) => invoke<void>('close_detached_panel', { dock });
 
 // Thread CRUD
 export const createThread = (name: string, description: string) =>
@@ -262,11 +693,21 @@ export const moveDocumentToThread = (docId: string, threadId: string) =>
 // Contacts & messaging
 export const listContacts = () => invoke<ContactSummaryDto[]>('list_contacts');
 export const getContactDetail = (id: string) => invoke<ContactDetailDto>('get_contact_detail', { id });
+export const createContact = (form: ContactFormDto) => invoke<ContactDetailDto>('create_contact', { form });
+export const updateContact = (id: string, form: ContactFormDto) =>
+	invoke<ContactDetailDto>('update_contact', { id, form });
 export const listConversations = (contactId?: string) =>
 	invoke<ConversationDto[]>('list_conversations', { contactId: contactId ?? null });
 export const listMessages = (conversationId: string, before?: string, limit: number = 50) =>
 	invoke<MessageDto[]>('list_messages', { conversationId, before: before ?? null, limit });
+export const translateMessage = (id: string, language: string) =>
+	invoke<string>('translate_message', { id, language });
 export const markMessageRead = (id: string) => invoke<void>('mark_message_read', { id });
+export const readMessageLater = (id: string) =>
+	invoke<DocSummary>('read_message_later', { id });
+export const listComposeChannels = () => invoke<ComposeChannelDto[]>('list_compose_channels');
+export const composeMessage = (data: ComposeMessageDto) =>
+	invoke<ConversationDto>('compose_message', { data });
 export const createRelationship = (fromId: string, toId: string, relationType: string, strength: number) =>
 	invoke<void>('create_relationship', { fromId, toId, relationType, strength });
 
@@ -278,6 +719,43 @@ export interface AuthCheckResult {
 	needs_onboarding: boolean;
 	needs_login: boolean;
 	crypto_enabled: boolean;
+	security_key_required: boolean;
+	/** The device was remotely wiped; there is nothing left to unlock. */
+	device_wiped: boolean;
+}
+
+export interface SecurityKeyDto {
+	path: string;
+	name: string;
+}
+
+export interface SecurityKeyRecoveryDto {
+	recovered: boolean;
+	approvals: number;
+	threshold: number;
+	pending: number;
+}
+
+export type RotationScope =
+	| 'all'
+	| 'documents'
+	| 'threads'
+	| 'messages'
+	| 'contacts'
+	| 'conversations'
+	| 'share_records';
+
+export interface KeyRotationProgress {
+	stage: string;
+	done: number;
+	total: number;
+}
+
+export interface KeyRotationEntryDto {
+	at: string;
+	scope: string;
+	keys: number;
+	resumed: boolean;
 }
 
 export interface PasswordValidationDto {
@@ -291,12 +769,14 @@ export interface UserProfileDto {
 	nickname: string | null;
 	bubble_style: string;
 	display_name: string | null;
+	editor_keymap: Keymap;
 }
 
 export interface SaveProfileDto {
 	nickname?: string;
 	bubble_style?: string;
 	display_name?: string;
+	editor_keymap?: Keymap;
 }
 
 export interface AppConfigDto {
@@ -310,7 +790,49 @@ export interface AppConfigDto {
 	crypto_keystroke_enabled: boolean;
 	crypto_max_login_attempts: number;
 	crypto_lockout_seconds: number;
+	crypto_auto_lock_minutes: number;
 	ui_theme: string;
+	ui_scale: number;
+	ui_editor_font_size: number;
+	ui_editor_font_family: string;
+	/** Action id → key combo, only where it differs from the default. */
+	ui_shortcuts: Record<string, string>;
+	voice_enabled: boolean;
+	voice_source: string;
+	voice_jiminy_ws_url: string;
+	voice_announce_reminders: boolean;
+	comms_enabled: boolean;
+	comms_poll_interval_secs: number;
+	p2p_enabled: boolean;
+	p2p_device_name: string;
+	p2p_enable_mdns: boolean;
+	p2p_wifi_only: boolean;
+	p2p_serve_viewer: boolean;
+	skills_max_concurrent_jobs: number;
+	autonomy_auto_approve_after: number;
+	metrics_enabled: boolean;
+	metrics_retention_days: number;
+	relevance_enabled: boolean;
+	/** Days for an unused relationship to lose half its strength. */
+	relevance_half_life_days: number;
+	/** Where `updateConfig` writes. */
+	config_path: string;
+}
+
+/** Settings-editable subset of the config; omitted fields are kept. */
+export type ConfigUpdate = Partial<
+	Omit<
+		AppConfigDto,
+		| 'ai_router_model'
+		| 'ai_reasoning_model'
+		| 'config_path'
+		| `crypto_${string}`
+	>
+>;
+
+export interface ConfigApplyReport {
+	/** Config sections that only take effect after a restart. */
+	restart_required: string[];
 }
 
 export interface KeystrokeSampleDto {
@@ -319,34 +841,96 @@ export interface KeystrokeSampleDto {
 	release_ms: number;
 }
 
+/** What typing a canary phrase does. */
+export type CanaryAction = 'lockdown' | 'silent_alert' | 'decoy' | 'wipe';
+
+export interface CanaryRule {
+	phrase: string;
+	action: CanaryAction;
+}
+
 export interface OnboardingData {
 	nickname: string | null;
 	bubble_style: string | null;
 	seed_sample_data: boolean;
 	password: string | null;
 	duress_password: string | null;
-	canary_phrase: string | null;
+	canary_rules: CanaryRule[];
 	keystrokes: KeystrokeSampleDto[][];
+	use_security_key?: boolean;
+	recovery_phrase?: boolean;
+}
+
+export interface OnboardingResult {
+	recovery_phrase: string | null;
 }
 
 // Auth
 export const checkAuthState = () => invoke<AuthCheckResult>('check_auth_state');
 export const validatePassword = (password: string, keystrokes: KeystrokeSampleDto[]) =>
 	invoke<string>('validate_password', { password, keystrokes });
+export const restoreFromRecoveryPhrase = (phrase: string, newPassword: string) =>
+	invoke<void>('restore_from_recovery_phrase', { phrase, newPassword });
 export const validatePasswordPolicy = (password: string) =>
 	invoke<PasswordValidationDto>('validate_password_policy', { password });
 
+// Security key (FIDO2) second factor
+export const listSecurityKeys = () => invoke<SecurityKeyDto[]>('list_security_keys');
+export const enrollSecurityKey = (device: string | null) =>
+	invoke<void>('enroll_security_key', { device });
+export const recoverSecurityKey = (password: string, guardianAddrs: string[]) =>
+	invoke<SecurityKeyRecoveryDto>('recover_security_key', { password, guardianAddrs });
+
+// Lock the session; the backend emits `session-locked`
+export const lockSession = () => invoke<void>('lock_session');
+
+// Key rotation (progress arrives as `key-rotation-progress` events)
+export const rotateKek = (password: string) => invoke<number>('rotate_kek', { password });
+export const rotateDocumentKeys = (scope: RotationScope, documentId?: string) =>
+	invoke<number>('rotate_document_keys', { scope, documentId: documentId ?? null });
+export const getKeyRotationLog = () => invoke<KeyRotationEntryDto[]>('get_key_rotation_log');
+
 // Onboarding
 export const completeOnboarding = (data: OnboardingData) =>
-	invoke<void>('complete_onboarding', { data });
+	invoke<OnboardingResult>('complete_onboarding', { data });
 
 // Profile
 export const getProfile = () => invoke<UserProfileDto>('get_profile');
 export const saveProfile = (data: SaveProfileDto) =>
 	invoke<void>('save_profile', { data });
 
+// User profiles: each is a separate identity with its own keys and data.
+// Switching restarts the app as the chosen profile.
+export interface ProfilesDto {
+	active: string;
+	profiles: string[];
+}
+
+export const listProfiles = () => invoke<ProfilesDto>('list_profiles');
+export const switchProfile = (name: string, create = false) =>
+	invoke<void>('switch_profile', { name, create });
+
 // Config
 export const getConfig = () => invoke<AppConfigDto>('get_config');
+/** Validate, save and hot-apply. Rejects with the validation errors. */
+export const updateConfig = (update: ConfigUpdate) =>
+	invoke<ConfigApplyReport>('update_config', { update });
+
+// Runtime feature registry: which optional subsystems this build carries,
+// which are switched on, and which are running.
+export interface FeatureDto {
+	id: string;
+	label: string;
+	compiled: boolean;
+	enabled: boolean;
+	toggleable: boolean;
+	/** Running this session; null when off or there is nothing to check. */
+	healthy: boolean | null;
+}
+
+export const listFeatures = () => invoke<FeatureDto[]>('list_features');
+export const setFeatureEnabled = (id: string, enabled: boolean) =>
+	invoke<ConfigApplyReport>('set_feature_enabled', { id, enabled });
 
 // Document deletion
 export const deleteDocument = (id: string) => invoke<void>('delete_document', { id });
@@ -393,6 +977,36 @@ export const resetTrustAll = () => invoke<void>('reset_trust_all');
 export const importFile = (filePath: string, threadId?: string) =>
 	invoke<CanvasDocDto>('import_file', { filePath, threadId: threadId ?? null });
 
+/** `markdown`: an Obsidian or Logseq vault; `notion`: an unzipped
+ *  Notion export; `maildir`: a local mail folder. */
+export type SourceKind = 'markdown' | 'notion' | 'maildir';
+/** One thread for the whole source, or one per top-level folder/mailbox. */
+export type ThreadMapping = 'single' | 'per_folder';
+
+export interface SourceImportPreview {
+	source_name: string;
+	total: number;
+	threads: { name: string; documents: number }[];
+}
+
+export interface SourceImportReport {
+	threads_created: number;
+	documents: number;
+	/** `path: reason` per file that was not imported. */
+	skipped: string[];
+}
+
+export interface SourceImportProgress {
+	done: number;
+	total: number;
+	current: string;
+}
+
+export const previewSourceImport = (kind: SourceKind, path: string, mapping: ThreadMapping) =>
+	invoke<SourceImportPreview>('preview_source_import', { kind, path, mapping });
+export const importSource = (kind: SourceKind, path: string, mapping: ThreadMapping) =>
+	invoke<SourceImportReport>('import_source', { kind, path, mapping });
+
 // Comms config
 export const getCommsConfig = () => invoke<CommsConfigDto>('get_comms_config');
 export const saveCommsConfig = (data: SaveCommsConfigDto) =>
@@ -655,6 +1269,14 @@ export const defaultPasswordPolicy = (): PasswordPolicy => ({
 export const generatePassword = (policy?: PasswordPolicy) =>
 	invoke<string>('generate_password', { policy: policy ?? defaultPasswordPolicy() });
 
+/** Copy a secret, hidden from clipboard managers and cleared after
+ *  `clearAfterSecs`. Rejects where there is no native clipboard — use
+ *  `secureCopy` from `$lib/utils/secureClipboard` instead of calling this. */
+export const secureCopyCmd = (text: string, clearAfterSecs?: number) =>
+	invoke<void>('secure_copy', { text, clearAfterSecs: clearAfterSecs ?? null });
+
+export const clearSecureClipboardCmd = () => invoke<void>('clear_secure_clipboard');
+
 /** Trigger a JS scan of the active browser webview for input fields.
  *  Results arrive asynchronously as the `browser-form-extracted`
  *  Tauri event. */
@@ -737,32 +1359,192 @@ export const clearEntityCookies = (entityId: string) =>
 // `voice-event` emit.
 export const startListening = () => invoke<void>('start_listening');
 export const stopListening = () => invoke<void>('stop_listening');
+/** Transcribe 16 kHz mono PCM captured with the Web Audio API. */
+export const voiceTranscribeBuffer = (samples: number[]) =>
+	invoke<string>('voice_transcribe_buffer', { samples });
 
 // ---------------------------------------------------------------------------
 // Phase 3c — P2P sync (pairing UI lands in Phase 5)
 // ---------------------------------------------------------------------------
 
+/** What this device syncs to one paired device. Empty
+ *  `include_threads` = every thread. */
+export interface SyncPolicy {
+	include_threads: string[];
+	exclude_threads: string[];
+	exclude_external: boolean;
+	max_document_bytes: number | null;
+	/** Documents never sent to this device. */
+	exclude_documents: string[];
+}
+
 export interface PairedDevice {
 	peer_id: string;
 	device_name: string;
 	paired_at: string;
+	last_seen: string | null;
+	last_sync: string | null;
+	sync_paused: boolean;
+	trust: PeerTrust;
+	/** Manually entered addresses (multiaddrs), dialed when mDNS can't
+	 *  find the device. */
+	addresses: string[];
+	sync_policy: SyncPolicy;
 }
 
+/** How far this device trusts a paired device:
+ *  - `full`: documents sync both ways.
+ *  - `read_only`: a replica; it is sent everything, its pushes are rejected.
+ *  - `guardian_only`: backup shards and remote wipe only, no document sync. */
+export type PeerTrust = 'full' | 'read_only' | 'guardian_only';
+
 /** Devices this device has paired with. Empty list on encryption-only
  *  builds (no `p2p` feature) or pre-login. */
 export const listPairedDevices = () =>
 	invoke<PairedDevice[]>('list_paired_devices');
 
-/** Drop a paired device record. The device may re-appear via mDNS
- *  on the same network; "forget" removes the persistent record so it
- *  no longer shows in Settings, but doesn't ban future re-pairing. */
-export const forgetPairedDevice = (peerId: string) =>
-	invoke<void>('forget_paired_device', { peerId });
+/** Revoke a paired device: drop its record and cut it off in the P2P
+ *  node (allow-list, sealing key, open connections). Rotates the backup
+ *  key shards if it held one. It can come back only by pairing again. */
+export const revokePairedDevice = (peerId: string) =>
+	invoke<void>('revoke_paired_device', { peerId });
+
+/** One finished sync session with a paired device. */
+export interface SyncHistoryEntry {
+	at: string;
+	items_synced: number;
+	docs_pulled: number;
+	docs_pushed: number;
+	commits_received: number;
+	commits_sent: number;
+	bytes_received: number;
+	bytes_sent: number;
+	duration_ms: number;
+}
+
+/** Recent sync sessions with one device, newest first. */
+export const getSyncHistory = (peerId: string, limit?: number) =>
+	invoke<SyncHistoryEntry[]>('get_sync_history', { peerId, limit });
+
+/** Give a paired device a new display name. */
+export const renamePairedDevice = (peerId: string, deviceName: string) =>
+	invoke<void>('rename_paired_device', { peerId, deviceName });
+
+/** Pause or resume sync with one paired device. Paused devices stay
+ *  paired (remote wipe and backup shards still work). */
+export const setDeviceSyncPaused = (peerId: string, paused: boolean) =>
+	invoke<void>('set_device_sync_paused', { peerId, paused });
+
+/** Replace the addresses dialed to reach a paired device on networks
+ *  that block mDNS. Entries are `ip:port` or multiaddrs; an invalid one
+ *  rejects the whole list. */
+export const setDeviceAddresses = (peerId: string, addresses: string[]) =>
+	invoke<void>('set_device_addresses', { peerId, addresses });
+
+/** Change how far this device trusts a paired device. */
+export const setDeviceTrust = (peerId: string, trust: PeerTrust) =>
+	invoke<void>('set_device_trust', { peerId, trust });
+
+/** Share this device's encryption keys with a paired device, so
+ *  backups and exports made here open there too. Call only after the
+ *  user confirmed. Resolves to the number of keys sent; the outcome
+ *  arrives as a `keys-shared` event. */
+export const shareDeviceKeys = (peerId: string) =>
+	invoke<number>('share_device_keys', { peerId });
+
+/** Limit what is synced to one paired device (e.g. a phone). Applies
+ *  from the next sync with it. */
+export const setDeviceSyncPolicy = (peerId: string, policy: SyncPolicy) =>
+	invoke<void>('set_device_sync_policy', { peerId, policy });
+
+/** Keep one document off exactly these paired devices. */
+export const setDocumentSyncExclusions = (docId: string, peerIds: string[]) =>
+	invoke<void>('set_document_sync_exclusions', { docId, peerIds });
+
+/** What one backup run sent out. */
+export interface BackupNowResult {
+	snapshot_id: string;
+	epoch: number;
+	fragment_count: number;
+	hosts: number;
+	guardians: number;
+	threshold: number;
+}
+
+/** Last backup, what this device hosts for others, and the saved
+ *  guardian policy (`null` halves until one is chosen). */
+export interface BackupStatus {
+	last_manifest_json: string | null;
+	hosting_enabled: boolean;
+	hosting: {
+		owner_tag: string;
+		snapshot_id: string;
+		epoch: number;
+		fragment_count: number;
+		total_bytes: number;
+	}[];
+	pending_releases: {
+		for_user: string;
+		epoch: number;
+		requested_at: string | null;
+		request_id: string | null;
+	}[];
+	threshold: number | null;
+	shares: number | null;
+}
+
+export const backupStatus = () => invoke<BackupStatus>('backup_status');
+
+/** Back up to the paired devices. `threshold` of `shares` guardians
+ *  reconstruct the key; pass both to change the saved policy, neither to
+ *  keep it. The policy is saved only once the shards have gone out. */
+export const backupNow = (threshold?: number, shares?: number) =>
+	invoke<BackupNowResult>('backup_now', { threshold, shares });
+
+/** Re-split the backup key across the current guardians. */
+export const refreshShards = () => invoke<BackupNowResult>('refresh_shards');
 
 /** Trigger a sync with every paired peer. Returns the number of
  *  StartSync commands queued (0 if the P2P node isn't running). */
 export const triggerSyncNow = () => invoke<number>('trigger_sync_now');
 
+/** Ask a paired device what a sync would move, without syncing. The
+ *  answer arrives as a `sync-preview` event. */
+export const previewSync = (peerId: string) => invoke<void>('preview_sync', { peerId });
+
+export interface ViewerDocument {
+	doc_id: string;
+	title: string;
+	content: string;
+	thread_id: string;
+	modified_at: string;
+}
+
+/** Read-only companion snapshot of a paired device's workspace. */
+export interface ViewerSnapshot {
+	device_id: string;
+	generated_at: string;
+	documents: ViewerDocument[];
+	threads: { thread_id: string; name: string }[];
+	/** Titles of documents too large to send. */
+	too_large: string[];
+}
+
+/** Fetch a paired device's read-only snapshot (it must serve viewers).
+ *  The answer arrives as a `viewer-snapshot` event. */
+export const fetchViewerSnapshot = (peerId: string) =>
+	invoke<void>('fetch_viewer_snapshot', { peerId });
+
+/** Ask for a lost or stolen paired device to be wiped. The order goes
+ *  to the other paired devices and reaches the target only after one of
+ *  its guardians approves it. */
+export const remoteWipeDevice = (peerId: string) =>
+	invoke<void>('remote_wipe_device', { peerId });
+
+/** Co-sign a wipe order this device was asked to approve as guardian. */
+export const approveRemoteWipe = (orderId: string) =>
+	invoke<void>('approve_remote_wipe', { orderId });
+
 /** Disarm the active pairing offer (P3.1) — called when the pairing
  *  panel closes so a stale QR can't be redeemed later. */
 export const cancelPairing = () => invoke<void>('cancel_pairing');
@@ -773,6 +1555,7 @@ export interface P2pSettings {
 	device_name: string;
 	enable_mdns: boolean;
 	wifi_only: boolean;
+	serve_viewer: boolean;
 	running: boolean;
 }
 
@@ -786,6 +1569,11 @@ export const getP2pSettings = () => invoke<P2pSettings>('get_p2p_settings');
 export const resolveSyncConflictKeepMine = (docId: string) =>
 	invoke<void>('resolve_sync_conflict_keep_mine', { docId });
 
+/** Settle a body merge conflict with the text chosen in the conflict
+ *  panel: commits it to the document's history, then triggers a sync. */
+export const resolveSyncMerge = (docId: string, body: string) =>
+	invoke<void>('resolve_sync_merge', { docId, body });
+
 export interface GeneratePairQrResult {
 	qr_payload_b64: string;
 	pin: string;
@@ -819,13 +1607,16 @@ export const consumePairQrPreview = (qrPayloadB64: string, pin: string) =>
 export interface CompletePairedOnboardingInput {
 	qr_payload_b64: string;
 	pin: string;
+	/** Where the existing device listens (`ip:port` or multiaddr), when
+	 *  mDNS can't find it. */
+	source_address?: string | null;
 	/** New local passphrase for this device — different from the
 	 *  source device is fine. */
 	password: string;
 	duress_password?: string | null;
 	nickname?: string | null;
 	bubble_style?: string | null;
-	canary_phrase?: string | null;
+	canary_rules?: CanaryRule[];
 	/** Accepted for API symmetry but ignored — paired devices skip
 	 *  seeding because their real data arrives through sync. */
 	seed_sample_data: boolean;
@@ -841,3 +1632,25 @@ export const completeOnboardingPaired = (input: CompletePairedOnboardingInput) =
  *  Empty string when the p2p feature is off or the identity key isn't
  *  loaded. */
 export const getLocalPeerId = () => invoke<string>('get_local_peer_id');
+
+/** Addresses this device listens on, to enter on a device that can't
+ *  find it over mDNS. Empty until the P2P node is listening. */
+export const getLocalAddresses = () => invoke<string[]>('get_local_addresses');
+
+/** How a direct link to a nearby device was raised. */
+export type ProximityKind = 'hotspot' | 'wifi_direct' | 'bluetooth_pan';
+
+/** A direct IP link to a nearby device when no shared network exists. */
+export interface ProximityLink {
+	kind: ProximityKind;
+	local_ip?: string | null;
+	/** `ip:port` of the device at the other end, when known. */
+	peer?: string | null;
+}
+
+/** Report a hotspot / Wi-Fi Direct / Bluetooth link: the node dials the
+ *  other end and syncs over it. */
+export const proximityLinkUp = (link: ProximityLink) =>
+	invoke<void>('proximity_link_up', { link });
+
+export const proximityLinkDown = () => invoke<void>('proximity_link_down');
@@ -1,8 +1,8 @@
 /** Listen for Tauri events emitted by the Rust backend. */
 
 import { listen, type UnlistenFn } from '@tauri-apps/api/event';
-import { pushAssistant, pushSystem } from '$lib/stores/chat.svelte';
-import { app, type BubbleState } from '$lib/stores/app.svelte';
+import { chat, pushAssistant, pushSystem } from '$lib/stores/chat.svelte';
+import { app, noteSuggestionEvent, showSuggestion, type BubbleState } from '$lib/stores/app.svelte';
 import { openById } from '$lib/stores/documents.svelte';
 import { refresh as canvasRefresh } from '$lib/stores/canvas.svelte';
 import { refreshContacts } from '$lib/stores/contacts.svelte';
@@ -24,11 +24,38 @@ import {
 	onSyncConflict,
 	onSyncDisconnected,
 	onSyncError,
-	onSyncStarted
+	onSyncConflictDetails,
+	onSyncPreview,
+	onSyncProgress,
+	onSyncStarted,
+	onViewerSnapshot
 } from '$lib/stores/sync.svelte';
-import { onDevicePaired, onPairingFailed } from '$lib/stores/pairing.svelte';
+import {
+	onDevicePaired,
+	onKeysImported,
+	onKeysShared,
+	onPairingFailed,
+	onWipeCosignRequested
+} from '$lib/stores/pairing.svelte';
+import { onRotationProgress } from '$lib/stores/keyRotation.svelte';
+import { refreshFrameProfiler } from '$lib/utils/frameProfiler';
+import { onImportProgress } from '$lib/stores/sourceImport.svelte';
+import { notify } from '$lib/stores/notifications.svelte';
+import { noteReminderChange } from '$lib/stores/reminders.svelte';
+import { applyTheme } from '$lib/stores/theme.svelte';
+import { applyUiPrefs } from '$lib/stores/uiPrefs.svelte';
+import { applyShortcuts } from '$lib/stores/shortcuts.svelte';
+import type { ThemeName } from '$lib/theme/colors';
+import { onSessionLocked } from '$lib/stores/session.svelte';
 import type { PendingShare } from '$lib/stores/app.svelte';
-import type { ReliabilityResultDto } from '$lib/api/commands';
+import type {
+	KeyRotationProgress,
+	SourceImportProgress,
+	ReliabilityResultDto,
+	ViewerSnapshot
+} from '$lib/api/commands';
+import type { ConflictHunk } from '$lib/utils/conflictMerge';
+import type { SyncPreview, SyncProgress } from '$lib/utils/syncProgress';
 
 // Payload types matching the Rust-side structs
 interface ChatResponsePayload {
@@ -55,6 +82,26 @@ interface ActionRejectedPayload {
 interface SuggestionPayload {
 	text: string;
 	action: string;
+	target: string | null;
+}
+interface MilestoneCreatedPayload {
+	milestone_id: string;
+	title: string;
+	thread_id: string;
+}
+interface ReminderSetPayload {
+	reminder_id: string;
+	doc_id: string;
+	text: string;
+	due_at: string;
+}
+interface ReminderDuePayload {
+	reminder_id: string;
+	doc_id: string;
+	text: string;
+}
+interface AdoptionStartedPayload {
+	doc_id: string;
 }
 interface SkillResultPayload {
 	skill: string;
@@ -69,6 +116,12 @@ interface DocumentCreatedPayload {
 interface DocumentOpenedPayload {
 	doc_id: string;
 }
+interface DetachedPanelClosedPayload {
+	kind: 'document' | 'inbox';
+	id: string;
+	/** The user docked the panel back rather than just closing it. */
+	dock: boolean;
+}
 interface ThreadRenamedPayload {
 	thread_id: string;
 	name: string;
@@ -98,6 +151,9 @@ interface ThreadSplitPayload {
 	name: string;
 	doc_ids: string[];
 }
+interface KeyUsageAlertPayload {
+	text: string;
+}
 interface InjectionDetectedPayload {
 	source: string;
 	indicators: string[];
@@ -156,14 +212,57 @@ interface SyncStatusPayload {
 	/** "started" | "completed (N items)" | "disconnected" | error message */
 	status: string;
 }
+interface SyncProgressPayload extends SyncProgress {
+	peer_id: string;
+}
+interface SyncPreviewPayload {
+	peer_id: string;
+	preview: SyncPreview | null;
+}
+interface ViewerSnapshotPayload {
+	peer_id: string;
+	snapshot: ViewerSnapshot | null;
+}
+interface ConfigReloadedPayload {
+	theme: string;
+	scale: number;
+	editor_font_size: number;
+	editor_font_family: string;
+	shortcuts: Record<string, string>;
+}
+interface ConfigInvalidPayload {
+	issues: string[];
+}
 interface SyncConflictPayload {
 	doc_id: string;
 	description: string;
 }
+interface SyncConflictDetailsPayload {
+	doc_id: string;
+	base: string | null;
+	local: string;
+	remote: string;
+	merged: string;
+	hunks: ConflictHunk[];
+}
 interface DevicePairedPayload {
 	device_id: string;
 	device_name: string;
 }
+interface KeysSharedPayload {
+	peer_id: string;
+	accepted: boolean;
+}
+interface KeysImportedPayload {
+	peer_id: string;
+	imported: number;
+}
+interface WipeCosignRequestedPayload {
+	order_id: string;
+	target_peer_id: string;
+	issuer_peer_id: string;
+}
+
 interface PairingFailedPayload {
 	reason: string;
 	/** True when the armed offer self-destructed (expired or attempts
@@ -209,30 +308,70 @@ export async function subscribeToEvents(): Promise<UnlistenFn> {
 			const msg = e.payload.success
 				? `Done: ${e.payload.action}`
 				: `Failed: ${e.payload.action}`;
+			noteSuggestionEvent(msg);
 			pushSystem(msg);
+			notify(e.payload.success ? 'action' : 'error', msg, { panel: 'chat' });
 			app.pendingAction = null;
 		})
 	);
 
 	unlisteners.push(
 		await listen<ActionRejectedPayload>('action-rejected', (e) => {
-			pushSystem(`Rejected: ${e.payload.action} — ${e.payload.reason}`);
+			const msg = `Rejected: ${e.payload.action} — ${e.payload.reason}`;
+			pushSystem(msg);
+			notify('action', msg, { panel: 'chat' });
 			app.pendingAction = null;
 		})
 	);
 
 	unlisteners.push(
 		await listen<SuggestionPayload>('suggestion', (e) => {
-			app.activeSuggestion = {
-				text: e.payload.text,
-				action: e.payload.action
-			};
+			showSuggestion(e.payload.text, e.payload.action, e.payload.target);
+		})
+	);
+
+	unlisteners.push(
+		await listen<{ message: string }>('thread-created', (e) => {
+			// `<thread_id>:<name>`; the id itself contains a colon.
+			const name = e.payload.message.split(':').slice(2).join(':');
+			noteSuggestionEvent(`Created thread “${name}”`);
+			canvasRefresh();
+		})
+	);
+
+	unlisteners.push(
+		await listen<MilestoneCreatedPayload>('milestone-created', (e) => {
+			noteSuggestionEvent(`Created milestone “${e.payload.title}”`);
+			canvasRefresh();
+		})
+	);
+
+	unlisteners.push(
+		await listen<ReminderSetPayload>('reminder-set', () => {
+			noteReminderChange();
+		})
+	);
+
+	unlisteners.push(
+		await listen<ReminderDuePayload>('reminder-due', (e) => {
+			const { doc_id, text } = e.payload;
+			pushSystem(`\u23f0 Reminder: ${text}`);
+			notify('reminder', `Reminder: ${text}`, { panel: 'document', docId: doc_id });
+			noteReminderChange();
+		})
+	);
+
+	unlisteners.push(
+		await listen<AdoptionStartedPayload>('adoption-started', () => {
+			noteSuggestionEvent('Adopted the document — it is now yours');
+			canvasRefresh();
 		})
 	);
 
 	unlisteners.push(
 		await listen<SkillResultPayload>('skill-result', (e) => {
 			pushSystem(`Skill "${e.payload.skill}": ${e.payload.data}`);
+			notify('skill', `Skill "${e.payload.skill}" finished ${e.payload.action}`, { panel: 'chat' });
 		})
 	);
 
@@ -249,6 +388,22 @@ export async function subscribeToEvents(): Promise<UnlistenFn> {
 		})
 	);
 
+	// A popped-out window went away. Its document may have been edited
+	// there, so the canvas re-reads titles; docking reopens it here.
+	unlisteners.push(
+		await listen<DetachedPanelClosedPayload>('detached-panel-closed', (e) => {
+			const { kind, id, dock } = e.payload;
+			if (kind === 'document') {
+				app.detachedDocIds = app.detachedDocIds.filter((d) => d !== id);
+				canvasRefresh();
+				if (dock) openById(id);
+			} else {
+				app.inboxDetached = false;
+				if (dock) app.inboxVisible = true;
+			}
+		})
+	);
+
 	// Phase 3: Thread + canvas events
 	unlisteners.push(
 		await listen<ThreadRenamedPayload>('thread-renamed', () => {
@@ -282,8 +437,12 @@ export async function subscribeToEvents(): Promise<UnlistenFn> {
 
 	// Phase 3: Comms events
 	unlisteners.push(
-		await listen<NewMessagesPayload>('new-messages', () => {
+		await listen<NewMessagesPayload>('new-messages', (e) => {
 			refreshContacts();
+			const { count, channel } = e.payload;
+			notify('message', `${count} new ${channel} message${count === 1 ? '' : 's'}`, {
+				panel: 'inbox'
+			});
 		})
 	);
 
@@ -305,6 +464,14 @@ export async function subscribeToEvents(): Promise<UnlistenFn> {
 		})
 	);
 
+	unlisteners.push(
+		await listen<KeyUsageAlertPayload>('key-usage-alert', (e) => {
+			app.keyAlert = e.payload.text;
+			pushSystem(`\u26a0\ufe0f ${e.payload.text}`);
+			notify('error', e.payload.text);
+		})
+	);
+
 	// Browser events
 	unlisteners.push(
 		await listen<BrowserNavigatedPayload>('browser-navigated', (e) => {
@@ -412,17 +579,66 @@ export async function subscribeToEvents(): Promise<UnlistenFn> {
 				onSyncStarted(peer_id);
 			} else if (status.startsWith('completed')) {
 				onSyncCompleted(peer_id);
+				notify('sync', `Synced with …${peer_id.slice(-6)}: ${status}`, { panel: 'devices' });
 			} else if (status === 'disconnected') {
 				onSyncDisconnected(peer_id);
 			} else {
 				// Anything else surfaces as an error string.
 				onSyncError(`${peer_id}: ${status}`);
+				notify('error', `Sync with …${peer_id.slice(-6)} failed: ${status}`, {
+					panel: 'devices'
+				});
 			}
 		})
 	);
+	unlisteners.push(
+		await listen<SyncProgressPayload>('sync-progress', (e) => {
+			const { peer_id, ...progress } = e.payload;
+			onSyncProgress(peer_id, progress);
+		})
+	);
+	unlisteners.push(
+		await listen<SyncPreviewPayload>('sync-preview', (e) => {
+			onSyncPreview(e.payload.peer_id, e.payload.preview);
+		})
+	);
+	unlisteners.push(
+		await listen<ViewerSnapshotPayload>('viewer-snapshot', (e) => {
+			onViewerSnapshot(e.payload.peer_id, e.payload.snapshot);
+		})
+	);
+	unlisteners.push(
+		await listen<ConfigReloadedPayload>('config-reloaded', (e) => {
+			applyTheme(e.payload.theme as ThemeName);
+			applyUiPrefs({
+				scale: e.payload.scale,
+				editorFontSize: e.payload.editor_font_size,
+				editorFontFamily: e.payload.editor_font_family
+			});
+			applyShortcuts(e.payload.shortcuts);
+			refreshFrameProfiler();
+		})
+	);
+	unlisteners.push(
+		await listen<ConfigInvalidPayload>('config-invalid', (e) => {
+			const [first, ...rest] = e.payload.issues;
+			const more = rest.length > 0 ? ` (+${rest.length} more)` : '';
+			notify('error', `Config change not applied: ${first}${more}`);
+		})
+	);
 	unlisteners.push(
 		await listen<SyncConflictPayload>('sync-conflict', (e) => {
 			onSyncConflict(e.payload.doc_id, e.payload.description);
+			notify('sync', `Sync conflict: ${e.payload.description}`, {
+				panel: 'document',
+				docId: e.payload.doc_id
+			});
+		})
+	);
+	unlisteners.push(
+		await listen<SyncConflictDetailsPayload>('sync-conflict-details', (e) => {
+			const { doc_id, base, local, remote, merged, hunks } = e.payload;
+			onSyncConflictDetails(doc_id, { base, local, remote, merged, hunks });
 		})
 	);
 	unlisteners.push(
@@ -436,6 +652,52 @@ export async function subscribeToEvents(): Promise<UnlistenFn> {
 			onPairingFailed(e.payload.reason, e.payload.offer_dead);
 		})
 	);
+	unlisteners.push(
+		await listen<KeysSharedPayload>('keys-shared', (e) => {
+			onKeysShared(e.payload.peer_id, e.payload.accepted);
+		})
+	);
+	unlisteners.push(
+		await listen<KeysImportedPayload>('keys-imported', (e) => {
+			onKeysImported(e.payload.peer_id, e.payload.imported);
+		})
+	);
+	unlisteners.push(
+		await listen<WipeCosignRequestedPayload>('wipe-cosign-requested', (e) => {
+			const p = e.payload;
+			onWipeCosignRequested(p.order_id, p.target_peer_id, p.issuer_peer_id);
+		})
+	);
+	unlisteners.push(
+		await listen('device-wiped', () => {
+			app.authState = 'wiped';
+		})
+	);
+	unlisteners.push(
+		// Canary decoy mode swapped the database underneath the UI: drop
+		// the earlier conversation and reload what is on screen.
+		await listen('decoy-mode', () => {
+			chat.messages = chat.messages.slice(-1);
+			chat.historyLoaded = true;
+			canvasRefresh();
+			refreshContacts();
+		})
+	);
+	unlisteners.push(
+		await listen<KeyRotationProgress>('key-rotation-progress', (e) => {
+			onRotationProgress(e.payload);
+		})
+	);
+	unlisteners.push(
+		await listen<SourceImportProgress>('source-import-progress', (e) => {
+			onImportProgress(e.payload);
+		})
+	);
+	unlisteners.push(
+		await listen('session-locked', () => {
+			if (app.authState === 'ready') onSessionLocked();
+		})
+	);
 
 	// Return a combined unlisten function
 	return () => {
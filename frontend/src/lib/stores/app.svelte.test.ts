@@ -1,8 +1,12 @@
 import { beforeEach, describe, expect, it } from 'vitest';
 import {
 	app,
+	acceptActiveSuggestion,
 	confirmPendingAction,
-	rejectPendingAction
+	dismissActiveSuggestion,
+	noteSuggestionEvent,
+	rejectPendingAction,
+	showSuggestion
 } from './app.svelte';
 import { chat } from './chat.svelte';
 import { mockTauriCommand } from '$lib/test/tauri';
@@ -18,6 +22,7 @@ beforeEach(() => {
 	app.contactPanelState = null;
 	app.authState = 'checking';
 	app.settingsVisible = false;
+	app.settingsTab = null;
 	app.contextMenu = null;
 	app.skillsPanelVisible = false;
 	app.bubbleStyle = 'icon';
@@ -153,3 +158,66 @@ describe('rejectPendingAction', () => {
 		expect(receivedArgs).toEqual({ reason: '' });
 	});
 });
+
+describe('suggestion card', () => {
+	it('runs the accepted action and collects its events', async () => {
+		let receivedArgs: unknown = null;
+		mockTauriCommand('accept_suggestion', (args) => {
+			receivedArgs = args;
+			noteSuggestionEvent('Created thread “General”');
+			noteSuggestionEvent('Done: create_thread');
+			return undefined;
+		});
+		showSuggestion('Create a thread?', 'create_thread', 'General');
+
+		await acceptActiveSuggestion();
+
+		expect(receivedArgs).toEqual({ action: 'create_thread', target: 'General' });
+		expect(app.activeSuggestion?.status).toBe('done');
+		expect(app.activeSuggestion?.events).toEqual(['Created thread “General”', 'Done: create_thread']);
+	});
+
+	it('ignores events while no suggestion is running', () => {
+		showSuggestion('Adopt "A"?', 'adopt', 'A');
+		noteSuggestionEvent('Created milestone “x”');
+		expect(app.activeSuggestion?.events).toEqual([]);
+	});
+
+	it('keeps a running suggestion on screen when another arrives', () => {
+		showSuggestion('Create a thread?', 'create_thread', 'General');
+		app.activeSuggestion!.status = 'running';
+		showSuggestion('Adopt "A"?', 'adopt', 'A');
+		expect(app.activeSuggestion?.action).toBe('create_thread');
+	});
+
+	it('shows a failed acceptance on the card', async () => {
+		mockTauriCommand('accept_suggestion', () => {
+			throw 'AI orchestrator not available';
+		});
+		showSuggestion('Adopt "A"?', 'adopt', 'A');
+
+		await acceptActiveSuggestion();
+
+		expect(app.activeSuggestion?.status).toBe('failed');
+		expect(app.activeSuggestion?.events).toEqual(['Error: AI orchestrator not available']);
+	});
+
+	it('only reports untouched suggestions as dismissed', async () => {
+		const dismissed: unknown[] = [];
+		mockTauriCommand('dismiss_suggestion', (args) => {
+			dismissed.push(args);
+			return undefined;
+		});
+
+		showSuggestion('Adopt "A"?', 'adopt', 'A');
+		await dismissActiveSuggestion();
+		expect(app.activeSuggestion).toBeNull();
+
+		showSuggestion('Create a thread?', 'create_thread', 'General');
+		app.activeSuggestion!.status = 'done';
+		await dismissActiveSuggestion();
+
+		expect(app.activeSuggestion).toBeNull();
+		expect(dismissed).toEqual([{ action: 'adopt' }]);
+	});
+});
@@ -1,5 +1,8 @@
 /** Rune-based reactive state for global app UI. */
 
+import type { Keymap } from '$lib/utils/editorKeymap';
+import type { DetachedPanel } from '$lib/api/commands';
+
 export type BubbleState =
 	| 'Idle'
 	| 'ProcessingOwned'
@@ -19,9 +22,14 @@ export interface PendingAction {
 export interface Suggestion {
 	text: string;
 	action: string;
+	/** What accepting acts on — a thread name, a document title. */
+	target: string | null;
+	status: 'open' | 'running' | 'done' | 'failed';
+	/** What accepting it did, in arrival order; shown under the card. */
+	events: string[];
 }
 
-export type AuthState = 'checking' | 'onboarding' | 'login' | 'ready';
+export type AuthState = 'checking' | 'onboarding' | 'login' | 'ready' | 'wiped';
 
 export interface PendingShare {
 	contentType: 'text' | 'url';
@@ -63,6 +71,48 @@ export async function rejectPendingAction(reason: string) {
 	}
 }
 
+/** Show a fresh suggestion card. One being carried out stays put. */
+export function showSuggestion(text: string, action: string, target: string | null) {
+	if (app.activeSuggestion?.status === 'running') return;
+	app.activeSuggestion = { text, action, target, status: 'open', events: [] };
+}
+
+/** Record an event that accepting the current suggestion caused. */
+export function noteSuggestionEvent(text: string) {
+	if (app.activeSuggestion?.status === 'running') app.activeSuggestion.events.push(text);
+}
+
+/** Accept the suggestion on the card: the backend records the feedback
+ *  and runs the action, whose events land on the card meanwhile. */
+export async function acceptActiveSuggestion() {
+	const s = app.activeSuggestion;
+	if (!s || s.status !== 'open') return;
+	const { acceptSuggestion } = await import('$lib/api/commands');
+	s.status = 'running';
+	try {
+		await acceptSuggestion(s.action, s.target);
+		s.status = 'done';
+	} catch (e) {
+		s.events.push(`Error: ${e}`);
+		s.status = 'failed';
+	}
+}
+
+/** Dismiss the card. Only an untouched suggestion counts as dismissed;
+ *  closing one that already ran just hides it. */
+export async function dismissActiveSuggestion() {
+	const s = app.activeSuggestion;
+	if (!s || s.status === 'running') return;
+	app.activeSuggestion = null;
+	if (s.status !== 'open') return;
+	const { dismissSuggestion } = await import('$lib/api/commands');
+	try {
+		await dismissSuggestion(s.action);
+	} catch {
+		/* feedback is best-effort */
+	}
+}
+
 /** Reactive app state — $state() creates a deep Proxy for fine-grained tracking. */
 export const app = $state({
 	bubbleState: 'Idle' as BubbleState,
@@ -73,12 +123,28 @@ export const app = $state({
 	modelPanelVisible: false,
 	inboxVisible: false,
 	contactPanelState: null as { contactId: string; conversationId?: string } | null,
+	/** ContactPanel shows the blank contact form instead of a contact. */
+	newContactVisible: false,
 	authState: 'checking' as AuthState,
 	settingsVisible: false,
+	/** Settings tab to show next time Settings opens (e.g. 'devices' from a
+	 *  notification click-through); consumed by the panel. */
+	settingsTab: null as string | null,
 	contextMenu: null as ContextMenuState | null,
 	skillsPanelVisible: false,
 	bubbleStyle: 'icon' as string,
+	editorKeymap: 'standard' as Keymap,
+	/** Set when this window is a popped-out panel rather than the main app. */
+	detached: null as DetachedPanel | null,
+	/** Main window: documents currently open in their own windows. */
+	detachedDocIds: [] as string[],
+	/** Main window: the inbox is open in its own window. */
+	inboxDetached: false,
 	piiDashboardVisible: false,
 	/** Content received from the OS share sheet, waiting for thread selection. */
-	pendingShare: null as PendingShare | null
+	pendingShare: null as PendingShare | null,
+	/** Latest unusual key usage reported by the key audit job. */
+	keyAlert: null as string | null,
+	/** A sealed document waiting for the password before it opens. */
+	unsealPrompt: null as { docId: string; title: string } | null
 });
@@ -0,0 +1,62 @@
+import { beforeEach, describe, expect, it } from 'vitest';
+import { mockTauriCommand } from '$lib/test/tauri';
+import { bulkEdit, applyBulkEdit, describeBulkEdit } from './bulkEdit.svelte';
+import { canvas } from './canvas.svelte';
+import { undoState, undo, redo, clearUndo } from './undo.svelte';
+
+beforeEach(() => {
+	clearUndo();
+	undoState.busy = false;
+	bulkEdit.running = false;
+	bulkEdit.error = '';
+	canvas.loaded = false;
+	canvas.threads = [];
+});
+
+describe('bulk edit', () => {
+	it('records one undo entry that reverts the whole edit', async () => {
+		const calls: { ids: string[] }[] = [];
+		let reverted: unknown[] = [];
+		mockTauriCommand('bulk_edit_documents', (a) => {
+			calls.push(a as { ids: string[] });
+			return {
+				changed: 2,
+				revert: [
+					{ op: 'favorite', doc: 'document:a', favorite: false },
+					{ op: 'favorite', doc: 'document:b', favorite: false }
+				]
+			};
+		});
+		mockTauriCommand('revert_bulk_edit', (a) => {
+			reverted = (a as { steps: unknown[] }).steps;
+		});
+
+		const ok = await applyBulkEdit(['document:a', 'document:b'], { favorite: true });
+		expect(ok).toBe(true);
+		expect(undoState.past).toHaveLength(1);
+		expect(undoState.past[0].label).toBe('Edit 2 documents: pin');
+
+		await undo();
+		expect(reverted).toHaveLength(2);
+		await redo();
+		expect(calls).toHaveLength(2);
+		expect(calls[1].ids).toEqual(['document:a', 'document:b']);
+	});
+
+	it('keeps the error and records nothing when the edit fails', async () => {
+		mockTauriCommand('bulk_edit_documents', () => {
+			throw "'Plan' is in the trash";
+		});
+		expect(await applyBulkEdit(['document:a'], { archive: true })).toBe(false);
+		expect(bulkEdit.error).toContain('in the trash');
+		expect(undoState.past).toHaveLength(0);
+	});
+
+	it('names the changes in the undo label', () => {
+		canvas.threads = [{ id: 'thread:r', name: 'Research' } as (typeof canvas.threads)[number]];
+		expect(describeBulkEdit({ add_tags: ['q3'], thread_id: 'thread:r' }, 3)).toBe(
+			'Edit 3 documents: retag, move to Research'
+		);
+		expect(describeBulkEdit({ archive: true }, 1)).toBe('Edit 1 document: archive');
+	});
+});
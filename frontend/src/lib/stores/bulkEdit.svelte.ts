@@ -0,0 +1,66 @@
+/** Bulk edit of the canvas selection — Svelte 5 rune store.
+ *
+ * The backend applies one `BulkEdit` to every selected document all or
+ * nothing, and returns the writes it made. Undo hands that list back to
+ * `revert_bulk_edit`; redo applies the same edit again and keeps the new
+ * list, so the whole edit is a single entry on the undo stack. */
+import {
+	bulkEditDocuments,
+	revertBulkEdit,
+	type BulkEdit,
+	type BulkEditRevert
+} from '$lib/api/commands';
+import { canvas, refresh as canvasRefresh } from './canvas.svelte';
+import { recordUndo } from './undo.svelte';
+
+export const bulkEdit = $state({
+	running: false,
+	error: ''
+});
+
+/** `Edit 3 documents: retag, move to Research` for the undo entry. */
+export function describeBulkEdit(edit: BulkEdit, count: number): string {
+	const docs = count === 1 ? '1 document' : `${count} documents`;
+	const parts: string[] = [];
+	if (edit.add_tags?.length || edit.remove_tags?.length) parts.push('retag');
+	if (edit.thread_id) {
+		const thread = canvas.threads.find((t) => t.id === edit.thread_id);
+		parts.push(thread ? `move to ${thread.name}` : 'move');
+	}
+	if (edit.doc_type) parts.push(`make ${edit.doc_type}`);
+	if (edit.favorite != null) parts.push(edit.favorite ? 'pin' : 'unpin');
+	if (edit.read_later != null) parts.push(edit.read_later ? 'queue' : 'unqueue');
+	if (edit.archive) parts.push('archive');
+	return parts.length ? `Edit ${docs}: ${parts.join(', ')}` : `Edit ${docs}`;
+}
+
+/** Apply `edit` to `ids` and record it for undo. Resolves to whether it
+ *  went through; the reason it didn't is left in `bulkEdit.error`. */
+export async function applyBulkEdit(ids: string[], edit: BulkEdit): Promise<boolean> {
+	if (bulkEdit.running || ids.length === 0) return false;
+	bulkEdit.running = true;
+	bulkEdit.error = '';
+	try {
+		const report = await bulkEditDocuments(ids, edit);
+		await canvasRefresh();
+		if (report.revert.length === 0) return true;
+		let steps: BulkEditRevert[] = report.revert;
+		recordUndo({
+			label: describeBulkEdit(edit, report.changed),
+			undo: async () => {
+				await revertBulkEdit(steps);
+				await canvasRefresh();
+			},
+			redo: async () => {
+				steps = (await bulkEditDocuments(ids, edit)).revert;
+				await canvasRefresh();
+			}
+		});
+		return true;
+	} catch (e) {
+		bulkEdit.error = String(e);
+		return false;
+	} finally {
+		bulkEdit.running = false;
+	}
+}
@@ -1,14 +1,29 @@
 import { beforeEach, describe, expect, it } from 'vitest';
 import type { CanvasDocDto } from '$lib/api/commands';
+import { mockTauriCommand } from '$lib/test/tauri';
 import {
 	canvas,
 	computeViewport,
 	getVisibleDocuments,
+	filteredDocuments,
+	matchesFilter,
+	isFilterActive,
+	emptyFilter,
+	setFilter,
+	clearFilter,
+	toggleThreadFilter,
+	toggleTagFilter,
+	tagCounts,
+	toggleCardInSelection,
+	selectThreadCards,
+	clearSelection,
 	panBy,
 	zoomAt,
+	setDocFavorite,
 	CARD_W,
 	CARD_H
 } from './canvas.svelte';
+import { undoState, undo, redo, clearUndo } from './undo.svelte';
 
 const ZOOM_MIN = 0.02;
 const ZOOM_MAX = 20.0; // keep in sync with canvas.svelte.ts (raised 5 -> 20 for minute-level zoom)
@@ -21,6 +36,7 @@ function makeDoc(overrides: Partial<CanvasDocDto> = {}): CanvasDocDto {
 		title: 'Test',
 		thread_id: 't:1',
 		is_owned: true,
+		doc_type: 'note',
 		spatial_x: 0,
 		spatial_y: 0,
 		created_at: '2026-01-01T00:00:00Z',
@@ -28,6 +44,10 @@ function makeDoc(overrides: Partial<CanvasDocDto> = {}): CanvasDocDto {
 		reliability_classification: null,
 		reliability_score: null,
 		source_url: null,
+		tags: [],
+		open_tasks: 0,
+		favorite: false,
+		sensitivity: 'normal',
 		...overrides
 	};
 }
@@ -41,10 +61,12 @@ beforeEach(() => {
 	canvas.camera = { panX: 0, panY: 0, zoom: 1 };
 	canvas.hoveredCardId = null;
 	canvas.selectedCardId = null;
+	canvas.selectedIds = [];
 	canvas.draggingCardId = null;
 	canvas.loaded = false;
 	canvas.loadError = null;
 	canvas.timelineScale = null;
+	canvas.filter = emptyFilter();
 });
 
 describe('panBy', () => {
@@ -193,6 +215,103 @@ describe('getVisibleDocuments', () => {
 	});
 });
 
+describe('canvas filter', () => {
+	it('filters by ownership, thread, tag and date range', () => {
+		const doc = makeDoc({ is_owned: false, thread_id: 't:2', tags: ['rust', 'notes'] });
+		const f = emptyFilter();
+		expect(matchesFilter(doc, f)).toBe(true);
+		expect(matchesFilter(doc, { ...f, ownership: 'owned' })).toBe(false);
+		expect(matchesFilter(doc, { ...f, ownership: 'external' })).toBe(true);
+		expect(matchesFilter(doc, { ...f, hiddenThreads: ['t:2'] })).toBe(false);
+		expect(matchesFilter(doc, { ...f, tags: ['go', 'rust'] })).toBe(true);
+		expect(matchesFilter(doc, { ...f, tags: ['go'] })).toBe(false);
+		expect(matchesFilter(doc, { ...f, favoritesOnly: true })).toBe(false);
+		expect(matchesFilter({ ...doc, favorite: true }, { ...f, favoritesOnly: true })).toBe(true);
+	});
+
+	it('treats date bounds as whole local days', () => {
+		const doc = makeDoc({ modified_at: new Date(2026, 2, 10, 18, 30).toISOString() });
+		const f = emptyFilter();
+		expect(matchesFilter(doc, { ...f, from: '2026-03-10', to: '2026-03-10' })).toBe(true);
+		expect(matchesFilter(doc, { ...f, from: '2026-03-11' })).toBe(false);
+		expect(matchesFilter(doc, { ...f, to: '2026-03-09' })).toBe(false);
+	});
+
+	it('applies to the visible documents and toggles chips', () => {
+		canvas.documents = [
+			makeDoc({ id: 'a', thread_id: 't:1', tags: ['x'] }),
+			makeDoc({ id: 'b', thread_id: 't:2' })
+		];
+		expect(isFilterActive(canvas.filter)).toBe(false);
+
+		toggleThreadFilter('t:2');
+		expect(getVisibleDocuments().map((d) => d.id)).toEqual(['a']);
+		toggleThreadFilter('t:2');
+		expect(canvas.filter.hiddenThreads).toEqual([]);
+
+		toggleTagFilter('y');
+		expect(filteredDocuments()).toEqual([]);
+		setFilter({ ownership: 'owned' });
+		expect(canvas.filter.tags).toEqual(['y']);
+		expect(isFilterActive(canvas.filter)).toBe(true);
+
+		clearFilter();
+		expect(filteredDocuments()).toHaveLength(2);
+	});
+
+	it('counts tags most-used first', () => {
+		const docs = [makeDoc({ tags: ['b', 'a'] }), makeDoc({ tags: ['b', 'c'] })];
+		expect(tagCounts(docs)).toEqual([
+			{ tag: 'b', count: 2 },
+			{ tag: 'a', count: 1 },
+			{ tag: 'c', count: 1 }
+		]);
+	});
+});
+
+describe('bulk-edit selection', () => {
+	it('toggles cards and takes in the card selected on its own', () => {
+		canvas.selectedCardId = 'doc:1';
+		toggleCardInSelection('doc:2');
+		expect(canvas.selectedIds).toEqual(['doc:1', 'doc:2']);
+		toggleCardInSelection('doc:1');
+		expect(canvas.selectedIds).toEqual(['doc:2']);
+		clearSelection();
+		expect(canvas.selectedIds).toEqual([]);
+	});
+
+	it('selects the shown cards of a thread', () => {
+		canvas.documents = [
+			makeDoc({ id: 'doc:1', thread_id: 't:1' }),
+			makeDoc({ id: 'doc:2', thread_id: 't:1', is_owned: false }),
+			makeDoc({ id: 'doc:3', thread_id: 't:2' })
+		];
+		setFilter({ ownership: 'owned' });
+		selectThreadCards('t:1');
+		expect(canvas.selectedIds).toEqual(['doc:1']);
+	});
+});
+
+describe('pins', () => {
+	it('records a pin toggle for undo', async () => {
+		clearUndo();
+		undoState.busy = false;
+		const saved: boolean[] = [];
+		mockTauriCommand('set_favorite', (a) => {
+			saved.push((a as { favorite: boolean }).favorite);
+		});
+		canvas.documents = [makeDoc({ id: 'doc:1', title: 'Plan' })];
+
+		await setDocFavorite('doc:1', true);
+		expect(undoState.past.map((e) => e.label)).toEqual(['Pin “Plan”']);
+		await undo();
+		expect(canvas.documents[0].favorite).toBe(false);
+		await redo();
+		expect(canvas.documents[0].favorite).toBe(true);
+		expect(saved).toEqual([true, false, true]);
+	});
+});
+
 describe('canvas constants (regression guard)', () => {
 	it('CARD_W and CARD_H are positive', () => {
 		expect(CARD_W).toBeGreaterThan(0);
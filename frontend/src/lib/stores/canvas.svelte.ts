@@ -5,12 +5,14 @@ import {
 	canvasLoadMessages,
 	updateDocumentPosition,
 	moveDocumentToThread,
+	setFavorite,
 	type CanvasDocDto,
 	type ThreadDto,
 	type RelationshipDto,
 	type MilestoneDto,
 	type CanvasMessageDto
 } from '$lib/api/commands';
+import { recordUndo } from './undo.svelte';
 
 export interface Camera {
 	panX: number;
@@ -40,10 +42,32 @@ export interface CanvasState {
 	camera: Camera;
 	hoveredCardId: string | null;
 	selectedCardId: string | null;
+	/** Cards picked for a bulk edit (shift/ctrl-click, or a whole thread). */
+	selectedIds: string[];
 	draggingCardId: string | null;
 	loaded: boolean;
 	loadError: string | null;
 	timelineScale: TimelineScale | null;
+	filter: CanvasFilter;
+}
+
+/** Which cards the canvas shows. Set from the filter chips above the canvas;
+ *  the cards, heatmap, edges and list view all go through `filteredDocuments()`. */
+export interface CanvasFilter {
+	ownership: 'all' | 'owned' | 'external';
+	/** Thread ids whose lanes are emptied. */
+	hiddenThreads: string[];
+	/** Show only documents carrying at least one of these tags. */
+	tags: string[];
+	/** Inclusive `YYYY-MM-DD` bounds on `modified_at` (local time). */
+	from: string | null;
+	to: string | null;
+	/** Show only documents pinned as favorites. */
+	favoritesOnly: boolean;
+}
+
+export function emptyFilter(): CanvasFilter {
+	return { ownership: 'all', hiddenThreads: [], tags: [], from: null, to: null, favoritesOnly: false };
 }
 
 const ZOOM_MIN = 0.02;
@@ -57,6 +81,18 @@ export const MSG_RADIUS = 30;
  *  compress beyond this so users can still reach hour / minute intervals. */
 export const MAX_VISUAL_ZOOM = 1.5;
 
+/** Relationship edge colors, shared by the canvas and its legend. Types not
+ *  listed draw as `References`. */
+export const RELATION_COLORS = {
+	References: 'rgba(100,180,255,0.65)',
+	DerivedFrom: 'rgba(255,200,100,0.65)',
+	Contradicts: 'rgba(255,100,100,0.65)',
+	Supports: 'rgba(100,255,100,0.65)'
+} as const;
+
+/** Message circle border colors. */
+export const MESSAGE_COLORS = { outbound: '#72bf80', inbound: '#a473cc' } as const;
+
 /** Left margin reserved for thread labels. */
 const LABEL_MARGIN = 200;
 /** Pixels per day at zoom = 1. */
@@ -74,15 +110,20 @@ export const canvas: CanvasState = $state({
 	camera: { panX: 0, panY: 0, zoom: 1 },
 	hoveredCardId: null,
 	selectedCardId: null,
+	selectedIds: [],
 	draggingCardId: null,
 	loaded: false,
 	loadError: null,
-	timelineScale: null
+	timelineScale: null,
+	filter: emptyFilter()
 });
 
 /** Interval handle for periodic "Now" line updates. */
 let nowTimer: ReturnType<typeof setInterval> | null = null;
 
+/** Camera from the last session, applied in place of `home()` on load. */
+let restoredCamera: Camera | null = null;
+
 /** Load canvas data from backend. */
 export async function load() {
 	try {
@@ -95,7 +136,12 @@ export async function load() {
 		canvas.messages = []; // loaded separately via viewport-scoped requestMessagesForViewport()
 		canvas.loaded = true;
 		canvas.loadError = null;
-		home(); // triggers $effect → requestMessagesForViewport()
+		if (restoredCamera) {
+			canvas.camera = restoredCamera;
+			restoredCamera = null;
+		} else {
+			home(); // triggers $effect → requestMessagesForViewport()
+		}
 		startNowTimer();
 	} catch (e) {
 		console.error('Failed to load canvas:', e);
@@ -114,6 +160,7 @@ export async function refresh() {
 		canvas.threads = data.threads;
 		canvas.relationships = data.relationships;
 		canvas.milestones = data.milestones;
+		pruneSelection();
 		// Messages will be refreshed by the viewport $effect
 		requestMessagesForViewport();
 	} catch (e) {
@@ -138,6 +185,13 @@ export function zoomAt(screenX: number, screenY: number, delta: number) {
 	canvas.camera.zoom = newZoom;
 }
 
+/** Put the camera back where the last session left it. Before the canvas
+ *  has loaded this is held until `load()` would otherwise go `home()`. */
+export function restoreCamera(camera: Camera) {
+	if (canvas.loaded) canvas.camera = camera;
+	else restoredCamera = camera;
+}
+
 /** Jump camera to center on "Now" with a readable zoom level. */
 export function home() {
 	const scale = canvas.timelineScale;
@@ -174,16 +228,67 @@ export function selectCard(id: string | null) {
 	canvas.selectedCardId = id;
 }
 
+/** Add a card to the bulk-edit selection, or take it out. The first
+ *  pick also takes in the card that was selected on its own. */
+export function toggleCardInSelection(id: string) {
+	const ids = canvas.selectedIds;
+	if (ids.includes(id)) {
+		canvas.selectedIds = ids.filter((d) => d !== id);
+		return;
+	}
+	const single = canvas.selectedCardId;
+	canvas.selectedIds =
+		ids.length === 0 && single && single !== id ? [single, id] : [...ids, id];
+}
+
+/** Select every card of a thread the current filter shows. */
+export function selectThreadCards(threadId: string) {
+	canvas.selectedIds = filteredDocuments()
+		.filter((d) => d.thread_id === threadId)
+		.map((d) => d.id);
+}
+
+export function clearSelection() {
+	canvas.selectedIds = [];
+}
+
+/** Drop selected ids whose cards are gone (deleted, archived). */
+function pruneSelection() {
+	if (canvas.selectedIds.length === 0) return;
+	const present = new Set(canvas.documents.map((d) => d.id));
+	canvas.selectedIds = canvas.selectedIds.filter((id) => present.has(id));
+}
+
 /** Hover a card. */
 export function hoverCard(id: string | null) {
 	canvas.hoveredCardId = id;
 }
 
+/** Where the card being dragged started, for the undo entry. */
+let dragOrigin: { id: string; y: number; threadId: string } | null = null;
+
 /** Set dragging state. */
 export function setDragging(id: string | null) {
+	if (id && canvas.draggingCardId !== id) {
+		const doc = canvas.documents.find((d) => d.id === id);
+		dragOrigin = doc ? { id, y: doc.spatial_y, threadId: doc.thread_id } : null;
+	}
 	canvas.draggingCardId = id;
 }
 
+/** Put a card at `y` in `threadId` and persist both. Used to replay a
+ *  drag from the undo stack. */
+async function placeCard(id: string, y: number, threadId: string) {
+	const doc = canvas.documents.find((d) => d.id === id);
+	if (!doc) return;
+	doc.spatial_y = y;
+	if (doc.thread_id !== threadId) {
+		doc.thread_id = threadId;
+		await moveDocumentToThread(id, threadId);
+	}
+	await updateDocumentPosition(id, doc.spatial_x, y);
+}
+
 /** Snap a card to the closest lane center after a drag ends. Updates thread if changed. */
 export function snapToLane(id: string) {
 	const doc = canvas.documents.find((d) => d.id === id);
@@ -205,6 +310,19 @@ export function snapToLane(id: string) {
 	doc.spatial_y = snappedY;
 
 	const newThread = canvas.threads[closestIdx];
+	const origin = dragOrigin?.id === id ? dragOrigin : null;
+	dragOrigin = null;
+	if (origin && newThread && (origin.y !== snappedY || origin.threadId !== newThread.id)) {
+		const target = { y: snappedY, threadId: newThread.id };
+		recordUndo({
+			label:
+				origin.threadId !== newThread.id
+					? `Move “${doc.title}” to ${newThread.name}`
+					: `Move “${doc.title}”`,
+			undo: () => placeCard(id, origin.y, origin.threadId),
+			redo: () => placeCard(id, target.y, target.threadId)
+		});
+	}
 	if (newThread && doc.thread_id !== newThread.id) {
 		doc.thread_id = newThread.id;
 		moveDocumentToThread(doc.id, newThread.id).catch((e) =>
@@ -217,6 +335,38 @@ export function snapToLane(id: string) {
 	);
 }
 
+/** Pin a document to the taskbar as a favorite, or unpin it. */
+export async function setDocFavorite(id: string, favorite: boolean) {
+	const doc = canvas.documents.find((d) => d.id === id);
+	const before = doc?.favorite ?? !favorite;
+	try {
+		await applyFavorite(id, favorite);
+	} catch (e) {
+		console.error('Failed to save favorite:', e);
+		return;
+	}
+	if (before !== favorite) {
+		const title = `“${doc?.title || 'Untitled'}”`;
+		recordUndo({
+			label: favorite ? `Pin ${title}` : `Unpin ${title}`,
+			undo: () => applyFavorite(id, before),
+			redo: () => applyFavorite(id, favorite)
+		});
+	}
+}
+
+async function applyFavorite(id: string, favorite: boolean) {
+	const doc = canvas.documents.find((d) => d.id === id);
+	const before = doc?.favorite;
+	if (doc) doc.favorite = favorite;
+	try {
+		await setFavorite(id, favorite);
+	} catch (e) {
+		if (doc && before !== undefined) doc.favorite = before;
+		throw e;
+	}
+}
+
 /** Navigate to and select a document by ID. */
 export function navigateToDoc(id: string) {
 	const doc = canvas.documents.find((d) => d.id === id);
@@ -253,7 +403,7 @@ export function computeViewport() {
 export function getVisibleDocuments(): CanvasDocDto[] {
 	if (canvas.camera.zoom < 0.15) return [];
 	const vp = computeViewport();
-	return canvas.documents.filter(
+	return filteredDocuments().filter(
 		(d) =>
 			d.spatial_x + CARD_W >= vp.left &&
 			d.spatial_x <= vp.right &&
@@ -262,6 +412,74 @@ export function getVisibleDocuments(): CanvasDocDto[] {
 	);
 }
 
+// ---------------------------------------------------------------------------
+// Filters
+// ---------------------------------------------------------------------------
+
+/** Whether `doc` passes every part of `filter`. */
+export function matchesFilter(doc: CanvasDocDto, filter: CanvasFilter): boolean {
+	if (filter.ownership === 'owned' && !doc.is_owned) return false;
+	if (filter.ownership === 'external' && doc.is_owned) return false;
+	if (filter.hiddenThreads.includes(doc.thread_id)) return false;
+	if (filter.favoritesOnly && !doc.favorite) return false;
+	if (filter.tags.length > 0 && !filter.tags.some((t) => doc.tags.includes(t))) return false;
+	if (filter.from || filter.to) {
+		const t = new Date(doc.modified_at).getTime();
+		if (filter.from && t < new Date(`${filter.from}T00:00:00`).getTime()) return false;
+		if (filter.to && t > new Date(`${filter.to}T23:59:59.999`).getTime()) return false;
+	}
+	return true;
+}
+
+export function isFilterActive(filter: CanvasFilter): boolean {
+	return (
+		filter.ownership !== 'all' ||
+		filter.hiddenThreads.length > 0 ||
+		filter.favoritesOnly ||
+		filter.tags.length > 0 ||
+		filter.from !== null ||
+		filter.to !== null
+	);
+}
+
+/** Documents that pass the current filter. Layout is unchanged, so filtered
+ *  cards leave gaps instead of shuffling the timeline. */
+export function filteredDocuments(): CanvasDocDto[] {
+	if (!isFilterActive(canvas.filter)) return canvas.documents;
+	return canvas.documents.filter((d) => matchesFilter(d, canvas.filter));
+}
+
+export function setFilter(patch: Partial<CanvasFilter>) {
+	canvas.filter = { ...canvas.filter, ...patch };
+}
+
+export function clearFilter() {
+	canvas.filter = emptyFilter();
+}
+
+export function toggleThreadFilter(threadId: string) {
+	const hidden = canvas.filter.hiddenThreads;
+	setFilter({
+		hiddenThreads: hidden.includes(threadId)
+			? hidden.filter((id) => id !== threadId)
+			: [...hidden, threadId]
+	});
+}
+
+export function toggleTagFilter(tag: string) {
+	const tags = canvas.filter.tags;
+	setFilter({ tags: tags.includes(tag) ? tags.filter((t) => t !== tag) : [...tags, tag] });
+}
+
+/** Tags across `docs`, most used first (ties alphabetical). */
+export function tagCounts(docs: CanvasDocDto[]): { tag: string; count: number }[] {
+	const counts = new Map<string, number>();
+	for (const d of docs) for (const t of d.tags) counts.set(t, (counts.get(t) ?? 0) + 1);
+	return [...counts]
+		.map(([tag, count]) => ({ tag, count }))
+		.sort((a, b) => b.count - a.count || a.tag.localeCompare(b.tag));
+}
+
 // ---------------------------------------------------------------------------
 // Timeline layout
 // ---------------------------------------------------------------------------
@@ -1,7 +1,11 @@
 import { beforeEach, describe, expect, it } from 'vitest';
+import { mockTauriCommand } from '$lib/test/tauri';
 import {
+	appendBlock,
 	chat,
 	clearGenerating,
+	historyToMessages,
+	loadHistory,
 	pushAssistant,
 	pushSystem,
 	pushUser,
@@ -151,3 +155,60 @@ describe('recentMessages', () => {
 		expect(recent[recent.length - 1].text).toBe('msg-249');
 	});
 });
+
+describe('history', () => {
+	beforeEach(() => {
+		chat.historyLoaded = false;
+	});
+
+	it('maps session-log entries to messages', () => {
+		expect(
+			historyToMessages([
+				{ role: 'user', text: 'hi', ts: '2026-02-18T14:30:00Z' },
+				{ role: 'assistant', text: 'hello', ts: 'garbled' }
+			])
+		).toEqual([
+			{ role: 'user', text: 'hi', timestamp: Date.parse('2026-02-18T14:30:00Z') },
+			{ role: 'assistant', text: 'hello', timestamp: 0 }
+		]);
+	});
+
+	it('prepends scrollback once, skipping turns already on screen', async () => {
+		const live = Date.parse('2026-02-18T15:00:00Z');
+		chat.messages = [{ role: 'user', text: 'new', timestamp: live }];
+		let calls = 0;
+		mockTauriCommand('get_chat_history', () => {
+			calls++;
+			return [
+				{ role: 'user', text: 'old', ts: '2026-02-18T14:00:00Z' },
+				{ role: 'assistant', text: 'old reply', ts: '2026-02-18T14:00:05Z' },
+				{ role: 'user', text: 'new', ts: '2026-02-18T15:00:01Z' }
+			];
+		});
+
+		await loadHistory();
+		await loadHistory();
+
+		expect(calls).toBe(1);
+		expect(chat.messages.map((m) => m.text)).toEqual(['old', 'old reply', 'new']);
+	});
+
+	it('retries after a failed load', async () => {
+		mockTauriCommand('get_chat_history', () => {
+			throw 'Session locked';
+		});
+		await loadHistory();
+		expect(chat.historyLoaded).toBe(false);
+		expect(chat.messages).toEqual([]);
+	});
+});
+
+describe('appendBlock', () => {
+	it('adds the text as a new paragraph', () => {
+		expect(appendBlock('# Notes\n\nfirst\n\n', '  answer ')).toBe('# Notes\n\nfirst\n\nanswer\n');
+	});
+
+	it('fills an empty body', () => {
+		expect(appendBlock('  \n', '```js\nx()\n```')).toBe('```js\nx()\n```\n');
+	});
+});
@@ -1,5 +1,7 @@
 /** Rune-based reactive state for the chat panel. */
 
+import { getChatHistory, type ChatHistoryEntry } from '$lib/api/commands';
+
 export interface ChatMessage {
 	role: 'user' | 'assistant' | 'system';
 	text: string;
@@ -11,7 +13,9 @@ export const chat = $state({
 	messages: [] as ChatMessage[],
 	generating: false,
 	input: '',
-	visible: false
+	visible: false,
+	/** Scrollback from the session log has been requested. */
+	historyLoaded: false
 });
 
 export function pushUser(text: string) {
@@ -49,3 +53,36 @@ export function toggleChat() {
 export function recentMessages(): ChatMessage[] {
 	return chat.messages.slice(-200);
 }
+
+/** Session-log entries as panel messages; unparseable timestamps sort first. */
+export function historyToMessages(entries: ChatHistoryEntry[]): ChatMessage[] {
+	return entries.map((e) => ({
+		role: e.role,
+		text: e.text,
+		timestamp: Date.parse(e.ts) || 0
+	}));
+}
+
+/** Put the persisted scrollback ahead of this session's messages, once.
+ *  Turns logged after the first live message are already on screen. */
+export async function loadHistory(limit = 100) {
+	if (chat.historyLoaded) return;
+	chat.historyLoaded = true;
+	let entries: ChatHistoryEntry[];
+	try {
+		entries = await getChatHistory(limit);
+	} catch {
+		// Locked or no orchestrator yet — try again next time the panel opens.
+		chat.historyLoaded = false;
+		return;
+	}
+	const firstLive = chat.messages[0]?.timestamp ?? Infinity;
+	const earlier = historyToMessages(entries).filter((m) => m.timestamp < firstLive);
+	chat.messages = [...earlier, ...chat.messages];
+}
+
+/** `text` appended to a document body as its own paragraph. */
+export function appendBlock(body: string, text: string): string {
+	const trimmed = body.replace(/\s+$/, '');
+	return trimmed ? `${trimmed}\n\n${text.trim()}\n` : `${text.trim()}\n`;
+}
@@ -1,13 +1,35 @@
 /** Rune-based reactive state for open document panels. */
 
-import type { FullDocument, CommitSummary } from '$lib/api/commands';
+import type {
+	FullDocument,
+	CommitSummary,
+	CommitSnapshot,
+	DocType,
+	RecoverableEdit,
+	Sensitivity
+} from '$lib/api/commands';
 import {
 	getDocument,
 	saveDocument as apiSave,
 	closeDocument as apiClose,
 	listCommits as apiListCommits,
-	restoreCommit as apiRestoreCommit
+	restoreCommit as apiRestoreCommit,
+	getCommitSnapshot as apiGetCommitSnapshot,
+	attachFile,
+	removeAttachment,
+	detachPanel,
+	setDocumentType,
+	setDocumentSensitivity,
+	journalEdit,
+	discardJournaledEdit
 } from '$lib/api/commands';
+import { fileToBase64 } from '$lib/utils/media';
+import { openTaskCount } from '$lib/utils/blocks';
+import { hashtags } from '$lib/utils/docStats';
+import { docTypeInfo } from '$lib/utils/docType';
+import { app } from './app.svelte';
+import { canvas } from './canvas.svelte';
+import { recordUndo } from './undo.svelte';
 
 export interface OpenPanel {
 	doc: FullDocument;
@@ -15,21 +37,39 @@ export interface OpenPanel {
 	position: { x: number; y: number };
 	size: { width: number; height: number };
 	zIndex: number;
-	mode: 'edit' | 'preview' | 'history';
+	mode: 'edit' | 'preview' | 'history' | 'activity';
 	commits: CommitSummary[];
 	commitsLoaded: boolean;
 	selectedCommit: number | null;
+	/** Full snapshot of `selectedCommit`, once loaded. */
+	selectedSnapshot: CommitSnapshot | null;
+	diffLayout: 'inline' | 'split';
 	skillsOverflowOpen: boolean;
+	outlineOpen: boolean;
 }
 
 let nextZ = 100;
 let openCount = 0;
 
+/** How long typing settles before a dirty panel is journaled. */
+const JOURNAL_DELAY_MS = 400;
+const journalTimers = new Map<string, ReturnType<typeof setTimeout>>();
+
 /** Reactive array of open document panels. */
 export const panels: OpenPanel[] = $state([]);
 
-/** Open a document by ID. Prevents duplicates — brings existing to front. */
-export async function openById(id: string) {
+/** Bumped after every save: saving one document can change links shown
+ *  in another. */
+export const saves = $state({ count: 0 });
+
+/** Open a document by ID. Prevents duplicates — brings existing to front.
+ *  `layout` places the panel (session restore); otherwise it cascades. */
+export async function openById(id: string, layout?: Pick<OpenPanel, 'position' | 'size'>) {
+	// Already in its own window: bring that window forward instead.
+	if (app.detachedDocIds.includes(id)) {
+		detachPanel('document', id, '').catch((e) => console.error('Failed to focus window:', e));
+		return;
+	}
 	const existing = panels.find((p) => p.doc.id === id);
 	if (existing) {
 		bringToFront(id);
@@ -37,29 +77,56 @@ export async function openById(id: string) {
 	}
 	try {
 		const doc = await getDocument(id);
+		const type = docTypeInfo(doc.doc_type);
 		const offset = openCount * 30;
 		openCount++;
 		panels.push({
 			doc,
 			dirty: false,
-			position: { x: 120 + offset, y: 80 + offset },
-			size: { width: 680, height: 520 },
+			position: layout?.position ?? { x: 120 + offset, y: 80 + offset },
+			size: layout?.size ?? { width: type.width, height: 520 },
 			zIndex: nextZ++,
-			mode: 'edit',
+			mode: type.preview ? 'preview' : 'edit',
 			commits: [],
 			commitsLoaded: false,
 			selectedCommit: null,
-			skillsOverflowOpen: false
+			selectedSnapshot: null,
+			diffLayout: 'inline',
+			skillsOverflowOpen: false,
+			outlineOpen: type.outline
 		});
 	} catch (e) {
+		// Sealed: ask for the password, then open it from the prompt.
+		const card = canvas.documents.find((d) => d.id === id);
+		if (card?.sensitivity === 'sealed') {
+			app.unsealPrompt = { docId: id, title: card.title };
+			return;
+		}
 		console.error('Failed to open document:', e);
 	}
 }
 
+/** Change how closely the document is guarded. */
+export async function setDocSensitivity(id: string, sensitivity: Sensitivity) {
+	const panel = panels.find((p) => p.doc.id === id);
+	if (!panel || panel.doc.sensitivity === sensitivity) return;
+	try {
+		await setDocumentSensitivity(id, sensitivity);
+		panel.doc.sensitivity = sensitivity;
+		const card = canvas.documents.find((d) => d.id === id);
+		if (card) card.sensitivity = sensitivity;
+	} catch (e) {
+		console.error('Failed to change document sensitivity:', e);
+	}
+}
+
 /** Save the document to the backend. */
 export async function save(id: string) {
 	const panel = panels.find((p) => p.doc.id === id);
 	if (!panel || !panel.dirty) return;
+	// The save clears the journal entry; a late write would bring it back.
+	clearTimeout(journalTimers.get(id));
+	journalTimers.delete(id);
 	try {
 		await apiSave(
 			panel.doc.id,
@@ -69,6 +136,9 @@ export async function save(id: string) {
 			panel.doc.videos
 		);
 		panel.dirty = false;
+		saves.count++;
+		const card = canvas.documents.find((d) => d.id === id);
+		if (card) card.open_tasks = openTaskCount(panel.doc.body);
 	} catch (e) {
 		console.error('Failed to save document:', e);
 	}
@@ -90,21 +160,138 @@ export async function close(id: string) {
 	if (idx !== -1) panels.splice(idx, 1);
 }
 
+/** Set a field from the undo stack and save it straight away. A panel
+ *  closed since the edit is updated through the backend. */
+async function applyEdit(id: string, field: 'body' | 'title', value: string) {
+	const panel = panels.find((p) => p.doc.id === id);
+	if (panel) {
+		panel.doc[field] = value;
+		panel.dirty = true;
+		await save(id);
+		return;
+	}
+	const doc = await getDocument(id);
+	doc[field] = value;
+	await apiSave(doc.id, doc.title, doc.body, doc.images, doc.videos);
+}
+
+/** Journal the panel's unsaved title and body once typing settles, so a
+ *  crash before the next save can offer them back. */
+function scheduleJournal(panel: OpenPanel) {
+	const id = panel.doc.id;
+	clearTimeout(journalTimers.get(id));
+	journalTimers.set(
+		id,
+		setTimeout(() => {
+			journalTimers.delete(id);
+			if (!panel.dirty) return;
+			journalEdit(id, panel.doc.title, panel.doc.body).catch((e) =>
+				console.warn('Failed to journal edits:', e)
+			);
+		}, JOURNAL_DELAY_MS)
+	);
+}
+
+/** `Tag “Notes” #plan` when an edit changes the body's tags, so the tag
+ *  change is its own entry rather than part of a burst of typing. */
+function tagChangeLabel(title: string, before: string, after: string): string | null {
+	const was = hashtags(before);
+	const now = hashtags(after);
+	const added = now.filter((t) => !was.includes(t));
+	const removed = was.filter((t) => !now.includes(t));
+	if (added.length === 0 && removed.length === 0) return null;
+	const name = `“${title || 'Untitled'}”`;
+	const list = (tags: string[]) => tags.map((t) => `#${t}`).join(' ');
+	if (removed.length === 0) return `Tag ${name} ${list(added)}`;
+	if (added.length === 0) return `Untag ${name} ${list(removed)}`;
+	return `Retag ${name}`;
+}
+
+function recordEdit(panel: OpenPanel, field: 'body' | 'title', before: string) {
+	const id = panel.doc.id;
+	const after = panel.doc[field];
+	const tagLabel = field === 'body' ? tagChangeLabel(panel.doc.title, before, after) : null;
+	recordUndo({
+		label:
+			tagLabel ??
+			(field === 'title' ? `Rename “${after || 'Untitled'}”` : `Edit “${panel.doc.title || 'Untitled'}”`),
+		key: tagLabel ? `tags:${id}` : `${field}:${id}`,
+		undo: () => applyEdit(id, field, before),
+		redo: () => applyEdit(id, field, after)
+	});
+}
+
 /** Update the body text. */
 export function updateBody(id: string, body: string) {
 	const panel = panels.find((p) => p.doc.id === id);
-	if (panel) {
+	if (panel && panel.doc.body !== body) {
+		const before = panel.doc.body;
 		panel.doc.body = body;
 		panel.dirty = true;
+		recordEdit(panel, 'body', before);
+		scheduleJournal(panel);
+	}
+}
+
+/** List an image in the document's media so sync carries its bytes. */
+export function addImage(id: string, path: string, caption: string) {
+	const panel = panels.find((p) => p.doc.id === id);
+	if (panel && !panel.doc.images.some((i) => i.path === path)) {
+		panel.doc.images = [...panel.doc.images, { path, caption }];
+		panel.dirty = true;
+	}
+}
+
+/** Store a file and attach it. The backend saves the attachment list
+ *  itself, so this doesn't mark the panel dirty. */
+export async function attachFileTo(id: string, file: File) {
+	const stored = await attachFile(id, file.name, await fileToBase64(file));
+	const panel = panels.find((p) => p.doc.id === id);
+	if (panel && !panel.doc.files.some((f) => f.path === stored.path)) {
+		panel.doc.files = [...panel.doc.files, stored];
 	}
 }
 
+/** Detach a file from the document (its blob stays in the store). */
+export async function removeAttachmentFrom(id: string, path: string) {
+	await removeAttachment(id, path);
+	const panel = panels.find((p) => p.doc.id === id);
+	if (panel) panel.doc.files = panel.doc.files.filter((f) => f.path !== path);
+}
+
 /** Update the title. */
 export function updateTitle(id: string, title: string) {
 	const panel = panels.find((p) => p.doc.id === id);
-	if (panel) {
+	if (panel && panel.doc.title !== title) {
+		const before = panel.doc.title;
 		panel.doc.title = title;
 		panel.dirty = true;
+		recordEdit(panel, 'title', before);
+		scheduleJournal(panel);
+	}
+}
+
+/** Apply edits recovered from the journal and save them. They go through
+ *  the undo stack, so the saved version is one undo away. */
+export async function recoverEdit(edit: RecoverableEdit) {
+	await openById(edit.doc_id);
+	if (!panels.some((p) => p.doc.id === edit.doc_id)) {
+		// Open in its own window: save through the backend instead.
+		const doc = await getDocument(edit.doc_id);
+		await apiSave(doc.id, edit.title, edit.body, doc.images, doc.videos);
+		return;
+	}
+	updateTitle(edit.doc_id, edit.title);
+	updateBody(edit.doc_id, edit.body);
+	await save(edit.doc_id);
+}
+
+/** Drop journaled edits without applying them. */
+export async function discardEdit(docId: string) {
+	try {
+		await discardJournaledEdit(docId);
+	} catch (e) {
+		console.error('Failed to discard journaled edits:', e);
 	}
 }
 
@@ -116,6 +303,11 @@ export function bringToFront(id: string) {
 	}
 }
 
+/** The panel on top of the stack, if any are open. */
+export function frontmostPanel(): OpenPanel | null {
+	return panels.reduce<OpenPanel | null>((top, p) => (!top || p.zIndex > top.zIndex ? p : top), null);
+}
+
 /** Update panel position (after drag). */
 export function updatePosition(id: string, x: number, y: number) {
 	const panel = panels.find((p) => p.doc.id === id);
@@ -124,8 +316,8 @@ export function updatePosition(id: string, x: number, y: number) {
 	}
 }
 
-/** Switch panel mode (edit / preview / history). */
-export function setMode(id: string, mode: 'edit' | 'preview' | 'history') {
+/** Switch panel mode (edit / preview / history / activity). */
+export function setMode(id: string, mode: 'edit' | 'preview' | 'history' | 'activity') {
 	const panel = panels.find((p) => p.doc.id === id);
 	if (panel) {
 		panel.mode = mode;
@@ -155,17 +347,60 @@ export async function restoreVersion(id: string, commitId: string) {
 			panel.dirty = false;
 			panel.mode = 'edit';
 			panel.commitsLoaded = false;
+			panel.selectedCommit = null;
+			panel.selectedSnapshot = null;
 		}
 	} catch (e) {
 		console.error('Failed to restore commit:', e);
 	}
 }
 
-/** Select a commit in history view. */
-export function selectCommit(id: string, index: number | null) {
+/** Select a commit in history view and fetch its snapshot for the diff. */
+export async function selectCommit(id: string, index: number | null) {
+	const panel = panels.find((p) => p.doc.id === id);
+	if (!panel) return;
+	panel.selectedCommit = index;
+	panel.selectedSnapshot = null;
+	const commit = index === null ? undefined : panel.commits[index];
+	if (!commit) return;
+	try {
+		const snapshot = await apiGetCommitSnapshot(id, commit.id);
+		// Ignore a late answer for a row the user already moved off.
+		if (panel.selectedCommit === index) {
+			panel.selectedSnapshot = snapshot;
+		}
+	} catch (e) {
+		console.error('Failed to load commit snapshot:', e);
+	}
+}
+
+/** Switch the history diff between inline and side-by-side. */
+export function setDiffLayout(id: string, layout: 'inline' | 'split') {
+	const panel = panels.find((p) => p.doc.id === id);
+	if (panel) {
+		panel.diffLayout = layout;
+	}
+}
+
+/** Change the document's type; the canvas card follows. */
+export async function setDocType(id: string, docType: DocType) {
+	const panel = panels.find((p) => p.doc.id === id);
+	if (!panel || panel.doc.doc_type === docType) return;
+	try {
+		await setDocumentType(id, docType);
+		panel.doc.doc_type = docType;
+		const card = canvas.documents.find((d) => d.id === id);
+		if (card) card.doc_type = docType;
+	} catch (e) {
+		console.error('Failed to change document type:', e);
+	}
+}
+
+/** Show or hide the heading outline beside the body. */
+export function toggleOutline(id: string) {
 	const panel = panels.find((p) => p.doc.id === id);
 	if (panel) {
-		panel.selectedCommit = index;
+		panel.outlineOpen = !panel.outlineOpen;
 	}
 }
 
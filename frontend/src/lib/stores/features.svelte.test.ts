@@ -0,0 +1,62 @@
+import { beforeEach, describe, expect, it } from 'vitest';
+import { mockTauriCommand } from '$lib/test/tauri';
+import type { FeatureDto } from '$lib/api/commands';
+import { features, featureOn, loadFeatures, setFeature } from './features.svelte';
+
+function feature(id: string, enabled: boolean, compiled = true): FeatureDto {
+	return { id, label: id, compiled, enabled, toggleable: compiled, healthy: null };
+}
+
+beforeEach(() => {
+	features.list = [];
+	features.loaded = false;
+});
+
+describe('featureOn', () => {
+	it('treats everything as on until the registry loads', () => {
+		expect(featureOn('voice')).toBe(true);
+	});
+
+	it('follows the registry once loaded', async () => {
+		mockTauriCommand('list_features', () => [
+			feature('voice', false),
+			feature('p2p', false, false),
+			feature('metrics', true)
+		]);
+
+		await loadFeatures();
+
+		expect(featureOn('voice')).toBe(false);
+		expect(featureOn('p2p')).toBe(false);
+		expect(featureOn('metrics')).toBe(true);
+		expect(featureOn('unknown')).toBe(true);
+	});
+
+	it('keeps the defaults when the registry fails to load', async () => {
+		mockTauriCommand('list_features', () => {
+			throw 'Session locked';
+		});
+
+		await loadFeatures();
+
+		expect(features.loaded).toBe(false);
+		expect(featureOn('voice')).toBe(true);
+	});
+});
+
+describe('setFeature', () => {
+	it('saves the switch and reloads the registry', async () => {
+		let voice = false;
+		mockTauriCommand('set_feature_enabled', (args: { id: string; enabled: boolean }) => {
+			expect(args.id).toBe('voice');
+			voice = args.enabled;
+			return { restart_required: [] };
+		});
+		mockTauriCommand('list_features', () => [feature('voice', voice)]);
+
+		const report = await setFeature('voice', true);
+
+		expect(report.restart_required).toEqual([]);
+		expect(featureOn('voice')).toBe(true);
+	});
+});
@@ -0,0 +1,44 @@
+/** Runtime feature registry — Svelte 5 rune store.
+ *
+ * Mirrors `list_features`: one entry per optional subsystem (P2P sync,
+ * comms, voice, vision, the embedded browser, …) saying whether the build
+ * carries it, whether it is switched on and whether it is running.
+ * Components ask `featureOn(id)` before showing an affordance instead of
+ * each probing its own command.
+ */
+import {
+	listFeatures,
+	setFeatureEnabled,
+	type ConfigApplyReport,
+	type FeatureDto
+} from '$lib/api/commands';
+
+export const features = $state({
+	list: [] as FeatureDto[],
+	loaded: false
+});
+
+export async function loadFeatures() {
+	try {
+		features.list = await listFeatures();
+		features.loaded = true;
+	} catch (e) {
+		console.warn('Failed to load feature registry:', e);
+	}
+}
+
+/** Whether `id` is built in and switched on. Until the registry loads,
+ *  and for ids it doesn't know, the answer is yes: a slow start or an
+ *  older backend shouldn't hide anything. */
+export function featureOn(id: string): boolean {
+	if (!features.loaded) return true;
+	const feature = features.list.find((f) => f.id === id);
+	return feature ? feature.enabled : true;
+}
+
+/** Switch a subsystem on or off, then refresh the registry. */
+export async function setFeature(id: string, enabled: boolean): Promise<ConfigApplyReport> {
+	const report = await setFeatureEnabled(id, enabled);
+	await loadFeatures();
+	return report;
+}
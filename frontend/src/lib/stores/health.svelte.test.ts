@@ -0,0 +1,69 @@
+import { beforeEach, describe, expect, it } from 'vitest';
+import { mockTauriCommand } from '$lib/test/tauri';
+import type { ComposeChannelDto, SystemHealth } from '$lib/api/commands';
+import {
+	health,
+	commsHealth,
+	formatMegabytes,
+	modelLabel,
+	refreshHealth
+} from './health.svelte';
+import { jobs } from './jobs.svelte';
+
+function channel(name: string, connected: boolean): ComposeChannelDto {
+	return { channel: name, connected, error: connected ? null : 'timeout' };
+}
+
+beforeEach(() => {
+	health.system = null;
+	health.comms = [];
+	health.error = null;
+	jobs.list = [];
+});
+
+describe('health helpers', () => {
+	it('summarises comms channels', () => {
+		expect(commsHealth([])).toBe('none');
+		expect(commsHealth([channel('email', true), channel('signal', true)])).toBe('ok');
+		expect(commsHealth([channel('email', true), channel('signal', false)])).toBe('degraded');
+		expect(commsHealth([channel('email', false)])).toBe('down');
+	});
+
+	it('formats sizes and model names', () => {
+		expect(formatMegabytes(640.4)).toBe('640 MB');
+		expect(formatMegabytes(2457.6)).toBe('2.4 GB');
+		expect(modelLabel('Qwen2.5-3B-Instruct-Q4_K_M.gguf')).toBe('Qwen2.5-3B-Instruct-Q4_K_M');
+	});
+});
+
+describe('refreshHealth', () => {
+	it('loads system health, comms and jobs', async () => {
+		const system: SystemHealth = {
+			db_mode: 'encrypted',
+			model: { router: 'r.gguf', reasoning: 'q.gguf', reasoning_loaded: false, vram_estimate_mb: 1900 }
+		};
+		mockTauriCommand('get_system_health', () => system);
+		mockTauriCommand('list_compose_channels', () => [channel('email', true)]);
+		mockTauriCommand('list_skill_jobs', () => []);
+
+		await refreshHealth();
+
+		expect(health.system).toEqual(system);
+		expect(health.comms).toEqual([channel('email', true)]);
+		expect(health.error).toBeNull();
+	});
+
+	it('keeps comms when system health fails', async () => {
+		mockTauriCommand('get_system_health', () => {
+			throw 'Session locked';
+		});
+		mockTauriCommand('list_compose_channels', () => [channel('signal', false)]);
+		mockTauriCommand('list_skill_jobs', () => []);
+
+		await refreshHealth();
+
+		expect(health.system).toBeNull();
+		expect(health.error).toBe('Session locked');
+		expect(health.comms).toEqual([channel('signal', false)]);
+	});
+});
@@ -0,0 +1,72 @@
+/** Status bar state — Svelte 5 rune store.
+ *
+ * Polls `get_system_health` (storage mode, local models) and
+ * `list_compose_channels` (comms accounts) while the status bar is
+ * mounted, and keeps the skill job list fresh for its job count. P2P
+ * peers come from the event-driven sync store, so they aren't polled.
+ */
+import {
+	getSystemHealth,
+	listComposeChannels,
+	type ComposeChannelDto,
+	type SystemHealth
+} from '$lib/api/commands';
+import { refreshJobs } from './jobs.svelte';
+
+export const POLL_MS = 15_000;
+
+export const health = $state({
+	system: null as SystemHealth | null,
+	comms: [] as ComposeChannelDto[],
+	error: null as string | null
+});
+
+/** `none`: no account set up; `down`: none connected; `degraded`: some. */
+export type CommsHealth = 'none' | 'ok' | 'degraded' | 'down';
+
+export function commsHealth(channels: ComposeChannelDto[]): CommsHealth {
+	if (channels.length === 0) return 'none';
+	const connected = channels.filter((c) => c.connected).length;
+	if (connected === channels.length) return 'ok';
+	return connected === 0 ? 'down' : 'degraded';
+}
+
+/** "1.2 GB" / "640 MB". */
+export function formatMegabytes(mb: number): string {
+	return mb >= 1024 ? `${(mb / 1024).toFixed(1)} GB` : `${Math.round(mb)} MB`;
+}
+
+/** The model name without its `.gguf` extension. */
+export function modelLabel(filename: string): string {
+	return filename.replace(/\.gguf$/i, '');
+}
+
+export async function refreshHealth() {
+	const [system, comms] = await Promise.allSettled([
+		getSystemHealth(),
+		listComposeChannels(),
+		refreshJobs()
+	]);
+	if (system.status === 'fulfilled') {
+		health.system = system.value;
+		health.error = null;
+	} else {
+		health.error = String(system.reason);
+	}
+	if (comms.status === 'fulfilled') health.comms = comms.value;
+}
+
+let pollTimer: ReturnType<typeof setInterval> | null = null;
+
+export function startHealthPolling() {
+	stopHealthPolling();
+	refreshHealth();
+	pollTimer = setInterval(refreshHealth, POLL_MS);
+}
+
+export function stopHealthPolling() {
+	if (pollTimer !== null) {
+		clearInterval(pollTimer);
+		pollTimer = null;
+	}
+}
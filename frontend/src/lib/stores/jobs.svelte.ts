@@ -0,0 +1,70 @@
+/** Skill jobs panel state — Svelte 5 rune store.
+ *
+ * Mirrors the backend skill job queue (queued / running / finished
+ * executions). The panel polls `list_skill_jobs` once a second while it is
+ * visible. */
+import { listSkillJobs, cancelSkillJob, clearFinishedSkillJobs, type SkillJob } from '$lib/api/commands';
+
+export const jobs = $state({
+	open: false,
+	list: [] as SkillJob[],
+	error: ''
+});
+
+/** Jobs still waiting or running. */
+export function activeJobCount(): number {
+	return jobs.list.filter((j) => j.status === 'queued' || j.status === 'running').length;
+}
+
+export async function refreshJobs() {
+	try {
+		jobs.list = await listSkillJobs();
+		jobs.error = '';
+	} catch (e) {
+		jobs.error = String(e);
+	}
+}
+
+export async function cancelJob(id: number) {
+	try {
+		await cancelSkillJob(id);
+	} catch (e) {
+		jobs.error = String(e);
+	}
+	await refreshJobs();
+}
+
+export async function clearFinishedJobs() {
+	try {
+		await clearFinishedSkillJobs();
+	} catch (e) {
+		jobs.error = String(e);
+	}
+	await refreshJobs();
+}
+
+let pollTimer: ReturnType<typeof setInterval> | null = null;
+
+function stopPolling() {
+	if (pollTimer !== null) {
+		clearInterval(pollTimer);
+		pollTimer = null;
+	}
+}
+
+export function openJobsPanel() {
+	jobs.open = true;
+	stopPolling();
+	refreshJobs();
+	pollTimer = setInterval(refreshJobs, 1000);
+}
+
+export function closeJobsPanel() {
+	jobs.open = false;
+	stopPolling();
+}
+
+export function toggleJobsPanel() {
+	if (jobs.open) closeJobsPanel();
+	else openJobsPanel();
+}
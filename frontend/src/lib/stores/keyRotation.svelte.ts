@@ -0,0 +1,62 @@
+/** Key rotation state for the Settings → Security tab — Svelte 5 rune store.
+ *
+ * Rotations run in the backend; progress arrives as `key-rotation-progress`
+ * events (wired in `api/events.ts`). */
+import {
+	rotateKek,
+	rotateDocumentKeys,
+	getKeyRotationLog,
+	type KeyRotationEntryDto,
+	type KeyRotationProgress,
+	type RotationScope
+} from '$lib/api/commands';
+
+export const keyRotation = $state({
+	running: false,
+	progress: null as KeyRotationProgress | null,
+	log: [] as KeyRotationEntryDto[],
+	status: '',
+	error: ''
+});
+
+export function onRotationProgress(progress: KeyRotationProgress) {
+	keyRotation.progress = progress;
+}
+
+export async function loadRotationLog() {
+	try {
+		keyRotation.log = await getKeyRotationLog();
+	} catch (e) {
+		keyRotation.error = String(e);
+	}
+}
+
+async function run(task: () => Promise<string>) {
+	if (keyRotation.running) return;
+	keyRotation.running = true;
+	keyRotation.progress = null;
+	keyRotation.status = '';
+	keyRotation.error = '';
+	try {
+		keyRotation.status = await task();
+	} catch (e) {
+		keyRotation.error = String(e);
+	}
+	keyRotation.running = false;
+	keyRotation.progress = null;
+	await loadRotationLog();
+}
+
+export function rotateMasterKey(password: string) {
+	return run(async () => {
+		const keys = await rotateKek(password);
+		return `Master key rotated — ${keys} document keys re-wrapped`;
+	});
+}
+
+export function rotateKeys(scope: RotationScope) {
+	return run(async () => {
+		const count = await rotateDocumentKeys(scope);
+		return `Re-encrypted ${count} items under new keys`;
+	});
+}
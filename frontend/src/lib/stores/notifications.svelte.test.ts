@@ -0,0 +1,55 @@
+import { beforeEach, describe, expect, it } from 'vitest';
+import {
+	notifications,
+	notify,
+	unreadNotificationCount,
+	visibleNotifications,
+	markNotificationRead,
+	markAllNotificationsRead,
+	clearNotifications,
+	MAX_NOTIFICATIONS
+} from './notifications.svelte';
+
+beforeEach(() => {
+	notifications.items = [];
+	notifications.filter = 'all';
+	notifications.unreadOnly = false;
+	notifications.open = false;
+});
+
+describe('notifications store', () => {
+	it('keeps the newest entry first and counts unread ones', () => {
+		notify('sync', 'Synced with Laptop', { panel: 'devices' });
+		notify('error', 'Sync failed');
+		expect(notifications.items.map((n) => n.text)).toEqual(['Sync failed', 'Synced with Laptop']);
+		expect(notifications.items[1].target).toEqual({ panel: 'devices' });
+		expect(unreadNotificationCount()).toBe(2);
+
+		markNotificationRead(notifications.items[0].id);
+		expect(unreadNotificationCount()).toBe(1);
+		markAllNotificationsRead();
+		expect(unreadNotificationCount()).toBe(0);
+	});
+
+	it('filters by kind and by unread state', () => {
+		notify('sync', 'a');
+		notify('message', 'b');
+		notify('sync', 'c');
+		markNotificationRead(notifications.items[0].id);
+
+		notifications.filter = 'sync';
+		expect(visibleNotifications().map((n) => n.text)).toEqual(['c', 'a']);
+		notifications.unreadOnly = true;
+		expect(visibleNotifications().map((n) => n.text)).toEqual(['a']);
+		notifications.filter = 'all';
+		expect(visibleNotifications().map((n) => n.text)).toEqual(['b', 'a']);
+	});
+
+	it('drops the oldest entries past the cap', () => {
+		for (let i = 0; i < MAX_NOTIFICATIONS + 5; i++) notify('skill', `n${i}`);
+		expect(notifications.items).toHaveLength(MAX_NOTIFICATIONS);
+		expect(notifications.items[0].text).toBe(`n${MAX_NOTIFICATIONS + 4}`);
+		clearNotifications();
+		expect(notifications.items).toHaveLength(0);
+	});
+});
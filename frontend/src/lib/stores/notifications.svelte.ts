@@ -0,0 +1,84 @@
+/** Notification center — Svelte 5 rune store.
+ *
+ * Accumulates what the orchestrator reports in passing (sync results, new
+ * messages, action outcomes, skill results, due reminders, errors) so a
+ * toast or chat line that scrolled away can still be found. Entries carry
+ * read/unread state and, where there is one, the panel a click should open.
+ *
+ * History lives in memory only: notifications quote document titles and
+ * message previews, which must not land in plaintext browser storage.
+ */
+
+export type NotificationKind = 'sync' | 'message' | 'action' | 'skill' | 'reminder' | 'error';
+
+/** Where clicking a notification leads. */
+export type NotificationTarget =
+	| { panel: 'document'; docId: string }
+	| { panel: 'inbox' }
+	| { panel: 'devices' }
+	| { panel: 'chat' }
+	| { panel: 'jobs' };
+
+export interface AppNotification {
+	id: number;
+	kind: NotificationKind;
+	text: string;
+	/** ISO timestamp. */
+	at: string;
+	read: boolean;
+	target?: NotificationTarget;
+}
+
+/** Oldest entries are dropped past this many. */
+export const MAX_NOTIFICATIONS = 200;
+
+export const notifications = $state({
+	open: false,
+	items: [] as AppNotification[],
+	filter: 'all' as NotificationKind | 'all',
+	unreadOnly: false
+});
+
+let nextId = 1;
+
+/** Record a notification, newest first. */
+export function notify(kind: NotificationKind, text: string, target?: NotificationTarget) {
+	const entry: AppNotification = {
+		id: nextId++,
+		kind,
+		text,
+		at: new Date().toISOString(),
+		read: false,
+		target
+	};
+	notifications.items = [entry, ...notifications.items].slice(0, MAX_NOTIFICATIONS);
+}
+
+export function unreadNotificationCount(): number {
+	return notifications.items.filter((n) => !n.read).length;
+}
+
+/** The entries the current filter lets through. */
+export function visibleNotifications(): AppNotification[] {
+	return notifications.items.filter(
+		(n) =>
+			(notifications.filter === 'all' || n.kind === notifications.filter) &&
+			(!notifications.unreadOnly || !n.read)
+	);
+}
+
+export function markNotificationRead(id: number) {
+	notifications.items = notifications.items.map((n) => (n.id === id ? { ...n, read: true } : n));
+}
+
+export function markAllNotificationsRead() {
+	notifications.items = notifications.items.map((n) => (n.read ? n : { ...n, read: true }));
+}
+
+export function clearNotifications() {
+	notifications.items = [];
+}
+
+export function toggleNotificationsPanel() {
+	notifications.open = !notifications.open;
+}
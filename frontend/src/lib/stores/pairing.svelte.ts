@@ -13,12 +13,21 @@
  *   - attemptsFailed: count of failed attempts against the CURRENT
  *     offer (reset on regenerate/clear), for "2 of 3 attempts used"
  *     style feedback.
+ *   - wipeRequests: remote-wipe orders waiting for this device's
+ *     approval as guardian (`wipe-cosign-requested`).
+ *   - keyShares: per paired device, where a key share stands —
+ *     `sent` until the `keys-shared` event says it was accepted or not.
+ *   - lastKeysImported: the most recent key share this device received
+ *     (`keys-imported`), for the new-device side.
  */
 
 export const pairing = $state({
 	lastPaired: null as { peerId: string; deviceName: string; at: string } | null,
 	lastFailure: null as { reason: string; offerDead: boolean } | null,
-	attemptsFailed: 0
+	attemptsFailed: 0,
+	wipeRequests: [] as { orderId: string; targetPeerId: string; issuerPeerId: string }[],
+	keyShares: {} as Record<string, 'sent' | 'accepted' | 'refused'>,
+	lastKeysImported: null as { peerId: string; imported: number; at: string } | null
 });
 
 export function onDevicePaired(peerId: string, deviceName: string) {
@@ -32,6 +41,31 @@ export function onPairingFailed(reason: string, offerDead: boolean) {
 	pairing.attemptsFailed += 1;
 }
 
+export function onWipeCosignRequested(
+	orderId: string,
+	targetPeerId: string,
+	issuerPeerId: string
+) {
+	if (pairing.wipeRequests.some((r) => r.orderId === orderId)) return;
+	pairing.wipeRequests.push({ orderId, targetPeerId, issuerPeerId });
+}
+
+export function dismissWipeRequest(orderId: string) {
+	pairing.wipeRequests = pairing.wipeRequests.filter((r) => r.orderId !== orderId);
+}
+
+export function onKeysSent(peerId: string) {
+	pairing.keyShares[peerId] = 'sent';
+}
+
+export function onKeysShared(peerId: string, accepted: boolean) {
+	pairing.keyShares[peerId] = accepted ? 'accepted' : 'refused';
+}
+
+export function onKeysImported(peerId: string, imported: number) {
+	pairing.lastKeysImported = { peerId, imported, at: new Date().toISOString() };
+}
+
 /** Reset transient status — called when the pairing panel (re)arms a
  *  fresh offer or closes. */
 export function clearPairingStatus() {
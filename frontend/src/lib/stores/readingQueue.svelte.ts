@@ -0,0 +1,50 @@
+/** Reading queue panel state — Svelte 5 rune store.
+ *
+ * Documents flagged "read later", oldest first. The flag clears itself
+ * once a queued document is opened and scrolled to the end (see
+ * DocumentPanel). */
+import { listReadingQueue, setReadLater, type ReadingQueueEntry } from '$lib/api/commands';
+
+export const readingQueue = $state({
+	open: false,
+	entries: [] as ReadingQueueEntry[],
+	error: ''
+});
+
+/** Total estimated reading time of the queue, in minutes. */
+export function queueMinutes(): number {
+	return readingQueue.entries.reduce((sum, e) => sum + e.reading_minutes, 0);
+}
+
+export async function refreshReadingQueue() {
+	try {
+		readingQueue.entries = await listReadingQueue();
+		readingQueue.error = '';
+	} catch (e) {
+		readingQueue.error = String(e);
+	}
+}
+
+/** Add a document to the queue or take it out. */
+export async function markReadLater(id: string, queued: boolean) {
+	try {
+		await setReadLater(id, queued);
+	} catch (e) {
+		readingQueue.error = String(e);
+	}
+	if (readingQueue.open) await refreshReadingQueue();
+}
+
+export function openReadingQueue() {
+	readingQueue.open = true;
+	refreshReadingQueue();
+}
+
+export function closeReadingQueue() {
+	readingQueue.open = false;
+}
+
+export function toggleReadingQueue() {
+	if (readingQueue.open) closeReadingQueue();
+	else openReadingQueue();
+}
@@ -0,0 +1,44 @@
+/** Document reminders — Svelte 5 rune store.
+ *
+ * Reminders are set from a document's RemindersList or from chat ("remind
+ * me about Budget Overview Friday 9am") and delivered by the backend as
+ * `reminder-due` notifications. Every open RemindersList watches
+ * `reminderChanges.count` and reloads its own document's reminders when a
+ * reminder is set, fires, or is deleted anywhere. */
+import { createReminder, deleteReminder } from '$lib/api/commands';
+
+export const reminderChanges = $state({ count: 0 });
+
+/** Called for `reminder-set` / `reminder-due` events. */
+export function noteReminderChange() {
+	reminderChanges.count++;
+}
+
+/** Set a reminder; `due` is a `datetime-local` value in the user's time zone.
+ *  Resolves to an error message, or '' on success. */
+export async function addReminder(
+	docId: string,
+	text: string,
+	due: string,
+	taskIndex?: number
+): Promise<string> {
+	const at = new Date(due);
+	if (!due || Number.isNaN(at.getTime())) return 'Pick a date and time';
+	try {
+		await createReminder(docId, text.trim(), at.toISOString(), taskIndex);
+	} catch (e) {
+		return String(e);
+	}
+	noteReminderChange();
+	return '';
+}
+
+export async function removeReminder(id: string): Promise<string> {
+	try {
+		await deleteReminder(id);
+	} catch (e) {
+		return String(e);
+	}
+	noteReminderChange();
+	return '';
+}
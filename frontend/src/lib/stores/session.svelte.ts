@@ -0,0 +1,58 @@
+/** Session locking — Ctrl+L, the idle timer, and the backend's
+ * `session-locked` event (also sent for the "lock" intent).
+ *
+ * The backend drops its keys on `lock_session`; the UI then reloads, which
+ * is the one way to be sure no store still holds decrypted content. The
+ * fresh page finds the session locked and shows the login screen. */
+import { lockSession } from '$lib/api/commands';
+
+const ACTIVITY_EVENTS = ['pointerdown', 'pointermove', 'keydown', 'wheel', 'touchstart'] as const;
+
+let idleTimer: ReturnType<typeof setTimeout> | null = null;
+let idleMs = 0;
+let locking = false;
+
+/** Lock now. Safe to call repeatedly. */
+export async function lockNow() {
+	if (locking) return;
+	locking = true;
+	stopIdleLock();
+	try {
+		await lockSession();
+	} catch (e) {
+		console.warn('lock_session failed:', e);
+	}
+	onSessionLocked();
+}
+
+/** The backend locked the session: discard every piece of UI state. */
+export function onSessionLocked() {
+	stopIdleLock();
+	window.location.reload();
+}
+
+function resetIdleTimer() {
+	if (idleTimer !== null) clearTimeout(idleTimer);
+	idleTimer = setTimeout(lockNow, idleMs);
+}
+
+/** Lock after `minutes` without input; 0 disables. */
+export function startIdleLock(minutes: number) {
+	stopIdleLock();
+	if (minutes <= 0) return;
+	idleMs = minutes * 60_000;
+	for (const ev of ACTIVITY_EVENTS) {
+		window.addEventListener(ev, resetIdleTimer, { passive: true });
+	}
+	resetIdleTimer();
+}
+
+export function stopIdleLock() {
+	if (idleTimer !== null) {
+		clearTimeout(idleTimer);
+		idleTimer = null;
+	}
+	for (const ev of ACTIVITY_EVENTS) {
+		window.removeEventListener(ev, resetIdleTimer);
+	}
+}
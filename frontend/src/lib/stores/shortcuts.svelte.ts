@@ -0,0 +1,35 @@
+/** Keyboard shortcut state — Svelte 5 rune store.
+ *
+ * Holds the resolved keymap (defaults plus the user's `ui.shortcuts`
+ * overrides) that the global, page and document key handlers match
+ * against, and whether the `?` cheat sheet is open.
+ */
+import {
+	resolveKeymap,
+	matchShortcut,
+	displayCombo,
+	type ShortcutDef,
+	type ShortcutScope
+} from '$lib/utils/shortcuts';
+
+export const shortcuts = $state({
+	keymap: resolveKeymap(),
+	cheatSheetOpen: false
+});
+
+/** Apply saved overrides, from `get_config` or a config reload. */
+export function applyShortcuts(overrides: Record<string, string> | undefined) {
+	shortcuts.keymap = resolveKeymap(overrides ?? {});
+}
+
+/** The `scope` action `e` triggers under the current keymap. */
+export function shortcutFor(e: KeyboardEvent, scope: ShortcutScope): ShortcutDef | null {
+	return matchShortcut(shortcuts.keymap, e, scope);
+}
+
+const isMac = typeof navigator !== 'undefined' && /Mac/.test(navigator.platform);
+
+/** How the current binding of `id` reads on this platform. */
+export function shortcutLabel(id: string): string {
+	return displayCombo(shortcuts.keymap[id] ?? '', isMac);
+}
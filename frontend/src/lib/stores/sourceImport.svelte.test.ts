@@ -0,0 +1,65 @@
+import { beforeEach, describe, expect, it } from 'vitest';
+import { mockTauriCommand } from '$lib/test/tauri';
+import { sourceImport, setImportSource, preview, run, onImportProgress } from './sourceImport.svelte';
+
+beforeEach(() => {
+	setImportSource('markdown', '', 'per_folder');
+	sourceImport.running = false;
+	sourceImport.progress = null;
+	sourceImport.report = null;
+});
+
+describe('source import', () => {
+	it('previews with the chosen source and mapping', async () => {
+		let args: Record<string, unknown> = {};
+		mockTauriCommand('preview_source_import', (a) => {
+			args = a as Record<string, unknown>;
+			return { source_name: 'Vault', total: 3, threads: [{ name: 'Projects', documents: 3 }] };
+		});
+		setImportSource('notion', ' /home/me/Documents/Export ', 'single');
+		await preview();
+
+		expect(args).toEqual({ kind: 'notion', path: '/home/me/Documents/Export', mapping: 'single' });
+		expect(sourceImport.preview?.total).toBe(3);
+	});
+
+	it('drops the preview when the source changes', async () => {
+		mockTauriCommand('preview_source_import', () => ({ source_name: 'Vault', total: 1, threads: [] }));
+		setImportSource('markdown', '/vault', 'per_folder');
+		await preview();
+		expect(sourceImport.preview).not.toBeNull();
+
+		setImportSource('markdown', '/vault', 'single');
+		expect(sourceImport.preview).toBeNull();
+	});
+
+	it('reports preview errors', async () => {
+		mockTauriCommand('preview_source_import', () => {
+			throw '/tmp is not a maildir (no cur/ or new/ folder)';
+		});
+		setImportSource('maildir', '/tmp', 'per_folder');
+		await preview();
+		expect(sourceImport.preview).toBeNull();
+		expect(sourceImport.error).toContain('not a maildir');
+	});
+
+	it('runs the import and tracks progress while it lasts', async () => {
+		mockTauriCommand('preview_source_import', () => ({ source_name: 'Vault', total: 2, threads: [] }));
+		mockTauriCommand('import_source', () => {
+			onImportProgress({ done: 1, total: 2, current: '/vault/a.md' });
+			expect(sourceImport.progress?.done).toBe(1);
+			return { threads_created: 1, documents: 2, skipped: [] };
+		});
+		setImportSource('markdown', '/vault', 'per_folder');
+		await preview();
+		await run();
+
+		expect(sourceImport.report?.documents).toBe(2);
+		expect(sourceImport.running).toBe(false);
+		expect(sourceImport.progress).toBeNull();
+
+		// Late events after the import finished are ignored.
+		onImportProgress({ done: 2, total: 2, current: '' });
+		expect(sourceImport.progress).toBeNull();
+	});
+});
@@ -0,0 +1,73 @@
+/** Import from other tools (onboarding's import step) — Svelte 5 rune store.
+ *
+ * The user picks a source kind, a folder and a thread mapping; `preview`
+ * shows what would be created, `run` imports it. Progress arrives as
+ * `source-import-progress` events (wired in `api/events.ts`).
+ */
+import {
+	previewSourceImport,
+	importSource,
+	type SourceKind,
+	type ThreadMapping,
+	type SourceImportPreview,
+	type SourceImportProgress,
+	type SourceImportReport
+} from '$lib/api/commands';
+
+export const sourceImport = $state({
+	kind: 'markdown' as SourceKind,
+	path: '',
+	mapping: 'per_folder' as ThreadMapping,
+	preview: null as SourceImportPreview | null,
+	previewing: false,
+	running: false,
+	progress: null as SourceImportProgress | null,
+	report: null as SourceImportReport | null,
+	error: ''
+});
+
+export function onImportProgress(progress: SourceImportProgress) {
+	if (sourceImport.running) sourceImport.progress = progress;
+}
+
+/** Changing the source or mapping invalidates the preview. */
+export function setImportSource(kind: SourceKind, path: string, mapping: ThreadMapping) {
+	sourceImport.kind = kind;
+	sourceImport.path = path;
+	sourceImport.mapping = mapping;
+	sourceImport.preview = null;
+	sourceImport.error = '';
+}
+
+export async function preview() {
+	const path = sourceImport.path.trim();
+	if (!path || sourceImport.previewing) return;
+	sourceImport.previewing = true;
+	sourceImport.error = '';
+	try {
+		sourceImport.preview = await previewSourceImport(sourceImport.kind, path, sourceImport.mapping);
+	} catch (e) {
+		sourceImport.preview = null;
+		sourceImport.error = String(e);
+	}
+	sourceImport.previewing = false;
+}
+
+export async function run() {
+	if (!sourceImport.preview || sourceImport.running) return;
+	sourceImport.running = true;
+	sourceImport.progress = { done: 0, total: sourceImport.preview.total, current: '' };
+	sourceImport.report = null;
+	sourceImport.error = '';
+	try {
+		sourceImport.report = await importSource(
+			sourceImport.kind,
+			sourceImport.path.trim(),
+			sourceImport.mapping
+		);
+	} catch (e) {
+		sourceImport.error = String(e);
+	}
+	sourceImport.running = false;
+	sourceImport.progress = null;
+}
@@ -5,24 +5,54 @@
  * minimal status surface for the Taskbar indicator + Settings panel:
  *
  *   - inProgress: peer ids currently in an active sync session.
+ *   - progress: the latest `sync-progress` per running session (diff
+ *     size, commits and bytes moved, ETA), dropped when it ends.
  *   - lastSyncedAt: ISO timestamp of the most recent successful completion.
  *   - lastError: the most recent error message (cleared by `clearError`).
- *   - conflicts: doc ids that surfaced a `sync-conflict` since the
- *     last user acknowledgement.
- *   - discoveredPeers: peer ids surfaced by mDNS but not yet paired.
+ *   - conflicts: doc ids that surfaced a `sync-conflict` or
+ *     `sync-conflict-details` since the last user acknowledgement. The
+ *     latter carry the base/mine/theirs texts and per-region hunks for
+ *     the conflict panel.
+ *   - discoveredPeers: peer ids mDNS currently sees, paired or not (the
+ *     status bar's connection count). Dropped again on `disconnected`.
+ *   - previews: per device, a requested `sync-preview` — 'pending' until
+ *     it arrives, null if the device couldn't be asked.
+ *   - viewers: per device, a requested `viewer-snapshot`, same states.
  *
  * The "paired devices" list is a separate read-only fetch via
  * `listPairedDevices()` since it lives on disk; it isn't event-driven.
  */
 
+import type { ViewerSnapshot } from '$lib/api/commands';
+import type { ConflictHunk } from '$lib/utils/conflictMerge';
+import type { SyncPreview, SyncProgress } from '$lib/utils/syncProgress';
+
 export type SyncStatus = 'idle' | 'syncing' | 'error';
 
+/** Body texts of a same-line edit the CRDT couldn't merge cleanly. */
+export interface SyncMerge {
+	base: string | null;
+	local: string;
+	remote: string;
+	merged: string;
+	hunks: ConflictHunk[];
+}
+
+export interface SyncConflict {
+	docId: string;
+	description: string;
+	merge?: SyncMerge;
+}
+
 export const sync = $state({
 	inProgress: new Set<string>(),
+	progress: {} as Record<string, SyncProgress>,
 	lastSyncedAt: null as string | null,
 	lastError: null as string | null,
-	conflicts: [] as { docId: string; description: string }[],
-	discoveredPeers: new Set<string>()
+	conflicts: [] as SyncConflict[],
+	discoveredPeers: new Set<string>(),
+	previews: {} as Record<string, SyncPreview | null | 'pending'>,
+	viewers: {} as Record<string, ViewerSnapshot | null | 'pending'>
 });
 
 /** Compute a single high-level status for the Taskbar icon. */
@@ -38,9 +68,25 @@ export function onSyncStarted(peerId: string) {
 	sync.inProgress = new Set(sync.inProgress);
 }
 
+export function onSyncProgress(peerId: string, progress: SyncProgress) {
+	// A late tick after completion must not resurrect the session.
+	if (sync.inProgress.has(peerId)) {
+		sync.progress = { ...sync.progress, [peerId]: progress };
+	}
+}
+
+function dropProgress(peerId: string) {
+	if (peerId in sync.progress) {
+		const rest = { ...sync.progress };
+		delete rest[peerId];
+		sync.progress = rest;
+	}
+}
+
 export function onSyncCompleted(peerId: string) {
 	sync.inProgress.delete(peerId);
 	sync.inProgress = new Set(sync.inProgress);
+	dropProgress(peerId);
 	sync.lastSyncedAt = new Date().toISOString();
 	sync.lastError = null;
 }
@@ -50,6 +96,10 @@ export function onSyncDisconnected(peerId: string) {
 	// peer left mDNS. Drop in-progress state if it was tracked.
 	sync.inProgress.delete(peerId);
 	sync.inProgress = new Set(sync.inProgress);
+	dropProgress(peerId);
+	if (sync.discoveredPeers.delete(peerId)) {
+		sync.discoveredPeers = new Set(sync.discoveredPeers);
+	}
 }
 
 export function onSyncError(message: string) {
@@ -62,10 +112,48 @@ export function onSyncConflict(docId: string, description: string) {
 	}
 }
 
+export function onSyncConflictDetails(docId: string, merge: SyncMerge) {
+	const entry = {
+		docId,
+		description: 'Both devices edited the same lines',
+		merge
+	};
+	// A newer merge for the same doc replaces the pending review.
+	sync.conflicts = [...sync.conflicts.filter((c) => c.docId !== docId), entry];
+}
+
 export function dismissConflict(docId: string) {
 	sync.conflicts = sync.conflicts.filter((c) => c.docId !== docId);
 }
 
+export function onPreviewRequested(peerId: string) {
+	sync.previews = { ...sync.previews, [peerId]: 'pending' };
+}
+
+export function onSyncPreview(peerId: string, preview: SyncPreview | null) {
+	sync.previews = { ...sync.previews, [peerId]: preview };
+}
+
+export function dismissPreview(peerId: string) {
+	const rest = { ...sync.previews };
+	delete rest[peerId];
+	sync.previews = rest;
+}
+
+export function onViewerRequested(peerId: string) {
+	sync.viewers = { ...sync.viewers, [peerId]: 'pending' };
+}
+
+export function onViewerSnapshot(peerId: string, snapshot: ViewerSnapshot | null) {
+	sync.viewers = { ...sync.viewers, [peerId]: snapshot };
+}
+
+export function closeViewer(peerId: string) {
+	const rest = { ...sync.viewers };
+	delete rest[peerId];
+	sync.viewers = rest;
+}
+
 export function clearError() {
 	sync.lastError = null;
 }
@@ -1,16 +1,25 @@
 /** Rune-based reactive state for theming. */
 
-import { themes, type ThemeName } from '$lib/theme/colors';
+import { highContrast, themes, type ThemeName } from '$lib/theme/colors';
 
 /** Reactive theme state. */
 export const theme = $state({ current: 'dark' as ThemeName });
 
-/** Apply the theme's CSS variables to :root. */
+const contrastQuery =
+	typeof window !== 'undefined' && window.matchMedia
+		? window.matchMedia('(prefers-contrast: more)')
+		: null;
+
+/** Apply the theme's CSS variables to :root, with the high-contrast
+ *  overrides when the system asks for them. */
 export function applyTheme(name: ThemeName) {
-	const vars = themes[name];
+	const vars = contrastQuery?.matches ? { ...themes[name], ...highContrast[name] } : themes[name];
 	const root = document.documentElement;
 	for (const [prop, value] of Object.entries(vars)) {
 		root.style.setProperty(prop, value);
 	}
 	theme.current = name;
 }
+
+// Follow the system setting while the app is open.
+contrastQuery?.addEventListener('change', () => applyTheme(theme.current));
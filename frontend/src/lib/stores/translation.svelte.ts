@@ -0,0 +1,46 @@
+/** Translation preferences — Svelte 5 rune store.
+ *
+ * The target language used by the Translation skill and the inbox's inline
+ * "Translate" action. Translation runs on the local model; only the
+ * preference lives here (persisted in localStorage). */
+
+const LANGUAGE_KEY = 'sovereign.translation.language';
+
+function loadLanguage(): string {
+	try {
+		if (typeof localStorage !== 'undefined') {
+			const v = localStorage.getItem(LANGUAGE_KEY);
+			if (v && v.trim()) return v;
+		}
+	} catch {
+		/* ignore */
+	}
+	return 'English';
+}
+
+export const translation = $state({
+	/** Target language name, e.g. "French" (persisted). */
+	language: loadLanguage()
+});
+
+/** Set the target language and persist it. */
+export function setTranslationLanguage(language: string) {
+	const trimmed = language.trim();
+	if (!trimmed) return;
+	translation.language = trimmed;
+	try {
+		if (typeof localStorage !== 'undefined') {
+			localStorage.setItem(LANGUAGE_KEY, trimmed);
+		}
+	} catch {
+		/* ignore */
+	}
+}
+
+/** `params` for a skill invocation: the Translation skill gets the target
+ * language, every other skill the given default. */
+export function skillParams(skillName: string, fallback: string): string {
+	return skillName === 'translation'
+		? JSON.stringify({ language: translation.language })
+		: fallback;
+}
@@ -0,0 +1,37 @@
+/** Rune-based reactive state for UI scale and editor font.
+ *
+ * The scale is the webview zoom, so panels, taskbar, chat and the canvas
+ * all grow together and the canvas redraws at the new pixel ratio. The
+ * editor font goes into CSS variables read by the document editor.
+ */
+
+import { getCurrentWebview } from '@tauri-apps/api/webview';
+
+export interface UiPrefs {
+	scale: number;
+	editorFontSize: number;
+	editorFontFamily: string;
+}
+
+export const DEFAULT_UI_PREFS: UiPrefs = {
+	scale: 1,
+	editorFontSize: 14,
+	editorFontFamily: "'SF Mono', 'Fira Code', monospace"
+};
+
+/** What is on screen right now — the saved prefs, or a Settings preview. */
+export const uiPrefs = $state<UiPrefs>({ ...DEFAULT_UI_PREFS });
+
+/** Apply `prefs` to this window. Settings calls it on every change for
+ *  a live preview, and again with the saved values to undo one. */
+export function applyUiPrefs(prefs: UiPrefs) {
+	const root = document.documentElement;
+	root.style.setProperty('--editor-font-size', `${prefs.editorFontSize}px`);
+	root.style.setProperty('--editor-font-family', prefs.editorFontFamily);
+	if (prefs.scale !== uiPrefs.scale) {
+		getCurrentWebview()
+			.setZoom(prefs.scale)
+			.catch((e) => console.warn('Failed to set UI scale:', e));
+	}
+	Object.assign(uiPrefs, prefs);
+}
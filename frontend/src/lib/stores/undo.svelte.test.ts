@@ -0,0 +1,119 @@
+import { beforeEach, describe, expect, it } from 'vitest';
+import {
+	undoState,
+	recordUndo,
+	undo,
+	redo,
+	undoMany,
+	canUndo,
+	canRedo,
+	clearUndo,
+	COALESCE_MS,
+	MAX_UNDO
+} from './undo.svelte';
+import { notifications } from './notifications.svelte';
+
+beforeEach(() => {
+	clearUndo();
+	undoState.busy = false;
+	notifications.items = [];
+});
+
+/** A counter whose every change is recorded. */
+function counter() {
+	const c = { value: 0 };
+	const set = (v: number, label = `set ${v}`, key?: string, now?: number) => {
+		const before = c.value;
+		c.value = v;
+		recordUndo(
+			{ label, key, undo: () => void (c.value = before), redo: () => void (c.value = v) },
+			now
+		);
+	};
+	return { c, set };
+}
+
+describe('undo store', () => {
+	it('undoes and redoes in order', async () => {
+		const { c, set } = counter();
+		set(1);
+		set(2);
+		expect(canUndo()).toBe(true);
+		expect(canRedo()).toBe(false);
+
+		expect((await undo())?.label).toBe('set 2');
+		expect(c.value).toBe(1);
+		await undo();
+		expect(c.value).toBe(0);
+		expect(await undo()).toBeNull();
+
+		await redo();
+		expect(c.value).toBe(1);
+		await redo();
+		expect(c.value).toBe(2);
+		expect(canRedo()).toBe(false);
+	});
+
+	it('drops the redo stack on a new change', async () => {
+		const { c, set } = counter();
+		set(1);
+		await undo();
+		set(5);
+		expect(canRedo()).toBe(false);
+		expect(await redo()).toBeNull();
+		expect(c.value).toBe(5);
+	});
+
+	it('coalesces a burst with the same key into one entry', async () => {
+		const { c, set } = counter();
+		set(1, 'edit', 'doc:a', 1000);
+		set(2, 'edit', 'doc:a', 1000 + COALESCE_MS / 2);
+		set(3, 'edit', 'doc:a', 1000 + COALESCE_MS);
+		// Gap after the last change, not the first.
+		set(4, 'edit', 'doc:a', 1000 + 3 * COALESCE_MS);
+		expect(undoState.past).toHaveLength(2);
+
+		await undo();
+		expect(c.value).toBe(3);
+		await undo();
+		expect(c.value).toBe(0);
+		await redo();
+		expect(c.value).toBe(3);
+	});
+
+	it('does not coalesce across keys', () => {
+		const { set } = counter();
+		set(1, 'a', 'doc:a', 1000);
+		set(2, 'b', 'doc:b', 1001);
+		expect(undoState.past.map((e) => e.label)).toEqual(['a', 'b']);
+	});
+
+	it('caps the history', () => {
+		const { set } = counter();
+		for (let i = 1; i <= MAX_UNDO + 5; i++) set(i);
+		expect(undoState.past).toHaveLength(MAX_UNDO);
+		expect(undoState.past[0].label).toBe('set 6');
+	});
+
+	it('drops a failing entry and reports it', async () => {
+		recordUndo({
+			label: 'Move card',
+			undo: () => Promise.reject('offline'),
+			redo: () => {}
+		});
+		expect(await undo()).toBeNull();
+		expect(canUndo()).toBe(false);
+		expect(canRedo()).toBe(false);
+		expect(notifications.items[0].text).toBe('Could not undo Move card: offline');
+	});
+
+	it('undoes several entries at once', async () => {
+		const { c, set } = counter();
+		set(1);
+		set(2);
+		set(3);
+		await undoMany(2);
+		expect(c.value).toBe(1);
+		expect(undoState.future.map((e) => e.label)).toEqual(['set 3', 'set 2']);
+	});
+});
@@ -0,0 +1,126 @@
+/** Application-level undo/redo — Svelte 5 rune store.
+ *
+ * Stores record an entry for each user operation they can reverse
+ * (document edits, tag changes, card moves, pins) as a pair of closures. Ctrl+Z / Ctrl+Shift+Z
+ * outside a text field walk the stack; inside one, the field's own undo
+ * runs instead. Consecutive edits with the same `key` inside
+ * `COALESCE_MS` fold into one entry so a burst of typing undoes as a unit.
+ *
+ * The stack lives in memory only: entries hold document text, and a lock
+ * reloads the page anyway.
+ */
+
+import { notify } from './notifications.svelte';
+
+export interface UndoEntry {
+	/** What undoing reverts, e.g. `Move “Notes” to Work`. */
+	label: string;
+	undo: () => void | Promise<void>;
+	redo: () => void | Promise<void>;
+	/** Entries with the same key recorded close together coalesce. */
+	key?: string;
+	/** Epoch ms of the latest change folded into this entry. */
+	at: number;
+}
+
+/** Oldest entries are dropped past this many. */
+export const MAX_UNDO = 100;
+export const COALESCE_MS = 1500;
+
+export const undoState = $state({
+	past: [] as UndoEntry[],
+	future: [] as UndoEntry[],
+	/** An undo or redo is running; further presses wait for it. */
+	busy: false,
+	/** History popover shown. */
+	open: false,
+	/** Brief "Undid …" / "Redid …" note after an undo or redo. */
+	flash: null as string | null
+});
+
+let flashTimer: ReturnType<typeof setTimeout> | null = null;
+
+function announce(text: string) {
+	undoState.flash = text;
+	if (flashTimer !== null) clearTimeout(flashTimer);
+	flashTimer = setTimeout(() => {
+		undoState.flash = null;
+		flashTimer = null;
+	}, 2500);
+}
+
+/** Record a reversible operation that has just been applied. */
+export function recordUndo(entry: Omit<UndoEntry, 'at'>, now = Date.now()) {
+	undoState.future = [];
+	const last = undoState.past[undoState.past.length - 1];
+	if (entry.key && last?.key === entry.key && now - last.at < COALESCE_MS) {
+		// Keep the oldest `undo` so the whole burst reverts at once.
+		undoState.past[undoState.past.length - 1] = { ...last, label: entry.label, redo: entry.redo, at: now };
+		return;
+	}
+	undoState.past = [...undoState.past, { ...entry, at: now }].slice(-MAX_UNDO);
+}
+
+export function canUndo(): boolean {
+	return undoState.past.length > 0 && !undoState.busy;
+}
+
+export function canRedo(): boolean {
+	return undoState.future.length > 0 && !undoState.busy;
+}
+
+/** Revert the latest entry. Returns it, or null when there was nothing
+ *  to undo. A failed undo drops the entry rather than retrying it. */
+export async function undo(): Promise<UndoEntry | null> {
+	if (!canUndo()) return null;
+	const entry = undoState.past[undoState.past.length - 1];
+	undoState.past = undoState.past.slice(0, -1);
+	undoState.busy = true;
+	try {
+		await entry.undo();
+		// A later edit must not coalesce into a reverted entry.
+		undoState.future = [...undoState.future, { ...entry, key: undefined }];
+		announce(`Undid ${entry.label}`);
+		return entry;
+	} catch (e) {
+		notify('error', `Could not undo ${entry.label}: ${e}`);
+		return null;
+	} finally {
+		undoState.busy = false;
+	}
+}
+
+/** Re-apply the latest undone entry. */
+export async function redo(): Promise<UndoEntry | null> {
+	if (!canRedo()) return null;
+	const entry = undoState.future[undoState.future.length - 1];
+	undoState.future = undoState.future.slice(0, -1);
+	undoState.busy = true;
+	try {
+		await entry.redo();
+		undoState.past = [...undoState.past, { ...entry, at: 0 }];
+		announce(`Redid ${entry.label}`);
+		return entry;
+	} catch (e) {
+		notify('error', `Could not redo ${entry.label}: ${e}`);
+		return null;
+	} finally {
+		undoState.busy = false;
+	}
+}
+
+/** Undo entries newest-first until `count` have been reverted. */
+export async function undoMany(count: number) {
+	for (let i = 0; i < count; i++) {
+		if (!(await undo())) break;
+	}
+}
+
+export function clearUndo() {
+	undoState.past = [];
+	undoState.future = [];
+}
+
+export function toggleUndoPopover() {
+	undoState.open = !undoState.open;
+}
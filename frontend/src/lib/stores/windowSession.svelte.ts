@@ -0,0 +1,84 @@
+/** Window session — reopen the document panels, contact panel, inbox and
+ * canvas camera from the last run, and keep the saved copy current while
+ * the app is in use (debounced, flushed on page hide) so quitting at any
+ * moment restores the layout the user last saw. */
+
+import { getWindowSession, saveWindowSession, type WindowSession } from '$lib/api/commands';
+import { app } from './app.svelte';
+import { canvas, restoreCamera } from './canvas.svelte';
+import { panels, openById } from './documents.svelte';
+
+const SAVE_DELAY_MS = 1000;
+
+let saveTimer: ReturnType<typeof setTimeout> | null = null;
+let pending: WindowSession | null = null;
+/** Saving before the restore has run would overwrite the last session. */
+let restored = false;
+
+/** The current layout. Reads reactive state, so calling it inside an
+ *  effect tracks every field that ends up in the session. */
+export function snapshotWindowSession(): WindowSession {
+	const { panX, panY, zoom } = canvas.camera;
+	return {
+		camera: canvas.loaded ? { pan_x: panX, pan_y: panY, zoom } : null,
+		doc_panels: [...panels]
+			.sort((a, b) => a.zIndex - b.zIndex)
+			.map((p) => ({
+				doc_id: p.doc.id,
+				x: p.position.x,
+				y: p.position.y,
+				width: p.size.width,
+				height: p.size.height
+			})),
+		contact_panel: app.contactPanelState
+			? {
+					contact_id: app.contactPanelState.contactId,
+					conversation_id: app.contactPanelState.conversationId ?? null
+				}
+			: null,
+		inbox_open: app.inboxVisible
+	};
+}
+
+/** Apply the saved session. Documents that no longer open (deleted, or
+ *  not on this device) are skipped and drop out on the next save. */
+export async function restoreWindowSession() {
+	try {
+		const s = await getWindowSession();
+		if (s.camera) restoreCamera({ panX: s.camera.pan_x, panY: s.camera.pan_y, zoom: s.camera.zoom });
+		for (const p of s.doc_panels) {
+			await openById(p.doc_id, {
+				position: { x: p.x, y: p.y },
+				size: { width: p.width, height: p.height }
+			});
+		}
+		if (s.contact_panel) {
+			app.contactPanelState = {
+				contactId: s.contact_panel.contact_id,
+				conversationId: s.contact_panel.conversation_id ?? undefined
+			};
+		}
+		app.inboxVisible = s.inbox_open;
+	} catch (e) {
+		console.warn('Failed to restore window session:', e);
+	}
+	restored = true;
+}
+
+/** Save `session` after a short quiet period. */
+export function scheduleWindowSessionSave(session: WindowSession) {
+	if (!restored) return;
+	pending = session;
+	if (saveTimer) clearTimeout(saveTimer);
+	saveTimer = setTimeout(flushWindowSessionSave, SAVE_DELAY_MS);
+}
+
+/** Write any pending save now. */
+export function flushWindowSessionSave() {
+	if (saveTimer) clearTimeout(saveTimer);
+	saveTimer = null;
+	if (!pending) return;
+	const session = pending;
+	pending = null;
+	saveWindowSession(session).catch((e) => console.warn('Failed to save window session:', e));
+}
@@ -36,7 +36,12 @@ export const themes = {
 		'--reliability-medium': '#F59E0B',
 		'--reliability-medium-bg': '#78350f',
 		'--reliability-low': '#EF4444',
-		'--reliability-low-bg': '#7f1d1d'
+		'--reliability-low-bg': '#7f1d1d',
+		'--syntax-keyword': '#c792ea',
+		'--syntax-string': '#a5d6a7',
+		'--syntax-comment': '#7f848e',
+		'--syntax-number': '#f78c6c',
+		'--syntax-type': '#82aaff'
 	},
 	light: {
 		'--bg-primary': '#f5f5f0',
@@ -73,8 +78,33 @@ export const themes = {
 		'--reliability-medium': '#D97706',
 		'--reliability-medium-bg': '#fef3c7',
 		'--reliability-low': '#DC2626',
-		'--reliability-low-bg': '#fee2e2'
+		'--reliability-low-bg': '#fee2e2',
+		'--syntax-keyword': '#7c3aed',
+		'--syntax-string': '#15803d',
+		'--syntax-comment': '#6e6e6e',
+		'--syntax-number': '#c2410c',
+		'--syntax-type': '#1d4ed8'
 	}
 } as const;
 
 export type ThemeName = keyof typeof themes;
+
+/** Overrides applied on top of a theme when the system asks for more
+ *  contrast (`prefers-contrast: more`): full-strength text and borders. */
+export const highContrast: Record<ThemeName, Record<string, string>> = {
+	dark: {
+		'--bg-primary': '#000000',
+		'--bg-panel': '#121216',
+		'--text-primary': '#ffffff',
+		'--text-secondary': '#e6e6e6',
+		'--text-muted': '#cccccc',
+		'--border': '#8a8a99'
+	},
+	light: {
+		'--bg-primary': '#ffffff',
+		'--text-primary': '#000000',
+		'--text-secondary': '#1f1f1f',
+		'--text-muted': '#3d3d3d',
+		'--border': '#5c5c50'
+	}
+};
@@ -0,0 +1,43 @@
+import { describe, expect, it } from 'vitest';
+import { attachmentKind, blobHash, documentAttachments } from './attachments';
+
+const HASH = 'ab'.repeat(32);
+
+describe('attachmentKind', () => {
+	it('maps known extensions case-insensitively', () => {
+		expect(attachmentKind('Report.PDF')).toBe('pdf');
+		expect(attachmentKind('voice.m4a')).toBe('audio');
+		expect(attachmentKind('clip.webm')).toBe('video');
+		expect(attachmentKind('photo.jpeg')).toBe('image');
+	});
+
+	it('falls back to a plain file', () => {
+		expect(attachmentKind('notes.txt')).toBe('file');
+		expect(attachmentKind('pdf')).toBe('file');
+	});
+});
+
+describe('blobHash', () => {
+	it('recognises blob-store paths on either separator', () => {
+		expect(blobHash(`/home/u/.sovereign/crypto/sync_blobs/${HASH}`)).toBe(HASH);
+		expect(blobHash(`C:\\sovereign\\sync_blobs\\${HASH}`)).toBe(HASH);
+		expect(blobHash('/tmp/a.png')).toBeNull();
+	});
+});
+
+describe('documentAttachments', () => {
+	it('lists images, videos then files; only files are removable', () => {
+		const list = documentAttachments({
+			images: [{ path: `/b/${HASH}`, caption: '' }],
+			videos: [{ path: '/v/talk.mp4', caption: 'Talk', duration_secs: null, thumbnail_path: null }],
+			files: [{ path: `/b/${HASH}`, name: 'minutes.pdf' }]
+		});
+		expect(list.map((a) => [a.kind, a.name, a.removable])).toEqual([
+			['image', HASH, false],
+			['video', 'Talk', false],
+			['pdf', 'minutes.pdf', true]
+		]);
+		expect(list[0].hash).toBe(HASH);
+		expect(list[1].hash).toBeNull();
+	});
+});
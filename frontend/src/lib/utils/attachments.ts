@@ -0,0 +1,83 @@
+/** A document's media as one list for the attachment strip and viewer. */
+
+import type { FullDocument } from '$lib/api/commands';
+
+export type AttachmentKind = 'image' | 'video' | 'audio' | 'pdf' | 'file';
+
+export interface Attachment {
+	path: string;
+	name: string;
+	kind: AttachmentKind;
+	/** Blob-store hash when the file lives there, so the `media` scheme
+	 *  can stream it; null for files elsewhere on disk. */
+	hash: string | null;
+	/** Listed in the document's `files` (the only ones that can be removed;
+	 *  images and videos belong to the body). */
+	removable: boolean;
+}
+
+const EXTENSIONS: Record<string, AttachmentKind> = {
+	png: 'image',
+	jpg: 'image',
+	jpeg: 'image',
+	gif: 'image',
+	webp: 'image',
+	mp4: 'video',
+	m4v: 'video',
+	mov: 'video',
+	webm: 'video',
+	mp3: 'audio',
+	m4a: 'audio',
+	ogg: 'audio',
+	oga: 'audio',
+	opus: 'audio',
+	flac: 'audio',
+	wav: 'audio',
+	pdf: 'pdf'
+};
+
+/** Kind from a file name's extension. */
+export function attachmentKind(name: string): AttachmentKind {
+	const ext = name.split('.').pop()?.toLowerCase() ?? '';
+	return name.includes('.') ? (EXTENSIONS[ext] ?? 'file') : 'file';
+}
+
+/** The SHA-256 a blob-store path is named by, if it is one. */
+export function blobHash(path: string): string | null {
+	const name = path.split(/[\\/]/).pop() ?? '';
+	return /^[0-9a-f]{64}$/.test(name) ? name : null;
+}
+
+function baseName(path: string): string {
+	return path.split(/[\\/]/).pop() ?? path;
+}
+
+/** Images, then videos, then attached files. */
+export function documentAttachments(doc: Pick<FullDocument, 'images' | 'videos' | 'files'>): Attachment[] {
+	return [
+		...doc.images.map((i) => ({
+			path: i.path,
+			name: i.caption || baseName(i.path),
+			kind: 'image' as const,
+			hash: blobHash(i.path),
+			removable: false
+		})),
+		...doc.videos.map((v) => ({
+			path: v.path,
+			name: v.caption || baseName(v.path),
+			kind: 'video' as const,
+			hash: blobHash(v.path),
+			removable: false
+		})),
+		...(doc.files ?? []).map((f) => {
+			const name = f.name || baseName(f.path);
+			return {
+				path: f.path,
+				name,
+				kind: attachmentKind(name),
+				hash: blobHash(f.path),
+				removable: true
+			};
+		})
+	];
+}
@@ -0,0 +1,41 @@
+import { describe, it, expect } from 'vitest';
+import { openTaskCount, setTaskChecked, taskItems, tableMarkdown, calloutMarkdown, embedMarkdown } from './blocks';
+
+const DOC = '* [ ] buy tea\n  - [X] boil water\n```\n- [ ] not a task\n```\n+ [ ]';
+
+describe('setTaskChecked', () => {
+	it('flips only the indexed box', () => {
+		expect(setTaskChecked(DOC, 1, false)).toBe(DOC.replace('[X] boil', '[ ] boil'));
+		expect(setTaskChecked(DOC, 0, true)).toBe(DOC.replace('* [ ] buy', '* [x] buy'));
+	});
+
+	it('skips fenced code and counts empty items', () => {
+		expect(setTaskChecked(DOC, 2, true)).toBe(DOC.replace(/\+ \[ \]$/, '+ [x]'));
+	});
+
+	it('counts open items for the canvas badge', () => {
+		expect(openTaskCount(DOC)).toBe(2);
+	});
+
+	it('lists task items with the indexes setTaskChecked takes', () => {
+		expect(taskItems(DOC)).toEqual([
+			{ index: 0, text: 'buy tea', done: false },
+			{ index: 1, text: 'boil water', done: true },
+			{ index: 2, text: '', done: false }
+		]);
+	});
+
+	it('leaves the body alone when nothing changes', () => {
+		expect(setTaskChecked(DOC, 1, true)).toBe(DOC);
+		expect(setTaskChecked(DOC, 3, true)).toBe(DOC);
+	});
+});
+
+describe('block snippets', () => {
+	it('builds tables, callouts and embeds', () => {
+		expect(tableMarkdown(2, 1)).toBe('| Column 1 | Column 2 |\n| --- | --- |\n|   |   |');
+		expect(calloutMarkdown('tip', 'One\n\nTwo')).toBe('> [!TIP]\n> One\n>\n> Two');
+		expect(calloutMarkdown('note')).toBe('> [!NOTE]\n> ');
+		expect(embedMarkdown('document:abc')).toBe('![[document:abc]]');
+	});
+});
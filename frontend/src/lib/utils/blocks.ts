@@ -0,0 +1,99 @@
+/** Structured Markdown blocks — task checklists, tables, callouts and
+ *  document embeds — as the editor inserts them and the preview toggles
+ *  them. The syntax matches `sovereign_sdk::blocks` on the Rust side. */
+
+export type CalloutKind = 'note' | 'tip' | 'important' | 'warning' | 'caution';
+
+export const CALLOUT_KINDS: CalloutKind[] = ['note', 'tip', 'important', 'warning', 'caution'];
+
+const TASK = /^( *)[-*+] \[([ xX])\](?: |$)/;
+const FENCE = /^\s*(```|~~~)/;
+
+/** Tick or untick the `index`th task item of the body, counting task
+ *  lines outside fenced code in order. Only that item's box changes; the
+ *  body comes back unchanged when there is no such item. */
+export function setTaskChecked(body: string, index: number, checked: boolean): string {
+	const lines = body.split('\n');
+	let fence: string | null = null;
+	let seen = 0;
+	for (let i = 0; i < lines.length; i++) {
+		const line = lines[i];
+		if (fence) {
+			if (line.trimStart().startsWith(fence)) fence = null;
+			continue;
+		}
+		const open = FENCE.exec(line);
+		if (open) {
+			fence = open[1];
+			continue;
+		}
+		const task = TASK.exec(line);
+		if (!task || seen++ !== index) continue;
+		if ((task[2] !== ' ') === checked) return body;
+		const box = line.indexOf('[') + 1;
+		lines[i] = line.slice(0, box) + (checked ? 'x' : ' ') + line.slice(box + 1);
+		return lines.join('\n');
+	}
+	return body;
+}
+
+/** How many task items are still unticked. */
+export function openTaskCount(body: string): number {
+	let fence: string | null = null;
+	let open = 0;
+	for (const line of body.split('\n')) {
+		if (fence) {
+			if (line.trimStart().startsWith(fence)) fence = null;
+			continue;
+		}
+		const f = FENCE.exec(line);
+		if (f) fence = f[1];
+		else if (TASK.exec(line)?.[2] === ' ') open++;
+	}
+	return open;
+}
+
+/** The body's task items in order, with the index `setTaskChecked` takes. */
+export function taskItems(body: string): { index: number; text: string; done: boolean }[] {
+	let fence: string | null = null;
+	const items: { index: number; text: string; done: boolean }[] = [];
+	for (const line of body.split('\n')) {
+		if (fence) {
+			if (line.trimStart().startsWith(fence)) fence = null;
+			continue;
+		}
+		const f = FENCE.exec(line);
+		if (f) {
+			fence = f[1];
+			continue;
+		}
+		const task = TASK.exec(line);
+		if (task) {
+			items.push({
+				index: items.length,
+				text: line.slice(task[0].length).trim(),
+				done: task[2] !== ' '
+			});
+		}
+	}
+	return items;
+}
+
+/** An empty table with `columns` columns and `rows` body rows. */
+export function tableMarkdown(columns: number, rows: number): string {
+	const row = (cells: string[]) => `| ${cells.join(' | ')} |`;
+	const header = Array.from({ length: columns }, (_, i) => `Column ${i + 1}`);
+	const lines = [row(header), row(header.map(() => '---'))];
+	for (let r = 0; r < rows; r++) lines.push(row(header.map(() => ' ')));
+	return lines.join('\n');
+}
+
+export function calloutMarkdown(kind: CalloutKind, text = ''): string {
+	const head = `> [!${kind.toUpperCase()}]`;
+	return text ? `${head}\n${text.split('\n').map((l) => (l ? `> ${l}` : '>')).join('\n')}` : `${head}\n> `;
+}
+
+/** A line embedding another document by id (`document:…`). */
+export function embedMarkdown(documentId: string): string {
+	return `![[${documentId}]]`;
+}
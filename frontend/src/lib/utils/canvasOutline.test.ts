@@ -0,0 +1,48 @@
+import { describe, expect, it } from 'vitest';
+import type { CanvasDocDto, ThreadDto } from '$lib/api/commands';
+import { canvasOutline } from './canvasOutline';
+
+function doc(id: string, thread_id: string, modified_at: string): CanvasDocDto {
+	return {
+		id,
+		title: id,
+		thread_id,
+		is_owned: true,
+		doc_type: 'note',
+		spatial_x: 0,
+		spatial_y: 0,
+		created_at: modified_at,
+		modified_at,
+		reliability_classification: null,
+		reliability_score: null,
+		source_url: null,
+		tags: [],
+		open_tasks: 0,
+		favorite: false,
+		sensitivity: 'normal'
+	};
+}
+
+function thread(id: string): ThreadDto {
+	return { id, name: id, description: '', created_at: '2026-01-01T00:00:00Z' };
+}
+
+describe('canvas outline', () => {
+	it('groups by thread in lane order, newest document first', () => {
+		const groups = canvasOutline(
+			[
+				doc('a1', 'a', '2026-03-01T00:00:00Z'),
+				doc('b1', 'b', '2026-03-01T00:00:00Z'),
+				doc('a2', 'a', '2026-04-01T00:00:00Z')
+			],
+			[thread('b'), thread('empty'), thread('a')]
+		);
+		expect(groups.map((g) => g.thread?.id)).toEqual(['b', 'a']);
+		expect(groups[1].docs.map((d) => d.id)).toEqual(['a2', 'a1']);
+	});
+
+	it('lists documents of unknown threads last', () => {
+		const groups = canvasOutline([doc('x', 'gone', '2026-03-01T00:00:00Z')], [thread('a')]);
+		expect(groups).toEqual([{ thread: null, docs: [doc('x', 'gone', '2026-03-01T00:00:00Z')] }]);
+	});
+});
@@ -0,0 +1,36 @@
+/** Text outline of the canvas — threads and their documents, newest
+ *  first — for the list view that stands in for the shader canvas when
+ *  navigating by keyboard or screen reader.
+ */
+
+import type { CanvasDocDto, ThreadDto } from '$lib/api/commands';
+
+export interface OutlineGroup {
+	/** Null for documents whose thread is not in the thread list. */
+	thread: ThreadDto | null;
+	docs: CanvasDocDto[];
+}
+
+/** Group `documents` by thread. Threads keep their canvas (lane) order
+ *  and empty ones are dropped; orphaned documents come last. */
+export function canvasOutline(documents: CanvasDocDto[], threads: ThreadDto[]): OutlineGroup[] {
+	const byThread = new Map<string, CanvasDocDto[]>();
+	for (const doc of documents) {
+		const list = byThread.get(doc.thread_id);
+		if (list) list.push(doc);
+		else byThread.set(doc.thread_id, [doc]);
+	}
+	const newestFirst = (docs: CanvasDocDto[]) =>
+		docs.sort((a, b) => Date.parse(b.modified_at) - Date.parse(a.modified_at));
+
+	const groups: OutlineGroup[] = [];
+	for (const thread of threads) {
+		const docs = byThread.get(thread.id);
+		if (!docs) continue;
+		byThread.delete(thread.id);
+		groups.push({ thread, docs: newestFirst(docs) });
+	}
+	const orphans = [...byThread.values()].flat();
+	if (orphans.length > 0) groups.push({ thread: null, docs: newestFirst(orphans) });
+	return groups;
+}
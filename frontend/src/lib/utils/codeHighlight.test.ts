@@ -0,0 +1,46 @@
+import { describe, expect, it } from 'vitest';
+import { fencedBlocks, highlightCode, highlightMarkdownSource } from './codeHighlight';
+
+describe('highlightCode', () => {
+	it('marks keywords, strings, comments, numbers and types', () => {
+		const html = highlightCode('let s: String = "hi"; // note\nx = 42;', 'rust');
+		expect(html).toBe(
+			'<span class="tok-keyword">let</span> s: <span class="tok-type">String</span> = ' +
+				'<span class="tok-string">&quot;hi&quot;</span>; <span class="tok-comment">// note</span>\n' +
+				'x = <span class="tok-number">42</span>;'
+		);
+	});
+
+	it('escapes markup inside code and strings', () => {
+		const html = highlightCode('const a = "<img onerror=x>" < b;', 'ts')!;
+		expect(html).not.toContain('<img');
+		expect(html).toContain('&lt;img onerror=x&gt;');
+	});
+
+	it('returns null for languages it does not know', () => {
+		expect(highlightCode('whatever', 'cobol')).toBeNull();
+		expect(highlightCode('whatever', undefined)).toBeNull();
+	});
+
+	it('does not treat digits inside identifiers as numbers', () => {
+		expect(highlightCode('x1 = 2', 'py')).toBe('x1 = <span class="tok-number">2</span>');
+	});
+});
+
+describe('fencedBlocks', () => {
+	it('finds the code between fences, including an unclosed last one', () => {
+		const text = 'intro\n```rust\nfn a() {}\n```\nmid\n~~~py\nx = 1';
+		const blocks = fencedBlocks(text);
+		expect(blocks.map((b) => [b.lang, text.slice(b.start, b.end)])).toEqual([
+			['rust', 'fn a() {}'],
+			['py', 'x = 1']
+		]);
+	});
+
+	it('highlights only known-language blocks in the editor source', () => {
+		const html = highlightMarkdownSource('# <T>\n```js\nlet x\n```\n```\nlet y\n```');
+		expect(html).toContain('# &lt;T&gt;');
+		expect(html).toContain('<span class="tok-keyword">let</span> x');
+		expect(html).toContain('\nlet y\n');
+	});
+});
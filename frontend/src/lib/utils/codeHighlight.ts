@@ -0,0 +1,211 @@
+/** Syntax highlighting for fenced code blocks.
+ *
+ * A small single-pass tokenizer rather than a grammar engine: comments,
+ * strings, numbers, keywords and capitalized type names, per language.
+ * It is shared by the Markdown preview and the editor's highlight layer,
+ * so it has to be cheap enough to re-run on every keystroke. Output is
+ * escaped HTML with `tok-*` spans, colored by the `--syntax-*` theme
+ * variables. */
+
+interface LangSpec {
+	keywords: Set<string>;
+	lineComments: string[];
+	blockComment?: [string, string];
+	quotes: string[];
+}
+
+const words = (s: string) => new Set(s.split(/\s+/));
+
+const C_LIKE_COMMENTS = { lineComments: ['//'], blockComment: ['/*', '*/'] as [string, string] };
+
+const LANGS: Record<string, LangSpec> = {
+	rust: {
+		keywords: words(
+			'as async await break const continue crate dyn else enum extern false fn for if impl in let loop match mod move mut pub ref return self Self static struct super trait true type unsafe use where while'
+		),
+		...C_LIKE_COMMENTS,
+		quotes: ['"']
+	},
+	typescript: {
+		keywords: words(
+			'abstract as async await break case catch class const continue default delete do else enum export extends false finally for from function if implements import in instanceof interface let new null of private protected public readonly return static super switch this throw true try type typeof undefined var void while yield'
+		),
+		...C_LIKE_COMMENTS,
+		quotes: ['"', "'", '`']
+	},
+	python: {
+		keywords: words(
+			'and as assert async await break class continue def del elif else except False finally for from global if import in is lambda None nonlocal not or pass raise return True try while with yield'
+		),
+		lineComments: ['#'],
+		quotes: ['"', "'"]
+	},
+	shell: {
+		keywords: words('case do done elif else esac export fi for function if in local return then until while'),
+		lineComments: ['#'],
+		quotes: ['"', "'"]
+	},
+	json: {
+		keywords: words('true false null'),
+		lineComments: [],
+		quotes: ['"']
+	},
+	toml: {
+		keywords: words('true false'),
+		lineComments: ['#'],
+		quotes: ['"', "'"]
+	},
+	sql: {
+		keywords: words(
+			'select from where and or not insert into values update set delete create table drop alter join left right inner outer on as order by group having limit null is in like SELECT FROM WHERE AND OR NOT INSERT INTO VALUES UPDATE SET DELETE CREATE TABLE DROP ALTER JOIN LEFT RIGHT INNER OUTER ON AS ORDER BY GROUP HAVING LIMIT NULL IS IN LIKE'
+		),
+		lineComments: ['--'],
+		blockComment: ['/*', '*/'],
+		quotes: ["'", '"']
+	}
+};
+
+const ALIASES: Record<string, string> = {
+	rs: 'rust',
+	ts: 'typescript',
+	js: 'typescript',
+	javascript: 'typescript',
+	jsx: 'typescript',
+	tsx: 'typescript',
+	py: 'python',
+	sh: 'shell',
+	bash: 'shell',
+	zsh: 'shell',
+	console: 'shell'
+};
+
+function langSpec(lang: string): LangSpec | undefined {
+	const key = lang.trim().toLowerCase();
+	return LANGS[ALIASES[key] ?? key];
+}
+
+/** Whether `lang` (a fence info string's first word) is highlighted. */
+export function isHighlighted(lang: string | undefined): boolean {
+	return !!lang && langSpec(lang) !== undefined;
+}
+
+export function escapeCode(s: string): string {
+	return s.replace(/&/g, '&amp;').replace(/</g, '&lt;').replace(/>/g, '&gt;').replace(/"/g, '&quot;');
+}
+
+function span(kind: string, text: string): string {
+	return `<span class="tok-${kind}">${escapeCode(text)}</span>`;
+}
+
+/** Highlighted HTML for `code`, or `null` for a language we don't know. */
+export function highlightCode(code: string, lang: string | undefined): string | null {
+	const spec = lang ? langSpec(lang) : undefined;
+	if (!spec) return null;
+	let out = '';
+	let plain = '';
+	const flush = () => {
+		out += escapeCode(plain);
+		plain = '';
+	};
+	const rest = (prefix: string, at: number) => code.startsWith(prefix, at);
+	let i = 0;
+	while (i < code.length) {
+		const line = spec.lineComments.find((p) => rest(p, i));
+		if (line) {
+			let end = code.indexOf('\n', i);
+			if (end < 0) end = code.length;
+			flush();
+			out += span('comment', code.slice(i, end));
+			i = end;
+			continue;
+		}
+		if (spec.blockComment && rest(spec.blockComment[0], i)) {
+			const close = code.indexOf(spec.blockComment[1], i + spec.blockComment[0].length);
+			const end = close < 0 ? code.length : close + spec.blockComment[1].length;
+			flush();
+			out += span('comment', code.slice(i, end));
+			i = end;
+			continue;
+		}
+		const ch = code[i];
+		if (spec.quotes.includes(ch)) {
+			let j = i + 1;
+			while (j < code.length && code[j] !== ch) {
+				// Unterminated single-line strings stop at the newline.
+				if (code[j] === '\n' && ch !== '`') break;
+				j += code[j] === '\\' ? 2 : 1;
+			}
+			const end = Math.min(code.length, j + (code[j] === ch ? 1 : 0));
+			flush();
+			out += span('string', code.slice(i, end));
+			i = end;
+			continue;
+		}
+		const num = /^(?:0x[\da-fA-F_]+|\d[\d_]*(?:\.\d+)?(?:[eE][+-]?\d+)?)/.exec(code.slice(i, i + 64));
+		if (num && !/[\w$]/.test(code[i - 1] ?? '')) {
+			flush();
+			out += span('number', num[0]);
+			i += num[0].length;
+			continue;
+		}
+		const word = /^[A-Za-z_$][\w$]*/.exec(code.slice(i, i + 128));
+		if (word) {
+			const w = word[0];
+			if (spec.keywords.has(w)) {
+				flush();
+				out += span('keyword', w);
+			} else if (/^[A-Z][a-z]/.test(w)) {
+				flush();
+				out += span('type', w);
+			} else {
+				plain += w;
+			}
+			i += w.length;
+			continue;
+		}
+		plain += ch;
+		i++;
+	}
+	flush();
+	return out;
+}
+
+/** A fenced block's code (between the fence lines) in a Markdown body. */
+export interface FencedBlock {
+	start: number;
+	end: number;
+	lang: string;
+}
+
+/** Fenced code blocks in `text`, in order. An unclosed fence runs to the end. */
+export function fencedBlocks(text: string): FencedBlock[] {
+	const out: FencedBlock[] = [];
+	const fence = /^(```|~~~)[ \t]*([^\s`]*)[^\n]*$/gm;
+	let open: { marker: string; lang: string; start: number } | null = null;
+	let m: RegExpExecArray | null;
+	while ((m = fence.exec(text))) {
+		if (!open) {
+			const start = Math.min(text.length, m.index + m[0].length + 1);
+			open = { marker: m[1], lang: m[2], start };
+		} else if (m[1] === open.marker && m[2] === '') {
+			out.push({ start: open.start, end: Math.max(open.start, m.index - 1), lang: open.lang });
+			open = null;
+		}
+	}
+	if (open) out.push({ start: open.start, end: text.length, lang: open.lang });
+	return out;
+}
+
+/** Editor highlight layer: the whole body as HTML, with fenced blocks of
+ *  known languages highlighted and everything else escaped. */
+export function highlightMarkdownSource(text: string): string {
+	let out = '';
+	let last = 0;
+	for (const block of fencedBlocks(text)) {
+		const html = highlightCode(text.slice(block.start, block.end), block.lang);
+		if (html === null) continue;
+		out += escapeCode(text.slice(last, block.start)) + html;
+		last = block.end;
+	}
+	return out + escapeCode(text.slice(last));
+}
@@ -0,0 +1,26 @@
+import { describe, expect, it } from 'vitest';
+import { assembleResolution, hunkText, type ConflictHunk } from './conflictMerge';
+
+const hunks: ConflictHunk[] = [
+	{ base: 'a\n', local: 'a\n', remote: 'a\n' },
+	{ base: 'b\n', local: 'B1\n', remote: 'B2\n' },
+	{ base: 'c\n', local: 'c\nd\n', remote: 'c\nd\n' }
+];
+
+describe('conflict resolution', () => {
+	it('settled hunks need no choice', () => {
+		expect(hunkText(hunks[0], undefined)).toBe('a\n');
+	});
+
+	it('stays unresolved until every conflict is picked', () => {
+		expect(assembleResolution(hunks, {})).toBeNull();
+	});
+
+	it('assembles the picked texts in order', () => {
+		expect(assembleResolution(hunks, { 1: { pick: 'remote' } })).toBe('a\nB2\nc\nd\n');
+		expect(assembleResolution(hunks, { 1: { pick: 'both' } })).toBe('a\nB1\nB2\nc\nd\n');
+		expect(assembleResolution(hunks, { 1: { pick: 'custom', text: 'B\n' } })).toBe(
+			'a\nB\nc\nd\n'
+		);
+	});
+});
@@ -0,0 +1,54 @@
+/** Per-region conflict resolution for the sync conflict panel.
+ *
+ * The backend splits a conflicting document body into hunks against the
+ * last synced base: settled hunks have `local === remote`, conflicting
+ * ones need a pick. A resolution is the hunks' chosen texts, concatenated
+ * in order. */
+
+export interface ConflictHunk {
+	base: string;
+	local: string;
+	remote: string;
+}
+
+/** How one conflicting hunk is settled. `both` keeps this device's lines
+ *  first, then the other device's. */
+export type HunkChoice =
+	| { pick: 'local' }
+	| { pick: 'remote' }
+	| { pick: 'both' }
+	| { pick: 'custom'; text: string };
+
+export function isConflict(hunk: ConflictHunk): boolean {
+	return hunk.local !== hunk.remote;
+}
+
+export function hunkText(hunk: ConflictHunk, choice: HunkChoice | undefined): string | null {
+	if (!isConflict(hunk)) return hunk.local;
+	switch (choice?.pick) {
+		case 'local':
+			return hunk.local;
+		case 'remote':
+			return hunk.remote;
+		case 'both':
+			return hunk.local + hunk.remote;
+		case 'custom':
+			return choice.text;
+		default:
+			return null;
+	}
+}
+
+/** The resolved body, or `null` while a conflicting hunk is unsettled. */
+export function assembleResolution(
+	hunks: ConflictHunk[],
+	choices: Record<number, HunkChoice>
+): string | null {
+	let out = '';
+	for (const [i, hunk] of hunks.entries()) {
+		const text = hunkText(hunk, choices[i]);
+		if (text === null) return null;
+		out += text;
+	}
+	return out;
+}
@@ -0,0 +1,29 @@
+import { describe, expect, it } from 'vitest';
+import { linkKey, resolveLink } from './docLinks';
+
+describe('linkKey', () => {
+	it('folds case and separators like the backend', () => {
+		expect(linkKey('Project  Apollo')).toBe('project-apollo');
+		expect(linkKey('project_apollo')).toBe('project-apollo');
+		expect(linkKey(' Notes -- 2026 ')).toBe('notes-2026');
+		expect(linkKey('  ')).toBe('');
+	});
+});
+
+describe('resolveLink', () => {
+	const docs = [
+		{ id: 'document:a', title: 'Budget', modified_at: '2026-03-01T00:00:00Z' },
+		{ id: 'document:b', title: 'budget', modified_at: '2026-03-05T00:00:00Z' },
+		{ id: 'document:c', title: 'Project Apollo', modified_at: '2026-03-02T00:00:00Z' }
+	];
+
+	it('prefers the most recently modified match', () => {
+		expect(resolveLink('BUDGET', docs)?.id).toBe('document:b');
+		expect(resolveLink('project-apollo', docs)?.id).toBe('document:c');
+	});
+
+	it('never resolves to the linking document or a missing title', () => {
+		expect(resolveLink('budget', docs, 'document:b')?.id).toBe('document:a');
+		expect(resolveLink('Nowhere', docs)).toBeUndefined();
+	});
+});
@@ -0,0 +1,32 @@
+/** `[[Title]]` wiki-links and `@mentions` of other documents, as
+ *  `sovereign_sdk::links` reads them. On save the backend turns each one
+ *  that names a document into a `References` edge; the preview renders
+ *  them as links and resolves them here against the loaded documents. */
+
+/** What titles and link targets compare by: lowercase, with each run of
+ *  spaces, `-` and `_` made a single `-`. */
+export function linkKey(title: string): string {
+	return title
+		.trim()
+		.toLowerCase()
+		.split(/[\s_-]+/)
+		.filter(Boolean)
+		.join('-');
+}
+
+/** The document a link names: the most recently modified one with that
+ *  title, never `selfId`. */
+export function resolveLink<T extends { id: string; title: string; modified_at: string }>(
+	target: string,
+	docs: T[],
+	selfId?: string
+): T | undefined {
+	const key = linkKey(target);
+	if (!key) return undefined;
+	let best: T | undefined;
+	for (const doc of docs) {
+		if (doc.id === selfId || linkKey(doc.title) !== key) continue;
+		if (!best || doc.modified_at > best.modified_at) best = doc;
+	}
+	return best;
+}
@@ -0,0 +1,50 @@
+import { describe, expect, it } from 'vitest';
+import { documentStats, formatStats, hashtags, markdownOutline } from './docStats';
+
+describe('documentStats', () => {
+	it('matches the word-count skill', () => {
+		expect(documentStats('hello world foo bar')).toEqual({
+			words: 4,
+			characters: 19,
+			lines: 1,
+			readingMinutes: 1
+		});
+		expect(documentStats('')).toEqual({ words: 0, characters: 0, lines: 0, readingMinutes: 0 });
+		expect(documentStats('line one\nline two\nline three\n').lines).toBe(3);
+	});
+
+	it('counts code points and rounds reading time up', () => {
+		expect(documentStats('héllo 👋').characters).toBe(7);
+		expect(documentStats('w '.repeat(201)).readingMinutes).toBe(2);
+	});
+
+	it('formats a footer line', () => {
+		expect(formatStats(documentStats('one'))).toBe('1 word · 3 characters · 1 min read');
+		expect(formatStats(documentStats(''))).toBe('0 words · 0 characters');
+		expect(formatStats({ words: 1234, characters: 6789, lines: 9, readingMinutes: 7 })).toBe(
+			'1,234 words · 6,789 characters · 7 min read'
+		);
+	});
+});
+
+describe('markdownOutline', () => {
+	it('lists ATX headings with their level and offset', () => {
+		const body = '# Title\n\nIntro\n\n## Part **one** ##\ntext\n### [Link](http://x) and `code`';
+		expect(markdownOutline(body)).toEqual([
+			{ level: 1, text: 'Title', offset: 0 },
+			{ level: 2, text: 'Part one', offset: body.indexOf('## Part') },
+			{ level: 3, text: 'Link and code', offset: body.indexOf('### ') }
+		]);
+	});
+
+	it('skips headings inside fenced code and non-headings', () => {
+		const body = '```sh\n# a comment\n```\n#hashtag\n####### seven\n## Real';
+		expect(markdownOutline(body).map((e) => e.text)).toEqual(['Real']);
+	});
+});
+
+describe('hashtags', () => {
+	it('skips headings and dedupes', () => {
+		expect(hashtags('# Title\n#Rust and #rust, mid#word #a-b_c\n#')).toEqual(['rust', 'a-b_c']);
+	});
+});
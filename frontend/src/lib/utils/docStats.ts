@@ -0,0 +1,74 @@
+/** Live document statistics and the Markdown heading outline.
+ *
+ * The counts follow the word-count skill (`sovereign-skills`
+ * `word_count.rs`) so the footer and the skill never disagree: words are
+ * whitespace-separated runs, characters are code points, and reading time
+ * rounds up at 200 words a minute.
+ */
+
+import { fencedBlocks } from './codeHighlight';
+
+export interface DocStats {
+	words: number;
+	characters: number;
+	lines: number;
+	readingMinutes: number;
+}
+
+const WORDS_PER_MINUTE = 200;
+
+export function documentStats(body: string): DocStats {
+	const words = body.split(/\s+/).filter(Boolean).length;
+	const characters = [...body].length;
+	// Rust's `str::lines` doesn't count a trailing newline as a line.
+	const lines = body === '' ? 0 : body.replace(/\r?\n$/, '').split(/\r?\n/).length;
+	return { words, characters, lines, readingMinutes: Math.ceil(words / WORDS_PER_MINUTE) };
+}
+
+/** `1,234 words · 6,789 characters · 7 min read` */
+export function formatStats(stats: DocStats): string {
+	const n = (x: number) => x.toLocaleString('en-US');
+	const read = stats.words === 0 ? '' : ` · ${stats.readingMinutes} min read`;
+	return `${n(stats.words)} ${stats.words === 1 ? 'word' : 'words'} · ${n(stats.characters)} ${
+		stats.characters === 1 ? 'character' : 'characters'
+	}${read}`;
+}
+
+export interface OutlineEntry {
+	level: number;
+	text: string;
+	/** Offset of the heading line in the body. */
+	offset: number;
+}
+
+/** ATX headings (`# Title` … `###### Title`) outside fenced code, in order. */
+export function markdownOutline(body: string): OutlineEntry[] {
+	const fences = fencedBlocks(body);
+	const inFence = (at: number) => fences.some((f) => at >= f.start && at <= f.end);
+	const out: OutlineEntry[] = [];
+	const heading = /^ {0,3}(#{1,6})[ \t]+(.+?)(?:[ \t]+#+)?[ \t]*$/gm;
+	let m: RegExpExecArray | null;
+	while ((m = heading.exec(body))) {
+		if (inFence(m.index)) continue;
+		const text = stripInline(m[2]);
+		if (text) out.push({ level: m[1].length, text, offset: m.index });
+	}
+	return out;
+}
+
+/** `#tag` markers in a body, by the rule of `sovereign_core::content::hashtags`:
+ *  a `#` that opens the text or follows whitespace, then letters, digits,
+ *  `-` and `_`; lowercased and deduped. Headings are not tags. */
+export function hashtags(body: string): string[] {
+	const out = new Set<string>();
+	for (const m of body.matchAll(/(?<!\S)#([\p{L}\p{N}_-]+)/gu)) out.add(m[1].toLowerCase());
+	return [...out];
+}
+
+/** Heading text without emphasis, code and link markup. */
+function stripInline(text: string): string {
+	return text
+		.replace(/!?\[([^\]]*)\]\([^)]*\)/g, '$1')
+		.replace(/(\*\*|__|\*|_|`|~~)(.+?)\1/g, '$2')
+		.trim();
+}
@@ -0,0 +1,20 @@
+import { describe, expect, it } from 'vitest';
+import type { DocType } from '$lib/api/commands';
+import { DOC_TYPE_NAMES, docTypeInfo } from './docType';
+
+describe('docTypeInfo', () => {
+	it('covers every wire name the backend sends', () => {
+		expect(DOC_TYPE_NAMES).toEqual(['note', 'spec', 'spreadsheet', 'image', 'pdf', 'web-clip', 'transcript']);
+	});
+
+	it('opens read-first types in preview', () => {
+		expect(docTypeInfo('note').preview).toBe(false);
+		expect(docTypeInfo('web-clip').preview).toBe(true);
+		expect(docTypeInfo('spec').outline).toBe(true);
+	});
+
+	it('falls back to a note for missing or unknown types', () => {
+		expect(docTypeInfo(undefined)).toBe(docTypeInfo('note'));
+		expect(docTypeInfo('slides' as DocType)).toBe(docTypeInfo('note'));
+	});
+});
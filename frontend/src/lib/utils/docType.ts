@@ -0,0 +1,36 @@
+/** Document types (`sovereign_sdk::DocType`) as the UI shows them: a
+ *  label for the type picker, a glyph for canvas cards, and how a panel
+ *  for the type first opens. */
+
+import type { DocType } from '$lib/api/commands';
+
+export interface DocTypeInfo {
+	label: string;
+	glyph: string;
+	/** Opens read-first in preview rather than in the editor. */
+	preview: boolean;
+	/** Opens with the heading outline beside the body. */
+	outline: boolean;
+	/** Opening panel width; tables want the room. */
+	width: number;
+}
+
+const DEFAULT_WIDTH = 680;
+
+export const DOC_TYPES: Record<DocType, DocTypeInfo> = {
+	note: { label: 'Note', glyph: '✎', preview: false, outline: false, width: DEFAULT_WIDTH },
+	spec: { label: 'Spec', glyph: '§', preview: false, outline: true, width: DEFAULT_WIDTH },
+	spreadsheet: { label: 'Spreadsheet', glyph: '▦', preview: true, outline: false, width: 880 },
+	image: { label: 'Image', glyph: '▣', preview: true, outline: false, width: DEFAULT_WIDTH },
+	pdf: { label: 'PDF', glyph: '⎙', preview: true, outline: true, width: DEFAULT_WIDTH },
+	'web-clip': { label: 'Web clip', glyph: '↗', preview: true, outline: false, width: DEFAULT_WIDTH },
+	transcript: { label: 'Transcript', glyph: '❝', preview: true, outline: false, width: DEFAULT_WIDTH }
+};
+
+export const DOC_TYPE_NAMES = Object.keys(DOC_TYPES) as DocType[];
+
+/** The type's info; documents from before types (or from a newer
+ *  build with types this one lacks) are treated as notes. */
+export function docTypeInfo(type: DocType | undefined): DocTypeInfo {
+	return (type && DOC_TYPES[type]) || DOC_TYPES.note;
+}
@@ -0,0 +1,116 @@
+import { describe, expect, it } from 'vitest';
+import {
+	createEmacsState,
+	createVimState,
+	emacsKey,
+	vimKey,
+	vimStatus,
+	type EditorText,
+	type KeyOutcome
+} from './editorKeymap';
+
+const key = (k: string, mods: { ctrl?: boolean; alt?: boolean } = {}) => ({
+	key: k,
+	ctrl: !!mods.ctrl,
+	alt: !!mods.alt,
+	shift: false
+});
+
+/** Apply an outcome to `ed` the way the panel does. */
+function apply(ed: EditorText, out: KeyOutcome | null): EditorText {
+	let text = ed.text;
+	if (out?.edit) text = text.slice(0, out.edit.from) + out.edit.insert + text.slice(out.edit.to);
+	const [start, end] = out?.select ?? [ed.start, ed.end];
+	return { text, start, end };
+}
+
+function typeVim(text: string, at: number, keys: string[]) {
+	const s = createVimState();
+	let ed: EditorText = { text, start: at, end: at };
+	for (const k of keys) ed = apply(ed, vimKey(s, key(k), ed));
+	return { s, ed };
+}
+
+describe('vim motions', () => {
+	it('moves by words and to line ends', () => {
+		expect(typeVim('one two three', 0, ['w']).ed.start).toBe(4);
+		expect(typeVim('one two three', 0, ['2', 'w']).ed.start).toBe(8);
+		expect(typeVim('one two three', 8, ['b']).ed.start).toBe(4);
+		expect(typeVim('one two three', 0, ['$']).ed.start).toBe(12);
+		expect(typeVim('ab\ncd', 1, ['j']).ed.start).toBe(4);
+		expect(typeVim('ab\ncd\nef', 7, ['g', 'g']).ed.start).toBe(0);
+	});
+
+	it('does not type unbound keys in normal mode', () => {
+		const { ed } = typeVim('abc', 0, ['z', 'Q']);
+		expect(ed.text).toBe('abc');
+	});
+});
+
+describe('vim operators', () => {
+	it('deletes words, lines and characters', () => {
+		expect(typeVim('one two three', 0, ['d', 'w']).ed.text).toBe('two three');
+		expect(typeVim('a\nb\nc', 2, ['d', 'd']).ed.text).toBe('a\nc');
+		expect(typeVim('a\nb\nc', 0, ['2', 'd', 'd']).ed.text).toBe('c');
+		expect(typeVim('abc', 1, ['x']).ed.text).toBe('ac');
+		expect(typeVim('abc def', 1, ['D']).ed.text).toBe('a');
+	});
+
+	it('changes into insert mode', () => {
+		const { s, ed } = typeVim('one two', 0, ['c', 'w']);
+		expect(ed.text).toBe(' two');
+		expect(s.mode).toBe('insert');
+		expect(vimStatus(s)).toBe('-- INSERT --');
+	});
+
+	it('yanks and puts lines', () => {
+		expect(typeVim('a\nb', 0, ['y', 'y', 'p']).ed.text).toBe('a\na\nb');
+		expect(typeVim('a\nb', 2, ['y', 'y', 'P']).ed.text).toBe('a\nb\nb');
+	});
+
+	it('deletes a visual selection', () => {
+		expect(typeVim('abcdef', 1, ['v', 'l', 'l', 'd']).ed.text).toBe('aef');
+		expect(typeVim('a\nb\nc', 2, ['V', 'j', 'd']).ed.text).toBe('a');
+	});
+
+	it('opens a line and leaves insert mode on Escape', () => {
+		const { s, ed } = typeVim('a\nb', 0, ['o', 'Escape']);
+		expect(ed.text).toBe('a\n\nb');
+		expect(s.mode).toBe('normal');
+	});
+
+	it('leaves ctrl chords other than redo to the panel', () => {
+		const s = createVimState();
+		const ed = { text: 'x', start: 0, end: 0 };
+		expect(vimKey(s, key('s', { ctrl: true }), ed)).toBeNull();
+		expect(vimKey(s, key('r', { ctrl: true }), ed)).toEqual({ action: 'redo' });
+	});
+});
+
+describe('emacs', () => {
+	it('moves and kills to the end of the line', () => {
+		const s = createEmacsState();
+		let ed: EditorText = { text: 'hello world\nnext', start: 0, end: 0 };
+		ed = apply(ed, emacsKey(s, key('f', { alt: true }), ed));
+		expect(ed.start).toBe(5);
+		ed = apply(ed, emacsKey(s, key('k', { ctrl: true }), ed));
+		expect(ed.text).toBe('hello\nnext');
+		ed = apply(ed, emacsKey(s, key('k', { ctrl: true }), ed));
+		expect(ed.text).toBe('hellonext');
+		// Consecutive kills append, so the yank brings both back.
+		ed = apply(ed, emacsKey(s, key('y', { ctrl: true }), ed));
+		expect(ed.text).toBe('hello world\nnext');
+	});
+
+	it('saves on C-x C-s and searches on C-s', () => {
+		const s = createEmacsState();
+		const ed = { text: '', start: 0, end: 0 };
+		expect(emacsKey(s, key('s', { ctrl: true }), ed)).toEqual({ action: 'find' });
+		emacsKey(s, key('x', { ctrl: true }), ed);
+		expect(emacsKey(s, key('s', { ctrl: true }), ed)).toEqual({ action: 'save' });
+	});
+
+	it('leaves plain typing to the textarea', () => {
+		expect(emacsKey(createEmacsState(), key('a'), { text: '', start: 0, end: 0 })).toBeNull();
+	});
+});
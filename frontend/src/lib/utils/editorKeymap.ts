@@ -0,0 +1,615 @@
+/** Vim and Emacs key bindings for the document editor's textarea.
+ *
+ * Pure functions from (state, key, text + selection) to an outcome the
+ * panel applies. Edits come back as a single range replacement so the
+ * panel can route them through the browser's own editing (which keeps
+ * native undo/redo working); everything not bound here returns `null`
+ * and is left to the textarea.
+ *
+ * Vim covers the core: counts, h j k l w b e 0 ^ $ gg G, the d / c / y
+ * operators with those motions (and dd / cc / yy), x X D C s r p P J,
+ * i a I A o O, charwise and linewise visual mode, u and Ctrl-R. Emacs
+ * covers the movement, kill/yank and mark commands people reach for
+ * first, with C-s searching and C-x C-s saving. */
+
+export type Keymap = 'standard' | 'vim' | 'emacs';
+
+export interface KeyInput {
+	key: string;
+	ctrl: boolean;
+	alt: boolean;
+	shift: boolean;
+}
+
+export interface EditorText {
+	text: string;
+	start: number;
+	end: number;
+}
+
+export interface KeyOutcome {
+	/** Replace `[from, to)` with `insert`. */
+	edit?: { from: number; to: number; insert: string };
+	/** Selection to show afterwards (in post-edit offsets). */
+	select?: [number, number];
+	/** Something the panel does itself. */
+	action?: 'undo' | 'redo' | 'save' | 'find' | 'replace';
+}
+
+/** Normalize a keyboard event. With Alt held, macOS reports the composed
+ *  character in `key`, so letters come from `code` instead. */
+export function keyInput(e: KeyboardEvent): KeyInput {
+	const letter = /^Key([A-Z])$/.exec(e.code);
+	const key = e.altKey && letter ? (e.shiftKey ? letter[1] : letter[1].toLowerCase()) : e.key;
+	return { key, ctrl: e.ctrlKey || e.metaKey, alt: e.altKey, shift: e.shiftKey };
+}
+
+// ---------------------------------------------------------------------------
+// Text geometry
+// ---------------------------------------------------------------------------
+
+export function lineStart(t: string, p: number): number {
+	return t.lastIndexOf('\n', p - 1) + 1;
+}
+
+export function lineEnd(t: string, p: number): number {
+	const i = t.indexOf('\n', p);
+	return i < 0 ? t.length : i;
+}
+
+function firstNonBlank(t: string, p: number): number {
+	let i = lineStart(t, p);
+	const end = lineEnd(t, p);
+	while (i < end && (t[i] === ' ' || t[i] === '\t')) i++;
+	return i;
+}
+
+/** Same column on the line `delta` lines away, clamped to its length. */
+function vertical(t: string, p: number, delta: number): number {
+	const col = p - lineStart(t, p);
+	let s = lineStart(t, p);
+	for (; delta > 0; delta--) {
+		const e = lineEnd(t, s);
+		if (e >= t.length) break;
+		s = e + 1;
+	}
+	for (; delta < 0; delta++) {
+		if (s === 0) break;
+		s = lineStart(t, s - 1);
+	}
+	return Math.min(s + col, lineEnd(t, s));
+}
+
+type CharClass = 0 | 1 | 2; // space, word, punctuation
+
+function charClass(c: string | undefined): CharClass {
+	if (c === undefined || /\s/.test(c)) return 0;
+	return /\w/.test(c) ? 1 : 2;
+}
+
+/** Start of the next word (vim `w`, emacs `M-f` end handled separately). */
+function wordForward(t: string, p: number): number {
+	const cls = charClass(t[p]);
+	let i = p;
+	if (cls !== 0) while (i < t.length && charClass(t[i]) === cls) i++;
+	while (i < t.length && charClass(t[i]) === 0) i++;
+	return i;
+}
+
+/** Last character of the current or next word (vim `e`). */
+function wordEnd(t: string, p: number): number {
+	let i = p + 1;
+	while (i < t.length && charClass(t[i]) === 0) i++;
+	const cls = charClass(t[i]);
+	while (i + 1 < t.length && charClass(t[i + 1]) === cls) i++;
+	return Math.min(i, Math.max(0, t.length - 1));
+}
+
+/** Start of the current or previous word (vim `b`, emacs `M-b`). */
+function wordBack(t: string, p: number): number {
+	let i = p - 1;
+	while (i > 0 && charClass(t[i]) === 0) i--;
+	const cls = charClass(t[i]);
+	while (i > 0 && charClass(t[i - 1]) === cls) i--;
+	return Math.max(0, i);
+}
+
+/** End of the current or next word (emacs `M-f`, `M-d`). */
+function wordForwardEnd(t: string, p: number): number {
+	let i = p;
+	while (i < t.length && charClass(t[i]) !== 1) i++;
+	while (i < t.length && charClass(t[i]) === 1) i++;
+	return i;
+}
+
+// ---------------------------------------------------------------------------
+// Vim
+// ---------------------------------------------------------------------------
+
+export type VimMode = 'normal' | 'insert' | 'visual' | 'visual-line';
+
+export interface VimState {
+	mode: VimMode;
+	/** Keys typed toward a command not yet complete (count, operator, `g`, `r`). */
+	pending: string;
+	register: string;
+	registerLinewise: boolean;
+	/** Visual mode: where the selection started and where the cursor is. */
+	anchor: number;
+	cursor: number;
+}
+
+export function createVimState(): VimState {
+	return { mode: 'normal', pending: '', register: '', registerLinewise: false, anchor: 0, cursor: 0 };
+}
+
+/** The status-line label for a vim state. */
+export function vimStatus(s: VimState): string {
+	const label = {
+		normal: '',
+		insert: '-- INSERT --',
+		visual: '-- VISUAL --',
+		'visual-line': '-- VISUAL LINE --'
+	}[s.mode];
+	return [label, s.pending].filter(Boolean).join(' ') || 'NORMAL';
+}
+
+/** Normal mode keeps the cursor on a character, never past a line's end. */
+function clampNormal(t: string, p: number): number {
+	p = Math.max(0, Math.min(p, t.length));
+	const s = lineStart(t, p);
+	const e = lineEnd(t, p);
+	return e > s && p >= e ? e - 1 : p;
+}
+
+/** The one-character selection that stands in for a block cursor. */
+function blockCursor(t: string, p: number): [number, number] {
+	return p < t.length && t[p] !== '\n' ? [p, p + 1] : [p, p];
+}
+
+function visualSelection(t: string, s: VimState): [number, number] {
+	const lo = Math.min(s.anchor, s.cursor);
+	const hi = Math.max(s.anchor, s.cursor);
+	if (s.mode === 'visual-line') return [lineStart(t, lo), lineEnd(t, hi)];
+	return [lo, Math.min(t.length, hi + 1)];
+}
+
+/** Selection to show for the current mode at cursor `p`. */
+export function vimSelection(t: string, s: VimState, p: number): [number, number] {
+	if (s.mode === 'visual' || s.mode === 'visual-line') return visualSelection(t, s);
+	if (s.mode === 'insert') return [p, p];
+	return blockCursor(t, p);
+}
+
+interface Motion {
+	to: number;
+	/** The motion's end character is part of an operator's range. */
+	inclusive?: boolean;
+	linewise?: boolean;
+}
+
+function motion(t: string, p: number, key: string, count: number, op: string): Motion | null {
+	let q = p;
+	switch (key) {
+		case 'h':
+		case 'ArrowLeft':
+			return { to: Math.max(lineStart(t, p), p - count) };
+		case 'l':
+		case 'ArrowRight':
+			// `dl` / `x` may take the last character of the line.
+			return { to: Math.min(lineEnd(t, p) - (op ? 0 : 1), p + count) };
+		case 'j':
+		case 'ArrowDown':
+			return { to: vertical(t, p, count), linewise: true };
+		case 'k':
+		case 'ArrowUp':
+			return { to: vertical(t, p, -count), linewise: true };
+		case 'w':
+			// `cw` changes to the end of the word, like `ce`.
+			if (op === 'c' && charClass(t[p]) !== 0) return motion(t, p, 'e', count, op);
+			for (let i = 0; i < count; i++) q = wordForward(t, q);
+			// An operator stops at the end of the line, not on the next one.
+			if (op && t.lastIndexOf('\n', q - 1) >= p) q = Math.max(p, t.lastIndexOf('\n', q - 1));
+			return { to: q };
+		case 'e':
+			for (let i = 0; i < count; i++) q = wordEnd(t, q);
+			return { to: q, inclusive: true };
+		case 'b':
+			for (let i = 0; i < count; i++) q = wordBack(t, q);
+			return { to: q };
+		case '0':
+		case 'Home':
+			return { to: lineStart(t, p) };
+		case '^':
+			return { to: firstNonBlank(t, p) };
+		case '$':
+		case 'End':
+			q = lineEnd(t, vertical(t, p, count - 1));
+			return { to: op ? q : Math.max(lineStart(t, q), q - 1) };
+		case 'G':
+			return { to: firstNonBlank(t, t.length), linewise: true };
+		case 'gg':
+			return { to: 0, linewise: true };
+	}
+	return null;
+}
+
+/** `[from, to)` of whole lines covering `a..b`, with one newline. */
+function lineRange(t: string, a: number, b: number): [number, number] {
+	const from = lineStart(t, Math.min(a, b));
+	const end = lineEnd(t, Math.max(a, b));
+	if (end < t.length) return [from, end + 1];
+	return [from > 0 ? from - 1 : 0, end];
+}
+
+function applyOperator(
+	s: VimState,
+	t: string,
+	op: string,
+	from: number,
+	to: number,
+	linewise: boolean
+): KeyOutcome {
+	const text = t.slice(from, to);
+	s.register = linewise ? text.replace(/^\n/, '').replace(/\n$/, '') : text;
+	s.registerLinewise = linewise;
+	if (op === 'y') {
+		s.mode = 'normal';
+		const at = linewise ? lineStart(t, from === 0 || t[from] !== '\n' ? from : from + 1) : from;
+		return { select: blockCursor(t, at) };
+	}
+	if (op === 'c') {
+		s.mode = 'insert';
+		if (linewise) {
+			// Keep the (now empty) line to type on.
+			const start = lineStart(t, from === 0 || t[from] !== '\n' ? from : from + 1);
+			const end = lineEnd(t, Math.max(start, to - 1));
+			return { edit: { from: start, to: end, insert: '' }, select: [start, start] };
+		}
+		return { edit: { from, to, insert: '' }, select: [from, from] };
+	}
+	s.mode = 'normal';
+	const after = t.slice(0, from) + t.slice(to);
+	const at = clampNormal(after, linewise ? firstNonBlank(after, Math.min(from, after.length)) : from);
+	return { edit: { from, to, insert: '' }, select: blockCursor(after, at) };
+}
+
+function enterInsert(s: VimState, at: number, edit?: KeyOutcome['edit']): KeyOutcome {
+	s.mode = 'insert';
+	return { edit, select: [at, at] };
+}
+
+/** Handle one key in vim mode. Mutates `s`. */
+export function vimKey(s: VimState, k: KeyInput, ed: EditorText): KeyOutcome | null {
+	const t = ed.text;
+
+	if (s.mode === 'insert') {
+		if (k.key === 'Escape' || (k.ctrl && k.key === '[')) {
+			s.mode = 'normal';
+			const p = ed.start > lineStart(t, ed.start) ? ed.start - 1 : ed.start;
+			return { select: blockCursor(t, clampNormal(t, p)) };
+		}
+		return null;
+	}
+
+	// Ctrl/Meta chords other than redo belong to the app (save, find…).
+	if (k.ctrl) {
+		if (k.key === 'r') {
+			s.pending = '';
+			return { action: 'redo' };
+		}
+		return null;
+	}
+	if (k.key.length > 1 && !/^(Escape|Arrow\w+|Home|End|Backspace)$/.test(k.key)) return null;
+
+	const visual = s.mode === 'visual' || s.mode === 'visual-line';
+	const p = visual ? s.cursor : clampNormal(t, ed.start);
+
+	if (k.key === 'Escape') {
+		s.pending = '';
+		if (visual) s.mode = 'normal';
+		return { select: blockCursor(t, p) };
+	}
+
+	// Pending `r{char}` replaces under the cursor.
+	if (s.pending.endsWith('r') && !visual) {
+		s.pending = '';
+		if (k.key.length !== 1 || p >= t.length || t[p] === '\n') return { select: blockCursor(t, p) };
+		return { edit: { from: p, to: p + 1, insert: k.key }, select: [p, p + 1] };
+	}
+
+	const countMatch = /^(\d*)/.exec(s.pending)!;
+	const countText = countMatch[1];
+	if (/^[0-9]$/.test(k.key) && (k.key !== '0' || countText)) {
+		s.pending += k.key;
+		return {};
+	}
+	const count = Math.max(1, parseInt(countText || '1', 10));
+	const rest = s.pending.slice(countText.length);
+	const op = /^[dcy]/.test(rest) ? rest[0] : '';
+	let key = k.key === 'Backspace' ? 'h' : k.key;
+	if (rest.endsWith('g')) {
+		if (key !== 'g') {
+			s.pending = '';
+			return {};
+		}
+		key = 'gg';
+	} else if (key === 'g') {
+		s.pending += 'g';
+		return {};
+	}
+
+	if (visual) {
+		s.pending = '';
+		const m = motion(t, p, key, count, '');
+		if (m) {
+			s.cursor = Math.max(0, Math.min(m.to, t.length));
+			return { select: visualSelection(t, s) };
+		}
+		const [from, to] = visualSelection(t, s);
+		const linewise = s.mode === 'visual-line';
+		const range: [number, number] = linewise ? lineRange(t, from, to) : [from, to];
+		switch (key) {
+			case 'd':
+			case 'x':
+				return applyOperator(s, t, 'd', range[0], range[1], linewise);
+			case 'y':
+				return applyOperator(s, t, 'y', range[0], range[1], linewise);
+			case 'c':
+			case 's':
+				return applyOperator(s, t, 'c', range[0], range[1], linewise);
+			case 'v':
+			case 'V': {
+				const next = key === 'v' ? 'visual' : 'visual-line';
+				s.mode = s.mode === next ? 'normal' : next;
+				return { select: vimSelection(t, s, s.cursor) };
+			}
+			case 'o':
+				[s.anchor, s.cursor] = [s.cursor, s.anchor];
+				return { select: visualSelection(t, s) };
+		}
+		return {};
+	}
+
+	// Operator pending: the same key again means whole lines.
+	if (op) {
+		s.pending = '';
+		if (key === op) {
+			const last = vertical(t, p, count - 1);
+			const [from, to] = lineRange(t, p, last);
+			return applyOperator(s, t, op, from, to, true);
+		}
+		const m = motion(t, p, key, count, op);
+		if (!m) return { select: blockCursor(t, p) };
+		if (m.linewise) {
+			const [from, to] = lineRange(t, p, m.to);
+			return applyOperator(s, t, op, from, to, true);
+		}
+		const from = Math.min(p, m.to);
+		const to = Math.max(p, m.to) + (m.inclusive ? 1 : 0);
+		return applyOperator(s, t, op, from, Math.min(to, t.length), false);
+	}
+
+	const m = motion(t, p, key, count, '');
+	if (m) {
+		s.pending = '';
+		return { select: blockCursor(t, clampNormal(t, m.to)) };
+	}
+
+	s.pending = '';
+	switch (key) {
+		case 'd':
+		case 'c':
+		case 'y':
+			s.pending = (countText || '') + key;
+			return {};
+		case 'r':
+			s.pending = 'r';
+			return {};
+		case 'x':
+			return p < lineEnd(t, p)
+				? applyOperator(s, t, 'd', p, Math.min(lineEnd(t, p), p + count), false)
+				: {};
+		case 'X': {
+			const from = Math.max(lineStart(t, p), p - count);
+			return from < p ? applyOperator(s, t, 'd', from, p, false) : {};
+		}
+		case 'D':
+			return applyOperator(s, t, 'd', p, lineEnd(t, p), false);
+		case 'C':
+			return applyOperator(s, t, 'c', p, lineEnd(t, p), false);
+		case 's':
+			return applyOperator(s, t, 'c', p, Math.min(lineEnd(t, p), p + count), false);
+		case 'i':
+			return enterInsert(s, p);
+		case 'a':
+			return enterInsert(s, Math.min(lineEnd(t, p), p + 1));
+		case 'I':
+			return enterInsert(s, firstNonBlank(t, p));
+		case 'A':
+			return enterInsert(s, lineEnd(t, p));
+		case 'o': {
+			const at = lineEnd(t, p);
+			return enterInsert(s, at + 1, { from: at, to: at, insert: '\n' });
+		}
+		case 'O': {
+			const at = lineStart(t, p);
+			return enterInsert(s, at, { from: at, to: at, insert: '\n' });
+		}
+		case 'v':
+		case 'V':
+			s.mode = key === 'v' ? 'visual' : 'visual-line';
+			s.anchor = s.cursor = p;
+			return { select: visualSelection(t, s) };
+		case 'p':
+		case 'P': {
+			if (!s.register) return {};
+			const reg = s.register.repeat(count);
+			if (s.registerLinewise) {
+				const lines = Array(count).fill(s.register).join('\n');
+				if (key === 'p') {
+					const at = lineEnd(t, p);
+					return { edit: { from: at, to: at, insert: '\n' + lines }, select: blockCursor('\n' + lines, 1).map((x) => x + at) as [number, number] };
+				}
+				const at = lineStart(t, p);
+				return { edit: { from: at, to: at, insert: lines + '\n' }, select: blockCursor(lines, 0).map((x) => x + at) as [number, number] };
+			}
+			const at = key === 'p' && p < lineEnd(t, p) ? p + 1 : p;
+			const end = at + reg.length - 1;
+			return { edit: { from: at, to: at, insert: reg }, select: [end, end + 1] };
+		}
+		case 'J': {
+			const e = lineEnd(t, p);
+			if (e >= t.length) return {};
+			let n = e + 1;
+			while (n < t.length && (t[n] === ' ' || t[n] === '\t')) n++;
+			const joiner = n < t.length && t[n] !== '\n' ? ' ' : '';
+			return { edit: { from: e, to: n, insert: joiner }, select: [e, e + 1] };
+		}
+		case 'u':
+			return { action: 'undo' };
+	}
+	// Unbound printable keys must not type in normal mode.
+	return {};
+}
+
+// ---------------------------------------------------------------------------
+// Emacs
+// ---------------------------------------------------------------------------
+
+export interface EmacsState {
+	/** Set by C-SPC; movement then extends the region from here. */
+	mark: number | null;
+	/** `C-x` typed, waiting for the second key. */
+	prefix: boolean;
+	killRing: string;
+	/** The previous command was a kill, so the next one appends. */
+	lastWasKill: boolean;
+}
+
+export function createEmacsState(): EmacsState {
+	return { mark: null, prefix: false, killRing: '', lastWasKill: false };
+}
+
+export function emacsStatus(s: EmacsState): string {
+	return ['Emacs', s.prefix ? 'C-x-' : '', s.mark !== null ? 'Mark set' : '']
+		.filter(Boolean)
+		.join('  ');
+}
+
+function kill(s: EmacsState, t: string, from: number, to: number): KeyOutcome {
+	const text = t.slice(from, to);
+	s.killRing = s.lastWasKill ? s.killRing + text : text;
+	s.mark = null;
+	return { edit: { from, to, insert: '' }, select: [from, from] };
+}
+
+/** Handle one key in emacs mode. Mutates `s`. */
+export function emacsKey(s: EmacsState, k: KeyInput, ed: EditorText): KeyOutcome | null {
+	const t = ed.text;
+	// The point is the moving end of the region.
+	const p = s.mark !== null && ed.start === s.mark ? ed.end : ed.start;
+	const move = (to: number): KeyOutcome => {
+		to = Math.max(0, Math.min(to, t.length));
+		if (s.mark === null) return { select: [to, to] };
+		return { select: [Math.min(s.mark, to), Math.max(s.mark, to)] };
+	};
+	const wasKill = s.lastWasKill;
+	s.lastWasKill = false;
+
+	if (s.prefix) {
+		s.prefix = false;
+		if (k.ctrl && k.key === 's') return { action: 'save' };
+		return {};
+	}
+
+	if (k.ctrl && !k.alt) {
+		switch (k.key) {
+			case 'f':
+				return move(p + 1);
+			case 'b':
+				return move(p - 1);
+			case 'n':
+				return move(vertical(t, p, 1));
+			case 'p':
+				return move(vertical(t, p, -1));
+			case 'a':
+				return move(lineStart(t, p));
+			case 'e':
+				return move(lineEnd(t, p));
+			case 'v':
+				return move(vertical(t, p, 20));
+			case 'd':
+				return p < t.length ? { edit: { from: p, to: p + 1, insert: '' }, select: [p, p] } : {};
+			case 'k': {
+				s.lastWasKill = wasKill;
+				const e = lineEnd(t, p);
+				const r = kill(s, t, p, e > p ? e : Math.min(t.length, e + 1));
+				s.lastWasKill = true;
+				return r;
+			}
+			case 'w': {
+				if (s.mark === null) return {};
+				const r = kill(s, t, Math.min(s.mark, p), Math.max(s.mark, p));
+				s.lastWasKill = true;
+				return r;
+			}
+			case 'y':
+				s.mark = null;
+				return s.killRing
+					? {
+							edit: { from: ed.start, to: ed.end, insert: s.killRing },
+							select: [ed.start + s.killRing.length, ed.start + s.killRing.length]
+						}
+					: {};
+			case ' ':
+				s.mark = p;
+				return { select: [p, p] };
+			case 'g':
+				s.mark = null;
+				return { select: [p, p] };
+			case 'x':
+				s.prefix = true;
+				return {};
+			case 's':
+				return { action: 'find' };
+			case '/':
+			case '_':
+				return { action: 'undo' };
+		}
+		return null;
+	}
+
+	if (k.alt && !k.ctrl) {
+		switch (k.key) {
+			case 'f':
+				return move(wordForwardEnd(t, p));
+			case 'b':
+				return move(wordBack(t, p));
+			case 'd': {
+				s.lastWasKill = wasKill;
+				const r = kill(s, t, p, wordForwardEnd(t, p));
+				s.lastWasKill = true;
+				return r;
+			}
+			case 'w':
+				if (s.mark === null) return {};
+				s.killRing = t.slice(Math.min(s.mark, p), Math.max(s.mark, p));
+				s.mark = null;
+				return { select: [p, p] };
+			case 'v':
+				return move(vertical(t, p, -20));
+			case '<':
+				return move(0);
+			case '>':
+				return move(t.length);
+			case '%':
+				return { action: 'replace' };
+		}
+		return null;
+	}
+
+	// Typing with a region active replaces it, as in a plain field.
+	if (k.key.length === 1 || k.key === 'Backspace') s.mark = null;
+	return null;
+}
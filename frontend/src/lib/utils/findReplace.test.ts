@@ -0,0 +1,43 @@
+import { describe, expect, it } from 'vitest';
+import { findMatches, matchAtOrAfter, replaceAllMatches, replaceMatch } from './findReplace';
+
+describe('findMatches', () => {
+	it('finds non-overlapping literal matches', () => {
+		expect(findMatches('aaaa', 'aa', true)).toEqual([
+			{ start: 0, end: 2 },
+			{ start: 2, end: 4 }
+		]);
+		expect(findMatches('a.b a*b', '.', true)).toEqual([{ start: 1, end: 2 }]);
+		expect(findMatches('anything', '', false)).toEqual([]);
+	});
+
+	it('ignores case unless asked not to', () => {
+		expect(findMatches('Foo foo FOO', 'foo', false)).toHaveLength(3);
+		expect(findMatches('Foo foo FOO', 'foo', true)).toEqual([{ start: 4, end: 7 }]);
+	});
+});
+
+describe('matchAtOrAfter', () => {
+	it('picks the next match from the caret and wraps around', () => {
+		const m = findMatches('x_x_x', 'x', true);
+		expect(matchAtOrAfter(m, 1)).toBe(1);
+		expect(matchAtOrAfter(m, 5)).toBe(0);
+		expect(matchAtOrAfter([], 0)).toBe(-1);
+	});
+});
+
+describe('replace', () => {
+	it('replaces one match or all of them', () => {
+		const text = 'cat Cat cat';
+		const [first] = findMatches(text, 'cat', true);
+		expect(replaceMatch(text, first, 'dog')).toBe('dog Cat cat');
+		expect(replaceAllMatches(text, 'cat', 'dog', false)).toEqual({
+			text: 'dog dog dog',
+			count: 3
+		});
+		expect(replaceAllMatches(text, 'cat', 'cats', true)).toEqual({
+			text: 'cats Cat cats',
+			count: 2
+		});
+	});
+});
@@ -0,0 +1,58 @@
+/** Literal find/replace for the document editor's find bar.
+ *
+ * Same semantics as the `find-replace` skill (plain text, no patterns),
+ * plus an optional case-insensitive mode. Matches never overlap: after a
+ * hit the scan resumes at its end, as `String.replaceAll` does. */
+
+export interface TextMatch {
+	start: number;
+	end: number;
+}
+
+export function findMatches(text: string, query: string, matchCase: boolean): TextMatch[] {
+	if (!query) return [];
+	const hay = matchCase ? text : text.toLowerCase();
+	const needle = matchCase ? query : query.toLowerCase();
+	// Lower-casing can change a string's length (e.g. 'İ'); fall back to
+	// exact matching rather than report offsets that don't line up.
+	if (hay.length !== text.length || needle.length !== query.length) {
+		return matchCase ? [] : findMatches(text, query, true);
+	}
+	const out: TextMatch[] = [];
+	let from = 0;
+	for (;;) {
+		const at = hay.indexOf(needle, from);
+		if (at < 0) break;
+		out.push({ start: at, end: at + needle.length });
+		from = at + needle.length;
+	}
+	return out;
+}
+
+/** Index of the first match at or after `offset`, wrapping to 0. */
+export function matchAtOrAfter(matches: TextMatch[], offset: number): number {
+	if (matches.length === 0) return -1;
+	const i = matches.findIndex((m) => m.start >= offset);
+	return i < 0 ? 0 : i;
+}
+
+export function replaceMatch(text: string, match: TextMatch, replacement: string): string {
+	return text.slice(0, match.start) + replacement + text.slice(match.end);
+}
+
+/** Replace every match; returns the new text and how many were replaced. */
+export function replaceAllMatches(
+	text: string,
+	query: string,
+	replacement: string,
+	matchCase: boolean
+): { text: string; count: number } {
+	const matches = findMatches(text, query, matchCase);
+	let out = '';
+	let last = 0;
+	for (const m of matches) {
+		out += text.slice(last, m.start) + replacement;
+		last = m.end;
+	}
+	return { text: out + text.slice(last), count: matches.length };
+}
@@ -0,0 +1,67 @@
+/** Frame timing for `sovereign --trace-profile` and local metrics: measures the
+ *  gap between animation frames and sends the timings to the backend about
+ *  once a second, where they land as `render.frame`. Does nothing unless
+ *  the backend wants them. */
+
+import { profilingEnabled, reportFrameTimes, type FrameTime } from '$lib/api/commands';
+
+const FLUSH_INTERVAL_MS = 1000;
+
+let active = false;
+let stopTiming: (() => void) | null = null;
+
+/** Start timing frames while the backend wants them. Resolves to the
+ *  function that stops it. */
+export async function startFrameProfiler(): Promise<() => void> {
+	active = true;
+	await refreshFrameProfiler();
+	return () => {
+		active = false;
+		stopTiming?.();
+		stopTiming = null;
+	};
+}
+
+/** Ask the backend again whether it wants frame timings, e.g. after
+ *  metrics were switched on or off in the config. */
+export async function refreshFrameProfiler(): Promise<void> {
+	if (!active) return;
+	const enabled = await profilingEnabled().catch(() => false);
+	if (!active) return;
+	if (enabled && !stopTiming) {
+		stopTiming = timeFrames();
+	} else if (!enabled && stopTiming) {
+		stopTiming();
+		stopTiming = null;
+	}
+}
+
+function timeFrames(): () => void {
+	let frames: { duration: number; end: number }[] = [];
+	let last: number | null = null;
+	let handle = 0;
+
+	const tick = (now: number) => {
+		if (last !== null) frames.push({ duration: now - last, end: now });
+		last = now;
+		handle = requestAnimationFrame(tick);
+	};
+	const flush = () => {
+		if (frames.length === 0) return;
+		const now = performance.now();
+		const batch: FrameTime[] = frames.map((f) => ({
+			duration_ms: f.duration,
+			ago_ms: now - f.end
+		}));
+		frames = [];
+		reportFrameTimes(batch).catch(() => {});
+	};
+
+	handle = requestAnimationFrame(tick);
+	const timer = setInterval(flush, FLUSH_INTERVAL_MS);
+	return () => {
+		cancelAnimationFrame(handle);
+		clearInterval(timer);
+		flush();
+	};
+}
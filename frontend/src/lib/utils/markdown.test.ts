@@ -19,6 +19,46 @@ describe('renderMarkdown', () => {
 		expect(html).toContain('escaped-html');
 	});
 
+	it('points pasted media images at the media scheme', () => {
+		const hash = 'ab'.repeat(32);
+		const html = renderMarkdown(`![shot](media:${hash}) and ![x](media:../etc/passwd)`);
+		expect(html).toContain(`<img src="media://localhost/${hash}" alt="shot">`);
+		expect(html).not.toContain('data-media');
+		expect(html).not.toContain('passwd"');
+	});
+
+	it('highlights fenced code in known languages', () => {
+		const html = renderMarkdown('```rust\nfn main() {}\n```\n\n```\nplain\n```');
+		expect(html).toContain('<span class="tok-keyword">fn</span> main');
+		expect(html).toContain('<code>plain\n</code>');
+	});
+
+	it('renders callouts and document embeds', () => {
+		const html = renderMarkdown('> [!warning] Hot\n> Mind the **kettle**.\n\n![[document:abc]]\n\n> plain quote');
+		expect(html).toContain('<div class="callout callout-warning"><p class="callout-title">Hot</p>');
+		expect(html).toContain('<strong>kettle</strong>');
+		expect(html).toContain('<div class="doc-embed" data-embed="document:abc">');
+		expect(html).toContain('<blockquote>');
+	});
+
+	it('renders wiki-links and mentions as document links', () => {
+		const html = renderMarkdown(
+			'See [[Project Apollo]], [[Budget|the money]] and @research-notes.\n\nMail me@example.com, not `[[code]]`.'
+		);
+		expect(html).toContain('<a class="doc-link" data-doc-link="Project Apollo">Project Apollo</a>');
+		expect(html).toContain('<a class="doc-link" data-doc-link="Budget">the money</a>');
+		expect(html).toContain('<a class="doc-link" data-doc-link="research-notes">@research-notes</a>');
+		expect(html).toContain('href="mailto:me@example.com"');
+		expect(html).toContain('<code>[[code]]</code>');
+		expect(html).not.toContain('data-doc-link="example');
+	});
+
+	it('renders contact links as data attributes', () => {
+		const html = renderMarkdown('**Attendees:** [Ana Ruiz](contact:abc), ben@example.com');
+		expect(html).toContain('<a class="contact-link" data-contact="contact:abc">Ana Ruiz</a>');
+		expect(html).not.toContain('href="contact:');
+	});
+
 	it('strips javascript: URLs', () => {
 		const html = renderMarkdown('[click](javascript:alert(1))');
 		expect(html).not.toContain('javascript:');
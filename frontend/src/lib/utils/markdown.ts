@@ -1,5 +1,9 @@
-import { marked } from 'marked';
+import { marked, type Token } from 'marked';
 import DOMPurify from 'dompurify';
+import { MEDIA_REF, mediaUrl } from './media';
+import { highlightCode, escapeCode } from './codeHighlight';
+
+const CONTACT_REF = /^contact:[\w-]+$/;
 
 marked.use({
 	breaks: true,
@@ -11,10 +15,119 @@ marked.use({
 		html({ text }: { text: string }) {
 			const escaped = text.replace(/&/g, '&amp;').replace(/</g, '&lt;').replace(/>/g, '&gt;');
 			return `<span class="escaped-html">// ${escaped.trim()}</span>`;
+		},
+		// Fenced blocks in a language codeHighlight knows; the rest keep
+		// marked's plain rendering.
+		code({ text, lang }: { text: string; lang?: string }) {
+			const language = (lang ?? '').split(/\s/)[0];
+			const html = highlightCode(text, language);
+			if (html === null) return false;
+			return `<pre><code class="language-${escapeCode(language)}">${html}</code></pre>\n`;
+		},
+		// Pasted images (`media:<sha256>`). DOMPurify drops unknown URL
+		// schemes, so the hash rides in a data attribute and becomes a
+		// `src` after sanitizing. Other images render as usual.
+		image({ href, text }: { href: string; text: string }) {
+			const m = MEDIA_REF.exec(href);
+			if (!m) return false;
+			return `<img data-media="${m[1]}" alt="${escapeHtml(text)}">`;
+		},
+		// Links to a contact (`[Ana](contact:abc)`, as in meeting notes).
+		// Like images, the id rides in a data attribute; the document
+		// panel opens the contact panel on click.
+		link({ href, tokens }: { href: string; tokens: Token[] }) {
+			if (!CONTACT_REF.test(href)) return false;
+			return `<a class="contact-link" data-contact="${escapeHtml(href)}">${this.parser.parseInline(tokens)}</a>`;
 		}
 	}
 });
 
+// Callouts (`> [!NOTE] Title`) and document embeds (`![[document:…]]` on
+// a line of its own), the block syntax of `sovereign_sdk::blocks`. An
+// embed renders as a placeholder naming the id; the document panel
+// fills it in with the embedded document's title and opening lines.
+const CALLOUT = /^> \[!(note|tip|important|warning|caution)\][^\S\n]*([^\n]*)((?:\n>[^\n]*)*)(?:\n|$)/i;
+const EMBED = /^!\[\[(document:[^[\]\n]+)\]\](?:\n|$)/;
+// Links to other documents by title (see `docLinks`): `[[Title]]`,
+// `[[Title|shown text]]` and `@mention`. They render as anchors without
+// an href; the document panel resolves and opens them on click. An `@`
+// inside a word (an email address) starts no mention.
+const WIKI_LINK = /^\[\[([^[\]|\n]+)(?:\|([^[\]\n]*))?\]\]/;
+const MENTION = /^@([\p{L}\p{N}_-]*[\p{L}\p{N}])/u;
+const MENTION_START = /(^|[^\p{L}\p{N}_-])@[\p{L}\p{N}]/u;
+
+marked.use({
+	extensions: [
+		{
+			name: 'callout',
+			level: 'block',
+			start: (src: string) => src.match(/^> \[!/m)?.index,
+			tokenizer(src: string) {
+				const m = CALLOUT.exec(src);
+				if (!m) return undefined;
+				const body = m[3]
+					.split('\n')
+					.slice(1)
+					.map((l) => l.replace(/^> ?/, ''))
+					.join('\n');
+				return {
+					type: 'callout',
+					raw: m[0],
+					kind: m[1].toLowerCase(),
+					title: m[2].trim(),
+					tokens: this.lexer.blockTokens(body, [])
+				};
+			},
+			renderer(token) {
+				const kind = token.kind as string;
+				const title = (token.title as string) || kind[0].toUpperCase() + kind.slice(1);
+				return `<div class="callout callout-${kind}"><p class="callout-title">${escapeHtml(title)}</p>${this.parser.parse(token.tokens ?? [])}</div>\n`;
+			}
+		},
+		{
+			name: 'embed',
+			level: 'block',
+			start: (src: string) => src.match(/^!\[\[document:/m)?.index,
+			tokenizer(src: string) {
+				const m = EMBED.exec(src);
+				if (!m) return undefined;
+				return { type: 'embed', raw: m[0], id: m[1] };
+			},
+			renderer(token) {
+				const id = escapeHtml(token.id as string);
+				return `<div class="doc-embed" data-embed="${id}"><span class="doc-embed-title">${id}</span></div>\n`;
+			}
+		},
+		{
+			name: 'docLink',
+			level: 'inline',
+			start(src: string) {
+				const wiki = src.indexOf('[[');
+				const m = MENTION_START.exec(src);
+				const mention = m ? m.index + m[1].length : -1;
+				const starts = [wiki, mention].filter((i) => i >= 0);
+				return starts.length ? Math.min(...starts) : undefined;
+			},
+			tokenizer(src: string) {
+				const wiki = WIKI_LINK.exec(src);
+				if (wiki && wiki[1].trim() && !wiki[1].trim().startsWith('document:')) {
+					const target = wiki[1].trim();
+					return { type: 'docLink', raw: wiki[0], target, text: wiki[2] ?? target };
+				}
+				const mention = MENTION.exec(src);
+				if (mention) {
+					return { type: 'docLink', raw: mention[0], target: mention[1], text: mention[0] };
+				}
+				return undefined;
+			},
+			renderer(token) {
+				const target = escapeHtml(token.target as string);
+				return `<a class="doc-link" data-doc-link="${target}">${escapeHtml(token.text as string)}</a>`;
+			}
+		}
+	]
+});
+
 // AI- and document-sourced markdown can contain links to arbitrary origins.
 // Without this hook a click navigates the MAIN app webview (which holds the
 // IPC bridge) off-app — a phishing/UI-spoof vector. Force external links into
@@ -63,7 +176,10 @@ export function renderMarkdown(text: string): string {
 		html = `<pre class="markdown-fallback">${escapeHtml(input)}</pre>`;
 	}
 
-	const clean = DOMPurify.sanitize(html);
+	const clean = DOMPurify.sanitize(html).replace(
+		/<img data-media="([0-9a-f]{64})"/g,
+		(_, hash: string) => `<img src="${mediaUrl(hash)}"`
+	);
 	// The note is trusted static markup appended AFTER sanitization.
 	return truncated
 		? `${clean}<p class="markdown-truncated"><em>… content truncated for display</em></p>`
@@ -0,0 +1,60 @@
+/** Pasted images: clipboard → blob store → `media:<sha256>` references.
+ *
+ * Document bodies refer to a pasted image by hash so the reference is the
+ * same on every device; the webview loads it through the `media` URI
+ * scheme the backend registers. */
+
+import { convertFileSrc } from '@tauri-apps/api/core';
+
+/** `media:<sha256>` as written in a document body. */
+export const MEDIA_REF = /^media:([0-9a-f]{64})$/;
+
+/** URL the webview loads a stored image from. */
+export function mediaUrl(hash: string): string {
+	try {
+		// Resolves to media://localhost/… or http://media.localhost/…
+		// depending on the platform's custom-scheme support.
+		return convertFileSrc(hash, 'media');
+	} catch {
+		// Outside Tauri (tests).
+		return `media://localhost/${hash}`;
+	}
+}
+
+/** Markdown for a pasted image. */
+export function mediaMarkdown(hash: string, alt = 'Pasted image'): string {
+	return `![${alt}](media:${hash})`;
+}
+
+/** The first image on the clipboard, if any. */
+export function clipboardImage(data: DataTransfer | null): File | null {
+	for (const item of Array.from(data?.items ?? [])) {
+		if (item.kind === 'file' && item.type.startsWith('image/')) {
+			return item.getAsFile();
+		}
+	}
+	return null;
+}
+
+export async function fileToBase64(file: Blob): Promise<string> {
+	const bytes = new Uint8Array(await file.arrayBuffer());
+	let binary = '';
+	// Chunked: String.fromCharCode(...bytes) overflows the stack on
+	// screenshot-sized arrays.
+	for (let i = 0; i < bytes.length; i += 0x8000) {
+		binary += String.fromCharCode(...bytes.subarray(i, i + 0x8000));
+	}
+	return btoa(binary);
+}
+
+/** Hand bytes to the webview's download / save-as flow. */
+export function saveBase64(dataBase64: string, fileName: string, mime: string) {
+	const bytes = Uint8Array.from(atob(dataBase64), (c) => c.charCodeAt(0));
+	const blob = new Blob([bytes], { type: mime || 'application/octet-stream' });
+	const url = URL.createObjectURL(blob);
+	const a = document.createElement('a');
+	a.href = url;
+	a.download = fileName || 'download';
+	a.click();
+	URL.revokeObjectURL(url);
+}
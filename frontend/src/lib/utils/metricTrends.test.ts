@@ -0,0 +1,43 @@
+import { describe, expect, it } from 'vitest';
+import type { DayStats, MetricTrend } from '$lib/api/commands';
+import { formatMs, groupMetrics, metricCategory, sparklinePoints } from './metricTrends';
+
+function day(date: string, p50_ms: number, count = 1): DayStats {
+	return { date, count, mean_ms: p50_ms, p50_ms, p95_ms: p50_ms * 2, max_ms: p50_ms * 3 };
+}
+
+function trend(name: string, count: number): MetricTrend {
+	return { name, days: [day('2026-10-01', 1, count)] };
+}
+
+describe('metric trends', () => {
+	it('takes the category from the name prefix', () => {
+		expect(metricCategory('db.get_document')).toBe('db');
+		expect(metricCategory('startup')).toBe('startup');
+	});
+
+	it('groups known categories first, busiest metric first', () => {
+		const groups = groupMetrics([
+			trend('zz.other', 1),
+			trend('db.get_document', 2),
+			trend('render.frame', 1),
+			trend('db.list_documents', 9)
+		]);
+		expect(groups.map((g) => g.category)).toEqual(['render', 'db', 'zz']);
+		expect(groups[1].label).toBe('Database queries');
+		expect(groups[1].metrics.map((m) => m.name)).toEqual(['db.list_documents', 'db.get_document']);
+	});
+
+	it('scales sparklines to the slowest day', () => {
+		const days = [day('2026-10-01', 5), day('2026-10-02', 10), day('2026-10-03', 0)];
+		expect(sparklinePoints(days, (d) => d.p50_ms, 100, 20)).toBe('0,10 50,0 100,20');
+		expect(sparklinePoints([days[0]], (d) => d.p50_ms, 100, 20)).toBe('0,0 100,0');
+		expect(sparklinePoints([], (d) => d.p50_ms, 100, 20)).toBe('');
+	});
+
+	it('formats durations for their size', () => {
+		expect(formatMs(0.4219)).toBe('0.42 ms');
+		expect(formatMs(12.3)).toBe('12 ms');
+		expect(formatMs(1840)).toBe('1.8 s');
+	});
+});
@@ -0,0 +1,82 @@
+/** Shaping local metrics (`get_metrics`) for the diagnostics panel:
+ *  grouping by what was measured, and sparkline points for a trend. */
+
+import type { DayStats, MetricTrend } from '$lib/api/commands';
+
+export interface MetricGroup {
+	category: string;
+	label: string;
+	metrics: MetricTrend[];
+}
+
+const CATEGORIES: Record<string, string> = {
+	render: 'Rendering',
+	db: 'Database queries',
+	llm: 'Language model',
+	sync: 'Sync'
+};
+
+/** `db.get_document` → `db`; names without a dot are their own category. */
+export function metricCategory(name: string): string {
+	const dot = name.indexOf('.');
+	return dot < 0 ? name : name.slice(0, dot);
+}
+
+/** Group metrics by category, known categories first in a fixed order,
+ *  and within a group the most frequent metric first. */
+export function groupMetrics(trends: MetricTrend[]): MetricGroup[] {
+	const groups = new Map<string, MetricTrend[]>();
+	for (const t of trends) {
+		const category = metricCategory(t.name);
+		const list = groups.get(category) ?? [];
+		list.push(t);
+		groups.set(category, list);
+	}
+	const order = Object.keys(CATEGORIES);
+	const rank = (c: string) => (order.includes(c) ? order.indexOf(c) : order.length);
+	return [...groups.entries()]
+		.sort(([a], [b]) => rank(a) - rank(b) || a.localeCompare(b))
+		.map(([category, metrics]) => ({
+			category,
+			label: CATEGORIES[category] ?? category,
+			metrics: metrics.sort((a, b) => totalCount(b) - totalCount(a) || a.name.localeCompare(b.name))
+		}));
+}
+
+export function totalCount(trend: MetricTrend): number {
+	return trend.days.reduce((n, d) => n + d.count, 0);
+}
+
+/** The most recent day, or null for a metric with no days. */
+export function latestDay(trend: MetricTrend): DayStats | null {
+	return trend.days[trend.days.length - 1] ?? null;
+}
+
+/** SVG polyline points for `pick` across the days, scaled to fill
+ *  `width` x `height` with the slowest day at the top. A single day is
+ *  drawn as a flat line. */
+export function sparklinePoints(
+	days: DayStats[],
+	pick: (d: DayStats) => number,
+	width: number,
+	height: number
+): string {
+	if (days.length === 0) return '';
+	const values = days.map(pick);
+	const max = Math.max(...values);
+	const y = (v: number) => (max === 0 ? height : height - (v / max) * height);
+	if (values.length === 1) return `0,${y(values[0])} ${width},${y(values[0])}`;
+	const step = width / (values.length - 1);
+	return values.map((v, i) => `${round(i * step)},${round(y(v))}`).join(' ');
+}
+
+/** `0.42` → `0.42 ms`, `12.3` → `12 ms`, `1840` → `1.8 s`. */
+export function formatMs(ms: number): string {
+	if (ms >= 1000) return `${(ms / 1000).toFixed(1)} s`;
+	if (ms >= 10) return `${Math.round(ms)} ms`;
+	return `${ms.toFixed(2)} ms`;
+}
+
+function round(n: number): number {
+	return Math.round(n * 10) / 10;
+}
@@ -0,0 +1,55 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import { mockTauriCommand } from '$lib/test/tauri';
+import { secureCopy, clearSecureClipboard } from './secureClipboard';
+
+let clipboardText = '';
+
+beforeEach(() => {
+	vi.useFakeTimers();
+	clipboardText = '';
+	Object.defineProperty(navigator, 'clipboard', {
+		configurable: true,
+		value: {
+			writeText: vi.fn(async (t: string) => {
+				clipboardText = t;
+			}),
+			readText: vi.fn(async () => clipboardText)
+		}
+	});
+});
+
+afterEach(() => {
+	vi.useRealTimers();
+});
+
+describe('secureCopy', () => {
+	it('uses the native command when available', async () => {
+		const calls: unknown[] = [];
+		mockTauriCommand('secure_copy', (args) => {
+			calls.push(args);
+		});
+		await secureCopy('alpha bravo', 10_000);
+		expect(calls).toEqual([{ text: 'alpha bravo', clearAfterSecs: 10 }]);
+		expect(clipboardText).toBe('');
+	});
+
+	it('falls back to the Clipboard API and clears after the timeout', async () => {
+		await secureCopy('alpha bravo', 5_000);
+		expect(clipboardText).toBe('alpha bravo');
+		await vi.advanceTimersByTimeAsync(5_000);
+		expect(clipboardText).toBe('');
+	});
+
+	it('leaves text the user copied afterwards alone', async () => {
+		await secureCopy('alpha bravo', 5_000);
+		clipboardText = 'something else';
+		await vi.advanceTimersByTimeAsync(5_000);
+		expect(clipboardText).toBe('something else');
+	});
+
+	it('clears immediately on demand', async () => {
+		await secureCopy('alpha bravo');
+		await clearSecureClipboard();
+		expect(clipboardText).toBe('');
+	});
+});
@@ -0,0 +1,55 @@
+/** Copying secrets — recovery phrases, revealed PII, pairing offers.
+ *
+ * Goes through the `secure_copy` command, which hides the copy from
+ * clipboard managers where the platform allows and clears it after a
+ * timeout (and on session lock). Where there is no native clipboard
+ * (mobile), falls back to the Clipboard API and clears the text after the
+ * same timeout if it is still there. */
+import { secureCopyCmd, clearSecureClipboardCmd } from '$lib/api/commands';
+
+/** How long a copied secret stays on the clipboard. */
+export const SECURE_CLIPBOARD_CLEAR_MS = 30_000;
+
+let fallbackCopied: string | null = null;
+let fallbackTimer: ReturnType<typeof setTimeout> | null = null;
+
+export async function secureCopy(text: string, clearAfterMs = SECURE_CLIPBOARD_CLEAR_MS): Promise<void> {
+	try {
+		await secureCopyCmd(text, Math.ceil(clearAfterMs / 1000));
+		return;
+	} catch {
+		/* no native clipboard on this platform */
+	}
+	await navigator.clipboard.writeText(text);
+	fallbackCopied = text;
+	if (fallbackTimer !== null) clearTimeout(fallbackTimer);
+	fallbackTimer = setTimeout(clearFallback, clearAfterMs);
+}
+
+/** Clear a secret copied with `secureCopy` if it is still on the clipboard. */
+export async function clearSecureClipboard(): Promise<void> {
+	if (fallbackTimer !== null) {
+		clearTimeout(fallbackTimer);
+		fallbackTimer = null;
+	}
+	await clearFallback();
+	try {
+		await clearSecureClipboardCmd();
+	} catch {
+		/* nothing native to clear */
+	}
+}
+
+async function clearFallback(): Promise<void> {
+	const copied = fallbackCopied;
+	fallbackCopied = null;
+	fallbackTimer = null;
+	if (copied === null) return;
+	try {
+		if ((await navigator.clipboard.readText()) === copied) {
+			await navigator.clipboard.writeText('');
+		}
+	} catch {
+		/* clipboard read denied — best effort */
+	}
+}
@@ -0,0 +1,98 @@
+import { describe, expect, it } from 'vitest';
+import {
+	SHORTCUTS,
+	parseCombo,
+	eventCombo,
+	resolveKeymap,
+	keymapOverrides,
+	findConflicts,
+	matchShortcut,
+	displayCombo
+} from './shortcuts';
+
+function key(k: string, mods: { ctrl?: boolean; meta?: boolean; alt?: boolean; shift?: boolean } = {}) {
+	return {
+		key: k,
+		ctrlKey: !!mods.ctrl,
+		metaKey: !!mods.meta,
+		altKey: !!mods.alt,
+		shiftKey: !!mods.shift
+	};
+}
+
+describe('parseCombo', () => {
+	it('normalizes case and modifier order', () => {
+		expect(parseCombo('shift+ctrl+z')).toBe('Ctrl+Shift+Z');
+		expect(parseCombo(' Alt+f2 ')).toBe('Alt+F2');
+		expect(parseCombo('Ctrl+ ')).toBeNull();
+	});
+
+	it('drops Shift for punctuation', () => {
+		expect(parseCombo('Shift+?')).toBe('?');
+	});
+
+	it('keeps an empty combo as unbound', () => {
+		expect(parseCombo('')).toBe('');
+	});
+
+	it('rejects unknown or repeated modifiers', () => {
+		expect(parseCombo('Hyper+K')).toBeNull();
+		expect(parseCombo('Ctrl+Ctrl+K')).toBeNull();
+		expect(parseCombo('Ctrl+')).toBeNull();
+	});
+});
+
+describe('eventCombo', () => {
+	it('spells key presses as combos', () => {
+		expect(eventCombo(key('f', { ctrl: true }))).toBe('Ctrl+F');
+		expect(eventCombo(key('z', { meta: true, shift: true }))).toBe('Ctrl+Shift+Z');
+		expect(eventCombo(key('?', { shift: true }))).toBe('?');
+		expect(eventCombo(key(' ', { ctrl: true }))).toBe('Ctrl+Space');
+		expect(eventCombo(key('Shift', { shift: true }))).toBeNull();
+	});
+});
+
+describe('keymaps', () => {
+	it('applies valid overrides over the defaults', () => {
+		const km = resolveKeymap({ search: 'ctrl+k', inbox: '', lock: 'Hyper+L', unknown: 'Ctrl+U' });
+		expect(km.search).toBe('Ctrl+K');
+		expect(km.inbox).toBe('');
+		expect(km.lock).toBe('Ctrl+L');
+		expect(km).not.toHaveProperty('unknown');
+		expect(Object.keys(km)).toHaveLength(SHORTCUTS.length);
+	});
+
+	it('saves only what differs from the defaults', () => {
+		const km = resolveKeymap({ search: 'Ctrl+K' });
+		km.inbox = '';
+		expect(keymapOverrides(km)).toEqual({ search: 'Ctrl+K', inbox: '' });
+		expect(keymapOverrides(resolveKeymap())).toEqual({});
+	});
+
+	it('has no conflicts by default', () => {
+		expect(findConflicts(resolveKeymap())).toEqual([]);
+	});
+
+	it('reports duplicates within a scope only', () => {
+		// doc.find shares Ctrl+F with global search by design.
+		const km = resolveKeymap({ chat: 'Ctrl+F', 'doc.save': 'Ctrl+H' });
+		expect(findConflicts(km)).toEqual([
+			{ combo: 'Ctrl+F', scope: 'global', ids: ['search', 'chat'] },
+			{ combo: 'Ctrl+H', scope: 'document', ids: ['doc.save', 'doc.replace'] }
+		]);
+	});
+
+	it('matches key presses to actions by scope', () => {
+		const km = resolveKeymap({ search: 'Ctrl+K' });
+		expect(matchShortcut(km, key('k', { ctrl: true }), 'global')?.id).toBe('search');
+		expect(matchShortcut(km, key('f', { ctrl: true }), 'global')).toBeNull();
+		expect(matchShortcut(km, key('f', { ctrl: true }), 'document')?.id).toBe('doc.find');
+		expect(matchShortcut(km, key('i'), 'global')?.id).toBe('inbox');
+	});
+
+	it('shows macOS symbols on request', () => {
+		expect(displayCombo('Ctrl+Shift+Z', true)).toBe('⌘⇧Z');
+		expect(displayCombo('Ctrl+Shift+Z')).toBe('Ctrl+Shift+Z');
+		expect(displayCombo('')).toBe('—');
+	});
+});
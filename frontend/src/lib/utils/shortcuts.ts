@@ -0,0 +1,143 @@
+/** Keyboard shortcut definitions, combo parsing and conflict detection.
+ *
+ * A combo is written `Ctrl+Alt+Shift+<key>`, modifiers in that order:
+ * letters upper-case (`Ctrl+F`), named keys as the browser reports them
+ * (`Enter`, `F2`, `ArrowUp`), `Space` for the space bar, and punctuation
+ * as typed (`?`). Ctrl also matches ⌘ on macOS. Shift is implied by the
+ * character for punctuation, so `?` needs no `Shift+`.
+ *
+ * The user's overrides (`ui.shortcuts` in the config) map action ids to
+ * combos; an empty combo unbinds the action.
+ */
+
+/** Where an action fires. Document bindings apply inside a document
+ *  panel and take precedence there, so they only conflict with each other. */
+export type ShortcutScope = 'global' | 'document';
+
+export interface ShortcutDef {
+	id: string;
+	label: string;
+	scope: ShortcutScope;
+	default: string;
+	/** Also fires while typing in a text field. */
+	inInputs?: boolean;
+}
+
+export const SHORTCUTS: ShortcutDef[] = [
+	{ id: 'search', label: 'Search', scope: 'global', default: 'Ctrl+F', inInputs: true },
+	{ id: 'chat', label: 'Toggle chat', scope: 'global', default: 'Ctrl+N', inInputs: true },
+	{ id: 'lock', label: 'Lock session', scope: 'global', default: 'Ctrl+L', inInputs: true },
+	{ id: 'browser', label: 'Toggle browser', scope: 'global', default: 'Ctrl+B', inInputs: true },
+	{ id: 'undo', label: 'Undo', scope: 'global', default: 'Ctrl+Z' },
+	{ id: 'redo', label: 'Redo', scope: 'global', default: 'Ctrl+Shift+Z' },
+	{ id: 'inbox', label: 'Toggle inbox', scope: 'global', default: 'I' },
+	{ id: 'pii_dashboard', label: 'Toggle PII dashboard', scope: 'global', default: 'P' },
+	{ id: 'shortcuts', label: 'Show shortcuts', scope: 'global', default: '?' },
+	{ id: 'doc.save', label: 'Save document', scope: 'document', default: 'Ctrl+S' },
+	{ id: 'doc.find', label: 'Find in document', scope: 'document', default: 'Ctrl+F' },
+	{ id: 'doc.replace', label: 'Find and replace', scope: 'document', default: 'Ctrl+H' }
+];
+
+/** Action id → combo ('' when unbound). */
+export type Keymap = Record<string, string>;
+
+const MODIFIERS = ['Ctrl', 'Alt', 'Shift'] as const;
+const MODIFIER_KEYS = new Set(['Control', 'Alt', 'Shift', 'Meta', 'AltGraph', 'CapsLock', 'OS']);
+
+/** Letters and named keys take an explicit Shift; punctuation doesn't. */
+function shiftMatters(key: string): boolean {
+	return /^[A-Z0-9]$/.test(key) || key.length > 1;
+}
+
+function normalizeKey(key: string): string {
+	if (key === ' ') return 'Space';
+	return key.length === 1 ? key.toUpperCase() : key[0].toUpperCase() + key.slice(1);
+}
+
+/** Normalize a written combo, or null if it isn't one. '' stays ''. */
+export function parseCombo(combo: string): string | null {
+	const text = combo.trim();
+	if (text === '') return '';
+	const parts = text.split('+');
+	const rawKey = parts.pop() ?? '';
+	if (!rawKey || /\s/.test(rawKey)) return null;
+	const mods = new Set<string>();
+	for (const part of parts) {
+		const m = MODIFIERS.find((x) => x.toLowerCase() === part.trim().toLowerCase());
+		if (!m || mods.has(m)) return null;
+		mods.add(m);
+	}
+	const key = normalizeKey(rawKey);
+	if (!shiftMatters(key)) mods.delete('Shift');
+	return [...MODIFIERS.filter((m) => mods.has(m)), key].join('+');
+}
+
+/** The combo a key press spells, or null for a bare modifier press. */
+export function eventCombo(e: Pick<KeyboardEvent, 'key' | 'ctrlKey' | 'metaKey' | 'altKey' | 'shiftKey'>): string | null {
+	if (MODIFIER_KEYS.has(e.key)) return null;
+	const key = normalizeKey(e.key);
+	const mods: string[] = [];
+	if (e.ctrlKey || e.metaKey) mods.push('Ctrl');
+	if (e.altKey) mods.push('Alt');
+	if (e.shiftKey && shiftMatters(key)) mods.push('Shift');
+	return [...mods, key].join('+');
+}
+
+/** Defaults with the user's overrides applied. Unknown ids and combos
+ *  that don't parse are ignored. */
+export function resolveKeymap(overrides: Record<string, string> = {}): Keymap {
+	const keymap: Keymap = {};
+	for (const def of SHORTCUTS) {
+		const override = def.id in overrides ? parseCombo(overrides[def.id]) : null;
+		keymap[def.id] = override ?? def.default;
+	}
+	return keymap;
+}
+
+/** Only the bindings that differ from the defaults — what gets saved. */
+export function keymapOverrides(keymap: Keymap): Record<string, string> {
+	const overrides: Record<string, string> = {};
+	for (const def of SHORTCUTS) {
+		const combo = keymap[def.id] ?? def.default;
+		if (combo !== def.default) overrides[def.id] = combo;
+	}
+	return overrides;
+}
+
+export interface ShortcutConflict {
+	combo: string;
+	scope: ShortcutScope;
+	ids: string[];
+}
+
+/** Combos bound to more than one action in the same scope. */
+export function findConflicts(keymap: Keymap): ShortcutConflict[] {
+	const byCombo = new Map<string, ShortcutConflict>();
+	for (const def of SHORTCUTS) {
+		const combo = keymap[def.id];
+		if (!combo) continue;
+		const k = `${def.scope} ${combo}`;
+		const entry = byCombo.get(k) ?? { combo, scope: def.scope, ids: [] };
+		entry.ids.push(def.id);
+		byCombo.set(k, entry);
+	}
+	return [...byCombo.values()].filter((c) => c.ids.length > 1);
+}
+
+/** The action of `scope` a key press triggers, if any. */
+export function matchShortcut(
+	keymap: Keymap,
+	e: Pick<KeyboardEvent, 'key' | 'ctrlKey' | 'metaKey' | 'altKey' | 'shiftKey'>,
+	scope: ShortcutScope
+): ShortcutDef | null {
+	const combo = eventCombo(e);
+	if (!combo) return null;
+	return SHORTCUTS.find((d) => d.scope === scope && keymap[d.id] === combo) ?? null;
+}
+
+/** `Ctrl+Shift+Z` → `⌘⇧Z` on macOS; unchanged elsewhere. */
+export function displayCombo(combo: string, mac = false): string {
+	if (!combo) return '—';
+	if (!mac) return combo;
+	return combo.replace('Ctrl+', '⌘').replace('Alt+', '⌥').replace('Shift+', '⇧');
+}
@@ -0,0 +1,80 @@
+import { describe, expect, it } from 'vitest';
+import {
+	combineProgress,
+	formatBytes,
+	formatDuration,
+	previewSummary,
+	type SyncPreview,
+	type SyncProgress
+} from './syncProgress';
+
+function session(over: Partial<SyncProgress>): SyncProgress {
+	return {
+		docs_to_pull: 0,
+		docs_to_push: 0,
+		rows_to_pull: 0,
+		rows_to_push: 0,
+		commits_received: 0,
+		commits_sent: 0,
+		bytes_received: 0,
+		bytes_sent: 0,
+		requests_done: 0,
+		requests_total: 0,
+		elapsed_ms: 0,
+		eta_ms: null,
+		...over
+	};
+}
+
+describe('sync progress', () => {
+	it('is null with no running session', () => {
+		expect(combineProgress([])).toBeNull();
+	});
+
+	it('combines sessions by requests and keeps the slowest ETA', () => {
+		const overview = combineProgress([
+			session({ requests_done: 1, requests_total: 4, bytes_received: 100, commits_received: 2, eta_ms: 3000 }),
+			session({ requests_done: 3, requests_total: 4, bytes_sent: 50, commits_sent: 1, eta_ms: 500 })
+		]);
+		expect(overview).toEqual({ fraction: 0.5, bytes: 150, commits: 3, etaMs: 3000 });
+	});
+
+	it('has no ETA while a session has not resolved a request', () => {
+		const overview = combineProgress([
+			session({ requests_total: 1 }),
+			session({ requests_done: 1, requests_total: 2, eta_ms: 100 })
+		]);
+		expect(overview?.etaMs).toBeNull();
+	});
+
+	it('formats sizes and durations', () => {
+		expect(formatBytes(512)).toBe('512 B');
+		expect(formatBytes(1536)).toBe('1.5 KB');
+		expect(formatBytes(5 * 1024 * 1024)).toBe('5.0 MB');
+		expect(formatDuration(400)).toBe('under a second');
+		expect(formatDuration(42_000)).toBe('42s');
+		expect(formatDuration(125_000)).toBe('2m 5s');
+		expect(formatDuration(120_000)).toBe('2m');
+	});
+
+	it('summarizes a sync preview', () => {
+		const none: SyncPreview = {
+			docs_to_send: 0,
+			docs_to_receive: 0,
+			conflicts: 0,
+			rows_to_send: 0,
+			rows_to_receive: 0,
+			blobs_to_send: 0,
+			blobs_to_receive: 0,
+			bytes_to_send: 0,
+			bytes_to_receive: 0
+		};
+		expect(previewSummary(none)).toBe('Already in sync');
+		expect(
+			previewSummary({ ...none, docs_to_send: 2, rows_to_send: 1, bytes_to_send: 5 * 1024 * 1024 })
+		).toBe('Send 2 documents, 1 record (~5.0 MB)');
+		expect(previewSummary({ ...none, docs_to_send: 1, blobs_to_receive: 3, conflicts: 1 })).toBe(
+			'Send 1 document · receive 3 media files · 1 conflicting edit to review'
+		);
+	});
+});
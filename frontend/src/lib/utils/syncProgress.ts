@@ -0,0 +1,109 @@
+/** Sync progress arithmetic for the `sync-progress` event: combining
+ *  concurrent sessions into one indicator and formatting sizes / ETAs.
+ *  Also summarizes a `sync-preview` (what a sync would move).
+ *
+ *  The backend's ETA extrapolates from requests resolved so far, and a
+ *  session's request count grows as the manifest diff fans out, so the
+ *  fraction can step backwards; callers show it as a hint, not a promise.
+ */
+
+/** One session's state, as carried by `sync-progress`. */
+export interface SyncProgress {
+	docs_to_pull: number;
+	docs_to_push: number;
+	rows_to_pull: number;
+	rows_to_push: number;
+	commits_received: number;
+	commits_sent: number;
+	bytes_received: number;
+	bytes_sent: number;
+	requests_done: number;
+	requests_total: number;
+	elapsed_ms: number;
+	eta_ms: number | null;
+}
+
+export interface SyncOverview {
+	/** 0..1 across every running session. */
+	fraction: number;
+	bytes: number;
+	commits: number;
+	/** Slowest session's estimate; null while any is still unknown. */
+	etaMs: number | null;
+}
+
+/** Combine the running sessions into one indicator. */
+export function combineProgress(sessions: SyncProgress[]): SyncOverview | null {
+	if (sessions.length === 0) return null;
+	let done = 0;
+	let total = 0;
+	let bytes = 0;
+	let commits = 0;
+	let etaMs: number | null = 0;
+	for (const s of sessions) {
+		done += s.requests_done;
+		total += s.requests_total;
+		bytes += s.bytes_received + s.bytes_sent;
+		commits += s.commits_received + s.commits_sent;
+		etaMs = etaMs === null || s.eta_ms === null ? null : Math.max(etaMs, s.eta_ms);
+	}
+	return { fraction: total === 0 ? 0 : Math.min(1, done / total), bytes, commits, etaMs };
+}
+
+/** What syncing with one device would move, as carried by `sync-preview`.
+ *  Byte totals are estimates and leave out media. */
+export interface SyncPreview {
+	docs_to_send: number;
+	docs_to_receive: number;
+	conflicts: number;
+	rows_to_send: number;
+	rows_to_receive: number;
+	blobs_to_send: number;
+	blobs_to_receive: number;
+	bytes_to_send: number;
+	bytes_to_receive: number;
+}
+
+function plural(n: number, word: string): string {
+	return `${n} ${word}${n === 1 ? '' : 's'}`;
+}
+
+function direction(docs: number, rows: number, blobs: number, bytes: number): string {
+	const parts = [];
+	if (docs) parts.push(plural(docs, 'document'));
+	if (rows) parts.push(plural(rows, 'record'));
+	if (blobs) parts.push(plural(blobs, 'media file'));
+	return bytes ? `${parts.join(', ')} (~${formatBytes(bytes)})` : parts.join(', ');
+}
+
+/** One-line summary of a preview, e.g. "Send 2 documents (~4.0 KB) ·
+ *  receive 1 media file". */
+export function previewSummary(p: SyncPreview): string {
+	const send = direction(p.docs_to_send, p.rows_to_send, p.blobs_to_send, p.bytes_to_send);
+	const receive = direction(
+		p.docs_to_receive,
+		p.rows_to_receive,
+		p.blobs_to_receive,
+		p.bytes_to_receive
+	);
+	const parts = [];
+	if (send) parts.push(`Send ${send}`);
+	if (receive) parts.push(`${parts.length ? 'receive' : 'Receive'} ${receive}`);
+	if (p.conflicts) parts.push(`${plural(p.conflicts, 'conflicting edit')} to review`);
+	return parts.length ? parts.join(' · ') : 'Already in sync';
+}
+
+export function formatBytes(n: number): string {
+	if (n < 1024) return `${n} B`;
+	if (n < 1024 * 1024) return `${(n / 1024).toFixed(1)} KB`;
+	if (n < 1024 * 1024 * 1024) return `${(n / (1024 * 1024)).toFixed(1)} MB`;
+	return `${(n / (1024 * 1024 * 1024)).toFixed(2)} GB`;
+}
+
+export function formatDuration(ms: number): string {
+	if (ms < 1000) return 'under a second';
+	const s = Math.round(ms / 1000);
+	if (s < 60) return `${s}s`;
+	const m = Math.floor(s / 60);
+	return s % 60 === 0 ? `${m}m` : `${m}m ${s % 60}s`;
+}
@@ -0,0 +1,37 @@
+import { describe, expect, it } from 'vitest';
+import { diffLines, diffStats, toSplitRows } from './textDiff';
+
+describe('diffLines', () => {
+	it('marks an edited line as removed then added', () => {
+		expect(diffLines('a\nb\nc', 'a\nB\nc')).toEqual([
+			{ kind: 'same', text: 'a' },
+			{ kind: 'removed', text: 'b' },
+			{ kind: 'added', text: 'B' },
+			{ kind: 'same', text: 'c' }
+		]);
+	});
+
+	it('keeps lines common to both sides around insertions', () => {
+		const lines = diffLines('one\ntwo\nthree', 'zero\none\ntwo\nthree\nfour');
+		expect(lines.map((l) => l.kind)).toEqual(['added', 'same', 'same', 'same', 'added']);
+		expect(diffStats(lines)).toEqual({ added: 2, removed: 0 });
+	});
+
+	it('handles empty sides', () => {
+		expect(diffLines('', '')).toEqual([]);
+		expect(diffLines('', 'x')).toEqual([{ kind: 'added', text: 'x' }]);
+		expect(diffLines('x', '')).toEqual([{ kind: 'removed', text: 'x' }]);
+	});
+});
+
+describe('toSplitRows', () => {
+	it('pairs a removed run with the added run that replaced it', () => {
+		const rows = toSplitRows(diffLines('a\nb\nc\nd', 'a\nX\nd'));
+		expect(rows).toEqual([
+			{ left: { kind: 'same', text: 'a' }, right: { kind: 'same', text: 'a' } },
+			{ left: { kind: 'removed', text: 'b' }, right: { kind: 'added', text: 'X' } },
+			{ left: { kind: 'removed', text: 'c' }, right: null },
+			{ left: { kind: 'same', text: 'd' }, right: { kind: 'same', text: 'd' } }
+		]);
+	});
+});
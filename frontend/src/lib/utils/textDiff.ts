@@ -0,0 +1,105 @@
+/** Line diff for the document history view.
+ *
+ * A plain longest-common-subsequence over lines: documents are short
+ * enough that the O(n·m) table is cheap, and the output reads the way
+ * people expect (unchanged lines kept, edits shown as removed + added).
+ * Common leading and trailing lines are trimmed before the table is
+ * built, so a small edit to a long document stays fast. */
+
+export type DiffKind = 'same' | 'added' | 'removed';
+
+export interface DiffLine {
+	kind: DiffKind;
+	text: string;
+}
+
+/** One row of the side-by-side view. A `null` cell is a gap. */
+export interface SplitRow {
+	left: DiffLine | null;
+	right: DiffLine | null;
+}
+
+function splitLines(text: string): string[] {
+	return text === '' ? [] : text.split('\n');
+}
+
+/** Lines of `before` and `after`, marked as kept, removed or added. */
+export function diffLines(before: string, after: string): DiffLine[] {
+	const a = splitLines(before);
+	const b = splitLines(after);
+
+	let start = 0;
+	while (start < a.length && start < b.length && a[start] === b[start]) start++;
+	let endA = a.length;
+	let endB = b.length;
+	while (endA > start && endB > start && a[endA - 1] === b[endB - 1]) {
+		endA--;
+		endB--;
+	}
+
+	const midA = a.slice(start, endA);
+	const midB = b.slice(start, endB);
+	const n = midA.length;
+	const m = midB.length;
+	// lcs[i][j] = LCS length of midA[i..] and midB[j..]
+	const lcs: number[][] = Array.from({ length: n + 1 }, () => new Array(m + 1).fill(0));
+	for (let i = n - 1; i >= 0; i--) {
+		for (let j = m - 1; j >= 0; j--) {
+			lcs[i][j] =
+				midA[i] === midB[j] ? lcs[i + 1][j + 1] + 1 : Math.max(lcs[i + 1][j], lcs[i][j + 1]);
+		}
+	}
+
+	const out: DiffLine[] = a.slice(0, start).map((text) => ({ kind: 'same', text }));
+	let i = 0;
+	let j = 0;
+	while (i < n && j < m) {
+		if (midA[i] === midB[j]) {
+			out.push({ kind: 'same', text: midA[i] });
+			i++;
+			j++;
+		} else if (lcs[i + 1][j] >= lcs[i][j + 1]) {
+			out.push({ kind: 'removed', text: midA[i++] });
+		} else {
+			out.push({ kind: 'added', text: midB[j++] });
+		}
+	}
+	while (i < n) out.push({ kind: 'removed', text: midA[i++] });
+	while (j < m) out.push({ kind: 'added', text: midB[j++] });
+	for (const text of a.slice(endA)) out.push({ kind: 'same', text });
+	return out;
+}
+
+/** Pair removed and added runs into rows for a two-column view. */
+export function toSplitRows(lines: DiffLine[]): SplitRow[] {
+	const rows: SplitRow[] = [];
+	let k = 0;
+	while (k < lines.length) {
+		if (lines[k].kind === 'same') {
+			rows.push({ left: lines[k], right: lines[k] });
+			k++;
+			continue;
+		}
+		const removed: DiffLine[] = [];
+		const added: DiffLine[] = [];
+		while (k < lines.length && lines[k].kind !== 'same') {
+			(lines[k].kind === 'removed' ? removed : added).push(lines[k]);
+			k++;
+		}
+		for (let r = 0; r < Math.max(removed.length, added.length); r++) {
+			rows.push({ left: removed[r] ?? null, right: added[r] ?? null });
+		}
+	}
+	return rows;
+}
+
+/** Counts for the diff header ("+3 −1"). */
+export function diffStats(lines: DiffLine[]): { added: number; removed: number } {
+	let added = 0;
+	let removed = 0;
+	for (const l of lines) {
+		if (l.kind === 'added') added++;
+		else if (l.kind === 'removed') removed++;
+	}
+	return { added, removed };
+}
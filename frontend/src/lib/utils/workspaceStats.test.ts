@@ -0,0 +1,57 @@
+import { describe, expect, it } from 'vitest';
+import type { WeekStats, WorkspaceTotals } from '$lib/api/commands';
+import { ownedPercent, weekBars, weekChannels, weekLine, weekMessages } from './workspaceStats';
+
+function week(week_start: string, created: number, messages: Record<string, number> = {}): WeekStats {
+	return {
+		week_start,
+		documents_created: created,
+		owned_created: created,
+		documents_total: created,
+		commits: 0,
+		messages
+	};
+}
+
+function totals(documents: number, owned: number): WorkspaceTotals {
+	return {
+		documents,
+		owned_documents: owned,
+		external_documents: documents - owned,
+		threads: 1,
+		commits: 0,
+		messages: 0
+	};
+}
+
+describe('workspace stats', () => {
+	it('computes the owned share', () => {
+		expect(ownedPercent(totals(0, 0))).toBe(0);
+		expect(ownedPercent(totals(3, 2))).toBe(67);
+	});
+
+	it('scales bars to the busiest week', () => {
+		const bars = weekBars([week('2026-03-02', 2), week('2026-03-09', 4)], (w) => w.documents_created, 100, 20);
+		expect(bars.map((b) => b.height)).toEqual([10, 20]);
+		expect(bars.map((b) => b.y)).toEqual([10, 0]);
+		expect(bars[1].x).toBeGreaterThan(bars[0].x);
+		expect(weekBars([week('2026-03-02', 0)], (w) => w.documents_created, 10, 10)[0].height).toBe(0);
+	});
+
+	it('draws a line across the weeks', () => {
+		expect(weekLine([], (w) => w.documents_total, 10, 10)).toBe('');
+		expect(weekLine([week('2026-03-02', 3)], (w) => w.documents_total, 10, 10)).toBe('0,0 10,0');
+		expect(
+			weekLine([week('2026-03-02', 1), week('2026-03-09', 2)], (w) => w.documents_total, 10, 10)
+		).toBe('0,5 10,0');
+	});
+
+	it('orders channels by volume and sums a week', () => {
+		const weeks = [
+			week('2026-03-02', 0, { signal: 1, email: 2 }),
+			week('2026-03-09', 0, { signal: 3 })
+		];
+		expect(weekChannels(weeks)).toEqual(['signal', 'email']);
+		expect(weekMessages(weeks[0])).toBe(3);
+	});
+});
@@ -0,0 +1,84 @@
+/** Shaping workspace statistics (`get_workspace_stats`) for the dashboard:
+ *  ratios, chart geometry and labels. */
+
+import type { WeekStats, WorkspaceTotals } from '$lib/api/commands';
+
+/** Share of documents the user wrote, 0–100; 0 for an empty workspace. */
+export function ownedPercent(totals: WorkspaceTotals): number {
+	if (totals.documents === 0) return 0;
+	return Math.round((totals.owned_documents / totals.documents) * 100);
+}
+
+export interface Bar {
+	x: number;
+	y: number;
+	width: number;
+	height: number;
+}
+
+/** One bar per week for `pick`, filling `width` x `height` with the
+ *  busiest week at full height. */
+export function weekBars(
+	weeks: WeekStats[],
+	pick: (w: WeekStats) => number,
+	width: number,
+	height: number
+): Bar[] {
+	if (weeks.length === 0) return [];
+	const max = Math.max(...weeks.map(pick));
+	const slot = width / weeks.length;
+	const barWidth = Math.max(1, round(slot * 0.7));
+	return weeks.map((w, i) => {
+		const h = max === 0 ? 0 : round((pick(w) / max) * height);
+		return { x: round(i * slot + (slot - barWidth) / 2), y: round(height - h), width: barWidth, height: h };
+	});
+}
+
+/** SVG polyline points for `pick` across the weeks, the highest value at
+ *  the top. A single week is drawn as a flat line. */
+export function weekLine(
+	weeks: WeekStats[],
+	pick: (w: WeekStats) => number,
+	width: number,
+	height: number
+): string {
+	if (weeks.length === 0) return '';
+	const values = weeks.map(pick);
+	const max = Math.max(...values);
+	const y = (v: number) => (max === 0 ? height : height - (v / max) * height);
+	if (values.length === 1) return `0,${round(y(values[0]))} ${width},${round(y(values[0]))}`;
+	const step = width / (values.length - 1);
+	return values.map((v, i) => `${round(i * step)},${round(y(v))}`).join(' ');
+}
+
+/** Every channel that had messages in the window, busiest first. */
+export function weekChannels(weeks: WeekStats[]): string[] {
+	const totals = new Map<string, number>();
+	for (const w of weeks) {
+		for (const [channel, n] of Object.entries(w.messages)) {
+			totals.set(channel, (totals.get(channel) ?? 0) + n);
+		}
+	}
+	return [...totals.entries()]
+		.sort(([a, x], [b, y]) => y - x || a.localeCompare(b))
+		.map(([channel]) => channel);
+}
+
+/** Messages across all channels in one week. */
+export function weekMessages(week: WeekStats): number {
+	return Object.values(week.messages).reduce((n, m) => n + m, 0);
+}
+
+/** `2026-03-02` → `Mar 2`. */
+export function weekLabel(weekStart: string): string {
+	const [y, m, d] = weekStart.split('-').map(Number);
+	return new Date(Date.UTC(y, m - 1, d)).toLocaleDateString(undefined, {
+		month: 'short',
+		day: 'numeric',
+		timeZone: 'UTC'
+	});
+}
+
+function round(n: number): number {
+	return Math.round(n * 10) / 10;
+}